@@ -0,0 +1,309 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use grpc::connection_config::ConnectionConfig;
+use serde::Deserialize;
+
+use crate::runtime_connectors::plugin::RuntimePluginConfig;
+use crate::workload_log_sink::WorkloadLogsConfig;
+
+pub const DEFAULT_AGENT_CONFIG_PATH: &str = "/etc/ankaios/ank-agent.conf";
+
+/// Content of the agent config file 'ank-agent.conf' in TOML format
+// [impl->swdd~agent-loads-agent-config-file~1]
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct AgentConfig {
+    #[serde(default)]
+    pub grpc: ConnectionConfig,
+    #[serde(default)]
+    pub workload_logs: WorkloadLogsConfig,
+    // [impl->swdd~agent-limits-parallel-workload-operations~1]
+    #[serde(default)]
+    pub max_parallel_workload_operations: Option<usize>,
+    // [impl->swdd~agent-reports-labels~1]
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    // [impl->swdd~agent-supports-runtime-plugins~2]
+    #[serde(default)]
+    pub runtime_plugins: HashMap<String, RuntimePluginConfig>,
+    // [impl->swdd~agent-reads-secrets-from-providers~3]
+    #[serde(default)]
+    pub secrets_directory: Option<PathBuf>,
+    // [impl->swdd~agent-reads-secrets-from-providers~3]
+    #[serde(default)]
+    pub allowed_env_secret_prefixes: Vec<String>,
+}
+
+impl AgentConfig {
+    /// Loads the agent config from the given path
+    ///
+    /// If no path is given, the default config file is used if it exists.
+    pub fn load(path: Option<&str>) -> Result<Self, String> {
+        match path {
+            Some(path) => Self::from_file(path),
+            None if Path::new(DEFAULT_AGENT_CONFIG_PATH).exists() => {
+                Self::from_file(DEFAULT_AGENT_CONFIG_PATH)
+            }
+            None => Ok(AgentConfig::default()),
+        }
+    }
+
+    fn from_file(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("Could not read the agent config '{}': '{}'", path, err))?;
+        Self::from_toml(&content)
+            .map_err(|err| format!("Could not parse the agent config '{}': '{}'", path, err))
+    }
+
+    fn from_toml(content: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(content).map_err(|err| err.to_string())?;
+        config.verify()?;
+        Ok(config)
+    }
+
+    // [impl->swdd~agent-supports-runtime-plugins~2]
+    fn verify(&self) -> Result<(), String> {
+        // a timeout of 0 would let every call of the plugin fail
+        match self
+            .runtime_plugins
+            .iter()
+            .find(|(_, plugin)| plugin.timeout_secs == 0)
+        {
+            Some((name, _)) => Err(format!(
+                "The timeout of the runtime plugin '{}' must be greater than 0",
+                name
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::AgentConfig;
+    use crate::runtime_connectors::plugin::RuntimePluginConfig;
+    use crate::workload_log_sink::{WorkloadLogSinkKind, WorkloadLogsConfig};
+    use grpc::connection_config::{Compression, ConnectionConfig};
+
+    // [utest->swdd~agent-loads-agent-config-file~1]
+    // [utest->swdd~grpc-supports-message-size-and-compression~1]
+    #[test]
+    fn utest_agent_config_with_grpc_connection_tuning() {
+        let content = r#"
+            [grpc]
+            keepalive_interval_secs = 10
+            keepalive_timeout_secs = 5
+            reconnect_initial_delay_ms = 500
+            reconnect_max_delay_ms = 30000
+            reconnect_multiplier = 3
+            max_message_size = 33554432
+            compression = "gzip"
+        "#;
+
+        assert_eq!(
+            AgentConfig::from_toml(content),
+            Ok(AgentConfig {
+                grpc: ConnectionConfig {
+                    keepalive_interval_secs: Some(10),
+                    keepalive_timeout_secs: 5,
+                    reconnect_initial_delay_ms: 500,
+                    reconnect_max_delay_ms: 30000,
+                    reconnect_multiplier: 3,
+                    max_message_size: 33554432,
+                    compression: Compression::Gzip,
+                },
+                workload_logs: WorkloadLogsConfig::default(),
+                max_parallel_workload_operations: None,
+                labels: HashMap::new(),
+                runtime_plugins: HashMap::new(),
+                secrets_directory: None,
+                allowed_env_secret_prefixes: vec![],
+            })
+        );
+    }
+
+    // [utest->swdd~agent-loads-agent-config-file~1]
+    // [utest->swdd~agent-selects-workload-log-sink~1]
+    #[test]
+    fn utest_agent_config_with_workload_logs() {
+        let content = r#"
+            [workload_logs]
+            sink = "syslog"
+            socket = "/run/rsyslog.sock"
+
+            [workload_logs.workloads]
+            databroker = "none"
+        "#;
+
+        assert_eq!(
+            AgentConfig::from_toml(content),
+            Ok(AgentConfig {
+                grpc: ConnectionConfig::default(),
+                workload_logs: WorkloadLogsConfig {
+                    sink: WorkloadLogSinkKind::Syslog,
+                    socket: Some("/run/rsyslog.sock".into()),
+                    workloads: HashMap::from([(
+                        "databroker".to_string(),
+                        WorkloadLogSinkKind::None,
+                    )]),
+                },
+                max_parallel_workload_operations: None,
+                labels: HashMap::new(),
+                runtime_plugins: HashMap::new(),
+                secrets_directory: None,
+                allowed_env_secret_prefixes: vec![],
+            })
+        );
+    }
+
+    // [utest->swdd~agent-loads-agent-config-file~1]
+    // [utest->swdd~agent-limits-parallel-workload-operations~1]
+    #[test]
+    fn utest_agent_config_with_max_parallel_workload_operations() {
+        let content = r#"
+            max_parallel_workload_operations = 4
+        "#;
+
+        assert_eq!(
+            AgentConfig::from_toml(content),
+            Ok(AgentConfig {
+                max_parallel_workload_operations: Some(4),
+                ..Default::default()
+            })
+        );
+    }
+
+    // [utest->swdd~agent-loads-agent-config-file~1]
+    // [utest->swdd~agent-reports-labels~1]
+    #[test]
+    fn utest_agent_config_with_labels() {
+        let content = r#"
+            [labels]
+            zone = "cockpit"
+            gpu = "true"
+        "#;
+
+        assert_eq!(
+            AgentConfig::from_toml(content),
+            Ok(AgentConfig {
+                labels: HashMap::from([
+                    ("zone".to_string(), "cockpit".to_string()),
+                    ("gpu".to_string(), "true".to_string()),
+                ]),
+                ..Default::default()
+            })
+        );
+    }
+
+    // [utest->swdd~agent-loads-agent-config-file~1]
+    // [utest->swdd~agent-supports-runtime-plugins~2]
+    #[test]
+    fn utest_agent_config_with_runtime_plugins() {
+        let content = r#"
+            [runtime_plugins.wasm]
+            path = "/usr/lib/ankaios/plugins/wasm-runtime"
+            args = ["--verbose"]
+            timeout_secs = 10
+        "#;
+
+        assert_eq!(
+            AgentConfig::from_toml(content),
+            Ok(AgentConfig {
+                runtime_plugins: HashMap::from([(
+                    "wasm".to_string(),
+                    RuntimePluginConfig {
+                        path: "/usr/lib/ankaios/plugins/wasm-runtime".to_string(),
+                        args: vec!["--verbose".to_string()],
+                        timeout_secs: 10,
+                    }
+                )]),
+                ..Default::default()
+            })
+        );
+    }
+
+    // [utest->swdd~agent-loads-agent-config-file~1]
+    // [utest->swdd~agent-supports-runtime-plugins~2]
+    #[test]
+    fn utest_agent_config_rejects_runtime_plugin_without_timeout() {
+        let content = r#"
+            [runtime_plugins.wasm]
+            path = "/usr/lib/ankaios/plugins/wasm-runtime"
+            timeout_secs = 0
+        "#;
+
+        assert_eq!(
+            AgentConfig::from_toml(content),
+            Err("The timeout of the runtime plugin 'wasm' must be greater than 0".to_string())
+        );
+    }
+
+    // [utest->swdd~agent-loads-agent-config-file~1]
+    // [utest->swdd~agent-reads-secrets-from-providers~3]
+    #[test]
+    fn utest_agent_config_with_secrets_directory() {
+        let content = r#"
+            secrets_directory = "/etc/ankaios/secrets"
+            allowed_env_secret_prefixes = ["APP_SECRET_"]
+        "#;
+
+        assert_eq!(
+            AgentConfig::from_toml(content),
+            Ok(AgentConfig {
+                secrets_directory: Some("/etc/ankaios/secrets".into()),
+                allowed_env_secret_prefixes: vec!["APP_SECRET_".to_string()],
+                ..Default::default()
+            })
+        );
+    }
+
+    // [utest->swdd~agent-loads-agent-config-file~1]
+    #[test]
+    fn utest_agent_config_empty() {
+        assert_eq!(AgentConfig::from_toml(""), Ok(AgentConfig::default()));
+    }
+
+    // [utest->swdd~agent-loads-agent-config-file~1]
+    #[test]
+    fn utest_agent_config_invalid() {
+        let content = r#"
+            [grpc]
+            keepalive_interval_secs = "often"
+        "#;
+
+        assert!(AgentConfig::from_toml(content).is_err());
+    }
+
+    // [utest->swdd~agent-loads-agent-config-file~1]
+    #[test]
+    fn utest_agent_config_missing_file() {
+        assert!(AgentConfig::load(Some("/non/existing/ank-agent.conf")).is_err());
+    }
+}
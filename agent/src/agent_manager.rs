@@ -11,12 +11,23 @@
 // under the License.
 //
 // SPDX-License-Identifier: Apache-2.0
-use sysinfo::{CpuRefreshKind, MemoryRefreshKind, RefreshKind, System};
+use std::{
+    collections::HashSet,
+    fs,
+    future::Future,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use sysinfo::{CpuRefreshKind, Disks, MemoryRefreshKind, Networks, RefreshKind, System};
 
 use common::{
     commands::AgentLoadStatus,
     from_server_interface::{FromServer, FromServerReceiver},
-    objects::{CpuUsage, FreeMemory, WorkloadState},
+    objects::{
+        CpuUsage, DiskUsage, FreeMemory, NetworkInterfaceUsage, NetworkUsage, Temperature,
+        ThermalZone, WorkloadState,
+    },
     std_extensions::{GracefulExitResult, IllegalStateResult},
     to_server_interface::{ToServerInterface, ToServerSender},
 };
@@ -24,25 +35,39 @@ use common::{
 #[cfg_attr(test, mockall_double::double)]
 use crate::workload_state::workload_state_store::WorkloadStateStore;
 
+use crate::eviction_controller::{EvictionController, EvictionDecision};
+use crate::runtime_connectors::podman::PodmanImagePuller;
 #[cfg_attr(test, mockall_double::double)]
 use crate::runtime_manager::RuntimeManager;
+use crate::shutdown_policy::ShutdownPolicy;
+use crate::workload_cache::WorkloadCache;
 use crate::workload_state::WorkloadStateReceiver;
 
 const RESOURCE_MEASUREMENT_INTERVAL_TICK: std::time::Duration = tokio::time::Duration::from_secs(2);
+const WORKLOAD_STATES_BATCH_WINDOW: std::time::Duration = tokio::time::Duration::from_millis(20);
+const THERMAL_ZONES_PATH: &str = "/sys/class/thermal";
+const THERMAL_ZONE_PREFIX: &str = "thermal_zone";
 
 struct ResourceMonitor {
     refresh_kind: RefreshKind,
     sys: System,
+    disks: Disks,
+    networks: Networks,
+    run_folder: PathBuf,
 }
 
 impl ResourceMonitor {
-    fn new() -> ResourceMonitor {
+    fn new(run_folder: PathBuf) -> ResourceMonitor {
         let refresh_kind = RefreshKind::new()
             .with_cpu(CpuRefreshKind::new().with_cpu_usage())
             .with_memory(MemoryRefreshKind::new().with_ram());
         ResourceMonitor {
             refresh_kind,
             sys: System::new_with_specifics(refresh_kind),
+            disks: Disks::new(),
+            networks: Networks::new(),
+            // the mount point of the run folder can only be found with an absolute path
+            run_folder: fs::canonicalize(&run_folder).unwrap_or(run_folder),
         }
     }
 
@@ -54,6 +79,87 @@ impl ResourceMonitor {
 
         (CpuUsage::new(cpu_usage), FreeMemory { free_memory })
     }
+
+    // [impl->swdd~agent-sends-extended-node-load-to-server~1]
+    fn sample_disk_usage(&mut self) -> DiskUsage {
+        self.disks.refresh_list();
+
+        // the disk with the longest mount point containing the run folder holds the run folder
+        self.disks
+            .list()
+            .iter()
+            .filter(|disk| self.run_folder.starts_with(disk.mount_point()))
+            .max_by_key(|disk| disk.mount_point().as_os_str().len())
+            .map(|disk| DiskUsage {
+                total_space: disk.total_space(),
+                available_space: disk.available_space(),
+            })
+            .unwrap_or_default()
+    }
+
+    // [impl->swdd~agent-sends-extended-node-load-to-server~1]
+    fn sample_network_usage(&mut self) -> NetworkUsage {
+        self.networks.refresh_list();
+
+        let mut interfaces: Vec<NetworkInterfaceUsage> = self
+            .networks
+            .list()
+            .iter()
+            .map(|(name, data)| NetworkInterfaceUsage {
+                name: name.clone(),
+                received_bytes: data.total_received(),
+                transmitted_bytes: data.total_transmitted(),
+            })
+            .collect();
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+
+        NetworkUsage { interfaces }
+    }
+}
+
+// [impl->swdd~agent-sends-extended-node-load-to-server~1]
+fn read_thermal_zones(thermal_zones_path: &Path) -> Temperature {
+    let Ok(entries) = fs::read_dir(thermal_zones_path) else {
+        // not all nodes provide thermal zones
+        return Temperature::default();
+    };
+
+    let mut thermal_zones: Vec<(String, ThermalZone)> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with(THERMAL_ZONE_PREFIX)
+        })
+        .filter_map(|entry| {
+            let zone_path = entry.path();
+            let temperature = fs::read_to_string(zone_path.join("temp"))
+                .ok()?
+                .trim()
+                .parse::<i32>()
+                .ok()?;
+            let zone_type = fs::read_to_string(zone_path.join("type"))
+                .map(|zone_type| zone_type.trim().to_owned())
+                .unwrap_or_default();
+            Some((
+                entry.file_name().to_string_lossy().into_owned(),
+                ThermalZone {
+                    zone_type,
+                    temperature,
+                },
+            ))
+        })
+        .collect();
+    // sort by the zone folder to keep the order stable between the measurements
+    thermal_zones.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    Temperature {
+        thermal_zones: thermal_zones
+            .into_iter()
+            .map(|(_, thermal_zone)| thermal_zone)
+            .collect(),
+    }
 }
 
 // [impl->swdd~agent-shall-use-interfaces-to-server~1]
@@ -66,15 +172,36 @@ pub struct AgentManager {
     workload_state_receiver: WorkloadStateReceiver,
     workload_state_store: WorkloadStateStore,
     res_monitor: ResourceMonitor,
+    // own workload states waiting to be sent to the server in a single message
+    workload_states_batch: Vec<WorkloadState>,
+    workload_states_batch_deadline: tokio::time::Instant,
+    shutdown_policy: ShutdownPolicy,
+    workload_cache: Option<WorkloadCache>,
+    // point in time the cached workloads are started at if the server did not send its hello until then
+    offline_start_deadline: Option<tokio::time::Instant>,
+    eviction_controller: Option<EvictionController>,
+    disconnect_threshold: Duration,
+    // point in time the connection to the server was lost at
+    disconnected_since: Option<tokio::time::Instant>,
+    // point in time the next agent disconnect policies are due
+    disconnect_policy_deadline: Option<tokio::time::Instant>,
+    image_puller: PodmanImagePuller,
 }
 
 impl AgentManager {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         agent_name: String,
         from_server_receiver: FromServerReceiver,
         runtime_manager: RuntimeManager,
         to_server: ToServerSender,
         workload_state_receiver: WorkloadStateReceiver,
+        shutdown_policy: ShutdownPolicy,
+        workload_cache: Option<WorkloadCache>,
+        run_folder: PathBuf,
+        eviction_controller: Option<EvictionController>,
+        disconnect_threshold: Duration,
+        image_puller: PodmanImagePuller,
     ) -> AgentManager {
         AgentManager {
             agent_name,
@@ -83,14 +210,29 @@ impl AgentManager {
             to_server,
             workload_state_receiver,
             workload_state_store: WorkloadStateStore::new(),
-            res_monitor: ResourceMonitor::new(),
+            res_monitor: ResourceMonitor::new(run_folder),
+            workload_states_batch: Vec::new(),
+            workload_states_batch_deadline: tokio::time::Instant::now(),
+            shutdown_policy,
+            workload_cache,
+            offline_start_deadline: None,
+            eviction_controller,
+            disconnect_threshold,
+            disconnected_since: None,
+            disconnect_policy_deadline: None,
+            image_puller,
         }
     }
 
-    pub async fn start(&mut self) {
+    pub async fn start(&mut self, shutdown_signal: impl Future<Output = ()>) {
         log::info!("Awaiting commands from the server ...");
 
         let mut interval = tokio::time::interval(RESOURCE_MEASUREMENT_INTERVAL_TICK);
+        tokio::pin!(shutdown_signal);
+
+        self.offline_start_deadline = self.workload_cache.as_ref().map(|workload_cache| {
+            tokio::time::Instant::now() + workload_cache.offline_start_delay()
+        });
 
         loop {
             tokio::select! {
@@ -101,6 +243,7 @@ impl AgentManager {
                         .unwrap_or_exit("Abort");
 
                     if self.execute_from_server_command(from_server).await.is_none() {
+                        self.forward_workload_states_batch().await;
                         break;
                     }
                 },
@@ -111,10 +254,88 @@ impl AgentManager {
                         .unwrap_or_exit("Abort");
                     self.store_and_forward_own_workload_states(workload_state).await;
                 }
+                // [impl->swdd~agent-batches-workload-states-of-its-workloads~1]
+                _ = tokio::time::sleep_until(self.workload_states_batch_deadline),
+                    if !self.workload_states_batch.is_empty() => {
+                    self.forward_workload_states_batch().await;
+                }
+                // [impl->swdd~agent-starts-cached-workloads-without-server~1]
+                _ = tokio::time::sleep_until(self.offline_start_deadline.unwrap_or_else(tokio::time::Instant::now)),
+                    if self.offline_start_deadline.is_some() => {
+                    self.start_cached_workloads().await;
+                }
+                // [impl->swdd~agent-enforces-agent-disconnect-policies~1]
+                _ = tokio::time::sleep_until(self.disconnect_policy_deadline.unwrap_or_else(tokio::time::Instant::now)),
+                    if self.disconnect_policy_deadline.is_some() => {
+                    self.enforce_agent_disconnect_policies().await;
+                }
                 // [impl->swdd~agent-sends-node-resource-availability-to-server~1]
                 _ = interval.tick() => {
                     self.measure_and_forward_resource_availability().await;
                 }
+                // [impl->swdd~agent-shuts-down-gracefully~1]
+                _ = &mut shutdown_signal => {
+                    self.shutdown().await;
+                    break;
+                }
+            }
+        }
+    }
+
+    // [impl->swdd~agent-shuts-down-gracefully~1]
+    async fn shutdown(&mut self) {
+        log::info!(
+            "Shutting down agent '{}' with shutdown policy '{}'.",
+            self.agent_name,
+            self.shutdown_policy
+        );
+
+        match self.shutdown_policy {
+            ShutdownPolicy::KeepRunning => {}
+            ShutdownPolicy::StopWorkloads => self.stop_workloads().await,
+            ShutdownPolicy::StopWithTimeout(timeout) => {
+                if tokio::time::timeout(timeout, self.stop_workloads())
+                    .await
+                    .is_err()
+                {
+                    log::warn!(
+                        "Workloads not stopped within '{}' seconds. Shutting down anyway.",
+                        timeout.as_secs()
+                    );
+                }
+            }
+        }
+
+        // [impl->swdd~agent-reports-final-workload-states-on-shutdown~1]
+        self.forward_workload_states_batch().await;
+        self.to_server.stop().await.unwrap_or_illegal_state();
+    }
+
+    // [impl->swdd~agent-shuts-down-gracefully~1]
+    async fn stop_workloads(&mut self) {
+        let mut stopping_workloads: HashSet<String> = self
+            .runtime_manager
+            .delete_all_workloads()
+            .await
+            .into_iter()
+            .collect();
+
+        while !stopping_workloads.is_empty() {
+            tokio::select! {
+                workload_state = self.workload_state_receiver.recv() => {
+                    let Some(workload_state) = workload_state else {
+                        log::warn!("Channel to listen to own workload states closed.");
+                        break;
+                    };
+                    if workload_state.execution_state.is_removed() {
+                        stopping_workloads.remove(workload_state.instance_name.workload_name());
+                    }
+                    self.store_and_batch_own_workload_state(workload_state);
+                }
+                _ = tokio::time::sleep_until(self.workload_states_batch_deadline),
+                    if !self.workload_states_batch.is_empty() => {
+                    self.forward_workload_states_batch().await;
+                }
             }
         }
     }
@@ -131,6 +352,18 @@ impl AgentManager {
                     method_obj.added_workloads
                 );
 
+                // [impl->swdd~agent-restarts-workloads-stopped-on-disconnect~1]
+                if self.disconnected_since.take().is_some() {
+                    log::info!("The connection to the server is established again.");
+                }
+                self.disconnect_policy_deadline = None;
+
+                // [impl->swdd~agent-caches-workloads-received-from-server~1]
+                self.offline_start_deadline = None;
+                if let Some(workload_cache) = &mut self.workload_cache {
+                    workload_cache.replace(&method_obj.added_workloads);
+                }
+
                 self.runtime_manager
                     .handle_server_hello(method_obj.added_workloads, &self.workload_state_store)
                     .await;
@@ -142,6 +375,12 @@ impl AgentManager {
                     method_obj.added_workloads,
                     method_obj.deleted_workloads);
 
+                // [impl->swdd~agent-caches-workloads-received-from-server~1]
+                if let Some(workload_cache) = &mut self.workload_cache {
+                    workload_cache
+                        .update(&method_obj.added_workloads, &method_obj.deleted_workloads);
+                }
+
                 // [impl->swdd~agent-handles-update-workload-requests~1]
                 self.runtime_manager
                     .handle_update_workload(
@@ -189,6 +428,97 @@ impl AgentManager {
 
                 Some(())
             }
+            FromServer::ExecRequest(method_obj) => {
+                log::debug!(
+                    "Agent '{}' received ExecRequest '{}'",
+                    self.agent_name,
+                    method_obj.request_id
+                );
+
+                // [impl->swdd~agent-handles-exec-requests~1]
+                self.runtime_manager
+                    .handle_exec_request(method_obj.request_id, method_obj.exec_request)
+                    .await;
+
+                Some(())
+            }
+            FromServer::PortForwardRequest(method_obj) => {
+                log::debug!(
+                    "Agent '{}' received PortForwardRequest '{}'",
+                    self.agent_name,
+                    method_obj.request_id
+                );
+
+                // [impl->swdd~agent-handles-port-forward-requests~2]
+                self.runtime_manager
+                    .handle_port_forward_request(
+                        method_obj.request_id,
+                        method_obj.port_forward_request,
+                    )
+                    .await;
+
+                Some(())
+            }
+            FromServer::RestartWorkloadRequest(method_obj) => {
+                log::debug!(
+                    "Agent '{}' received RestartWorkloadRequest '{}'",
+                    self.agent_name,
+                    method_obj.request_id
+                );
+
+                // [impl->swdd~agent-handles-restart-workload-requests~1]
+                self.runtime_manager
+                    .handle_restart_workload_request(
+                        method_obj.request_id,
+                        method_obj.workload_names,
+                    )
+                    .await;
+
+                Some(())
+            }
+            FromServer::PauseWorkloadRequest(method_obj) => {
+                log::debug!(
+                    "Agent '{}' received PauseWorkloadRequest '{}'",
+                    self.agent_name,
+                    method_obj.request_id
+                );
+
+                // [impl->swdd~agent-handles-pause-workload-requests~1]
+                self.runtime_manager
+                    .handle_pause_workload_request(
+                        method_obj.request_id,
+                        method_obj.workload_names,
+                        method_obj.resume,
+                    )
+                    .await;
+
+                Some(())
+            }
+            FromServer::PullImages(method_obj) => {
+                log::debug!(
+                    "Agent '{}' received {} images to pull",
+                    self.agent_name,
+                    method_obj.images.len()
+                );
+
+                // [impl->swdd~agent-pins-images-received-from-server~1]
+                self.image_puller.pin_images(method_obj.images);
+
+                Some(())
+            }
+            FromServer::ServerConnectionLost(_method_obj) => {
+                // [impl->swdd~agent-enforces-agent-disconnect-policies~1]
+                if self.disconnected_since.is_none() {
+                    log::warn!(
+                        "Agent '{}' lost the connection to the server.",
+                        self.agent_name
+                    );
+                    let now = tokio::time::Instant::now();
+                    self.disconnected_since = Some(now);
+                    self.disconnect_policy_deadline = Some(now + self.disconnect_threshold);
+                }
+                Some(())
+            }
             FromServer::Stop(_method_obj) => {
                 log::debug!("Agent '{}' received Stop from server", self.agent_name);
                 None
@@ -196,10 +526,57 @@ impl AgentManager {
         }
     }
 
-    async fn store_and_forward_own_workload_states(
-        &mut self,
-        mut new_workload_state: WorkloadState,
-    ) {
+    // [impl->swdd~agent-enforces-agent-disconnect-policies~1]
+    async fn enforce_agent_disconnect_policies(&mut self) {
+        self.disconnect_policy_deadline = None;
+        let Some(disconnected_since) = self.disconnected_since else {
+            return;
+        };
+
+        let time_over_threshold = disconnected_since
+            .elapsed()
+            .saturating_sub(self.disconnect_threshold);
+        self.disconnect_policy_deadline = self
+            .runtime_manager
+            .stop_workloads_on_disconnect(time_over_threshold, &self.workload_state_store)
+            .await
+            .map(|next_stop_delay| tokio::time::Instant::now() + next_stop_delay);
+    }
+
+    // [impl->swdd~agent-starts-cached-workloads-without-server~1]
+    async fn start_cached_workloads(&mut self) {
+        self.offline_start_deadline = None;
+        let Some(workload_cache) = &mut self.workload_cache else {
+            return;
+        };
+
+        let cached_workloads = workload_cache.load();
+        if cached_workloads.is_empty() {
+            return;
+        }
+
+        log::info!(
+            "No connection to the server yet. Starting '{}' cached workloads.",
+            cached_workloads.len()
+        );
+
+        // the server hello received later on reconciles the workloads with the current desired state
+        self.runtime_manager
+            .handle_server_hello(cached_workloads, &self.workload_state_store)
+            .await;
+    }
+
+    async fn store_and_forward_own_workload_states(&mut self, new_workload_state: WorkloadState) {
+        self.store_and_batch_own_workload_state(new_workload_state);
+
+        // notify the runtime manager s.t. dependencies and restarts can be handled
+        // [impl->swdd~agent-handles-update-workload-state-requests~1]
+        self.runtime_manager
+            .update_workloads_on_fulfilled_dependencies(&self.workload_state_store)
+            .await;
+    }
+
+    fn store_and_batch_own_workload_state(&mut self, mut new_workload_state: WorkloadState) {
         // execute hysteresis on the local workload states as we could be stopping
         // [impl->swdd~agent-manager-hysteresis_on-workload-states-of-its-workloads~1]
         if let Some(old_execution_state) = self
@@ -219,15 +596,38 @@ impl AgentManager {
         self.workload_state_store
             .update_workload_state(new_workload_state.clone());
 
-        // notify the runtime manager s.t. dependencies and restarts can be handled
-        // [impl->swdd~agent-handles-update-workload-state-requests~1]
-        self.runtime_manager
-            .update_workloads_on_fulfilled_dependencies(&self.workload_state_store)
-            .await;
+        // [impl->swdd~agent-batches-workload-states-of-its-workloads~1]
+        if self.workload_states_batch.is_empty() {
+            self.workload_states_batch_deadline =
+                tokio::time::Instant::now() + WORKLOAD_STATES_BATCH_WINDOW;
+        }
+
+        // only the latest state of a workload is relevant for the server
+        if let Some(batched_workload_state) = self
+            .workload_states_batch
+            .iter_mut()
+            .find(|state| state.instance_name == new_workload_state.instance_name)
+        {
+            *batched_workload_state = new_workload_state;
+        } else {
+            self.workload_states_batch.push(new_workload_state);
+        }
+    }
+
+    async fn forward_workload_states_batch(&mut self) {
+        if self.workload_states_batch.is_empty() {
+            return;
+        }
+
+        let workload_states = std::mem::take(&mut self.workload_states_batch);
+        log::debug!(
+            "Forwarding '{}' local workload states to the server.",
+            workload_states.len()
+        );
 
         // [impl->swdd~agent-sends-workload-states-of-its-workloads-to-server~2]
         self.to_server
-            .update_workload_state(vec![new_workload_state])
+            .update_workload_state(workload_states)
             .await
             .unwrap_or_illegal_state();
     }
@@ -235,12 +635,23 @@ impl AgentManager {
     // [impl->swdd~agent-sends-node-resource-availability-to-server~1]
     async fn measure_and_forward_resource_availability(&mut self) {
         let (cpu_usage, free_memory) = self.res_monitor.sample_resource_usage();
+        let disk_usage = self.res_monitor.sample_disk_usage();
+        let network_usage = self.res_monitor.sample_network_usage();
+        let temperature = read_thermal_zones(Path::new(THERMAL_ZONES_PATH));
+        // [impl->swdd~agent-sends-workload-resources-to-server~1]
+        let workload_resources = self.runtime_manager.collect_workload_resources().await;
+
+        self.handle_memory_pressure(free_memory.free_memory).await;
 
         log::trace!(
-            "Agent '{}' reports resource usage: CPU Usage: {}%, Free Memory: {}B",
+            "Agent '{}' reports resource usage: CPU Usage: {}%, Free Memory: {}B, Available Disk: {}B, Network Interfaces: {}, Thermal Zones: {}, Workloads: {}",
             self.agent_name,
             cpu_usage.cpu_usage,
             free_memory.free_memory,
+            disk_usage.available_space,
+            network_usage.interfaces.len(),
+            temperature.thermal_zones.len(),
+            workload_resources.len(),
         );
 
         self.to_server
@@ -248,10 +659,55 @@ impl AgentManager {
                 agent_name: self.agent_name.clone(),
                 cpu_usage,
                 free_memory,
+                workload_resources,
+                disk_usage,
+                network_usage,
+                temperature,
+                // [impl->swdd~agent-pins-images-received-from-server~1]
+                image_states: self.image_puller.image_states(),
             })
             .await
             .unwrap_or_illegal_state();
     }
+
+    // [impl->swdd~agent-evicts-workloads-on-memory-pressure~1]
+    async fn handle_memory_pressure(&mut self, free_memory: u64) {
+        let Some(eviction_decision) = self
+            .eviction_controller
+            .as_ref()
+            .and_then(|eviction_controller| eviction_controller.decide(free_memory))
+        else {
+            return;
+        };
+
+        match eviction_decision {
+            EvictionDecision::EvictLowestPriority => {
+                if let Some(workload_name) = self
+                    .runtime_manager
+                    .evict_lowest_priority_workload(&self.workload_state_store)
+                    .await
+                {
+                    log::warn!(
+                        "Evicted workload '{}' because of low free memory ({}B).",
+                        workload_name,
+                        free_memory
+                    );
+                }
+            }
+            EvictionDecision::RestoreHighestPriority => {
+                if let Some(workload_name) = self
+                    .runtime_manager
+                    .restore_highest_priority_workload(&self.workload_state_store)
+                    .await
+                {
+                    log::info!(
+                        "Restoring evicted workload '{}' as the memory pressure subsided.",
+                        workload_name
+                    );
+                }
+            }
+        }
+    }
 }
 
 //////////////////////////////////////////////////////////////////////////////
@@ -266,21 +722,38 @@ impl AgentManager {
 mod tests {
     use core::panic;
 
-    use super::RuntimeManager;
+    use super::{read_thermal_zones, RuntimeManager};
     use crate::agent_manager::AgentManager;
+    use crate::eviction_controller::EvictionController;
+    use crate::runtime_connectors::podman::PodmanImagePuller;
+    use crate::shutdown_policy::ShutdownPolicy;
+    use crate::workload_cache::WorkloadCache;
     use crate::workload_state::{
         workload_state_store::{mock_parameter_storage_new_returns, MockWorkloadStateStore},
         WorkloadStateSenderInterface,
     };
     use api::ank_base;
     use common::{
-        commands::UpdateWorkloadState,
-        from_server_interface::FromServerInterface,
-        objects::{generate_test_workload_spec_with_param, ExecutionState},
+        commands::{
+            ExecRequest, ExecStart, PortForwardRequest, PortForwardStart, ServerConnectionLost,
+            ServerHello, UpdateWorkloadState,
+        },
+        from_server_interface::{FromServer, FromServerInterface},
+        objects::{
+            generate_test_workload_resources, generate_test_workload_spec_with_param,
+            ExecutionState, ImageState, Temperature, ThermalZone,
+        },
         to_server_interface::ToServer,
     };
     use mockall::predicate::eq;
-    use tokio::{join, sync::mpsc::channel};
+    use std::collections::HashMap;
+    use std::future::{pending, ready};
+    use std::path::PathBuf;
+    use std::time::Duration;
+    use tokio::{
+        join,
+        sync::mpsc::{channel, Receiver},
+    };
 
     const BUFFER_SIZE: usize = 20;
     const AGENT_NAME: &str = "agent_x";
@@ -288,6 +761,26 @@ mod tests {
     const WORKLOAD_2_NAME: &str = "workload2";
     const REQUEST_ID: &str = "request_id";
     const RUNTIME_NAME: &str = "runtime_name";
+    const RUN_FOLDER: &str = "/tmp/ankaios";
+    const MEMORY_THRESHOLD: u64 = 1000;
+    const DISCONNECT_THRESHOLD: Duration = Duration::from_secs(30);
+
+    // skips the periodically sent load status messages
+    async fn receive_workload_states(
+        to_server_receiver: &mut Receiver<ToServer>,
+    ) -> Option<ToServer> {
+        tokio::time::timeout(tokio::time::Duration::from_millis(200), async {
+            loop {
+                match to_server_receiver.recv().await {
+                    Some(ToServer::AgentLoadStatus(_)) => continue,
+                    other => return other,
+                }
+            }
+        })
+        .await
+        .ok()
+        .flatten()
+    }
 
     // [utest->swdd~agent-manager-listens-requests-from-server~1]
     // [utest->swdd~agent-uses-async-channels~1]
@@ -305,6 +798,9 @@ mod tests {
         let (to_server, _) = channel(BUFFER_SIZE);
         let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
         let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_collect_workload_resources()
+            .return_const(vec![]);
         mock_runtime_manager
             .expect_handle_update_workload()
             .once()
@@ -316,6 +812,12 @@ mod tests {
             mock_runtime_manager,
             to_server,
             workload_state_receiver,
+            ShutdownPolicy::default(),
+            None,
+            PathBuf::from(RUN_FOLDER),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
         );
 
         let workload_spec_1 = generate_test_workload_spec_with_param(
@@ -330,7 +832,7 @@ mod tests {
             RUNTIME_NAME.into(),
         );
 
-        let handle = tokio::spawn(async move { agent_manager.start().await });
+        let handle = tokio::spawn(async move { agent_manager.start(pending()).await });
 
         let update_workload_result = to_manager
             .update_workload(
@@ -366,6 +868,9 @@ mod tests {
         );
 
         let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_collect_workload_resources()
+            .return_const(vec![]);
         mock_runtime_manager.expect_handle_update_workload().never();
         mock_runtime_manager
             .expect_update_workloads_on_fulfilled_dependencies()
@@ -384,9 +889,15 @@ mod tests {
             mock_runtime_manager,
             to_server,
             workload_state_receiver,
+            ShutdownPolicy::default(),
+            None,
+            PathBuf::from(RUN_FOLDER),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
         );
 
-        let handle = tokio::spawn(async move { agent_manager.start().await });
+        let handle = tokio::spawn(async move { agent_manager.start(pending()).await });
 
         let update_workload_result = to_manager.update_workload_state(vec![workload_state]).await;
         assert!(update_workload_result.is_ok());
@@ -412,6 +923,9 @@ mod tests {
         let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
 
         let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_collect_workload_resources()
+            .return_const(vec![]);
         mock_runtime_manager.expect_handle_update_workload().never();
 
         let mut agent_manager = AgentManager::new(
@@ -420,13 +934,19 @@ mod tests {
             mock_runtime_manager,
             to_server,
             workload_state_receiver,
+            ShutdownPolicy::default(),
+            None,
+            PathBuf::from(RUN_FOLDER),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
         );
 
         let new_empty_states = vec![];
         let update_workload_result = to_manager.update_workload_state(new_empty_states).await;
         assert!(update_workload_result.is_ok());
 
-        let handle = tokio::spawn(async move { agent_manager.start().await });
+        let handle = tokio::spawn(async move { agent_manager.start(pending()).await });
 
         // Terminate the infinite receiver loop
         to_manager.stop().await.unwrap();
@@ -455,11 +975,14 @@ mod tests {
         let response = ank_base::Response {
             request_id: request_id.clone(),
             response_content: Some(ank_base::response::ResponseContent::CompleteState(
-                complete_state.clone(),
+                Box::new(complete_state.clone()),
             )),
         };
 
         let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_collect_workload_resources()
+            .return_const(vec![]);
         mock_runtime_manager
             .expect_forward_response()
             .with(eq(response.clone()))
@@ -472,9 +995,15 @@ mod tests {
             mock_runtime_manager,
             to_server,
             workload_state_receiver,
+            ShutdownPolicy::default(),
+            None,
+            PathBuf::from(RUN_FOLDER),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
         );
 
-        let handle = tokio::spawn(async move { agent_manager.start().await });
+        let handle = tokio::spawn(async move { agent_manager.start(pending()).await });
 
         let complete_state_result = to_manager.complete_state(request_id, complete_state).await;
         assert!(complete_state_result.is_ok());
@@ -484,49 +1013,90 @@ mod tests {
         assert!(join!(handle).0.is_ok());
     }
 
-    // [utest->swdd~agent-manager-receives-workload-states-of-its-workloads~1]
-    // [utest->swdd~agent-stores-workload-states-of-its-workloads~1]
-    // [utest->swdd~agent-sends-workload-states-of-its-workloads-to-server~2]
-    // [utest->swdd~agent-handles-update-workload-state-requests~1]
-    // [utest->swdd~agent-manager-hysteresis_on-workload-states-of-its-workloads~1]
+    // [utest->swdd~agent-manager-listens-requests-from-server~1]
+    // [utest->swdd~agent-handles-exec-requests~1]
     #[tokio::test]
-    async fn utest_agent_manager_receives_own_workload_states() {
+    async fn utest_agent_manager_forwards_exec_request() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
             .get_lock_async()
             .await;
 
+        let mock_wl_state_store_context = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store_context);
+
         let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
-        let (to_server, mut to_server_receiver) = channel(BUFFER_SIZE);
-        let (workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+        let (to_server, _) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
 
-        let workload_state_incoming = common::objects::generate_test_workload_state_with_agent(
-            WORKLOAD_1_NAME,
-            AGENT_NAME,
-            ExecutionState::running(),
-        );
+        let request_id = format!("cli@{REQUEST_ID}");
+        let exec_request = ExecRequest::Start(ExecStart {
+            workload_name: WORKLOAD_1_NAME.to_string(),
+            command: vec!["sh".to_string()],
+        });
 
-        let wl_state_after_hysteresis = common::objects::generate_test_workload_state_with_agent(
-            WORKLOAD_1_NAME,
-            AGENT_NAME,
-            ExecutionState::stopping_requested(),
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_collect_workload_resources()
+            .return_const(vec![]);
+        mock_runtime_manager
+            .expect_handle_exec_request()
+            .with(eq(request_id.clone()), eq(exec_request.clone()))
+            .once()
+            .return_const(());
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+            ShutdownPolicy::default(),
+            None,
+            PathBuf::from(RUN_FOLDER),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
         );
 
-        let mut mock_wl_state_store = MockWorkloadStateStore::default();
+        let handle = tokio::spawn(async move { agent_manager.start(pending()).await });
 
-        mock_wl_state_store.states_storage.insert(
-            WORKLOAD_1_NAME.to_string(),
-            ExecutionState::stopping_requested(),
-        );
+        let exec_request_result = to_manager
+            .exec_request(None, request_id, exec_request)
+            .await;
+        assert!(exec_request_result.is_ok());
 
-        mock_wl_state_store
-            .expected_update_workload_state_parameters
-            .push_back(wl_state_after_hysteresis.clone());
+        // Terminate the infinite receiver loop
+        to_manager.stop().await.unwrap();
+        assert!(join!(handle).0.is_ok());
+    }
 
-        mock_parameter_storage_new_returns(mock_wl_state_store);
+    // [utest->swdd~agent-handles-port-forward-requests~2]
+    #[tokio::test]
+    async fn utest_agent_manager_forwards_port_forward_request() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store_context = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store_context);
+
+        let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, _) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+
+        let request_id = format!("cli@{REQUEST_ID}");
+        let port_forward_request = PortForwardRequest::Start(PortForwardStart {
+            workload_name: WORKLOAD_1_NAME.to_string(),
+            port: 8080,
+        });
 
         let mut mock_runtime_manager = RuntimeManager::default();
         mock_runtime_manager
-            .expect_update_workloads_on_fulfilled_dependencies()
+            .expect_collect_workload_resources()
+            .return_const(vec![]);
+        mock_runtime_manager
+            .expect_handle_port_forward_request()
+            .with(eq(request_id.clone()), eq(port_forward_request.clone()))
             .once()
             .return_const(());
 
@@ -536,55 +1106,52 @@ mod tests {
             mock_runtime_manager,
             to_server,
             workload_state_receiver,
+            ShutdownPolicy::default(),
+            None,
+            PathBuf::from(RUN_FOLDER),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
         );
 
-        let handle = tokio::spawn(async move { agent_manager.start().await });
+        let handle = tokio::spawn(async move { agent_manager.start(pending()).await });
 
-        workload_state_sender
-            .report_workload_execution_state(
-                &workload_state_incoming.instance_name,
-                workload_state_incoming.execution_state.clone(),
-            )
+        let port_forward_request_result = to_manager
+            .port_forward_request(None, request_id, port_forward_request)
             .await;
-
-        let expected_workload_states = ToServer::UpdateWorkloadState(UpdateWorkloadState {
-            workload_states: vec![wl_state_after_hysteresis],
-        });
-        assert_eq!(
-            Ok(Some(expected_workload_states)),
-            tokio::time::timeout(
-                tokio::time::Duration::from_millis(200),
-                to_server_receiver.recv()
-            )
-            .await
-        );
+        assert!(port_forward_request_result.is_ok());
 
         // Terminate the infinite receiver loop
         to_manager.stop().await.unwrap();
         assert!(join!(handle).0.is_ok());
     }
 
-    // [utest->swdd~agent-sends-node-resource-availability-to-server~1]
+    // [utest->swdd~agent-handles-restart-workload-requests~1]
     #[tokio::test]
-    async fn utest_agent_manager_sends_available_resources() {
+    async fn utest_agent_manager_forwards_restart_workload_request() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
             .get_lock_async()
             .await;
 
-        let mock_wl_state_store = MockWorkloadStateStore::default();
-        mock_parameter_storage_new_returns(mock_wl_state_store);
+        let mock_wl_state_store_context = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store_context);
 
         let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
-        let (to_server, mut server_receiver) = channel(BUFFER_SIZE);
+        let (to_server, _) = channel(BUFFER_SIZE);
         let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+
+        let request_id = format!("cli@{REQUEST_ID}");
+        let workload_names = vec![WORKLOAD_1_NAME.to_string()];
+
         let mut mock_runtime_manager = RuntimeManager::default();
-        mock_runtime_manager.expect_handle_update_workload().never();
-        mock_runtime_manager.expect_forward_response().never();
-        mock_runtime_manager.expect_execute_workloads().never();
-        mock_runtime_manager.expect_handle_server_hello().never();
         mock_runtime_manager
-            .expect_update_workloads_on_fulfilled_dependencies()
-            .never();
+            .expect_collect_workload_resources()
+            .return_const(vec![]);
+        mock_runtime_manager
+            .expect_handle_restart_workload_request()
+            .with(eq(request_id.clone()), eq(workload_names.clone()))
+            .once()
+            .return_const(());
 
         let mut agent_manager = AgentManager::new(
             AGENT_NAME.to_string(),
@@ -592,15 +1159,367 @@ mod tests {
             mock_runtime_manager,
             to_server,
             workload_state_receiver,
+            ShutdownPolicy::default(),
+            None,
+            PathBuf::from(RUN_FOLDER),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
         );
 
-        let handle = tokio::spawn(async move { agent_manager.start().await });
+        let handle = tokio::spawn(async move { agent_manager.start(pending()).await });
 
-        let result = server_receiver.recv().await.unwrap();
-        if let ToServer::AgentLoadStatus(load_status) = result {
-            assert_eq!(load_status.agent_name, AGENT_NAME.to_string());
-            assert_ne!(load_status.cpu_usage.cpu_usage, 0);
-            assert_ne!(load_status.cpu_usage.cpu_usage, 0);
+        let restart_workload_request_result = to_manager
+            .restart_workload_request(None, request_id, workload_names)
+            .await;
+        assert!(restart_workload_request_result.is_ok());
+
+        // Terminate the infinite receiver loop
+        to_manager.stop().await.unwrap();
+        assert!(join!(handle).0.is_ok());
+    }
+
+    // [utest->swdd~agent-handles-pause-workload-requests~1]
+    #[tokio::test]
+    async fn utest_agent_manager_forwards_pause_workload_request() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store_context = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store_context);
+
+        let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, _) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+
+        let request_id = format!("cli@{REQUEST_ID}");
+        let workload_names = vec![WORKLOAD_1_NAME.to_string()];
+
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_collect_workload_resources()
+            .return_const(vec![]);
+        mock_runtime_manager
+            .expect_handle_pause_workload_request()
+            .with(
+                eq(request_id.clone()),
+                eq(workload_names.clone()),
+                eq(false),
+            )
+            .once()
+            .return_const(());
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+            ShutdownPolicy::default(),
+            None,
+            PathBuf::from(RUN_FOLDER),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
+        );
+
+        let handle = tokio::spawn(async move { agent_manager.start(pending()).await });
+
+        let pause_workload_request_result = to_manager
+            .pause_workload_request(None, request_id, workload_names, false)
+            .await;
+        assert!(pause_workload_request_result.is_ok());
+
+        // Terminate the infinite receiver loop
+        to_manager.stop().await.unwrap();
+        assert!(join!(handle).0.is_ok());
+    }
+
+    // [utest->swdd~agent-pins-images-received-from-server~1]
+    #[tokio::test]
+    async fn utest_agent_manager_pins_images_received_from_server() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store_context = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store_context);
+
+        let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, _) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_collect_workload_resources()
+            .return_const(vec![]);
+
+        let image_puller = PodmanImagePuller::default();
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+            ShutdownPolicy::default(),
+            None,
+            PathBuf::from(RUN_FOLDER),
+            None,
+            DISCONNECT_THRESHOLD,
+            image_puller.clone(),
+        );
+
+        let handle = tokio::spawn(async move { agent_manager.start(pending()).await });
+
+        let image = "ghcr.io/eclipse-ankaios/tests/nginx:alpine-slim".to_string();
+        assert!(to_manager
+            .pull_images(None, vec![image.clone()])
+            .await
+            .is_ok());
+
+        // Terminate the infinite receiver loop
+        to_manager.stop().await.unwrap();
+        assert!(join!(handle).0.is_ok());
+
+        assert_eq!(
+            image_puller.image_states(),
+            HashMap::from([(image, ImageState::pending())])
+        );
+    }
+
+    // [utest->swdd~agent-manager-receives-workload-states-of-its-workloads~1]
+    // [utest->swdd~agent-stores-workload-states-of-its-workloads~1]
+    // [utest->swdd~agent-sends-workload-states-of-its-workloads-to-server~2]
+    // [utest->swdd~agent-handles-update-workload-state-requests~1]
+    // [utest->swdd~agent-manager-hysteresis_on-workload-states-of-its-workloads~1]
+    #[tokio::test]
+    async fn utest_agent_manager_receives_own_workload_states() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, mut to_server_receiver) = channel(BUFFER_SIZE);
+        let (workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+
+        let workload_state_incoming = common::objects::generate_test_workload_state_with_agent(
+            WORKLOAD_1_NAME,
+            AGENT_NAME,
+            ExecutionState::running(),
+        );
+
+        let wl_state_after_hysteresis = common::objects::generate_test_workload_state_with_agent(
+            WORKLOAD_1_NAME,
+            AGENT_NAME,
+            ExecutionState::stopping_requested(),
+        );
+
+        let mut mock_wl_state_store = MockWorkloadStateStore::default();
+
+        mock_wl_state_store.states_storage.insert(
+            WORKLOAD_1_NAME.to_string(),
+            ExecutionState::stopping_requested(),
+        );
+
+        mock_wl_state_store
+            .expected_update_workload_state_parameters
+            .push_back(wl_state_after_hysteresis.clone());
+
+        mock_parameter_storage_new_returns(mock_wl_state_store);
+
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_collect_workload_resources()
+            .return_const(vec![]);
+        mock_runtime_manager
+            .expect_update_workloads_on_fulfilled_dependencies()
+            .once()
+            .return_const(());
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+            ShutdownPolicy::default(),
+            None,
+            PathBuf::from(RUN_FOLDER),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
+        );
+
+        let handle = tokio::spawn(async move { agent_manager.start(pending()).await });
+
+        workload_state_sender
+            .report_workload_execution_state(
+                &workload_state_incoming.instance_name,
+                workload_state_incoming.execution_state.clone(),
+            )
+            .await;
+
+        let expected_workload_states = ToServer::UpdateWorkloadState(UpdateWorkloadState {
+            workload_states: vec![wl_state_after_hysteresis],
+        });
+        assert_eq!(
+            Some(expected_workload_states),
+            receive_workload_states(&mut to_server_receiver).await
+        );
+
+        // Terminate the infinite receiver loop
+        to_manager.stop().await.unwrap();
+        assert!(join!(handle).0.is_ok());
+    }
+
+    // [utest->swdd~agent-batches-workload-states-of-its-workloads~1]
+    #[tokio::test]
+    async fn utest_agent_manager_batches_own_workload_states() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, mut to_server_receiver) = channel(BUFFER_SIZE);
+        let (workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+
+        let wl_1_state_pending = common::objects::generate_test_workload_state_with_agent(
+            WORKLOAD_1_NAME,
+            AGENT_NAME,
+            ExecutionState::starting_triggered(),
+        );
+        let wl_1_state_running = common::objects::generate_test_workload_state_with_agent(
+            WORKLOAD_1_NAME,
+            AGENT_NAME,
+            ExecutionState::running(),
+        );
+        let wl_2_state_running = common::objects::generate_test_workload_state_with_agent(
+            WORKLOAD_2_NAME,
+            AGENT_NAME,
+            ExecutionState::running(),
+        );
+
+        let mut mock_wl_state_store = MockWorkloadStateStore::default();
+        mock_wl_state_store
+            .expected_update_workload_state_parameters
+            .extend([
+                wl_1_state_pending.clone(),
+                wl_1_state_running.clone(),
+                wl_2_state_running.clone(),
+            ]);
+        mock_parameter_storage_new_returns(mock_wl_state_store);
+
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_collect_workload_resources()
+            .return_const(vec![]);
+        mock_runtime_manager
+            .expect_update_workloads_on_fulfilled_dependencies()
+            .times(3)
+            .return_const(());
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+            ShutdownPolicy::default(),
+            None,
+            PathBuf::from(RUN_FOLDER),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
+        );
+
+        for workload_state in [
+            &wl_1_state_pending,
+            &wl_1_state_running,
+            &wl_2_state_running,
+        ] {
+            workload_state_sender
+                .report_workload_execution_state(
+                    &workload_state.instance_name,
+                    workload_state.execution_state.clone(),
+                )
+                .await;
+        }
+
+        let handle = tokio::spawn(async move { agent_manager.start(pending()).await });
+
+        let expected_workload_states = ToServer::UpdateWorkloadState(UpdateWorkloadState {
+            workload_states: vec![wl_1_state_running, wl_2_state_running],
+        });
+        assert_eq!(
+            Some(expected_workload_states),
+            receive_workload_states(&mut to_server_receiver).await
+        );
+
+        // Terminate the infinite receiver loop
+        to_manager.stop().await.unwrap();
+        assert!(join!(handle).0.is_ok());
+
+        assert!(to_server_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~agent-sends-node-resource-availability-to-server~1]
+    // [utest->swdd~agent-sends-workload-resources-to-server~1]
+    #[tokio::test]
+    async fn utest_agent_manager_sends_available_resources() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store);
+
+        let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, mut server_receiver) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_collect_workload_resources()
+            .return_const(vec![generate_test_workload_resources(
+                AGENT_NAME,
+                WORKLOAD_1_NAME,
+            )]);
+        mock_runtime_manager.expect_handle_update_workload().never();
+        mock_runtime_manager.expect_forward_response().never();
+        mock_runtime_manager.expect_execute_workloads().never();
+        mock_runtime_manager.expect_handle_server_hello().never();
+        mock_runtime_manager
+            .expect_update_workloads_on_fulfilled_dependencies()
+            .never();
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+            ShutdownPolicy::default(),
+            None,
+            PathBuf::from(RUN_FOLDER),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
+        );
+
+        let handle = tokio::spawn(async move { agent_manager.start(pending()).await });
+
+        let result = server_receiver.recv().await.unwrap();
+        if let ToServer::AgentLoadStatus(load_status) = result {
+            assert_eq!(load_status.agent_name, AGENT_NAME.to_string());
+            assert_ne!(load_status.cpu_usage.cpu_usage, 0);
+            assert_ne!(load_status.cpu_usage.cpu_usage, 0);
+            assert_eq!(
+                load_status.workload_resources,
+                vec![generate_test_workload_resources(
+                    AGENT_NAME,
+                    WORKLOAD_1_NAME
+                )]
+            );
         } else {
             panic!("Expected AgentLoadStatus, got something else");
         }
@@ -608,4 +1527,524 @@ mod tests {
         to_manager.stop().await.unwrap();
         assert!(join!(handle).0.is_ok());
     }
+
+    // [utest->swdd~agent-sends-extended-node-load-to-server~1]
+    #[test]
+    fn utest_read_thermal_zones() {
+        let thermal_zones_folder = tempfile::tempdir().unwrap();
+        for (zone, zone_type, temperature) in [
+            ("thermal_zone1", "gpu-thermal\n", "40000\n"),
+            ("thermal_zone0", "cpu-thermal\n", "42500\n"),
+            ("thermal_zone2", "broken-thermal\n", "not a temperature\n"),
+            ("cooling_device0", "fan\n", "1\n"),
+        ] {
+            let zone_path = thermal_zones_folder.path().join(zone);
+            std::fs::create_dir(&zone_path).unwrap();
+            std::fs::write(zone_path.join("type"), zone_type).unwrap();
+            std::fs::write(zone_path.join("temp"), temperature).unwrap();
+        }
+
+        assert_eq!(
+            read_thermal_zones(thermal_zones_folder.path()),
+            Temperature {
+                thermal_zones: vec![
+                    ThermalZone {
+                        zone_type: "cpu-thermal".to_string(),
+                        temperature: 42500,
+                    },
+                    ThermalZone {
+                        zone_type: "gpu-thermal".to_string(),
+                        temperature: 40000,
+                    },
+                ]
+            }
+        );
+    }
+
+    // [utest->swdd~agent-sends-extended-node-load-to-server~1]
+    #[test]
+    fn utest_read_thermal_zones_not_available() {
+        let thermal_zones_folder = tempfile::tempdir().unwrap();
+
+        assert_eq!(
+            read_thermal_zones(&thermal_zones_folder.path().join("not_existing")),
+            Temperature::default()
+        );
+    }
+
+    // [utest->swdd~agent-shuts-down-gracefully~1]
+    // [utest->swdd~agent-reports-final-workload-states-on-shutdown~1]
+    #[tokio::test]
+    async fn utest_agent_manager_shutdown_keeps_workloads_running() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store);
+
+        let (_to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, mut to_server_receiver) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_collect_workload_resources()
+            .return_const(vec![]);
+        mock_runtime_manager.expect_delete_all_workloads().never();
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+            ShutdownPolicy::KeepRunning,
+            None,
+            PathBuf::from(RUN_FOLDER),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
+        );
+
+        let handle = tokio::spawn(async move { agent_manager.start(ready(())).await });
+
+        assert!(matches!(
+            receive_workload_states(&mut to_server_receiver).await,
+            Some(ToServer::Stop(_))
+        ));
+        assert!(join!(handle).0.is_ok());
+    }
+
+    // [utest->swdd~agent-shuts-down-gracefully~1]
+    // [utest->swdd~agent-reports-final-workload-states-on-shutdown~1]
+    #[tokio::test]
+    async fn utest_agent_manager_shutdown_stops_workloads() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let (_to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, mut to_server_receiver) = channel(BUFFER_SIZE);
+        let (workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+
+        let wl_state_stopping = common::objects::generate_test_workload_state_with_agent(
+            WORKLOAD_1_NAME,
+            AGENT_NAME,
+            ExecutionState::stopping_requested(),
+        );
+        let wl_state_removed = common::objects::generate_test_workload_state_with_agent(
+            WORKLOAD_1_NAME,
+            AGENT_NAME,
+            ExecutionState::removed(),
+        );
+
+        let mut mock_wl_state_store = MockWorkloadStateStore::default();
+        mock_wl_state_store
+            .expected_update_workload_state_parameters
+            .extend([wl_state_stopping.clone(), wl_state_removed.clone()]);
+        mock_parameter_storage_new_returns(mock_wl_state_store);
+
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_collect_workload_resources()
+            .return_const(vec![]);
+        mock_runtime_manager
+            .expect_update_workloads_on_fulfilled_dependencies()
+            .never();
+        let reported_states = [wl_state_stopping, wl_state_removed.clone()];
+        mock_runtime_manager
+            .expect_delete_all_workloads()
+            .once()
+            .return_once(move || {
+                for workload_state in reported_states {
+                    workload_state_sender.try_send(workload_state).unwrap();
+                }
+                vec![WORKLOAD_1_NAME.to_string()]
+            });
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+            ShutdownPolicy::StopWorkloads,
+            None,
+            PathBuf::from(RUN_FOLDER),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
+        );
+
+        let handle = tokio::spawn(async move { agent_manager.start(ready(())).await });
+
+        let expected_workload_states = ToServer::UpdateWorkloadState(UpdateWorkloadState {
+            workload_states: vec![wl_state_removed],
+        });
+        assert_eq!(
+            Some(expected_workload_states),
+            receive_workload_states(&mut to_server_receiver).await
+        );
+        assert!(matches!(
+            receive_workload_states(&mut to_server_receiver).await,
+            Some(ToServer::Stop(_))
+        ));
+        assert!(join!(handle).0.is_ok());
+    }
+
+    // [utest->swdd~agent-shuts-down-gracefully~1]
+    #[tokio::test]
+    async fn utest_agent_manager_shutdown_stops_workloads_with_timeout() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store);
+
+        let (_to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, mut to_server_receiver) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_collect_workload_resources()
+            .return_const(vec![]);
+        mock_runtime_manager
+            .expect_delete_all_workloads()
+            .once()
+            .return_const(vec![WORKLOAD_1_NAME.to_string()]);
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+            ShutdownPolicy::StopWithTimeout(Duration::from_millis(10)),
+            None,
+            PathBuf::from(RUN_FOLDER),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
+        );
+
+        let handle = tokio::spawn(async move { agent_manager.start(ready(())).await });
+
+        // the workload never reports the removed state, but the agent shuts down anyway
+        assert!(matches!(
+            receive_workload_states(&mut to_server_receiver).await,
+            Some(ToServer::Stop(_))
+        ));
+        assert!(join!(handle).0.is_ok());
+    }
+
+    // [utest->swdd~agent-starts-cached-workloads-without-server~1]
+    #[tokio::test]
+    async fn utest_agent_manager_starts_cached_workloads_without_server_hello() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store);
+
+        let run_folder = tempfile::tempdir().unwrap();
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.into(),
+            WORKLOAD_1_NAME.into(),
+            RUNTIME_NAME.into(),
+        );
+        WorkloadCache::new(run_folder.path(), AGENT_NAME, Duration::ZERO)
+            .replace(std::slice::from_ref(&workload_spec));
+
+        let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, _to_server_receiver) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_collect_workload_resources()
+            .return_const(vec![]);
+        mock_runtime_manager
+            .expect_handle_server_hello()
+            .withf(move |added_workloads, _| added_workloads == &vec![workload_spec.clone()])
+            .once()
+            .return_const(());
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+            ShutdownPolicy::default(),
+            Some(WorkloadCache::new(
+                run_folder.path(),
+                AGENT_NAME,
+                Duration::from_millis(1),
+            )),
+            run_folder.path().to_path_buf(),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
+        );
+
+        let handle = tokio::spawn(async move { agent_manager.start(pending()).await });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // Terminate the infinite receiver loop
+        to_manager.stop().await.unwrap();
+        assert!(join!(handle).0.is_ok());
+    }
+
+    // [utest->swdd~agent-caches-workloads-received-from-server~1]
+    #[tokio::test]
+    async fn utest_agent_manager_caches_workloads_of_server_hello() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store);
+
+        let run_folder = tempfile::tempdir().unwrap();
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.into(),
+            WORKLOAD_1_NAME.into(),
+            RUNTIME_NAME.into(),
+        );
+
+        let (to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, _to_server_receiver) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_collect_workload_resources()
+            .return_const(vec![]);
+        mock_runtime_manager
+            .expect_handle_server_hello()
+            .once()
+            .return_const(());
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+            ShutdownPolicy::default(),
+            Some(WorkloadCache::new(
+                run_folder.path(),
+                AGENT_NAME,
+                Duration::from_secs(3600),
+            )),
+            run_folder.path().to_path_buf(),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
+        );
+
+        let handle = tokio::spawn(async move { agent_manager.start(pending()).await });
+
+        to_manager
+            .server_hello(None, vec![workload_spec.clone()])
+            .await
+            .unwrap();
+
+        // Terminate the infinite receiver loop
+        to_manager.stop().await.unwrap();
+        assert!(join!(handle).0.is_ok());
+
+        assert_eq!(
+            WorkloadCache::new(run_folder.path(), AGENT_NAME, Duration::ZERO).load(),
+            vec![workload_spec]
+        );
+    }
+
+    // [utest->swdd~agent-evicts-workloads-on-memory-pressure~1]
+    #[tokio::test]
+    async fn utest_agent_manager_evicts_workload_on_memory_pressure() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store);
+
+        let (_to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, _to_server_receiver) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_evict_lowest_priority_workload()
+            .once()
+            .return_const(Some(WORKLOAD_1_NAME.to_string()));
+        mock_runtime_manager
+            .expect_restore_highest_priority_workload()
+            .never();
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+            ShutdownPolicy::default(),
+            None,
+            PathBuf::from(RUN_FOLDER),
+            Some(EvictionController::new(MEMORY_THRESHOLD)),
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
+        );
+
+        agent_manager
+            .handle_memory_pressure(MEMORY_THRESHOLD - 1)
+            .await;
+    }
+
+    // [utest->swdd~agent-evicts-workloads-on-memory-pressure~1]
+    #[tokio::test]
+    async fn utest_agent_manager_restores_workload_when_memory_pressure_subsided() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store);
+
+        let (_to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, _to_server_receiver) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_evict_lowest_priority_workload()
+            .never();
+        mock_runtime_manager
+            .expect_restore_highest_priority_workload()
+            .once()
+            .return_const(None);
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+            ShutdownPolicy::default(),
+            None,
+            PathBuf::from(RUN_FOLDER),
+            Some(EvictionController::new(MEMORY_THRESHOLD)),
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
+        );
+
+        agent_manager
+            .handle_memory_pressure(2 * MEMORY_THRESHOLD)
+            .await;
+    }
+
+    // [utest->swdd~agent-evicts-workloads-on-memory-pressure~1]
+    #[tokio::test]
+    async fn utest_agent_manager_eviction_disabled() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store);
+
+        let (_to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, _to_server_receiver) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_evict_lowest_priority_workload()
+            .never();
+        mock_runtime_manager
+            .expect_restore_highest_priority_workload()
+            .never();
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+            ShutdownPolicy::default(),
+            None,
+            PathBuf::from(RUN_FOLDER),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
+        );
+
+        agent_manager.handle_memory_pressure(0).await;
+    }
+
+    // [utest->swdd~agent-enforces-agent-disconnect-policies~1]
+    // [utest->swdd~agent-restarts-workloads-stopped-on-disconnect~1]
+    #[tokio::test]
+    async fn utest_agent_manager_enforces_agent_disconnect_policies_until_server_hello() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_wl_state_store = MockWorkloadStateStore::default();
+        mock_parameter_storage_new_returns(mock_wl_state_store);
+
+        let (_to_manager, manager_receiver) = channel(BUFFER_SIZE);
+        let (to_server, _to_server_receiver) = channel(BUFFER_SIZE);
+        let (_workload_state_sender, workload_state_receiver) = channel(BUFFER_SIZE);
+        let mut mock_runtime_manager = RuntimeManager::default();
+        mock_runtime_manager
+            .expect_stop_workloads_on_disconnect()
+            .once()
+            .return_const(Some(Duration::from_secs(5)));
+        mock_runtime_manager
+            .expect_handle_server_hello()
+            .once()
+            .return_const(());
+
+        let mut agent_manager = AgentManager::new(
+            AGENT_NAME.to_string(),
+            manager_receiver,
+            mock_runtime_manager,
+            to_server,
+            workload_state_receiver,
+            ShutdownPolicy::default(),
+            None,
+            PathBuf::from(RUN_FOLDER),
+            None,
+            DISCONNECT_THRESHOLD,
+            PodmanImagePuller::default(),
+        );
+
+        for _ in 0..2 {
+            agent_manager
+                .execute_from_server_command(FromServer::ServerConnectionLost(
+                    ServerConnectionLost {},
+                ))
+                .await;
+            // a repeated notification does not restart the disconnect threshold
+            assert_eq!(
+                agent_manager.disconnect_policy_deadline,
+                agent_manager
+                    .disconnected_since
+                    .map(|disconnected_since| disconnected_since + DISCONNECT_THRESHOLD)
+            );
+        }
+
+        agent_manager.enforce_agent_disconnect_policies().await;
+        assert!(agent_manager.disconnect_policy_deadline.is_some());
+
+        agent_manager
+            .execute_from_server_command(FromServer::ServerHello(ServerHello {
+                agent_name: None,
+                added_workloads: vec![],
+            }))
+            .await;
+        assert!(agent_manager.disconnected_since.is_none());
+        assert!(agent_manager.disconnect_policy_deadline.is_none());
+    }
 }
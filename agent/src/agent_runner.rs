@@ -0,0 +1,400 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashMap, future::Future, net::SocketAddr, path::Path, time::Duration};
+
+use common::communications_client::CommunicationsClient;
+use common::from_server_interface::FromServer;
+use common::metrics_endpoint::{bind_metrics_endpoint, serve_metrics};
+use common::objects::{AgentName, WorkloadState};
+use common::to_server_interface::ToServer;
+use grpc::client::GRPCCommunicationsClient;
+use grpc::security::TLSConfig;
+
+use crate::agent_config::AgentConfig;
+use crate::agent_manager::AgentManager;
+use crate::eviction_controller::EvictionController;
+use crate::generic_polling_state_checker::GenericPollingStateChecker;
+use crate::io_utils::prepare_agent_run_directory;
+use crate::metrics;
+use crate::runtime_connectors::{
+    containerd::{ContainerdRuntime, ContainerdWorkloadId},
+    kubernetes::{KubernetesRuntime, KubernetesStateChecker, KubernetesWorkloadId},
+    plugin::{PluginRuntimeConnector, PluginWorkloadId},
+    podman::{
+        ImageGcPolicy, PodmanImageGarbageCollector, PodmanImagePuller, PodmanRuntime,
+        PodmanWorkloadId,
+    },
+    podman_kube::{PodmanKubeRuntime, PodmanKubeWorkloadId},
+    systemd::{SystemdRuntime, SystemdStateChecker, SystemdWorkloadId},
+    GenericRuntimeFacade, RuntimeConnector, RuntimeFacade,
+};
+#[cfg_attr(test, mockall_double::double)]
+use crate::runtime_manager::RuntimeManager;
+use crate::secret_resolver::SecretResolver;
+use crate::shutdown_policy::ShutdownPolicy;
+use crate::workload::OperationLimiter;
+use crate::workload_cache::WorkloadCache;
+use crate::BUFFER_SIZE;
+
+const FINAL_MESSAGES_DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+// The settings of an agent started with run_agent. The binary fills them from its command line
+// arguments and other processes, e.g., the development mode of the CLI, with their own values.
+pub struct AgentRunConfig {
+    pub agent_name: String,
+    pub run_folder: String,
+    pub agent_config: AgentConfig,
+    pub shutdown_policy: ShutdownPolicy,
+    pub disconnect_threshold: Duration,
+    pub offline_start_delay: Option<Duration>,
+    pub eviction_memory_threshold: Option<u64>,
+    pub image_gc_max_age: Option<Duration>,
+    pub image_gc_max_cache_size: Option<u64>,
+    pub metrics_address: Option<SocketAddr>,
+}
+
+pub enum AgentConnection {
+    Grpc {
+        server_url: String,
+        tls_config: Option<TLSConfig>,
+    },
+    // [impl->swdd~agent-runs-within-other-process~1]
+    InProcess(Box<dyn CommunicationsClient + Send>),
+}
+
+// [impl->swdd~agent-supports-podman~2]
+// [impl->swdd~agent-supports-podman-kube-runtime~1]
+// [impl->swdd~agent-supports-containerd~1]
+// [impl->swdd~agent-supports-kubernetes~1]
+// [impl->swdd~agent-supports-systemd~1]
+// [impl->swdd~agent-supports-runtime-plugins~2]
+fn create_runtime_facades(
+    agent_config: &AgentConfig,
+    operation_limiter: &OperationLimiter,
+    secret_resolver: &SecretResolver,
+) -> HashMap<String, Box<dyn RuntimeFacade>> {
+    let mut runtime_facade_map: HashMap<String, Box<dyn RuntimeFacade>> = HashMap::new();
+
+    // [impl->swdd~agent-selects-workload-log-sink~1]
+    let podman_runtime = Box::new(PodmanRuntime {
+        workload_logs: agent_config.workload_logs.clone(),
+    });
+    let podman_runtime_name = podman_runtime.name();
+    let podman_facade = Box::new(
+        GenericRuntimeFacade::<PodmanWorkloadId, GenericPollingStateChecker>::new(podman_runtime)
+            .with_operation_limiter(operation_limiter.clone())
+            .with_secret_resolver(secret_resolver.clone()),
+    );
+    runtime_facade_map.insert(podman_runtime_name, podman_facade);
+
+    let podman_kube_runtime = Box::new(PodmanKubeRuntime {});
+    let podman_kube_runtime_name = podman_kube_runtime.name();
+    let podman_kube_facade = Box::new(
+        GenericRuntimeFacade::<PodmanKubeWorkloadId, GenericPollingStateChecker>::new(
+            podman_kube_runtime,
+        )
+        .with_operation_limiter(operation_limiter.clone())
+        .with_secret_resolver(secret_resolver.clone()),
+    );
+    runtime_facade_map.insert(podman_kube_runtime_name, podman_kube_facade);
+
+    let containerd_runtime = Box::new(ContainerdRuntime {
+        workload_logs: agent_config.workload_logs.clone(),
+    });
+    let containerd_runtime_name = containerd_runtime.name();
+    let containerd_facade = Box::new(
+        GenericRuntimeFacade::<ContainerdWorkloadId, GenericPollingStateChecker>::new(
+            containerd_runtime,
+        )
+        .with_operation_limiter(operation_limiter.clone())
+        .with_secret_resolver(secret_resolver.clone()),
+    );
+    runtime_facade_map.insert(containerd_runtime_name, containerd_facade);
+
+    let kubernetes_runtime = Box::new(KubernetesRuntime {});
+    let kubernetes_runtime_name = kubernetes_runtime.name();
+    let kubernetes_facade = Box::new(
+        GenericRuntimeFacade::<KubernetesWorkloadId, KubernetesStateChecker>::new(
+            kubernetes_runtime,
+        )
+        .with_operation_limiter(operation_limiter.clone())
+        .with_secret_resolver(secret_resolver.clone()),
+    );
+    runtime_facade_map.insert(kubernetes_runtime_name, kubernetes_facade);
+
+    let systemd_runtime = Box::new(SystemdRuntime {
+        workload_logs: agent_config.workload_logs.clone(),
+    });
+    let systemd_runtime_name = systemd_runtime.name();
+    let systemd_facade = Box::new(
+        GenericRuntimeFacade::<SystemdWorkloadId, SystemdStateChecker>::new(systemd_runtime)
+            .with_operation_limiter(operation_limiter.clone())
+            .with_secret_resolver(secret_resolver.clone()),
+    );
+    runtime_facade_map.insert(systemd_runtime_name, systemd_facade);
+
+    // [impl->swdd~agent-supports-mock-runtime~1]
+    #[cfg(feature = "mock_runtime")]
+    {
+        use crate::runtime_connectors::mock::{MockRuntime, MockWorkloadId};
+        let mock_runtime = Box::new(MockRuntime::default());
+        let mock_runtime_name = mock_runtime.name();
+        let mock_facade = Box::new(
+            GenericRuntimeFacade::<MockWorkloadId, GenericPollingStateChecker>::new(mock_runtime)
+                .with_operation_limiter(operation_limiter.clone())
+                .with_secret_resolver(secret_resolver.clone()),
+        );
+        runtime_facade_map.insert(mock_runtime_name, mock_facade);
+    }
+
+    for (plugin_name, plugin_config) in &agent_config.runtime_plugins {
+        if runtime_facade_map.contains_key(plugin_name) {
+            log::warn!(
+                "Ignoring the runtime plugin '{}' as a runtime with this name already exists",
+                plugin_name
+            );
+            continue;
+        }
+        let plugin_runtime = Box::new(PluginRuntimeConnector::new(
+            plugin_name.clone(),
+            plugin_config.clone(),
+        ));
+        let plugin_facade = Box::new(
+            GenericRuntimeFacade::<PluginWorkloadId, GenericPollingStateChecker>::new(
+                plugin_runtime,
+            )
+            .with_operation_limiter(operation_limiter.clone())
+            .with_secret_resolver(secret_resolver.clone()),
+        );
+        runtime_facade_map.insert(plugin_name.clone(), plugin_facade);
+    }
+
+    runtime_facade_map
+}
+
+// Runs the agent until the shutdown signal completes or the connection to the server fails.
+// [impl->swdd~agent-runs-within-other-process~1]
+pub async fn run_agent(
+    config: AgentRunConfig,
+    connection: AgentConnection,
+    shutdown_signal: impl Future<Output = ()>,
+) -> Result<(), String> {
+    // [impl->swdd~agent-uses-async-channels~1]
+    let (to_manager, manager_receiver) = tokio::sync::mpsc::channel::<FromServer>(BUFFER_SIZE);
+    let (to_server, server_receiver) = tokio::sync::mpsc::channel::<ToServer>(BUFFER_SIZE);
+    let (workload_state_sender, workload_state_receiver) =
+        tokio::sync::mpsc::channel::<WorkloadState>(BUFFER_SIZE);
+
+    if let Some(metrics_address) = config.metrics_address {
+        // [impl->swdd~agent-provides-metrics-endpoint~1]
+        let listener = bind_metrics_endpoint(metrics_address)
+            .await
+            .map_err(|err| format!("Could not start the metrics endpoint: '{}'", err))?;
+        log::info!("Providing metrics at 'http://{}/metrics'", metrics_address);
+
+        // [impl->swdd~agent-collects-metrics~1]
+        let agent_metrics = metrics::agent_metrics();
+        agent_metrics.monitor_channel("from_server", &to_manager);
+        agent_metrics.monitor_channel("to_server", &to_server);
+        agent_metrics.monitor_channel("workload_states", &workload_state_sender);
+        tokio::spawn(serve_metrics(listener, move || agent_metrics.render()));
+    }
+
+    // [impl->swdd~agent-prepares-dedicated-run-folder~1]
+    let run_directory = prepare_agent_run_directory(&config.run_folder, &config.agent_name)
+        .map_err(|err| {
+            format!(
+                "Run folder creation failed. Cannot continue without run folder: '{}'",
+                err
+            )
+        })?;
+
+    // [impl->swdd~agent-limits-parallel-workload-operations~1]
+    let operation_limiter =
+        OperationLimiter::new(config.agent_config.max_parallel_workload_operations);
+
+    // [impl->swdd~agent-reads-secrets-from-providers~3]
+    let secret_resolver = SecretResolver::new(
+        config.agent_config.secrets_directory.clone(),
+        config.agent_config.allowed_env_secret_prefixes.clone(),
+    );
+
+    let runtime_facade_map =
+        create_runtime_facades(&config.agent_config, &operation_limiter, &secret_resolver);
+
+    // [impl->swdd~podman-image-puller-pulls-pinned-images~1]
+    let image_puller = PodmanImagePuller::default();
+    tokio::spawn(image_puller.clone().run());
+
+    // [impl->swdd~podman-image-gc-runs-periodically~1]
+    let image_gc_policy = ImageGcPolicy {
+        max_age: config.image_gc_max_age,
+        max_cache_size: config.image_gc_max_cache_size,
+    };
+    if image_gc_policy.is_enabled() {
+        tokio::spawn(PodmanImageGarbageCollector::new(image_gc_policy, image_puller.clone()).run());
+    }
+
+    // [impl->swdd~agent-reports-version-and-runtimes~1]
+    let mut runtime_names: Vec<String> = runtime_facade_map.keys().cloned().collect();
+    runtime_names.sort();
+
+    // The RuntimeManager currently directly gets the server ToServerInterface, but it shall get the agent manager interface
+    // This is needed to be able to filter/authorize the commands towards the Ankaios server
+    // The pipe connecting the workload to Ankaios must be in the runtime adapter
+    let runtime_manager = RuntimeManager::new(
+        AgentName::from(config.agent_name.as_str()),
+        run_directory.get_path(),
+        to_server.clone(),
+        runtime_facade_map,
+        workload_state_sender,
+    );
+
+    let mut communications_client: Box<dyn CommunicationsClient + Send> = match connection {
+        AgentConnection::Grpc {
+            server_url,
+            tls_config,
+        } => Box::new(
+            GRPCCommunicationsClient::new_agent_communication(
+                config.agent_name.clone(),
+                server_url,
+                tls_config,
+            )
+            .map_err(|err| format!("Failed to create communications client: '{}'", err))?
+            // [impl->swdd~grpc-supports-connection-tuning~1]
+            .with_connection_config(config.agent_config.grpc)
+            // [impl->swdd~agent-reports-labels~1]
+            .with_agent_labels(config.agent_config.labels)
+            // [impl->swdd~agent-reports-version-and-runtimes~1]
+            .with_agent_runtimes(runtime_names),
+        ),
+        AgentConnection::InProcess(communications_client) => communications_client,
+    };
+
+    // [impl->swdd~agent-caches-workloads-received-from-server~1]
+    let workload_cache = config.offline_start_delay.map(|offline_start_delay| {
+        WorkloadCache::new(
+            Path::new(&config.run_folder),
+            &config.agent_name,
+            offline_start_delay,
+        )
+    });
+
+    let mut agent_manager = AgentManager::new(
+        config.agent_name,
+        manager_receiver,
+        runtime_manager,
+        to_server,
+        workload_state_receiver,
+        config.shutdown_policy,
+        workload_cache,
+        Path::new(&config.run_folder).to_path_buf(),
+        // [impl->swdd~agent-evicts-workloads-on-memory-pressure~1]
+        config
+            .eviction_memory_threshold
+            .map(EvictionController::new),
+        config.disconnect_threshold,
+        image_puller,
+    );
+
+    // [impl->swdd~agent-sends-hello~1]
+    // [impl->swdd~agent-default-communication-grpc~1]
+    let communication = communications_client.run(server_receiver, to_manager);
+    tokio::pin!(communication);
+
+    tokio::select! {
+        communication_result = &mut communication => {
+            communication_result.map_err(|err| err.to_string())?;
+        }
+        _agent_mgr_result = agent_manager.start(shutdown_signal) => {
+            log::info!("AgentManager exited.");
+
+            // [impl->swdd~agent-reports-final-workload-states-on-shutdown~1]
+            if tokio::time::timeout(FINAL_MESSAGES_DELIVERY_TIMEOUT, &mut communication)
+                .await
+                .is_err()
+            {
+                log::warn!("Could not deliver the last messages to the server in time.");
+            }
+        }
+    }
+    // the run folder is kept until the workloads are stopped
+    drop(run_directory);
+
+    Ok(())
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::create_runtime_facades;
+    use crate::agent_config::AgentConfig;
+    use crate::runtime_connectors::plugin::RuntimePluginConfig;
+    use crate::secret_resolver::SecretResolver;
+    use crate::workload::OperationLimiter;
+
+    // [utest->swdd~agent-supports-runtime-plugins~2]
+    #[test]
+    fn utest_create_runtime_facades_provides_built_in_runtimes_and_plugins() {
+        let agent_config = AgentConfig {
+            runtime_plugins: HashMap::from([
+                (
+                    "my-runtime".to_owned(),
+                    RuntimePluginConfig {
+                        path: "/usr/bin/my-runtime".to_owned(),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "podman".to_owned(),
+                    RuntimePluginConfig {
+                        path: "/usr/bin/other-podman".to_owned(),
+                        ..Default::default()
+                    },
+                ),
+            ]),
+            ..Default::default()
+        };
+
+        let runtime_facades = create_runtime_facades(
+            &agent_config,
+            &OperationLimiter::new(None),
+            &SecretResolver::new(None, vec![]),
+        );
+
+        for runtime_name in [
+            "podman",
+            "podman-kube",
+            "containerd",
+            "k8s",
+            "systemd",
+            "my-runtime",
+        ] {
+            assert!(
+                runtime_facades.contains_key(runtime_name),
+                "missing runtime '{}'",
+                runtime_name
+            );
+        }
+    }
+}
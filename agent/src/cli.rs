@@ -13,8 +13,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use regex::Regex;
+use std::net::SocketAddr;
 
 use crate::io_utils::DEFAULT_RUN_FOLDER;
+use crate::shutdown_policy::ShutdownPolicy;
 use clap::Parser;
 use common::objects::STR_RE_AGENT;
 use common::DEFAULT_SERVER_ADDRESS;
@@ -45,7 +47,7 @@ pub struct Arguments {
     /// Agent name shall contain only regular upper and lowercase characters (a-z and A-Z), numbers and the symbols "-" and "_".
     pub agent_name: String,
     #[clap(short = 's', long = "server-url", default_value_t = DEFAULT_SERVER_ADDRESS.to_string())]
-    /// The server url.
+    /// The server url. Use 'unix:///path/to/server.sock' to connect to a server listening at a unix domain socket.
     pub server_url: String,
     /// An existing directory where agent specific runtime files will be stored. If not specified, a default folder is created.
     #[clap(short = 'r', long = "run-folder", default_value_t = DEFAULT_RUN_FOLDER.into())]
@@ -67,6 +69,53 @@ pub struct Arguments {
     #[clap(long = "key_pem", env = "ANKAGENT_KEY_PEM")]
     /// Path to agent key pem file.
     pub key_pem: Option<String>,
+    #[clap(long = "image-gc-max-age", env = "ANKAGENT_IMAGE_GC_MAX_AGE")]
+    /// Maximum age in seconds of container images not used by any container before they are removed. Image garbage collection is disabled if neither a maximum age nor a maximum cache size is given.
+    pub image_gc_max_age: Option<u64>,
+    #[clap(
+        long = "image-gc-max-cache-size",
+        env = "ANKAGENT_IMAGE_GC_MAX_CACHE_SIZE"
+    )]
+    /// Maximum size in bytes of all container images. If exceeded, the oldest images not used by any container are removed.
+    pub image_gc_max_cache_size: Option<u64>,
+    // [impl->swdd~agent-supports-shutdown-policies~1]
+    #[clap(
+        long = "shutdown-policy",
+        env = "ANKAGENT_SHUTDOWN_POLICY",
+        default_value_t = ShutdownPolicy::KeepRunning
+    )]
+    /// Behavior for the workloads of the agent when it is terminated with SIGTERM or SIGINT.
+    /// Supported policies are "keep-running", "stop-workloads" and "stop-with-timeout=<seconds>".
+    pub shutdown_policy: ShutdownPolicy,
+    // [impl->swdd~agent-provides-metrics-endpoint~1]
+    #[clap(long = "metrics-address", env = "ANKAGENT_METRICS_ADDRESS")]
+    /// Address the agent provides its metrics in the Prometheus text format at, e.g. "127.0.0.1:9101". The metrics endpoint is disabled if not specified.
+    pub metrics_address: Option<SocketAddr>,
+    // [impl->swdd~agent-starts-cached-workloads-without-server~1]
+    #[clap(long = "offline-start-delay", env = "ANKAGENT_OFFLINE_START_DELAY")]
+    /// Seconds to wait for the server after the start before the workloads of the last received desired state are started from the local workload cache.
+    /// The workload cache is disabled if not specified.
+    pub offline_start_delay: Option<u64>,
+    // [impl->swdd~agent-evicts-workloads-on-memory-pressure~1]
+    #[clap(
+        long = "eviction-memory-threshold",
+        env = "ANKAGENT_EVICTION_MEMORY_THRESHOLD"
+    )]
+    /// Free memory in bytes below which the agent evicts its running workloads with the lowest priority.
+    /// Evicted workloads are started again once the free memory is twice the threshold. Eviction is disabled if not specified.
+    pub eviction_memory_threshold: Option<u64>,
+    // [impl->swdd~agent-enforces-agent-disconnect-policies~1]
+    #[clap(
+        long = "disconnect-threshold",
+        env = "ANKAGENT_DISCONNECT_THRESHOLD",
+        default_value_t = 30
+    )]
+    /// Seconds the connection to the server must be lost before the agent enforces the agent disconnect policies of its workloads.
+    pub disconnect_threshold: u64,
+    // [impl->swdd~agent-loads-agent-config-file~1]
+    #[clap(long = "agent-config", env = "ANKAGENT_CONFIG")]
+    /// The path to the agent config file in TOML format. If not provided, '/etc/ankaios/ank-agent.conf' is used if it exists.
+    pub agent_config: Option<String>,
 }
 
 pub fn parse() -> Arguments {
@@ -29,7 +29,7 @@ pub use fifo::MockFifo;
 #[cfg(test)]
 use mockall::automock;
 
-use common::objects::WorkloadInstanceName;
+use common::objects::{PipesOwner, WorkloadInstanceName};
 use common::{from_server_interface::FromServerSender, to_server_interface::ToServerSender};
 
 #[cfg_attr(test, mockall_double::double)]
@@ -81,9 +81,13 @@ impl ControlInterface {
         execution_instance_name: &WorkloadInstanceName,
         output_pipe_channel: ToServerSender,
         authorizer: Authorizer,
+        pipes_owner: Option<PipesOwner>,
     ) -> Result<Self, ControlInterfaceError> {
         // [impl->swdd~agent-control-interface-pipes-path-naming~1]
-        match InputOutput::new(execution_instance_name.pipes_folder_name(run_directory)) {
+        match InputOutput::new(
+            execution_instance_name.pipes_folder_name(run_directory),
+            pipes_owner,
+        ) {
             Ok(pipes) => {
                 let input_stream = ReopenFile::open(pipes.get_output().get_path());
                 let output_stream = ReopenFile::create(pipes.get_input().get_path());
@@ -122,6 +126,7 @@ impl ControlInterface {
     pub fn get_api_location(&self) -> PathBuf {
         self.pipes.get_location()
     }
+
     pub fn get_input_pipe_sender(&self) -> FromServerSender {
         self.input_pipe_sender.clone()
     }
@@ -201,6 +206,7 @@ mod tests {
                 .build(),
             mpsc::channel(1).0,
             MockAuthorizer::default(),
+            None,
         )
         .unwrap();
 
@@ -251,6 +257,7 @@ mod tests {
                 .build(),
             mpsc::channel(1).0,
             MockAuthorizer::default(),
+            None,
         )
         .unwrap();
 
@@ -17,8 +17,13 @@ mod path_pattern;
 mod rule;
 
 use common::{
-    commands::Request,
-    objects::{AccessRightsRule, ControlInterfaceAccess, ReadWriteEnum},
+    commands::{
+        CompleteStateRequest, ExecRequest, PortForwardRequest, Request, RequestContent,
+        UpdateStateRequest,
+    },
+    objects::{
+        desired_state_path_of_workload, AccessRightsRule, ControlInterfaceAccess, ReadWriteEnum,
+    },
 };
 use path_pattern::{AllowPathPattern, DenyPathPattern, PathPattern, PathPatternMatcher};
 #[cfg(not(test))]
@@ -55,11 +60,74 @@ mock! {
     }
 }
 
+// The dependency graph contains the workloads of the desired state and their execution states
+const DEPENDENCY_GRAPH_FIELD_MASK: [&str; 2] = ["desiredState.workloads", "workloadStates"];
+
+// The events report changes of the workloads, their execution states and the connected agents
+const EVENTS_FIELD_MASK: [&str; 3] = ["desiredState.workloads", "workloadStates", "agents"];
+
+// The audit log reports the update masks of the updates of the complete desired state
+const AUDIT_FIELD_MASK: [&str; 1] = ["desiredState"];
+
 impl Authorizer {
     // [impl->swdd~agent-authorizing-request-operations~1]
     // [impl->swdd~agent-authorizing-condition-element-filter-mask-allowed~1]
     pub fn authorize(&self, request: &Request) -> bool {
         match &request.request_content {
+            // [impl->swdd~agent-authorizing-dependency-graph-request~1]
+            RequestContent::DependencyGraphRequest(_) => self.authorize(&Request {
+                request_id: request.request_id.clone(),
+                request_content: RequestContent::CompleteStateRequest(CompleteStateRequest {
+                    field_mask: DEPENDENCY_GRAPH_FIELD_MASK
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                    selector: vec![],
+                }),
+            }),
+            // [impl->swdd~agent-authorizing-events-request~1]
+            RequestContent::EventsRequest(_) => self.authorize(&Request {
+                request_id: request.request_id.clone(),
+                request_content: RequestContent::CompleteStateRequest(CompleteStateRequest {
+                    field_mask: EVENTS_FIELD_MASK.iter().map(ToString::to_string).collect(),
+                    selector: vec![],
+                }),
+            }),
+            // [impl->swdd~agent-authorizing-audit-request~1]
+            RequestContent::AuditRequest(_) => self.authorize(&Request {
+                request_id: request.request_id.clone(),
+                request_content: RequestContent::CompleteStateRequest(CompleteStateRequest {
+                    field_mask: AUDIT_FIELD_MASK.iter().map(ToString::to_string).collect(),
+                    selector: vec![],
+                }),
+            }),
+            // [impl->swdd~agent-authorizing-exec-request~1]
+            RequestContent::ExecRequest(ExecRequest::Start(exec_start)) => {
+                self.authorize_workload_update(&request.request_id, &exec_start.workload_name)
+            }
+            // The input can only reach exec sessions started with the same request id
+            RequestContent::ExecRequest(ExecRequest::Input(_)) => true,
+            // [impl->swdd~agent-authorizing-port-forward-request~1]
+            RequestContent::PortForwardRequest(PortForwardRequest::Start(port_forward_start)) => {
+                self.authorize_workload_update(
+                    &request.request_id,
+                    &port_forward_start.workload_name,
+                )
+            }
+            // The data can only reach port forwardings started with the same request id
+            RequestContent::PortForwardRequest(PortForwardRequest::Data(_)) => true,
+            // [impl->swdd~agent-authorizing-restart-workload-request~1]
+            RequestContent::RestartWorkloadRequest(restart_workload_request) => self
+                .authorize_workload_update(
+                    &request.request_id,
+                    &restart_workload_request.workload_name,
+                ),
+            // [impl->swdd~agent-authorizing-pause-workload-request~1]
+            RequestContent::PauseWorkloadRequest(pause_workload_request) => self
+                .authorize_workload_update(
+                    &request.request_id,
+                    &pause_workload_request.workload_name,
+                ),
             common::commands::RequestContent::CompleteStateRequest(r) => {
                 let field_mask = if r.field_mask.is_empty() {
                     // [impl->swdd~agent-authorizing-request-without-filter-mask~1]
@@ -164,6 +232,20 @@ impl Authorizer {
             }
         }
     }
+
+    // Executing a command in a workload or connecting to its ports is treated like modifying it
+    // [impl->swdd~agent-authorizing-namespaced-workload-requests~1]
+    fn authorize_workload_update(&self, request_id: &str, workload_name: &str) -> bool {
+        self.authorize(&Request {
+            request_id: request_id.to_owned(),
+            request_content: RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
+                state: Default::default(),
+                update_mask: vec![desired_state_path_of_workload(workload_name)],
+                dry_run: false,
+                grace_period_secs: None,
+            })),
+        })
+    }
 }
 
 impl From<&ControlInterfaceAccess> for Authorizer {
@@ -230,13 +312,22 @@ impl From<&ControlInterfaceAccess> for Authorizer {
 #[cfg(test)]
 mod test {
     use common::{
-        commands::{CompleteStateRequest, Request, UpdateStateRequest},
+        commands::{
+            AuditRequest, CompleteStateRequest, DependencyGraphRequest, EventsRequest, ExecRequest,
+            ExecStart, PauseWorkloadRequest, PortForwardRequest, PortForwardStart, Request,
+            RestartWorkloadRequest, UpdateStateRequest,
+        },
         objects::{AccessRightsRule, ControlInterfaceAccess, StateRule},
     };
 
     use super::super::authorizer::path_pattern::{AllowPathPattern, DenyPathPattern};
 
-    use super::{path::Path, path_pattern::PathPatternMatcher, Authorizer};
+    use super::{
+        path::Path,
+        path_pattern::{PathPattern, PathPatternMatcher},
+        rule::Rule,
+        Authorizer, AUDIT_FIELD_MASK, DEPENDENCY_GRAPH_FIELD_MASK, EVENTS_FIELD_MASK,
+    };
 
     const MATCHING_PATH: &str = "matching.path";
     const MATCHING_PATH_2: &str = "matching.path.2";
@@ -268,8 +359,12 @@ mod test {
         }
     }
 
-    impl<T> PathPatternMatcher for MockRule<T> {
+    impl<T: PathPattern + Clone> PathPatternMatcher for MockRule<T> {
         fn matches(&self, path: &Path) -> (bool, String) {
+            // rules created from the control interface access match like the real ones
+            if let Some(patterns) = &self.patterns {
+                return Rule::create(patterns.clone()).matches(path);
+            }
             if path.to_string() == MATCHING_PATH
                 || path.to_string() == MATCHING_PATH_2
                 || path.sections.is_empty()
@@ -307,7 +402,10 @@ mod test {
         let request = Request {
             request_id: "".into(),
             request_content: common::commands::RequestContent::CompleteStateRequest(
-                CompleteStateRequest { field_mask: vec![] },
+                CompleteStateRequest {
+                    field_mask: vec![],
+                    selector: vec![],
+                },
             ),
         };
         assert!(!authorizer.authorize(&request));
@@ -318,6 +416,8 @@ mod test {
                 UpdateStateRequest {
                     state: Default::default(),
                     update_mask: vec![],
+                    dry_run: false,
+                    grace_period_secs: None,
                 },
             )),
         };
@@ -332,7 +432,10 @@ mod test {
         let request = Request {
             request_id: "".into(),
             request_content: common::commands::RequestContent::CompleteStateRequest(
-                CompleteStateRequest { field_mask: vec![] },
+                CompleteStateRequest {
+                    field_mask: vec![],
+                    selector: vec![],
+                },
             ),
         };
         assert!(authorizer.authorize(&request));
@@ -343,6 +446,8 @@ mod test {
                 UpdateStateRequest {
                     state: Default::default(),
                     update_mask: vec![],
+                    dry_run: false,
+                    grace_period_secs: None,
                 },
             )),
         };
@@ -358,6 +463,7 @@ mod test {
             request_content: common::commands::RequestContent::CompleteStateRequest(
                 CompleteStateRequest {
                     field_mask: vec![MATCHING_PATH.into()],
+                    selector: vec![],
                 },
             ),
         };
@@ -388,6 +494,8 @@ mod test {
                 UpdateStateRequest {
                     update_mask: vec![MATCHING_PATH.into()],
                     state: Default::default(),
+                    dry_run: false,
+                    grace_period_secs: None,
                 },
             )),
         };
@@ -418,6 +526,7 @@ mod test {
             request_content: common::commands::RequestContent::CompleteStateRequest(
                 CompleteStateRequest {
                     field_mask: vec![MATCHING_PATH.into(), MATCHING_PATH_2.into()],
+                    selector: vec![],
                 },
             ),
         };
@@ -429,6 +538,8 @@ mod test {
                 UpdateStateRequest {
                     update_mask: vec![MATCHING_PATH.into(), MATCHING_PATH_2.into()],
                     state: Default::default(),
+                    dry_run: false,
+                    grace_period_secs: None,
                 },
             )),
         };
@@ -445,6 +556,7 @@ mod test {
             request_content: common::commands::RequestContent::CompleteStateRequest(
                 CompleteStateRequest {
                     field_mask: vec![MATCHING_PATH.into(), NON_MATCHING_PATH.into()],
+                    selector: vec![],
                 },
             ),
         };
@@ -456,12 +568,364 @@ mod test {
                 UpdateStateRequest {
                     update_mask: vec![MATCHING_PATH.into(), NON_MATCHING_PATH.into()],
                     state: Default::default(),
+                    dry_run: false,
+                    grace_period_secs: None,
                 },
             )),
         };
         assert!(!authorizer.authorize(&request));
     }
 
+    // [utest->swdd~agent-authorizing-dependency-graph-request~1]
+    #[test]
+    fn utest_dependency_graph_request_authorized_as_read_of_workloads_and_states() {
+        let dependency_graph_request = Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::DependencyGraphRequest(
+                DependencyGraphRequest {},
+            ),
+        };
+        let equivalent_complete_state_request = Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::CompleteStateRequest(
+                CompleteStateRequest {
+                    field_mask: DEPENDENCY_GRAPH_FIELD_MASK
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                    selector: vec![],
+                },
+            ),
+        };
+
+        for rules in [
+            vec![],
+            vec![RuleType::AllowRead],
+            vec![RuleType::AllowWrite],
+            vec![RuleType::AllowReadWrite, RuleType::DenyRead],
+        ] {
+            let authorizer = create_authorizer(&rules);
+            assert_eq!(
+                authorizer.authorize(&dependency_graph_request),
+                authorizer.authorize(&equivalent_complete_state_request)
+            );
+        }
+    }
+
+    // [utest->swdd~agent-authorizing-events-request~1]
+    #[test]
+    fn utest_events_request_authorized_as_read_of_workloads_states_and_agents() {
+        let events_request = Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::EventsRequest(EventsRequest {
+                after_sequence_number: 0,
+            }),
+        };
+        let equivalent_complete_state_request = Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::CompleteStateRequest(
+                CompleteStateRequest {
+                    field_mask: EVENTS_FIELD_MASK.iter().map(ToString::to_string).collect(),
+                    selector: vec![],
+                },
+            ),
+        };
+
+        for rules in [
+            vec![],
+            vec![RuleType::AllowRead],
+            vec![RuleType::AllowWrite],
+            vec![RuleType::AllowReadWrite, RuleType::DenyRead],
+        ] {
+            let authorizer = create_authorizer(&rules);
+            assert_eq!(
+                authorizer.authorize(&events_request),
+                authorizer.authorize(&equivalent_complete_state_request)
+            );
+        }
+    }
+
+    // [utest->swdd~agent-authorizing-audit-request~1]
+    #[test]
+    fn utest_audit_request_authorized_as_read_of_desired_state() {
+        let audit_request = Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::AuditRequest(AuditRequest {
+                after_sequence_number: 0,
+            }),
+        };
+        let equivalent_complete_state_request = Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::CompleteStateRequest(
+                CompleteStateRequest {
+                    field_mask: AUDIT_FIELD_MASK.iter().map(ToString::to_string).collect(),
+                    selector: vec![],
+                },
+            ),
+        };
+
+        for rules in [
+            vec![],
+            vec![RuleType::AllowRead],
+            vec![RuleType::AllowWrite],
+            vec![RuleType::AllowReadWrite, RuleType::DenyRead],
+        ] {
+            let authorizer = create_authorizer(&rules);
+            assert_eq!(
+                authorizer.authorize(&audit_request),
+                authorizer.authorize(&equivalent_complete_state_request)
+            );
+        }
+    }
+
+    // [utest->swdd~agent-authorizing-exec-request~1]
+    #[test]
+    fn utest_exec_request_authorized_as_write_of_workload() {
+        let exec_start_request = Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::ExecRequest(ExecRequest::Start(
+                ExecStart {
+                    workload_name: "workload_1".into(),
+                    command: vec!["sh".into()],
+                },
+            )),
+        };
+        let equivalent_update_state_request = Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::UpdateStateRequest(Box::new(
+                UpdateStateRequest {
+                    state: Default::default(),
+                    update_mask: vec!["desiredState.workloads.workload_1".into()],
+                    dry_run: false,
+                    grace_period_secs: None,
+                },
+            )),
+        };
+
+        for rules in [
+            vec![],
+            vec![RuleType::AllowRead],
+            vec![RuleType::AllowWrite],
+            vec![RuleType::AllowReadWrite, RuleType::DenyWrite],
+        ] {
+            let authorizer = create_authorizer(&rules);
+            assert_eq!(
+                authorizer.authorize(&exec_start_request),
+                authorizer.authorize(&equivalent_update_state_request)
+            );
+        }
+    }
+
+    // [utest->swdd~agent-authorizing-port-forward-request~1]
+    #[test]
+    fn utest_port_forward_request_authorized_as_write_of_workload() {
+        let port_forward_start_request = Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::PortForwardRequest(
+                PortForwardRequest::Start(PortForwardStart {
+                    workload_name: "workload_1".into(),
+                    port: 8080,
+                }),
+            ),
+        };
+        let equivalent_update_state_request = Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::UpdateStateRequest(Box::new(
+                UpdateStateRequest {
+                    state: Default::default(),
+                    update_mask: vec!["desiredState.workloads.workload_1".into()],
+                    dry_run: false,
+                    grace_period_secs: None,
+                },
+            )),
+        };
+
+        for rules in [
+            vec![],
+            vec![RuleType::AllowRead],
+            vec![RuleType::AllowWrite],
+            vec![RuleType::AllowReadWrite, RuleType::DenyWrite],
+        ] {
+            let authorizer = create_authorizer(&rules);
+            assert_eq!(
+                authorizer.authorize(&port_forward_start_request),
+                authorizer.authorize(&equivalent_update_state_request)
+            );
+        }
+    }
+
+    // [utest->swdd~agent-authorizing-restart-workload-request~1]
+    #[test]
+    fn utest_restart_workload_request_authorized_as_write_of_workload() {
+        let restart_workload_request = Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::RestartWorkloadRequest(
+                RestartWorkloadRequest {
+                    workload_name: "workload_1".into(),
+                },
+            ),
+        };
+        let equivalent_update_state_request = Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::UpdateStateRequest(Box::new(
+                UpdateStateRequest {
+                    state: Default::default(),
+                    update_mask: vec!["desiredState.workloads.workload_1".into()],
+                    dry_run: false,
+                    grace_period_secs: None,
+                },
+            )),
+        };
+
+        for rules in [
+            vec![],
+            vec![RuleType::AllowRead],
+            vec![RuleType::AllowWrite],
+            vec![RuleType::AllowReadWrite, RuleType::DenyWrite],
+        ] {
+            let authorizer = create_authorizer(&rules);
+            assert_eq!(
+                authorizer.authorize(&restart_workload_request),
+                authorizer.authorize(&equivalent_update_state_request)
+            );
+        }
+    }
+
+    // [utest->swdd~agent-authorizing-pause-workload-request~1]
+    #[test]
+    fn utest_pause_workload_request_authorized_as_write_of_workload() {
+        let pause_workload_request = Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::PauseWorkloadRequest(
+                PauseWorkloadRequest {
+                    workload_name: "workload_1".into(),
+                    resume: false,
+                },
+            ),
+        };
+        let equivalent_update_state_request = Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::UpdateStateRequest(Box::new(
+                UpdateStateRequest {
+                    state: Default::default(),
+                    update_mask: vec!["desiredState.workloads.workload_1".into()],
+                    dry_run: false,
+                    grace_period_secs: None,
+                },
+            )),
+        };
+
+        for rules in [
+            vec![],
+            vec![RuleType::AllowRead],
+            vec![RuleType::AllowWrite],
+            vec![RuleType::AllowReadWrite, RuleType::DenyWrite],
+        ] {
+            let authorizer = create_authorizer(&rules);
+            assert_eq!(
+                authorizer.authorize(&pause_workload_request),
+                authorizer.authorize(&equivalent_update_state_request)
+            );
+        }
+    }
+
+    // [utest->swdd~agent-authorizing-namespaced-workload-requests~1]
+    #[test]
+    fn utest_restart_workload_request_of_namespace_authorized_as_write_of_namespaced_workload() {
+        let restart_workload_request = Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::RestartWorkloadRequest(
+                RestartWorkloadRequest {
+                    workload_name: "team-a__workload_1".into(),
+                },
+            ),
+        };
+        let equivalent_update_state_request = Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::UpdateStateRequest(Box::new(
+                UpdateStateRequest {
+                    state: Default::default(),
+                    update_mask: vec!["desiredState.namespaces.team-a.workloads.workload_1".into()],
+                    dry_run: false,
+                    grace_period_secs: None,
+                },
+            )),
+        };
+
+        for rules in [
+            vec![],
+            vec![RuleType::AllowRead],
+            vec![RuleType::AllowWrite],
+            vec![RuleType::AllowReadWrite, RuleType::DenyWrite],
+        ] {
+            let authorizer = create_authorizer(&rules);
+            assert_eq!(
+                authorizer.authorize(&restart_workload_request),
+                authorizer.authorize(&equivalent_update_state_request)
+            );
+        }
+    }
+
+    // [utest->swdd~agent-authorizing-matching-allow-rules~1]
+    // [utest->swdd~agent-authorizing-matching-deny-rules~1]
+    #[test]
+    fn utest_deny_rule_excludes_workload_from_wildcard_allow_rule() {
+        let access_rights = ControlInterfaceAccess {
+            allow_rules: vec![AccessRightsRule::StateRule(StateRule {
+                operation: common::objects::ReadWriteEnum::ReadWrite,
+                filter_mask: vec!["desiredState.workloads.*".into()],
+            })],
+            deny_rules: vec![AccessRightsRule::StateRule(StateRule {
+                operation: common::objects::ReadWriteEnum::ReadWrite,
+                filter_mask: vec!["desiredState.workloads.secure_app".into()],
+            })],
+            pipes_owner: None,
+        };
+        let authorizer = Authorizer::from(&access_rights);
+
+        let update_state_request = |path: &str| Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::UpdateStateRequest(Box::new(
+                UpdateStateRequest {
+                    state: Default::default(),
+                    update_mask: vec![path.into()],
+                    dry_run: false,
+                    grace_period_secs: None,
+                },
+            )),
+        };
+        let complete_state_request = |path: &str| Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::CompleteStateRequest(
+                CompleteStateRequest {
+                    field_mask: vec![path.into()],
+                    selector: vec![],
+                },
+            ),
+        };
+        let restart_workload_request = |workload_name: &str| Request {
+            request_id: "".into(),
+            request_content: common::commands::RequestContent::RestartWorkloadRequest(
+                RestartWorkloadRequest {
+                    workload_name: workload_name.into(),
+                },
+            ),
+        };
+
+        assert!(authorizer.authorize(&update_state_request("desiredState.workloads.nginx")));
+        assert!(authorizer.authorize(&update_state_request("desiredState.workloads.nginx.agent")));
+        assert!(authorizer.authorize(&complete_state_request("desiredState.workloads.nginx")));
+        assert!(authorizer.authorize(&restart_workload_request("nginx")));
+
+        assert!(!authorizer.authorize(&update_state_request("desiredState.workloads.secure_app")));
+        assert!(!authorizer.authorize(&update_state_request(
+            "desiredState.workloads.secure_app.agent"
+        )));
+        assert!(!authorizer.authorize(&complete_state_request("desiredState.workloads.secure_app")));
+        assert!(!authorizer.authorize(&restart_workload_request("secure_app")));
+        assert!(!authorizer.authorize(&update_state_request("desiredState.workloads")));
+    }
+
     #[test]
     fn utest_authorizer_from_control_interface_access() {
         let access_rights = ControlInterfaceAccess {
@@ -501,6 +965,7 @@ mod test {
                     filter_mask: vec!["deny.read.write".into()],
                 }),
             ],
+            pipes_owner: None,
         };
 
         let authorizer = Authorizer::from(&access_rights);
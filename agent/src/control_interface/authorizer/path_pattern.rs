@@ -310,7 +310,7 @@ mod tests {
     // [utest->swdd~agent-authorizing-matching-deny-rules~1]
     #[test]
     fn utest_deny_path_pattern_with_wildcard_only() {
-        let p = AllowPathPattern::from("*");
+        let p = DenyPathPattern::from("*");
 
         assert!(p.matches(&"".into()).0);
         assert!(p.matches(&"some".into()).0);
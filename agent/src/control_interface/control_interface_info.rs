@@ -12,9 +12,15 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::path::{Path, PathBuf};
+use std::{
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+};
 
-use common::{objects::WorkloadInstanceName, to_server_interface::ToServerSender};
+use common::{
+    objects::{PipesOwner, WorkloadInstanceName},
+    to_server_interface::ToServerSender,
+};
 
 #[cfg(test)]
 use mockall::automock;
@@ -30,6 +36,7 @@ pub struct ControlInterfaceInfo {
     #[cfg_attr(test, allow(dead_code))]
     control_interface_to_server_sender: ToServerSender,
     authorizer: Authorizer,
+    pipes_owner: Option<PipesOwner>,
 }
 
 #[cfg_attr(test, automock)]
@@ -39,12 +46,14 @@ impl ControlInterfaceInfo {
         control_interface_to_server_sender: ToServerSender,
         workload_instance_name: &WorkloadInstanceName,
         authorizer: Authorizer,
+        pipes_owner: Option<PipesOwner>,
     ) -> Self {
         Self {
             run_folder: run_folder.to_path_buf(),
             workload_instance_name: workload_instance_name.clone(),
             control_interface_to_server_sender,
             authorizer,
+            pipes_owner,
         }
     }
 
@@ -61,12 +70,17 @@ impl ControlInterfaceInfo {
         &self.workload_instance_name
     }
 
+    #[cfg_attr(test, allow(dead_code))]
+    pub fn get_pipes_owner(&self) -> Option<PipesOwner> {
+        self.pipes_owner
+    }
+
     #[cfg_attr(test, allow(dead_code))]
     pub fn move_authorizer(self) -> Authorizer {
         self.authorizer
     }
 
-    // [impl->swdd~agent-compares-control-interface-metadata~2]
+    // [impl->swdd~agent-compares-control-interface-metadata~4]
     pub fn has_same_configuration(&self, other: &ControlInterface) -> bool {
         let self_location = self
             .workload_instance_name
@@ -76,6 +90,10 @@ impl ControlInterfaceInfo {
             return false;
         };
 
+        if !is_owned_by(&self_location, self.pipes_owner) {
+            return false;
+        }
+
         let self_authorizer = &self.authorizer;
         let other_authorizer = other.get_authorizer();
 
@@ -83,6 +101,18 @@ impl ControlInterfaceInfo {
     }
 }
 
+// The control interface only applies the owner to its pipes, thus the owner is read from the
+// existing pipes folder. Without an explicit owner, the folder is owned by the agent.
+fn is_owned_by(pipes_folder: &Path, pipes_owner: Option<PipesOwner>) -> bool {
+    let Ok(metadata) = std::fs::metadata(pipes_folder) else {
+        return false;
+    };
+    match pipes_owner {
+        Some(owner) => metadata.uid() == owner.uid && metadata.gid() == owner.gid,
+        None => metadata.uid() == nix::unistd::geteuid().as_raw(),
+    }
+}
+
 //////////////////////////////////////////////////////////////////////////////
 //                 ########  #######    #########  #########                //
 //                    ##     ##        ##             ##                    //
@@ -93,7 +123,9 @@ impl ControlInterfaceInfo {
 
 #[cfg(test)]
 mod tests {
-    use super::{ControlInterfaceInfo, Path, PathBuf, WorkloadInstanceName};
+    use std::os::unix::fs::MetadataExt;
+
+    use super::{ControlInterfaceInfo, Path, PathBuf, PipesOwner, WorkloadInstanceName};
 
     use crate::control_interface::{authorizer::MockAuthorizer, MockControlInterface};
 
@@ -102,6 +134,12 @@ mod tests {
     const WORKLOAD_1_NAME: &str = "workload1";
     const PIPES_LOCATION: &str = "/some/path";
 
+    fn create_pipes_folder(run_folder: &Path, instance_name: &WorkloadInstanceName) -> PathBuf {
+        let pipes_folder = instance_name.pipes_folder_name(run_folder);
+        std::fs::create_dir(&pipes_folder).unwrap();
+        pipes_folder
+    }
+
     #[test]
     fn utest_new() {
         let workload_instance_name = WorkloadInstanceName::builder()
@@ -113,6 +151,7 @@ mod tests {
             tokio::sync::mpsc::channel::<ToServer>(1).0,
             &workload_instance_name,
             MockAuthorizer::default(),
+            None,
         );
 
         assert_eq!(
@@ -135,6 +174,7 @@ mod tests {
                 .workload_name(WORKLOAD_1_NAME)
                 .build(),
             MockAuthorizer::default(),
+            None,
         );
 
         assert_eq!(&path.to_path_buf(), new_context_info.get_run_folder());
@@ -151,28 +191,30 @@ mod tests {
                 .workload_name(WORKLOAD_1_NAME)
                 .build(),
             MockAuthorizer::default(),
+            None,
         );
 
         assert!(to_server_sender.same_channel(&new_context_info.get_to_server_sender()));
     }
 
-    // [utest->swdd~agent-compares-control-interface-metadata~2]
+    // [utest->swdd~agent-compares-control-interface-metadata~4]
     #[test]
     fn utest_has_same_configuration_true() {
-        let run_folder = Path::new(PIPES_LOCATION);
+        let run_folder = tempfile::tempdir().unwrap();
         let workload_instance_name = WorkloadInstanceName::builder()
             .workload_name(WORKLOAD_1_NAME)
             .build();
-        let pipes_folder = workload_instance_name.pipes_folder_name(run_folder);
+        let pipes_folder = create_pipes_folder(run_folder.path(), &workload_instance_name);
         let mut context_info_authorizer = MockAuthorizer::default();
         let other_context_authorizer = MockAuthorizer::default();
         context_info_authorizer.expect_eq().return_const(true);
 
         let context_info = ControlInterfaceInfo::new(
-            run_folder,
+            run_folder.path(),
             tokio::sync::mpsc::channel::<ToServer>(1).0,
             &workload_instance_name,
             context_info_authorizer,
+            None,
         );
 
         let mut other_context = MockControlInterface::default();
@@ -188,7 +230,7 @@ mod tests {
         assert!(context_info.has_same_configuration(&other_context));
     }
 
-    // [utest->swdd~agent-compares-control-interface-metadata~2]
+    // [utest->swdd~agent-compares-control-interface-metadata~4]
     #[test]
     fn utest_has_same_configuration_with_different_location_returns_false() {
         let run_folder = Path::new(PIPES_LOCATION);
@@ -201,6 +243,7 @@ mod tests {
             tokio::sync::mpsc::channel::<ToServer>(1).0,
             &workload_instance_name,
             MockAuthorizer::default(),
+            None,
         );
 
         let mut other_context = MockControlInterface::default();
@@ -212,23 +255,24 @@ mod tests {
         assert!(!context_info.has_same_configuration(&other_context));
     }
 
-    // [utest->swdd~agent-compares-control-interface-metadata~2]
+    // [utest->swdd~agent-compares-control-interface-metadata~4]
     #[test]
     fn utest_has_same_configuration_with_different_authorizer_returns_false() {
-        let run_folder = Path::new(PIPES_LOCATION);
+        let run_folder = tempfile::tempdir().unwrap();
         let workload_instance_name = WorkloadInstanceName::builder()
             .workload_name(WORKLOAD_1_NAME)
             .build();
-        let pipes_folder = workload_instance_name.pipes_folder_name(run_folder);
+        let pipes_folder = create_pipes_folder(run_folder.path(), &workload_instance_name);
         let mut context_info_authorizer = MockAuthorizer::default();
         let other_context_authorizer = MockAuthorizer::default();
         context_info_authorizer.expect_eq().return_const(false);
 
         let context_info = ControlInterfaceInfo::new(
-            run_folder,
+            run_folder.path(),
             tokio::sync::mpsc::channel::<ToServer>(1).0,
             &workload_instance_name,
             context_info_authorizer,
+            None,
         );
 
         let mut other_context = MockControlInterface::default();
@@ -243,4 +287,70 @@ mod tests {
 
         assert!(!context_info.has_same_configuration(&other_context));
     }
+
+    // [utest->swdd~agent-compares-control-interface-metadata~4]
+    #[test]
+    fn utest_has_same_configuration_with_different_pipes_owner_returns_false() {
+        let run_folder = tempfile::tempdir().unwrap();
+        let workload_instance_name = WorkloadInstanceName::builder()
+            .workload_name(WORKLOAD_1_NAME)
+            .build();
+        let pipes_folder = create_pipes_folder(run_folder.path(), &workload_instance_name);
+        let folder_metadata = std::fs::metadata(&pipes_folder).unwrap();
+
+        let context_info = ControlInterfaceInfo::new(
+            run_folder.path(),
+            tokio::sync::mpsc::channel::<ToServer>(1).0,
+            &workload_instance_name,
+            MockAuthorizer::default(),
+            Some(PipesOwner {
+                uid: folder_metadata.uid() + 1,
+                gid: folder_metadata.gid(),
+            }),
+        );
+
+        let mut other_context = MockControlInterface::default();
+        other_context
+            .expect_get_api_location()
+            .once()
+            .return_const(pipes_folder);
+
+        assert!(!context_info.has_same_configuration(&other_context));
+    }
+
+    // [utest->swdd~agent-compares-control-interface-metadata~4]
+    #[test]
+    fn utest_has_same_configuration_with_requested_pipes_owner_true() {
+        let run_folder = tempfile::tempdir().unwrap();
+        let workload_instance_name = WorkloadInstanceName::builder()
+            .workload_name(WORKLOAD_1_NAME)
+            .build();
+        let pipes_folder = create_pipes_folder(run_folder.path(), &workload_instance_name);
+        let folder_metadata = std::fs::metadata(&pipes_folder).unwrap();
+        let mut context_info_authorizer = MockAuthorizer::default();
+        context_info_authorizer.expect_eq().return_const(true);
+
+        let context_info = ControlInterfaceInfo::new(
+            run_folder.path(),
+            tokio::sync::mpsc::channel::<ToServer>(1).0,
+            &workload_instance_name,
+            context_info_authorizer,
+            Some(PipesOwner {
+                uid: folder_metadata.uid(),
+                gid: folder_metadata.gid(),
+            }),
+        );
+
+        let mut other_context = MockControlInterface::default();
+        other_context
+            .expect_get_api_location()
+            .once()
+            .return_const(pipes_folder);
+        other_context
+            .expect_get_authorizer()
+            .once()
+            .return_const(MockAuthorizer::default());
+
+        assert!(context_info.has_same_configuration(&other_context));
+    }
 }
@@ -15,6 +15,7 @@
 use std::sync::Arc;
 
 use crate::control_interface::{to_ankaios, ToAnkaios};
+use crate::metrics::agent_metrics;
 
 #[cfg_attr(test, mockall_double::double)]
 use super::authorizer::Authorizer;
@@ -95,6 +96,9 @@ impl ControlInterfaceTask {
             return;
         }
 
+        // [impl->swdd~agent-collects-metrics~1]
+        let _connection = agent_metrics().control_interface_connected();
+
         loop {
             select! {
                 // [impl->swdd~agent-ensures-control-interface-output-pipe-read~1]
@@ -250,6 +254,7 @@ mod tests {
             request_content: Some(ank_base::request::RequestContent::CompleteStateRequest(
                 ank_base::CompleteStateRequest {
                     field_mask: vec![field_mask.into()],
+                    selector: vec![],
                 },
             )),
         };
@@ -269,7 +274,7 @@ mod tests {
         let response = ank_base::Response {
             request_id: REQUEST_ID.into(),
             response_content: Some(ank_base::response::ResponseContent::CompleteState(
-                Default::default(),
+                Box::default(),
             )),
         };
 
@@ -323,7 +328,10 @@ mod tests {
                 ank_base::Request {
                     request_id: REQUEST_ID.into(),
                     request_content: Some(ank_base::request::RequestContent::CompleteStateRequest(
-                        ank_base::CompleteStateRequest { field_mask: vec![] },
+                        ank_base::CompleteStateRequest {
+                            field_mask: vec![],
+                            selector: vec![],
+                        },
                     )),
                 },
             )),
@@ -352,7 +360,7 @@ mod tests {
             .expect_read_protobuf_data()
             .once()
             .in_sequence(&mut mockall_seq)
-            .returning(move || Err(Error::new(std::io::ErrorKind::Other, "error")));
+            .returning(move || Err(Error::other("error")));
 
         let error = ank_base::Response {
             request_id: REQUEST_ID.into(),
@@ -413,6 +421,7 @@ mod tests {
             request_content: Some(ank_base::request::RequestContent::CompleteStateRequest(
                 ank_base::CompleteStateRequest {
                     field_mask: vec!["desiredState.workloads.nginx".to_string()],
+                    selector: vec![],
                 },
             )),
         };
@@ -445,7 +454,7 @@ mod tests {
             .expect_read_protobuf_data()
             .once()
             .in_sequence(&mut mockall_seq)
-            .returning(move || Err(Error::new(std::io::ErrorKind::Other, "error")));
+            .returning(move || Err(Error::other("error")));
 
         let output_stream_mock = MockReopenFile::default();
 
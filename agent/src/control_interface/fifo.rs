@@ -18,6 +18,9 @@ use std::path::PathBuf;
 use crate::io_utils::filesystem;
 use crate::io_utils::FileSystemError;
 
+// Only the owner is allowed to read and write the fifo
+const OWNER_ONLY_FIFO_MODE: u32 = 0o600;
+
 #[derive(Debug)]
 pub struct Fifo {
     path: PathBuf,
@@ -38,6 +41,12 @@ impl Fifo {
     pub fn get_path(&self) -> &PathBuf {
         &self.path
     }
+
+    // [impl->swdd~agent-control-interface-pipes-owned-by-workload-user~1]
+    pub fn restrict_to_owner(&self, uid: u32, gid: u32) -> Result<(), FileSystemError> {
+        filesystem::set_owner(&self.path, uid, gid)?;
+        filesystem::set_permissions(&self.path, OWNER_ONLY_FIFO_MODE)
+    }
 }
 
 impl Drop for Fifo {
@@ -53,6 +62,7 @@ mockall::mock! {
     pub Fifo {
         pub fn new(path: PathBuf) -> Result<Self, FileSystemError>;
         pub fn get_path(&self) -> &PathBuf;
+        pub fn restrict_to_owner(&self, uid: u32, gid: u32) -> Result<(), FileSystemError>;
     }
     impl Drop for Fifo {
         fn drop(&mut self);
@@ -233,4 +243,67 @@ mod tests {
             fifo.unwrap().get_path()
         );
     }
+
+    // [utest->swdd~agent-control-interface-pipes-owned-by-workload-user~1]
+    #[test]
+    fn utest_fifo_restrict_to_owner_sets_owner_and_mode() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC.get_lock();
+
+        let is_fifo_context = mock_filesystem::is_fifo_context();
+        is_fifo_context.expect().return_const(true);
+
+        let set_owner_context = mock_filesystem::set_owner_context();
+        set_owner_context
+            .expect()
+            .with(
+                predicate::eq(Path::new("test_fifo").to_path_buf()),
+                predicate::eq(1000),
+                predicate::eq(1001),
+            )
+            .once()
+            .return_once(|_, _, _| Ok(()));
+        let set_permissions_context = mock_filesystem::set_permissions_context();
+        set_permissions_context
+            .expect()
+            .with(
+                predicate::eq(Path::new("test_fifo").to_path_buf()),
+                predicate::eq(0o600),
+            )
+            .once()
+            .return_once(|_, _| Ok(()));
+
+        let rm_fifo_context = mock_filesystem::remove_fifo_context();
+        rm_fifo_context.expect().return_once(|_| Ok(()));
+
+        let fifo = Fifo::new(Path::new("test_fifo").to_path_buf()).unwrap();
+        assert!(fifo.restrict_to_owner(1000, 1001).is_ok());
+    }
+
+    // [utest->swdd~agent-control-interface-pipes-owned-by-workload-user~1]
+    #[test]
+    fn utest_fifo_restrict_to_owner_fails_if_owner_cannot_be_set() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC.get_lock();
+
+        let is_fifo_context = mock_filesystem::is_fifo_context();
+        is_fifo_context.expect().return_const(true);
+
+        let set_owner_context = mock_filesystem::set_owner_context();
+        set_owner_context.expect().return_once(|path, _, _| {
+            Err(FileSystemError::Owner(
+                path.to_path_buf().into_os_string(),
+                nix::errno::Errno::EPERM,
+            ))
+        });
+        let set_permissions_context = mock_filesystem::set_permissions_context();
+        set_permissions_context.expect().never();
+
+        let rm_fifo_context = mock_filesystem::remove_fifo_context();
+        rm_fifo_context.expect().return_once(|_| Ok(()));
+
+        let fifo = Fifo::new(Path::new("test_fifo").to_path_buf()).unwrap();
+        assert!(matches!(
+            fifo.restrict_to_owner(1000, 1001),
+            Err(FileSystemError::Owner(_, nix::errno::Errno::EPERM))
+        ));
+    }
 }
@@ -14,6 +14,8 @@
 
 use std::path::PathBuf;
 
+use common::objects::PipesOwner;
+
 #[cfg_attr(test, mockall_double::double)]
 use super::fifo::Fifo;
 #[cfg_attr(test, mockall_double::double)]
@@ -29,12 +31,18 @@ pub struct InputOutput {
 #[cfg_attr(test, mockall::automock)]
 impl InputOutput {
     // [impl->swdd~agent-control-interface-creates-two-pipes-per-workload~1]
-    pub fn new(path: PathBuf) -> Result<Self, FileSystemError> {
+    pub fn new(path: PathBuf, pipes_owner: Option<PipesOwner>) -> Result<Self, FileSystemError> {
         let input_path = path.join(String::from("input"));
         let output_path = path.join(String::from("output"));
         let base_dir = Directory::new(path)?;
         let input = Fifo::new(input_path)?;
         let output = Fifo::new(output_path)?;
+        // [impl->swdd~agent-control-interface-pipes-owned-by-workload-user~1]
+        if let Some(owner) = pipes_owner {
+            base_dir.restrict_to_owner(owner.uid, owner.gid)?;
+            input.restrict_to_owner(owner.uid, owner.gid)?;
+            output.restrict_to_owner(owner.uid, owner.gid)?;
+        }
         Ok(Self {
             input,
             output,
@@ -68,7 +76,7 @@ pub fn generate_test_input_output_mock() -> __mock_MockInputOutput::__new::Conte
 
     let input_output_mock = MockInputOutput::new_context();
 
-    input_output_mock.expect().return_once(|path| {
+    input_output_mock.expect().return_once(|path, _| {
         let mut mock = MockInputOutput::default();
         mock.expect_get_output().return_const({
             let mut output_fifo_mock = MockFifo::default();
@@ -97,11 +105,12 @@ pub fn generate_test_input_output_mock() -> __mock_MockInputOutput::__new::Conte
 #[cfg(test)]
 mod tests {
     use super::InputOutput;
+    use common::objects::PipesOwner;
     use mockall::predicate;
     use std::path::Path;
 
     use crate::control_interface::MockFifo;
-    use crate::io_utils::generate_test_directory_mock;
+    use crate::io_utils::{generate_test_directory_mock, MockDirectory};
 
     // [utest->swdd~agent-control-interface-creates-two-pipes-per-workload~1]
     #[test]
@@ -136,7 +145,7 @@ mod tests {
                 Ok(output_mock)
             });
 
-        let io = InputOutput::new(Path::new("test_path").join("workload_name"));
+        let io = InputOutput::new(Path::new("test_path").join("workload_name"), None);
         assert!(io.is_ok());
         assert_eq!(
             &Path::new("test_path").join("workload_name").join("input"),
@@ -151,4 +160,41 @@ mod tests {
             io.as_ref().unwrap().get_location()
         );
     }
+
+    // [utest->swdd~agent-control-interface-pipes-owned-by-workload-user~1]
+    #[test]
+    fn utest_input_output_new_restricts_pipes_to_owner() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC.get_lock();
+        let directory_mock_context = MockDirectory::new_context();
+        directory_mock_context.expect().return_once(|path| {
+            let mut mock = MockDirectory::default();
+            mock.expect_get_path().return_const(path);
+            mock.expect_restrict_to_owner()
+                .with(predicate::eq(1000), predicate::eq(1001))
+                .once()
+                .return_once(|_, _| Ok(()));
+            mock.expect_drop().return_const(());
+            Ok(mock)
+        });
+        let fifo_mock_context = MockFifo::new_context();
+        fifo_mock_context.expect().times(2).returning(|path| {
+            let mut mock = MockFifo::default();
+            mock.expect_get_path().return_const(path);
+            mock.expect_restrict_to_owner()
+                .with(predicate::eq(1000), predicate::eq(1001))
+                .once()
+                .return_once(|_, _| Ok(()));
+            mock.expect_drop().return_const(());
+            Ok(mock)
+        });
+
+        let io = InputOutput::new(
+            Path::new("test_path").join("workload_name"),
+            Some(PipesOwner {
+                uid: 1000,
+                gid: 1001,
+            }),
+        );
+        assert!(io.is_ok());
+    }
 }
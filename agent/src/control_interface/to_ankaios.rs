@@ -94,6 +94,7 @@ mod tests {
                     request_content: Some(ank_base::request::RequestContent::CompleteStateRequest(
                         ank_base::CompleteStateRequest {
                             field_mask: vec![FIELD_1.into(), FIELD_2.into()],
+                            selector: vec![],
                         },
                     )),
                 },
@@ -104,6 +105,7 @@ mod tests {
             request_id: REQUEST_ID.into(),
             request_content: RequestContent::CompleteStateRequest(CompleteStateRequest {
                 field_mask: vec![FIELD_1.into(), FIELD_2.into()],
+                selector: vec![],
             }),
         });
 
@@ -0,0 +1,96 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+// The pressure is only considered as subsided if the free memory is this factor above the threshold.
+// This avoids evicting and restoring the same workload on every measurement.
+const RESTORE_THRESHOLD_FACTOR: u64 = 2;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EvictionDecision {
+    EvictLowestPriority,
+    RestoreHighestPriority,
+}
+
+// [impl->swdd~agent-eviction-controller-decides-on-free-memory~1]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EvictionController {
+    memory_threshold: u64,
+}
+
+impl EvictionController {
+    pub fn new(memory_threshold: u64) -> Self {
+        EvictionController { memory_threshold }
+    }
+
+    // [impl->swdd~agent-eviction-controller-decides-on-free-memory~1]
+    pub fn decide(&self, free_memory: u64) -> Option<EvictionDecision> {
+        if free_memory < self.memory_threshold {
+            Some(EvictionDecision::EvictLowestPriority)
+        } else if free_memory
+            >= self
+                .memory_threshold
+                .saturating_mul(RESTORE_THRESHOLD_FACTOR)
+        {
+            Some(EvictionDecision::RestoreHighestPriority)
+        } else {
+            None
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{EvictionController, EvictionDecision};
+
+    const MEMORY_THRESHOLD: u64 = 1000;
+
+    // [utest->swdd~agent-eviction-controller-decides-on-free-memory~1]
+    #[test]
+    fn utest_eviction_controller_evicts_below_threshold() {
+        let eviction_controller = EvictionController::new(MEMORY_THRESHOLD);
+
+        assert_eq!(
+            eviction_controller.decide(MEMORY_THRESHOLD - 1),
+            Some(EvictionDecision::EvictLowestPriority)
+        );
+    }
+
+    // [utest->swdd~agent-eviction-controller-decides-on-free-memory~1]
+    #[test]
+    fn utest_eviction_controller_keeps_workloads_between_thresholds() {
+        let eviction_controller = EvictionController::new(MEMORY_THRESHOLD);
+
+        assert_eq!(eviction_controller.decide(MEMORY_THRESHOLD), None);
+        assert_eq!(eviction_controller.decide(2 * MEMORY_THRESHOLD - 1), None);
+    }
+
+    // [utest->swdd~agent-eviction-controller-decides-on-free-memory~1]
+    #[test]
+    fn utest_eviction_controller_restores_when_pressure_subsided() {
+        let eviction_controller = EvictionController::new(MEMORY_THRESHOLD);
+
+        assert_eq!(
+            eviction_controller.decide(2 * MEMORY_THRESHOLD),
+            Some(EvictionDecision::RestoreHighestPriority)
+        );
+    }
+}
@@ -0,0 +1,237 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use api::ank_base::{self, exec_output::ExecOutputEnum, response::ResponseContent, ExecOutput};
+use common::{
+    commands::ExecInput,
+    to_server_interface::{ToServerInterface, ToServerSender},
+};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWriteExt},
+    process::{Child, ChildStdin},
+    sync::mpsc,
+    task::JoinHandle,
+};
+
+const INPUT_BUFFER_SIZE: usize = 20;
+const OUTPUT_CHUNK_SIZE: usize = 4096;
+
+pub fn exec_response(request_id: String, exec_output_enum: ExecOutputEnum) -> ank_base::Response {
+    ank_base::Response {
+        request_id,
+        response_content: Some(ResponseContent::ExecOutput(ExecOutput {
+            exec_output_enum: Some(exec_output_enum),
+        })),
+    }
+}
+
+pub fn error_response(request_id: String, message: String) -> ank_base::Response {
+    ank_base::Response {
+        request_id,
+        response_content: Some(ResponseContent::Error(ank_base::Error { message })),
+    }
+}
+
+// An exec session forwards the input received from the server to the process started
+// in the workload and sends the output of the process back to the server.
+pub struct ExecSession {
+    input_tx: mpsc::Sender<ExecInput>,
+    task_handle: JoinHandle<()>,
+}
+
+impl ExecSession {
+    // [impl->swdd~agent-forwards-exec-session-io~1]
+    pub fn start(request_id: String, mut child: Child, to_server: ToServerSender) -> Self {
+        let (input_tx, input_rx) = mpsc::channel(INPUT_BUFFER_SIZE);
+        let stdin = child.stdin.take();
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+
+        let task_handle = tokio::spawn(async move {
+            let stdin_task = tokio::spawn(forward_input(stdin, input_rx));
+
+            tokio::join!(
+                forward_output(stdout, &request_id, &to_server, ExecOutputEnum::Stdout),
+                forward_output(stderr, &request_id, &to_server, ExecOutputEnum::Stderr),
+            );
+
+            let response = match child.wait().await {
+                Ok(status) => exec_response(
+                    request_id.clone(),
+                    // a process terminated by a signal has no exit code
+                    ExecOutputEnum::ExitCode(status.code().unwrap_or(-1)),
+                ),
+                Err(err) => error_response(
+                    request_id.clone(),
+                    format!("Could not get the exit code of the exec session: '{}'", err),
+                ),
+            };
+            stdin_task.abort();
+
+            if let Err(err) = to_server.response(response).await {
+                log::warn!(
+                    "Could not send the end of exec session '{}': '{}'",
+                    request_id,
+                    err
+                );
+            }
+        });
+
+        ExecSession {
+            input_tx,
+            task_handle,
+        }
+    }
+
+    // [impl->swdd~agent-forwards-exec-session-io~1]
+    pub async fn send_input(&self, exec_input: ExecInput) -> Result<(), String> {
+        self.input_tx
+            .send(exec_input)
+            .await
+            .map_err(|_| "The exec session has already ended".to_string())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.task_handle.is_finished()
+    }
+}
+
+async fn forward_input(mut stdin: Option<ChildStdin>, mut input_rx: mpsc::Receiver<ExecInput>) {
+    while let Some(exec_input) = input_rx.recv().await {
+        if let Some(stdin_pipe) = stdin.as_mut() {
+            if !exec_input.stdin.is_empty()
+                && stdin_pipe.write_all(&exec_input.stdin).await.is_err()
+            {
+                log::debug!("The stdin of the exec session is closed.");
+                stdin = None;
+            }
+        }
+        if exec_input.close_stdin {
+            // dropping the pipe signals EOF to the process
+            stdin = None;
+        }
+    }
+}
+
+async fn forward_output(
+    output: Option<impl AsyncRead + Unpin>,
+    request_id: &str,
+    to_server: &ToServerSender,
+    into_exec_output: fn(Vec<u8>) -> ExecOutputEnum,
+) {
+    let Some(mut output) = output else {
+        return;
+    };
+    let mut buffer = vec![0; OUTPUT_CHUNK_SIZE];
+    loop {
+        match output.read(&mut buffer).await {
+            Ok(0) => break,
+            Ok(read_bytes) => {
+                let response = exec_response(
+                    request_id.to_owned(),
+                    into_exec_output(buffer[..read_bytes].to_vec()),
+                );
+                if to_server.response(response).await.is_err() {
+                    log::warn!(
+                        "Could not forward the output of exec session '{}'",
+                        request_id
+                    );
+                    break;
+                }
+            }
+            Err(err) => {
+                log::warn!(
+                    "Could not read the output of exec session '{}': '{}'",
+                    request_id,
+                    err
+                );
+                break;
+            }
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{exec_response, ExecSession};
+    use api::ank_base::exec_output::ExecOutputEnum;
+    use common::{commands::ExecInput, to_server_interface::ToServer};
+    use std::process::Stdio;
+    use tokio::sync::mpsc::channel;
+
+    const REQUEST_ID: &str = "cli@request_id";
+
+    // [utest->swdd~agent-forwards-exec-session-io~1]
+    #[tokio::test]
+    async fn utest_exec_session_forwards_input_and_output() {
+        let (to_server, mut server_receiver) = channel(20);
+        let child = tokio::process::Command::new("sh")
+            .args(["-c", "cat; exit 3"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .unwrap();
+
+        let exec_session = ExecSession::start(REQUEST_ID.into(), child, to_server);
+        exec_session
+            .send_input(ExecInput {
+                stdin: b"hello".to_vec(),
+                close_stdin: true,
+            })
+            .await
+            .unwrap();
+
+        let mut stdout = Vec::new();
+        let exit_code = loop {
+            let Some(ToServer::Response(response)) = server_receiver.recv().await else {
+                panic!("Expected an exec output response");
+            };
+            assert_eq!(response.request_id, REQUEST_ID);
+            match response.response_content {
+                Some(api::ank_base::response::ResponseContent::ExecOutput(output)) => {
+                    match output.exec_output_enum.unwrap() {
+                        ExecOutputEnum::Stdout(data) => stdout.extend(data),
+                        ExecOutputEnum::Stderr(_) => panic!("Unexpected stderr output"),
+                        ExecOutputEnum::ExitCode(exit_code) => break exit_code,
+                    }
+                }
+                other => panic!("Unexpected response content: {:?}", other),
+            }
+        };
+
+        assert_eq!(stdout, b"hello");
+        assert_eq!(exit_code, 3);
+    }
+
+    #[test]
+    fn utest_exec_response_contains_output() {
+        let response = exec_response(REQUEST_ID.into(), ExecOutputEnum::ExitCode(0));
+
+        assert_eq!(response.request_id, REQUEST_ID);
+        assert!(matches!(
+            response.response_content,
+            Some(api::ank_base::response::ResponseContent::ExecOutput(_))
+        ));
+    }
+}
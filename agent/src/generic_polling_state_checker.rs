@@ -14,16 +14,146 @@
 
 use async_trait::async_trait;
 use std::{str::FromStr, time::Duration};
-use tokio::{task::JoinHandle, time};
+use tokio::{
+    task::JoinHandle,
+    time::{self, Instant},
+};
 
 use crate::{
+    health_check_runner::HealthCheckRunner,
     runtime_connectors::{RuntimeStateGetter, StateChecker},
     workload_state::{WorkloadStateSender, WorkloadStateSenderInterface},
 };
-use common::objects::{ExecutionState, ExecutionStateEnum, WorkloadSpec};
+use common::objects::{ExecutionState, ExecutionStateEnum, ResourceThresholds, WorkloadSpec};
 
 // [impl->swdd~agent-provides-generic-state-checker-implementation~1]
 const STATUS_CHECK_INTERVAL_MS: u64 = 500;
+// [impl->swdd~generic-state-checker-reports-resource-warning~1]
+const SUSTAINED_RESOURCE_BREACH_CHECKS: u32 = 4;
+
+struct ResourceThresholdMonitor {
+    thresholds: ResourceThresholds,
+    consecutive_breaches: u32,
+}
+
+impl ResourceThresholdMonitor {
+    fn new(thresholds: ResourceThresholds) -> Self {
+        ResourceThresholdMonitor {
+            thresholds,
+            consecutive_breaches: 0,
+        }
+    }
+
+    // [impl->swdd~generic-state-checker-reports-resource-warning~1]
+    async fn annotate<WorkloadId>(
+        &mut self,
+        state: ExecutionState,
+        state_getter: &impl RuntimeStateGetter<WorkloadId>,
+        workload_id: &WorkloadId,
+    ) -> ExecutionState
+    where
+        WorkloadId: ToString + FromStr + Clone + Send + Sync + 'static,
+    {
+        if !state.is_running() {
+            self.consecutive_breaches = 0;
+            return state;
+        }
+
+        let breaches = state_getter
+            .get_resource_usage(workload_id)
+            .await
+            .and_then(|usage| {
+                self.thresholds
+                    .breaches(usage.cpu_usage, usage.memory_usage)
+            });
+
+        match breaches {
+            Some(details) => {
+                self.consecutive_breaches = self.consecutive_breaches.saturating_add(1);
+                if self.consecutive_breaches >= SUSTAINED_RESOURCE_BREACH_CHECKS {
+                    return ExecutionState::running_resource_warning(details);
+                }
+            }
+            None => self.consecutive_breaches = 0,
+        }
+        state
+    }
+}
+
+// Delays the report of a changed state until it was stable for the minimum stable duration.
+// Terminal states are reported immediately. The changes not reported as they did not last
+// long enough are counted and added to the additional info of the next reported state.
+// [impl->swdd~generic-state-checker-debounces-state-changes~1]
+struct StateDebouncer {
+    min_stable_duration: Duration,
+    reported_state: Option<ExecutionState>,
+    pending_state: Option<(ExecutionState, Instant)>,
+    suppressed_changes: u32,
+}
+
+impl StateDebouncer {
+    fn new(min_stable_duration: Duration) -> Self {
+        StateDebouncer {
+            min_stable_duration,
+            reported_state: None,
+            pending_state: None,
+            suppressed_changes: 0,
+        }
+    }
+
+    // Returns the state to report, if any
+    fn debounce(&mut self, state: ExecutionState, now: Instant) -> Option<ExecutionState> {
+        // the first state is reported immediately so that the start of the workload is not delayed
+        if self.reported_state.is_none() || is_terminal(&state) {
+            return Some(self.report(state));
+        }
+
+        if let Some((pending_state, pending_since)) = &self.pending_state {
+            if *pending_state == state {
+                if now.duration_since(*pending_since) >= self.min_stable_duration {
+                    return Some(self.report(state));
+                }
+                return None;
+            }
+            self.suppressed_changes = self.suppressed_changes.saturating_add(1);
+        }
+
+        // returning to the reported state after suppressed changes is reported again to show the flapping
+        if self.reported_state.as_ref() == Some(&state) && self.suppressed_changes == 0 {
+            self.pending_state = None;
+        } else {
+            self.pending_state = Some((state, now));
+        }
+        None
+    }
+
+    fn report(&mut self, mut state: ExecutionState) -> ExecutionState {
+        self.pending_state = None;
+        self.reported_state = Some(state.clone());
+        if self.suppressed_changes > 0 {
+            let flapping = format!(
+                "{} short-lived state changes suppressed",
+                self.suppressed_changes
+            );
+            state.additional_info = if state.additional_info.is_empty() {
+                flapping
+            } else {
+                format!("{}; {}", state.additional_info, flapping)
+            };
+            self.suppressed_changes = 0;
+        }
+        state
+    }
+}
+
+fn is_terminal(state: &ExecutionState) -> bool {
+    matches!(
+        state.state,
+        ExecutionStateEnum::Succeeded(_)
+            | ExecutionStateEnum::Failed(_)
+            | ExecutionStateEnum::Removed
+    )
+}
 
 #[derive(Debug)]
 pub struct GenericPollingStateChecker {
@@ -48,9 +178,44 @@ where
         let task_handle = tokio::spawn(async move {
             let mut last_state = ExecutionState::unknown("Never received an execution state.");
             let mut interval = time::interval(Duration::from_millis(STATUS_CHECK_INTERVAL_MS));
+            let mut resource_monitor = workload_spec
+                .resource_thresholds
+                .clone()
+                .map(ResourceThresholdMonitor::new);
+            let mut health_check_runner = workload_spec
+                .health_check
+                .clone()
+                .map(HealthCheckRunner::new);
+            let mut state_debouncer = workload_spec
+                .state_debounce
+                .filter(|state_debounce| *state_debounce > 0)
+                .map(|state_debounce| {
+                    StateDebouncer::new(Duration::from_millis(state_debounce.into()))
+                });
             loop {
                 interval.tick().await;
-                let current_state = state_getter.get_state(&workload_id).await;
+                let mut current_state = state_getter.get_state(&workload_id).await;
+
+                if let Some(resource_monitor) = resource_monitor.as_mut() {
+                    current_state = resource_monitor
+                        .annotate(current_state, &state_getter, &workload_id)
+                        .await;
+                }
+
+                // [impl->swdd~generic-state-checker-reports-unhealthy~1]
+                if let Some(health_check_runner) = health_check_runner.as_mut() {
+                    current_state = health_check_runner
+                        .annotate(current_state, &state_getter, &workload_id)
+                        .await;
+                }
+
+                // [impl->swdd~generic-state-checker-debounces-state-changes~1]
+                if let Some(state_debouncer) = state_debouncer.as_mut() {
+                    match state_debouncer.debounce(current_state, Instant::now()) {
+                        Some(debounced_state) => current_state = debounced_state,
+                        None => continue,
+                    }
+                }
 
                 if current_state != last_state {
                     log::debug!(
@@ -105,11 +270,18 @@ impl Drop for GenericPollingStateChecker {
 mod tests {
     use std::time::Duration;
 
-    use common::{objects::generate_test_workload_spec_with_param, objects::ExecutionState};
+    use common::{
+        objects::generate_test_workload_spec_with_param,
+        objects::{ExecutionState, ResourceThresholds},
+    };
+    use tokio::time::Instant;
 
     use crate::{
-        generic_polling_state_checker::GenericPollingStateChecker,
-        runtime_connectors::{MockRuntimeStateGetter, StateChecker},
+        generic_polling_state_checker::{
+            GenericPollingStateChecker, ResourceThresholdMonitor, StateDebouncer,
+            SUSTAINED_RESOURCE_BREACH_CHECKS,
+        },
+        runtime_connectors::{MockRuntimeStateGetter, StateChecker, WorkloadResourceUsage},
     };
 
     const RUNTIME_NAME: &str = "runtime1";
@@ -162,4 +334,201 @@ mod tests {
         let state_update_1 = state_receiver.recv().await.unwrap();
         assert_eq!(state_update_1, expected_state);
     }
+
+    const MEMORY_THRESHOLD: u64 = 1024;
+
+    fn mock_runtime_getter_with_memory_usage(memory_usage: u64) -> MockRuntimeStateGetter<String> {
+        let mut mock_runtime_getter = MockRuntimeStateGetter::default();
+        mock_runtime_getter
+            .expect_get_resource_usage()
+            .returning(move |_: &String| {
+                Box::pin(async move {
+                    Some(WorkloadResourceUsage {
+                        cpu_usage: 10.0,
+                        memory_usage,
+                    })
+                })
+            });
+        mock_runtime_getter
+    }
+
+    fn memory_threshold_monitor() -> ResourceThresholdMonitor {
+        ResourceThresholdMonitor::new(ResourceThresholds {
+            cpu_usage: None,
+            memory_usage: Some(MEMORY_THRESHOLD),
+        })
+    }
+
+    // [utest->swdd~generic-state-checker-reports-resource-warning~1]
+    #[tokio::test]
+    async fn utest_resource_threshold_monitor_reports_sustained_breach() {
+        let mock_runtime_getter = mock_runtime_getter_with_memory_usage(MEMORY_THRESHOLD + 1);
+        let mut resource_monitor = memory_threshold_monitor();
+
+        for _ in 1..SUSTAINED_RESOURCE_BREACH_CHECKS {
+            assert_eq!(
+                resource_monitor
+                    .annotate(
+                        ExecutionState::running(),
+                        &mock_runtime_getter,
+                        &WORKLOAD_ID.to_string()
+                    )
+                    .await,
+                ExecutionState::running()
+            );
+        }
+
+        assert_eq!(
+            resource_monitor
+                .annotate(
+                    ExecutionState::running(),
+                    &mock_runtime_getter,
+                    &WORKLOAD_ID.to_string()
+                )
+                .await,
+            ExecutionState::running_resource_warning(format!(
+                "memory usage above threshold of {MEMORY_THRESHOLD}B"
+            ))
+        );
+    }
+
+    // [utest->swdd~generic-state-checker-reports-resource-warning~1]
+    #[tokio::test]
+    async fn utest_resource_threshold_monitor_resets_on_recovery() {
+        let mut resource_monitor = memory_threshold_monitor();
+        resource_monitor.consecutive_breaches = SUSTAINED_RESOURCE_BREACH_CHECKS;
+
+        let mock_runtime_getter = mock_runtime_getter_with_memory_usage(MEMORY_THRESHOLD);
+
+        assert_eq!(
+            resource_monitor
+                .annotate(
+                    ExecutionState::running(),
+                    &mock_runtime_getter,
+                    &WORKLOAD_ID.to_string()
+                )
+                .await,
+            ExecutionState::running()
+        );
+        assert_eq!(resource_monitor.consecutive_breaches, 0);
+    }
+
+    // [utest->swdd~generic-state-checker-reports-resource-warning~1]
+    #[tokio::test]
+    async fn utest_resource_threshold_monitor_ignores_workloads_not_running() {
+        let mut mock_runtime_getter = MockRuntimeStateGetter::default();
+        mock_runtime_getter.expect_get_resource_usage().never();
+
+        let mut resource_monitor = memory_threshold_monitor();
+        resource_monitor.consecutive_breaches = SUSTAINED_RESOURCE_BREACH_CHECKS;
+
+        assert_eq!(
+            resource_monitor
+                .annotate(
+                    ExecutionState::succeeded(),
+                    &mock_runtime_getter,
+                    &WORKLOAD_ID.to_string()
+                )
+                .await,
+            ExecutionState::succeeded()
+        );
+        assert_eq!(resource_monitor.consecutive_breaches, 0);
+    }
+
+    const MIN_STABLE_DURATION: Duration = Duration::from_millis(2000);
+
+    // [utest->swdd~generic-state-checker-debounces-state-changes~1]
+    #[test]
+    fn utest_state_debouncer_reports_stable_state_changes() {
+        let mut state_debouncer = StateDebouncer::new(MIN_STABLE_DURATION);
+        let start = Instant::now();
+
+        assert_eq!(
+            state_debouncer.debounce(ExecutionState::starting("Triggered at runtime."), start),
+            Some(ExecutionState::starting("Triggered at runtime."))
+        );
+        assert_eq!(
+            state_debouncer.debounce(ExecutionState::running(), start),
+            None
+        );
+        assert_eq!(
+            state_debouncer.debounce(
+                ExecutionState::running(),
+                start + MIN_STABLE_DURATION - Duration::from_millis(1)
+            ),
+            None
+        );
+        assert_eq!(
+            state_debouncer.debounce(ExecutionState::running(), start + MIN_STABLE_DURATION),
+            Some(ExecutionState::running())
+        );
+        assert_eq!(
+            state_debouncer.debounce(ExecutionState::running(), start + MIN_STABLE_DURATION * 2),
+            None
+        );
+    }
+
+    // [utest->swdd~generic-state-checker-debounces-state-changes~1]
+    #[test]
+    fn utest_state_debouncer_reports_terminal_states_immediately() {
+        let mut state_debouncer = StateDebouncer::new(MIN_STABLE_DURATION);
+        let start = Instant::now();
+
+        state_debouncer.debounce(ExecutionState::running(), start);
+
+        assert_eq!(
+            state_debouncer.debounce(ExecutionState::succeeded(), start),
+            Some(ExecutionState::succeeded())
+        );
+        assert_eq!(
+            state_debouncer.debounce(ExecutionState::removed(), start),
+            Some(ExecutionState::removed())
+        );
+    }
+
+    // [utest->swdd~generic-state-checker-debounces-state-changes~1]
+    #[test]
+    fn utest_state_debouncer_counts_suppressed_state_changes() {
+        let mut state_debouncer = StateDebouncer::new(MIN_STABLE_DURATION);
+        let start = Instant::now();
+        let unhealthy = ExecutionState::running_unhealthy("probe failed");
+
+        state_debouncer.debounce(ExecutionState::running(), start);
+        assert_eq!(state_debouncer.debounce(unhealthy.clone(), start), None);
+        assert_eq!(
+            state_debouncer.debounce(ExecutionState::running(), start),
+            None
+        );
+        assert_eq!(state_debouncer.debounce(unhealthy.clone(), start), None);
+
+        let stable = start + MIN_STABLE_DURATION;
+        assert_eq!(
+            state_debouncer.debounce(unhealthy.clone(), stable),
+            Some(ExecutionState::running_unhealthy(
+                "probe failed; 2 short-lived state changes suppressed"
+            ))
+        );
+        assert_eq!(state_debouncer.debounce(unhealthy, stable), None);
+    }
+
+    // [utest->swdd~generic-state-checker-debounces-state-changes~1]
+    #[test]
+    fn utest_state_debouncer_reports_return_to_reported_state_after_flapping() {
+        let mut state_debouncer = StateDebouncer::new(MIN_STABLE_DURATION);
+        let start = Instant::now();
+
+        state_debouncer.debounce(ExecutionState::running(), start);
+        state_debouncer.debounce(ExecutionState::running_unhealthy("probe failed"), start);
+        assert_eq!(
+            state_debouncer.debounce(ExecutionState::running(), start),
+            None
+        );
+
+        let mut expected_state = ExecutionState::running();
+        expected_state.additional_info = "1 short-lived state changes suppressed".to_string();
+        assert_eq!(
+            state_debouncer.debounce(ExecutionState::running(), start + MIN_STABLE_DURATION),
+            Some(expected_state)
+        );
+    }
 }
@@ -0,0 +1,476 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    time::{self, Instant},
+};
+
+use common::objects::{ExecutionState, HealthCheck, HttpProbe, TcpProbe};
+
+use crate::runtime_connectors::RuntimeStateGetter;
+
+const HTTP_DEFAULT_PORT: u16 = 80;
+const HTTP_STATUS_LINE_MAX_LENGTH: usize = 1024;
+const NOT_READY_DETAILS: &str = "Waiting for the health check to succeed";
+
+pub struct HealthCheckRunner {
+    health_check: HealthCheck,
+    consecutive_failures: u32,
+    next_probe: Option<Instant>,
+    unhealthy_details: Option<String>,
+    ready: bool,
+}
+
+impl HealthCheckRunner {
+    pub fn new(health_check: HealthCheck) -> Self {
+        HealthCheckRunner {
+            health_check,
+            consecutive_failures: 0,
+            next_probe: None,
+            unhealthy_details: None,
+            ready: false,
+        }
+    }
+
+    // [impl->swdd~health-check-runner-probes-running-workloads~1]
+    // [impl->swdd~health-check-runner-reports-unhealthy~1]
+    // [impl->swdd~health-check-runner-reports-not-ready~1]
+    pub async fn annotate<WorkloadId>(
+        &mut self,
+        state: ExecutionState,
+        state_getter: &impl RuntimeStateGetter<WorkloadId>,
+        workload_id: &WorkloadId,
+    ) -> ExecutionState
+    where
+        WorkloadId: ToString + FromStr + Clone + Send + Sync + 'static,
+    {
+        if !state.is_running() {
+            self.consecutive_failures = 0;
+            self.next_probe = None;
+            self.unhealthy_details = None;
+            self.ready = false;
+            return state;
+        }
+
+        let now = Instant::now();
+        if self.next_probe.is_none_or(|next_probe| now >= next_probe) {
+            let interval = self.health_check.interval();
+            self.next_probe = Some(now + interval);
+
+            // [impl->swdd~health-check-runner-probe-timeout~1]
+            let result = time::timeout(interval, self.probe(state_getter, workload_id))
+                .await
+                .unwrap_or_else(|_| {
+                    Err(format!("no response within {} seconds", interval.as_secs()))
+                });
+
+            match result {
+                Ok(()) => {
+                    self.consecutive_failures = 0;
+                    self.unhealthy_details = None;
+                    self.ready = true;
+                }
+                Err(err) => {
+                    log::debug!(
+                        "Health check of workload '{}' failed: '{}'",
+                        workload_id.to_string(),
+                        err
+                    );
+                    self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+                    if self.consecutive_failures >= self.health_check.retries() {
+                        self.unhealthy_details = Some(format!("Health check failed: {err}"));
+                    }
+                }
+            }
+        }
+
+        match &self.unhealthy_details {
+            Some(details) => ExecutionState::running_unhealthy(details),
+            None if !self.ready => ExecutionState::running_not_ready(NOT_READY_DETAILS),
+            None => state,
+        }
+    }
+
+    async fn probe<WorkloadId>(
+        &self,
+        state_getter: &impl RuntimeStateGetter<WorkloadId>,
+        workload_id: &WorkloadId,
+    ) -> Result<(), String>
+    where
+        WorkloadId: ToString + FromStr + Clone + Send + Sync + 'static,
+    {
+        // [impl->swdd~health-check-runner-executes-command-probe~1]
+        if let Some(command) = &self.health_check.command {
+            return state_getter
+                .exec_health_command(workload_id, command)
+                .await
+                .unwrap_or_else(|| Err("command probes are not supported by the runtime".into()));
+        }
+        if let Some(tcp_probe) = &self.health_check.tcp {
+            return probe_tcp(tcp_probe).await;
+        }
+        if let Some(http_probe) = &self.health_check.http {
+            return probe_http(http_probe).await;
+        }
+        Ok(())
+    }
+}
+
+// [impl->swdd~health-check-runner-executes-tcp-probe~1]
+async fn probe_tcp(tcp_probe: &TcpProbe) -> Result<(), String> {
+    TcpStream::connect((tcp_probe.host.as_str(), tcp_probe.port))
+        .await
+        .map(|_| ())
+        .map_err(|err| {
+            format!(
+                "could not connect to '{}:{}': {}",
+                tcp_probe.host, tcp_probe.port, err
+            )
+        })
+}
+
+// [impl->swdd~health-check-runner-executes-http-probe~1]
+async fn probe_http(http_probe: &HttpProbe) -> Result<(), String> {
+    let address = http_probe
+        .url
+        .strip_prefix(HttpProbe::URL_SCHEME)
+        .unwrap_or(&http_probe.url);
+    let (authority, path) = match address.find('/') {
+        Some(index) => address.split_at(index),
+        None => (address, "/"),
+    };
+    let has_port = !authority.ends_with(']')
+        && authority
+            .rsplit_once(':')
+            .is_some_and(|(_, port)| port.parse::<u16>().is_ok());
+    let socket_address = if has_port {
+        authority.to_string()
+    } else {
+        format!("{authority}:{HTTP_DEFAULT_PORT}")
+    };
+
+    let request_error =
+        |err: std::io::Error| format!("request to '{}' failed: {}", http_probe.url, err);
+    let mut stream = TcpStream::connect(socket_address)
+        .await
+        .map_err(request_error)?;
+    stream
+        .write_all(
+            format!("GET {path} HTTP/1.0\r\nHost: {authority}\r\nConnection: close\r\n\r\n")
+                .as_bytes(),
+        )
+        .await
+        .map_err(request_error)?;
+
+    let mut response = Vec::new();
+    let mut buffer = [0; HTTP_STATUS_LINE_MAX_LENGTH];
+    while !response.windows(2).any(|window| window == b"\r\n")
+        && response.len() < HTTP_STATUS_LINE_MAX_LENGTH
+    {
+        let read_bytes = stream.read(&mut buffer).await.map_err(request_error)?;
+        if read_bytes == 0 {
+            break;
+        }
+        response.extend_from_slice(&buffer[..read_bytes]);
+    }
+
+    match parse_http_status_code(&String::from_utf8_lossy(&response)) {
+        Some(status_code) if (200..400).contains(&status_code) => Ok(()),
+        Some(status_code) => Err(format!(
+            "'{}' returned the status code {}",
+            http_probe.url, status_code
+        )),
+        None => Err(format!("'{}' returned an invalid response", http_probe.url)),
+    }
+}
+
+fn parse_http_status_code(response: &str) -> Option<u16> {
+    let mut status_line = response.lines().next()?.split_whitespace();
+    status_line
+        .next()
+        .filter(|version| version.starts_with("HTTP/"))?;
+    status_line.next()?.parse().ok()
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::objects::{ExecutionState, HealthCheck, HttpProbe, TcpProbe};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::{
+        parse_http_status_code, probe_http, probe_tcp, HealthCheckRunner, NOT_READY_DETAILS,
+    };
+    use crate::runtime_connectors::MockRuntimeStateGetter;
+
+    const WORKLOAD_ID: &str = "some strange Id";
+    const RETRIES: u32 = 2;
+
+    fn command_health_check_runner() -> HealthCheckRunner {
+        HealthCheckRunner::new(HealthCheck {
+            command: Some(vec!["pg_isready".into()]),
+            retries: Some(RETRIES),
+            ..Default::default()
+        })
+    }
+
+    fn mock_runtime_getter_with_health_command_result(
+        result: Option<Result<(), String>>,
+    ) -> MockRuntimeStateGetter<String> {
+        let mut mock_runtime_getter = MockRuntimeStateGetter::default();
+        mock_runtime_getter
+            .expect_exec_health_command()
+            .returning(move |_: &String, _| {
+                let result = result.clone();
+                Box::pin(async move { result })
+            });
+        mock_runtime_getter
+    }
+
+    async fn serve_http_response(response: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = [0; 1024];
+            let _ = stream.read(&mut buffer).await;
+            let _ = stream.write_all(response.as_bytes()).await;
+        });
+        format!("http://{address}/health")
+    }
+
+    // [utest->swdd~health-check-runner-reports-unhealthy~1]
+    // [utest->swdd~health-check-runner-reports-not-ready~1]
+    // [utest->swdd~health-check-runner-executes-command-probe~1]
+    #[tokio::test]
+    async fn utest_health_check_runner_reports_unhealthy_after_retries() {
+        let mock_runtime_getter =
+            mock_runtime_getter_with_health_command_result(Some(Err("exit code 1".into())));
+        let mut health_check_runner = command_health_check_runner();
+
+        for _ in 1..RETRIES {
+            health_check_runner.next_probe = None;
+            assert_eq!(
+                health_check_runner
+                    .annotate(
+                        ExecutionState::running(),
+                        &mock_runtime_getter,
+                        &WORKLOAD_ID.to_string()
+                    )
+                    .await,
+                ExecutionState::running_not_ready(NOT_READY_DETAILS)
+            );
+        }
+
+        health_check_runner.next_probe = None;
+        assert_eq!(
+            health_check_runner
+                .annotate(
+                    ExecutionState::running(),
+                    &mock_runtime_getter,
+                    &WORKLOAD_ID.to_string()
+                )
+                .await,
+            ExecutionState::running_unhealthy("Health check failed: exit code 1")
+        );
+    }
+
+    // [utest->swdd~health-check-runner-probes-running-workloads~1]
+    #[tokio::test]
+    async fn utest_health_check_runner_probes_once_per_interval() {
+        let mut mock_runtime_getter = MockRuntimeStateGetter::default();
+        mock_runtime_getter
+            .expect_exec_health_command()
+            .once()
+            .returning(|_: &String, _| Box::pin(async { Some(Err("exit code 1".into())) }));
+        let mut health_check_runner = HealthCheckRunner::new(HealthCheck {
+            command: Some(vec!["pg_isready".into()]),
+            retries: Some(1),
+            ..Default::default()
+        });
+
+        for _ in 0..3 {
+            assert_eq!(
+                health_check_runner
+                    .annotate(
+                        ExecutionState::running(),
+                        &mock_runtime_getter,
+                        &WORKLOAD_ID.to_string()
+                    )
+                    .await,
+                ExecutionState::running_unhealthy("Health check failed: exit code 1")
+            );
+        }
+    }
+
+    // [utest->swdd~health-check-runner-reports-unhealthy~1]
+    #[tokio::test]
+    async fn utest_health_check_runner_recovers_on_successful_probe() {
+        let mut health_check_runner = command_health_check_runner();
+        health_check_runner.consecutive_failures = RETRIES;
+        health_check_runner.unhealthy_details = Some("Health check failed: exit code 1".into());
+
+        let mock_runtime_getter = mock_runtime_getter_with_health_command_result(Some(Ok(())));
+
+        assert_eq!(
+            health_check_runner
+                .annotate(
+                    ExecutionState::running(),
+                    &mock_runtime_getter,
+                    &WORKLOAD_ID.to_string()
+                )
+                .await,
+            ExecutionState::running()
+        );
+        assert_eq!(health_check_runner.consecutive_failures, 0);
+    }
+
+    // [utest->swdd~health-check-runner-reports-not-ready~1]
+    #[tokio::test]
+    async fn utest_health_check_runner_reports_ready_after_first_successful_probe() {
+        let mock_runtime_getter = mock_runtime_getter_with_health_command_result(Some(Ok(())));
+        let mut health_check_runner = command_health_check_runner();
+        assert!(!health_check_runner.ready);
+
+        assert_eq!(
+            health_check_runner
+                .annotate(
+                    ExecutionState::running(),
+                    &mock_runtime_getter,
+                    &WORKLOAD_ID.to_string()
+                )
+                .await,
+            ExecutionState::running()
+        );
+        assert!(health_check_runner.ready);
+
+        assert_eq!(
+            health_check_runner
+                .annotate(
+                    ExecutionState::stopping_requested(),
+                    &mock_runtime_getter,
+                    &WORKLOAD_ID.to_string()
+                )
+                .await,
+            ExecutionState::stopping_requested()
+        );
+        assert!(!health_check_runner.ready);
+    }
+
+    // [utest->swdd~health-check-runner-probes-running-workloads~1]
+    #[tokio::test]
+    async fn utest_health_check_runner_ignores_workloads_not_running() {
+        let mut mock_runtime_getter = MockRuntimeStateGetter::default();
+        mock_runtime_getter.expect_exec_health_command().never();
+
+        let mut health_check_runner = command_health_check_runner();
+        health_check_runner.consecutive_failures = RETRIES;
+        health_check_runner.unhealthy_details = Some("Health check failed: exit code 1".into());
+
+        assert_eq!(
+            health_check_runner
+                .annotate(
+                    ExecutionState::succeeded(),
+                    &mock_runtime_getter,
+                    &WORKLOAD_ID.to_string()
+                )
+                .await,
+            ExecutionState::succeeded()
+        );
+        assert_eq!(health_check_runner.consecutive_failures, 0);
+        assert_eq!(health_check_runner.unhealthy_details, None);
+    }
+
+    // [utest->swdd~health-check-runner-executes-command-probe~1]
+    #[tokio::test]
+    async fn utest_health_check_runner_command_probe_not_supported_by_runtime() {
+        let mock_runtime_getter = mock_runtime_getter_with_health_command_result(None);
+        let mut health_check_runner = HealthCheckRunner::new(HealthCheck {
+            command: Some(vec!["pg_isready".into()]),
+            retries: Some(1),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            health_check_runner
+                .annotate(
+                    ExecutionState::running(),
+                    &mock_runtime_getter,
+                    &WORKLOAD_ID.to_string()
+                )
+                .await,
+            ExecutionState::running_unhealthy(
+                "Health check failed: command probes are not supported by the runtime"
+            )
+        );
+    }
+
+    // [utest->swdd~health-check-runner-executes-tcp-probe~1]
+    #[tokio::test]
+    async fn utest_probe_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let tcp_probe = TcpProbe {
+            host: "127.0.0.1".into(),
+            port,
+        };
+        assert_eq!(probe_tcp(&tcp_probe).await, Ok(()));
+
+        drop(listener);
+        assert!(probe_tcp(&tcp_probe).await.is_err());
+    }
+
+    // [utest->swdd~health-check-runner-executes-http-probe~1]
+    #[tokio::test]
+    async fn utest_probe_http_success() {
+        let url = serve_http_response("HTTP/1.1 204 No Content\r\n\r\n").await;
+
+        assert_eq!(probe_http(&HttpProbe { url }).await, Ok(()));
+    }
+
+    // [utest->swdd~health-check-runner-executes-http-probe~1]
+    #[tokio::test]
+    async fn utest_probe_http_fails_on_error_status_code() {
+        let url = serve_http_response("HTTP/1.1 503 Service Unavailable\r\n\r\n").await;
+
+        assert_eq!(
+            probe_http(&HttpProbe { url: url.clone() }).await,
+            Err(format!("'{url}' returned the status code 503"))
+        );
+    }
+
+    // [utest->swdd~health-check-runner-executes-http-probe~1]
+    #[test]
+    fn utest_parse_http_status_code() {
+        assert_eq!(parse_http_status_code("HTTP/1.1 200 OK\r\n"), Some(200));
+        assert_eq!(parse_http_status_code("HTTP/1.0 404 Not Found"), Some(404));
+        assert_eq!(parse_http_status_code("SSH-2.0-OpenSSH\r\n"), None);
+        assert_eq!(parse_http_status_code(""), None);
+    }
+}
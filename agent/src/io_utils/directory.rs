@@ -18,6 +18,9 @@ use super::fs::FileSystemError;
 #[cfg_attr(test, mockall_double::double)]
 use crate::io_utils::filesystem;
 
+// Only the owner is allowed to list and enter the directory
+const OWNER_ONLY_DIRECTORY_MODE: u32 = 0o700;
+
 #[derive(Debug)]
 pub struct Directory {
     path: PathBuf,
@@ -37,6 +40,12 @@ impl Directory {
     pub fn get_path(&self) -> PathBuf {
         self.path.to_path_buf()
     }
+
+    // [impl->swdd~agent-control-interface-pipes-owned-by-workload-user~1]
+    pub fn restrict_to_owner(&self, uid: u32, gid: u32) -> Result<(), FileSystemError> {
+        filesystem::set_owner(&self.path, uid, gid)?;
+        filesystem::set_permissions(&self.path, OWNER_ONLY_DIRECTORY_MODE)
+    }
 }
 
 impl Drop for Directory {
@@ -61,6 +70,7 @@ mockall::mock! {
     pub Directory {
         pub fn new(path: PathBuf) -> Result<Self, FileSystemError>;
         pub fn get_path(&self) -> PathBuf;
+        pub fn restrict_to_owner(&self, uid: u32, gid: u32) -> Result<(), FileSystemError>;
     }
     impl Drop for Directory {
         fn drop(&mut self);
@@ -135,6 +145,39 @@ mod tests {
             directory.unwrap().get_path()
         );
     }
+    // [utest->swdd~agent-control-interface-pipes-owned-by-workload-user~1]
+    #[test]
+    fn utest_directory_restrict_to_owner_sets_owner_and_mode() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC.get_lock();
+
+        let mk_dir_context = mock_filesystem::make_dir_context();
+        mk_dir_context.expect().return_once(|_| Ok(()));
+        let set_owner_context = mock_filesystem::set_owner_context();
+        set_owner_context
+            .expect()
+            .with(
+                predicate::eq(Path::new("test_path").to_path_buf()),
+                predicate::eq(1000),
+                predicate::eq(1001),
+            )
+            .once()
+            .return_once(|_, _, _| Ok(()));
+        let set_permissions_context = mock_filesystem::set_permissions_context();
+        set_permissions_context
+            .expect()
+            .with(
+                predicate::eq(Path::new("test_path").to_path_buf()),
+                predicate::eq(0o700),
+            )
+            .once()
+            .return_once(|_, _| Ok(()));
+        let rm_dir_context = mock_filesystem::remove_dir_context();
+        rm_dir_context.expect().return_once(|_| Ok(()));
+
+        let directory = Directory::new(Path::new("test_path").to_path_buf()).unwrap();
+        assert!(directory.restrict_to_owner(1000, 1001).is_ok());
+    }
+
     #[test]
     fn utest_directory_new_failed() {
         let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC.get_lock();
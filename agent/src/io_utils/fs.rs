@@ -27,6 +27,7 @@ pub enum FileSystemError {
     RemoveFifo(OsString, std::io::ErrorKind),
     RemoveDirectory(OsString, std::io::ErrorKind),
     Permissions(OsString, std::io::ErrorKind),
+    Owner(OsString, Errno),
 }
 
 impl Display for FileSystemError {
@@ -50,6 +51,9 @@ impl Display for FileSystemError {
             FileSystemError::Permissions(path, err) => {
                 write!(f, "Could not set permissions to {path:?}  {err:?}")
             }
+            FileSystemError::Owner(path, err) => {
+                write!(f, "Could not set owner of {path:?}: {err}")
+            }
         }
     }
 }
@@ -58,11 +62,11 @@ impl Display for FileSystemError {
 pub mod filesystem {
 
     #[cfg(not(test))]
-    use nix::unistd::mkfifo;
+    use nix::unistd::{chown, mkfifo};
 
     #[cfg(test)]
     use super::tests::{
-        create_dir_all, metadata, mkfifo, remove_dir as fs_remove_dir, remove_file,
+        chown, create_dir_all, metadata, mkfifo, remove_dir as fs_remove_dir, remove_file,
         set_permissions as fs_set_permissions,
     };
     use super::FileSystemError;
@@ -75,6 +79,7 @@ pub mod filesystem {
     use std::os::unix::fs::FileTypeExt;
 
     use nix::sys::stat::Mode;
+    use nix::unistd::{Gid, Uid};
     use std::fs::Permissions;
     use std::os::unix::fs::PermissionsExt;
     use std::path::Path;
@@ -90,6 +95,11 @@ pub mod filesystem {
             .map_err(|err| FileSystemError::Permissions(path.as_os_str().to_owned(), err.kind()))
     }
 
+    pub fn set_owner(path: &Path, uid: u32, gid: u32) -> Result<(), FileSystemError> {
+        chown(path, Some(Uid::from_raw(uid)), Some(Gid::from_raw(gid)))
+            .map_err(|err| FileSystemError::Owner(path.as_os_str().to_owned(), err))
+    }
+
     pub fn is_fifo(path: &Path) -> bool {
         if let Ok(meta) = metadata(path) {
             return meta.file_type().is_fifo();
@@ -134,7 +144,7 @@ mod tests {
     use std::{
         collections::VecDeque,
         fs::Permissions,
-        io::{self, Error, ErrorKind},
+        io::{self, Error},
         os::unix::fs::PermissionsExt,
         path::{Path, PathBuf},
         sync::Mutex,
@@ -142,6 +152,7 @@ mod tests {
 
     use mockall::lazy_static;
     use nix::sys::stat::Mode;
+    use nix::unistd::{Gid, Uid};
 
     use super::{filesystem, FileSystemError};
 
@@ -153,6 +164,7 @@ mod tests {
         remove_file(PathBuf, io::Result<()>),    // remove_file(path, fake_result)
         metadata(PathBuf, io::Result<Metadata>), // metadata(path, fake_result)
         set_permissions(PathBuf, u32, io::Result<()>), // set_permissions(path, mode, fake_result)
+        chown(PathBuf, u32, u32, nix::Result<()>), // chown(path, uid, gid, fake_result)
     }
 
     lazy_static! {
@@ -273,6 +285,24 @@ mod tests {
         );
     }
 
+    pub fn chown(path: &Path, owner: Option<Uid>, group: Option<Gid>) -> nix::Result<()> {
+        if let Some(FakeCall::chown(fake_path, fake_uid, fake_gid, fake_result)) =
+            FAKE_CALL_LIST.lock().unwrap().pop_front()
+        {
+            if fake_path == path
+                && owner == Some(Uid::from_raw(fake_uid))
+                && group == Some(Gid::from_raw(fake_gid))
+            {
+                return fake_result;
+            }
+        }
+
+        panic!(
+            "No mock specified for call chown({:?}, {:?}, {:?})",
+            path, owner, group
+        );
+    }
+
     #[test]
     fn utest_set_permissions_ok() {
         let _test_lock = TEST_LOCK.lock();
@@ -311,6 +341,40 @@ mod tests {
         );
     }
 
+    // [utest->swdd~agent-control-interface-pipes-owned-by-workload-user~1]
+    #[test]
+    fn utest_set_owner_ok() {
+        let _test_lock = TEST_LOCK.lock();
+        FAKE_CALL_LIST.lock().unwrap().push_back(FakeCall::chown(
+            Path::new("test_dir").to_path_buf(),
+            1000,
+            1001,
+            Ok(()),
+        ));
+
+        assert!(filesystem::set_owner(Path::new("test_dir"), 1000, 1001).is_ok());
+    }
+
+    // [utest->swdd~agent-control-interface-pipes-owned-by-workload-user~1]
+    #[test]
+    fn utest_set_owner_failed() {
+        let _test_lock = TEST_LOCK.lock();
+        FAKE_CALL_LIST.lock().unwrap().push_back(FakeCall::chown(
+            Path::new("test_dir").to_path_buf(),
+            1000,
+            1001,
+            Err(nix::Error::EPERM),
+        ));
+
+        assert_eq!(
+            filesystem::set_owner(Path::new("test_dir"), 1000, 1001),
+            Err(FileSystemError::Owner(
+                Path::new("test_dir").as_os_str().to_owned(),
+                nix::Error::EPERM
+            ))
+        );
+    }
+
     #[test]
     fn utest_filesystem_make_dir_ok() {
         let _test_lock = TEST_LOCK.lock();
@@ -332,7 +396,7 @@ mod tests {
             .unwrap()
             .push_back(FakeCall::create_dir_all(
                 Path::new("test_dir").to_path_buf(),
-                Err(std::io::Error::new(std::io::ErrorKind::Other, "some error")),
+                Err(std::io::Error::other("some error")),
             ));
 
         assert_eq!(
@@ -393,7 +457,7 @@ mod tests {
         let _test_lock = TEST_LOCK.lock();
         FAKE_CALL_LIST.lock().unwrap().push_back(FakeCall::metadata(
             Path::new("test_fifo").to_path_buf(),
-            Err(std::io::Error::new(ErrorKind::Other, "oh no!")),
+            Err(std::io::Error::other("oh no!")),
         ));
 
         assert!(!filesystem::is_fifo(Path::new("test_fifo")));
@@ -419,7 +483,7 @@ mod tests {
             .unwrap()
             .push_back(FakeCall::remove_dir(
                 Path::new("test_dir").to_path_buf(),
-                Err(Error::new(ErrorKind::Other, "Some Error!")),
+                Err(Error::other("Some Error!")),
             ));
 
         assert!(matches!(
@@ -448,7 +512,7 @@ mod tests {
             .unwrap()
             .push_back(FakeCall::remove_file(
                 Path::new("test_file").to_path_buf(),
-                Err(Error::new(ErrorKind::Other, "Some Error!")),
+                Err(Error::other("Some Error!")),
             ));
 
         assert!(matches!(
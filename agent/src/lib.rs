@@ -0,0 +1,51 @@
+// Copyright (c) 2023 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+// The library target is used by the binary and allows running the agent within another process,
+// e.g., in the development mode of the CLI. The modules are private and only the entry point is
+// exported.
+
+mod agent_config;
+mod agent_manager;
+mod agent_runner;
+mod cli;
+mod control_interface;
+mod eviction_controller;
+mod exec_session;
+mod metrics;
+mod port_forward_session;
+mod runtime_connectors;
+mod secret_resolver;
+mod shutdown_policy;
+#[cfg(test)]
+mod test_helper;
+mod workload_operation;
+
+mod generic_polling_state_checker;
+mod health_check_runner;
+mod runtime_manager;
+mod workload;
+mod workload_cache;
+mod workload_log_sink;
+mod workload_scheduler;
+mod workload_state;
+
+mod io_utils;
+
+pub use agent_config::AgentConfig;
+pub use agent_runner::{run_agent, AgentConnection, AgentRunConfig};
+pub use cli::{parse as parse_arguments, Arguments as AgentArguments};
+pub use shutdown_policy::ShutdownPolicy;
+
+const BUFFER_SIZE: usize = 20;
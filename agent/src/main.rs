@@ -12,50 +12,19 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use common::communications_client::CommunicationsClient;
-use common::objects::{AgentName, WorkloadState};
-use common::to_server_interface::ToServer;
-use generic_polling_state_checker::GenericPollingStateChecker;
-use grpc::security::TLSConfig;
-use std::collections::HashMap;
-
-mod agent_manager;
-mod cli;
-mod control_interface;
-mod runtime_connectors;
-#[cfg(test)]
-pub mod test_helper;
-mod workload_operation;
-
-mod generic_polling_state_checker;
-mod runtime_manager;
-mod workload;
-mod workload_scheduler;
-mod workload_state;
+use std::time::Duration;
 
-mod io_utils;
+use tokio::signal::unix::{signal, SignalKind};
 
-use common::from_server_interface::FromServer;
+use ank_agent::{AgentConfig, AgentConnection, AgentRunConfig};
 use common::std_extensions::GracefulExitResult;
-use grpc::client::GRPCCommunicationsClient;
-
-use agent_manager::AgentManager;
-
-#[cfg_attr(test, mockall_double::double)]
-use crate::runtime_manager::RuntimeManager;
-use runtime_connectors::{
-    podman::{PodmanRuntime, PodmanWorkloadId},
-    podman_kube::{PodmanKubeRuntime, PodmanKubeWorkloadId},
-    GenericRuntimeFacade, RuntimeConnector, RuntimeFacade,
-};
-
-const BUFFER_SIZE: usize = 20;
+use grpc::security::TLSConfig;
 
 #[tokio::main]
 async fn main() {
     env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
 
-    let args = cli::parse();
+    let args = ank_agent::parse_arguments();
 
     let server_url = match args.insecure {
         true => args.server_url.replace("http[s]", "http"),
@@ -69,45 +38,9 @@ async fn main() {
         args.run_folder,
     );
 
-    // [impl->swdd~agent-uses-async-channels~1]
-    let (to_manager, manager_receiver) = tokio::sync::mpsc::channel::<FromServer>(BUFFER_SIZE);
-    let (to_server, server_receiver) = tokio::sync::mpsc::channel::<ToServer>(BUFFER_SIZE);
-    let (workload_state_sender, workload_state_receiver) =
-        tokio::sync::mpsc::channel::<WorkloadState>(BUFFER_SIZE);
-
-    // [impl->swdd~agent-prepares-dedicated-run-folder~1]
-    let run_directory = io_utils::prepare_agent_run_directory(args.run_folder.as_str(), args.agent_name.as_str())
-        .unwrap_or_exit("Run folder creation failed. Cannot continue without run folder.");
-
-    // [impl->swdd~agent-supports-podman~2]
-    let podman_runtime = Box::new(PodmanRuntime {});
-    let podman_runtime_name = podman_runtime.name();
-    let podman_facade = Box::new(GenericRuntimeFacade::<
-        PodmanWorkloadId,
-        GenericPollingStateChecker,
-    >::new(podman_runtime));
-    let mut runtime_facade_map: HashMap<String, Box<dyn RuntimeFacade>> = HashMap::new();
-    runtime_facade_map.insert(podman_runtime_name, podman_facade);
-
-    // [impl->swdd~agent-supports-podman-kube-runtime~1]
-    let podman_kube_runtime = Box::new(PodmanKubeRuntime {});
-    let podman_kube_runtime_name = podman_kube_runtime.name();
-    let podman_kube_facade = Box::new(GenericRuntimeFacade::<
-        PodmanKubeWorkloadId,
-        GenericPollingStateChecker,
-    >::new(podman_kube_runtime));
-    runtime_facade_map.insert(podman_kube_runtime_name, podman_kube_facade);
-
-    // The RuntimeManager currently directly gets the server ToServerInterface, but it shall get the agent manager interface
-    // This is needed to be able to filter/authorize the commands towards the Ankaios server
-    // The pipe connecting the workload to Ankaios must be in the runtime adapter
-    let runtime_manager = RuntimeManager::new(
-        AgentName::from(args.agent_name.as_str()),
-        run_directory.get_path(),
-        to_server.clone(),
-        runtime_facade_map,
-        workload_state_sender,
-    );
+    // [impl->swdd~agent-loads-agent-config-file~1]
+    let agent_config = AgentConfig::load(args.agent_config.as_deref())
+        .unwrap_or_exit("Could not load the agent config");
 
     if let Err(err_message) =
         TLSConfig::is_config_conflicting(args.insecure, &args.ca_pem, &args.crt_pem, &args.key_pem)
@@ -118,32 +51,41 @@ async fn main() {
     // [impl->swdd~agent-establishes-insecure-communication-based-on-provided-insecure-cli-argument~1]
     // [impl->swdd~agent-provides-file-paths-to-communication-middleware~1]
     // [impl->swdd~agent-fails-on-missing-file-paths-and-insecure-cli-arguments~1]
-    let tls_config = TLSConfig::new(args.insecure, args.ca_pem, args.crt_pem, args.key_pem);
-
-    let mut communications_client = GRPCCommunicationsClient::new_agent_communication(
-        args.agent_name.clone(),
-        server_url,
+    let tls_config = TLSConfig::new(args.insecure, args.ca_pem, args.crt_pem, args.key_pem)
         // [impl->swdd~agent-fails-on-missing-file-paths-and-insecure-cli-arguments~1]
-        tls_config.unwrap_or_exit("Missing certificate file"),
-    )
-    .unwrap_or_exit("Failed to create communications client.");
+        .unwrap_or_exit("Missing certificate file");
+
+    let config = AgentRunConfig {
+        agent_name: args.agent_name,
+        run_folder: args.run_folder,
+        agent_config,
+        shutdown_policy: args.shutdown_policy,
+        disconnect_threshold: Duration::from_secs(args.disconnect_threshold),
+        offline_start_delay: args.offline_start_delay.map(Duration::from_secs),
+        eviction_memory_threshold: args.eviction_memory_threshold,
+        image_gc_max_age: args.image_gc_max_age.map(Duration::from_secs),
+        image_gc_max_cache_size: args.image_gc_max_cache_size,
+        metrics_address: args.metrics_address,
+    };
+    let connection = AgentConnection::Grpc {
+        server_url,
+        tls_config,
+    };
 
-    let mut agent_manager = AgentManager::new(
-        args.agent_name,
-        manager_receiver,
-        runtime_manager,
-        to_server,
-        workload_state_receiver,
-    );
+    ank_agent::run_agent(config, connection, shutdown_signal())
+        .await
+        .unwrap_or_exit("agent error");
+}
+
+// [impl->swdd~agent-shuts-down-gracefully~1]
+async fn shutdown_signal() {
+    let mut sigterm = signal(SignalKind::terminate())
+        .unwrap_or_exit("Could not register the handler for SIGTERM");
+    let mut sigint =
+        signal(SignalKind::interrupt()).unwrap_or_exit("Could not register the handler for SIGINT");
 
     tokio::select! {
-        // [impl->swdd~agent-sends-hello~1]
-        // [impl->swdd~agent-default-communication-grpc~1]
-        communication_result = communications_client.run(server_receiver, to_manager) => {
-            communication_result.unwrap_or_exit("agent error")
-        }
-        _agent_mgr_result = agent_manager.start() => {
-            log::info!("AgentManager exited.");
-        }
+        _ = sigterm.recv() => log::info!("Received SIGTERM."),
+        _ = sigint.recv() => log::info!("Received SIGINT."),
     }
 }
@@ -0,0 +1,377 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
+
+use common::metrics_endpoint::write_metric_header;
+use tokio::sync::mpsc::Sender;
+
+const RESULT_SUCCESS: &str = "success";
+const RESULT_ERROR: &str = "error";
+
+static METRICS: AgentMetrics = AgentMetrics::new();
+
+/// Returns the metrics of the Ankaios agent shared by all its components
+pub fn agent_metrics() -> &'static AgentMetrics {
+    &METRICS
+}
+
+#[derive(Debug, Default)]
+struct OperationMetrics {
+    results: BTreeMap<&'static str, u64>,
+    duration_sum: Duration,
+}
+
+impl OperationMetrics {
+    fn record(&mut self, duration: Duration, success: bool) {
+        let result = if success {
+            RESULT_SUCCESS
+        } else {
+            RESULT_ERROR
+        };
+        *self.results.entry(result).or_default() += 1;
+        self.duration_sum += duration;
+    }
+
+    fn count(&self) -> u64 {
+        self.results.values().sum()
+    }
+}
+
+#[derive(Debug, Default)]
+struct RuntimeMetrics {
+    create: OperationMetrics,
+    delete: OperationMetrics,
+    create_retries: u64,
+}
+
+struct MonitoredChannel {
+    name: &'static str,
+    max_capacity: usize,
+    // Returns the free capacity or None if the channel is already closed.
+    capacity: Box<dyn Fn() -> Option<usize> + Send>,
+}
+
+struct MetricsData {
+    runtimes: BTreeMap<String, RuntimeMetrics>,
+    control_interface_connections: u64,
+    channels: Vec<MonitoredChannel>,
+}
+
+/// Collects the metrics of the Ankaios agent
+///
+/// The metrics are updated by the workload control loops and the control interface tasks
+/// and rendered in the Prometheus text format when the metrics endpoint is scraped.
+pub struct AgentMetrics {
+    data: Mutex<MetricsData>,
+}
+
+impl AgentMetrics {
+    pub const fn new() -> Self {
+        AgentMetrics {
+            data: Mutex::new(MetricsData {
+                runtimes: BTreeMap::new(),
+                control_interface_connections: 0,
+                channels: Vec::new(),
+            }),
+        }
+    }
+
+    // [impl->swdd~agent-collects-metrics~1]
+    pub fn workload_created(&self, runtime: &str, duration: Duration, success: bool) {
+        self.runtime_metrics(runtime, |metrics| metrics.create.record(duration, success));
+    }
+
+    // [impl->swdd~agent-collects-metrics~1]
+    pub fn workload_deleted(&self, runtime: &str, duration: Duration, success: bool) {
+        self.runtime_metrics(runtime, |metrics| metrics.delete.record(duration, success));
+    }
+
+    // [impl->swdd~agent-collects-metrics~1]
+    pub fn workload_create_retried(&self, runtime: &str) {
+        self.runtime_metrics(runtime, |metrics| metrics.create_retries += 1);
+    }
+
+    /// Counts an open control interface connection until the returned guard is dropped
+    // [impl->swdd~agent-collects-metrics~1]
+    pub fn control_interface_connected(&self) -> ControlInterfaceConnectionGuard<'_> {
+        self.lock_data().control_interface_connections += 1;
+        ControlInterfaceConnectionGuard { metrics: self }
+    }
+
+    /// Reports the fill level of the channel without keeping it open
+    // [impl->swdd~agent-collects-metrics~1]
+    pub fn monitor_channel<T: Send + 'static>(&self, name: &'static str, sender: &Sender<T>) {
+        let weak_sender = sender.downgrade();
+        self.lock_data().channels.push(MonitoredChannel {
+            name,
+            max_capacity: sender.max_capacity(),
+            capacity: Box::new(move || weak_sender.upgrade().map(|sender| sender.capacity())),
+        });
+    }
+
+    // [impl->swdd~agent-provides-metrics-endpoint~1]
+    pub fn render(&self) -> String {
+        let data = self.lock_data();
+        let mut output = String::new();
+
+        render_operation(
+            &mut output,
+            "ankaios_agent_workload_create",
+            "workload creations",
+            data.runtimes
+                .iter()
+                .map(|(runtime, metrics)| (runtime, &metrics.create)),
+        );
+        render_operation(
+            &mut output,
+            "ankaios_agent_workload_delete",
+            "workload deletions",
+            data.runtimes
+                .iter()
+                .map(|(runtime, metrics)| (runtime, &metrics.delete)),
+        );
+
+        write_metric_header(
+            &mut output,
+            "ankaios_agent_workload_create_retries_total",
+            "Number of retries after a failed workload creation.",
+            "counter",
+        );
+        for (runtime, metrics) in &data.runtimes {
+            let _ = writeln!(
+                output,
+                "ankaios_agent_workload_create_retries_total{{runtime=\"{}\"}} {}",
+                runtime, metrics.create_retries
+            );
+        }
+
+        write_metric_header(
+            &mut output,
+            "ankaios_agent_control_interface_connections",
+            "Number of open control interface connections.",
+            "gauge",
+        );
+        let _ = writeln!(
+            output,
+            "ankaios_agent_control_interface_connections {}",
+            data.control_interface_connections
+        );
+
+        write_metric_header(
+            &mut output,
+            "ankaios_agent_channel_queued_messages",
+            "Number of messages waiting in the internal channels.",
+            "gauge",
+        );
+        for channel in &data.channels {
+            if let Some(capacity) = (channel.capacity)() {
+                let _ = writeln!(
+                    output,
+                    "ankaios_agent_channel_queued_messages{{channel=\"{}\"}} {}",
+                    channel.name,
+                    channel.max_capacity - capacity
+                );
+            }
+        }
+
+        write_metric_header(
+            &mut output,
+            "ankaios_agent_channel_capacity",
+            "Maximum number of messages the internal channels can hold.",
+            "gauge",
+        );
+        for channel in &data.channels {
+            let _ = writeln!(
+                output,
+                "ankaios_agent_channel_capacity{{channel=\"{}\"}} {}",
+                channel.name, channel.max_capacity
+            );
+        }
+
+        output
+    }
+
+    fn runtime_metrics(&self, runtime: &str, update: impl FnOnce(&mut RuntimeMetrics)) {
+        let mut data = self.lock_data();
+        update(data.runtimes.entry(runtime.to_owned()).or_default());
+    }
+
+    fn lock_data(&self) -> MutexGuard<'_, MetricsData> {
+        // The data stays consistent even if a thread panicked while holding the lock.
+        self.data
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for AgentMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct ControlInterfaceConnectionGuard<'a> {
+    metrics: &'a AgentMetrics,
+}
+
+impl Drop for ControlInterfaceConnectionGuard<'_> {
+    fn drop(&mut self) {
+        self.metrics.lock_data().control_interface_connections -= 1;
+    }
+}
+
+fn render_operation<'a>(
+    output: &mut String,
+    name: &str,
+    description: &str,
+    operations: impl Iterator<Item = (&'a String, &'a OperationMetrics)> + Clone,
+) {
+    write_metric_header(
+        output,
+        &format!("{}_total", name),
+        &format!("Number of {} per runtime.", description),
+        "counter",
+    );
+    for (runtime, metrics) in operations.clone() {
+        for (result, count) in &metrics.results {
+            let _ = writeln!(
+                output,
+                "{}_total{{runtime=\"{}\",result=\"{}\"}} {}",
+                name, runtime, result, count
+            );
+        }
+    }
+
+    write_metric_header(
+        output,
+        &format!("{}_duration_seconds", name),
+        &format!("Time needed for the {} per runtime.", description),
+        "summary",
+    );
+    for (runtime, metrics) in operations {
+        let _ = writeln!(
+            output,
+            "{}_duration_seconds_sum{{runtime=\"{}\"}} {}",
+            name,
+            runtime,
+            metrics.duration_sum.as_secs_f64()
+        );
+        let _ = writeln!(
+            output,
+            "{}_duration_seconds_count{{runtime=\"{}\"}} {}",
+            name,
+            runtime,
+            metrics.count()
+        );
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::AgentMetrics;
+
+    const RUNTIME: &str = "podman";
+
+    // [utest->swdd~agent-collects-metrics~1]
+    // [utest->swdd~agent-provides-metrics-endpoint~1]
+    #[test]
+    fn utest_agent_metrics_render_workload_operations() {
+        let metrics = AgentMetrics::new();
+
+        metrics.workload_created(RUNTIME, Duration::from_millis(250), true);
+        metrics.workload_created(RUNTIME, Duration::from_millis(250), false);
+        metrics.workload_create_retried(RUNTIME);
+        metrics.workload_deleted(RUNTIME, Duration::from_millis(100), true);
+
+        let output = metrics.render();
+
+        assert!(output.contains("# TYPE ankaios_agent_workload_create_total counter\n"));
+        assert!(output.contains(
+            "\nankaios_agent_workload_create_total{runtime=\"podman\",result=\"success\"} 1\n"
+        ));
+        assert!(output.contains(
+            "\nankaios_agent_workload_create_total{runtime=\"podman\",result=\"error\"} 1\n"
+        ));
+        assert!(output.contains(
+            "\nankaios_agent_workload_create_duration_seconds_sum{runtime=\"podman\"} 0.5\n"
+        ));
+        assert!(output.contains(
+            "\nankaios_agent_workload_create_duration_seconds_count{runtime=\"podman\"} 2\n"
+        ));
+        assert!(output
+            .contains("\nankaios_agent_workload_create_retries_total{runtime=\"podman\"} 1\n"));
+        assert!(output.contains(
+            "\nankaios_agent_workload_delete_total{runtime=\"podman\",result=\"success\"} 1\n"
+        ));
+        assert!(output.contains(
+            "\nankaios_agent_workload_delete_duration_seconds_sum{runtime=\"podman\"} 0.1\n"
+        ));
+    }
+
+    // [utest->swdd~agent-collects-metrics~1]
+    #[test]
+    fn utest_agent_metrics_control_interface_connections() {
+        let metrics = AgentMetrics::new();
+
+        let first_connection = metrics.control_interface_connected();
+        let second_connection = metrics.control_interface_connected();
+        assert!(metrics
+            .render()
+            .contains("\nankaios_agent_control_interface_connections 2\n"));
+
+        drop(first_connection);
+        drop(second_connection);
+        assert!(metrics
+            .render()
+            .contains("\nankaios_agent_control_interface_connections 0\n"));
+    }
+
+    // [utest->swdd~agent-collects-metrics~1]
+    #[tokio::test]
+    async fn utest_agent_metrics_channel_fill_level() {
+        let metrics = AgentMetrics::new();
+        let (sender, _receiver) = tokio::sync::mpsc::channel::<u32>(5);
+        metrics.monitor_channel("to_server", &sender);
+
+        sender.send(1).await.unwrap();
+        sender.send(2).await.unwrap();
+
+        let output = metrics.render();
+        assert!(
+            output.contains("\nankaios_agent_channel_queued_messages{channel=\"to_server\"} 2\n")
+        );
+        assert!(output.contains("\nankaios_agent_channel_capacity{channel=\"to_server\"} 5\n"));
+
+        drop(sender);
+        assert!(!metrics
+            .render()
+            .contains("ankaios_agent_channel_queued_messages{"));
+    }
+}
@@ -0,0 +1,284 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{net::SocketAddr, time::Duration};
+
+use api::ank_base::{self, response::ResponseContent};
+use common::{
+    commands::PortForwardData,
+    to_server_interface::{ToServerInterface, ToServerSender},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpStream,
+    },
+    sync::mpsc,
+    task::JoinHandle,
+};
+
+use crate::exec_session::error_response;
+
+const DATA_BUFFER_SIZE: usize = 20;
+const READ_CHUNK_SIZE: usize = 4096;
+// A workload silently dropping the connection attempt must not keep the port forwarding open forever
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub fn port_forward_response(request_id: String, data: Vec<u8>, close: bool) -> ank_base::Response {
+    ank_base::Response {
+        request_id,
+        response_content: Some(ResponseContent::PortForwardData(
+            ank_base::PortForwardData { data, close },
+        )),
+    }
+}
+
+// A port forward session opens a TCP connection to the workload, writes the data received
+// from the server to it and sends the data read from the connection back to the server.
+pub struct PortForwardSession {
+    data_tx: mpsc::Sender<PortForwardData>,
+    task_handle: JoinHandle<()>,
+}
+
+impl PortForwardSession {
+    // [impl->swdd~agent-forwards-port-forward-data~1]
+    // [impl->swdd~agent-port-forward-connects-to-workload~1]
+    pub fn start(
+        request_id: String,
+        workload_name: String,
+        address: SocketAddr,
+        to_server: ToServerSender,
+    ) -> Self {
+        let (data_tx, data_rx) = mpsc::channel(DATA_BUFFER_SIZE);
+
+        // the data received while connecting is buffered in the channel
+        let task_handle = tokio::spawn(async move {
+            let stream = match connect(address).await {
+                Ok(stream) => stream,
+                Err(err) => {
+                    let message = format!(
+                        "Could not connect to port {} of workload '{}': '{}'",
+                        address.port(),
+                        workload_name,
+                        err
+                    );
+                    log::warn!(
+                        "Port forward request '{}' failed: '{}'",
+                        request_id,
+                        message
+                    );
+                    if let Err(err) = to_server
+                        .response(error_response(request_id, message))
+                        .await
+                    {
+                        log::error!("Could not send the port forward response: '{}'", err);
+                    }
+                    return;
+                }
+            };
+            let (read_half, write_half) = stream.into_split();
+
+            let write_task = tokio::spawn(forward_to_workload(write_half, data_rx));
+
+            forward_from_workload(read_half, &request_id, &to_server).await;
+            write_task.abort();
+
+            // the workload has closed the connection
+            let response = port_forward_response(request_id.clone(), vec![], true);
+            if let Err(err) = to_server.response(response).await {
+                log::warn!(
+                    "Could not send the end of port forwarding '{}': '{}'",
+                    request_id,
+                    err
+                );
+            }
+        });
+
+        PortForwardSession {
+            data_tx,
+            task_handle,
+        }
+    }
+
+    // [impl->swdd~agent-forwards-port-forward-data~1]
+    pub async fn send_data(&self, port_forward_data: PortForwardData) -> Result<(), String> {
+        self.data_tx
+            .send(port_forward_data)
+            .await
+            .map_err(|_| "The port forwarding has already ended".to_string())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.task_handle.is_finished()
+    }
+}
+
+// [impl->swdd~agent-port-forward-connects-to-workload~1]
+async fn connect(address: SocketAddr) -> Result<TcpStream, String> {
+    tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect(address))
+        .await
+        .map_err(|_| format!("Timed out after {} seconds", CONNECT_TIMEOUT.as_secs()))?
+        .map_err(|err| err.to_string())
+}
+
+async fn forward_to_workload(
+    mut write_half: OwnedWriteHalf,
+    mut data_rx: mpsc::Receiver<PortForwardData>,
+) {
+    while let Some(port_forward_data) = data_rx.recv().await {
+        if !port_forward_data.data.is_empty()
+            && write_half.write_all(&port_forward_data.data).await.is_err()
+        {
+            log::debug!("The connection to the workload is closed.");
+            break;
+        }
+        if port_forward_data.close {
+            // the workload still sends its remaining data after the shutdown of the writing side
+            let _ = write_half.shutdown().await;
+            break;
+        }
+    }
+}
+
+async fn forward_from_workload(
+    mut read_half: OwnedReadHalf,
+    request_id: &str,
+    to_server: &ToServerSender,
+) {
+    let mut buffer = vec![0; READ_CHUNK_SIZE];
+    loop {
+        match read_half.read(&mut buffer).await {
+            Ok(0) => break,
+            Ok(read_bytes) => {
+                let response = port_forward_response(
+                    request_id.to_owned(),
+                    buffer[..read_bytes].to_vec(),
+                    false,
+                );
+                if to_server.response(response).await.is_err() {
+                    log::warn!(
+                        "Could not forward the data of port forwarding '{}'",
+                        request_id
+                    );
+                    break;
+                }
+            }
+            Err(err) => {
+                log::warn!(
+                    "Could not read the data of port forwarding '{}': '{}'",
+                    request_id,
+                    err
+                );
+                break;
+            }
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{port_forward_response, PortForwardSession};
+    use common::{commands::PortForwardData, to_server_interface::ToServer};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        sync::mpsc::channel,
+    };
+
+    const REQUEST_ID: &str = "cli@request_id";
+    const WORKLOAD_NAME: &str = "workload_1";
+
+    // [utest->swdd~agent-forwards-port-forward-data~1]
+    // [utest->swdd~agent-port-forward-connects-to-workload~1]
+    #[tokio::test]
+    async fn utest_port_forward_session_forwards_data_in_both_directions() {
+        let (to_server, mut server_receiver) = channel(20);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+
+        let port_forward_session = PortForwardSession::start(
+            REQUEST_ID.into(),
+            WORKLOAD_NAME.into(),
+            listener.local_addr().unwrap(),
+            to_server,
+        );
+        let (mut workload_stream, _) = listener.accept().await.unwrap();
+        port_forward_session
+            .send_data(PortForwardData {
+                data: b"ping".to_vec(),
+                close: true,
+            })
+            .await
+            .unwrap();
+
+        let mut received = Vec::new();
+        workload_stream.read_to_end(&mut received).await.unwrap();
+        assert_eq!(received, b"ping");
+
+        workload_stream.write_all(b"pong").await.unwrap();
+        drop(workload_stream);
+
+        assert_eq!(
+            server_receiver.recv().await,
+            Some(ToServer::Response(port_forward_response(
+                REQUEST_ID.into(),
+                b"pong".to_vec(),
+                false
+            )))
+        );
+        assert_eq!(
+            server_receiver.recv().await,
+            Some(ToServer::Response(port_forward_response(
+                REQUEST_ID.into(),
+                vec![],
+                true
+            )))
+        );
+    }
+
+    // [utest->swdd~agent-port-forward-connects-to-workload~1]
+    #[tokio::test]
+    async fn utest_port_forward_session_sends_error_if_connection_fails() {
+        let (to_server, mut server_receiver) = channel(20);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        let _port_forward_session =
+            PortForwardSession::start(REQUEST_ID.into(), WORKLOAD_NAME.into(), address, to_server);
+
+        let Some(ToServer::Response(response)) = server_receiver.recv().await else {
+            panic!("Expected a response");
+        };
+        let expected_prefix = format!(
+            "Could not connect to port {} of workload '{}'",
+            address.port(),
+            WORKLOAD_NAME
+        );
+        assert!(matches!(
+            response.response_content,
+            Some(api::ank_base::response::ResponseContent::Error(ref error))
+                if error.message.starts_with(&expected_prefix)
+        ));
+        assert_eq!(response.request_id, REQUEST_ID);
+    }
+}
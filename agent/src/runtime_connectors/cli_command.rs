@@ -12,7 +12,11 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+use nix::errno::Errno;
+use nix::sys::signal::{killpg, Signal};
+use nix::unistd::Pid;
 use std::process::Stdio;
+use std::time::Duration;
 use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 
@@ -24,6 +28,8 @@ pub struct CliCommand<'a> {
     stdin: Option<&'a [u8]>,
     program: String,
     args: Vec<String>,
+    timeout: Option<Duration>,
+    process_id: Option<u32>,
 }
 
 impl<'a> CliCommand<'a> {
@@ -38,6 +44,8 @@ impl<'a> CliCommand<'a> {
             stdin: None,
             program: program.to_owned(),
             args: Vec::new(),
+            timeout: None,
+            process_id: None,
         }
     }
 
@@ -52,13 +60,56 @@ impl<'a> CliCommand<'a> {
         self
     }
 
+    // The command is started in its own process group, which is killed if the command does not
+    // exit within the timeout. Killing only the command would keep the processes it started running.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.command.process_group(0).kill_on_drop(true);
+        self.timeout = Some(timeout);
+        self
+    }
+
     pub async fn exec(&mut self) -> Result<String, String> {
+        let Some(timeout) = self.timeout else {
+            return self.exec_until_exit().await;
+        };
+        match tokio::time::timeout(timeout, self.exec_until_exit()).await {
+            Ok(result) => result,
+            Err(_) => {
+                self.kill_process_group();
+                Err(format!(
+                    "Execution of '{} {}' timed out after {} ms and was killed",
+                    self.program,
+                    self.get_quoted_args(),
+                    timeout.as_millis()
+                ))
+            }
+        }
+    }
+
+    fn kill_process_group(&self) {
+        let Some(process_id) = self.process_id else {
+            return;
+        };
+        // the process group is gone if the command and all processes it started have exited
+        match killpg(Pid::from_raw(process_id as i32), Signal::SIGKILL) {
+            Ok(()) | Err(Errno::ESRCH) => {}
+            Err(err) => log::warn!(
+                "Could not kill the processes started by '{}': '{}'",
+                self.program,
+                err
+            ),
+        }
+    }
+
+    async fn exec_until_exit(&mut self) -> Result<String, String> {
         let mut child = self.command.spawn().map_err(|err| {
             format!(
                 "Error: '{}'. Could not spawn command '{:?}'.",
                 err, self.command
             )
         })?;
+        // the id of the process is the id of its process group
+        self.process_id = child.id();
 
         if let Some(stdin) = self.stdin {
             child
@@ -139,6 +190,46 @@ mod tests {
         assert!(matches!(result, Err(x) if x.contains("Could not spawn command")));
     }
 
+    #[tokio::test]
+    async fn utest_cli_command_fails_on_timeout() {
+        let result = CliCommand::new("sleep")
+            .args(&["10"])
+            .timeout(std::time::Duration::from_millis(50))
+            .exec()
+            .await;
+        assert!(matches!(result, Err(x) if x.contains("timed out after 50 ms")));
+    }
+
+    #[tokio::test]
+    async fn utest_cli_command_kills_started_processes_on_timeout() {
+        let pid_file = tempfile::NamedTempFile::new().unwrap();
+        let script = format!("sleep 30 & echo $! > {}; wait", pid_file.path().display());
+        let result = CliCommand::new("sh")
+            .args(&["-c", &script])
+            .timeout(std::time::Duration::from_millis(500))
+            .exec()
+            .await;
+        assert!(matches!(result, Err(x) if x.contains("timed out after 500 ms")));
+
+        let pid = std::fs::read_to_string(pid_file.path()).unwrap();
+        let stat_path = format!("/proc/{}/stat", pid.trim());
+        // a killed process, which is not reaped yet, is a zombie
+        let is_running = || {
+            std::fs::read_to_string(&stat_path).is_ok_and(|stat| {
+                stat.rsplit(')')
+                    .next()
+                    .is_some_and(|s| !s.starts_with(" Z"))
+            })
+        };
+        for _ in 0..20 {
+            if !is_running() {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        panic!("The process started by the command is still running");
+    }
+
     #[tokio::test]
     async fn utest_cli_command_simple_input_output() {
         let result = CliCommand::new("tr")
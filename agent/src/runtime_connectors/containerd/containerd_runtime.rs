@@ -0,0 +1,654 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fmt::Display, path::PathBuf, str::FromStr};
+
+use async_trait::async_trait;
+
+use common::{
+    objects::{AgentName, ExecutionState, WorkloadInstanceName, WorkloadSpec},
+    std_extensions::UnreachableOption,
+};
+
+use crate::{
+    generic_polling_state_checker::GenericPollingStateChecker,
+    runtime_connectors::{
+        nerdctl_cli::NerdctlStartConfig, ResourceUsageCollector, ReusableWorkloadState,
+        RuntimeConnector, RuntimeError, RuntimeStateGetter, StateChecker,
+    },
+    workload_log_sink::WorkloadLogsConfig,
+    workload_state::WorkloadStateSender,
+};
+
+#[cfg(test)]
+use mockall_double::double;
+
+// [impl->swdd~containerd-uses-nerdctl~1]
+#[cfg_attr(test, double)]
+use crate::runtime_connectors::nerdctl_cli::NerdctlCli;
+
+use super::containerd_runtime_config::ContainerdRuntimeConfig;
+
+pub const CONTAINERD_RUNTIME_NAME: &str = "containerd";
+
+#[derive(Debug, Clone, Default)]
+pub struct ContainerdRuntime {
+    pub workload_logs: WorkloadLogsConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContainerdStateGetter {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContainerdWorkloadId {
+    pub id: String,
+}
+
+impl Display for ContainerdWorkloadId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.id)
+    }
+}
+
+impl FromStr for ContainerdWorkloadId {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(ContainerdWorkloadId { id: s.to_string() })
+    }
+}
+
+#[async_trait]
+// [impl->swdd~containerd-implements-runtime-state-getter~1]
+impl RuntimeStateGetter<ContainerdWorkloadId> for ContainerdStateGetter {
+    async fn get_state(&self, workload_id: &ContainerdWorkloadId) -> ExecutionState {
+        log::trace!("Getting the state for the workload '{}'", workload_id.id);
+
+        // [impl->swdd~containerd-state-getter-uses-nerdctl~1]
+        let exec_state = match NerdctlCli::get_state_by_id(workload_id.id.as_str()).await {
+            Ok(Some(state)) => state,
+            Ok(None) => ExecutionState::lost(),
+            Err(err) => {
+                log::warn!(
+                    "Could not get state of workload '{}': '{}'. Returning unknown.",
+                    workload_id.id,
+                    err
+                );
+                ExecutionState::unknown("Error getting state from containerd.")
+            }
+        };
+
+        log::trace!(
+            "Returning the state '{}' for the workload '{}'",
+            exec_state,
+            workload_id.id
+        );
+        exec_state
+    }
+
+    // [impl->swdd~containerd-state-getter-executes-health-command~1]
+    async fn exec_health_command(
+        &self,
+        workload_id: &ContainerdWorkloadId,
+        command: &[String],
+    ) -> Option<Result<(), String>> {
+        Some(NerdctlCli::exec_command(workload_id.id.as_str(), command).await)
+    }
+}
+
+impl ContainerdRuntime {
+    fn start_state_checker(
+        &self,
+        workload_id: &ContainerdWorkloadId,
+        workload_spec: WorkloadSpec,
+        update_state_tx: WorkloadStateSender,
+    ) -> GenericPollingStateChecker {
+        log::debug!(
+            "Starting the checker for the workload '{}' with internal id '{}'",
+            workload_spec.instance_name,
+            workload_id.id
+        );
+        GenericPollingStateChecker::start_checker(
+            &workload_spec,
+            workload_id.clone(),
+            update_state_tx,
+            ContainerdStateGetter {},
+        )
+    }
+
+    // [impl->swdd~containerd-follows-workload-logs~1]
+    fn mirror_logs(
+        &self,
+        workload_id: &ContainerdWorkloadId,
+        workload_spec: &WorkloadSpec,
+        only_new_lines: bool,
+    ) {
+        let instance_name = &workload_spec.instance_name;
+        let Some(sink) = self.workload_logs.sink_for(instance_name.workload_name()) else {
+            return;
+        };
+        match NerdctlCli::spawn_log_follower(&workload_id.id, only_new_lines) {
+            Ok(log_follower) => {
+                tokio::spawn(sink.forward(instance_name.clone(), log_follower));
+            }
+            Err(err) => log::warn!(
+                "Could not mirror the logs of workload '{}': '{}'",
+                instance_name.workload_name(),
+                err
+            ),
+        }
+    }
+}
+
+impl ResourceUsageCollector for ContainerdRuntime {}
+
+#[async_trait]
+// [impl->swdd~containerd-implements-runtime-connector~1]
+impl RuntimeConnector<ContainerdWorkloadId, GenericPollingStateChecker> for ContainerdRuntime {
+    // [impl->swdd~containerd-name-returns-containerd~1]
+    fn name(&self) -> String {
+        CONTAINERD_RUNTIME_NAME.to_string()
+    }
+
+    // [impl->swdd~containerd-list-of-existing-workloads-uses-labels~1]
+    async fn get_reusable_workloads(
+        &self,
+        agent_name: &AgentName,
+    ) -> Result<Vec<ReusableWorkloadState>, RuntimeError> {
+        let res = NerdctlCli::list_workload_names_by_label("agent", agent_name.get())
+            .await
+            .map_err(RuntimeError::List)?;
+
+        log::debug!("Found {} reusable workload(s): '{:?}'", res.len(), &res);
+
+        let mut workload_states = Vec::new();
+        for instance_name in res
+            .iter()
+            .filter_map(|x| WorkloadInstanceName::try_from(x.as_str()).ok())
+        {
+            let workload_id = self.get_workload_id(&instance_name).await?.id;
+            match NerdctlCli::get_state_by_id(&workload_id).await {
+                Ok(Some(execution_state)) => workload_states.push(ReusableWorkloadState::new(
+                    instance_name,
+                    execution_state,
+                    Some(workload_id),
+                )),
+                Ok(None) => {
+                    return Err(RuntimeError::List(format!(
+                        "Could not get execution state for workload '{}'",
+                        instance_name
+                    )))
+                }
+                Err(err) => return Err(RuntimeError::List(err)),
+            }
+        }
+        Ok(workload_states)
+    }
+
+    // [impl->swdd~containerd-create-workload-runs-workload~1]
+    async fn create_workload(
+        &self,
+        workload_spec: WorkloadSpec,
+        reusable_workload_id: Option<ContainerdWorkloadId>,
+        control_interface_path: Option<PathBuf>,
+        update_state_tx: WorkloadStateSender,
+    ) -> Result<(ContainerdWorkloadId, GenericPollingStateChecker), RuntimeError> {
+        let workload_cfg = ContainerdRuntimeConfig::try_from(&workload_spec)
+            .map_err(|err| RuntimeError::Create(err.into()))?;
+
+        let only_new_log_lines = reusable_workload_id.is_some();
+        let cli_result = match reusable_workload_id {
+            Some(workload_id) => {
+                let start_config = NerdctlStartConfig {
+                    general_options: workload_cfg.general_options,
+                    container_id: workload_id.id,
+                };
+                NerdctlCli::nerdctl_start(start_config, &workload_spec.instance_name.to_string())
+                    .await
+            }
+            None => {
+                NerdctlCli::nerdctl_run(
+                    workload_cfg.into(),
+                    &workload_spec.instance_name.to_string(),
+                    workload_spec.instance_name.agent_name(),
+                    control_interface_path,
+                )
+                .await
+            }
+        };
+
+        match cli_result {
+            Ok(workload_id) => {
+                log::debug!(
+                    "The workload '{}' has been created with internal id '{}'",
+                    workload_spec.instance_name,
+                    workload_id
+                );
+
+                let containerd_workload_id = ContainerdWorkloadId { id: workload_id };
+                self.mirror_logs(&containerd_workload_id, &workload_spec, only_new_log_lines);
+                let state_checker = self.start_state_checker(
+                    &containerd_workload_id,
+                    workload_spec,
+                    update_state_tx,
+                );
+
+                Ok((containerd_workload_id, state_checker))
+            }
+            Err(err) => {
+                // [impl->swdd~containerd-create-workload-deletes-failed-container~1]
+                log::debug!("Creating/starting container failed, cleaning up. Error: '{err}'");
+                match NerdctlCli::remove_workloads_by_id(&workload_spec.instance_name.to_string())
+                    .await
+                {
+                    Ok(()) => log::debug!("The broken container has been deleted successfully"),
+                    Err(e) => log::warn!(
+                        "Failed container cleanup after failed create. Error: '{}'",
+                        e
+                    ),
+                }
+
+                Err(RuntimeError::Create(err))
+            }
+        }
+    }
+
+    // [impl->swdd~containerd-get-workload-id-uses-label~1]
+    async fn get_workload_id(
+        &self,
+        instance_name: &WorkloadInstanceName,
+    ) -> Result<ContainerdWorkloadId, RuntimeError> {
+        let res =
+            NerdctlCli::list_workload_ids_by_label("name", instance_name.to_string().as_str())
+                .await
+                .map_err(RuntimeError::List)?;
+
+        if 1 == res.len() {
+            let id = res.first().unwrap_or_unreachable();
+            log::debug!("Found an id for workload '{}': '{}'", instance_name, id);
+            Ok(ContainerdWorkloadId { id: id.to_string() })
+        } else {
+            log::warn!(
+                "get_workload_id returned unexpected number of workloads {:?}",
+                res
+            );
+            Err(RuntimeError::List(
+                "Unexpected number of workloads".to_string(),
+            ))
+        }
+    }
+
+    async fn start_checker(
+        &self,
+        workload_id: &ContainerdWorkloadId,
+        workload_spec: WorkloadSpec,
+        update_state_tx: WorkloadStateSender,
+    ) -> Result<GenericPollingStateChecker, RuntimeError> {
+        // the lines written before are already mirrored by the previous agent run
+        self.mirror_logs(workload_id, &workload_spec, true);
+        Ok(self.start_state_checker(workload_id, workload_spec, update_state_tx))
+    }
+
+    // [impl->swdd~containerd-delete-workload-removes-workload~1]
+    async fn delete_workload(
+        &self,
+        workload_id: &ContainerdWorkloadId,
+    ) -> Result<(), RuntimeError> {
+        log::debug!("Deleting workload with id '{}'", workload_id.id);
+        NerdctlCli::remove_workloads_by_id(&workload_id.id)
+            .await
+            .map_err(RuntimeError::Delete)
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use common::objects::{
+        generate_test_workload_spec_with_param, AgentName, ExecutionState, WorkloadInstanceName,
+    };
+    use mockall::Sequence;
+
+    use super::NerdctlCli;
+    use super::{
+        ContainerdRuntime, ContainerdStateGetter, ContainerdWorkloadId, CONTAINERD_RUNTIME_NAME,
+    };
+    use crate::runtime_connectors::{RuntimeConnector, RuntimeError, RuntimeStateGetter};
+    use crate::test_helper::MOCKALL_CONTEXT_SYNC;
+    use crate::workload_log_sink::{WorkloadLogSinkKind, WorkloadLogsConfig};
+
+    const BUFFER_SIZE: usize = 20;
+
+    const AGENT_NAME: &str = "agent_x";
+    const WORKLOAD_1_NAME: &str = "workload1";
+
+    // [utest->swdd~containerd-name-returns-containerd~1]
+    #[test]
+    fn utest_name_containerd() {
+        let containerd_runtime = ContainerdRuntime::default();
+        assert_eq!(containerd_runtime.name(), "containerd".to_string());
+    }
+
+    // [utest->swdd~containerd-list-of-existing-workloads-uses-labels~1]
+    // [utest->swdd~containerd-get-workload-id-uses-label~1]
+    #[tokio::test]
+    async fn utest_get_reusable_workloads_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let list_workload_names_by_label_context =
+            NerdctlCli::list_workload_names_by_label_context();
+        list_workload_names_by_label_context
+            .expect()
+            .withf(|key, value| key == "agent" && value == "dummy_agent")
+            .return_const(Ok(vec![
+                "container1.hash.dummy_agent".to_string(),
+                "wrongcontainername".to_string(),
+            ]));
+
+        let list_workload_ids_by_label_context = NerdctlCli::list_workload_ids_by_label_context();
+        list_workload_ids_by_label_context
+            .expect()
+            .withf(|key, value| key == "name" && value == "container1.hash.dummy_agent")
+            .return_const(Ok(vec!["test_id".to_string()]));
+
+        let get_state_by_id_context = NerdctlCli::get_state_by_id_context();
+        get_state_by_id_context
+            .expect()
+            .return_const(Ok(Some(ExecutionState::succeeded())));
+
+        let containerd_runtime = ContainerdRuntime::default();
+        let res = containerd_runtime
+            .get_reusable_workloads(&AgentName::from("dummy_agent"))
+            .await
+            .unwrap();
+
+        assert_eq!(res.len(), 1);
+        assert_eq!(
+            res[0].workload_state.instance_name,
+            WorkloadInstanceName::try_from("container1.hash.dummy_agent").unwrap()
+        );
+        assert_eq!(res[0].workload_id, Some("test_id".to_string()));
+    }
+
+    #[tokio::test]
+    async fn utest_get_reusable_workloads_failed() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let context = NerdctlCli::list_workload_names_by_label_context();
+        context
+            .expect()
+            .return_const(Err("Simulated error".to_string()));
+
+        let containerd_runtime = ContainerdRuntime::default();
+
+        assert_eq!(
+            containerd_runtime
+                .get_reusable_workloads(&AgentName::from("dummy_agent"))
+                .await,
+            Err(RuntimeError::List("Simulated error".into()))
+        );
+    }
+
+    // [utest->swdd~containerd-create-workload-runs-workload~1]
+    #[tokio::test]
+    async fn utest_create_workload_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let run_context = NerdctlCli::nerdctl_run_context();
+        run_context.expect().return_const(Ok("test_id".into()));
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            CONTAINERD_RUNTIME_NAME.to_string(),
+        );
+        let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let containerd_runtime = ContainerdRuntime::default();
+        let (workload_id, _checker) = containerd_runtime
+            .create_workload(
+                workload_spec,
+                None,
+                Some(PathBuf::from("run_folder")),
+                state_change_tx,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(workload_id.id, "test_id".to_string());
+    }
+
+    // [utest->swdd~containerd-create-workload-runs-workload~1]
+    #[tokio::test]
+    async fn utest_create_workload_with_existing_workload_id_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let start_context = NerdctlCli::nerdctl_start_context();
+        start_context
+            .expect()
+            .returning(|start_config, _| Ok(start_config.container_id));
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            CONTAINERD_RUNTIME_NAME.to_string(),
+        );
+        let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let containerd_runtime = ContainerdRuntime::default();
+        let (workload_id, _checker) = containerd_runtime
+            .create_workload(
+                workload_spec,
+                Some(ContainerdWorkloadId {
+                    id: "test_id".into(),
+                }),
+                None,
+                state_change_tx,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(workload_id.id, "test_id".to_string());
+    }
+
+    // [utest->swdd~containerd-follows-workload-logs~1]
+    #[tokio::test]
+    async fn utest_create_workload_and_start_checker_mirror_logs() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let run_context = NerdctlCli::nerdctl_run_context();
+        run_context.expect().return_const(Ok("test_id".into()));
+
+        let mut seq = Sequence::new();
+        let log_follower_context = NerdctlCli::spawn_log_follower_context();
+        log_follower_context
+            .expect()
+            .with(
+                mockall::predicate::eq("test_id"),
+                mockall::predicate::eq(false),
+            )
+            .once()
+            .in_sequence(&mut seq)
+            .returning(|_, _| Err("simulated error".to_string()));
+        log_follower_context
+            .expect()
+            .with(
+                mockall::predicate::eq("test_id"),
+                mockall::predicate::eq(true),
+            )
+            .once()
+            .in_sequence(&mut seq)
+            .returning(|_, _| Err("simulated error".to_string()));
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            CONTAINERD_RUNTIME_NAME.to_string(),
+        );
+        let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let containerd_runtime = ContainerdRuntime {
+            workload_logs: WorkloadLogsConfig {
+                sink: WorkloadLogSinkKind::Journald,
+                ..Default::default()
+            },
+        };
+        let (workload_id, _checker) = containerd_runtime
+            .create_workload(workload_spec.clone(), None, None, state_change_tx.clone())
+            .await
+            .unwrap();
+
+        // a failing log follower does not prevent the workload from running
+        let res = containerd_runtime
+            .start_checker(&workload_id, workload_spec, state_change_tx)
+            .await;
+        assert!(res.is_ok());
+    }
+
+    // [utest->swdd~containerd-create-workload-deletes-failed-container~1]
+    #[tokio::test]
+    async fn utest_create_workload_run_failed_cleanup_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let run_context = NerdctlCli::nerdctl_run_context();
+        run_context
+            .expect()
+            .return_const(Err("nerdctl run failed".into()));
+
+        let remove_context = NerdctlCli::remove_workloads_by_id_context();
+        remove_context.expect().once().return_const(Ok(()));
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            CONTAINERD_RUNTIME_NAME.to_string(),
+        );
+        let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let containerd_runtime = ContainerdRuntime::default();
+        let res = containerd_runtime
+            .create_workload(workload_spec, None, None, state_change_tx)
+            .await;
+
+        assert!(matches!(res, Err(RuntimeError::Create(msg)) if msg == "nerdctl run failed"));
+    }
+
+    #[tokio::test]
+    async fn utest_get_workload_id_fails_on_multiple_ids() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let context = NerdctlCli::list_workload_ids_by_label_context();
+        context
+            .expect()
+            .return_const(Ok(vec!["test_id_1".to_string(), "test_id_2".to_string()]));
+
+        let containerd_runtime = ContainerdRuntime::default();
+        let res = containerd_runtime
+            .get_workload_id(&"container1.hash.dummy_agent".try_into().unwrap())
+            .await;
+
+        assert!(matches!(res, Err(RuntimeError::List(_))));
+    }
+
+    // [utest->swdd~containerd-delete-workload-removes-workload~1]
+    #[tokio::test]
+    async fn utest_delete_workload_failed() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let remove_context = NerdctlCli::remove_workloads_by_id_context();
+        remove_context
+            .expect()
+            .return_const(Err("simulated error".into()));
+
+        let containerd_runtime = ContainerdRuntime::default();
+        let res = containerd_runtime
+            .delete_workload(&ContainerdWorkloadId {
+                id: "test_id".into(),
+            })
+            .await;
+
+        assert_eq!(res, Err(RuntimeError::Delete("simulated error".into())));
+    }
+
+    // [utest->swdd~containerd-state-getter-uses-nerdctl~1]
+    #[tokio::test]
+    async fn utest_state_getter_returns_lost_state_for_missing_container() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let context = NerdctlCli::get_state_by_id_context();
+        context.expect().return_const(Ok(None));
+
+        let state = ContainerdStateGetter {}
+            .get_state(&ContainerdWorkloadId {
+                id: "test_id".into(),
+            })
+            .await;
+
+        assert_eq!(state, ExecutionState::lost());
+    }
+
+    // [utest->swdd~containerd-state-getter-uses-nerdctl~1]
+    #[tokio::test]
+    async fn utest_state_getter_returns_unknown_state_on_error() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let context = NerdctlCli::get_state_by_id_context();
+        context.expect().return_const(Err("simulated error".into()));
+
+        let state = ContainerdStateGetter {}
+            .get_state(&ContainerdWorkloadId {
+                id: "test_id".into(),
+            })
+            .await;
+
+        assert_eq!(
+            state,
+            ExecutionState::unknown("Error getting state from containerd.")
+        );
+    }
+
+    // [utest->swdd~containerd-state-getter-executes-health-command~1]
+    #[tokio::test]
+    async fn utest_state_getter_executes_health_command() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let context = NerdctlCli::exec_command_context();
+        context
+            .expect()
+            .withf(|workload_id, command| {
+                workload_id == "test_id" && command == ["pg_isready".to_string()]
+            })
+            .once()
+            .return_const(Ok(()));
+
+        let result = ContainerdStateGetter {}
+            .exec_health_command(
+                &ContainerdWorkloadId {
+                    id: "test_id".into(),
+                },
+                &["pg_isready".into()],
+            )
+            .await;
+
+        assert_eq!(result, Some(Ok(())));
+    }
+}
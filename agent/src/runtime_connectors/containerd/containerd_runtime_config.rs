@@ -0,0 +1,282 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use common::objects::{VolumeType, WorkloadSpec};
+
+use crate::runtime_connectors::nerdctl_cli::NerdctlRunConfig;
+
+use super::containerd_runtime::CONTAINERD_RUNTIME_NAME;
+
+#[derive(Debug, serde::Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerdRuntimeConfig {
+    #[serde(default)]
+    pub general_options: Vec<String>,
+    #[serde(default)]
+    pub command_options: Vec<String>,
+    pub image: String,
+    #[serde(default)]
+    pub command_args: Vec<String>,
+}
+
+impl From<ContainerdRuntimeConfig> for NerdctlRunConfig {
+    fn from(value: ContainerdRuntimeConfig) -> Self {
+        NerdctlRunConfig {
+            general_options: value.general_options,
+            command_options: value.command_options,
+            image: value.image,
+            command_args: value.command_args,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TryFromWorkloadSpecError(String);
+
+impl TryFrom<&WorkloadSpec> for ContainerdRuntimeConfig {
+    type Error = TryFromWorkloadSpecError;
+    fn try_from(workload_spec: &WorkloadSpec) -> Result<Self, Self::Error> {
+        if CONTAINERD_RUNTIME_NAME != workload_spec.runtime {
+            return Err(TryFromWorkloadSpecError(format!(
+                "Received a spec for the wrong runtime: '{}'",
+                workload_spec.runtime
+            )));
+        }
+        // [impl->swdd~containerd-rejects-registry-auth~1]
+        if workload_spec.registry_auth.is_some() {
+            return Err(TryFromWorkloadSpecError(format!(
+                "The field 'registryAuth' is not supported by the runtime '{}', log in to the registry on the host with 'nerdctl login' instead",
+                CONTAINERD_RUNTIME_NAME
+            )));
+        }
+        // [impl->swdd~containerd-rejects-networks~1]
+        if !workload_spec.networks.is_empty() {
+            return Err(TryFromWorkloadSpecError(format!(
+                "The field 'networks' is not supported by the runtime '{}', set the network in the command options instead",
+                CONTAINERD_RUNTIME_NAME
+            )));
+        }
+        // [impl->swdd~containerd-rejects-security-context~1]
+        if workload_spec.security_context.is_some() {
+            return Err(TryFromWorkloadSpecError(format!(
+                "The field 'securityContext' is not supported by the runtime '{}', set the security options in the command options instead",
+                CONTAINERD_RUNTIME_NAME
+            )));
+        }
+        let mut workload_cfg: ContainerdRuntimeConfig =
+            serde_yaml::from_str(workload_spec.runtime_config.as_str())
+                .map_err(|e| TryFromWorkloadSpecError(e.to_string()))?;
+
+        // [impl->swdd~containerd-maps-workload-spec-to-options~4]
+        if let Some(hostname) = &workload_spec.hostname {
+            workload_cfg
+                .command_options
+                .extend(["--hostname".to_owned(), hostname.clone()]);
+        }
+        for extra_host in &workload_spec.extra_hosts {
+            workload_cfg.command_options.extend([
+                "--add-host".to_owned(),
+                format!("{}:{}", extra_host.hostname, extra_host.ip),
+            ]);
+        }
+        for port in &workload_spec.ports {
+            workload_cfg
+                .command_options
+                .extend(["--publish".to_owned(), port.to_string()]);
+        }
+        for volume in &workload_spec.volumes {
+            let read_only = if volume.read_only { ":ro" } else { "" };
+            let mount_option = match volume.volume_type {
+                VolumeType::HostPath | VolumeType::Named => [
+                    "--volume".to_owned(),
+                    format!("{}:{}{}", volume.source, volume.mount_path, read_only),
+                ],
+                VolumeType::Tmpfs => [
+                    "--tmpfs".to_owned(),
+                    format!("{}{}", volume.mount_path, read_only),
+                ],
+            };
+            workload_cfg.command_options.extend(mount_option);
+        }
+        for (name, value) in workload_spec.env.iter().collect::<BTreeMap<_, _>>() {
+            workload_cfg
+                .command_options
+                .extend(["--env".to_owned(), format!("{name}={value}")]);
+        }
+        if let Some(resources) = &workload_spec.resources {
+            if let Some(cpus) = resources.cpus() {
+                workload_cfg
+                    .command_options
+                    .extend(["--cpus".to_owned(), cpus]);
+            }
+            if let Some(memory_limit) = resources.memory_limit {
+                workload_cfg
+                    .command_options
+                    .extend(["--memory".to_owned(), format!("{memory_limit}b")]);
+            }
+        }
+        Ok(workload_cfg)
+    }
+}
+
+impl From<TryFromWorkloadSpecError> for String {
+    fn from(value: TryFromWorkloadSpecError) -> Self {
+        value.0
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use common::objects::{
+        generate_test_workload_spec_with_param, ExtraHost, Network, ResourceLimits,
+        SecurityContext, Volume, VolumeType,
+    };
+
+    use super::ContainerdRuntimeConfig;
+    use crate::runtime_connectors::containerd::containerd_runtime::CONTAINERD_RUNTIME_NAME;
+
+    const AGENT_NAME: &str = "agent_x";
+    const WORKLOAD_1_NAME: &str = "workload1";
+
+    #[test]
+    fn utest_containerd_config_failure_missing_image() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            CONTAINERD_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.runtime_config = "something without an image".to_string();
+
+        assert!(ContainerdRuntimeConfig::try_from(&workload_spec).is_err());
+    }
+
+    #[test]
+    fn utest_containerd_config_failure_wrong_runtime() {
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            "podman".to_string(),
+        );
+
+        assert!(ContainerdRuntimeConfig::try_from(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~containerd-rejects-registry-auth~1]
+    #[test]
+    fn utest_containerd_config_failure_registry_auth_set() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            CONTAINERD_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.registry_auth = Some("{}".to_string());
+
+        assert!(ContainerdRuntimeConfig::try_from(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~containerd-rejects-networks~1]
+    #[test]
+    fn utest_containerd_config_failure_networks_set() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            CONTAINERD_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.networks = vec![Network {
+            name: "backend".to_string(),
+            aliases: vec![],
+        }];
+
+        assert!(ContainerdRuntimeConfig::try_from(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~containerd-rejects-security-context~1]
+    #[test]
+    fn utest_containerd_config_failure_security_context_set() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            CONTAINERD_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.security_context = Some(SecurityContext {
+            run_as_user: Some(1000),
+            ..Default::default()
+        });
+
+        assert!(ContainerdRuntimeConfig::try_from(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~containerd-maps-workload-spec-to-options~4]
+    #[test]
+    fn utest_containerd_config_success() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            CONTAINERD_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.runtime_config = "generalOptions: [\"--namespace=ankaios\"]\ncommandOptions: [\"--network=host\"]\nimage: alpine:latest\ncommandArgs: [\"sh\"]\n".to_string();
+        workload_spec.hostname = Some("vehicle-1".to_string());
+        workload_spec.extra_hosts = vec![ExtraHost {
+            hostname: "database".to_string(),
+            ip: "10.0.0.5".to_string(),
+        }];
+        workload_spec.volumes = vec![Volume {
+            volume_type: VolumeType::Named,
+            source: "data".to_string(),
+            mount_path: "/var/lib/data".to_string(),
+            read_only: true,
+        }];
+        workload_spec.env = HashMap::from([("LOG_LEVEL".to_string(), "debug".to_string())]);
+        workload_spec.resources = Some(ResourceLimits {
+            cpu_quota: Some(50),
+            memory_limit: Some(1024),
+        });
+
+        assert_eq!(
+            ContainerdRuntimeConfig::try_from(&workload_spec).unwrap(),
+            ContainerdRuntimeConfig {
+                general_options: vec!["--namespace=ankaios".to_string()],
+                command_options: vec![
+                    "--network=host".to_string(),
+                    "--hostname".to_string(),
+                    "vehicle-1".to_string(),
+                    "--add-host".to_string(),
+                    "database:10.0.0.5".to_string(),
+                    "--volume".to_string(),
+                    "data:/var/lib/data:ro".to_string(),
+                    "--env".to_string(),
+                    "LOG_LEVEL=debug".to_string(),
+                    "--cpus".to_string(),
+                    "0.50".to_string(),
+                    "--memory".to_string(),
+                    "1024b".to_string(),
+                ],
+                image: "alpine:latest".to_string(),
+                command_args: vec!["sh".to_string()],
+            }
+        );
+    }
+}
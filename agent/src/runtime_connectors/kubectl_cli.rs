@@ -0,0 +1,491 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeSet, HashMap};
+
+use common::objects::ExecutionState;
+#[cfg(test)]
+use mockall::automock;
+use serde::Deserialize;
+
+#[cfg_attr(test, mockall_double::double)]
+use crate::runtime_connectors::cli_command::CliCommand;
+
+const KUBECTL_CMD: &str = "kubectl";
+// The kinds of objects searched for when listing, checking or deleting the objects of a workload.
+// "all" covers the workload related kinds like pods, deployments, services or jobs.
+const MANAGED_KINDS: &str =
+    "all,configmaps,secrets,persistentvolumeclaims,serviceaccounts,ingresses";
+const POD_KIND: &str = "Pod";
+const FAILED_WAITING_REASONS: [&str; 5] = [
+    "CrashLoopBackOff",
+    "ErrImagePull",
+    "ImagePullBackOff",
+    "InvalidImageName",
+    "CreateContainerConfigError",
+];
+
+#[derive(Deserialize, Debug)]
+struct KubectlObjectList {
+    #[serde(default)]
+    items: Vec<KubectlObject>,
+}
+
+#[derive(Deserialize, Debug)]
+struct KubectlObject {
+    kind: String,
+    #[serde(default)]
+    metadata: KubectlObjectMetadata,
+    // The status differs for each kind and is only decoded for pods
+    #[serde(default)]
+    status: serde_json::Value,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct KubectlObjectMetadata {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    annotations: HashMap<String, String>,
+    deletion_timestamp: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct KubectlPodStatus {
+    #[serde(default)]
+    phase: String,
+    message: Option<String>,
+    reason: Option<String>,
+    #[serde(default)]
+    container_statuses: Vec<KubectlContainerStatus>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct KubectlContainerStatus {
+    #[serde(default)]
+    state: KubectlContainerState,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct KubectlContainerState {
+    waiting: Option<KubectlContainerWaitingState>,
+}
+
+#[derive(Deserialize, Debug)]
+struct KubectlContainerWaitingState {
+    reason: Option<String>,
+}
+
+impl KubectlPodStatus {
+    fn waiting_reason(&self) -> Option<&str> {
+        self.container_statuses
+            .iter()
+            .filter_map(|x| x.state.waiting.as_ref())
+            .find_map(|x| x.reason.as_deref())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum OrderedPodState {
+    Failed(String),
+    Starting(String),
+    Unknown(String),
+    Running,
+    Stopping,
+    Succeeded,
+}
+
+// [impl->swdd~kubernetes-state-getter-maps-state~1]
+fn to_pod_state(pod: &KubectlObject) -> OrderedPodState {
+    if pod.metadata.deletion_timestamp.is_some() {
+        return OrderedPodState::Stopping;
+    }
+    let status: KubectlPodStatus = serde_json::from_value(pod.status.clone()).unwrap_or_default();
+    let waiting_reason = status.waiting_reason();
+    if let Some(reason) = waiting_reason.filter(|x| FAILED_WAITING_REASONS.contains(x)) {
+        return OrderedPodState::Failed(format!("Pod '{}': {}", pod.metadata.name, reason));
+    }
+    match status.phase.as_str() {
+        "Pending" => OrderedPodState::Starting(waiting_reason.unwrap_or("Pending").to_owned()),
+        "Running" => OrderedPodState::Running,
+        "Succeeded" => OrderedPodState::Succeeded,
+        "Failed" => OrderedPodState::Failed(format!(
+            "Pod '{}': {}",
+            pod.metadata.name,
+            status
+                .message
+                .or(status.reason)
+                .unwrap_or_else(|| "Failed".to_owned())
+        )),
+        phase => {
+            log::trace!(
+                "Mapping the pod phase '{}' to the execution state 'ExecUnknown'",
+                phase
+            );
+            OrderedPodState::Unknown(phase.to_owned())
+        }
+    }
+}
+
+impl From<OrderedPodState> for ExecutionState {
+    fn from(value: OrderedPodState) -> Self {
+        match value {
+            OrderedPodState::Failed(message) => ExecutionState::failed(message),
+            OrderedPodState::Starting(reason) => ExecutionState::starting(reason),
+            OrderedPodState::Unknown(phase) => ExecutionState::unknown(phase),
+            OrderedPodState::Running => ExecutionState::running(),
+            OrderedPodState::Stopping => ExecutionState::stopping("stopping pod"),
+            OrderedPodState::Succeeded => ExecutionState::succeeded(),
+        }
+    }
+}
+
+fn selector(key: &str, value: &str) -> String {
+    format!("--selector={key}={value}")
+}
+
+pub struct KubectlCli {}
+
+#[cfg_attr(test, automock)]
+impl KubectlCli {
+    async fn list_objects_by_label(key: &str, value: &str) -> Result<Vec<KubectlObject>, String> {
+        let output = CliCommand::new(KUBECTL_CMD)
+            .args(&[
+                "get",
+                MANAGED_KINDS,
+                "--all-namespaces",
+                &selector(key, value),
+                "--output=json",
+            ])
+            .exec()
+            .await?;
+        let object_list: KubectlObjectList = serde_json::from_str(&output)
+            .map_err(|err| format!("Could not parse kubectl output: '{}'", err))?;
+        Ok(object_list.items)
+    }
+
+    pub async fn list_annotation_values_by_label(
+        key: &str,
+        value: &str,
+        annotation: &str,
+    ) -> Result<Vec<String>, String> {
+        log::trace!(
+            "Listing '{}' annotations for: '{}'='{}'",
+            annotation,
+            key,
+            value
+        );
+        // Several objects belong to the same workload, e.g., a deployment and its pods
+        Ok(Self::list_objects_by_label(key, value)
+            .await?
+            .into_iter()
+            .filter_map(|mut x| x.metadata.annotations.remove(annotation))
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect())
+    }
+
+    pub async fn apply(manifest: &str, namespace: Option<String>) -> Result<(), String> {
+        let mut args = vec!["apply".to_owned()];
+        if let Some(namespace) = namespace {
+            args.push(format!("--namespace={namespace}"));
+        }
+        args.push("--filename=-".to_owned());
+
+        CliCommand::new(KUBECTL_CMD)
+            .args(&args.iter().map(|x| &**x).collect::<Vec<&str>>())
+            .stdin(manifest.as_bytes())
+            .exec()
+            .await?;
+        Ok(())
+    }
+
+    // [impl->swdd~kubernetes-state-getter-uses-objects~1]
+    pub async fn get_state_by_label(
+        key: &str,
+        value: &str,
+    ) -> Result<Option<ExecutionState>, String> {
+        let objects = Self::list_objects_by_label(key, value).await?;
+        if objects.is_empty() {
+            return Ok(None);
+        }
+
+        // Manifests without pods, e.g., only config maps or services, are running as long as their objects exist
+        Ok(Some(
+            objects
+                .iter()
+                .filter(|x| x.kind == POD_KIND)
+                .map(to_pod_state)
+                .min()
+                .unwrap_or(OrderedPodState::Running)
+                .into(),
+        ))
+    }
+
+    // [impl->swdd~kubernetes-state-checker-watches-pods~1]
+    #[cfg_attr(test, allow(dead_code))]
+    pub fn spawn_pod_watcher(key: &str, value: &str) -> Result<tokio::process::Child, String> {
+        tokio::process::Command::new(KUBECTL_CMD)
+            .args([
+                "get",
+                "pods",
+                "--all-namespaces",
+                &selector(key, value),
+                "--watch",
+                "--output=name",
+            ])
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| format!("Could not execute '{}': '{}'", KUBECTL_CMD, err))
+    }
+
+    pub async fn delete_by_label(key: &str, value: &str) -> Result<(), String> {
+        CliCommand::new(KUBECTL_CMD)
+            .args(&[
+                "delete",
+                MANAGED_KINDS,
+                "--all-namespaces",
+                &selector(key, value),
+                "--ignore-not-found",
+                "--wait=false",
+            ])
+            .exec()
+            .await?;
+        Ok(())
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+// [utest->swdd~kubernetes-uses-kubectl~1]
+#[cfg(test)]
+mod tests {
+    use common::objects::ExecutionState;
+
+    use super::{KubectlCli, MANAGED_KINDS};
+    use crate::test_helper::MOCKALL_CONTEXT_SYNC;
+
+    const SAMPLE_ERROR_MESSAGE: &str = "error message";
+    const SAMPLE_MANIFEST: &str = "apiVersion: v1\nkind: Pod\n";
+
+    fn expect_get(output: Result<String, String>) {
+        super::CliCommand::new_expect(
+            "kubectl",
+            super::CliCommand::default()
+                .expect_args(&[
+                    "get",
+                    MANAGED_KINDS,
+                    "--all-namespaces",
+                    "--selector=ankaios.io/instance=hash",
+                    "--output=json",
+                ])
+                .exec_returns(output),
+        );
+    }
+
+    fn object_list(items: &[serde_json::Value]) -> String {
+        serde_json::json!({ "kind": "List", "items": items }).to_string()
+    }
+
+    fn pod(name: &str, status: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "kind": "Pod",
+            "metadata": {
+                "name": name,
+                "annotations": {"ankaios.io/instance-name": "workload_1.hash.agent_A"}
+            },
+            "status": status
+        })
+    }
+
+    #[tokio::test]
+    async fn utest_list_annotation_values_removes_duplicates() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        expect_get(Ok(object_list(&[
+            pod("pod_1", serde_json::json!({"phase": "Running"})),
+            pod("pod_2", serde_json::json!({"phase": "Running"})),
+            serde_json::json!({"kind": "ConfigMap", "metadata": {"name": "config"}}),
+        ])));
+
+        let res = KubectlCli::list_annotation_values_by_label(
+            "ankaios.io/instance",
+            "hash",
+            "ankaios.io/instance-name",
+        )
+        .await;
+
+        assert_eq!(res, Ok(vec!["workload_1.hash.agent_A".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn utest_list_annotation_values_broken_response() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        expect_get(Ok("broken".to_string()));
+
+        let res = KubectlCli::list_annotation_values_by_label(
+            "ankaios.io/instance",
+            "hash",
+            "ankaios.io/instance-name",
+        )
+        .await;
+
+        assert!(matches!(res, Err(msg) if msg.starts_with("Could not parse kubectl output")));
+    }
+
+    #[tokio::test]
+    async fn utest_apply_passes_manifest_via_stdin() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "kubectl",
+            super::CliCommand::default()
+                .expect_args(&["apply", "--namespace=ankaios", "--filename=-"])
+                .expect_stdin(SAMPLE_MANIFEST)
+                .exec_returns(Ok("pod/nginx created".to_string())),
+        );
+
+        let res = KubectlCli::apply(SAMPLE_MANIFEST, Some("ankaios".to_string())).await;
+
+        assert_eq!(res, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn utest_apply_fails() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "kubectl",
+            super::CliCommand::default()
+                .expect_args(&["apply", "--filename=-"])
+                .expect_stdin(SAMPLE_MANIFEST)
+                .exec_returns(Err(SAMPLE_ERROR_MESSAGE.to_string())),
+        );
+
+        let res = KubectlCli::apply(SAMPLE_MANIFEST, None).await;
+
+        assert_eq!(res, Err(SAMPLE_ERROR_MESSAGE.to_string()));
+    }
+
+    // [utest->swdd~kubernetes-state-getter-uses-objects~1]
+    // [utest->swdd~kubernetes-state-getter-maps-state~1]
+    #[tokio::test]
+    async fn utest_get_state_by_label_combines_pod_states() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        expect_get(Ok(object_list(&[
+            pod("pod_1", serde_json::json!({"phase": "Running"})),
+            pod(
+                "pod_2",
+                serde_json::json!({
+                    "phase": "Pending",
+                    "containerStatuses": [{"state": {"waiting": {"reason": "ContainerCreating"}}}]
+                }),
+            ),
+        ])));
+        expect_get(Ok(object_list(&[
+            pod("pod_1", serde_json::json!({"phase": "Succeeded"})),
+            pod(
+                "pod_2",
+                serde_json::json!({
+                    "phase": "Running",
+                    "containerStatuses": [{"state": {"waiting": {"reason": "CrashLoopBackOff"}}}]
+                }),
+            ),
+        ])));
+        expect_get(Ok(object_list(&[
+            pod("pod_1", serde_json::json!({"phase": "Succeeded"})),
+            pod("pod_2", serde_json::json!({"phase": "Succeeded"})),
+        ])));
+
+        assert_eq!(
+            KubectlCli::get_state_by_label("ankaios.io/instance", "hash").await,
+            Ok(Some(ExecutionState::starting("ContainerCreating")))
+        );
+        assert_eq!(
+            KubectlCli::get_state_by_label("ankaios.io/instance", "hash").await,
+            Ok(Some(ExecutionState::failed(
+                "Pod 'pod_2': CrashLoopBackOff"
+            )))
+        );
+        assert_eq!(
+            KubectlCli::get_state_by_label("ankaios.io/instance", "hash").await,
+            Ok(Some(ExecutionState::succeeded()))
+        );
+    }
+
+    // [utest->swdd~kubernetes-state-getter-uses-objects~1]
+    #[tokio::test]
+    async fn utest_get_state_by_label_without_pods() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        expect_get(Ok(object_list(&[serde_json::json!({
+            "kind": "Service",
+            "metadata": {"name": "nginx"},
+            "status": {"loadBalancer": {}}
+        })])));
+        expect_get(Ok(object_list(&[])));
+
+        assert_eq!(
+            KubectlCli::get_state_by_label("ankaios.io/instance", "hash").await,
+            Ok(Some(ExecutionState::running()))
+        );
+        assert_eq!(
+            KubectlCli::get_state_by_label("ankaios.io/instance", "hash").await,
+            Ok(None)
+        );
+    }
+
+    #[tokio::test]
+    async fn utest_delete_by_label_ignores_missing_objects() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "kubectl",
+            super::CliCommand::default()
+                .expect_args(&[
+                    "delete",
+                    MANAGED_KINDS,
+                    "--all-namespaces",
+                    "--selector=ankaios.io/instance=hash",
+                    "--ignore-not-found",
+                    "--wait=false",
+                ])
+                .exec_returns(Ok(String::new())),
+        );
+
+        let res = KubectlCli::delete_by_label("ankaios.io/instance", "hash").await;
+
+        assert_eq!(res, Ok(()));
+    }
+}
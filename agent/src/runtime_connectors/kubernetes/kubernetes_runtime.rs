@@ -0,0 +1,508 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, fmt::Display, path::PathBuf, str::FromStr};
+
+use async_trait::async_trait;
+
+use common::objects::{AgentName, ExecutionState, WorkloadInstanceName, WorkloadSpec};
+
+use crate::{
+    runtime_connectors::{
+        ResourceUsageCollector, ReusableWorkloadState, RuntimeConnector, RuntimeError,
+        RuntimeStateGetter, StateChecker,
+    },
+    workload_state::WorkloadStateSender,
+};
+
+#[cfg(test)]
+use mockall_double::double;
+
+// [impl->swdd~kubernetes-uses-kubectl~1]
+#[cfg_attr(test, double)]
+use crate::runtime_connectors::kubectl_cli::KubectlCli;
+
+use super::{
+    kubernetes_runtime_config::KubernetesRuntimeConfig,
+    kubernetes_state_checker::KubernetesStateChecker,
+};
+
+pub const KUBERNETES_RUNTIME_NAME: &str = "k8s";
+const AGENT_LABEL: &str = "ankaios.io/agent";
+pub(super) const INSTANCE_LABEL: &str = "ankaios.io/instance";
+const INSTANCE_NAME_ANNOTATION: &str = "ankaios.io/instance-name";
+// Label values are limited to 63 characters
+const INSTANCE_LABEL_LENGTH: usize = 32;
+
+#[derive(Debug, Clone)]
+pub struct KubernetesRuntime {}
+
+#[derive(Debug, Clone)]
+pub struct KubernetesStateGetter {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct KubernetesWorkloadId {
+    pub name: WorkloadInstanceName,
+}
+
+impl KubernetesWorkloadId {
+    pub(super) fn instance_label(&self) -> String {
+        let mut hash = sha256::digest(self.name.to_string());
+        hash.truncate(INSTANCE_LABEL_LENGTH);
+        hash
+    }
+}
+
+impl Display for KubernetesWorkloadId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl FromStr for KubernetesWorkloadId {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(KubernetesWorkloadId {
+            name: WorkloadInstanceName::try_from(s)?,
+        })
+    }
+}
+
+#[async_trait]
+// [impl->swdd~kubernetes-implements-runtime-state-getter~1]
+impl RuntimeStateGetter<KubernetesWorkloadId> for KubernetesStateGetter {
+    async fn get_state(&self, workload_id: &KubernetesWorkloadId) -> ExecutionState {
+        log::trace!("Getting the state for the workload '{}'", workload_id);
+
+        let exec_state =
+            match KubectlCli::get_state_by_label(INSTANCE_LABEL, &workload_id.instance_label())
+                .await
+            {
+                Ok(Some(state)) => state,
+                Ok(None) => ExecutionState::lost(),
+                Err(err) => {
+                    log::warn!(
+                        "Could not get state of workload '{}': '{}'. Returning unknown.",
+                        workload_id,
+                        err
+                    );
+                    ExecutionState::unknown("Error getting state from Kubernetes.")
+                }
+            };
+
+        log::trace!(
+            "Returning the state '{}' for the workload '{}'",
+            exec_state,
+            workload_id
+        );
+        exec_state
+    }
+}
+
+impl ResourceUsageCollector for KubernetesRuntime {}
+
+#[async_trait]
+// [impl->swdd~kubernetes-implements-runtime-connector~1]
+impl RuntimeConnector<KubernetesWorkloadId, KubernetesStateChecker> for KubernetesRuntime {
+    // [impl->swdd~kubernetes-name-returns-k8s~1]
+    fn name(&self) -> String {
+        KUBERNETES_RUNTIME_NAME.to_string()
+    }
+
+    // [impl->swdd~kubernetes-list-of-existing-workloads-uses-labels~1]
+    async fn get_reusable_workloads(
+        &self,
+        agent_name: &AgentName,
+    ) -> Result<Vec<ReusableWorkloadState>, RuntimeError> {
+        let res = KubectlCli::list_annotation_values_by_label(
+            AGENT_LABEL,
+            agent_name.get(),
+            INSTANCE_NAME_ANNOTATION,
+        )
+        .await
+        .map_err(RuntimeError::List)?;
+
+        log::debug!("Found {} reusable workload(s): '{:?}'", res.len(), &res);
+
+        let mut workload_states = Vec::new();
+        for workload_id in res
+            .iter()
+            .filter_map(|x| KubernetesWorkloadId::from_str(x).ok())
+        {
+            match KubectlCli::get_state_by_label(INSTANCE_LABEL, &workload_id.instance_label())
+                .await
+            {
+                Ok(Some(execution_state)) => workload_states.push(ReusableWorkloadState::new(
+                    workload_id.name.clone(),
+                    execution_state,
+                    Some(workload_id.to_string()),
+                )),
+                // The objects have been deleted in the meantime
+                Ok(None) => {}
+                Err(err) => return Err(RuntimeError::List(err)),
+            }
+        }
+        Ok(workload_states)
+    }
+
+    // [impl->swdd~kubernetes-create-workload-applies-manifest~1]
+    async fn create_workload(
+        &self,
+        workload_spec: WorkloadSpec,
+        _reusable_workload_id: Option<KubernetesWorkloadId>,
+        _control_interface_path: Option<PathBuf>,
+        update_state_tx: WorkloadStateSender,
+    ) -> Result<(KubernetesWorkloadId, KubernetesStateChecker), RuntimeError> {
+        let workload_cfg =
+            KubernetesRuntimeConfig::try_from(&workload_spec).map_err(RuntimeError::Create)?;
+
+        let workload_id = KubernetesWorkloadId {
+            name: workload_spec.instance_name.clone(),
+        };
+
+        // [impl->swdd~kubernetes-create-workload-adds-labels~1]
+        let labels = BTreeMap::from([
+            (
+                AGENT_LABEL.to_string(),
+                workload_spec.instance_name.agent_name().to_string(),
+            ),
+            (INSTANCE_LABEL.to_string(), workload_id.instance_label()),
+        ]);
+        let annotations = BTreeMap::from([(
+            INSTANCE_NAME_ANNOTATION.to_string(),
+            workload_spec.instance_name.to_string(),
+        )]);
+        let manifest = workload_cfg
+            .manifest_with_metadata(&labels, &annotations)
+            .map_err(RuntimeError::Create)?;
+
+        // Applying a manifest is idempotent, thus a reusable workload is just applied again
+        if let Err(err) = KubectlCli::apply(&manifest, workload_cfg.namespace).await {
+            // [impl->swdd~kubernetes-create-workload-deletes-failed-objects~1]
+            log::debug!("Applying the manifest failed, cleaning up. Error: '{err}'");
+            if let Err(e) =
+                KubectlCli::delete_by_label(INSTANCE_LABEL, &workload_id.instance_label()).await
+            {
+                log::warn!("Failed cleanup after failed create. Error: '{}'", e);
+            }
+            return Err(RuntimeError::Create(err));
+        }
+
+        log::debug!(
+            "The manifest of workload '{}' has been applied",
+            workload_spec.instance_name
+        );
+
+        let state_checker = self
+            .start_checker(&workload_id, workload_spec, update_state_tx)
+            .await?;
+
+        Ok((workload_id, state_checker))
+    }
+
+    // [impl->swdd~kubernetes-get-workload-id-uses-instance-name~1]
+    async fn get_workload_id(
+        &self,
+        instance_name: &WorkloadInstanceName,
+    ) -> Result<KubernetesWorkloadId, RuntimeError> {
+        Ok(KubernetesWorkloadId {
+            name: instance_name.clone(),
+        })
+    }
+
+    async fn start_checker(
+        &self,
+        workload_id: &KubernetesWorkloadId,
+        workload_spec: WorkloadSpec,
+        update_state_tx: WorkloadStateSender,
+    ) -> Result<KubernetesStateChecker, RuntimeError> {
+        log::debug!(
+            "Starting the checker for the workload '{}'",
+            workload_spec.instance_name,
+        );
+        let checker = KubernetesStateChecker::start_checker(
+            &workload_spec,
+            workload_id.clone(),
+            update_state_tx,
+            KubernetesStateGetter {},
+        );
+        Ok(checker)
+    }
+
+    // [impl->swdd~kubernetes-delete-workload-deletes-objects~1]
+    async fn delete_workload(
+        &self,
+        workload_id: &KubernetesWorkloadId,
+    ) -> Result<(), RuntimeError> {
+        log::debug!("Deleting workload '{}'", workload_id);
+        KubectlCli::delete_by_label(INSTANCE_LABEL, &workload_id.instance_label())
+            .await
+            .map_err(RuntimeError::Delete)
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::objects::{
+        generate_test_workload_spec_with_param, AgentName, ExecutionState, WorkloadInstanceName,
+    };
+
+    use super::KubectlCli;
+    use super::{
+        KubernetesRuntime, KubernetesStateGetter, KubernetesWorkloadId, INSTANCE_LABEL,
+        KUBERNETES_RUNTIME_NAME,
+    };
+    use crate::runtime_connectors::{RuntimeConnector, RuntimeError, RuntimeStateGetter};
+    use crate::test_helper::MOCKALL_CONTEXT_SYNC;
+
+    const BUFFER_SIZE: usize = 20;
+
+    const AGENT_NAME: &str = "agent_x";
+    const WORKLOAD_1_NAME: &str = "workload1";
+    const INSTANCE_NAME: &str = "workload1.hash.agent_x";
+
+    fn generate_workload_spec(runtime_config: &str) -> common::objects::WorkloadSpec {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            KUBERNETES_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.runtime_config = runtime_config.to_string();
+        workload_spec
+    }
+
+    fn workload_id() -> KubernetesWorkloadId {
+        KubernetesWorkloadId {
+            name: WorkloadInstanceName::try_from(INSTANCE_NAME).unwrap(),
+        }
+    }
+
+    // [utest->swdd~kubernetes-name-returns-k8s~1]
+    #[test]
+    fn utest_name_k8s() {
+        let kubernetes_runtime = KubernetesRuntime {};
+        assert_eq!(kubernetes_runtime.name(), "k8s".to_string());
+    }
+
+    #[test]
+    fn utest_workload_id_instance_label_fits_into_label_value() {
+        let instance_label = workload_id().instance_label();
+
+        assert_eq!(instance_label.len(), 32);
+        assert_eq!(
+            KubernetesWorkloadId {
+                name: WorkloadInstanceName::try_from("workload2.hash.agent_x").unwrap()
+            }
+            .instance_label()
+            .len(),
+            32
+        );
+        assert_ne!(
+            instance_label,
+            KubernetesWorkloadId {
+                name: WorkloadInstanceName::try_from("workload2.hash.agent_x").unwrap()
+            }
+            .instance_label()
+        );
+    }
+
+    // [utest->swdd~kubernetes-list-of-existing-workloads-uses-labels~1]
+    #[tokio::test]
+    async fn utest_get_reusable_workloads_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let list_context = KubectlCli::list_annotation_values_by_label_context();
+        list_context
+            .expect()
+            .withf(|key, value, annotation| {
+                key == "ankaios.io/agent"
+                    && value == "agent_x"
+                    && annotation == "ankaios.io/instance-name"
+            })
+            .return_const(Ok(vec![
+                INSTANCE_NAME.to_string(),
+                "wrongworkloadname".to_string(),
+            ]));
+
+        let expected_label = workload_id().instance_label();
+        let get_state_context = KubectlCli::get_state_by_label_context();
+        get_state_context
+            .expect()
+            .withf(move |key, value| key == INSTANCE_LABEL && value == expected_label)
+            .return_const(Ok(Some(ExecutionState::running())));
+
+        let kubernetes_runtime = KubernetesRuntime {};
+        let res = kubernetes_runtime
+            .get_reusable_workloads(&AgentName::from(AGENT_NAME))
+            .await
+            .unwrap();
+
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].workload_state.instance_name, workload_id().name);
+        assert_eq!(res[0].workload_id, Some(INSTANCE_NAME.to_string()));
+    }
+
+    #[tokio::test]
+    async fn utest_get_reusable_workloads_failed() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let context = KubectlCli::list_annotation_values_by_label_context();
+        context
+            .expect()
+            .return_const(Err("Simulated error".to_string()));
+
+        let kubernetes_runtime = KubernetesRuntime {};
+
+        assert_eq!(
+            kubernetes_runtime
+                .get_reusable_workloads(&AgentName::from(AGENT_NAME))
+                .await,
+            Err(RuntimeError::List("Simulated error".into()))
+        );
+    }
+
+    // [utest->swdd~kubernetes-create-workload-applies-manifest~1]
+    // [utest->swdd~kubernetes-create-workload-adds-labels~1]
+    #[tokio::test]
+    async fn utest_create_workload_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let apply_context = KubectlCli::apply_context();
+        apply_context
+            .expect()
+            .withf(|manifest, namespace| {
+                manifest.contains("ankaios.io/agent: agent_x")
+                    && manifest.contains("ankaios.io/instance:")
+                    && namespace == &Some("ankaios".to_string())
+            })
+            .once()
+            .return_const(Ok(()));
+
+        let workload_spec = generate_workload_spec("namespace: ankaios\nmanifest: \"kind: Pod\"");
+        let instance_name = workload_spec.instance_name.clone();
+        let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let kubernetes_runtime = KubernetesRuntime {};
+        let (workload_id, _checker) = kubernetes_runtime
+            .create_workload(workload_spec, None, None, state_change_tx)
+            .await
+            .unwrap();
+
+        assert_eq!(workload_id.name, instance_name);
+    }
+
+    // [utest->swdd~kubernetes-create-workload-deletes-failed-objects~1]
+    #[tokio::test]
+    async fn utest_create_workload_apply_failed_cleanup() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let apply_context = KubectlCli::apply_context();
+        apply_context
+            .expect()
+            .return_const(Err("kubectl apply failed".into()));
+
+        let delete_context = KubectlCli::delete_by_label_context();
+        delete_context.expect().once().return_const(Ok(()));
+
+        let workload_spec = generate_workload_spec("manifest: \"kind: Pod\"");
+        let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let kubernetes_runtime = KubernetesRuntime {};
+        let res = kubernetes_runtime
+            .create_workload(workload_spec, None, None, state_change_tx)
+            .await;
+
+        assert!(matches!(res, Err(RuntimeError::Create(msg)) if msg == "kubectl apply failed"));
+    }
+
+    #[tokio::test]
+    async fn utest_create_workload_invalid_config() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let workload_spec = generate_workload_spec("namespace: ankaios");
+        let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let kubernetes_runtime = KubernetesRuntime {};
+        let res = kubernetes_runtime
+            .create_workload(workload_spec, None, None, state_change_tx)
+            .await;
+
+        assert!(matches!(res, Err(RuntimeError::Create(_))));
+    }
+
+    // [utest->swdd~kubernetes-get-workload-id-uses-instance-name~1]
+    #[tokio::test]
+    async fn utest_get_workload_id_returns_instance_name() {
+        let kubernetes_runtime = KubernetesRuntime {};
+        let res = kubernetes_runtime
+            .get_workload_id(&WorkloadInstanceName::try_from(INSTANCE_NAME).unwrap())
+            .await;
+
+        assert_eq!(res, Ok(workload_id()));
+        assert_eq!(res.unwrap().to_string(), INSTANCE_NAME);
+    }
+
+    // [utest->swdd~kubernetes-delete-workload-deletes-objects~1]
+    #[tokio::test]
+    async fn utest_delete_workload_failed() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let expected_label = workload_id().instance_label();
+        let delete_context = KubectlCli::delete_by_label_context();
+        delete_context
+            .expect()
+            .withf(move |key, value| key == INSTANCE_LABEL && value == expected_label)
+            .return_const(Err("simulated error".into()));
+
+        let kubernetes_runtime = KubernetesRuntime {};
+        let res = kubernetes_runtime.delete_workload(&workload_id()).await;
+
+        assert_eq!(res, Err(RuntimeError::Delete("simulated error".into())));
+    }
+
+    #[tokio::test]
+    async fn utest_state_getter_returns_lost_state_for_missing_objects() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let context = KubectlCli::get_state_by_label_context();
+        context.expect().return_const(Ok(None));
+
+        let state = KubernetesStateGetter {}.get_state(&workload_id()).await;
+
+        assert_eq!(state, ExecutionState::lost());
+    }
+
+    #[tokio::test]
+    async fn utest_state_getter_returns_unknown_state_on_error() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let context = KubectlCli::get_state_by_label_context();
+        context.expect().return_const(Err("simulated error".into()));
+
+        let state = KubernetesStateGetter {}.get_state(&workload_id()).await;
+
+        assert_eq!(
+            state,
+            ExecutionState::unknown("Error getting state from Kubernetes.")
+        );
+    }
+}
@@ -0,0 +1,356 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use common::{objects::WorkloadSpec, std_extensions::UnreachableOption};
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+
+use super::kubernetes_runtime::KUBERNETES_RUNTIME_NAME;
+
+const METADATA: &str = "metadata";
+const LABELS: &str = "labels";
+const ANNOTATIONS: &str = "annotations";
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct KubernetesRuntimeConfig {
+    pub namespace: Option<String>,
+    pub manifest: String,
+}
+
+impl TryFrom<&WorkloadSpec> for KubernetesRuntimeConfig {
+    type Error = String;
+    fn try_from(workload_spec: &WorkloadSpec) -> Result<Self, Self::Error> {
+        if KUBERNETES_RUNTIME_NAME != workload_spec.runtime {
+            return Err(format!(
+                "Received a spec for the wrong runtime: '{}'",
+                workload_spec.runtime
+            ));
+        }
+        // [impl->swdd~kubernetes-rejects-container-fields~7]
+        if workload_spec.hostname.is_some()
+            || !workload_spec.extra_hosts.is_empty()
+            || !workload_spec.ports.is_empty()
+            || !workload_spec.volumes.is_empty()
+            || workload_spec.resources.is_some()
+            || workload_spec.health_check.is_some()
+            || workload_spec.registry_auth.is_some()
+            || !workload_spec.networks.is_empty()
+            || !workload_spec.env.is_empty()
+            || workload_spec.security_context.is_some()
+        {
+            return Err(format!(
+                "The fields 'hostname', 'extraHosts', 'ports', 'volumes', 'resources', 'healthCheck', 'registryAuth', 'networks', 'env' and 'securityContext' are not supported by the runtime '{}', set them in the Kubernetes manifest instead",
+                KUBERNETES_RUNTIME_NAME
+            ));
+        }
+        serde_yaml::from_str(workload_spec.runtime_config.as_str()).map_err(|e| e.to_string())
+    }
+}
+
+impl KubernetesRuntimeConfig {
+    // [impl->swdd~kubernetes-create-workload-adds-labels~1]
+    pub fn manifest_with_metadata(
+        &self,
+        labels: &BTreeMap<String, String>,
+        annotations: &BTreeMap<String, String>,
+    ) -> Result<String, String> {
+        let mut documents = Vec::new();
+        for document in serde_yaml::Deserializer::from_str(&self.manifest) {
+            let mut object = Value::deserialize(document)
+                .map_err(|err| format!("Could not parse the manifest: '{}'", err))?;
+            if object.is_null() {
+                continue;
+            }
+            if !object.is_mapping() {
+                return Err(
+                    "Each document of the manifest must contain a Kubernetes object".to_string(),
+                );
+            }
+            add_metadata(&mut object, labels, annotations);
+
+            // The objects created by controllers, e.g., the pods of a deployment, get the metadata of the templates
+            if let Some(spec) = object.get_mut("spec") {
+                if let Some(template) = spec.get_mut("template") {
+                    add_metadata(template, labels, annotations);
+                }
+                if let Some(job_template) = spec.get_mut("jobTemplate") {
+                    add_metadata(job_template, labels, annotations);
+                    if let Some(template) = job_template
+                        .get_mut("spec")
+                        .and_then(|x| x.get_mut("template"))
+                    {
+                        add_metadata(template, labels, annotations);
+                    }
+                }
+            }
+
+            documents.push(
+                serde_yaml::to_string(&object)
+                    .map_err(|err| format!("Could not serialize the manifest: '{}'", err))?,
+            );
+        }
+        Ok(documents.join("---\n"))
+    }
+}
+
+fn add_metadata(
+    object: &mut Value,
+    labels: &BTreeMap<String, String>,
+    annotations: &BTreeMap<String, String>,
+) {
+    let Some(object) = object.as_mapping_mut() else {
+        return;
+    };
+    let metadata = get_or_insert_mapping(object, METADATA);
+    for (key, entries) in [(LABELS, labels), (ANNOTATIONS, annotations)] {
+        let values = get_or_insert_mapping(metadata, key);
+        for (name, value) in entries {
+            values.insert(Value::from(name.as_str()), Value::from(value.as_str()));
+        }
+    }
+}
+
+fn get_or_insert_mapping<'a>(mapping: &'a mut Mapping, key: &str) -> &'a mut Mapping {
+    let value = mapping
+        .entry(Value::from(key))
+        .or_insert_with(|| Value::Mapping(Mapping::new()));
+    if !value.is_mapping() {
+        *value = Value::Mapping(Mapping::new());
+    }
+    value.as_mapping_mut().unwrap_or_unreachable()
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use common::objects::{
+        generate_test_workload_spec_with_param, HealthCheck, Network, ResourceLimits,
+        SecurityContext,
+    };
+
+    use super::KubernetesRuntimeConfig;
+    use crate::runtime_connectors::kubernetes::kubernetes_runtime::KUBERNETES_RUNTIME_NAME;
+
+    const AGENT_NAME: &str = "agent_x";
+    const WORKLOAD_1_NAME: &str = "workload1";
+    const MANIFEST: &str = r#"apiVersion: v1
+kind: ConfigMap
+metadata:
+  name: config
+  labels:
+    app: nginx
+---
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: nginx
+spec:
+  template:
+    metadata:
+      labels:
+        app: nginx
+---
+"#;
+
+    fn generate_workload_spec(runtime_config: &str) -> common::objects::WorkloadSpec {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            KUBERNETES_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.runtime_config = runtime_config.to_string();
+        workload_spec
+    }
+
+    #[test]
+    fn utest_kubernetes_config_success() {
+        let workload_spec = generate_workload_spec("namespace: ankaios\nmanifest: \"kind: Pod\"");
+
+        assert_eq!(
+            KubernetesRuntimeConfig::try_from(&workload_spec),
+            Ok(KubernetesRuntimeConfig {
+                namespace: Some("ankaios".to_string()),
+                manifest: "kind: Pod".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn utest_kubernetes_config_failure_missing_manifest() {
+        let workload_spec = generate_workload_spec("namespace: ankaios");
+
+        assert!(KubernetesRuntimeConfig::try_from(&workload_spec).is_err());
+    }
+
+    #[test]
+    fn utest_kubernetes_config_failure_wrong_runtime() {
+        let mut workload_spec = generate_workload_spec("manifest: \"kind: Pod\"");
+        workload_spec.runtime = "podman".to_string();
+
+        assert!(KubernetesRuntimeConfig::try_from(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~kubernetes-rejects-container-fields~7]
+    #[test]
+    fn utest_kubernetes_config_failure_health_check_set() {
+        let mut workload_spec = generate_workload_spec("manifest: \"kind: Pod\"");
+        workload_spec.health_check = Some(HealthCheck {
+            command: Some(vec!["pg_isready".into()]),
+            ..Default::default()
+        });
+
+        assert!(matches!(
+            KubernetesRuntimeConfig::try_from(&workload_spec),
+            Err(msg) if msg.contains("not supported by the runtime 'k8s'")
+        ));
+    }
+
+    // [utest->swdd~kubernetes-rejects-container-fields~7]
+    #[test]
+    fn utest_kubernetes_config_failure_resources_set() {
+        let mut workload_spec = generate_workload_spec("manifest: \"kind: Pod\"");
+        workload_spec.resources = Some(ResourceLimits {
+            cpu_quota: Some(50),
+            memory_limit: None,
+        });
+
+        assert!(matches!(
+            KubernetesRuntimeConfig::try_from(&workload_spec),
+            Err(msg) if msg.contains("not supported by the runtime 'k8s'")
+        ));
+    }
+
+    // [utest->swdd~kubernetes-rejects-container-fields~7]
+    #[test]
+    fn utest_kubernetes_config_failure_registry_auth_set() {
+        let mut workload_spec = generate_workload_spec("manifest: \"kind: Pod\"");
+        workload_spec.registry_auth = Some("{}".to_string());
+
+        assert!(matches!(
+            KubernetesRuntimeConfig::try_from(&workload_spec),
+            Err(msg) if msg.contains("not supported by the runtime 'k8s'")
+        ));
+    }
+
+    // [utest->swdd~kubernetes-rejects-container-fields~7]
+    #[test]
+    fn utest_kubernetes_config_failure_networks_set() {
+        let mut workload_spec = generate_workload_spec("manifest: \"kind: Pod\"");
+        workload_spec.networks = vec![Network {
+            name: "backend".to_string(),
+            aliases: vec![],
+        }];
+
+        assert!(matches!(
+            KubernetesRuntimeConfig::try_from(&workload_spec),
+            Err(msg) if msg.contains("not supported by the runtime 'k8s'")
+        ));
+    }
+
+    // [utest->swdd~kubernetes-rejects-container-fields~7]
+    #[test]
+    fn utest_kubernetes_config_failure_env_set() {
+        let mut workload_spec = generate_workload_spec("manifest: \"kind: Pod\"");
+        workload_spec.env = HashMap::from([("LOG_LEVEL".to_string(), "debug".to_string())]);
+
+        assert!(matches!(
+            KubernetesRuntimeConfig::try_from(&workload_spec),
+            Err(msg) if msg.contains("not supported by the runtime 'k8s'")
+        ));
+    }
+
+    // [utest->swdd~kubernetes-rejects-container-fields~7]
+    #[test]
+    fn utest_kubernetes_config_failure_security_context_set() {
+        let mut workload_spec = generate_workload_spec("manifest: \"kind: Pod\"");
+        workload_spec.security_context = Some(SecurityContext {
+            no_new_privileges: true,
+            ..Default::default()
+        });
+
+        assert!(matches!(
+            KubernetesRuntimeConfig::try_from(&workload_spec),
+            Err(msg) if msg.contains("not supported by the runtime 'k8s'")
+        ));
+    }
+
+    // [utest->swdd~kubernetes-create-workload-adds-labels~1]
+    #[test]
+    fn utest_manifest_with_metadata_adds_labels_to_objects_and_templates() {
+        let runtime_config = KubernetesRuntimeConfig {
+            namespace: None,
+            manifest: MANIFEST.to_string(),
+        };
+        let labels = BTreeMap::from([("ankaios.io/agent".to_string(), AGENT_NAME.to_string())]);
+        let annotations = BTreeMap::from([(
+            "ankaios.io/instance-name".to_string(),
+            "workload1.hash.agent_x".to_string(),
+        )]);
+
+        let manifest = runtime_config
+            .manifest_with_metadata(&labels, &annotations)
+            .unwrap();
+
+        let objects: Vec<serde_yaml::Value> = manifest
+            .split("---\n")
+            .map(|x| serde_yaml::from_str(x).unwrap())
+            .collect();
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0]["metadata"]["labels"]["app"], "nginx");
+        assert_eq!(
+            objects[0]["metadata"]["labels"]["ankaios.io/agent"],
+            AGENT_NAME
+        );
+        assert_eq!(
+            objects[0]["metadata"]["annotations"]["ankaios.io/instance-name"],
+            "workload1.hash.agent_x"
+        );
+        assert_eq!(
+            objects[1]["metadata"]["labels"]["ankaios.io/agent"],
+            AGENT_NAME
+        );
+        assert_eq!(
+            objects[1]["spec"]["template"]["metadata"]["labels"]["ankaios.io/agent"],
+            AGENT_NAME
+        );
+        assert_eq!(
+            objects[1]["spec"]["template"]["metadata"]["labels"]["app"],
+            "nginx"
+        );
+    }
+
+    #[test]
+    fn utest_manifest_with_metadata_rejects_non_objects() {
+        let runtime_config = KubernetesRuntimeConfig {
+            namespace: None,
+            manifest: "- just\n- a list\n".to_string(),
+        };
+
+        assert!(runtime_config
+            .manifest_with_metadata(&BTreeMap::new(), &BTreeMap::new())
+            .is_err());
+    }
+}
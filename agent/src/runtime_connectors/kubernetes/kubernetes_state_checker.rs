@@ -0,0 +1,292 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use common::objects::{ExecutionState, ExecutionStateEnum, WorkloadSpec};
+use tokio::{
+    io::{AsyncBufReadExt, BufReader, Lines},
+    process::ChildStdout,
+    task::JoinHandle,
+    time,
+};
+
+use crate::{
+    runtime_connectors::{RuntimeStateGetter, StateChecker},
+    workload_state::{WorkloadStateSender, WorkloadStateSenderInterface},
+};
+
+#[cfg_attr(test, mockall_double::double)]
+use crate::runtime_connectors::kubectl_cli::KubectlCli;
+
+use super::kubernetes_runtime::{KubernetesWorkloadId, INSTANCE_LABEL};
+
+// Only pods are watched, changes of other objects like config maps are found by the resync
+const RESYNC_INTERVAL: Duration = Duration::from_secs(30);
+const WATCH_RESTART_DELAY: Duration = Duration::from_secs(1);
+
+type PodEvents = Lines<BufReader<ChildStdout>>;
+
+// Returns None when the watch has ended, e.g., because the API server closed the connection
+async fn next_pod_event(pod_events: &mut Option<PodEvents>) -> Option<String> {
+    match pod_events {
+        Some(pod_events) => pod_events.next_line().await.ok().flatten(),
+        None => None,
+    }
+}
+
+#[derive(Debug)]
+pub struct KubernetesStateChecker {
+    workload_name: String,
+    task_handle: JoinHandle<()>,
+}
+
+#[async_trait]
+impl StateChecker<KubernetesWorkloadId> for KubernetesStateChecker {
+    // [impl->swdd~kubernetes-state-checker-watches-pods~1]
+    fn start_checker(
+        workload_spec: &WorkloadSpec,
+        workload_id: KubernetesWorkloadId,
+        workload_state_sender: WorkloadStateSender,
+        state_getter: impl RuntimeStateGetter<KubernetesWorkloadId>,
+    ) -> Self {
+        let instance_name = workload_spec.instance_name.clone();
+        let workload_name = instance_name.workload_name().to_owned();
+        let task_handle = tokio::spawn(async move {
+            let mut last_state = ExecutionState::unknown("Never received an execution state.");
+            let instance_label = workload_id.instance_label();
+            loop {
+                // The watch is started before getting the state, thus no change is missed
+                let mut pod_watcher =
+                    KubectlCli::spawn_pod_watcher(INSTANCE_LABEL, &instance_label)
+                        .map_err(|err| {
+                            log::warn!(
+                                "Could not watch the pods of workload '{}': '{}'",
+                                instance_name.workload_name(),
+                                err
+                            )
+                        })
+                        .ok();
+                let mut pod_events = pod_watcher
+                    .as_mut()
+                    .and_then(|pod_watcher| pod_watcher.stdout.take())
+                    .map(|stdout| BufReader::new(stdout).lines());
+                let mut resync = time::interval(RESYNC_INTERVAL);
+
+                loop {
+                    tokio::select! {
+                        biased;
+                        _ = resync.tick() => {}
+                        pod_event = next_pod_event(&mut pod_events) => {
+                            let Some(pod_event) = pod_event else {
+                                break;
+                            };
+                            log::trace!("Received the pod event '{}'", pod_event);
+                        }
+                    }
+
+                    let current_state = state_getter.get_state(&workload_id).await;
+                    if current_state != last_state {
+                        log::debug!(
+                            "The workload {} has changed its state to {:?}",
+                            instance_name.workload_name(),
+                            current_state
+                        );
+                        last_state = current_state.clone();
+
+                        workload_state_sender
+                            .report_workload_execution_state(&instance_name, current_state)
+                            .await;
+
+                        if last_state.state == ExecutionStateEnum::Removed {
+                            return;
+                        }
+                    }
+                }
+
+                log::debug!(
+                    "The watch of the pods of workload '{}' has ended, restarting it",
+                    instance_name.workload_name()
+                );
+                time::sleep(WATCH_RESTART_DELAY).await;
+            }
+        });
+
+        KubernetesStateChecker {
+            workload_name,
+            task_handle,
+        }
+    }
+
+    async fn stop_checker(self) {
+        drop(self);
+    }
+}
+
+impl Drop for KubernetesStateChecker {
+    fn drop(&mut self) {
+        self.task_handle.abort();
+        log::trace!("Over and out for workload '{}'", self.workload_name);
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use common::objects::{
+        generate_test_workload_spec_with_param, ExecutionState, WorkloadInstanceName,
+    };
+    use mockall::Sequence;
+
+    use super::{KubectlCli, KubernetesStateChecker, KubernetesWorkloadId, INSTANCE_LABEL};
+    use crate::runtime_connectors::{
+        kubernetes::kubernetes_runtime::KUBERNETES_RUNTIME_NAME, MockRuntimeStateGetter,
+        StateChecker,
+    };
+    use crate::test_helper::MOCKALL_CONTEXT_SYNC;
+
+    const BUFFER_SIZE: usize = 20;
+
+    const AGENT_NAME: &str = "agent_x";
+    const WORKLOAD_1_NAME: &str = "workload1";
+    const INSTANCE_NAME: &str = "workload1.hash.agent_x";
+
+    fn workload_id() -> KubernetesWorkloadId {
+        KubernetesWorkloadId {
+            name: WorkloadInstanceName::try_from(INSTANCE_NAME).unwrap(),
+        }
+    }
+
+    fn fake_pod_watcher(script: &str) -> tokio::process::Child {
+        tokio::process::Command::new("sh")
+            .args(["-c", script])
+            .stdout(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .unwrap()
+    }
+
+    // [utest->swdd~kubernetes-state-checker-watches-pods~1]
+    #[tokio::test]
+    async fn utest_state_checker_reports_state_on_pod_event() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let expected_label = workload_id().instance_label();
+        let watcher_context = KubectlCli::spawn_pod_watcher_context();
+        watcher_context
+            .expect()
+            .withf(move |key, value| key == INSTANCE_LABEL && value == expected_label)
+            .once()
+            .returning(|_, _| Ok(fake_pod_watcher("sleep 0.1; echo pod/nginx; sleep 10")));
+
+        let mut seq = Sequence::new();
+        let mut state_getter = MockRuntimeStateGetter::default();
+        state_getter
+            .expect_get_state()
+            .once()
+            .in_sequence(&mut seq)
+            .returning(|_| Box::pin(async { ExecutionState::starting("Pending") }));
+        state_getter
+            .expect_get_state()
+            .once()
+            .in_sequence(&mut seq)
+            .returning(|_| Box::pin(async { ExecutionState::running() }));
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            KUBERNETES_RUNTIME_NAME.to_string(),
+        );
+        let (state_sender, mut state_receiver) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let checker = KubernetesStateChecker::start_checker(
+            &workload_spec,
+            workload_id(),
+            state_sender,
+            state_getter,
+        );
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        checker.stop_checker().await;
+
+        let mut states = Vec::new();
+        while let Ok(state) = state_receiver.try_recv() {
+            states.push(state.execution_state);
+        }
+        assert_eq!(
+            states,
+            vec![
+                ExecutionState::starting("Pending"),
+                ExecutionState::running()
+            ]
+        );
+    }
+
+    // [utest->swdd~kubernetes-state-checker-watches-pods~1]
+    #[tokio::test]
+    async fn utest_state_checker_restarts_ended_watch() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let mut seq = Sequence::new();
+        let watcher_context = KubectlCli::spawn_pod_watcher_context();
+        watcher_context
+            .expect()
+            .once()
+            .in_sequence(&mut seq)
+            .returning(|_, _| Err("simulated error".to_string()));
+        watcher_context
+            .expect()
+            .once()
+            .in_sequence(&mut seq)
+            .returning(|_, _| Ok(fake_pod_watcher("sleep 10")));
+
+        let mut state_getter = MockRuntimeStateGetter::default();
+        state_getter
+            .expect_get_state()
+            .times(2)
+            .returning(|_| Box::pin(async { ExecutionState::running() }));
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            KUBERNETES_RUNTIME_NAME.to_string(),
+        );
+        let (state_sender, mut state_receiver) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let checker = KubernetesStateChecker::start_checker(
+            &workload_spec,
+            workload_id(),
+            state_sender,
+            state_getter,
+        );
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        checker.stop_checker().await;
+
+        // the unchanged state is reported only once
+        assert_eq!(
+            state_receiver.try_recv().unwrap().execution_state,
+            ExecutionState::running()
+        );
+        assert!(state_receiver.try_recv().is_err());
+    }
+}
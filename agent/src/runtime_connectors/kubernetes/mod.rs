@@ -0,0 +1,19 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+mod kubernetes_runtime;
+mod kubernetes_runtime_config;
+mod kubernetes_state_checker;
+pub use kubernetes_runtime::{KubernetesRuntime, KubernetesWorkloadId};
+pub use kubernetes_state_checker::KubernetesStateChecker;
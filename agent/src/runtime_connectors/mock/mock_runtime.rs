@@ -0,0 +1,354 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    path::PathBuf,
+    str::FromStr,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Instant,
+};
+
+use async_trait::async_trait;
+
+use common::objects::{AgentName, ExecutionState, WorkloadInstanceName, WorkloadSpec};
+
+use crate::{
+    generic_polling_state_checker::GenericPollingStateChecker,
+    runtime_connectors::{
+        ResourceUsageCollector, ReusableWorkloadState, RuntimeConnector, RuntimeError,
+        RuntimeStateGetter, StateChecker,
+    },
+    workload_state::WorkloadStateSender,
+};
+
+use super::mock_runtime_config::MockRuntimeConfig;
+
+pub const MOCK_RUNTIME_NAME: &str = "mock";
+
+#[derive(Debug)]
+struct MockWorkload {
+    instance_name: WorkloadInstanceName,
+    config: MockRuntimeConfig,
+    created_at: Instant,
+}
+
+impl MockWorkload {
+    fn current_state(&self) -> ExecutionState {
+        self.config.state_after(self.created_at.elapsed())
+    }
+}
+
+type MockWorkloads = Arc<Mutex<HashMap<String, MockWorkload>>>;
+
+fn lock(workloads: &MockWorkloads) -> MutexGuard<'_, HashMap<String, MockWorkload>> {
+    workloads.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+// The workloads are only simulated and shared between all clones of the runtime.
+#[derive(Debug, Clone, Default)]
+pub struct MockRuntime {
+    workloads: MockWorkloads,
+}
+
+#[derive(Debug, Clone)]
+pub struct MockStateGetter {
+    workloads: MockWorkloads,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockWorkloadId {
+    pub id: String,
+}
+
+impl Display for MockWorkloadId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.id)
+    }
+}
+
+impl FromStr for MockWorkloadId {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(MockWorkloadId { id: s.to_string() })
+    }
+}
+
+#[async_trait]
+// [impl->swdd~mock-runtime-scripted-behavior~1]
+impl RuntimeStateGetter<MockWorkloadId> for MockStateGetter {
+    async fn get_state(&self, workload_id: &MockWorkloadId) -> ExecutionState {
+        lock(&self.workloads)
+            .get(&workload_id.id)
+            .map(MockWorkload::current_state)
+            .unwrap_or(ExecutionState::removed())
+    }
+}
+
+impl ResourceUsageCollector for MockRuntime {}
+
+#[async_trait]
+// [impl->swdd~mock-runtime-implements-runtime-connector~1]
+impl RuntimeConnector<MockWorkloadId, GenericPollingStateChecker> for MockRuntime {
+    fn name(&self) -> String {
+        MOCK_RUNTIME_NAME.to_string()
+    }
+
+    async fn get_reusable_workloads(
+        &self,
+        agent_name: &AgentName,
+    ) -> Result<Vec<ReusableWorkloadState>, RuntimeError> {
+        Ok(lock(&self.workloads)
+            .iter()
+            .filter(|(_, workload)| workload.instance_name.agent_name() == agent_name.get())
+            .map(|(id, workload)| {
+                ReusableWorkloadState::new(
+                    workload.instance_name.clone(),
+                    workload.current_state(),
+                    Some(id.clone()),
+                )
+            })
+            .collect())
+    }
+
+    // [impl->swdd~mock-runtime-scripted-behavior~1]
+    async fn create_workload(
+        &self,
+        workload_spec: WorkloadSpec,
+        _reusable_workload_id: Option<MockWorkloadId>,
+        _control_interface_path: Option<PathBuf>,
+        update_state_tx: WorkloadStateSender,
+    ) -> Result<(MockWorkloadId, GenericPollingStateChecker), RuntimeError> {
+        let config = MockRuntimeConfig::try_from(&workload_spec)
+            .map_err(|err| RuntimeError::Create(err.into()))?;
+
+        if let Some(create_error) = &config.create_error {
+            return Err(RuntimeError::Create(create_error.clone()));
+        }
+
+        let workload_id = MockWorkloadId {
+            id: workload_spec.instance_name.to_string(),
+        };
+        lock(&self.workloads).insert(
+            workload_id.id.clone(),
+            MockWorkload {
+                instance_name: workload_spec.instance_name.clone(),
+                config,
+                created_at: Instant::now(),
+            },
+        );
+        log::debug!("The mock workload '{}' has been created", workload_id);
+
+        let state_checker = self
+            .start_checker(&workload_id, workload_spec, update_state_tx)
+            .await?;
+        Ok((workload_id, state_checker))
+    }
+
+    async fn get_workload_id(
+        &self,
+        instance_name: &WorkloadInstanceName,
+    ) -> Result<MockWorkloadId, RuntimeError> {
+        let id = instance_name.to_string();
+        if lock(&self.workloads).contains_key(&id) {
+            Ok(MockWorkloadId { id })
+        } else {
+            Err(RuntimeError::List(format!(
+                "No mock workload found for '{}'",
+                instance_name
+            )))
+        }
+    }
+
+    async fn start_checker(
+        &self,
+        workload_id: &MockWorkloadId,
+        workload_spec: WorkloadSpec,
+        update_state_tx: WorkloadStateSender,
+    ) -> Result<GenericPollingStateChecker, RuntimeError> {
+        Ok(GenericPollingStateChecker::start_checker(
+            &workload_spec,
+            workload_id.clone(),
+            update_state_tx,
+            MockStateGetter {
+                workloads: self.workloads.clone(),
+            },
+        ))
+    }
+
+    // [impl->swdd~mock-runtime-scripted-behavior~1]
+    async fn delete_workload(&self, workload_id: &MockWorkloadId) -> Result<(), RuntimeError> {
+        let mut workloads = lock(&self.workloads);
+        if let Some(delete_error) = workloads
+            .get(&workload_id.id)
+            .and_then(|workload| workload.config.delete_error.as_ref())
+        {
+            return Err(RuntimeError::Delete(delete_error.clone()));
+        }
+        workloads.remove(&workload_id.id);
+        Ok(())
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::objects::{
+        generate_test_workload_spec_with_param, AgentName, ExecutionState, WorkloadSpec,
+    };
+    use tokio::sync::mpsc::channel;
+
+    use super::{MockRuntime, MockStateGetter, MOCK_RUNTIME_NAME};
+    use crate::runtime_connectors::{RuntimeConnector, RuntimeError, RuntimeStateGetter};
+
+    const BUFFER_SIZE: usize = 20;
+    const AGENT_NAME: &str = "agent_x";
+    const WORKLOAD_1_NAME: &str = "workload1";
+
+    fn generate_test_mock_workload_spec(runtime_config: &str) -> WorkloadSpec {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            MOCK_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.runtime_config = runtime_config.to_string();
+        workload_spec
+    }
+
+    // [utest->swdd~mock-runtime-implements-runtime-connector~1]
+    #[test]
+    fn utest_name_mock() {
+        assert_eq!(MockRuntime::default().name(), "mock");
+    }
+
+    // [utest->swdd~mock-runtime-implements-runtime-connector~1]
+    // [utest->swdd~mock-runtime-scripted-behavior~1]
+    #[tokio::test]
+    async fn utest_mock_runtime_create_workload_reports_scripted_state() {
+        let runtime = MockRuntime::default();
+        let workload_spec = generate_test_mock_workload_spec("states: [{state: succeeded}]");
+        let (state_sender, mut state_receiver) = channel(BUFFER_SIZE);
+
+        let (workload_id, _checker) = runtime
+            .create_workload(workload_spec.clone(), None, None, state_sender)
+            .await
+            .unwrap();
+
+        assert_eq!(workload_id.id, workload_spec.instance_name.to_string());
+        let workload_state = state_receiver.recv().await.unwrap();
+        assert_eq!(workload_state.instance_name, workload_spec.instance_name);
+        assert_eq!(workload_state.execution_state, ExecutionState::succeeded());
+
+        assert_eq!(
+            runtime
+                .get_workload_id(&workload_spec.instance_name)
+                .await
+                .unwrap(),
+            workload_id
+        );
+        let reusable_workloads = runtime
+            .get_reusable_workloads(&AgentName::from(AGENT_NAME))
+            .await
+            .unwrap();
+        assert_eq!(reusable_workloads.len(), 1);
+        assert!(runtime
+            .get_reusable_workloads(&AgentName::from("other_agent"))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    // [utest->swdd~mock-runtime-scripted-behavior~1]
+    #[tokio::test]
+    async fn utest_mock_runtime_create_workload_scripted_error() {
+        let runtime = MockRuntime::default();
+        let workload_spec = generate_test_mock_workload_spec("createError: simulated error");
+        let (state_sender, _state_receiver) = channel(BUFFER_SIZE);
+
+        let result = runtime
+            .create_workload(workload_spec.clone(), None, None, state_sender)
+            .await;
+
+        assert!(matches!(result, Err(RuntimeError::Create(msg)) if msg == "simulated error"));
+        assert!(runtime
+            .get_workload_id(&workload_spec.instance_name)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn utest_mock_runtime_create_workload_invalid_config() {
+        let runtime = MockRuntime::default();
+        let workload_spec = generate_test_mock_workload_spec("states: not a list");
+        let (state_sender, _state_receiver) = channel(BUFFER_SIZE);
+
+        let result = runtime
+            .create_workload(workload_spec, None, None, state_sender)
+            .await;
+
+        assert!(matches!(result, Err(RuntimeError::Create(_))));
+    }
+
+    // [utest->swdd~mock-runtime-scripted-behavior~1]
+    #[tokio::test]
+    async fn utest_mock_runtime_delete_workload_reports_removed() {
+        let runtime = MockRuntime::default();
+        let workload_spec = generate_test_mock_workload_spec("");
+        let (state_sender, _state_receiver) = channel(BUFFER_SIZE);
+
+        let (workload_id, _checker) = runtime
+            .create_workload(workload_spec, None, None, state_sender)
+            .await
+            .unwrap();
+        let state_getter = MockStateGetter {
+            workloads: runtime.workloads.clone(),
+        };
+        assert_eq!(
+            state_getter.get_state(&workload_id).await,
+            ExecutionState::running()
+        );
+
+        assert_eq!(runtime.delete_workload(&workload_id).await, Ok(()));
+        assert_eq!(
+            state_getter.get_state(&workload_id).await,
+            ExecutionState::removed()
+        );
+    }
+
+    // [utest->swdd~mock-runtime-scripted-behavior~1]
+    #[tokio::test]
+    async fn utest_mock_runtime_delete_workload_scripted_error() {
+        let runtime = MockRuntime::default();
+        let workload_spec = generate_test_mock_workload_spec("deleteError: simulated error");
+        let (state_sender, _state_receiver) = channel(BUFFER_SIZE);
+
+        let (workload_id, _checker) = runtime
+            .create_workload(workload_spec, None, None, state_sender)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            runtime.delete_workload(&workload_id).await,
+            Err(RuntimeError::Delete("simulated error".to_string()))
+        );
+    }
+}
@@ -0,0 +1,230 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use common::objects::{ExecutionState, WorkloadSpec};
+
+use super::mock_runtime::MOCK_RUNTIME_NAME;
+
+// [impl->swdd~mock-runtime-scripted-behavior~1]
+#[derive(Debug, Default, Clone, serde::Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MockRuntimeConfig {
+    #[serde(default)]
+    pub states: Vec<MockStateStep>,
+    #[serde(default)]
+    pub create_error: Option<String>,
+    #[serde(default)]
+    pub delete_error: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MockStateStep {
+    pub state: MockState,
+    #[serde(default)]
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum MockState {
+    Starting,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl From<&MockState> for ExecutionState {
+    fn from(value: &MockState) -> Self {
+        match value {
+            MockState::Starting => ExecutionState::starting("Simulated by the mock runtime"),
+            MockState::Running => ExecutionState::running(),
+            MockState::Succeeded => ExecutionState::succeeded(),
+            MockState::Failed => ExecutionState::failed("Simulated by the mock runtime"),
+        }
+    }
+}
+
+impl MockRuntimeConfig {
+    // Every step is reported for its duration, the last step is kept forever.
+    pub fn state_after(&self, elapsed: Duration) -> ExecutionState {
+        let mut step_end = Duration::ZERO;
+        for step in &self.states {
+            step_end += Duration::from_millis(step.duration_ms);
+            if elapsed < step_end {
+                return (&step.state).into();
+            }
+        }
+
+        self.states
+            .last()
+            .map(|step| (&step.state).into())
+            .unwrap_or(ExecutionState::running())
+    }
+}
+
+#[derive(Debug)]
+pub struct TryFromWorkloadSpecError(String);
+
+impl TryFrom<&WorkloadSpec> for MockRuntimeConfig {
+    type Error = TryFromWorkloadSpecError;
+    fn try_from(workload_spec: &WorkloadSpec) -> Result<Self, Self::Error> {
+        if MOCK_RUNTIME_NAME != workload_spec.runtime {
+            return Err(TryFromWorkloadSpecError(format!(
+                "Received a spec for the wrong runtime: '{}'",
+                workload_spec.runtime
+            )));
+        }
+        if workload_spec.runtime_config.trim().is_empty() {
+            return Ok(MockRuntimeConfig::default());
+        }
+        serde_yaml::from_str(workload_spec.runtime_config.as_str())
+            .map_err(|err| TryFromWorkloadSpecError(err.to_string()))
+    }
+}
+
+impl From<TryFromWorkloadSpecError> for String {
+    fn from(value: TryFromWorkloadSpecError) -> Self {
+        value.0
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use common::objects::{generate_test_workload_spec_with_param, ExecutionState};
+
+    use super::{MockRuntimeConfig, MockState, MockStateStep, MOCK_RUNTIME_NAME};
+
+    const AGENT_NAME: &str = "agent_x";
+    const WORKLOAD_1_NAME: &str = "workload1";
+
+    // [utest->swdd~mock-runtime-scripted-behavior~1]
+    #[test]
+    fn utest_mock_runtime_config_from_workload_spec() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            MOCK_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.runtime_config = r#"
+            states:
+              - state: starting
+                durationMs: 100
+              - state: succeeded
+            deleteError: simulated error
+            "#
+        .to_string();
+
+        assert_eq!(
+            MockRuntimeConfig::try_from(&workload_spec).unwrap(),
+            MockRuntimeConfig {
+                states: vec![
+                    MockStateStep {
+                        state: MockState::Starting,
+                        duration_ms: 100
+                    },
+                    MockStateStep {
+                        state: MockState::Succeeded,
+                        duration_ms: 0
+                    }
+                ],
+                create_error: None,
+                delete_error: Some("simulated error".to_string()),
+            }
+        );
+    }
+
+    // [utest->swdd~mock-runtime-scripted-behavior~1]
+    #[test]
+    fn utest_mock_runtime_config_from_workload_spec_empty_config() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            MOCK_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.runtime_config = String::new();
+
+        assert_eq!(
+            MockRuntimeConfig::try_from(&workload_spec).unwrap(),
+            MockRuntimeConfig::default()
+        );
+    }
+
+    #[test]
+    fn utest_mock_runtime_config_from_workload_spec_wrong_runtime() {
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            "podman".to_string(),
+        );
+
+        assert!(MockRuntimeConfig::try_from(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~mock-runtime-scripted-behavior~1]
+    #[test]
+    fn utest_mock_runtime_config_state_after() {
+        let config = MockRuntimeConfig {
+            states: vec![
+                MockStateStep {
+                    state: MockState::Starting,
+                    duration_ms: 100,
+                },
+                MockStateStep {
+                    state: MockState::Running,
+                    duration_ms: 200,
+                },
+                MockStateStep {
+                    state: MockState::Failed,
+                    duration_ms: 0,
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.state_after(Duration::from_millis(50)),
+            ExecutionState::starting("Simulated by the mock runtime")
+        );
+        assert_eq!(
+            config.state_after(Duration::from_millis(250)),
+            ExecutionState::running()
+        );
+        assert_eq!(
+            config.state_after(Duration::from_secs(10)),
+            ExecutionState::failed("Simulated by the mock runtime")
+        );
+    }
+
+    // [utest->swdd~mock-runtime-scripted-behavior~1]
+    #[test]
+    fn utest_mock_runtime_config_state_after_without_states_is_running() {
+        assert_eq!(
+            MockRuntimeConfig::default().state_after(Duration::from_secs(1)),
+            ExecutionState::running()
+        );
+    }
+}
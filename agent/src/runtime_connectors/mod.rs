@@ -16,9 +16,28 @@ mod cli_command;
 
 mod podman_cli;
 
-pub(crate) mod podman;
+mod nerdctl_cli;
 
-pub(crate) mod podman_kube;
+mod kubectl_cli;
+
+mod systemd_cli;
+
+mod registry_auth_file;
+
+pub mod podman;
+
+pub mod podman_kube;
+
+pub mod containerd;
+
+pub mod kubernetes;
+
+pub mod systemd;
+
+pub mod plugin;
+
+#[cfg(any(test, feature = "mock_runtime"))]
+pub mod mock;
 
 mod runtime_connector;
 pub use runtime_connector::{
@@ -26,7 +45,10 @@ pub use runtime_connector::{
 };
 
 #[cfg(test)]
-pub use runtime_connector::test;
+pub(crate) use runtime_connector::test;
+
+mod resource_usage_collector;
+pub use resource_usage_collector::ResourceUsageCollector;
 
 mod runtime_facade;
 pub use runtime_facade::{GenericRuntimeFacade, RuntimeFacade};
@@ -35,7 +57,7 @@ pub use runtime_facade::{GenericRuntimeFacade, RuntimeFacade};
 pub use runtime_facade::MockRuntimeFacade;
 
 mod state_checker;
-pub use state_checker::{RuntimeStateGetter, StateChecker};
+pub use state_checker::{RuntimeStateGetter, StateChecker, WorkloadResourceUsage};
 
 #[cfg(test)]
 pub use state_checker::MockRuntimeStateGetter;
@@ -0,0 +1,561 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashMap, path::PathBuf};
+
+use common::objects::ExecutionState;
+#[cfg(test)]
+use mockall::automock;
+use serde::Deserialize;
+
+#[cfg_attr(test, mockall_double::double)]
+use crate::runtime_connectors::cli_command::CliCommand;
+
+const NERDCTL_CMD: &str = "nerdctl";
+const API_PIPES_MOUNT_POINT: &str = "/run/ankaios/control_interface";
+const NO_SUCH_CONTAINER: &str = "no such container";
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct NerdctlRunConfig {
+    pub general_options: Vec<String>,
+    pub command_options: Vec<String>,
+    pub image: String,
+    pub command_args: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct NerdctlStartConfig {
+    pub general_options: Vec<String>,
+    pub container_id: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct NerdctlContainerInfo {
+    #[serde(rename = "ID")]
+    id: String,
+    // The labels are provided as comma separated list of "key=value" entries
+    #[serde(default)]
+    labels: String,
+}
+
+impl NerdctlContainerInfo {
+    fn labels(&self) -> HashMap<&str, &str> {
+        self.labels
+            .split(',')
+            .filter_map(|label| label.split_once('='))
+            .collect()
+    }
+}
+
+// [impl->swdd~containerd-state-getter-maps-state~1]
+fn to_execution_state(status: &str, exit_code: i64) -> ExecutionState {
+    match status {
+        "created" | "restarting" => ExecutionState::starting(status),
+        "running" => ExecutionState::running(),
+        "exited" if exit_code == 0 => ExecutionState::succeeded(),
+        "exited" | "dead" => ExecutionState::failed(format!("Exit code: '{}'", exit_code)),
+        "removing" => ExecutionState::stopping(status),
+        state => {
+            log::trace!(
+                "Mapping the container state '{}' to the execution state 'ExecUnknown'",
+                state
+            );
+            ExecutionState::unknown(state)
+        }
+    }
+}
+
+fn parse_container_infos(output: &str) -> Result<Vec<NerdctlContainerInfo>, String> {
+    // nerdctl prints one JSON object per container
+    output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line)
+                .map_err(|err| format!("Could not parse nerdctl output: '{}'", err))
+        })
+        .collect()
+}
+
+pub struct NerdctlCli {}
+
+#[cfg_attr(test, automock)]
+impl NerdctlCli {
+    async fn list_containers_by_label(
+        key: &str,
+        value: &str,
+    ) -> Result<Vec<NerdctlContainerInfo>, String> {
+        let output = CliCommand::new(NERDCTL_CMD)
+            .args(&[
+                "ps",
+                "--all",
+                "--filter",
+                &format!("label={key}={value}"),
+                "--format={{json .}}",
+            ])
+            .exec()
+            .await?;
+        parse_container_infos(&output)
+    }
+
+    pub async fn list_workload_ids_by_label(key: &str, value: &str) -> Result<Vec<String>, String> {
+        log::debug!("Listing workload ids for: {}='{}'", key, value);
+        Ok(Self::list_containers_by_label(key, value)
+            .await?
+            .into_iter()
+            .map(|x| x.id)
+            .collect())
+    }
+
+    pub async fn list_workload_names_by_label(
+        key: &str,
+        value: &str,
+    ) -> Result<Vec<String>, String> {
+        log::trace!("Listing workload names for: '{}'='{}'", key, value);
+        Ok(Self::list_containers_by_label(key, value)
+            .await?
+            .iter()
+            .filter_map(|x| x.labels().get("name").map(ToString::to_string))
+            .collect())
+    }
+
+    pub async fn nerdctl_run(
+        mut run_config: NerdctlRunConfig,
+        workload_name: &str,
+        agent: &str,
+        control_interface_path: Option<PathBuf>,
+    ) -> Result<String, String> {
+        log::debug!(
+            "Creating the workload '{}' with image '{}'",
+            workload_name,
+            run_config.image
+        );
+
+        let mut args = run_config.general_options;
+
+        args.push("run".into());
+        args.push("--detach".into());
+
+        // [impl->swdd~containerd-create-workload-sets-optionally-container-name~1]
+        args.append(&mut vec!["--name".into(), workload_name.to_string()]);
+
+        args.append(&mut run_config.command_options);
+
+        // [impl->swdd~containerd-create-workload-mounts-fifo-files~1]
+        if let Some(path) = control_interface_path {
+            args.push(
+                [
+                    "--mount=type=bind,source=",
+                    &path.to_string_lossy(),
+                    ",destination=",
+                    API_PIPES_MOUNT_POINT,
+                ]
+                .concat(),
+            );
+        }
+
+        // [impl->swdd~containerd-create-workload-creates-labels~1]
+        args.push(format!("--label=name={workload_name}"));
+        args.push(format!("--label=agent={agent}"));
+        args.push(run_config.image);
+
+        args.append(&mut run_config.command_args);
+
+        log::debug!("The args are: '{:?}'", args);
+        let id = CliCommand::new(NERDCTL_CMD)
+            .args(&args.iter().map(|x| &**x).collect::<Vec<&str>>())
+            .exec()
+            .await?
+            .trim()
+            .to_string();
+        Ok(id)
+    }
+
+    pub async fn nerdctl_start(
+        start_config: NerdctlStartConfig,
+        workload_name: &str,
+    ) -> Result<String, String> {
+        log::debug!(
+            "Starting the workload '{}' with id '{}'",
+            workload_name,
+            start_config.container_id
+        );
+
+        let mut args = start_config.general_options;
+        args.push("start".into());
+        args.push(start_config.container_id.clone());
+
+        CliCommand::new(NERDCTL_CMD)
+            .args(&args.iter().map(|x| &**x).collect::<Vec<&str>>())
+            .exec()
+            .await?;
+        Ok(start_config.container_id)
+    }
+
+    // [impl->swdd~containerd-state-getter-uses-nerdctl~1]
+    pub async fn get_state_by_id(workload_id: &str) -> Result<Option<ExecutionState>, String> {
+        let output = match CliCommand::new(NERDCTL_CMD)
+            .args(&[
+                "inspect",
+                "--type=container",
+                "--format={{.State.Status}} {{.State.ExitCode}}",
+                workload_id,
+            ])
+            .exec()
+            .await
+        {
+            Ok(output) => output,
+            Err(err) if err.to_lowercase().contains(NO_SUCH_CONTAINER) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        let parse_error = || {
+            format!(
+                "Could not parse nerdctl inspect output: '{}'",
+                output.trim()
+            )
+        };
+        let mut values = output.split_whitespace();
+        let status = values.next().ok_or_else(parse_error)?;
+        let exit_code = values
+            .next()
+            .and_then(|x| x.parse::<i64>().ok())
+            .ok_or_else(parse_error)?;
+
+        Ok(Some(to_execution_state(status, exit_code)))
+    }
+
+    // [impl->swdd~containerd-state-getter-executes-health-command~1]
+    pub async fn exec_command(workload_id: &str, command: &[String]) -> Result<(), String> {
+        let mut args = vec!["exec", workload_id];
+        args.extend(command.iter().map(String::as_str));
+        CliCommand::new(NERDCTL_CMD).args(&args).exec().await?;
+        Ok(())
+    }
+
+    // [impl->swdd~containerd-follows-workload-logs~1]
+    #[cfg_attr(test, allow(dead_code))]
+    pub fn spawn_log_follower(
+        workload_id: &str,
+        only_new_lines: bool,
+    ) -> Result<tokio::process::Child, String> {
+        let mut args = vec!["logs", "--follow"];
+        if only_new_lines {
+            args.push("--tail=0");
+        }
+        args.push(workload_id);
+        tokio::process::Command::new(NERDCTL_CMD)
+            .args(args)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| format!("Could not execute '{}': '{}'", NERDCTL_CMD, err))
+    }
+
+    pub async fn remove_workloads_by_id(workload_id: &str) -> Result<(), String> {
+        // Containers may have "--rm" flag -> it can happen, that they already do not exist.
+        match CliCommand::new(NERDCTL_CMD)
+            .args(&["rm", "--force", workload_id])
+            .exec()
+            .await
+        {
+            Err(err) if !err.to_lowercase().contains(NO_SUCH_CONTAINER) => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+// [utest->swdd~containerd-uses-nerdctl~1]
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use common::objects::ExecutionState;
+
+    use super::{NerdctlCli, NerdctlRunConfig, NerdctlStartConfig};
+    use crate::test_helper::MOCKALL_CONTEXT_SYNC;
+
+    const SAMPLE_ERROR_MESSAGE: &str = "error message";
+    const PS_OUTPUT: &str = concat!(
+        r#"{"ID":"id_1","Image":"alpine:latest","Labels":"agent=agent_A,name=workload_1.hash.agent_A"}"#,
+        "\n",
+        r#"{"ID":"id_2","Image":"alpine:latest","Labels":"name=workload_2.hash.agent_A,agent=agent_A"}"#,
+        "\n"
+    );
+
+    fn expect_ps_by_label(output: Result<String, String>) {
+        super::CliCommand::new_expect(
+            "nerdctl",
+            super::CliCommand::default()
+                .expect_args(&[
+                    "ps",
+                    "--all",
+                    "--filter",
+                    "label=agent=agent_A",
+                    "--format={{json .}}",
+                ])
+                .exec_returns(output),
+        );
+    }
+
+    fn expect_inspect(output: Result<String, String>) {
+        super::CliCommand::new_expect(
+            "nerdctl",
+            super::CliCommand::default()
+                .expect_args(&[
+                    "inspect",
+                    "--type=container",
+                    "--format={{.State.Status}} {{.State.ExitCode}}",
+                    "test_id",
+                ])
+                .exec_returns(output),
+        );
+    }
+
+    #[tokio::test]
+    async fn utest_list_workload_ids_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+        expect_ps_by_label(Ok(PS_OUTPUT.into()));
+
+        let res = NerdctlCli::list_workload_ids_by_label("agent", "agent_A").await;
+
+        assert_eq!(res, Ok(vec!["id_1".into(), "id_2".into()]));
+    }
+
+    #[tokio::test]
+    async fn utest_list_workload_names_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+        expect_ps_by_label(Ok(PS_OUTPUT.into()));
+
+        let res = NerdctlCli::list_workload_names_by_label("agent", "agent_A").await;
+
+        assert_eq!(
+            res,
+            Ok(vec![
+                "workload_1.hash.agent_A".into(),
+                "workload_2.hash.agent_A".into()
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn utest_list_workload_names_broken_response() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+        expect_ps_by_label(Ok("broken".into()));
+
+        let res = NerdctlCli::list_workload_names_by_label("agent", "agent_A").await;
+
+        assert!(res.is_err());
+    }
+
+    // [utest->swdd~containerd-create-workload-sets-optionally-container-name~1]
+    // [utest->swdd~containerd-create-workload-mounts-fifo-files~1]
+    // [utest->swdd~containerd-create-workload-creates-labels~1]
+    #[tokio::test]
+    async fn utest_run_container_success_with_options() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "nerdctl",
+            super::CliCommand::default()
+                .expect_args(&[
+                    "--namespace=ankaios",
+                    "run",
+                    "--detach",
+                    "--name",
+                    "test_workload_name",
+                    "--network=host",
+                    "--mount=type=bind,source=/test/path,destination=/run/ankaios/control_interface",
+                    "--label=name=test_workload_name",
+                    "--label=agent=test_agent",
+                    "alpine:latest",
+                    "sleep",
+                    "1",
+                ])
+                .exec_returns(Ok("test_id\n".into())),
+        );
+
+        let run_config = NerdctlRunConfig {
+            general_options: vec!["--namespace=ankaios".into()],
+            command_options: vec!["--network=host".into()],
+            image: "alpine:latest".into(),
+            command_args: vec!["sleep".into(), "1".into()],
+        };
+        let res = NerdctlCli::nerdctl_run(
+            run_config,
+            "test_workload_name",
+            "test_agent",
+            Some(PathBuf::from("/test/path")),
+        )
+        .await;
+
+        assert_eq!(res, Ok("test_id".into()));
+    }
+
+    #[tokio::test]
+    async fn utest_start_container_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "nerdctl",
+            super::CliCommand::default()
+                .expect_args(&["start", "test_id"])
+                .exec_returns(Ok("test_id\n".into())),
+        );
+
+        let res = NerdctlCli::nerdctl_start(
+            NerdctlStartConfig {
+                general_options: vec![],
+                container_id: "test_id".into(),
+            },
+            "test_workload_name",
+        )
+        .await;
+
+        assert_eq!(res, Ok("test_id".into()));
+    }
+
+    // [utest->swdd~containerd-state-getter-maps-state~1]
+    #[tokio::test]
+    async fn utest_get_state_by_id_maps_states() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        for (output, expected_state) in [
+            ("created 0", ExecutionState::starting("created")),
+            ("restarting 0", ExecutionState::starting("restarting")),
+            ("running 0", ExecutionState::running()),
+            ("exited 0", ExecutionState::succeeded()),
+            ("exited 1", ExecutionState::failed("Exit code: '1'")),
+            ("removing 0", ExecutionState::stopping("removing")),
+            ("paused 0", ExecutionState::unknown("paused")),
+        ] {
+            super::CliCommand::reset();
+            expect_inspect(Ok(format!("{output}\n")));
+
+            let res = NerdctlCli::get_state_by_id("test_id").await;
+
+            assert_eq!(res, Ok(Some(expected_state)));
+        }
+    }
+
+    // [utest->swdd~containerd-state-getter-uses-nerdctl~1]
+    #[tokio::test]
+    async fn utest_get_state_by_id_missing_container() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+        expect_inspect(Err("no such container: test_id".into()));
+
+        let res = NerdctlCli::get_state_by_id("test_id").await;
+
+        assert_eq!(res, Ok(None));
+    }
+
+    // [utest->swdd~containerd-state-getter-uses-nerdctl~1]
+    #[tokio::test]
+    async fn utest_get_state_by_id_fails() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+        expect_inspect(Err(SAMPLE_ERROR_MESSAGE.into()));
+
+        let res = NerdctlCli::get_state_by_id("test_id").await;
+
+        assert_eq!(res, Err(SAMPLE_ERROR_MESSAGE.into()));
+    }
+
+    #[tokio::test]
+    async fn utest_remove_workloads_by_id_ignores_missing_container() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "nerdctl",
+            super::CliCommand::default()
+                .expect_args(&["rm", "--force", "test_id"])
+                .exec_returns(Err("No such container: test_id".into())),
+        );
+
+        let res = NerdctlCli::remove_workloads_by_id("test_id").await;
+
+        assert_eq!(res, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn utest_remove_workloads_by_id_fails() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "nerdctl",
+            super::CliCommand::default()
+                .expect_args(&["rm", "--force", "test_id"])
+                .exec_returns(Err(SAMPLE_ERROR_MESSAGE.into())),
+        );
+
+        let res = NerdctlCli::remove_workloads_by_id("test_id").await;
+
+        assert_eq!(res, Err(SAMPLE_ERROR_MESSAGE.into()));
+    }
+
+    // [utest->swdd~containerd-state-getter-executes-health-command~1]
+    #[tokio::test]
+    async fn utest_exec_command_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "nerdctl",
+            super::CliCommand::default()
+                .expect_args(&["exec", "test_id", "pg_isready", "-q"])
+                .exec_returns(Ok("".into())),
+        );
+
+        let res = NerdctlCli::exec_command("test_id", &["pg_isready".into(), "-q".into()]).await;
+
+        assert_eq!(res, Ok(()));
+    }
+
+    // [utest->swdd~containerd-state-getter-executes-health-command~1]
+    #[tokio::test]
+    async fn utest_exec_command_fails() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "nerdctl",
+            super::CliCommand::default()
+                .expect_args(&["exec", "test_id", "pg_isready"])
+                .exec_returns(Err(SAMPLE_ERROR_MESSAGE.into())),
+        );
+
+        let res = NerdctlCli::exec_command("test_id", &["pg_isready".into()]).await;
+
+        assert_eq!(res, Err(SAMPLE_ERROR_MESSAGE.into()));
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use common::objects::ExecutionState;
+use serde::{Deserialize, Serialize};
+
+// Every request is written as a single JSON object to the stdin of a new plugin process.
+// The plugin writes its response as a JSON object to stdout and exits with 0. A failed request
+// is signaled by a non-zero exit code with the error message on stderr.
+// [impl->swdd~plugin-runtime-connector-proxies-runtime-connector-calls~3]
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(
+    tag = "method",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum PluginRequest {
+    ListWorkloads {
+        agent_name: String,
+    },
+    CreateWorkload {
+        instance_name: String,
+        runtime_config: String,
+        reusable_workload_id: Option<String>,
+        control_interface_path: Option<PathBuf>,
+    },
+    GetWorkloadId {
+        instance_name: String,
+    },
+    GetState {
+        workload_id: String,
+    },
+    StopWorkload {
+        workload_id: String,
+        grace_period_secs: u32,
+    },
+    DeleteWorkload {
+        workload_id: String,
+    },
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWorkloadsResponse {
+    #[serde(default)]
+    pub workloads: Vec<PluginWorkload>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginWorkload {
+    pub instance_name: String,
+    pub workload_id: String,
+    #[serde(flatten)]
+    pub state: PluginWorkloadState,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadIdResponse {
+    pub workload_id: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginWorkloadState {
+    pub state: PluginExecutionState,
+    #[serde(default)]
+    pub additional_info: String,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PluginExecutionState {
+    Starting,
+    Running,
+    Succeeded,
+    Failed,
+    Stopping,
+    Removed,
+    Lost,
+    Unknown,
+}
+
+// [impl->swdd~plugin-runtime-connector-reports-plugin-states~1]
+impl From<PluginWorkloadState> for ExecutionState {
+    fn from(value: PluginWorkloadState) -> Self {
+        let additional_info = value.additional_info;
+        match value.state {
+            PluginExecutionState::Starting => ExecutionState::starting(additional_info),
+            PluginExecutionState::Running => ExecutionState::running(),
+            PluginExecutionState::Succeeded => ExecutionState::succeeded(),
+            PluginExecutionState::Failed => ExecutionState::failed(additional_info),
+            PluginExecutionState::Stopping => ExecutionState::stopping(additional_info),
+            PluginExecutionState::Removed => ExecutionState::removed(),
+            PluginExecutionState::Lost => ExecutionState::lost(),
+            PluginExecutionState::Unknown => ExecutionState::unknown(additional_info),
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::objects::ExecutionState;
+
+    use super::{
+        ListWorkloadsResponse, PluginExecutionState, PluginRequest, PluginWorkload,
+        PluginWorkloadState,
+    };
+
+    // [utest->swdd~plugin-runtime-connector-proxies-runtime-connector-calls~3]
+    #[test]
+    fn utest_plugin_request_is_encoded_as_json_object_with_method() {
+        let request = PluginRequest::CreateWorkload {
+            instance_name: "workload_1.1234.agent_A".to_string(),
+            runtime_config: "image: alpine".to_string(),
+            reusable_workload_id: None,
+            control_interface_path: Some("/tmp/control_interface".into()),
+        };
+
+        assert_eq!(
+            serde_json::to_value(request).unwrap(),
+            serde_json::json!({
+                "method": "createWorkload",
+                "instanceName": "workload_1.1234.agent_A",
+                "runtimeConfig": "image: alpine",
+                "reusableWorkloadId": null,
+                "controlInterfacePath": "/tmp/control_interface",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(PluginRequest::StopWorkload {
+                workload_id: "id_1".to_string(),
+                grace_period_secs: 10,
+            })
+            .unwrap(),
+            serde_json::json!({
+                "method": "stopWorkload",
+                "workloadId": "id_1",
+                "gracePeriodSecs": 10,
+            })
+        );
+    }
+
+    // [utest->swdd~plugin-runtime-connector-proxies-runtime-connector-calls~3]
+    #[test]
+    fn utest_list_workloads_response_is_decoded() {
+        let response: ListWorkloadsResponse = serde_json::from_str(
+            r#"{"workloads": [{"instanceName": "workload_1.1234.agent_A", "workloadId": "id_1", "state": "failed", "additionalInfo": "exit code 1"}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            response,
+            ListWorkloadsResponse {
+                workloads: vec![PluginWorkload {
+                    instance_name: "workload_1.1234.agent_A".to_string(),
+                    workload_id: "id_1".to_string(),
+                    state: PluginWorkloadState {
+                        state: PluginExecutionState::Failed,
+                        additional_info: "exit code 1".to_string(),
+                    },
+                }],
+            }
+        );
+    }
+
+    // [utest->swdd~plugin-runtime-connector-reports-plugin-states~1]
+    #[test]
+    fn utest_plugin_workload_state_is_converted_to_execution_state() {
+        let state = |state, additional_info: &str| PluginWorkloadState {
+            state,
+            additional_info: additional_info.to_string(),
+        };
+
+        assert_eq!(
+            ExecutionState::from(state(PluginExecutionState::Running, "")),
+            ExecutionState::running()
+        );
+        assert_eq!(
+            ExecutionState::from(state(PluginExecutionState::Failed, "exit code 1")),
+            ExecutionState::failed("exit code 1")
+        );
+        assert_eq!(
+            ExecutionState::from(state(PluginExecutionState::Removed, "")),
+            ExecutionState::removed()
+        );
+    }
+}
@@ -0,0 +1,454 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fmt::Display, path::PathBuf, str::FromStr, time::Duration};
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize};
+
+use common::objects::{AgentName, ExecutionState, WorkloadInstanceName, WorkloadSpec};
+
+use crate::{
+    generic_polling_state_checker::GenericPollingStateChecker,
+    runtime_connectors::{
+        cli_command::CliCommand, ResourceUsageCollector, ReusableWorkloadState, RuntimeConnector,
+        RuntimeError, RuntimeStateGetter, StateChecker,
+    },
+    workload_state::WorkloadStateSender,
+};
+
+use super::plugin_protocol::{
+    ListWorkloadsResponse, PluginRequest, PluginWorkloadState, WorkloadIdResponse,
+};
+
+const DEFAULT_PLUGIN_TIMEOUT_SECS: u64 = 30;
+
+// [impl->swdd~agent-supports-runtime-plugins~2]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RuntimePluginConfig {
+    pub path: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default = "default_plugin_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for RuntimePluginConfig {
+    fn default() -> Self {
+        RuntimePluginConfig {
+            path: String::new(),
+            args: Vec::new(),
+            timeout_secs: DEFAULT_PLUGIN_TIMEOUT_SECS,
+        }
+    }
+}
+
+fn default_plugin_timeout_secs() -> u64 {
+    DEFAULT_PLUGIN_TIMEOUT_SECS
+}
+
+impl RuntimePluginConfig {
+    async fn call<T: DeserializeOwned>(&self, request: PluginRequest) -> Result<T, String> {
+        let output = self.call_ignoring_response(request).await?;
+        serde_json::from_str(&output).map_err(|err| {
+            format!(
+                "Could not decode the response of the runtime plugin '{}': '{}'",
+                self.path, err
+            )
+        })
+    }
+
+    async fn call_ignoring_response(&self, request: PluginRequest) -> Result<String, String> {
+        let request = serde_json::to_string(&request)
+            .map_err(|err| format!("Could not encode the runtime plugin request: '{}'", err))?;
+        let args: Vec<&str> = self.args.iter().map(String::as_str).collect();
+        // the plugins are third party executables, a hanging plugin must not block the workload forever
+        // [impl->swdd~plugin-runtime-connector-proxies-runtime-connector-calls~3]
+        CliCommand::new(&self.path)
+            .args(&args)
+            .stdin(request.as_bytes())
+            .timeout(Duration::from_secs(self.timeout_secs))
+            .exec()
+            .await
+    }
+}
+
+// Proxies the calls of the runtime connector trait to an external plugin executable,
+// so that runtimes can be added without changing the agent.
+#[derive(Debug, Clone)]
+pub struct PluginRuntimeConnector {
+    name: String,
+    plugin: RuntimePluginConfig,
+}
+
+impl PluginRuntimeConnector {
+    pub fn new(name: String, plugin: RuntimePluginConfig) -> Self {
+        PluginRuntimeConnector { name, plugin }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PluginStateGetter {
+    plugin: RuntimePluginConfig,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PluginWorkloadId {
+    pub id: String,
+}
+
+impl Display for PluginWorkloadId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.id)
+    }
+}
+
+impl FromStr for PluginWorkloadId {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(PluginWorkloadId { id: s.to_string() })
+    }
+}
+
+#[async_trait]
+// [impl->swdd~plugin-runtime-connector-reports-plugin-states~1]
+impl RuntimeStateGetter<PluginWorkloadId> for PluginStateGetter {
+    async fn get_state(&self, workload_id: &PluginWorkloadId) -> ExecutionState {
+        log::trace!("Getting the state for the workload '{}'", workload_id);
+
+        match self
+            .plugin
+            .call::<PluginWorkloadState>(PluginRequest::GetState {
+                workload_id: workload_id.id.clone(),
+            })
+            .await
+        {
+            Ok(state) => state.into(),
+            Err(err) => {
+                log::warn!(
+                    "Could not get state of workload '{}': '{}'. Returning unknown.",
+                    workload_id,
+                    err
+                );
+                ExecutionState::unknown("Error getting state from the runtime plugin.")
+            }
+        }
+    }
+}
+
+impl ResourceUsageCollector for PluginRuntimeConnector {}
+
+#[async_trait]
+// [impl->swdd~plugin-runtime-connector-proxies-runtime-connector-calls~3]
+impl RuntimeConnector<PluginWorkloadId, GenericPollingStateChecker> for PluginRuntimeConnector {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn get_reusable_workloads(
+        &self,
+        agent_name: &AgentName,
+    ) -> Result<Vec<ReusableWorkloadState>, RuntimeError> {
+        let response: ListWorkloadsResponse = self
+            .plugin
+            .call(PluginRequest::ListWorkloads {
+                agent_name: agent_name.get().to_owned(),
+            })
+            .await
+            .map_err(RuntimeError::List)?;
+
+        Ok(response
+            .workloads
+            .into_iter()
+            .filter_map(|workload| {
+                match WorkloadInstanceName::try_from(workload.instance_name.as_str()) {
+                    Ok(instance_name) => Some(ReusableWorkloadState::new(
+                        instance_name,
+                        workload.state.into(),
+                        Some(workload.workload_id),
+                    )),
+                    Err(err) => {
+                        log::warn!(
+                            "Ignoring the workload '{}' listed by the runtime '{}': '{}'",
+                            workload.instance_name,
+                            self.name,
+                            err
+                        );
+                        None
+                    }
+                }
+            })
+            .collect())
+    }
+
+    async fn create_workload(
+        &self,
+        workload_spec: WorkloadSpec,
+        reusable_workload_id: Option<PluginWorkloadId>,
+        control_interface_path: Option<PathBuf>,
+        update_state_tx: WorkloadStateSender,
+    ) -> Result<(PluginWorkloadId, GenericPollingStateChecker), RuntimeError> {
+        let response: WorkloadIdResponse = self
+            .plugin
+            .call(PluginRequest::CreateWorkload {
+                instance_name: workload_spec.instance_name.to_string(),
+                runtime_config: workload_spec.runtime_config.clone(),
+                reusable_workload_id: reusable_workload_id.map(|workload_id| workload_id.id),
+                control_interface_path,
+            })
+            .await
+            .map_err(RuntimeError::Create)?;
+
+        let workload_id = PluginWorkloadId {
+            id: response.workload_id,
+        };
+        log::debug!(
+            "The runtime '{}' created the workload '{}' with id '{}'",
+            self.name,
+            workload_spec.instance_name,
+            workload_id
+        );
+
+        let state_checker = self
+            .start_checker(&workload_id, workload_spec, update_state_tx)
+            .await?;
+        Ok((workload_id, state_checker))
+    }
+
+    async fn get_workload_id(
+        &self,
+        instance_name: &WorkloadInstanceName,
+    ) -> Result<PluginWorkloadId, RuntimeError> {
+        let response: WorkloadIdResponse = self
+            .plugin
+            .call(PluginRequest::GetWorkloadId {
+                instance_name: instance_name.to_string(),
+            })
+            .await
+            .map_err(RuntimeError::List)?;
+        Ok(PluginWorkloadId {
+            id: response.workload_id,
+        })
+    }
+
+    async fn start_checker(
+        &self,
+        workload_id: &PluginWorkloadId,
+        workload_spec: WorkloadSpec,
+        update_state_tx: WorkloadStateSender,
+    ) -> Result<GenericPollingStateChecker, RuntimeError> {
+        Ok(GenericPollingStateChecker::start_checker(
+            &workload_spec,
+            workload_id.clone(),
+            update_state_tx,
+            PluginStateGetter {
+                plugin: self.plugin.clone(),
+            },
+        ))
+    }
+
+    async fn stop_workload(
+        &self,
+        workload_id: &PluginWorkloadId,
+        grace_period_secs: u32,
+    ) -> Result<(), RuntimeError> {
+        self.plugin
+            .call_ignoring_response(PluginRequest::StopWorkload {
+                workload_id: workload_id.id.clone(),
+                grace_period_secs,
+            })
+            .await
+            .map_err(RuntimeError::Delete)?;
+        Ok(())
+    }
+
+    async fn delete_workload(&self, workload_id: &PluginWorkloadId) -> Result<(), RuntimeError> {
+        self.plugin
+            .call_ignoring_response(PluginRequest::DeleteWorkload {
+                workload_id: workload_id.id.clone(),
+            })
+            .await
+            .map_err(RuntimeError::Delete)?;
+        Ok(())
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::objects::{
+        generate_test_workload_spec_with_param, AgentName, ExecutionState, WorkloadInstanceName,
+    };
+    use tokio::sync::mpsc::channel;
+
+    use super::{PluginRuntimeConnector, PluginStateGetter, PluginWorkloadId, RuntimePluginConfig};
+    use crate::runtime_connectors::{
+        ReusableWorkloadState, RuntimeConnector, RuntimeError, RuntimeStateGetter,
+    };
+
+    const BUFFER_SIZE: usize = 20;
+    const AGENT_NAME: &str = "agent_x";
+    const WORKLOAD_1_NAME: &str = "workload1";
+    const PLUGIN_NAME: &str = "wasm";
+
+    // The plugin is simulated by a shell script answering the requests by their method
+    fn plugin_config(script: &str) -> RuntimePluginConfig {
+        RuntimePluginConfig {
+            path: "bash".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn plugin_runtime(script: &str) -> PluginRuntimeConnector {
+        PluginRuntimeConnector::new(PLUGIN_NAME.to_string(), plugin_config(script))
+    }
+
+    // [utest->swdd~plugin-runtime-connector-proxies-runtime-connector-calls~3]
+    #[test]
+    fn utest_plugin_runtime_name_is_configured_name() {
+        assert_eq!(plugin_runtime("").name(), PLUGIN_NAME);
+    }
+
+    // [utest->swdd~plugin-runtime-connector-proxies-runtime-connector-calls~3]
+    #[tokio::test]
+    async fn utest_plugin_runtime_get_reusable_workloads_ignores_invalid_instance_names() {
+        let runtime = plugin_runtime(
+            r#"read -r request
+            case "$request" in
+                *'"method":"listWorkloads","agentName":"agent_x"'*)
+                    echo '{"workloads": [
+                        {"instanceName": "workload1.1234.agent_x", "workloadId": "id_1", "state": "running"},
+                        {"instanceName": "invalid", "workloadId": "id_2", "state": "running"}
+                    ]}';;
+                *) exit 1;;
+            esac"#,
+        );
+
+        let reusable_workloads = runtime
+            .get_reusable_workloads(&AgentName::from(AGENT_NAME))
+            .await;
+
+        assert_eq!(
+            reusable_workloads,
+            Ok(vec![ReusableWorkloadState::new(
+                WorkloadInstanceName::new(AGENT_NAME, WORKLOAD_1_NAME, "1234"),
+                ExecutionState::running(),
+                Some("id_1".to_string()),
+            )])
+        );
+    }
+
+    // [utest->swdd~plugin-runtime-connector-proxies-runtime-connector-calls~3]
+    // [utest->swdd~plugin-runtime-connector-reports-plugin-states~1]
+    #[tokio::test]
+    async fn utest_plugin_runtime_create_workload_reports_plugin_state() {
+        let runtime = plugin_runtime(
+            r#"read -r request
+            case "$request" in
+                *'"method":"createWorkload"'*'"runtimeConfig":"generic config"'*)
+                    echo '{"workloadId": "id_1"}';;
+                *'"method":"getState","workloadId":"id_1"'*)
+                    echo '{"state": "succeeded"}';;
+                *) exit 1;;
+            esac"#,
+        );
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PLUGIN_NAME.to_string(),
+        );
+        workload_spec.runtime_config = "generic config".to_string();
+        let (state_sender, mut state_receiver) = channel(BUFFER_SIZE);
+
+        let (workload_id, _checker) = runtime
+            .create_workload(workload_spec.clone(), None, None, state_sender)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            workload_id,
+            PluginWorkloadId {
+                id: "id_1".to_string()
+            }
+        );
+        let workload_state = state_receiver.recv().await.unwrap();
+        assert_eq!(workload_state.instance_name, workload_spec.instance_name);
+        assert_eq!(workload_state.execution_state, ExecutionState::succeeded());
+    }
+
+    // [utest->swdd~plugin-runtime-connector-proxies-runtime-connector-calls~3]
+    #[tokio::test]
+    async fn utest_plugin_runtime_forwards_plugin_errors() {
+        let runtime = plugin_runtime("read -r request; echo 'unknown workload' >&2; exit 1");
+
+        let result = runtime
+            .delete_workload(&PluginWorkloadId {
+                id: "id_1".to_string(),
+            })
+            .await;
+
+        assert!(
+            matches!(result, Err(RuntimeError::Delete(msg)) if msg.starts_with("unknown workload"))
+        );
+    }
+
+    // [utest->swdd~plugin-runtime-connector-proxies-runtime-connector-calls~3]
+    #[tokio::test]
+    async fn utest_plugin_runtime_kills_plugin_on_timeout() {
+        let runtime = PluginRuntimeConnector::new(
+            PLUGIN_NAME.to_string(),
+            RuntimePluginConfig {
+                timeout_secs: 1,
+                ..plugin_config("read -r request; sleep 10")
+            },
+        );
+
+        let start = std::time::Instant::now();
+        let result = runtime
+            .delete_workload(&PluginWorkloadId {
+                id: "id_1".to_string(),
+            })
+            .await;
+
+        assert!(matches!(result, Err(RuntimeError::Delete(msg)) if msg.contains("timed out")));
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    // [utest->swdd~plugin-runtime-connector-reports-plugin-states~1]
+    #[tokio::test]
+    async fn utest_plugin_state_getter_returns_unknown_on_invalid_response() {
+        let state_getter = PluginStateGetter {
+            plugin: plugin_config("read -r request; echo 'not json'"),
+        };
+
+        let state = state_getter
+            .get_state(&PluginWorkloadId {
+                id: "id_1".to_string(),
+            })
+            .await;
+
+        assert_eq!(
+            state,
+            ExecutionState::unknown("Error getting state from the runtime plugin.")
+        );
+    }
+}
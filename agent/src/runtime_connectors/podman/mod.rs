@@ -12,6 +12,10 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+mod podman_image_gc;
+mod podman_image_puller;
 mod podman_runtime;
 mod podman_runtime_config;
+pub use podman_image_gc::{ImageGcPolicy, PodmanImageGarbageCollector};
+pub use podman_image_puller::PodmanImagePuller;
 pub use podman_runtime::{PodmanRuntime, PodmanWorkloadId};
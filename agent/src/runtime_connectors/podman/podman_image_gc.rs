@@ -0,0 +1,304 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashSet,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+#[cfg(test)]
+use mockall_double::double;
+
+#[cfg_attr(test, double)]
+use crate::runtime_connectors::podman_cli::PodmanCli;
+use crate::runtime_connectors::podman_cli::PodmanImageInfo;
+
+use super::PodmanImagePuller;
+
+const IMAGE_GC_INTERVAL: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ImageGcPolicy {
+    pub max_age: Option<Duration>,
+    pub max_cache_size: Option<u64>,
+}
+
+impl ImageGcPolicy {
+    pub fn is_enabled(&self) -> bool {
+        self.max_age.is_some() || self.max_cache_size.is_some()
+    }
+}
+
+// [impl->swdd~podman-image-gc-runs-periodically~1]
+pub struct PodmanImageGarbageCollector {
+    policy: ImageGcPolicy,
+    image_puller: PodmanImagePuller,
+}
+
+impl PodmanImageGarbageCollector {
+    pub fn new(policy: ImageGcPolicy, image_puller: PodmanImagePuller) -> Self {
+        Self {
+            policy,
+            image_puller,
+        }
+    }
+
+    // [impl->swdd~podman-image-gc-runs-periodically~1]
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(IMAGE_GC_INTERVAL);
+        loop {
+            interval.tick().await;
+            self.collect_garbage().await;
+        }
+    }
+
+    // [impl->swdd~podman-image-gc-removes-unused-images~1]
+    // [impl->swdd~podman-image-gc-keeps-used-images~1]
+    async fn collect_garbage(&self) {
+        let images = match PodmanCli::list_images().await {
+            Ok(images) => images,
+            Err(err) => {
+                log::warn!("Image garbage collection skipped, could not list images: '{err}'");
+                return;
+            }
+        };
+        let mut used_image_ids: HashSet<String> = match PodmanCli::list_used_image_ids().await {
+            Ok(used_image_ids) => used_image_ids.into_iter().collect(),
+            Err(err) => {
+                log::warn!("Image garbage collection skipped, could not list used images: '{err}'");
+                return;
+            }
+        };
+        // [impl->swdd~podman-image-gc-keeps-pinned-images~1]
+        used_image_ids.extend(self.image_puller.pinned_image_ids());
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        for image_id in select_images_to_remove(&images, &used_image_ids, &self.policy, now) {
+            // Podman refuses to remove images used by containers created in the meantime.
+            match PodmanCli::remove_image(&image_id).await {
+                Ok(()) => log::info!("Removed unused image '{image_id}'."),
+                Err(err) => log::warn!("Could not remove unused image '{image_id}': '{err}'"),
+            }
+        }
+    }
+}
+
+// [impl->swdd~podman-image-gc-removes-unused-images~1]
+// [impl->swdd~podman-image-gc-keeps-used-images~1]
+fn select_images_to_remove(
+    images: &[PodmanImageInfo],
+    used_image_ids: &HashSet<String>,
+    policy: &ImageGcPolicy,
+    now: u64,
+) -> Vec<String> {
+    let mut unused_images: Vec<&PodmanImageInfo> = images
+        .iter()
+        .filter(|image| !used_image_ids.contains(&image.id))
+        .collect();
+    // The oldest images are removed first when the cache size is exceeded
+    unused_images.sort_by_key(|image| image.created);
+
+    let mut cache_size: u64 = images.iter().map(|image| image.size).sum();
+    let mut images_to_remove = Vec::new();
+    for image in unused_images {
+        let is_outdated = policy
+            .max_age
+            .is_some_and(|max_age| now.saturating_sub(image.created) > max_age.as_secs());
+        let is_cache_exceeded = policy
+            .max_cache_size
+            .is_some_and(|max_cache_size| cache_size > max_cache_size);
+
+        if is_outdated || is_cache_exceeded {
+            cache_size = cache_size.saturating_sub(image.size);
+            images_to_remove.push(image.id.clone());
+        }
+    }
+    images_to_remove
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, time::Duration};
+
+    use super::{select_images_to_remove, ImageGcPolicy, PodmanImageGarbageCollector};
+    use crate::runtime_connectors::podman::PodmanImagePuller;
+    use crate::runtime_connectors::podman_cli::{MockPodmanCli, PodmanImageInfo};
+    use crate::test_helper::MOCKALL_CONTEXT_SYNC;
+
+    const NOW: u64 = 1700010000;
+    const HOUR: u64 = 3600;
+
+    fn generate_test_image(id: &str, size: u64, age: u64) -> PodmanImageInfo {
+        PodmanImageInfo {
+            id: id.to_string(),
+            size,
+            created: NOW - age,
+        }
+    }
+
+    // [utest->swdd~podman-image-gc-removes-unused-images~1]
+    #[test]
+    fn utest_select_images_to_remove_outdated_images() {
+        let images = vec![
+            generate_test_image("old", 10, 2 * HOUR),
+            generate_test_image("new", 10, HOUR / 2),
+        ];
+        let policy = ImageGcPolicy {
+            max_age: Some(Duration::from_secs(HOUR)),
+            max_cache_size: None,
+        };
+
+        let result = select_images_to_remove(&images, &HashSet::new(), &policy, NOW);
+
+        assert_eq!(result, vec!["old".to_string()]);
+    }
+
+    // [utest->swdd~podman-image-gc-removes-unused-images~1]
+    #[test]
+    fn utest_select_images_to_remove_oldest_images_on_exceeded_cache_size() {
+        let images = vec![
+            generate_test_image("middle", 30, 2 * HOUR),
+            generate_test_image("newest", 30, HOUR),
+            generate_test_image("oldest", 30, 3 * HOUR),
+        ];
+        let policy = ImageGcPolicy {
+            max_age: None,
+            max_cache_size: Some(50),
+        };
+
+        let result = select_images_to_remove(&images, &HashSet::new(), &policy, NOW);
+
+        assert_eq!(result, vec!["oldest".to_string(), "middle".to_string()]);
+    }
+
+    // [utest->swdd~podman-image-gc-keeps-used-images~1]
+    #[test]
+    fn utest_select_images_to_remove_keeps_used_images() {
+        let images = vec![
+            generate_test_image("used", 100, 3 * HOUR),
+            generate_test_image("unused", 10, 2 * HOUR),
+        ];
+        let used_image_ids = HashSet::from(["used".to_string()]);
+        let policy = ImageGcPolicy {
+            max_age: Some(Duration::from_secs(HOUR)),
+            max_cache_size: Some(50),
+        };
+
+        let result = select_images_to_remove(&images, &used_image_ids, &policy, NOW);
+
+        assert_eq!(result, vec!["unused".to_string()]);
+    }
+
+    // [utest->swdd~podman-image-gc-removes-unused-images~1]
+    #[tokio::test]
+    async fn utest_collect_garbage_removes_selected_images() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let list_images_context = MockPodmanCli::list_images_context();
+        list_images_context.expect().return_const(Ok(vec![
+            generate_test_image("used", 10, 0),
+            generate_test_image("unused", 10, 0),
+        ]));
+        let list_used_image_ids_context = MockPodmanCli::list_used_image_ids_context();
+        list_used_image_ids_context
+            .expect()
+            .return_const(Ok(vec!["used".to_string()]));
+        let remove_image_context = MockPodmanCli::remove_image_context();
+        remove_image_context
+            .expect()
+            .with(mockall::predicate::eq("unused"))
+            .once()
+            .return_const(Ok(()));
+
+        PodmanImageGarbageCollector::new(
+            ImageGcPolicy {
+                max_age: None,
+                max_cache_size: Some(15),
+            },
+            PodmanImagePuller::default(),
+        )
+        .collect_garbage()
+        .await;
+    }
+
+    // [utest->swdd~podman-image-gc-removes-unused-images~1]
+    #[tokio::test]
+    async fn utest_collect_garbage_skipped_on_failing_image_listing() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let list_images_context = MockPodmanCli::list_images_context();
+        list_images_context
+            .expect()
+            .return_const(Err("podman error".to_string()));
+        let remove_image_context = MockPodmanCli::remove_image_context();
+        remove_image_context.expect().never();
+
+        PodmanImageGarbageCollector::new(
+            ImageGcPolicy {
+                max_age: Some(Duration::ZERO),
+                max_cache_size: None,
+            },
+            PodmanImagePuller::default(),
+        )
+        .collect_garbage()
+        .await;
+    }
+
+    // [utest->swdd~podman-image-gc-keeps-pinned-images~1]
+    #[tokio::test]
+    async fn utest_collect_garbage_keeps_pinned_images() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let pull_image_context = MockPodmanCli::pull_image_context();
+        pull_image_context
+            .expect()
+            .return_const(Ok("pinned".to_string()));
+        let image_puller = PodmanImagePuller::default();
+        image_puller
+            .pull_images(vec!["pinned:1.0".to_string()])
+            .await;
+
+        let list_images_context = MockPodmanCli::list_images_context();
+        list_images_context
+            .expect()
+            .return_const(Ok(vec![generate_test_image("pinned", 10, 2 * HOUR)]));
+        let list_used_image_ids_context = MockPodmanCli::list_used_image_ids_context();
+        list_used_image_ids_context
+            .expect()
+            .return_const(Ok(vec![]));
+        let remove_image_context = MockPodmanCli::remove_image_context();
+        remove_image_context.expect().never();
+
+        PodmanImageGarbageCollector::new(
+            ImageGcPolicy {
+                max_age: Some(Duration::from_secs(HOUR)),
+                max_cache_size: None,
+            },
+            image_puller,
+        )
+        .collect_garbage()
+        .await;
+    }
+}
@@ -0,0 +1,243 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+use common::objects::{ImagePullState, ImageState};
+use tokio::sync::watch;
+
+#[cfg(test)]
+use mockall_double::double;
+
+#[cfg_attr(test, double)]
+use crate::runtime_connectors::podman_cli::PodmanCli;
+
+const PULL_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+type ImageName = String;
+
+#[derive(Debug, Default)]
+struct PinnedImages {
+    states: HashMap<ImageName, ImageState>,
+    // the ids of the pulled images, used to keep them during the image garbage collection
+    ids: HashMap<ImageName, String>,
+}
+
+// Pulls the images pinned by the server ahead of their use. The images to pin, the pull states
+// and the ids of the pulled images are shared between all clones of the puller.
+// [impl->swdd~podman-image-puller-pulls-pinned-images~1]
+#[derive(Debug, Clone)]
+pub struct PodmanImagePuller {
+    images_to_pin: Arc<watch::Sender<Vec<ImageName>>>,
+    pinned_images: Arc<Mutex<PinnedImages>>,
+}
+
+impl Default for PodmanImagePuller {
+    fn default() -> Self {
+        PodmanImagePuller {
+            images_to_pin: Arc::new(watch::channel(Vec::new()).0),
+            pinned_images: Default::default(),
+        }
+    }
+}
+
+impl PodmanImagePuller {
+    // Replaces the pinned images. Images not contained anymore are unpinned.
+    pub fn pin_images(&self, images: Vec<ImageName>) {
+        self.update_pinned_images(&images);
+        self.images_to_pin.send_replace(images);
+    }
+
+    pub fn image_states(&self) -> HashMap<ImageName, ImageState> {
+        self.lock().states.clone()
+    }
+
+    // [impl->swdd~podman-image-gc-keeps-pinned-images~1]
+    pub fn pinned_image_ids(&self) -> HashSet<String> {
+        self.lock().ids.values().cloned().collect()
+    }
+
+    // [impl->swdd~podman-image-puller-pulls-pinned-images~1]
+    pub async fn run(self) {
+        let mut images_to_pin = self.images_to_pin.subscribe();
+        loop {
+            let images = images_to_pin.borrow_and_update().clone();
+            self.pull_images(images).await;
+            // failed pulls are retried after the interval, e.g., if the registry was not reachable
+            let _ = tokio::time::timeout(PULL_RETRY_INTERVAL, images_to_pin.changed()).await;
+        }
+    }
+
+    pub(super) async fn pull_images(&self, images: Vec<ImageName>) {
+        self.update_pinned_images(&images);
+
+        for image in images {
+            if self
+                .lock()
+                .states
+                .get(&image)
+                .is_some_and(|image_state| image_state.state == ImagePullState::Pulled)
+            {
+                continue;
+            }
+
+            log::debug!("Pulling pinned image '{image}'");
+            self.lock()
+                .states
+                .insert(image.clone(), ImageState::pulling());
+            let result = PodmanCli::pull_image(&image).await;
+
+            let mut pinned_images = self.lock();
+            match result {
+                Ok(image_id) => {
+                    log::info!("Pulled pinned image '{image}'");
+                    pinned_images.ids.insert(image.clone(), image_id);
+                    pinned_images.states.insert(image, ImageState::pulled());
+                }
+                Err(err) => {
+                    log::warn!("Could not pull pinned image '{image}': '{err}'");
+                    pinned_images.states.insert(image, ImageState::failed(err));
+                }
+            }
+        }
+    }
+
+    fn update_pinned_images(&self, images: &[ImageName]) {
+        let mut pinned_images = self.lock();
+        // unpinned images are only forgotten, the image garbage collection removes them once unused
+        pinned_images
+            .states
+            .retain(|image, _| images.contains(image));
+        pinned_images.ids.retain(|image, _| images.contains(image));
+        for image in images {
+            pinned_images
+                .states
+                .entry(image.clone())
+                .or_insert_with(ImageState::pending);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PinnedImages> {
+        self.pinned_images
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use common::objects::ImageState;
+
+    use super::PodmanImagePuller;
+    use crate::runtime_connectors::podman_cli::MockPodmanCli;
+    use crate::test_helper::MOCKALL_CONTEXT_SYNC;
+
+    const IMAGE_1: &str = "ghcr.io/eclipse-ankaios/image_1:1.0";
+    const IMAGE_2: &str = "ghcr.io/eclipse-ankaios/image_2:1.0";
+
+    // [utest->swdd~podman-image-puller-pulls-pinned-images~1]
+    #[test]
+    fn utest_pin_images_reports_new_images_as_pending() {
+        let image_puller = PodmanImagePuller::default();
+
+        image_puller.pin_images(vec![IMAGE_1.to_string()]);
+
+        assert_eq!(
+            image_puller.image_states(),
+            HashMap::from([(IMAGE_1.to_string(), ImageState::pending())])
+        );
+        assert!(image_puller.pinned_image_ids().is_empty());
+    }
+
+    // [utest->swdd~podman-image-puller-pulls-pinned-images~1]
+    #[tokio::test]
+    async fn utest_pull_images_reports_pull_states() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let pull_image_context = MockPodmanCli::pull_image_context();
+        pull_image_context
+            .expect()
+            .with(mockall::predicate::eq(IMAGE_1))
+            .once()
+            .return_const(Ok("image_id_1".to_string()));
+        pull_image_context
+            .expect()
+            .with(mockall::predicate::eq(IMAGE_2))
+            .once()
+            .return_const(Err("manifest unknown".to_string()));
+
+        let image_puller = PodmanImagePuller::default();
+        image_puller
+            .pull_images(vec![IMAGE_1.to_string(), IMAGE_2.to_string()])
+            .await;
+
+        assert_eq!(
+            image_puller.image_states(),
+            HashMap::from([
+                (IMAGE_1.to_string(), ImageState::pulled()),
+                (IMAGE_2.to_string(), ImageState::failed("manifest unknown")),
+            ])
+        );
+        assert_eq!(
+            image_puller.pinned_image_ids(),
+            HashSet::from(["image_id_1".to_string()])
+        );
+    }
+
+    // [utest->swdd~podman-image-puller-pulls-pinned-images~1]
+    #[tokio::test]
+    async fn utest_pull_images_skips_pulled_images_and_forgets_unpinned_images() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let pull_image_context = MockPodmanCli::pull_image_context();
+        pull_image_context
+            .expect()
+            .with(mockall::predicate::eq(IMAGE_1))
+            .once()
+            .return_const(Ok("image_id_1".to_string()));
+        pull_image_context
+            .expect()
+            .with(mockall::predicate::eq(IMAGE_2))
+            .once()
+            .return_const(Ok("image_id_2".to_string()));
+
+        let image_puller = PodmanImagePuller::default();
+        image_puller.pull_images(vec![IMAGE_1.to_string()]).await;
+        image_puller.pull_images(vec![IMAGE_2.to_string()]).await;
+        image_puller.pull_images(vec![IMAGE_2.to_string()]).await;
+
+        assert_eq!(
+            image_puller.image_states(),
+            HashMap::from([(IMAGE_2.to_string(), ImageState::pulled())])
+        );
+        assert_eq!(
+            image_puller.pinned_image_ids(),
+            HashSet::from(["image_id_2".to_string()])
+        );
+    }
+}
@@ -12,21 +12,27 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{fmt::Display, path::PathBuf, str::FromStr};
+use std::{fmt::Display, net::IpAddr, path::PathBuf, str::FromStr};
 
 use async_trait::async_trait;
 
 use common::{
-    objects::{AgentName, ExecutionState, WorkloadInstanceName, WorkloadSpec},
+    objects::{
+        AgentName, ExecutionState, ResourceUsage, WorkloadInstanceName, WorkloadResources,
+        WorkloadSpec,
+    },
     std_extensions::UnreachableOption,
 };
 
 use crate::{
     generic_polling_state_checker::GenericPollingStateChecker,
     runtime_connectors::{
-        podman_cli::PodmanStartConfig, ReusableWorkloadState, RuntimeConnector, RuntimeError,
-        RuntimeStateGetter, StateChecker,
+        podman_cli::{PodmanRunConfig, PodmanStartConfig},
+        registry_auth_file::RegistryAuthFile,
+        ResourceUsageCollector, ReusableWorkloadState, RuntimeConnector, RuntimeError,
+        RuntimeStateGetter, StateChecker, WorkloadResourceUsage,
     },
+    workload_log_sink::WorkloadLogsConfig,
     workload_state::WorkloadStateSender,
 };
 
@@ -41,8 +47,10 @@ use super::podman_runtime_config::PodmanRuntimeConfig;
 
 pub const PODMAN_RUNTIME_NAME: &str = "podman";
 
-#[derive(Debug, Clone)]
-pub struct PodmanRuntime {}
+#[derive(Debug, Clone, Default)]
+pub struct PodmanRuntime {
+    pub workload_logs: WorkloadLogsConfig,
+}
 
 #[derive(Debug, Clone)]
 pub struct PodmanStateGetter {}
@@ -99,9 +107,80 @@ impl RuntimeStateGetter<PodmanWorkloadId> for PodmanStateGetter {
         );
         exec_state
     }
+
+    // [impl->swdd~podman-state-getter-provides-resource-usage~1]
+    async fn get_resource_usage(
+        &self,
+        workload_id: &PodmanWorkloadId,
+    ) -> Option<WorkloadResourceUsage> {
+        PodmanCli::get_resource_usage(workload_id.id.as_str())
+            .await
+            .map_err(|err| {
+                log::debug!(
+                    "Could not get resource usage of workload '{}': '{}'",
+                    workload_id.id,
+                    err
+                );
+            })
+            .ok()
+    }
+
+    // [impl->swdd~podman-state-getter-executes-health-command~1]
+    async fn exec_health_command(
+        &self,
+        workload_id: &PodmanWorkloadId,
+        command: &[String],
+    ) -> Option<Result<(), String>> {
+        Some(PodmanCli::exec_command(workload_id.id.as_str(), command).await)
+    }
 }
 
 impl PodmanRuntime {
+    async fn start_state_checker(
+        &self,
+        workload_id: &PodmanWorkloadId,
+        workload_spec: WorkloadSpec,
+        update_state_tx: WorkloadStateSender,
+    ) -> GenericPollingStateChecker {
+        // [impl->swdd~podman-state-getter-reset-cache~1]
+        PodmanCli::reset_ps_cache().await;
+
+        log::debug!(
+            "Starting the checker for the workload '{}' with internal id '{}'",
+            workload_spec.instance_name,
+            workload_id.id
+        );
+        GenericPollingStateChecker::start_checker(
+            &workload_spec,
+            workload_id.clone(),
+            update_state_tx,
+            PodmanStateGetter {},
+        )
+    }
+
+    // [impl->swdd~podman-follows-workload-logs~1]
+    fn mirror_logs(
+        &self,
+        workload_id: &PodmanWorkloadId,
+        workload_spec: &WorkloadSpec,
+        only_new_lines: bool,
+    ) {
+        let instance_name = &workload_spec.instance_name;
+        let Some(sink) = self.workload_logs.sink_for(instance_name.workload_name()) else {
+            return;
+        };
+        match PodmanCli::spawn_log_follower(&workload_id.id, only_new_lines) {
+            Ok(log_follower) => {
+                tokio::spawn(sink.forward(instance_name.clone(), log_follower));
+            }
+            Err(err) => log::warn!(
+                "Could not mirror the logs of workload '{}': '{}'",
+                instance_name.workload_name(),
+                err
+            ),
+        }
+    }
+
     async fn workload_instance_names_to_workload_states(
         &self,
         workload_instance_names: &Vec<WorkloadInstanceName>,
@@ -128,6 +207,35 @@ impl PodmanRuntime {
     }
 }
 
+#[async_trait]
+impl ResourceUsageCollector for PodmanRuntime {
+    // [impl->swdd~podman-collects-resource-usage-of-running-containers~1]
+    async fn collect_resource_usage(&self, agent_name: &AgentName) -> Vec<WorkloadResources> {
+        let resource_usage = match PodmanCli::list_resource_usage().await {
+            Ok(resource_usage) => resource_usage,
+            Err(err) => {
+                log::debug!(
+                    "Could not collect the resource usage of the workloads: '{}'",
+                    err
+                );
+                return Vec::new();
+            }
+        };
+
+        // The container names of workloads are their instance names.
+        resource_usage
+            .into_iter()
+            .filter_map(|(name, usage)| {
+                let instance_name: WorkloadInstanceName = name.as_str().try_into().ok()?;
+                (instance_name.agent_name() == agent_name.get()).then(|| WorkloadResources {
+                    instance_name,
+                    resource_usage: ResourceUsage::new(usage.cpu_usage, usage.memory_usage),
+                })
+            })
+            .collect()
+    }
+}
+
 #[async_trait]
 // [impl->swdd~podman-implements-runtime-connector~1]
 impl RuntimeConnector<PodmanWorkloadId, GenericPollingStateChecker> for PodmanRuntime {
@@ -168,6 +276,7 @@ impl RuntimeConnector<PodmanWorkloadId, GenericPollingStateChecker> for PodmanRu
         let workload_cfg = PodmanRuntimeConfig::try_from(&workload_spec)
             .map_err(|err| RuntimeError::Create(err.into()))?;
 
+        let only_new_log_lines = reusable_workload_id.is_some();
         let cli_result = match reusable_workload_id {
             Some(workload_id) => {
                 let start_config = PodmanStartConfig {
@@ -178,8 +287,21 @@ impl RuntimeConnector<PodmanWorkloadId, GenericPollingStateChecker> for PodmanRu
                     .await
             }
             None => {
+                // [impl->swdd~podman-uses-registry-auth~1]
+                let registry_auth_file = workload_spec
+                    .registry_auth
+                    .as_deref()
+                    .map(RegistryAuthFile::create)
+                    .transpose()
+                    .map_err(RuntimeError::Create)?;
+                let mut run_config: PodmanRunConfig = workload_cfg.into();
+                if let Some(registry_auth_file) = &registry_auth_file {
+                    run_config
+                        .command_options
+                        .push(registry_auth_file.authfile_option());
+                }
                 PodmanCli::podman_run(
-                    workload_cfg.into(),
+                    run_config,
                     &workload_spec.instance_name.to_string(),
                     workload_spec.instance_name.agent_name(),
                     control_interface_path,
@@ -197,9 +319,10 @@ impl RuntimeConnector<PodmanWorkloadId, GenericPollingStateChecker> for PodmanRu
                 );
 
                 let podman_workload_id = PodmanWorkloadId { id: workload_id };
+                self.mirror_logs(&podman_workload_id, &workload_spec, only_new_log_lines);
                 let state_checker = self
-                    .start_checker(&podman_workload_id, workload_spec, update_state_tx)
-                    .await?;
+                    .start_state_checker(&podman_workload_id, workload_spec, update_state_tx)
+                    .await;
 
                 // [impl->swdd~podman-create-workload-returns-workload-id~1]
                 Ok((podman_workload_id, state_checker))
@@ -254,21 +377,11 @@ impl RuntimeConnector<PodmanWorkloadId, GenericPollingStateChecker> for PodmanRu
         workload_spec: WorkloadSpec,
         update_state_tx: WorkloadStateSender,
     ) -> Result<GenericPollingStateChecker, RuntimeError> {
-        // [impl->swdd~podman-state-getter-reset-cache~1]
-        PodmanCli::reset_ps_cache().await;
-
-        log::debug!(
-            "Starting the checker for the workload '{}' with internal id '{}'",
-            workload_spec.instance_name,
-            workload_id.id
-        );
-        let checker = GenericPollingStateChecker::start_checker(
-            &workload_spec,
-            workload_id.clone(),
-            update_state_tx,
-            PodmanStateGetter {},
-        );
-        Ok(checker)
+        // the lines written before are already mirrored by the previous agent run
+        self.mirror_logs(workload_id, &workload_spec, true);
+        Ok(self
+            .start_state_checker(workload_id, workload_spec, update_state_tx)
+            .await)
     }
 
     // [impl->swdd~podman-delete-workload-stops-and-removes-workload~1]
@@ -278,6 +391,67 @@ impl RuntimeConnector<PodmanWorkloadId, GenericPollingStateChecker> for PodmanRu
             .await
             .map_err(|err| RuntimeError::Delete(err.to_string()))
     }
+
+    // [impl->swdd~podman-stops-workload-within-grace-period~1]
+    async fn stop_workload(
+        &self,
+        workload_id: &PodmanWorkloadId,
+        grace_period_secs: u32,
+    ) -> Result<(), RuntimeError> {
+        log::debug!(
+            "Stopping workload with id '{}' within {}s",
+            workload_id.id,
+            grace_period_secs
+        );
+        PodmanCli::stop_workload_by_id(&workload_id.id, grace_period_secs)
+            .await
+            .map_err(|err| RuntimeError::Delete(err.to_string()))
+    }
+
+    // [impl->swdd~podman-pauses-workload~1]
+    async fn pause_workload(&self, workload_id: &PodmanWorkloadId) -> Result<(), RuntimeError> {
+        log::debug!("Pausing workload with id '{}'", workload_id.id);
+        PodmanCli::pause_workload_by_id(&workload_id.id)
+            .await
+            .map_err(RuntimeError::Pause)?;
+        // the state checker shall not report the state from before the pause
+        PodmanCli::reset_ps_cache().await;
+        Ok(())
+    }
+
+    // [impl->swdd~podman-pauses-workload~1]
+    async fn unpause_workload(&self, workload_id: &PodmanWorkloadId) -> Result<(), RuntimeError> {
+        log::debug!("Resuming workload with id '{}'", workload_id.id);
+        PodmanCli::unpause_workload_by_id(&workload_id.id)
+            .await
+            .map_err(RuntimeError::Pause)?;
+        PodmanCli::reset_ps_cache().await;
+        Ok(())
+    }
+
+    // [impl->swdd~podman-executes-command-in-workload~1]
+    async fn exec(
+        &self,
+        workload_id: &PodmanWorkloadId,
+        command: Vec<String>,
+    ) -> Result<tokio::process::Child, RuntimeError> {
+        log::debug!(
+            "Executing '{:?}' in workload with id '{}'",
+            command,
+            workload_id.id
+        );
+        PodmanCli::spawn_exec_session(&workload_id.id, &command).map_err(RuntimeError::Exec)
+    }
+
+    // [impl->swdd~podman-provides-workload-address~1]
+    async fn get_workload_address(
+        &self,
+        workload_id: &PodmanWorkloadId,
+    ) -> Result<IpAddr, RuntimeError> {
+        PodmanCli::get_container_address(&workload_id.id)
+            .await
+            .map_err(RuntimeError::PortForward)
+    }
 }
 
 //////////////////////////////////////////////////////////////////////////////
@@ -293,17 +467,23 @@ impl RuntimeConnector<PodmanWorkloadId, GenericPollingStateChecker> for PodmanRu
 mod tests {
     use std::path::PathBuf;
     use std::str::FromStr;
+    use std::sync::{Arc, Mutex};
 
     use common::objects::{
-        generate_test_workload_spec_with_param, AgentName, ExecutionState, WorkloadInstanceName,
+        generate_test_workload_spec_with_param, AgentName, ExecutionState, ResourceUsage,
+        WorkloadInstanceName, WorkloadResources,
     };
     use mockall::Sequence;
 
     use super::PodmanCli;
     use super::PodmanRuntime;
     use super::{PodmanStateGetter, PodmanWorkloadId, PODMAN_RUNTIME_NAME};
-    use crate::runtime_connectors::{RuntimeConnector, RuntimeError, RuntimeStateGetter};
+    use crate::runtime_connectors::{
+        ResourceUsageCollector, RuntimeConnector, RuntimeError, RuntimeStateGetter,
+        WorkloadResourceUsage,
+    };
     use crate::test_helper::MOCKALL_CONTEXT_SYNC;
+    use crate::workload_log_sink::{WorkloadLogSinkKind, WorkloadLogsConfig};
 
     const BUFFER_SIZE: usize = 20;
 
@@ -313,7 +493,7 @@ mod tests {
     // [utest->swdd~podman-name-returns-podman~1]
     #[test]
     fn utest_name_podman() {
-        let podman_runtime = PodmanRuntime {};
+        let podman_runtime = PodmanRuntime::default();
         assert_eq!(podman_runtime.name(), "podman".to_string());
     }
 
@@ -347,7 +527,7 @@ mod tests {
             .expect()
             .return_const(Ok(Some(ExecutionState::initial())));
 
-        let podman_runtime = PodmanRuntime {};
+        let podman_runtime = PodmanRuntime::default();
         let agent_name = AgentName::from("dummy_agent");
         let res = podman_runtime
             .get_reusable_workloads(&agent_name)
@@ -372,7 +552,7 @@ mod tests {
         let context = PodmanCli::list_workload_names_by_label_context();
         context.expect().return_const(Ok(Vec::new()));
 
-        let podman_runtime = PodmanRuntime {};
+        let podman_runtime = PodmanRuntime::default();
         let agent_name = AgentName::from("different_agent");
         let res = podman_runtime
             .get_reusable_workloads(&agent_name)
@@ -391,7 +571,7 @@ mod tests {
             .expect()
             .return_const(Err("Simulated error".to_string()));
 
-        let podman_runtime = PodmanRuntime {};
+        let podman_runtime = PodmanRuntime::default();
         let agent_name = AgentName::from("dummy_agent");
 
         assert_eq!(
@@ -420,7 +600,7 @@ mod tests {
         );
         let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
 
-        let podman_runtime = PodmanRuntime {};
+        let podman_runtime = PodmanRuntime::default();
         let res = podman_runtime
             .create_workload(
                 workload_spec,
@@ -436,6 +616,48 @@ mod tests {
         assert_eq!(workload_id.id, "test_id".to_string());
     }
 
+    // [utest->swdd~podman-uses-registry-auth~1]
+    #[tokio::test]
+    async fn utest_create_workload_passes_registry_auth_as_authfile() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let authfile_path = Arc::new(Mutex::new(None));
+        let authfile_path_clone = authfile_path.clone();
+        let run_context = PodmanCli::podman_run_context();
+        run_context.expect().returning(move |run_config, _, _, _| {
+            let path = run_config
+                .command_options
+                .iter()
+                .find_map(|option| option.strip_prefix("--authfile="))
+                .map(PathBuf::from)
+                .unwrap();
+            assert!(path.exists());
+            *authfile_path_clone.lock().unwrap() = Some(path);
+            Ok("test_id".into())
+        });
+
+        let resest_cache_context = PodmanCli::reset_ps_cache_context();
+        resest_cache_context.expect().return_const(());
+
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.registry_auth =
+            Some(r#"{"auths": {"registry.example.com": {"auth": "dXNlcjpwdw=="}}}"#.into());
+        let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let podman_runtime = PodmanRuntime::default();
+        let res = podman_runtime
+            .create_workload(workload_spec, None, None, state_change_tx)
+            .await;
+
+        assert!(res.is_ok());
+        let authfile_path = authfile_path.lock().unwrap().take().unwrap();
+        assert!(!authfile_path.exists());
+    }
+
     // [utest->swdd~podman-create-workload-starts-existing-workload~1]
     #[tokio::test]
     async fn utest_create_workload_with_existing_workload_id_success() {
@@ -458,7 +680,7 @@ mod tests {
         );
         let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
 
-        let podman_runtime = PodmanRuntime {};
+        let podman_runtime = PodmanRuntime::default();
         let res = podman_runtime
             .create_workload(
                 workload_spec,
@@ -474,6 +696,63 @@ mod tests {
         assert_eq!(workload_id.id, reusable_workload_id);
     }
 
+    // [utest->swdd~podman-follows-workload-logs~1]
+    #[tokio::test]
+    async fn utest_create_workload_and_start_checker_mirror_logs() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let run_context = PodmanCli::podman_run_context();
+        run_context.expect().return_const(Ok("test_id".into()));
+
+        let resest_cache_context = PodmanCli::reset_ps_cache_context();
+        resest_cache_context.expect().return_const(());
+
+        let mut seq = Sequence::new();
+        let log_follower_context = PodmanCli::spawn_log_follower_context();
+        log_follower_context
+            .expect()
+            .with(
+                mockall::predicate::eq("test_id"),
+                mockall::predicate::eq(false),
+            )
+            .once()
+            .in_sequence(&mut seq)
+            .returning(|_, _| Err("simulated error".to_string()));
+        log_follower_context
+            .expect()
+            .with(
+                mockall::predicate::eq("test_id"),
+                mockall::predicate::eq(true),
+            )
+            .once()
+            .in_sequence(&mut seq)
+            .returning(|_, _| Err("simulated error".to_string()));
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_RUNTIME_NAME.to_string(),
+        );
+        let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let podman_runtime = PodmanRuntime {
+            workload_logs: WorkloadLogsConfig {
+                sink: WorkloadLogSinkKind::Journald,
+                ..Default::default()
+            },
+        };
+        let (workload_id, _checker) = podman_runtime
+            .create_workload(workload_spec.clone(), None, None, state_change_tx.clone())
+            .await
+            .unwrap();
+
+        // a failing log follower does not prevent the workload from running
+        let res = podman_runtime
+            .start_checker(&workload_id, workload_spec, state_change_tx)
+            .await;
+        assert!(res.is_ok());
+    }
+
     // [utest->swdd~podman-state-getter-reset-cache~1]
     #[tokio::test]
     async fn utest_state_getter_resets_cache() {
@@ -505,7 +784,7 @@ mod tests {
         );
         let (state_change_tx, mut state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
 
-        let podman_runtime = PodmanRuntime {};
+        let podman_runtime = PodmanRuntime::default();
         let res = podman_runtime
             .create_workload(
                 workload_spec,
@@ -540,6 +819,135 @@ mod tests {
         assert_eq!(execution_state, ExecutionState::running());
     }
 
+    // [utest->swdd~podman-state-getter-provides-resource-usage~1]
+    #[tokio::test]
+    async fn utest_state_getter_provides_resource_usage() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let resource_usage = WorkloadResourceUsage {
+            cpu_usage: 12.5,
+            memory_usage: 4096,
+        };
+
+        let resource_usage_context = PodmanCli::get_resource_usage_context();
+        resource_usage_context
+            .expect()
+            .once()
+            .return_const(Ok(resource_usage.clone()));
+
+        let state_getter = PodmanStateGetter {};
+        let workload_id = PodmanWorkloadId {
+            id: "test_workload_id".into(),
+        };
+
+        assert_eq!(
+            state_getter.get_resource_usage(&workload_id).await,
+            Some(resource_usage)
+        );
+
+        resource_usage_context
+            .expect()
+            .return_const(Err("podman stats failed".into()));
+
+        assert_eq!(state_getter.get_resource_usage(&workload_id).await, None);
+    }
+
+    // [utest->swdd~podman-collects-resource-usage-of-running-containers~1]
+    #[tokio::test]
+    async fn utest_collect_resource_usage_of_own_workloads() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let list_resource_usage_context = PodmanCli::list_resource_usage_context();
+        list_resource_usage_context
+            .expect()
+            .once()
+            .return_const(Ok(vec![
+                (
+                    format!("{WORKLOAD_1_NAME}.hash.{AGENT_NAME}"),
+                    WorkloadResourceUsage {
+                        cpu_usage: 12.5,
+                        memory_usage: 4096,
+                    },
+                ),
+                (
+                    "workload2.hash.other_agent".to_string(),
+                    WorkloadResourceUsage {
+                        cpu_usage: 1.0,
+                        memory_usage: 1024,
+                    },
+                ),
+                (
+                    "not_an_ankaios_container".to_string(),
+                    WorkloadResourceUsage {
+                        cpu_usage: 1.0,
+                        memory_usage: 1024,
+                    },
+                ),
+            ]));
+
+        let podman_runtime = PodmanRuntime::default();
+        let res = podman_runtime
+            .collect_resource_usage(&AgentName::from(AGENT_NAME))
+            .await;
+
+        assert_eq!(
+            res,
+            vec![WorkloadResources {
+                instance_name: WorkloadInstanceName::new(AGENT_NAME, WORKLOAD_1_NAME, "hash"),
+                resource_usage: ResourceUsage {
+                    cpu_usage: 13,
+                    memory_usage: 4096,
+                },
+            }]
+        );
+    }
+
+    // [utest->swdd~podman-collects-resource-usage-of-running-containers~1]
+    #[tokio::test]
+    async fn utest_collect_resource_usage_returns_empty_list_on_error() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let list_resource_usage_context = PodmanCli::list_resource_usage_context();
+        list_resource_usage_context
+            .expect()
+            .once()
+            .return_const(Err("podman stats failed".into()));
+
+        let podman_runtime = PodmanRuntime::default();
+        let res = podman_runtime
+            .collect_resource_usage(&AgentName::from(AGENT_NAME))
+            .await;
+
+        assert!(res.is_empty());
+    }
+
+    // [utest->swdd~podman-state-getter-executes-health-command~1]
+    #[tokio::test]
+    async fn utest_state_getter_executes_health_command() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let exec_command_context = PodmanCli::exec_command_context();
+        exec_command_context
+            .expect()
+            .withf(|workload_id, command| {
+                workload_id == "test_workload_id" && command == ["pg_isready".to_string()]
+            })
+            .once()
+            .return_const(Err("exit code 1".into()));
+
+        let state_getter = PodmanStateGetter {};
+        let workload_id = PodmanWorkloadId {
+            id: "test_workload_id".into(),
+        };
+
+        assert_eq!(
+            state_getter
+                .exec_health_command(&workload_id, &["pg_isready".into()])
+                .await,
+            Some(Err("exit code 1".into()))
+        );
+    }
+
     // [utest->swdd~podman-create-workload-deletes-failed-container~1]
     #[tokio::test]
     async fn utest_create_workload_run_failed_cleanup_success() {
@@ -561,7 +969,7 @@ mod tests {
         );
         let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
 
-        let podman_runtime = PodmanRuntime {};
+        let podman_runtime = PodmanRuntime::default();
         let res = podman_runtime
             .create_workload(
                 workload_spec,
@@ -596,7 +1004,7 @@ mod tests {
         );
         let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
 
-        let podman_runtime = PodmanRuntime {};
+        let podman_runtime = PodmanRuntime::default();
         let res = podman_runtime
             .create_workload(
                 workload_spec,
@@ -622,7 +1030,7 @@ mod tests {
 
         let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
 
-        let podman_runtime = PodmanRuntime {};
+        let podman_runtime = PodmanRuntime::default();
         let res = podman_runtime
             .create_workload(
                 workload_spec,
@@ -647,7 +1055,7 @@ mod tests {
 
         let workload_name = "container1.hash.dummy_agent".try_into().unwrap();
 
-        let podman_runtime = PodmanRuntime {};
+        let podman_runtime = PodmanRuntime::default();
         let res = podman_runtime.get_workload_id(&workload_name).await;
 
         assert_eq!(
@@ -667,7 +1075,7 @@ mod tests {
 
         let workload_name = "container1.hash.dummy_agent".try_into().unwrap();
 
-        let podman_runtime = PodmanRuntime {};
+        let podman_runtime = PodmanRuntime::default();
         let res = podman_runtime.get_workload_id(&workload_name).await;
 
         assert_eq!(
@@ -687,7 +1095,7 @@ mod tests {
 
         let workload_name = "container1.hash.dummy_agent".try_into().unwrap();
 
-        let podman_runtime = PodmanRuntime {};
+        let podman_runtime = PodmanRuntime::default();
         let res = podman_runtime.get_workload_id(&workload_name).await;
 
         assert_eq!(res, Err(RuntimeError::List("simulated error".to_owned())))
@@ -758,7 +1166,7 @@ mod tests {
             id: "test_id".into(),
         };
 
-        let podman_runtime = PodmanRuntime {};
+        let podman_runtime = PodmanRuntime::default();
         let res = podman_runtime.delete_workload(&workload_id).await;
         assert_eq!(res, Ok(()));
     }
@@ -774,8 +1182,96 @@ mod tests {
             id: "test_id".into(),
         };
 
-        let podman_runtime = PodmanRuntime {};
+        let podman_runtime = PodmanRuntime::default();
         let res = podman_runtime.delete_workload(&workload_id).await;
         assert_eq!(res, Err(RuntimeError::Delete("simulated error".into())));
     }
+
+    // [utest->swdd~podman-stops-workload-within-grace-period~1]
+    #[tokio::test]
+    async fn utest_stop_workload_succeeds() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let context = PodmanCli::stop_workload_by_id_context();
+        context
+            .expect()
+            .with(
+                mockall::predicate::eq("test_id"),
+                mockall::predicate::eq(30),
+            )
+            .return_const(Ok(()));
+
+        let workload_id = PodmanWorkloadId {
+            id: "test_id".into(),
+        };
+
+        let podman_runtime = PodmanRuntime::default();
+        let res = podman_runtime.stop_workload(&workload_id, 30).await;
+        assert_eq!(res, Ok(()));
+    }
+
+    // [utest->swdd~podman-pauses-workload~1]
+    #[tokio::test]
+    async fn utest_pause_workload_fails() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let context = PodmanCli::pause_workload_by_id_context();
+        context
+            .expect()
+            .with(mockall::predicate::eq("test_id"))
+            .return_const(Err("simulated error".to_string()));
+
+        let workload_id = PodmanWorkloadId {
+            id: "test_id".into(),
+        };
+
+        let podman_runtime = PodmanRuntime::default();
+        let res = podman_runtime.pause_workload(&workload_id).await;
+        assert_eq!(res, Err(RuntimeError::Pause("simulated error".into())));
+    }
+
+    // [utest->swdd~podman-executes-command-in-workload~1]
+    #[tokio::test]
+    async fn utest_exec_fails_if_exec_session_not_started() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let context = PodmanCli::spawn_exec_session_context();
+        context
+            .expect()
+            .with(
+                mockall::predicate::eq("test_id"),
+                mockall::predicate::eq(vec!["sh".to_string()]),
+            )
+            .return_once(|_, _| Err("simulated error".to_string()));
+
+        let workload_id = PodmanWorkloadId {
+            id: "test_id".into(),
+        };
+
+        let podman_runtime = PodmanRuntime::default();
+        let res = podman_runtime
+            .exec(&workload_id, vec!["sh".to_string()])
+            .await;
+        assert!(matches!(res, Err(RuntimeError::Exec(msg)) if msg == "simulated error"));
+    }
+
+    // [utest->swdd~podman-provides-workload-address~1]
+    #[tokio::test]
+    async fn utest_get_workload_address_returns_container_address() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let context = PodmanCli::get_container_address_context();
+        context
+            .expect()
+            .with(mockall::predicate::eq("test_id"))
+            .return_once(|_| Ok("10.88.0.5".parse().unwrap()));
+
+        let workload_id = PodmanWorkloadId {
+            id: "test_id".into(),
+        };
+
+        let podman_runtime = PodmanRuntime::default();
+        let res = podman_runtime.get_workload_address(&workload_id).await;
+        assert_eq!(res, Ok("10.88.0.5".parse().unwrap()));
+    }
 }
@@ -12,7 +12,9 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use common::objects::WorkloadSpec;
+use std::collections::BTreeMap;
+
+use common::objects::{VolumeType, WorkloadSpec};
 
 use crate::runtime_connectors::podman_cli::PodmanRunConfig;
 
@@ -53,10 +55,109 @@ impl TryFrom<&WorkloadSpec> for PodmanRuntimeConfig {
                 workload_spec.runtime
             )));
         }
-        match serde_yaml::from_str(workload_spec.runtime_config.as_str()) {
-            Ok(workload_cfg) => Ok(workload_cfg),
-            Err(e) => Err(TryFromWorkloadSpecError(e.to_string())),
+        let mut workload_cfg: PodmanRuntimeConfig =
+            serde_yaml::from_str(workload_spec.runtime_config.as_str())
+                .map_err(|e| TryFromWorkloadSpecError(e.to_string()))?;
+
+        // [impl->swdd~podman-maps-hostname-and-extra-hosts-to-options~1]
+        if let Some(hostname) = &workload_spec.hostname {
+            workload_cfg
+                .command_options
+                .extend(["--hostname".to_owned(), hostname.clone()]);
+        }
+        for extra_host in &workload_spec.extra_hosts {
+            workload_cfg.command_options.extend([
+                "--add-host".to_owned(),
+                format!("{}:{}", extra_host.hostname, extra_host.ip),
+            ]);
+        }
+        // [impl->swdd~podman-maps-ports-to-publish-options~1]
+        for port in &workload_spec.ports {
+            workload_cfg
+                .command_options
+                .extend(["--publish".to_owned(), port.to_string()]);
+        }
+        // [impl->swdd~podman-maps-networks-to-network-options~1]
+        for network in &workload_spec.networks {
+            let network_option = if network.aliases.is_empty() {
+                network.name.clone()
+            } else {
+                let aliases: Vec<String> = network
+                    .aliases
+                    .iter()
+                    .map(|alias| format!("alias={alias}"))
+                    .collect();
+                format!("{}:{}", network.name, aliases.join(","))
+            };
+            workload_cfg
+                .command_options
+                .extend(["--network".to_owned(), network_option]);
+        }
+        // [impl->swdd~podman-maps-env-to-env-options~1]
+        for (name, value) in workload_spec.env.iter().collect::<BTreeMap<_, _>>() {
+            workload_cfg
+                .command_options
+                .extend(["--env".to_owned(), format!("{name}={value}")]);
+        }
+        // [impl->swdd~podman-maps-volumes-to-mount-options~1]
+        for volume in &workload_spec.volumes {
+            let read_only = if volume.read_only { ":ro" } else { "" };
+            let mount_option = match volume.volume_type {
+                VolumeType::HostPath | VolumeType::Named => [
+                    "--volume".to_owned(),
+                    format!("{}:{}{}", volume.source, volume.mount_path, read_only),
+                ],
+                VolumeType::Tmpfs => [
+                    "--tmpfs".to_owned(),
+                    format!("{}{}", volume.mount_path, read_only),
+                ],
+            };
+            workload_cfg.command_options.extend(mount_option);
         }
+        // [impl->swdd~podman-maps-resource-limits-to-options~1]
+        if let Some(resources) = &workload_spec.resources {
+            if let Some(cpus) = resources.cpus() {
+                workload_cfg
+                    .command_options
+                    .extend(["--cpus".to_owned(), cpus]);
+            }
+            if let Some(memory_limit) = resources.memory_limit {
+                workload_cfg
+                    .command_options
+                    .extend(["--memory".to_owned(), format!("{memory_limit}b")]);
+            }
+        }
+        // [impl->swdd~podman-maps-security-context-to-options~1]
+        if let Some(security_context) = &workload_spec.security_context {
+            if let Some(run_as_user) = security_context.run_as_user {
+                let user = match security_context.run_as_group {
+                    Some(run_as_group) => format!("{run_as_user}:{run_as_group}"),
+                    None => run_as_user.to_string(),
+                };
+                workload_cfg
+                    .command_options
+                    .extend(["--user".to_owned(), user]);
+            }
+            for capability in &security_context.capabilities.drop {
+                workload_cfg
+                    .command_options
+                    .extend(["--cap-drop".to_owned(), capability.clone()]);
+            }
+            for capability in &security_context.capabilities.add {
+                workload_cfg
+                    .command_options
+                    .extend(["--cap-add".to_owned(), capability.clone()]);
+            }
+            if security_context.read_only_root_filesystem {
+                workload_cfg.command_options.push("--read-only".to_owned());
+            }
+            if security_context.no_new_privileges {
+                workload_cfg
+                    .command_options
+                    .extend(["--security-opt".to_owned(), "no-new-privileges".to_owned()]);
+            }
+        }
+        Ok(workload_cfg)
     }
 }
 
@@ -76,7 +177,12 @@ impl From<TryFromWorkloadSpecError> for String {
 
 #[cfg(test)]
 mod tests {
-    use common::objects::generate_test_workload_spec_with_param;
+    use std::collections::HashMap;
+
+    use common::objects::{
+        generate_test_workload_spec_with_param, Capabilities, ExtraHost, Network, Port,
+        PortProtocol, ResourceLimits, SecurityContext, Volume, VolumeType,
+    };
 
     use super::PodmanRuntimeConfig;
     use crate::runtime_connectors::{
@@ -134,6 +240,253 @@ mod tests {
         );
     }
 
+    // [utest->swdd~podman-maps-hostname-and-extra-hosts-to-options~1]
+    #[test]
+    fn utest_podman_config_adds_hostname_and_extra_hosts_options() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.hostname = Some("vehicle-1".to_string());
+        workload_spec.extra_hosts = vec![
+            ExtraHost {
+                hostname: "database".to_string(),
+                ip: "10.0.0.5".to_string(),
+            },
+            ExtraHost {
+                hostname: "broker".to_string(),
+                ip: "fd00::5".to_string(),
+            },
+        ];
+
+        let podman_config = PodmanRuntimeConfig::try_from(&workload_spec).unwrap();
+
+        assert_eq!(
+            podman_config.command_options,
+            vec![
+                "--network=host",
+                "--hostname",
+                "vehicle-1",
+                "--add-host",
+                "database:10.0.0.5",
+                "--add-host",
+                "broker:fd00::5"
+            ]
+        );
+    }
+
+    // [utest->swdd~podman-maps-ports-to-publish-options~1]
+    #[test]
+    fn utest_podman_config_adds_publish_options_for_ports() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.ports = vec![
+            Port {
+                host_port: 8080,
+                container_port: 80,
+                protocol: PortProtocol::Tcp,
+            },
+            Port {
+                host_port: 5353,
+                container_port: 53,
+                protocol: PortProtocol::Udp,
+            },
+        ];
+
+        let podman_config = PodmanRuntimeConfig::try_from(&workload_spec).unwrap();
+
+        assert_eq!(
+            podman_config.command_options,
+            vec![
+                "--network=host",
+                "--publish",
+                "8080:80/tcp",
+                "--publish",
+                "5353:53/udp"
+            ]
+        );
+    }
+
+    // [utest->swdd~podman-maps-networks-to-network-options~1]
+    #[test]
+    fn utest_podman_config_adds_network_options_for_networks() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.runtime_config = "image: alpine:latest".to_string();
+        workload_spec.networks = vec![
+            Network {
+                name: "backend".to_string(),
+                aliases: vec!["api".to_string(), "api.local".to_string()],
+            },
+            Network {
+                name: "frontend".to_string(),
+                aliases: vec![],
+            },
+        ];
+
+        let podman_config = PodmanRuntimeConfig::try_from(&workload_spec).unwrap();
+
+        assert_eq!(
+            podman_config.command_options,
+            vec![
+                "--network",
+                "backend:alias=api,alias=api.local",
+                "--network",
+                "frontend"
+            ]
+        );
+    }
+
+    // [utest->swdd~podman-maps-env-to-env-options~1]
+    #[test]
+    fn utest_podman_config_adds_env_options_for_env() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.runtime_config =
+            "image: alpine:latest\ncommandOptions: [\"--env\", \"LOG_LEVEL=info\"]".to_string();
+        workload_spec.env = HashMap::from([
+            ("LOG_LEVEL".to_string(), "debug".to_string()),
+            ("GREETING".to_string(), "hello world".to_string()),
+        ]);
+
+        let podman_config = PodmanRuntimeConfig::try_from(&workload_spec).unwrap();
+
+        assert_eq!(
+            podman_config.command_options,
+            vec![
+                "--env",
+                "LOG_LEVEL=info",
+                "--env",
+                "GREETING=hello world",
+                "--env",
+                "LOG_LEVEL=debug"
+            ]
+        );
+    }
+
+    // [utest->swdd~podman-maps-resource-limits-to-options~1]
+    #[test]
+    fn utest_podman_config_adds_resource_limit_options() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.resources = Some(ResourceLimits {
+            cpu_quota: Some(150),
+            memory_limit: Some(256 * 1024 * 1024),
+        });
+
+        let podman_config = PodmanRuntimeConfig::try_from(&workload_spec).unwrap();
+
+        assert_eq!(
+            podman_config.command_options,
+            vec!["--network=host", "--cpus", "1.50", "--memory", "268435456b"]
+        );
+    }
+
+    // [utest->swdd~podman-maps-security-context-to-options~1]
+    #[test]
+    fn utest_podman_config_adds_security_context_options() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.runtime_config = "image: alpine:latest".to_string();
+        workload_spec.security_context = Some(SecurityContext {
+            run_as_user: Some(1000),
+            run_as_group: Some(100),
+            capabilities: Capabilities {
+                add: vec!["NET_BIND_SERVICE".into()],
+                drop: vec!["ALL".into()],
+            },
+            read_only_root_filesystem: true,
+            no_new_privileges: true,
+        });
+
+        let podman_config = PodmanRuntimeConfig::try_from(&workload_spec).unwrap();
+
+        assert_eq!(
+            podman_config.command_options,
+            vec![
+                "--user",
+                "1000:100",
+                "--cap-drop",
+                "ALL",
+                "--cap-add",
+                "NET_BIND_SERVICE",
+                "--read-only",
+                "--security-opt",
+                "no-new-privileges"
+            ]
+        );
+
+        workload_spec.security_context = Some(SecurityContext {
+            run_as_user: Some(0),
+            ..Default::default()
+        });
+
+        let podman_config = PodmanRuntimeConfig::try_from(&workload_spec).unwrap();
+
+        assert_eq!(podman_config.command_options, vec!["--user", "0"]);
+    }
+
+    // [utest->swdd~podman-maps-volumes-to-mount-options~1]
+    #[test]
+    fn utest_podman_config_adds_mount_options_for_volumes() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.volumes = vec![
+            Volume {
+                volume_type: VolumeType::HostPath,
+                source: "/opt/config".into(),
+                mount_path: "/etc/app".into(),
+                read_only: true,
+            },
+            Volume {
+                volume_type: VolumeType::Named,
+                source: "data".into(),
+                mount_path: "/var/lib/app".into(),
+                read_only: false,
+            },
+            Volume {
+                volume_type: VolumeType::Tmpfs,
+                source: String::new(),
+                mount_path: "/tmp".into(),
+                read_only: false,
+            },
+        ];
+
+        let podman_config = PodmanRuntimeConfig::try_from(&workload_spec).unwrap();
+
+        assert_eq!(
+            podman_config.command_options,
+            vec![
+                "--network=host",
+                "--volume",
+                "/opt/config:/etc/app:ro",
+                "--volume",
+                "data:/var/lib/app",
+                "--tmpfs",
+                "/tmp"
+            ]
+        );
+    }
+
     #[test]
     fn utest_podman_config_to_podman_run_config() {
         let podman_runtime_config = PodmanRuntimeConfig {
@@ -19,6 +19,7 @@ use mockall::automock;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{
     collections::HashMap,
+    net::{IpAddr, Ipv4Addr},
     ops::Deref,
     path::PathBuf,
     sync::Arc,
@@ -28,6 +29,7 @@ use tokio::sync::Mutex;
 
 #[cfg_attr(test, mockall_double::double)]
 use crate::runtime_connectors::cli_command::CliCommand;
+use crate::runtime_connectors::WorkloadResourceUsage;
 
 const PODMAN_CMD: &str = "podman";
 const API_PIPES_MOUNT_POINT: &str = "/run/ankaios/control_interface";
@@ -81,7 +83,7 @@ impl From<PodmanContainerInfo> for ContainerState {
     }
 }
 
-// [impl->swdd~podman-state-getter-maps-state~3]
+// [impl->swdd~podman-state-getter-maps-state~4]
 impl From<PodmanContainerInfo> for ExecutionState {
     fn from(value: PodmanContainerInfo) -> Self {
         match value.state.to_lowercase().as_str() {
@@ -93,6 +95,7 @@ impl From<PodmanContainerInfo> for ExecutionState {
                 ExecutionState::failed(format!("Exit code: '{}'", value.exit_code))
             }
             "running" => ExecutionState::running(),
+            "paused" => ExecutionState::paused(),
             "stopping" => ExecutionState::stopping(value.state),
             "stopped" => ExecutionState::stopping(value.state),
             "removing" => ExecutionState::stopping(value.state),
@@ -113,31 +116,53 @@ struct PodmanPsCache {
     cache: Arc<PodmanPsResult>,
 }
 
-struct TimedPodmanPsResult(Mutex<Option<PodmanPsCache>>);
+impl PodmanPsCache {
+    fn is_outdated(&self, reset_at: Option<Instant>) -> bool {
+        self.last_update.elapsed() > PODMAN_PS_CACHE_MAX_AGE
+            || reset_at.is_some_and(|reset_at| self.last_update <= reset_at)
+    }
+}
+
+struct TimedPodmanPsResult {
+    ps_cache: Mutex<Option<PodmanPsCache>>,
+    reset_at: std::sync::Mutex<Option<Instant>>,
+}
 
 impl TimedPodmanPsResult {
+    // [impl->swdd~podmancli-container-state-cache-reset~1]
     async fn reset(&self) {
-        *self.lock().await = None;
+        // Only mark the cache as outdated instead of waiting for a running refresh.
+        // This way all resets done during a refresh are handled by a single Podman call.
+        *self
+            .reset_at
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(Instant::now());
     }
 
     // [impl->swdd~podmancli-container-state-cache-refresh~1]
+    // [impl->swdd~podmancli-container-state-cache-reset~1]
     async fn get(&self) -> Arc<PodmanPsResult> {
         let mut guard = self.lock().await;
-
-        if let Some(value) = &mut *guard {
-            if value.last_update.elapsed() > PODMAN_PS_CACHE_MAX_AGE {
-                *value = Self::new_inner().await;
+        let reset_at = *self
+            .reset_at
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        match &mut *guard {
+            Some(value) if !value.is_outdated(reset_at) => value.cache.clone(),
+            _ => {
+                let ps_result = Self::new_inner().await;
+                let result = ps_result.cache.clone();
+                *guard = Some(ps_result);
+                result
             }
-            value.cache.clone()
-        } else {
-            let ps_result = Self::new_inner().await;
-            let result = ps_result.cache.clone();
-            *guard = Some(ps_result);
-            result
         }
     }
 
     async fn new_inner() -> PodmanPsCache {
+        // The time stamp is taken before calling Podman as the result
+        // contains all containers created before this point in time.
+        let last_update = Instant::now();
         let mut res = PodmanCli::list_states_internal().await;
         if res.is_err() {
             // This is a workaround for the known issue in podman (podman ps sometimes fails).
@@ -145,7 +170,7 @@ impl TimedPodmanPsResult {
             res = PodmanCli::list_states_internal().await;
         }
         PodmanPsCache {
-            last_update: Instant::now(),
+            last_update,
             cache: Arc::new(res.into()),
         }
     }
@@ -155,7 +180,7 @@ impl Deref for TimedPodmanPsResult {
     type Target = Mutex<Option<PodmanPsCache>>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.ps_cache
     }
 }
 
@@ -194,7 +219,10 @@ impl From<Result<Vec<PodmanContainerInfo>, String>> for PodmanPsResult {
     }
 }
 
-static LAST_PS_RESULT: TimedPodmanPsResult = TimedPodmanPsResult(Mutex::const_new(Option::None));
+static LAST_PS_RESULT: TimedPodmanPsResult = TimedPodmanPsResult {
+    ps_cache: Mutex::const_new(Option::None),
+    reset_at: std::sync::Mutex::new(Option::None),
+};
 
 pub struct PodmanCli {}
 
@@ -428,6 +456,157 @@ impl PodmanCli {
             .map_err(|err| format!("Could not parse podman output:{}", err))
     }
 
+    // [impl->swdd~podman-state-getter-provides-resource-usage~1]
+    pub async fn get_resource_usage(workload_id: &str) -> Result<WorkloadResourceUsage, String> {
+        let output = CliCommand::new(PODMAN_CMD)
+            .args(&[
+                "stats",
+                "--no-stream",
+                "--format={{.ContainerStats.CPU}} {{.ContainerStats.MemUsage}}",
+                workload_id,
+            ])
+            .exec()
+            .await?;
+
+        parse_resource_usage(&mut output.split_whitespace())
+            .ok_or_else(|| format!("Could not parse podman stats output: '{}'", output.trim()))
+    }
+
+    // [impl->swdd~podman-collects-resource-usage-of-running-containers~1]
+    pub async fn list_resource_usage() -> Result<Vec<(String, WorkloadResourceUsage)>, String> {
+        let output = CliCommand::new(PODMAN_CMD)
+            .args(&[
+                "stats",
+                "--no-stream",
+                "--format={{.ContainerStats.Name}} {{.ContainerStats.CPU}} {{.ContainerStats.MemUsage}}",
+            ])
+            .exec()
+            .await?;
+
+        output
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let mut values = line.split_whitespace();
+                values
+                    .next()
+                    .map(ToOwned::to_owned)
+                    .zip(parse_resource_usage(&mut values))
+                    .ok_or_else(|| format!("Could not parse podman stats output: '{}'", line))
+            })
+            .collect()
+    }
+
+    // [impl->swdd~podman-state-getter-executes-health-command~1]
+    pub async fn exec_command(workload_id: &str, command: &[String]) -> Result<(), String> {
+        let mut args = vec!["exec", workload_id];
+        args.extend(command.iter().map(String::as_str));
+        CliCommand::new(PODMAN_CMD).args(&args).exec().await?;
+        Ok(())
+    }
+
+    // [impl->swdd~podman-executes-command-in-workload~1]
+    #[cfg_attr(test, allow(dead_code))]
+    pub fn spawn_exec_session(
+        workload_id: &str,
+        command: &[String],
+    ) -> Result<tokio::process::Child, String> {
+        tokio::process::Command::new(PODMAN_CMD)
+            .args(["exec", "--interactive", workload_id])
+            .args(command)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| format!("Could not execute '{}': '{}'", PODMAN_CMD, err))
+    }
+
+    // [impl->swdd~podman-follows-workload-logs~1]
+    #[cfg_attr(test, allow(dead_code))]
+    pub fn spawn_log_follower(
+        workload_id: &str,
+        only_new_lines: bool,
+    ) -> Result<tokio::process::Child, String> {
+        let mut args = vec!["logs", "--follow"];
+        if only_new_lines {
+            args.push("--tail=0");
+        }
+        args.push(workload_id);
+        tokio::process::Command::new(PODMAN_CMD)
+            .args(args)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| format!("Could not execute '{}': '{}'", PODMAN_CMD, err))
+    }
+
+    // [impl->swdd~podman-provides-workload-address~1]
+    pub async fn get_container_address(workload_id: &str) -> Result<IpAddr, String> {
+        let output = CliCommand::new(PODMAN_CMD)
+            .args(&[
+                "inspect",
+                "--type=container",
+                "--format={{.NetworkSettings.IPAddress}}\
+                {{range .NetworkSettings.Networks}} {{.IPAddress}}{{end}}",
+                workload_id,
+            ])
+            .exec()
+            .await?;
+
+        match output.split_whitespace().next() {
+            Some(address) => address
+                .parse()
+                .map_err(|err| format!("Could not parse container address '{}': {}", address, err)),
+            // containers in the host network have no address of their own
+            None => Ok(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+        }
+    }
+
+    // [impl->swdd~podman-image-gc-removes-unused-images~1]
+    pub async fn list_images() -> Result<Vec<PodmanImageInfo>, String> {
+        let output = CliCommand::new(PODMAN_CMD)
+            .args(&["images", "--format=json"])
+            .exec()
+            .await?;
+
+        serde_json::from_str(&output)
+            .map_err(|err| format!("Could not parse podman output:{}", err))
+    }
+
+    // [impl->swdd~podman-image-gc-keeps-used-images~1]
+    pub async fn list_used_image_ids() -> Result<Vec<String>, String> {
+        let output = CliCommand::new(PODMAN_CMD)
+            .args(&["ps", "--all", "--format={{.ImageID}}"])
+            .exec()
+            .await?;
+        Ok(output
+            .split('\n')
+            .map(|x| x.trim().to_string())
+            .filter(|x| !x.is_empty())
+            .collect())
+    }
+
+    // [impl->swdd~podman-image-gc-removes-unused-images~1]
+    pub async fn remove_image(image_id: &str) -> Result<(), String> {
+        CliCommand::new(PODMAN_CMD)
+            .args(&["rmi", image_id])
+            .exec()
+            .await?;
+        Ok(())
+    }
+
+    // [impl->swdd~podman-image-puller-pulls-pinned-images~1]
+    pub async fn pull_image(image: &str) -> Result<String, String> {
+        let output = CliCommand::new(PODMAN_CMD)
+            .args(&["pull", "--quiet", image])
+            .exec()
+            .await?;
+        Ok(output.trim().to_string())
+    }
+
     pub async fn list_volumes_by_name(name: &str) -> Result<Vec<String>, String> {
         let output = CliCommand::new(PODMAN_CMD)
             .args(&[
@@ -498,6 +677,32 @@ impl PodmanCli {
         CliCommand::new(PODMAN_CMD).args(&args).exec().await?;
         Ok(())
     }
+
+    // [impl->swdd~podman-stops-workload-within-grace-period~1]
+    pub async fn stop_workload_by_id(
+        workload_id: &str,
+        grace_period_secs: u32,
+    ) -> Result<(), String> {
+        // Podman kills the container itself if it does not stop within the timeout.
+        let grace_period_secs = grace_period_secs.to_string();
+        let args = vec!["stop", "--ignore", "-t", &grace_period_secs, workload_id];
+        CliCommand::new(PODMAN_CMD).args(&args).exec().await?;
+        Ok(())
+    }
+
+    // [impl->swdd~podman-pauses-workload~1]
+    pub async fn pause_workload_by_id(workload_id: &str) -> Result<(), String> {
+        let args = vec!["pause", workload_id];
+        CliCommand::new(PODMAN_CMD).args(&args).exec().await?;
+        Ok(())
+    }
+
+    // [impl->swdd~podman-pauses-workload~1]
+    pub async fn unpause_workload_by_id(workload_id: &str) -> Result<(), String> {
+        let args = vec!["unpause", workload_id];
+        CliCommand::new(PODMAN_CMD).args(&args).exec().await?;
+        Ok(())
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -511,6 +716,15 @@ struct DataLabel {
     data: String,
 }
 
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub struct PodmanImageInfo {
+    pub id: String,
+    pub size: u64,
+    // Creation time of the image as seconds since the Unix epoch
+    pub created: u64,
+}
+
 #[derive(Deserialize, Serialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 struct PodmanContainerInfo {
@@ -533,6 +747,17 @@ where
     Ok(opt.unwrap_or_default())
 }
 
+fn parse_resource_usage<'a>(
+    values: &mut impl Iterator<Item = &'a str>,
+) -> Option<WorkloadResourceUsage> {
+    let cpu_usage = values.next()?.parse::<f32>().ok()?;
+    let memory_usage = values.next()?.parse::<u64>().ok()?;
+    Some(WorkloadResourceUsage {
+        cpu_usage,
+        memory_usage,
+    })
+}
+
 //////////////////////////////////////////////////////////////////////////////
 //                 ########  #######    #########  #########                //
 //                    ##     ##        ##             ##                    //
@@ -545,8 +770,9 @@ where
 #[cfg(test)]
 mod tests {
     use super::{ContainerState, PodmanCli, PodmanPsCache};
+    use crate::runtime_connectors::WorkloadResourceUsage;
 
-    use super::PodmanContainerInfo;
+    use super::{PodmanContainerInfo, PodmanImageInfo};
     use crate::test_helper::MOCKALL_CONTEXT_SYNC;
     use common::objects::ExecutionState;
     use common::test_utils::serialize_as_map;
@@ -1152,7 +1378,30 @@ mod tests {
         assert_eq!(res, Err(SAMPLE_ERROR_MESSAGE.to_string()));
     }
 
-    // [utest->swdd~podman-state-getter-maps-state~3]
+    // [utest->swdd~podman-state-getter-maps-state~4]
+    #[tokio::test]
+    async fn utest_list_states_by_id_paused() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+        PodmanCli::reset_ps_cache().await;
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&["ps", "--all", "--format=json"])
+                .exec_returns(Ok([TestPodmanContainerInfo {
+                    id: "test_id",
+                    state: "paused",
+                    ..Default::default()
+                }]
+                .to_json())),
+        );
+
+        let res = PodmanCli::list_states_by_id("test_id").await;
+        assert_eq!(res, Ok(Some(ExecutionState::paused())));
+    }
+
+    // [utest->swdd~podman-state-getter-maps-state~4]
     // [utest->swdd~podmancli-container-state-cache-refresh~1]
     #[tokio::test]
     async fn utest_list_states_by_id_created() {
@@ -1176,7 +1425,7 @@ mod tests {
         assert_eq!(res, Ok(Some(ExecutionState::starting("created"))));
     }
 
-    // [utest->swdd~podman-state-getter-maps-state~3]
+    // [utest->swdd~podman-state-getter-maps-state~4]
     // [utest->swdd~podmancli-container-state-cache-refresh~1]
     #[tokio::test]
     async fn utest_list_states_by_id_configured() {
@@ -1200,7 +1449,7 @@ mod tests {
         assert_eq!(res, Ok(Some(ExecutionState::starting("configured"))));
     }
 
-    // [utest->swdd~podman-state-getter-maps-state~3]
+    // [utest->swdd~podman-state-getter-maps-state~4]
     // [utest->swdd~podmancli-container-state-cache-refresh~1]
     #[tokio::test]
     async fn utest_list_states_by_id_initialized() {
@@ -1224,7 +1473,7 @@ mod tests {
         assert_eq!(res, Ok(Some(ExecutionState::starting("initialized"))));
     }
 
-    // [utest->swdd~podman-state-getter-maps-state~3]
+    // [utest->swdd~podman-state-getter-maps-state~4]
     // [utest->swdd~podmancli-container-state-cache-refresh~1]
     #[tokio::test]
     async fn utest_list_states_by_id_succeeded() {
@@ -1249,7 +1498,7 @@ mod tests {
         assert_eq!(res, Ok(Some(ExecutionState::succeeded())));
     }
 
-    // [utest->swdd~podman-state-getter-maps-state~3]
+    // [utest->swdd~podman-state-getter-maps-state~4]
     // [utest->swdd~podmancli-container-state-cache-refresh~1]
     #[tokio::test]
     async fn utest_list_states_by_id_failed() {
@@ -1274,7 +1523,7 @@ mod tests {
         assert_eq!(res, Ok(Some(ExecutionState::failed("Exit code: '1'"))));
     }
 
-    // [utest->swdd~podman-state-getter-maps-state~3]
+    // [utest->swdd~podman-state-getter-maps-state~4]
     // [utest->swdd~podmancli-container-state-cache-refresh~1]
     #[tokio::test]
     async fn utest_list_states_by_id_running() {
@@ -1298,7 +1547,7 @@ mod tests {
         assert_eq!(res, Ok(Some(ExecutionState::running())));
     }
 
-    // [utest->swdd~podman-state-getter-maps-state~3]
+    // [utest->swdd~podman-state-getter-maps-state~4]
     // [utest->swdd~podmancli-container-state-cache-refresh~1]
     #[tokio::test]
     async fn utest_list_states_by_id_stopping() {
@@ -1322,7 +1571,7 @@ mod tests {
         assert_eq!(res, Ok(Some(ExecutionState::stopping("stopping"))));
     }
 
-    // [utest->swdd~podman-state-getter-maps-state~3]
+    // [utest->swdd~podman-state-getter-maps-state~4]
     // [utest->swdd~podmancli-container-state-cache-refresh~1]
     #[tokio::test]
     async fn utest_list_states_by_id_stopped() {
@@ -1346,7 +1595,7 @@ mod tests {
         assert_eq!(res, Ok(Some(ExecutionState::stopping("stopped"))));
     }
 
-    // [utest->swdd~podman-state-getter-maps-state~3]
+    // [utest->swdd~podman-state-getter-maps-state~4]
     // [utest->swdd~podmancli-container-state-cache-refresh~1]
     #[tokio::test]
     async fn utest_list_states_by_id_removing() {
@@ -1370,7 +1619,7 @@ mod tests {
         assert_eq!(res, Ok(Some(ExecutionState::stopping("removing"))));
     }
 
-    // [utest->swdd~podman-state-getter-maps-state~3]
+    // [utest->swdd~podman-state-getter-maps-state~4]
     // [utest->swdd~podmancli-container-state-cache-refresh~1]
     #[tokio::test]
     async fn utest_list_states_by_id_unknown() {
@@ -1394,7 +1643,7 @@ mod tests {
         assert_eq!(res, Ok(Some(ExecutionState::unknown("unknown"))));
     }
 
-    // [utest->swdd~podman-state-getter-maps-state~3]
+    // [utest->swdd~podman-state-getter-maps-state~4]
     // [utest->swdd~podmancli-container-state-cache-refresh~1]
     #[tokio::test]
     async fn utest_list_states_by_id_undefined() {
@@ -1517,6 +1766,43 @@ mod tests {
         assert_eq!(res, Ok(Some(ExecutionState::running())));
     }
 
+    // [utest->swdd~podmancli-container-state-cache-reset~1]
+    #[tokio::test]
+    async fn utest_list_states_by_id_podman_refreshes_once_after_multiple_resets() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        *super::LAST_PS_RESULT.lock().await = Some(PodmanPsCache {
+            last_update: time::Instant::now(),
+            cache: Arc::new(super::PodmanPsResult {
+                container_states: Ok([("test_id".into(), ExecutionState::starting("created"))]
+                    .into_iter()
+                    .collect()),
+                pod_states: Err("".into()),
+            }),
+        });
+
+        PodmanCli::reset_ps_cache().await;
+        PodmanCli::reset_ps_cache().await;
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&["ps", "--all", "--format=json"])
+                .exec_returns(Ok([TestPodmanContainerInfo {
+                    id: "test_id",
+                    state: "running",
+                    ..Default::default()
+                }]
+                .to_json())),
+        );
+
+        let res = PodmanCli::list_states_by_id("test_id").await;
+        assert_eq!(res, Ok(Some(ExecutionState::running())));
+        let res = PodmanCli::list_states_by_id("test_id").await;
+        assert_eq!(res, Ok(Some(ExecutionState::running())));
+    }
+
     #[tokio::test]
     async fn utest_list_states_by_id_broken_response_retry_failed() {
         let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
@@ -1839,6 +2125,294 @@ mod tests {
         );
     }
 
+    // [utest->swdd~podman-state-getter-provides-resource-usage~1]
+    #[tokio::test]
+    async fn utest_get_resource_usage_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&[
+                    "stats",
+                    "--no-stream",
+                    "--format={{.ContainerStats.CPU}} {{.ContainerStats.MemUsage}}",
+                    "test_id",
+                ])
+                .exec_returns(Ok("12.5 4096\n".into())),
+        );
+
+        let res = PodmanCli::get_resource_usage("test_id").await;
+
+        assert_eq!(
+            res,
+            Ok(WorkloadResourceUsage {
+                cpu_usage: 12.5,
+                memory_usage: 4096
+            })
+        );
+    }
+
+    // [utest->swdd~podman-state-getter-provides-resource-usage~1]
+    #[tokio::test]
+    async fn utest_get_resource_usage_fails_on_unparsable_output() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&[
+                    "stats",
+                    "--no-stream",
+                    "--format={{.ContainerStats.CPU}} {{.ContainerStats.MemUsage}}",
+                    "test_id",
+                ])
+                .exec_returns(Ok("12.5%".into())),
+        );
+
+        let res = PodmanCli::get_resource_usage("test_id").await;
+
+        assert!(matches!(res, Err(msg) if msg.contains("12.5%")));
+    }
+
+    // [utest->swdd~podman-collects-resource-usage-of-running-containers~1]
+    #[tokio::test]
+    async fn utest_list_resource_usage_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&[
+                    "stats",
+                    "--no-stream",
+                    "--format={{.ContainerStats.Name}} {{.ContainerStats.CPU}} {{.ContainerStats.MemUsage}}",
+                ])
+                .exec_returns(Ok(
+                    "workload_1.hash.agent_A 12.5 4096\nworkload_2.hash.agent_A 0 1024\n".into(),
+                )),
+        );
+
+        let res = PodmanCli::list_resource_usage().await;
+
+        assert_eq!(
+            res,
+            Ok(vec![
+                (
+                    "workload_1.hash.agent_A".to_string(),
+                    WorkloadResourceUsage {
+                        cpu_usage: 12.5,
+                        memory_usage: 4096
+                    }
+                ),
+                (
+                    "workload_2.hash.agent_A".to_string(),
+                    WorkloadResourceUsage {
+                        cpu_usage: 0.0,
+                        memory_usage: 1024
+                    }
+                )
+            ])
+        );
+    }
+
+    // [utest->swdd~podman-collects-resource-usage-of-running-containers~1]
+    #[tokio::test]
+    async fn utest_list_resource_usage_fails_on_unparsable_output() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&[
+                    "stats",
+                    "--no-stream",
+                    "--format={{.ContainerStats.Name}} {{.ContainerStats.CPU}} {{.ContainerStats.MemUsage}}",
+                ])
+                .exec_returns(Ok("workload_1.hash.agent_A 12.5%".into())),
+        );
+
+        let res = PodmanCli::list_resource_usage().await;
+
+        assert!(matches!(res, Err(msg) if msg.contains("workload_1.hash.agent_A 12.5%")));
+    }
+
+    // [utest->swdd~podman-state-getter-executes-health-command~1]
+    #[tokio::test]
+    async fn utest_exec_command_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&["exec", "test_id", "pg_isready", "-q"])
+                .exec_returns(Ok("".into())),
+        );
+
+        let res = PodmanCli::exec_command("test_id", &["pg_isready".into(), "-q".into()]).await;
+
+        assert_eq!(res, Ok(()));
+    }
+
+    // [utest->swdd~podman-state-getter-executes-health-command~1]
+    #[tokio::test]
+    async fn utest_exec_command_fails() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&["exec", "test_id", "pg_isready"])
+                .exec_returns(Err(SAMPLE_ERROR_MESSAGE.into())),
+        );
+
+        let res = PodmanCli::exec_command("test_id", &["pg_isready".into()]).await;
+
+        assert_eq!(res, Err(SAMPLE_ERROR_MESSAGE.into()));
+    }
+
+    // [utest->swdd~podman-provides-workload-address~1]
+    #[tokio::test]
+    async fn utest_get_container_address_returns_first_address() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&[
+                    "inspect",
+                    "--type=container",
+                    "--format={{.NetworkSettings.IPAddress}}\
+                    {{range .NetworkSettings.Networks}} {{.IPAddress}}{{end}}",
+                    "test_id",
+                ])
+                .exec_returns(Ok(" 10.88.0.5 10.89.0.2\n".into())),
+        );
+
+        let res = PodmanCli::get_container_address("test_id").await;
+
+        assert_eq!(res, Ok("10.88.0.5".parse().unwrap()));
+    }
+
+    // [utest->swdd~podman-provides-workload-address~1]
+    #[tokio::test]
+    async fn utest_get_container_address_returns_localhost_for_host_network() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&[
+                    "inspect",
+                    "--type=container",
+                    "--format={{.NetworkSettings.IPAddress}}\
+                    {{range .NetworkSettings.Networks}} {{.IPAddress}}{{end}}",
+                    "test_id",
+                ])
+                .exec_returns(Ok("\n".into())),
+        );
+
+        let res = PodmanCli::get_container_address("test_id").await;
+
+        assert_eq!(res, Ok("127.0.0.1".parse().unwrap()));
+    }
+
+    // [utest->swdd~podman-image-gc-removes-unused-images~1]
+    #[tokio::test]
+    async fn utest_list_images_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&["images", "--format=json"])
+                .exec_returns(Ok(
+                    r#"[{"Id":"image_id_1","Size":1024,"Created":1700000000,"Names":["alpine:latest"]},{"Id":"image_id_2","Size":2048,"Created":1700000100}]"#.into(),
+                )),
+        );
+
+        let res = PodmanCli::list_images().await;
+
+        assert_eq!(
+            res,
+            Ok(vec![
+                PodmanImageInfo {
+                    id: "image_id_1".into(),
+                    size: 1024,
+                    created: 1700000000
+                },
+                PodmanImageInfo {
+                    id: "image_id_2".into(),
+                    size: 2048,
+                    created: 1700000100
+                }
+            ])
+        );
+    }
+
+    // [utest->swdd~podman-image-gc-keeps-used-images~1]
+    #[tokio::test]
+    async fn utest_list_used_image_ids_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&["ps", "--all", "--format={{.ImageID}}"])
+                .exec_returns(Ok("image_id_1\nimage_id_2\n\n".into())),
+        );
+
+        let res = PodmanCli::list_used_image_ids().await;
+
+        assert_eq!(res, Ok(vec!["image_id_1".into(), "image_id_2".into()]));
+    }
+
+    // [utest->swdd~podman-image-gc-removes-unused-images~1]
+    #[tokio::test]
+    async fn utest_remove_image_fails() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&["rmi", "image_id_1"])
+                .exec_returns(Err(SAMPLE_ERROR_MESSAGE.into())),
+        );
+
+        let res = PodmanCli::remove_image("image_id_1").await;
+
+        assert_eq!(res, Err(SAMPLE_ERROR_MESSAGE.into()));
+    }
+
+    // [utest->swdd~podman-image-puller-pulls-pinned-images~1]
+    #[tokio::test]
+    async fn utest_pull_image_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&["pull", "--quiet", "alpine:latest"])
+                .exec_returns(Ok("image_id_1\n".into())),
+        );
+
+        let res = PodmanCli::pull_image("alpine:latest").await;
+
+        assert_eq!(res, Ok("image_id_1".into()));
+    }
+
     #[tokio::test]
     async fn utest_list_volumes_by_name_success() {
         let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
@@ -2141,6 +2715,46 @@ mod tests {
         assert_eq!(res, Ok(()));
     }
 
+    // [utest->swdd~podman-stops-workload-within-grace-period~1]
+    #[tokio::test]
+    async fn utest_stop_workload_by_id_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&["stop", "--ignore", "-t", "30", "test_id"])
+                .exec_returns(Ok("".to_string())),
+        );
+
+        let res = PodmanCli::stop_workload_by_id("test_id", 30).await;
+        assert_eq!(res, Ok(()));
+    }
+
+    // [utest->swdd~podman-pauses-workload~1]
+    #[tokio::test]
+    async fn utest_pause_and_unpause_workload_by_id_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&["pause", "test_id"])
+                .exec_returns(Ok("".to_string())),
+        );
+        super::CliCommand::new_expect(
+            "podman",
+            super::CliCommand::default()
+                .expect_args(&["unpause", "test_id"])
+                .exec_returns(Ok("".to_string())),
+        );
+
+        assert_eq!(PodmanCli::pause_workload_by_id("test_id").await, Ok(()));
+        assert_eq!(PodmanCli::unpause_workload_by_id("test_id").await, Ok(()));
+    }
+
     #[derive(Serialize, Clone, Default)]
     #[serde(rename_all = "PascalCase")]
     struct TestPodmanContainerInfo<'a> {
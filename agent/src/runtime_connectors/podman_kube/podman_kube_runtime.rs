@@ -28,8 +28,8 @@ use crate::runtime_connectors::podman_cli::PodmanCli;
 use crate::{
     generic_polling_state_checker::GenericPollingStateChecker,
     runtime_connectors::{
-        podman_cli, ReusableWorkloadState, RuntimeConnector, RuntimeError, RuntimeStateGetter,
-        StateChecker,
+        podman_cli, registry_auth_file::RegistryAuthFile, ResourceUsageCollector,
+        ReusableWorkloadState, RuntimeConnector, RuntimeError, RuntimeStateGetter, StateChecker,
     },
     workload_state::WorkloadStateSender,
 };
@@ -93,6 +93,8 @@ impl PodmanKubeRuntime {
     }
 }
 
+impl ResourceUsageCollector for PodmanKubeRuntime {}
+
 #[async_trait]
 // [impl->swdd~podman-kube-implements-runtime-connector~1]
 impl RuntimeConnector<PodmanKubeWorkloadId, GenericPollingStateChecker> for PodmanKubeRuntime {
@@ -148,9 +150,22 @@ impl RuntimeConnector<PodmanKubeWorkloadId, GenericPollingStateChecker> for Podm
     ) -> Result<(PodmanKubeWorkloadId, GenericPollingStateChecker), RuntimeError> {
         let instance_name = workload_spec.instance_name.clone();
 
-        let workload_config =
+        let mut workload_config =
             PodmanKubeRuntimeConfig::try_from(&workload_spec).map_err(RuntimeError::Create)?;
 
+        // [impl->swdd~podman-kube-uses-registry-auth~1]
+        let registry_auth_file = workload_spec
+            .registry_auth
+            .as_deref()
+            .map(RegistryAuthFile::create)
+            .transpose()
+            .map_err(RuntimeError::Create)?;
+        if let Some(registry_auth_file) = &registry_auth_file {
+            workload_config
+                .play_options
+                .push(registry_auth_file.authfile_option());
+        }
+
         // [impl->swdd~podman-kube-create-workload-creates-config-volume~1]
         // [impl->swdd~podman-kube-create-continues-if-cannot-create-volume~1]
         PodmanCli::store_data_as_volume(
@@ -174,6 +189,7 @@ impl RuntimeConnector<PodmanKubeWorkloadId, GenericPollingStateChecker> for Podm
         )
         .await
         .map_err(RuntimeError::Create)?;
+        drop(registry_auth_file);
 
         // [impl->swdd~podman-kube-create-workload-creates-pods-volume~1]
         // [impl->swdd~podman-kube-create-continues-if-cannot-create-volume~1]
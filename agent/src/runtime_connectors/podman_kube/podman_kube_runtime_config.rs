@@ -37,6 +37,66 @@ impl TryFrom<&WorkloadSpec> for PodmanKubeRuntimeConfig {
                 workload_spec.runtime
             ));
         }
+        // [impl->swdd~podman-kube-rejects-hostname-and-extra-hosts~1]
+        if workload_spec.hostname.is_some() || !workload_spec.extra_hosts.is_empty() {
+            return Err(format!(
+                "The fields 'hostname' and 'extraHosts' are not supported by the runtime '{}', set them in the Kubernetes manifest instead",
+                PODMAN_KUBE_RUNTIME_NAME
+            ));
+        }
+        // [impl->swdd~podman-kube-rejects-ports~1]
+        if !workload_spec.ports.is_empty() {
+            return Err(format!(
+                "The field 'ports' is not supported by the runtime '{}', set the host ports in the Kubernetes manifest instead",
+                PODMAN_KUBE_RUNTIME_NAME
+            ));
+        }
+        // [impl->swdd~podman-kube-rejects-volumes~1]
+        if !workload_spec.volumes.is_empty() {
+            return Err(format!(
+                "The field 'volumes' is not supported by the runtime '{}', set the volumes in the Kubernetes manifest instead",
+                PODMAN_KUBE_RUNTIME_NAME
+            ));
+        }
+        // [impl->swdd~podman-kube-rejects-networks~1]
+        if !workload_spec.networks.is_empty() {
+            return Err(format!(
+                "The field 'networks' is not supported by the runtime '{}', set the network of the pod with the '--network' option in the play options instead",
+                PODMAN_KUBE_RUNTIME_NAME
+            ));
+        }
+        // [impl->swdd~podman-kube-rejects-env~1]
+        if !workload_spec.env.is_empty() {
+            return Err(format!(
+                "The field 'env' is not supported by the runtime '{}', set the environment variables of the containers in the Kubernetes manifest instead",
+                PODMAN_KUBE_RUNTIME_NAME
+            ));
+        }
+        // [impl->swdd~podman-kube-rejects-security-context~1]
+        if workload_spec.security_context.is_some() {
+            return Err(format!(
+                "The field 'securityContext' is not supported by the runtime '{}', set the security context of the containers in the Kubernetes manifest instead",
+                PODMAN_KUBE_RUNTIME_NAME
+            ));
+        }
+        // [impl->swdd~podman-kube-rejects-resource-limits~1]
+        if workload_spec.resources.is_some() {
+            return Err(format!(
+                "The field 'resources' is not supported by the runtime '{}', set the resource limits in the Kubernetes manifest instead",
+                PODMAN_KUBE_RUNTIME_NAME
+            ));
+        }
+        // [impl->swdd~podman-kube-rejects-command-health-checks~1]
+        if workload_spec
+            .health_check
+            .as_ref()
+            .is_some_and(|health_check| health_check.command.is_some())
+        {
+            return Err(format!(
+                "Command health checks are not supported by the runtime '{}', use a 'tcp' or 'http' probe instead",
+                PODMAN_KUBE_RUNTIME_NAME
+            ));
+        }
         match serde_yaml::from_str(workload_spec.runtime_config.as_str()) {
             Ok(workload_cfg) => Ok(workload_cfg),
             Err(e) => Err(e.to_string()),
@@ -54,7 +114,12 @@ impl TryFrom<&WorkloadSpec> for PodmanKubeRuntimeConfig {
 
 #[cfg(test)]
 mod tests {
-    use common::objects::generate_test_workload_spec_with_param;
+    use std::collections::HashMap;
+
+    use common::objects::{
+        generate_test_workload_spec_with_param, HealthCheck, Network, Port, PortProtocol,
+        ResourceLimits, SecurityContext, TcpProbe, Volume, VolumeType,
+    };
 
     use super::{PodmanKubeRuntimeConfig, PODMAN_KUBE_RUNTIME_NAME};
 
@@ -102,4 +167,152 @@ mod tests {
                 == *MANIFEST_CONTENT
         );
     }
+
+    // [utest->swdd~podman-kube-rejects-hostname-and-extra-hosts~1]
+    #[tokio::test]
+    async fn utest_podman_kube_config_failure_hostname_set() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_KUBE_RUNTIME_NAME.to_string(),
+        );
+
+        workload_spec.runtime_config = format!("manifest: {}", MANIFEST_CONTENT);
+        workload_spec.hostname = Some("vehicle-1".to_string());
+
+        assert!(PodmanKubeRuntimeConfig::try_from(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~podman-kube-rejects-ports~1]
+    #[tokio::test]
+    async fn utest_podman_kube_config_failure_ports_set() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_KUBE_RUNTIME_NAME.to_string(),
+        );
+
+        workload_spec.runtime_config = format!("manifest: {}", MANIFEST_CONTENT);
+        workload_spec.ports = vec![Port {
+            host_port: 8080,
+            container_port: 80,
+            protocol: PortProtocol::Tcp,
+        }];
+
+        assert!(PodmanKubeRuntimeConfig::try_from(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~podman-kube-rejects-volumes~1]
+    #[tokio::test]
+    async fn utest_podman_kube_config_failure_volumes_set() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_KUBE_RUNTIME_NAME.to_string(),
+        );
+
+        workload_spec.runtime_config = format!("manifest: {}", MANIFEST_CONTENT);
+        workload_spec.volumes = vec![Volume {
+            volume_type: VolumeType::Tmpfs,
+            mount_path: "/tmp".into(),
+            ..Default::default()
+        }];
+
+        assert!(PodmanKubeRuntimeConfig::try_from(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~podman-kube-rejects-networks~1]
+    #[tokio::test]
+    async fn utest_podman_kube_config_failure_networks_set() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_KUBE_RUNTIME_NAME.to_string(),
+        );
+
+        workload_spec.runtime_config = format!("manifest: {}", MANIFEST_CONTENT);
+        workload_spec.networks = vec![Network {
+            name: "backend".into(),
+            aliases: vec![],
+        }];
+
+        assert!(PodmanKubeRuntimeConfig::try_from(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~podman-kube-rejects-env~1]
+    #[tokio::test]
+    async fn utest_podman_kube_config_failure_env_set() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_KUBE_RUNTIME_NAME.to_string(),
+        );
+
+        workload_spec.runtime_config = format!("manifest: {}", MANIFEST_CONTENT);
+        workload_spec.env = HashMap::from([("LOG_LEVEL".to_string(), "debug".to_string())]);
+
+        assert!(PodmanKubeRuntimeConfig::try_from(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~podman-kube-rejects-security-context~1]
+    #[tokio::test]
+    async fn utest_podman_kube_config_failure_security_context_set() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_KUBE_RUNTIME_NAME.to_string(),
+        );
+
+        workload_spec.runtime_config = format!("manifest: {}", MANIFEST_CONTENT);
+        workload_spec.security_context = Some(SecurityContext {
+            read_only_root_filesystem: true,
+            ..Default::default()
+        });
+
+        assert!(PodmanKubeRuntimeConfig::try_from(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~podman-kube-rejects-resource-limits~1]
+    #[tokio::test]
+    async fn utest_podman_kube_config_failure_resources_set() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_KUBE_RUNTIME_NAME.to_string(),
+        );
+
+        workload_spec.runtime_config = format!("manifest: {}", MANIFEST_CONTENT);
+        workload_spec.resources = Some(ResourceLimits {
+            cpu_quota: Some(100),
+            memory_limit: None,
+        });
+
+        assert!(PodmanKubeRuntimeConfig::try_from(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~podman-kube-rejects-command-health-checks~1]
+    #[tokio::test]
+    async fn utest_podman_kube_config_failure_command_health_check_set() {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            PODMAN_KUBE_RUNTIME_NAME.to_string(),
+        );
+
+        workload_spec.runtime_config = format!("manifest: {}", MANIFEST_CONTENT);
+        workload_spec.health_check = Some(HealthCheck {
+            command: Some(vec!["pg_isready".into()]),
+            ..Default::default()
+        });
+        assert!(PodmanKubeRuntimeConfig::try_from(&workload_spec).is_err());
+
+        workload_spec.health_check = Some(HealthCheck {
+            tcp: Some(TcpProbe {
+                host: "localhost".into(),
+                port: 5432,
+            }),
+            ..Default::default()
+        });
+        assert!(PodmanKubeRuntimeConfig::try_from(&workload_spec).is_ok());
+    }
 }
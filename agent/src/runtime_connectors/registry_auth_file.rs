@@ -0,0 +1,118 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fs::{remove_file, OpenOptions},
+    io::Write,
+    os::unix::fs::OpenOptionsExt,
+    path::PathBuf,
+};
+
+const AUTH_FILE_PREFIX: &str = "ankaios-registry-auth-";
+const AUTH_FILE_MODE: u32 = 0o600;
+
+// The registry credentials of a workload are only written to disk while the image is pulled.
+// The file is readable by the agent user only and is removed when the value is dropped.
+#[derive(Debug)]
+pub struct RegistryAuthFile {
+    path: PathBuf,
+}
+
+impl RegistryAuthFile {
+    // [impl->swdd~podman-uses-registry-auth~1]
+    pub fn create(registry_auth: &str) -> Result<Self, String> {
+        let auth_content: serde_json::Value = serde_json::from_str(registry_auth)
+            .map_err(|err| format!("The field 'registryAuth' is not valid JSON: '{}'", err))?;
+        if !auth_content.is_object() {
+            return Err("The field 'registryAuth' must contain a JSON object".to_string());
+        }
+
+        let path = std::env::temp_dir().join(format!(
+            "{}{:016x}.json",
+            AUTH_FILE_PREFIX,
+            rand::random::<u64>()
+        ));
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(AUTH_FILE_MODE)
+            .open(&path)
+            .map_err(|err| format!("Could not create the registry auth file: '{}'", err))?;
+        let registry_auth_file = RegistryAuthFile { path };
+        file.write_all(registry_auth.as_bytes())
+            .map_err(|err| format!("Could not write the registry auth file: '{}'", err))?;
+
+        Ok(registry_auth_file)
+    }
+
+    pub fn authfile_option(&self) -> String {
+        format!("--authfile={}", self.path.display())
+    }
+}
+
+impl Drop for RegistryAuthFile {
+    fn drop(&mut self) {
+        if let Err(err) = remove_file(&self.path) {
+            log::warn!(
+                "Could not remove the registry auth file '{}': '{}'",
+                self.path.display(),
+                err
+            );
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::fs::PermissionsExt;
+
+    use super::{RegistryAuthFile, AUTH_FILE_MODE};
+
+    const REGISTRY_AUTH: &str = r#"{"auths": {"registry.example.com": {"auth": "dXNlcjpwdw=="}}}"#;
+
+    // [utest->swdd~podman-uses-registry-auth~1]
+    #[test]
+    fn utest_registry_auth_file_is_written_and_removed() {
+        let registry_auth_file = RegistryAuthFile::create(REGISTRY_AUTH).unwrap();
+        let path = registry_auth_file.path.clone();
+
+        assert_eq!(
+            registry_auth_file.authfile_option(),
+            format!("--authfile={}", path.display())
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), REGISTRY_AUTH);
+        assert_eq!(
+            std::fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+            AUTH_FILE_MODE
+        );
+
+        drop(registry_auth_file);
+        assert!(!path.exists());
+    }
+
+    // [utest->swdd~podman-uses-registry-auth~1]
+    #[test]
+    fn utest_registry_auth_file_rejects_invalid_content() {
+        assert!(RegistryAuthFile::create("user:password").is_err());
+        assert!(RegistryAuthFile::create(r#"["registry.example.com"]"#).is_err());
+    }
+}
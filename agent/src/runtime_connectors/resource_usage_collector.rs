@@ -0,0 +1,26 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+
+use common::objects::{AgentName, WorkloadResources};
+
+// [impl->swdd~agent-general-resource-usage-collector-interface~1]
+#[async_trait]
+pub trait ResourceUsageCollector: Send + Sync {
+    // Runtimes that are not able to provide statistics of their workloads keep the default.
+    async fn collect_resource_usage(&self, _agent_name: &AgentName) -> Vec<WorkloadResources> {
+        Vec::new()
+    }
+}
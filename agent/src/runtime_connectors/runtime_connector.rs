@@ -12,7 +12,7 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{fmt::Display, path::PathBuf, str::FromStr};
+use std::{fmt::Display, net::IpAddr, path::PathBuf, str::FromStr};
 
 use async_trait::async_trait;
 
@@ -20,13 +20,19 @@ use common::objects::{
     AgentName, ExecutionState, WorkloadInstanceName, WorkloadSpec, WorkloadState,
 };
 
-use crate::{runtime_connectors::StateChecker, workload_state::WorkloadStateSender};
+use crate::{
+    runtime_connectors::{ResourceUsageCollector, StateChecker},
+    workload_state::WorkloadStateSender,
+};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum RuntimeError {
     Create(String),
     Delete(String),
     List(String),
+    Exec(String),
+    PortForward(String),
+    Pause(String),
 }
 
 impl Display for RuntimeError {
@@ -41,6 +47,15 @@ impl Display for RuntimeError {
             RuntimeError::List(msg) => {
                 write!(f, "{}", msg)
             }
+            RuntimeError::Exec(msg) => {
+                write!(f, "{}", msg)
+            }
+            RuntimeError::PortForward(msg) => {
+                write!(f, "{}", msg)
+            }
+            RuntimeError::Pause(msg) => {
+                write!(f, "{}", msg)
+            }
         }
     }
 }
@@ -69,7 +84,7 @@ impl ReusableWorkloadState {
 
 // [impl->swdd~functions-required-by-runtime-connector~1]
 #[async_trait]
-pub trait RuntimeConnector<WorkloadId, StChecker>: Sync + Send
+pub trait RuntimeConnector<WorkloadId, StChecker>: ResourceUsageCollector + Sync + Send
 where
     StChecker: StateChecker<WorkloadId> + Send + Sync,
     WorkloadId: ToString + FromStr + Clone + Send + Sync + 'static,
@@ -102,6 +117,58 @@ where
     ) -> Result<StChecker, RuntimeError>;
 
     async fn delete_workload(&self, workload_id: &WorkloadId) -> Result<(), RuntimeError>;
+
+    // Runtimes not supporting a graceful stop rely on the subsequent deletion of the workload.
+    // [impl->swdd~agent-workload-control-loop-stops-workload-within-grace-period~1]
+    async fn stop_workload(
+        &self,
+        _workload_id: &WorkloadId,
+        _grace_period_secs: u32,
+    ) -> Result<(), RuntimeError> {
+        Ok(())
+    }
+
+    // Starts the command inside the workload with piped stdin, stdout and stderr.
+    // [impl->swdd~agent-runtime-connector-executes-command-in-workload~1]
+    async fn exec(
+        &self,
+        _workload_id: &WorkloadId,
+        _command: Vec<String>,
+    ) -> Result<tokio::process::Child, RuntimeError> {
+        Err(RuntimeError::Exec(format!(
+            "Exec sessions are not supported by the runtime '{}'",
+            self.name()
+        )))
+    }
+
+    // Returns the address the ports of the workload are reachable at from the agent.
+    // [impl->swdd~agent-runtime-connector-provides-workload-address~1]
+    async fn get_workload_address(
+        &self,
+        _workload_id: &WorkloadId,
+    ) -> Result<IpAddr, RuntimeError> {
+        Err(RuntimeError::PortForward(format!(
+            "Port forwarding is not supported by the runtime '{}'",
+            self.name()
+        )))
+    }
+
+    // Freezes all processes of the workload without stopping it.
+    // [impl->swdd~agent-runtime-connector-pauses-workload~1]
+    async fn pause_workload(&self, _workload_id: &WorkloadId) -> Result<(), RuntimeError> {
+        Err(RuntimeError::Pause(format!(
+            "Pausing workloads is not supported by the runtime '{}'",
+            self.name()
+        )))
+    }
+
+    // [impl->swdd~agent-runtime-connector-pauses-workload~1]
+    async fn unpause_workload(&self, _workload_id: &WorkloadId) -> Result<(), RuntimeError> {
+        Err(RuntimeError::Pause(format!(
+            "Resuming workloads is not supported by the runtime '{}'",
+            self.name()
+        )))
+    }
 }
 
 pub trait OwnableRuntime<WorkloadId, StChecker>: RuntimeConnector<WorkloadId, StChecker>
@@ -132,15 +199,19 @@ where
 //////////////////////////////////////////////////////////////////////////////
 
 #[cfg(test)]
-pub mod test {
+pub(crate) mod test {
     use std::{collections::VecDeque, path::PathBuf, sync::Arc};
 
     use async_trait::async_trait;
-    use common::objects::{AgentName, ExecutionState, WorkloadInstanceName, WorkloadSpec};
+    use common::objects::{
+        AgentName, ExecutionState, WorkloadInstanceName, WorkloadResources, WorkloadSpec,
+    };
     use tokio::sync::Mutex;
 
     use crate::{
-        runtime_connectors::{ReusableWorkloadState, RuntimeStateGetter, StateChecker},
+        runtime_connectors::{
+            ResourceUsageCollector, ReusableWorkloadState, RuntimeStateGetter, StateChecker,
+        },
         workload_state::WorkloadStateSender,
     };
 
@@ -212,6 +283,12 @@ pub mod test {
             Result<StubStateChecker, RuntimeError>,
         ),
         DeleteWorkload(String, Result<(), RuntimeError>),
+        StopWorkload(String, u32, Result<(), RuntimeError>),
+        PauseWorkload(String, Result<(), RuntimeError>),
+        UnpauseWorkload(String, Result<(), RuntimeError>),
+        // The executed command is replaced by a local process exiting with the given exit code
+        Exec(String, Vec<String>, Result<i32, RuntimeError>),
+        CollectResourceUsage(AgentName, Vec<WorkloadResources>),
     }
 
     #[derive(Debug)]
@@ -307,6 +384,23 @@ pub mod test {
 
     pub type MockRuntimeConnector = MockBase<RuntimeCall>;
 
+    #[async_trait]
+    impl ResourceUsageCollector for MockBase<RuntimeCall> {
+        async fn collect_resource_usage(&self, agent_name: &AgentName) -> Vec<WorkloadResources> {
+            match self.get_expected_call().await {
+                RuntimeCall::CollectResourceUsage(expected_agent_name, result)
+                    if expected_agent_name == *agent_name =>
+                {
+                    return result;
+                }
+                expected_call => {
+                    self.unexpected_call().await;
+                    panic!("Unexpected collect_resource_usage call. Expected: '{expected_call:?}'\n\nGot: {agent_name:?}");
+                }
+            }
+        }
+    }
+
     #[async_trait]
     impl RuntimeConnector<String, StubStateChecker> for MockBase<RuntimeCall> {
         fn name(&self) -> String {
@@ -409,5 +503,80 @@ pub mod test {
                 }
             }
         }
+
+        async fn stop_workload(
+            &self,
+            workload_id: &String,
+            grace_period_secs: u32,
+        ) -> Result<(), RuntimeError> {
+            match self.get_expected_call().await {
+                RuntimeCall::StopWorkload(
+                    expected_workload_id,
+                    expected_grace_period_secs,
+                    result,
+                ) if expected_workload_id == *workload_id
+                    && expected_grace_period_secs == grace_period_secs =>
+                {
+                    return result;
+                }
+                expected_call => {
+                    self.unexpected_call().await;
+                    panic!("Unexpected stop_workload call. Expected: '{expected_call:?}'\n\nGot: {workload_id:?}, {grace_period_secs:?}");
+                }
+            }
+        }
+
+        async fn pause_workload(&self, workload_id: &String) -> Result<(), RuntimeError> {
+            match self.get_expected_call().await {
+                RuntimeCall::PauseWorkload(expected_workload_id, result)
+                    if expected_workload_id == *workload_id =>
+                {
+                    return result;
+                }
+                expected_call => {
+                    self.unexpected_call().await;
+                    panic!("Unexpected pause_workload call. Expected: '{expected_call:?}'\n\nGot: {workload_id:?}");
+                }
+            }
+        }
+
+        async fn unpause_workload(&self, workload_id: &String) -> Result<(), RuntimeError> {
+            match self.get_expected_call().await {
+                RuntimeCall::UnpauseWorkload(expected_workload_id, result)
+                    if expected_workload_id == *workload_id =>
+                {
+                    return result;
+                }
+                expected_call => {
+                    self.unexpected_call().await;
+                    panic!("Unexpected unpause_workload call. Expected: '{expected_call:?}'\n\nGot: {workload_id:?}");
+                }
+            }
+        }
+
+        async fn exec(
+            &self,
+            workload_id: &String,
+            command: Vec<String>,
+        ) -> Result<tokio::process::Child, RuntimeError> {
+            match self.get_expected_call().await {
+                RuntimeCall::Exec(expected_workload_id, expected_command, result)
+                    if expected_workload_id == *workload_id && expected_command == command =>
+                {
+                    result.map(|exit_code| {
+                        tokio::process::Command::new("sh")
+                            .args(["-c", &format!("exit {exit_code}")])
+                            .stdout(std::process::Stdio::piped())
+                            .stderr(std::process::Stdio::piped())
+                            .spawn()
+                            .unwrap()
+                    })
+                }
+                expected_call => {
+                    self.unexpected_call().await;
+                    panic!("Unexpected exec call. Expected: '{expected_call:?}'\n\nGot: {workload_id:?}, {command:?}");
+                }
+            }
+        }
     }
 }
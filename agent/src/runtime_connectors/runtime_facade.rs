@@ -12,11 +12,11 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::str::FromStr;
+use std::{net::IpAddr, str::FromStr};
 
 use async_trait::async_trait;
 use common::{
-    objects::{AgentName, ExecutionState, WorkloadInstanceName, WorkloadSpec},
+    objects::{AgentName, ExecutionState, WorkloadInstanceName, WorkloadResources, WorkloadSpec},
     std_extensions::IllegalStateResult,
 };
 #[cfg(test)]
@@ -30,6 +30,7 @@ use crate::control_interface::control_interface_info::ControlInterfaceInfo;
 
 use crate::{
     runtime_connectors::{OwnableRuntime, ReusableWorkloadState, RuntimeError, StateChecker},
+    secret_resolver::SecretResolver,
     workload_operation::ReusableWorkloadSpec,
     workload_state::{WorkloadStateSender, WorkloadStateSenderInterface},
 };
@@ -39,7 +40,7 @@ use crate::workload::control_loop_state::ControlLoopState;
 use crate::workload::workload_control_loop::WorkloadControlLoop;
 #[cfg_attr(test, mockall_double::double)]
 use crate::workload::Workload;
-use crate::workload::WorkloadCommandSender;
+use crate::workload::{OperationLimiter, WorkloadCommandSender};
 
 use tokio::task::JoinHandle;
 
@@ -71,6 +72,19 @@ pub trait RuntimeFacade: Send + Sync + 'static {
         update_state_tx: &WorkloadStateSender,
         report_workload_states_for_workload: bool,
     );
+
+    async fn collect_resource_usage(&self, agent_name: &AgentName) -> Vec<WorkloadResources>;
+
+    async fn exec(
+        &self,
+        instance_name: &WorkloadInstanceName,
+        command: Vec<String>,
+    ) -> Result<tokio::process::Child, RuntimeError>;
+
+    async fn get_workload_address(
+        &self,
+        instance_name: &WorkloadInstanceName,
+    ) -> Result<IpAddr, RuntimeError>;
 }
 
 pub struct GenericRuntimeFacade<
@@ -78,6 +92,8 @@ pub struct GenericRuntimeFacade<
     StChecker: StateChecker<WorkloadId> + Send + Sync,
 > {
     runtime: Box<dyn OwnableRuntime<WorkloadId, StChecker>>,
+    operation_limiter: OperationLimiter,
+    secret_resolver: SecretResolver,
 }
 
 impl<WorkloadId, StChecker> GenericRuntimeFacade<WorkloadId, StChecker>
@@ -86,7 +102,23 @@ where
     StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
 {
     pub fn new(runtime: Box<dyn OwnableRuntime<WorkloadId, StChecker>>) -> Self {
-        GenericRuntimeFacade { runtime }
+        GenericRuntimeFacade {
+            runtime,
+            operation_limiter: OperationLimiter::default(),
+            secret_resolver: SecretResolver::default(),
+        }
+    }
+
+    // [impl->swdd~agent-limits-parallel-workload-operations~1]
+    pub fn with_operation_limiter(mut self, operation_limiter: OperationLimiter) -> Self {
+        self.operation_limiter = operation_limiter;
+        self
+    }
+
+    // [impl->swdd~agent-reads-secrets-from-providers~3]
+    pub fn with_secret_resolver(mut self, secret_resolver: SecretResolver) -> Self {
+        self.secret_resolver = secret_resolver;
+        self
     }
 }
 
@@ -109,6 +141,46 @@ impl<
         self.runtime.get_reusable_workloads(agent_name).await
     }
 
+    // [impl->swdd~agent-facade-forwards-collect-resource-usage-call~1]
+    async fn collect_resource_usage(&self, agent_name: &AgentName) -> Vec<WorkloadResources> {
+        log::trace!(
+            "Collecting the resource usage of the '{}' workloads on agent '{}'.",
+            self.runtime.name(),
+            agent_name
+        );
+        self.runtime.collect_resource_usage(agent_name).await
+    }
+
+    // [impl->swdd~agent-facade-forwards-exec-call~1]
+    async fn exec(
+        &self,
+        instance_name: &WorkloadInstanceName,
+        command: Vec<String>,
+    ) -> Result<tokio::process::Child, RuntimeError> {
+        log::debug!(
+            "Executing '{:?}' in workload '{}' of runtime '{}'.",
+            command,
+            instance_name.workload_name(),
+            self.runtime.name()
+        );
+        let workload_id = self.runtime.get_workload_id(instance_name).await?;
+        self.runtime.exec(&workload_id, command).await
+    }
+
+    // [impl->swdd~agent-facade-forwards-get-workload-address-call~1]
+    async fn get_workload_address(
+        &self,
+        instance_name: &WorkloadInstanceName,
+    ) -> Result<IpAddr, RuntimeError> {
+        log::debug!(
+            "Getting the address of workload '{}' of runtime '{}'.",
+            instance_name.workload_name(),
+            self.runtime.name()
+        );
+        let workload_id = self.runtime.get_workload_id(instance_name).await?;
+        self.runtime.get_workload_address(&workload_id).await
+    }
+
     // [impl->swdd~agent-create-workload~2]
     fn create_workload(
         &self,
@@ -183,6 +255,8 @@ impl<
         };
 
         let runtime = self.runtime.to_owned();
+        let operation_limiter = self.operation_limiter.clone();
+        let secret_resolver = self.secret_resolver.clone();
         let update_state_tx = update_state_tx.clone();
         let workload_name = workload_spec.instance_name.workload_name().to_owned();
 
@@ -191,9 +265,15 @@ impl<
             let run_folder = info.get_run_folder().clone();
             let output_pipe_sender = info.get_to_server_sender();
             let instance_name = info.get_instance_name().clone();
+            let pipes_owner = info.get_pipes_owner();
             let authorizer = info.move_authorizer();
-            match ControlInterface::new(&run_folder, &instance_name, output_pipe_sender, authorizer)
-            {
+            match ControlInterface::new(
+                &run_folder,
+                &instance_name,
+                output_pipe_sender,
+                authorizer,
+                pipes_owner,
+            ) {
                 Ok(result) => {
                     log::info!(
                         "Successfully created control interface for workload '{}'.",
@@ -244,6 +324,8 @@ impl<
                 .runtime(runtime)
                 .workload_command_receiver(workload_command_receiver)
                 .retry_sender(workload_command_sender)
+                .operation_limiter(operation_limiter)
+                .secret_resolver(secret_resolver)
                 .build()
                 .unwrap_or_illegal_state();
 
@@ -265,6 +347,8 @@ impl<
     ) -> (JoinHandle<()>, Workload) {
         let workload_name = workload_spec.instance_name.workload_name().to_owned();
         let runtime = self.runtime.to_owned();
+        let operation_limiter = self.operation_limiter.clone();
+        let secret_resolver = self.secret_resolver.clone();
         let update_state_tx = update_state_tx.clone();
 
         log::debug!(
@@ -278,9 +362,15 @@ impl<
             let run_folder = info.get_run_folder().clone();
             let output_pipe_sender = info.get_to_server_sender();
             let instance_name = info.get_instance_name().clone();
+            let pipes_owner = info.get_pipes_owner();
             let authorizer = info.move_authorizer();
-            match ControlInterface::new(&run_folder, &instance_name, output_pipe_sender, authorizer)
-            {
+            match ControlInterface::new(
+                &run_folder,
+                &instance_name,
+                output_pipe_sender,
+                authorizer,
+                pipes_owner,
+            ) {
                 Ok(result) => Some(result),
                 Err(err) => {
                     log::warn!(
@@ -316,6 +406,8 @@ impl<
                 .runtime(runtime)
                 .workload_command_receiver(workload_command_receiver)
                 .retry_sender(workload_command_sender)
+                .operation_limiter(operation_limiter)
+                .secret_resolver(secret_resolver)
                 .build()
                 .unwrap_or_illegal_state();
 
@@ -340,6 +432,7 @@ impl<
         report_workload_states_for_workload: bool,
     ) -> JoinHandle<()> {
         let runtime = self.runtime.to_owned();
+        let operation_limiter = self.operation_limiter.clone();
         let update_state_tx = update_state_tx.clone();
 
         log::debug!(
@@ -360,7 +453,11 @@ impl<
             }
 
             if let Ok(id) = runtime.get_workload_id(&instance_name).await {
-                if let Err(err) = runtime.delete_workload(&id).await {
+                // [impl->swdd~agent-limits-parallel-workload-operations~1]
+                let operation_permit = operation_limiter.acquire().await;
+                let delete_result = runtime.delete_workload(&id).await;
+                drop(operation_permit);
+                if let Err(err) = delete_result {
                     if report_workload_states_for_workload {
                         update_state_tx
                             .report_workload_execution_state(
@@ -395,6 +492,7 @@ impl<
 #[cfg(test)]
 mod tests {
     use common::objects::{
+        generate_test_workload_resources,
         generate_test_workload_spec_with_control_interface_access,
         generate_test_workload_spec_with_param, ExecutionState, WorkloadInstanceName,
     };
@@ -462,6 +560,39 @@ mod tests {
         runtime_mock.assert_all_expectations().await;
     }
 
+    // [utest->swdd~agent-facade-forwards-collect-resource-usage-call~1]
+    #[tokio::test]
+    async fn utest_runtime_facade_collect_resource_usage() {
+        let mut runtime_mock = MockRuntimeConnector::new();
+
+        let workload_resources = vec![generate_test_workload_resources(
+            AGENT_NAME,
+            WORKLOAD_1_NAME,
+        )];
+
+        runtime_mock
+            .expect(vec![RuntimeCall::CollectResourceUsage(
+                AGENT_NAME.into(),
+                workload_resources.clone(),
+            )])
+            .await;
+
+        let ownable_runtime_mock: Box<dyn OwnableRuntime<String, StubStateChecker>> =
+            Box::new(runtime_mock.clone());
+        let test_runtime_facade = Box::new(GenericRuntimeFacade::<String, StubStateChecker>::new(
+            ownable_runtime_mock,
+        ));
+
+        assert_eq!(
+            test_runtime_facade
+                .collect_resource_usage(&AGENT_NAME.into())
+                .await,
+            workload_resources
+        );
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
     // [utest->swdd~agent-create-workload~2]
     #[tokio::test]
     async fn utest_runtime_facade_create_workload() {
@@ -487,7 +618,7 @@ mod tests {
         control_interface_new_context
             .expect()
             .once()
-            .return_once(|_, _, _, _| Ok(control_interface_mock));
+            .return_once(|_, _, _, _, _| Ok(control_interface_mock));
 
         let mut control_interface_info_mock = MockControlInterfaceInfo::default();
         control_interface_info_mock
@@ -505,6 +636,10 @@ mod tests {
             .once()
             .return_const(reusable_workload_spec.workload_spec.instance_name.clone());
 
+        control_interface_info_mock
+            .expect_get_pipes_owner()
+            .once()
+            .return_const(None);
         control_interface_info_mock
             .expect_move_authorizer()
             .once()
@@ -572,6 +707,10 @@ mod tests {
                     .workload_name(WORKLOAD_1_NAME)
                     .build(),
             );
+        control_interface_info_mock
+            .expect_get_pipes_owner()
+            .once()
+            .return_const(None);
         control_interface_info_mock
             .expect_move_authorizer()
             .once()
@@ -581,7 +720,7 @@ mod tests {
         control_interface_new_context
             .expect()
             .once()
-            .return_once(|_, _, _, _| Ok(MockControlInterface::default()));
+            .return_once(|_, _, _, _, _| Ok(MockControlInterface::default()));
 
         let workload_spec = generate_test_workload_spec_with_control_interface_access(
             AGENT_NAME.to_string(),
@@ -830,4 +969,73 @@ mod tests {
 
         runtime_mock.assert_all_expectations().await;
     }
+
+    // [utest->swdd~agent-facade-forwards-exec-call~1]
+    #[tokio::test]
+    async fn utest_runtime_facade_exec_forwards_to_runtime() {
+        let mut runtime_mock = MockRuntimeConnector::new();
+
+        let workload_instance_name = WorkloadInstanceName::builder()
+            .workload_name(WORKLOAD_1_NAME)
+            .build();
+
+        runtime_mock
+            .expect(vec![
+                RuntimeCall::GetWorkloadId(
+                    workload_instance_name.clone(),
+                    Ok(WORKLOAD_ID.to_string()),
+                ),
+                RuntimeCall::Exec(WORKLOAD_ID.to_string(), vec!["sh".to_string()], Ok(0)),
+            ])
+            .await;
+
+        let ownable_runtime_mock: Box<dyn OwnableRuntime<String, StubStateChecker>> =
+            Box::new(runtime_mock.clone());
+        let test_runtime_facade = Box::new(GenericRuntimeFacade::<String, StubStateChecker>::new(
+            ownable_runtime_mock,
+        ));
+
+        let mut child = test_runtime_facade
+            .exec(&workload_instance_name, vec!["sh".to_string()])
+            .await
+            .unwrap();
+        assert!(child.wait().await.unwrap().success());
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
+    // [utest->swdd~agent-facade-forwards-get-workload-address-call~1]
+    #[tokio::test]
+    async fn utest_runtime_facade_get_workload_address_forwards_to_runtime() {
+        let mut runtime_mock = MockRuntimeConnector::new();
+
+        let workload_instance_name = WorkloadInstanceName::builder()
+            .workload_name(WORKLOAD_1_NAME)
+            .build();
+
+        runtime_mock
+            .expect(vec![RuntimeCall::GetWorkloadId(
+                workload_instance_name.clone(),
+                Ok(WORKLOAD_ID.to_string()),
+            )])
+            .await;
+
+        let ownable_runtime_mock: Box<dyn OwnableRuntime<String, StubStateChecker>> =
+            Box::new(runtime_mock.clone());
+        let test_runtime_facade = Box::new(GenericRuntimeFacade::<String, StubStateChecker>::new(
+            ownable_runtime_mock,
+        ));
+
+        // the mock runtime does not override the default implementation
+        let result = test_runtime_facade
+            .get_workload_address(&workload_instance_name)
+            .await;
+        assert!(matches!(
+            result,
+            Err(crate::runtime_connectors::RuntimeError::PortForward(msg))
+                if msg == "Port forwarding is not supported by the runtime 'mock-runtime'"
+        ));
+
+        runtime_mock.assert_all_expectations().await;
+    }
 }
@@ -32,6 +32,28 @@ where
 {
     // [impl->swdd~allowed-workload-states~2]
     async fn get_state(&self, workload_id: &WorkloadId) -> ExecutionState;
+
+    // [impl->swdd~agent-general-runtime-state-getter-provides-resource-usage~1]
+    async fn get_resource_usage(&self, _workload_id: &WorkloadId) -> Option<WorkloadResourceUsage> {
+        None
+    }
+
+    // [impl->swdd~agent-general-runtime-state-getter-executes-health-command~1]
+    async fn exec_health_command(
+        &self,
+        _workload_id: &WorkloadId,
+        _command: &[String],
+    ) -> Option<Result<(), String>> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkloadResourceUsage {
+    // CPU usage in percent
+    pub cpu_usage: f32,
+    // memory usage in bytes
+    pub memory_usage: u64,
 }
 
 // [impl->swdd~agent-general-state-checker-interface~1]
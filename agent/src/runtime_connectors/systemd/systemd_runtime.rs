@@ -0,0 +1,641 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fmt::Display, path::PathBuf, str::FromStr, time::SystemTime};
+
+use async_trait::async_trait;
+use tokio::task::JoinHandle;
+
+use common::objects::{AgentName, ExecutionState, WorkloadInstanceName, WorkloadSpec};
+
+use crate::{
+    runtime_connectors::{
+        ResourceUsageCollector, ReusableWorkloadState, RuntimeConnector, RuntimeError,
+        RuntimeStateGetter, StateChecker,
+    },
+    workload_log_sink::{WorkloadLogSinkKind, WorkloadLogsConfig},
+    workload_state::WorkloadStateSender,
+};
+
+#[cfg(test)]
+use mockall_double::double;
+
+// [impl->swdd~systemd-uses-systemctl~2]
+#[cfg_attr(test, double)]
+use crate::runtime_connectors::systemd_cli::SystemdCli;
+
+use super::{
+    systemd_runtime_config::SystemdRuntimeConfig, systemd_state_checker::SystemdStateChecker,
+};
+
+pub const SYSTEMD_RUNTIME_NAME: &str = "systemd";
+const UNIT_PREFIX: &str = "ankaios-";
+const UNIT_SUFFIX: &str = ".service";
+
+#[derive(Debug, Clone, Default)]
+pub struct SystemdRuntime {
+    pub workload_logs: WorkloadLogsConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct SystemdStateGetter {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SystemdWorkloadId {
+    pub name: WorkloadInstanceName,
+}
+
+impl SystemdWorkloadId {
+    // [impl->swdd~systemd-unit-name-contains-instance-name~1]
+    fn unit_name(&self) -> String {
+        format!("{}{}{}", UNIT_PREFIX, self.name, UNIT_SUFFIX)
+    }
+
+    fn from_unit_name(unit_name: &str) -> Option<Self> {
+        unit_name
+            .strip_prefix(UNIT_PREFIX)
+            .and_then(|x| x.strip_suffix(UNIT_SUFFIX))
+            .and_then(|x| x.parse().ok())
+    }
+}
+
+impl Display for SystemdWorkloadId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl FromStr for SystemdWorkloadId {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(SystemdWorkloadId {
+            name: WorkloadInstanceName::try_from(s)?,
+        })
+    }
+}
+
+// The fields are the same as the fields of the logs mirrored from containers to journald
+// [impl->swdd~systemd-create-workload-adds-log-fields~1]
+fn log_extra_fields(instance_name: &WorkloadInstanceName) -> Vec<String> {
+    vec![
+        format!(
+            "--property=LogExtraFields=ANKAIOS_AGENT={}",
+            instance_name.agent_name()
+        ),
+        format!(
+            "--property=LogExtraFields=ANKAIOS_WORKLOAD={}",
+            instance_name.workload_name()
+        ),
+        format!(
+            "--property=LogExtraFields=ANKAIOS_INSTANCE_ID={}",
+            instance_name.id()
+        ),
+    ]
+}
+
+#[async_trait]
+// [impl->swdd~systemd-implements-runtime-state-getter~1]
+impl RuntimeStateGetter<SystemdWorkloadId> for SystemdStateGetter {
+    async fn get_state(&self, workload_id: &SystemdWorkloadId) -> ExecutionState {
+        log::trace!("Getting the state for the workload '{}'", workload_id);
+
+        let exec_state = match SystemdCli::get_state(&workload_id.unit_name()).await {
+            Ok(Some(state)) => state,
+            Ok(None) => ExecutionState::lost(),
+            Err(err) => {
+                log::warn!(
+                    "Could not get state of workload '{}': '{}'. Returning unknown.",
+                    workload_id,
+                    err
+                );
+                ExecutionState::unknown("Error getting state from systemd.")
+            }
+        };
+
+        log::trace!(
+            "Returning the state '{}' for the workload '{}'",
+            exec_state,
+            workload_id
+        );
+        exec_state
+    }
+}
+
+impl SystemdRuntime {
+    fn start_state_checker(
+        &self,
+        workload_id: &SystemdWorkloadId,
+        workload_spec: WorkloadSpec,
+        update_state_tx: WorkloadStateSender,
+    ) -> SystemdStateChecker {
+        log::debug!(
+            "Starting the checker for the workload '{}'",
+            workload_spec.instance_name,
+        );
+        SystemdStateChecker::start_checker(
+            &workload_spec,
+            workload_id.clone(),
+            update_state_tx,
+            SystemdStateGetter {},
+        )
+    }
+
+    // [impl->swdd~systemd-follows-workload-logs~1]
+    fn mirror_logs(
+        &self,
+        workload_id: &SystemdWorkloadId,
+        since: Option<SystemTime>,
+    ) -> Option<JoinHandle<()>> {
+        let workload_name = workload_id.name.workload_name();
+        // The output of the unit is already stored in the journal
+        if self.workload_logs.kind_for(workload_name) == WorkloadLogSinkKind::Journald {
+            return None;
+        }
+        let sink = self.workload_logs.sink_for(workload_name)?;
+        match SystemdCli::spawn_journal_follower(&workload_id.unit_name(), since) {
+            Ok(journal_follower) => Some(tokio::spawn(
+                sink.forward(workload_id.name.clone(), journal_follower),
+            )),
+            Err(err) => {
+                log::warn!(
+                    "Could not mirror the logs of workload '{}': '{}'",
+                    workload_name,
+                    err
+                );
+                None
+            }
+        }
+    }
+}
+
+impl ResourceUsageCollector for SystemdRuntime {}
+
+#[async_trait]
+// [impl->swdd~systemd-implements-runtime-connector~1]
+impl RuntimeConnector<SystemdWorkloadId, SystemdStateChecker> for SystemdRuntime {
+    // [impl->swdd~systemd-name-returns-systemd~1]
+    fn name(&self) -> String {
+        SYSTEMD_RUNTIME_NAME.to_string()
+    }
+
+    // [impl->swdd~systemd-list-of-existing-workloads-uses-unit-names~1]
+    async fn get_reusable_workloads(
+        &self,
+        agent_name: &AgentName,
+    ) -> Result<Vec<ReusableWorkloadState>, RuntimeError> {
+        let res = SystemdCli::list_units(&format!(
+            "{}*.{}{}",
+            UNIT_PREFIX,
+            agent_name.get(),
+            UNIT_SUFFIX
+        ))
+        .await
+        .map_err(RuntimeError::List)?;
+
+        log::debug!("Found {} reusable workload(s): '{:?}'", res.len(), &res);
+
+        let mut workload_states = Vec::new();
+        for workload_id in res
+            .iter()
+            .filter_map(|x| SystemdWorkloadId::from_unit_name(x))
+        {
+            match SystemdCli::get_state(&workload_id.unit_name()).await {
+                Ok(Some(execution_state)) => workload_states.push(ReusableWorkloadState::new(
+                    workload_id.name.clone(),
+                    execution_state,
+                    Some(workload_id.to_string()),
+                )),
+                // The unit has been unloaded in the meantime
+                Ok(None) => {}
+                Err(err) => return Err(RuntimeError::List(err)),
+            }
+        }
+        Ok(workload_states)
+    }
+
+    // [impl->swdd~systemd-create-workload-runs-transient-unit~2]
+    async fn create_workload(
+        &self,
+        workload_spec: WorkloadSpec,
+        reusable_workload_id: Option<SystemdWorkloadId>,
+        _control_interface_path: Option<PathBuf>,
+        update_state_tx: WorkloadStateSender,
+    ) -> Result<(SystemdWorkloadId, SystemdStateChecker), RuntimeError> {
+        let workload_cfg =
+            SystemdRuntimeConfig::try_from(&workload_spec).map_err(RuntimeError::Create)?;
+
+        let workload_id = SystemdWorkloadId {
+            name: workload_spec.instance_name.clone(),
+        };
+
+        // [impl->swdd~systemd-create-workload-removes-existing-unit~1]
+        // A transient unit cannot be started again, thus the remaining unit is removed first
+        if let Some(reusable_workload_id) = reusable_workload_id {
+            SystemdCli::remove_unit(&reusable_workload_id.unit_name())
+                .await
+                .map_err(RuntimeError::Create)?;
+        }
+
+        // [impl->swdd~systemd-create-workload-adds-log-fields~1]
+        let mut run_args = log_extra_fields(&workload_spec.instance_name);
+        run_args.extend(workload_cfg.run_args());
+        let started_at = SystemTime::now();
+        SystemdCli::run(
+            &workload_id.unit_name(),
+            &workload_spec.instance_name.to_string(),
+            run_args,
+        )
+        .await
+        .map_err(RuntimeError::Create)?;
+
+        log::debug!(
+            "The unit of workload '{}' has been started",
+            workload_spec.instance_name
+        );
+
+        let log_mirror = self.mirror_logs(&workload_id, Some(started_at));
+        let state_checker = self
+            .start_state_checker(&workload_id, workload_spec, update_state_tx)
+            .with_log_mirror(log_mirror);
+
+        Ok((workload_id, state_checker))
+    }
+
+    // [impl->swdd~systemd-get-workload-id-uses-instance-name~1]
+    async fn get_workload_id(
+        &self,
+        instance_name: &WorkloadInstanceName,
+    ) -> Result<SystemdWorkloadId, RuntimeError> {
+        Ok(SystemdWorkloadId {
+            name: instance_name.clone(),
+        })
+    }
+
+    async fn start_checker(
+        &self,
+        workload_id: &SystemdWorkloadId,
+        workload_spec: WorkloadSpec,
+        update_state_tx: WorkloadStateSender,
+    ) -> Result<SystemdStateChecker, RuntimeError> {
+        // the lines written before are already mirrored by the previous agent run
+        let log_mirror = self.mirror_logs(workload_id, None);
+        Ok(self
+            .start_state_checker(workload_id, workload_spec, update_state_tx)
+            .with_log_mirror(log_mirror))
+    }
+
+    // [impl->swdd~systemd-delete-workload-stops-unit~1]
+    async fn delete_workload(&self, workload_id: &SystemdWorkloadId) -> Result<(), RuntimeError> {
+        log::debug!("Deleting workload '{}'", workload_id);
+        SystemdCli::remove_unit(&workload_id.unit_name())
+            .await
+            .map_err(RuntimeError::Delete)
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::objects::{
+        generate_test_workload_spec_with_param, AgentName, ExecutionState, WorkloadInstanceName,
+    };
+
+    use mockall::Sequence;
+
+    use super::SystemdCli;
+    use super::{SystemdRuntime, SystemdStateGetter, SystemdWorkloadId, SYSTEMD_RUNTIME_NAME};
+    use crate::runtime_connectors::{RuntimeConnector, RuntimeError, RuntimeStateGetter};
+    use crate::test_helper::MOCKALL_CONTEXT_SYNC;
+    use crate::workload_log_sink::{WorkloadLogSinkKind, WorkloadLogsConfig};
+
+    const BUFFER_SIZE: usize = 20;
+
+    const AGENT_NAME: &str = "agent_x";
+    const WORKLOAD_1_NAME: &str = "workload1";
+    const INSTANCE_NAME: &str = "workload1.hash.agent_x";
+    const UNIT_NAME: &str = "ankaios-workload1.hash.agent_x.service";
+
+    fn generate_workload_spec(runtime_config: &str) -> common::objects::WorkloadSpec {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            SYSTEMD_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.runtime_config = runtime_config.to_string();
+        workload_spec.instance_name = workload_id().name;
+        workload_spec
+    }
+
+    fn workload_id() -> SystemdWorkloadId {
+        SystemdWorkloadId {
+            name: WorkloadInstanceName::try_from(INSTANCE_NAME).unwrap(),
+        }
+    }
+
+    // [utest->swdd~systemd-name-returns-systemd~1]
+    #[test]
+    fn utest_name_systemd() {
+        let systemd_runtime = SystemdRuntime::default();
+        assert_eq!(systemd_runtime.name(), "systemd".to_string());
+    }
+
+    // [utest->swdd~systemd-unit-name-contains-instance-name~1]
+    #[test]
+    fn utest_workload_id_unit_name() {
+        assert_eq!(workload_id().unit_name(), UNIT_NAME);
+        assert_eq!(
+            SystemdWorkloadId::from_unit_name(UNIT_NAME),
+            Some(workload_id())
+        );
+        assert_eq!(SystemdWorkloadId::from_unit_name("sshd.service"), None);
+    }
+
+    // [utest->swdd~systemd-list-of-existing-workloads-uses-unit-names~1]
+    #[tokio::test]
+    async fn utest_get_reusable_workloads_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let list_context = SystemdCli::list_units_context();
+        list_context
+            .expect()
+            .withf(|pattern| pattern == "ankaios-*.agent_x.service")
+            .return_const(Ok(vec![
+                UNIT_NAME.to_string(),
+                "ankaios-wrongworkloadname.service".to_string(),
+            ]));
+
+        let get_state_context = SystemdCli::get_state_context();
+        get_state_context
+            .expect()
+            .withf(|unit_name| unit_name == UNIT_NAME)
+            .return_const(Ok(Some(ExecutionState::running())));
+
+        let systemd_runtime = SystemdRuntime::default();
+        let res = systemd_runtime
+            .get_reusable_workloads(&AgentName::from(AGENT_NAME))
+            .await
+            .unwrap();
+
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].workload_state.instance_name, workload_id().name);
+        assert_eq!(res[0].workload_id, Some(INSTANCE_NAME.to_string()));
+    }
+
+    #[tokio::test]
+    async fn utest_get_reusable_workloads_failed() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let context = SystemdCli::list_units_context();
+        context
+            .expect()
+            .return_const(Err("Simulated error".to_string()));
+
+        let systemd_runtime = SystemdRuntime::default();
+
+        assert_eq!(
+            systemd_runtime
+                .get_reusable_workloads(&AgentName::from(AGENT_NAME))
+                .await,
+            Err(RuntimeError::List("Simulated error".into()))
+        );
+    }
+
+    // [utest->swdd~systemd-create-workload-runs-transient-unit~2]
+    // [utest->swdd~systemd-create-workload-adds-log-fields~1]
+    #[tokio::test]
+    async fn utest_create_workload_success() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let run_context = SystemdCli::run_context();
+        run_context
+            .expect()
+            .withf(|unit_name, description, run_args| {
+                unit_name == UNIT_NAME
+                    && description == INSTANCE_NAME
+                    && *run_args
+                        == [
+                            "--property=LogExtraFields=ANKAIOS_AGENT=agent_x",
+                            "--property=LogExtraFields=ANKAIOS_WORKLOAD=workload1",
+                            "--property=LogExtraFields=ANKAIOS_INSTANCE_ID=hash",
+                            "--",
+                            "/usr/bin/sleep",
+                        ]
+            })
+            .once()
+            .return_const(Ok(()));
+
+        let workload_spec = generate_workload_spec("command: [/usr/bin/sleep]");
+        let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let systemd_runtime = SystemdRuntime::default();
+        let (created_workload_id, _checker) = systemd_runtime
+            .create_workload(workload_spec, None, None, state_change_tx)
+            .await
+            .unwrap();
+
+        assert_eq!(created_workload_id, workload_id());
+    }
+
+    // [utest->swdd~systemd-follows-workload-logs~1]
+    #[tokio::test]
+    async fn utest_create_workload_and_start_checker_mirror_logs() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let run_context = SystemdCli::run_context();
+        run_context.expect().return_const(Ok(()));
+
+        let mut seq = Sequence::new();
+        let journal_follower_context = SystemdCli::spawn_journal_follower_context();
+        journal_follower_context
+            .expect()
+            .withf(|unit_name, since| unit_name == UNIT_NAME && since.is_some())
+            .once()
+            .in_sequence(&mut seq)
+            .returning(|_, _| Err("simulated error".to_string()));
+        journal_follower_context
+            .expect()
+            .withf(|unit_name, since| unit_name == UNIT_NAME && since.is_none())
+            .once()
+            .in_sequence(&mut seq)
+            .returning(|_, _| Err("simulated error".to_string()));
+
+        let workload_spec = generate_workload_spec("command: [/usr/bin/sleep]");
+        let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let systemd_runtime = SystemdRuntime {
+            workload_logs: WorkloadLogsConfig {
+                sink: WorkloadLogSinkKind::Syslog,
+                ..Default::default()
+            },
+        };
+        let (workload_id, _checker) = systemd_runtime
+            .create_workload(workload_spec.clone(), None, None, state_change_tx.clone())
+            .await
+            .unwrap();
+
+        // a failing journal follower does not prevent the workload from running
+        let res = systemd_runtime
+            .start_checker(&workload_id, workload_spec, state_change_tx)
+            .await;
+        assert!(res.is_ok());
+    }
+
+    // [utest->swdd~systemd-follows-workload-logs~1]
+    #[tokio::test]
+    async fn utest_create_workload_does_not_mirror_logs_to_journald() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let run_context = SystemdCli::run_context();
+        run_context.expect().return_const(Ok(()));
+        let journal_follower_context = SystemdCli::spawn_journal_follower_context();
+        journal_follower_context.expect().never();
+
+        let workload_spec = generate_workload_spec("command: [/usr/bin/sleep]");
+        let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let systemd_runtime = SystemdRuntime {
+            workload_logs: WorkloadLogsConfig {
+                sink: WorkloadLogSinkKind::Journald,
+                ..Default::default()
+            },
+        };
+        let res = systemd_runtime
+            .create_workload(workload_spec, None, None, state_change_tx)
+            .await;
+
+        assert!(res.is_ok());
+    }
+
+    // [utest->swdd~systemd-create-workload-removes-existing-unit~1]
+    #[tokio::test]
+    async fn utest_create_workload_removes_reusable_unit() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let remove_context = SystemdCli::remove_unit_context();
+        remove_context
+            .expect()
+            .withf(|unit_name| unit_name == UNIT_NAME)
+            .once()
+            .return_const(Ok(()));
+        let run_context = SystemdCli::run_context();
+        run_context.expect().once().return_const(Ok(()));
+
+        let workload_spec = generate_workload_spec("command: [/usr/bin/sleep]");
+        let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let systemd_runtime = SystemdRuntime::default();
+        let res = systemd_runtime
+            .create_workload(workload_spec, Some(workload_id()), None, state_change_tx)
+            .await;
+
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn utest_create_workload_run_failed() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let run_context = SystemdCli::run_context();
+        run_context
+            .expect()
+            .return_const(Err("systemd-run failed".into()));
+
+        let workload_spec = generate_workload_spec("command: [/usr/bin/sleep]");
+        let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let systemd_runtime = SystemdRuntime::default();
+        let res = systemd_runtime
+            .create_workload(workload_spec, None, None, state_change_tx)
+            .await;
+
+        assert!(matches!(res, Err(RuntimeError::Create(msg)) if msg == "systemd-run failed"));
+    }
+
+    #[tokio::test]
+    async fn utest_create_workload_invalid_config() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let workload_spec = generate_workload_spec("environment: {}");
+        let (state_change_tx, _state_change_rx) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+
+        let systemd_runtime = SystemdRuntime::default();
+        let res = systemd_runtime
+            .create_workload(workload_spec, None, None, state_change_tx)
+            .await;
+
+        assert!(matches!(res, Err(RuntimeError::Create(_))));
+    }
+
+    // [utest->swdd~systemd-get-workload-id-uses-instance-name~1]
+    #[tokio::test]
+    async fn utest_get_workload_id_returns_instance_name() {
+        let systemd_runtime = SystemdRuntime::default();
+        let res = systemd_runtime
+            .get_workload_id(&WorkloadInstanceName::try_from(INSTANCE_NAME).unwrap())
+            .await;
+
+        assert_eq!(res, Ok(workload_id()));
+    }
+
+    // [utest->swdd~systemd-delete-workload-stops-unit~1]
+    #[tokio::test]
+    async fn utest_delete_workload_failed() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let remove_context = SystemdCli::remove_unit_context();
+        remove_context
+            .expect()
+            .withf(|unit_name| unit_name == UNIT_NAME)
+            .return_const(Err("simulated error".into()));
+
+        let systemd_runtime = SystemdRuntime::default();
+        let res = systemd_runtime.delete_workload(&workload_id()).await;
+
+        assert_eq!(res, Err(RuntimeError::Delete("simulated error".into())));
+    }
+
+    #[tokio::test]
+    async fn utest_state_getter_returns_lost_state_for_missing_unit() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let context = SystemdCli::get_state_context();
+        context.expect().return_const(Ok(None));
+
+        let state = SystemdStateGetter {}.get_state(&workload_id()).await;
+
+        assert_eq!(state, ExecutionState::lost());
+    }
+
+    #[tokio::test]
+    async fn utest_state_getter_returns_unknown_state_on_error() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+
+        let context = SystemdCli::get_state_context();
+        context.expect().return_const(Err("simulated error".into()));
+
+        let state = SystemdStateGetter {}.get_state(&workload_id()).await;
+
+        assert_eq!(
+            state,
+            ExecutionState::unknown("Error getting state from systemd.")
+        );
+    }
+}
@@ -0,0 +1,284 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use common::objects::{ResourceLimits, WorkloadSpec};
+use serde::Deserialize;
+
+use super::systemd_runtime::SYSTEMD_RUNTIME_NAME;
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemdRuntimeConfig {
+    pub command: Vec<String>,
+    #[serde(default)]
+    pub environment: BTreeMap<String, String>,
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+    #[serde(skip)]
+    pub resources: Option<ResourceLimits>,
+}
+
+impl TryFrom<&WorkloadSpec> for SystemdRuntimeConfig {
+    type Error = String;
+    fn try_from(workload_spec: &WorkloadSpec) -> Result<Self, Self::Error> {
+        if SYSTEMD_RUNTIME_NAME != workload_spec.runtime {
+            return Err(format!(
+                "Received a spec for the wrong runtime: '{}'",
+                workload_spec.runtime
+            ));
+        }
+        // [impl->swdd~systemd-rejects-container-fields~5]
+        if workload_spec.hostname.is_some()
+            || !workload_spec.extra_hosts.is_empty()
+            || !workload_spec.ports.is_empty()
+            || !workload_spec.volumes.is_empty()
+            || workload_spec.registry_auth.is_some()
+            || !workload_spec.networks.is_empty()
+            || workload_spec.security_context.is_some()
+            || workload_spec
+                .health_check
+                .as_ref()
+                .is_some_and(|health_check| health_check.command.is_some())
+        {
+            return Err(format!(
+                "The fields 'hostname', 'extraHosts', 'ports', 'volumes', 'registryAuth', 'networks', 'securityContext' and command health checks are not supported by the runtime '{}' as the workloads run directly on the host",
+                SYSTEMD_RUNTIME_NAME
+            ));
+        }
+
+        let mut workload_cfg: SystemdRuntimeConfig =
+            serde_yaml::from_str(workload_spec.runtime_config.as_str())
+                .map_err(|e| e.to_string())?;
+        if workload_cfg.command.is_empty() {
+            return Err("The command of a systemd workload must not be empty".to_string());
+        }
+        // [impl->swdd~systemd-maps-env-to-environment~1]
+        workload_cfg.environment.extend(workload_spec.env.clone());
+        workload_cfg.resources = workload_spec.resources.clone();
+        Ok(workload_cfg)
+    }
+}
+
+impl SystemdRuntimeConfig {
+    /// Returns the arguments for `systemd-run` creating a unit for the configured command
+    // [impl->swdd~systemd-create-workload-runs-transient-unit~2]
+    pub fn run_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        for (key, value) in &self.environment {
+            args.push(format!("--setenv={key}={value}"));
+        }
+        for (key, value) in &self.properties {
+            args.push(format!("--property={key}={value}"));
+        }
+        // [impl->swdd~systemd-maps-resource-limits-to-properties~1]
+        if let Some(resources) = &self.resources {
+            if let Some(cpu_quota) = resources.cpu_quota {
+                args.push(format!("--property=CPUQuota={cpu_quota}%"));
+            }
+            if let Some(memory_limit) = resources.memory_limit {
+                args.push(format!("--property=MemoryMax={memory_limit}"));
+            }
+        }
+        args.push("--".to_owned());
+        args.extend(self.command.iter().cloned());
+        args
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use common::objects::{
+        generate_test_workload_spec_with_param, HealthCheck, Network, ResourceLimits,
+        SecurityContext, TcpProbe, Volume,
+    };
+
+    use super::SystemdRuntimeConfig;
+    use crate::runtime_connectors::systemd::systemd_runtime::SYSTEMD_RUNTIME_NAME;
+
+    const AGENT_NAME: &str = "agent_x";
+    const WORKLOAD_1_NAME: &str = "workload1";
+
+    fn generate_workload_spec(runtime_config: &str) -> common::objects::WorkloadSpec {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            SYSTEMD_RUNTIME_NAME.to_string(),
+        );
+        workload_spec.runtime_config = runtime_config.to_string();
+        workload_spec
+    }
+
+    #[test]
+    fn utest_systemd_config_success() {
+        let mut workload_spec = generate_workload_spec(
+            "command: [/usr/bin/sleep, \"100\"]\nenvironment:\n  KEY: value\nproperties:\n  User: nobody\n",
+        );
+        workload_spec.health_check = Some(HealthCheck {
+            tcp: Some(TcpProbe {
+                host: "localhost".into(),
+                port: 8080,
+            }),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            SystemdRuntimeConfig::try_from(&workload_spec),
+            Ok(SystemdRuntimeConfig {
+                command: vec!["/usr/bin/sleep".to_string(), "100".to_string()],
+                environment: BTreeMap::from([("KEY".to_string(), "value".to_string())]),
+                properties: BTreeMap::from([("User".to_string(), "nobody".to_string())]),
+                resources: None,
+            })
+        );
+    }
+
+    // [utest->swdd~systemd-maps-env-to-environment~1]
+    #[test]
+    fn utest_systemd_config_merges_env_into_environment() {
+        let mut workload_spec = generate_workload_spec(
+            "command: [/usr/bin/sleep]\nenvironment:\n  KEY: value\n  LOG_LEVEL: info\n",
+        );
+        workload_spec.env = HashMap::from([("LOG_LEVEL".to_string(), "debug".to_string())]);
+
+        assert_eq!(
+            SystemdRuntimeConfig::try_from(&workload_spec).map(|config| config.environment),
+            Ok(BTreeMap::from([
+                ("KEY".to_string(), "value".to_string()),
+                ("LOG_LEVEL".to_string(), "debug".to_string()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn utest_systemd_config_failure_empty_command() {
+        let workload_spec = generate_workload_spec("command: []");
+
+        assert!(SystemdRuntimeConfig::try_from(&workload_spec).is_err());
+    }
+
+    #[test]
+    fn utest_systemd_config_failure_wrong_runtime() {
+        let mut workload_spec = generate_workload_spec("command: [/usr/bin/sleep]");
+        workload_spec.runtime = "podman".to_string();
+
+        assert!(SystemdRuntimeConfig::try_from(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~systemd-rejects-container-fields~5]
+    #[test]
+    fn utest_systemd_config_failure_container_fields_set() {
+        let mut workload_spec = generate_workload_spec("command: [/usr/bin/sleep]");
+        workload_spec.hostname = Some("web-server".to_string());
+
+        assert!(matches!(
+            SystemdRuntimeConfig::try_from(&workload_spec),
+            Err(msg) if msg.contains("not supported by the runtime 'systemd'")
+        ));
+
+        let mut workload_spec = generate_workload_spec("command: [/usr/bin/sleep]");
+        workload_spec.volumes = vec![Volume {
+            source: "/opt/data".to_string(),
+            mount_path: "/data".to_string(),
+            ..Default::default()
+        }];
+
+        assert!(matches!(
+            SystemdRuntimeConfig::try_from(&workload_spec),
+            Err(msg) if msg.contains("not supported by the runtime 'systemd'")
+        ));
+
+        let mut workload_spec = generate_workload_spec("command: [/usr/bin/sleep]");
+        workload_spec.registry_auth = Some("{}".to_string());
+
+        assert!(matches!(
+            SystemdRuntimeConfig::try_from(&workload_spec),
+            Err(msg) if msg.contains("not supported by the runtime 'systemd'")
+        ));
+
+        let mut workload_spec = generate_workload_spec("command: [/usr/bin/sleep]");
+        workload_spec.health_check = Some(HealthCheck {
+            command: Some(vec!["pg_isready".into()]),
+            ..Default::default()
+        });
+
+        assert!(matches!(
+            SystemdRuntimeConfig::try_from(&workload_spec),
+            Err(msg) if msg.contains("not supported by the runtime 'systemd'")
+        ));
+
+        let mut workload_spec = generate_workload_spec("command: [/usr/bin/sleep]");
+        workload_spec.networks = vec![Network {
+            name: "backend".to_string(),
+            aliases: vec![],
+        }];
+
+        assert!(matches!(
+            SystemdRuntimeConfig::try_from(&workload_spec),
+            Err(msg) if msg.contains("not supported by the runtime 'systemd'")
+        ));
+
+        let mut workload_spec = generate_workload_spec("command: [/usr/bin/sleep]");
+        workload_spec.security_context = Some(SecurityContext {
+            run_as_user: Some(1000),
+            ..Default::default()
+        });
+
+        assert!(matches!(
+            SystemdRuntimeConfig::try_from(&workload_spec),
+            Err(msg) if msg.contains("not supported by the runtime 'systemd'")
+        ));
+    }
+
+    // [utest->swdd~systemd-create-workload-runs-transient-unit~2]
+    // [utest->swdd~systemd-maps-resource-limits-to-properties~1]
+    #[test]
+    fn utest_systemd_config_run_args() {
+        let mut workload_spec = generate_workload_spec(
+            "command: [/usr/bin/sleep, \"100\"]\nenvironment:\n  KEY: value\nproperties:\n  User: nobody\n",
+        );
+        workload_spec.resources = Some(ResourceLimits {
+            cpu_quota: Some(150),
+            memory_limit: Some(1024),
+        });
+
+        let run_args = SystemdRuntimeConfig::try_from(&workload_spec)
+            .unwrap()
+            .run_args();
+
+        assert_eq!(
+            run_args,
+            vec![
+                "--setenv=KEY=value",
+                "--property=User=nobody",
+                "--property=CPUQuota=150%",
+                "--property=MemoryMax=1024",
+                "--",
+                "/usr/bin/sleep",
+                "100",
+            ]
+        );
+    }
+}
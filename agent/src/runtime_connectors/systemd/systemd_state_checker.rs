@@ -0,0 +1,79 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use common::objects::WorkloadSpec;
+use tokio::task::JoinHandle;
+
+use crate::{
+    generic_polling_state_checker::GenericPollingStateChecker,
+    runtime_connectors::{RuntimeStateGetter, StateChecker},
+    workload_state::WorkloadStateSender,
+};
+
+use super::systemd_runtime::SystemdWorkloadId;
+
+// Polls the state of the unit like the generic state checker. In contrast to the logs of a
+// container, the journal of a unit does not end when the unit stops, thus the state checker
+// also owns the task mirroring the logs and stops it together with the checker.
+#[derive(Debug)]
+pub struct SystemdStateChecker {
+    state_checker: GenericPollingStateChecker,
+    log_mirror: Option<LogMirror>,
+}
+
+#[derive(Debug)]
+struct LogMirror(JoinHandle<()>);
+
+impl Drop for LogMirror {
+    // [impl->swdd~systemd-follows-workload-logs~1]
+    fn drop(&mut self) {
+        // the journal follower is killed when the task is dropped
+        self.0.abort();
+    }
+}
+
+impl SystemdStateChecker {
+    pub fn with_log_mirror(mut self, log_mirror: Option<JoinHandle<()>>) -> Self {
+        self.log_mirror = log_mirror.map(LogMirror);
+        self
+    }
+}
+
+#[async_trait]
+impl StateChecker<SystemdWorkloadId> for SystemdStateChecker {
+    fn start_checker(
+        workload_spec: &WorkloadSpec,
+        workload_id: SystemdWorkloadId,
+        workload_state_sender: WorkloadStateSender,
+        state_getter: impl RuntimeStateGetter<SystemdWorkloadId>,
+    ) -> Self {
+        SystemdStateChecker {
+            state_checker: GenericPollingStateChecker::start_checker(
+                workload_spec,
+                workload_id,
+                workload_state_sender,
+                state_getter,
+            ),
+            log_mirror: None,
+        }
+    }
+
+    async fn stop_checker(self) {
+        <GenericPollingStateChecker as StateChecker<SystemdWorkloadId>>::stop_checker(
+            self.state_checker,
+        )
+        .await;
+    }
+}
@@ -0,0 +1,381 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use common::objects::ExecutionState;
+#[cfg(test)]
+use mockall::automock;
+
+#[cfg_attr(test, mockall_double::double)]
+use crate::runtime_connectors::cli_command::CliCommand;
+
+const SYSTEMCTL_CMD: &str = "systemctl";
+const SYSTEMD_RUN_CMD: &str = "systemd-run";
+const JOURNALCTL_CMD: &str = "journalctl";
+const UNIT_NOT_LOADED: &str = "not loaded";
+const STATE_PROPERTIES: &str = "--property=LoadState,ActiveState,SubState,Result";
+
+// [impl->swdd~systemd-state-getter-maps-state~1]
+fn to_execution_state(unit_name: &str, properties: &HashMap<&str, &str>) -> Option<ExecutionState> {
+    let property = |key: &str| properties.get(key).copied().unwrap_or_default();
+
+    if property("LoadState") == "not-found" {
+        return None;
+    }
+
+    let sub_state = property("SubState");
+    let result = property("Result");
+    Some(match property("ActiveState") {
+        // Units are started with 'RemainAfterExit', thus a finished service stays active
+        "active" if sub_state == "exited" => ExecutionState::succeeded(),
+        "active" | "reloading" => ExecutionState::running(),
+        "activating" => ExecutionState::starting(sub_state),
+        "deactivating" => ExecutionState::stopping(sub_state),
+        "inactive" if result == "success" => ExecutionState::succeeded(),
+        "inactive" | "failed" => {
+            ExecutionState::failed(format!("Unit '{}' failed: {}", unit_name, result))
+        }
+        active_state => {
+            log::trace!(
+                "Mapping the active state '{}' to the execution state 'ExecUnknown'",
+                active_state
+            );
+            ExecutionState::unknown(active_state)
+        }
+    })
+}
+
+pub struct SystemdCli {}
+
+#[cfg_attr(test, automock)]
+impl SystemdCli {
+    pub async fn list_units(pattern: &str) -> Result<Vec<String>, String> {
+        log::trace!("Listing the units matching: '{}'", pattern);
+        let output = CliCommand::new(SYSTEMCTL_CMD)
+            .args(&[
+                "list-units",
+                "--all",
+                "--plain",
+                "--no-legend",
+                "--type=service",
+                pattern,
+            ])
+            .exec()
+            .await?;
+        Ok(output
+            .lines()
+            .filter_map(|line| line.split_whitespace().next())
+            .map(str::to_owned)
+            .collect())
+    }
+
+    // [impl->swdd~systemd-create-workload-runs-transient-unit~2]
+    pub async fn run(
+        unit_name: &str,
+        description: &str,
+        run_args: Vec<String>,
+    ) -> Result<(), String> {
+        let mut args = vec![
+            format!("--unit={unit_name}"),
+            format!("--description={description}"),
+            "--remain-after-exit".to_owned(),
+        ];
+        args.extend(run_args);
+
+        CliCommand::new(SYSTEMD_RUN_CMD)
+            .args(&args.iter().map(|x| &**x).collect::<Vec<&str>>())
+            .exec()
+            .await?;
+        Ok(())
+    }
+
+    // [impl->swdd~systemd-state-getter-uses-unit-properties~1]
+    pub async fn get_state(unit_name: &str) -> Result<Option<ExecutionState>, String> {
+        let output = CliCommand::new(SYSTEMCTL_CMD)
+            .args(&["show", unit_name, STATE_PROPERTIES])
+            .exec()
+            .await?;
+        let properties = output
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .collect::<HashMap<&str, &str>>();
+        Ok(to_execution_state(unit_name, &properties))
+    }
+
+    // [impl->swdd~systemd-follows-workload-logs~1]
+    #[cfg_attr(test, allow(dead_code))]
+    pub fn spawn_journal_follower(
+        unit_name: &str,
+        since: Option<SystemTime>,
+    ) -> Result<tokio::process::Child, String> {
+        let mut args = vec![
+            format!("--unit={unit_name}"),
+            "--follow".to_owned(),
+            "--output=cat".to_owned(),
+        ];
+        match since.and_then(|since| since.duration_since(UNIX_EPOCH).ok()) {
+            // the journal still contains the lines of previous units with the same name
+            Some(since) => args.push(format!(
+                "--since=@{}.{:06}",
+                since.as_secs(),
+                since.subsec_micros()
+            )),
+            None => args.push("--lines=0".to_owned()),
+        }
+        // the lines of stdout and stderr of the unit are both written to stdout of journalctl
+        tokio::process::Command::new(JOURNALCTL_CMD)
+            .args(args)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| format!("Could not execute '{}': '{}'", JOURNALCTL_CMD, err))
+    }
+
+    // [impl->swdd~systemd-delete-workload-stops-unit~1]
+    pub async fn remove_unit(unit_name: &str) -> Result<(), String> {
+        match CliCommand::new(SYSTEMCTL_CMD)
+            .args(&["stop", unit_name])
+            .exec()
+            .await
+        {
+            Ok(_) => {}
+            Err(err) if err.contains(UNIT_NOT_LOADED) => return Ok(()),
+            Err(err) => return Err(err),
+        }
+
+        // Failed units are kept loaded until their failed state is reset
+        if let Err(err) = CliCommand::new(SYSTEMCTL_CMD)
+            .args(&["reset-failed", unit_name])
+            .exec()
+            .await
+        {
+            log::trace!(
+                "Could not reset the failed state of '{}': '{}'",
+                unit_name,
+                err
+            );
+        }
+        Ok(())
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+// [utest->swdd~systemd-uses-systemctl~2]
+#[cfg(test)]
+mod tests {
+    use common::objects::ExecutionState;
+
+    use super::{SystemdCli, STATE_PROPERTIES};
+    use crate::test_helper::MOCKALL_CONTEXT_SYNC;
+
+    const UNIT_NAME: &str = "ankaios-workload_1.hash.agent_A.service";
+    const SAMPLE_ERROR_MESSAGE: &str = "error message";
+
+    fn expect_show(output: &str) {
+        super::CliCommand::new_expect(
+            "systemctl",
+            super::CliCommand::default()
+                .expect_args(&["show", UNIT_NAME, STATE_PROPERTIES])
+                .exec_returns(Ok(output.to_string())),
+        );
+    }
+
+    fn unit_properties(active_state: &str, sub_state: &str, result: &str) -> String {
+        format!(
+            "LoadState=loaded\nActiveState={}\nSubState={}\nResult={}\n",
+            active_state, sub_state, result
+        )
+    }
+
+    #[tokio::test]
+    async fn utest_list_units() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "systemctl",
+            super::CliCommand::default()
+                .expect_args(&[
+                    "list-units",
+                    "--all",
+                    "--plain",
+                    "--no-legend",
+                    "--type=service",
+                    "ankaios-*.agent_A.service",
+                ])
+                .exec_returns(Ok(format!(
+                    "{} loaded active running workload_1.hash.agent_A\nankaios-workload_2.hash.agent_A.service loaded failed failed workload_2.hash.agent_A\n",
+                    UNIT_NAME
+                ))),
+        );
+
+        let res = SystemdCli::list_units("ankaios-*.agent_A.service").await;
+
+        assert_eq!(
+            res,
+            Ok(vec![
+                UNIT_NAME.to_string(),
+                "ankaios-workload_2.hash.agent_A.service".to_string()
+            ])
+        );
+    }
+
+    // [utest->swdd~systemd-create-workload-runs-transient-unit~2]
+    #[tokio::test]
+    async fn utest_run_creates_transient_unit() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "systemd-run",
+            super::CliCommand::default()
+                .expect_args(&[
+                    &format!("--unit={UNIT_NAME}"),
+                    "--description=workload_1.hash.agent_A",
+                    "--remain-after-exit",
+                    "--setenv=KEY=value",
+                    "--",
+                    "/usr/bin/sleep",
+                    "100",
+                ])
+                .exec_returns(Ok(String::new())),
+        );
+
+        let res = SystemdCli::run(
+            UNIT_NAME,
+            "workload_1.hash.agent_A",
+            vec![
+                "--setenv=KEY=value".to_string(),
+                "--".to_string(),
+                "/usr/bin/sleep".to_string(),
+                "100".to_string(),
+            ],
+        )
+        .await;
+
+        assert_eq!(res, Ok(()));
+    }
+
+    // [utest->swdd~systemd-state-getter-uses-unit-properties~1]
+    // [utest->swdd~systemd-state-getter-maps-state~1]
+    #[tokio::test]
+    async fn utest_get_state_maps_unit_states() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        expect_show(&unit_properties("active", "running", "success"));
+        expect_show(&unit_properties("active", "exited", "success"));
+        expect_show(&unit_properties("activating", "start-pre", "success"));
+        expect_show(&unit_properties("deactivating", "stop-sigterm", "success"));
+        expect_show(&unit_properties("failed", "failed", "exit-code"));
+        expect_show("LoadState=not-found\nActiveState=inactive\nSubState=dead\nResult=success\n");
+
+        assert_eq!(
+            SystemdCli::get_state(UNIT_NAME).await,
+            Ok(Some(ExecutionState::running()))
+        );
+        assert_eq!(
+            SystemdCli::get_state(UNIT_NAME).await,
+            Ok(Some(ExecutionState::succeeded()))
+        );
+        assert_eq!(
+            SystemdCli::get_state(UNIT_NAME).await,
+            Ok(Some(ExecutionState::starting("start-pre")))
+        );
+        assert_eq!(
+            SystemdCli::get_state(UNIT_NAME).await,
+            Ok(Some(ExecutionState::stopping("stop-sigterm")))
+        );
+        assert_eq!(
+            SystemdCli::get_state(UNIT_NAME).await,
+            Ok(Some(ExecutionState::failed(format!(
+                "Unit '{}' failed: exit-code",
+                UNIT_NAME
+            ))))
+        );
+        assert_eq!(SystemdCli::get_state(UNIT_NAME).await, Ok(None));
+    }
+
+    // [utest->swdd~systemd-delete-workload-stops-unit~1]
+    #[tokio::test]
+    async fn utest_remove_unit_stops_and_resets_unit() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "systemctl",
+            super::CliCommand::default()
+                .expect_args(&["stop", UNIT_NAME])
+                .exec_returns(Ok(String::new())),
+        );
+        super::CliCommand::new_expect(
+            "systemctl",
+            super::CliCommand::default()
+                .expect_args(&["reset-failed", UNIT_NAME])
+                .exec_returns(Err(SAMPLE_ERROR_MESSAGE.to_string())),
+        );
+
+        assert_eq!(SystemdCli::remove_unit(UNIT_NAME).await, Ok(()));
+    }
+
+    // [utest->swdd~systemd-delete-workload-stops-unit~1]
+    #[tokio::test]
+    async fn utest_remove_unit_ignores_missing_unit() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "systemctl",
+            super::CliCommand::default()
+                .expect_args(&["stop", UNIT_NAME])
+                .exec_returns(Err(format!(
+                    "Failed to stop {0}: Unit {0} not loaded.",
+                    UNIT_NAME
+                ))),
+        );
+
+        assert_eq!(SystemdCli::remove_unit(UNIT_NAME).await, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn utest_remove_unit_fails() {
+        let _guard = MOCKALL_CONTEXT_SYNC.get_lock_async().await;
+        super::CliCommand::reset();
+
+        super::CliCommand::new_expect(
+            "systemctl",
+            super::CliCommand::default()
+                .expect_args(&["stop", UNIT_NAME])
+                .exec_returns(Err(SAMPLE_ERROR_MESSAGE.to_string())),
+        );
+
+        assert_eq!(
+            SystemdCli::remove_unit(UNIT_NAME).await,
+            Err(SAMPLE_ERROR_MESSAGE.to_string())
+        );
+    }
+}
@@ -12,7 +12,12 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 #[cfg_attr(test, mockall_double::double)]
 use crate::control_interface::authorizer::Authorizer;
@@ -20,12 +25,13 @@ use crate::control_interface::authorizer::Authorizer;
 use api::ank_base;
 
 use common::{
+    commands::{ExecRequest, ExecStart, PortForwardRequest, PortForwardStart},
     objects::{
-        AgentName, DeletedWorkload, ExecutionState, WorkloadInstanceName, WorkloadSpec,
-        WorkloadState,
+        AgentDisconnectPolicy, AgentName, DeletedWorkload, ExecutionState, Port, VolumeType,
+        WorkloadInstanceName, WorkloadResources, WorkloadSpec, WorkloadState,
     },
     request_id_prepending::detach_prefix_from_request_id,
-    to_server_interface::ToServerSender,
+    to_server_interface::{ToServerInterface, ToServerSender},
 };
 
 #[cfg_attr(test, mockall_double::double)]
@@ -37,7 +43,10 @@ use crate::workload_scheduler::scheduler::WorkloadScheduler;
 #[cfg_attr(test, mockall_double::double)]
 use crate::workload_state::workload_state_store::WorkloadStateStore;
 use crate::{
+    exec_session::{error_response, ExecSession},
+    port_forward_session::PortForwardSession,
     runtime_connectors::RuntimeFacade,
+    workload::EvictionReason,
     workload_operation::{ReusableWorkloadSpec, WorkloadOperation},
     workload_state::{WorkloadStateSender, WorkloadStateSenderInterface},
 };
@@ -48,6 +57,54 @@ use crate::workload::Workload;
 #[cfg(test)]
 use mockall::automock;
 
+// Returns the runtime and the instance name of a workload running on the agent.
+async fn find_running_instance<'a>(
+    runtime_map: &'a HashMap<String, Box<dyn RuntimeFacade>>,
+    workloads: &HashMap<String, Workload>,
+    agent_name: &AgentName,
+    workload_name: &str,
+) -> Result<(&'a dyn RuntimeFacade, WorkloadInstanceName), String> {
+    if !workloads.contains_key(workload_name) {
+        return Err(format!(
+            "Workload '{}' is not running on agent '{}'",
+            workload_name, agent_name
+        ));
+    }
+
+    for runtime in runtime_map.values() {
+        let Ok(workload_states) = runtime.get_reusable_workloads(agent_name).await else {
+            continue;
+        };
+        if let Some(instance_name) = workload_states
+            .into_iter()
+            .map(|reusable_state| reusable_state.workload_state.instance_name)
+            .find(|instance_name| instance_name.workload_name() == workload_name)
+        {
+            return Ok((runtime.as_ref(), instance_name));
+        }
+    }
+
+    Err(format!(
+        "Could not find the running instance of workload '{}'",
+        workload_name
+    ))
+}
+
+// [impl->swdd~agent-checks-host-path-volumes~1]
+fn find_missing_host_path(workload_spec: &WorkloadSpec) -> Option<String> {
+    workload_spec
+        .volumes
+        .iter()
+        .filter(|volume| volume.volume_type == VolumeType::HostPath)
+        .find(|volume| !Path::new(&volume.source).exists())
+        .map(|volume| {
+            format!(
+                "Host path '{}' of the volume mounted to '{}' does not exist",
+                volume.source, volume.mount_path
+            )
+        })
+}
+
 fn flatten(
     mut runtime_workload_map: HashMap<String, HashMap<String, WorkloadSpec>>,
 ) -> Vec<ReusableWorkloadSpec> {
@@ -78,6 +135,12 @@ pub struct RuntimeManager {
     run_folder: PathBuf,
     control_interface_tx: ToServerSender,
     workloads: HashMap<String, Workload>,
+    workload_ports: HashMap<String, Vec<Port>>,
+    workload_priorities: HashMap<String, u32>,
+    workload_disconnect_policies: HashMap<String, AgentDisconnectPolicy>,
+    workload_restart_on: HashMap<String, Vec<String>>,
+    exec_sessions: HashMap<String, ExecSession>,
+    port_forward_sessions: HashMap<String, PortForwardSession>,
     // [impl->swdd~agent-supports-multiple-runtime-connectors~1]
     runtime_map: HashMap<String, Box<dyn RuntimeFacade>>,
     update_state_tx: WorkloadStateSender,
@@ -98,6 +161,12 @@ impl RuntimeManager {
             run_folder,
             control_interface_tx,
             workloads: HashMap::new(),
+            workload_ports: HashMap::new(),
+            workload_priorities: HashMap::new(),
+            workload_disconnect_policies: HashMap::new(),
+            workload_restart_on: HashMap::new(),
+            exec_sessions: HashMap::new(),
+            port_forward_sessions: HashMap::new(),
             runtime_map,
             update_state_tx: update_state_tx.clone(),
             workload_queue: WorkloadScheduler::new(update_state_tx),
@@ -200,6 +269,387 @@ impl RuntimeManager {
         }
     }
 
+    // [impl->swdd~agent-handles-exec-requests~1]
+    pub async fn handle_exec_request(&mut self, request_id: String, exec_request: ExecRequest) {
+        self.exec_sessions
+            .retain(|_, exec_session| !exec_session.is_finished());
+
+        let result = match exec_request {
+            ExecRequest::Start(exec_start) => {
+                self.start_exec_session(&request_id, exec_start).await
+            }
+            ExecRequest::Input(exec_input) => match self.exec_sessions.get(&request_id) {
+                Some(exec_session) => exec_session.send_input(exec_input).await,
+                None => Err("No running exec session".to_string()),
+            },
+        };
+
+        if let Err(err) = result {
+            log::warn!("Exec request '{}' failed: '{}'", request_id, err);
+            self.exec_sessions.remove(&request_id);
+            let response = error_response(request_id, err);
+            if let Err(err) = self.control_interface_tx.response(response).await {
+                log::error!("Could not send the exec response: '{}'", err);
+            }
+        }
+    }
+
+    // [impl->swdd~agent-handles-port-forward-requests~2]
+    pub async fn handle_port_forward_request(
+        &mut self,
+        request_id: String,
+        port_forward_request: PortForwardRequest,
+    ) {
+        self.port_forward_sessions
+            .retain(|_, port_forward_session| !port_forward_session.is_finished());
+
+        let result = match port_forward_request {
+            PortForwardRequest::Start(port_forward_start) => {
+                self.start_port_forward_session(&request_id, port_forward_start)
+                    .await
+            }
+            PortForwardRequest::Data(port_forward_data) => {
+                match self.port_forward_sessions.get(&request_id) {
+                    Some(session) => session.send_data(port_forward_data).await,
+                    None => Err("No open port forwarding".to_string()),
+                }
+            }
+        };
+
+        if let Err(err) = result {
+            log::warn!("Port forward request '{}' failed: '{}'", request_id, err);
+            self.port_forward_sessions.remove(&request_id);
+            let response = error_response(request_id, err);
+            if let Err(err) = self.control_interface_tx.response(response).await {
+                log::error!("Could not send the port forward response: '{}'", err);
+            }
+        }
+    }
+
+    // [impl->swdd~agent-handles-restart-workload-requests~1]
+    pub async fn handle_restart_workload_request(
+        &mut self,
+        request_id: String,
+        workload_names: Vec<String>,
+    ) {
+        let mut restarted_workloads = Vec::new();
+        let mut errors = Vec::new();
+        // [impl->swdd~agent-restarts-workloads-restarting-on-restarted-workload~1]
+        for workload_name in self.with_workloads_restarting_on(workload_names) {
+            match self.workloads.get(&workload_name) {
+                Some(workload) => match workload.restart().await {
+                    Ok(()) => restarted_workloads.push(workload_name),
+                    Err(err) => errors.push(format!(
+                        "Could not restart workload '{}': '{}'",
+                        workload_name, err
+                    )),
+                },
+                None => errors.push(format!(
+                    "Workload '{}' is not running on agent '{}'",
+                    workload_name, self.agent_name
+                )),
+            }
+        }
+
+        let response = if errors.is_empty() {
+            ank_base::Response {
+                request_id,
+                response_content: Some(
+                    ank_base::response::ResponseContent::RestartWorkloadSuccess(
+                        ank_base::RestartWorkloadSuccess {
+                            restarted_workloads,
+                        },
+                    ),
+                ),
+            }
+        } else {
+            log::warn!(
+                "Restart workload request '{}' failed: '{}'",
+                request_id,
+                errors.join(", ")
+            );
+            error_response(request_id, errors.join(", "))
+        };
+        if let Err(err) = self.control_interface_tx.response(response).await {
+            log::error!("Could not send the restart workload response: '{}'", err);
+        }
+    }
+
+    // Extends the restarted workloads by the managed workloads restarting on them, including the ones restarting on those.
+    // [impl->swdd~agent-restarts-workloads-restarting-on-restarted-workload~1]
+    fn with_workloads_restarting_on(&self, mut workload_names: Vec<String>) -> Vec<String> {
+        let mut index = 0;
+        while let Some(restarted_workload) = workload_names.get(index) {
+            let mut restarting_workloads: Vec<String> = self
+                .workload_restart_on
+                .iter()
+                .filter(|(workload_name, restart_on)| {
+                    restart_on.contains(restarted_workload)
+                        && !workload_names.contains(*workload_name)
+                })
+                .map(|(workload_name, _)| workload_name.clone())
+                .collect();
+            // sort in order to restart the workloads in a consistent order
+            restarting_workloads.sort();
+            workload_names.extend(restarting_workloads);
+            index += 1;
+        }
+        workload_names
+    }
+
+    // [impl->swdd~agent-handles-pause-workload-requests~1]
+    pub async fn handle_pause_workload_request(
+        &mut self,
+        request_id: String,
+        workload_names: Vec<String>,
+        resume: bool,
+    ) {
+        let action = if resume { "resume" } else { "pause" };
+        let mut workloads = Vec::new();
+        let mut errors = Vec::new();
+        for workload_name in workload_names {
+            let Some(workload) = self.workloads.get(&workload_name) else {
+                errors.push(format!(
+                    "Workload '{}' is not running on agent '{}'",
+                    workload_name, self.agent_name
+                ));
+                continue;
+            };
+            let result = if resume {
+                workload.unpause().await
+            } else {
+                workload.pause().await
+            };
+            match result {
+                Ok(()) => workloads.push(workload_name),
+                Err(err) => errors.push(format!(
+                    "Could not {} workload '{}': '{}'",
+                    action, workload_name, err
+                )),
+            }
+        }
+
+        let response = if errors.is_empty() {
+            ank_base::Response {
+                request_id,
+                response_content: Some(ank_base::response::ResponseContent::PauseWorkloadSuccess(
+                    ank_base::PauseWorkloadSuccess { workloads },
+                )),
+            }
+        } else {
+            log::warn!(
+                "Pause workload request '{}' failed: '{}'",
+                request_id,
+                errors.join(", ")
+            );
+            error_response(request_id, errors.join(", "))
+        };
+        if let Err(err) = self.control_interface_tx.response(response).await {
+            log::error!("Could not send the pause workload response: '{}'", err);
+        }
+    }
+
+    async fn start_exec_session(
+        &mut self,
+        request_id: &str,
+        exec_start: ExecStart,
+    ) -> Result<(), String> {
+        let (runtime, instance_name) = find_running_instance(
+            &self.runtime_map,
+            &self.workloads,
+            &self.agent_name,
+            &exec_start.workload_name,
+        )
+        .await?;
+
+        log::info!(
+            "Starting exec session '{}' in workload '{}'.",
+            request_id,
+            exec_start.workload_name
+        );
+        let child = runtime
+            .exec(&instance_name, exec_start.command)
+            .await
+            .map_err(|err| err.to_string())?;
+        self.exec_sessions.insert(
+            request_id.to_owned(),
+            ExecSession::start(
+                request_id.to_owned(),
+                child,
+                self.control_interface_tx.clone(),
+            ),
+        );
+        Ok(())
+    }
+
+    async fn start_port_forward_session(
+        &mut self,
+        request_id: &str,
+        port_forward_start: PortForwardStart,
+    ) -> Result<(), String> {
+        let port = u16::try_from(port_forward_start.port)
+            .map_err(|_| format!("Invalid port '{}'", port_forward_start.port))?;
+        let (runtime, instance_name) = find_running_instance(
+            &self.runtime_map,
+            &self.workloads,
+            &self.agent_name,
+            &port_forward_start.workload_name,
+        )
+        .await?;
+
+        let address = runtime
+            .get_workload_address(&instance_name)
+            .await
+            .map_err(|err| err.to_string())?;
+        log::info!(
+            "Forwarding connection '{}' to port {} of workload '{}'.",
+            request_id,
+            port,
+            port_forward_start.workload_name
+        );
+        // the session connects on its own, a slow workload must not block the other requests
+        self.port_forward_sessions.insert(
+            request_id.to_owned(),
+            PortForwardSession::start(
+                request_id.to_owned(),
+                port_forward_start.workload_name,
+                SocketAddr::new(address, port),
+                self.control_interface_tx.clone(),
+            ),
+        );
+        Ok(())
+    }
+
+    // [impl->swdd~agent-collects-resource-usage-of-its-workloads~1]
+    pub async fn collect_workload_resources(&self) -> Vec<WorkloadResources> {
+        let mut workload_resources = Vec::new();
+        for runtime in self.runtime_map.values() {
+            workload_resources.extend(runtime.collect_resource_usage(&self.agent_name).await);
+        }
+        workload_resources
+    }
+
+    // [impl->swdd~agent-evicts-lowest-priority-workload~1]
+    pub async fn evict_lowest_priority_workload(
+        &self,
+        workload_state_db: &WorkloadStateStore,
+    ) -> Option<String> {
+        let workload_name = self
+            .workloads
+            .keys()
+            .filter(|workload_name| {
+                workload_state_db
+                    .get_state_of_workload(workload_name)
+                    .is_some_and(ExecutionState::is_running)
+            })
+            .min_by(|a, b| {
+                (self.priority_of(a), a.as_str()).cmp(&(self.priority_of(b), b.as_str()))
+            })?
+            .clone();
+
+        let workload = self.workloads.get(&workload_name)?;
+        if let Err(err) = workload.evict(EvictionReason::MemoryPressure).await {
+            log::error!("Failed to evict workload '{}': '{}'", workload_name, err);
+            return None;
+        }
+        Some(workload_name)
+    }
+
+    // [impl->swdd~agent-restores-highest-priority-workload~1]
+    pub async fn restore_highest_priority_workload(
+        &self,
+        workload_state_db: &WorkloadStateStore,
+    ) -> Option<String> {
+        let workload_name = self
+            .workloads
+            .keys()
+            .filter(|workload_name| {
+                workload_state_db
+                    .get_state_of_workload(workload_name)
+                    .is_some_and(ExecutionState::is_evicted)
+            })
+            .max_by(|a, b| {
+                // on equal priorities the workload with the lower name is restored first
+                (self.priority_of(a), b.as_str()).cmp(&(self.priority_of(b), a.as_str()))
+            })?
+            .clone();
+
+        let workload = self.workloads.get(&workload_name)?;
+        if let Err(err) = workload.restore().await {
+            log::error!("Failed to restore workload '{}': '{}'", workload_name, err);
+            return None;
+        }
+        Some(workload_name)
+    }
+
+    // [impl->swdd~agent-enforces-agent-disconnect-policies~1]
+    pub async fn stop_workloads_on_disconnect(
+        &self,
+        time_over_threshold: Duration,
+        workload_state_db: &WorkloadStateStore,
+    ) -> Option<Duration> {
+        let mut next_stop_delay: Option<Duration> = None;
+        for (workload_name, workload) in &self.workloads {
+            let Some(stop_delay) = self
+                .workload_disconnect_policies
+                .get(workload_name)
+                .and_then(AgentDisconnectPolicy::stop_delay)
+            else {
+                continue;
+            };
+
+            if stop_delay > time_over_threshold {
+                let remaining_delay = stop_delay - time_over_threshold;
+                next_stop_delay = next_stop_delay
+                    .map(|delay| delay.min(remaining_delay))
+                    .or(Some(remaining_delay));
+                continue;
+            }
+
+            if !workload_state_db
+                .get_state_of_workload(workload_name)
+                .is_some_and(ExecutionState::is_running)
+            {
+                continue;
+            }
+
+            log::warn!(
+                "Stopping workload '{}' according to its agent disconnect policy.",
+                workload_name
+            );
+            if let Err(err) = workload.evict(EvictionReason::AgentDisconnect).await {
+                log::error!("Failed to stop workload '{}': '{}'", workload_name, err);
+            }
+        }
+        next_stop_delay
+    }
+
+    fn priority_of(&self, workload_name: &str) -> u32 {
+        self.workload_priorities
+            .get(workload_name)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    // [impl->swdd~agent-deletes-all-workloads-on-shutdown~1]
+    pub async fn delete_all_workloads(&mut self) -> Vec<String> {
+        log::info!("Deleting all '{}' workloads.", self.workloads.len());
+
+        self.workload_ports.clear();
+        self.workload_priorities.clear();
+        self.workload_disconnect_policies.clear();
+        self.workload_restart_on.clear();
+        let mut deleted_workloads = Vec::new();
+        for (workload_name, workload) in self.workloads.drain() {
+            if let Err(err) = workload.delete(None).await {
+                log::error!("Failed to delete workload '{}': '{}'", workload_name, err);
+            } else {
+                deleted_workloads.push(workload_name);
+            }
+        }
+        deleted_workloads
+    }
+
     // [impl->swdd~agent-initial-list-existing-workloads~1]
     async fn resume_and_remove_from_added_workloads(
         &mut self,
@@ -259,6 +709,7 @@ impl RuntimeManager {
                                     self.control_interface_tx.clone(),
                                     &new_instance_name,
                                     Authorizer::from(&new_workload_spec.control_interface_access),
+                                    new_workload_spec.control_interface_access.pipes_owner,
                                 ));
 
                                 log::info!(
@@ -266,6 +717,19 @@ impl RuntimeManager {
                                     new_instance_name.workload_name()
                                 );
 
+                                self.workload_priorities.insert(
+                                    new_instance_name.workload_name().to_owned(),
+                                    new_workload_spec.priority.unwrap_or_default(),
+                                );
+                                self.workload_disconnect_policies.insert(
+                                    new_instance_name.workload_name().to_owned(),
+                                    new_workload_spec.on_agent_disconnect.unwrap_or_default(),
+                                );
+                                self.workload_restart_on.insert(
+                                    new_instance_name.workload_name().to_owned(),
+                                    new_workload_spec.restart_on.clone(),
+                                );
+
                                 // [impl->swdd~agent-stores-running-workload~1]
                                 self.workloads.insert(
                                     new_instance_name.workload_name().to_owned(),
@@ -419,6 +883,7 @@ impl RuntimeManager {
                     DeletedWorkload {
                         instance_name,
                         dependencies: HashMap::default(),
+                        grace_period_secs: None,
                     },
                 ));
             } else {
@@ -453,9 +918,61 @@ impl RuntimeManager {
         }
     }
 
+    // [impl->swdd~agent-detects-host-port-conflicts~1]
+    fn find_host_port_conflict(&self, workload_spec: &WorkloadSpec) -> Option<String> {
+        let workload_name = workload_spec.instance_name.workload_name();
+        self.workload_ports
+            .iter()
+            .filter(|(other_workload_name, _)| other_workload_name.as_str() != workload_name)
+            .find_map(|(other_workload_name, other_ports)| {
+                workload_spec.ports.iter().find_map(|port| {
+                    other_ports
+                        .iter()
+                        .any(|other_port| port.conflicts_with(other_port))
+                        .then(|| {
+                            format!(
+                                "Host port '{}/{}' is already used by workload '{}'",
+                                port.host_port, port.protocol, other_workload_name
+                            )
+                        })
+                })
+            })
+    }
+
+    // [impl->swdd~agent-detects-host-port-conflicts~1]
+    // [impl->swdd~agent-checks-host-path-volumes~1]
+    fn find_unmet_host_requirement(&self, workload_spec: &WorkloadSpec) -> Option<String> {
+        self.find_host_port_conflict(workload_spec)
+            .or_else(|| find_missing_host_path(workload_spec))
+    }
+
+    // [impl->swdd~agent-detects-host-port-conflicts~1]
+    // [impl->swdd~agent-checks-host-path-volumes~1]
+    async fn report_starting_failed(&self, workload_spec: &WorkloadSpec, reason: String) {
+        log::warn!(
+            "Workload '{}' not created: {}",
+            workload_spec.instance_name.workload_name(),
+            reason
+        );
+        self.update_state_tx
+            .report_workload_execution_state(
+                &workload_spec.instance_name,
+                ExecutionState::starting_failed(reason),
+            )
+            .await;
+    }
+
     async fn add_workload(&mut self, reusable_workload_spec: ReusableWorkloadSpec) {
         let workload_spec = &reusable_workload_spec.workload_spec;
         let workload_name = workload_spec.instance_name.workload_name().to_owned();
+
+        // [impl->swdd~agent-detects-host-port-conflicts~1]
+        // [impl->swdd~agent-checks-host-path-volumes~1]
+        if let Some(reason) = self.find_unmet_host_requirement(workload_spec) {
+            self.report_starting_failed(workload_spec, reason).await;
+            return;
+        }
+
         // [impl->swdd~agent-control-interface-created-for-eligible-workloads~1]
         let control_interface_info = if workload_spec.needs_control_interface() {
             Some(ControlInterfaceInfo::new(
@@ -463,6 +980,7 @@ impl RuntimeManager {
                 self.control_interface_tx.clone(),
                 &workload_spec.instance_name,
                 Authorizer::from(&workload_spec.control_interface_access),
+                workload_spec.control_interface_access.pipes_owner,
             ))
         } else {
             log::info!(
@@ -475,6 +993,18 @@ impl RuntimeManager {
         // [impl->swdd~agent-uses-specified-runtime~1]
         // [impl->swdd~agent-skips-unknown-runtime~1]
         if let Some(runtime) = self.runtime_map.get(&workload_spec.runtime) {
+            self.workload_ports
+                .insert(workload_name.clone(), workload_spec.ports.clone());
+            self.workload_priorities.insert(
+                workload_name.clone(),
+                workload_spec.priority.unwrap_or_default(),
+            );
+            self.workload_disconnect_policies.insert(
+                workload_name.clone(),
+                workload_spec.on_agent_disconnect.unwrap_or_default(),
+            );
+            self.workload_restart_on
+                .insert(workload_name.clone(), workload_spec.restart_on.clone());
             // [impl->swdd~agent-executes-create-workload-operation~1]
             let workload = runtime.create_workload(
                 reusable_workload_spec,
@@ -493,12 +1023,21 @@ impl RuntimeManager {
     }
 
     async fn delete_workload(&mut self, deleted_workload: DeletedWorkload) {
+        self.workload_ports
+            .remove(deleted_workload.instance_name.workload_name());
+        self.workload_priorities
+            .remove(deleted_workload.instance_name.workload_name());
+        self.workload_disconnect_policies
+            .remove(deleted_workload.instance_name.workload_name());
+        self.workload_restart_on
+            .remove(deleted_workload.instance_name.workload_name());
         if let Some(workload) = self
             .workloads
             .remove(deleted_workload.instance_name.workload_name())
         {
             // [impl->swdd~agent-executes-delete-workload-operation~1]
-            if let Err(err) = workload.delete().await {
+            // [impl->swdd~agent-deletes-workload-with-grace-period~1]
+            if let Err(err) = workload.delete(deleted_workload.grace_period_secs).await {
                 log::error!(
                     "Failed to delete workload '{}': '{}'",
                     deleted_workload.instance_name.workload_name(),
@@ -526,7 +1065,35 @@ impl RuntimeManager {
     async fn update_workload(&mut self, workload_spec: WorkloadSpec) {
         let workload_name = workload_spec.instance_name.workload_name().to_owned();
 
+        // [impl->swdd~agent-detects-host-port-conflicts~1]
+        // [impl->swdd~agent-checks-host-path-volumes~1]
+        if let Some(reason) = self.find_unmet_host_requirement(&workload_spec) {
+            self.workload_ports.remove(&workload_name);
+            self.workload_priorities.remove(&workload_name);
+            self.workload_disconnect_policies.remove(&workload_name);
+            self.workload_restart_on.remove(&workload_name);
+            if let Some(workload) = self.workloads.remove(&workload_name) {
+                if let Err(err) = workload.delete(None).await {
+                    log::error!("Failed to delete workload '{}': '{}'", workload_name, err);
+                }
+            }
+            self.report_starting_failed(&workload_spec, reason).await;
+            return;
+        }
+
         if let Some(workload) = self.workloads.get_mut(&workload_name) {
+            self.workload_ports
+                .insert(workload_name.clone(), workload_spec.ports.clone());
+            self.workload_priorities.insert(
+                workload_name.clone(),
+                workload_spec.priority.unwrap_or_default(),
+            );
+            self.workload_disconnect_policies.insert(
+                workload_name.clone(),
+                workload_spec.on_agent_disconnect.unwrap_or_default(),
+            );
+            self.workload_restart_on
+                .insert(workload_name.clone(), workload_spec.restart_on.clone());
             // [impl->swdd~agent-control-interface-created-for-eligible-workloads~1]
             let control_interface_info = if workload_spec.needs_control_interface() {
                 Some(ControlInterfaceInfo::new(
@@ -534,6 +1101,7 @@ impl RuntimeManager {
                     self.control_interface_tx.clone(),
                     &workload_spec.instance_name,
                     Authorizer::from(&workload_spec.control_interface_access),
+                    workload_spec.control_interface_access.pipes_owner,
                 ))
             } else {
                 log::info!(
@@ -591,65 +1159,882 @@ mod tests {
     };
     use crate::runtime_connectors::{MockRuntimeFacade, ReusableWorkloadState, RuntimeError};
     use crate::runtime_manager::ToReusableWorkloadSpecs;
-    use crate::workload::{MockWorkload, WorkloadError};
+    use crate::workload::{EvictionReason, MockWorkload, WorkloadError};
     use crate::workload_operation::ReusableWorkloadSpec;
     use crate::workload_scheduler::scheduler::MockWorkloadScheduler;
     use crate::workload_state::workload_state_store::MockWorkloadStateStore;
     use crate::workload_state::WorkloadStateReceiver;
     use ank_base::response::ResponseContent;
+    use common::commands::{
+        ExecInput, ExecRequest, ExecStart, PortForwardData, PortForwardRequest, PortForwardStart,
+    };
     use common::objects::{
         self, generate_test_control_interface_access,
         generate_test_workload_spec_with_control_interface_access,
         generate_test_workload_spec_with_dependencies, generate_test_workload_spec_with_param,
-        AddCondition, WorkloadInstanceNameBuilder, WorkloadState,
+        AddCondition, AgentDisconnectPolicy, Port, PortProtocol, Volume,
+        WorkloadInstanceNameBuilder, WorkloadState,
     };
     use common::test_utils::{
         self, generate_test_complete_state, generate_test_deleted_workload,
         generate_test_deleted_workload_with_dependencies,
     };
-    use common::to_server_interface::ToServerReceiver;
+    use common::to_server_interface::{ToServer, ToServerReceiver};
     use mockall::{predicate, Sequence};
     use std::collections::HashMap;
-    use std::{any::Any, path::Path};
+    use std::{any::Any, path::Path, time::Duration};
     use tokio::sync::mpsc::channel;
 
-    const BUFFER_SIZE: usize = 20;
-    const RUNTIME_NAME: &str = "runtime1";
-    const RUNTIME_NAME_2: &str = "runtime2";
-    const AGENT_NAME: &str = "agent_x";
-    const WORKLOAD_1_NAME: &str = "workload1";
-    const WORKLOAD_2_NAME: &str = "workload2";
-    const REQUEST_ID: &str = "request_id";
-    const RUN_FOLDER: &str = "run/folder";
+    const BUFFER_SIZE: usize = 20;
+    const RUNTIME_NAME: &str = "runtime1";
+    const RUNTIME_NAME_2: &str = "runtime2";
+    const AGENT_NAME: &str = "agent_x";
+    const WORKLOAD_1_NAME: &str = "workload1";
+    const WORKLOAD_2_NAME: &str = "workload2";
+    const WORKLOAD_3_NAME: &str = "workload3";
+    const REQUEST_ID: &str = "request_id";
+    const RUN_FOLDER: &str = "run/folder";
+
+    #[derive(Default)]
+    pub struct RuntimeManagerBuilder {
+        runtime_facade_map: HashMap<String, Box<dyn RuntimeFacade>>,
+    }
+
+    impl RuntimeManagerBuilder {
+        pub fn with_runtime(
+            mut self,
+            runtime_name: &str,
+            runtime_facade: Box<dyn RuntimeFacade>,
+        ) -> Self {
+            self.runtime_facade_map
+                .insert(runtime_name.to_string(), runtime_facade);
+            self
+        }
+
+        pub fn build(self) -> (ToServerReceiver, RuntimeManager, WorkloadStateReceiver) {
+            let (to_server, server_receiver) = channel(BUFFER_SIZE);
+            let (wl_state_sender, wl_state_receiver) = channel(BUFFER_SIZE);
+            let runtime_manager = RuntimeManager::new(
+                AGENT_NAME.into(),
+                Path::new(RUN_FOLDER).into(),
+                to_server.clone(),
+                self.runtime_facade_map,
+                wl_state_sender.clone(),
+            );
+            (server_receiver, runtime_manager, wl_state_receiver)
+        }
+    }
+
+    // [utest->swdd~agent-collects-resource-usage-of-its-workloads~1]
+    #[tokio::test]
+    async fn utest_collect_workload_resources_of_all_runtimes() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        runtime_facade_mock
+            .expect_collect_resource_usage()
+            .once()
+            .return_once(|_| {
+                Box::pin(async {
+                    vec![objects::generate_test_workload_resources(
+                        AGENT_NAME,
+                        WORKLOAD_1_NAME,
+                    )]
+                })
+            });
+
+        let mut runtime_facade_mock_2 = MockRuntimeFacade::new();
+        runtime_facade_mock_2
+            .expect_collect_resource_usage()
+            .once()
+            .return_once(|_| Box::pin(async { vec![] }));
+
+        let (_, runtime_manager, _) = RuntimeManagerBuilder::default()
+            .with_runtime(
+                RUNTIME_NAME,
+                Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+            )
+            .with_runtime(
+                RUNTIME_NAME_2,
+                Box::new(runtime_facade_mock_2) as Box<dyn RuntimeFacade>,
+            )
+            .build();
+
+        assert_eq!(
+            runtime_manager.collect_workload_resources().await,
+            vec![objects::generate_test_workload_resources(
+                AGENT_NAME,
+                WORKLOAD_1_NAME
+            )]
+        );
+    }
+
+    fn assert_error_response(to_server: &mut ToServerReceiver, expected_message: &str) {
+        let Ok(ToServer::Response(response)) = to_server.try_recv() else {
+            panic!("Expected an error response");
+        };
+        assert_eq!(response.request_id, REQUEST_ID);
+        assert_eq!(
+            response.response_content,
+            Some(ResponseContent::Error(ank_base::Error {
+                message: expected_message.to_string()
+            }))
+        );
+    }
+
+    // [utest->swdd~agent-handles-exec-requests~1]
+    #[tokio::test]
+    async fn utest_handle_exec_request_start_unknown_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        runtime_facade_mock.expect_exec().never();
+
+        let (mut to_server, mut runtime_manager, _) = RuntimeManagerBuilder::default()
+            .with_runtime(
+                RUNTIME_NAME,
+                Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+            )
+            .build();
+
+        runtime_manager
+            .handle_exec_request(
+                REQUEST_ID.to_string(),
+                ExecRequest::Start(ExecStart {
+                    workload_name: WORKLOAD_1_NAME.to_string(),
+                    command: vec!["sh".to_string()],
+                }),
+            )
+            .await;
+
+        assert_error_response(
+            &mut to_server,
+            "Workload 'workload1' is not running on agent 'agent_x'",
+        );
+        assert!(runtime_manager.exec_sessions.is_empty());
+    }
+
+    // [utest->swdd~agent-handles-exec-requests~1]
+    #[tokio::test]
+    async fn utest_handle_exec_request_start_fails_in_runtime() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let instance_name = WorkloadInstanceName::builder()
+            .workload_name(WORKLOAD_1_NAME)
+            .agent_name(AGENT_NAME)
+            .config(&"config".to_string())
+            .build();
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        let reusable_state =
+            ReusableWorkloadState::new(instance_name.clone(), ExecutionState::running(), None);
+        runtime_facade_mock
+            .expect_get_reusable_workloads()
+            .once()
+            .return_once(|_| Box::pin(async { Ok(vec![reusable_state]) }));
+        runtime_facade_mock
+            .expect_exec()
+            .with(
+                predicate::eq(instance_name),
+                predicate::eq(vec!["sh".to_string()]),
+            )
+            .once()
+            .return_once(|_, _| {
+                Box::pin(async { Err(RuntimeError::Exec("exec failed".to_string())) })
+            });
+
+        let (mut to_server, mut runtime_manager, _) = RuntimeManagerBuilder::default()
+            .with_runtime(
+                RUNTIME_NAME,
+                Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+            )
+            .build();
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_1_NAME.to_string(), MockWorkload::default());
+
+        runtime_manager
+            .handle_exec_request(
+                REQUEST_ID.to_string(),
+                ExecRequest::Start(ExecStart {
+                    workload_name: WORKLOAD_1_NAME.to_string(),
+                    command: vec!["sh".to_string()],
+                }),
+            )
+            .await;
+
+        assert_error_response(&mut to_server, "exec failed");
+        assert!(runtime_manager.exec_sessions.is_empty());
+    }
+
+    // [utest->swdd~agent-handles-exec-requests~1]
+    #[tokio::test]
+    async fn utest_handle_exec_request_input_without_session() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let (mut to_server, mut runtime_manager, _) = RuntimeManagerBuilder::default().build();
+
+        runtime_manager
+            .handle_exec_request(
+                REQUEST_ID.to_string(),
+                ExecRequest::Input(ExecInput {
+                    stdin: b"ls".to_vec(),
+                    close_stdin: false,
+                }),
+            )
+            .await;
+
+        assert_error_response(&mut to_server, "No running exec session");
+    }
+
+    // [utest->swdd~agent-handles-port-forward-requests~2]
+    #[tokio::test]
+    async fn utest_handle_port_forward_request_start_connects_to_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let workload_listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let workload_port = workload_listener.local_addr().unwrap().port();
+
+        let instance_name = WorkloadInstanceName::builder()
+            .workload_name(WORKLOAD_1_NAME)
+            .agent_name(AGENT_NAME)
+            .config(&"config".to_string())
+            .build();
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        let reusable_state =
+            ReusableWorkloadState::new(instance_name.clone(), ExecutionState::running(), None);
+        runtime_facade_mock
+            .expect_get_reusable_workloads()
+            .once()
+            .return_once(|_| Box::pin(async { Ok(vec![reusable_state]) }));
+        runtime_facade_mock
+            .expect_get_workload_address()
+            .with(predicate::eq(instance_name))
+            .once()
+            .return_once(|_| Box::pin(async { Ok("127.0.0.1".parse().unwrap()) }));
+
+        let (mut to_server, mut runtime_manager, _) = RuntimeManagerBuilder::default()
+            .with_runtime(
+                RUNTIME_NAME,
+                Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+            )
+            .build();
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_1_NAME.to_string(), MockWorkload::default());
+
+        runtime_manager
+            .handle_port_forward_request(
+                REQUEST_ID.to_string(),
+                PortForwardRequest::Start(PortForwardStart {
+                    workload_name: WORKLOAD_1_NAME.to_string(),
+                    port: workload_port as u32,
+                }),
+            )
+            .await;
+
+        assert!(workload_listener.accept().await.is_ok());
+        assert!(runtime_manager
+            .port_forward_sessions
+            .contains_key(REQUEST_ID));
+        assert!(to_server.try_recv().is_err());
+    }
+
+    // [utest->swdd~agent-handles-port-forward-requests~2]
+    #[tokio::test]
+    async fn utest_handle_port_forward_request_start_unknown_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        runtime_facade_mock.expect_get_workload_address().never();
+
+        let (mut to_server, mut runtime_manager, _) = RuntimeManagerBuilder::default()
+            .with_runtime(
+                RUNTIME_NAME,
+                Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+            )
+            .build();
+
+        runtime_manager
+            .handle_port_forward_request(
+                REQUEST_ID.to_string(),
+                PortForwardRequest::Start(PortForwardStart {
+                    workload_name: WORKLOAD_1_NAME.to_string(),
+                    port: 8080,
+                }),
+            )
+            .await;
+
+        assert_error_response(
+            &mut to_server,
+            "Workload 'workload1' is not running on agent 'agent_x'",
+        );
+        assert!(runtime_manager.port_forward_sessions.is_empty());
+    }
+
+    // [utest->swdd~agent-handles-port-forward-requests~2]
+    #[tokio::test]
+    async fn utest_handle_port_forward_request_data_without_session() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let (mut to_server, mut runtime_manager, _) = RuntimeManagerBuilder::default().build();
+
+        runtime_manager
+            .handle_port_forward_request(
+                REQUEST_ID.to_string(),
+                PortForwardRequest::Data(PortForwardData {
+                    data: b"GET /".to_vec(),
+                    close: false,
+                }),
+            )
+            .await;
+
+        assert_error_response(&mut to_server, "No open port forwarding");
+    }
+
+    // [utest->swdd~agent-handles-restart-workload-requests~1]
+    #[tokio::test]
+    async fn utest_handle_restart_workload_request_restarts_workloads() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let mut workload_mock_1 = MockWorkload::default();
+        workload_mock_1
+            .expect_restart()
+            .once()
+            .return_once(|| Ok(()));
+        let mut workload_mock_2 = MockWorkload::default();
+        workload_mock_2.expect_restart().never();
+
+        let (mut to_server, mut runtime_manager, _) = RuntimeManagerBuilder::default().build();
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_1_NAME.to_string(), workload_mock_1);
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_2_NAME.to_string(), workload_mock_2);
+
+        runtime_manager
+            .handle_restart_workload_request(
+                REQUEST_ID.to_string(),
+                vec![WORKLOAD_1_NAME.to_string()],
+            )
+            .await;
+
+        let Ok(ToServer::Response(response)) = to_server.try_recv() else {
+            panic!("Expected a response");
+        };
+        assert_eq!(response.request_id, REQUEST_ID);
+        assert_eq!(
+            response.response_content,
+            Some(ResponseContent::RestartWorkloadSuccess(
+                ank_base::RestartWorkloadSuccess {
+                    restarted_workloads: vec![WORKLOAD_1_NAME.to_string()]
+                }
+            ))
+        );
+    }
+
+    // [utest->swdd~agent-restarts-workloads-restarting-on-restarted-workload~1]
+    #[tokio::test]
+    async fn utest_handle_restart_workload_request_restarts_workloads_restarting_on_it() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let mut workload_mock_1 = MockWorkload::default();
+        workload_mock_1
+            .expect_restart()
+            .once()
+            .return_once(|| Ok(()));
+        let mut workload_mock_2 = MockWorkload::default();
+        workload_mock_2
+            .expect_restart()
+            .once()
+            .return_once(|| Ok(()));
+
+        let (mut to_server, mut runtime_manager, _) = RuntimeManagerBuilder::default().build();
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_1_NAME.to_string(), workload_mock_1);
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_2_NAME.to_string(), workload_mock_2);
+        runtime_manager
+            .workload_restart_on
+            .insert(WORKLOAD_1_NAME.to_string(), vec![]);
+        runtime_manager.workload_restart_on.insert(
+            WORKLOAD_2_NAME.to_string(),
+            vec![WORKLOAD_1_NAME.to_string()],
+        );
+
+        runtime_manager
+            .handle_restart_workload_request(
+                REQUEST_ID.to_string(),
+                vec![WORKLOAD_1_NAME.to_string()],
+            )
+            .await;
+
+        let Ok(ToServer::Response(response)) = to_server.try_recv() else {
+            panic!("Expected a response");
+        };
+        assert_eq!(
+            response.response_content,
+            Some(ResponseContent::RestartWorkloadSuccess(
+                ank_base::RestartWorkloadSuccess {
+                    restarted_workloads: vec![
+                        WORKLOAD_1_NAME.to_string(),
+                        WORKLOAD_2_NAME.to_string()
+                    ]
+                }
+            ))
+        );
+    }
+
+    // [utest->swdd~agent-handles-restart-workload-requests~1]
+    #[tokio::test]
+    async fn utest_handle_restart_workload_request_unknown_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let (mut to_server, mut runtime_manager, _) = RuntimeManagerBuilder::default().build();
+
+        runtime_manager
+            .handle_restart_workload_request(
+                REQUEST_ID.to_string(),
+                vec![WORKLOAD_1_NAME.to_string()],
+            )
+            .await;
+
+        assert_error_response(
+            &mut to_server,
+            "Workload 'workload1' is not running on agent 'agent_x'",
+        );
+    }
+
+    // [utest->swdd~agent-handles-pause-workload-requests~1]
+    #[tokio::test]
+    async fn utest_handle_pause_workload_request_pauses_and_resumes_workloads() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let mut workload_mock = MockWorkload::default();
+        workload_mock.expect_pause().once().return_once(|| Ok(()));
+        workload_mock.expect_unpause().once().return_once(|| Ok(()));
+
+        let (mut to_server, mut runtime_manager, _) = RuntimeManagerBuilder::default().build();
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_1_NAME.to_string(), workload_mock);
+
+        for resume in [false, true] {
+            runtime_manager
+                .handle_pause_workload_request(
+                    REQUEST_ID.to_string(),
+                    vec![WORKLOAD_1_NAME.to_string()],
+                    resume,
+                )
+                .await;
+
+            let Ok(ToServer::Response(response)) = to_server.try_recv() else {
+                panic!("Expected a response");
+            };
+            assert_eq!(response.request_id, REQUEST_ID);
+            assert_eq!(
+                response.response_content,
+                Some(ResponseContent::PauseWorkloadSuccess(
+                    ank_base::PauseWorkloadSuccess {
+                        workloads: vec![WORKLOAD_1_NAME.to_string()]
+                    }
+                ))
+            );
+        }
+    }
+
+    // [utest->swdd~agent-handles-pause-workload-requests~1]
+    #[tokio::test]
+    async fn utest_handle_pause_workload_request_unknown_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let (mut to_server, mut runtime_manager, _) = RuntimeManagerBuilder::default().build();
+
+        runtime_manager
+            .handle_pause_workload_request(
+                REQUEST_ID.to_string(),
+                vec![WORKLOAD_1_NAME.to_string()],
+                false,
+            )
+            .await;
+
+        assert_error_response(
+            &mut to_server,
+            "Workload 'workload1' is not running on agent 'agent_x'",
+        );
+    }
+
+    // [utest->swdd~agent-deletes-all-workloads-on-shutdown~1]
+    #[tokio::test]
+    async fn utest_delete_all_workloads() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let mut workload_mock_1 = MockWorkload::default();
+        workload_mock_1
+            .expect_delete()
+            .once()
+            .return_once(|_| Ok(()));
+
+        let mut workload_mock_2 = MockWorkload::default();
+        workload_mock_2
+            .expect_delete()
+            .once()
+            .return_once(|_| Err(WorkloadError::Communication("channel closed".to_string())));
+
+        let (_, mut runtime_manager, _) = RuntimeManagerBuilder::default().build();
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_1_NAME.to_string(), workload_mock_1);
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_2_NAME.to_string(), workload_mock_2);
+        runtime_manager
+            .workload_ports
+            .insert(WORKLOAD_1_NAME.to_string(), vec![]);
+
+        assert_eq!(
+            runtime_manager.delete_all_workloads().await,
+            vec![WORKLOAD_1_NAME.to_string()]
+        );
+        assert!(runtime_manager.workloads.is_empty());
+        assert!(runtime_manager.workload_ports.is_empty());
+    }
+
+    // [utest->swdd~agent-evicts-lowest-priority-workload~1]
+    #[tokio::test]
+    async fn utest_evict_lowest_priority_running_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let mut workload_mock_1 = MockWorkload::default();
+        workload_mock_1.expect_evict().never();
+
+        let mut workload_mock_2 = MockWorkload::default();
+        workload_mock_2
+            .expect_evict()
+            .with(predicate::eq(EvictionReason::MemoryPressure))
+            .once()
+            .return_once(|_| Ok(()));
+
+        // the workload with the lowest priority is not running and cannot be evicted
+        let mut workload_mock_3 = MockWorkload::default();
+        workload_mock_3.expect_evict().never();
+
+        let (_, mut runtime_manager, _) = RuntimeManagerBuilder::default().build();
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_1_NAME.to_string(), workload_mock_1);
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_2_NAME.to_string(), workload_mock_2);
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_3_NAME.to_string(), workload_mock_3);
+        runtime_manager
+            .workload_priorities
+            .insert(WORKLOAD_1_NAME.to_string(), 10);
+        runtime_manager
+            .workload_priorities
+            .insert(WORKLOAD_2_NAME.to_string(), 5);
+
+        let mut workload_state_store_mock = MockWorkloadStateStore::default();
+        workload_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_1_NAME.to_string(), ExecutionState::running());
+        workload_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_2_NAME.to_string(), ExecutionState::running());
+        workload_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_3_NAME.to_string(), ExecutionState::succeeded());
+
+        assert_eq!(
+            runtime_manager
+                .evict_lowest_priority_workload(&workload_state_store_mock)
+                .await,
+            Some(WORKLOAD_2_NAME.to_string())
+        );
+    }
+
+    // [utest->swdd~agent-evicts-lowest-priority-workload~1]
+    #[tokio::test]
+    async fn utest_evict_lowest_priority_workload_nothing_running() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let mut workload_mock_1 = MockWorkload::default();
+        workload_mock_1.expect_evict().never();
+
+        let (_, mut runtime_manager, _) = RuntimeManagerBuilder::default().build();
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_1_NAME.to_string(), workload_mock_1);
+
+        let mut workload_state_store_mock = MockWorkloadStateStore::default();
+        workload_state_store_mock.states_storage.insert(
+            WORKLOAD_1_NAME.to_string(),
+            ExecutionState::evicted("some info"),
+        );
 
-    #[derive(Default)]
-    pub struct RuntimeManagerBuilder {
-        runtime_facade_map: HashMap<String, Box<dyn RuntimeFacade>>,
+        assert!(runtime_manager
+            .evict_lowest_priority_workload(&workload_state_store_mock)
+            .await
+            .is_none());
     }
 
-    impl RuntimeManagerBuilder {
-        pub fn with_runtime(
-            mut self,
-            runtime_name: &str,
-            runtime_facade: Box<dyn RuntimeFacade>,
-        ) -> Self {
-            self.runtime_facade_map
-                .insert(runtime_name.to_string(), runtime_facade);
-            self
+    // [utest->swdd~agent-enforces-agent-disconnect-policies~1]
+    #[tokio::test]
+    async fn utest_stop_workloads_on_disconnect_according_to_policies() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let mut workload_mock_1 = MockWorkload::default();
+        workload_mock_1
+            .expect_evict()
+            .with(predicate::eq(EvictionReason::AgentDisconnect))
+            .once()
+            .return_once(|_| Ok(()));
+
+        // the stop delay of the policy is not yet over
+        let mut workload_mock_2 = MockWorkload::default();
+        workload_mock_2.expect_evict().never();
+
+        // the workload is kept running without a policy
+        let mut workload_mock_3 = MockWorkload::default();
+        workload_mock_3.expect_evict().never();
+
+        let (_, mut runtime_manager, _) = RuntimeManagerBuilder::default().build();
+        for (workload_name, workload_mock, policy) in [
+            (
+                WORKLOAD_1_NAME,
+                workload_mock_1,
+                AgentDisconnectPolicy::Stop,
+            ),
+            (
+                WORKLOAD_2_NAME,
+                workload_mock_2,
+                AgentDisconnectPolicy::StopAfter(10),
+            ),
+            (
+                WORKLOAD_3_NAME,
+                workload_mock_3,
+                AgentDisconnectPolicy::Keep,
+            ),
+        ] {
+            runtime_manager
+                .workloads
+                .insert(workload_name.to_string(), workload_mock);
+            runtime_manager
+                .workload_disconnect_policies
+                .insert(workload_name.to_string(), policy);
         }
 
-        pub fn build(self) -> (ToServerReceiver, RuntimeManager, WorkloadStateReceiver) {
-            let (to_server, server_receiver) = channel(BUFFER_SIZE);
-            let (wl_state_sender, wl_state_receiver) = channel(BUFFER_SIZE);
-            let runtime_manager = RuntimeManager::new(
-                AGENT_NAME.into(),
-                Path::new(RUN_FOLDER).into(),
-                to_server.clone(),
-                self.runtime_facade_map,
-                wl_state_sender.clone(),
-            );
-            (server_receiver, runtime_manager, wl_state_receiver)
+        let mut workload_state_store_mock = MockWorkloadStateStore::default();
+        for workload_name in [WORKLOAD_1_NAME, WORKLOAD_2_NAME, WORKLOAD_3_NAME] {
+            workload_state_store_mock
+                .states_storage
+                .insert(workload_name.to_string(), ExecutionState::running());
         }
+
+        assert_eq!(
+            runtime_manager
+                .stop_workloads_on_disconnect(Duration::from_secs(4), &workload_state_store_mock)
+                .await,
+            Some(Duration::from_secs(6))
+        );
+    }
+
+    // [utest->swdd~agent-restores-highest-priority-workload~1]
+    #[tokio::test]
+    async fn utest_restore_highest_priority_evicted_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let mut workload_mock_1 = MockWorkload::default();
+        workload_mock_1.expect_restore().never();
+
+        let mut workload_mock_2 = MockWorkload::default();
+        workload_mock_2
+            .expect_restore()
+            .once()
+            .return_once(|| Ok(()));
+
+        // the running workload with the highest priority is not restored
+        let mut workload_mock_3 = MockWorkload::default();
+        workload_mock_3.expect_restore().never();
+
+        let (_, mut runtime_manager, _) = RuntimeManagerBuilder::default().build();
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_1_NAME.to_string(), workload_mock_1);
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_2_NAME.to_string(), workload_mock_2);
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_3_NAME.to_string(), workload_mock_3);
+        runtime_manager
+            .workload_priorities
+            .insert(WORKLOAD_2_NAME.to_string(), 5);
+        runtime_manager
+            .workload_priorities
+            .insert(WORKLOAD_3_NAME.to_string(), 10);
+
+        let mut workload_state_store_mock = MockWorkloadStateStore::default();
+        workload_state_store_mock.states_storage.insert(
+            WORKLOAD_1_NAME.to_string(),
+            ExecutionState::evicted("some info"),
+        );
+        workload_state_store_mock.states_storage.insert(
+            WORKLOAD_2_NAME.to_string(),
+            ExecutionState::evicted("some info"),
+        );
+        workload_state_store_mock
+            .states_storage
+            .insert(WORKLOAD_3_NAME.to_string(), ExecutionState::running());
+
+        assert_eq!(
+            runtime_manager
+                .restore_highest_priority_workload(&workload_state_store_mock)
+                .await,
+            Some(WORKLOAD_2_NAME.to_string())
+        );
     }
 
     // [utest->swdd~agent-initial-list-existing-workloads~1]
@@ -666,7 +2051,7 @@ mod tests {
         control_interface_info_mock
             .expect()
             .times(1)
-            .returning(|_, _, _, _| MockControlInterfaceInfo::default());
+            .returning(|_, _, _, _, _| MockControlInterfaceInfo::default());
 
         let new_workload_access = generate_test_workload_spec_with_control_interface_access(
             AGENT_NAME.to_string(),
@@ -751,7 +2136,7 @@ mod tests {
         control_interface_info_mock
             .expect()
             .once()
-            .return_once(|_, _, _, _| MockControlInterfaceInfo::default());
+            .return_once(|_, _, _, _, _| MockControlInterfaceInfo::default());
 
         let workload_with_unknown_runtime =
             generate_test_workload_spec_with_control_interface_access(
@@ -812,7 +2197,7 @@ mod tests {
         control_interface_info_mock
             .expect()
             .once()
-            .return_once(|_, _, _, _| MockControlInterfaceInfo::default());
+            .return_once(|_, _, _, _, _| MockControlInterfaceInfo::default());
 
         let workload = generate_test_workload_spec_with_control_interface_access(
             AGENT_NAME.to_string(),
@@ -909,7 +2294,7 @@ mod tests {
         control_interface_info_new_context
             .expect()
             .once()
-            .return_once(|_, _, _, _| MockControlInterfaceInfo::default());
+            .return_once(|_, _, _, _, _| MockControlInterfaceInfo::default());
         let workload_spec_no_access = generate_test_workload_spec_with_param(
             AGENT_NAME.to_string(),
             WORKLOAD_1_NAME.to_string(),
@@ -944,7 +2329,7 @@ mod tests {
         control_interface_info_new_context
             .expect()
             .once()
-            .returning(move |_, _, _, _| MockControlInterfaceInfo::default());
+            .returning(move |_, _, _, _, _| MockControlInterfaceInfo::default());
 
         let workload_operations = vec![];
         let mut mock_workload_scheduler = MockWorkloadScheduler::default();
@@ -1315,7 +2700,7 @@ mod tests {
         control_interface_info_new_context
             .expect()
             .once()
-            .return_once(|_, _, _, _| MockControlInterfaceInfo::default());
+            .return_once(|_, _, _, _, _| MockControlInterfaceInfo::default());
         let workload_spec_no_access = generate_test_workload_spec_with_param(
             AGENT_NAME.to_string(),
             WORKLOAD_1_NAME.to_string(),
@@ -1429,7 +2814,7 @@ mod tests {
         control_interface_info_mock
             .expect()
             .once()
-            .return_once(|_, _, _, _| MockControlInterfaceInfo::default());
+            .return_once(|_, _, _, _, _| MockControlInterfaceInfo::default());
 
         let old_workload =
             generate_test_deleted_workload(AGENT_NAME.to_string(), WORKLOAD_1_NAME.to_string());
@@ -1511,7 +2896,7 @@ mod tests {
         control_interface_info_mock
             .expect()
             .once()
-            .return_once(|_, _, _, _| MockControlInterfaceInfo::default());
+            .return_once(|_, _, _, _, _| MockControlInterfaceInfo::default());
 
         let new_workload = generate_test_workload_spec_with_control_interface_access(
             AGENT_NAME.to_string(),
@@ -1545,7 +2930,7 @@ mod tests {
             .expect_delete()
             .once()
             .in_sequence(&mut delete_before_add_seq)
-            .return_once(move || Ok(()));
+            .return_once(move |_| Ok(()));
 
         let mut runtime_facade_mock = MockRuntimeFacade::new();
         runtime_facade_mock
@@ -1603,7 +2988,7 @@ mod tests {
         control_interface_info_mock
             .expect()
             .once()
-            .return_once(|_, _, _, _| MockControlInterfaceInfo::default());
+            .return_once(|_, _, _, _, _| MockControlInterfaceInfo::default());
 
         let new_workload = generate_test_workload_spec_with_control_interface_access(
             AGENT_NAME.to_string(),
@@ -1675,7 +3060,7 @@ mod tests {
         control_interface_info_mock
             .expect()
             .once()
-            .return_once(|_, _, _, _| MockControlInterfaceInfo::default());
+            .return_once(|_, _, _, _, _| MockControlInterfaceInfo::default());
 
         let old_workload = generate_test_deleted_workload_with_dependencies(
             AGENT_NAME.to_owned(),
@@ -1749,7 +3134,7 @@ mod tests {
         control_interface_info_mock
             .expect()
             .once()
-            .return_once(|_, _, _, _| MockControlInterfaceInfo::default());
+            .return_once(|_, _, _, _, _| MockControlInterfaceInfo::default());
 
         let new_workload = generate_test_workload_spec_with_control_interface_access(
             AGENT_NAME.to_string(),
@@ -2000,7 +3385,7 @@ mod tests {
         let expected_response = ank_base::Response {
             request_id,
             response_content: Some(ank_base::response::ResponseContent::CompleteState(
-                complete_state.clone(),
+                Box::new(complete_state.clone()),
             )),
         };
         let mut mock_workload = MockWorkload::default();
@@ -2018,7 +3403,7 @@ mod tests {
             .forward_response(ank_base::Response {
                 request_id: format!("{WORKLOAD_1_NAME}@{REQUEST_ID}"),
                 response_content: Some(ank_base::response::ResponseContent::CompleteState(
-                    complete_state,
+                    Box::new(complete_state),
                 )),
             })
             .await;
@@ -2073,7 +3458,26 @@ mod tests {
                                 runtime_config: Some("generalOptions: [\"--version\"]\ncommandOptions: [\"--network=host\"]\nimage: alpine:latest\ncommandArgs: [\"bash\"]\n".to_string()),
                                 control_interface_access: None,
                                 configs: Some(ank_base::ConfigMappings {
-                                    configs: Default::default()})
+                                    configs: Default::default()}),
+                                hostname: None,
+                                extra_hosts: Some(Default::default()),
+                                ports: Some(Default::default()),
+                                resource_thresholds: None,
+                                resources: None,
+                                health_check: None,
+                                restart_backoff: None,
+                                priority: None,
+                                volumes: Some(Default::default()),
+                                registry_auth: None,
+                                replicas: None,
+                                networks: Some(Default::default()),
+                                env: Some(Default::default()),
+                                on_agent_disconnect: None,
+                                restart_on: Some(Default::default()),
+                                lifecycle_hooks: None,
+                                node_selector: Some(Default::default()),
+                                state_debounce: None,
+                                security_context: None,
                             })];
         let mut complete_state = test_utils::generate_test_proto_complete_state(&workloads);
         complete_state.workload_states = Some(ank_base::WorkloadStatesMap {
@@ -2105,13 +3509,21 @@ mod tests {
                 objects::AgentAttributes {
                     cpu_usage: Some(objects::CpuUsage { cpu_usage: 42 }),
                     free_memory: Some(objects::FreeMemory { free_memory: 42 }),
+                    disk_usage: None,
+                    network_usage: None,
+                    temperature: None,
+                    labels: HashMap::new(),
+                    version: String::new(),
+                    runtimes: Vec::new(),
+                    connected_at: 0,
+                    load_history: Vec::new(),
                 }
                 .into(),
             )]),
         });
         let expected_response = ank_base::Response {
             request_id,
-            response_content: Some(ResponseContent::CompleteState(complete_state)),
+            response_content: Some(ResponseContent::CompleteState(Box::new(complete_state))),
         };
         let mut mock_workload = MockWorkload::default();
         mock_workload
@@ -2131,14 +3543,14 @@ mod tests {
         runtime_manager
             .forward_response(ank_base::Response {
                 request_id: format!("{WORKLOAD_1_NAME}@{REQUEST_ID}"),
-                response_content: Some(ResponseContent::CompleteState(
+                response_content: Some(ResponseContent::CompleteState(Box::new(
                     generate_test_complete_state(vec![generate_test_workload_spec_with_param(
                         AGENT_NAME.to_string(),
                         WORKLOAD_1_NAME.to_string(),
                         RUNTIME_NAME.to_string(),
                     )])
                     .into(),
-                )),
+                ))),
             })
             .await;
     }
@@ -2173,12 +3585,14 @@ mod tests {
             .forward_response(ank_base::Response {
                 request_id: format!("{WORKLOAD_1_NAME}@{REQUEST_ID}"),
                 response_content: Some(ank_base::response::ResponseContent::CompleteState(
-                    generate_test_complete_state(vec![generate_test_workload_spec_with_param(
-                        AGENT_NAME.to_string(),
-                        WORKLOAD_1_NAME.to_string(),
-                        RUNTIME_NAME.to_string(),
-                    )])
-                    .into(),
+                    Box::new(
+                        generate_test_complete_state(vec![generate_test_workload_spec_with_param(
+                            AGENT_NAME.to_string(),
+                            WORKLOAD_1_NAME.to_string(),
+                            RUNTIME_NAME.to_string(),
+                        )])
+                        .into(),
+                    ),
                 )),
             })
             .await;
@@ -2196,7 +3610,7 @@ mod tests {
         control_interface_info_mock
             .expect()
             .once()
-            .return_once(|_, _, _, _| MockControlInterfaceInfo::default());
+            .return_once(|_, _, _, _, _| MockControlInterfaceInfo::default());
 
         let mut workload_spec = generate_test_workload_spec_with_dependencies(
             AGENT_NAME,
@@ -2284,6 +3698,7 @@ mod tests {
     }
 
     // [utest->swdd~agent-handles-workloads-with-fulfilled-dependencies~1]
+    // [utest->swdd~agent-deletes-workload-with-grace-period~1]
     #[tokio::test]
     async fn utest_update_workload_state_delete_workload_dependencies_with_fulfilled_dependencies()
     {
@@ -2291,8 +3706,9 @@ mod tests {
             .get_lock_async()
             .await;
 
-        let deleted_workload =
+        let mut deleted_workload =
             generate_test_deleted_workload(AGENT_NAME.to_owned(), WORKLOAD_1_NAME.to_owned());
+        deleted_workload.grace_period_secs = Some(30);
 
         let next_workload_operations = vec![WorkloadOperation::Delete(deleted_workload)];
 
@@ -2314,8 +3730,9 @@ mod tests {
         let mut workload_mock = MockWorkload::default();
         workload_mock
             .expect_delete()
+            .with(predicate::eq(Some(30)))
             .once()
-            .return_once(move || Ok(()));
+            .return_once(move |_| Ok(()));
 
         runtime_manager
             .workloads
@@ -2393,6 +3810,7 @@ mod tests {
             .delete_workload(DeletedWorkload {
                 instance_name,
                 dependencies: HashMap::new(),
+                grace_period_secs: None,
             })
             .await;
         server_receiver.close();
@@ -2525,7 +3943,7 @@ mod tests {
         control_interface_info_mock
             .expect()
             .once()
-            .return_once(|_, _, _, _| MockControlInterfaceInfo::default());
+            .return_once(|_, _, _, _, _| MockControlInterfaceInfo::default());
 
         let mut runtime_facade_mock = MockRuntimeFacade::new();
         runtime_facade_mock
@@ -2575,7 +3993,7 @@ mod tests {
         workload_mock
             .expect_delete()
             .once()
-            .return_once(move || Ok(()));
+            .return_once(move |_| Ok(()));
 
         runtime_manager
             .workloads
@@ -2666,6 +4084,245 @@ mod tests {
             .await;
     }
 
+    // [utest->swdd~agent-detects-host-port-conflicts~1]
+    #[tokio::test]
+    async fn utest_add_workload_with_conflicting_host_port_reports_starting_failed() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        runtime_facade_mock.expect_create_workload().never();
+
+        let (_server_receiver, mut runtime_manager, mut wl_state_receiver) =
+            RuntimeManagerBuilder::default()
+                .with_runtime(
+                    RUNTIME_NAME,
+                    Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+                )
+                .build();
+
+        runtime_manager
+            .workload_ports
+            .insert(WORKLOAD_2_NAME.to_string(), vec![generate_test_port(8080)]);
+
+        let mut new_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        new_workload.ports = vec![generate_test_port(8080)];
+
+        runtime_manager
+            .execute_workload_operations(vec![WorkloadOperation::Create(
+                ReusableWorkloadSpec::new(new_workload.clone(), None),
+            )])
+            .await;
+
+        assert!(!runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
+        assert!(!runtime_manager.workload_ports.contains_key(WORKLOAD_1_NAME));
+        assert_eq!(
+            wl_state_receiver.recv().await,
+            Some(WorkloadState {
+                instance_name: new_workload.instance_name,
+                execution_state: ExecutionState::starting_failed(format!(
+                    "Host port '8080/tcp' is already used by workload '{}'",
+                    WORKLOAD_2_NAME
+                )),
+            })
+        );
+    }
+
+    // [utest->swdd~agent-detects-host-port-conflicts~1]
+    #[tokio::test]
+    async fn utest_add_workload_with_free_host_port_stores_ports() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        runtime_facade_mock
+            .expect_create_workload()
+            .once()
+            .return_once(|_, _, _| MockWorkload::default());
+
+        let (_server_receiver, mut runtime_manager, _wl_state_receiver) =
+            RuntimeManagerBuilder::default()
+                .with_runtime(
+                    RUNTIME_NAME,
+                    Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+                )
+                .build();
+
+        let mut used_udp_port = generate_test_port(8080);
+        used_udp_port.protocol = PortProtocol::Udp;
+        runtime_manager
+            .workload_ports
+            .insert(WORKLOAD_2_NAME.to_string(), vec![used_udp_port]);
+
+        let mut new_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        new_workload.ports = vec![generate_test_port(8080)];
+
+        runtime_manager
+            .execute_workload_operations(vec![WorkloadOperation::Create(
+                ReusableWorkloadSpec::new(new_workload, None),
+            )])
+            .await;
+
+        assert!(runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
+        assert_eq!(
+            runtime_manager.workload_ports.get(WORKLOAD_1_NAME),
+            Some(&vec![generate_test_port(8080)])
+        );
+    }
+
+    // [utest->swdd~agent-detects-host-port-conflicts~1]
+    #[tokio::test]
+    async fn utest_update_workload_with_conflicting_host_port_deletes_old_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let (_server_receiver, mut runtime_manager, mut wl_state_receiver) =
+            RuntimeManagerBuilder::default().build();
+
+        let mut workload_mock = MockWorkload::default();
+        workload_mock.expect_update().never();
+        workload_mock
+            .expect_delete()
+            .once()
+            .return_once(move |_| Ok(()));
+
+        runtime_manager
+            .workloads
+            .insert(WORKLOAD_1_NAME.to_string(), workload_mock);
+        runtime_manager
+            .workload_ports
+            .insert(WORKLOAD_1_NAME.to_string(), vec![]);
+        runtime_manager
+            .workload_ports
+            .insert(WORKLOAD_2_NAME.to_string(), vec![generate_test_port(8080)]);
+
+        let mut new_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        new_workload.ports = vec![generate_test_port(8080)];
+        let deleted_workload =
+            generate_test_deleted_workload(AGENT_NAME.to_owned(), WORKLOAD_1_NAME.to_owned());
+
+        runtime_manager
+            .execute_workload_operations(vec![WorkloadOperation::Update(
+                new_workload.clone(),
+                deleted_workload,
+            )])
+            .await;
+
+        assert!(!runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
+        assert!(!runtime_manager.workload_ports.contains_key(WORKLOAD_1_NAME));
+        assert_eq!(
+            wl_state_receiver.recv().await,
+            Some(WorkloadState {
+                instance_name: new_workload.instance_name,
+                execution_state: ExecutionState::starting_failed(format!(
+                    "Host port '8080/tcp' is already used by workload '{}'",
+                    WORKLOAD_2_NAME
+                )),
+            })
+        );
+    }
+
+    // [utest->swdd~agent-checks-host-path-volumes~1]
+    #[tokio::test]
+    async fn utest_add_workload_with_missing_host_path_reports_starting_failed() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mock_workload_scheduler_context = MockWorkloadScheduler::new_context();
+        mock_workload_scheduler_context
+            .expect()
+            .once()
+            .return_once(|_| MockWorkloadScheduler::default());
+
+        let mut runtime_facade_mock = MockRuntimeFacade::new();
+        runtime_facade_mock.expect_create_workload().never();
+
+        let (_server_receiver, mut runtime_manager, mut wl_state_receiver) =
+            RuntimeManagerBuilder::default()
+                .with_runtime(
+                    RUNTIME_NAME,
+                    Box::new(runtime_facade_mock) as Box<dyn RuntimeFacade>,
+                )
+                .build();
+
+        let mut new_workload = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_owned(),
+            WORKLOAD_1_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        new_workload.volumes = vec![
+            Volume {
+                source: std::env::temp_dir().to_string_lossy().into_owned(),
+                mount_path: "/tmp".into(),
+                ..Default::default()
+            },
+            Volume {
+                source: "/not/existing/path".into(),
+                mount_path: "/data".into(),
+                ..Default::default()
+            },
+        ];
+
+        runtime_manager
+            .execute_workload_operations(vec![WorkloadOperation::Create(
+                ReusableWorkloadSpec::new(new_workload.clone(), None),
+            )])
+            .await;
+
+        assert!(!runtime_manager.workloads.contains_key(WORKLOAD_1_NAME));
+        assert_eq!(
+            wl_state_receiver.recv().await,
+            Some(WorkloadState {
+                instance_name: new_workload.instance_name,
+                execution_state: ExecutionState::starting_failed(
+                    "Host path '/not/existing/path' of the volume mounted to '/data' does not exist"
+                ),
+            })
+        );
+    }
+
+    fn generate_test_port(host_port: u16) -> Port {
+        Port {
+            host_port,
+            container_port: 80,
+            protocol: PortProtocol::Tcp,
+        }
+    }
+
     fn setup_from_authorizer() -> Box<dyn Any> {
         let authorizer_from_context_mock = MockAuthorizer::from_context();
         authorizer_from_context_mock
@@ -0,0 +1,331 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    env, fs,
+    path::{Component, Path, PathBuf},
+};
+
+use common::objects::{replace_secret_placeholders, SecretProvider, SecretRef, WorkloadSpec};
+
+const CREDENTIALS_DIRECTORY: &str = "CREDENTIALS_DIRECTORY";
+
+/// Resolves the secret placeholders of a workload with the secret providers of the agent
+#[derive(Debug, Default, Clone)]
+pub struct SecretResolver {
+    secrets_directory: Option<PathBuf>,
+    allowed_env_prefixes: Vec<String>,
+}
+
+impl SecretResolver {
+    pub fn new(secrets_directory: Option<PathBuf>, allowed_env_prefixes: Vec<String>) -> Self {
+        SecretResolver {
+            secrets_directory,
+            allowed_env_prefixes,
+        }
+    }
+
+    // Only variables with an allowed prefix can be read, as the environment of the agent can contain credentials.
+    fn is_env_allowed(&self, variable_name: &str) -> bool {
+        self.allowed_env_prefixes
+            .iter()
+            .any(|prefix| !prefix.is_empty() && variable_name.starts_with(prefix.as_str()))
+    }
+
+    // The secrets are resolved on every creation of the workload and are never sent back to the server.
+    // [impl->swdd~agent-resolves-secret-placeholders~2]
+    pub fn resolve_secrets(&self, workload_spec: WorkloadSpec) -> Result<WorkloadSpec, String> {
+        self.resolve_secrets_with(workload_spec, |name| env::var(name).ok())
+    }
+
+    fn resolve_secrets_with(
+        &self,
+        mut workload_spec: WorkloadSpec,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<WorkloadSpec, String> {
+        let read_secret = |secret_ref: &SecretRef| self.read_secret(secret_ref, &lookup);
+        workload_spec.runtime_config =
+            replace_secret_placeholders(&workload_spec.runtime_config, read_secret)?;
+        workload_spec.registry_auth = workload_spec
+            .registry_auth
+            .map(|registry_auth| replace_secret_placeholders(&registry_auth, read_secret))
+            .transpose()?;
+        for value in workload_spec.env.values_mut() {
+            *value = replace_secret_placeholders(value, read_secret)?;
+        }
+        Ok(workload_spec)
+    }
+
+    // [impl->swdd~agent-reads-secrets-from-providers~3]
+    fn read_secret(
+        &self,
+        secret_ref: &SecretRef,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<String, String> {
+        match secret_ref.provider {
+            SecretProvider::File => {
+                let secrets_directory = self.secrets_directory.as_ref().ok_or_else(|| {
+                    format!(
+                        "Could not read the secret file '{}', the agent has no secrets directory configured",
+                        secret_ref.name
+                    )
+                })?;
+                let is_relative_without_parent = Path::new(&secret_ref.name)
+                    .components()
+                    .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+                if secret_ref.name.is_empty() || !is_relative_without_parent {
+                    return Err(format!(
+                        "The secret file name '{}' must be a relative path inside the secrets directory",
+                        secret_ref.name
+                    ));
+                }
+                read_secret_file(&secrets_directory.join(&secret_ref.name))
+            }
+            SecretProvider::Env => {
+                if !self.is_env_allowed(&secret_ref.name) {
+                    return Err(format!(
+                        "Reading the secret from environment variable '{}' is not allowed",
+                        secret_ref.name
+                    ));
+                }
+                lookup(&secret_ref.name).ok_or_else(|| {
+                    format!(
+                        "Could not read the secret from environment variable '{}'",
+                        secret_ref.name
+                    )
+                })
+            }
+            SecretProvider::SystemdCreds => {
+                if secret_ref.name.contains('/') {
+                    return Err(format!(
+                        "The systemd credential name '{}' must not contain '/'",
+                        secret_ref.name
+                    ));
+                }
+                let credentials_directory = lookup(CREDENTIALS_DIRECTORY).ok_or_else(|| {
+                    format!(
+                        "Could not read the systemd credential '{}', the agent has no '{}'",
+                        secret_ref.name, CREDENTIALS_DIRECTORY
+                    )
+                })?;
+                read_secret_file(&Path::new(&credentials_directory).join(&secret_ref.name))
+            }
+        }
+    }
+}
+
+fn read_secret_file(path: &Path) -> Result<String, String> {
+    let secret = fs::read_to_string(path).map_err(|err| {
+        format!(
+            "Could not read the secret file '{}': '{}'",
+            path.display(),
+            err
+        )
+    })?;
+    // files written with an editor or 'echo' usually end with a line break not part of the secret
+    Ok(secret.trim_end_matches(['\r', '\n']).to_owned())
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, fs};
+
+    use common::objects::generate_test_workload_spec_with_param;
+
+    use super::SecretResolver;
+
+    const AGENT_NAME: &str = "agent_A";
+    const WORKLOAD_NAME: &str = "workload_1";
+    const RUNTIME_NAME: &str = "podman";
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "DB_PASSWORD" => Some("env_secret".to_owned()),
+            _ => None,
+        }
+    }
+
+    fn allowed_env() -> Vec<String> {
+        vec!["DB_".to_owned()]
+    }
+
+    fn workload_spec_with_runtime_config(runtime_config: &str) -> common::objects::WorkloadSpec {
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+        workload_spec.runtime_config = runtime_config.to_string();
+        workload_spec
+    }
+
+    // [utest->swdd~agent-resolves-secret-placeholders~2]
+    // [utest->swdd~agent-reads-secrets-from-providers~3]
+    #[test]
+    fn utest_resolve_secrets_from_file_and_environment() {
+        let secrets_directory = tempfile::tempdir().unwrap();
+        fs::create_dir(secrets_directory.path().join("db")).unwrap();
+        fs::write(
+            secrets_directory.path().join("db/password"),
+            "file_secret\n",
+        )
+        .unwrap();
+
+        let mut workload_spec =
+            workload_spec_with_runtime_config("password: ${ankaios-secret:file:db/password}");
+        workload_spec.registry_auth =
+            Some(r#"{"auth": "${ankaios-secret:env:DB_PASSWORD}"}"#.to_string());
+        workload_spec.env = HashMap::from([(
+            "PASSWORD".to_string(),
+            "${ankaios-secret:env:DB_PASSWORD}".to_string(),
+        )]);
+        let instance_name = workload_spec.instance_name.clone();
+
+        let resolved_workload_spec =
+            SecretResolver::new(Some(secrets_directory.path().to_path_buf()), allowed_env())
+                .resolve_secrets_with(workload_spec, lookup)
+                .unwrap();
+
+        assert_eq!(
+            resolved_workload_spec.runtime_config,
+            "password: file_secret"
+        );
+        assert_eq!(
+            resolved_workload_spec.registry_auth,
+            Some(r#"{"auth": "env_secret"}"#.to_string())
+        );
+        assert_eq!(
+            resolved_workload_spec.env,
+            HashMap::from([("PASSWORD".to_string(), "env_secret".to_string())])
+        );
+        assert_eq!(resolved_workload_spec.instance_name, instance_name);
+    }
+
+    // [utest->swdd~agent-reads-secrets-from-providers~3]
+    #[test]
+    fn utest_resolve_secrets_from_systemd_credentials() {
+        let credentials_directory = tempfile::tempdir().unwrap();
+        fs::write(
+            credentials_directory.path().join("db_password"),
+            "cred_secret\n",
+        )
+        .unwrap();
+        let credentials_directory_path = credentials_directory.path().display().to_string();
+
+        let resolved_workload_spec = SecretResolver::default()
+            .resolve_secrets_with(
+                workload_spec_with_runtime_config(
+                    "password: ${ankaios-secret:systemd-creds:db_password}",
+                ),
+                |name| {
+                    (name == "CREDENTIALS_DIRECTORY").then(|| credentials_directory_path.clone())
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            resolved_workload_spec.runtime_config,
+            "password: cred_secret"
+        );
+    }
+
+    // [utest->swdd~agent-reads-secrets-from-providers~3]
+    #[test]
+    fn utest_resolve_secrets_fails_on_missing_secret() {
+        let secrets_directory = tempfile::tempdir().unwrap();
+        let secret_resolver =
+            SecretResolver::new(Some(secrets_directory.path().to_path_buf()), allowed_env());
+
+        for runtime_config in [
+            "password: ${ankaios-secret:env:NOT_EXISTING}",
+            "password: ${ankaios-secret:file:not_existing}",
+            "password: ${ankaios-secret:systemd-creds:db_password}",
+        ] {
+            assert!(secret_resolver
+                .resolve_secrets_with(workload_spec_with_runtime_config(runtime_config), lookup)
+                .is_err());
+        }
+    }
+
+    // [utest->swdd~agent-reads-secrets-from-providers~3]
+    #[test]
+    fn utest_resolve_secrets_rejects_paths_outside_of_secrets_directory() {
+        let base_directory = tempfile::tempdir().unwrap();
+        let secrets_directory = base_directory.path().join("secrets");
+        fs::create_dir(&secrets_directory).unwrap();
+        fs::write(base_directory.path().join("outside"), "outside_secret").unwrap();
+        let secret_resolver = SecretResolver::new(Some(secrets_directory), allowed_env());
+        let outside_path = base_directory.path().join("outside").display().to_string();
+
+        for secret_name in ["../outside", "db/../../outside", outside_path.as_str()] {
+            let result = secret_resolver.resolve_secrets_with(
+                workload_spec_with_runtime_config(&format!(
+                    "password: ${{ankaios-secret:file:{secret_name}}}"
+                )),
+                lookup,
+            );
+            assert!(result
+                .unwrap_err()
+                .contains("must be a relative path inside the secrets directory"));
+        }
+
+        assert!(secret_resolver
+            .resolve_secrets_with(
+                workload_spec_with_runtime_config(
+                    "password: ${ankaios-secret:systemd-creds:../outside}"
+                ),
+                lookup,
+            )
+            .is_err());
+    }
+
+    // [utest->swdd~agent-reads-secrets-from-providers~3]
+    #[test]
+    fn utest_resolve_secrets_rejects_file_secrets_without_secrets_directory() {
+        let result = SecretResolver::default().resolve_secrets_with(
+            workload_spec_with_runtime_config("password: ${ankaios-secret:file:db/password}"),
+            lookup,
+        );
+
+        assert!(result
+            .unwrap_err()
+            .contains("the agent has no secrets directory configured"));
+    }
+
+    // [utest->swdd~agent-reads-secrets-from-providers~3]
+    #[test]
+    fn utest_resolve_secrets_rejects_env_secrets_without_allowed_prefix() {
+        let workload_spec =
+            workload_spec_with_runtime_config("password: ${ankaios-secret:env:DB_PASSWORD}");
+
+        let result = SecretResolver::new(None, vec!["APP_".to_owned()])
+            .resolve_secrets_with(workload_spec.clone(), lookup);
+        assert!(result
+            .unwrap_err()
+            .contains("Reading the secret from environment variable 'DB_PASSWORD' is not allowed"));
+
+        let result = SecretResolver::default().resolve_secrets_with(workload_spec, lookup);
+        assert!(result
+            .unwrap_err()
+            .contains("Reading the secret from environment variable 'DB_PASSWORD' is not allowed"));
+    }
+}
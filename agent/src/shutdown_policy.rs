@@ -0,0 +1,114 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fmt::Display, str::FromStr, time::Duration};
+
+const KEEP_RUNNING: &str = "keep-running";
+const STOP_WORKLOADS: &str = "stop-workloads";
+const STOP_WITH_TIMEOUT: &str = "stop-with-timeout";
+
+// [impl->swdd~agent-supports-shutdown-policies~1]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownPolicy {
+    #[default]
+    KeepRunning,
+    StopWorkloads,
+    StopWithTimeout(Duration),
+}
+
+impl FromStr for ShutdownPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            KEEP_RUNNING => Ok(ShutdownPolicy::KeepRunning),
+            STOP_WORKLOADS => Ok(ShutdownPolicy::StopWorkloads),
+            _ => value
+                .strip_prefix(STOP_WITH_TIMEOUT)
+                .and_then(|timeout| timeout.strip_prefix('='))
+                .and_then(|timeout| timeout.parse::<u64>().ok())
+                .map(|timeout| ShutdownPolicy::StopWithTimeout(Duration::from_secs(timeout)))
+                .ok_or_else(|| {
+                    format!(
+                        "Shutdown policy '{}' is invalid. Supported policies are '{}', '{}' and '{}=<seconds>'.",
+                        value, KEEP_RUNNING, STOP_WORKLOADS, STOP_WITH_TIMEOUT
+                    )
+                }),
+        }
+    }
+}
+
+impl Display for ShutdownPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShutdownPolicy::KeepRunning => write!(f, "{}", KEEP_RUNNING),
+            ShutdownPolicy::StopWorkloads => write!(f, "{}", STOP_WORKLOADS),
+            ShutdownPolicy::StopWithTimeout(timeout) => {
+                write!(f, "{}={}", STOP_WITH_TIMEOUT, timeout.as_secs())
+            }
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::ShutdownPolicy;
+    use std::time::Duration;
+
+    // [utest->swdd~agent-supports-shutdown-policies~1]
+    #[test]
+    fn utest_shutdown_policy_from_str() {
+        assert_eq!(
+            "keep-running".parse::<ShutdownPolicy>(),
+            Ok(ShutdownPolicy::KeepRunning)
+        );
+        assert_eq!(
+            "stop-workloads".parse::<ShutdownPolicy>(),
+            Ok(ShutdownPolicy::StopWorkloads)
+        );
+        assert_eq!(
+            "stop-with-timeout=30".parse::<ShutdownPolicy>(),
+            Ok(ShutdownPolicy::StopWithTimeout(Duration::from_secs(30)))
+        );
+    }
+
+    // [utest->swdd~agent-supports-shutdown-policies~1]
+    #[test]
+    fn utest_shutdown_policy_from_str_fails_on_invalid_policy() {
+        assert!("stop".parse::<ShutdownPolicy>().is_err());
+        assert!("stop-with-timeout".parse::<ShutdownPolicy>().is_err());
+        assert!("stop-with-timeout=".parse::<ShutdownPolicy>().is_err());
+        assert!("stop-with-timeout=-1".parse::<ShutdownPolicy>().is_err());
+        assert!("stop-with-timeout30".parse::<ShutdownPolicy>().is_err());
+    }
+
+    // [utest->swdd~agent-supports-shutdown-policies~1]
+    #[test]
+    fn utest_shutdown_policy_display() {
+        assert_eq!(ShutdownPolicy::KeepRunning.to_string(), "keep-running");
+        assert_eq!(ShutdownPolicy::StopWorkloads.to_string(), "stop-workloads");
+        assert_eq!(
+            ShutdownPolicy::StopWithTimeout(Duration::from_secs(5)).to_string(),
+            "stop-with-timeout=5"
+        );
+    }
+}
@@ -14,11 +14,13 @@
 
 // mod exports
 pub mod control_loop_state;
+pub mod operation_limiter;
 pub mod workload_command_channel;
 pub mod workload_control_loop;
 
 // public api exports
 pub use control_loop_state::ControlLoopState;
+pub use operation_limiter::OperationLimiter;
 pub use workload_command_channel::WorkloadCommandSender;
 #[cfg(test)]
 pub use workload_control_loop::MockWorkloadControlLoop;
@@ -61,11 +63,22 @@ impl Display for WorkloadError {
 
 #[derive(Debug, PartialEq)]
 pub enum WorkloadCommand {
-    Delete,
+    Delete(Option<u32>),
     Update(Option<Box<WorkloadSpec>>, Option<PathBuf>),
     Retry(Box<WorkloadInstanceName>),
     Create,
     Resume,
+    Evict(EvictionReason),
+    Restore,
+    Restart,
+    Pause,
+    Unpause,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionReason {
+    MemoryPressure,
+    AgentDisconnect,
 }
 
 pub struct Workload {
@@ -106,9 +119,15 @@ impl Workload {
             let run_folder = info.get_run_folder().clone();
             let output_pipe_sender = info.get_to_server_sender();
             let instance_name = info.get_instance_name().clone();
+            let pipes_owner = info.get_pipes_owner();
             let authorizer = info.move_authorizer();
-            match ControlInterface::new(&run_folder, &instance_name, output_pipe_sender, authorizer)
-            {
+            match ControlInterface::new(
+                &run_folder,
+                &instance_name,
+                output_pipe_sender,
+                authorizer,
+                pipes_owner,
+            ) {
                 Ok(control_interface) => Some(control_interface),
                 Err(err) => {
                     log::warn!("Could not exchange control interface. Error: '{}'", err);
@@ -118,7 +137,7 @@ impl Workload {
         });
     }
 
-    // [impl->swdd~agent-compares-control-interface-metadata~2]
+    // [impl->swdd~agent-compares-control-interface-metadata~4]
     fn is_control_interface_changed(
         &self,
         control_interface_info: &Option<ControlInterfaceInfo>,
@@ -142,8 +161,8 @@ impl Workload {
             // [impl->swdd~agent-control-interface-created-for-eligible-workloads~1]
             self.exchange_control_interface(
                 control_interface_info,
-                spec.clone()
-                    .map_or(false, |spec| !spec.needs_control_interface()),
+                spec.as_ref()
+                    .is_some_and(|spec| !spec.needs_control_interface()),
             );
         }
 
@@ -160,7 +179,7 @@ impl Workload {
     }
 
     // [impl->swdd~agent-workload-obj-delete-command~1]
-    pub async fn delete(self) -> Result<(), WorkloadError> {
+    pub async fn delete(self, grace_period_secs: Option<u32>) -> Result<(), WorkloadError> {
         log::info!("Deleting workload '{}'.", self.name);
 
         if let Some(control_interface) = self.control_interface {
@@ -168,7 +187,57 @@ impl Workload {
         }
 
         self.channel
-            .delete()
+            .delete(grace_period_secs)
+            .await
+            .map_err(|err| WorkloadError::Communication(err.to_string()))
+    }
+
+    // [impl->swdd~agent-workload-obj-evict-command~2]
+    pub async fn evict(&self, reason: EvictionReason) -> Result<(), WorkloadError> {
+        log::info!("Evicting workload '{}'.", self.name);
+
+        self.channel
+            .evict(reason)
+            .await
+            .map_err(|err| WorkloadError::Communication(err.to_string()))
+    }
+
+    // [impl->swdd~agent-workload-obj-evict-command~2]
+    pub async fn restore(&self) -> Result<(), WorkloadError> {
+        log::info!("Restoring evicted workload '{}'.", self.name);
+
+        self.channel
+            .restore()
+            .await
+            .map_err(|err| WorkloadError::Communication(err.to_string()))
+    }
+
+    // [impl->swdd~agent-workload-obj-restart-command~1]
+    pub async fn restart(&self) -> Result<(), WorkloadError> {
+        log::info!("Restarting workload '{}'.", self.name);
+
+        self.channel
+            .restart()
+            .await
+            .map_err(|err| WorkloadError::Communication(err.to_string()))
+    }
+
+    // [impl->swdd~agent-workload-obj-pause-command~1]
+    pub async fn pause(&self) -> Result<(), WorkloadError> {
+        log::info!("Pausing workload '{}'.", self.name);
+
+        self.channel
+            .pause()
+            .await
+            .map_err(|err| WorkloadError::Communication(err.to_string()))
+    }
+
+    // [impl->swdd~agent-workload-obj-pause-command~1]
+    pub async fn unpause(&self) -> Result<(), WorkloadError> {
+        log::info!("Resuming paused workload '{}'.", self.name);
+
+        self.channel
+            .unpause()
             .await
             .map_err(|err| WorkloadError::Communication(err.to_string()))
     }
@@ -221,7 +290,9 @@ mod tests {
             authorizer::MockAuthorizer, control_interface_info::MockControlInterfaceInfo,
             MockControlInterface,
         },
-        workload::{Workload, WorkloadCommand, WorkloadCommandSender, WorkloadError},
+        workload::{
+            EvictionReason, Workload, WorkloadCommand, WorkloadCommandSender, WorkloadError,
+        },
     };
 
     const RUNTIME_NAME: &str = "runtime1";
@@ -258,12 +329,73 @@ mod tests {
         );
 
         assert!(matches!(
-            test_workload.delete().await,
+            test_workload.delete(None).await,
             Err(WorkloadError::Communication(_))
         ));
     }
 
-    // [utest->swdd~agent-compares-control-interface-metadata~2]
+    // [utest->swdd~agent-workload-obj-evict-command~2]
+    #[tokio::test]
+    async fn utest_workload_obj_evict_and_restore() {
+        let (workload_command_sender, mut workload_command_receiver) = WorkloadCommandSender::new();
+
+        let test_workload =
+            Workload::new(WORKLOAD_1_NAME.to_string(), workload_command_sender, None);
+
+        assert!(test_workload
+            .evict(EvictionReason::AgentDisconnect)
+            .await
+            .is_ok());
+        assert!(test_workload.restore().await.is_ok());
+
+        assert_eq!(
+            Some(WorkloadCommand::Evict(EvictionReason::AgentDisconnect)),
+            workload_command_receiver.recv().await
+        );
+        assert_eq!(
+            Some(WorkloadCommand::Restore),
+            workload_command_receiver.recv().await
+        );
+    }
+
+    // [utest->swdd~agent-workload-obj-restart-command~1]
+    #[tokio::test]
+    async fn utest_workload_obj_restart() {
+        let (workload_command_sender, mut workload_command_receiver) = WorkloadCommandSender::new();
+
+        let test_workload =
+            Workload::new(WORKLOAD_1_NAME.to_string(), workload_command_sender, None);
+
+        assert!(test_workload.restart().await.is_ok());
+
+        assert_eq!(
+            Some(WorkloadCommand::Restart),
+            workload_command_receiver.recv().await
+        );
+    }
+
+    // [utest->swdd~agent-workload-obj-pause-command~1]
+    #[tokio::test]
+    async fn utest_workload_obj_pause_and_unpause() {
+        let (workload_command_sender, mut workload_command_receiver) = WorkloadCommandSender::new();
+
+        let test_workload =
+            Workload::new(WORKLOAD_1_NAME.to_string(), workload_command_sender, None);
+
+        assert!(test_workload.pause().await.is_ok());
+        assert!(test_workload.unpause().await.is_ok());
+
+        assert_eq!(
+            Some(WorkloadCommand::Pause),
+            workload_command_receiver.recv().await
+        );
+        assert_eq!(
+            Some(WorkloadCommand::Unpause),
+            workload_command_receiver.recv().await
+        );
+    }
+
+    // [utest->swdd~agent-compares-control-interface-metadata~4]
     #[test]
     fn utest_is_control_interface_changed_set_from_none_to_new_returns_true() {
         let (workload_command_sender, _) = WorkloadCommandSender::new();
@@ -276,7 +408,7 @@ mod tests {
             .is_control_interface_changed(&Some(MockControlInterfaceInfo::default())));
     }
 
-    // [utest->swdd~agent-compares-control-interface-metadata~2]
+    // [utest->swdd~agent-compares-control-interface-metadata~4]
     #[test]
     fn utest_is_control_interface_changed_set_from_existing_to_none_returns_true() {
         let (workload_command_sender, _) = WorkloadCommandSender::new();
@@ -290,7 +422,7 @@ mod tests {
         assert!(test_workload_with_control_interface.is_control_interface_changed(&None));
     }
 
-    // [utest->swdd~agent-compares-control-interface-metadata~2]
+    // [utest->swdd~agent-compares-control-interface-metadata~4]
     #[test]
     fn utest_is_control_interface_changed_set_from_none_to_none_returns_false() {
         let (workload_command_sender, _) = WorkloadCommandSender::new();
@@ -304,7 +436,7 @@ mod tests {
         assert!(!test_workload_with_control_interface.is_control_interface_changed(&None));
     }
 
-    // [utest->swdd~agent-compares-control-interface-metadata~2]
+    // [utest->swdd~agent-compares-control-interface-metadata~4]
     #[test]
     fn utest_is_control_interface_changed_returns_true() {
         let (workload_command_sender, _) = WorkloadCommandSender::new();
@@ -325,7 +457,7 @@ mod tests {
             .is_control_interface_changed(&Some(control_interface_info_mock)));
     }
 
-    // [utest->swdd~agent-compares-control-interface-metadata~2]
+    // [utest->swdd~agent-compares-control-interface-metadata~4]
     #[test]
     fn utest_is_control_interface_changed_returns_false() {
         let (workload_command_sender, _) = WorkloadCommandSender::new();
@@ -396,7 +528,7 @@ mod tests {
         new_control_interface_context
             .expect()
             .once()
-            .return_once(|_, _, _, _| Ok(new_control_interface_mock));
+            .return_once(|_, _, _, _, _| Ok(new_control_interface_mock));
 
         let mut new_control_interface_info_mock = MockControlInterfaceInfo::default();
         new_control_interface_info_mock
@@ -414,6 +546,10 @@ mod tests {
             .once()
             .return_const(workload_spec.instance_name.clone());
 
+        new_control_interface_info_mock
+            .expect_get_pipes_owner()
+            .once()
+            .return_const(None);
         new_control_interface_info_mock
             .expect_move_authorizer()
             .once()
@@ -495,6 +631,10 @@ mod tests {
             .once()
             .return_const(workload_spec.instance_name.clone());
 
+        new_control_interface_info_mock
+            .expect_get_pipes_owner()
+            .once()
+            .return_const(None);
         new_control_interface_info_mock
             .expect_move_authorizer()
             .once()
@@ -509,7 +649,7 @@ mod tests {
         control_interface_new_context
             .expect()
             .once()
-            .return_once(|_, _, _, _| Ok(new_control_interface_mock));
+            .return_once(|_, _, _, _, _| Ok(new_control_interface_mock));
 
         let mut test_workload = Workload::new(
             WORKLOAD_1_NAME.to_string(),
@@ -549,11 +689,11 @@ mod tests {
             Some(old_control_interface_mock),
         );
 
-        test_workload.delete().await.unwrap();
+        test_workload.delete(None).await.unwrap();
 
         assert!(matches!(
             timeout(Duration::from_millis(200), workload_command_receiver.recv()).await,
-            Ok(Some(WorkloadCommand::Delete))
+            Ok(Some(WorkloadCommand::Delete(None)))
         ));
     }
 
@@ -589,7 +729,7 @@ mod tests {
             .forward_response(ank_base::Response {
                 request_id: REQUEST_ID.to_owned(),
                 response_content: Some(ank_base::response::ResponseContent::CompleteState(
-                    complete_state.clone().into(),
+                    Box::new(complete_state.clone().into()),
                 )),
             })
             .await
@@ -600,7 +740,7 @@ mod tests {
         assert!(matches!(
             timeout(Duration::from_millis(200), to_server_rx.recv()).await,
             Ok(Some(FromServer::Response(Response{request_id: _, response_content: Some(ResponseContent::CompleteState(complete_state))})))
-        if ank_base::CompleteState::from(expected_complete_state) == complete_state));
+        if ank_base::CompleteState::from(expected_complete_state) == *complete_state));
     }
 
     // [utest->swdd~agent-forward-responses-to-control-interface-pipe~1]
@@ -633,7 +773,7 @@ mod tests {
                 .forward_response(ank_base::Response {
                     request_id: REQUEST_ID.to_owned(),
                     response_content: Some(ank_base::response::ResponseContent::CompleteState(
-                        complete_state.clone().into(),
+                        Box::new(complete_state.clone().into())
                     )),
                 })
                 .await,
@@ -659,7 +799,7 @@ mod tests {
                 .forward_response(ank_base::Response {
                     request_id: REQUEST_ID.to_owned(),
                     response_content: Some(ank_base::response::ResponseContent::CompleteState(
-                        complete_state.clone().into(),
+                        Box::new(complete_state.clone().into())
                     )),
                 })
                 .await,
@@ -12,8 +12,10 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 use crate::runtime_connectors::{RuntimeConnector, StateChecker};
+use crate::secret_resolver::SecretResolver;
 use crate::workload::workload_command_channel::{WorkloadCommandReceiver, WorkloadCommandSender};
 use crate::workload::workload_control_loop::RetryCounter;
+use crate::workload::OperationLimiter;
 use crate::workload_state::{WorkloadStateReceiver, WorkloadStateSender};
 use crate::BUFFER_SIZE;
 use common::objects::{WorkloadInstanceName, WorkloadSpec, WorkloadState};
@@ -36,6 +38,12 @@ where
     pub command_receiver: WorkloadCommandReceiver,
     pub retry_sender: WorkloadCommandSender,
     pub retry_counter: RetryCounter,
+    pub operation_limiter: OperationLimiter,
+    pub secret_resolver: SecretResolver,
+    // the workload was stopped on memory pressure and waits to be restored
+    pub evicted: bool,
+    // the workload is paused on the runtime and waits to be resumed
+    pub paused: bool,
 }
 
 impl<WorkloadId, StChecker> ControlLoopState<WorkloadId, StChecker>
@@ -64,7 +72,18 @@ where
     runtime: Option<Box<dyn RuntimeConnector<WorkloadId, StChecker>>>,
     workload_command_receiver: Option<WorkloadCommandReceiver>,
     retry_sender: Option<WorkloadCommandSender>,
-    retry_counter: RetryCounter,
+    operation_limiter: OperationLimiter,
+    secret_resolver: SecretResolver,
+}
+
+impl<WorkloadId, StChecker> Default for ControlLoopStateBuilder<WorkloadId, StChecker>
+where
+    WorkloadId: ToString + FromStr + Clone + Send + Sync + 'static,
+    StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<WorkloadId, StChecker> ControlLoopStateBuilder<WorkloadId, StChecker>
@@ -81,7 +100,8 @@ where
             runtime: None,
             workload_command_receiver: None,
             retry_sender: None,
-            retry_counter: RetryCounter::new(),
+            operation_limiter: OperationLimiter::default(),
+            secret_resolver: SecretResolver::default(),
         }
     }
 
@@ -120,15 +140,29 @@ where
         self
     }
 
+    pub fn operation_limiter(mut self, operation_limiter: OperationLimiter) -> Self {
+        self.operation_limiter = operation_limiter;
+        self
+    }
+
+    pub fn secret_resolver(mut self, secret_resolver: SecretResolver) -> Self {
+        self.secret_resolver = secret_resolver;
+        self
+    }
+
     pub fn build(self) -> Result<ControlLoopState<WorkloadId, StChecker>, String> {
         // new channel for receiving the workload states from the state checker
         let (state_checker_wl_state_sender, state_checker_wl_state_receiver) =
             tokio::sync::mpsc::channel::<WorkloadState>(BUFFER_SIZE);
 
+        let workload_spec = self
+            .workload_spec
+            .ok_or_else(|| "WorkloadSpec is not set".to_string())?;
+        // [impl->swdd~agent-workload-control-loop-applies-restart-backoff~1]
+        let retry_counter = RetryCounter::new(workload_spec.restart_backoff.clone());
+
         Ok(ControlLoopState {
-            workload_spec: self
-                .workload_spec
-                .ok_or_else(|| "WorkloadSpec is not set".to_string())?,
+            workload_spec,
             control_interface_path: self.control_interface_path,
             workload_id: self.workload_id,
             state_checker: None,
@@ -146,7 +180,11 @@ where
             retry_sender: self
                 .retry_sender
                 .ok_or_else(|| "WorkloadCommandSender is not set".to_string())?,
-            retry_counter: self.retry_counter,
+            retry_counter,
+            operation_limiter: self.operation_limiter,
+            secret_resolver: self.secret_resolver,
+            evicted: false,
+            paused: false,
         })
     }
 }
@@ -164,8 +202,10 @@ mod tests {
     use super::ControlLoopState;
     use crate::{
         runtime_connectors::test::{MockRuntimeConnector, StubStateChecker},
+        secret_resolver::SecretResolver,
         workload::{
             workload_command_channel::WorkloadCommandSender, workload_control_loop::RetryCounter,
+            OperationLimiter,
         },
         workload_state::WorkloadStateSenderInterface,
     };
@@ -288,7 +328,11 @@ mod tests {
             runtime,
             command_receiver: workload_command_receiver,
             retry_sender,
-            retry_counter: RetryCounter::new(),
+            retry_counter: RetryCounter::new(None),
+            operation_limiter: OperationLimiter::default(),
+            secret_resolver: SecretResolver::default(),
+            evicted: false,
+            paused: false,
         };
 
         assert_eq!(
@@ -0,0 +1,91 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+// Limits the number of create and delete operations executed on the runtimes in parallel.
+// The limiter is shared by the control loops of all workloads of the agent. Waiting operations
+// are executed in the order they were requested, so the order given by the workload scheduler is kept.
+// [impl->swdd~agent-limits-parallel-workload-operations~1]
+#[derive(Debug, Clone, Default)]
+pub struct OperationLimiter {
+    semaphore: Option<Arc<Semaphore>>,
+}
+
+impl OperationLimiter {
+    // No limit is applied if the maximum number of parallel operations is not set or 0
+    pub fn new(max_parallel_operations: Option<usize>) -> Self {
+        OperationLimiter {
+            semaphore: max_parallel_operations
+                .filter(|max| *max > 0)
+                .map(|max| Arc::new(Semaphore::new(max))),
+        }
+    }
+
+    // The returned permit must be held until the operation is finished
+    pub async fn acquire(&self) -> Option<OwnedSemaphorePermit> {
+        match &self.semaphore {
+            // The semaphore is never closed, thus acquiring only fails if it is dropped
+            Some(semaphore) => semaphore.clone().acquire_owned().await.ok(),
+            None => None,
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::OperationLimiter;
+    use std::time::Duration;
+    use tokio::time::timeout;
+
+    const WAIT_TIMEOUT: Duration = Duration::from_millis(50);
+
+    // [utest->swdd~agent-limits-parallel-workload-operations~1]
+    #[tokio::test]
+    async fn utest_operation_limiter_blocks_operations_above_the_limit() {
+        let limiter = OperationLimiter::new(Some(2));
+
+        let first_permit = limiter.acquire().await;
+        let second_permit = limiter.acquire().await;
+        assert!(first_permit.is_some());
+        assert!(second_permit.is_some());
+
+        assert!(timeout(WAIT_TIMEOUT, limiter.acquire()).await.is_err());
+
+        drop(first_permit);
+        assert!(timeout(WAIT_TIMEOUT, limiter.acquire())
+            .await
+            .is_ok_and(|permit| permit.is_some()));
+    }
+
+    // [utest->swdd~agent-limits-parallel-workload-operations~1]
+    #[tokio::test]
+    async fn utest_operation_limiter_without_limit_never_blocks() {
+        for limiter in [OperationLimiter::new(None), OperationLimiter::new(Some(0))] {
+            let _permits = [limiter.acquire().await, limiter.acquire().await];
+
+            assert!(timeout(WAIT_TIMEOUT, limiter.acquire())
+                .await
+                .is_ok_and(|permit| permit.is_none()));
+        }
+    }
+}
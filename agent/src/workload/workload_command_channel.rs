@@ -11,7 +11,7 @@
 // under the License.
 //
 // SPDX-License-Identifier: Apache-2.0
-use crate::workload::WorkloadCommand;
+use crate::workload::{EvictionReason, WorkloadCommand};
 use common::objects::{WorkloadInstanceName, WorkloadSpec};
 use std::path::PathBuf;
 use tokio::sync::mpsc;
@@ -66,8 +66,36 @@ impl WorkloadCommandSender {
         self.sender.send(WorkloadCommand::Resume).await
     }
 
-    pub async fn delete(self) -> Result<(), mpsc::error::SendError<WorkloadCommand>> {
-        self.sender.send(WorkloadCommand::Delete).await
+    pub async fn evict(
+        &self,
+        reason: EvictionReason,
+    ) -> Result<(), mpsc::error::SendError<WorkloadCommand>> {
+        self.sender.send(WorkloadCommand::Evict(reason)).await
+    }
+
+    pub async fn restore(&self) -> Result<(), mpsc::error::SendError<WorkloadCommand>> {
+        self.sender.send(WorkloadCommand::Restore).await
+    }
+
+    pub async fn restart(&self) -> Result<(), mpsc::error::SendError<WorkloadCommand>> {
+        self.sender.send(WorkloadCommand::Restart).await
+    }
+
+    pub async fn pause(&self) -> Result<(), mpsc::error::SendError<WorkloadCommand>> {
+        self.sender.send(WorkloadCommand::Pause).await
+    }
+
+    pub async fn unpause(&self) -> Result<(), mpsc::error::SendError<WorkloadCommand>> {
+        self.sender.send(WorkloadCommand::Unpause).await
+    }
+
+    pub async fn delete(
+        self,
+        grace_period_secs: Option<u32>,
+    ) -> Result<(), mpsc::error::SendError<WorkloadCommand>> {
+        self.sender
+            .send(WorkloadCommand::Delete(grace_period_secs))
+            .await
     }
 }
 
@@ -81,7 +109,7 @@ impl WorkloadCommandSender {
 
 #[cfg(test)]
 mod tests {
-    use super::{PathBuf, WorkloadCommand, WorkloadCommandSender, WorkloadSpec};
+    use super::{EvictionReason, PathBuf, WorkloadCommand, WorkloadCommandSender, WorkloadSpec};
     use common::objects::generate_test_workload_spec;
     const PIPES_LOCATION: &str = "/some/path";
 
@@ -148,11 +176,14 @@ mod tests {
     async fn utest_send_delete() {
         let (workload_command_sender, mut workload_command_receiver) = WorkloadCommandSender::new();
 
-        workload_command_sender.delete().await.unwrap();
+        workload_command_sender.delete(Some(30)).await.unwrap();
 
         let workload_command = workload_command_receiver.recv().await.unwrap();
 
-        assert!(matches!(workload_command, WorkloadCommand::Delete));
+        assert!(matches!(
+            workload_command,
+            WorkloadCommand::Delete(Some(30))
+        ));
     }
 
     #[tokio::test]
@@ -166,6 +197,58 @@ mod tests {
         assert_eq!(Some(WorkloadCommand::Resume), workload_command);
     }
 
+    // [utest->swdd~agent-workload-obj-evict-command~2]
+    #[tokio::test]
+    async fn utest_send_evict_and_restore() {
+        let (workload_command_sender, mut workload_command_receiver) = WorkloadCommandSender::new();
+
+        workload_command_sender
+            .evict(EvictionReason::MemoryPressure)
+            .await
+            .unwrap();
+        workload_command_sender.restore().await.unwrap();
+
+        assert_eq!(
+            Some(WorkloadCommand::Evict(EvictionReason::MemoryPressure)),
+            workload_command_receiver.recv().await
+        );
+        assert_eq!(
+            Some(WorkloadCommand::Restore),
+            workload_command_receiver.recv().await
+        );
+    }
+
+    // [utest->swdd~agent-workload-obj-restart-command~1]
+    #[tokio::test]
+    async fn utest_send_restart() {
+        let (workload_command_sender, mut workload_command_receiver) = WorkloadCommandSender::new();
+
+        workload_command_sender.restart().await.unwrap();
+
+        assert_eq!(
+            Some(WorkloadCommand::Restart),
+            workload_command_receiver.recv().await
+        );
+    }
+
+    // [utest->swdd~agent-workload-obj-pause-command~1]
+    #[tokio::test]
+    async fn utest_send_pause_and_unpause() {
+        let (workload_command_sender, mut workload_command_receiver) = WorkloadCommandSender::new();
+
+        workload_command_sender.pause().await.unwrap();
+        workload_command_sender.unpause().await.unwrap();
+
+        assert_eq!(
+            Some(WorkloadCommand::Pause),
+            workload_command_receiver.recv().await
+        );
+        assert_eq!(
+            Some(WorkloadCommand::Unpause),
+            workload_command_receiver.recv().await
+        );
+    }
+
     #[tokio::test]
     async fn utest_send_resume_error() {
         let (workload_command_sender, mut workload_command_receiver) = WorkloadCommandSender::new();
@@ -12,14 +12,19 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::runtime_connectors::StateChecker;
-use crate::workload::{ControlLoopState, WorkloadCommand};
+use crate::metrics::agent_metrics;
+use crate::runtime_connectors::{RuntimeError, StateChecker};
+use crate::workload::{ControlLoopState, EvictionReason, WorkloadCommand};
 use crate::workload_state::{WorkloadStateSender, WorkloadStateSenderInterface};
-use common::objects::{ExecutionState, RestartPolicy, WorkloadInstanceName, WorkloadSpec};
+use common::objects::{
+    ExecutionState, LifecycleHook, RestartBackoff, RestartPolicy, WorkloadInstanceName,
+    WorkloadSpec,
+};
 use common::std_extensions::IllegalStateResult;
 use futures_util::Future;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::{Duration, Instant};
 
 #[cfg(not(test))]
 const MAX_RETRIES: usize = 20;
@@ -33,29 +38,41 @@ const RETRY_WAITING_TIME_MS: u64 = 1000;
 #[cfg(test)]
 const RETRY_WAITING_TIME_MS: u64 = 50;
 
+const EVICTED_MSG: &str = "Evicted on memory pressure";
+const STOPPED_ON_DISCONNECT_MSG: &str = "Stopped on lost server connection";
+
 pub struct RetryCounter {
     retry_counter: usize,
+    restart_backoff: Option<RestartBackoff>,
 }
 
 impl RetryCounter {
-    pub fn new() -> Self {
-        RetryCounter { retry_counter: 1 }
+    pub fn new(restart_backoff: Option<RestartBackoff>) -> Self {
+        RetryCounter {
+            retry_counter: 1,
+            restart_backoff,
+        }
     }
 
     pub fn reset(&mut self) {
         self.retry_counter = 1;
     }
 
+    // [impl->swdd~agent-workload-control-loop-limits-retry-attempts~2]
     pub fn limit(&self) -> usize {
-        MAX_RETRIES
+        self.restart_backoff
+            .as_ref()
+            .map_or(MAX_RETRIES, |restart_backoff| {
+                restart_backoff.max_retries() as usize
+            })
     }
 
     pub fn limit_exceeded(&self) -> bool {
-        self.retry_counter > MAX_RETRIES
+        self.retry_counter > self.limit()
     }
 
     pub fn count_retry(&mut self) {
-        if self.retry_counter <= MAX_RETRIES {
+        if self.retry_counter <= self.limit() {
             self.retry_counter += 1;
         }
     }
@@ -63,6 +80,21 @@ impl RetryCounter {
     pub fn current_retry(&self) -> usize {
         self.retry_counter
     }
+
+    // [impl->swdd~agent-workload-control-loop-requests-retries-on-failing-retry-attempt~2]
+    pub fn waiting_time(&self) -> Duration {
+        let Some(restart_backoff) = &self.restart_backoff else {
+            return Duration::from_millis(RETRY_WAITING_TIME_MS);
+        };
+
+        // The first retry is executed immediately, the delays start with the second one.
+        let delayed_retry = self.retry_counter.saturating_sub(2);
+        let delay = restart_backoff.delay(u32::try_from(delayed_retry).unwrap_or(u32::MAX));
+
+        // Equal jitter spreads the retries of workloads failing at the same time.
+        let half_delay = delay / 2;
+        half_delay + half_delay.mul_f64(rand::random::<f64>())
+    }
 }
 
 pub struct WorkloadControlLoop;
@@ -86,7 +118,8 @@ impl WorkloadControlLoop {
                         .unwrap_or_illegal_state();
 
                     // [impl->swdd~workload-control-loop-checks-workload-state-validity~1]
-                    if Self::is_same_workload(control_loop_state.instance_name(), &new_workload_state.instance_name) {
+                    // [impl->swdd~agent-workload-control-loop-executes-evict~2]
+                    if !control_loop_state.evicted && Self::is_same_workload(control_loop_state.instance_name(), &new_workload_state.instance_name) {
 
                         /* forward immediately the new workload state to the agent manager
                         to avoid delays through the restart handling */
@@ -97,8 +130,9 @@ impl WorkloadControlLoop {
                             new_workload_state.execution_state.clone(),
                         ).await;
 
-                        // [impl->swdd~workload-control-loop-restarts-workload-with-enabled-restart-policy~2]
-                        if Self::restart_policy_matches_execution_state(&control_loop_state.workload_spec.restart_policy, &new_workload_state.execution_state) {
+                        // [impl->swdd~workload-control-loop-restarts-workload-with-enabled-restart-policy~3]
+                        // [impl->swdd~agent-workload-control-loop-executes-pause~1]
+                        if !control_loop_state.paused && Self::restart_policy_matches_execution_state(&control_loop_state.workload_spec.restart_policy, &new_workload_state.execution_state) {
                             // [impl->swdd~workload-control-loop-handles-workload-restarts~2]
                             control_loop_state = Self::restart_workload_on_runtime(control_loop_state).await;
                         }
@@ -109,10 +143,10 @@ impl WorkloadControlLoop {
                 workload_command = control_loop_state.command_receiver.recv() => {
                     match workload_command {
                         // [impl->swdd~agent-workload-control-loop-executes-delete~2]
-                        Some(WorkloadCommand::Delete) => {
+                        Some(WorkloadCommand::Delete(grace_period_secs)) => {
                             log::debug!("Received WorkloadCommand::Delete.");
 
-                            if let Some(new_control_loop_state) = Self::delete_workload_on_runtime(control_loop_state).await {
+                            if let Some(new_control_loop_state) = Self::delete_workload_on_runtime(control_loop_state, grace_period_secs).await {
                                 control_loop_state = new_control_loop_state;
                             } else {
                                 // [impl->swdd~agent-workload-control-loop-prevents-retries-on-other-workload-commands~1]
@@ -164,6 +198,31 @@ impl WorkloadControlLoop {
                             log::debug!("Received WorkloadCommand::Resume.");
                             control_loop_state = Self::resume_workload_on_runtime(control_loop_state).await;
                         }
+                        // [impl->swdd~agent-workload-control-loop-executes-evict~2]
+                        Some(WorkloadCommand::Evict(reason)) => {
+                            log::debug!("Received WorkloadCommand::Evict.");
+                            control_loop_state = Self::evict_workload_on_runtime(control_loop_state, reason).await;
+                        }
+                        // [impl->swdd~agent-workload-control-loop-executes-restore~1]
+                        Some(WorkloadCommand::Restore) => {
+                            log::debug!("Received WorkloadCommand::Restore.");
+                            control_loop_state = Self::restore_workload_on_runtime(control_loop_state).await;
+                        }
+                        // [impl->swdd~agent-workload-control-loop-executes-restart~1]
+                        Some(WorkloadCommand::Restart) => {
+                            log::debug!("Received WorkloadCommand::Restart.");
+                            control_loop_state = Self::restart_workload_on_runtime(control_loop_state).await;
+                        }
+                        // [impl->swdd~agent-workload-control-loop-executes-pause~1]
+                        Some(WorkloadCommand::Pause) => {
+                            log::debug!("Received WorkloadCommand::Pause.");
+                            control_loop_state = Self::pause_workload_on_runtime(control_loop_state).await;
+                        }
+                        // [impl->swdd~agent-workload-control-loop-executes-pause~1]
+                        Some(WorkloadCommand::Unpause) => {
+                            log::debug!("Received WorkloadCommand::Unpause.");
+                            control_loop_state = Self::unpause_workload_on_runtime(control_loop_state).await;
+                        }
                         _ => {
                             log::warn!(
                                 "Could not wait for internal stop command for workload '{}'.",
@@ -229,8 +288,14 @@ impl WorkloadControlLoop {
     ) -> bool {
         match restart_policy {
             RestartPolicy::Never => false,
-            RestartPolicy::OnFailure => execution_state.is_failed(),
-            RestartPolicy::Always => execution_state.is_failed() || execution_state.is_succeeded(),
+            RestartPolicy::OnFailure => {
+                execution_state.is_failed() || execution_state.is_unhealthy()
+            }
+            RestartPolicy::Always => {
+                execution_state.is_failed()
+                    || execution_state.is_unhealthy()
+                    || execution_state.is_succeeded()
+            }
         }
     }
 
@@ -282,8 +347,10 @@ impl WorkloadControlLoop {
 
         let retry_counter: &mut RetryCounter = &mut control_loop_state.retry_counter;
         retry_counter.count_retry();
+        // [impl->swdd~agent-collects-metrics~1]
+        agent_metrics().workload_create_retried(&control_loop_state.workload_spec.runtime);
 
-        // [impl->swdd~agent-workload-control-loop-limits-retry-attempts~1]
+        // [impl->swdd~agent-workload-control-loop-limits-retry-attempts~2]
         if retry_counter.limit_exceeded() {
             log::warn!(
                 "Abort retries: reached maximum amount of retries ('{}')",
@@ -302,9 +369,10 @@ impl WorkloadControlLoop {
         }
 
         let sender = control_loop_state.retry_sender.clone();
+        let waiting_time = retry_counter.waiting_time();
         tokio::task::spawn(async move {
-            // [impl->swdd~agent-workload-control-loop-requests-retries-on-failing-retry-attempt~1]
-            tokio::time::sleep(tokio::time::Duration::from_millis(RETRY_WAITING_TIME_MS)).await;
+            // [impl->swdd~agent-workload-control-loop-requests-retries-on-failing-retry-attempt~2]
+            tokio::time::sleep(waiting_time).await;
             log::debug!("Send WorkloadCommand::Retry.");
 
             sender.retry(instance_name).await.unwrap_or_else(|err| {
@@ -328,18 +396,38 @@ impl WorkloadControlLoop {
     {
         let new_instance_name = control_loop_state.workload_spec.instance_name.clone();
 
-        match control_loop_state
-            .runtime
-            .create_workload(
-                control_loop_state.workload_spec.clone(),
-                control_loop_state.workload_id.clone(),
-                control_loop_state.control_interface_path.clone(),
-                control_loop_state
-                    .state_checker_workload_state_sender
-                    .clone(),
-            )
-            .await
+        // [impl->swdd~agent-limits-parallel-workload-operations~1]
+        let operation_permit = control_loop_state.operation_limiter.acquire().await;
+        let start_time = Instant::now();
+        // [impl->swdd~agent-resolves-secret-placeholders~2]
+        let create_result = match control_loop_state
+            .secret_resolver
+            .resolve_secrets(control_loop_state.workload_spec.clone())
         {
+            Ok(resolved_workload_spec) => {
+                control_loop_state
+                    .runtime
+                    .create_workload(
+                        resolved_workload_spec,
+                        control_loop_state.workload_id.clone(),
+                        control_loop_state.control_interface_path.clone(),
+                        control_loop_state
+                            .state_checker_workload_state_sender
+                            .clone(),
+                    )
+                    .await
+            }
+            Err(err) => Err(RuntimeError::Create(err)),
+        };
+        drop(operation_permit);
+        // [impl->swdd~agent-collects-metrics~1]
+        agent_metrics().workload_created(
+            &control_loop_state.workload_spec.runtime,
+            start_time.elapsed(),
+            create_result.is_ok(),
+        );
+
+        let create_result = match create_result {
             Ok((new_workload_id, new_state_checker)) => {
                 log::info!(
                     "Successfully created workload '{}'.",
@@ -347,35 +435,182 @@ impl WorkloadControlLoop {
                 );
                 control_loop_state.workload_id = Some(new_workload_id);
                 control_loop_state.state_checker = Some(new_state_checker);
-                control_loop_state
+                Self::execute_post_start_hook(&mut control_loop_state).await
             }
+            Err(err) => Err(err.to_string()),
+        };
+
+        match create_result {
+            Ok(()) => control_loop_state,
             Err(err) => {
                 let current_retry_counter = control_loop_state.retry_counter.current_retry();
 
                 Self::send_workload_state_to_agent(
                     &control_loop_state.to_agent_workload_state_sender,
                     &new_instance_name,
+                    // [impl->swdd~agent-workload-control-loop-applies-restart-backoff~1]
                     ExecutionState::retry_starting(
                         current_retry_counter,
-                        MAX_RETRIES,
-                        err.to_string(),
+                        control_loop_state.retry_counter.limit(),
+                        err.clone(),
                     ),
                 )
                 .await;
 
-                func_on_error(control_loop_state, new_instance_name, err.to_string()).await
+                func_on_error(control_loop_state, new_instance_name, err).await
             }
         }
     }
 
+    // A workload with a failed postStart hook is deleted again, so that the retry creates it anew.
+    // [impl->swdd~agent-workload-control-loop-executes-lifecycle-hooks~1]
+    async fn execute_post_start_hook<WorkloadId, StChecker>(
+        control_loop_state: &mut ControlLoopState<WorkloadId, StChecker>,
+    ) -> Result<(), String>
+    where
+        WorkloadId: ToString + FromStr + Clone + Send + Sync + 'static,
+        StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+    {
+        let (Some(post_start_hook), Some(workload_id)) = (
+            control_loop_state
+                .workload_spec
+                .lifecycle_hooks
+                .as_ref()
+                .and_then(|lifecycle_hooks| lifecycle_hooks.post_start.clone()),
+            control_loop_state.workload_id.clone(),
+        ) else {
+            return Ok(());
+        };
+
+        let Err(err) =
+            Self::execute_lifecycle_hook(control_loop_state, &workload_id, &post_start_hook).await
+        else {
+            return Ok(());
+        };
+
+        control_loop_state.workload_id = None;
+        if let Some(state_checker) = control_loop_state.state_checker.take() {
+            state_checker.stop_checker().await;
+        }
+        if let Err(delete_err) =
+            Self::delete_workload_with_metrics(control_loop_state, &workload_id).await
+        {
+            log::warn!(
+                "Could not delete workload '{}' after its failed postStart hook: '{}'",
+                control_loop_state.instance_name().workload_name(),
+                delete_err
+            );
+        }
+        Err(format!("The postStart hook failed: {err}"))
+    }
+
+    // A failed preStop hook does not prevent the deletion of the workload.
+    // [impl->swdd~agent-workload-control-loop-executes-lifecycle-hooks~1]
+    async fn execute_pre_stop_hook<WorkloadId, StChecker>(
+        control_loop_state: &ControlLoopState<WorkloadId, StChecker>,
+        workload_id: &WorkloadId,
+    ) where
+        WorkloadId: ToString + FromStr + Clone + Send + Sync + 'static,
+        StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+    {
+        if control_loop_state.paused {
+            return;
+        }
+        let Some(pre_stop_hook) = control_loop_state
+            .workload_spec
+            .lifecycle_hooks
+            .as_ref()
+            .and_then(|lifecycle_hooks| lifecycle_hooks.pre_stop.as_ref())
+        else {
+            return;
+        };
+
+        if let Err(err) =
+            Self::execute_lifecycle_hook(control_loop_state, workload_id, pre_stop_hook).await
+        {
+            log::warn!(
+                "The preStop hook of workload '{}' failed: '{}'",
+                control_loop_state.instance_name().workload_name(),
+                err
+            );
+        }
+    }
+
+    async fn execute_lifecycle_hook<WorkloadId, StChecker>(
+        control_loop_state: &ControlLoopState<WorkloadId, StChecker>,
+        workload_id: &WorkloadId,
+        lifecycle_hook: &LifecycleHook,
+    ) -> Result<(), String>
+    where
+        WorkloadId: ToString + FromStr + Clone + Send + Sync + 'static,
+        StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+    {
+        log::debug!(
+            "Executing the lifecycle hook '{:?}' in workload '{}'",
+            lifecycle_hook.command,
+            control_loop_state.instance_name().workload_name()
+        );
+        let hook_process = control_loop_state
+            .runtime
+            .exec(workload_id, lifecycle_hook.command.clone())
+            .await
+            .map_err(|err| err.to_string())?;
+
+        let hook_timeout = lifecycle_hook.timeout();
+        let output = tokio::time::timeout(hook_timeout, hook_process.wait_with_output())
+            .await
+            .map_err(|_| format!("timed out after {} seconds", hook_timeout.as_secs()))?
+            .map_err(|err| err.to_string())?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "exited with '{}': '{}'",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    }
+
     // [impl->swdd~agent-workload-control-loop-executes-delete~2]
     async fn delete_workload_on_runtime<WorkloadId, StChecker>(
         mut control_loop_state: ControlLoopState<WorkloadId, StChecker>,
+        grace_period_secs: Option<u32>,
     ) -> Option<ControlLoopState<WorkloadId, StChecker>>
     where
         WorkloadId: ToString + FromStr + Clone + Send + Sync + 'static,
         StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
     {
+        if let Some(workload_id) = control_loop_state.workload_id.as_ref() {
+            Self::execute_pre_stop_hook(&control_loop_state, workload_id).await;
+        }
+
+        // [impl->swdd~agent-workload-control-loop-stops-workload-within-grace-period~1]
+        if let (Some(grace_period_secs), Some(workload_id)) =
+            (grace_period_secs, control_loop_state.workload_id.as_ref())
+        {
+            Self::send_workload_state_to_agent(
+                &control_loop_state.to_agent_workload_state_sender,
+                control_loop_state.instance_name(),
+                ExecutionState::stopping_grace_period(grace_period_secs),
+            )
+            .await;
+
+            // The deletion afterwards kills the workload if it could not be stopped gracefully.
+            if let Err(err) = control_loop_state
+                .runtime
+                .stop_workload(workload_id, grace_period_secs)
+                .await
+            {
+                log::warn!(
+                    "Could not stop workload '{}' within its grace period: '{}'",
+                    control_loop_state.instance_name().workload_name(),
+                    err
+                );
+            }
+        }
+
         Self::send_workload_state_to_agent(
             &control_loop_state.to_agent_workload_state_sender,
             control_loop_state.instance_name(),
@@ -384,7 +619,8 @@ impl WorkloadControlLoop {
         .await;
 
         if let Some(old_id) = control_loop_state.workload_id.take() {
-            if let Err(err) = control_loop_state.runtime.delete_workload(&old_id).await {
+            if let Err(err) = Self::delete_workload_with_metrics(&control_loop_state, &old_id).await
+            {
                 Self::send_workload_state_to_agent(
                     &control_loop_state.to_agent_workload_state_sender,
                     control_loop_state.instance_name(),
@@ -425,6 +661,31 @@ impl WorkloadControlLoop {
         None
     }
 
+    async fn delete_workload_with_metrics<WorkloadId, StChecker>(
+        control_loop_state: &ControlLoopState<WorkloadId, StChecker>,
+        workload_id: &WorkloadId,
+    ) -> Result<(), RuntimeError>
+    where
+        WorkloadId: ToString + FromStr + Clone + Send + Sync + 'static,
+        StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+    {
+        // [impl->swdd~agent-limits-parallel-workload-operations~1]
+        let operation_permit = control_loop_state.operation_limiter.acquire().await;
+        let start_time = Instant::now();
+        let delete_result = control_loop_state
+            .runtime
+            .delete_workload(workload_id)
+            .await;
+        drop(operation_permit);
+        // [impl->swdd~agent-collects-metrics~1]
+        agent_metrics().workload_deleted(
+            &control_loop_state.workload_spec.runtime,
+            start_time.elapsed(),
+            delete_result.is_ok(),
+        );
+        delete_result
+    }
+
     // [impl->swdd~agent-workload-control-loop-executes-update~2]
     async fn update_workload_on_runtime<WorkloadId, StChecker>(
         mut control_loop_state: ControlLoopState<WorkloadId, StChecker>,
@@ -435,6 +696,14 @@ impl WorkloadControlLoop {
         WorkloadId: ToString + FromStr + Clone + Send + Sync + 'static,
         StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
     {
+        if let Some(workload_id) = control_loop_state.workload_id.as_ref() {
+            Self::execute_pre_stop_hook(&control_loop_state, workload_id).await;
+        }
+
+        // an update replaces an evicted or paused workload with a new one
+        control_loop_state.evicted = false;
+        control_loop_state.paused = false;
+
         Self::send_workload_state_to_agent(
             &control_loop_state.to_agent_workload_state_sender,
             control_loop_state.instance_name(),
@@ -443,7 +712,8 @@ impl WorkloadControlLoop {
         .await;
 
         if let Some(old_id) = control_loop_state.workload_id.take() {
-            if let Err(err) = control_loop_state.runtime.delete_workload(&old_id).await {
+            if let Err(err) = Self::delete_workload_with_metrics(&control_loop_state, &old_id).await
+            {
                 Self::send_workload_state_to_agent(
                     &control_loop_state.to_agent_workload_state_sender,
                     control_loop_state.instance_name(),
@@ -487,6 +757,9 @@ impl WorkloadControlLoop {
             // [impl->swdd~agent-workload-control-loop-update-create-failed-allows-retry~1]
             control_loop_state.workload_spec = *spec;
             control_loop_state.control_interface_path = control_interface_path;
+            // [impl->swdd~agent-workload-control-loop-applies-restart-backoff~1]
+            control_loop_state.retry_counter =
+                RetryCounter::new(control_loop_state.workload_spec.restart_backoff.clone());
 
             Self::send_workload_state_to_agent(
                 &control_loop_state.to_agent_workload_state_sender,
@@ -526,6 +799,177 @@ impl WorkloadControlLoop {
         }
     }
 
+    // [impl->swdd~agent-workload-control-loop-executes-evict~2]
+    async fn evict_workload_on_runtime<WorkloadId, StChecker>(
+        mut control_loop_state: ControlLoopState<WorkloadId, StChecker>,
+        reason: EvictionReason,
+    ) -> ControlLoopState<WorkloadId, StChecker>
+    where
+        WorkloadId: ToString + FromStr + Clone + Send + Sync + 'static,
+        StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+    {
+        let Some(old_id) = control_loop_state.workload_id.take() else {
+            log::debug!(
+                "Workload '{}' is not created, nothing to evict.",
+                control_loop_state.instance_name().workload_name()
+            );
+            return control_loop_state;
+        };
+
+        Self::execute_pre_stop_hook(&control_loop_state, &old_id).await;
+
+        Self::send_workload_state_to_agent(
+            &control_loop_state.to_agent_workload_state_sender,
+            control_loop_state.instance_name(),
+            ExecutionState::stopping_requested(),
+        )
+        .await;
+
+        if let Err(err) = Self::delete_workload_with_metrics(&control_loop_state, &old_id).await {
+            Self::send_workload_state_to_agent(
+                &control_loop_state.to_agent_workload_state_sender,
+                control_loop_state.instance_name(),
+                ExecutionState::delete_failed(err.to_string()),
+            )
+            .await;
+            log::warn!(
+                "Could not evict workload '{}': '{}'",
+                control_loop_state.instance_name().workload_name(),
+                err
+            );
+            control_loop_state.workload_id = Some(old_id);
+            return control_loop_state;
+        }
+
+        if let Some(old_checker) = control_loop_state.state_checker.take() {
+            old_checker.stop_checker().await;
+        }
+        // the states still reported by the stopped state checker must not trigger a restart
+        control_loop_state.evicted = true;
+        control_loop_state.paused = false;
+
+        let evicted_state = match reason {
+            EvictionReason::MemoryPressure => ExecutionState::evicted(EVICTED_MSG),
+            EvictionReason::AgentDisconnect => {
+                ExecutionState::stopped_on_disconnect(STOPPED_ON_DISCONNECT_MSG)
+            }
+        };
+        Self::send_workload_state_to_agent(
+            &control_loop_state.to_agent_workload_state_sender,
+            control_loop_state.instance_name(),
+            evicted_state,
+        )
+        .await;
+        control_loop_state
+    }
+
+    // [impl->swdd~agent-workload-control-loop-executes-restore~1]
+    async fn restore_workload_on_runtime<WorkloadId, StChecker>(
+        mut control_loop_state: ControlLoopState<WorkloadId, StChecker>,
+    ) -> ControlLoopState<WorkloadId, StChecker>
+    where
+        WorkloadId: ToString + FromStr + Clone + Send + Sync + 'static,
+        StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+    {
+        if !control_loop_state.evicted {
+            log::debug!(
+                "Workload '{}' is not evicted, nothing to restore.",
+                control_loop_state.instance_name().workload_name()
+            );
+            return control_loop_state;
+        }
+
+        control_loop_state.evicted = false;
+        control_loop_state.retry_counter.reset();
+
+        Self::send_workload_state_to_agent(
+            &control_loop_state.to_agent_workload_state_sender,
+            control_loop_state.instance_name(),
+            ExecutionState::starting_triggered(),
+        )
+        .await;
+
+        Self::create_workload_on_runtime(control_loop_state, Self::send_retry_for_workload).await
+    }
+
+    // [impl->swdd~agent-workload-control-loop-executes-pause~1]
+    async fn pause_workload_on_runtime<WorkloadId, StChecker>(
+        mut control_loop_state: ControlLoopState<WorkloadId, StChecker>,
+    ) -> ControlLoopState<WorkloadId, StChecker>
+    where
+        WorkloadId: ToString + FromStr + Clone + Send + Sync + 'static,
+        StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+    {
+        let Some(workload_id) = control_loop_state.workload_id.as_ref() else {
+            log::warn!(
+                "Workload '{}' is not created, nothing to pause.",
+                control_loop_state.instance_name().workload_name()
+            );
+            return control_loop_state;
+        };
+
+        if let Err(err) = control_loop_state.runtime.pause_workload(workload_id).await {
+            log::warn!(
+                "Could not pause workload '{}': '{}'",
+                control_loop_state.instance_name().workload_name(),
+                err
+            );
+            return control_loop_state;
+        }
+
+        // a paused workload is not restarted until it is resumed
+        control_loop_state.paused = true;
+        Self::send_workload_state_to_agent(
+            &control_loop_state.to_agent_workload_state_sender,
+            control_loop_state.instance_name(),
+            ExecutionState::paused(),
+        )
+        .await;
+        control_loop_state
+    }
+
+    // [impl->swdd~agent-workload-control-loop-executes-pause~1]
+    async fn unpause_workload_on_runtime<WorkloadId, StChecker>(
+        mut control_loop_state: ControlLoopState<WorkloadId, StChecker>,
+    ) -> ControlLoopState<WorkloadId, StChecker>
+    where
+        WorkloadId: ToString + FromStr + Clone + Send + Sync + 'static,
+        StChecker: StateChecker<WorkloadId> + Send + Sync + 'static,
+    {
+        let (true, Some(workload_id)) = (
+            control_loop_state.paused,
+            control_loop_state.workload_id.as_ref(),
+        ) else {
+            log::debug!(
+                "Workload '{}' is not paused, nothing to resume.",
+                control_loop_state.instance_name().workload_name()
+            );
+            return control_loop_state;
+        };
+
+        if let Err(err) = control_loop_state
+            .runtime
+            .unpause_workload(workload_id)
+            .await
+        {
+            log::warn!(
+                "Could not resume workload '{}': '{}'",
+                control_loop_state.instance_name().workload_name(),
+                err
+            );
+            return control_loop_state;
+        }
+
+        control_loop_state.paused = false;
+        Self::send_workload_state_to_agent(
+            &control_loop_state.to_agent_workload_state_sender,
+            control_loop_state.instance_name(),
+            ExecutionState::running(),
+        )
+        .await;
+        control_loop_state
+    }
+
     // [impl->swdd~agent-workload-control-loop-executes-resume~1]
     async fn resume_workload_on_runtime<WorkloadId, StChecker>(
         mut control_loop_state: ControlLoopState<WorkloadId, StChecker>,
@@ -599,12 +1043,13 @@ mockall::mock! {
 
 #[cfg(test)]
 mod tests {
-    use super::WorkloadControlLoop;
+    use super::{RetryCounter, WorkloadControlLoop};
     use std::time::Duration;
 
     use common::objects::{
         generate_test_workload_spec_with_control_interface_access,
-        generate_test_workload_spec_with_param, ExecutionState, WorkloadInstanceName,
+        generate_test_workload_spec_with_param, ExecutionState, LifecycleHook, LifecycleHooks,
+        RestartBackoff, WorkloadInstanceName,
     };
     use common::objects::{generate_test_workload_state_with_workload_spec, RestartPolicy};
 
@@ -613,7 +1058,8 @@ mod tests {
     use crate::workload_state::WorkloadStateSenderInterface;
     use crate::{
         runtime_connectors::test::{MockRuntimeConnector, RuntimeCall, StubStateChecker},
-        workload::{ControlLoopState, WorkloadCommandSender},
+        secret_resolver::SecretResolver,
+        workload::{ControlLoopState, EvictionReason, WorkloadCommandSender},
         workload_state::assert_execution_state_sequence,
     };
 
@@ -680,7 +1126,7 @@ mod tests {
             .await
             .unwrap();
         // Send also a delete command so that we can properly get out of the loop
-        workload_command_sender.clone().delete().await.unwrap();
+        workload_command_sender.clone().delete(None).await.unwrap();
 
         let old_instance_name = old_workload_spec.instance_name.clone();
         let new_instance_name = new_workload_spec.instance_name.clone();
@@ -749,7 +1195,7 @@ mod tests {
             .unwrap();
 
         // Send also a delete command so that we can properly get out of the loop
-        workload_command_sender.clone().delete().await.unwrap();
+        workload_command_sender.clone().delete(None).await.unwrap();
 
         let old_instance_name = old_workload_spec.instance_name.clone();
 
@@ -839,7 +1285,7 @@ mod tests {
             .unwrap();
 
         // Send also a delete command so that we can properly get out of the loop
-        workload_command_sender.clone().delete().await.unwrap();
+        workload_command_sender.clone().delete(None).await.unwrap();
 
         let old_instance_name = old_workload_spec.instance_name.clone();
 
@@ -920,7 +1366,7 @@ mod tests {
             .await
             .unwrap();
         // Send also a delete command so that we can properly get out of the loop
-        workload_command_sender.clone().delete().await.unwrap();
+        workload_command_sender.clone().delete(None).await.unwrap();
 
         let old_instance_name = old_workload_spec.instance_name.clone();
         let new_instance_name = new_workload_spec.instance_name.clone();
@@ -1000,7 +1446,7 @@ mod tests {
             .await
             .unwrap();
         // Send also a delete command so that we can properly get out of the loop
-        workload_command_sender.clone().delete().await.unwrap();
+        workload_command_sender.clone().delete(None).await.unwrap();
 
         let old_instance_name = old_workload_spec.instance_name.clone();
 
@@ -1086,7 +1532,7 @@ mod tests {
             .await
             .unwrap();
         // Send also a delete command so that we can properly get out of the loop
-        workload_command_sender.clone().delete().await.unwrap();
+        workload_command_sender.clone().delete(None).await.unwrap();
 
         let old_instance_name = old_workload_spec.instance_name.clone();
         let new_instance_name = new_workload_spec.instance_name.clone();
@@ -1147,7 +1593,601 @@ mod tests {
             .await;
 
         // Send the delete command now. It will be buffered until the await receives it.
-        workload_command_sender.clone().delete().await.unwrap();
+        workload_command_sender.clone().delete(None).await.unwrap();
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let instance_name = workload_spec.instance_name.clone();
+
+        let mut control_loop_state = ControlLoopState::builder()
+            .workload_spec(workload_spec)
+            .workload_state_sender(state_change_tx)
+            .runtime(Box::new(runtime_mock.clone()))
+            .workload_command_receiver(workload_command_receiver)
+            .retry_sender(workload_command_sender)
+            .build()
+            .unwrap();
+
+        control_loop_state.workload_id = Some(OLD_WORKLOAD_ID.to_string());
+        control_loop_state.state_checker = Some(mock_state_checker);
+
+        assert!(timeout(
+            Duration::from_millis(200),
+            WorkloadControlLoop::run(control_loop_state)
+        )
+        .await
+        .is_ok());
+
+        assert_execution_state_sequence(
+            state_change_rx,
+            vec![
+                (&instance_name, ExecutionState::stopping_requested()),
+                (&instance_name, ExecutionState::removed()),
+            ],
+        )
+        .await;
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
+    // [utest->swdd~agent-workload-control-loop-stops-workload-within-grace-period~1]
+    #[tokio::test]
+    async fn utest_workload_obj_run_delete_with_grace_period() {
+        let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
+        let (state_change_tx, state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
+
+        let mut mock_state_checker = StubStateChecker::new();
+        mock_state_checker.panic_if_not_stopped();
+
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![
+                RuntimeCall::StopWorkload(
+                    OLD_WORKLOAD_ID.to_string(),
+                    30,
+                    Err(crate::runtime_connectors::RuntimeError::Delete(
+                        "stop timed out".to_string(),
+                    )),
+                ),
+                RuntimeCall::DeleteWorkload(OLD_WORKLOAD_ID.to_string(), Ok(())),
+            ])
+            .await;
+
+        workload_command_sender
+            .clone()
+            .delete(Some(30))
+            .await
+            .unwrap();
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let instance_name = workload_spec.instance_name.clone();
+
+        let mut control_loop_state = ControlLoopState::builder()
+            .workload_spec(workload_spec)
+            .workload_state_sender(state_change_tx)
+            .runtime(Box::new(runtime_mock.clone()))
+            .workload_command_receiver(workload_command_receiver)
+            .retry_sender(workload_command_sender)
+            .build()
+            .unwrap();
+
+        control_loop_state.workload_id = Some(OLD_WORKLOAD_ID.to_string());
+        control_loop_state.state_checker = Some(mock_state_checker);
+
+        assert!(timeout(
+            Duration::from_millis(200),
+            WorkloadControlLoop::run(control_loop_state)
+        )
+        .await
+        .is_ok());
+
+        assert_execution_state_sequence(
+            state_change_rx,
+            vec![
+                (&instance_name, ExecutionState::stopping_grace_period(30)),
+                (&instance_name, ExecutionState::stopping_requested()),
+                (&instance_name, ExecutionState::removed()),
+            ],
+        )
+        .await;
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
+    // [utest->swdd~agent-workload-control-loop-delete-failed-allows-retry~1]
+    #[tokio::test]
+    async fn utest_workload_obj_run_delete_failed_allows_retry() {
+        let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
+        let (state_change_tx, state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
+
+        let mut mock_state_checker = StubStateChecker::new();
+        mock_state_checker.panic_if_not_stopped();
+
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![
+                RuntimeCall::DeleteWorkload(
+                    OLD_WORKLOAD_ID.to_string(),
+                    Err(crate::runtime_connectors::RuntimeError::Delete(
+                        "some delete error".to_string(),
+                    )),
+                ),
+                // First fail, now success
+                RuntimeCall::DeleteWorkload(OLD_WORKLOAD_ID.to_string(), Ok(())),
+            ])
+            .await;
+
+        // Send the delete command now. It will be buffered until the await receives it.
+        workload_command_sender.clone().delete(None).await.unwrap();
+        workload_command_sender.clone().delete(None).await.unwrap();
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+        let instance_name = workload_spec.instance_name.clone();
+
+        let mut control_loop_state = ControlLoopState::builder()
+            .workload_spec(workload_spec)
+            .workload_state_sender(state_change_tx)
+            .runtime(Box::new(runtime_mock.clone()))
+            .workload_command_receiver(workload_command_receiver)
+            .retry_sender(workload_command_sender)
+            .build()
+            .unwrap();
+
+        control_loop_state.workload_id = Some(OLD_WORKLOAD_ID.to_string());
+        control_loop_state.state_checker = Some(mock_state_checker);
+
+        assert!(timeout(
+            Duration::from_millis(200),
+            WorkloadControlLoop::run(control_loop_state)
+        )
+        .await
+        .is_ok());
+
+        assert_execution_state_sequence(
+            state_change_rx,
+            vec![
+                (&instance_name, ExecutionState::stopping_requested()),
+                (
+                    &instance_name,
+                    ExecutionState::delete_failed("some delete error"),
+                ),
+                (&instance_name, ExecutionState::stopping_requested()),
+                (&instance_name, ExecutionState::removed()),
+            ],
+        )
+        .await;
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
+    // [utest->swdd~agent-workload-control-loop-executes-evict~2]
+    #[tokio::test]
+    async fn utest_workload_obj_run_evict_success() {
+        let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
+        let (state_change_tx, state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
+
+        let mut mock_state_checker = StubStateChecker::new();
+        mock_state_checker.panic_if_not_stopped();
+
+        // the evicted workload is already gone when it is deleted afterwards
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![RuntimeCall::DeleteWorkload(
+                OLD_WORKLOAD_ID.to_string(),
+                Ok(()),
+            )])
+            .await;
+
+        workload_command_sender
+            .evict(EvictionReason::MemoryPressure)
+            .await
+            .unwrap();
+        workload_command_sender.clone().delete(None).await.unwrap();
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let instance_name = workload_spec.instance_name.clone();
+
+        let mut control_loop_state = ControlLoopState::builder()
+            .workload_spec(workload_spec)
+            .workload_state_sender(state_change_tx)
+            .runtime(Box::new(runtime_mock.clone()))
+            .workload_command_receiver(workload_command_receiver)
+            .retry_sender(workload_command_sender)
+            .build()
+            .unwrap();
+
+        control_loop_state.workload_id = Some(OLD_WORKLOAD_ID.to_string());
+        control_loop_state.state_checker = Some(mock_state_checker);
+
+        assert!(timeout(
+            Duration::from_millis(200),
+            WorkloadControlLoop::run(control_loop_state)
+        )
+        .await
+        .is_ok());
+
+        assert_execution_state_sequence(
+            state_change_rx,
+            vec![
+                (&instance_name, ExecutionState::stopping_requested()),
+                (&instance_name, ExecutionState::evicted(super::EVICTED_MSG)),
+                (&instance_name, ExecutionState::stopping_requested()),
+                (&instance_name, ExecutionState::removed()),
+            ],
+        )
+        .await;
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
+    // [utest->swdd~agent-workload-control-loop-executes-evict~2]
+    #[tokio::test]
+    async fn utest_workload_obj_run_evict_on_agent_disconnect_success() {
+        let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
+        let (state_change_tx, state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
+
+        let mut mock_state_checker = StubStateChecker::new();
+        mock_state_checker.panic_if_not_stopped();
+
+        // the evicted workload is already gone when it is deleted afterwards
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![RuntimeCall::DeleteWorkload(
+                OLD_WORKLOAD_ID.to_string(),
+                Ok(()),
+            )])
+            .await;
+
+        workload_command_sender
+            .evict(EvictionReason::AgentDisconnect)
+            .await
+            .unwrap();
+        workload_command_sender.clone().delete(None).await.unwrap();
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let instance_name = workload_spec.instance_name.clone();
+
+        let mut control_loop_state = ControlLoopState::builder()
+            .workload_spec(workload_spec)
+            .workload_state_sender(state_change_tx)
+            .runtime(Box::new(runtime_mock.clone()))
+            .workload_command_receiver(workload_command_receiver)
+            .retry_sender(workload_command_sender)
+            .build()
+            .unwrap();
+
+        control_loop_state.workload_id = Some(OLD_WORKLOAD_ID.to_string());
+        control_loop_state.state_checker = Some(mock_state_checker);
+
+        assert!(timeout(
+            Duration::from_millis(200),
+            WorkloadControlLoop::run(control_loop_state)
+        )
+        .await
+        .is_ok());
+
+        assert_execution_state_sequence(
+            state_change_rx,
+            vec![
+                (&instance_name, ExecutionState::stopping_requested()),
+                (
+                    &instance_name,
+                    ExecutionState::stopped_on_disconnect(super::STOPPED_ON_DISCONNECT_MSG),
+                ),
+                (&instance_name, ExecutionState::stopping_requested()),
+                (&instance_name, ExecutionState::removed()),
+            ],
+        )
+        .await;
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
+    // [utest->swdd~agent-workload-control-loop-executes-evict~2]
+    #[tokio::test]
+    async fn utest_workload_obj_run_evict_failed_keeps_workload() {
+        let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
+        let (state_change_tx, state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
+
+        let mut mock_state_checker = StubStateChecker::new();
+        mock_state_checker.panic_if_not_stopped();
+
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![
+                RuntimeCall::DeleteWorkload(
+                    OLD_WORKLOAD_ID.to_string(),
+                    Err(crate::runtime_connectors::RuntimeError::Delete(
+                        "some delete error".to_string(),
+                    )),
+                ),
+                RuntimeCall::DeleteWorkload(OLD_WORKLOAD_ID.to_string(), Ok(())),
+            ])
+            .await;
+
+        workload_command_sender
+            .evict(EvictionReason::MemoryPressure)
+            .await
+            .unwrap();
+        workload_command_sender.clone().delete(None).await.unwrap();
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let instance_name = workload_spec.instance_name.clone();
+
+        let mut control_loop_state = ControlLoopState::builder()
+            .workload_spec(workload_spec)
+            .workload_state_sender(state_change_tx)
+            .runtime(Box::new(runtime_mock.clone()))
+            .workload_command_receiver(workload_command_receiver)
+            .retry_sender(workload_command_sender)
+            .build()
+            .unwrap();
+
+        control_loop_state.workload_id = Some(OLD_WORKLOAD_ID.to_string());
+        control_loop_state.state_checker = Some(mock_state_checker);
+
+        assert!(timeout(
+            Duration::from_millis(200),
+            WorkloadControlLoop::run(control_loop_state)
+        )
+        .await
+        .is_ok());
+
+        assert_execution_state_sequence(
+            state_change_rx,
+            vec![
+                (&instance_name, ExecutionState::stopping_requested()),
+                (
+                    &instance_name,
+                    ExecutionState::delete_failed("some delete error"),
+                ),
+                (&instance_name, ExecutionState::stopping_requested()),
+                (&instance_name, ExecutionState::removed()),
+            ],
+        )
+        .await;
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
+    // [utest->swdd~agent-workload-control-loop-executes-restore~1]
+    #[tokio::test]
+    async fn utest_workload_obj_run_restore_evicted_workload() {
+        let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
+        let (state_change_tx, state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let instance_name = workload_spec.instance_name.clone();
+
+        let mut old_mock_state_checker = StubStateChecker::new();
+        old_mock_state_checker.panic_if_not_stopped();
+
+        let mut new_mock_state_checker = StubStateChecker::new();
+        new_mock_state_checker.panic_if_not_stopped();
+
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![
+                RuntimeCall::DeleteWorkload(OLD_WORKLOAD_ID.to_string(), Ok(())),
+                RuntimeCall::CreateWorkload(
+                    workload_spec.clone(),
+                    None,
+                    Ok((WORKLOAD_ID.to_string(), new_mock_state_checker)),
+                ),
+                // Delete the restored workload to exit the control loop
+                RuntimeCall::DeleteWorkload(WORKLOAD_ID.to_string(), Ok(())),
+            ])
+            .await;
+
+        workload_command_sender
+            .evict(EvictionReason::MemoryPressure)
+            .await
+            .unwrap();
+        workload_command_sender.restore().await.unwrap();
+        workload_command_sender.clone().delete(None).await.unwrap();
+
+        let mut control_loop_state = ControlLoopState::builder()
+            .workload_spec(workload_spec)
+            .workload_state_sender(state_change_tx)
+            .runtime(Box::new(runtime_mock.clone()))
+            .workload_command_receiver(workload_command_receiver)
+            .retry_sender(workload_command_sender)
+            .build()
+            .unwrap();
+
+        control_loop_state.workload_id = Some(OLD_WORKLOAD_ID.to_string());
+        control_loop_state.state_checker = Some(old_mock_state_checker);
+
+        assert!(timeout(
+            Duration::from_millis(200),
+            WorkloadControlLoop::run(control_loop_state)
+        )
+        .await
+        .is_ok());
+
+        assert_execution_state_sequence(
+            state_change_rx,
+            vec![
+                (&instance_name, ExecutionState::stopping_requested()),
+                (&instance_name, ExecutionState::evicted(super::EVICTED_MSG)),
+                (&instance_name, ExecutionState::starting_triggered()),
+                (&instance_name, ExecutionState::stopping_requested()),
+                (&instance_name, ExecutionState::removed()),
+            ],
+        )
+        .await;
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
+    // [utest->swdd~agent-workload-control-loop-executes-restore~1]
+    #[tokio::test]
+    async fn utest_workload_obj_run_restore_ignored_if_not_evicted() {
+        let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
+        let (state_change_tx, state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
+
+        let mut mock_state_checker = StubStateChecker::new();
+        mock_state_checker.panic_if_not_stopped();
+
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![RuntimeCall::DeleteWorkload(
+                OLD_WORKLOAD_ID.to_string(),
+                Ok(()),
+            )])
+            .await;
+
+        workload_command_sender.restore().await.unwrap();
+        workload_command_sender.clone().delete(None).await.unwrap();
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let instance_name = workload_spec.instance_name.clone();
+
+        let mut control_loop_state = ControlLoopState::builder()
+            .workload_spec(workload_spec)
+            .workload_state_sender(state_change_tx)
+            .runtime(Box::new(runtime_mock.clone()))
+            .workload_command_receiver(workload_command_receiver)
+            .retry_sender(workload_command_sender)
+            .build()
+            .unwrap();
+
+        control_loop_state.workload_id = Some(OLD_WORKLOAD_ID.to_string());
+        control_loop_state.state_checker = Some(mock_state_checker);
+
+        assert!(timeout(
+            Duration::from_millis(200),
+            WorkloadControlLoop::run(control_loop_state)
+        )
+        .await
+        .is_ok());
+
+        assert_execution_state_sequence(
+            state_change_rx,
+            vec![
+                (&instance_name, ExecutionState::stopping_requested()),
+                (&instance_name, ExecutionState::removed()),
+            ],
+        )
+        .await;
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
+    // [utest->swdd~agent-workload-control-loop-executes-restart~1]
+    #[tokio::test]
+    async fn utest_workload_obj_run_restart_recreates_workload_with_same_spec() {
+        let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
+        let (state_change_tx, state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
+
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let instance_name = workload_spec.instance_name.clone();
+
+        let mut old_mock_state_checker = StubStateChecker::new();
+        old_mock_state_checker.panic_if_not_stopped();
+
+        let mut new_mock_state_checker = StubStateChecker::new();
+        new_mock_state_checker.panic_if_not_stopped();
+
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![
+                RuntimeCall::DeleteWorkload(OLD_WORKLOAD_ID.to_string(), Ok(())),
+                RuntimeCall::CreateWorkload(
+                    workload_spec.clone(),
+                    None,
+                    Ok((WORKLOAD_ID.to_string(), new_mock_state_checker)),
+                ),
+                // Delete the restarted workload to exit the control loop
+                RuntimeCall::DeleteWorkload(WORKLOAD_ID.to_string(), Ok(())),
+            ])
+            .await;
+
+        workload_command_sender.restart().await.unwrap();
+        workload_command_sender.clone().delete(None).await.unwrap();
+
+        let mut control_loop_state = ControlLoopState::builder()
+            .workload_spec(workload_spec)
+            .workload_state_sender(state_change_tx)
+            .runtime(Box::new(runtime_mock.clone()))
+            .workload_command_receiver(workload_command_receiver)
+            .retry_sender(workload_command_sender)
+            .build()
+            .unwrap();
+
+        control_loop_state.workload_id = Some(OLD_WORKLOAD_ID.to_string());
+        control_loop_state.state_checker = Some(old_mock_state_checker);
+
+        assert!(timeout(
+            Duration::from_millis(200),
+            WorkloadControlLoop::run(control_loop_state)
+        )
+        .await
+        .is_ok());
+
+        assert_execution_state_sequence(
+            state_change_rx,
+            vec![
+                (&instance_name, ExecutionState::stopping_requested()),
+                (&instance_name, ExecutionState::removed()),
+                (&instance_name, ExecutionState::starting_triggered()),
+                (&instance_name, ExecutionState::stopping_requested()),
+                (&instance_name, ExecutionState::removed()),
+            ],
+        )
+        .await;
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
+    // [utest->swdd~agent-workload-control-loop-executes-pause~1]
+    #[tokio::test]
+    async fn utest_workload_obj_run_pause_and_unpause_workload() {
+        let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
+        let (state_change_tx, state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
 
         let workload_spec = generate_test_workload_spec_with_param(
             AGENT_NAME.to_string(),
@@ -1157,6 +2197,22 @@ mod tests {
 
         let instance_name = workload_spec.instance_name.clone();
 
+        let mut mock_state_checker = StubStateChecker::new();
+        mock_state_checker.panic_if_not_stopped();
+
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![
+                RuntimeCall::PauseWorkload(OLD_WORKLOAD_ID.to_string(), Ok(())),
+                RuntimeCall::UnpauseWorkload(OLD_WORKLOAD_ID.to_string(), Ok(())),
+                RuntimeCall::DeleteWorkload(OLD_WORKLOAD_ID.to_string(), Ok(())),
+            ])
+            .await;
+
+        workload_command_sender.pause().await.unwrap();
+        workload_command_sender.unpause().await.unwrap();
+        workload_command_sender.clone().delete(None).await.unwrap();
+
         let mut control_loop_state = ControlLoopState::builder()
             .workload_spec(workload_spec)
             .workload_state_sender(state_change_tx)
@@ -1179,6 +2235,8 @@ mod tests {
         assert_execution_state_sequence(
             state_change_rx,
             vec![
+                (&instance_name, ExecutionState::paused()),
+                (&instance_name, ExecutionState::running()),
                 (&instance_name, ExecutionState::stopping_requested()),
                 (&instance_name, ExecutionState::removed()),
             ],
@@ -1188,52 +2246,60 @@ mod tests {
         runtime_mock.assert_all_expectations().await;
     }
 
-    // [utest->swdd~agent-workload-control-loop-delete-failed-allows-retry~1]
+    // [utest->swdd~agent-workload-control-loop-executes-pause~1]
     #[tokio::test]
-    async fn utest_workload_obj_run_delete_failed_allows_retry() {
+    async fn utest_workload_obj_run_paused_workload_not_restarted() {
         let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
         let (state_change_tx, state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
 
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let instance_name = workload_spec.instance_name.clone();
+
         let mut mock_state_checker = StubStateChecker::new();
         mock_state_checker.panic_if_not_stopped();
 
         let mut runtime_mock = MockRuntimeConnector::new();
         runtime_mock
             .expect(vec![
-                RuntimeCall::DeleteWorkload(
-                    OLD_WORKLOAD_ID.to_string(),
-                    Err(crate::runtime_connectors::RuntimeError::Delete(
-                        "some delete error".to_string(),
-                    )),
-                ),
-                // First fail, now success
+                RuntimeCall::PauseWorkload(OLD_WORKLOAD_ID.to_string(), Ok(())),
+                // no restart of the paused workload, only the final delete
                 RuntimeCall::DeleteWorkload(OLD_WORKLOAD_ID.to_string(), Ok(())),
             ])
             .await;
 
-        // Send the delete command now. It will be buffered until the await receives it.
-        workload_command_sender.clone().delete().await.unwrap();
-        workload_command_sender.clone().delete().await.unwrap();
-
-        let workload_spec = generate_test_workload_spec_with_param(
-            AGENT_NAME.to_string(),
-            WORKLOAD_1_NAME.to_string(),
-            RUNTIME_NAME.to_string(),
-        );
-        let instance_name = workload_spec.instance_name.clone();
+        workload_command_sender.pause().await.unwrap();
 
         let mut control_loop_state = ControlLoopState::builder()
             .workload_spec(workload_spec)
             .workload_state_sender(state_change_tx)
             .runtime(Box::new(runtime_mock.clone()))
             .workload_command_receiver(workload_command_receiver)
-            .retry_sender(workload_command_sender)
+            .retry_sender(workload_command_sender.clone())
             .build()
             .unwrap();
 
         control_loop_state.workload_id = Some(OLD_WORKLOAD_ID.to_string());
         control_loop_state.state_checker = Some(mock_state_checker);
 
+        let state_checker_wl_state_sender = control_loop_state
+            .state_checker_workload_state_sender
+            .clone();
+        let state_instance_name = instance_name.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+            // the restart policy of the workload matches the succeeded state
+            state_checker_wl_state_sender
+                .report_workload_execution_state(&state_instance_name, ExecutionState::succeeded())
+                .await;
+            tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+            workload_command_sender.delete(None).await.unwrap();
+        });
+
         assert!(timeout(
             Duration::from_millis(200),
             WorkloadControlLoop::run(control_loop_state)
@@ -1244,11 +2310,8 @@ mod tests {
         assert_execution_state_sequence(
             state_change_rx,
             vec![
-                (&instance_name, ExecutionState::stopping_requested()),
-                (
-                    &instance_name,
-                    ExecutionState::delete_failed("some delete error"),
-                ),
+                (&instance_name, ExecutionState::paused()),
+                (&instance_name, ExecutionState::succeeded()),
                 (&instance_name, ExecutionState::stopping_requested()),
                 (&instance_name, ExecutionState::removed()),
             ],
@@ -1267,7 +2330,7 @@ mod tests {
         let runtime_mock = MockRuntimeConnector::new();
 
         // Send the delete command now. It will be buffered until the await receives it.
-        workload_command_sender.clone().delete().await.unwrap();
+        workload_command_sender.clone().delete(None).await.unwrap();
 
         let workload_spec = generate_test_workload_spec_with_param(
             AGENT_NAME.to_string(),
@@ -1329,7 +2392,7 @@ mod tests {
         let workload_command_sender_clone = workload_command_sender.clone();
         tokio::spawn(async move {
             tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
-            workload_command_sender_clone.delete().await.unwrap();
+            workload_command_sender_clone.delete(None).await.unwrap();
         });
 
         let control_loop_state = ControlLoopState::builder()
@@ -1352,6 +2415,150 @@ mod tests {
         runtime_mock.assert_all_expectations().await;
     }
 
+    // [utest->swdd~agent-workload-control-loop-executes-lifecycle-hooks~1]
+    #[tokio::test]
+    async fn utest_workload_obj_run_executes_lifecycle_hooks() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
+        let (state_change_tx, _state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
+
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+        workload_spec.lifecycle_hooks = Some(LifecycleHooks {
+            post_start: Some(LifecycleHook {
+                command: vec!["warmup".to_string()],
+                timeout_secs: None,
+            }),
+            pre_stop: Some(LifecycleHook {
+                command: vec!["flush".to_string()],
+                timeout_secs: None,
+            }),
+        });
+
+        let mut failed_hook_state_checker = StubStateChecker::new();
+        failed_hook_state_checker.panic_if_not_stopped();
+        let mut new_mock_state_checker = StubStateChecker::new();
+        new_mock_state_checker.panic_if_not_stopped();
+
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![
+                RuntimeCall::CreateWorkload(
+                    workload_spec.clone(),
+                    Some(PIPES_LOCATION.into()),
+                    Ok((WORKLOAD_ID.to_string(), failed_hook_state_checker)),
+                ),
+                // the failed postStart hook deletes the workload and triggers a retry
+                RuntimeCall::Exec(WORKLOAD_ID.to_string(), vec!["warmup".to_string()], Ok(1)),
+                RuntimeCall::DeleteWorkload(WORKLOAD_ID.to_string(), Ok(())),
+                RuntimeCall::CreateWorkload(
+                    workload_spec.clone(),
+                    Some(PIPES_LOCATION.into()),
+                    Ok((WORKLOAD_ID_2.to_string(), new_mock_state_checker)),
+                ),
+                RuntimeCall::Exec(WORKLOAD_ID_2.to_string(), vec!["warmup".to_string()], Ok(0)),
+                // a failed preStop hook does not prevent the deletion
+                RuntimeCall::Exec(WORKLOAD_ID_2.to_string(), vec!["flush".to_string()], Ok(1)),
+                RuntimeCall::DeleteWorkload(WORKLOAD_ID_2.to_string(), Ok(())),
+            ])
+            .await;
+
+        workload_command_sender.create().await.unwrap();
+
+        let workload_command_sender_clone = workload_command_sender.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+            workload_command_sender_clone.delete(None).await.unwrap();
+        });
+
+        let control_loop_state = ControlLoopState::builder()
+            .workload_spec(workload_spec)
+            .workload_state_sender(state_change_tx)
+            .control_interface_path(Some(PIPES_LOCATION.into()))
+            .runtime(Box::new(runtime_mock.clone()))
+            .workload_command_receiver(workload_command_receiver)
+            .retry_sender(workload_command_sender)
+            .build()
+            .unwrap();
+
+        assert!(timeout(
+            Duration::from_millis(400),
+            WorkloadControlLoop::run(control_loop_state)
+        )
+        .await
+        .is_ok());
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
+    // [utest->swdd~agent-resolves-secret-placeholders~2]
+    #[tokio::test]
+    async fn utest_workload_obj_run_create_with_resolved_secrets() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
+        let (state_change_tx, _state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
+        let secrets_directory = tempfile::tempdir().unwrap();
+        std::fs::write(secrets_directory.path().join("password"), "secret_value").unwrap();
+
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+        workload_spec.runtime_config = "password: ${ankaios-secret:file:password}".to_string();
+        let mut resolved_workload_spec = workload_spec.clone();
+        resolved_workload_spec.runtime_config = "password: secret_value".to_string();
+
+        let mut new_mock_state_checker = StubStateChecker::new();
+        new_mock_state_checker.panic_if_not_stopped();
+
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![
+                RuntimeCall::CreateWorkload(
+                    resolved_workload_spec,
+                    Some(PIPES_LOCATION.into()),
+                    Ok((WORKLOAD_ID.to_string(), new_mock_state_checker)),
+                ),
+                RuntimeCall::DeleteWorkload(WORKLOAD_ID.to_string(), Ok(())),
+            ])
+            .await;
+
+        workload_command_sender.create().await.unwrap();
+
+        let workload_command_sender_clone = workload_command_sender.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+            workload_command_sender_clone.delete(None).await.unwrap();
+        });
+
+        let control_loop_state = ControlLoopState::builder()
+            .workload_spec(workload_spec)
+            .workload_state_sender(state_change_tx.clone())
+            .control_interface_path(Some(PIPES_LOCATION.into()))
+            .runtime(Box::new(runtime_mock.clone()))
+            .workload_command_receiver(workload_command_receiver)
+            .retry_sender(workload_command_sender)
+            .secret_resolver(SecretResolver::new(
+                Some(secrets_directory.path().to_path_buf()),
+                vec![],
+            ))
+            .build()
+            .unwrap();
+
+        assert!(timeout(
+            Duration::from_millis(100),
+            WorkloadControlLoop::run(control_loop_state)
+        )
+        .await
+        .is_ok());
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
     // [utest->swdd~agent-workload-control-loop-executes-create~3]
     // [utest->swdd~agent-workload-control-loop-retries-workload-creation-on-create-failure~1]
     #[tokio::test]
@@ -1395,7 +2602,7 @@ mod tests {
         let workload_command_sender_clone = workload_command_sender.clone();
         tokio::spawn(async move {
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-            workload_command_sender_clone.delete().await.unwrap();
+            workload_command_sender_clone.delete(None).await.unwrap();
         });
 
         let control_loop_state = ControlLoopState::builder()
@@ -1464,11 +2671,15 @@ mod tests {
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
         // send some randomly selected command
-        assert!(new_control_loop_state.retry_sender.delete().await.is_err());
+        assert!(new_control_loop_state
+            .retry_sender
+            .delete(None)
+            .await
+            .is_err());
     }
 
     // [utest->swdd~agent-workload-control-loop-executes-retry~1]
-    // [utest->swdd~agent-workload-control-loop-requests-retries-on-failing-retry-attempt~1]
+    // [utest->swdd~agent-workload-control-loop-requests-retries-on-failing-retry-attempt~2]
     #[tokio::test]
     async fn utest_workload_obj_run_retry_creation_successful_after_create_fails() {
         let _ = env_logger::builder().is_test(true).try_init();
@@ -1515,7 +2726,7 @@ mod tests {
         let workload_command_sender_clone = workload_command_sender.clone();
         tokio::spawn(async move {
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-            workload_command_sender_clone.delete().await.unwrap();
+            workload_command_sender_clone.delete(None).await.unwrap();
         });
 
         let control_loop_state = ControlLoopState::builder()
@@ -1539,8 +2750,8 @@ mod tests {
     }
 
     // [utest->swdd~agent-workload-control-loop-executes-retry~1]
-    // [utest->swdd~agent-workload-control-loop-requests-retries-on-failing-retry-attempt~1]
-    // [utest->swdd~agent-workload-control-loop-limits-retry-attempts~1]
+    // [utest->swdd~agent-workload-control-loop-requests-retries-on-failing-retry-attempt~2]
+    // [utest->swdd~agent-workload-control-loop-limits-retry-attempts~2]
     // [utest->swdd~agent-workload-control-loop-retry-limit-set-execution-state~2]
     #[tokio::test]
     async fn utest_workload_obj_run_retry_attempts_exceeded_workload_creation() {
@@ -1583,7 +2794,7 @@ mod tests {
         let workload_command_sender_clone = workload_command_sender.clone();
         tokio::spawn(async move {
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-            workload_command_sender_clone.delete().await.unwrap();
+            workload_command_sender_clone.delete(None).await.unwrap();
         });
 
         let control_loop_state = ControlLoopState::builder()
@@ -1627,6 +2838,86 @@ mod tests {
         runtime_mock.assert_all_expectations().await;
     }
 
+    // [utest->swdd~agent-workload-control-loop-limits-retry-attempts~2]
+    // [utest->swdd~agent-workload-control-loop-applies-restart-backoff~1]
+    #[tokio::test]
+    async fn utest_workload_obj_run_retry_attempts_exceeded_with_restart_backoff() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (workload_command_sender, workload_command_receiver) = WorkloadCommandSender::new();
+        let (state_change_tx, state_change_rx) = mpsc::channel(TEST_EXEC_COMMAND_BUFFER_SIZE);
+
+        let mut workload_spec = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+        workload_spec.restart_backoff = Some(RestartBackoff {
+            max_retries: Some(1),
+            ..Default::default()
+        });
+
+        let instance_name = workload_spec.instance_name.clone();
+
+        let create_runtime_error_msg = "some create error";
+        let mut runtime_mock = MockRuntimeConnector::new();
+        runtime_mock
+            .expect(vec![RuntimeCall::CreateWorkload(
+                workload_spec.clone(),
+                Some(PIPES_LOCATION.into()),
+                Err(crate::runtime_connectors::RuntimeError::Create(
+                    create_runtime_error_msg.to_owned(),
+                )),
+            )])
+            .await;
+
+        workload_command_sender
+            .retry(instance_name.clone())
+            .await
+            .unwrap();
+
+        let workload_command_sender_clone = workload_command_sender.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            workload_command_sender_clone.delete(None).await.unwrap();
+        });
+
+        let control_loop_state = ControlLoopState::builder()
+            .workload_spec(workload_spec)
+            .control_interface_path(Some(PIPES_LOCATION.into()))
+            .workload_state_sender(state_change_tx)
+            .runtime(Box::new(runtime_mock.clone()))
+            .workload_command_receiver(workload_command_receiver)
+            .retry_sender(workload_command_sender)
+            .build()
+            .unwrap();
+
+        assert!(timeout(
+            Duration::from_millis(150),
+            WorkloadControlLoop::run(control_loop_state)
+        )
+        .await
+        .is_ok());
+
+        assert_execution_state_sequence(
+            state_change_rx,
+            vec![
+                (
+                    &instance_name,
+                    ExecutionState::retry_starting(1, 1, create_runtime_error_msg),
+                ),
+                (
+                    &instance_name,
+                    ExecutionState::retry_failed_no_retry(create_runtime_error_msg),
+                ),
+                (&instance_name, ExecutionState::stopping_requested()),
+                (&instance_name, ExecutionState::removed()),
+            ],
+        )
+        .await;
+
+        runtime_mock.assert_all_expectations().await;
+    }
+
     // [utest->swdd~agent-workload-control-loop-executes-retry~1]
     #[tokio::test]
     async fn utest_workload_obj_run_retry_creation_workload_command_channel_closed() {
@@ -1674,11 +2965,15 @@ mod tests {
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
         // send some randomly selected command
-        assert!(new_control_loop_state.retry_sender.delete().await.is_err());
+        assert!(new_control_loop_state
+            .retry_sender
+            .delete(None)
+            .await
+            .is_err());
     }
 
     // [utest->swdd~agent-workload-control-loop-executes-retry~1]
-    // [utest->swdd~agent-workload-control-loop-requests-retries-on-failing-retry-attempt~1]
+    // [utest->swdd~agent-workload-control-loop-requests-retries-on-failing-retry-attempt~2]
     // [utest->swdd~agent-workload-control-loop-prevents-retries-on-other-workload-commands~1]
     #[tokio::test]
     async fn utest_workload_obj_run_retry_stop_retry_commands_on_update_command() {
@@ -1730,7 +3025,7 @@ mod tests {
         let workload_command_sender_clone = workload_command_sender.clone();
         tokio::spawn(async move {
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-            workload_command_sender_clone.delete().await.unwrap();
+            workload_command_sender_clone.delete(None).await.unwrap();
         });
 
         let control_loop_state = ControlLoopState::builder()
@@ -1754,7 +3049,7 @@ mod tests {
     }
 
     // [utest->swdd~agent-workload-control-loop-executes-retry~1]
-    // [utest->swdd~agent-workload-control-loop-requests-retries-on-failing-retry-attempt~1]
+    // [utest->swdd~agent-workload-control-loop-requests-retries-on-failing-retry-attempt~2]
     // [utest->swdd~agent-workload-control-loop-prevents-retries-on-other-workload-commands~1]
     #[tokio::test]
     async fn utest_workload_obj_run_retry_on_update_with_create_failure() {
@@ -1810,7 +3105,7 @@ mod tests {
         let workload_command_sender_clone = workload_command_sender.clone();
         tokio::spawn(async move {
             tokio::time::sleep(tokio::time::Duration::from_millis(125)).await;
-            workload_command_sender_clone.delete().await.unwrap();
+            workload_command_sender_clone.delete(None).await.unwrap();
         });
 
         let mut control_loop_state = ControlLoopState::builder()
@@ -1836,7 +3131,7 @@ mod tests {
     }
 
     // [utest->swdd~agent-workload-control-loop-executes-retry~1]
-    // [utest->swdd~agent-workload-control-loop-requests-retries-on-failing-retry-attempt~1]
+    // [utest->swdd~agent-workload-control-loop-requests-retries-on-failing-retry-attempt~2]
     // [utest->swdd~agent-workload-control-loop-prevents-retries-on-other-workload-commands~1]
     // [utest->swdd~agent-workload-control-loop-reset-retry-attempts-on-update~1]
     #[tokio::test]
@@ -1894,7 +3189,7 @@ mod tests {
         let workload_command_sender_clone = workload_command_sender.clone();
         tokio::spawn(async move {
             tokio::time::sleep(tokio::time::Duration::from_millis(125)).await;
-            workload_command_sender_clone.delete().await.unwrap();
+            workload_command_sender_clone.delete(None).await.unwrap();
         });
 
         let mut control_loop_state = ControlLoopState::builder()
@@ -1925,7 +3220,7 @@ mod tests {
     }
 
     // [utest->swdd~agent-workload-control-loop-executes-retry~1]
-    // [utest->swdd~agent-workload-control-loop-requests-retries-on-failing-retry-attempt~1]
+    // [utest->swdd~agent-workload-control-loop-requests-retries-on-failing-retry-attempt~2]
     // [utest->swdd~agent-workload-control-loop-prevents-retries-on-other-workload-commands~1]
     #[tokio::test]
     async fn utest_workload_obj_run_retry_create_correct_workload_on_two_updates() {
@@ -1990,7 +3285,7 @@ mod tests {
         let workload_command_sender_clone = workload_command_sender.clone();
         tokio::spawn(async move {
             tokio::time::sleep(tokio::time::Duration::from_millis(125)).await;
-            workload_command_sender_clone.delete().await.unwrap();
+            workload_command_sender_clone.delete(None).await.unwrap();
         });
 
         let mut control_loop_state = ControlLoopState::builder()
@@ -2066,7 +3361,7 @@ mod tests {
             state_checker_workload_state_receiver;
 
         workload_command_sender.resume().await.unwrap();
-        workload_command_sender.delete().await.unwrap();
+        workload_command_sender.delete(None).await.unwrap();
 
         assert!(timeout(
             Duration::from_millis(150),
@@ -2261,7 +3556,7 @@ mod tests {
         let workload_command_sender_clone = workload_command_sender.clone();
         tokio::spawn(async move {
             tokio::time::sleep(tokio::time::Duration::from_millis(70)).await;
-            workload_command_sender_clone.delete().await.unwrap();
+            workload_command_sender_clone.delete(None).await.unwrap();
         });
 
         let control_loop_state = ControlLoopState::builder()
@@ -2326,7 +3621,7 @@ mod tests {
         let workload_command_sender_clone = workload_command_sender.clone();
         tokio::spawn(async move {
             tokio::time::sleep(tokio::time::Duration::from_millis(70)).await;
-            workload_command_sender_clone.delete().await.unwrap();
+            workload_command_sender_clone.delete(None).await.unwrap();
         });
 
         let control_loop_state = ControlLoopState::builder()
@@ -2352,7 +3647,7 @@ mod tests {
         runtime_mock.assert_all_expectations().await;
     }
 
-    // [utest->swdd~workload-control-loop-restarts-workload-with-enabled-restart-policy~2]
+    // [utest->swdd~workload-control-loop-restarts-workload-with-enabled-restart-policy~3]
     // [utest->swdd~workload-control-loop-handles-workload-restarts~2]
     // [utest->swdd~workload-control-loop-restarts-workloads-using-update~1]
     #[tokio::test]
@@ -2391,7 +3686,7 @@ mod tests {
         let workload_command_sender_clone = workload_command_sender.clone();
         tokio::spawn(async move {
             tokio::time::sleep(tokio::time::Duration::from_millis(70)).await;
-            workload_command_sender_clone.delete().await.unwrap();
+            workload_command_sender_clone.delete(None).await.unwrap();
         });
 
         let mut control_loop_state = ControlLoopState::builder()
@@ -2434,7 +3729,7 @@ mod tests {
         runtime_mock.assert_all_expectations().await;
     }
 
-    // [utest->swdd~workload-control-loop-restarts-workload-with-enabled-restart-policy~2]
+    // [utest->swdd~workload-control-loop-restarts-workload-with-enabled-restart-policy~3]
     #[test]
     fn utest_is_restart_allowed_never() {
         let restart_policy = RestartPolicy::Never;
@@ -2457,9 +3752,15 @@ mod tests {
                 &ExecutionState::failed("some error".to_owned())
             )
         );
+        assert!(
+            !WorkloadControlLoop::restart_policy_matches_execution_state(
+                &restart_policy,
+                &ExecutionState::running_unhealthy("health check failed")
+            )
+        );
     }
 
-    // [utest->swdd~workload-control-loop-restarts-workload-with-enabled-restart-policy~2]
+    // [utest->swdd~workload-control-loop-restarts-workload-with-enabled-restart-policy~3]
     #[test]
     fn utest_is_restart_allowed_on_failure() {
         let restart_policy = RestartPolicy::OnFailure;
@@ -2474,6 +3775,10 @@ mod tests {
             &restart_policy,
             &ExecutionState::failed("some error".to_owned())
         ));
+        assert!(WorkloadControlLoop::restart_policy_matches_execution_state(
+            &restart_policy,
+            &ExecutionState::running_unhealthy("health check failed")
+        ));
         assert!(
             !WorkloadControlLoop::restart_policy_matches_execution_state(
                 &restart_policy,
@@ -2482,7 +3787,7 @@ mod tests {
         );
     }
 
-    // [utest->swdd~workload-control-loop-restarts-workload-with-enabled-restart-policy~2]
+    // [utest->swdd~workload-control-loop-restarts-workload-with-enabled-restart-policy~3]
     #[test]
     fn utest_restart_policy_matches_execution_state_always() {
         let restart_policy = RestartPolicy::Always;
@@ -2501,10 +3806,56 @@ mod tests {
             &restart_policy,
             &ExecutionState::succeeded()
         ));
+        assert!(WorkloadControlLoop::restart_policy_matches_execution_state(
+            &restart_policy,
+            &ExecutionState::running_unhealthy("health check failed")
+        ));
+    }
+
+    // [utest->swdd~agent-workload-control-loop-limits-retry-attempts~2]
+    // [utest->swdd~agent-workload-control-loop-requests-retries-on-failing-retry-attempt~2]
+    #[test]
+    fn utest_retry_counter_without_restart_backoff() {
+        let mut retry_counter = RetryCounter::new(None);
+
+        assert_eq!(retry_counter.limit(), super::MAX_RETRIES);
+        retry_counter.count_retry();
+        assert_eq!(
+            retry_counter.waiting_time(),
+            Duration::from_millis(super::RETRY_WAITING_TIME_MS)
+        );
+    }
+
+    // [utest->swdd~agent-workload-control-loop-limits-retry-attempts~2]
+    // [utest->swdd~agent-workload-control-loop-requests-retries-on-failing-retry-attempt~2]
+    #[test]
+    fn utest_retry_counter_with_restart_backoff() {
+        let mut retry_counter = RetryCounter::new(Some(RestartBackoff {
+            initial_delay: Some(100),
+            max_delay: Some(250),
+            multiplier: Some(3),
+            max_retries: Some(3),
+        }));
+        assert_eq!(retry_counter.limit(), 3);
+
+        let expected_delays = [100, 250];
+        for expected_delay in expected_delays {
+            retry_counter.count_retry();
+            assert!(!retry_counter.limit_exceeded());
+            let waiting_time = retry_counter.waiting_time();
+            assert!(waiting_time >= Duration::from_millis(expected_delay / 2));
+            assert!(waiting_time <= Duration::from_millis(expected_delay));
+        }
+
+        retry_counter.count_retry();
+        assert!(retry_counter.limit_exceeded());
+
+        retry_counter.reset();
+        assert_eq!(retry_counter.current_retry(), 1);
     }
 
     // [utest->swdd~agent-sends-workload-states-of-its-workloads-to-server~2]
-    // [utest->swdd~workload-control-loop-restarts-workload-with-enabled-restart-policy~2]
+    // [utest->swdd~workload-control-loop-restarts-workload-with-enabled-restart-policy~3]
     // [utest->swdd~workload-control-loop-checks-workload-state-validity~1]
     #[test]
     fn utest_is_same_workload() {
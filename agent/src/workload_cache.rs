@@ -0,0 +1,245 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use common::objects::{DeletedWorkload, WorkloadSpec};
+
+const WORKLOAD_CACHE_FILE_SUFFIX: &str = "_workloads.json";
+const TEMPORARY_FILE_EXTENSION: &str = "tmp";
+
+/// Persists the last desired workloads of the agent received from the server
+///
+/// The cache file is stored next to the run folder of the agent, as the run folder
+/// itself is removed when the agent terminates. It allows the agent to start its
+/// workloads after a restart even if the server is not reachable.
+#[derive(Debug)]
+pub struct WorkloadCache {
+    path: PathBuf,
+    offline_start_delay: Duration,
+    workloads: BTreeMap<String, WorkloadSpec>,
+}
+
+impl WorkloadCache {
+    pub fn new(run_folder: &Path, agent_name: &str, offline_start_delay: Duration) -> Self {
+        WorkloadCache {
+            path: run_folder.join(format!("{}{}", agent_name, WORKLOAD_CACHE_FILE_SUFFIX)),
+            offline_start_delay,
+            workloads: BTreeMap::new(),
+        }
+    }
+
+    pub fn offline_start_delay(&self) -> Duration {
+        self.offline_start_delay
+    }
+
+    // [impl->swdd~agent-starts-cached-workloads-without-server~1]
+    pub fn load(&mut self) -> Vec<WorkloadSpec> {
+        if !self.path.exists() {
+            return Vec::new();
+        }
+
+        let workloads = fs::read_to_string(&self.path)
+            .map_err(|err| err.to_string())
+            .and_then(|data| {
+                serde_json::from_str::<Vec<WorkloadSpec>>(&data).map_err(|err| err.to_string())
+            });
+
+        match workloads {
+            Ok(workloads) => {
+                self.workloads = workloads
+                    .into_iter()
+                    .map(|workload| (workload.instance_name.workload_name().to_owned(), workload))
+                    .collect();
+                self.workloads.values().cloned().collect()
+            }
+            Err(err) => {
+                log::warn!(
+                    "Could not load the workload cache '{}': '{}'",
+                    self.path.display(),
+                    err
+                );
+                Vec::new()
+            }
+        }
+    }
+
+    // [impl->swdd~agent-caches-workloads-received-from-server~1]
+    pub fn replace(&mut self, workloads: &[WorkloadSpec]) {
+        self.workloads = workloads
+            .iter()
+            .map(|workload| {
+                (
+                    workload.instance_name.workload_name().to_owned(),
+                    workload.clone(),
+                )
+            })
+            .collect();
+        self.persist();
+    }
+
+    // [impl->swdd~agent-caches-workloads-received-from-server~1]
+    pub fn update(
+        &mut self,
+        added_workloads: &[WorkloadSpec],
+        deleted_workloads: &[DeletedWorkload],
+    ) {
+        for deleted_workload in deleted_workloads {
+            self.workloads
+                .remove(deleted_workload.instance_name.workload_name());
+        }
+        for added_workload in added_workloads {
+            self.workloads.insert(
+                added_workload.instance_name.workload_name().to_owned(),
+                added_workload.clone(),
+            );
+        }
+        self.persist();
+    }
+
+    fn persist(&self) {
+        let workloads: Vec<&WorkloadSpec> = self.workloads.values().collect();
+        let temporary_path = self.path.with_extension(TEMPORARY_FILE_EXTENSION);
+
+        // The cache is written to a temporary file first to never leave a partially written cache behind.
+        let result = serde_json::to_string(&workloads)
+            .map_err(|err| err.to_string())
+            .and_then(|data| fs::write(&temporary_path, data).map_err(|err| err.to_string()))
+            .and_then(|_| fs::rename(&temporary_path, &self.path).map_err(|err| err.to_string()));
+
+        if let Err(err) = result {
+            log::warn!(
+                "Could not write the workload cache '{}': '{}'",
+                self.path.display(),
+                err
+            );
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, time::Duration};
+
+    use common::{
+        objects::generate_test_workload_spec_with_param, test_utils::generate_test_deleted_workload,
+    };
+
+    use super::WorkloadCache;
+
+    const AGENT_NAME: &str = "agent_A";
+    const WORKLOAD_1_NAME: &str = "workload_1";
+    const WORKLOAD_2_NAME: &str = "workload_2";
+    const RUNTIME_NAME: &str = "runtime";
+    const OFFLINE_START_DELAY: Duration = Duration::from_secs(10);
+
+    // [utest->swdd~agent-caches-workloads-received-from-server~1]
+    // [utest->swdd~agent-starts-cached-workloads-without-server~1]
+    #[test]
+    fn utest_workload_cache_load_returns_persisted_workloads() {
+        let run_folder = tempfile::tempdir().unwrap();
+        let workload_1 = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+        let workload_2 = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_2_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let mut workload_cache =
+            WorkloadCache::new(run_folder.path(), AGENT_NAME, OFFLINE_START_DELAY);
+        workload_cache.replace(&[workload_1.clone(), workload_2.clone()]);
+
+        let mut restarted_workload_cache =
+            WorkloadCache::new(run_folder.path(), AGENT_NAME, OFFLINE_START_DELAY);
+        assert_eq!(
+            restarted_workload_cache.load(),
+            vec![workload_1, workload_2]
+        );
+    }
+
+    // [utest->swdd~agent-caches-workloads-received-from-server~1]
+    #[test]
+    fn utest_workload_cache_update_adds_and_removes_workloads() {
+        let run_folder = tempfile::tempdir().unwrap();
+        let workload_1 = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_1_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+        let workload_2 = generate_test_workload_spec_with_param(
+            AGENT_NAME.to_string(),
+            WORKLOAD_2_NAME.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+
+        let mut workload_cache =
+            WorkloadCache::new(run_folder.path(), AGENT_NAME, OFFLINE_START_DELAY);
+        workload_cache.replace(&[workload_1]);
+        workload_cache.update(
+            std::slice::from_ref(&workload_2),
+            &[generate_test_deleted_workload(
+                AGENT_NAME.to_string(),
+                WORKLOAD_1_NAME.to_string(),
+            )],
+        );
+
+        let mut restarted_workload_cache =
+            WorkloadCache::new(run_folder.path(), AGENT_NAME, OFFLINE_START_DELAY);
+        assert_eq!(restarted_workload_cache.load(), vec![workload_2]);
+    }
+
+    // [utest->swdd~agent-starts-cached-workloads-without-server~1]
+    #[test]
+    fn utest_workload_cache_load_without_cache_file() {
+        let run_folder = tempfile::tempdir().unwrap();
+
+        let mut workload_cache =
+            WorkloadCache::new(run_folder.path(), AGENT_NAME, OFFLINE_START_DELAY);
+
+        assert!(workload_cache.load().is_empty());
+    }
+
+    // [utest->swdd~agent-starts-cached-workloads-without-server~1]
+    #[test]
+    fn utest_workload_cache_load_ignores_invalid_cache_file() {
+        let run_folder = tempfile::tempdir().unwrap();
+        fs::write(
+            run_folder.path().join("agent_A_workloads.json"),
+            "not a workload cache",
+        )
+        .unwrap();
+
+        let mut workload_cache =
+            WorkloadCache::new(run_folder.path(), AGENT_NAME, OFFLINE_START_DELAY);
+
+        assert!(workload_cache.load().is_empty());
+    }
+}
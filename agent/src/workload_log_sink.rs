@@ -0,0 +1,342 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashMap, path::PathBuf};
+
+use common::objects::WorkloadInstanceName;
+use serde::Deserialize;
+use tokio::{
+    io::{AsyncBufRead, AsyncBufReadExt, BufReader},
+    net::UnixDatagram,
+    process::Child,
+};
+
+const JOURNALD_SOCKET: &str = "/run/systemd/journal/socket";
+const SYSLOG_SOCKET: &str = "/dev/log";
+const SYSLOG_STRUCTURED_DATA_ID: &str = "ankaios@32473";
+// facility 'user' as defined in RFC 5424
+const SYSLOG_FACILITY_USER: u8 = 1;
+const PRIORITY_ERROR: u8 = 3;
+const PRIORITY_INFO: u8 = 6;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkloadLogSinkKind {
+    #[default]
+    None,
+    Journald,
+    Syslog,
+}
+
+/// Configuration of the sink the workload logs are mirrored to as provided in the agent config file
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct WorkloadLogsConfig {
+    /// The sink used for all workloads of the agent
+    pub sink: WorkloadLogSinkKind,
+    /// The socket of the sink, if not the default socket of journald or syslog
+    pub socket: Option<PathBuf>,
+    /// The sinks of single workloads overriding the sink of the agent
+    pub workloads: HashMap<String, WorkloadLogSinkKind>,
+}
+
+impl WorkloadLogsConfig {
+    // [impl->swdd~agent-selects-workload-log-sink~1]
+    pub fn kind_for(&self, workload_name: &str) -> WorkloadLogSinkKind {
+        self.workloads
+            .get(workload_name)
+            .copied()
+            .unwrap_or(self.sink)
+    }
+
+    // [impl->swdd~agent-selects-workload-log-sink~1]
+    pub fn sink_for(&self, workload_name: &str) -> Option<WorkloadLogSink> {
+        let (protocol, default_socket) = match self.kind_for(workload_name) {
+            WorkloadLogSinkKind::None => return None,
+            WorkloadLogSinkKind::Journald => (SinkProtocol::Journald, JOURNALD_SOCKET),
+            WorkloadLogSinkKind::Syslog => (SinkProtocol::Syslog, SYSLOG_SOCKET),
+        };
+        Some(WorkloadLogSink {
+            protocol,
+            socket: self
+                .socket
+                .clone()
+                .unwrap_or_else(|| PathBuf::from(default_socket)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SinkProtocol {
+    Journald,
+    Syslog,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+impl LogStream {
+    fn priority(&self) -> u8 {
+        match self {
+            LogStream::Stdout => PRIORITY_INFO,
+            LogStream::Stderr => PRIORITY_ERROR,
+        }
+    }
+}
+
+/// Mirrors the output of a workload line by line to journald or to a syslog socket
+///
+/// Every line is sent as a separate message with the agent, the workload name and the
+/// instance id of the workload as structured fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkloadLogSink {
+    protocol: SinkProtocol,
+    socket: PathBuf,
+}
+
+impl WorkloadLogSink {
+    /// Forwards the stdout and stderr of the log follower until both are closed
+    // [impl->swdd~agent-mirrors-workload-logs-to-sink~1]
+    pub async fn forward(self, instance_name: WorkloadInstanceName, mut log_follower: Child) {
+        let socket = match UnixDatagram::unbound() {
+            Ok(socket) => socket,
+            Err(err) => {
+                log::warn!(
+                    "Could not create a socket to mirror the logs of workload '{}': '{}'",
+                    instance_name.workload_name(),
+                    err
+                );
+                return;
+            }
+        };
+
+        let stdout = log_follower.stdout.take().map(BufReader::new);
+        let stderr = log_follower.stderr.take().map(BufReader::new);
+        tokio::join!(
+            self.forward_stream(&socket, &instance_name, LogStream::Stdout, stdout),
+            self.forward_stream(&socket, &instance_name, LogStream::Stderr, stderr),
+        );
+        let _ = log_follower.wait().await;
+        log::debug!(
+            "Stopped mirroring the logs of workload '{}'",
+            instance_name.workload_name()
+        );
+    }
+
+    async fn forward_stream(
+        &self,
+        socket: &UnixDatagram,
+        instance_name: &WorkloadInstanceName,
+        stream: LogStream,
+        reader: Option<impl AsyncBufRead + Unpin>,
+    ) {
+        let Some(reader) = reader else {
+            return;
+        };
+        let mut lines = reader.lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let message = self.format(instance_name, stream, &line);
+            if let Err(err) = socket.send_to(&message, &self.socket).await {
+                log::debug!(
+                    "Could not mirror a log line of workload '{}' to '{}': '{}'",
+                    instance_name.workload_name(),
+                    self.socket.display(),
+                    err
+                );
+            }
+        }
+    }
+
+    // [impl->swdd~agent-mirrors-workload-logs-to-sink~1]
+    fn format(
+        &self,
+        instance_name: &WorkloadInstanceName,
+        stream: LogStream,
+        line: &str,
+    ) -> Vec<u8> {
+        match self.protocol {
+            // the native journald protocol: one 'FIELD=value' per line
+            SinkProtocol::Journald => format!(
+                "MESSAGE={}\nPRIORITY={}\nSYSLOG_IDENTIFIER={}\n\
+                ANKAIOS_AGENT={}\nANKAIOS_WORKLOAD={}\nANKAIOS_INSTANCE_ID={}\n",
+                line,
+                stream.priority(),
+                instance_name.workload_name(),
+                instance_name.agent_name(),
+                instance_name.workload_name(),
+                instance_name.id()
+            ),
+            // RFC 5424 without timestamp and hostname, which are added by the syslog daemon
+            SinkProtocol::Syslog => format!(
+                "<{}>1 - - {} - - [{} agent=\"{}\" workload=\"{}\" instanceId=\"{}\"] {}",
+                SYSLOG_FACILITY_USER * 8 + stream.priority(),
+                syslog_app_name(instance_name.workload_name()),
+                SYSLOG_STRUCTURED_DATA_ID,
+                escape_param_value(instance_name.agent_name()),
+                escape_param_value(instance_name.workload_name()),
+                escape_param_value(instance_name.id()),
+                line
+            ),
+        }
+        .into_bytes()
+    }
+}
+
+// The app name is limited to 48 printable ASCII characters.
+fn syslog_app_name(workload_name: &str) -> String {
+    workload_name
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(48)
+        .collect()
+}
+
+fn escape_param_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace(']', "\\]")
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, path::PathBuf};
+
+    use common::objects::WorkloadInstanceName;
+    use tokio::net::UnixDatagram;
+
+    use super::{
+        LogStream, SinkProtocol, WorkloadLogSink, WorkloadLogSinkKind, WorkloadLogsConfig,
+    };
+
+    const AGENT_NAME: &str = "agent_A";
+    const WORKLOAD_NAME: &str = "nginx";
+
+    fn instance_name() -> WorkloadInstanceName {
+        WorkloadInstanceName::builder()
+            .agent_name(AGENT_NAME)
+            .workload_name(WORKLOAD_NAME)
+            .config(&String::from("config"))
+            .build()
+    }
+
+    // [utest->swdd~agent-selects-workload-log-sink~1]
+    #[test]
+    fn utest_workload_logs_config_sink_for_workload() {
+        let config = WorkloadLogsConfig {
+            sink: WorkloadLogSinkKind::Journald,
+            socket: None,
+            workloads: HashMap::from([
+                ("noisy".to_string(), WorkloadLogSinkKind::None),
+                ("legacy".to_string(), WorkloadLogSinkKind::Syslog),
+            ]),
+        };
+
+        assert_eq!(
+            config.sink_for(WORKLOAD_NAME),
+            Some(WorkloadLogSink {
+                protocol: SinkProtocol::Journald,
+                socket: PathBuf::from("/run/systemd/journal/socket"),
+            })
+        );
+        assert_eq!(
+            config.sink_for("legacy"),
+            Some(WorkloadLogSink {
+                protocol: SinkProtocol::Syslog,
+                socket: PathBuf::from("/dev/log"),
+            })
+        );
+        assert_eq!(config.kind_for("legacy"), WorkloadLogSinkKind::Syslog);
+        assert_eq!(
+            config.kind_for(WORKLOAD_NAME),
+            WorkloadLogSinkKind::Journald
+        );
+        assert_eq!(config.sink_for("noisy"), None);
+        assert_eq!(WorkloadLogsConfig::default().sink_for(WORKLOAD_NAME), None);
+    }
+
+    // [utest->swdd~agent-mirrors-workload-logs-to-sink~1]
+    #[test]
+    fn utest_workload_log_sink_formats_structured_messages() {
+        let instance_name = instance_name();
+        let journald_sink = WorkloadLogSink {
+            protocol: SinkProtocol::Journald,
+            socket: PathBuf::from("/run/systemd/journal/socket"),
+        };
+        let syslog_sink = WorkloadLogSink {
+            protocol: SinkProtocol::Syslog,
+            socket: PathBuf::from("/dev/log"),
+        };
+
+        assert_eq!(
+            String::from_utf8(journald_sink.format(&instance_name, LogStream::Stderr, "failed"))
+                .unwrap(),
+            format!(
+                "MESSAGE=failed\nPRIORITY=3\nSYSLOG_IDENTIFIER=nginx\nANKAIOS_AGENT=agent_A\n\
+                ANKAIOS_WORKLOAD=nginx\nANKAIOS_INSTANCE_ID={}\n",
+                instance_name.id()
+            )
+        );
+        assert_eq!(
+            String::from_utf8(syslog_sink.format(&instance_name, LogStream::Stdout, "started"))
+                .unwrap(),
+            format!(
+                "<14>1 - - nginx - - [ankaios@32473 agent=\"agent_A\" workload=\"nginx\" \
+                instanceId=\"{}\"] started",
+                instance_name.id()
+            )
+        );
+    }
+
+    // [utest->swdd~agent-mirrors-workload-logs-to-sink~1]
+    #[tokio::test]
+    async fn utest_workload_log_sink_forwards_lines_of_log_follower() {
+        let socket_dir = tempfile::tempdir().unwrap();
+        let socket_path = socket_dir.path().join("journal.socket");
+        let receiver = UnixDatagram::bind(&socket_path).unwrap();
+        let log_follower = tokio::process::Command::new("sh")
+            .args(["-c", "echo first; echo second >&2"])
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let sink = WorkloadLogSink {
+            protocol: SinkProtocol::Journald,
+            socket: socket_path,
+        };
+        sink.forward(instance_name(), log_follower).await;
+
+        let mut messages = Vec::new();
+        let mut buffer = [0; 1024];
+        for _ in 0..2 {
+            let length = receiver.recv(&mut buffer).await.unwrap();
+            messages.push(String::from_utf8_lossy(&buffer[..length]).to_string());
+        }
+        messages.sort();
+        assert!(messages[0].starts_with("MESSAGE=first\nPRIORITY=6\n"));
+        assert!(messages[1].starts_with("MESSAGE=second\nPRIORITY=3\n"));
+    }
+}
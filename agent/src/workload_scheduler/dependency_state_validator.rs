@@ -33,10 +33,15 @@ impl DependencyStateValidator {
             .iter()
             // [impl->swdd~workload-ready-to-create-on-fulfilled-dependencies~1]
             .all(|(dependency_name, add_condition)| {
+                // [impl->swdd~agent-ignores-config-dependencies-on-create~1]
+                if add_condition.refers_to_config() {
+                    return true;
+                }
+
                 workload_state_db
                     .get_state_of_workload(dependency_name)
-                    .map_or(false, |wl_state| {
-                        // [impl->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~1]
+                    .is_some_and(|wl_state| {
+                        // [impl->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~2]
                         add_condition.fulfilled_by(wl_state)
                     })
             })
@@ -53,8 +58,8 @@ impl DependencyStateValidator {
             .all(|(dependency_name, delete_condition)| {
                 workload_state_db
                     .get_state_of_workload(dependency_name)
-                    .map_or(true, |wl_state| {
-                        // [impl->swdd~execution-states-of-workload-dependencies-fulfill-delete-conditions~1]
+                    .is_none_or(|wl_state| {
+                        // [impl->swdd~execution-states-of-workload-dependencies-fulfill-delete-conditions~2]
                         delete_condition.fulfilled_by(wl_state)
                     })
             })
@@ -91,7 +96,7 @@ mod tests {
     const RUNTIME: &str = "runtime";
 
     // [utest->swdd~workload-ready-to-create-on-fulfilled-dependencies~1]
-    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~1]
+    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~2]
     #[test]
     fn utest_create_fulfilled() {
         let workload_with_dependencies = generate_test_workload_spec_with_dependencies(
@@ -132,7 +137,25 @@ mod tests {
         ));
     }
 
-    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~1]
+    // [utest->swdd~agent-ignores-config-dependencies-on-create~1]
+    #[test]
+    fn utest_create_fulfilled_config_dependency() {
+        let workload_with_dependencies = generate_test_workload_spec_with_dependencies(
+            AGENT_A,
+            WORKLOAD_NAME_1,
+            RUNTIME,
+            HashMap::from([("config_1".to_string(), AddCondition::AddCondConfigChanged)]),
+        );
+
+        let wl_state_store_mock = MockWorkloadStateStore::default();
+
+        assert!(DependencyStateValidator::create_fulfilled(
+            &workload_with_dependencies,
+            &wl_state_store_mock
+        ));
+    }
+
+    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~2]
     #[test]
     fn utest_create_fulfilled_no_workload_state_known() {
         let workload_with_dependencies = generate_test_workload_spec_with_dependencies(
@@ -151,7 +174,7 @@ mod tests {
     }
 
     // [utest->swdd~workload-ready-to-create-on-fulfilled-dependencies~1]
-    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~1]
+    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~2]
     #[test]
     fn utest_create_fulfilled_unfulfilled_execution_state() {
         let workload_with_dependencies = generate_test_workload_spec_with_dependencies(
@@ -174,7 +197,7 @@ mod tests {
     }
 
     // [utest->swdd~workload-ready-to-delete-on-fulfilled-dependencies~1]
-    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-delete-conditions~1]
+    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-delete-conditions~2]
     #[test]
     fn utest_delete_fulfilled() {
         let deleted_workload_with_dependencies = generate_test_deleted_workload_with_dependencies(
@@ -199,7 +222,7 @@ mod tests {
     }
 
     // [utest->swdd~workload-ready-to-delete-on-fulfilled-dependencies~1]
-    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-delete-conditions~1]
+    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-delete-conditions~2]
     #[test]
     fn utest_delete_fulfilled_unfulfilled_execution_state() {
         let deleted_workload_with_dependencies = generate_test_deleted_workload_with_dependencies(
@@ -239,7 +262,7 @@ mod tests {
         ));
     }
 
-    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-delete-conditions~1]
+    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-delete-conditions~2]
     #[test]
     fn utest_delete_fulfilled_no_workload_state_known() {
         let deleted_workload_with_dependencies = generate_test_deleted_workload_with_dependencies(
@@ -23,6 +23,12 @@ pub struct WorkloadStateStore {
     states_storage: WorkloadStates,
 }
 
+impl Default for WorkloadStateStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl WorkloadStateStore {
     pub fn new() -> Self {
         Self {
@@ -12,132 +12,27 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{error::Error, ffi::OsStr};
+use std::{error::Error, time::Duration};
 
-use clap::{command, CommandFactory, Parser, Subcommand, ValueHint};
+use clap::{builder::PossibleValuesParser, CommandFactory, Parser, Subcommand, ValueHint};
 
-use clap_complete::{ArgValueCompleter, CompleteEnv, CompletionCandidate};
+use clap_complete::{ArgValueCompleter, CompleteEnv, Shell};
+#[cfg(feature = "dev_mode")]
+use common::communications_server::ServerAddress;
+use common::objects::{Tag, SUPPORTED_API_VERSIONS};
 use common::DEFAULT_SERVER_ADDRESS;
+#[cfg(feature = "dev_mode")]
+use common::DEFAULT_SOCKET_ADDRESS;
+use grpc::connection_config::{Compression, ConnectionConfig};
 
-use crate::filtered_complete_state::FilteredCompleteState;
+use crate::cli_commands::output::ListOutputFormat;
+use crate::cli_completion::{
+    agent_completer, config_completer, object_field_mask_completer, workload_completer,
+};
 
 const ANK_SERVER_URL_ENV_KEY: &str = "ANK_SERVER_URL";
-
-fn state_from_command(object_field_mask: &str) -> Vec<u8> {
-    std::process::Command::new("sh")
-        .arg("-c")
-        .arg(format!("ank get state -o json {}", object_field_mask))
-        .output()
-        .expect("failed to execute process")
-        .stdout
-}
-
-fn completions_workloads(state: Vec<u8>, current: &OsStr) -> Vec<CompletionCandidate> {
-    let mut result = Vec::new();
-
-    let Ok(state) = serde_json::from_slice::<FilteredCompleteState>(&state) else {
-        return vec![];
-    };
-
-    if let Some(desired_state) = state.desired_state {
-        if let Some(workloads) = desired_state.workloads {
-            for workload_name in workloads.keys() {
-                result.push(workload_name.clone());
-            }
-        }
-    }
-
-    let cur = current.to_str().unwrap_or("");
-    result
-        .into_iter()
-        .filter(|s| s.to_string().starts_with(cur))
-        .map(CompletionCandidate::new)
-        .collect()
-}
-
-fn completions_configs(state: Vec<u8>, current: &OsStr) -> Vec<CompletionCandidate> {
-    let mut result = Vec::new();
-
-    let Ok(state) = serde_json::from_slice::<FilteredCompleteState>(&state) else {
-        return vec![];
-    };
-
-    if let Some(desired_state) = state.desired_state {
-        if let Some(configs) = desired_state.configs {
-            for config_name in configs.keys() {
-                result.push(config_name.clone());
-            }
-        }
-    }
-
-    let cur = current.to_str().unwrap_or("");
-    result
-        .into_iter()
-        .filter(|s| s.to_string().starts_with(cur))
-        .map(CompletionCandidate::new)
-        .collect()
-}
-
-// [impl->swdd~cli-shell-completion~1]
-fn workload_completer(current: &OsStr) -> Vec<CompletionCandidate> {
-    completions_workloads(state_from_command("desiredState.workloads"), current)
-}
-
-fn config_completer(current: &OsStr) -> Vec<CompletionCandidate> {
-    completions_configs(state_from_command("desiredState.configs"), current)
-}
-
-fn completions_object_field_mask(state: Vec<u8>, current: &OsStr) -> Vec<CompletionCandidate> {
-    const DESIRED_STATE: &str = "desiredState";
-    const WORKLOADS: &str = "workloads";
-    const CONFIGS: &str = "configs";
-    const WORKLOAD_STATES: &str = "workloadStates";
-
-    let mut result = Vec::new();
-
-    let Ok(state) = serde_json::from_slice::<FilteredCompleteState>(&state) else {
-        return vec![];
-    };
-
-    if let Some(desired_state) = state.desired_state {
-        result.push(DESIRED_STATE.to_string());
-        if let Some(workloads) = desired_state.workloads {
-            result.push(format!("{}.{}", DESIRED_STATE, WORKLOADS));
-            for workload_name in workloads.keys() {
-                result.push(format!("{}.{}.{}", DESIRED_STATE, WORKLOADS, workload_name));
-            }
-        }
-        result.push(CONFIGS.to_string());
-        if let Some(configs) = desired_state.configs {
-            result.push(format!("{}.{}", DESIRED_STATE, CONFIGS));
-            for config_name in configs.keys() {
-                result.push(format!("{}.{}.{}", DESIRED_STATE, CONFIGS, config_name));
-            }
-        }
-    }
-
-    if let Some(workload_states) = state.workload_states {
-        result.push(WORKLOAD_STATES.to_string());
-        for (agent, workloads) in workload_states.into_iter() {
-            result.push(format!("{}.{}", WORKLOAD_STATES, agent));
-            for workload_name in workloads.keys() {
-                result.push(format!("{}.{}.{}", WORKLOAD_STATES, agent, workload_name));
-            }
-        }
-    }
-
-    let cur = current.to_str().unwrap_or("");
-    result
-        .into_iter()
-        .filter(|s| s.to_string().starts_with(cur))
-        .map(CompletionCandidate::new)
-        .collect()
-}
-
-// [impl->swdd~cli-shell-completion~1]
-fn object_field_mask_completer(current: &OsStr) -> Vec<CompletionCandidate> {
-    completions_object_field_mask(state_from_command(""), current)
-}
+#[cfg(feature = "dev_mode")]
+const DEFAULT_DEV_MODE_RUN_FOLDER: &str = "/tmp/ankaios-dev";
 
 // [impl->swdd~cli-supports-server-url-cli-argument~1]
 // [impl->swdd~cli-supports-pem-file-paths-as-cli-arguments~1]
@@ -151,7 +46,7 @@ pub struct AnkCli {
     #[command(subcommand)]
     pub command: Commands,
     #[clap(short = 's', long = "server-url", default_value_t = DEFAULT_SERVER_ADDRESS.to_string(), env = ANK_SERVER_URL_ENV_KEY)]
-    /// The url to Ankaios server.
+    /// The url to Ankaios server. Use 'unix:///path/to/server.sock' to connect to a server listening at a unix domain socket.
     pub server_url: String,
     #[clap(long = "response-timeout", default_value_t = 3000)]
     /// The timeout in milliseconds to wait for a response.
@@ -162,6 +57,9 @@ pub struct AnkCli {
     #[clap(short = 'q', long = "quiet")]
     /// Disable all output
     pub quiet: bool,
+    #[clap(long = "debug-requests")]
+    /// Print every request sent to and response received from the Ankaios server with timing information to stderr. Secrets are redacted.
+    pub debug_requests: bool,
     #[clap(long = "no-wait")]
     /// Do not wait for workloads to be created/deleted
     pub no_wait: bool,
@@ -182,6 +80,36 @@ pub struct AnkCli {
     #[clap(long = "key_pem", env = "ANK_KEY_PEM")]
     /// Path to cli key pem file.
     pub key_pem: Option<String>,
+    #[clap(long = "grpc-max-message-size", env = "ANK_GRPC_MAX_MESSAGE_SIZE")]
+    /// The maximum size in bytes of a message sent to or received from the Ankaios server [default: 16777216]
+    pub grpc_max_message_size: Option<usize>,
+    #[clap(
+        long = "grpc-compression",
+        env = "ANK_GRPC_COMPRESSION",
+        value_parser = parse_compression
+    )]
+    /// Compression of the messages sent to the Ankaios server, one of 'none' or 'gzip' [default: none]
+    pub grpc_compression: Option<Compression>,
+    #[clap(long = "proxy-url", env = "ANK_PROXY_URL")]
+    /// The url of the HTTP proxy to connect to the Ankaios server through. If not provided, the proxy of the HTTPS_PROXY or HTTP_PROXY environment variables is used. An empty url disables the proxy.
+    pub proxy_url: Option<String>,
+    #[clap(long = "ca-bundle", env = "ANK_CA_BUNDLE")]
+    /// Path to a pem file with additional ca certificates to trust, e.g. the one of a TLS intercepting proxy.
+    pub ca_bundle: Option<String>,
+}
+
+impl AnkCli {
+    // [impl->swdd~cli-supports-grpc-message-size-and-compression~1]
+    pub fn connection_config(&self) -> ConnectionConfig {
+        let default_config = ConnectionConfig::default();
+        ConnectionConfig {
+            max_message_size: self
+                .grpc_max_message_size
+                .unwrap_or(default_config.max_message_size),
+            compression: self.grpc_compression.unwrap_or(default_config.compression),
+            ..default_config
+        }
+    }
 }
 
 /// Supported actions
@@ -192,11 +120,41 @@ pub enum Commands {
     #[command(arg_required_else_help = true)]
     Set(SetArgs),
     #[command(arg_required_else_help = true)]
+    Edit(EditArgs),
+    #[command(arg_required_else_help = true)]
+    Exec(ExecArgs),
+    #[command(arg_required_else_help = true)]
+    PortForward(PortForwardArgs),
+    #[command(arg_required_else_help = true)]
     Delete(DeleteArgs),
     #[command(arg_required_else_help = true)]
     Run(RunArgs),
     #[command(arg_required_else_help = true)]
+    Scale(ScaleArgs),
+    #[command(arg_required_else_help = true)]
+    Restart(RestartArgs),
+    #[command(arg_required_else_help = true)]
+    Pause(PauseArgs),
+    #[command(arg_required_else_help = true)]
+    Resume(ResumeArgs),
+    #[command(arg_required_else_help = true)]
+    Describe(DescribeArgs),
+    #[command(arg_required_else_help = true)]
     Apply(ApplyArgs),
+    #[command(arg_required_else_help = true)]
+    Diff(DiffArgs),
+    #[command(arg_required_else_help = true)]
+    Check(CheckArgs),
+    Graph(GraphArgs),
+    Top(TopArgs),
+    #[command(arg_required_else_help = true)]
+    Wait(WaitArgs),
+    #[cfg(feature = "dev_mode")]
+    #[command(arg_required_else_help = true)]
+    Dev(DevArgs),
+    #[command(arg_required_else_help = true)]
+    Completion(CompletionArgs),
+    Schema(SchemaArgs),
 }
 
 /// Retrieve information about the current Ankaios system
@@ -205,12 +163,23 @@ pub enum Commands {
 pub struct GetArgs {
     #[command(subcommand)]
     pub command: Option<GetCommands>,
+    /// The timeout to wait for a response, e.g. '500ms', '10s' or '1m', overriding the response timeout of the CLI
+    #[arg(long = "timeout", global = true, value_parser = parse_duration)]
+    pub timeout: Option<Duration>,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
 pub enum OutputFormat {
     Yaml,
     Json,
+    /// One compact JSON document per line
+    JsonStream,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum EventsOutputFormat {
+    Table,
+    Json,
 }
 
 /// Get commands
@@ -221,6 +190,15 @@ pub enum GetCommands {
         /// Specify the output format
         #[arg(short = 'o', value_enum, default_value_t = OutputFormat::Yaml)]
         output_format: OutputFormat,
+        /// Keep running and output the state again whenever it changes until Ctrl+C is pressed
+        #[arg(short = 'w', long = "watch")]
+        watch: bool,
+        /// Output the desired state in an older API version, fields not supported by this version are omitted
+        #[arg(long = "target-api-version", value_parser = PossibleValuesParser::new(SUPPORTED_API_VERSIONS))]
+        target_api_version: Option<String>,
+        /// Only output the desired state of the given namespace, same as the object field mask 'desiredState.namespaces.<NAMESPACE>'
+        #[arg(short = 'n', long = "namespace", conflicts_with = "object_field_mask")]
+        namespace: Option<String>,
         /// Select which parts of the state object shall be output e.g. 'desiredState.workloads.nginx' [default: empty = the complete state]
         #[arg(add = ArgValueCompleter::new(object_field_mask_completer))]
         object_field_mask: Vec<String>,
@@ -230,23 +208,84 @@ pub enum GetCommands {
     #[clap(visible_alias("workloads"), verbatim_doc_comment)]
     Workload {
         /// Only workloads of the given agent shall be output
-        #[arg(short = 'a', long = "agent", required = false)]
+        #[arg(short = 'a', long = "agent", required = false, add = ArgValueCompleter::new(agent_completer))]
         agent_name: Option<String>,
         /// Only workloads in the given state shall be output
         #[arg(short = 's', long = "state", required = false)]
         state: Option<String>,
+        /// Only workloads of the given namespace shall be output
+        #[arg(short = 'n', long = "namespace", required = false)]
+        namespace: Option<String>,
         /// Select which workload(s) shall be returned [default: empty = all workloads]
         #[arg(add = ArgValueCompleter::new(workload_completer))]
         workload_name: Vec<String>,
+        /// Only output the workloads having the tag, given as key=value, e.g. 'team=adas'. Multiple tags can be separated by commas or given by repeating the option, all of them must match
+        #[arg(long = "selector", value_name = "KEY=VALUE", value_delimiter = ',', value_parser = parse_tag)]
+        selector: Vec<Tag>,
+        /// Output the last execution state transitions recorded by the Ankaios server instead of the current states
+        #[arg(long = "history", conflicts_with_all = ["state", "namespace", "selector"])]
+        history: bool,
+        /// Specify the output format: table, wide, yaml, json or custom-columns=<HEADER>:<field>,... e.g. custom-columns=NAME:.name
+        #[arg(short = 'o', long = "output", default_value = "table")]
+        output_format: ListOutputFormat,
     },
     /// Information about the Ankaios agents connected to the Ankaios server
     /// For automation use "ank get state -o json" and process the agents
     #[clap(visible_alias("agents"), verbatim_doc_comment)]
-    Agent {},
+    Agent {
+        /// Output the details of the given agent, e.g. its version, runtimes, load history and workloads, instead of the list of agents
+        #[arg(add = ArgValueCompleter::new(agent_completer))]
+        agent_name: Option<String>,
+        /// Output additional columns with the disk, network and temperature data of the agents, same as '-o wide'
+        #[arg(short = 'w', long = "wide")]
+        wide: bool,
+        /// Specify the output format: table, wide, yaml, json or custom-columns=<HEADER>:<field>,... e.g. custom-columns=NAME:.name
+        #[arg(short = 'o', long = "output", default_value = "table")]
+        output_format: ListOutputFormat,
+    },
     /// Information about the Ankaios configs present in the Ankaios system
     /// For automation use "ank get state -o json" and process desiredState.configs
     #[clap(visible_alias("configs"), verbatim_doc_comment)]
-    Config {},
+    Config {
+        /// Output the content of the given config as yaml or, with '-o json', as json instead of the list of configs
+        #[arg(add = ArgValueCompleter::new(config_completer))]
+        config_name: Option<String>,
+        /// Output the fields of the given workload rendered with its configs instead of the content of the config
+        #[arg(long = "rendered-for", value_name = "WORKLOAD", requires = "config_name", add = ArgValueCompleter::new(workload_completer))]
+        rendered_for: Option<String>,
+        /// Specify the output format: table, wide, yaml, json or custom-columns=<HEADER>:<field>,... e.g. custom-columns=NAME:.name
+        #[arg(short = 'o', long = "output", default_value = "table")]
+        output_format: ListOutputFormat,
+    },
+    /// Information about the images pinned on the Ankaios agents and their pull states
+    /// For automation use "ank get state -o json imageStates" and process the imageStates
+    #[clap(visible_alias("images"), verbatim_doc_comment)]
+    Image {
+        /// Only images pinned on the given agent shall be output
+        #[arg(short = 'a', long = "agent", required = false, add = ArgValueCompleter::new(agent_completer))]
+        agent_name: Option<String>,
+        /// Specify the output format: table, wide, yaml, json or custom-columns=<HEADER>:<field>,... e.g. custom-columns=NAME:.name
+        #[arg(short = 'o', long = "output", default_value = "table")]
+        output_format: ListOutputFormat,
+    },
+    /// Events of the Ankaios system recorded by the Ankaios server, e.g. added workloads or connected agents
+    Events {
+        /// Keep waiting for new events and output them as they occur
+        #[arg(short = 'f', long = "follow")]
+        follow: bool,
+        /// Specify the output format, the json format outputs one event per line
+        #[arg(short = 'o', value_enum, default_value_t = EventsOutputFormat::Table)]
+        output_format: EventsOutputFormat,
+    },
+    /// Audit log of the updates of the desired state accepted or rejected by the Ankaios server
+    Audit {
+        /// Keep waiting for new entries and output them as they are recorded
+        #[arg(short = 'f', long = "follow")]
+        follow: bool,
+        /// Specify the output format, the json format outputs one entry per line
+        #[arg(short = 'o', value_enum, default_value_t = EventsOutputFormat::Table)]
+        output_format: EventsOutputFormat,
+    },
 }
 
 /// Update the state of Ankaios system
@@ -271,6 +310,53 @@ pub enum SetCommands {
     },
 }
 
+/// Edit the state of Ankaios system in an editor
+#[derive(clap::Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct EditArgs {
+    #[command(subcommand)]
+    pub command: Option<EditCommands>,
+}
+
+/// Edit commands
+#[derive(Debug, Subcommand)]
+pub enum EditCommands {
+    /// Open the desired state in the editor set in $EDITOR and apply the changes
+    State {
+        /// Select which parts of the state object shall be edited e.g. 'desiredState.workloads.nginx', defaults to 'desiredState'
+        #[arg(add = ArgValueCompleter::new(object_field_mask_completer))]
+        object_field_mask: Vec<String>,
+    },
+}
+
+/// Execute a command inside a running workload, e.g. 'ank exec nginx -- sh'
+#[derive(clap::Args, Debug)]
+pub struct ExecArgs {
+    /// The name of the workload the command is executed in
+    #[arg(add = ArgValueCompleter::new(workload_completer))]
+    pub workload_name: String,
+    /// The command and its arguments, given after '--'
+    #[arg(last = true, required = true)]
+    pub command: Vec<String>,
+}
+
+/// Forward a local port to a port of a running workload, e.g. 'ank port-forward nginx 8080:80'
+#[derive(clap::Args, Debug)]
+pub struct PortForwardArgs {
+    /// The name of the workload the connections are forwarded to
+    #[arg(add = ArgValueCompleter::new(workload_completer))]
+    pub workload_name: String,
+    /// The local port and the port of the workload as '<local_port>:<workload_port>'
+    #[arg(value_parser = parse_port_mapping)]
+    pub ports: PortMapping,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortMapping {
+    pub local_port: u16,
+    pub workload_port: u16,
+}
+
 /// Delete the workload
 #[derive(clap::Args, Debug)]
 #[command(args_conflicts_with_subcommands = true)]
@@ -285,8 +371,15 @@ pub enum DeleteCommands {
     #[clap(visible_alias("workloads"))]
     Workload {
         /// One or more workload(s) to be deleted
-        #[arg(required = true, add = ArgValueCompleter::new(workload_completer))]
+        #[arg(required_unless_present = "selector", add = ArgValueCompleter::new(workload_completer))]
         workload_name: Vec<String>,
+        /// Only delete the workloads having the tag, given as key=value, e.g. 'team=adas'. Multiple tags can be separated by commas or given by repeating the option, all of them must match
+        #[arg(long = "selector", value_name = "KEY=VALUE", value_delimiter = ',', value_parser = parse_tag, conflicts_with = "workload_name")]
+        selector: Vec<Tag>,
+
+        /// Time in seconds the workload(s) get to stop gracefully before they are killed and removed
+        #[arg(long = "grace-period", value_name = "SECONDS")]
+        grace_period_secs: Option<u32>,
     },
     #[clap(visible_alias("configs"))]
     Config {
@@ -323,7 +416,7 @@ pub enum RunCommands {
         #[arg(long = "config")]
         runtime_config: String,
         /// Name of the agent where the workload is supposed to run
-        #[arg(long = "agent")]
+        #[arg(long = "agent", add = ArgValueCompleter::new(agent_completer))]
         agent_name: String,
         ///Tags formatted as: "--tags key1=value1 --tags key2=value2"
         #[arg(long = "tags", value_parser = parse_key_val::<String, String>)]
@@ -331,18 +424,291 @@ pub enum RunCommands {
     },
 }
 
+/// Scale the number of instances of a workload
+#[derive(clap::Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct ScaleArgs {
+    #[command(subcommand)]
+    pub command: Option<ScaleCommands>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ScaleCommands {
+    /// Set the number of instances of a workload, the additional instances are named '<workload name>-<index>'
+    Workload {
+        /// Name of the workload to scale
+        #[arg(required = true, add = ArgValueCompleter::new(workload_completer))]
+        workload_name: String,
+        /// The number of instances, 0 stops all instances of the workload
+        #[arg(long = "replicas", required = true)]
+        replicas: u32,
+    },
+}
+
+/// Restart a workload without changing the desired state
+#[derive(clap::Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct RestartArgs {
+    #[command(subcommand)]
+    pub command: Option<RestartCommands>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum RestartCommands {
+    /// Restart all instances of a workload with their current configuration
+    Workload {
+        /// Name of the workload to restart
+        #[arg(required = true, add = ArgValueCompleter::new(workload_completer))]
+        workload_name: String,
+    },
+}
+
+/// Pause a workload without changing the desired state
+#[derive(clap::Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct PauseArgs {
+    #[command(subcommand)]
+    pub command: Option<PauseCommands>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PauseCommands {
+    /// Freeze all instances of a workload until they are resumed
+    Workload {
+        /// Name of the workload to pause
+        #[arg(required = true, add = ArgValueCompleter::new(workload_completer))]
+        workload_name: String,
+    },
+}
+
+/// Resume a paused workload
+#[derive(clap::Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct ResumeArgs {
+    #[command(subcommand)]
+    pub command: Option<ResumeCommands>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ResumeCommands {
+    /// Resume all paused instances of a workload
+    Workload {
+        /// Name of the workload to resume
+        #[arg(required = true, add = ArgValueCompleter::new(workload_completer))]
+        workload_name: String,
+    },
+}
+
+/// Show a human-readable report about an object of the Ankaios system
+#[derive(clap::Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct DescribeArgs {
+    #[command(subcommand)]
+    pub command: Option<DescribeCommands>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DescribeCommands {
+    /// Show the spec, the execution states, the dependencies, the control interface access, the configs and the recent state transitions of a workload
+    Workload {
+        /// Name of the workload to describe
+        #[arg(required = true, add = ArgValueCompleter::new(workload_completer))]
+        workload_name: String,
+    },
+}
+
 /// Apply Ankaios manifest content or file(s)
 #[derive(clap::Args, Debug)]
 pub struct ApplyArgs {
     #[arg(value_name = "Ankaios manifest file(s) or '-' for stdin", value_hint = ValueHint::FilePath)]
     pub manifest_files: Vec<String>,
+    /// Ankaios manifest file or '-' for stdin, can be given multiple times
+    #[arg(
+        short = 'f',
+        long = "file",
+        value_name = "FILE",
+        value_hint = ValueHint::FilePath,
+        conflicts_with = "overlay_dir"
+    )]
+    pub files: Vec<String>,
+    /// Apply the manifest built from the resources and patches listed in the kustomization.yaml of the directory
+    #[arg(
+        short = 'k',
+        long = "kustomize",
+        value_name = "OVERLAY_DIR",
+        value_hint = ValueHint::DirPath,
+        conflicts_with = "manifest_files"
+    )]
+    pub overlay_dir: Option<String>,
     /// Specify on which agent to apply the Ankaios manifests.
     /// If not specified, the agent(s) must be specified in the Ankaios manifest(s)
-    #[arg(long = "agent")]
+    #[arg(long = "agent", add = ArgValueCompleter::new(agent_completer))]
     pub agent_name: Option<String>,
     /// Delete mode activated
     #[arg(short)]
     pub delete_mode: bool,
+    /// Only show the changes the Ankaios manifest(s) would cause without applying them
+    #[arg(long = "dry-run")]
+    pub dry_run: bool,
+    /// The timeout to wait for a response, e.g. '500ms', '10s' or '1m', overriding the response timeout of the CLI
+    #[arg(long = "timeout", value_parser = parse_duration)]
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum DiffOutputFormat {
+    /// Unified diff of the YAML of the changed workloads and configs
+    Unified,
+    /// Changed fields of the workloads and configs
+    Json,
+}
+
+/// Show the differences between the desired state and Ankaios manifest file(s)
+#[derive(clap::Args, Debug)]
+pub struct DiffArgs {
+    #[arg(value_name = "Ankaios manifest file(s) or '-' for stdin", value_hint = ValueHint::FilePath)]
+    pub manifest_files: Vec<String>,
+    /// Specify on which agent the Ankaios manifests are intended to be applied.
+    /// If not specified, the agent(s) must be specified in the Ankaios manifest(s)
+    #[arg(long = "agent", add = ArgValueCompleter::new(agent_completer))]
+    pub agent_name: Option<String>,
+    /// Specify the output format
+    #[arg(short = 'o', value_enum, default_value_t = DiffOutputFormat::Unified)]
+    pub output_format: DiffOutputFormat,
+    /// The timeout to wait for a response, e.g. '500ms', '10s' or '1m', overriding the response timeout of the CLI
+    #[arg(long = "timeout", value_parser = parse_duration)]
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum CheckOutputFormat {
+    Text,
+    Json,
+}
+
+/// Check Ankaios manifest file(s) offline without connecting to the Ankaios server
+#[derive(clap::Args, Debug)]
+pub struct CheckArgs {
+    #[arg(value_name = "Ankaios manifest file(s) or '-' for stdin", value_hint = ValueHint::FilePath)]
+    pub manifest_files: Vec<String>,
+    /// Specify on which agent the Ankaios manifests are intended to be applied.
+    /// If not specified, the agent(s) must be specified in the Ankaios manifest(s)
+    #[arg(long = "agent")]
+    pub agent_name: Option<String>,
+    /// Specify the output format of the found problems
+    #[arg(short = 'o', value_enum, default_value_t = CheckOutputFormat::Text)]
+    pub output_format: CheckOutputFormat,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum GraphOutputFormat {
+    Dot,
+    Json,
+}
+
+/// Output the dependency graph of the workloads annotated with their execution states
+#[derive(clap::Args, Debug)]
+pub struct GraphArgs {
+    /// Specify the output format
+    #[arg(short = 'o', value_enum, default_value_t = GraphOutputFormat::Dot)]
+    pub output_format: GraphOutputFormat,
+    /// The timeout to wait for a response, e.g. '500ms', '10s' or '1m', overriding the response timeout of the CLI
+    #[arg(long = "timeout", value_parser = parse_duration)]
+    pub timeout: Option<Duration>,
+}
+
+/// Display the CPU and memory usage of the running workloads, refreshed periodically
+#[derive(clap::Args, Debug)]
+pub struct TopArgs {
+    /// Only workloads of the given agent shall be output
+    #[arg(short = 'a', long = "agent", add = ArgValueCompleter::new(agent_completer))]
+    pub agent_name: Option<String>,
+    /// The interval between two refreshes, e.g. '500ms', '10s' or '1m'
+    #[arg(short = 'i', long = "interval", default_value = "2s", value_parser = parse_refresh_interval)]
+    pub interval: Duration,
+    /// Output the resource usage once instead of refreshing it periodically
+    #[arg(long = "once")]
+    pub once: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum WaitState {
+    Running,
+    Succeeded,
+    Failed,
+    Removed,
+}
+
+/// Wait until the given workloads reach the requested execution state
+#[derive(clap::Args, Debug)]
+pub struct WaitArgs {
+    /// The execution state the workloads shall reach
+    #[arg(long = "state", value_enum, default_value_t = WaitState::Running)]
+    pub state: WaitState,
+    /// The maximum time to wait, e.g. '500ms', '10s' or '1m'
+    #[arg(long = "timeout", default_value = "60s", value_parser = parse_duration)]
+    pub timeout: Duration,
+    /// One or more workload names to wait for
+    #[arg(required = true, add = ArgValueCompleter::new(workload_completer))]
+    pub workload_name: Vec<String>,
+}
+
+/// Run a local Ankaios setup for development
+#[cfg(feature = "dev_mode")]
+#[derive(clap::Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct DevArgs {
+    #[command(subcommand)]
+    pub command: Option<DevCommands>,
+}
+
+#[cfg(feature = "dev_mode")]
+#[derive(Debug, Subcommand)]
+pub enum DevCommands {
+    /// Run an Ankaios server and agent within the CLI process until Ctrl+C is pressed
+    Up(DevUpArgs),
+}
+
+#[cfg(feature = "dev_mode")]
+#[derive(clap::Args, Debug)]
+pub struct DevUpArgs {
+    /// Name of the started agent
+    #[arg(long = "agent", default_value = "agent_A")]
+    pub agent_name: String,
+    /// An Ankaios manifest used as startup config of the started server
+    #[arg(short = 'c', long = "startup-config", value_hint = ValueHint::FilePath)]
+    pub startup_config: Option<String>,
+    /// The loopback address, including the port, or the 'unix://' socket the started server serves the CLI at without TLS
+    #[arg(short = 'a', long = "address", default_value_t = DEFAULT_SOCKET_ADDRESS.parse().unwrap())]
+    pub address: ServerAddress,
+    /// The folder the started agent stores its runtime files in, which is created if it does not exist
+    #[arg(short = 'r', long = "run-folder", default_value = DEFAULT_DEV_MODE_RUN_FOLDER, value_hint = ValueHint::DirPath)]
+    pub run_folder: String,
+}
+
+/// Output the script to register the completions of the ank CLI in the given shell
+#[derive(clap::Args, Debug)]
+pub struct CompletionArgs {
+    /// The shell the completion script is generated for
+    #[arg(value_enum)]
+    pub shell: Shell,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum SchemaFormat {
+    Json,
+    Yaml,
+}
+
+/// Output the JSON schema of Ankaios manifests, e.g., for the validation in editors and CI pipelines
+#[derive(clap::Args, Debug)]
+pub struct SchemaArgs {
+    /// The API version of the manifests the schema is output for
+    #[arg(long = "api-version", default_value_t = common::objects::CURRENT_API_VERSION.to_string())]
+    pub api_version: String,
+    /// Specify the output format of the schema
+    #[arg(long = "format", value_enum, default_value_t = SchemaFormat::Json)]
+    pub format: SchemaFormat,
 }
 
 fn parse_key_val<K, V>(s: &str) -> Result<(K, V), Box<dyn Error + Send + Sync + 'static>>
@@ -358,6 +724,76 @@ where
     Ok((s[..pos].parse()?, s[pos + 1..].parse()?))
 }
 
+fn parse_compression(s: &str) -> Result<Compression, String> {
+    match s {
+        "none" => Ok(Compression::None),
+        "gzip" => Ok(Compression::Gzip),
+        _ => Err(format!("invalid compression `{s}`: use 'none' or 'gzip'")),
+    }
+}
+
+// [impl->swdd~cli-supports-per-command-response-timeout~2]
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    humantime::parse_duration(s).map_err(|err| format!("invalid duration `{s}`: {err}"))
+}
+
+fn parse_refresh_interval(s: &str) -> Result<Duration, String> {
+    let interval = parse_duration(s)?;
+    if interval.is_zero() {
+        return Err(format!("invalid interval `{s}`: must be greater than zero"));
+    }
+    Ok(interval)
+}
+
+// [impl->swdd~cli-provides-port-forward~1]
+fn parse_port_mapping(s: &str) -> Result<PortMapping, String> {
+    let parse_port = |port: &str| {
+        port.parse::<u16>()
+            .map_err(|_| format!("invalid port `{port}` in `{s}`"))
+    };
+    // a single port is used as local port and as port of the workload
+    let (local_port, workload_port) = s.split_once(':').unwrap_or((s, s));
+    Ok(PortMapping {
+        local_port: parse_port(local_port)?,
+        workload_port: parse_port(workload_port)?,
+    })
+}
+
+// [impl->swdd~cli-supports-workload-selector~1]
+fn parse_tag(s: &str) -> Result<Tag, String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok(Tag {
+            key: key.to_owned(),
+            value: value.to_owned(),
+        }),
+        _ => Err(format!("invalid tag `{s}`: expected key=value")),
+    }
+}
+
+impl Commands {
+    // [impl->swdd~cli-supports-per-command-response-timeout~2]
+    pub fn timeout(&self) -> Option<Duration> {
+        match self {
+            Commands::Get(get_args) => get_args.timeout,
+            Commands::Apply(apply_args) => apply_args.timeout,
+            Commands::Diff(diff_args) => diff_args.timeout,
+            Commands::Graph(graph_args) => graph_args.timeout,
+            _ => None,
+        }
+    }
+}
+
+impl ApplyArgs {
+    // [impl->swdd~cli-apply-accepts-manifest-files-with-file-option~1]
+    pub fn all_manifest_files(&self) -> Vec<String> {
+        self.manifest_files
+            .iter()
+            .chain(&self.files)
+            .cloned()
+            .collect()
+    }
+}
+
 pub fn parse() -> AnkCli {
     CompleteEnv::with_factory(AnkCli::command).complete();
     AnkCli::parse()
@@ -373,177 +809,409 @@ pub fn parse() -> AnkCli {
 #[cfg(test)]
 mod tests {
 
-    use super::{completions_object_field_mask, completions_workloads};
-    use clap_complete::CompletionCandidate;
-    use std::ffi::OsStr;
-
-    static WORKLOAD_STATE: &str = r#"
-        {
-          "desiredState": {
-            "apiVersion": "v0.1",
-            "workloads": {
-              "databroker": {
-                "agent": "agent_A",
-                "tags": [],
-                "dependencies": {},
-                "restartPolicy": "ALWAYS",
-                "runtime": "podman",
-                "runtimeConfig": "image: ghcr.io/eclipse/kuksa.val/databroker:0.4.1\ncommandArgs: [\"--insecure\"]\ncommandOptions: [\"--net=host\"]\n"
-              },
-              "speed-provider": {
-                  "agent": "agent_A",
-                  "tags": [],
-                  "dependencies": {
-                  "databroker": "ADD_COND_RUNNING"
-                  },
-                  "restartPolicy": "ALWAYS",
-                  "runtime": "podman",
-                  "runtimeConfig": "image: ghcr.io/eclipse-ankaios/speed-provider:0.1.1\ncommandOptions:\n  - \"--net=host\"\n  - \"-e\"\n  - \"SPEED_PROVIDER_MODE=auto\"\n"
-              }
-            }
-          }
-        }
-    "#;
+    use super::{
+        parse_duration, AnkCli, Commands, CompletionArgs, DeleteCommands, ExecArgs, GetCommands,
+        OutputFormat, PortForwardArgs, PortMapping, SchemaArgs, SchemaFormat, TopArgs, WaitArgs,
+        WaitState,
+    };
+    use clap::Parser;
+    use clap_complete::Shell;
+    use common::objects::Tag;
+    use grpc::connection_config::{Compression, ConnectionConfig};
+    use std::time::Duration;
 
-    // [utest->swdd~cli-shell-completion~1]
+    // [utest->swdd~cli-supports-per-command-response-timeout~2]
     #[test]
-    fn utest_completions_workloads() {
-        let state = WORKLOAD_STATE.as_bytes();
+    fn utest_parse_duration_supported_units() {
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("10s"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_duration("1m 30s"), Ok(Duration::from_secs(90)));
+    }
+
+    // [utest->swdd~cli-supports-per-command-response-timeout~2]
+    #[test]
+    fn utest_parse_duration_invalid_input() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("250").is_err());
+        assert!(parse_duration("10x").is_err());
+        assert!(parse_duration("-10s").is_err());
+    }
+
+    // [utest->swdd~cli-supports-per-command-response-timeout~2]
+    #[test]
+    fn utest_timeout_argument_of_get_and_apply_commands() {
+        let args = AnkCli::try_parse_from(["ank", "get", "state", "--timeout", "10s"]).unwrap();
+        assert_eq!(args.command.timeout(), Some(Duration::from_secs(10)));
+
+        let args = AnkCli::try_parse_from(["ank", "get", "workloads", "--timeout", "1m"]).unwrap();
+        assert_eq!(args.command.timeout(), Some(Duration::from_secs(60)));
+
+        let args = AnkCli::try_parse_from(["ank", "apply", "--timeout", "500ms", "manifest.yaml"])
+            .unwrap();
+        assert_eq!(args.command.timeout(), Some(Duration::from_millis(500)));
+
+        let args = AnkCli::try_parse_from(["ank", "get", "state"]).unwrap();
+        assert!(matches!(args.command, Commands::Get(_)));
+        assert_eq!(args.command.timeout(), None);
+    }
 
-        let mut completions = completions_workloads(state.to_vec(), OsStr::new(""));
-        completions.sort();
+    // [utest->swdd~cli-apply-accepts-manifest-files-with-file-option~1]
+    #[test]
+    fn utest_apply_command_combines_manifest_files_and_file_options() {
+        let args = AnkCli::try_parse_from([
+            "ank",
+            "apply",
+            "-f",
+            "workloads.yaml",
+            "--file",
+            "-",
+            "configs.yaml",
+        ])
+        .unwrap();
+        let Commands::Apply(apply_args) = args.command else {
+            panic!("Expected the apply command");
+        };
         assert_eq!(
-            completions,
-            vec![
-                CompletionCandidate::new("databroker"),
-                CompletionCandidate::new("speed-provider")
-            ],
-            "Completions do not match"
+            apply_args.all_manifest_files(),
+            vec!["configs.yaml", "workloads.yaml", "-"]
+        );
+
+        assert!(
+            AnkCli::try_parse_from(["ank", "apply", "-f", "manifest.yaml", "-k", "overlay"])
+                .is_err()
         );
     }
 
-    // [utest->swdd~cli-shell-completion~1]
+    // [utest->swdd~cli-supports-grpc-message-size-and-compression~1]
     #[test]
-    fn utest_completions_workloads_with_current() {
-        let state = WORKLOAD_STATE.as_bytes();
+    fn utest_grpc_message_size_and_compression_arguments() {
+        let args = AnkCli::try_parse_from(["ank", "get", "state"]).unwrap();
+        assert_eq!(args.connection_config(), ConnectionConfig::default());
 
-        let mut completions = completions_workloads(state.to_vec(), OsStr::new("d"));
-        completions.sort();
-        assert_eq!(
-            completions,
-            vec![CompletionCandidate::new("databroker"),],
-            "Completions do not match"
+        let args = AnkCli::try_parse_from([
+            "ank",
+            "--grpc-max-message-size",
+            "67108864",
+            "--grpc-compression",
+            "gzip",
+            "get",
+            "state",
+        ])
+        .unwrap();
+        let connection_config = args.connection_config();
+        assert_eq!(connection_config.max_message_size, 67108864);
+        assert_eq!(connection_config.compression, Compression::Gzip);
+
+        assert!(
+            AnkCli::try_parse_from(["ank", "--grpc-compression", "zstd", "get", "state"]).is_err()
         );
     }
 
-    // [utest->swdd~cli-shell-completion~1]
+    // [utest->swdd~cli-supports-proxy-and-ca-bundle-arguments~1]
     #[test]
-    fn utest_completions_workloads_invalid_input() {
-        let state = "".as_bytes();
+    fn utest_proxy_url_and_ca_bundle_arguments() {
+        let args = AnkCli::try_parse_from([
+            "ank",
+            "--proxy-url",
+            "http://proxy.corp:3128",
+            "--ca-bundle",
+            "/etc/ssl/corporate-ca.pem",
+            "get",
+            "state",
+        ])
+        .unwrap();
+        assert_eq!(args.proxy_url.as_deref(), Some("http://proxy.corp:3128"));
+        assert_eq!(args.ca_bundle.as_deref(), Some("/etc/ssl/corporate-ca.pem"));
 
-        let mut completions = completions_workloads(state.to_vec(), OsStr::new("d"));
-        completions.sort();
-        assert_eq!(completions, vec![], "Completions do not match");
+        let args = AnkCli::try_parse_from(["ank", "--proxy-url", "", "get", "state"]).unwrap();
+        assert_eq!(args.proxy_url.as_deref(), Some(""));
     }
 
-    static OBJECT_FIELD_MASK_STATE: &str = r#"
-        {
-          "desiredState": {
-            "apiVersion": "v0.1",
-            "workloads": {
-              "databroker": {
-                "agent": "agent_A",
-                "tags": [],
-                "dependencies": {},
-                "restartPolicy": "ALWAYS",
-                "runtime": "podman",
-                "runtimeConfig": "image: ghcr.io/eclipse/kuksa.val/databroker:0.4.1\ncommandArgs: [\"--insecure\"]\ncommandOptions: [\"--net=host\"]\n"
-              },
-              "speed-provider": {
-                "agent": "agent_A",
-                "tags": [],
-                "dependencies": {
-                  "databroker": "ADD_COND_RUNNING"
-                },
-                "restartPolicy": "ALWAYS",
-                "runtime": "podman",
-                "runtimeConfig": "image: ghcr.io/eclipse-ankaios/speed-provider:0.1.1\ncommandOptions:\n  - \"--net=host\"\n  - \"-e\"\n  - \"SPEED_PROVIDER_MODE=auto\"\n"
-              }
-            }
-          },
-          "workloadStates": {
-            "agent_A": {
-              "databroker": {
-                "211c1e7c1170508711b76bb9be19ad73af7a2b11e3c2a4fb895d0ce5f4894eaa": {
-                  "state": "Running",
-                  "subState": "Ok",
-                  "additionalInfo": ""
-                }
-              },
-              "speed-provider": {
-                "4bc1b2047e6a67b60b7a6c3b07955a2f29040ab7a2b6bc7d1bee78efc81a48d9": {
-                  "state": "Running",
-                  "subState": "Ok",
-                  "additionalInfo": ""
-                }
-              }
-            }
-          }
-        }
-    "#;
+    // [utest->swdd~cli-supports-workload-selector~1]
+    #[test]
+    fn utest_selector_argument_of_get_and_delete_workload() {
+        let tag = |key: &str, value: &str| Tag {
+            key: key.to_owned(),
+            value: value.to_owned(),
+        };
 
-    // [utest->swdd~cli-shell-completion~1]
+        let args = AnkCli::try_parse_from([
+            "ank",
+            "get",
+            "workloads",
+            "--selector",
+            "team=adas,env=",
+            "--selector",
+            "tier=a=b",
+        ])
+        .unwrap();
+        let Commands::Get(get_args) = args.command else {
+            panic!("Expected the get command");
+        };
+        assert!(matches!(
+            get_args.command,
+            Some(GetCommands::Workload { selector, .. })
+                if selector == vec![tag("team", "adas"), tag("env", ""), tag("tier", "a=b")]
+        ));
+
+        let args = AnkCli::try_parse_from(["ank", "delete", "workload", "--selector", "team=adas"])
+            .unwrap();
+        let Commands::Delete(delete_args) = args.command else {
+            panic!("Expected the delete command");
+        };
+        assert!(matches!(
+            delete_args.command,
+            Some(DeleteCommands::Workload { workload_name, selector, .. })
+                if workload_name.is_empty() && selector == vec![tag("team", "adas")]
+        ));
+
+        assert!(AnkCli::try_parse_from(["ank", "delete", "workload"]).is_err());
+        assert!(AnkCli::try_parse_from([
+            "ank",
+            "delete",
+            "workload",
+            "nginx",
+            "--selector",
+            "a=b"
+        ])
+        .is_err());
+        assert!(AnkCli::try_parse_from(["ank", "get", "workloads", "--selector", "team"]).is_err());
+    }
+
+    // [utest->swdd~cli-refreshes-workload-resources-periodically~1]
     #[test]
-    fn utest_completions_object_field_mask() {
-        let state = OBJECT_FIELD_MASK_STATE.as_bytes();
+    fn utest_top_command_default_and_custom_interval() {
+        let args = AnkCli::try_parse_from(["ank", "top"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Commands::Top(TopArgs {
+                agent_name: None,
+                interval,
+                once: false,
+            }) if interval == Duration::from_secs(2)
+        ));
 
-        let mut completions = completions_object_field_mask(state.to_vec(), OsStr::new(""));
-        completions.sort();
-        assert_eq!(
-            completions,
-            vec![
-                CompletionCandidate::new("configs"),
-                CompletionCandidate::new("desiredState"),
-                CompletionCandidate::new("desiredState.workloads"),
-                CompletionCandidate::new("desiredState.workloads.databroker"),
-                CompletionCandidate::new("desiredState.workloads.speed-provider"),
-                CompletionCandidate::new("workloadStates"),
-                CompletionCandidate::new("workloadStates.agent_A"),
-                CompletionCandidate::new("workloadStates.agent_A.databroker"),
-                CompletionCandidate::new("workloadStates.agent_A.speed-provider"),
-            ],
-            "Completions do not match"
-        );
+        let args =
+            AnkCli::try_parse_from(["ank", "top", "--agent", "agent_A", "-i", "500ms", "--once"])
+                .unwrap();
+        assert!(matches!(
+            args.command,
+            Commands::Top(TopArgs {
+                agent_name: Some(agent_name),
+                interval,
+                once: true,
+            }) if agent_name == "agent_A" && interval == Duration::from_millis(500)
+        ));
+
+        assert!(AnkCli::try_parse_from(["ank", "top", "-i", "0s"]).is_err());
     }
 
-    // [utest->swdd~cli-shell-completion~1]
+    // [utest->swdd~cli-watches-state~1]
     #[test]
-    fn utest_completions_object_field_mask_with_current() {
-        let state = OBJECT_FIELD_MASK_STATE.as_bytes();
+    fn utest_get_state_watch_with_json_stream() {
+        let args = AnkCli::try_parse_from([
+            "ank",
+            "get",
+            "state",
+            "--watch",
+            "-o",
+            "json-stream",
+            "workloadStates",
+        ])
+        .unwrap();
+        let Commands::Get(get_args) = args.command else {
+            panic!("Expected the get command");
+        };
+        assert!(matches!(
+            get_args.command,
+            Some(GetCommands::State {
+                output_format: OutputFormat::JsonStream,
+                watch: true,
+                target_api_version: None,
+                namespace: None,
+                object_field_mask,
+            }) if object_field_mask == ["workloadStates"]
+        ));
+    }
 
-        let mut completions =
-            completions_object_field_mask(state.to_vec(), OsStr::new("workloadStates"));
-        completions.sort();
-        assert_eq!(
-            completions,
-            vec![
-                CompletionCandidate::new("workloadStates"),
-                CompletionCandidate::new("workloadStates.agent_A"),
-                CompletionCandidate::new("workloadStates.agent_A.databroker"),
-                CompletionCandidate::new("workloadStates.agent_A.speed-provider"),
-            ],
-            "Completions do not match"
+    // [utest->swdd~cli-outputs-state-in-target-api-version~1]
+    #[test]
+    fn utest_get_state_with_target_api_version() {
+        let args = AnkCli::try_parse_from(["ank", "get", "state", "--target-api-version", "v0.1"])
+            .unwrap();
+        let Commands::Get(get_args) = args.command else {
+            panic!("Expected the get command");
+        };
+        assert!(matches!(
+            get_args.command,
+            Some(GetCommands::State {
+                target_api_version: Some(target_api_version),
+                ..
+            }) if target_api_version == "v0.1"
+        ));
+
+        assert!(
+            AnkCli::try_parse_from(["ank", "get", "state", "--target-api-version", "v9"]).is_err()
         );
     }
 
-    // [utest->swdd~cli-shell-completion~1]
+    // [utest->swdd~cli-filters-by-namespace~1]
+    #[test]
+    fn utest_get_state_and_workloads_with_namespace() {
+        let args = AnkCli::try_parse_from(["ank", "get", "state", "-n", "team-a"]).unwrap();
+        let Commands::Get(get_args) = args.command else {
+            panic!("Expected the get command");
+        };
+        assert!(matches!(
+            get_args.command,
+            Some(GetCommands::State {
+                namespace: Some(namespace),
+                ..
+            }) if namespace == "team-a"
+        ));
+
+        let args =
+            AnkCli::try_parse_from(["ank", "get", "workloads", "--namespace", "team-a"]).unwrap();
+        let Commands::Get(get_args) = args.command else {
+            panic!("Expected the get command");
+        };
+        assert!(matches!(
+            get_args.command,
+            Some(GetCommands::Workload {
+                namespace: Some(namespace),
+                ..
+            }) if namespace == "team-a"
+        ));
+
+        assert!(AnkCli::try_parse_from([
+            "ank",
+            "get",
+            "state",
+            "-n",
+            "team-a",
+            "desiredState.workloads"
+        ])
+        .is_err());
+    }
+
+    // [utest->swdd~cli-waits-for-workload-states~1]
+    #[test]
+    fn utest_wait_command_default_and_custom_args() {
+        let args = AnkCli::try_parse_from(["ank", "wait", "workload_1"]).unwrap();
+        assert!(matches!(
+            &args.command,
+            Commands::Wait(WaitArgs {
+                state: WaitState::Running,
+                timeout,
+                workload_name,
+            }) if *timeout == Duration::from_secs(60) && *workload_name == ["workload_1"]
+        ));
+        assert_eq!(args.command.timeout(), None);
+
+        let args = AnkCli::try_parse_from([
+            "ank",
+            "wait",
+            "--state",
+            "removed",
+            "--timeout",
+            "5s",
+            "workload_1",
+            "workload_2",
+        ])
+        .unwrap();
+        assert!(matches!(
+            args.command,
+            Commands::Wait(WaitArgs {
+                state: WaitState::Removed,
+                timeout,
+                workload_name,
+            }) if timeout == Duration::from_secs(5) && workload_name == ["workload_1", "workload_2"]
+        ));
+
+        assert!(AnkCli::try_parse_from(["ank", "wait", "--state", "running"]).is_err());
+    }
+
+    // [utest->swdd~cli-provides-exec~1]
+    #[test]
+    fn utest_exec_command_takes_command_after_separator() {
+        let args = AnkCli::try_parse_from(["ank", "exec", "nginx", "--", "ls", "-l", "/"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Commands::Exec(ExecArgs {
+                workload_name,
+                command,
+            }) if workload_name == "nginx" && command == ["ls", "-l", "/"]
+        ));
+
+        assert!(AnkCli::try_parse_from(["ank", "exec", "nginx"]).is_err());
+    }
+
+    // [utest->swdd~cli-provides-port-forward~1]
+    #[test]
+    fn utest_port_forward_command_parses_ports() {
+        let args = AnkCli::try_parse_from(["ank", "port-forward", "nginx", "8080:80"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Commands::PortForward(PortForwardArgs {
+                workload_name,
+                ports: PortMapping {
+                    local_port: 8080,
+                    workload_port: 80,
+                },
+            }) if workload_name == "nginx"
+        ));
+
+        let args = AnkCli::try_parse_from(["ank", "port-forward", "nginx", "8080"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Commands::PortForward(PortForwardArgs {
+                ports: PortMapping {
+                    local_port: 8080,
+                    workload_port: 8080,
+                },
+                ..
+            })
+        ));
+
+        assert!(AnkCli::try_parse_from(["ank", "port-forward", "nginx", "8080:http"]).is_err());
+        assert!(AnkCli::try_parse_from(["ank", "port-forward", "nginx", "70000"]).is_err());
+    }
+
+    // [utest->swdd~cli-provides-completion-command~1]
+    #[test]
+    fn utest_completion_command_supported_shells() {
+        let args = AnkCli::try_parse_from(["ank", "completion", "zsh"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Commands::Completion(CompletionArgs { shell: Shell::Zsh })
+        ));
+
+        assert!(AnkCli::try_parse_from(["ank", "completion", "unknown"]).is_err());
+    }
+
+    // [utest->swdd~cli-provides-manifest-schema~1]
     #[test]
-    fn utest_completions_object_field_mask_invalid_input() {
-        let state = "".as_bytes();
+    fn utest_schema_command_default_and_custom_args() {
+        let args = AnkCli::try_parse_from(["ank", "schema"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Commands::Schema(SchemaArgs {
+                api_version,
+                format: SchemaFormat::Json,
+            }) if api_version == common::objects::CURRENT_API_VERSION
+        ));
 
-        let mut completions =
-            completions_object_field_mask(state.to_vec(), OsStr::new("workloadStates"));
-        completions.sort();
-        assert_eq!(completions, vec![], "Completions do not match");
+        let args =
+            AnkCli::try_parse_from(["ank", "schema", "--api-version", "v0.1", "--format", "yaml"])
+                .unwrap();
+        assert!(matches!(
+            args.command,
+            Commands::Schema(SchemaArgs {
+                api_version,
+                format: SchemaFormat::Yaml,
+            }) if api_version == "v0.1"
+        ));
     }
 }
@@ -18,6 +18,7 @@ use std::{
 };
 pub mod server_connection;
 mod wait_list;
+use grpc::connection_config::ConnectionConfig;
 use grpc::security::TLSConfig;
 mod cli_table;
 use tokio::time::interval;
@@ -25,24 +26,49 @@ use wait_list::WaitList;
 mod workload_table_row;
 use workload_table_row::WorkloadTableRow;
 mod agent_table_row;
+mod audit_table_row;
 mod config_table_row;
+mod event_table_row;
+mod image_table_row;
 mod wait_list_display;
+mod workload_history_table_row;
+mod workload_resources_table_row;
 
 // CLI commands implemented in another files
 mod apply_manifests;
 mod delete_configs;
 mod delete_workloads;
+mod describe_workload;
+mod diff_manifests;
+mod edit_state;
+mod exec;
 mod get_agents;
+mod get_audit;
 mod get_configs;
+mod get_dependency_graph;
+mod get_events;
+mod get_images;
 mod get_state;
 mod get_workloads;
+pub mod output;
+mod overlay;
+mod pause_workload;
+mod port_forward;
+mod request_tracer;
+mod restart_workload;
 mod run_workload;
+mod scale_workload;
 mod set_state;
+mod top;
+mod wait_workloads;
 
 use common::{
     communications_error::CommunicationMiddlewareError,
     from_server_interface::FromServer,
-    objects::{CompleteState, State, WorkloadInstanceName, WorkloadState, WorkloadStatesMap},
+    objects::{
+        qualified_workload_name, CompleteState, State, Tag, WorkloadInstanceName, WorkloadState,
+        WorkloadStatesMap,
+    },
 };
 
 use wait_list_display::WaitListDisplay;
@@ -74,32 +100,35 @@ pub const DESIRED_STATE_CONFIGS: &str = "desiredState.configs";
 pub const DESIRED_STATE_WORKLOADS: &str = "desiredState.workloads";
 
 pub fn get_input_sources(manifest_files: &[String]) -> Result<Vec<InputSourcePair>, String> {
-    if let Some(first_arg) = manifest_files.first() {
-        match first_arg.as_str() {
+    if manifest_files
+        .iter()
+        .filter(|file_path| file_path.as_str() == "-")
+        .count()
+        > 1
+    {
+        return Err("The manifest content can be read from stdin only once!".to_owned());
+    }
+
+    let mut res: Vec<InputSourcePair> = vec![];
+    for file_path in manifest_files.iter() {
+        match file_path.as_str() {
             // [impl->swdd~cli-apply-accepts-ankaios-manifest-content-from-stdin~1]
-            "-" => Ok(vec![("stdin".to_owned(), Box::new(std::io::stdin()))]),
+            "-" => res.push(("stdin".to_owned(), Box::new(std::io::stdin()))),
             // [impl->swdd~cli-apply-accepts-list-of-ankaios-manifests~1]
-            _ => {
-                let mut res: Vec<InputSourcePair> = vec![];
-                for file_path in manifest_files.iter() {
-                    match open_manifest(file_path) {
-                        Ok(open_file) => res.push(open_file),
-                        Err(err) => {
-                            return Err(match err.kind() {
-                                std::io::ErrorKind::NotFound => {
-                                    format!("File '{}' not found!", file_path)
-                                }
-                                _ => err.to_string(),
-                            });
+            _ => match open_manifest(file_path) {
+                Ok(open_file) => res.push(open_file),
+                Err(err) => {
+                    return Err(match err.kind() {
+                        std::io::ErrorKind::NotFound => {
+                            format!("File '{}' not found!", file_path)
                         }
-                    }
+                        _ => err.to_string(),
+                    });
                 }
-                Ok(res)
-            }
+            },
         }
-    } else {
-        Ok(vec![])
     }
+    Ok(res)
 }
 
 pub type InputSourcePair = (String, Box<dyn std::io::Read + Send + Sync + 'static>);
@@ -147,27 +176,34 @@ impl From<WorkloadStatesMap> for WorkloadInfos {
 
 // The CLI commands are implemented in the modules included above. The rest are the common function.
 pub struct CliCommands {
-    // Left here for the future use.
-    _response_timeout_ms: u64,
     no_wait: bool,
     server_connection: ServerConnection,
 }
 
 impl CliCommands {
+    #[allow(clippy::too_many_arguments)]
     pub fn init(
-        response_timeout_ms: u64,
+        response_timeout: Duration,
         cli_name: String,
         server_url: String,
         no_wait: bool,
+        connection_config: ConnectionConfig,
         tls_config: Option<TLSConfig>,
+        proxy_url: Option<String>,
+        ca_bundle: Option<String>,
+        debug_requests: bool,
     ) -> Result<Self, CommunicationMiddlewareError> {
         Ok(Self {
-            _response_timeout_ms: response_timeout_ms,
             no_wait,
             server_connection: ServerConnection::new(
                 cli_name.as_str(),
                 server_url.clone(),
+                connection_config,
                 tls_config,
+                proxy_url,
+                ca_bundle,
+                response_timeout,
+                debug_requests,
             )?,
         })
     }
@@ -186,6 +222,23 @@ impl CliCommands {
         Ok(self.transform_into_workload_infos(res_complete_state))
     }
 
+    // [impl->swdd~cli-supports-workload-selector~1]
+    async fn get_selected_workloads(
+        &mut self,
+        selector: &[Tag],
+    ) -> Result<WorkloadInfos, CliError> {
+        if selector.is_empty() {
+            return self.get_workloads().await;
+        }
+
+        let res_complete_state = self
+            .server_connection
+            .get_selected_complete_state(&[], selector)
+            .await?;
+
+        Ok(self.transform_into_workload_infos(res_complete_state))
+    }
+
     // [impl->swdd~processes-complete-state-to-list-workloads~1]
     fn transform_into_workload_infos(
         &self,
@@ -194,10 +247,28 @@ impl CliCommands {
         let workload_states_map = complete_state.workload_states.unwrap_or_default();
         let workload_infos = WorkloadInfos::from(workload_states_map);
 
-        let desired_state_workloads = complete_state
+        let (mut desired_state_workloads, namespaces) = complete_state
             .desired_state
-            .and_then(|desired_state| desired_state.workloads)
+            .map(|desired_state| {
+                (
+                    desired_state.workloads.unwrap_or_default(),
+                    desired_state.namespaces.unwrap_or_default(),
+                )
+            })
             .unwrap_or_default();
+        // the workload states contain the workloads of the namespaces under their qualified names
+        for (namespace_name, namespace) in namespaces {
+            desired_state_workloads.extend(
+                namespace.workloads.unwrap_or_default().into_iter().map(
+                    |(workload_name, workload)| {
+                        (
+                            qualified_workload_name(&namespace_name, &workload_name),
+                            workload,
+                        )
+                    },
+                ),
+            );
+        }
 
         self.add_runtime_name_to_workload_infos(workload_infos, desired_state_workloads)
     }
@@ -216,7 +287,7 @@ impl CliCommands {
                         && wl_spec
                             .agent
                             .as_deref()
-                            .map_or(false, |x| x == table_row.agent)
+                            .is_some_and(|x| x == table_row.agent)
                         && wl_spec.runtime.as_ref().is_some()
                 })
                 // runtime is valid because the filter above has found one
@@ -234,6 +305,7 @@ impl CliCommands {
         &mut self,
         new_state: CompleteState,
         update_mask: Vec<String>,
+        grace_period_secs: Option<u32>,
     ) -> Result<(), CliError> {
         /* to keep track of deleted not initially started workloads in the wait mode
         the current workloads before the update must be stored in an ordered map. Affects only user output.
@@ -241,10 +313,18 @@ impl CliCommands {
         let current_workload_infos: BTreeMap<WorkloadInstanceName, WorkloadTableRow> =
             self.get_workloads().await?.into_iter().collect();
 
-        let update_state_success = self
-            .server_connection
-            .update_state(new_state, update_mask)
-            .await?;
+        let update_state_success = match grace_period_secs {
+            Some(grace_period_secs) => {
+                self.server_connection
+                    .update_state_with_grace_period(new_state, update_mask, grace_period_secs)
+                    .await?
+            }
+            None => {
+                self.server_connection
+                    .update_state(new_state, update_mask)
+                    .await?
+            }
+        };
 
         output_debug!("Got update success: {:?}", update_state_success);
 
@@ -301,7 +381,7 @@ impl CliCommands {
         let new_workload_infos = self.transform_into_workload_infos(new_complete_state);
 
         // new workloads were added through an updated state or the previous ones might already have reached the next state
-        previous_workload_infos.extend(new_workload_infos.into_iter());
+        previous_workload_infos.extend(new_workload_infos);
 
         let changed_workload_infos = previous_workload_infos
             .into_iter()
@@ -431,6 +511,53 @@ mod tests {
         )
     }
 
+    // [utest->swdd~cli-apply-accepts-ankaios-manifest-content-from-stdin~1]
+    // [utest->swdd~cli-apply-accepts-list-of-ankaios-manifests~1]
+    #[tokio::test]
+    async fn utest_apply_args_get_input_sources_manifest_files_and_stdin() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let _dummy_content = io::Cursor::new(b"manifest content");
+        for i in 1..3 {
+            FAKE_OPEN_MANIFEST_MOCK_RESULT_LIST
+                .lock()
+                .unwrap()
+                .push_back(Ok((
+                    format!("manifest{i}.yml"),
+                    Box::new(_dummy_content.clone()),
+                )));
+        }
+
+        let expected = vec![
+            "manifest1.yml".to_owned(),
+            "stdin".to_owned(),
+            "manifest2.yml".to_owned(),
+        ];
+        let actual = get_input_sources(&[
+            "manifest1.yml".to_owned(),
+            "-".to_owned(),
+            "manifest2.yml".to_owned(),
+        ])
+        .unwrap();
+
+        let get_file_name = |item: &InputSourcePair| -> String { item.0.to_owned() };
+        assert_eq!(
+            expected,
+            actual.iter().map(get_file_name).collect::<Vec<String>>()
+        )
+    }
+
+    // [utest->swdd~cli-apply-accepts-ankaios-manifest-content-from-stdin~1]
+    #[test]
+    fn utest_apply_args_get_input_sources_stdin_only_once() {
+        assert!(
+            get_input_sources(&["-".to_owned(), "-".to_owned()]).is_err(),
+            "Expected an error"
+        );
+    }
+
     // [utest->swdd~cli-apply-accepts-list-of-ankaios-manifests~1]
     #[tokio::test]
     async fn utest_apply_args_get_input_sources_manifest_files_ok() {
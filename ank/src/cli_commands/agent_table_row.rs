@@ -12,12 +12,15 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+use serde::Serialize;
 use tabled::Tabled;
 
-#[derive(Debug, Tabled, Clone)]
+#[derive(Debug, Tabled, Clone, Serialize)]
 #[tabled(rename_all = "UPPERCASE")]
+#[serde(rename_all = "camelCase")]
 pub struct AgentTableRow {
     #[tabled(rename = "NAME")]
+    #[serde(rename = "name")]
     pub agent_name: String,
     #[tabled(rename = "WORKLOADS")]
     pub workloads: u32,
@@ -26,3 +29,20 @@ pub struct AgentTableRow {
     #[tabled(rename = "FREE MEMORY")]
     pub free_memory: String,
 }
+
+#[derive(Debug, Tabled, Clone, Serialize)]
+#[tabled(rename_all = "UPPERCASE")]
+#[serde(rename_all = "camelCase")]
+pub struct WideAgentTableRow {
+    #[tabled(inline)]
+    #[serde(flatten)]
+    pub agent_table_row: AgentTableRow,
+    #[tabled(rename = "DISK AVAILABLE")]
+    pub disk_available: String,
+    #[tabled(rename = "NETWORK RX")]
+    pub network_received: String,
+    #[tabled(rename = "NETWORK TX")]
+    pub network_transmitted: String,
+    #[tabled(rename = "TEMPERATURE")]
+    pub temperature: String,
+}
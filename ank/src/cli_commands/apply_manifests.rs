@@ -12,13 +12,17 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use super::{CliCommands, InputSourcePair};
+use super::{overlay::build_overlay_manifest, CliCommands, InputSourcePair};
 use crate::cli_commands::State;
 use crate::cli_error::CliError;
 use crate::output;
 use crate::{cli::ApplyArgs, output_debug};
-use common::objects::{CompleteState, CURRENT_API_VERSION, STR_RE_WORKLOAD};
+use api::ank_base;
+use common::objects::{
+    CompleteState, WorkloadInstanceName, STR_RE_WORKLOAD, SUPPORTED_API_VERSIONS,
+};
 use common::state_manipulation::{Object, Path};
+use serde::Deserialize;
 use std::collections::HashSet;
 
 #[cfg(test)]
@@ -30,11 +34,22 @@ use super::get_input_sources;
 const WORKLOAD_LEVEL: usize = 1;
 
 // [impl->swdd~cli-apply-supports-ankaios-manifest~1]
-// [impl->swdd~cli-apply-manifest-check-for-api-version-compatibility~1]
-pub fn parse_manifest(manifest: &mut InputSourcePair) -> Result<(Object, Vec<Path>), String> {
-    let state_obj_parsing_check: serde_yaml::Value = serde_yaml::from_reader(&mut manifest.1)
-        .map_err(|err| format!("Invalid manifest data provided: {}", err))?;
-    match Object::try_from(&state_obj_parsing_check) {
+// [impl->swdd~cli-apply-accepts-multi-document-ankaios-manifests~1]
+pub fn parse_manifest(manifest: &mut InputSourcePair) -> Result<Vec<(Object, Vec<Path>)>, String> {
+    let mut documents = Vec::new();
+    for document in serde_yaml::Deserializer::from_reader(&mut manifest.1) {
+        let state_obj_parsing_check = serde_yaml::Value::deserialize(document)
+            .map_err(|err| format!("Invalid manifest data provided: {}", err))?;
+        documents.push(parse_manifest_document(&state_obj_parsing_check)?);
+    }
+    Ok(documents)
+}
+
+// [impl->swdd~cli-apply-manifest-check-for-api-version-compatibility~2]
+fn parse_manifest_document(
+    state_obj_parsing_check: &serde_yaml::Value,
+) -> Result<(Object, Vec<Path>), String> {
+    match Object::try_from(state_obj_parsing_check) {
         Err(err) => Err(format!(
             "Error while parsing the manifest data.\nError: {err}"
         )),
@@ -51,10 +66,12 @@ pub fn parse_manifest(manifest: &mut InputSourcePair) -> Result<(Object, Vec<Pat
                         .get(&path)
                         .and_then(|value| value.as_str())
                         .unwrap_or("Invalid manifest API version or format provided.");
-                    if manifest_api_version != CURRENT_API_VERSION {
+                    // older API versions are migrated by the server
+                    if !SUPPORTED_API_VERSIONS.contains(&manifest_api_version) {
                         return Err(format!(
-                            "Invalid manifest API version provided. Expected: '{}', got: '{}'.",
-                            CURRENT_API_VERSION, manifest_api_version
+                            "Invalid manifest API version provided. Expected one of: '{}', got: '{}'.",
+                            SUPPORTED_API_VERSIONS.join("', '"),
+                            manifest_api_version
                         ));
                     }
                 }
@@ -137,16 +154,18 @@ pub fn create_filter_masks_from_paths(
 // [impl->swdd~cli-apply-generates-filter-masks-from-ankaios-manifests~1]
 pub fn generate_state_obj_and_filter_masks_from_manifests(
     manifests: &mut [InputSourcePair],
-    apply_args: &ApplyArgs,
+    cli_specified_agent_name: &Option<String>,
+    delete_mode: bool,
 ) -> Result<Option<(CompleteState, Vec<String>)>, String> {
     let mut req_obj: Object = State::default().try_into().unwrap();
     let mut req_paths: Vec<common::state_manipulation::Path> = Vec::new();
+    // all documents of all manifests are combined into one request
     for manifest in manifests.iter_mut() {
-        let (cur_obj, mut cur_workload_paths) = parse_manifest(manifest)?;
-
-        update_request_obj(&mut req_obj, &cur_obj, &cur_workload_paths)?;
+        for (cur_obj, mut cur_workload_paths) in parse_manifest(manifest)? {
+            update_request_obj(&mut req_obj, &cur_obj, &cur_workload_paths)?;
 
-        req_paths.append(&mut cur_workload_paths);
+            req_paths.append(&mut cur_workload_paths);
+        }
     }
 
     if req_paths.is_empty() {
@@ -157,13 +176,13 @@ pub fn generate_state_obj_and_filter_masks_from_manifests(
     let filter_masks = create_filter_masks_from_paths(&req_paths, "desiredState");
     output_debug!("\nfilter_masks:\n{:?}\n", filter_masks);
 
-    let complete_state_req_obj = if apply_args.delete_mode {
+    let complete_state_req_obj = if delete_mode {
         CompleteState {
             ..Default::default()
         }
     } else {
         let state_from_req_obj =
-            handle_agent_overwrite(&req_paths, &apply_args.agent_name, req_obj)?;
+            handle_agent_overwrite(&req_paths, cli_specified_agent_name, req_obj)?;
         CompleteState {
             desired_state: state_from_req_obj,
             ..Default::default()
@@ -174,18 +193,96 @@ pub fn generate_state_obj_and_filter_masks_from_manifests(
     Ok(Some((complete_state_req_obj, filter_masks)))
 }
 
+// [impl->swdd~cli-apply-dry-run~1]
+pub fn format_dry_run_result(dry_run_result: &ank_base::UpdateStateDryRunResult) -> String {
+    if dry_run_result.added_workloads.is_empty()
+        && dry_run_result.deleted_workloads.is_empty()
+        && dry_run_result.updated_workloads.is_empty()
+    {
+        return "Dry run: no workloads would be changed.".to_string();
+    }
+
+    let mut lines = vec!["Dry run: the following changes would be applied.".to_string()];
+    for added_workload in &dry_run_result.added_workloads {
+        lines.push(format!("+ {}", format_instance_name(added_workload)));
+    }
+    for deleted_workload in &dry_run_result.deleted_workloads {
+        lines.push(format!("- {}", format_instance_name(deleted_workload)));
+    }
+    for updated_workload in &dry_run_result.updated_workloads {
+        lines.push(format!("~ {}", updated_workload.workload_name));
+        for field_diff in &updated_workload.field_diffs {
+            if field_diff.current_value.contains('\n') || field_diff.new_value.contains('\n') {
+                lines.push(format!("    {}:", field_diff.field));
+                lines.extend(
+                    field_diff
+                        .current_value
+                        .lines()
+                        .map(|line| format!("      - {line}")),
+                );
+                lines.extend(
+                    field_diff
+                        .new_value
+                        .lines()
+                        .map(|line| format!("      + {line}")),
+                );
+            } else {
+                lines.push(format!(
+                    "    {}: '{}' -> '{}'",
+                    field_diff.field, field_diff.current_value, field_diff.new_value
+                ));
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+fn format_instance_name(instance_name: &str) -> String {
+    match WorkloadInstanceName::try_from(instance_name) {
+        Ok(instance_name) => format!(
+            "{} (agent: {})",
+            instance_name.workload_name(),
+            instance_name.agent_name()
+        ),
+        Err(_) => instance_name.to_string(),
+    }
+}
+
 impl CliCommands {
     // [impl->swdd~cli-apply-accepts-list-of-ankaios-manifests~1]
     pub async fn apply_manifests(&mut self, apply_args: ApplyArgs) -> Result<(), CliError> {
-        match get_input_sources(&apply_args.manifest_files) {
+        let manifests = match &apply_args.overlay_dir {
+            // [impl->swdd~cli-apply-builds-manifest-from-overlay~1]
+            Some(overlay_dir) => build_overlay_manifest(overlay_dir).map(|manifest| vec![manifest]),
+            None => get_input_sources(&apply_args.all_manifest_files()),
+        };
+        match manifests {
             Ok(mut manifests) => {
                 if let Some((complete_state_req_obj, filter_masks)) =
-                    generate_state_obj_and_filter_masks_from_manifests(&mut manifests, &apply_args)
-                        .map_err(CliError::ExecutionError)?
+                    generate_state_obj_and_filter_masks_from_manifests(
+                        &mut manifests,
+                        &apply_args.agent_name,
+                        apply_args.delete_mode,
+                    )
+                    .map_err(CliError::ExecutionError)?
                 {
+                    if apply_args.dry_run {
+                        // [impl->swdd~cli-apply-dry-run~1]
+                        let dry_run_result = self
+                            .server_connection
+                            .update_state_dry_run(complete_state_req_obj, filter_masks)
+                            .await?;
+                        output!("{}", format_dry_run_result(&dry_run_result));
+                        return Ok(());
+                    }
+
                     // [impl->swdd~cli-apply-send-update-state~1]
-                    self.update_state_and_wait_for_complete(complete_state_req_obj, filter_masks)
-                        .await
+                    self.update_state_and_wait_for_complete(
+                        complete_state_req_obj,
+                        filter_masks,
+                        None,
+                    )
+                    .await
                 } else {
                     output!("Nothing to update.");
                     Ok(())
@@ -216,7 +313,7 @@ mod tests {
         from_server_interface::FromServer,
         objects::{
             self, generate_test_workload_spec_with_param, CompleteState, ExecutionState,
-            RunningSubstate, State, WorkloadState,
+            RunningSubstate, State, WorkloadState, SUPPORTED_API_VERSIONS,
         },
         state_manipulation::{Object, Path},
         test_utils,
@@ -227,8 +324,9 @@ mod tests {
         cli::ApplyArgs,
         cli_commands::{
             apply_manifests::{
-                create_filter_masks_from_paths, generate_state_obj_and_filter_masks_from_manifests,
-                handle_agent_overwrite, parse_manifest, update_request_obj,
+                create_filter_masks_from_paths, format_dry_run_result,
+                generate_state_obj_and_filter_masks_from_manifests, handle_agent_overwrite,
+                parse_manifest, update_request_obj,
             },
             server_connection::MockServerConnection,
             CliCommands, InputSourcePair,
@@ -251,7 +349,6 @@ mod tests {
             .unwrap()
     }
 
-    const RESPONSE_TIMEOUT_MS: u64 = 3000;
     const OTHER_REQUEST_ID: &str = "other_request_id";
 
     // [utest->swdd~cli-apply-supports-ankaios-manifest~1]
@@ -282,13 +379,47 @@ mod tests {
             "invalid_manifest_content".to_string(),
             Box::new(manifest_content),
         ))
-        .unwrap();
+        .unwrap()
+        .remove(0);
 
         assert!(TryInto::<State>::try_into(obj).is_err());
         assert!(paths.is_empty());
     }
 
-    // [utest->swdd~cli-apply-manifest-check-for-api-version-compatibility~1]
+    // [utest->swdd~cli-apply-accepts-multi-document-ankaios-manifests~1]
+    #[test]
+    fn utest_parse_manifest_multiple_documents() {
+        let manifest_content = io::Cursor::new(
+            b"apiVersion: v0.1\nworkloads:\n  simple:\n    agent: agent_A
+---
+apiVersion: v0.1\nconfigs:\n  config_1: value_1",
+        );
+
+        let documents = parse_manifest(&mut (
+            "multi_document_manifest".to_string(),
+            Box::new(manifest_content),
+        ))
+        .unwrap();
+
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0].1, vec![Path::from("workloads.simple")]);
+        assert_eq!(documents[1].1, vec![Path::from("configs.config_1")]);
+    }
+
+    // [utest->swdd~cli-apply-accepts-multi-document-ankaios-manifests~1]
+    // [utest->swdd~cli-apply-manifest-check-for-api-version-compatibility~2]
+    #[test]
+    fn utest_parse_manifest_invalid_api_version_in_second_document() {
+        let manifest_content = io::Cursor::new(b"apiVersion: v0.1\n---\napiVersion: v3");
+
+        assert!(parse_manifest(&mut (
+            "invalid_api_version".to_string(),
+            Box::new(manifest_content),
+        ))
+        .is_err());
+    }
+
+    // [utest->swdd~cli-apply-manifest-check-for-api-version-compatibility~2]
     #[test]
     fn utest_parse_manifest_invalid_api_version() {
         let manifest_content = io::Cursor::new(b"apiVersion: v3");
@@ -300,6 +431,20 @@ mod tests {
         .is_err());
     }
 
+    // [utest->swdd~cli-apply-manifest-check-for-api-version-compatibility~2]
+    #[test]
+    fn utest_parse_manifest_supported_api_versions() {
+        for api_version in SUPPORTED_API_VERSIONS {
+            let manifest_content = io::Cursor::new(format!("apiVersion: {}", api_version));
+
+            assert!(parse_manifest(&mut (
+                "supported_api_version".to_string(),
+                Box::new(manifest_content),
+            ))
+            .is_ok());
+        }
+    }
+
     #[test]
     fn utest_update_request_obj_ok() {
         let mut req_obj = Object::default();
@@ -550,7 +695,7 @@ mod tests {
     fn utest_generate_state_obj_and_filter_masks_from_manifests_ok() {
         let manifest_file_name = "manifest.yaml";
         let manifest_content = io::Cursor::new(
-            b"apiVersion: \"v0.1\"\nworkloads:
+            b"apiVersion: \"v0.2\"\nworkloads:
         simple:
           runtime: podman
           agent: agent_A
@@ -581,14 +726,7 @@ mod tests {
 
         assert_eq!(
             Ok(Some((expected_complete_state_obj, expected_filter_masks))),
-            generate_state_obj_and_filter_masks_from_manifests(
-                &mut manifests[..],
-                &ApplyArgs {
-                    agent_name: None,
-                    manifest_files: vec![manifest_file_name.to_string()],
-                    delete_mode: false,
-                },
-            )
+            generate_state_obj_and_filter_masks_from_manifests(&mut manifests[..], &None, false)
         );
     }
 
@@ -618,14 +756,81 @@ mod tests {
 
         assert_eq!(
             Ok(Some((expected_complete_state_obj, expected_filter_masks))),
-            generate_state_obj_and_filter_masks_from_manifests(
-                &mut manifests[..],
-                &ApplyArgs {
-                    agent_name: None,
-                    manifest_files: vec![manifest_file_name.to_string()],
-                    delete_mode: true,
-                },
+            generate_state_obj_and_filter_masks_from_manifests(&mut manifests[..], &None, true)
+        );
+    }
+
+    // [utest->swdd~cli-apply-accepts-multi-document-ankaios-manifests~1]
+    // [utest->swdd~cli-apply-generates-state-object-from-ankaios-manifests~1]
+    // [utest->swdd~cli-apply-generates-filter-masks-from-ankaios-manifests~1]
+    #[test]
+    fn utest_generate_state_obj_and_filter_masks_from_multi_document_manifests_ok() {
+        let multi_document_content = io::Cursor::new(
+            b"apiVersion: \"v0.2\"\nworkloads:
+  simple:
+    runtime: podman
+    agent: agent_A
+    runtimeConfig: \"\"
+---
+apiVersion: \"v0.2\"\nconfigs:\n  config_1: value_1",
+        );
+        let other_content = io::Cursor::new(
+            b"apiVersion: \"v0.2\"\nworkloads:
+  complex:
+    runtime: podman
+    agent: agent_B
+    runtimeConfig: \"\"",
+        );
+
+        let expected_complete_state_obj = CompleteState {
+            desired_state: serde_yaml::from_str(
+                "apiVersion: \"v0.2\"\nworkloads:
+  simple:
+    runtime: podman
+    agent: agent_A
+    runtimeConfig: \"\"
+  complex:
+    runtime: podman
+    agent: agent_B
+    runtimeConfig: \"\"
+configs:\n  config_1: value_1",
             )
+            .unwrap(),
+            ..Default::default()
+        };
+
+        let expected_filter_masks = vec![
+            "desiredState.configs.config_1".to_string(),
+            "desiredState.workloads.complex".to_string(),
+            "desiredState.workloads.simple".to_string(),
+        ];
+
+        let mut manifests: Vec<InputSourcePair> = vec![
+            ("stdin".to_string(), Box::new(multi_document_content)),
+            ("manifest.yaml".to_string(), Box::new(other_content)),
+        ];
+
+        assert_eq!(
+            Ok(Some((expected_complete_state_obj, expected_filter_masks))),
+            generate_state_obj_and_filter_masks_from_manifests(&mut manifests[..], &None, false)
+        );
+    }
+
+    // [utest->swdd~cli-apply-accepts-multi-document-ankaios-manifests~1]
+    #[test]
+    fn utest_generate_state_obj_and_filter_masks_from_manifests_same_workload_in_two_documents() {
+        let manifest_content = io::Cursor::new(
+            b"apiVersion: v0.1\nworkloads:\n  simple:\n    agent: agent_A
+---
+apiVersion: v0.1\nworkloads:\n  simple:\n    agent: agent_B",
+        );
+
+        let mut manifests: Vec<InputSourcePair> =
+            vec![("manifest.yaml".to_string(), Box::new(manifest_content))];
+
+        assert_eq!(
+            Err("Multiple workloads with the same name 'simple' found!".to_string()),
+            generate_state_obj_and_filter_masks_from_manifests(&mut manifests[..], &None, false)
         );
     }
 
@@ -693,7 +898,6 @@ mod tests {
             });
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
@@ -710,12 +914,114 @@ mod tests {
             .apply_manifests(ApplyArgs {
                 agent_name: None,
                 delete_mode: true,
+                dry_run: false,
+                timeout: None,
+                manifest_files: vec!["manifest_yaml".to_string()],
+                files: vec![],
+                overlay_dir: None,
+            })
+            .await;
+        assert!(apply_result.is_ok());
+    }
+
+    // [utest->swdd~cli-apply-dry-run~1]
+    #[tokio::test]
+    async fn utest_apply_manifests_dry_run_does_not_update_state() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let manifest_content = io::Cursor::new(
+            b"apiVersion: \"v0.1\"\nworkloads:
+    simple_manifest1:
+      runtime: podman
+      agent: agent_A
+      runtimeConfig: \"\"",
+        );
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_update_state_dry_run()
+            .with(
+                mockall::predicate::always(),
+                eq(vec!["desiredState.workloads.simple_manifest1".to_string()]),
+            )
+            .once()
+            .return_once(|_, _| {
+                Ok(ank_base::UpdateStateDryRunResult {
+                    added_workloads: vec!["simple_manifest1.abc.agent_A".to_string()],
+                    ..Default::default()
+                })
+            });
+        mock_server_connection.expect_update_state().never();
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        FAKE_GET_INPUT_SOURCE_MOCK_RESULT_LIST
+            .lock()
+            .unwrap()
+            .push_back(Ok(vec![(
+                "manifest.yml".to_string(),
+                Box::new(manifest_content),
+            )]));
+
+        let apply_result = cmd
+            .apply_manifests(ApplyArgs {
+                agent_name: None,
+                delete_mode: false,
+                dry_run: true,
+                timeout: None,
                 manifest_files: vec!["manifest_yaml".to_string()],
+                files: vec![],
+                overlay_dir: None,
             })
             .await;
         assert!(apply_result.is_ok());
     }
 
+    // [utest->swdd~cli-apply-dry-run~1]
+    #[test]
+    fn utest_format_dry_run_result() {
+        let dry_run_result = ank_base::UpdateStateDryRunResult {
+            added_workloads: vec!["nginx.abc.agent_A".to_string()],
+            deleted_workloads: vec!["sleepy.def.agent_B".to_string()],
+            updated_workloads: vec![ank_base::WorkloadDiff {
+                workload_name: "api".to_string(),
+                field_diffs: vec![
+                    ank_base::FieldDiff {
+                        field: "restartPolicy".to_string(),
+                        current_value: "NEVER".to_string(),
+                        new_value: "ALWAYS".to_string(),
+                    },
+                    ank_base::FieldDiff {
+                        field: "runtimeConfig".to_string(),
+                        current_value: "image: api:1\n".to_string(),
+                        new_value: "image: api:2\n".to_string(),
+                    },
+                ],
+            }],
+        };
+
+        assert_eq!(
+            format_dry_run_result(&dry_run_result),
+            "Dry run: the following changes would be applied.
++ nginx (agent: agent_A)
+- sleepy (agent: agent_B)
+~ api
+    restartPolicy: 'NEVER' -> 'ALWAYS'
+    runtimeConfig:
+      - image: api:1
+      + image: api:2"
+        );
+        assert_eq!(
+            format_dry_run_result(&ank_base::UpdateStateDryRunResult::default()),
+            "Dry run: no workloads would be changed."
+        );
+    }
+
     //[utest->swdd~cli-apply-send-update-state~1]
     // [utest->swdd~cli-watches-workloads~1]
     #[tokio::test]
@@ -725,7 +1031,7 @@ mod tests {
             .await;
 
         let manifest_content = io::Cursor::new(
-            b"apiVersion: \"v0.1\"\nworkloads:
+            b"apiVersion: \"v0.2\"\nworkloads:
         simple_manifest1:
           runtime: podman
           agent: agent_A
@@ -805,7 +1111,6 @@ mod tests {
             });
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
@@ -822,7 +1127,11 @@ mod tests {
             .apply_manifests(ApplyArgs {
                 agent_name: None,
                 delete_mode: false,
+                dry_run: false,
+                timeout: None,
                 manifest_files: vec!["manifest_yaml".to_string()],
+                files: vec![],
+                overlay_dir: None,
             })
             .await;
         assert!(apply_result.is_ok());
@@ -838,7 +1147,7 @@ mod tests {
             .await;
 
         let manifest_content = io::Cursor::new(
-            b"apiVersion: \"v0.1\"\nworkloads: {}\nconfigs:\n  config_1: config_value_1",
+            b"apiVersion: \"v0.2\"\nworkloads: {}\nconfigs:\n  config_1: config_value_1",
         );
 
         let mut manifest_data = String::new();
@@ -871,7 +1180,6 @@ mod tests {
             .never();
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: true,
             server_connection: mock_server_connection,
         };
@@ -888,7 +1196,11 @@ mod tests {
             .apply_manifests(ApplyArgs {
                 agent_name: None,
                 delete_mode: false,
+                dry_run: false,
+                timeout: None,
                 manifest_files: vec!["manifest_yaml".to_string()],
+                files: vec![],
+                overlay_dir: None,
             })
             .await;
         assert!(apply_result.is_ok());
@@ -918,7 +1230,6 @@ mod tests {
             .never();
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
@@ -935,7 +1246,11 @@ mod tests {
             .apply_manifests(ApplyArgs {
                 agent_name: None,
                 delete_mode: false,
+                dry_run: false,
+                timeout: None,
                 manifest_files: vec!["manifest_yaml".to_string()],
+                files: vec![],
+                overlay_dir: None,
             })
             .await;
         assert!(apply_result.is_ok());
@@ -1023,7 +1338,6 @@ mod tests {
             });
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
@@ -1040,7 +1354,11 @@ mod tests {
             .apply_manifests(ApplyArgs {
                 agent_name: None,
                 delete_mode: false,
+                dry_run: false,
+                timeout: None,
                 manifest_files: vec!["manifest_yaml".to_string()],
+                files: vec![],
+                overlay_dir: None,
             })
             .await;
         assert!(apply_result.is_err());
@@ -0,0 +1,31 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use tabled::Tabled;
+
+#[derive(Debug, Tabled, Clone)]
+#[tabled(rename_all = "UPPERCASE")]
+pub struct AuditTableRow {
+    #[tabled(rename = "SEQ")]
+    pub sequence_number: u64,
+    pub time: String,
+    #[tabled(rename = "TYPE")]
+    pub requester_type: String,
+    pub requester: String,
+    #[tabled(rename = "WORKLOAD")]
+    pub workload_instance_name: String,
+    #[tabled(rename = "UPDATE MASK")]
+    pub update_mask: String,
+    pub result: String,
+}
@@ -67,12 +67,10 @@ where
         let terminal_width = terminal_width();
         let truncated_lines: Vec<&str> = default_table
             .lines()
-            .map(|line| {
-                if line.len() > terminal_width {
-                    &line[..terminal_width]
-                } else {
-                    line
-                }
+            // truncate at a char boundary as cells can contain multibyte chars like '°'
+            .map(|line| match line.char_indices().nth(terminal_width) {
+                Some((byte_index, _)) => &line[..byte_index],
+                None => line,
             })
             .collect();
         truncated_lines.join(NEWLINE)
@@ -249,6 +247,26 @@ mod tests {
         assert_eq!(table_output, expected_table_output);
     }
 
+    // [utest->swdd~cli-table-provides-default-table-output~2]
+    #[test]
+    fn utest_create_default_table_truncates_multibyte_chars_by_char() {
+        let table_rows = [TestRow {
+            col1: "temperature".to_string(),
+            col2: "42.5°C".to_string(),
+            col3: "°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°".to_string(),
+        }];
+
+        let table = CliTable::new(&table_rows);
+        let table_output = table.create_default_table();
+        let expected_table_output = [
+            "COLUMN 1      COL2     ANOTHER COLUMN3                                          ",
+            "temperature   42.5°C   °°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°°",
+        ]
+        .join("\n");
+
+        assert_eq!(table_output, expected_table_output);
+    }
+
     // [utest->swdd~cli-table-provides-default-table-output~2]
     #[test]
     fn utest_create_default_table_line_length_equal_to_terminal_length() {
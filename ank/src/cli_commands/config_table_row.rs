@@ -12,9 +12,10 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+use serde::Serialize;
 use tabled::Tabled;
 
-#[derive(Debug, Tabled, Clone)]
+#[derive(Debug, Tabled, Clone, Serialize)]
 #[tabled(rename_all = "UPPERCASE")]
 pub struct ConfigTableRow {
     #[tabled(rename = "CONFIG")]
@@ -55,13 +55,14 @@ impl CliCommands {
 //////////////////////////////////////////////////////////////////////////////
 #[cfg(test)]
 mod tests {
-    use crate::cli_commands::{server_connection::MockServerConnection, CliCommands};
+    use crate::cli_commands::{
+        output::ListOutputFormat, server_connection::MockServerConnection, CliCommands,
+    };
     use crate::filtered_complete_state::FilteredCompleteState;
     use api::ank_base::UpdateStateSuccess;
     use common::objects::CompleteState;
     use mockall::predicate::eq;
 
-    const RESPONSE_TIMEOUT_MS: u64 = 3000;
     const CONFIG_1: &str = "config_1";
     const CONFIG_2: &str = "config_2";
 
@@ -95,7 +96,6 @@ mod tests {
             .returning(|_| Ok(FilteredCompleteState::default()));
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
@@ -106,7 +106,7 @@ mod tests {
         assert!(delete_result.is_ok());
 
         // Verify that the deleted configs no longer exist in the desired state
-        let get_result = cmd.get_configs().await.unwrap();
+        let get_result = cmd.get_configs(&ListOutputFormat::Table).await.unwrap();
 
         assert!(!get_result.contains(CONFIG_1));
         assert!(!get_result.contains(CONFIG_2));
@@ -136,7 +136,6 @@ mod tests {
             });
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
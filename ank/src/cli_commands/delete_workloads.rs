@@ -12,16 +12,21 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use common::objects::CompleteState;
+use common::objects::{CompleteState, Tag};
 
-use crate::{cli_commands::DESIRED_STATE_WORKLOADS, cli_error::CliError, output_debug};
+use crate::{cli_commands::DESIRED_STATE_WORKLOADS, cli_error::CliError, output, output_debug};
 
 use super::CliCommands;
 
 impl CliCommands {
     // [impl->swdd~cli-provides-delete-workload~1]
     // [impl->swdd~cli-blocks-until-ankaios-server-responds-delete-workload~2]
-    pub async fn delete_workloads(&mut self, workload_names: Vec<String>) -> Result<(), CliError> {
+    // [impl->swdd~cli-supports-delete-grace-period~1]
+    pub async fn delete_workloads(
+        &mut self,
+        workload_names: Vec<String>,
+        grace_period_secs: Option<u32>,
+    ) -> Result<(), CliError> {
         let complete_state_update = CompleteState::default();
 
         let update_mask = workload_names
@@ -36,7 +41,41 @@ impl CliCommands {
             update_mask
         );
 
-        self.update_state_and_wait_for_complete(complete_state_update, update_mask)
+        self.update_state_and_wait_for_complete(
+            complete_state_update,
+            update_mask,
+            grace_period_secs,
+        )
+        .await
+    }
+
+    // [impl->swdd~cli-supports-workload-selector~1]
+    pub async fn delete_selected_workloads(
+        &mut self,
+        selector: Vec<Tag>,
+        grace_period_secs: Option<u32>,
+    ) -> Result<(), CliError> {
+        let selected_state = self
+            .server_connection
+            .get_selected_complete_state(&[DESIRED_STATE_WORKLOADS.to_string()], &selector)
+            .await?;
+
+        let mut workload_names: Vec<String> = selected_state
+            .desired_state
+            .and_then(|desired_state| desired_state.workloads)
+            .unwrap_or_default()
+            .into_keys()
+            .collect();
+
+        // an empty update mask would replace the complete state
+        if workload_names.is_empty() {
+            output!("No workloads match the selector.");
+            return Ok(());
+        }
+
+        workload_names.sort();
+        output_debug!("Deleting the selected workloads {:?}", workload_names);
+        self.delete_workloads(workload_names, grace_period_secs)
             .await
     }
 }
@@ -54,7 +93,7 @@ mod tests {
     use common::{
         commands::UpdateWorkloadState,
         from_server_interface::FromServer,
-        objects::{self, CompleteState, ExecutionState, WorkloadState},
+        objects::{self, CompleteState, ExecutionState, Tag, WorkloadState},
     };
     use mockall::predicate::eq;
 
@@ -63,8 +102,6 @@ mod tests {
         filtered_complete_state::FilteredCompleteState,
     };
 
-    const RESPONSE_TIMEOUT_MS: u64 = 3000;
-
     // [utest->swdd~cli-provides-delete-workload~1]
     // [utest->swdd~cli-blocks-until-ankaios-server-responds-delete-workload~2]
     // [utest->swdd~cli-watches-workloads~1]
@@ -128,13 +165,12 @@ mod tests {
             });
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
         let delete_result = cmd
-            .delete_workloads(vec!["name1".to_string(), "name2".to_string()])
+            .delete_workloads(vec!["name1".to_string(), "name2".to_string()], None)
             .await;
         assert!(delete_result.is_ok());
     }
@@ -168,14 +204,80 @@ mod tests {
             });
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
         let delete_result = cmd
-            .delete_workloads(vec!["unknown_workload".to_string()])
+            .delete_workloads(vec!["unknown_workload".to_string()], None)
+            .await;
+        assert!(delete_result.is_ok());
+    }
+
+    // [utest->swdd~cli-supports-delete-grace-period~1]
+    #[tokio::test]
+    async fn utest_delete_workloads_with_grace_period() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let complete_state_update = CompleteState::default();
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .once()
+            .returning(|_| Ok(FilteredCompleteState::default()));
+        mock_server_connection
+            .expect_update_state_with_grace_period()
+            .with(
+                eq(complete_state_update),
+                eq(vec!["desiredState.workloads.name1".to_string()]),
+                eq(30),
+            )
+            .return_once(|_, _, _| {
+                Ok(UpdateStateSuccess {
+                    added_workloads: vec![],
+                    deleted_workloads: vec![],
+                })
+            });
+        mock_server_connection.expect_update_state().never();
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let delete_result = cmd
+            .delete_workloads(vec!["name1".to_string()], Some(30))
             .await;
         assert!(delete_result.is_ok());
     }
+
+    // [utest->swdd~cli-supports-workload-selector~1]
+    #[tokio::test]
+    async fn utest_delete_selected_workloads_none_selected() {
+        let selector = vec![Tag {
+            key: "team".to_string(),
+            value: "adas".to_string(),
+        }];
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_selected_complete_state()
+            .with(
+                eq(vec!["desiredState.workloads".to_string()]),
+                eq(selector.clone()),
+            )
+            .return_once(|_, _| Ok(FilteredCompleteState::default()));
+        mock_server_connection.expect_update_state().never();
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let delete_result = cmd.delete_selected_workloads(selector, None).await;
+        assert!(delete_result.is_ok());
+    }
 }
@@ -0,0 +1,460 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fmt::Write,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use common::objects::{AccessRightsRule, WorkloadState};
+
+use crate::{
+    cli_commands::DESIRED_STATE_WORKLOADS,
+    cli_error::CliError,
+    filtered_complete_state::{FilteredWorkloadSpec, FilteredWorkloadStateTransition},
+    output_debug,
+};
+
+use super::{get_dependency_graph::DependencyGraph, CliCommands};
+
+const WORKLOAD_STATES: &str = "workloadStates";
+const WORKLOAD_STATE_HISTORY: &str = "workloadStateHistory";
+pub(super) const NONE: &str = "<none>";
+const INDENTATION: &str = "  ";
+
+impl CliCommands {
+    // [impl->swdd~cli-provides-describe-workload~1]
+    pub async fn describe_workload(&mut self, workload_name: String) -> Result<String, CliError> {
+        let complete_state = self
+            .server_connection
+            .get_complete_state(&[
+                format!("{}.{}", DESIRED_STATE_WORKLOADS, workload_name),
+                format!("{}.{}", WORKLOAD_STATE_HISTORY, workload_name),
+            ])
+            .await?;
+        let Some(workload_spec) = complete_state
+            .desired_state
+            .and_then(|desired_state| desired_state.workloads)
+            .and_then(|mut workloads| workloads.remove(&workload_name))
+        else {
+            return Err(CliError::ExecutionError(format!(
+                "Workload '{}' does not exist",
+                workload_name
+            )));
+        };
+        let transitions = complete_state
+            .workload_state_history
+            .and_then(|mut history| history.remove(&workload_name))
+            .unwrap_or_default();
+
+        // the workload states are keyed by the agent, which is only known from the workload
+        let agent_name = workload_spec.agent.clone().unwrap_or_default();
+        let workload_states: Vec<WorkloadState> = self
+            .server_connection
+            .get_complete_state(&[format!(
+                "{}.{}.{}",
+                WORKLOAD_STATES, agent_name, workload_name
+            )])
+            .await?
+            .workload_states
+            .map(Into::into)
+            .unwrap_or_default();
+
+        let dependency_graph: DependencyGraph =
+            self.server_connection.get_dependency_graph().await?.into();
+        output_debug!(
+            "Describing workload '{}' with spec {:?}, states {:?} and dependency graph {:?}",
+            workload_name,
+            workload_spec,
+            workload_states,
+            dependency_graph
+        );
+
+        Ok(describe(
+            &workload_name,
+            &workload_spec,
+            &workload_states,
+            &dependency_graph,
+            &transitions,
+        ))
+    }
+}
+
+// [impl->swdd~cli-provides-describe-workload~1]
+fn describe(
+    workload_name: &str,
+    workload_spec: &FilteredWorkloadSpec,
+    workload_states: &[WorkloadState],
+    dependency_graph: &DependencyGraph,
+    transitions: &[FilteredWorkloadStateTransition],
+) -> String {
+    let mut report = String::new();
+    let optional = |value: &Option<String>| value.clone().unwrap_or_else(|| NONE.to_owned());
+
+    let _ = writeln!(report, "Name:            {}", workload_name);
+    let _ = writeln!(
+        report,
+        "Agent:           {}",
+        optional(&workload_spec.agent)
+    );
+    let _ = writeln!(
+        report,
+        "Runtime:         {}",
+        optional(&workload_spec.runtime)
+    );
+    let tags = workload_spec
+        .tags
+        .iter()
+        .flatten()
+        .map(|tag| format!("{}={}", tag.key, tag.value))
+        .collect::<Vec<_>>();
+    let _ = writeln!(report, "Tags:            {}", join_or_none(&tags));
+    let _ = writeln!(
+        report,
+        "Restart Policy:  {}",
+        optional(
+            &workload_spec
+                .restart_policy
+                .as_ref()
+                .map(ToString::to_string)
+        )
+    );
+
+    let _ = writeln!(report, "Execution States:");
+    write_lines(
+        &mut report,
+        workload_states
+            .iter()
+            .filter(|state| state.instance_name.workload_name() == workload_name)
+            .map(|state| {
+                format!(
+                    "{} {} {}",
+                    state.instance_name.id(),
+                    state.execution_state.state,
+                    state.execution_state.additional_info
+                )
+            }),
+    );
+
+    let _ = writeln!(report, "Dependencies:");
+    write_lines(
+        &mut report,
+        dependency_graph
+            .edges
+            .iter()
+            .filter(|edge| edge.workload_name == workload_name)
+            .map(|edge| {
+                let dependency_state = dependency_graph
+                    .nodes
+                    .iter()
+                    .find(|node| node.workload_name == edge.dependency)
+                    .and_then(|node| node.execution_state.clone())
+                    .unwrap_or_else(|| "Unknown".to_owned());
+                format!(
+                    "{} {} (currently {})",
+                    edge.dependency, edge.condition, dependency_state
+                )
+            }),
+    );
+    let _ = writeln!(report, "Dependents:");
+    write_lines(
+        &mut report,
+        dependency_graph
+            .edges
+            .iter()
+            .filter(|edge| edge.dependency == workload_name)
+            .map(|edge| format!("{} {}", edge.workload_name, edge.condition)),
+    );
+
+    let _ = writeln!(report, "Control Interface Access:");
+    let access = workload_spec.control_interface_access.as_ref();
+    let allow_rules = access.iter().flat_map(|access| &access.allow_rules);
+    let deny_rules = access.iter().flat_map(|access| &access.deny_rules);
+    let pipes_owner = access.and_then(|access| access.pipes_owner.as_ref());
+    write_lines(
+        &mut report,
+        allow_rules
+            .map(|rule| format!("allow {}", describe_rule(rule)))
+            .chain(deny_rules.map(|rule| format!("deny {}", describe_rule(rule))))
+            .chain(pipes_owner.map(|owner| format!("pipes owned by {}:{}", owner.uid, owner.gid))),
+    );
+
+    let _ = writeln!(report, "Configs:");
+    let mut configs = workload_spec
+        .configs
+        .iter()
+        .flatten()
+        .map(|(alias, config_name)| format!("{} -> {}", alias, config_name))
+        .collect::<Vec<_>>();
+    configs.sort();
+    write_lines(&mut report, configs.into_iter());
+
+    let _ = writeln!(report, "Recent State Transitions:");
+    write_lines(
+        &mut report,
+        transitions.iter().map(|transition| {
+            format!(
+                "{} {} {} {}",
+                humantime::format_rfc3339_millis(
+                    UNIX_EPOCH + Duration::from_millis(transition.timestamp)
+                ),
+                transition.id,
+                transition.execution_state.state,
+                transition.execution_state.additional_info
+            )
+        }),
+    );
+
+    let _ = writeln!(report, "Runtime Config:");
+    write_lines(
+        &mut report,
+        workload_spec
+            .runtime_config
+            .iter()
+            .flat_map(|runtime_config| runtime_config.lines())
+            .map(ToOwned::to_owned),
+    );
+    report.trim_end().to_owned()
+}
+
+fn describe_rule(rule: &AccessRightsRule) -> String {
+    match rule {
+        AccessRightsRule::StateRule(state_rule) => format!(
+            "{:?} {}",
+            state_rule.operation,
+            state_rule.filter_mask.join(", ")
+        ),
+    }
+}
+
+pub(super) fn join_or_none(values: &[String]) -> String {
+    if values.is_empty() {
+        NONE.to_owned()
+    } else {
+        values.join(", ")
+    }
+}
+
+pub(super) fn write_lines(report: &mut String, lines: impl Iterator<Item = String>) {
+    let mut is_empty = true;
+    for line in lines {
+        is_empty = false;
+        let _ = writeln!(report, "{}{}", INDENTATION, line.trim_end());
+    }
+    if is_empty {
+        let _ = writeln!(report, "{}{}", INDENTATION, NONE);
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use api::ank_base;
+    use common::objects::{
+        generate_test_workload_states_map_with_data, AccessRightsRule, ControlInterfaceAccess,
+        ExecutionState, PipesOwner, ReadWriteEnum, StateRule, Tag,
+    };
+    use mockall::predicate::eq;
+
+    use crate::{
+        cli_commands::{server_connection::MockServerConnection, CliCommands},
+        cli_error::CliError,
+        filtered_complete_state::{
+            FilteredCompleteState, FilteredState, FilteredWorkloadSpec,
+            FilteredWorkloadStateTransition,
+        },
+    };
+
+    const WORKLOAD_NAME: &str = "frontend";
+    const AGENT_NAME: &str = "agent_A";
+
+    fn generate_test_workload_spec() -> FilteredWorkloadSpec {
+        FilteredWorkloadSpec {
+            agent: Some(AGENT_NAME.to_owned()),
+            tags: Some(vec![Tag {
+                key: "team".to_owned(),
+                value: "adas".to_owned(),
+            }]),
+            dependencies: None,
+            restart_policy: None,
+            runtime: Some("podman".to_owned()),
+            runtime_config: Some("image: nginx\ncommandOptions: [\"-p\", \"8080:80\"]".to_owned()),
+            control_interface_access: Some(ControlInterfaceAccess {
+                allow_rules: vec![AccessRightsRule::StateRule(StateRule {
+                    operation: ReadWriteEnum::Read,
+                    filter_mask: vec!["workloadStates".to_owned()],
+                })],
+                deny_rules: vec![],
+                pipes_owner: Some(PipesOwner {
+                    uid: 1000,
+                    gid: 1000,
+                }),
+            }),
+            configs: Some(HashMap::from([("web".to_owned(), "web_config".to_owned())])),
+            hostname: None,
+            extra_hosts: None,
+            ports: None,
+            resource_thresholds: None,
+            resources: None,
+            health_check: None,
+            restart_backoff: None,
+            priority: None,
+            volumes: None,
+            registry_auth: None,
+            replicas: None,
+            networks: None,
+            env: None,
+            on_agent_disconnect: None,
+            restart_on: None,
+            lifecycle_hooks: None,
+            node_selector: None,
+            state_debounce: None,
+            security_context: None,
+        }
+    }
+
+    fn generate_test_dependency_graph() -> ank_base::DependencyGraph {
+        ank_base::DependencyGraph {
+            nodes: vec![ank_base::DependencyGraphNode {
+                workload_name: "backend".to_owned(),
+                agent: AGENT_NAME.to_owned(),
+                execution_state: Some(ExecutionState::running().into()),
+            }],
+            edges: vec![ank_base::DependencyGraphEdge {
+                workload_name: WORKLOAD_NAME.to_owned(),
+                dependency: "backend".to_owned(),
+                condition: ank_base::AddCondition::AddCondRunning as i32,
+            }],
+        }
+    }
+
+    // [utest->swdd~cli-provides-describe-workload~1]
+    #[tokio::test]
+    async fn utest_describe_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let desired_state = FilteredCompleteState {
+            desired_state: Some(FilteredState {
+                api_version: "v0.2".to_owned(),
+                workloads: Some(HashMap::from([(
+                    WORKLOAD_NAME.to_owned(),
+                    generate_test_workload_spec(),
+                )])),
+                configs: None,
+                defaults: None,
+                namespaces: None,
+                images: None,
+            }),
+            workload_state_history: Some(HashMap::from([(
+                WORKLOAD_NAME.to_owned(),
+                vec![FilteredWorkloadStateTransition {
+                    timestamp: 1_700_000_000_000,
+                    agent: AGENT_NAME.to_owned(),
+                    id: "ID_X".to_owned(),
+                    execution_state: ExecutionState::failed("exit code 1"),
+                }],
+            )])),
+            ..Default::default()
+        };
+        let workload_states = FilteredCompleteState {
+            workload_states: Some(generate_test_workload_states_map_with_data(
+                AGENT_NAME,
+                WORKLOAD_NAME,
+                "ID_X",
+                ExecutionState::running(),
+            )),
+            ..Default::default()
+        };
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec![
+                "desiredState.workloads.frontend".to_owned(),
+                "workloadStateHistory.frontend".to_owned(),
+            ]))
+            .return_once(|_| Ok(desired_state));
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec!["workloadStates.agent_A.frontend".to_owned()]))
+            .return_once(|_| Ok(workload_states));
+        mock_server_connection
+            .expect_get_dependency_graph()
+            .return_once(|| Ok(generate_test_dependency_graph()));
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let report = cmd.describe_workload(WORKLOAD_NAME.to_owned()).await;
+
+        let expected_report = [
+            "Name:            frontend",
+            "Agent:           agent_A",
+            "Runtime:         podman",
+            "Tags:            team=adas",
+            "Restart Policy:  <none>",
+            "Execution States:",
+            "  ID_X Running(Ok)",
+            "Dependencies:",
+            "  backend ADD_COND_RUNNING (currently Running(Ok))",
+            "Dependents:",
+            "  <none>",
+            "Control Interface Access:",
+            "  allow Read workloadStates",
+            "  pipes owned by 1000:1000",
+            "Configs:",
+            "  web -> web_config",
+            "Recent State Transitions:",
+            "  2023-11-14T22:13:20.000Z ID_X Failed(ExecFailed) exit code 1",
+            "Runtime Config:",
+            "  image: nginx",
+            "  commandOptions: [\"-p\", \"8080:80\"]",
+        ]
+        .join("\n");
+        assert_eq!(report, Ok(expected_report));
+    }
+
+    // [utest->swdd~cli-provides-describe-workload~1]
+    #[tokio::test]
+    async fn utest_describe_workload_fails_for_unknown_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .once()
+            .return_once(|_| Ok(FilteredCompleteState::default()));
+        mock_server_connection.expect_get_dependency_graph().never();
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd.describe_workload(WORKLOAD_NAME.to_owned()).await;
+        assert!(matches!(result, Err(CliError::ExecutionError(_))));
+    }
+}
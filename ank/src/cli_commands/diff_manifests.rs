@@ -0,0 +1,683 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::Write;
+
+use api::ank_base;
+use common::objects::{desired_state_path_of_workload, qualified_workload_name, ConfigItem};
+use serde::Serialize;
+use serde_yaml::Value;
+
+use super::{
+    apply_manifests::generate_state_obj_and_filter_masks_from_manifests, CliCommands,
+    InputSourcePair,
+};
+use crate::{
+    cli::DiffOutputFormat,
+    cli_error::CliError,
+    filtered_complete_state::{FilteredCompleteState, FilteredState, FilteredWorkloadSpec},
+    output_debug,
+};
+
+const DESIRED_STATE_PREFIX: &str = "desiredState.";
+const WORKLOADS: &str = "workloads";
+const NAMESPACES: &str = "namespaces";
+const CONFIGS: &str = "configs";
+const NO_FILE: &str = "/dev/null";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Change {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldDiff {
+    pub field: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_value: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_value: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemDiff {
+    pub name: String,
+    pub change: Change,
+    pub field_diffs: Vec<FieldDiff>,
+    #[serde(skip)]
+    current_value: Option<Value>,
+    #[serde(skip)]
+    new_value: Option<Value>,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateDiff {
+    pub workloads: Vec<ItemDiff>,
+    pub configs: Vec<ItemDiff>,
+}
+
+impl StateDiff {
+    pub fn is_empty(&self) -> bool {
+        self.workloads.is_empty() && self.configs.is_empty()
+    }
+}
+
+type CurrentAndNewValues = BTreeMap<String, (Option<Value>, Option<Value>)>;
+
+// Only the workloads and configs contained in the manifests are compared, as only these are changed by 'ank apply'.
+// As 'ank apply' replaces a namespace as a whole, the workloads of the namespace which are not contained
+// in the manifests are reported as removed.
+// [impl->swdd~cli-diffs-manifests-against-desired-state~1]
+pub fn diff_states(
+    current_state: &FilteredCompleteState,
+    new_state: &FilteredCompleteState,
+    filter_masks: &[String],
+) -> Result<StateDiff, CliError> {
+    let current_state = current_state.desired_state.as_ref();
+    let new_state = new_state.desired_state.as_ref();
+
+    let mut workloads = CurrentAndNewValues::new();
+    let mut configs = CurrentAndNewValues::new();
+    for filter_mask in filter_masks {
+        let path = filter_mask
+            .strip_prefix(DESIRED_STATE_PREFIX)
+            .unwrap_or(filter_mask);
+        match path.split_once('.') {
+            Some((WORKLOADS, workload_name)) => {
+                let entry = workloads.entry(workload_name.to_owned()).or_default();
+                entry.0 = to_value(current_state.and_then(|state| workload(state, workload_name)))?;
+                entry.1 = to_value(new_state.and_then(|state| workload(state, workload_name)))?;
+            }
+            Some((NAMESPACES, namespace)) => {
+                for (workload_name, workload_spec) in namespace_workloads(current_state, namespace)
+                {
+                    workloads
+                        .entry(qualified_workload_name(namespace, workload_name))
+                        .or_default()
+                        .0 = to_value(Some(workload_spec))?;
+                }
+                for (workload_name, workload_spec) in namespace_workloads(new_state, namespace) {
+                    workloads
+                        .entry(qualified_workload_name(namespace, workload_name))
+                        .or_default()
+                        .1 = to_value(Some(workload_spec))?;
+                }
+            }
+            Some((CONFIGS, config_name)) => {
+                let entry = configs.entry(config_name.to_owned()).or_default();
+                entry.0 = to_value(current_state.and_then(|state| config(state, config_name)))?;
+                entry.1 = to_value(new_state.and_then(|state| config(state, config_name)))?;
+            }
+            _ => output_debug!("Ignoring the path '{}' in the diff", path),
+        }
+    }
+
+    Ok(StateDiff {
+        workloads: diff_items(workloads),
+        configs: diff_items(configs),
+    })
+}
+
+fn workload<'a>(state: &'a FilteredState, workload_name: &str) -> Option<&'a FilteredWorkloadSpec> {
+    state.workloads.as_ref()?.get(workload_name)
+}
+
+fn config<'a>(state: &'a FilteredState, config_name: &str) -> Option<&'a ConfigItem> {
+    state.configs.as_ref()?.get(config_name)
+}
+
+fn namespace_workloads<'a>(
+    state: Option<&'a FilteredState>,
+    namespace: &str,
+) -> impl Iterator<Item = (&'a String, &'a FilteredWorkloadSpec)> {
+    state
+        .and_then(|state| state.namespaces.as_ref())
+        .and_then(|namespaces| namespaces.get(namespace))
+        .and_then(|namespace| namespace.workloads.as_ref())
+        .into_iter()
+        .flat_map(HashMap::iter)
+}
+
+// Empty lists and maps are removed, as the server does not distinguish them from missing fields.
+fn to_value(item: Option<&impl Serialize>) -> Result<Option<Value>, CliError> {
+    Ok(item
+        .map(serde_yaml::to_value)
+        .transpose()?
+        .and_then(without_empty_values))
+}
+
+fn without_empty_values(value: Value) -> Option<Value> {
+    match value {
+        Value::Null => None,
+        Value::Sequence(sequence) if sequence.is_empty() => None,
+        Value::Mapping(mapping) => {
+            let mapping: serde_yaml::Mapping = mapping
+                .into_iter()
+                .filter_map(|(key, value)| without_empty_values(value).map(|value| (key, value)))
+                .collect();
+            (!mapping.is_empty()).then_some(Value::Mapping(mapping))
+        }
+        value => Some(value),
+    }
+}
+
+fn diff_items(items: CurrentAndNewValues) -> Vec<ItemDiff> {
+    items
+        .into_iter()
+        .filter_map(|(name, (current_value, new_value))| {
+            let change = match (&current_value, &new_value) {
+                (None, None) => return None,
+                (None, Some(_)) => Change::Added,
+                (Some(_), None) => Change::Removed,
+                (Some(current), Some(new)) if current == new => return None,
+                (Some(_), Some(_)) => Change::Changed,
+            };
+            let mut field_diffs = Vec::new();
+            diff_fields(
+                "",
+                current_value.as_ref(),
+                new_value.as_ref(),
+                &mut field_diffs,
+            );
+            Some(ItemDiff {
+                name,
+                change,
+                field_diffs,
+                current_value,
+                new_value,
+            })
+        })
+        .collect()
+}
+
+// Mappings are compared field by field, all other values are compared as a whole.
+fn diff_fields(
+    field: &str,
+    current_value: Option<&Value>,
+    new_value: Option<&Value>,
+    field_diffs: &mut Vec<FieldDiff>,
+) {
+    let is_mapping_or_missing = |value: Option<&Value>| value.is_none_or(Value::is_mapping);
+    if (current_value.is_some() || new_value.is_some())
+        && is_mapping_or_missing(current_value)
+        && is_mapping_or_missing(new_value)
+    {
+        let keys: BTreeSet<&str> = [current_value, new_value]
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_mapping)
+            .flat_map(|mapping| mapping.keys().filter_map(Value::as_str))
+            .collect();
+        for key in keys {
+            let sub_field = if field.is_empty() {
+                key.to_owned()
+            } else {
+                format!("{field}.{key}")
+            };
+            diff_fields(
+                &sub_field,
+                current_value.and_then(|value| value.get(key)),
+                new_value.and_then(|value| value.get(key)),
+                field_diffs,
+            );
+        }
+    } else if current_value != new_value {
+        field_diffs.push(FieldDiff {
+            field: field.to_owned(),
+            current_value: current_value.cloned(),
+            new_value: new_value.cloned(),
+        });
+    }
+}
+
+fn write_state_diff(
+    state_diff: &StateDiff,
+    output_format: DiffOutputFormat,
+    mut writer: impl Write,
+) -> Result<(), CliError> {
+    match output_format {
+        DiffOutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, state_diff)?;
+            writeln!(writer)?;
+        }
+        DiffOutputFormat::Unified if state_diff.is_empty() => {
+            writeln!(
+                writer,
+                "No differences between the desired state and the manifest(s)."
+            )?;
+        }
+        DiffOutputFormat::Unified => {
+            for workload_diff in &state_diff.workloads {
+                let path = desired_state_path_of_workload(&workload_diff.name);
+                write_unified_diff(&path, workload_diff, &mut writer)?;
+            }
+            for config_diff in &state_diff.configs {
+                let path = format!("{DESIRED_STATE_PREFIX}{CONFIGS}.{}", config_diff.name);
+                write_unified_diff(&path, config_diff, &mut writer)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// The YAML of an item is output as a single hunk containing the whole item.
+fn write_unified_diff(
+    path: &str,
+    item_diff: &ItemDiff,
+    mut writer: impl Write,
+) -> Result<(), CliError> {
+    let current_yaml = to_yaml(item_diff.current_value.as_ref())?;
+    let new_yaml = to_yaml(item_diff.new_value.as_ref())?;
+    let current_lines: Vec<&str> = current_yaml.lines().collect();
+    let new_lines: Vec<&str> = new_yaml.lines().collect();
+
+    let file_name = |value: &Option<Value>| match value {
+        Some(_) => path,
+        None => NO_FILE,
+    };
+    writeln!(writer, "--- {}", file_name(&item_diff.current_value))?;
+    writeln!(writer, "+++ {}", file_name(&item_diff.new_value))?;
+    writeln!(
+        writer,
+        "@@ -{} +{} @@",
+        hunk_range(current_lines.len()),
+        hunk_range(new_lines.len())
+    )?;
+    for (prefix, line) in diff_lines(&current_lines, &new_lines) {
+        writeln!(writer, "{prefix}{line}")?;
+    }
+    Ok(())
+}
+
+fn to_yaml(value: Option<&Value>) -> Result<String, CliError> {
+    Ok(value
+        .map(serde_yaml::to_string)
+        .transpose()?
+        .unwrap_or_default())
+}
+
+fn hunk_range(line_count: usize) -> String {
+    match line_count {
+        0 => "0,0".to_owned(),
+        _ => format!("1,{line_count}"),
+    }
+}
+
+// Line based diff using the longest common subsequence of the lines
+fn diff_lines<'a>(current_lines: &[&'a str], new_lines: &[&'a str]) -> Vec<(char, &'a str)> {
+    let mut common_lengths = vec![vec![0usize; new_lines.len() + 1]; current_lines.len() + 1];
+    for i in (0..current_lines.len()).rev() {
+        for j in (0..new_lines.len()).rev() {
+            common_lengths[i][j] = if current_lines[i] == new_lines[j] {
+                common_lengths[i + 1][j + 1] + 1
+            } else {
+                common_lengths[i + 1][j].max(common_lengths[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < current_lines.len() && j < new_lines.len() {
+        if current_lines[i] == new_lines[j] {
+            lines.push((' ', current_lines[i]));
+            i += 1;
+            j += 1;
+        } else if common_lengths[i + 1][j] >= common_lengths[i][j + 1] {
+            lines.push(('-', current_lines[i]));
+            i += 1;
+        } else {
+            lines.push(('+', new_lines[j]));
+            j += 1;
+        }
+    }
+    lines.extend(current_lines[i..].iter().map(|line| ('-', *line)));
+    lines.extend(new_lines[j..].iter().map(|line| ('+', *line)));
+    lines
+}
+
+impl CliCommands {
+    // [impl->swdd~cli-diffs-manifests-against-desired-state~1]
+    pub async fn diff_manifests(
+        &mut self,
+        mut manifests: Vec<InputSourcePair>,
+        agent_name: &Option<String>,
+        output_format: DiffOutputFormat,
+        mut writer: impl Write,
+    ) -> Result<(), CliError> {
+        let state_diff = match generate_state_obj_and_filter_masks_from_manifests(
+            &mut manifests,
+            agent_name,
+            false,
+        )
+        .map_err(CliError::ExecutionError)?
+        {
+            Some((manifest_state, filter_masks)) => {
+                let current_state = self
+                    .server_connection
+                    .get_complete_state(&filter_masks)
+                    .await?;
+                let new_state =
+                    FilteredCompleteState::from(ank_base::CompleteState::from(manifest_state));
+                diff_states(&current_state, &new_state, &filter_masks)?
+            }
+            None => StateDiff::default(),
+        };
+
+        write_state_diff(&state_diff, output_format, &mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use api::ank_base;
+    use common::objects::{CompleteState, State};
+    use mockall::predicate::eq;
+    use serde_yaml::Value;
+
+    use super::{
+        diff_lines, diff_states, write_unified_diff, Change, FieldDiff, ItemDiff, StateDiff,
+    };
+    use crate::{
+        cli::DiffOutputFormat,
+        cli_commands::{server_connection::MockServerConnection, CliCommands, InputSourcePair},
+        filtered_complete_state::FilteredCompleteState,
+    };
+
+    const CURRENT_STATE: &str = r#"
+apiVersion: v0.2
+workloads:
+  nginx:
+    runtime: podman
+    agent: agent_A
+    runtimeConfig: |
+      image: docker.io/nginx:1.25
+  untouched:
+    runtime: podman
+    agent: agent_A
+    runtimeConfig: ""
+configs:
+  port: "8080"
+namespaces:
+  team-a:
+    workloads:
+      backend:
+        runtime: podman
+        agent: agent_B
+        runtimeConfig: ""
+"#;
+
+    const MANIFEST: &str = r#"
+apiVersion: v0.2
+workloads:
+  nginx:
+    runtime: podman
+    agent: agent_A
+    tags: []
+    runtimeConfig: |
+      image: docker.io/nginx:1.26
+  api:
+    runtime: podman
+    agent: agent_A
+    runtimeConfig: ""
+"#;
+
+    fn generate_test_filtered_complete_state(desired_state: &str) -> FilteredCompleteState {
+        let desired_state: State = serde_yaml::from_str(desired_state).unwrap();
+        ank_base::CompleteState::from(CompleteState {
+            desired_state,
+            ..Default::default()
+        })
+        .into()
+    }
+
+    fn yaml(value: &str) -> Value {
+        serde_yaml::from_str(value).unwrap()
+    }
+
+    // [utest->swdd~cli-diffs-manifests-against-desired-state~1]
+    #[test]
+    fn utest_diff_states_compares_workloads_and_configs_of_manifest() {
+        let current_state = generate_test_filtered_complete_state(CURRENT_STATE);
+        let new_state = generate_test_filtered_complete_state(
+            r#"
+apiVersion: v0.2
+workloads:
+  nginx:
+    runtime: podman
+    agent: agent_B
+    tags: []
+    runtimeConfig: |
+      image: docker.io/nginx:1.25
+configs:
+  port: "8081"
+"#,
+        );
+
+        let state_diff = diff_states(
+            &current_state,
+            &new_state,
+            &[
+                "desiredState.configs.port".to_owned(),
+                "desiredState.workloads.nginx".to_owned(),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(state_diff.workloads.len(), 1);
+        assert_eq!(state_diff.workloads[0].name, "nginx");
+        assert_eq!(state_diff.workloads[0].change, Change::Changed);
+        assert_eq!(
+            state_diff.workloads[0].field_diffs,
+            vec![FieldDiff {
+                field: "agent".to_owned(),
+                current_value: Some(yaml("agent_A")),
+                new_value: Some(yaml("agent_B")),
+            }]
+        );
+        assert_eq!(state_diff.configs.len(), 1);
+        assert_eq!(
+            state_diff.configs[0].field_diffs,
+            vec![FieldDiff {
+                field: "".to_owned(),
+                current_value: Some(yaml("'8080'")),
+                new_value: Some(yaml("'8081'")),
+            }]
+        );
+    }
+
+    // [utest->swdd~cli-diffs-manifests-against-desired-state~1]
+    #[test]
+    fn utest_diff_states_reports_removed_workloads_of_namespace() {
+        let current_state = generate_test_filtered_complete_state(CURRENT_STATE);
+        let new_state = generate_test_filtered_complete_state(
+            r#"
+apiVersion: v0.2
+namespaces:
+  team-a:
+    workloads:
+      frontend:
+        runtime: podman
+        agent: agent_B
+        runtimeConfig: ""
+"#,
+        );
+
+        let state_diff = diff_states(
+            &current_state,
+            &new_state,
+            &["desiredState.namespaces.team-a".to_owned()],
+        )
+        .unwrap();
+
+        let changes: Vec<(&str, Change)> = state_diff
+            .workloads
+            .iter()
+            .map(|item_diff| (item_diff.name.as_str(), item_diff.change))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("team-a__backend", Change::Removed),
+                ("team-a__frontend", Change::Added)
+            ]
+        );
+        assert!(state_diff.workloads[1]
+            .field_diffs
+            .iter()
+            .all(|field_diff| field_diff.current_value.is_none()));
+    }
+
+    // [utest->swdd~cli-diffs-manifests-against-desired-state~1]
+    #[test]
+    fn utest_diff_states_without_differences() {
+        let current_state = generate_test_filtered_complete_state(CURRENT_STATE);
+
+        let state_diff = diff_states(
+            &current_state,
+            &current_state,
+            &[
+                "desiredState.workloads.nginx".to_owned(),
+                "desiredState.namespaces.team-a".to_owned(),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(state_diff, StateDiff::default());
+    }
+
+    #[test]
+    fn utest_diff_lines() {
+        assert_eq!(
+            diff_lines(&["a", "b", "c"], &["a", "x", "c", "d"]),
+            vec![(' ', "a"), ('-', "b"), ('+', "x"), (' ', "c"), ('+', "d")]
+        );
+    }
+
+    // [utest->swdd~cli-diffs-manifests-against-desired-state~1]
+    #[test]
+    fn utest_write_unified_diff() {
+        let item_diff = ItemDiff {
+            name: "nginx".to_owned(),
+            change: Change::Changed,
+            field_diffs: vec![],
+            current_value: Some(yaml("agent: agent_A\nruntime: podman")),
+            new_value: Some(yaml("agent: agent_B\nruntime: podman")),
+        };
+
+        let mut output = Vec::new();
+        write_unified_diff("desiredState.workloads.nginx", &item_diff, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            [
+                "--- desiredState.workloads.nginx",
+                "+++ desiredState.workloads.nginx",
+                "@@ -1,2 +1,2 @@",
+                "-agent: agent_A",
+                "+agent: agent_B",
+                " runtime: podman",
+                ""
+            ]
+            .join("\n")
+        );
+
+        let added_item_diff = ItemDiff {
+            current_value: None,
+            change: Change::Added,
+            ..item_diff
+        };
+
+        let mut output = Vec::new();
+        write_unified_diff(
+            "desiredState.workloads.nginx",
+            &added_item_diff,
+            &mut output,
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            [
+                "--- /dev/null",
+                "+++ desiredState.workloads.nginx",
+                "@@ -0,0 +1,2 @@",
+                "+agent: agent_B",
+                "+runtime: podman",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+
+    // [utest->swdd~cli-diffs-manifests-against-desired-state~1]
+    #[tokio::test]
+    async fn utest_diff_manifests_outputs_json() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec![
+                "desiredState.workloads.api".to_owned(),
+                "desiredState.workloads.nginx".to_owned(),
+            ]))
+            .return_once(|_| Ok(generate_test_filtered_complete_state(CURRENT_STATE)));
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let manifests: Vec<InputSourcePair> = vec![(
+            "manifest.yaml".to_owned(),
+            Box::new(io::Cursor::new(MANIFEST.as_bytes())),
+        )];
+        let mut output = Vec::new();
+        cmd.diff_manifests(manifests, &None, DiffOutputFormat::Json, &mut output)
+            .await
+            .unwrap();
+
+        let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(output["workloads"][0]["name"], "api");
+        assert_eq!(output["workloads"][0]["change"], "added");
+        assert_eq!(output["workloads"][1]["name"], "nginx");
+        assert_eq!(output["workloads"][1]["change"], "changed");
+        assert_eq!(
+            output["workloads"][1]["fieldDiffs"],
+            serde_json::json!([{
+                "field": "runtimeConfig",
+                "currentValue": "image: docker.io/nginx:1.25\n",
+                "newValue": "image: docker.io/nginx:1.26\n"
+            }])
+        );
+        assert_eq!(output["configs"], serde_json::json!([]));
+    }
+}
@@ -0,0 +1,304 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use common::{objects::CompleteState, state_manipulation::Object};
+
+use super::{
+    apply_manifests::format_dry_run_result,
+    set_state::{create_state_with_default_workload_specs, overwrite_using_field_mask},
+    CliCommands,
+};
+use crate::{cli_error::CliError, output, output_debug};
+
+#[cfg(test)]
+use tests::open_editor_mock as open_editor;
+
+const DEFAULT_EDIT_MASK: &str = "desiredState";
+#[cfg(not(test))]
+const DEFAULT_EDITOR: &str = "vi";
+
+// [impl->swdd~cli-edit-state-opens-editor~1]
+#[cfg(not(test))]
+fn open_editor(file_path: &std::path::Path) -> Result<(), CliError> {
+    let editor = std::env::var("EDITOR").unwrap_or_else(|_| DEFAULT_EDITOR.to_string());
+    let mut editor_parts = editor.split_whitespace();
+    let program = editor_parts.next().unwrap_or(DEFAULT_EDITOR);
+
+    let status = std::process::Command::new(program)
+        .args(editor_parts)
+        .arg(file_path)
+        .status()
+        .map_err(|error| {
+            CliError::ExecutionError(format!("Could not start editor '{}': '{}'", editor, error))
+        })?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(CliError::ExecutionError(format!(
+            "Editor '{}' exited with '{}'",
+            editor, status
+        )))
+    }
+}
+
+fn edit_file_path() -> PathBuf {
+    std::env::temp_dir().join(format!("ank-edit-{}.yaml", uuid::Uuid::new_v4()))
+}
+
+// [impl->swdd~cli-edit-state-validates-edited-state~1]
+fn parse_edited_state(
+    edited_state: &str,
+    object_field_mask: &Vec<String>,
+) -> Result<CompleteState, CliError> {
+    let value: serde_yaml::Value = serde_yaml::from_str(edited_state).map_err(|error| {
+        CliError::YamlSerialization(format!(
+            "Could not convert the edited state to yaml.\nError: '{}'",
+            error
+        ))
+    })?;
+    let edited_obj = Object::try_from(&value)?;
+
+    let default_complete_state = create_state_with_default_workload_specs(object_field_mask);
+    let complete_state_object: Object = default_complete_state.try_into()?;
+    let complete_state_object =
+        overwrite_using_field_mask(complete_state_object, object_field_mask, &edited_obj)?;
+
+    complete_state_object.try_into().map_err(|error| {
+        CliError::ExecutionError(format!("The edited state is invalid: '{}'", error))
+    })
+}
+
+impl CliCommands {
+    // [impl->swdd~cli-provides-edit-state~1]
+    pub async fn edit_state(&mut self, object_field_mask: Vec<String>) -> Result<(), CliError> {
+        let object_field_mask = if object_field_mask.is_empty() {
+            vec![DEFAULT_EDIT_MASK.to_string()]
+        } else {
+            object_field_mask
+        };
+        output_debug!("Got: object_field_mask={:?}", object_field_mask);
+
+        let filtered_complete_state = self
+            .server_connection
+            .get_complete_state(&object_field_mask)
+            .await?;
+        let current_state = serde_yaml::to_string(&filtered_complete_state)?;
+
+        let file_path = edit_file_path();
+        std::fs::write(&file_path, &current_state).map_err(|error| {
+            CliError::ExecutionError(format!(
+                "Could not write the state to '{}': '{}'",
+                file_path.display(),
+                error
+            ))
+        })?;
+
+        let edited_state = open_editor(&file_path).and_then(|_| {
+            std::fs::read_to_string(&file_path).map_err(|error| {
+                CliError::ExecutionError(format!(
+                    "Could not read the edited state from '{}': '{}'",
+                    file_path.display(),
+                    error
+                ))
+            })
+        });
+        let edited_state = match edited_state {
+            Ok(edited_state) => edited_state,
+            Err(error) => {
+                let _ = std::fs::remove_file(&file_path);
+                return Err(error);
+            }
+        };
+
+        if edited_state == current_state {
+            let _ = std::fs::remove_file(&file_path);
+            output!("Edit cancelled, no changes made.");
+            return Ok(());
+        }
+
+        // the edited file is kept on errors to not lose the changes of the user
+        let new_complete_state =
+            parse_edited_state(&edited_state, &object_field_mask).map_err(|error| {
+                CliError::ExecutionError(format!(
+                    "{}\nThe edited state is kept in '{}'.",
+                    error,
+                    file_path.display()
+                ))
+            })?;
+        let _ = std::fs::remove_file(&file_path);
+
+        // [impl->swdd~cli-edit-state-shows-changes-before-applying~1]
+        let dry_run_result = self
+            .server_connection
+            .update_state_dry_run(new_complete_state.clone(), object_field_mask.clone())
+            .await?;
+        output!("{}", format_dry_run_result(&dry_run_result));
+
+        output_debug!(
+            "Send UpdateState request with the CompleteState {:?}",
+            new_complete_state
+        );
+        self.update_state_and_wait_for_complete(new_complete_state, object_field_mask, None)
+            .await
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, path::Path};
+
+    use super::{parse_edited_state, CliCommands, CliError};
+    use crate::{
+        cli_commands::server_connection::MockServerConnection,
+        filtered_complete_state::FilteredCompleteState,
+    };
+    use api::ank_base::{UpdateStateDryRunResult, UpdateStateSuccess};
+    use common::{
+        objects::RestartPolicy,
+        test_utils::{generate_test_proto_complete_state, generate_test_proto_workload_with_param},
+    };
+    use mockall::predicate::{always, eq};
+
+    thread_local! {
+        // the content the mocked editor writes into the file, the file is left unchanged if not set
+        static EDITED_CONTENT: RefCell<Option<String>> = const { RefCell::new(None) };
+    }
+
+    pub fn open_editor_mock(file_path: &Path) -> Result<(), CliError> {
+        if let Some(content) = EDITED_CONTENT.with(|edited| edited.borrow_mut().take()) {
+            std::fs::write(file_path, content).unwrap();
+        }
+        Ok(())
+    }
+
+    fn mock_server_connection_with_current_state() -> MockServerConnection {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .returning(|_| {
+                Ok(FilteredCompleteState::from(
+                    generate_test_proto_complete_state(&[(
+                        "nginx",
+                        generate_test_proto_workload_with_param("agent_A", "podman"),
+                    )]),
+                ))
+            });
+        mock_server_connection
+    }
+
+    const EDITED_WORKLOAD: &str = r#"desiredState:
+  workloads:
+    nginx:
+      agent: agent_B
+      runtime: podman
+      restartPolicy: ALWAYS
+      runtimeConfig: |
+        image: docker.io/nginx:latest
+"#;
+
+    // [utest->swdd~cli-provides-edit-state~1]
+    // [utest->swdd~cli-edit-state-opens-editor~1]
+    #[tokio::test]
+    async fn utest_edit_state_without_changes_does_not_update_state() {
+        let mut mock_server_connection = mock_server_connection_with_current_state();
+        mock_server_connection.expect_update_state_dry_run().never();
+        mock_server_connection.expect_update_state().never();
+
+        let mut cmd = CliCommands {
+            no_wait: true,
+            server_connection: mock_server_connection,
+        };
+
+        assert!(cmd.edit_state(vec![]).await.is_ok());
+    }
+
+    // [utest->swdd~cli-provides-edit-state~1]
+    // [utest->swdd~cli-edit-state-shows-changes-before-applying~1]
+    #[tokio::test]
+    async fn utest_edit_state_applies_edited_state() {
+        let update_mask = vec!["desiredState.workloads.nginx".to_string()];
+        let expected_state = parse_edited_state(EDITED_WORKLOAD, &update_mask).unwrap();
+
+        let mut mock_server_connection = mock_server_connection_with_current_state();
+        mock_server_connection
+            .expect_update_state_dry_run()
+            .with(always(), eq(update_mask.clone()))
+            .once()
+            .return_once(|_, _| Ok(UpdateStateDryRunResult::default()));
+        mock_server_connection
+            .expect_update_state()
+            .with(eq(expected_state), eq(update_mask.clone()))
+            .once()
+            .return_once(|_, _| Ok(UpdateStateSuccess::default()));
+
+        let mut cmd = CliCommands {
+            no_wait: true,
+            server_connection: mock_server_connection,
+        };
+
+        EDITED_CONTENT.with(|edited| *edited.borrow_mut() = Some(EDITED_WORKLOAD.to_string()));
+        assert!(cmd.edit_state(update_mask).await.is_ok());
+    }
+
+    // [utest->swdd~cli-edit-state-validates-edited-state~1]
+    #[tokio::test]
+    async fn utest_edit_state_invalid_state_keeps_edited_file() {
+        let mut mock_server_connection = mock_server_connection_with_current_state();
+        mock_server_connection.expect_update_state_dry_run().never();
+        mock_server_connection.expect_update_state().never();
+
+        let mut cmd = CliCommands {
+            no_wait: true,
+            server_connection: mock_server_connection,
+        };
+
+        EDITED_CONTENT.with(|edited| {
+            *edited.borrow_mut() =
+                Some("desiredState:\n  workloads:\n    nginx:\n      restartPolicy: 42\n".into())
+        });
+        let Err(CliError::ExecutionError(message)) = cmd.edit_state(vec![]).await else {
+            panic!("Expected the edit to fail");
+        };
+
+        let kept_file = message
+            .rsplit_once("The edited state is kept in '")
+            .and_then(|(_, path)| path.strip_suffix("'."))
+            .expect("Expected the path of the kept file");
+        assert!(Path::new(kept_file).exists());
+        std::fs::remove_file(kept_file).unwrap();
+    }
+
+    // [utest->swdd~cli-edit-state-validates-edited-state~1]
+    #[test]
+    fn utest_parse_edited_state() {
+        let update_mask = vec!["desiredState.workloads.nginx".to_string()];
+
+        let complete_state = parse_edited_state(EDITED_WORKLOAD, &update_mask).unwrap();
+
+        let nginx = complete_state.desired_state.workloads.get("nginx").unwrap();
+        assert_eq!(nginx.agent, "agent_B");
+        assert_eq!(nginx.restart_policy, Some(RestartPolicy::Always));
+        assert!(parse_edited_state("invalid: [yaml", &update_mask).is_err());
+    }
+}
@@ -0,0 +1,247 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Write;
+
+use api::ank_base::exec_output::ExecOutputEnum;
+use common::commands::ExecInput;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use super::{server_connection::ServerConnectionError, CliCommands};
+use crate::{cli_error::CliError, output_debug};
+
+const STDIN_CHUNK_SIZE: usize = 4096;
+
+enum ExecEvent {
+    Input(std::io::Result<Vec<u8>>),
+    Output(Result<ExecOutputEnum, ServerConnectionError>),
+}
+
+fn write_output(writer: &mut impl Write, data: &[u8]) -> Result<(), CliError> {
+    writer.write_all(data)?;
+    writer.flush()?;
+    Ok(())
+}
+
+impl CliCommands {
+    // [impl->swdd~cli-provides-exec~1]
+    pub async fn exec(
+        &mut self,
+        workload_name: String,
+        command: Vec<String>,
+    ) -> Result<i32, CliError> {
+        self.exec_with_io(
+            workload_name,
+            command,
+            tokio::io::stdin(),
+            &mut std::io::stdout(),
+            &mut std::io::stderr(),
+        )
+        .await
+    }
+
+    async fn exec_with_io(
+        &mut self,
+        workload_name: String,
+        command: Vec<String>,
+        mut stdin: impl AsyncRead + Unpin,
+        stdout: &mut impl Write,
+        stderr: &mut impl Write,
+    ) -> Result<i32, CliError> {
+        let request_id = self
+            .server_connection
+            .start_exec(workload_name, command)
+            .await?;
+
+        let mut stdin_open = true;
+        let mut buffer = vec![0; STDIN_CHUNK_SIZE];
+        loop {
+            // the input is preferred to forward it before the workload reacts on it
+            let event = tokio::select! {
+                biased;
+                read_result = stdin.read(&mut buffer), if stdin_open => {
+                    ExecEvent::Input(read_result.map(|read_bytes| buffer[..read_bytes].to_vec()))
+                }
+                output = self.server_connection.read_next_exec_output(&request_id) => {
+                    ExecEvent::Output(output)
+                }
+            };
+
+            match event {
+                // [impl->swdd~cli-exec-forwards-input~1]
+                ExecEvent::Input(read_result) => {
+                    let exec_input = match read_result {
+                        Ok(data) if !data.is_empty() => ExecInput {
+                            stdin: data,
+                            close_stdin: false,
+                        },
+                        result => {
+                            if let Err(error) = result {
+                                output_debug!("Could not read from stdin: '{}'", error);
+                            }
+                            stdin_open = false;
+                            ExecInput {
+                                stdin: vec![],
+                                close_stdin: true,
+                            }
+                        }
+                    };
+                    self.server_connection
+                        .send_exec_input(request_id.clone(), exec_input)
+                        .await?;
+                }
+                // [impl->swdd~cli-exec-outputs-workload-output~1]
+                ExecEvent::Output(output) => match output? {
+                    ExecOutputEnum::Stdout(data) => write_output(stdout, &data)?,
+                    ExecOutputEnum::Stderr(data) => write_output(stderr, &data)?,
+                    ExecOutputEnum::ExitCode(exit_code) => {
+                        output_debug!("Exec session ended with exit code '{}'", exit_code);
+                        return Ok(exit_code);
+                    }
+                },
+            }
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use api::ank_base::exec_output::ExecOutputEnum;
+    use common::commands::ExecInput;
+    use mockall::predicate::eq;
+
+    use crate::cli_commands::{
+        server_connection::{MockServerConnection, ServerConnectionError},
+        CliCommands,
+    };
+    use crate::cli_error::CliError;
+
+    const WORKLOAD_NAME: &str = "nginx";
+    const REQUEST_ID: &str = "request_id";
+
+    // [utest->swdd~cli-provides-exec~1]
+    // [utest->swdd~cli-exec-forwards-input~1]
+    // [utest->swdd~cli-exec-outputs-workload-output~1]
+    #[tokio::test]
+    async fn utest_exec_forwards_input_and_output() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_start_exec()
+            .with(eq(WORKLOAD_NAME.to_string()), eq(vec!["cat".to_string()]))
+            .once()
+            .return_once(|_, _| Ok(REQUEST_ID.to_string()));
+
+        let sent_inputs = Arc::new(Mutex::new(Vec::new()));
+        let sent_inputs_clone = sent_inputs.clone();
+        mock_server_connection
+            .expect_send_exec_input()
+            .returning(move |_, exec_input| {
+                sent_inputs_clone.lock().unwrap().push(exec_input);
+                Ok(())
+            });
+
+        let mut outputs = vec![
+            ExecOutputEnum::ExitCode(2),
+            ExecOutputEnum::Stderr(b"warning".to_vec()),
+            ExecOutputEnum::Stdout(b"hello".to_vec()),
+        ];
+        mock_server_connection
+            .expect_read_next_exec_output()
+            .returning(move |_| Ok(outputs.pop().unwrap()));
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let exit_code = cmd
+            .exec_with_io(
+                WORKLOAD_NAME.to_string(),
+                vec!["cat".to_string()],
+                &b"hello"[..],
+                &mut stdout,
+                &mut stderr,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(exit_code, 2);
+        assert_eq!(stdout, b"hello");
+        assert_eq!(stderr, b"warning");
+        assert_eq!(
+            *sent_inputs.lock().unwrap(),
+            vec![
+                ExecInput {
+                    stdin: b"hello".to_vec(),
+                    close_stdin: false,
+                },
+                ExecInput {
+                    stdin: vec![],
+                    close_stdin: true,
+                },
+            ]
+        );
+    }
+
+    // [utest->swdd~cli-provides-exec~1]
+    #[tokio::test]
+    async fn utest_exec_fails_on_error_response() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_start_exec()
+            .once()
+            .return_once(|_, _| Ok(REQUEST_ID.to_string()));
+        mock_server_connection
+            .expect_send_exec_input()
+            .returning(|_, _| Ok(()));
+        mock_server_connection
+            .expect_read_next_exec_output()
+            .return_once(|_| {
+                Err(ServerConnectionError::ExecutionError(
+                    "Workload 'nginx' does not exist".to_string(),
+                ))
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd
+            .exec_with_io(
+                WORKLOAD_NAME.to_string(),
+                vec!["cat".to_string()],
+                &b""[..],
+                &mut Vec::new(),
+                &mut Vec::new(),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(CliError::ExecutionError(message)) if message == "Workload 'nginx' does not exist"
+        ));
+    }
+}
@@ -11,22 +11,57 @@
 // under the License.
 //
 // SPDX-License-Identifier: Apache-2.0
+use std::{
+    fmt::Write,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
 use super::CliCommands;
 use crate::{
-    cli_commands::{agent_table_row::AgentTableRow, cli_table::CliTable},
+    cli_commands::{
+        agent_table_row::{AgentTableRow, WideAgentTableRow},
+        cli_table::CliTable,
+        describe_workload::{join_or_none, write_lines, NONE},
+        output::{render_list, ListOutputFormat},
+    },
     cli_error::CliError,
     filtered_complete_state::FilteredAgentAttributes,
     output_debug,
 };
 
-use common::objects::WorkloadStatesMap;
+use common::objects::{WorkloadState, WorkloadStatesMap};
+use serde::Serialize;
 
 const EMPTY_FILTER_MASK: [String; 0] = [];
+const AGENTS: &str = "agents";
+const WORKLOAD_STATES: &str = "workloadStates";
+
+// The details of a single agent as output in the yaml and json formats
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AgentDetails {
+    name: String,
+    #[serde(flatten)]
+    attributes: FilteredAgentAttributes,
+    workloads: Vec<AgentWorkload>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AgentWorkload {
+    name: String,
+    id: String,
+    execution_state: String,
+    additional_info: String,
+}
 
 impl CliCommands {
     // [impl->swdd~cli-provides-list-of-agents~1]
     // [impl->swdd~cli-processes-complete-state-to-provide-connected-agents~1]
-    pub async fn get_agents(&mut self) -> Result<String, CliError> {
+    pub async fn get_agents(
+        &mut self,
+        output_format: &ListOutputFormat,
+    ) -> Result<String, CliError> {
         let filtered_complete_state = self
             .server_connection
             .get_complete_state(&EMPTY_FILTER_MASK)
@@ -44,32 +79,210 @@ impl CliCommands {
 
         output_debug!("Got agents of complete state: {:?}", agent_table_rows);
 
-        // [impl->swdd~cli-presents-connected-agents-as-table~2]
-        Ok(CliTable::new(&agent_table_rows).create_default_table())
+        let wide_agent_table_rows: Vec<WideAgentTableRow> = agent_table_rows
+            .into_iter()
+            .map(|(agent_table_row, agent_attributes)| WideAgentTableRow {
+                agent_table_row,
+                disk_available: agent_attributes.get_disk_available_as_string(),
+                network_received: agent_attributes.get_network_received_as_string(),
+                network_transmitted: agent_attributes.get_network_transmitted_as_string(),
+                temperature: agent_attributes.get_temperature_as_string(),
+            })
+            .collect();
+
+        // [impl->swdd~cli-supports-list-output-formats~1]
+        render_list(&wide_agent_table_rows, output_format, |rows, wide| {
+            if wide {
+                // [impl->swdd~cli-presents-extended-agent-load-with-wide-flag~1]
+                CliTable::new(rows).create_default_table()
+            } else {
+                let agent_table_rows: Vec<AgentTableRow> =
+                    rows.iter().map(|row| row.agent_table_row.clone()).collect();
+
+                // [impl->swdd~cli-presents-connected-agents-as-table~2]
+                CliTable::new(&agent_table_rows).create_default_table()
+            }
+        })
+    }
+
+    // [impl->swdd~cli-provides-agent-details~1]
+    pub async fn get_agent(
+        &mut self,
+        agent_name: &str,
+        output_format: &ListOutputFormat,
+    ) -> Result<String, CliError> {
+        let filtered_complete_state = self
+            .server_connection
+            .get_complete_state(&[
+                format!("{}.{}", AGENTS, agent_name),
+                format!("{}.{}", WORKLOAD_STATES, agent_name),
+            ])
+            .await?;
+
+        let attributes = filtered_complete_state
+            .agents
+            .and_then(|agents| agents.agents)
+            .and_then(|mut agents| agents.remove(agent_name))
+            .ok_or_else(|| {
+                CliError::ExecutionError(format!("Agent '{}' is not connected", agent_name))
+            })?;
+
+        let mut workload_states = filtered_complete_state
+            .workload_states
+            .unwrap_or_default()
+            .get_workload_state_for_agent(agent_name);
+        // sort to ensure consistent output
+        workload_states.sort_by(|a, b| {
+            (a.instance_name.workload_name(), a.instance_name.id())
+                .cmp(&(b.instance_name.workload_name(), b.instance_name.id()))
+        });
+
+        output_debug!(
+            "Got agent '{}' with attributes {:?} and workload states {:?}",
+            agent_name,
+            attributes,
+            workload_states
+        );
+
+        match output_format {
+            ListOutputFormat::Table | ListOutputFormat::Wide => Ok(describe_agent(
+                agent_name,
+                &attributes,
+                &workload_states,
+                SystemTime::now(),
+            )),
+            ListOutputFormat::Yaml | ListOutputFormat::Json => {
+                let agent_details = AgentDetails {
+                    name: agent_name.to_owned(),
+                    attributes,
+                    workloads: workload_states
+                        .into_iter()
+                        .map(|workload_state| AgentWorkload {
+                            name: workload_state.instance_name.workload_name().to_owned(),
+                            id: workload_state.instance_name.id().to_owned(),
+                            execution_state: workload_state.execution_state.state.to_string(),
+                            additional_info: workload_state.execution_state.additional_info,
+                        })
+                        .collect(),
+                };
+                if *output_format == ListOutputFormat::Json {
+                    Ok(serde_json::to_string_pretty(&agent_details)?)
+                } else {
+                    Ok(serde_yaml::to_string(&agent_details)?.trim_end().to_owned())
+                }
+            }
+            ListOutputFormat::CustomColumns(_) => Err(CliError::ExecutionError(
+                "Custom columns are not supported for a single agent".to_owned(),
+            )),
+        }
     }
 }
 
+// [impl->swdd~cli-provides-agent-details~1]
+fn describe_agent(
+    agent_name: &str,
+    attributes: &FilteredAgentAttributes,
+    workload_states: &[WorkloadState],
+    now: SystemTime,
+) -> String {
+    let mut report = String::new();
+    let format_timestamp = |timestamp: u64| {
+        humantime::format_rfc3339_millis(UNIX_EPOCH + Duration::from_millis(timestamp))
+    };
+
+    let _ = writeln!(report, "Name:            {}", agent_name);
+    let version = if attributes.version.is_empty() {
+        NONE
+    } else {
+        attributes.version.as_str()
+    };
+    let _ = writeln!(report, "Version:         {}", version);
+    let _ = writeln!(
+        report,
+        "Runtimes:        {}",
+        join_or_none(&attributes.runtimes)
+    );
+    let mut labels = attributes
+        .labels
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>();
+    labels.sort();
+    let _ = writeln!(report, "Labels:          {}", join_or_none(&labels));
+    match attributes.connected_at {
+        Some(connected_at) => {
+            let uptime = now
+                .duration_since(UNIX_EPOCH + Duration::from_millis(connected_at))
+                .unwrap_or_default();
+            let _ = writeln!(
+                report,
+                "Connected Since: {}",
+                format_timestamp(connected_at)
+            );
+            let _ = writeln!(
+                report,
+                "Uptime:          {}",
+                humantime::format_duration(Duration::from_secs(uptime.as_secs()))
+            );
+        }
+        None => {
+            let _ = writeln!(report, "Connected Since: {}", NONE);
+            let _ = writeln!(report, "Uptime:          {}", NONE);
+        }
+    }
+
+    let _ = writeln!(report, "Load History:");
+    write_lines(
+        &mut report,
+        attributes.load_history.iter().map(|load_sample| {
+            format!(
+                "{} cpu {}% free memory {}B",
+                format_timestamp(load_sample.timestamp),
+                load_sample.cpu_usage,
+                load_sample.free_memory
+            )
+        }),
+    );
+
+    let _ = writeln!(report, "Workloads:");
+    write_lines(
+        &mut report,
+        workload_states.iter().map(|workload_state| {
+            format!(
+                "{} {} {} {}",
+                workload_state.instance_name.workload_name(),
+                workload_state.instance_name.id(),
+                workload_state.execution_state.state,
+                workload_state.execution_state.additional_info
+            )
+        }),
+    );
+    report.trim_end().to_owned()
+}
+
+// The agent attributes are kept next to the rows to fill the additional columns of the wide output.
 fn transform_into_table_rows(
     agents_map: impl Iterator<Item = (String, FilteredAgentAttributes)>,
     workload_states_map: &WorkloadStatesMap,
-) -> Vec<AgentTableRow> {
-    let mut agent_table_rows: Vec<AgentTableRow> = agents_map
+) -> Vec<(AgentTableRow, FilteredAgentAttributes)> {
+    let mut agent_table_rows: Vec<(AgentTableRow, FilteredAgentAttributes)> = agents_map
         .map(|(agent_name, mut agent_attributes)| {
             let workload_states_count = workload_states_map
                 .get_workload_state_for_agent(&agent_name)
                 .len() as u32;
 
-            AgentTableRow {
+            let agent_table_row = AgentTableRow {
                 agent_name,
                 workloads: workload_states_count,
                 cpu_usage: agent_attributes.get_cpu_usage_as_string(),
                 free_memory: agent_attributes.get_free_memory_as_string(),
-            }
+            };
+            (agent_table_row, agent_attributes)
         })
         .collect();
 
     // sort to ensure consistent output
-    agent_table_rows.sort_by(|a, b| a.agent_name.cmp(&b.agent_name));
+    agent_table_rows.sort_by(|(a, _), (b, _)| a.agent_name.cmp(&b.agent_name));
     agent_table_rows
 }
 
@@ -83,22 +296,34 @@ fn transform_into_table_rows(
 
 #[cfg(test)]
 mod tests {
-    use crate::cli_commands::{
-        server_connection::{MockServerConnection, ServerConnectionError},
-        CliCommands,
+    use std::{
+        collections::HashMap,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    use super::describe_agent;
+    use crate::{
+        cli_commands::{
+            output::ListOutputFormat,
+            server_connection::{MockServerConnection, ServerConnectionError},
+            CliCommands,
+        },
+        cli_error::CliError,
+        filtered_complete_state::FilteredAgentAttributes,
     };
     use api::ank_base;
     use common::{
         objects::{
             generate_test_agent_map, generate_test_agent_map_from_specs,
             generate_test_workload_spec_with_param, generate_test_workload_states_map_with_data,
-            AgentMap, ExecutionState,
+            AgentAttributes, AgentLoadSample, AgentMap, CpuUsage, DiskUsage, ExecutionState,
+            FreeMemory, NetworkInterfaceUsage, NetworkUsage, Temperature, ThermalZone,
+            WorkloadInstanceName, WorkloadState,
         },
         test_utils,
     };
     use mockall::predicate::eq;
 
-    const RESPONSE_TIMEOUT_MS: u64 = 3000;
     const AGENT_A_NAME: &str = "agent_A";
     const AGENT_B_NAME: &str = "agent_B";
     const AGENT_UNCONNECTED_NAME: &str = "agent_not_connected";
@@ -134,12 +359,11 @@ mod tests {
             });
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
-        let table_output_result = cmd.get_agents().await;
+        let table_output_result = cmd.get_agents(&ListOutputFormat::Table).await;
 
         let expected_table_output = [
             "NAME      WORKLOADS   CPU USAGE   FREE MEMORY",
@@ -172,12 +396,11 @@ mod tests {
             });
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
-        let table_output_result = cmd.get_agents().await;
+        let table_output_result = cmd.get_agents(&ListOutputFormat::Table).await;
 
         let expected_table_output = "NAME   WORKLOADS   CPU USAGE   FREE MEMORY".to_string();
 
@@ -199,12 +422,11 @@ mod tests {
             });
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
-        let table_output_result = cmd.get_agents().await;
+        let table_output_result = cmd.get_agents(&ListOutputFormat::Table).await;
 
         let expected_table_output = [
             "NAME      WORKLOADS   CPU USAGE   FREE MEMORY",
@@ -229,12 +451,11 @@ mod tests {
             });
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
-        let table_output_result = cmd.get_agents().await;
+        let table_output_result = cmd.get_agents(&ListOutputFormat::Table).await;
         assert!(table_output_result.is_err());
     }
 
@@ -265,12 +486,11 @@ mod tests {
             });
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
-        let table_output_result = cmd.get_agents().await;
+        let table_output_result = cmd.get_agents(&ListOutputFormat::Table).await;
 
         let expected_table_output = [
             "NAME      WORKLOADS   CPU USAGE   FREE MEMORY",
@@ -302,12 +522,11 @@ mod tests {
             });
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
-        let table_output_result = cmd.get_agents().await;
+        let table_output_result = cmd.get_agents(&ListOutputFormat::Table).await;
 
         let expected_table_output = [
             "NAME      WORKLOADS   CPU USAGE   FREE MEMORY",
@@ -317,4 +536,248 @@ mod tests {
 
         assert_eq!(Ok(expected_table_output), table_output_result);
     }
+
+    // [utest->swdd~cli-presents-extended-agent-load-with-wide-flag~1]
+    #[tokio::test]
+    async fn test_get_agents_wide() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec![]))
+            .return_once(|_| {
+                let mut complete_state = test_utils::generate_test_complete_state(vec![]);
+                complete_state.agents = generate_test_agent_map(AGENT_A_NAME);
+                complete_state
+                    .agents
+                    .entry(AGENT_B_NAME.to_string())
+                    .or_insert(AgentAttributes {
+                        cpu_usage: Some(CpuUsage { cpu_usage: 42 }),
+                        free_memory: Some(FreeMemory { free_memory: 42 }),
+                        disk_usage: Some(DiskUsage {
+                            total_space: 100,
+                            available_space: 42,
+                        }),
+                        network_usage: Some(NetworkUsage {
+                            interfaces: vec![
+                                NetworkInterfaceUsage {
+                                    name: "eth0".to_string(),
+                                    received_bytes: 40,
+                                    transmitted_bytes: 20,
+                                },
+                                NetworkInterfaceUsage {
+                                    name: "lo".to_string(),
+                                    received_bytes: 2,
+                                    transmitted_bytes: 1,
+                                },
+                            ],
+                        }),
+                        temperature: Some(Temperature {
+                            thermal_zones: vec![
+                                ThermalZone {
+                                    zone_type: "cpu-thermal".to_string(),
+                                    temperature: 42500,
+                                },
+                                ThermalZone {
+                                    zone_type: "gpu-thermal".to_string(),
+                                    temperature: 40000,
+                                },
+                            ],
+                        }),
+                        labels: Default::default(),
+                        version: Default::default(),
+                        runtimes: Default::default(),
+                        connected_at: Default::default(),
+                        load_history: Default::default(),
+                    });
+                Ok(ank_base::CompleteState::from(complete_state).into())
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let table_output_result = cmd.get_agents(&ListOutputFormat::Wide).await;
+
+        // the wide table is truncated to the terminal width of 80 columns in the tests
+        let expected_table_output = [
+            "NAME      WORKLOADS   CPU USAGE   FREE MEMORY   DISK AVAILABLE   NETWORK RX   NE",
+            "agent_A   0           42%         42B                                           ",
+            "agent_B   0           42%         42B           42B              42B          21",
+        ]
+        .join("\n");
+
+        assert_eq!(Ok(expected_table_output), table_output_result);
+    }
+
+    // 2024-01-01T00:00:00Z
+    const CONNECTED_AT: u64 = 1_704_067_200_000;
+
+    fn generate_test_agent_attributes() -> AgentAttributes {
+        AgentAttributes {
+            cpu_usage: Some(CpuUsage { cpu_usage: 42 }),
+            free_memory: Some(FreeMemory { free_memory: 42 }),
+            labels: HashMap::from([("zone".to_string(), "cockpit".to_string())]),
+            version: "0.6.0".to_string(),
+            runtimes: vec!["podman".to_string(), "podman-kube".to_string()],
+            connected_at: CONNECTED_AT,
+            load_history: vec![
+                AgentLoadSample {
+                    timestamp: CONNECTED_AT,
+                    cpu_usage: 10,
+                    free_memory: 1024,
+                },
+                AgentLoadSample {
+                    timestamp: CONNECTED_AT + 60_000,
+                    cpu_usage: 42,
+                    free_memory: 42,
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    // [utest->swdd~cli-provides-agent-details~1]
+    #[test]
+    fn utest_describe_agent() {
+        let attributes = FilteredAgentAttributes::from(ank_base::AgentAttributes::from(
+            generate_test_agent_attributes(),
+        ));
+        let workload_states = vec![WorkloadState {
+            instance_name: WorkloadInstanceName::new(AGENT_A_NAME, WORKLOAD_NAME_1, "ID_X"),
+            execution_state: ExecutionState::running(),
+        }];
+
+        let report = describe_agent(
+            AGENT_A_NAME,
+            &attributes,
+            &workload_states,
+            UNIX_EPOCH + Duration::from_millis(CONNECTED_AT + 3_723_000),
+        );
+
+        let expected_report = [
+            "Name:            agent_A",
+            "Version:         0.6.0",
+            "Runtimes:        podman, podman-kube",
+            "Labels:          zone=cockpit",
+            "Connected Since: 2024-01-01T00:00:00.000Z",
+            "Uptime:          1h 2m 3s",
+            "Load History:",
+            "  2024-01-01T00:00:00.000Z cpu 10% free memory 1024B",
+            "  2024-01-01T00:01:00.000Z cpu 42% free memory 42B",
+            "Workloads:",
+            "  workload_1 ID_X Running(Ok)",
+        ]
+        .join("\n");
+
+        assert_eq!(report, expected_report);
+    }
+
+    // [utest->swdd~cli-provides-agent-details~1]
+    #[test]
+    fn utest_describe_agent_without_reported_details() {
+        let attributes = FilteredAgentAttributes::from(ank_base::AgentAttributes::default());
+
+        let report = describe_agent(AGENT_A_NAME, &attributes, &[], UNIX_EPOCH);
+
+        let expected_report = [
+            "Name:            agent_A",
+            "Version:         <none>",
+            "Runtimes:        <none>",
+            "Labels:          <none>",
+            "Connected Since: <none>",
+            "Uptime:          <none>",
+            "Load History:",
+            "  <none>",
+            "Workloads:",
+            "  <none>",
+        ]
+        .join("\n");
+
+        assert_eq!(report, expected_report);
+    }
+
+    // [utest->swdd~cli-provides-agent-details~1]
+    #[tokio::test]
+    async fn utest_get_agent_json() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec![
+                format!("agents.{}", AGENT_A_NAME),
+                format!("workloadStates.{}", AGENT_A_NAME),
+            ]))
+            .return_once(|_| {
+                let mut complete_state = test_utils::generate_test_complete_state(vec![]);
+                complete_state.agents = AgentMap::new();
+                complete_state
+                    .agents
+                    .entry(AGENT_A_NAME.to_string())
+                    .or_insert(generate_test_agent_attributes());
+                complete_state.workload_states = generate_test_workload_states_map_with_data(
+                    AGENT_A_NAME,
+                    WORKLOAD_NAME_1,
+                    "ID_X",
+                    ExecutionState::running(),
+                );
+                Ok(ank_base::CompleteState::from(complete_state).into())
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let output = cmd
+            .get_agent(AGENT_A_NAME, &ListOutputFormat::Json)
+            .await
+            .unwrap();
+        let agent_details: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(agent_details["name"], AGENT_A_NAME);
+        assert_eq!(agent_details["cpuUsage"], 42);
+        assert_eq!(agent_details["version"], "0.6.0");
+        assert_eq!(
+            agent_details["runtimes"],
+            serde_json::json!(["podman", "podman-kube"])
+        );
+        assert_eq!(agent_details["connectedAt"], CONNECTED_AT);
+        assert_eq!(agent_details["loadHistory"][1]["cpuUsage"], 42);
+        assert_eq!(
+            agent_details["workloads"],
+            serde_json::json!([{
+                "name": WORKLOAD_NAME_1,
+                "id": "ID_X",
+                "executionState": "Running(Ok)",
+                "additionalInfo": "",
+            }])
+        );
+    }
+
+    // [utest->swdd~cli-provides-agent-details~1]
+    #[tokio::test]
+    async fn utest_get_agent_not_connected() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .return_once(|_| {
+                let mut complete_state = test_utils::generate_test_complete_state(vec![]);
+                complete_state.agents = AgentMap::new();
+                Ok(ank_base::CompleteState::from(complete_state).into())
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        assert_eq!(
+            cmd.get_agent(AGENT_UNCONNECTED_NAME, &ListOutputFormat::Table)
+                .await,
+            Err(CliError::ExecutionError(format!(
+                "Agent '{}' is not connected",
+                AGENT_UNCONNECTED_NAME
+            )))
+        );
+    }
 }
@@ -0,0 +1,336 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Write;
+use std::time::{Duration, UNIX_EPOCH};
+
+use api::ank_base;
+use serde::Serialize;
+
+use crate::{
+    cli::EventsOutputFormat,
+    cli_commands::{audit_table_row::AuditTableRow, cli_table::CliTable},
+    cli_error::CliError,
+    output_debug,
+};
+
+use super::CliCommands;
+
+#[cfg(not(test))]
+const AUDIT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+#[cfg(test)]
+const AUDIT_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct AuditEntry {
+    sequence_number: u64,
+    timestamp: String,
+    requester_type: String,
+    requester: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    workload_instance_name: String,
+    update_mask: Vec<String>,
+    accepted: bool,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    message: String,
+}
+
+impl From<ank_base::AuditEntry> for AuditEntry {
+    fn from(value: ank_base::AuditEntry) -> Self {
+        AuditEntry {
+            sequence_number: value.sequence_number,
+            timestamp: humantime::format_rfc3339_millis(
+                UNIX_EPOCH + Duration::from_millis(value.timestamp),
+            )
+            .to_string(),
+            requester_type: ank_base::RequesterType::from_i32(value.requester_type)
+                .map(|requester_type| format!("{requester_type:?}"))
+                .unwrap_or_else(|| format!("Unknown({})", value.requester_type)),
+            requester: value.requester,
+            workload_instance_name: value.workload_instance_name,
+            update_mask: value.update_mask,
+            accepted: value.accepted,
+            message: value.message,
+        }
+    }
+}
+
+impl From<AuditEntry> for AuditTableRow {
+    fn from(value: AuditEntry) -> Self {
+        AuditTableRow {
+            sequence_number: value.sequence_number,
+            time: value.timestamp,
+            requester_type: value.requester_type,
+            requester: value.requester,
+            workload_instance_name: value.workload_instance_name,
+            update_mask: value.update_mask.join(", "),
+            result: if value.accepted {
+                "Accepted".to_owned()
+            } else {
+                format!("Rejected: {}", value.message)
+            },
+        }
+    }
+}
+
+// [impl->swdd~cli-provides-audit-log~1]
+fn write_audit_entries(
+    entries: Vec<AuditEntry>,
+    output_format: EventsOutputFormat,
+    with_header: bool,
+    mut writer: impl Write,
+) -> Result<(), CliError> {
+    match output_format {
+        EventsOutputFormat::Table => {
+            if entries.is_empty() && !with_header {
+                return Ok(());
+            }
+            let rows: Vec<AuditTableRow> = entries.into_iter().map(Into::into).collect();
+            let table = CliTable::new(&rows).create_default_table();
+            let lines_to_skip = if with_header { 0 } else { 1 };
+            for line in table.lines().skip(lines_to_skip) {
+                writeln!(writer, "{line}")?;
+            }
+        }
+        EventsOutputFormat::Json => {
+            for entry in entries {
+                serde_json::to_writer(&mut writer, &entry)?;
+                writeln!(writer)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+impl CliCommands {
+    // [impl->swdd~cli-provides-audit-log~1]
+    pub async fn get_audit(
+        &mut self,
+        follow: bool,
+        output_format: EventsOutputFormat,
+        mut writer: impl Write,
+    ) -> Result<(), CliError> {
+        let mut last_sequence_number = 0;
+        let mut with_header = true;
+        let mut poll_interval = tokio::time::interval(AUDIT_POLL_INTERVAL);
+        loop {
+            poll_interval.tick().await;
+            let entries = self
+                .server_connection
+                .get_audit(last_sequence_number)
+                .await?
+                .entries;
+            output_debug!("Received audit entries: {:?}", entries);
+
+            if let Some(last_entry) = entries.last() {
+                last_sequence_number = last_entry.sequence_number;
+            }
+            write_audit_entries(
+                entries.into_iter().map(Into::into).collect(),
+                output_format,
+                with_header,
+                &mut writer,
+            )?;
+            writer.flush()?;
+            with_header = false;
+
+            if !follow {
+                return Ok(());
+            }
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use api::ank_base;
+    use mockall::predicate::eq;
+
+    use crate::{
+        cli::EventsOutputFormat,
+        cli_commands::{
+            server_connection::{MockServerConnection, ServerConnectionError},
+            CliCommands,
+        },
+    };
+
+    fn generate_test_audit_entries() -> ank_base::AuditEntries {
+        ank_base::AuditEntries {
+            entries: vec![
+                ank_base::AuditEntry {
+                    sequence_number: 1,
+                    timestamp: 1_700_000_000_000,
+                    requester_type: ank_base::RequesterType::Cli as i32,
+                    requester: "cli-conn-1234".to_owned(),
+                    workload_instance_name: String::new(),
+                    update_mask: vec!["desiredState.workloads.nginx".to_owned()],
+                    accepted: true,
+                    message: String::new(),
+                },
+                ank_base::AuditEntry {
+                    sequence_number: 2,
+                    timestamp: 1_700_000_000_500,
+                    requester_type: ank_base::RequesterType::Workload as i32,
+                    requester: "agent_A".to_owned(),
+                    workload_instance_name: "controller.1234.agent_A".to_owned(),
+                    update_mask: vec!["desiredState.configs".to_owned()],
+                    accepted: false,
+                    message: "Access denied".to_owned(),
+                },
+            ],
+        }
+    }
+
+    // [utest->swdd~cli-provides-audit-log~1]
+    #[tokio::test]
+    async fn utest_get_audit_table() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_audit()
+            .with(eq(0))
+            .return_once(|_| Ok(generate_test_audit_entries()));
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let mut cmd_output = Vec::new();
+        cmd.get_audit(false, EventsOutputFormat::Table, &mut cmd_output)
+            .await
+            .unwrap();
+
+        // the table is truncated to the terminal width of 80 characters used in the tests
+        let expected_table_output = [
+            "SEQ   TIME                       TYPE       REQUESTER       WORKLOAD            ",
+            "1     2023-11-14T22:13:20.000Z   Cli        cli-conn-1234                       ",
+            "2     2023-11-14T22:13:20.500Z   Workload   agent_A         controller.1234.agen",
+        ]
+        .join("\n");
+        assert_eq!(
+            String::from_utf8(cmd_output)
+                .unwrap()
+                .trim_end_matches('\n'),
+            expected_table_output
+        );
+    }
+
+    // [utest->swdd~cli-provides-audit-log~1]
+    #[tokio::test]
+    async fn utest_get_audit_json() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_audit()
+            .with(eq(0))
+            .return_once(|_| Ok(generate_test_audit_entries()));
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let mut cmd_output = Vec::new();
+        cmd.get_audit(false, EventsOutputFormat::Json, &mut cmd_output)
+            .await
+            .unwrap();
+
+        let output: Vec<serde_json::Value> = String::from_utf8(cmd_output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            output,
+            vec![
+                serde_json::json!({
+                    "sequenceNumber": 1,
+                    "timestamp": "2023-11-14T22:13:20.000Z",
+                    "requesterType": "Cli",
+                    "requester": "cli-conn-1234",
+                    "updateMask": ["desiredState.workloads.nginx"],
+                    "accepted": true
+                }),
+                serde_json::json!({
+                    "sequenceNumber": 2,
+                    "timestamp": "2023-11-14T22:13:20.500Z",
+                    "requesterType": "Workload",
+                    "requester": "agent_A",
+                    "workloadInstanceName": "controller.1234.agent_A",
+                    "updateMask": ["desiredState.configs"],
+                    "accepted": false,
+                    "message": "Access denied"
+                }),
+            ]
+        );
+    }
+
+    // [utest->swdd~cli-provides-audit-log~1]
+    #[tokio::test]
+    async fn utest_get_audit_follow_requests_entries_after_last_received() {
+        let mut entries = generate_test_audit_entries().entries;
+        let second_entry = entries.pop().unwrap();
+
+        let mut mock_server_connection = MockServerConnection::default();
+        let mut seq = mockall::Sequence::new();
+        mock_server_connection
+            .expect_get_audit()
+            .with(eq(0))
+            .once()
+            .in_sequence(&mut seq)
+            .return_once(|_| Ok(ank_base::AuditEntries { entries }));
+        mock_server_connection
+            .expect_get_audit()
+            .with(eq(1))
+            .once()
+            .in_sequence(&mut seq)
+            .return_once(|_| {
+                Ok(ank_base::AuditEntries {
+                    entries: vec![second_entry],
+                })
+            });
+        mock_server_connection
+            .expect_get_audit()
+            .with(eq(2))
+            .once()
+            .in_sequence(&mut seq)
+            .return_once(|_| {
+                Err(ServerConnectionError::ExecutionError(
+                    "connection lost".to_owned(),
+                ))
+            });
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let mut cmd_output = Vec::new();
+        let result = cmd
+            .get_audit(true, EventsOutputFormat::Table, &mut cmd_output)
+            .await;
+        assert!(result.is_err());
+
+        let output = String::from_utf8(cmd_output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("SEQ"));
+        assert!(lines[1].starts_with("1 "));
+        assert!(lines[2].starts_with("2 "));
+    }
+}
@@ -11,17 +11,46 @@
 // under the License.
 //
 // SPDX-License-Identifier: Apache-2.0
-use super::CliCommands;
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::{
+    output::{render_list, ListOutputFormat},
+    CliCommands,
+};
 use crate::cli_commands::config_table_row::ConfigTableRow;
 use crate::cli_commands::DESIRED_STATE_CONFIGS;
-use crate::filtered_complete_state::FilteredCompleteState;
+use crate::filtered_complete_state::{FilteredCompleteState, FilteredWorkloadSpec};
 use crate::{cli_commands::cli_table::CliTable, cli_error::CliError, output_debug};
-use common::objects::ConfigItem;
+use common::config_renderer::ConfigRenderer;
+use common::helpers::serialize_to_ordered_map;
+use common::objects::{
+    desired_state_path_of_workload, split_qualified_workload_name, ConfigItem, StoredWorkloadSpec,
+};
+
+// The fields of a workload which can contain templates referencing the configs of the workload
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RenderedWorkloadFields {
+    agent: String,
+    runtime_config: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    registry_auth: Option<String>,
+    #[serde(
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    env: HashMap<String, String>,
+}
 
 impl CliCommands {
     // [impl->swdd~cli-provides-list-of-configs~1]
     // [impl->swdd~cli-processes-complete-state-to-provide-connected-agents~1]
-    pub async fn get_configs(&mut self) -> Result<String, CliError> {
+    pub async fn get_configs(
+        &mut self,
+        output_format: &ListOutputFormat,
+    ) -> Result<String, CliError> {
         let filtered_complete_state: FilteredCompleteState = self
             .server_connection
             .get_complete_state(&[DESIRED_STATE_CONFIGS.to_string()])
@@ -39,7 +68,126 @@ impl CliCommands {
         output_debug!("Got configs: {:?}", config_table_rows);
 
         // [impl->swdd~cli-shall-present-configs-as-table~1]
-        Ok(CliTable::new(&config_table_rows).create_default_table())
+        render_list(&config_table_rows, output_format, |rows, _wide| {
+            CliTable::new(rows).create_default_table()
+        })
+    }
+
+    // [impl->swdd~cli-provides-content-of-config~1]
+    pub async fn get_config(
+        &mut self,
+        config_name: &str,
+        output_format: &ListOutputFormat,
+    ) -> Result<String, CliError> {
+        let config_item = self
+            .server_connection
+            .get_complete_state(&[format!("{}.{}", DESIRED_STATE_CONFIGS, config_name)])
+            .await?
+            .desired_state
+            .and_then(|state| state.configs)
+            .and_then(|mut configs| configs.remove(config_name))
+            .ok_or_else(|| {
+                CliError::ExecutionError(format!("Config '{}' does not exist", config_name))
+            })?;
+
+        output_debug!("Got config '{}': {:?}", config_name, config_item);
+
+        render_single_item(&config_item, output_format)
+    }
+
+    // [impl->swdd~cli-provides-rendered-config-of-workload~2]
+    pub async fn get_rendered_config(
+        &mut self,
+        config_name: &str,
+        workload_name: &str,
+        output_format: &ListOutputFormat,
+    ) -> Result<String, CliError> {
+        let filtered_complete_state = self
+            .server_connection
+            .get_complete_state(&[
+                desired_state_path_of_workload(workload_name),
+                DESIRED_STATE_CONFIGS.to_string(),
+            ])
+            .await?;
+
+        let workload_not_existing =
+            || CliError::ExecutionError(format!("Workload '{}' does not exist", workload_name));
+        let desired_state = filtered_complete_state
+            .desired_state
+            .ok_or_else(workload_not_existing)?;
+        let workload_spec = match split_qualified_workload_name(workload_name) {
+            Some((namespace, unqualified_name)) => desired_state
+                .namespaces
+                .and_then(|mut namespaces| namespaces.remove(namespace))
+                .and_then(|namespace| namespace.workloads)
+                .and_then(|mut workloads| workloads.remove(unqualified_name)),
+            None => desired_state
+                .workloads
+                .and_then(|mut workloads| workloads.remove(workload_name)),
+        }
+        .ok_or_else(workload_not_existing)?;
+        let configs = desired_state.configs.unwrap_or_default();
+
+        let stored_workload = templated_fields_of_workload(workload_spec);
+        if !stored_workload
+            .configs
+            .values()
+            .any(|referenced_config| referenced_config == config_name)
+        {
+            return Err(CliError::ExecutionError(format!(
+                "Workload '{}' does not reference config '{}'",
+                workload_name, config_name
+            )));
+        }
+
+        // The same renderer as in the Ankaios server is used so that the output matches the started workload,
+        // except for the environment variables, which are only known by the server and shown as placeholders.
+        let mut config_renderer = ConfigRenderer::default();
+        config_renderer.set_env_placeholders();
+        let rendered_workload = config_renderer
+            .render_workload(workload_name, &stored_workload, &configs)
+            .map_err(|err| CliError::ExecutionError(err.to_string()))?;
+
+        output_debug!(
+            "Rendered workload '{}': {:?}",
+            workload_name,
+            rendered_workload
+        );
+
+        render_single_item(
+            &RenderedWorkloadFields {
+                agent: rendered_workload.instance_name.agent_name().to_owned(),
+                runtime_config: rendered_workload.runtime_config,
+                registry_auth: rendered_workload.registry_auth,
+                env: rendered_workload.env,
+            },
+            output_format,
+        )
+    }
+}
+
+fn templated_fields_of_workload(workload_spec: FilteredWorkloadSpec) -> StoredWorkloadSpec {
+    StoredWorkloadSpec {
+        agent: workload_spec.agent.unwrap_or_default(),
+        runtime_config: workload_spec.runtime_config.unwrap_or_default(),
+        registry_auth: workload_spec.registry_auth,
+        env: workload_spec.env.unwrap_or_default(),
+        configs: workload_spec.configs.unwrap_or_default(),
+        ..Default::default()
+    }
+}
+
+// A single item has no table representation and is output as yaml unless json is requested.
+fn render_single_item(
+    item: &impl Serialize,
+    output_format: &ListOutputFormat,
+) -> Result<String, CliError> {
+    match output_format {
+        ListOutputFormat::Json => Ok(serde_json::to_string_pretty(item)?),
+        ListOutputFormat::CustomColumns(_) => Err(CliError::ExecutionError(
+            "Custom columns are not supported for a single config".to_owned(),
+        )),
+        _ => Ok(serde_yaml::to_string(item)?.trim_end().to_owned()),
     }
 }
 
@@ -66,18 +214,47 @@ fn transform_into_table_rows(
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use crate::cli_commands::{
+        output::ListOutputFormat,
         server_connection::{MockServerConnection, ServerConnectionError},
         CliCommands, DESIRED_STATE_CONFIGS,
     };
+    use crate::cli_error::CliError;
 
     use api::ank_base;
+    use common::objects::{
+        generate_test_configs, generate_test_stored_workload_spec_with_config, CompleteState, State,
+    };
     use common::test_utils;
     use mockall::predicate::eq;
 
-    const RESPONSE_TIMEOUT_MS: u64 = 3000;
     const CONFIG_1: &str = "config_1";
     const CONFIG_2: &str = "config_2";
+    const WORKLOAD_NAME_1: &str = "workload_1";
+    const RUNTIME: &str = "podman";
+
+    fn generate_test_complete_state_with_templated_workload(
+        runtime_config: &str,
+    ) -> ank_base::CompleteState {
+        CompleteState {
+            desired_state: State {
+                workloads: HashMap::from([(
+                    WORKLOAD_NAME_1.to_owned(),
+                    generate_test_stored_workload_spec_with_config(
+                        "{{ref1.agent_name}}",
+                        RUNTIME,
+                        runtime_config,
+                    ),
+                )]),
+                configs: generate_test_configs(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .into()
+    }
 
     // [utest->swdd~cli-provides-list-of-configs~1]
     // [utest->swdd~cli-shall-present-configs-as-table~1]
@@ -101,12 +278,11 @@ mod tests {
             });
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
-        let table_output_result = cmd.get_configs().await;
+        let table_output_result = cmd.get_configs(&ListOutputFormat::Table).await;
 
         let expected_table_output = ["CONFIG  ", "config_1", "config_2"].join("\n");
 
@@ -123,12 +299,11 @@ mod tests {
             .return_once(|_| Ok(ank_base::CompleteState::default().into()));
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
-        let table_output_result = cmd.get_configs().await;
+        let table_output_result = cmd.get_configs(&ListOutputFormat::Table).await;
 
         let expected_table_output = "CONFIG".to_string();
 
@@ -149,12 +324,193 @@ mod tests {
             });
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
-        let table_output_result = cmd.get_configs().await;
+        let table_output_result = cmd.get_configs(&ListOutputFormat::Table).await;
         assert!(table_output_result.is_err());
     }
+
+    // [utest->swdd~cli-provides-content-of-config~1]
+    #[tokio::test]
+    async fn test_get_config_outputs_content_of_config() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec![format!("{}.{}", DESIRED_STATE_CONFIGS, CONFIG_1)]))
+            .return_once(|_| {
+                Ok(ank_base::CompleteState::from(CompleteState {
+                    desired_state: State {
+                        configs: generate_test_configs(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .into())
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let json_output_result = cmd.get_config(CONFIG_1, &ListOutputFormat::Json).await;
+
+        let expected_json_output = r#"{
+  "agent_name": "agent_A",
+  "values": {
+    "value_1": "value123",
+    "value_2": [
+      "list_value_1",
+      "list_value_2"
+    ]
+  }
+}"#;
+
+        assert_eq!(Ok(expected_json_output.to_owned()), json_output_result);
+    }
+
+    // [utest->swdd~cli-provides-content-of-config~1]
+    #[tokio::test]
+    async fn test_get_config_fails_config_does_not_exist() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec![format!("{}.{}", DESIRED_STATE_CONFIGS, CONFIG_2)]))
+            .return_once(|_| Ok(ank_base::CompleteState::default().into()));
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd.get_config(CONFIG_2, &ListOutputFormat::Yaml).await;
+
+        assert_eq!(
+            Err(CliError::ExecutionError(
+                "Config 'config_2' does not exist".to_owned()
+            )),
+            result
+        );
+    }
+
+    // [utest->swdd~cli-provides-rendered-config-of-workload~2]
+    #[tokio::test]
+    async fn test_get_rendered_config_outputs_rendered_fields_of_workload() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec![
+                format!("desiredState.workloads.{}", WORKLOAD_NAME_1),
+                DESIRED_STATE_CONFIGS.to_string(),
+            ]))
+            .return_once(|_| {
+                Ok(generate_test_complete_state_with_templated_workload("image: {{ref2}}").into())
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let json_output_result = cmd
+            .get_rendered_config(CONFIG_2, WORKLOAD_NAME_1, &ListOutputFormat::Json)
+            .await;
+
+        let expected_json_output = r#"{
+  "agent": "agent_A",
+  "runtimeConfig": "image: value_3"
+}"#;
+
+        assert_eq!(Ok(expected_json_output.to_owned()), json_output_result);
+    }
+
+    // [utest->swdd~cli-provides-rendered-config-of-workload~2]
+    #[tokio::test]
+    async fn test_get_rendered_config_outputs_env_variables_as_placeholders() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .return_once(|_| {
+                Ok(generate_test_complete_state_with_templated_workload(
+                    "image: {{ref2}}\nhost: {{env \"APP_HOST\"}}",
+                )
+                .into())
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let yaml_output_result = cmd
+            .get_rendered_config(CONFIG_2, WORKLOAD_NAME_1, &ListOutputFormat::Yaml)
+            .await;
+
+        let expected_yaml_output = concat!(
+            "agent: agent_A\n",
+            "runtimeConfig: |-\n",
+            "  image: value_3\n",
+            "  host: ${server-env:APP_HOST}"
+        );
+
+        assert_eq!(Ok(expected_yaml_output.to_owned()), yaml_output_result);
+    }
+
+    // [utest->swdd~cli-provides-rendered-config-of-workload~2]
+    #[tokio::test]
+    async fn test_get_rendered_config_fails_on_render_error() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .return_once(|_| {
+                Ok(generate_test_complete_state_with_templated_workload(
+                    "image: {{ref2.not_existing}}",
+                )
+                .into())
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd
+            .get_rendered_config(CONFIG_2, WORKLOAD_NAME_1, &ListOutputFormat::Yaml)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(CliError::ExecutionError(message))
+                if message.starts_with("Failed to render field 'runtimeConfig'")
+        ));
+    }
+
+    // [utest->swdd~cli-provides-rendered-config-of-workload~2]
+    #[tokio::test]
+    async fn test_get_rendered_config_fails_workload_does_not_reference_config() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .return_once(|_| {
+                Ok(generate_test_complete_state_with_templated_workload("image: {{ref2}}").into())
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd
+            .get_rendered_config("config_3", WORKLOAD_NAME_1, &ListOutputFormat::Yaml)
+            .await;
+
+        assert_eq!(
+            Err(CliError::ExecutionError(
+                "Workload 'workload_1' does not reference config 'config_3'".to_owned()
+            )),
+            result
+        );
+    }
 }
@@ -0,0 +1,219 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Write;
+
+use api::ank_base;
+use common::objects::ExecutionState;
+use serde::Serialize;
+
+use crate::{cli::GraphOutputFormat, cli_error::CliError, output_debug};
+
+use super::CliCommands;
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyGraphNode {
+    pub workload_name: String,
+    pub agent: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_state: Option<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyGraphEdge {
+    pub workload_name: String,
+    pub dependency: String,
+    pub condition: String,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct DependencyGraph {
+    pub nodes: Vec<DependencyGraphNode>,
+    pub edges: Vec<DependencyGraphEdge>,
+}
+
+impl From<ank_base::DependencyGraph> for DependencyGraph {
+    fn from(value: ank_base::DependencyGraph) -> Self {
+        DependencyGraph {
+            nodes: value
+                .nodes
+                .into_iter()
+                .map(|node| DependencyGraphNode {
+                    workload_name: node.workload_name,
+                    agent: node.agent,
+                    execution_state: node
+                        .execution_state
+                        .map(|state| ExecutionState::from(state).to_string()),
+                })
+                .collect(),
+            edges: value
+                .edges
+                .into_iter()
+                .map(|edge| DependencyGraphEdge {
+                    workload_name: edge.workload_name,
+                    dependency: edge.dependency,
+                    condition: ank_base::AddCondition::from_i32(edge.condition)
+                        .map(|condition| condition.as_str_name().to_owned())
+                        .unwrap_or_else(|| format!("UNKNOWN({})", edge.condition)),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn escape_dot_string(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// [impl->swdd~cli-provides-dependency-graph~1]
+fn to_dot(dependency_graph: &DependencyGraph) -> String {
+    let mut dot = String::from("digraph dependencies {\n");
+    for node in &dependency_graph.nodes {
+        dot.push_str(&format!(
+            "    \"{}\" [label=\"{}\\n{}\\n{}\"];\n",
+            escape_dot_string(&node.workload_name),
+            escape_dot_string(&node.workload_name),
+            escape_dot_string(&node.agent),
+            escape_dot_string(node.execution_state.as_deref().unwrap_or("Unknown"))
+        ));
+    }
+    for edge in &dependency_graph.edges {
+        dot.push_str(&format!(
+            "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+            escape_dot_string(&edge.workload_name),
+            escape_dot_string(&edge.dependency),
+            escape_dot_string(&edge.condition)
+        ));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+impl CliCommands {
+    // [impl->swdd~cli-provides-dependency-graph~1]
+    pub async fn get_dependency_graph(
+        &mut self,
+        output_format: GraphOutputFormat,
+        mut writer: impl Write,
+    ) -> Result<(), CliError> {
+        let dependency_graph: DependencyGraph =
+            self.server_connection.get_dependency_graph().await?.into();
+        output_debug!("Received dependency graph: {:?}", dependency_graph);
+
+        match output_format {
+            GraphOutputFormat::Dot => write!(writer, "{}", to_dot(&dependency_graph))?,
+            GraphOutputFormat::Json => {
+                serde_json::to_writer_pretty(&mut writer, &dependency_graph)?;
+                writeln!(writer)?;
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use api::ank_base;
+    use common::objects::ExecutionState;
+
+    use crate::{
+        cli::GraphOutputFormat,
+        cli_commands::{server_connection::MockServerConnection, CliCommands},
+    };
+
+    fn generate_test_dependency_graph() -> ank_base::DependencyGraph {
+        ank_base::DependencyGraph {
+            nodes: vec![
+                ank_base::DependencyGraphNode {
+                    workload_name: "backend".to_owned(),
+                    agent: "agent_A".to_owned(),
+                    execution_state: Some(ExecutionState::running().into()),
+                },
+                ank_base::DependencyGraphNode {
+                    workload_name: "frontend".to_owned(),
+                    agent: "agent_B".to_owned(),
+                    execution_state: None,
+                },
+            ],
+            edges: vec![ank_base::DependencyGraphEdge {
+                workload_name: "frontend".to_owned(),
+                dependency: "backend".to_owned(),
+                condition: ank_base::AddCondition::AddCondRunning as i32,
+            }],
+        }
+    }
+
+    async fn get_dependency_graph_output(output_format: GraphOutputFormat) -> String {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_dependency_graph()
+            .return_once(|| Ok(generate_test_dependency_graph()));
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let mut cmd_output = Vec::new();
+        cmd.get_dependency_graph(output_format, &mut cmd_output)
+            .await
+            .unwrap();
+        String::from_utf8(cmd_output).unwrap()
+    }
+
+    // [utest->swdd~cli-provides-dependency-graph~1]
+    #[tokio::test]
+    async fn utest_get_dependency_graph_dot() {
+        assert_eq!(
+            get_dependency_graph_output(GraphOutputFormat::Dot).await,
+            concat!(
+                "digraph dependencies {\n",
+                "    \"backend\" [label=\"backend\\nagent_A\\nRunning(Ok)\"];\n",
+                "    \"frontend\" [label=\"frontend\\nagent_B\\nUnknown\"];\n",
+                "    \"frontend\" -> \"backend\" [label=\"ADD_COND_RUNNING\"];\n",
+                "}\n"
+            )
+        );
+    }
+
+    // [utest->swdd~cli-provides-dependency-graph~1]
+    #[tokio::test]
+    async fn utest_get_dependency_graph_json() {
+        let output: serde_json::Value =
+            serde_json::from_str(&get_dependency_graph_output(GraphOutputFormat::Json).await)
+                .unwrap();
+
+        assert_eq!(
+            output,
+            serde_json::json!({
+                "nodes": [
+                    {"workloadName": "backend", "agent": "agent_A", "executionState": "Running(Ok)"},
+                    {"workloadName": "frontend", "agent": "agent_B"}
+                ],
+                "edges": [
+                    {"workloadName": "frontend", "dependency": "backend", "condition": "ADD_COND_RUNNING"}
+                ]
+            })
+        );
+    }
+}
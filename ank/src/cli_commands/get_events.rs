@@ -0,0 +1,330 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Write;
+use std::time::{Duration, UNIX_EPOCH};
+
+use api::ank_base;
+use common::objects::ExecutionState;
+use serde::Serialize;
+
+use crate::{
+    cli::EventsOutputFormat,
+    cli_commands::{cli_table::CliTable, event_table_row::EventTableRow},
+    cli_error::CliError,
+    output_debug,
+};
+
+use super::CliCommands;
+
+#[cfg(not(test))]
+const EVENTS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+#[cfg(test)]
+const EVENTS_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct Event {
+    sequence_number: u64,
+    timestamp: String,
+    event_type: String,
+    agent: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    workload_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    execution_state: Option<String>,
+}
+
+impl From<ank_base::Event> for Event {
+    fn from(value: ank_base::Event) -> Self {
+        Event {
+            sequence_number: value.sequence_number,
+            timestamp: humantime::format_rfc3339_millis(
+                UNIX_EPOCH + Duration::from_millis(value.timestamp),
+            )
+            .to_string(),
+            event_type: ank_base::EventType::from_i32(value.event_type)
+                .map(|event_type| format!("{event_type:?}"))
+                .unwrap_or_else(|| format!("Unknown({})", value.event_type)),
+            agent: value.agent,
+            workload_name: value.workload_name,
+            execution_state: value
+                .execution_state
+                .map(|state| ExecutionState::from(state).to_string()),
+        }
+    }
+}
+
+impl From<Event> for EventTableRow {
+    fn from(value: Event) -> Self {
+        EventTableRow {
+            sequence_number: value.sequence_number,
+            time: value.timestamp,
+            event_type: value.event_type,
+            agent: value.agent,
+            workload_name: value.workload_name,
+            execution_state: value.execution_state.unwrap_or_default(),
+        }
+    }
+}
+
+// [impl->swdd~cli-provides-events~1]
+fn write_events(
+    events: Vec<Event>,
+    output_format: EventsOutputFormat,
+    with_header: bool,
+    mut writer: impl Write,
+) -> Result<(), CliError> {
+    match output_format {
+        EventsOutputFormat::Table => {
+            if events.is_empty() && !with_header {
+                return Ok(());
+            }
+            let rows: Vec<EventTableRow> = events.into_iter().map(Into::into).collect();
+            let table = CliTable::new(&rows).create_default_table();
+            let lines_to_skip = if with_header { 0 } else { 1 };
+            for line in table.lines().skip(lines_to_skip) {
+                writeln!(writer, "{line}")?;
+            }
+        }
+        EventsOutputFormat::Json => {
+            for event in events {
+                serde_json::to_writer(&mut writer, &event)?;
+                writeln!(writer)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+impl CliCommands {
+    // [impl->swdd~cli-provides-events~1]
+    // [impl->swdd~cli-follows-events~1]
+    pub async fn get_events(
+        &mut self,
+        follow: bool,
+        output_format: EventsOutputFormat,
+        mut writer: impl Write,
+    ) -> Result<(), CliError> {
+        let mut last_sequence_number = 0;
+        let mut with_header = true;
+        let mut poll_interval = tokio::time::interval(EVENTS_POLL_INTERVAL);
+        loop {
+            poll_interval.tick().await;
+            let events = self
+                .server_connection
+                .get_events(last_sequence_number)
+                .await?
+                .events;
+            output_debug!("Received events: {:?}", events);
+
+            if let Some(last_event) = events.last() {
+                last_sequence_number = last_event.sequence_number;
+            }
+            write_events(
+                events.into_iter().map(Into::into).collect(),
+                output_format,
+                with_header,
+                &mut writer,
+            )?;
+            writer.flush()?;
+            with_header = false;
+
+            if !follow {
+                return Ok(());
+            }
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use api::ank_base;
+    use common::objects::ExecutionState;
+    use mockall::predicate::eq;
+
+    use crate::{
+        cli::EventsOutputFormat,
+        cli_commands::{
+            server_connection::{MockServerConnection, ServerConnectionError},
+            CliCommands,
+        },
+    };
+
+    fn generate_test_events() -> ank_base::Events {
+        ank_base::Events {
+            events: vec![
+                ank_base::Event {
+                    sequence_number: 1,
+                    timestamp: 1_700_000_000_000,
+                    event_type: ank_base::EventType::AgentConnected as i32,
+                    agent: "agent_A".to_owned(),
+                    workload_name: String::new(),
+                    execution_state: None,
+                },
+                ank_base::Event {
+                    sequence_number: 2,
+                    timestamp: 1_700_000_000_500,
+                    event_type: ank_base::EventType::ExecutionStateChanged as i32,
+                    agent: "agent_A".to_owned(),
+                    workload_name: "nginx".to_owned(),
+                    execution_state: Some(ExecutionState::running().into()),
+                },
+            ],
+        }
+    }
+
+    // [utest->swdd~cli-provides-events~1]
+    #[tokio::test]
+    async fn utest_get_events_table() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_events()
+            .with(eq(0))
+            .return_once(|_| Ok(generate_test_events()));
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let mut cmd_output = Vec::new();
+        cmd.get_events(false, EventsOutputFormat::Table, &mut cmd_output)
+            .await
+            .unwrap();
+
+        // the table is truncated to the terminal width of 80 characters used in the tests
+        let expected_table_output = [
+            "SEQ   TIME                       EVENT                   AGENT     WORKLOAD NAME",
+            "1     2023-11-14T22:13:20.000Z   AgentConnected          agent_A                ",
+            "2     2023-11-14T22:13:20.500Z   ExecutionStateChanged   agent_A   nginx        ",
+        ]
+        .join("\n");
+        assert_eq!(
+            String::from_utf8(cmd_output)
+                .unwrap()
+                .trim_end_matches('\n'),
+            expected_table_output
+        );
+    }
+
+    // [utest->swdd~cli-provides-events~1]
+    #[tokio::test]
+    async fn utest_get_events_json() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_events()
+            .with(eq(0))
+            .return_once(|_| Ok(generate_test_events()));
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let mut cmd_output = Vec::new();
+        cmd.get_events(false, EventsOutputFormat::Json, &mut cmd_output)
+            .await
+            .unwrap();
+
+        let output: Vec<serde_json::Value> = String::from_utf8(cmd_output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            output,
+            vec![
+                serde_json::json!({
+                    "sequenceNumber": 1,
+                    "timestamp": "2023-11-14T22:13:20.000Z",
+                    "eventType": "AgentConnected",
+                    "agent": "agent_A"
+                }),
+                serde_json::json!({
+                    "sequenceNumber": 2,
+                    "timestamp": "2023-11-14T22:13:20.500Z",
+                    "eventType": "ExecutionStateChanged",
+                    "agent": "agent_A",
+                    "workloadName": "nginx",
+                    "executionState": "Running(Ok)"
+                }),
+            ]
+        );
+    }
+
+    // [utest->swdd~cli-follows-events~1]
+    #[tokio::test]
+    async fn utest_get_events_follow_requests_events_after_last_received() {
+        let mut events = generate_test_events().events;
+        let second_event = events.pop().unwrap();
+
+        let mut mock_server_connection = MockServerConnection::default();
+        let mut seq = mockall::Sequence::new();
+        mock_server_connection
+            .expect_get_events()
+            .with(eq(0))
+            .once()
+            .in_sequence(&mut seq)
+            .return_once(|_| Ok(ank_base::Events { events }));
+        mock_server_connection
+            .expect_get_events()
+            .with(eq(1))
+            .once()
+            .in_sequence(&mut seq)
+            .return_once(|_| Ok(ank_base::Events::default()));
+        mock_server_connection
+            .expect_get_events()
+            .with(eq(1))
+            .once()
+            .in_sequence(&mut seq)
+            .return_once(|_| {
+                Ok(ank_base::Events {
+                    events: vec![second_event],
+                })
+            });
+        mock_server_connection
+            .expect_get_events()
+            .with(eq(2))
+            .once()
+            .in_sequence(&mut seq)
+            .return_once(|_| {
+                Err(ServerConnectionError::ExecutionError(
+                    "connection lost".to_owned(),
+                ))
+            });
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let mut cmd_output = Vec::new();
+        let result = cmd
+            .get_events(true, EventsOutputFormat::Table, &mut cmd_output)
+            .await;
+        assert!(result.is_err());
+
+        let output = String::from_utf8(cmd_output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("SEQ"));
+        assert!(lines[1].starts_with("1 "));
+        assert!(lines[2].starts_with("2 "));
+    }
+}
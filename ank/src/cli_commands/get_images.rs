@@ -0,0 +1,205 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+use std::collections::HashMap;
+
+use super::{
+    output::{render_list, ListOutputFormat},
+    CliCommands,
+};
+use crate::{
+    cli_commands::{cli_table::CliTable, image_table_row::ImageTableRow},
+    cli_error::CliError,
+    output_debug,
+};
+
+use common::objects::{ImageState, ImageStatesMap};
+
+const DESIRED_STATE_IMAGES: &str = "desiredState.images";
+const IMAGE_STATES: &str = "imageStates";
+
+impl CliCommands {
+    // [impl->swdd~cli-provides-list-of-images~1]
+    pub async fn get_images(
+        &mut self,
+        agent_name: Option<String>,
+        output_format: &ListOutputFormat,
+    ) -> Result<String, CliError> {
+        let filtered_complete_state = self
+            .server_connection
+            .get_complete_state(&[DESIRED_STATE_IMAGES.to_string(), IMAGE_STATES.to_string()])
+            .await?;
+
+        let pinned_images = filtered_complete_state
+            .desired_state
+            .and_then(|state| state.images)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(agent, _)| agent_name.as_ref().is_none_or(|name| name == agent));
+        let image_states_map = filtered_complete_state.image_states.unwrap_or_default();
+
+        let image_table_rows = transform_into_table_rows(pinned_images, &image_states_map);
+
+        output_debug!("Got pinned images: {:?}", image_table_rows);
+
+        render_list(&image_table_rows, output_format, |rows, _wide| {
+            CliTable::new(rows).create_default_table()
+        })
+    }
+}
+
+// The pinned images of the desired state are listed with the pull states reported by their agents.
+// Images not reported yet, e.g., because the agent is not connected, are shown as pending.
+fn transform_into_table_rows(
+    pinned_images: impl Iterator<Item = (String, Vec<String>)>,
+    image_states_map: &ImageStatesMap,
+) -> Vec<ImageTableRow> {
+    let empty_image_states = HashMap::new();
+    let mut image_table_rows: Vec<ImageTableRow> = pinned_images
+        .flat_map(|(agent, images)| {
+            let image_states = image_states_map
+                .get_image_states_for_agent(&agent)
+                .unwrap_or(&empty_image_states);
+            images
+                .into_iter()
+                .map(|image| {
+                    let image_state = image_states.get(&image).cloned().unwrap_or_default();
+                    create_image_table_row(agent.clone(), image, image_state)
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    // sort to ensure consistent output
+    image_table_rows.sort_by(|a, b| (&a.agent, &a.image).cmp(&(&b.agent, &b.image)));
+    image_table_rows
+}
+
+fn create_image_table_row(agent: String, image: String, image_state: ImageState) -> ImageTableRow {
+    ImageTableRow {
+        agent,
+        image,
+        state: image_state.state.to_string(),
+        additional_info: image_state.additional_info,
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::cli_commands::{
+        output::ListOutputFormat, server_connection::MockServerConnection, CliCommands,
+    };
+    use api::ank_base;
+    use common::objects::{CompleteState, ImageState, ImageStatesMap, State};
+    use mockall::predicate::eq;
+
+    use super::{DESIRED_STATE_IMAGES, IMAGE_STATES};
+
+    const AGENT_A_NAME: &str = "agent_A";
+    const AGENT_B_NAME: &str = "agent_B";
+    const IMAGE_1: &str = "ghcr.io/eclipse-ankaios/image_1:1.0";
+    const IMAGE_2: &str = "ghcr.io/eclipse-ankaios/image_2:1.0";
+
+    fn generate_test_complete_state_with_pinned_images() -> ank_base::CompleteState {
+        let mut image_states = ImageStatesMap::new();
+        image_states.update_for_agent(
+            AGENT_A_NAME,
+            HashMap::from([
+                (IMAGE_1.to_string(), ImageState::pulled()),
+                (IMAGE_2.to_string(), ImageState::failed("manifest unknown")),
+            ]),
+        );
+
+        CompleteState {
+            desired_state: State {
+                images: HashMap::from([
+                    (
+                        AGENT_A_NAME.to_string(),
+                        vec![IMAGE_2.to_string(), IMAGE_1.to_string()],
+                    ),
+                    (AGENT_B_NAME.to_string(), vec![IMAGE_1.to_string()]),
+                ]),
+                ..Default::default()
+            },
+            image_states,
+            ..Default::default()
+        }
+        .into()
+    }
+
+    // [utest->swdd~cli-provides-list-of-images~1]
+    #[tokio::test]
+    async fn utest_get_images() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec![
+                DESIRED_STATE_IMAGES.to_string(),
+                IMAGE_STATES.to_string(),
+            ]))
+            .return_once(|_| Ok(generate_test_complete_state_with_pinned_images().into()));
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let table_output_result = cmd.get_images(None, &ListOutputFormat::Table).await;
+
+        let expected_table_output = [
+            "AGENT     IMAGE                                 STATE     ADDITIONAL INFO ",
+            "agent_A   ghcr.io/eclipse-ankaios/image_1:1.0   Pulled                    ",
+            "agent_A   ghcr.io/eclipse-ankaios/image_2:1.0   Failed    manifest unknown",
+            "agent_B   ghcr.io/eclipse-ankaios/image_1:1.0   Pending                   ",
+        ]
+        .join("\n");
+
+        assert_eq!(Ok(expected_table_output), table_output_result);
+    }
+
+    // [utest->swdd~cli-provides-list-of-images~1]
+    #[tokio::test]
+    async fn utest_get_images_of_agent() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .return_once(|_| Ok(generate_test_complete_state_with_pinned_images().into()));
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let table_output_result = cmd
+            .get_images(Some(AGENT_B_NAME.to_string()), &ListOutputFormat::Table)
+            .await;
+
+        let expected_table_output = [
+            "AGENT     IMAGE                                 STATE     ADDITIONAL INFO",
+            "agent_B   ghcr.io/eclipse-ankaios/image_1:1.0   Pending                  ",
+        ]
+        .join("\n");
+
+        assert_eq!(Ok(expected_table_output), table_output_result);
+    }
+}
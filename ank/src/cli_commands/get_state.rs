@@ -12,20 +12,100 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{cli::OutputFormat, cli_error::CliError, output_debug};
+use std::{io::Write, time::Duration};
+
+use common::objects::convert_state_to_api_version;
+use serde::Serialize;
+
+use crate::{
+    cli::OutputFormat, cli_error::CliError, filtered_complete_state::FilteredCompleteState,
+    output_debug, output_warn,
+};
 
 use super::CliCommands;
 
+#[cfg(not(test))]
+const STATE_WATCH_INTERVAL: Duration = Duration::from_secs(1);
+#[cfg(test)]
+const STATE_WATCH_INTERVAL: Duration = Duration::from_millis(1);
+const DESIRED_STATE: &str = "desiredState";
+
+// The state is serialized directly into the writer to avoid keeping
+// additional copies of a potentially large state in memory.
+// [impl->swdd~cli-streams-state-output~1]
+fn write_state(
+    filtered_complete_state: &FilteredCompleteState,
+    output_format: OutputFormat,
+    target_api_version: Option<&str>,
+    mut writer: impl Write,
+) -> Result<(), CliError> {
+    match target_api_version {
+        Some(api_version) => {
+            let converted_state = convert_to_api_version(filtered_complete_state, api_version)?;
+            serialize_state(&converted_state, output_format, &mut writer)?;
+        }
+        None => serialize_state(filtered_complete_state, output_format, &mut writer)?,
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn serialize_state(
+    state: &impl Serialize,
+    output_format: OutputFormat,
+    mut writer: impl Write,
+) -> Result<(), CliError> {
+    match output_format {
+        // [impl -> swdd~cli-shall-support-desired-state-yaml~1]
+        OutputFormat::Yaml => serde_yaml::to_writer(&mut writer, state)?,
+        // [impl -> swdd~cli-shall-support-desired-state-json~1]
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, state)?;
+            writeln!(writer)?;
+        }
+        // [impl->swdd~cli-watches-state~1]
+        OutputFormat::JsonStream => {
+            serde_json::to_writer(&mut writer, state)?;
+            writeln!(writer)?;
+        }
+    }
+    Ok(())
+}
+
+// [impl->swdd~cli-outputs-state-in-target-api-version~1]
+fn convert_to_api_version(
+    filtered_complete_state: &FilteredCompleteState,
+    target_api_version: &str,
+) -> Result<serde_yaml::Value, CliError> {
+    let mut state = serde_yaml::to_value(filtered_complete_state)?;
+    if let Some(desired_state) = state.get_mut(DESIRED_STATE) {
+        let omitted_fields = convert_state_to_api_version(desired_state, target_api_version)
+            .map_err(CliError::ExecutionError)?;
+        for field in omitted_fields {
+            output_warn!(
+                "The field '{}.{}' is not supported by the API version '{}' and is omitted",
+                DESIRED_STATE,
+                field,
+                target_api_version
+            );
+        }
+    }
+    Ok(state)
+}
+
 impl CliCommands {
     pub async fn get_state(
         &mut self,
         object_field_mask: Vec<String>,
         output_format: OutputFormat,
-    ) -> Result<String, CliError> {
+        target_api_version: Option<&str>,
+        writer: impl Write,
+    ) -> Result<(), CliError> {
         output_debug!(
-            "Got: object_field_mask={:?} output_format={:?}",
+            "Got: object_field_mask={:?} output_format={:?} target_api_version={:?}",
             object_field_mask,
-            output_format
+            output_format,
+            target_api_version
         );
 
         // [impl->swdd~cli-returns-compact-state-object-when-object-field-mask-provided~1]
@@ -36,12 +116,54 @@ impl CliCommands {
 
         output_debug!("Raw complete state: {:?}", filtered_complete_state);
 
-        let serialized_state: serde_yaml::Value = serde_yaml::to_value(filtered_complete_state)?;
-        match output_format {
-            // [impl -> swdd~cli-shall-support-desired-state-yaml~1]
-            OutputFormat::Yaml => Ok(serde_yaml::to_string(&serialized_state)?),
-            // [impl -> swdd~cli-shall-support-desired-state-json~1]
-            OutputFormat::Json => Ok(serde_json::to_string_pretty(&serialized_state)?),
+        write_state(
+            &filtered_complete_state,
+            output_format,
+            target_api_version,
+            writer,
+        )
+    }
+
+    // [impl->swdd~cli-watches-state~1]
+    pub async fn watch_state(
+        &mut self,
+        object_field_mask: Vec<String>,
+        output_format: OutputFormat,
+        target_api_version: Option<&str>,
+        mut writer: impl Write,
+    ) -> Result<(), CliError> {
+        output_debug!(
+            "Watch: object_field_mask={:?} output_format={:?} target_api_version={:?}",
+            object_field_mask,
+            output_format,
+            target_api_version
+        );
+
+        let mut last_state: Option<FilteredCompleteState> = None;
+        let mut watch_interval = tokio::time::interval(STATE_WATCH_INTERVAL);
+        loop {
+            watch_interval.tick().await;
+            let filtered_complete_state = self
+                .server_connection
+                .get_complete_state(&object_field_mask)
+                .await?;
+
+            if last_state.as_ref() == Some(&filtered_complete_state) {
+                continue;
+            }
+            output_debug!("Changed complete state: {:?}", filtered_complete_state);
+
+            // Separate the YAML documents of the consecutive states
+            if output_format == OutputFormat::Yaml && last_state.is_some() {
+                writeln!(writer, "---")?;
+            }
+            write_state(
+                &filtered_complete_state,
+                output_format,
+                target_api_version,
+                &mut writer,
+            )?;
+            last_state = Some(filtered_complete_state);
         }
     }
 }
@@ -56,20 +178,25 @@ impl CliCommands {
 #[cfg(test)]
 mod tests {
     use api::ank_base;
-    use common::test_utils::{
-        self, generate_test_proto_complete_state, generate_test_proto_workload_with_param,
+    use common::{
+        objects::{API_VERSION_V0_1, CURRENT_API_VERSION},
+        test_utils::{
+            self, generate_test_proto_complete_state, generate_test_proto_workload_with_param,
+        },
     };
     use mockall::predicate::eq;
 
     use crate::{
-        cli_commands::{server_connection::MockServerConnection, CliCommands},
+        cli_commands::{
+            server_connection::{MockServerConnection, ServerConnectionError},
+            CliCommands,
+        },
         filtered_complete_state,
     };
 
-    const RESPONSE_TIMEOUT_MS: u64 = 3000;
-
     // [utest -> swdd~cli-returns-desired-state-from-server~1]
     // [utest -> swdd~cli-shall-support-desired-state-yaml~1]
+    // [utest->swdd~cli-streams-state-output~1]
     // [utest->swdd~cli-blocks-until-ankaios-server-responds-get-desired-state~1]
     // [utest->swdd~cli-provides-get-desired-state~1]
     #[tokio::test]
@@ -98,20 +225,26 @@ mod tests {
             .with(eq(vec![]))
             .return_once(|_| Ok(test_data_clone));
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
-        let cmd_text = cmd
-            .get_state(vec![], crate::cli::OutputFormat::Yaml)
-            .await
-            .unwrap();
+        let mut cmd_output = Vec::new();
+        cmd.get_state(
+            vec![],
+            crate::cli::OutputFormat::Yaml,
+            None,
+            &mut cmd_output,
+        )
+        .await
+        .unwrap();
+        let cmd_text = String::from_utf8(cmd_output).unwrap();
         let expected_text = serde_yaml::to_string(&test_data).unwrap();
         assert_eq!(cmd_text, expected_text);
     }
 
     // [utest -> swdd~cli-shall-support-desired-state-json~1]
+    // [utest->swdd~cli-streams-state-output~1]
     #[tokio::test]
     async fn utest_get_state_complete_desired_state_json() {
         let test_data = filtered_complete_state::FilteredCompleteState::from(
@@ -138,17 +271,22 @@ mod tests {
             .return_once(|_| Ok(cloned_test_data));
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: 0,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
-        let cmd_text = cmd
-            .get_state(vec![], crate::cli::OutputFormat::Json)
-            .await
-            .unwrap();
+        let mut cmd_output = Vec::new();
+        cmd.get_state(
+            vec![],
+            crate::cli::OutputFormat::Json,
+            None,
+            &mut cmd_output,
+        )
+        .await
+        .unwrap();
+        let cmd_text = String::from_utf8(cmd_output).unwrap();
 
-        let expected_text = serde_json::to_string_pretty(&test_data).unwrap();
+        let expected_text = serde_json::to_string_pretty(&test_data).unwrap() + "\n";
         assert_eq!(cmd_text, expected_text);
     }
 
@@ -181,18 +319,20 @@ mod tests {
             .return_once(|_| Ok(test_data_clone));
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
-        let cmd_text = cmd
-            .get_state(
-                vec!["desiredState.workloads.name3.runtime".to_owned()],
-                crate::cli::OutputFormat::Yaml,
-            )
-            .await
-            .unwrap();
+        let mut cmd_output = Vec::new();
+        cmd.get_state(
+            vec!["desiredState.workloads.name3.runtime".to_owned()],
+            crate::cli::OutputFormat::Yaml,
+            None,
+            &mut cmd_output,
+        )
+        .await
+        .unwrap();
+        let cmd_text = String::from_utf8(cmd_output).unwrap();
 
         let expected_single_field_result_text = serde_yaml::to_string(&test_data).unwrap();
 
@@ -232,25 +372,221 @@ mod tests {
             .return_once(|_| Ok(test_data_clone));
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
-        let cmd_text = cmd
-            .get_state(
-                vec![
-                    "desiredState.workloads.name1.runtime".to_owned(),
-                    "desiredState.workloads.name2.runtime".to_owned(),
-                ],
+        let mut cmd_output = Vec::new();
+        cmd.get_state(
+            vec![
+                "desiredState.workloads.name1.runtime".to_owned(),
+                "desiredState.workloads.name2.runtime".to_owned(),
+            ],
+            crate::cli::OutputFormat::Yaml,
+            None,
+            &mut cmd_output,
+        )
+        .await
+        .unwrap();
+        let cmd_text = String::from_utf8(cmd_output).unwrap();
+        let expected_text = serde_yaml::to_string(&test_data).unwrap();
+        assert_eq!(cmd_text, expected_text);
+    }
+
+    // [utest->swdd~cli-watches-state~1]
+    #[tokio::test]
+    async fn utest_get_state_json_stream() {
+        let test_data = filtered_complete_state::FilteredCompleteState::from(
+            generate_test_proto_complete_state(&[(
+                "name1",
+                generate_test_proto_workload_with_param("agent_A", "runtime"),
+            )]),
+        );
+
+        let mut mock_server_connection = MockServerConnection::default();
+        let cloned_test_data = test_data.clone();
+        mock_server_connection
+            .expect_get_complete_state()
+            .return_once(|_| Ok(cloned_test_data));
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let mut cmd_output = Vec::new();
+        cmd.get_state(
+            vec![],
+            crate::cli::OutputFormat::JsonStream,
+            None,
+            &mut cmd_output,
+        )
+        .await
+        .unwrap();
+        let cmd_text = String::from_utf8(cmd_output).unwrap();
+
+        let expected_text = serde_json::to_string(&test_data).unwrap() + "\n";
+        assert_eq!(cmd_text, expected_text);
+    }
+
+    // [utest->swdd~cli-watches-state~1]
+    #[tokio::test]
+    async fn utest_watch_state_outputs_changed_states() {
+        let first_state = filtered_complete_state::FilteredCompleteState::from(
+            generate_test_proto_complete_state(&[(
+                "name1",
+                generate_test_proto_workload_with_param("agent_A", "runtime"),
+            )]),
+        );
+        let second_state = filtered_complete_state::FilteredCompleteState::from(
+            generate_test_proto_complete_state(&[(
+                "name1",
+                generate_test_proto_workload_with_param("agent_B", "runtime"),
+            )]),
+        );
+
+        let mut mock_server_connection = MockServerConnection::default();
+        let mut seq = mockall::Sequence::new();
+        for state in [&first_state, &first_state, &second_state] {
+            let state = state.clone();
+            mock_server_connection
+                .expect_get_complete_state()
+                .with(eq(vec!["desiredState.workloads".to_owned()]))
+                .once()
+                .in_sequence(&mut seq)
+                .return_once(|_| Ok(state));
+        }
+        mock_server_connection
+            .expect_get_complete_state()
+            .once()
+            .in_sequence(&mut seq)
+            .return_once(|_| {
+                Err(ServerConnectionError::ExecutionError(
+                    "Connection to server interrupted".to_owned(),
+                ))
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let mut cmd_output = Vec::new();
+        let result = cmd
+            .watch_state(
+                vec!["desiredState.workloads".to_owned()],
+                crate::cli::OutputFormat::JsonStream,
+                None,
+                &mut cmd_output,
+            )
+            .await;
+        assert!(result.is_err());
+
+        let cmd_text = String::from_utf8(cmd_output).unwrap();
+        let expected_text = [
+            serde_json::to_string(&first_state).unwrap(),
+            serde_json::to_string(&second_state).unwrap(),
+        ]
+        .join("\n")
+            + "\n";
+        assert_eq!(cmd_text, expected_text);
+    }
+
+    // [utest->swdd~cli-watches-state~1]
+    #[tokio::test]
+    async fn utest_watch_state_separates_yaml_documents() {
+        let first_state = filtered_complete_state::FilteredCompleteState::from(
+            generate_test_proto_complete_state(&[(
+                "name1",
+                generate_test_proto_workload_with_param("agent_A", "runtime"),
+            )]),
+        );
+        let second_state = filtered_complete_state::FilteredCompleteState::default();
+
+        let mut mock_server_connection = MockServerConnection::default();
+        let mut seq = mockall::Sequence::new();
+        for state in [&first_state, &second_state] {
+            let state = state.clone();
+            mock_server_connection
+                .expect_get_complete_state()
+                .once()
+                .in_sequence(&mut seq)
+                .return_once(|_| Ok(state));
+        }
+        mock_server_connection
+            .expect_get_complete_state()
+            .once()
+            .in_sequence(&mut seq)
+            .return_once(|_| {
+                Err(ServerConnectionError::ExecutionError(
+                    "Connection to server interrupted".to_owned(),
+                ))
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let mut cmd_output = Vec::new();
+        let result = cmd
+            .watch_state(
+                vec![],
                 crate::cli::OutputFormat::Yaml,
+                None,
+                &mut cmd_output,
             )
-            .await
-            .unwrap();
-        let expected_text = serde_yaml::to_string(&test_data).unwrap();
+            .await;
+        assert!(result.is_err());
+
+        let cmd_text = String::from_utf8(cmd_output).unwrap();
+        let expected_text = serde_yaml::to_string(&first_state).unwrap()
+            + "---\n"
+            + &serde_yaml::to_string(&second_state).unwrap();
         assert_eq!(cmd_text, expected_text);
     }
 
+    // [utest->swdd~cli-outputs-state-in-target-api-version~1]
+    #[tokio::test]
+    async fn utest_get_state_in_target_api_version() {
+        let mut proto_complete_state = generate_test_proto_complete_state(&[(
+            "name1",
+            ank_base::Workload {
+                replicas: Some(2),
+                ..generate_test_proto_workload_with_param("agent_A", "runtime")
+            },
+        )]);
+        if let Some(desired_state) = proto_complete_state.desired_state.as_mut() {
+            desired_state.api_version = CURRENT_API_VERSION.to_string();
+        }
+        let test_data = filtered_complete_state::FilteredCompleteState::from(proto_complete_state);
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .return_once(|_| Ok(test_data));
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let mut cmd_output = Vec::new();
+        cmd.get_state(
+            vec![],
+            crate::cli::OutputFormat::Yaml,
+            Some(API_VERSION_V0_1),
+            &mut cmd_output,
+        )
+        .await
+        .unwrap();
+        let output: serde_yaml::Value = serde_yaml::from_slice(&cmd_output).unwrap();
+
+        assert_eq!(output["desiredState"]["apiVersion"], API_VERSION_V0_1);
+        let workload = &output["desiredState"]["workloads"]["name1"];
+        assert_eq!(workload["agent"], "agent_A");
+        assert!(workload.get("replicas").is_none());
+    }
+
     #[tokio::test]
     async fn utest_get_state_single_field_without_api_version() {
         let test_data = filtered_complete_state::FilteredCompleteState::from(
@@ -264,18 +600,20 @@ mod tests {
             .with(eq(vec!["workloadStates".to_owned()]))
             .return_once(|_| Ok(test_data_clone));
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
-        let cmd_text = cmd
-            .get_state(
-                vec!["workloadStates".to_owned()],
-                crate::cli::OutputFormat::Yaml,
-            )
-            .await
-            .unwrap();
+        let mut cmd_output = Vec::new();
+        cmd.get_state(
+            vec!["workloadStates".to_owned()],
+            crate::cli::OutputFormat::Yaml,
+            None,
+            &mut cmd_output,
+        )
+        .await
+        .unwrap();
+        let cmd_text = String::from_utf8(cmd_output).unwrap();
 
         let expected_text = serde_yaml::to_string(&test_data).unwrap();
         assert_eq!(cmd_text, expected_text);
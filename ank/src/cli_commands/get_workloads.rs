@@ -11,22 +11,34 @@
 // under the License.
 //
 // SPDX-License-Identifier: Apache-2.0
+use std::time::{Duration, UNIX_EPOCH};
+
+use common::objects::{split_qualified_workload_name, Tag};
+use tabled::Tabled;
+
 use crate::{cli_error::CliError, output_debug};
 
 use super::cli_table::CliTable;
-use super::workload_table_row::WorkloadTableRow;
+use super::output::{render_list, ListOutputFormat};
+use super::workload_history_table_row::WorkloadHistoryTableRow;
+use super::workload_table_row::{WideWorkloadTableRow, WorkloadTableRow};
 use super::CliCommands;
 
+const WORKLOAD_STATE_HISTORY: &str = "workloadStateHistory";
+
 impl CliCommands {
     // [impl->swdd~cli-provides-list-of-workloads~1]
     pub async fn get_workloads_table(
         &mut self,
         agent_name: Option<String>,
         state: Option<String>,
+        namespace: Option<String>,
         workload_name: Vec<String>,
+        selector: Vec<Tag>,
+        output_format: &ListOutputFormat,
     ) -> Result<String, CliError> {
         // [impl->swdd~cli-blocks-until-ankaios-server-responds-list-workloads~1]
-        let mut workload_infos = self.get_workloads().await?;
+        let mut workload_infos = self.get_selected_workloads(&selector).await?;
         output_debug!("The table before filtering:\n{:?}", workload_infos);
 
         // [impl->swdd~cli-shall-filter-list-of-workloads~1]
@@ -43,6 +55,15 @@ impl CliCommands {
                 .retain(|wi| wi.1.execution_state.to_lowercase() == state.to_lowercase());
         }
 
+        // [impl->swdd~cli-shall-filter-list-of-workloads~1]
+        // [impl->swdd~cli-filters-by-namespace~1]
+        if let Some(namespace) = namespace {
+            workload_infos.get_mut().retain(|wi| {
+                split_qualified_workload_name(&wi.1.name)
+                    .is_some_and(|(workload_namespace, _)| workload_namespace == namespace)
+            });
+        }
+
         // [impl->swdd~cli-shall-filter-list-of-workloads~1]
         if !workload_name.is_empty() {
             workload_infos
@@ -57,17 +78,89 @@ impl CliCommands {
         output_debug!("The table after filtering:\n{:?}", workload_infos);
 
         // [impl->swdd~cli-shall-present-list-of-workloads~1]
-        let table_rows: Vec<WorkloadTableRow> = workload_infos.into_iter().map(|x| x.1).collect();
+        let rows: Vec<WideWorkloadTableRow> = workload_infos
+            .into_iter()
+            .map(|(instance_name, workload_table_row)| WideWorkloadTableRow {
+                workload_table_row,
+                instance_id: instance_name.id().to_owned(),
+            })
+            .collect();
+
+        // [impl->swdd~cli-supports-list-output-formats~1]
+        render_list(&rows, output_format, |rows, wide| {
+            if wide {
+                create_workloads_table(rows, WideWorkloadTableRow::ADDITIONAL_INFO_POS)
+            } else {
+                let table_rows: Vec<WorkloadTableRow> = rows
+                    .iter()
+                    .map(|row| row.workload_table_row.clone())
+                    .collect();
+                create_workloads_table(&table_rows, WorkloadTableRow::ADDITIONAL_INFO_POS)
+            }
+        })
+    }
 
-        // [impl->swdd~cli-shall-present-workloads-as-table~1]
-        Ok(CliTable::new(&table_rows)
-            .table_with_wrapped_column_to_remaining_terminal_width(
-                WorkloadTableRow::ADDITIONAL_INFO_POS,
-            )
-            .unwrap_or_else(|_err| CliTable::new(&table_rows).create_default_table()))
+    // [impl->swdd~cli-provides-workload-state-history~1]
+    pub async fn get_workload_history_table(
+        &mut self,
+        agent_name: Option<String>,
+        workload_name: Vec<String>,
+        output_format: &ListOutputFormat,
+    ) -> Result<String, CliError> {
+        let field_mask = if workload_name.is_empty() {
+            vec![WORKLOAD_STATE_HISTORY.to_owned()]
+        } else {
+            workload_name
+                .iter()
+                .map(|name| format!("{}.{}", WORKLOAD_STATE_HISTORY, name))
+                .collect()
+        };
+        let complete_state = self
+            .server_connection
+            .get_complete_state(&field_mask)
+            .await?;
+
+        let mut rows: Vec<WorkloadHistoryTableRow> = complete_state
+            .workload_state_history
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|(name, transitions)| {
+                transitions
+                    .into_iter()
+                    .map(move |transition| WorkloadHistoryTableRow {
+                        name: name.clone(),
+                        agent: transition.agent,
+                        time: humantime::format_rfc3339_millis(
+                            UNIX_EPOCH + Duration::from_millis(transition.timestamp),
+                        )
+                        .to_string(),
+                        execution_state: transition.execution_state.state.to_string(),
+                        additional_info: transition.execution_state.additional_info,
+                    })
+            })
+            .collect();
+
+        if let Some(agent_name) = agent_name {
+            rows.retain(|row| row.agent == agent_name);
+        }
+
+        // the transitions of a workload are already ordered, the stable sort keeps them in order
+        rows.sort_by(|a, b| a.name.cmp(&b.name));
+        output_debug!("The workload state history:\n{:?}", rows);
+
+        render_list(&rows, output_format, |rows, _wide| {
+            create_workloads_table(rows, WorkloadHistoryTableRow::ADDITIONAL_INFO_POS)
+        })
     }
 }
 
+// [impl->swdd~cli-shall-present-workloads-as-table~1]
+fn create_workloads_table<RowType: Tabled>(rows: &[RowType], wrapped_column: usize) -> String {
+    CliTable::new(rows)
+        .table_with_wrapped_column_to_remaining_terminal_width(wrapped_column)
+        .unwrap_or_else(|_err| CliTable::new(rows).create_default_table())
+}
+
 //////////////////////////////////////////////////////////////////////////////
 //                 ########  #######    #########  #########                //
 //                    ##     ##        ##             ##                    //
@@ -81,15 +174,19 @@ mod tests {
     use common::{
         objects::{
             self, generate_test_workload_spec_with_param,
-            generate_test_workload_states_map_with_data, ExecutionState,
+            generate_test_workload_states_map_with_data, ExecutionState, Tag,
         },
         test_utils,
     };
     use mockall::predicate::eq;
+    use std::collections::HashMap;
 
-    use crate::cli_commands::{server_connection::MockServerConnection, CliCommands};
-
-    const RESPONSE_TIMEOUT_MS: u64 = 3000;
+    use crate::{
+        cli_commands::{
+            output::ListOutputFormat, server_connection::MockServerConnection, CliCommands,
+        },
+        filtered_complete_state::{FilteredCompleteState, FilteredWorkloadStateTransition},
+    };
 
     // [utest->swdd~cli-shall-present-workloads-as-table~1]
     #[tokio::test]
@@ -107,12 +204,20 @@ mod tests {
                 )
             });
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
-        let cmd_text = cmd.get_workloads_table(None, None, Vec::new()).await;
+        let cmd_text = cmd
+            .get_workloads_table(
+                None,
+                None,
+                None,
+                Vec::new(),
+                vec![],
+                &ListOutputFormat::Table,
+            )
+            .await;
         assert!(cmd_text.is_ok());
 
         let expected_table_output =
@@ -154,12 +259,20 @@ mod tests {
             .return_once(|_| Ok((ank_base::CompleteState::from(test_data)).into()));
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
-        let cmd_text = cmd.get_workloads_table(None, None, Vec::new()).await;
+        let cmd_text = cmd
+            .get_workloads_table(
+                None,
+                None,
+                None,
+                Vec::new(),
+                vec![],
+                &ListOutputFormat::Table,
+            )
+            .await;
         assert!(cmd_text.is_ok());
 
         let expected_table_output = [
@@ -200,13 +313,19 @@ mod tests {
             .with(eq(vec![]))
             .return_once(|_| Ok((ank_base::CompleteState::from(test_data)).into()));
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
         let cmd_text = cmd
-            .get_workloads_table(None, None, vec!["name1".to_string()])
+            .get_workloads_table(
+                None,
+                None,
+                None,
+                vec!["name1".to_string()],
+                vec![],
+                &ListOutputFormat::Table,
+            )
             .await;
         assert!(cmd_text.is_ok());
 
@@ -219,6 +338,49 @@ mod tests {
         assert_eq!(cmd_text.unwrap(), expected_table_output);
     }
 
+    // [utest->swdd~cli-supports-workload-selector~1]
+    #[tokio::test]
+    async fn utest_get_workloads_selected_by_tags() {
+        let selector = vec![Tag {
+            key: "team".to_string(),
+            value: "adas".to_string(),
+        }];
+        // the server returns only the workloads matching the selector
+        let test_data =
+            test_utils::generate_test_complete_state(vec![generate_test_workload_spec_with_param(
+                "agent_A".to_string(),
+                "name1".to_string(),
+                "runtime".to_string(),
+            )]);
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_selected_complete_state()
+            .with(eq(vec![]), eq(selector.clone()))
+            .return_once(|_, _| Ok((ank_base::CompleteState::from(test_data)).into()));
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+        let cmd_text = cmd
+            .get_workloads_table(
+                None,
+                None,
+                None,
+                Vec::new(),
+                selector,
+                &ListOutputFormat::Table,
+            )
+            .await;
+
+        let expected_table_output = [
+            "WORKLOAD NAME   AGENT     RUNTIME   EXECUTION STATE   ADDITIONAL INFO",
+            "name1           agent_A   runtime   Running(Ok)                      ",
+        ]
+        .join("\n");
+        assert_eq!(cmd_text, Ok(expected_table_output));
+    }
+
     // [utest->swdd~cli-shall-filter-list-of-workloads~1]
     #[tokio::test]
     async fn utest_get_workloads_filter_agent() {
@@ -246,12 +408,18 @@ mod tests {
             .with(eq(vec![]))
             .return_once(|_| Ok((ank_base::CompleteState::from(test_data)).into()));
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
         let cmd_text = cmd
-            .get_workloads_table(Some("agent_B".to_string()), None, Vec::new())
+            .get_workloads_table(
+                Some("agent_B".to_string()),
+                None,
+                None,
+                Vec::new(),
+                vec![],
+                &ListOutputFormat::Table,
+            )
             .await;
         assert!(cmd_text.is_ok());
 
@@ -265,6 +433,58 @@ mod tests {
         assert_eq!(cmd_text.unwrap(), expected_table_output);
     }
 
+    // [utest->swdd~cli-shall-filter-list-of-workloads~1]
+    // [utest->swdd~cli-filters-by-namespace~1]
+    #[tokio::test]
+    async fn utest_get_workloads_filter_namespace() {
+        let test_data = test_utils::generate_test_complete_state(vec![
+            generate_test_workload_spec_with_param(
+                "agent_A".to_string(),
+                "name1".to_string(),
+                "runtime".to_string(),
+            ),
+            generate_test_workload_spec_with_param(
+                "agent_B".to_string(),
+                "team-a__name1".to_string(),
+                "runtime".to_string(),
+            ),
+            generate_test_workload_spec_with_param(
+                "agent_B".to_string(),
+                "team-b__name1".to_string(),
+                "runtime".to_string(),
+            ),
+        ]);
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec![]))
+            .return_once(|_| Ok((ank_base::CompleteState::from(test_data)).into()));
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+        let cmd_text = cmd
+            .get_workloads_table(
+                None,
+                None,
+                Some("team-a".to_string()),
+                Vec::new(),
+                vec![],
+                &ListOutputFormat::Table,
+            )
+            .await;
+        assert!(cmd_text.is_ok());
+
+        let expected_table_output = [
+            "WORKLOAD NAME   AGENT     RUNTIME   EXECUTION STATE   ADDITIONAL INFO",
+            "team-a__name1   agent_B   runtime   Running(Ok)                      ",
+        ]
+        .join("\n");
+
+        assert_eq!(cmd_text.unwrap(), expected_table_output);
+    }
+
     // [utest->swdd~cli-shall-filter-list-of-workloads~1]
     #[tokio::test]
     async fn utest_get_workloads_filter_state() {
@@ -292,12 +512,18 @@ mod tests {
             .with(eq(vec![]))
             .return_once(|_| Ok((ank_base::CompleteState::from(test_data)).into()));
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
         let cmd_text = cmd
-            .get_workloads_table(None, Some("Failed".to_string()), Vec::new())
+            .get_workloads_table(
+                None,
+                Some("Failed".to_string()),
+                None,
+                Vec::new(),
+                vec![],
+                &ListOutputFormat::Table,
+            )
             .await;
         assert!(cmd_text.is_ok());
 
@@ -326,12 +552,20 @@ mod tests {
             .with(eq(vec![]))
             .return_once(|_| Ok((ank_base::CompleteState::from(test_data)).into()));
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
 
-        let cmd_text = cmd.get_workloads_table(None, None, Vec::new()).await;
+        let cmd_text = cmd
+            .get_workloads_table(
+                None,
+                None,
+                None,
+                Vec::new(),
+                vec![],
+                &ListOutputFormat::Table,
+            )
+            .await;
         assert!(cmd_text.is_ok());
 
         let expected_table_output = [
@@ -342,4 +576,155 @@ mod tests {
 
         assert_eq!(cmd_text.unwrap(), expected_table_output);
     }
+
+    // [utest->swdd~cli-supports-list-output-formats~1]
+    #[tokio::test]
+    async fn utest_get_workloads_as_yaml_and_wide_table() {
+        let test_data = objects::CompleteState {
+            workload_states: generate_test_workload_states_map_with_data(
+                "agent_A",
+                "Workload_1",
+                "ID_X",
+                ExecutionState::running(),
+            ),
+            ..Default::default()
+        };
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec![]))
+            .times(2)
+            .returning(move |_| Ok((ank_base::CompleteState::from(test_data.clone())).into()));
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let yaml_output = cmd
+            .get_workloads_table(
+                None,
+                None,
+                None,
+                Vec::new(),
+                vec![],
+                &ListOutputFormat::Yaml,
+            )
+            .await;
+        let expected_yaml_output = [
+            "- name: Workload_1",
+            "  agent: agent_A",
+            "  runtime: ''",
+            "  executionState: Running(Ok)",
+            "  additionalInfo: ''",
+            "  instanceId: ID_X",
+        ]
+        .join("\n");
+        assert_eq!(yaml_output, Ok(expected_yaml_output));
+
+        let wide_output = cmd
+            .get_workloads_table(
+                None,
+                None,
+                None,
+                Vec::new(),
+                vec![],
+                &ListOutputFormat::Wide,
+            )
+            .await;
+        // the wide table is truncated to the terminal width of 80 columns in the tests
+        let expected_wide_output = [
+            "WORKLOAD NAME   AGENT     RUNTIME   EXECUTION STATE   INSTANCE ID   ADDITIONAL I",
+            "Workload_1      agent_A             Running(Ok)       ID_X                      ",
+        ]
+        .join("\n");
+        assert_eq!(wide_output, Ok(expected_wide_output));
+    }
+
+    // [utest->swdd~cli-provides-workload-state-history~1]
+    #[tokio::test]
+    async fn utest_get_workload_history() {
+        let transition =
+            |agent: &str, timestamp, execution_state| FilteredWorkloadStateTransition {
+                timestamp,
+                agent: agent.to_string(),
+                id: "ID_X".to_string(),
+                execution_state,
+            };
+        let complete_state = FilteredCompleteState {
+            workload_state_history: Some(HashMap::from([
+                (
+                    "Workload_2".to_string(),
+                    vec![transition(
+                        "agent_B",
+                        1_700_000_000_000,
+                        ExecutionState::running(),
+                    )],
+                ),
+                (
+                    "Workload_1".to_string(),
+                    vec![
+                        transition("agent_A", 1_700_000_000_000, ExecutionState::running()),
+                        transition(
+                            "agent_A",
+                            1_700_000_000_500,
+                            ExecutionState::failed("exit code 1"),
+                        ),
+                    ],
+                ),
+            ])),
+            ..Default::default()
+        };
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec!["workloadStateHistory".to_string()]))
+            .times(2)
+            .returning(move |_| Ok(complete_state.clone()));
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let json_output = cmd
+            .get_workload_history_table(None, Vec::new(), &ListOutputFormat::Json)
+            .await
+            .unwrap();
+        let rows: Vec<serde_json::Value> = serde_json::from_str(&json_output).unwrap();
+        let expected_rows = [
+            ("Workload_1", "2023-11-14T22:13:20.000Z", "Running(Ok)", ""),
+            (
+                "Workload_1",
+                "2023-11-14T22:13:20.500Z",
+                "Failed(ExecFailed)",
+                "exit code 1",
+            ),
+            ("Workload_2", "2023-11-14T22:13:20.000Z", "Running(Ok)", ""),
+        ];
+        assert_eq!(rows.len(), expected_rows.len());
+        for (row, (name, time, execution_state, info)) in rows.iter().zip(expected_rows) {
+            assert_eq!(row["name"], name);
+            assert_eq!(row["time"], time);
+            assert_eq!(row["executionState"], execution_state);
+            assert_eq!(row["additionalInfo"], info);
+        }
+
+        let filtered_output = cmd
+            .get_workload_history_table(
+                Some("agent_B".to_string()),
+                Vec::new(),
+                &ListOutputFormat::Yaml,
+            )
+            .await;
+        let expected_yaml_output = [
+            "- name: Workload_2",
+            "  agent: agent_B",
+            "  time: 2023-11-14T22:13:20.000Z",
+            "  executionState: Running(Ok)",
+            "  additionalInfo: ''",
+        ]
+        .join("\n");
+        assert_eq!(filtered_output, Ok(expected_yaml_output));
+    }
 }
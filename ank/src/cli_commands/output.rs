@@ -0,0 +1,265 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use serde::Serialize;
+use serde_json::Value;
+use tabled::{
+    builder::Builder,
+    settings::{object::Columns, Modify, Padding, Style},
+};
+
+use crate::cli_error::CliError;
+
+const CUSTOM_COLUMNS_PREFIX: &str = "custom-columns=";
+const MISSING_FIELD_VALUE: &str = "<none>";
+
+/// A column of the custom-columns output given as `<HEADER>:<field>`, e.g. `NAME:.name`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomColumn {
+    pub header: String,
+    pub field: String,
+}
+
+/// The output formats of the lists output by `ank get workload`, `ank get agent` and `ank get config`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ListOutputFormat {
+    #[default]
+    Table,
+    Wide,
+    Yaml,
+    Json,
+    CustomColumns(Vec<CustomColumn>),
+}
+
+impl FromStr for ListOutputFormat {
+    type Err = String;
+
+    // [impl->swdd~cli-supports-list-output-formats~1]
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "table" => Ok(ListOutputFormat::Table),
+            "wide" => Ok(ListOutputFormat::Wide),
+            "yaml" => Ok(ListOutputFormat::Yaml),
+            "json" => Ok(ListOutputFormat::Json),
+            _ => match value.strip_prefix(CUSTOM_COLUMNS_PREFIX) {
+                Some(spec) => parse_custom_columns(spec).map(ListOutputFormat::CustomColumns),
+                None => Err(format!(
+                    "unknown output format '{value}', expected one of 'table', 'wide', 'yaml', 'json' or '{CUSTOM_COLUMNS_PREFIX}<spec>'"
+                )),
+            },
+        }
+    }
+}
+
+fn parse_custom_columns(spec: &str) -> Result<Vec<CustomColumn>, String> {
+    spec.split(',').map(parse_custom_column).collect()
+}
+
+fn parse_custom_column(column: &str) -> Result<CustomColumn, String> {
+    let (header, field) = column.split_once(':').unwrap_or((column, ""));
+    // the leading dot of the field is optional
+    let field = field.trim_start_matches('.');
+    if header.is_empty() || field.is_empty() {
+        return Err(format!(
+            "invalid custom column '{column}', expected '<HEADER>:<field>', e.g. 'NAME:.name'"
+        ));
+    }
+    Ok(CustomColumn {
+        header: header.to_owned(),
+        field: field.to_owned(),
+    })
+}
+
+/// Renders the rows of a list in the given output format. The table formats are rendered by
+/// the caller, which gets whether the wide table with additional columns is requested.
+// [impl->swdd~cli-supports-list-output-formats~1]
+pub fn render_list<Row: Serialize>(
+    rows: &[Row],
+    output_format: &ListOutputFormat,
+    render_table: impl FnOnce(&[Row], bool) -> String,
+) -> Result<String, CliError> {
+    match output_format {
+        ListOutputFormat::Table => Ok(render_table(rows, false)),
+        ListOutputFormat::Wide => Ok(render_table(rows, true)),
+        ListOutputFormat::Yaml => Ok(serde_yaml::to_string(rows)?.trim_end().to_owned()),
+        ListOutputFormat::Json => Ok(serde_json::to_string_pretty(rows)?),
+        ListOutputFormat::CustomColumns(columns) => render_custom_columns(rows, columns),
+    }
+}
+
+// [impl->swdd~cli-supports-custom-columns-output~1]
+fn render_custom_columns<Row: Serialize>(
+    rows: &[Row],
+    columns: &[CustomColumn],
+) -> Result<String, CliError> {
+    let mut builder = Builder::default();
+    builder.push_record(columns.iter().map(|column| column.header.clone()));
+    for row in rows {
+        let row_value = serde_json::to_value(row)?;
+        builder.push_record(
+            columns
+                .iter()
+                .map(|column| field_as_string(&row_value, &column.field)),
+        );
+    }
+
+    let mut table = builder.build();
+    table.with(Style::blank());
+    // align the table to the left border of the terminal like the other tables
+    table.with(Modify::new(Columns::first()).with(Padding::new(0, 1, 0, 0)));
+    if columns.len() > 1 {
+        table.with(Modify::new(Columns::last()).with(Padding::new(1, 0, 0, 0)));
+    } else {
+        table.with(Modify::new(Columns::first()).with(Padding::zero()));
+    }
+    Ok(table.to_string())
+}
+
+fn field_as_string(row_value: &Value, field: &str) -> String {
+    let field_value = field
+        .split('.')
+        .try_fold(row_value, |value, field_part| value.get(field_part));
+
+    match field_value {
+        None | Some(Value::Null) => MISSING_FIELD_VALUE.to_owned(),
+        Some(Value::String(text)) => text.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::{render_list, CustomColumn, ListOutputFormat};
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct TestRow {
+        name: String,
+        execution_state: String,
+        restarts: u32,
+    }
+
+    fn test_rows() -> Vec<TestRow> {
+        vec![
+            TestRow {
+                name: "nginx".into(),
+                execution_state: "Running(Ok)".into(),
+                restarts: 0,
+            },
+            TestRow {
+                name: "api".into(),
+                execution_state: "Pending(Starting)".into(),
+                restarts: 2,
+            },
+        ]
+    }
+
+    fn render_table(rows: &[TestRow], wide: bool) -> String {
+        format!("{} rows, wide: {}", rows.len(), wide)
+    }
+
+    // [utest->swdd~cli-supports-list-output-formats~1]
+    #[test]
+    fn utest_parse_list_output_format() {
+        assert_eq!("table".parse(), Ok(ListOutputFormat::Table));
+        assert_eq!("wide".parse(), Ok(ListOutputFormat::Wide));
+        assert_eq!("yaml".parse(), Ok(ListOutputFormat::Yaml));
+        assert_eq!("json".parse(), Ok(ListOutputFormat::Json));
+        assert_eq!(
+            "custom-columns=NAME:.name,STATE:executionState".parse(),
+            Ok(ListOutputFormat::CustomColumns(vec![
+                CustomColumn {
+                    header: "NAME".into(),
+                    field: "name".into(),
+                },
+                CustomColumn {
+                    header: "STATE".into(),
+                    field: "executionState".into(),
+                },
+            ]))
+        );
+
+        assert!("xml".parse::<ListOutputFormat>().is_err());
+        assert!("custom-columns=NAME".parse::<ListOutputFormat>().is_err());
+        assert!("custom-columns=NAME:.".parse::<ListOutputFormat>().is_err());
+    }
+
+    // [utest->swdd~cli-supports-list-output-formats~1]
+    #[test]
+    fn utest_render_list_in_table_formats() {
+        assert_eq!(
+            render_list(&test_rows(), &ListOutputFormat::Table, render_table),
+            Ok("2 rows, wide: false".to_owned())
+        );
+        assert_eq!(
+            render_list(&test_rows(), &ListOutputFormat::Wide, render_table),
+            Ok("2 rows, wide: true".to_owned())
+        );
+    }
+
+    // [utest->swdd~cli-supports-list-output-formats~1]
+    #[test]
+    fn utest_render_list_as_yaml_and_json() {
+        let yaml = render_list(&test_rows(), &ListOutputFormat::Yaml, render_table).unwrap();
+        assert_eq!(
+            yaml,
+            [
+                "- name: nginx",
+                "  executionState: Running(Ok)",
+                "  restarts: 0",
+                "- name: api",
+                "  executionState: Pending(Starting)",
+                "  restarts: 2",
+            ]
+            .join("\n")
+        );
+
+        let json = render_list(&test_rows(), &ListOutputFormat::Json, render_table).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[1]["name"], "api");
+        assert_eq!(parsed[1]["restarts"], 2);
+    }
+
+    // [utest->swdd~cli-supports-custom-columns-output~1]
+    #[test]
+    fn utest_render_list_with_custom_columns() {
+        let output_format = "custom-columns=NAME:.name,RESTARTS:.restarts,OWNER:.owner"
+            .parse()
+            .unwrap();
+
+        let table = render_list(&test_rows(), &output_format, render_table).unwrap();
+
+        assert_eq!(
+            table,
+            [
+                "NAME    RESTARTS   OWNER ",
+                "nginx   0          <none>",
+                "api     2          <none>",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,629 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+
+use super::InputSourcePair;
+
+pub const KUSTOMIZATION_FILE_NAME: &str = "kustomization.yaml";
+const PATCH_DIRECTIVE: &str = "$patch";
+const PATCH_DELETE: &str = "delete";
+const API_VERSION: &str = "apiVersion";
+const WORKLOADS: &str = "workloads";
+const CONFIGS: &str = "configs";
+// the lists of a workload which are merged item by item, identified by the given field
+const MERGE_KEYS: [(&str, &str); 4] = [
+    ("tags", "key"),
+    ("extraHosts", "hostname"),
+    ("volumes", "mountPath"),
+    ("networks", "name"),
+];
+
+/// The kustomization file of an overlay directory
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Kustomization {
+    /// Manifests or overlay directories building the base, relative to the overlay directory
+    #[serde(default)]
+    resources: Vec<PathBuf>,
+    /// Patch files applied to the base, relative to the overlay directory
+    #[serde(default)]
+    patches: Vec<PathBuf>,
+}
+
+/// Builds a single manifest out of the kustomization file in the given overlay directory
+// [impl->swdd~cli-apply-builds-manifest-from-overlay~1]
+pub fn build_overlay_manifest(overlay_dir: &str) -> Result<InputSourcePair, String> {
+    let manifest = build_overlay(Path::new(overlay_dir), &mut Vec::new())?;
+    let content = serde_yaml::to_string(&manifest).map_err(|err| {
+        format!(
+            "Could not serialize the manifest built from '{}': {}",
+            overlay_dir, err
+        )
+    })?;
+    Ok((
+        overlay_dir.to_owned(),
+        Box::new(Cursor::new(content.into_bytes())),
+    ))
+}
+
+fn build_overlay(overlay_dir: &Path, parent_dirs: &mut Vec<PathBuf>) -> Result<Value, String> {
+    let canonical_dir = overlay_dir.canonicalize().map_err(|err| {
+        format!(
+            "Could not open the overlay directory '{}': {}",
+            overlay_dir.display(),
+            err
+        )
+    })?;
+    if parent_dirs.contains(&canonical_dir) {
+        return Err(format!(
+            "The overlay directory '{}' includes itself as resource",
+            overlay_dir.display()
+        ));
+    }
+    let kustomization_file = overlay_dir.join(KUSTOMIZATION_FILE_NAME);
+    let kustomization: Kustomization = serde_yaml::from_value(read_yaml_file(&kustomization_file)?)
+        .map_err(|err| {
+            format!(
+                "Invalid kustomization file '{}': {}",
+                kustomization_file.display(),
+                err
+            )
+        })?;
+
+    parent_dirs.push(canonical_dir);
+    let mut resources = Vec::new();
+    for resource in &kustomization.resources {
+        let path = overlay_dir.join(resource);
+        let manifest = if path.is_dir() {
+            build_overlay(&path, parent_dirs)?
+        } else {
+            read_yaml_file(&path)?
+        };
+        resources.push((path.display().to_string(), manifest));
+    }
+    parent_dirs.pop();
+
+    let patches = kustomization
+        .patches
+        .iter()
+        .map(|patch| {
+            let path = overlay_dir.join(patch);
+            Ok((path.display().to_string(), read_yaml_file(&path)?))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    apply_patches(merge_resources(resources)?, &patches)
+}
+
+fn read_yaml_file(path: &Path) -> Result<Value, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|err| format!("Could not read '{}': {}", path.display(), err))?;
+    serde_yaml::from_str(&content)
+        .map_err(|err| format!("Invalid YAML in '{}': {}", path.display(), err))
+}
+
+// The resources must not overlap, i.e. every workload and config is defined by one resource only.
+// [impl->swdd~cli-apply-builds-manifest-from-overlay~1]
+fn merge_resources(resources: Vec<(String, Value)>) -> Result<Value, String> {
+    let mut base = Mapping::new();
+    let mut origins: HashMap<String, String> = HashMap::new();
+    let mut conflicts = BTreeSet::new();
+
+    for (file, resource) in resources {
+        let Value::Mapping(resource) = resource else {
+            return Err(format!("The resource '{}' is not a manifest", file));
+        };
+        for (key, value) in resource {
+            let field = value_to_string(&key);
+            if field == WORKLOADS || field == CONFIGS {
+                let Value::Mapping(entries) = value else {
+                    return Err(format!(
+                        "The field '{}' of the resource '{}' is not a mapping",
+                        field, file
+                    ));
+                };
+                let Value::Mapping(base_entries) = base
+                    .entry(key)
+                    .or_insert_with(|| Value::Mapping(Mapping::new()))
+                else {
+                    unreachable!("the entries of the base are always a mapping");
+                };
+                for (name, entry) in entries {
+                    let path = format!("{}.{}", field, value_to_string(&name));
+                    if let Some(origin) = origins.get(&path) {
+                        conflicts.insert(format!(
+                            "'{}' is defined by '{}' and '{}'",
+                            path, origin, file
+                        ));
+                    } else {
+                        origins.insert(path, file.clone());
+                        base_entries.insert(name, entry);
+                    }
+                }
+            } else if let Some(existing) = base.get(&key) {
+                if *existing != value {
+                    conflicts.insert(format!(
+                        "'{}' differs between '{}' and '{}'",
+                        field,
+                        origins.get(&field).map(String::as_str).unwrap_or_default(),
+                        file
+                    ));
+                }
+            } else {
+                origins.insert(field, file.clone());
+                base.insert(key, value);
+            }
+        }
+    }
+
+    if !conflicts.is_empty() {
+        return Err(format_conflicts(
+            "Overlapping overlay resources",
+            &conflicts,
+        ));
+    }
+    Ok(Value::Mapping(base))
+}
+
+// All patches are checked against each other before any of them is applied such that
+// every conflict is reported at once and the result does not depend on the order of the patches.
+// [impl->swdd~cli-apply-overlay-reports-conflicts~1]
+fn apply_patches(mut manifest: Value, patches: &[(String, Value)]) -> Result<Value, String> {
+    let mut patched_fields: Vec<(Vec<String>, Value, &str)> = Vec::new();
+    let mut conflicts = BTreeSet::new();
+    for (file, patch) in patches {
+        let Value::Mapping(patch) = patch else {
+            return Err(format!("The patch '{}' is not a manifest", file));
+        };
+        let mut fields = Vec::new();
+        for (key, value) in patch {
+            let field = value_to_string(key);
+            if field != API_VERSION {
+                collect_patched_fields(value, &mut vec![field.clone()], &field, &mut fields)
+                    .map_err(|err| format!("Invalid patch '{}': {}", file, err))?;
+            }
+        }
+
+        for (path, value) in &fields {
+            for (other_path, other_value, other_file) in &patched_fields {
+                let is_overlapping = path.starts_with(other_path) || other_path.starts_with(path);
+                if is_overlapping && (path != other_path || value != other_value) {
+                    let shorter_path = if path.len() < other_path.len() {
+                        path
+                    } else {
+                        other_path
+                    };
+                    conflicts.insert(format!(
+                        "'{}' is patched differently by '{}' and '{}'",
+                        display_path(shorter_path),
+                        other_file,
+                        file
+                    ));
+                }
+            }
+        }
+        patched_fields.extend(
+            fields
+                .into_iter()
+                .map(|(path, value)| (path, value, file.as_str())),
+        );
+    }
+    if !conflicts.is_empty() {
+        return Err(format_conflicts("Conflicting overlay patches", &conflicts));
+    }
+
+    let Value::Mapping(base) = &mut manifest else {
+        return Err("The resources of the overlay do not build a manifest".to_owned());
+    };
+    for (file, patch) in patches {
+        let Value::Mapping(patch) = patch else {
+            continue;
+        };
+        verify_patched_workloads_exist(base, patch, file)?;
+        merge_mapping(base, patch);
+    }
+    Ok(manifest)
+}
+
+// A patch must not add workloads, as a workload missing in the base is most likely a typo.
+fn verify_patched_workloads_exist(
+    base: &Mapping,
+    patch: &Mapping,
+    file: &str,
+) -> Result<(), String> {
+    let Some(Value::Mapping(patched_workloads)) = patch.get(WORKLOADS) else {
+        return Ok(());
+    };
+    let base_workloads = base.get(WORKLOADS).and_then(Value::as_mapping);
+    let mut missing_workloads = patched_workloads
+        .keys()
+        .filter(|name| !base_workloads.is_some_and(|workloads| workloads.contains_key(*name)))
+        .map(value_to_string)
+        .collect::<Vec<_>>();
+    missing_workloads.sort();
+    if missing_workloads.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "The patch '{}' targets workloads not defined by the resources: '{}'",
+            file,
+            missing_workloads.join("', '")
+        ))
+    }
+}
+
+fn collect_patched_fields(
+    value: &Value,
+    path: &mut Vec<String>,
+    field: &str,
+    patched_fields: &mut Vec<(Vec<String>, Value)>,
+) -> Result<(), String> {
+    if has_delete_directive(value, path)? {
+        patched_fields.push((path.clone(), value.clone()));
+        return Ok(());
+    }
+
+    match (value, merge_key_of(field)) {
+        (Value::Mapping(mapping), _) if !mapping.is_empty() => {
+            for (key, child) in mapping {
+                let child_field = value_to_string(key);
+                path.push(child_field.clone());
+                collect_patched_fields(child, path, &child_field, patched_fields)?;
+                path.pop();
+            }
+        }
+        (Value::Sequence(items), Some(merge_key)) => {
+            for item in items {
+                let key_value = item.get(merge_key).map(value_to_string).unwrap_or_default();
+                path.push(format!("[{}={}]", merge_key, key_value));
+                match item {
+                    Value::Mapping(item_fields)
+                        if item_fields.len() > 1 && !has_delete_directive(item, path)? =>
+                    {
+                        for (key, child) in item_fields {
+                            let child_field = value_to_string(key);
+                            if child_field != merge_key {
+                                path.push(child_field.clone());
+                                collect_patched_fields(child, path, &child_field, patched_fields)?;
+                                path.pop();
+                            }
+                        }
+                    }
+                    _ => patched_fields.push((path.clone(), item.clone())),
+                }
+                path.pop();
+            }
+        }
+        _ => patched_fields.push((path.clone(), value.clone())),
+    }
+    Ok(())
+}
+
+fn has_delete_directive(value: &Value, path: &[String]) -> Result<bool, String> {
+    match value.get(PATCH_DIRECTIVE) {
+        None => Ok(false),
+        Some(directive) if directive.as_str() == Some(PATCH_DELETE) => Ok(true),
+        Some(directive) => Err(format!(
+            "unsupported directive '{}: {}' at '{}', only '{}: {}' is supported",
+            PATCH_DIRECTIVE,
+            value_to_string(directive),
+            display_path(path),
+            PATCH_DIRECTIVE,
+            PATCH_DELETE
+        )),
+    }
+}
+
+// [impl->swdd~cli-apply-builds-manifest-from-overlay~1]
+fn merge_mapping(target: &mut Mapping, patch: &Mapping) {
+    for (key, value) in patch {
+        if value.is_null() || is_delete_directive(value) {
+            target.remove(key);
+            continue;
+        }
+        let merge_key = merge_key_of(&value_to_string(key));
+        match (target.get_mut(key), value, merge_key) {
+            (Some(Value::Mapping(mapping)), Value::Mapping(patch_mapping), _) => {
+                merge_mapping(mapping, patch_mapping)
+            }
+            (Some(Value::Sequence(items)), Value::Sequence(patch_items), Some(merge_key)) => {
+                merge_sequence(items, patch_items, merge_key)
+            }
+            _ => {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+fn merge_sequence(target: &mut Vec<Value>, patch: &[Value], merge_key: &str) {
+    for item in patch {
+        let position = item.get(merge_key).and_then(|key_value| {
+            target
+                .iter()
+                .position(|target_item| target_item.get(merge_key) == Some(key_value))
+        });
+        match (position, item) {
+            (Some(position), item) if is_delete_directive(item) => {
+                target.remove(position);
+            }
+            (None, item) if is_delete_directive(item) => {}
+            (Some(position), Value::Mapping(patch_item)) => {
+                if let Value::Mapping(target_item) = &mut target[position] {
+                    merge_mapping(target_item, patch_item);
+                }
+            }
+            _ => target.push(item.clone()),
+        }
+    }
+}
+
+fn merge_key_of(field: &str) -> Option<&'static str> {
+    MERGE_KEYS
+        .iter()
+        .find(|(list_field, _)| *list_field == field)
+        .map(|(_, merge_key)| *merge_key)
+}
+
+fn is_delete_directive(value: &Value) -> bool {
+    value.get(PATCH_DIRECTIVE).and_then(Value::as_str) == Some(PATCH_DELETE)
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => serde_yaml::to_string(value)
+            .map(|value| value.trim_end().to_owned())
+            .unwrap_or_default(),
+    }
+}
+
+// The items of merged lists are appended without separator, e.g. 'workloads.nginx.tags[key=owner]'.
+fn display_path(path: &[String]) -> String {
+    path.iter().fold(String::new(), |mut display, part| {
+        if !display.is_empty() && !part.starts_with('[') {
+            display.push('.');
+        }
+        display.push_str(part);
+        display
+    })
+}
+
+fn format_conflicts(message: &str, conflicts: &BTreeSet<String>) -> String {
+    conflicts
+        .iter()
+        .fold(format!("{}:", message), |report, conflict| {
+            format!("{}\n  - {}", report, conflict)
+        })
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use serde_yaml::Value;
+
+    use super::{apply_patches, merge_resources};
+
+    const BASE_WORKLOADS: &str = r#"
+apiVersion: v0.2
+workloads:
+  nginx:
+    runtime: podman
+    agent: agent_A
+    tags:
+      - key: owner
+        value: team_a
+      - key: variant
+        value: base
+    runtimeConfig: |
+      image: nginx
+"#;
+    const BASE_CONFIGS: &str = r#"
+apiVersion: v0.2
+configs:
+  port: "8080"
+"#;
+
+    fn yaml(content: &str) -> Value {
+        serde_yaml::from_str(content).unwrap()
+    }
+
+    fn resources(contents: &[&str]) -> Vec<(String, Value)> {
+        contents
+            .iter()
+            .enumerate()
+            .map(|(index, content)| (format!("resource_{}.yaml", index), yaml(content)))
+            .collect()
+    }
+
+    fn patch(name: &str, content: &str) -> (String, Value) {
+        (name.to_owned(), yaml(content))
+    }
+
+    // [utest->swdd~cli-apply-builds-manifest-from-overlay~1]
+    #[test]
+    fn utest_merge_resources_combines_manifests() {
+        let base = merge_resources(resources(&[BASE_WORKLOADS, BASE_CONFIGS])).unwrap();
+
+        assert_eq!(base["apiVersion"], yaml("v0.2"));
+        assert_eq!(base["workloads"]["nginx"]["agent"], yaml("agent_A"));
+        assert_eq!(base["configs"]["port"], yaml("'8080'"));
+    }
+
+    // [utest->swdd~cli-apply-builds-manifest-from-overlay~1]
+    #[test]
+    fn utest_merge_resources_rejects_overlapping_resources() {
+        let other_api_version = BASE_CONFIGS.replace("v0.2", "v0.1");
+
+        let result = merge_resources(resources(&[
+            BASE_WORKLOADS,
+            BASE_WORKLOADS,
+            &other_api_version,
+        ]));
+
+        assert_eq!(
+            result,
+            Err([
+                "Overlapping overlay resources:",
+                "  - 'apiVersion' differs between 'resource_0.yaml' and 'resource_2.yaml'",
+                "  - 'workloads.nginx' is defined by 'resource_0.yaml' and 'resource_1.yaml'",
+            ]
+            .join("\n"))
+        );
+    }
+
+    // [utest->swdd~cli-apply-builds-manifest-from-overlay~1]
+    #[test]
+    fn utest_apply_patches_merges_strategically() {
+        let base = merge_resources(resources(&[BASE_WORKLOADS, BASE_CONFIGS])).unwrap();
+        let patches = [
+            patch(
+                "variant.yaml",
+                r#"
+workloads:
+  nginx:
+    agent: agent_B
+    tags:
+      - key: variant
+        value: premium
+      - key: owner
+        $patch: delete
+      - key: region
+        value: eu
+configs:
+  port: "9090"
+"#,
+            ),
+            patch(
+                "cleanup.yaml",
+                r#"
+workloads:
+  nginx:
+    runtimeConfig: null
+"#,
+            ),
+        ];
+
+        let manifest = apply_patches(base, &patches).unwrap();
+
+        let expected = yaml(
+            r#"
+apiVersion: v0.2
+workloads:
+  nginx:
+    runtime: podman
+    agent: agent_B
+    tags:
+      - key: variant
+        value: premium
+      - key: region
+        value: eu
+configs:
+  port: "9090"
+"#,
+        );
+        assert_eq!(manifest, expected);
+    }
+
+    // [utest->swdd~cli-apply-builds-manifest-from-overlay~1]
+    #[test]
+    fn utest_apply_patches_deletes_workload() {
+        let base = merge_resources(resources(&[BASE_WORKLOADS])).unwrap();
+        let patches = [patch(
+            "delete.yaml",
+            "workloads:\n  nginx:\n    $patch: delete\n",
+        )];
+
+        let manifest = apply_patches(base, &patches).unwrap();
+
+        assert_eq!(manifest["workloads"], yaml("{}"));
+    }
+
+    // [utest->swdd~cli-apply-builds-manifest-from-overlay~1]
+    #[test]
+    fn utest_apply_patches_rejects_unknown_workload() {
+        let base = merge_resources(resources(&[BASE_WORKLOADS])).unwrap();
+        let patches = [patch(
+            "typo.yaml",
+            "workloads:\n  ngnix:\n    agent: agent_B\n",
+        )];
+
+        assert_eq!(
+            apply_patches(base, &patches),
+            Err(
+                "The patch 'typo.yaml' targets workloads not defined by the resources: 'ngnix'"
+                    .to_owned()
+            )
+        );
+    }
+
+    // [utest->swdd~cli-apply-overlay-reports-conflicts~1]
+    #[test]
+    fn utest_apply_patches_reports_all_conflicts_sorted() {
+        let base = merge_resources(resources(&[BASE_WORKLOADS, BASE_CONFIGS])).unwrap();
+        let patches = [
+            patch(
+                "b.yaml",
+                "workloads:\n  nginx:\n    agent: agent_B\n    tags:\n      - key: owner\n        value: team_b\n",
+            ),
+            patch(
+                "c.yaml",
+                "workloads:\n  nginx:\n    agent: agent_C\n    tags:\n      - key: owner\n        $patch: delete\n",
+            ),
+            patch(
+                "d.yaml",
+                "workloads:\n  nginx:\n    agent: agent_B\n    runtime: podman-kube\n",
+            ),
+        ];
+
+        assert_eq!(
+            apply_patches(base, &patches),
+            Err([
+                "Conflicting overlay patches:",
+                "  - 'workloads.nginx.agent' is patched differently by 'b.yaml' and 'c.yaml'",
+                "  - 'workloads.nginx.agent' is patched differently by 'c.yaml' and 'd.yaml'",
+                "  - 'workloads.nginx.tags[key=owner]' is patched differently by 'b.yaml' and 'c.yaml'",
+            ]
+            .join("\n"))
+        );
+    }
+
+    // [utest->swdd~cli-apply-overlay-reports-conflicts~1]
+    #[test]
+    fn utest_apply_patches_rejects_unsupported_directive() {
+        let base = merge_resources(resources(&[BASE_WORKLOADS])).unwrap();
+        let patches = [patch(
+            "replace.yaml",
+            "workloads:\n  nginx:\n    $patch: replace\n",
+        )];
+
+        assert!(apply_patches(base, &patches)
+            .unwrap_err()
+            .contains("unsupported directive '$patch: replace' at 'workloads.nginx'"));
+    }
+}
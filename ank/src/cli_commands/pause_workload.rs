@@ -0,0 +1,125 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{cli_error::CliError, output_debug};
+
+use super::CliCommands;
+
+impl CliCommands {
+    // [impl->swdd~cli-provides-pause-workload~1]
+    pub async fn pause_workload(
+        &mut self,
+        workload_name: String,
+        resume: bool,
+    ) -> Result<String, CliError> {
+        let pause_workload_success = self
+            .server_connection
+            .pause_workload(workload_name, resume)
+            .await?;
+
+        output_debug!("Got pause success: {:?}", pause_workload_success);
+        let action = if resume { "Resumed" } else { "Paused" };
+        Ok(format!(
+            "{} workload(s): {}",
+            action,
+            pause_workload_success.workloads.join(", ")
+        ))
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use api::ank_base;
+    use mockall::predicate::eq;
+
+    use crate::{
+        cli_commands::{
+            server_connection::{MockServerConnection, ServerConnectionError},
+            CliCommands,
+        },
+        cli_error::CliError,
+    };
+
+    const WORKLOAD_NAME: &str = "nginx";
+
+    // [utest->swdd~cli-provides-pause-workload~1]
+    #[tokio::test]
+    async fn utest_pause_and_resume_workload_output_workloads() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_server_connection = MockServerConnection::default();
+        for resume in [false, true] {
+            mock_server_connection
+                .expect_pause_workload()
+                .with(eq(WORKLOAD_NAME.to_string()), eq(resume))
+                .once()
+                .return_once(|_, _| {
+                    Ok(ank_base::PauseWorkloadSuccess {
+                        workloads: vec!["nginx".to_string(), "nginx-1".to_string()],
+                    })
+                });
+        }
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd.pause_workload(WORKLOAD_NAME.to_string(), false).await;
+        assert_eq!(
+            result.unwrap(),
+            "Paused workload(s): nginx, nginx-1".to_string()
+        );
+        let result = cmd.pause_workload(WORKLOAD_NAME.to_string(), true).await;
+        assert_eq!(
+            result.unwrap(),
+            "Resumed workload(s): nginx, nginx-1".to_string()
+        );
+    }
+
+    // [utest->swdd~cli-provides-pause-workload~1]
+    #[tokio::test]
+    async fn utest_pause_workload_fails_on_server_error() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_pause_workload()
+            .once()
+            .return_once(|_, _| {
+                Err(ServerConnectionError::ExecutionError(
+                    "Request to pause failed with: 'Workload 'nginx' does not exist'".to_string(),
+                ))
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd.pause_workload(WORKLOAD_NAME.to_string(), false).await;
+        assert!(matches!(result, Err(CliError::ExecutionError(_))));
+    }
+}
@@ -0,0 +1,303 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{HashMap, HashSet},
+    net::Ipv4Addr,
+};
+
+use common::commands::PortForwardData;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpListener, TcpStream,
+    },
+    sync::mpsc,
+    task::JoinHandle,
+};
+
+use super::{server_connection::ServerConnectionError, CliCommands};
+use crate::{cli_error::CliError, output, output_debug, output_warn};
+
+const LOCAL_DATA_BUFFER_SIZE: usize = 20;
+const READ_CHUNK_SIZE: usize = 4096;
+
+enum PortForwardEvent {
+    Connection(std::io::Result<TcpStream>),
+    // empty data signals that the local client has closed its side of the connection
+    LocalData(String, Vec<u8>),
+    WorkloadData(Result<(String, Result<PortForwardData, String>), ServerConnectionError>),
+}
+
+struct ForwardedConnection {
+    write_half: OwnedWriteHalf,
+    read_task: JoinHandle<()>,
+}
+
+impl Drop for ForwardedConnection {
+    fn drop(&mut self) {
+        self.read_task.abort();
+    }
+}
+
+impl CliCommands {
+    // [impl->swdd~cli-provides-port-forward~1]
+    pub async fn port_forward(
+        &mut self,
+        workload_name: String,
+        local_port: u16,
+        workload_port: u16,
+    ) -> Result<(), CliError> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, local_port))
+            .await
+            .map_err(|err| {
+                CliError::ExecutionError(format!(
+                    "Could not listen on local port {}: '{}'",
+                    local_port, err
+                ))
+            })?;
+        output!(
+            "Forwarding from {}:{} to port {} of workload '{}'",
+            Ipv4Addr::LOCALHOST,
+            local_port,
+            workload_port,
+            workload_name
+        );
+        self.port_forward_with_listener(workload_name, workload_port, listener)
+            .await
+    }
+
+    // [impl->swdd~cli-port-forward-forwards-connections~1]
+    async fn port_forward_with_listener(
+        &mut self,
+        workload_name: String,
+        workload_port: u16,
+        listener: TcpListener,
+    ) -> Result<(), CliError> {
+        let (local_data_tx, mut local_data_rx) = mpsc::channel(LOCAL_DATA_BUFFER_SIZE);
+        let mut connections: HashMap<String, ForwardedConnection> = HashMap::new();
+
+        loop {
+            let request_ids: HashSet<String> = connections.keys().cloned().collect();
+            // the local data is preferred to forward it before the workload reacts on it
+            let event = tokio::select! {
+                biased;
+                accepted = listener.accept() => {
+                    PortForwardEvent::Connection(accepted.map(|(stream, _)| stream))
+                }
+                Some((request_id, data)) = local_data_rx.recv() => {
+                    PortForwardEvent::LocalData(request_id, data)
+                }
+                data = self.server_connection.read_next_port_forward_data(&request_ids) => {
+                    PortForwardEvent::WorkloadData(data)
+                }
+            };
+
+            match event {
+                PortForwardEvent::Connection(Ok(stream)) => {
+                    let request_id = self
+                        .server_connection
+                        .start_port_forward(workload_name.clone(), workload_port)
+                        .await?;
+                    output_debug!("Forwarding new connection as '{}'", request_id);
+                    let (read_half, write_half) = stream.into_split();
+                    let read_task = tokio::spawn(read_local_connection(
+                        request_id.clone(),
+                        read_half,
+                        local_data_tx.clone(),
+                    ));
+                    connections.insert(
+                        request_id,
+                        ForwardedConnection {
+                            write_half,
+                            read_task,
+                        },
+                    );
+                }
+                PortForwardEvent::Connection(Err(err)) => {
+                    output_warn!("Could not accept the local connection: '{}'", err);
+                }
+                PortForwardEvent::LocalData(request_id, data) => {
+                    if !connections.contains_key(&request_id) {
+                        continue;
+                    }
+                    let close = data.is_empty();
+                    self.server_connection
+                        .send_port_forward_data(request_id, PortForwardData { data, close })
+                        .await?;
+                }
+                PortForwardEvent::WorkloadData(workload_data) => match workload_data? {
+                    (request_id, Ok(port_forward_data)) => {
+                        let Some(connection) = connections.get_mut(&request_id) else {
+                            continue;
+                        };
+                        let write_result = connection
+                            .write_half
+                            .write_all(&port_forward_data.data)
+                            .await;
+                        if port_forward_data.close || write_result.is_err() {
+                            output_debug!("Connection '{}' closed", request_id);
+                            connections.remove(&request_id);
+                        }
+                    }
+                    (request_id, Err(message)) => {
+                        output_warn!("Forwarding the connection failed: '{}'", message);
+                        connections.remove(&request_id);
+                    }
+                },
+            }
+        }
+    }
+}
+
+async fn read_local_connection(
+    request_id: String,
+    mut read_half: OwnedReadHalf,
+    local_data_tx: mpsc::Sender<(String, Vec<u8>)>,
+) {
+    let mut buffer = vec![0; READ_CHUNK_SIZE];
+    loop {
+        let data = match read_half.read(&mut buffer).await {
+            Ok(read_bytes) => buffer[..read_bytes].to_vec(),
+            Err(err) => {
+                output_debug!("Could not read from connection '{}': '{}'", request_id, err);
+                vec![]
+            }
+        };
+        let closed = data.is_empty();
+        if local_data_tx
+            .send((request_id.clone(), data))
+            .await
+            .is_err()
+            || closed
+        {
+            break;
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use common::commands::PortForwardData;
+    use mockall::predicate::eq;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+
+    use crate::cli_commands::{
+        server_connection::{MockServerConnection, ServerConnectionError},
+        CliCommands,
+    };
+    use crate::cli_error::CliError;
+
+    const WORKLOAD_NAME: &str = "nginx";
+    const WORKLOAD_PORT: u16 = 80;
+    const REQUEST_ID: &str = "request_id";
+    const OTHER_REQUEST_ID: &str = "other_request_id";
+
+    // [utest->swdd~cli-port-forward-forwards-connections~1]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn utest_port_forward_forwards_connection_to_workload() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        client.write_all(b"ping").await.unwrap();
+        client.shutdown().await.unwrap();
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_start_port_forward()
+            .with(eq(WORKLOAD_NAME.to_string()), eq(WORKLOAD_PORT))
+            .once()
+            .return_once(|_, _| Ok(REQUEST_ID.to_string()));
+
+        let sent_data = Arc::new(Mutex::new(Vec::new()));
+        let sent_data_clone = sent_data.clone();
+        mock_server_connection
+            .expect_send_port_forward_data()
+            .returning(move |_, port_forward_data| {
+                sent_data_clone.lock().unwrap().push(port_forward_data);
+                Ok(())
+            });
+
+        let sent_data_check = sent_data.clone();
+        let mut workload_answered = false;
+        mock_server_connection
+            .expect_read_next_port_forward_data()
+            .returning(move |_| {
+                if workload_answered {
+                    return Err(ServerConnectionError::ExecutionError(
+                        "Connection to server interrupted".into(),
+                    ));
+                }
+                if sent_data_check.lock().unwrap().len() < 2 {
+                    // data of other connections is ignored until the local data has been sent
+                    return Ok((
+                        OTHER_REQUEST_ID.to_string(),
+                        Ok(PortForwardData {
+                            data: vec![],
+                            close: false,
+                        }),
+                    ));
+                }
+                workload_answered = true;
+                Ok((
+                    REQUEST_ID.to_string(),
+                    Ok(PortForwardData {
+                        data: b"pong".to_vec(),
+                        close: true,
+                    }),
+                ))
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd
+            .port_forward_with_listener(WORKLOAD_NAME.to_string(), WORKLOAD_PORT, listener)
+            .await;
+
+        assert!(matches!(result, Err(CliError::ExecutionError(_))));
+        assert_eq!(
+            *sent_data.lock().unwrap(),
+            vec![
+                PortForwardData {
+                    data: b"ping".to_vec(),
+                    close: false,
+                },
+                PortForwardData {
+                    data: vec![],
+                    close: true,
+                },
+            ]
+        );
+        let mut received = Vec::new();
+        client.read_to_end(&mut received).await.unwrap();
+        assert_eq!(received, b"pong");
+    }
+}
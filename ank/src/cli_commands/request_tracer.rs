@@ -0,0 +1,250 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use api::ank_base;
+use common::{
+    from_server_interface::{FromServer, FromServerReceiver, FromServerSender},
+    to_server_interface::{ToServer, ToServerReceiver, ToServerSender},
+};
+use serde::Serialize;
+use serde_yaml::Value;
+
+const REDACTED: &str = "<redacted>";
+const REDACTED_FIELDS: [&str; 1] = ["registryAuth"];
+
+/// Traces the requests sent to and the responses received from the Ankaios server on stderr
+///
+/// The tracer forwards the messages between the CLI and the communication middleware
+/// and remembers when a request was sent to print the elapsed time with its responses.
+#[derive(Clone, Default)]
+pub struct RequestTracer {
+    // The send times are kept as streaming requests, e.g. for events or exec, get many responses.
+    sent_requests: Arc<Mutex<HashMap<String, Instant>>>,
+}
+
+impl RequestTracer {
+    // [impl->swdd~cli-traces-requests-with-debug-requests~1]
+    pub fn spawn(
+        &self,
+        mut cli_receiver: ToServerReceiver,
+        to_server: ToServerSender,
+        mut middleware_receiver: FromServerReceiver,
+        to_cli: FromServerSender,
+    ) {
+        let tracer = self.clone();
+        tokio::spawn(async move {
+            while let Some(to_server_message) = cli_receiver.recv().await {
+                if let ToServer::Request(request) = &to_server_message {
+                    eprintln!("{}", tracer.trace_request(request.clone().into()));
+                }
+                if to_server.send(to_server_message).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let tracer = self.clone();
+        tokio::spawn(async move {
+            while let Some(from_server_message) = middleware_receiver.recv().await {
+                if let FromServer::Response(response) = &from_server_message {
+                    eprintln!("{}", tracer.trace_response(response));
+                }
+                if to_cli.send(from_server_message).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    fn trace_request(&self, request: ank_base::Request) -> String {
+        self.sent_requests
+            .lock()
+            .unwrap()
+            .insert(request.request_id.clone(), Instant::now());
+        format!(
+            ">>> Request '{}'\n{}",
+            request.request_id,
+            to_redacted_yaml(&request)
+        )
+    }
+
+    fn trace_response(&self, response: &ank_base::Response) -> String {
+        let elapsed = self
+            .sent_requests
+            .lock()
+            .unwrap()
+            .get(&response.request_id)
+            .map(Instant::elapsed);
+        format!(
+            "<<< Response '{}' {}\n{}",
+            response.request_id,
+            format_elapsed(elapsed),
+            to_redacted_yaml(response)
+        )
+    }
+}
+
+fn format_elapsed(elapsed: Option<Duration>) -> String {
+    match elapsed {
+        Some(elapsed) => format!("after {:.1} ms", elapsed.as_secs_f64() * 1000.0),
+        None => "for an unknown request".to_owned(),
+    }
+}
+
+// [impl->swdd~cli-traces-requests-with-debug-requests~1]
+fn to_redacted_yaml(message: &impl Serialize) -> String {
+    match serde_yaml::to_value(message) {
+        Ok(mut value) => {
+            redact(&mut value);
+            serde_yaml::to_string(&value).unwrap_or_else(|err| format!("<{}>\n", err))
+        }
+        Err(err) => format!("<{}>\n", err),
+    }
+}
+
+fn redact(value: &mut Value) {
+    match value {
+        Value::Mapping(mapping) => {
+            for (key, field) in mapping.iter_mut() {
+                if matches!(key.as_str(), Some(key) if REDACTED_FIELDS.contains(&key)) {
+                    *field = Value::String(REDACTED.to_owned());
+                } else {
+                    redact(field);
+                }
+            }
+        }
+        Value::Sequence(sequence) => sequence.iter_mut().for_each(redact),
+        Value::Tagged(tagged) => redact(&mut tagged.value),
+        _ => {}
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use api::ank_base;
+    use common::{
+        commands::{Request, RequestContent, UpdateStateRequest},
+        from_server_interface::FromServer,
+        objects::{generate_test_stored_workload_spec, CompleteState, State},
+        to_server_interface::ToServer,
+    };
+    use tokio::sync::mpsc;
+
+    use super::{format_elapsed, RequestTracer};
+
+    const REQUEST_ID: &str = "request_id";
+
+    fn generate_test_request() -> Request {
+        let mut workload = generate_test_stored_workload_spec("agent_A", "podman");
+        workload.registry_auth = Some(r#"{"auth": "secret_token"}"#.to_owned());
+        Request {
+            request_id: REQUEST_ID.to_owned(),
+            request_content: RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
+                state: CompleteState {
+                    desired_state: State {
+                        workloads: [("workload_A".to_owned(), workload)].into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                update_mask: vec!["desiredState.workloads.workload_A".to_owned()],
+                dry_run: false,
+                grace_period_secs: None,
+            })),
+        }
+    }
+
+    // [utest->swdd~cli-traces-requests-with-debug-requests~1]
+    #[test]
+    fn utest_trace_request_redacts_secrets() {
+        let tracer = RequestTracer::default();
+
+        let trace = tracer.trace_request(generate_test_request().into());
+
+        assert!(trace.starts_with(">>> Request 'request_id'\n"));
+        assert!(trace.contains("workload_A"));
+        assert!(trace.contains("desiredState.workloads.workload_A"));
+        assert!(trace.contains("registryAuth: <redacted>"));
+        assert!(!trace.contains("secret_token"));
+    }
+
+    // [utest->swdd~cli-traces-requests-with-debug-requests~1]
+    #[test]
+    fn utest_trace_response_contains_elapsed_time() {
+        let tracer = RequestTracer::default();
+        let response = ank_base::Response {
+            request_id: REQUEST_ID.to_owned(),
+            response_content: Some(ank_base::response::ResponseContent::Error(
+                ank_base::Error {
+                    message: "access denied".to_owned(),
+                },
+            )),
+        };
+
+        let trace = tracer.trace_response(&response);
+        assert!(trace.starts_with("<<< Response 'request_id' for an unknown request\n"));
+        assert!(trace.contains("access denied"));
+
+        tracer.trace_request(generate_test_request().into());
+        let trace = tracer.trace_response(&response);
+        assert!(trace.starts_with("<<< Response 'request_id' after "));
+
+        assert_eq!(
+            format_elapsed(Some(Duration::from_micros(12345))),
+            "after 12.3 ms"
+        );
+    }
+
+    // [utest->swdd~cli-traces-requests-with-debug-requests~1]
+    #[tokio::test]
+    async fn utest_request_tracer_forwards_messages() {
+        let (to_tracer, cli_receiver) = mpsc::channel(1);
+        let (to_server, mut server_receiver) = mpsc::channel(1);
+        let (to_tracer_from_server, middleware_receiver) = mpsc::channel(1);
+        let (to_cli, mut from_server) = mpsc::channel(1);
+        RequestTracer::default().spawn(cli_receiver, to_server, middleware_receiver, to_cli);
+
+        to_tracer
+            .send(ToServer::Request(generate_test_request()))
+            .await
+            .unwrap();
+        assert_eq!(
+            server_receiver.recv().await,
+            Some(ToServer::Request(generate_test_request()))
+        );
+
+        let response = FromServer::Response(ank_base::Response {
+            request_id: REQUEST_ID.to_owned(),
+            response_content: None,
+        });
+        to_tracer_from_server.send(response.clone()).await.unwrap();
+        assert_eq!(from_server.recv().await, Some(response));
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{cli_error::CliError, output_debug};
+
+use super::CliCommands;
+
+impl CliCommands {
+    // [impl->swdd~cli-provides-restart-workload~1]
+    pub async fn restart_workload(&mut self, workload_name: String) -> Result<String, CliError> {
+        let restart_workload_success = self
+            .server_connection
+            .restart_workload(workload_name)
+            .await?;
+
+        output_debug!("Got restart success: {:?}", restart_workload_success);
+        Ok(format!(
+            "Restarted workload(s): {}",
+            restart_workload_success.restarted_workloads.join(", ")
+        ))
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use api::ank_base;
+    use mockall::predicate::eq;
+
+    use crate::{
+        cli_commands::{
+            server_connection::{MockServerConnection, ServerConnectionError},
+            CliCommands,
+        },
+        cli_error::CliError,
+    };
+
+    const WORKLOAD_NAME: &str = "nginx";
+
+    // [utest->swdd~cli-provides-restart-workload~1]
+    #[tokio::test]
+    async fn utest_restart_workload_outputs_restarted_workloads() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_restart_workload()
+            .with(eq(WORKLOAD_NAME.to_string()))
+            .once()
+            .return_once(|_| {
+                Ok(ank_base::RestartWorkloadSuccess {
+                    restarted_workloads: vec!["nginx".to_string(), "nginx-1".to_string()],
+                })
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd.restart_workload(WORKLOAD_NAME.to_string()).await;
+        assert_eq!(
+            result.unwrap(),
+            "Restarted workload(s): nginx, nginx-1".to_string()
+        );
+    }
+
+    // [utest->swdd~cli-provides-restart-workload~1]
+    #[tokio::test]
+    async fn utest_restart_workload_fails_on_server_error() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_restart_workload()
+            .once()
+            .return_once(|_| {
+                Err(ServerConnectionError::ExecutionError(
+                    "Restart failed with: 'Workload 'nginx' does not exist'".to_string(),
+                ))
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd.restart_workload(WORKLOAD_NAME.to_string()).await;
+        assert!(matches!(result, Err(CliError::ExecutionError(_))));
+    }
+}
@@ -56,7 +56,7 @@ impl CliCommands {
             complete_state_update,
             update_mask
         );
-        self.update_state_and_wait_for_complete(complete_state_update, update_mask)
+        self.update_state_and_wait_for_complete(complete_state_update, update_mask, None)
             .await
     }
 }
@@ -83,8 +83,6 @@ mod tests {
         filtered_complete_state::FilteredCompleteState,
     };
 
-    const RESPONSE_TIMEOUT_MS: u64 = 3000;
-
     // [utest->swdd~cli-provides-run-workload~1]
     // [utest->swdd~cli-blocks-until-ankaios-server-responds-run-workload~2]
     // [utest->swdd~cli-watches-workloads~1]
@@ -162,7 +160,6 @@ mod tests {
             });
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: false,
             server_connection: mock_server_connection,
         };
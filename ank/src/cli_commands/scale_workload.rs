@@ -0,0 +1,169 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use common::objects::{CompleteState, StoredWorkloadSpec};
+
+use crate::{cli_commands::DESIRED_STATE_WORKLOADS, cli_error::CliError, output_debug};
+
+use super::CliCommands;
+
+impl CliCommands {
+    // [impl->swdd~cli-provides-scale-workload~1]
+    pub async fn scale_workload(
+        &mut self,
+        workload_name: String,
+        replicas: u32,
+    ) -> Result<(), CliError> {
+        let workload_field_mask = format!("{}.{}", DESIRED_STATE_WORKLOADS, workload_name);
+        let current_state = self
+            .server_connection
+            .get_complete_state(std::slice::from_ref(&workload_field_mask))
+            .await?;
+
+        // only the replicas are updated, a not existing workload would be created incomplete
+        let workload_exists = current_state
+            .desired_state
+            .and_then(|desired_state| desired_state.workloads)
+            .is_some_and(|workloads| workloads.contains_key(&workload_name));
+        if !workload_exists {
+            return Err(CliError::ExecutionError(format!(
+                "Workload '{}' does not exist",
+                workload_name
+            )));
+        }
+
+        let mut complete_state_update = CompleteState::default();
+        complete_state_update.desired_state.workloads.insert(
+            workload_name,
+            StoredWorkloadSpec {
+                replicas: Some(replicas),
+                ..Default::default()
+            },
+        );
+        let update_mask = vec![format!("{}.replicas", workload_field_mask)];
+
+        output_debug!(
+            "Scaling with complete state update {:?} and update mask {:?}",
+            complete_state_update,
+            update_mask
+        );
+        self.update_state_and_wait_for_complete(complete_state_update, update_mask, None)
+            .await
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use api::ank_base::{self, UpdateStateSuccess};
+    use common::objects::{generate_test_stored_workload_spec, CompleteState, StoredWorkloadSpec};
+    use mockall::predicate::eq;
+
+    use crate::{
+        cli_commands::{server_connection::MockServerConnection, CliCommands},
+        cli_error::CliError,
+        filtered_complete_state::FilteredCompleteState,
+    };
+
+    const WORKLOAD_NAME: &str = "nginx";
+    const WORKLOAD_FIELD_MASK: &str = "desiredState.workloads.nginx";
+
+    // [utest->swdd~cli-provides-scale-workload~1]
+    #[tokio::test]
+    async fn utest_scale_workload_updates_replicas() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut current_state = CompleteState::default();
+        current_state.desired_state.workloads.insert(
+            WORKLOAD_NAME.to_string(),
+            generate_test_stored_workload_spec("agent_A", "podman"),
+        );
+        let mut complete_state_update = CompleteState::default();
+        complete_state_update.desired_state.workloads.insert(
+            WORKLOAD_NAME.to_string(),
+            StoredWorkloadSpec {
+                replicas: Some(3),
+                ..Default::default()
+            },
+        );
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec![WORKLOAD_FIELD_MASK.to_string()]))
+            .once()
+            .return_once(|_| Ok(ank_base::CompleteState::from(current_state).into()));
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec![]))
+            .once()
+            .return_once(|_| Ok(FilteredCompleteState::default()));
+        mock_server_connection
+            .expect_update_state()
+            .with(
+                eq(complete_state_update),
+                eq(vec![format!("{}.replicas", WORKLOAD_FIELD_MASK)]),
+            )
+            .once()
+            .return_once(|_, _| {
+                Ok(UpdateStateSuccess {
+                    added_workloads: vec![
+                        "nginx-1.abc.agent_A".to_string(),
+                        "nginx-2.abc.agent_A".to_string(),
+                    ],
+                    deleted_workloads: vec![],
+                })
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: true,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd.scale_workload(WORKLOAD_NAME.to_string(), 3).await;
+        assert!(result.is_ok());
+    }
+
+    // [utest->swdd~cli-provides-scale-workload~1]
+    #[tokio::test]
+    async fn utest_scale_workload_fails_for_unknown_workload() {
+        let _guard = crate::test_helper::MOCKALL_CONTEXT_SYNC
+            .get_lock_async()
+            .await;
+
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec![WORKLOAD_FIELD_MASK.to_string()]))
+            .once()
+            .return_once(|_| Ok(FilteredCompleteState::default()));
+        mock_server_connection.expect_update_state().never();
+
+        let mut cmd = CliCommands {
+            no_wait: true,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd.scale_workload(WORKLOAD_NAME.to_string(), 3).await;
+        assert!(matches!(result, Err(CliError::ExecutionError(_))));
+    }
+}
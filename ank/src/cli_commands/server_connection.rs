@@ -12,33 +12,39 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{mem::take, time::Duration};
+use std::{collections::HashSet, mem::take, time::Duration};
 
+use super::request_tracer::RequestTracer;
 use crate::filtered_complete_state::FilteredCompleteState;
 use crate::{output_and_error, output_debug};
-use api::ank_base;
+use api::ank_base::{self, exec_output::ExecOutputEnum};
 use common::communications_client::CommunicationsClient;
 use common::communications_error::CommunicationMiddlewareError;
 use common::to_server_interface::ToServer;
 use common::{
-    commands::{CompleteStateRequest, UpdateWorkloadState},
+    commands::{
+        AuditRequest, CompleteStateRequest, EventsRequest, ExecInput, ExecRequest, ExecStart,
+        PauseWorkloadRequest, PortForwardData, PortForwardRequest, PortForwardStart,
+        RestartWorkloadRequest, UpdateWorkloadState,
+    },
     from_server_interface::{FromServer, FromServerReceiver},
-    objects::CompleteState,
+    objects::{CompleteState, Tag},
     to_server_interface::{ToServerInterface, ToServerSender},
 };
 use grpc::client::GRPCCommunicationsClient;
+use grpc::connection_config::ConnectionConfig;
 use grpc::security::TLSConfig;
 #[cfg(test)]
 use mockall::automock;
 
 const BUFFER_SIZE: usize = 20;
-const WAIT_TIME_MS: Duration = Duration::from_millis(3000);
 
 pub struct ServerConnection {
     to_server: ToServerSender,
     from_server: FromServerReceiver,
     task: tokio::task::JoinHandle<()>,
     missed_from_server_messages: Vec<FromServer>,
+    response_timeout: Duration,
 }
 
 #[cfg_attr(test, automock)]
@@ -47,19 +53,38 @@ impl ServerConnection {
     // [impl->swdd~cli-communication-over-middleware~1]
     // testing the function does not bring any benefit so disable the dead code warning when building for test
     #[cfg_attr(test, allow(dead_code))]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         cli_name: &str,
         server_url: String,
+        connection_config: ConnectionConfig,
         tls_config: Option<TLSConfig>,
+        proxy_url: Option<String>,
+        ca_bundle: Option<String>,
+        response_timeout: Duration,
+        debug_requests: bool,
     ) -> Result<Self, CommunicationMiddlewareError> {
         let mut grpc_communications_client = GRPCCommunicationsClient::new_cli_communication(
             cli_name.to_owned(),
             server_url,
             tls_config,
-        )?;
-
-        let (to_cli, cli_receiver) = tokio::sync::mpsc::channel::<FromServer>(BUFFER_SIZE);
-        let (to_server, server_receiver) = tokio::sync::mpsc::channel::<ToServer>(BUFFER_SIZE);
+        )?
+        .with_connection_config(connection_config)
+        // [impl->swdd~cli-supports-proxy-and-ca-bundle-arguments~1]
+        .with_proxy(proxy_url)
+        .with_ca_bundle(ca_bundle);
+
+        let (mut to_cli, cli_receiver) = tokio::sync::mpsc::channel::<FromServer>(BUFFER_SIZE);
+        let (to_server, mut server_receiver) = tokio::sync::mpsc::channel::<ToServer>(BUFFER_SIZE);
+
+        // [impl->swdd~cli-traces-requests-with-debug-requests~1]
+        if debug_requests {
+            let (to_tracer, tracer_receiver) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+            let (to_middleware, middleware_receiver) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+            RequestTracer::default().spawn(server_receiver, to_middleware, tracer_receiver, to_cli);
+            to_cli = to_tracer;
+            server_receiver = middleware_receiver;
+        }
 
         let task = tokio::spawn(async move {
             if let Err(err) = grpc_communications_client
@@ -75,6 +100,7 @@ impl ServerConnection {
             from_server: cli_receiver,
             task,
             missed_from_server_messages: Vec::new(),
+            response_timeout,
         })
     }
 
@@ -89,10 +115,21 @@ impl ServerConnection {
     pub async fn get_complete_state(
         &mut self,
         object_field_mask: &[String],
+    ) -> Result<FilteredCompleteState, ServerConnectionError> {
+        self.get_selected_complete_state(object_field_mask, &[])
+            .await
+    }
+
+    // [impl->swdd~cli-supports-workload-selector~1]
+    pub async fn get_selected_complete_state(
+        &mut self,
+        object_field_mask: &[String],
+        selector: &[Tag],
     ) -> Result<FilteredCompleteState, ServerConnectionError> {
         output_debug!(
-            "get_complete_state: object_field_mask={:?} ",
-            object_field_mask
+            "get_complete_state: object_field_mask={:?}, selector={:?} ",
+            object_field_mask,
+            selector
         );
 
         let request_id = uuid::Uuid::new_v4().to_string();
@@ -102,11 +139,13 @@ impl ServerConnection {
                 request_id.to_owned(),
                 CompleteStateRequest {
                     field_mask: object_field_mask.to_vec(),
+                    selector: selector.to_vec(),
                 },
             )
             .await
             .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
 
+        let response_timeout = self.response_timeout;
         let poll_complete_state_response = async {
             loop {
                 match self.from_server.recv().await {
@@ -116,7 +155,7 @@ impl ServerConnection {
                             Some(ank_base::response::ResponseContent::CompleteState(res)),
                     })) if received_request_id == request_id => {
                         output_debug!("Received from server: {res:?} ");
-                        return Ok(res.into());
+                        return Ok((*res).into());
                     }
                     None => return Err("Channel preliminary closed."),
                     Some(message) => {
@@ -126,13 +165,154 @@ impl ServerConnection {
                 }
             }
         };
-        match tokio::time::timeout(WAIT_TIME_MS, poll_complete_state_response).await {
+        // [impl->swdd~cli-uses-response-timeout~1]
+        match tokio::time::timeout(response_timeout, poll_complete_state_response).await {
             Ok(Ok(res)) => Ok(res),
             Ok(Err(err)) => Err(ServerConnectionError::ExecutionError(format!(
                 "Failed to get complete state.\nError: {err}"
             ))),
             Err(_) => Err(ServerConnectionError::ExecutionError(format!(
-                "Failed to get complete state in time (timeout={WAIT_TIME_MS:?})."
+                "Failed to get complete state in time (timeout={response_timeout:?})."
+            ))),
+        }
+    }
+
+    // [impl->swdd~cli-provides-dependency-graph~1]
+    pub async fn get_dependency_graph(
+        &mut self,
+    ) -> Result<ank_base::DependencyGraph, ServerConnectionError> {
+        let request_id = uuid::Uuid::new_v4().to_string();
+
+        self.to_server
+            .request_dependency_graph(request_id.to_owned())
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
+
+        let response_timeout = self.response_timeout;
+        let poll_dependency_graph_response = async {
+            loop {
+                match self.from_server.recv().await {
+                    Some(FromServer::Response(ank_base::Response {
+                        request_id: received_request_id,
+                        response_content:
+                            Some(ank_base::response::ResponseContent::DependencyGraph(res)),
+                    })) if received_request_id == request_id => {
+                        output_debug!("Received from server: {res:?} ");
+                        return Ok(res);
+                    }
+                    None => return Err("Channel preliminary closed."),
+                    Some(message) => {
+                        // [impl->swdd~cli-stores-unexpected-message~1]
+                        self.missed_from_server_messages.push(message);
+                    }
+                }
+            }
+        };
+        // [impl->swdd~cli-uses-response-timeout~1]
+        match tokio::time::timeout(response_timeout, poll_dependency_graph_response).await {
+            Ok(Ok(res)) => Ok(res),
+            Ok(Err(err)) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get dependency graph.\nError: {err}"
+            ))),
+            Err(_) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get dependency graph in time (timeout={response_timeout:?})."
+            ))),
+        }
+    }
+
+    // [impl->swdd~cli-provides-events~1]
+    pub async fn get_events(
+        &mut self,
+        after_sequence_number: u64,
+    ) -> Result<ank_base::Events, ServerConnectionError> {
+        let request_id = uuid::Uuid::new_v4().to_string();
+
+        self.to_server
+            .request_events(
+                request_id.to_owned(),
+                EventsRequest {
+                    after_sequence_number,
+                },
+            )
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
+
+        let response_timeout = self.response_timeout;
+        let poll_events_response = async {
+            loop {
+                match self.from_server.recv().await {
+                    Some(FromServer::Response(ank_base::Response {
+                        request_id: received_request_id,
+                        response_content: Some(ank_base::response::ResponseContent::Events(res)),
+                    })) if received_request_id == request_id => {
+                        output_debug!("Received from server: {res:?} ");
+                        return Ok(res);
+                    }
+                    None => return Err("Channel preliminary closed."),
+                    Some(message) => {
+                        // [impl->swdd~cli-stores-unexpected-message~1]
+                        self.missed_from_server_messages.push(message);
+                    }
+                }
+            }
+        };
+        // [impl->swdd~cli-uses-response-timeout~1]
+        match tokio::time::timeout(response_timeout, poll_events_response).await {
+            Ok(Ok(res)) => Ok(res),
+            Ok(Err(err)) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get events.\nError: {err}"
+            ))),
+            Err(_) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get events in time (timeout={response_timeout:?})."
+            ))),
+        }
+    }
+
+    // [impl->swdd~cli-provides-audit-log~1]
+    pub async fn get_audit(
+        &mut self,
+        after_sequence_number: u64,
+    ) -> Result<ank_base::AuditEntries, ServerConnectionError> {
+        let request_id = uuid::Uuid::new_v4().to_string();
+
+        self.to_server
+            .request_audit(
+                request_id.to_owned(),
+                AuditRequest {
+                    after_sequence_number,
+                },
+            )
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
+
+        let response_timeout = self.response_timeout;
+        let poll_audit_response = async {
+            loop {
+                match self.from_server.recv().await {
+                    Some(FromServer::Response(ank_base::Response {
+                        request_id: received_request_id,
+                        response_content:
+                            Some(ank_base::response::ResponseContent::AuditEntries(res)),
+                    })) if received_request_id == request_id => {
+                        output_debug!("Received from server: {res:?} ");
+                        return Ok(res);
+                    }
+                    None => return Err("Channel preliminary closed."),
+                    Some(message) => {
+                        // [impl->swdd~cli-stores-unexpected-message~1]
+                        self.missed_from_server_messages.push(message);
+                    }
+                }
+            }
+        };
+        // [impl->swdd~cli-uses-response-timeout~1]
+        match tokio::time::timeout(response_timeout, poll_audit_response).await {
+            Ok(Ok(res)) => Ok(res),
+            Ok(Err(err)) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get audit log.\nError: {err}"
+            ))),
+            Err(_) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get audit log in time (timeout={response_timeout:?})."
             ))),
         }
     }
@@ -149,6 +329,40 @@ impl ServerConnection {
             .await
             .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
 
+        self.wait_for_update_state_success(request_id).await
+    }
+
+    // [impl->swdd~cli-supports-delete-grace-period~1]
+    pub async fn update_state_with_grace_period(
+        &mut self,
+        new_state: CompleteState,
+        update_mask: Vec<String>,
+        grace_period_secs: u32,
+    ) -> Result<ank_base::UpdateStateSuccess, ServerConnectionError> {
+        let request_id = uuid::Uuid::new_v4().to_string();
+        output_debug!(
+            "Sending the new state {:?} with a grace period of {}s",
+            new_state,
+            grace_period_secs
+        );
+        self.to_server
+            .update_state_with_grace_period(
+                request_id.clone(),
+                new_state,
+                update_mask,
+                grace_period_secs,
+            )
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
+
+        self.wait_for_update_state_success(request_id).await
+    }
+
+    async fn wait_for_update_state_success(
+        &mut self,
+        request_id: String,
+    ) -> Result<ank_base::UpdateStateSuccess, ServerConnectionError> {
+        let response_timeout = self.response_timeout;
         let poll_update_state_success = async {
             loop {
                 let Some(server_message) = self.from_server.recv().await else {
@@ -181,7 +395,8 @@ impl ServerConnection {
                 }
             }
         };
-        match tokio::time::timeout(WAIT_TIME_MS, poll_update_state_success).await {
+        // [impl->swdd~cli-uses-response-timeout~1]
+        match tokio::time::timeout(response_timeout, poll_update_state_success).await {
             Ok(Ok(res)) => {
                 output_debug!("Got update success: {:?}", res);
                 Ok(res)
@@ -191,7 +406,175 @@ impl ServerConnection {
                 Err(err)
             }
             Err(_) => Err(ServerConnectionError::ExecutionError(format!(
-                "Failed to get complete state in time (timeout={WAIT_TIME_MS:?})."
+                "Failed to get complete state in time (timeout={response_timeout:?})."
+            ))),
+        }
+    }
+
+    // [impl->swdd~cli-apply-dry-run~1]
+    pub async fn update_state_dry_run(
+        &mut self,
+        new_state: CompleteState,
+        update_mask: Vec<String>,
+    ) -> Result<ank_base::UpdateStateDryRunResult, ServerConnectionError> {
+        let request_id = uuid::Uuid::new_v4().to_string();
+        output_debug!("Sending the new state {:?} as dry run", new_state);
+        self.to_server
+            .update_state_dry_run(request_id.clone(), new_state, update_mask)
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
+
+        let response_timeout = self.response_timeout;
+        let poll_dry_run_result = async {
+            loop {
+                let Some(server_message) = self.from_server.recv().await else {
+                    return Err(ServerConnectionError::ExecutionError(
+                        "Connection to server interrupted".into(),
+                    ));
+                };
+                match server_message {
+                    FromServer::Response(ank_base::Response {
+                        request_id: received_request_id,
+                        response_content:
+                            Some(ank_base::response::ResponseContent::UpdateStateDryRunResult(
+                                dry_run_result,
+                            )),
+                    }) if received_request_id == request_id => return Ok(dry_run_result),
+                    FromServer::Response(ank_base::Response {
+                        request_id: received_request_id,
+                        response_content: Some(ank_base::response::ResponseContent::Error(error)),
+                    }) if received_request_id == request_id => {
+                        return Err(ServerConnectionError::ExecutionError(format!(
+                            "Dry run failed with: '{}'",
+                            error.message
+                        )));
+                    }
+                    message => {
+                        // [impl->swdd~cli-stores-unexpected-message~1]
+                        self.missed_from_server_messages.push(message);
+                    }
+                }
+            }
+        };
+        // [impl->swdd~cli-uses-response-timeout~1]
+        match tokio::time::timeout(response_timeout, poll_dry_run_result).await {
+            Ok(result) => result,
+            Err(_) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to get dry run result in time (timeout={response_timeout:?})."
+            ))),
+        }
+    }
+
+    // [impl->swdd~cli-provides-restart-workload~1]
+    pub async fn restart_workload(
+        &mut self,
+        workload_name: String,
+    ) -> Result<ank_base::RestartWorkloadSuccess, ServerConnectionError> {
+        let request_id = uuid::Uuid::new_v4().to_string();
+        output_debug!("Requesting the restart of workload '{}'", workload_name);
+        self.to_server
+            .request_restart_workload(request_id.clone(), RestartWorkloadRequest { workload_name })
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
+
+        let response_timeout = self.response_timeout;
+        let poll_restart_workload_success = async {
+            loop {
+                let Some(server_message) = self.from_server.recv().await else {
+                    return Err(ServerConnectionError::ExecutionError(
+                        "Connection to server interrupted".into(),
+                    ));
+                };
+                match server_message {
+                    FromServer::Response(ank_base::Response {
+                        request_id: received_request_id,
+                        response_content:
+                            Some(ank_base::response::ResponseContent::RestartWorkloadSuccess(
+                                restart_workload_success,
+                            )),
+                    }) if received_request_id == request_id => return Ok(restart_workload_success),
+                    FromServer::Response(ank_base::Response {
+                        request_id: received_request_id,
+                        response_content: Some(ank_base::response::ResponseContent::Error(error)),
+                    }) if received_request_id == request_id => {
+                        return Err(ServerConnectionError::ExecutionError(format!(
+                            "Restart failed with: '{}'",
+                            error.message
+                        )));
+                    }
+                    message => {
+                        // [impl->swdd~cli-stores-unexpected-message~1]
+                        self.missed_from_server_messages.push(message);
+                    }
+                }
+            }
+        };
+        // [impl->swdd~cli-uses-response-timeout~1]
+        match tokio::time::timeout(response_timeout, poll_restart_workload_success).await {
+            Ok(result) => result,
+            Err(_) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to restart the workload in time (timeout={response_timeout:?})."
+            ))),
+        }
+    }
+
+    // [impl->swdd~cli-provides-pause-workload~1]
+    pub async fn pause_workload(
+        &mut self,
+        workload_name: String,
+        resume: bool,
+    ) -> Result<ank_base::PauseWorkloadSuccess, ServerConnectionError> {
+        let request_id = uuid::Uuid::new_v4().to_string();
+        let action = if resume { "resume" } else { "pause" };
+        output_debug!("Requesting the {} of workload '{}'", action, workload_name);
+        self.to_server
+            .request_pause_workload(
+                request_id.clone(),
+                PauseWorkloadRequest {
+                    workload_name,
+                    resume,
+                },
+            )
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
+
+        let response_timeout = self.response_timeout;
+        let poll_pause_workload_success = async {
+            loop {
+                let Some(server_message) = self.from_server.recv().await else {
+                    return Err(ServerConnectionError::ExecutionError(
+                        "Connection to server interrupted".into(),
+                    ));
+                };
+                match server_message {
+                    FromServer::Response(ank_base::Response {
+                        request_id: received_request_id,
+                        response_content:
+                            Some(ank_base::response::ResponseContent::PauseWorkloadSuccess(
+                                pause_workload_success,
+                            )),
+                    }) if received_request_id == request_id => return Ok(pause_workload_success),
+                    FromServer::Response(ank_base::Response {
+                        request_id: received_request_id,
+                        response_content: Some(ank_base::response::ResponseContent::Error(error)),
+                    }) if received_request_id == request_id => {
+                        return Err(ServerConnectionError::ExecutionError(format!(
+                            "Request to {} failed with: '{}'",
+                            action, error.message
+                        )));
+                    }
+                    message => {
+                        // [impl->swdd~cli-stores-unexpected-message~1]
+                        self.missed_from_server_messages.push(message);
+                    }
+                }
+            }
+        };
+        // [impl->swdd~cli-uses-response-timeout~1]
+        match tokio::time::timeout(response_timeout, poll_pause_workload_success).await {
+            Ok(result) => result,
+            Err(_) => Err(ServerConnectionError::ExecutionError(format!(
+                "Failed to {action} the workload in time (timeout={response_timeout:?})."
             ))),
         }
     }
@@ -216,18 +599,161 @@ impl ServerConnection {
         }
     }
 
-    pub fn take_missed_from_server_messages(&mut self) -> Vec<FromServer> {
-        take(&mut self.missed_from_server_messages)
+    // [impl->swdd~cli-provides-exec~1]
+    pub async fn start_exec(
+        &mut self,
+        workload_name: String,
+        command: Vec<String>,
+    ) -> Result<String, ServerConnectionError> {
+        let request_id = uuid::Uuid::new_v4().to_string();
+        output_debug!(
+            "Starting exec session '{}' in workload '{}' with command {:?}",
+            request_id,
+            workload_name,
+            command
+        );
+        self.to_server
+            .request_exec(
+                request_id.clone(),
+                ExecRequest::Start(ExecStart {
+                    workload_name,
+                    command,
+                }),
+            )
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
+        Ok(request_id)
     }
-}
-
-#[derive(Debug)]
-pub enum ServerConnectionError {
-    ExecutionError(String),
-}
 
-//////////////////////////////////////////////////////////////////////////////
-//                 ########  #######    #########  #########                //
+    // [impl->swdd~cli-exec-forwards-input~1]
+    pub async fn send_exec_input(
+        &mut self,
+        request_id: String,
+        exec_input: ExecInput,
+    ) -> Result<(), ServerConnectionError> {
+        self.to_server
+            .request_exec(request_id, ExecRequest::Input(exec_input))
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))
+    }
+
+    // [impl->swdd~cli-exec-outputs-workload-output~1]
+    pub async fn read_next_exec_output(
+        &mut self,
+        request_id: &str,
+    ) -> Result<ExecOutputEnum, ServerConnectionError> {
+        loop {
+            let Some(server_message) = self.from_server.recv().await else {
+                return Err(ServerConnectionError::ExecutionError(
+                    "Connection to server interrupted".into(),
+                ));
+            };
+            match server_message {
+                FromServer::Response(ank_base::Response {
+                    request_id: received_request_id,
+                    response_content:
+                        Some(ank_base::response::ResponseContent::ExecOutput(ank_base::ExecOutput {
+                            exec_output_enum: Some(exec_output),
+                        })),
+                }) if received_request_id == request_id => return Ok(exec_output),
+                FromServer::Response(ank_base::Response {
+                    request_id: received_request_id,
+                    response_content: Some(ank_base::response::ResponseContent::Error(error)),
+                }) if received_request_id == request_id => {
+                    return Err(ServerConnectionError::ExecutionError(error.message));
+                }
+                message => {
+                    // [impl->swdd~cli-stores-unexpected-message~1]
+                    self.missed_from_server_messages.push(message);
+                }
+            }
+        }
+    }
+
+    // [impl->swdd~cli-port-forward-forwards-connections~1]
+    pub async fn start_port_forward(
+        &mut self,
+        workload_name: String,
+        port: u16,
+    ) -> Result<String, ServerConnectionError> {
+        let request_id = uuid::Uuid::new_v4().to_string();
+        output_debug!(
+            "Starting port forwarding '{}' to port {} of workload '{}'",
+            request_id,
+            port,
+            workload_name
+        );
+        self.to_server
+            .request_port_forward(
+                request_id.clone(),
+                PortForwardRequest::Start(PortForwardStart {
+                    workload_name,
+                    port: port.into(),
+                }),
+            )
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))?;
+        Ok(request_id)
+    }
+
+    // [impl->swdd~cli-port-forward-forwards-connections~1]
+    pub async fn send_port_forward_data(
+        &mut self,
+        request_id: String,
+        port_forward_data: PortForwardData,
+    ) -> Result<(), ServerConnectionError> {
+        self.to_server
+            .request_port_forward(request_id, PortForwardRequest::Data(port_forward_data))
+            .await
+            .map_err(|err| ServerConnectionError::ExecutionError(err.to_string()))
+    }
+
+    // Returns the data or the error received for one of the given port forwardings
+    // [impl->swdd~cli-port-forward-forwards-connections~1]
+    pub async fn read_next_port_forward_data(
+        &mut self,
+        request_ids: &HashSet<String>,
+    ) -> Result<(String, Result<PortForwardData, String>), ServerConnectionError> {
+        loop {
+            let Some(server_message) = self.from_server.recv().await else {
+                return Err(ServerConnectionError::ExecutionError(
+                    "Connection to server interrupted".into(),
+                ));
+            };
+            match server_message {
+                FromServer::Response(ank_base::Response {
+                    request_id,
+                    response_content:
+                        Some(ank_base::response::ResponseContent::PortForwardData(port_forward_data)),
+                }) if request_ids.contains(&request_id) => {
+                    return Ok((request_id, Ok(port_forward_data.into())));
+                }
+                FromServer::Response(ank_base::Response {
+                    request_id,
+                    response_content: Some(ank_base::response::ResponseContent::Error(error)),
+                }) if request_ids.contains(&request_id) => {
+                    return Ok((request_id, Err(error.message)));
+                }
+                message => {
+                    // [impl->swdd~cli-stores-unexpected-message~1]
+                    self.missed_from_server_messages.push(message);
+                }
+            }
+        }
+    }
+
+    pub fn take_missed_from_server_messages(&mut self) -> Vec<FromServer> {
+        take(&mut self.missed_from_server_messages)
+    }
+}
+
+#[derive(Debug)]
+pub enum ServerConnectionError {
+    ExecutionError(String),
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
 //                    ##     ##        ##             ##                    //
 //                    ##     #####     #########      ##                    //
 //                    ##     ##                ##     ##                    //
@@ -235,14 +761,22 @@ pub enum ServerConnectionError {
 //////////////////////////////////////////////////////////////////////////////
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::{
+        collections::{HashMap, HashSet},
+        time::Duration,
+    };
 
-    use super::ank_base::{self, UpdateStateSuccess};
+    use super::ank_base::{self, exec_output::ExecOutputEnum, UpdateStateSuccess};
     use common::{
-        commands::{CompleteStateRequest, RequestContent, UpdateStateRequest, UpdateWorkloadState},
+        commands::{
+            AuditRequest, CompleteStateRequest, DependencyGraphRequest, EventsRequest, ExecInput,
+            ExecRequest, ExecStart, PauseWorkloadRequest, PortForwardData, PortForwardRequest,
+            PortForwardStart, RequestContent, RestartWorkloadRequest, UpdateStateRequest,
+            UpdateWorkloadState,
+        },
         from_server_interface::FromServer,
         objects::{
-            CompleteState, ExecutionState, State, StoredWorkloadSpec, WorkloadInstanceName,
+            CompleteState, ExecutionState, State, StoredWorkloadSpec, Tag, WorkloadInstanceName,
             WorkloadState,
         },
         test_utils,
@@ -250,8 +784,10 @@ mod tests {
     };
     use tokio::sync::mpsc::Receiver;
 
-    use super::ServerConnection;
+    use super::{ServerConnection, ServerConnectionError};
 
+    const RESPONSE_TIMEOUT: Duration = Duration::from_millis(3000);
+    const SHORT_RESPONSE_TIMEOUT: Duration = Duration::from_millis(10);
     const WORKLOAD_NAME_1: &str = "workload_1";
     const WORKLOAD_NAME_2: &str = "workload_2";
     const AGENT_A: &str = "agent_A";
@@ -330,6 +866,7 @@ mod tests {
                     from_server: cli_receiver,
                     task: tokio::spawn(async {}),
                     missed_from_server_messages: Vec::new(),
+                    response_timeout: RESPONSE_TIMEOUT,
                 },
             )
         }
@@ -361,6 +898,11 @@ mod tests {
     }
 
     impl CorrectCommuncationChecker {
+        // requests without a response are not awaited by the server connection
+        async fn wait_for_simulator(&mut self) {
+            (&mut self.join_handle).await.unwrap();
+        }
+
         fn check_communication(mut self) {
             let Ok(mut to_server) = self.is_ready.try_recv() else {
                 panic!("Not all messages have been sent or received");
@@ -409,11 +951,12 @@ mod tests {
             REQUEST,
             RequestContent::CompleteStateRequest(CompleteStateRequest {
                 field_mask: vec![FIELD_MASK.into()],
+                selector: vec![],
             }),
         );
         sim.will_send_response(
             REQUEST,
-            ank_base::response::ResponseContent::CompleteState(
+            ank_base::response::ResponseContent::CompleteState(Box::new(
                 test_utils::generate_test_proto_complete_state(&[(
                     WORKLOAD_NAME_1,
                     ank_base::Workload {
@@ -433,9 +976,28 @@ mod tests {
                             ]
                             .into(),
                         }),
+                        hostname: None,
+                        extra_hosts: None,
+                        ports: None,
+                        resource_thresholds: None,
+                        resources: None,
+                        health_check: None,
+                        restart_backoff: None,
+                        priority: None,
+                        volumes: None,
+                        registry_auth: None,
+                        replicas: None,
+                        networks: None,
+                        env: None,
+                        on_agent_disconnect: None,
+                        restart_on: None,
+                        lifecycle_hooks: None,
+                        node_selector: None,
+                        state_debounce: None,
+                        security_context: None,
                     },
                 )]),
-            ),
+            )),
         );
         let (checker, mut server_connection) = sim.create_server_connection();
 
@@ -463,7 +1025,26 @@ mod tests {
                             ("ref2".into(), "config_2".into()),
                         ]
                         .into()
-                    })
+                    }),
+                    hostname: None,
+                    extra_hosts: None,
+                    ports: None,
+                    resource_thresholds: None,
+                    resources: None,
+                    health_check: None,
+                    restart_backoff: None,
+                    priority: None,
+                    volumes: None,
+                    registry_auth: None,
+                    replicas: None,
+                    networks: None,
+                    env: None,
+                    on_agent_disconnect: None,
+                    restart_on: None,
+                    lifecycle_hooks: None,
+                    node_selector: None,
+                    state_debounce: None,
+                    security_context: None,
                 },
             )])
             .into())
@@ -471,6 +1052,34 @@ mod tests {
         checker.check_communication();
     }
 
+    // [utest->swdd~cli-supports-workload-selector~1]
+    #[tokio::test]
+    async fn utest_get_selected_complete_state() {
+        let selector = vec![Tag {
+            key: "team".into(),
+            value: "adas".into(),
+        }];
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::CompleteStateRequest(CompleteStateRequest {
+                field_mask: vec![FIELD_MASK.into()],
+                selector: selector.clone(),
+            }),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ank_base::response::ResponseContent::CompleteState(Default::default()),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection
+            .get_selected_complete_state(&[FIELD_MASK.into()], &selector)
+            .await;
+        assert!(result.is_ok());
+        checker.check_communication();
+    }
+
     #[tokio::test]
     async fn utest_get_complete_state_fails_at_request() {
         let sim = CommunicationSimulator::default();
@@ -492,6 +1101,7 @@ mod tests {
             REQUEST,
             RequestContent::CompleteStateRequest(CompleteStateRequest {
                 field_mask: vec![FIELD_MASK.into()],
+                selector: vec![],
             }),
         );
         let (_checker, mut server_connection) = sim.create_server_connection();
@@ -502,6 +1112,7 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // [utest->swdd~cli-uses-response-timeout~1]
     #[tokio::test]
     async fn utest_get_complete_state_fails_response_timeout() {
         let mut sim = CommunicationSimulator::default();
@@ -509,16 +1120,23 @@ mod tests {
             REQUEST,
             RequestContent::CompleteStateRequest(CompleteStateRequest {
                 field_mask: vec![FIELD_MASK.into()],
+                selector: vec![],
             }),
         );
         let (checker, mut server_connection) = sim.create_server_connection();
         let (_to_client, from_server) = tokio::sync::mpsc::channel(1);
         server_connection.from_server = from_server;
 
+        server_connection.response_timeout = SHORT_RESPONSE_TIMEOUT;
+
         let result = server_connection
             .get_complete_state(&[FIELD_MASK.into()])
             .await;
-        assert!(result.is_err());
+        assert!(matches!(
+            result,
+            Err(ServerConnectionError::ExecutionError(message))
+                if message == "Failed to get complete state in time (timeout=10ms)."
+        ));
         checker.check_communication();
     }
 
@@ -528,7 +1146,7 @@ mod tests {
         let other_response = FromServer::Response(ank_base::Response {
             request_id: OTHER_REQUEST.into(),
             response_content: Some(ank_base::response::ResponseContent::CompleteState(
-                test_utils::generate_test_proto_complete_state(&[(
+                Box::new(test_utils::generate_test_proto_complete_state(&[(
                     WORKLOAD_NAME_2,
                     ank_base::Workload {
                         agent: Some(AGENT_A.to_string()),
@@ -547,189 +1165,676 @@ mod tests {
                             ]
                             .into(),
                         }),
+                        hostname: None,
+                        extra_hosts: None,
+                        ports: None,
+                        resource_thresholds: None,
+                        resources: None,
+                        health_check: None,
+                        restart_backoff: None,
+                        priority: None,
+                        volumes: None,
+                        registry_auth: None,
+                        replicas: None,
+                        networks: None,
+                        env: None,
+                        on_agent_disconnect: None,
+                        restart_on: None,
+                        lifecycle_hooks: None,
+                        node_selector: None,
+                        state_debounce: None,
+                        security_context: None,
+                    },
+                )])),
+            )),
+        });
+
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::CompleteStateRequest(CompleteStateRequest {
+                field_mask: vec![FIELD_MASK.into()],
+                selector: vec![],
+            }),
+        );
+        sim.will_send_message(other_response.clone());
+        sim.will_send_response(
+            REQUEST,
+            ank_base::response::ResponseContent::CompleteState(Box::new(
+                test_utils::generate_test_proto_complete_state(&[(
+                    WORKLOAD_NAME_1,
+                    ank_base::Workload {
+                        agent: Some(AGENT_A.to_string()),
+                        runtime: Some(RUNTIME.to_string()),
+                        tags: Some(ank_base::Tags { tags: vec![] }),
+                        dependencies: Some(ank_base::Dependencies {
+                            dependencies: HashMap::new(),
+                        }),
+                        restart_policy: Some(ank_base::RestartPolicy::Never as i32),
+                        runtime_config: Some("".to_string()),
+                        control_interface_access: None,
+                        configs: Some(ank_base::ConfigMappings {
+                            configs: [
+                                ("ref1".into(), "config_1".into()),
+                                ("ref2".into(), "config_2".into()),
+                            ]
+                            .into(),
+                        }),
+                        hostname: None,
+                        extra_hosts: None,
+                        ports: None,
+                        resource_thresholds: None,
+                        resources: None,
+                        health_check: None,
+                        restart_backoff: None,
+                        priority: None,
+                        volumes: None,
+                        registry_auth: None,
+                        replicas: None,
+                        networks: None,
+                        env: None,
+                        on_agent_disconnect: None,
+                        restart_on: None,
+                        lifecycle_hooks: None,
+                        node_selector: None,
+                        state_debounce: None,
+                        security_context: None,
                     },
                 )]),
             )),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection
+            .get_complete_state(&[FIELD_MASK.into()])
+            .await;
+        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap(),
+            (test_utils::generate_test_proto_complete_state(&[(
+                WORKLOAD_NAME_1,
+                ank_base::Workload {
+                    agent: Some(AGENT_A.to_string()),
+                    runtime: Some(RUNTIME.to_string()),
+                    tags: Some(ank_base::Tags { tags: vec![] }),
+                    dependencies: Some(ank_base::Dependencies {
+                        dependencies: HashMap::new()
+                    }),
+                    restart_policy: Some(ank_base::RestartPolicy::Never as i32),
+                    runtime_config: Some("".to_string()),
+                    control_interface_access: None,
+                    configs: Some(ank_base::ConfigMappings {
+                        configs: [
+                            ("ref1".into(), "config_1".into()),
+                            ("ref2".into(), "config_2".into()),
+                        ]
+                        .into()
+                    }),
+                    hostname: None,
+                    extra_hosts: None,
+                    ports: None,
+                    resource_thresholds: None,
+                    resources: None,
+                    health_check: None,
+                    restart_backoff: None,
+                    priority: None,
+                    volumes: None,
+                    registry_auth: None,
+                    replicas: None,
+                    networks: None,
+                    env: None,
+                    on_agent_disconnect: None,
+                    restart_on: None,
+                    lifecycle_hooks: None,
+                    node_selector: None,
+                    state_debounce: None,
+                    security_context: None,
+                },
+            )])
+            .into())
+        );
+        assert_eq!(
+            server_connection.take_missed_from_server_messages(),
+            vec![other_response]
+        );
+        checker.check_communication();
+    }
+
+    // [utest->swdd~cli-stores-unexpected-message~1]
+    #[tokio::test]
+    async fn utest_get_complete_state_other_message_in_between() {
+        let other_message = FromServer::UpdateWorkloadState(UpdateWorkloadState {
+            workload_states: vec![],
         });
 
         let mut sim = CommunicationSimulator::default();
         sim.expect_receive_request(
             REQUEST,
-            RequestContent::CompleteStateRequest(CompleteStateRequest {
-                field_mask: vec![FIELD_MASK.into()],
+            RequestContent::CompleteStateRequest(CompleteStateRequest {
+                field_mask: vec![FIELD_MASK.into()],
+                selector: vec![],
+            }),
+        );
+        sim.will_send_message(other_message.clone());
+        sim.will_send_response(
+            REQUEST,
+            ank_base::response::ResponseContent::CompleteState(Box::new(
+                test_utils::generate_test_proto_complete_state(&[(
+                    WORKLOAD_NAME_1,
+                    ank_base::Workload {
+                        agent: Some(AGENT_A.to_string()),
+                        runtime: Some(RUNTIME.to_string()),
+                        tags: Some(ank_base::Tags { tags: vec![] }),
+                        dependencies: Some(ank_base::Dependencies {
+                            dependencies: HashMap::new(),
+                        }),
+                        restart_policy: Some(ank_base::RestartPolicy::Never as i32),
+                        runtime_config: Some("".to_string()),
+                        control_interface_access: None,
+                        configs: Some(ank_base::ConfigMappings {
+                            configs: [
+                                ("ref1".into(), "config_1".into()),
+                                ("ref2".into(), "config_2".into()),
+                            ]
+                            .into(),
+                        }),
+                        hostname: None,
+                        extra_hosts: None,
+                        ports: None,
+                        resource_thresholds: None,
+                        resources: None,
+                        health_check: None,
+                        restart_backoff: None,
+                        priority: None,
+                        volumes: None,
+                        registry_auth: None,
+                        replicas: None,
+                        networks: None,
+                        env: None,
+                        on_agent_disconnect: None,
+                        restart_on: None,
+                        lifecycle_hooks: None,
+                        node_selector: None,
+                        state_debounce: None,
+                        security_context: None,
+                    },
+                )]),
+            )),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection
+            .get_complete_state(&[FIELD_MASK.into()])
+            .await;
+        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap(),
+            (test_utils::generate_test_proto_complete_state(&[(
+                WORKLOAD_NAME_1,
+                ank_base::Workload {
+                    agent: Some(AGENT_A.to_string()),
+                    runtime: Some(RUNTIME.to_string()),
+                    tags: Some(ank_base::Tags { tags: vec![] }),
+                    dependencies: Some(ank_base::Dependencies {
+                        dependencies: HashMap::new()
+                    }),
+                    restart_policy: Some(ank_base::RestartPolicy::Never as i32),
+                    runtime_config: Some("".to_string()),
+                    control_interface_access: None,
+                    configs: Some(ank_base::ConfigMappings {
+                        configs: [
+                            ("ref1".into(), "config_1".into()),
+                            ("ref2".into(), "config_2".into()),
+                        ]
+                        .into()
+                    }),
+                    hostname: None,
+                    extra_hosts: None,
+                    ports: None,
+                    resource_thresholds: None,
+                    resources: None,
+                    health_check: None,
+                    restart_backoff: None,
+                    priority: None,
+                    volumes: None,
+                    registry_auth: None,
+                    replicas: None,
+                    networks: None,
+                    env: None,
+                    on_agent_disconnect: None,
+                    restart_on: None,
+                    lifecycle_hooks: None,
+                    node_selector: None,
+                    state_debounce: None,
+                    security_context: None,
+                },
+            )])
+            .into())
+        );
+        assert_eq!(
+            server_connection.take_missed_from_server_messages(),
+            vec![other_message]
+        );
+        checker.check_communication();
+    }
+
+    // [utest->swdd~cli-provides-dependency-graph~1]
+    #[tokio::test]
+    async fn utest_get_dependency_graph() {
+        let dependency_graph = ank_base::DependencyGraph {
+            nodes: vec![ank_base::DependencyGraphNode {
+                workload_name: WORKLOAD_NAME_1.into(),
+                agent: AGENT_A.into(),
+                execution_state: None,
+            }],
+            edges: vec![],
+        };
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::DependencyGraphRequest(DependencyGraphRequest {}),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ank_base::response::ResponseContent::DependencyGraph(dependency_graph.clone()),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection.get_dependency_graph().await;
+        assert_eq!(result.unwrap(), dependency_graph);
+        checker.check_communication();
+    }
+
+    // [utest->swdd~cli-uses-response-timeout~1]
+    #[tokio::test]
+    async fn utest_get_dependency_graph_fails_response_timeout() {
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::DependencyGraphRequest(DependencyGraphRequest {}),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+        let (_to_client, from_server) = tokio::sync::mpsc::channel(1);
+        server_connection.from_server = from_server;
+
+        server_connection.response_timeout = SHORT_RESPONSE_TIMEOUT;
+
+        let result = server_connection.get_dependency_graph().await;
+        assert!(matches!(
+            result,
+            Err(ServerConnectionError::ExecutionError(message))
+                if message == "Failed to get dependency graph in time (timeout=10ms)."
+        ));
+        checker.check_communication();
+    }
+
+    // [utest->swdd~cli-provides-events~1]
+    #[tokio::test]
+    async fn utest_get_events() {
+        let events = ank_base::Events {
+            events: vec![ank_base::Event {
+                sequence_number: 2,
+                timestamp: 1000,
+                event_type: ank_base::EventType::AgentConnected as i32,
+                agent: AGENT_A.into(),
+                workload_name: String::new(),
+                execution_state: None,
+            }],
+        };
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::EventsRequest(EventsRequest {
+                after_sequence_number: 1,
+            }),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ank_base::response::ResponseContent::Events(events.clone()),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection.get_events(1).await;
+        assert_eq!(result.unwrap(), events);
+        checker.check_communication();
+    }
+
+    // [utest->swdd~cli-uses-response-timeout~1]
+    #[tokio::test]
+    async fn utest_get_events_fails_response_timeout() {
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::EventsRequest(EventsRequest {
+                after_sequence_number: 0,
+            }),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+        let (_to_client, from_server) = tokio::sync::mpsc::channel(1);
+        server_connection.from_server = from_server;
+
+        server_connection.response_timeout = SHORT_RESPONSE_TIMEOUT;
+
+        let result = server_connection.get_events(0).await;
+        assert!(matches!(
+            result,
+            Err(ServerConnectionError::ExecutionError(message))
+                if message == "Failed to get events in time (timeout=10ms)."
+        ));
+        checker.check_communication();
+    }
+
+    // [utest->swdd~cli-provides-audit-log~1]
+    #[tokio::test]
+    async fn utest_get_audit() {
+        let audit_entries = ank_base::AuditEntries {
+            entries: vec![ank_base::AuditEntry {
+                sequence_number: 2,
+                timestamp: 1000,
+                requester_type: ank_base::RequesterType::Cli as i32,
+                requester: "cli-conn-1234".into(),
+                workload_instance_name: String::new(),
+                update_mask: vec!["desiredState.workloads.nginx".into()],
+                accepted: true,
+                message: String::new(),
+            }],
+        };
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::AuditRequest(AuditRequest {
+                after_sequence_number: 1,
+            }),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ank_base::response::ResponseContent::AuditEntries(audit_entries.clone()),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection.get_audit(1).await;
+        assert_eq!(result.unwrap(), audit_entries);
+        checker.check_communication();
+    }
+
+    // [utest->swdd~cli-uses-response-timeout~1]
+    #[tokio::test]
+    async fn utest_get_audit_fails_response_timeout() {
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::AuditRequest(AuditRequest {
+                after_sequence_number: 0,
+            }),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+        let (_to_client, from_server) = tokio::sync::mpsc::channel(1);
+        server_connection.from_server = from_server;
+
+        server_connection.response_timeout = SHORT_RESPONSE_TIMEOUT;
+
+        let result = server_connection.get_audit(0).await;
+        assert!(matches!(
+            result,
+            Err(ServerConnectionError::ExecutionError(message))
+                if message == "Failed to get audit log in time (timeout=10ms)."
+        ));
+        checker.check_communication();
+    }
+
+    #[tokio::test]
+    async fn utest_update_state() {
+        let update_state_success = UpdateStateSuccess {
+            added_workloads: vec![WORKLOAD_NAME_1.into()],
+            deleted_workloads: vec![],
+        };
+
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
+                state: complete_state(WORKLOAD_NAME_1),
+                update_mask: vec![FIELD_MASK.into()],
+                dry_run: false,
+                grace_period_secs: None,
+            })),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ank_base::response::ResponseContent::UpdateStateSuccess(update_state_success.clone()),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection
+            .update_state(complete_state(WORKLOAD_NAME_1), vec![FIELD_MASK.into()])
+            .await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), update_state_success);
+        checker.check_communication();
+    }
+
+    // [utest->swdd~cli-supports-delete-grace-period~1]
+    #[tokio::test]
+    async fn utest_update_state_with_grace_period() {
+        let update_state_success = UpdateStateSuccess {
+            added_workloads: vec![],
+            deleted_workloads: vec![WORKLOAD_NAME_1.into()],
+        };
+
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
+                state: complete_state(WORKLOAD_NAME_1),
+                update_mask: vec![FIELD_MASK.into()],
+                dry_run: false,
+                grace_period_secs: Some(30),
+            })),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ank_base::response::ResponseContent::UpdateStateSuccess(update_state_success.clone()),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection
+            .update_state_with_grace_period(
+                complete_state(WORKLOAD_NAME_1),
+                vec![FIELD_MASK.into()],
+                30,
+            )
+            .await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), update_state_success);
+        checker.check_communication();
+    }
+
+    // [utest->swdd~cli-apply-dry-run~1]
+    #[tokio::test]
+    async fn utest_update_state_dry_run() {
+        let dry_run_result = ank_base::UpdateStateDryRunResult {
+            added_workloads: vec![WORKLOAD_NAME_1.into()],
+            ..Default::default()
+        };
+
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
+                state: complete_state(WORKLOAD_NAME_1),
+                update_mask: vec![FIELD_MASK.into()],
+                dry_run: true,
+                grace_period_secs: None,
+            })),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ank_base::response::ResponseContent::UpdateStateDryRunResult(dry_run_result.clone()),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection
+            .update_state_dry_run(complete_state(WORKLOAD_NAME_1), vec![FIELD_MASK.into()])
+            .await;
+
+        assert_eq!(result.unwrap(), dry_run_result);
+        checker.check_communication();
+    }
+
+    // [utest->swdd~cli-apply-dry-run~1]
+    #[tokio::test]
+    async fn utest_update_state_dry_run_fails_on_error_response() {
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
+                state: complete_state(WORKLOAD_NAME_1),
+                update_mask: vec![FIELD_MASK.into()],
+                dry_run: true,
+                grace_period_secs: None,
+            })),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ank_base::response::ResponseContent::Error(ank_base::Error {
+                message: "cycle".into(),
+            }),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection
+            .update_state_dry_run(complete_state(WORKLOAD_NAME_1), vec![FIELD_MASK.into()])
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ServerConnectionError::ExecutionError(message))
+                if message == "Dry run failed with: 'cycle'"
+        ));
+        checker.check_communication();
+    }
+
+    // [utest->swdd~cli-provides-restart-workload~1]
+    #[tokio::test]
+    async fn utest_restart_workload() {
+        let restart_workload_success = ank_base::RestartWorkloadSuccess {
+            restarted_workloads: vec![WORKLOAD_NAME_1.into()],
+        };
+
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::RestartWorkloadRequest(RestartWorkloadRequest {
+                workload_name: WORKLOAD_NAME_1.into(),
+            }),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ank_base::response::ResponseContent::RestartWorkloadSuccess(
+                restart_workload_success.clone(),
+            ),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection
+            .restart_workload(WORKLOAD_NAME_1.into())
+            .await;
+
+        assert_eq!(result.unwrap(), restart_workload_success);
+        checker.check_communication();
+    }
+
+    // [utest->swdd~cli-provides-restart-workload~1]
+    #[tokio::test]
+    async fn utest_restart_workload_fails_on_error_response() {
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::RestartWorkloadRequest(RestartWorkloadRequest {
+                workload_name: WORKLOAD_NAME_1.into(),
             }),
         );
-        sim.will_send_message(other_response.clone());
         sim.will_send_response(
             REQUEST,
-            ank_base::response::ResponseContent::CompleteState(
-                test_utils::generate_test_proto_complete_state(&[(
-                    WORKLOAD_NAME_1,
-                    ank_base::Workload {
-                        agent: Some(AGENT_A.to_string()),
-                        runtime: Some(RUNTIME.to_string()),
-                        tags: Some(ank_base::Tags { tags: vec![] }),
-                        dependencies: Some(ank_base::Dependencies {
-                            dependencies: HashMap::new(),
-                        }),
-                        restart_policy: Some(ank_base::RestartPolicy::Never as i32),
-                        runtime_config: Some("".to_string()),
-                        control_interface_access: None,
-                        configs: Some(ank_base::ConfigMappings {
-                            configs: [
-                                ("ref1".into(), "config_1".into()),
-                                ("ref2".into(), "config_2".into()),
-                            ]
-                            .into(),
-                        }),
-                    },
-                )]),
-            ),
+            ank_base::response::ResponseContent::Error(ank_base::Error {
+                message: "agent not connected".into(),
+            }),
         );
         let (checker, mut server_connection) = sim.create_server_connection();
 
         let result = server_connection
-            .get_complete_state(&[FIELD_MASK.into()])
+            .restart_workload(WORKLOAD_NAME_1.into())
             .await;
-        assert!(result.is_ok());
-        assert_eq!(
-            result.unwrap(),
-            (test_utils::generate_test_proto_complete_state(&[(
-                WORKLOAD_NAME_1,
-                ank_base::Workload {
-                    agent: Some(AGENT_A.to_string()),
-                    runtime: Some(RUNTIME.to_string()),
-                    tags: Some(ank_base::Tags { tags: vec![] }),
-                    dependencies: Some(ank_base::Dependencies {
-                        dependencies: HashMap::new()
-                    }),
-                    restart_policy: Some(ank_base::RestartPolicy::Never as i32),
-                    runtime_config: Some("".to_string()),
-                    control_interface_access: None,
-                    configs: Some(ank_base::ConfigMappings {
-                        configs: [
-                            ("ref1".into(), "config_1".into()),
-                            ("ref2".into(), "config_2".into()),
-                        ]
-                        .into()
-                    })
-                },
-            )])
-            .into())
-        );
-        assert_eq!(
-            server_connection.take_missed_from_server_messages(),
-            vec![other_response]
-        );
+
+        assert!(matches!(
+            result,
+            Err(ServerConnectionError::ExecutionError(message))
+                if message == "Restart failed with: 'agent not connected'"
+        ));
         checker.check_communication();
     }
 
-    // [utest->swdd~cli-stores-unexpected-message~1]
+    // [utest->swdd~cli-provides-pause-workload~1]
     #[tokio::test]
-    async fn utest_get_complete_state_other_message_in_between() {
-        let other_message = FromServer::UpdateWorkloadState(UpdateWorkloadState {
-            workload_states: vec![],
-        });
+    async fn utest_pause_workload() {
+        let pause_workload_success = ank_base::PauseWorkloadSuccess {
+            workloads: vec![WORKLOAD_NAME_1.into()],
+        };
 
         let mut sim = CommunicationSimulator::default();
         sim.expect_receive_request(
             REQUEST,
-            RequestContent::CompleteStateRequest(CompleteStateRequest {
-                field_mask: vec![FIELD_MASK.into()],
+            RequestContent::PauseWorkloadRequest(PauseWorkloadRequest {
+                workload_name: WORKLOAD_NAME_1.into(),
+                resume: false,
             }),
         );
-        sim.will_send_message(other_message.clone());
         sim.will_send_response(
             REQUEST,
-            ank_base::response::ResponseContent::CompleteState(
-                test_utils::generate_test_proto_complete_state(&[(
-                    WORKLOAD_NAME_1,
-                    ank_base::Workload {
-                        agent: Some(AGENT_A.to_string()),
-                        runtime: Some(RUNTIME.to_string()),
-                        tags: Some(ank_base::Tags { tags: vec![] }),
-                        dependencies: Some(ank_base::Dependencies {
-                            dependencies: HashMap::new(),
-                        }),
-                        restart_policy: Some(ank_base::RestartPolicy::Never as i32),
-                        runtime_config: Some("".to_string()),
-                        control_interface_access: None,
-                        configs: Some(ank_base::ConfigMappings {
-                            configs: [
-                                ("ref1".into(), "config_1".into()),
-                                ("ref2".into(), "config_2".into()),
-                            ]
-                            .into(),
-                        }),
-                    },
-                )]),
+            ank_base::response::ResponseContent::PauseWorkloadSuccess(
+                pause_workload_success.clone(),
             ),
         );
         let (checker, mut server_connection) = sim.create_server_connection();
 
         let result = server_connection
-            .get_complete_state(&[FIELD_MASK.into()])
+            .pause_workload(WORKLOAD_NAME_1.into(), false)
             .await;
-        assert!(result.is_ok());
-        assert_eq!(
-            result.unwrap(),
-            (test_utils::generate_test_proto_complete_state(&[(
-                WORKLOAD_NAME_1,
-                ank_base::Workload {
-                    agent: Some(AGENT_A.to_string()),
-                    runtime: Some(RUNTIME.to_string()),
-                    tags: Some(ank_base::Tags { tags: vec![] }),
-                    dependencies: Some(ank_base::Dependencies {
-                        dependencies: HashMap::new()
-                    }),
-                    restart_policy: Some(ank_base::RestartPolicy::Never as i32),
-                    runtime_config: Some("".to_string()),
-                    control_interface_access: None,
-                    configs: Some(ank_base::ConfigMappings {
-                        configs: [
-                            ("ref1".into(), "config_1".into()),
-                            ("ref2".into(), "config_2".into()),
-                        ]
-                        .into()
-                    })
-                },
-            )])
-            .into())
-        );
-        assert_eq!(
-            server_connection.take_missed_from_server_messages(),
-            vec![other_message]
-        );
+
+        assert_eq!(result.unwrap(), pause_workload_success);
         checker.check_communication();
     }
 
+    // [utest->swdd~cli-provides-pause-workload~1]
     #[tokio::test]
-    async fn utest_update_state() {
-        let update_state_success = UpdateStateSuccess {
-            added_workloads: vec![WORKLOAD_NAME_1.into()],
-            deleted_workloads: vec![],
-        };
-
+    async fn utest_resume_workload_fails_on_error_response() {
         let mut sim = CommunicationSimulator::default();
         sim.expect_receive_request(
             REQUEST,
-            RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
-                state: complete_state(WORKLOAD_NAME_1),
-                update_mask: vec![FIELD_MASK.into()],
-            })),
+            RequestContent::PauseWorkloadRequest(PauseWorkloadRequest {
+                workload_name: WORKLOAD_NAME_1.into(),
+                resume: true,
+            }),
         );
         sim.will_send_response(
             REQUEST,
-            ank_base::response::ResponseContent::UpdateStateSuccess(update_state_success.clone()),
+            ank_base::response::ResponseContent::Error(ank_base::Error {
+                message: "agent not connected".into(),
+            }),
         );
         let (checker, mut server_connection) = sim.create_server_connection();
 
         let result = server_connection
-            .update_state(complete_state(WORKLOAD_NAME_1), vec![FIELD_MASK.into()])
+            .pause_workload(WORKLOAD_NAME_1.into(), true)
             .await;
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), update_state_success);
+        assert!(matches!(
+            result,
+            Err(ServerConnectionError::ExecutionError(message))
+                if message == "Request to resume failed with: 'agent not connected'"
+        ));
         checker.check_communication();
     }
 
@@ -756,6 +1861,8 @@ mod tests {
             RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
                 state: complete_state(WORKLOAD_NAME_1),
                 update_mask: vec![FIELD_MASK.into()],
+                dry_run: false,
+                grace_period_secs: None,
             })),
         );
 
@@ -776,6 +1883,8 @@ mod tests {
             RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
                 state: complete_state(WORKLOAD_NAME_1),
                 update_mask: vec![FIELD_MASK.into()],
+                dry_run: false,
+                grace_period_secs: None,
             })),
         );
         sim.will_send_response(
@@ -793,6 +1902,7 @@ mod tests {
         checker.check_communication();
     }
 
+    // [utest->swdd~cli-uses-response-timeout~1]
     #[tokio::test]
     async fn utest_update_state_fails_response_timeout() {
         let mut sim = CommunicationSimulator::default();
@@ -801,6 +1911,8 @@ mod tests {
             RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
                 state: complete_state(WORKLOAD_NAME_1),
                 update_mask: vec![FIELD_MASK.into()],
+                dry_run: false,
+                grace_period_secs: None,
             })),
         );
 
@@ -808,11 +1920,17 @@ mod tests {
         let (_to_client, from_server) = tokio::sync::mpsc::channel(1);
         server_connection.from_server = from_server;
 
+        server_connection.response_timeout = SHORT_RESPONSE_TIMEOUT;
+
         let result = server_connection
             .update_state(complete_state(WORKLOAD_NAME_1), vec![FIELD_MASK.into()])
             .await;
 
-        assert!(result.is_err());
+        assert!(matches!(
+            result,
+            Err(ServerConnectionError::ExecutionError(message))
+                if message == "Failed to get complete state in time (timeout=10ms)."
+        ));
         checker.check_communication();
     }
 
@@ -826,7 +1944,7 @@ mod tests {
         let other_response = FromServer::Response(ank_base::Response {
             request_id: OTHER_REQUEST.into(),
             response_content: Some(ank_base::response::ResponseContent::CompleteState(
-                test_utils::generate_test_proto_complete_state(&[(
+                Box::new(test_utils::generate_test_proto_complete_state(&[(
                     WORKLOAD_NAME_2,
                     ank_base::Workload {
                         agent: Some(AGENT_A.to_string()),
@@ -845,8 +1963,27 @@ mod tests {
                             ]
                             .into(),
                         }),
+                        hostname: None,
+                        extra_hosts: None,
+                        ports: None,
+                        resource_thresholds: None,
+                        resources: None,
+                        health_check: None,
+                        restart_backoff: None,
+                        priority: None,
+                        volumes: None,
+                        registry_auth: None,
+                        replicas: None,
+                        networks: None,
+                        env: None,
+                        on_agent_disconnect: None,
+                        restart_on: None,
+                        lifecycle_hooks: None,
+                        node_selector: None,
+                        state_debounce: None,
+                        security_context: None,
                     },
-                )]),
+                )])),
             )),
         });
 
@@ -856,6 +1993,8 @@ mod tests {
             RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
                 state: complete_state(WORKLOAD_NAME_1),
                 update_mask: vec![FIELD_MASK.into()],
+                dry_run: false,
+                grace_period_secs: None,
             })),
         );
         sim.will_send_message(other_response.clone());
@@ -895,6 +2034,8 @@ mod tests {
             RequestContent::UpdateStateRequest(Box::new(UpdateStateRequest {
                 state: complete_state(WORKLOAD_NAME_1),
                 update_mask: vec![FIELD_MASK.into()],
+                dry_run: false,
+                grace_period_secs: None,
             })),
         );
         sim.will_send_message(other_message.clone());
@@ -983,4 +2124,208 @@ mod tests {
 
         checker.check_communication();
     }
+
+    // [utest->swdd~cli-provides-exec~1]
+    // [utest->swdd~cli-exec-outputs-workload-output~1]
+    #[tokio::test]
+    async fn utest_exec_session_receives_output_until_exit_code() {
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::ExecRequest(ExecRequest::Start(ExecStart {
+                workload_name: WORKLOAD_NAME_1.into(),
+                command: vec!["ls".into()],
+            })),
+        );
+        sim.will_send_message(FromServer::Response(ank_base::Response {
+            request_id: OTHER_REQUEST.into(),
+            response_content: None,
+        }));
+        sim.will_send_response(
+            REQUEST,
+            ank_base::response::ResponseContent::ExecOutput(ank_base::ExecOutput {
+                exec_output_enum: Some(ExecOutputEnum::Stdout(b"file".to_vec())),
+            }),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ank_base::response::ResponseContent::ExecOutput(ank_base::ExecOutput {
+                exec_output_enum: Some(ExecOutputEnum::ExitCode(0)),
+            }),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let request_id = server_connection
+            .start_exec(WORKLOAD_NAME_1.into(), vec!["ls".into()])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            server_connection
+                .read_next_exec_output(&request_id)
+                .await
+                .unwrap(),
+            ExecOutputEnum::Stdout(b"file".to_vec())
+        );
+        assert_eq!(
+            server_connection
+                .read_next_exec_output(&request_id)
+                .await
+                .unwrap(),
+            ExecOutputEnum::ExitCode(0)
+        );
+        assert_eq!(
+            server_connection.take_missed_from_server_messages(),
+            vec![FromServer::Response(ank_base::Response {
+                request_id: OTHER_REQUEST.into(),
+                response_content: None,
+            })]
+        );
+        checker.check_communication();
+    }
+
+    // [utest->swdd~cli-exec-outputs-workload-output~1]
+    #[tokio::test]
+    async fn utest_exec_session_fails_on_error_response() {
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::ExecRequest(ExecRequest::Start(ExecStart {
+                workload_name: WORKLOAD_NAME_1.into(),
+                command: vec!["ls".into()],
+            })),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ank_base::response::ResponseContent::Error(ank_base::Error {
+                message: "Workload 'workload_1' does not exist".into(),
+            }),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let request_id = server_connection
+            .start_exec(WORKLOAD_NAME_1.into(), vec!["ls".into()])
+            .await
+            .unwrap();
+        let result = server_connection.read_next_exec_output(&request_id).await;
+
+        assert!(matches!(
+            result,
+            Err(ServerConnectionError::ExecutionError(message))
+                if message == "Workload 'workload_1' does not exist"
+        ));
+        checker.check_communication();
+    }
+
+    // [utest->swdd~cli-exec-forwards-input~1]
+    #[tokio::test]
+    async fn utest_exec_session_sends_input() {
+        let exec_input = ExecInput {
+            stdin: b"exit".to_vec(),
+            close_stdin: true,
+        };
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::ExecRequest(ExecRequest::Input(exec_input.clone())),
+        );
+        let (mut checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection
+            .send_exec_input(REQUEST.into(), exec_input)
+            .await;
+
+        assert!(result.is_ok());
+        checker.wait_for_simulator().await;
+        checker.check_communication();
+    }
+
+    // [utest->swdd~cli-port-forward-forwards-connections~1]
+    #[tokio::test]
+    async fn utest_port_forward_receives_data_and_errors_of_connection() {
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::PortForwardRequest(PortForwardRequest::Start(PortForwardStart {
+                workload_name: WORKLOAD_NAME_1.into(),
+                port: 80,
+            })),
+        );
+        sim.will_send_message(FromServer::Response(ank_base::Response {
+            request_id: OTHER_REQUEST.into(),
+            response_content: None,
+        }));
+        sim.will_send_response(
+            REQUEST,
+            ank_base::response::ResponseContent::PortForwardData(ank_base::PortForwardData {
+                data: b"HTTP/1.1 200 OK".to_vec(),
+                close: false,
+            }),
+        );
+        sim.will_send_response(
+            REQUEST,
+            ank_base::response::ResponseContent::Error(ank_base::Error {
+                message: "connection refused".into(),
+            }),
+        );
+        let (checker, mut server_connection) = sim.create_server_connection();
+
+        let request_id = server_connection
+            .start_port_forward(WORKLOAD_NAME_1.into(), 80)
+            .await
+            .unwrap();
+        let request_ids = HashSet::from([request_id.clone()]);
+
+        assert_eq!(
+            server_connection
+                .read_next_port_forward_data(&request_ids)
+                .await
+                .unwrap(),
+            (
+                request_id.clone(),
+                Ok(PortForwardData {
+                    data: b"HTTP/1.1 200 OK".to_vec(),
+                    close: false,
+                })
+            )
+        );
+        assert_eq!(
+            server_connection
+                .read_next_port_forward_data(&request_ids)
+                .await
+                .unwrap(),
+            (request_id, Err("connection refused".to_string()))
+        );
+        assert_eq!(
+            server_connection.take_missed_from_server_messages(),
+            vec![FromServer::Response(ank_base::Response {
+                request_id: OTHER_REQUEST.into(),
+                response_content: None,
+            })]
+        );
+        checker.check_communication();
+    }
+
+    // [utest->swdd~cli-port-forward-forwards-connections~1]
+    #[tokio::test]
+    async fn utest_port_forward_sends_data() {
+        let port_forward_data = PortForwardData {
+            data: b"GET /".to_vec(),
+            close: true,
+        };
+        let mut sim = CommunicationSimulator::default();
+        sim.expect_receive_request(
+            REQUEST,
+            RequestContent::PortForwardRequest(PortForwardRequest::Data(port_forward_data.clone())),
+        );
+        let (mut checker, mut server_connection) = sim.create_server_connection();
+
+        let result = server_connection
+            .send_port_forward_data(REQUEST.into(), port_forward_data)
+            .await;
+
+        assert!(result.is_ok());
+        checker.wait_for_simulator().await;
+        checker.check_communication();
+    }
 }
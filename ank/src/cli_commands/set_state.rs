@@ -28,7 +28,7 @@ use tests::read_to_string_mock as read_file_to_string;
 
 use super::CliCommands;
 
-fn create_state_with_default_workload_specs(update_mask: &[String]) -> CompleteState {
+pub fn create_state_with_default_workload_specs(update_mask: &[String]) -> CompleteState {
     let mut complete_state = CompleteState::default();
     const WORKLOAD_ATTRIBUTE_LEVEL: usize = 4;
     let workload_level_mask_parts = ["desiredState".to_string(), "workloads".to_string()];
@@ -90,7 +90,7 @@ async fn process_inputs<R: Read>(reader: R, state_object_file: &str) -> Result<O
     }
 }
 
-fn overwrite_using_field_mask(
+pub fn overwrite_using_field_mask(
     mut complete_state_object: Object,
     object_field_mask: &Vec<String>,
     temp_obj: &Object,
@@ -135,7 +135,7 @@ impl CliCommands {
         );
 
         // [impl->swdd~cli-blocks-until-ankaios-server-responds-set-desired-state~2]
-        self.update_state_and_wait_for_complete(new_complete_state, object_field_mask)
+        self.update_state_and_wait_for_complete(new_complete_state, object_field_mask, None)
             .await
     }
 }
@@ -170,8 +170,6 @@ mod tests {
         Ok(_file)
     }
 
-    const RESPONSE_TIMEOUT_MS: u64 = 3000;
-
     const SAMPLE_CONFIG: &str = r#"desiredState:
         workloads:
           nginx:
@@ -261,7 +259,7 @@ mod tests {
                 .get("nginx")
                 .unwrap()
                 .restart_policy,
-            RestartPolicy::Always
+            Some(RestartPolicy::Always)
         )
     }
 
@@ -314,7 +312,7 @@ mod tests {
         let state_object_file = SAMPLE_CONFIG.to_owned();
 
         let workload_spec = StoredWorkloadSpec {
-            restart_policy: RestartPolicy::Always,
+            restart_policy: Some(RestartPolicy::Always),
             ..Default::default()
         };
         let updated_state = CompleteState {
@@ -334,7 +332,6 @@ mod tests {
             .return_once(|_, _| Ok(UpdateStateSuccess::default()));
 
         let mut cmd = CliCommands {
-            _response_timeout_ms: RESPONSE_TIMEOUT_MS,
             no_wait: true,
             server_connection: mock_server_connection,
         };
@@ -0,0 +1,254 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use super::CliCommands;
+use crate::{
+    cli_commands::{cli_table::CliTable, workload_resources_table_row::WorkloadResourcesTableRow},
+    cli_error::CliError,
+    output, output_debug, output_update,
+};
+
+use common::objects::WorkloadResourcesMap;
+
+const WORKLOAD_RESOURCES_FILTER_MASK: &str = "workloadResources";
+
+impl CliCommands {
+    // [impl->swdd~cli-provides-workload-resources-as-table~1]
+    pub async fn get_workload_resources_table(
+        &mut self,
+        agent_name: &Option<String>,
+    ) -> Result<String, CliError> {
+        let filtered_complete_state = self
+            .server_connection
+            .get_complete_state(&[WORKLOAD_RESOURCES_FILTER_MASK.to_owned()])
+            .await?;
+
+        let workload_resources_map = filtered_complete_state
+            .workload_resources
+            .unwrap_or_default();
+
+        let table_rows = transform_into_table_rows(workload_resources_map, agent_name);
+
+        output_debug!("Got workload resources of complete state: {:?}", table_rows);
+
+        Ok(CliTable::new(&table_rows).create_default_table())
+    }
+
+    // [impl->swdd~cli-refreshes-workload-resources-periodically~1]
+    pub async fn top(
+        &mut self,
+        agent_name: Option<String>,
+        refresh_interval: Duration,
+        once: bool,
+    ) -> Result<(), CliError> {
+        let mut refresh_interval = tokio::time::interval(refresh_interval);
+        loop {
+            refresh_interval.tick().await;
+            let table = self.get_workload_resources_table(&agent_name).await?;
+
+            if once {
+                output!("{}", table);
+                return Ok(());
+            }
+            output_update!("{}", table);
+        }
+    }
+}
+
+fn transform_into_table_rows(
+    workload_resources_map: WorkloadResourcesMap,
+    agent_name: &Option<String>,
+) -> Vec<WorkloadResourcesTableRow> {
+    let mut table_rows: Vec<WorkloadResourcesTableRow> = workload_resources_map
+        .into_iter()
+        .flat_map(|(agent, workloads)| {
+            workloads
+                .into_iter()
+                .map(
+                    move |(workload_name, resource_usage)| WorkloadResourcesTableRow {
+                        name: workload_name,
+                        agent: agent.clone(),
+                        cpu_usage: format!("{}%", resource_usage.cpu_usage),
+                        memory_usage: format!("{}B", resource_usage.memory_usage),
+                    },
+                )
+        })
+        .collect();
+
+    if let Some(agent_name) = agent_name {
+        table_rows.retain(|row| &row.agent == agent_name);
+    }
+
+    // sort to ensure consistent output
+    table_rows.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.agent.cmp(&b.agent)));
+    table_rows
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::cli_commands::{
+        server_connection::{MockServerConnection, ServerConnectionError},
+        CliCommands,
+    };
+    use api::ank_base;
+    use common::objects::{generate_test_workload_resources, CompleteState, WorkloadResourcesMap};
+    use mockall::predicate::eq;
+
+    const AGENT_A_NAME: &str = "agent_A";
+    const AGENT_B_NAME: &str = "agent_B";
+    const WORKLOAD_NAME_1: &str = "workload_1";
+    const WORKLOAD_NAME_2: &str = "workload_2";
+
+    fn generate_test_complete_state_with_workload_resources() -> ank_base::CompleteState {
+        let mut workload_resources = WorkloadResourcesMap::default();
+        workload_resources.update_for_agent(
+            AGENT_A_NAME,
+            vec![
+                generate_test_workload_resources(AGENT_A_NAME, WORKLOAD_NAME_2),
+                generate_test_workload_resources(AGENT_A_NAME, WORKLOAD_NAME_1),
+            ],
+        );
+        workload_resources.update_for_agent(
+            AGENT_B_NAME,
+            vec![generate_test_workload_resources(
+                AGENT_B_NAME,
+                WORKLOAD_NAME_1,
+            )],
+        );
+
+        CompleteState {
+            workload_resources,
+            ..Default::default()
+        }
+        .into()
+    }
+
+    // [utest->swdd~cli-provides-workload-resources-as-table~1]
+    #[tokio::test]
+    async fn utest_get_workload_resources_table() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec!["workloadResources".to_owned()]))
+            .return_once(|_| Ok(generate_test_complete_state_with_workload_resources().into()));
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let table_output_result = cmd.get_workload_resources_table(&None).await;
+
+        let expected_table_output = [
+            "WORKLOAD NAME   AGENT     CPU USAGE   MEMORY USAGE",
+            "workload_1      agent_A   42%         4096B       ",
+            "workload_1      agent_B   42%         4096B       ",
+            "workload_2      agent_A   42%         4096B       ",
+        ]
+        .join("\n");
+
+        assert_eq!(Ok(expected_table_output), table_output_result);
+    }
+
+    // [utest->swdd~cli-provides-workload-resources-as-table~1]
+    #[tokio::test]
+    async fn utest_get_workload_resources_table_filtered_by_agent() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec!["workloadResources".to_owned()]))
+            .return_once(|_| Ok(generate_test_complete_state_with_workload_resources().into()));
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let table_output_result = cmd
+            .get_workload_resources_table(&Some(AGENT_B_NAME.to_owned()))
+            .await;
+
+        let expected_table_output = [
+            "WORKLOAD NAME   AGENT     CPU USAGE   MEMORY USAGE",
+            "workload_1      agent_B   42%         4096B       ",
+        ]
+        .join("\n");
+
+        assert_eq!(Ok(expected_table_output), table_output_result);
+    }
+
+    // [utest->swdd~cli-provides-workload-resources-as-table~1]
+    #[tokio::test]
+    async fn utest_get_workload_resources_table_without_reported_resources() {
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec!["workloadResources".to_owned()]))
+            .return_once(|_| Ok(ank_base::CompleteState::default().into()));
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let table_output_result = cmd.get_workload_resources_table(&None).await;
+
+        assert_eq!(
+            Ok("WORKLOAD NAME   AGENT   CPU USAGE   MEMORY USAGE".to_owned()),
+            table_output_result
+        );
+    }
+
+    // [utest->swdd~cli-refreshes-workload-resources-periodically~1]
+    #[tokio::test]
+    async fn utest_top_stops_on_connection_error() {
+        let mut mock_server_connection = MockServerConnection::default();
+        let mut seq = mockall::Sequence::new();
+        mock_server_connection
+            .expect_get_complete_state()
+            .once()
+            .in_sequence(&mut seq)
+            .return_once(|_| Ok(generate_test_complete_state_with_workload_resources().into()));
+        mock_server_connection
+            .expect_get_complete_state()
+            .once()
+            .in_sequence(&mut seq)
+            .return_once(|_| {
+                Err(ServerConnectionError::ExecutionError(
+                    "connection lost".to_owned(),
+                ))
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd
+            .top(None, std::time::Duration::from_millis(1), false)
+            .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -92,7 +92,7 @@ impl<T: WaitListDisplayTrait> WaitList<T> {
         }
     }
 
-    // [impl->swdd~cli-checks-for-final-workload-state~3]
+    // [impl->swdd~cli-checks-for-final-workload-state~4]
     pub fn update(&mut self, values: impl IntoIterator<Item = WorkloadState>) {
         for workload_state in values.into_iter() {
             self.display.update(&workload_state);
@@ -100,22 +100,20 @@ impl<T: WaitListDisplayTrait> WaitList<T> {
                 common::objects::ExecutionStateEnum::Running(_)
                 | common::objects::ExecutionStateEnum::Succeeded(_)
                 | common::objects::ExecutionStateEnum::Failed(_)
-                | common::objects::ExecutionStateEnum::NotScheduled => {
-                    if self.added_workloads.remove(&workload_state.instance_name) {
-                        self.display.set_complete(&workload_state.instance_name)
-                    }
-                }
-                common::objects::ExecutionStateEnum::Pending(PendingSubstate::StartingFailed) => {
-                    if self.added_workloads.remove(&workload_state.instance_name) {
-                        self.display.set_complete(&workload_state.instance_name)
-                    }
+                | common::objects::ExecutionStateEnum::NotScheduled
+                | common::objects::ExecutionStateEnum::Pending(PendingSubstate::StartingFailed)
+                    if self.added_workloads.remove(&workload_state.instance_name) =>
+                {
+                    self.display.set_complete(&workload_state.instance_name)
                 }
-                common::objects::ExecutionStateEnum::Removed => {
-                    if self.deleted_workloads.remove(&workload_state.instance_name) {
-                        self.display.set_complete(&workload_state.instance_name)
-                    }
+                common::objects::ExecutionStateEnum::Removed
+                | common::objects::ExecutionStateEnum::Rescheduled
+                    if self.deleted_workloads.remove(&workload_state.instance_name) =>
+                {
+                    self.display.set_complete(&workload_state.instance_name)
                 }
-                common::objects::ExecutionStateEnum::AgentDisconnected => {
+                common::objects::ExecutionStateEnum::AgentDisconnected
+                | common::objects::ExecutionStateEnum::AgentLost => {
                     if self.added_workloads.remove(&workload_state.instance_name) {
                         self.display.set_complete(&workload_state.instance_name)
                     }
@@ -239,7 +237,7 @@ mod tests {
         my_mock
     }
 
-    // [utest->swdd~cli-checks-for-final-workload-state~3]
+    // [utest->swdd~cli-checks-for-final-workload-state~4]
     #[test]
     fn utest_update_wait_list_added_running() {
         let (i_name_1, i_name_2, i_name_3) = prepare_test_instance_names();
@@ -265,7 +263,7 @@ mod tests {
         assert!(wait_list.deleted_workloads.contains(&i_name_3));
     }
 
-    // [utest->swdd~cli-checks-for-final-workload-state~3]
+    // [utest->swdd~cli-checks-for-final-workload-state~4]
     #[test]
     fn utest_update_wait_list_added_succeeded() {
         let (i_name_1, i_name_2, i_name_3) = prepare_test_instance_names();
@@ -291,7 +289,7 @@ mod tests {
         assert!(wait_list.deleted_workloads.contains(&i_name_3));
     }
 
-    // [utest->swdd~cli-checks-for-final-workload-state~3]
+    // [utest->swdd~cli-checks-for-final-workload-state~4]
     #[test]
     fn utest_update_wait_list_added_not_scheduled() {
         let (i_name_1, i_name_2, i_name_3) = prepare_test_instance_names();
@@ -317,7 +315,7 @@ mod tests {
         assert!(wait_list.deleted_workloads.contains(&i_name_3));
     }
 
-    // [utest->swdd~cli-checks-for-final-workload-state~3]
+    // [utest->swdd~cli-checks-for-final-workload-state~4]
     #[test]
     fn utest_update_wait_list_added_failed() {
         let (i_name_1, i_name_2, i_name_3) = prepare_test_instance_names();
@@ -343,7 +341,7 @@ mod tests {
         assert!(wait_list.deleted_workloads.contains(&i_name_3));
     }
 
-    // [utest->swdd~cli-checks-for-final-workload-state~3]
+    // [utest->swdd~cli-checks-for-final-workload-state~4]
     #[test]
     fn utest_update_wait_list_added_starting_failed_no_more_retries() {
         let (i_name_1, i_name_2, i_name_3) = prepare_test_instance_names();
@@ -369,7 +367,7 @@ mod tests {
         assert!(wait_list.deleted_workloads.contains(&i_name_3));
     }
 
-    // [utest->swdd~cli-checks-for-final-workload-state~3]
+    // [utest->swdd~cli-checks-for-final-workload-state~4]
     #[test]
     fn utest_update_wait_list_deleted_removed() {
         let (i_name_1, i_name_2, i_name_3) = prepare_test_instance_names();
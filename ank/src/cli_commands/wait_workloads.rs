@@ -0,0 +1,462 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+
+use tokio::time::{timeout_at, Instant};
+
+use common::{
+    from_server_interface::FromServer,
+    objects::{ExecutionState, WorkloadInstanceName, WorkloadState},
+};
+
+use super::CliCommands;
+use crate::{cli::WaitState, cli_error::CliError, output, output_debug};
+
+const WORKLOAD_STATES_FILTER_MASK: &str = "workloadStates";
+
+impl WaitState {
+    fn is_reached_by(&self, execution_state: &ExecutionState) -> bool {
+        match self {
+            WaitState::Running => execution_state.is_running(),
+            WaitState::Succeeded => execution_state.is_succeeded(),
+            WaitState::Failed => execution_state.is_failed(),
+            WaitState::Removed => execution_state.is_removed(),
+        }
+    }
+}
+
+// Tracks the execution states of all instances of the workloads waited for
+struct WaitedWorkloads {
+    target_state: WaitState,
+    instances: BTreeMap<String, HashMap<WorkloadInstanceName, ExecutionState>>,
+}
+
+impl WaitedWorkloads {
+    fn new(workload_names: Vec<String>, target_state: WaitState) -> Self {
+        WaitedWorkloads {
+            target_state,
+            instances: workload_names
+                .into_iter()
+                .map(|workload_name| (workload_name, HashMap::new()))
+                .collect(),
+        }
+    }
+
+    // [impl->swdd~cli-wait-fails-on-failed-workload~1]
+    fn update(&mut self, workload_states: Vec<WorkloadState>) -> Result<(), CliError> {
+        for workload_state in workload_states {
+            let Some(instances) = self
+                .instances
+                .get_mut(workload_state.instance_name.workload_name())
+            else {
+                continue;
+            };
+
+            if workload_state.execution_state.is_removed() {
+                instances.remove(&workload_state.instance_name);
+                continue;
+            }
+
+            if workload_state.execution_state.is_failed() && self.target_state != WaitState::Failed
+            {
+                return Err(CliError::ExecutionError(format!(
+                    "Workload '{}' failed: '{}'",
+                    workload_state.instance_name.workload_name(),
+                    workload_state.execution_state
+                )));
+            }
+            instances.insert(workload_state.instance_name, workload_state.execution_state);
+        }
+        Ok(())
+    }
+
+    fn pending_workloads(&self) -> Vec<&str> {
+        self.instances
+            .iter()
+            .filter(|(_, instances)| match self.target_state {
+                WaitState::Removed => !instances.is_empty(),
+                target_state => !instances
+                    .values()
+                    .any(|execution_state| target_state.is_reached_by(execution_state)),
+            })
+            .map(|(workload_name, _)| workload_name.as_str())
+            .collect()
+    }
+}
+
+impl CliCommands {
+    // [impl->swdd~cli-waits-for-workload-states~1]
+    pub async fn wait_for_workloads(
+        &mut self,
+        workload_names: Vec<String>,
+        target_state: WaitState,
+        timeout: Duration,
+    ) -> Result<(), CliError> {
+        let deadline = Instant::now() + timeout;
+        let mut waited_workloads = WaitedWorkloads::new(workload_names, target_state);
+
+        let Ok(complete_state) = timeout_at(
+            deadline,
+            self.server_connection
+                .get_complete_state(&[WORKLOAD_STATES_FILTER_MASK.to_owned()]),
+        )
+        .await
+        else {
+            return Err(timeout_error(&waited_workloads, timeout));
+        };
+        waited_workloads.update(
+            complete_state?
+                .workload_states
+                .map(Vec::<WorkloadState>::from)
+                .unwrap_or_default(),
+        )?;
+
+        let missed_workload_states = self
+            .server_connection
+            .take_missed_from_server_messages()
+            .into_iter()
+            .filter_map(|message| match message {
+                FromServer::UpdateWorkloadState(update) => Some(update.workload_states),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        waited_workloads.update(missed_workload_states)?;
+
+        while !waited_workloads.pending_workloads().is_empty() {
+            // The deadline is also checked explicitly as the updates of other workloads could keep
+            // the server connection busy.
+            if Instant::now() >= deadline {
+                return Err(timeout_error(&waited_workloads, timeout));
+            }
+            let Ok(update_workload_state) = timeout_at(
+                deadline,
+                self.server_connection.read_next_update_workload_state(),
+            )
+            .await
+            else {
+                return Err(timeout_error(&waited_workloads, timeout));
+            };
+            let update_workload_state = update_workload_state?;
+            output_debug!("Got update workload state: {:?}", update_workload_state);
+            waited_workloads.update(update_workload_state.workload_states)?;
+        }
+
+        output!("All workloads reached the state '{:?}'.", target_state);
+        Ok(())
+    }
+}
+
+// [impl->swdd~cli-wait-fails-on-timeout~1]
+fn timeout_error(waited_workloads: &WaitedWorkloads, timeout: Duration) -> CliError {
+    CliError::ExecutionError(format!(
+        "Timeout after {:?}, the workload(s) '{}' did not reach the state '{:?}'",
+        timeout,
+        waited_workloads.pending_workloads().join("', '"),
+        waited_workloads.target_state
+    ))
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use api::ank_base;
+    use common::{
+        from_server_interface::FromServer,
+        objects::{
+            generate_test_workload_state_with_agent, CompleteState, ExecutionState,
+            WorkloadStatesMap,
+        },
+    };
+    use mockall::predicate::eq;
+
+    use crate::{
+        cli::WaitState,
+        cli_commands::{
+            server_connection::{MockServerConnection, ServerConnectionError},
+            CliCommands,
+        },
+    };
+
+    const AGENT_A_NAME: &str = "agent_A";
+    const WORKLOAD_NAME_1: &str = "workload_1";
+    const WORKLOAD_NAME_2: &str = "workload_2";
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn complete_state_with_workload_states(
+        workload_states: Vec<(&str, ExecutionState)>,
+    ) -> ank_base::CompleteState {
+        let mut workload_states_map = WorkloadStatesMap::default();
+        workload_states_map.process_new_states(
+            workload_states
+                .into_iter()
+                .map(|(workload_name, execution_state)| {
+                    generate_test_workload_state_with_agent(
+                        workload_name,
+                        AGENT_A_NAME,
+                        execution_state,
+                    )
+                })
+                .collect(),
+        );
+        CompleteState {
+            workload_states: workload_states_map,
+            ..Default::default()
+        }
+        .into()
+    }
+
+    fn mock_server_connection_with_initial_states(
+        workload_states: Vec<(&str, ExecutionState)>,
+    ) -> MockServerConnection {
+        let complete_state = complete_state_with_workload_states(workload_states);
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .with(eq(vec!["workloadStates".to_owned()]))
+            .return_once(|_| Ok(complete_state.into()));
+        mock_server_connection
+            .expect_take_missed_from_server_messages()
+            .return_once(Vec::new);
+        mock_server_connection
+    }
+
+    fn update_workload_state(
+        workload_name: &str,
+        execution_state: ExecutionState,
+    ) -> common::commands::UpdateWorkloadState {
+        common::commands::UpdateWorkloadState {
+            workload_states: vec![generate_test_workload_state_with_agent(
+                workload_name,
+                AGENT_A_NAME,
+                execution_state,
+            )],
+        }
+    }
+
+    // [utest->swdd~cli-waits-for-workload-states~1]
+    #[tokio::test]
+    async fn utest_wait_for_workloads_already_reached() {
+        let mock_server_connection = mock_server_connection_with_initial_states(vec![
+            (WORKLOAD_NAME_1, ExecutionState::running()),
+            (WORKLOAD_NAME_2, ExecutionState::running()),
+        ]);
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd
+            .wait_for_workloads(
+                vec![WORKLOAD_NAME_1.to_owned(), WORKLOAD_NAME_2.to_owned()],
+                WaitState::Running,
+                TIMEOUT,
+            )
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    // [utest->swdd~cli-waits-for-workload-states~1]
+    #[tokio::test]
+    async fn utest_wait_for_workloads_reached_with_updates() {
+        let mut mock_server_connection = mock_server_connection_with_initial_states(vec![
+            (WORKLOAD_NAME_1, ExecutionState::starting_triggered()),
+            (WORKLOAD_NAME_2, ExecutionState::succeeded()),
+        ]);
+        let mut seq = mockall::Sequence::new();
+        mock_server_connection
+            .expect_read_next_update_workload_state()
+            .once()
+            .in_sequence(&mut seq)
+            .return_once(|| {
+                Ok(update_workload_state(
+                    WORKLOAD_NAME_2,
+                    ExecutionState::removed(),
+                ))
+            });
+        mock_server_connection
+            .expect_read_next_update_workload_state()
+            .once()
+            .in_sequence(&mut seq)
+            .return_once(|| {
+                Ok(update_workload_state(
+                    WORKLOAD_NAME_1,
+                    ExecutionState::removed(),
+                ))
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd
+            .wait_for_workloads(
+                vec![WORKLOAD_NAME_1.to_owned(), WORKLOAD_NAME_2.to_owned()],
+                WaitState::Removed,
+                TIMEOUT,
+            )
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    // [utest->swdd~cli-waits-for-workload-states~1]
+    #[tokio::test]
+    async fn utest_wait_for_workloads_uses_missed_workload_states() {
+        let complete_state = complete_state_with_workload_states(vec![(
+            WORKLOAD_NAME_1,
+            ExecutionState::starting_triggered(),
+        )]);
+        let mut mock_server_connection = MockServerConnection::default();
+        mock_server_connection
+            .expect_get_complete_state()
+            .return_once(|_| Ok(complete_state.into()));
+        mock_server_connection
+            .expect_take_missed_from_server_messages()
+            .return_once(|| {
+                vec![FromServer::UpdateWorkloadState(update_workload_state(
+                    WORKLOAD_NAME_1,
+                    ExecutionState::running(),
+                ))]
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd
+            .wait_for_workloads(
+                vec![WORKLOAD_NAME_1.to_owned()],
+                WaitState::Running,
+                TIMEOUT,
+            )
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    // [utest->swdd~cli-wait-fails-on-failed-workload~1]
+    #[tokio::test]
+    async fn utest_wait_for_workloads_fails_on_failed_workload() {
+        let mut mock_server_connection = mock_server_connection_with_initial_states(vec![(
+            WORKLOAD_NAME_1,
+            ExecutionState::starting_triggered(),
+        )]);
+        mock_server_connection
+            .expect_read_next_update_workload_state()
+            .once()
+            .return_once(|| {
+                Ok(update_workload_state(
+                    WORKLOAD_NAME_1,
+                    ExecutionState::failed("exit code 1"),
+                ))
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd
+            .wait_for_workloads(
+                vec![WORKLOAD_NAME_1.to_owned()],
+                WaitState::Running,
+                TIMEOUT,
+            )
+            .await;
+
+        assert!(
+            matches!(result, Err(crate::cli_error::CliError::ExecutionError(msg)) if msg.starts_with("Workload 'workload_1' failed"))
+        );
+    }
+
+    // [utest->swdd~cli-wait-fails-on-timeout~1]
+    #[tokio::test]
+    async fn utest_wait_for_workloads_fails_on_timeout() {
+        let mut mock_server_connection = mock_server_connection_with_initial_states(vec![
+            (WORKLOAD_NAME_1, ExecutionState::running()),
+            (WORKLOAD_NAME_2, ExecutionState::starting_triggered()),
+        ]);
+        mock_server_connection
+            .expect_read_next_update_workload_state()
+            .returning(|| {
+                std::thread::sleep(Duration::from_millis(5));
+                Ok(update_workload_state(
+                    WORKLOAD_NAME_2,
+                    ExecutionState::starting_triggered(),
+                ))
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd
+            .wait_for_workloads(
+                vec![WORKLOAD_NAME_1.to_owned(), WORKLOAD_NAME_2.to_owned()],
+                WaitState::Running,
+                Duration::from_millis(10),
+            )
+            .await;
+
+        assert!(
+            matches!(result, Err(crate::cli_error::CliError::ExecutionError(msg)) if msg.contains("'workload_2' did not reach"))
+        );
+    }
+
+    #[tokio::test]
+    async fn utest_wait_for_workloads_fails_on_connection_error() {
+        let mut mock_server_connection = mock_server_connection_with_initial_states(vec![]);
+        mock_server_connection
+            .expect_read_next_update_workload_state()
+            .return_once(|| {
+                Err(ServerConnectionError::ExecutionError(
+                    "Connection to server interrupted".to_owned(),
+                ))
+            });
+
+        let mut cmd = CliCommands {
+            no_wait: false,
+            server_connection: mock_server_connection,
+        };
+
+        let result = cmd
+            .wait_for_workloads(
+                vec![WORKLOAD_NAME_1.to_owned()],
+                WaitState::Running,
+                TIMEOUT,
+            )
+            .await;
+
+        assert!(result.is_err());
+    }
+}
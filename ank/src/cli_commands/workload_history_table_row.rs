@@ -0,0 +1,34 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Serialize;
+use tabled::Tabled;
+
+#[derive(Debug, Tabled, Clone, Serialize)]
+#[tabled(rename_all = "UPPERCASE")]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadHistoryTableRow {
+    #[tabled(rename = "WORKLOAD NAME")]
+    pub name: String,
+    pub agent: String,
+    pub time: String,
+    #[tabled(rename = "EXECUTION STATE")]
+    pub execution_state: String,
+    #[tabled(rename = "ADDITIONAL INFO")]
+    pub additional_info: String,
+}
+
+impl WorkloadHistoryTableRow {
+    pub const ADDITIONAL_INFO_POS: usize = 4;
+}
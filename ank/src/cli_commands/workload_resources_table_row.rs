@@ -0,0 +1,27 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use tabled::Tabled;
+
+#[derive(Debug, Tabled, Clone)]
+#[tabled(rename_all = "UPPERCASE")]
+pub struct WorkloadResourcesTableRow {
+    #[tabled(rename = "WORKLOAD NAME")]
+    pub name: String,
+    pub agent: String,
+    #[tabled(rename = "CPU USAGE")]
+    pub cpu_usage: String,
+    #[tabled(rename = "MEMORY USAGE")]
+    pub memory_usage: String,
+}
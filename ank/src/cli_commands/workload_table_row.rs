@@ -12,10 +12,12 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+use serde::Serialize;
 use tabled::Tabled;
 
-#[derive(Debug, Tabled, Clone)]
+#[derive(Debug, Tabled, Clone, Serialize)]
 #[tabled(rename_all = "UPPERCASE")]
+#[serde(rename_all = "camelCase")]
 pub struct WorkloadTableRow {
     #[tabled(rename = "WORKLOAD NAME")]
     pub name: String,
@@ -32,6 +34,38 @@ impl WorkloadTableRow {
     pub const ADDITIONAL_INFO_POS: usize = 4;
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WideWorkloadTableRow {
+    #[serde(flatten)]
+    pub workload_table_row: WorkloadTableRow,
+    pub instance_id: String,
+}
+
+impl WideWorkloadTableRow {
+    // the additional info stays the last column to be wrapped to the terminal width
+    pub const ADDITIONAL_INFO_POS: usize = WorkloadTableRow::ADDITIONAL_INFO_POS + 1;
+}
+
+impl Tabled for WideWorkloadTableRow {
+    const LENGTH: usize = WorkloadTableRow::LENGTH + 1;
+
+    fn fields(&self) -> Vec<std::borrow::Cow<'_, str>> {
+        let mut fields = self.workload_table_row.fields();
+        fields.insert(
+            WorkloadTableRow::ADDITIONAL_INFO_POS,
+            self.instance_id.as_str().into(),
+        );
+        fields
+    }
+
+    fn headers() -> Vec<std::borrow::Cow<'static, str>> {
+        let mut headers = WorkloadTableRow::headers();
+        headers.insert(WorkloadTableRow::ADDITIONAL_INFO_POS, "INSTANCE ID".into());
+        headers
+    }
+}
+
 pub struct WorkloadTableRowWithSpinner<'a> {
     pub data: &'a WorkloadTableRow,
     pub spinner: &'a str,
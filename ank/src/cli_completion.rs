@@ -0,0 +1,415 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{ffi::OsStr, io::Write};
+
+use clap_complete::{env::Shells, CompletionCandidate, Shell};
+
+use crate::filtered_complete_state::FilteredCompleteState;
+
+const BIN_NAME: &str = "ank";
+const COMPLETE_ENV_KEY: &str = "COMPLETE";
+
+// The dynamic completions query the server with the CLI itself. If the server is not reachable,
+// no state is returned and only the static completions for commands and options remain.
+fn state_from_command(object_field_mask: &str) -> Vec<u8> {
+    std::process::Command::new("sh")
+        .arg("-c")
+        .arg(format!(
+            "{} get state -o json {}",
+            BIN_NAME, object_field_mask
+        ))
+        .output()
+        .map(|output| output.stdout)
+        .unwrap_or_default()
+}
+
+fn filter_candidates(candidates: Vec<String>, current: &OsStr) -> Vec<CompletionCandidate> {
+    let cur = current.to_str().unwrap_or("");
+    candidates
+        .into_iter()
+        .filter(|s| s.starts_with(cur))
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+fn completions_workloads(state: Vec<u8>, current: &OsStr) -> Vec<CompletionCandidate> {
+    let mut result = Vec::new();
+
+    let Ok(state) = serde_json::from_slice::<FilteredCompleteState>(&state) else {
+        return vec![];
+    };
+
+    if let Some(desired_state) = state.desired_state {
+        if let Some(workloads) = desired_state.workloads {
+            for workload_name in workloads.keys() {
+                result.push(workload_name.clone());
+            }
+        }
+    }
+
+    filter_candidates(result, current)
+}
+
+fn completions_configs(state: Vec<u8>, current: &OsStr) -> Vec<CompletionCandidate> {
+    let mut result = Vec::new();
+
+    let Ok(state) = serde_json::from_slice::<FilteredCompleteState>(&state) else {
+        return vec![];
+    };
+
+    if let Some(desired_state) = state.desired_state {
+        if let Some(configs) = desired_state.configs {
+            for config_name in configs.keys() {
+                result.push(config_name.clone());
+            }
+        }
+    }
+
+    filter_candidates(result, current)
+}
+
+fn completions_agents(state: Vec<u8>, current: &OsStr) -> Vec<CompletionCandidate> {
+    let mut result = Vec::new();
+
+    let Ok(state) = serde_json::from_slice::<FilteredCompleteState>(&state) else {
+        return vec![];
+    };
+
+    if let Some(agents) = state.agents.and_then(|agent_map| agent_map.agents) {
+        for agent_name in agents.keys() {
+            result.push(agent_name.clone());
+        }
+    }
+
+    filter_candidates(result, current)
+}
+
+fn completions_object_field_mask(state: Vec<u8>, current: &OsStr) -> Vec<CompletionCandidate> {
+    const DESIRED_STATE: &str = "desiredState";
+    const WORKLOADS: &str = "workloads";
+    const CONFIGS: &str = "configs";
+    const WORKLOAD_STATES: &str = "workloadStates";
+
+    let mut result = Vec::new();
+
+    let Ok(state) = serde_json::from_slice::<FilteredCompleteState>(&state) else {
+        return vec![];
+    };
+
+    if let Some(desired_state) = state.desired_state {
+        result.push(DESIRED_STATE.to_string());
+        if let Some(workloads) = desired_state.workloads {
+            result.push(format!("{}.{}", DESIRED_STATE, WORKLOADS));
+            for workload_name in workloads.keys() {
+                result.push(format!("{}.{}.{}", DESIRED_STATE, WORKLOADS, workload_name));
+            }
+        }
+        result.push(CONFIGS.to_string());
+        if let Some(configs) = desired_state.configs {
+            result.push(format!("{}.{}", DESIRED_STATE, CONFIGS));
+            for config_name in configs.keys() {
+                result.push(format!("{}.{}.{}", DESIRED_STATE, CONFIGS, config_name));
+            }
+        }
+    }
+
+    if let Some(workload_states) = state.workload_states {
+        result.push(WORKLOAD_STATES.to_string());
+        for (agent, workloads) in workload_states.into_iter() {
+            result.push(format!("{}.{}", WORKLOAD_STATES, agent));
+            for workload_name in workloads.keys() {
+                result.push(format!("{}.{}.{}", WORKLOAD_STATES, agent, workload_name));
+            }
+        }
+    }
+
+    filter_candidates(result, current)
+}
+
+// [impl->swdd~cli-shell-completion~1]
+pub fn workload_completer(current: &OsStr) -> Vec<CompletionCandidate> {
+    completions_workloads(state_from_command("desiredState.workloads"), current)
+}
+
+// [impl->swdd~cli-shell-completion~1]
+pub fn config_completer(current: &OsStr) -> Vec<CompletionCandidate> {
+    completions_configs(state_from_command("desiredState.configs"), current)
+}
+
+// [impl->swdd~cli-shell-completion~1]
+pub fn agent_completer(current: &OsStr) -> Vec<CompletionCandidate> {
+    completions_agents(state_from_command("agents"), current)
+}
+
+// [impl->swdd~cli-shell-completion~1]
+pub fn object_field_mask_completer(current: &OsStr) -> Vec<CompletionCandidate> {
+    completions_object_field_mask(state_from_command(""), current)
+}
+
+// [impl->swdd~cli-provides-completion-command~1]
+pub fn write_completion_script(shell: Shell, buf: &mut dyn Write) -> Result<(), String> {
+    let shell_name = shell.to_string();
+    let shells = Shells::builtins();
+    let completer = shells
+        .completer(&shell_name)
+        .ok_or_else(|| format!("Shell completion for '{}' is not supported.", shell_name))?;
+
+    completer
+        .write_registration(COMPLETE_ENV_KEY, BIN_NAME, BIN_NAME, BIN_NAME, buf)
+        .map_err(|err| format!("Could not write the completion script: '{}'", err))
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        completions_agents, completions_object_field_mask, completions_workloads,
+        write_completion_script,
+    };
+    use clap_complete::{CompletionCandidate, Shell};
+    use std::ffi::OsStr;
+
+    static WORKLOAD_STATE: &str = r#"
+        {
+          "desiredState": {
+            "apiVersion": "v0.1",
+            "workloads": {
+              "databroker": {
+                "agent": "agent_A",
+                "tags": [],
+                "dependencies": {},
+                "restartPolicy": "ALWAYS",
+                "runtime": "podman",
+                "runtimeConfig": "image: ghcr.io/eclipse/kuksa.val/databroker:0.4.1\ncommandArgs: [\"--insecure\"]\ncommandOptions: [\"--net=host\"]\n"
+              },
+              "speed-provider": {
+                  "agent": "agent_A",
+                  "tags": [],
+                  "dependencies": {
+                  "databroker": "ADD_COND_RUNNING"
+                  },
+                  "restartPolicy": "ALWAYS",
+                  "runtime": "podman",
+                  "runtimeConfig": "image: ghcr.io/eclipse-ankaios/speed-provider:0.1.1\ncommandOptions:\n  - \"--net=host\"\n  - \"-e\"\n  - \"SPEED_PROVIDER_MODE=auto\"\n"
+              }
+            }
+          }
+        }
+    "#;
+
+    // [utest->swdd~cli-shell-completion~1]
+    #[test]
+    fn utest_completions_workloads() {
+        let state = WORKLOAD_STATE.as_bytes();
+
+        let mut completions = completions_workloads(state.to_vec(), OsStr::new(""));
+        completions.sort();
+        assert_eq!(
+            completions,
+            vec![
+                CompletionCandidate::new("databroker"),
+                CompletionCandidate::new("speed-provider")
+            ],
+            "Completions do not match"
+        );
+    }
+
+    // [utest->swdd~cli-shell-completion~1]
+    #[test]
+    fn utest_completions_workloads_with_current() {
+        let state = WORKLOAD_STATE.as_bytes();
+
+        let mut completions = completions_workloads(state.to_vec(), OsStr::new("d"));
+        completions.sort();
+        assert_eq!(
+            completions,
+            vec![CompletionCandidate::new("databroker"),],
+            "Completions do not match"
+        );
+    }
+
+    // [utest->swdd~cli-shell-completion~1]
+    #[test]
+    fn utest_completions_workloads_invalid_input() {
+        let state = "".as_bytes();
+
+        let mut completions = completions_workloads(state.to_vec(), OsStr::new("d"));
+        completions.sort();
+        assert_eq!(completions, vec![], "Completions do not match");
+    }
+
+    static AGENTS_STATE: &str = r#"
+        {
+          "agents": {
+            "agent_A": {
+              "cpuUsage": 42,
+              "freeMemory": 42
+            },
+            "agent_B": {
+              "cpuUsage": 42,
+              "freeMemory": 42
+            },
+            "other_agent": {}
+          }
+        }
+    "#;
+
+    // [utest->swdd~cli-shell-completion~1]
+    #[test]
+    fn utest_completions_agents_with_current() {
+        let state = AGENTS_STATE.as_bytes();
+
+        let mut completions = completions_agents(state.to_vec(), OsStr::new("agent"));
+        completions.sort();
+        assert_eq!(
+            completions,
+            vec![
+                CompletionCandidate::new("agent_A"),
+                CompletionCandidate::new("agent_B")
+            ],
+            "Completions do not match"
+        );
+    }
+
+    // [utest->swdd~cli-shell-completion~1]
+    #[test]
+    fn utest_completions_agents_without_server_connection() {
+        let state = "".as_bytes();
+
+        let completions = completions_agents(state.to_vec(), OsStr::new(""));
+        assert_eq!(completions, vec![], "Completions do not match");
+    }
+
+    static OBJECT_FIELD_MASK_STATE: &str = r#"
+        {
+          "desiredState": {
+            "apiVersion": "v0.1",
+            "workloads": {
+              "databroker": {
+                "agent": "agent_A",
+                "tags": [],
+                "dependencies": {},
+                "restartPolicy": "ALWAYS",
+                "runtime": "podman",
+                "runtimeConfig": "image: ghcr.io/eclipse/kuksa.val/databroker:0.4.1\ncommandArgs: [\"--insecure\"]\ncommandOptions: [\"--net=host\"]\n"
+              },
+              "speed-provider": {
+                "agent": "agent_A",
+                "tags": [],
+                "dependencies": {
+                  "databroker": "ADD_COND_RUNNING"
+                },
+                "restartPolicy": "ALWAYS",
+                "runtime": "podman",
+                "runtimeConfig": "image: ghcr.io/eclipse-ankaios/speed-provider:0.1.1\ncommandOptions:\n  - \"--net=host\"\n  - \"-e\"\n  - \"SPEED_PROVIDER_MODE=auto\"\n"
+              }
+            }
+          },
+          "workloadStates": {
+            "agent_A": {
+              "databroker": {
+                "211c1e7c1170508711b76bb9be19ad73af7a2b11e3c2a4fb895d0ce5f4894eaa": {
+                  "state": "Running",
+                  "subState": "Ok",
+                  "additionalInfo": ""
+                }
+              },
+              "speed-provider": {
+                "4bc1b2047e6a67b60b7a6c3b07955a2f29040ab7a2b6bc7d1bee78efc81a48d9": {
+                  "state": "Running",
+                  "subState": "Ok",
+                  "additionalInfo": ""
+                }
+              }
+            }
+          }
+        }
+    "#;
+
+    // [utest->swdd~cli-shell-completion~1]
+    #[test]
+    fn utest_completions_object_field_mask() {
+        let state = OBJECT_FIELD_MASK_STATE.as_bytes();
+
+        let mut completions = completions_object_field_mask(state.to_vec(), OsStr::new(""));
+        completions.sort();
+        assert_eq!(
+            completions,
+            vec![
+                CompletionCandidate::new("configs"),
+                CompletionCandidate::new("desiredState"),
+                CompletionCandidate::new("desiredState.workloads"),
+                CompletionCandidate::new("desiredState.workloads.databroker"),
+                CompletionCandidate::new("desiredState.workloads.speed-provider"),
+                CompletionCandidate::new("workloadStates"),
+                CompletionCandidate::new("workloadStates.agent_A"),
+                CompletionCandidate::new("workloadStates.agent_A.databroker"),
+                CompletionCandidate::new("workloadStates.agent_A.speed-provider"),
+            ],
+            "Completions do not match"
+        );
+    }
+
+    // [utest->swdd~cli-shell-completion~1]
+    #[test]
+    fn utest_completions_object_field_mask_with_current() {
+        let state = OBJECT_FIELD_MASK_STATE.as_bytes();
+
+        let mut completions =
+            completions_object_field_mask(state.to_vec(), OsStr::new("workloadStates"));
+        completions.sort();
+        assert_eq!(
+            completions,
+            vec![
+                CompletionCandidate::new("workloadStates"),
+                CompletionCandidate::new("workloadStates.agent_A"),
+                CompletionCandidate::new("workloadStates.agent_A.databroker"),
+                CompletionCandidate::new("workloadStates.agent_A.speed-provider"),
+            ],
+            "Completions do not match"
+        );
+    }
+
+    // [utest->swdd~cli-shell-completion~1]
+    #[test]
+    fn utest_completions_object_field_mask_invalid_input() {
+        let state = "".as_bytes();
+
+        let mut completions =
+            completions_object_field_mask(state.to_vec(), OsStr::new("workloadStates"));
+        completions.sort();
+        assert_eq!(completions, vec![], "Completions do not match");
+    }
+
+    // [utest->swdd~cli-provides-completion-command~1]
+    #[test]
+    fn utest_write_completion_script_for_supported_shells() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let mut script = Vec::new();
+            assert!(write_completion_script(shell, &mut script).is_ok());
+
+            let script = String::from_utf8(script).unwrap();
+            assert!(script.contains("COMPLETE"), "Script for '{}'", shell);
+            assert!(script.contains("ank"), "Script for '{}'", shell);
+        }
+    }
+}
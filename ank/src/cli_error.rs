@@ -51,6 +51,12 @@ impl From<serde_json::Error> for CliError {
     }
 }
 
+impl From<std::io::Error> for CliError {
+    fn from(value: std::io::Error) -> Self {
+        CliError::ExecutionError(format!("Could not write output: '{value}'"))
+    }
+}
+
 impl From<server_connection::ServerConnectionError> for CliError {
     fn from(value: server_connection::ServerConnectionError) -> Self {
         match value {
@@ -0,0 +1,426 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use ank_agent::{AgentConfig, AgentConnection, AgentRunConfig, ShutdownPolicy};
+use ank_server::{ServerConfig, ServerRunConfig};
+use async_trait::async_trait;
+use common::{
+    communications_error::CommunicationMiddlewareError,
+    communications_server::{CommunicationsServer, ServerAddress},
+    from_server_interface::{FromServer, FromServerReceiver, FromServerSender},
+    loopback_communications::LoopbackCommunicationsServer,
+    request_id_prepending::detach_prefix_from_request_id,
+    to_server_interface::ToServerInterface,
+    CHANNEL_CAPACITY,
+};
+use grpc::server::GRPCCommunicationsServer;
+use tokio::{sync::mpsc::channel, task::JoinError};
+
+use crate::{cli::DevUpArgs, cli_error::CliError, output, output_debug};
+
+const DISCONNECT_THRESHOLD: Duration = Duration::from_secs(30);
+
+// [impl->swdd~cli-dev-mode-runs-server-and-agent-in-process~3]
+fn create_run_folder(run_folder: &str) -> Result<(), CliError> {
+    std::fs::create_dir_all(run_folder).map_err(|err| {
+        CliError::ExecutionError(format!(
+            "Could not create the run folder '{run_folder}': '{err}'"
+        ))
+    })
+}
+
+// [impl->swdd~cli-dev-mode-serves-cli-at-local-endpoint~1]
+fn check_cli_endpoint_address(address: &ServerAddress) -> Result<(), CliError> {
+    match address {
+        ServerAddress::Tcp(socket_address) if !socket_address.ip().is_loopback() => {
+            Err(CliError::ExecutionError(format!(
+                "The development mode serves the CLI without TLS and thus only at a loopback address, but '{socket_address}' was given."
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn cli_endpoint_url(address: &ServerAddress) -> String {
+    match address {
+        ServerAddress::Tcp(socket_address) => format!("http://{socket_address}"),
+        ServerAddress::UnixSocket(_) => address.to_string(),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Destination {
+    Agent,
+    CliEndpoint,
+    Both,
+}
+
+// The in-process agent is connected over the loopback middleware and all other connections, e.g.,
+// the ones of the CLI, over the gRPC middleware.
+// [impl->swdd~cli-dev-mode-serves-cli-at-local-endpoint~1]
+fn destination_of(agent_name: &str, message: &FromServer) -> Destination {
+    let destination_of_agent = |name: &Option<String>| {
+        if name.as_deref() == Some(agent_name) {
+            Destination::Agent
+        } else {
+            Destination::CliEndpoint
+        }
+    };
+
+    match message {
+        FromServer::ServerHello(server_hello) => destination_of_agent(&server_hello.agent_name),
+        FromServer::ExecRequest(request) => destination_of_agent(&request.agent_name),
+        FromServer::PortForwardRequest(request) => destination_of_agent(&request.agent_name),
+        FromServer::RestartWorkloadRequest(request) => destination_of_agent(&request.agent_name),
+        FromServer::PauseWorkloadRequest(request) => destination_of_agent(&request.agent_name),
+        FromServer::PullImages(pull_images) => destination_of_agent(&pull_images.agent_name),
+        FromServer::Response(response) => {
+            let (prefix, _) = detach_prefix_from_request_id(&response.request_id);
+            destination_of_agent(&Some(prefix))
+        }
+        // both middlewares distribute the workloads and workload states to their connections
+        FromServer::UpdateWorkload(_)
+        | FromServer::UpdateWorkloadState(_)
+        | FromServer::ServerConnectionLost(_)
+        | FromServer::Stop(_) => Destination::Both,
+    }
+}
+
+// [impl->swdd~cli-dev-mode-serves-cli-at-local-endpoint~1]
+async fn distribute_from_server_messages(
+    agent_name: String,
+    mut receiver: FromServerReceiver,
+    to_agent: FromServerSender,
+    to_cli_endpoint: FromServerSender,
+) {
+    while let Some(message) = receiver.recv().await {
+        let is_stop = matches!(message, FromServer::Stop(_));
+        // a middleware not receiving anymore has ended and its task reports why
+        match destination_of(&agent_name, &message) {
+            Destination::Agent => {
+                let _ = to_agent.send(message).await;
+            }
+            Destination::CliEndpoint => {
+                let _ = to_cli_endpoint.send(message).await;
+            }
+            Destination::Both => {
+                let _ = to_agent.send(message.clone()).await;
+                let _ = to_cli_endpoint.send(message).await;
+            }
+        }
+        if is_stop {
+            break;
+        }
+    }
+}
+
+// The communication middleware of the server in the development mode. The in-process agent is
+// connected over the loopback middleware and the CLI over the gRPC middleware.
+// [impl->swdd~cli-dev-mode-serves-cli-at-local-endpoint~1]
+struct DevCommunicationsServer {
+    agent_name: String,
+    loopback_server: LoopbackCommunicationsServer,
+    cli_endpoint: GRPCCommunicationsServer,
+}
+
+#[async_trait]
+impl CommunicationsServer for DevCommunicationsServer {
+    async fn start(
+        &mut self,
+        receiver: FromServerReceiver,
+        addr: ServerAddress,
+    ) -> Result<(), CommunicationMiddlewareError> {
+        let (to_loopback, loopback_receiver) = channel(CHANNEL_CAPACITY);
+        let (to_cli_endpoint, cli_endpoint_receiver) = channel(CHANNEL_CAPACITY);
+        tokio::spawn(distribute_from_server_messages(
+            self.agent_name.clone(),
+            receiver,
+            to_loopback,
+            to_cli_endpoint,
+        ));
+
+        tokio::try_join!(
+            self.loopback_server.start(loopback_receiver, addr.clone()),
+            self.cli_endpoint.start(cli_endpoint_receiver, addr)
+        )
+        .map(|_| ())
+    }
+}
+
+fn server_exited(result: Result<Result<(), String>, JoinError>) -> CliError {
+    match result {
+        Ok(Ok(())) => CliError::ExecutionError("The Ankaios server exited unexpectedly".to_owned()),
+        Ok(Err(err)) => CliError::ExecutionError(format!("The Ankaios server failed: '{err}'")),
+        Err(err) => CliError::ExecutionError(format!("The Ankaios server crashed: '{err}'")),
+    }
+}
+
+// [impl->swdd~cli-provides-dev-mode~2]
+// [impl->swdd~cli-dev-mode-runs-server-and-agent-in-process~3]
+pub async fn dev_up(args: &DevUpArgs) -> Result<(), CliError> {
+    check_cli_endpoint_address(&args.address)?;
+    create_run_folder(&args.run_folder)?;
+
+    let (to_server, server_receiver) = ank_server::create_to_server_channel(CHANNEL_CAPACITY);
+    let loopback_server = LoopbackCommunicationsServer::new(to_server.clone());
+    let communications_client = loopback_server.new_agent_communication(&args.agent_name);
+    // [impl->swdd~cli-dev-mode-serves-cli-at-local-endpoint~1]
+    let cli_endpoint = GRPCCommunicationsServer::new(to_server.clone(), None);
+    let server_config = ServerRunConfig {
+        startup_config: args.startup_config.clone(),
+        startup_manifest_dir: None,
+        address: args.address.clone(),
+        server_config: ServerConfig::default(),
+        standby: None,
+        state_replicator: None,
+        stream_errors: cli_endpoint.stream_error_counter(),
+        workload_states_resync_interval: None,
+        agent_lost_grace_period: None,
+        agent_request_timeout: None,
+    };
+    let communications_server = DevCommunicationsServer {
+        agent_name: args.agent_name.clone(),
+        loopback_server,
+        cli_endpoint,
+    };
+    let mut server_task = tokio::spawn(ank_server::run_server(
+        server_config,
+        communications_server,
+        (to_server.clone(), server_receiver),
+    ));
+
+    output!(
+        "Ankaios server with agent '{}' is up. Connect the CLI with '--insecure --server-url {}'. Send SIGHUP to reload the startup config. Press Ctrl+C to stop.",
+        args.agent_name,
+        cli_endpoint_url(&args.address)
+    );
+
+    let agent_config = AgentRunConfig {
+        agent_name: args.agent_name.clone(),
+        run_folder: args.run_folder.clone(),
+        agent_config: AgentConfig::default(),
+        // [impl->swdd~cli-dev-mode-shuts-down-gracefully~2]
+        shutdown_policy: ShutdownPolicy::StopWorkloads,
+        disconnect_threshold: DISCONNECT_THRESHOLD,
+        offline_start_delay: None,
+        eviction_memory_threshold: None,
+        image_gc_max_age: None,
+        image_gc_max_cache_size: None,
+        metrics_address: None,
+    };
+    let mut server_result = None;
+    let shutdown_signal = async {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {}
+            result = &mut server_task => server_result = Some(result),
+        }
+    };
+    // [impl->swdd~cli-dev-mode-shuts-down-gracefully~2]
+    let agent_result = ank_agent::run_agent(
+        agent_config,
+        AgentConnection::InProcess(Box::new(communications_client)),
+        shutdown_signal,
+    )
+    .await
+    .map_err(|err| CliError::ExecutionError(format!("The Ankaios agent failed: '{err}'")));
+
+    let server_result = match server_result {
+        Some(result) => Err(server_exited(result)),
+        None => {
+            output_debug!("Stopping the Ankaios server");
+            // the server could already be gone, its result tells in that case
+            let _ = to_server.stop().await;
+            match server_task.await {
+                Ok(Ok(())) => Ok(()),
+                result => Err(server_exited(result)),
+            }
+        }
+    };
+
+    server_result.and(agent_result)
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use super::{
+        check_cli_endpoint_address, create_run_folder, destination_of,
+        distribute_from_server_messages, server_exited, Destination,
+    };
+    use crate::cli_error::CliError;
+    use api::ank_base;
+    use common::{
+        commands,
+        from_server_interface::{FromServer, FromServerInterface},
+        CHANNEL_CAPACITY,
+    };
+    use tokio::sync::mpsc::channel;
+
+    const AGENT_NAME: &str = "agent_A";
+
+    fn response(request_id: &str) -> FromServer {
+        FromServer::Response(ank_base::Response {
+            request_id: request_id.to_owned(),
+            response_content: None,
+        })
+    }
+
+    // [utest->swdd~cli-dev-mode-runs-server-and-agent-in-process~3]
+    #[test]
+    fn utest_dev_mode_creates_run_folder() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let run_folder = tmp_dir.path().join("ankaios-dev").join("run");
+
+        create_run_folder(run_folder.to_str().unwrap()).unwrap();
+
+        assert!(run_folder.is_dir());
+    }
+
+    // [utest->swdd~cli-dev-mode-serves-cli-at-local-endpoint~1]
+    #[test]
+    fn utest_dev_mode_accepts_only_local_cli_endpoints() {
+        assert!(check_cli_endpoint_address(&"127.0.0.1:25551".parse().unwrap()).is_ok());
+        assert!(check_cli_endpoint_address(&"[::1]:25551".parse().unwrap()).is_ok());
+        assert!(check_cli_endpoint_address(
+            &"unix:///tmp/ankaios-dev/server.sock".parse().unwrap()
+        )
+        .is_ok());
+        assert_eq!(
+            check_cli_endpoint_address(&"0.0.0.0:25551".parse().unwrap()),
+            Err(CliError::ExecutionError(
+                "The development mode serves the CLI without TLS and thus only at a loopback address, but '0.0.0.0:25551' was given.".to_owned()
+            ))
+        );
+    }
+
+    // [utest->swdd~cli-dev-mode-serves-cli-at-local-endpoint~1]
+    #[test]
+    fn utest_dev_mode_sends_agent_messages_over_loopback() {
+        assert_eq!(
+            destination_of(
+                AGENT_NAME,
+                &FromServer::ServerHello(commands::ServerHello {
+                    agent_name: Some(AGENT_NAME.to_owned()),
+                    added_workloads: vec![],
+                })
+            ),
+            Destination::Agent
+        );
+        assert_eq!(
+            destination_of(
+                AGENT_NAME,
+                &FromServer::PullImages(commands::PullImages {
+                    agent_name: Some(AGENT_NAME.to_owned()),
+                    images: vec![],
+                })
+            ),
+            Destination::Agent
+        );
+        assert_eq!(
+            destination_of(AGENT_NAME, &response("agent_A@workload_1@request_1")),
+            Destination::Agent
+        );
+    }
+
+    // [utest->swdd~cli-dev-mode-serves-cli-at-local-endpoint~1]
+    #[test]
+    fn utest_dev_mode_sends_cli_messages_to_cli_endpoint() {
+        assert_eq!(
+            destination_of(AGENT_NAME, &response("cli-conn-1234@request_1")),
+            Destination::CliEndpoint
+        );
+        assert_eq!(
+            destination_of(
+                AGENT_NAME,
+                &FromServer::ServerHello(commands::ServerHello {
+                    agent_name: Some("agent_B".to_owned()),
+                    added_workloads: vec![],
+                })
+            ),
+            Destination::CliEndpoint
+        );
+    }
+
+    // [utest->swdd~cli-dev-mode-serves-cli-at-local-endpoint~1]
+    #[tokio::test]
+    async fn utest_dev_mode_distributes_workload_states_and_stop_to_both() {
+        let (to_agents, agents_receiver) = channel(CHANNEL_CAPACITY);
+        let (to_agent, mut agent_receiver) = channel(CHANNEL_CAPACITY);
+        let (to_cli_endpoint, mut cli_endpoint_receiver) = channel(CHANNEL_CAPACITY);
+        let distribution = tokio::spawn(distribute_from_server_messages(
+            AGENT_NAME.to_owned(),
+            agents_receiver,
+            to_agent,
+            to_cli_endpoint,
+        ));
+
+        to_agents.update_workload_state(vec![]).await.unwrap();
+        to_agents
+            .send(response("cli-conn-1234@request_1"))
+            .await
+            .unwrap();
+        to_agents.stop().await.unwrap();
+        distribution.await.unwrap();
+
+        let update_workload_state =
+            FromServer::UpdateWorkloadState(commands::UpdateWorkloadState {
+                workload_states: vec![],
+            });
+        let stop = FromServer::Stop(commands::Stop {});
+        assert_eq!(
+            agent_receiver.recv().await,
+            Some(update_workload_state.clone())
+        );
+        assert_eq!(agent_receiver.recv().await, Some(stop.clone()));
+        assert_eq!(agent_receiver.recv().await, None);
+        assert_eq!(
+            cli_endpoint_receiver.recv().await,
+            Some(update_workload_state)
+        );
+        assert_eq!(
+            cli_endpoint_receiver.recv().await,
+            Some(response("cli-conn-1234@request_1"))
+        );
+        assert_eq!(cli_endpoint_receiver.recv().await, Some(stop));
+        assert_eq!(cli_endpoint_receiver.recv().await, None);
+    }
+
+    // [utest->swdd~cli-provides-dev-mode~2]
+    #[test]
+    fn utest_dev_mode_reports_server_failure() {
+        assert_eq!(
+            server_exited(Ok(Err("invalid startup config".to_owned()))),
+            CliError::ExecutionError(
+                "The Ankaios server failed: 'invalid startup config'".to_owned()
+            )
+        );
+    }
+
+    // [utest->swdd~cli-provides-dev-mode~2]
+    #[test]
+    fn utest_dev_mode_reports_unexpected_server_exit() {
+        assert_eq!(
+            server_exited(Ok(Ok(()))),
+            CliError::ExecutionError("The Ankaios server exited unexpectedly".to_owned())
+        );
+    }
+}
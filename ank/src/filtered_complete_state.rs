@@ -18,7 +18,10 @@ use api::ank_base;
 use common::{
     helpers::serialize_to_ordered_map,
     objects::{
-        AddCondition, ConfigItem, ControlInterfaceAccess, RestartPolicy, Tag, WorkloadStatesMap,
+        AddCondition, ConfigItem, ControlInterfaceAccess, ExecutionState, ExtraHost, HealthCheck,
+        ImageStatesMap, LifecycleHooks, Network, Port, ResourceLimits, ResourceThresholds,
+        RestartBackoff, RestartPolicy, SecurityContext, Tag, Volume, WorkloadDefaults,
+        WorkloadResourcesMap, WorkloadStatesMap,
     },
 };
 use serde::{Deserialize, Serialize, Serializer};
@@ -51,6 +54,15 @@ pub struct FilteredCompleteState {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default, flatten)]
     pub agents: Option<FilteredAgentMap>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub workload_resources: Option<WorkloadResourcesMap>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, serialize_with = "serialize_option_to_ordered_map")]
+    pub workload_state_history: Option<HashMap<String, Vec<FilteredWorkloadStateTransition>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub image_states: Option<ImageStatesMap>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
@@ -64,6 +76,23 @@ pub struct FilteredState {
     #[serde(serialize_with = "serialize_option_to_ordered_map")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub configs: Option<HashMap<String, ConfigItem>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub defaults: Option<WorkloadDefaults>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, serialize_with = "serialize_option_to_ordered_map")]
+    pub namespaces: Option<HashMap<String, FilteredNamespace>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, serialize_with = "serialize_option_to_ordered_map")]
+    pub images: Option<HashMap<String, Vec<String>>>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilteredNamespace {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, serialize_with = "serialize_option_to_ordered_map")]
+    pub workloads: Option<HashMap<String, FilteredWorkloadSpec>>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
@@ -88,6 +117,52 @@ pub struct FilteredFreeMemory {
     pub free_memory: Option<u64>,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FilteredDiskUsage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_space: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_space: Option<u64>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FilteredNetworkInterfaceUsage {
+    pub name: String,
+    pub received_bytes: u64,
+    pub transmitted_bytes: u64,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FilteredNetworkUsage {
+    #[serde(default)]
+    pub interfaces: Vec<FilteredNetworkInterfaceUsage>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FilteredThermalZone {
+    pub zone_type: String,
+    pub temperature: i32,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FilteredTemperature {
+    #[serde(default)]
+    pub thermal_zones: Vec<FilteredThermalZone>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FilteredAgentLoadSample {
+    pub timestamp: u64,
+    pub cpu_usage: u32,
+    pub free_memory: u64,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FilteredAgentAttributes {
@@ -95,6 +170,26 @@ pub struct FilteredAgentAttributes {
     pub cpu_usage: Option<FilteredCpuUsage>,
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub free_memory: Option<FilteredFreeMemory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_usage: Option<FilteredDiskUsage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_usage: Option<FilteredNetworkUsage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<FilteredTemperature>,
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub labels: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub runtimes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connected_at: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub load_history: Vec<FilteredAgentLoadSample>,
 }
 
 impl FilteredAgentAttributes {
@@ -121,6 +216,57 @@ impl FilteredAgentAttributes {
             "".to_string()
         }
     }
+
+    pub fn get_disk_available_as_string(&self) -> String {
+        self.disk_usage
+            .as_ref()
+            .and_then(|disk_usage| disk_usage.available_space)
+            .map(|available_space| format!("{}B", available_space))
+            .unwrap_or_default()
+    }
+
+    pub fn get_network_received_as_string(&self) -> String {
+        self.network_usage
+            .as_ref()
+            .map(|network_usage| {
+                let received_bytes: u64 = network_usage
+                    .interfaces
+                    .iter()
+                    .map(|interface| interface.received_bytes)
+                    .sum();
+                format!("{}B", received_bytes)
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn get_network_transmitted_as_string(&self) -> String {
+        self.network_usage
+            .as_ref()
+            .map(|network_usage| {
+                let transmitted_bytes: u64 = network_usage
+                    .interfaces
+                    .iter()
+                    .map(|interface| interface.transmitted_bytes)
+                    .sum();
+                format!("{}B", transmitted_bytes)
+            })
+            .unwrap_or_default()
+    }
+
+    // The hottest thermal zone is shown as it is the relevant one for throttling.
+    pub fn get_temperature_as_string(&self) -> String {
+        self.temperature
+            .as_ref()
+            .and_then(|temperature| {
+                temperature
+                    .thermal_zones
+                    .iter()
+                    .map(|thermal_zone| thermal_zone.temperature)
+                    .max()
+            })
+            .map(|millidegree| format!("{:.1}°C", millidegree as f64 / 1000.0))
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
@@ -144,6 +290,56 @@ pub struct FilteredWorkloadSpec {
     #[serde(serialize_with = "serialize_option_to_ordered_map")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub configs: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_hosts: Option<Vec<ExtraHost>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ports: Option<Vec<Port>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_thresholds: Option<ResourceThresholds>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resources: Option<ResourceLimits>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheck>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restart_backoff: Option<RestartBackoff>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volumes: Option<Vec<Volume>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registry_auth: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replicas: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub networks: Option<Vec<Network>>,
+    #[serde(serialize_with = "serialize_option_to_ordered_map")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_agent_disconnect: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restart_on: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lifecycle_hooks: Option<LifecycleHooks>,
+    #[serde(serialize_with = "serialize_option_to_ordered_map")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_selector: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_debounce: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security_context: Option<SecurityContext>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilteredWorkloadStateTransition {
+    // milliseconds since the Unix epoch
+    pub timestamp: u64,
+    pub agent: String,
+    pub id: String,
+    pub execution_state: ExecutionState,
 }
 
 impl From<ank_base::CompleteState> for FilteredCompleteState {
@@ -152,6 +348,33 @@ impl From<ank_base::CompleteState> for FilteredCompleteState {
             desired_state: value.desired_state.map(Into::into),
             workload_states: value.workload_states.map(Into::into),
             agents: value.agents.map(Into::into),
+            workload_resources: value.workload_resources.map(Into::into),
+            workload_state_history: value.workload_state_history.map(|history| {
+                history
+                    .workloads
+                    .into_iter()
+                    .map(|(workload_name, workload_history)| {
+                        let transitions = workload_history
+                            .transitions
+                            .into_iter()
+                            .map(Into::into)
+                            .collect();
+                        (workload_name, transitions)
+                    })
+                    .collect()
+            }),
+            image_states: value.image_states.map(Into::into),
+        }
+    }
+}
+
+impl From<ank_base::WorkloadStateTransition> for FilteredWorkloadStateTransition {
+    fn from(value: ank_base::WorkloadStateTransition) -> Self {
+        FilteredWorkloadStateTransition {
+            timestamp: value.timestamp,
+            agent: value.agent,
+            id: value.id,
+            execution_state: value.execution_state.unwrap_or_default().into(),
         }
     }
 }
@@ -180,6 +403,36 @@ impl From<ank_base::State> for FilteredState {
                     })
                     .collect()
             }),
+            defaults: value.defaults.map(|x| {
+                (*x).try_into().unwrap_or_else(|error| {
+                    output_and_error!("Could not convert the WorkloadDefaults.\nError: '{error}'. Check the Ankaios component compatibility.")
+                })
+            }),
+            namespaces: value.namespaces.map(|x| {
+                x.namespaces
+                    .into_iter()
+                    .map(|(k, v)| (k, v.into()))
+                    .collect()
+            }),
+            images: value.images.map(|x| {
+                x.agent_images
+                    .into_iter()
+                    .map(|(agent_name, agent_images)| (agent_name, agent_images.images))
+                    .collect()
+            }),
+        }
+    }
+}
+
+impl From<ank_base::Namespace> for FilteredNamespace {
+    fn from(value: ank_base::Namespace) -> Self {
+        FilteredNamespace {
+            workloads: value.workloads.map(|x| {
+                x.workloads
+                    .into_iter()
+                    .map(|(k, v)| (k, v.into()))
+                    .collect()
+            }),
         }
     }
 }
@@ -216,7 +469,44 @@ impl From<ank_base::Workload> for FilteredWorkloadSpec {
                 .map(|x| x.try_into().unwrap_or_else(|error| {
                     output_and_error!("Could not convert the ControlInterfaceAccess.\nError: '{error}'. Check the Ankaios component compatibility.")
                 })),
-            configs: value.configs.map(|x| x.configs)
+            configs: value.configs.map(|x| x.configs),
+            hostname: value.hostname,
+            extra_hosts: value.extra_hosts.map(|x| map_vec(x.extra_hosts)),
+            ports: value.ports.map(|x| {
+                x.ports
+                    .into_iter()
+                    .map(|port| port.try_into().unwrap_or_else(|error| {
+                        output_and_error!("Could not convert the Port.\nError: '{error}'. Check the Ankaios component compatibility.")
+                    }))
+                    .collect()
+            }),
+            resource_thresholds: value.resource_thresholds.map(Into::into),
+            resources: value.resources.map(Into::into),
+            health_check: value.health_check.map(|health_check| {
+                health_check.try_into().unwrap_or_else(|error| {
+                    output_and_error!("Could not convert the HealthCheck.\nError: '{error}'. Check the Ankaios component compatibility.")
+                })
+            }),
+            restart_backoff: value.restart_backoff.map(Into::into),
+            priority: value.priority,
+            volumes: value.volumes.map(|x| {
+                x.volumes
+                    .into_iter()
+                    .map(|volume| volume.try_into().unwrap_or_else(|error| {
+                        output_and_error!("Could not convert the Volume.\nError: '{error}'. Check the Ankaios component compatibility.")
+                    }))
+                    .collect()
+            }),
+            registry_auth: value.registry_auth,
+            replicas: value.replicas,
+            networks: value.networks.map(|x| map_vec(x.networks)),
+            env: value.env.map(|x| x.env),
+            on_agent_disconnect: value.on_agent_disconnect,
+            restart_on: value.restart_on.map(|x| x.workloads),
+            lifecycle_hooks: value.lifecycle_hooks.map(Into::into),
+            node_selector: value.node_selector.map(|x| x.labels),
+            state_debounce: value.state_debounce,
+            security_context: value.security_context.map(Into::into),
         }
     }
 }
@@ -240,6 +530,22 @@ impl From<ank_base::AgentAttributes> for FilteredAgentAttributes {
         FilteredAgentAttributes {
             cpu_usage: value.cpu_usage.map(Into::into),
             free_memory: value.free_memory.map(Into::into),
+            disk_usage: value.disk_usage.map(Into::into),
+            network_usage: value.network_usage.map(Into::into),
+            temperature: value.temperature.map(Into::into),
+            labels: value.labels,
+            version: value.version,
+            runtimes: value.runtimes,
+            connected_at: Some(value.connected_at).filter(|connected_at| *connected_at != 0),
+            load_history: value
+                .load_history
+                .into_iter()
+                .map(|load_sample| FilteredAgentLoadSample {
+                    timestamp: load_sample.timestamp,
+                    cpu_usage: load_sample.cpu_usage,
+                    free_memory: load_sample.free_memory,
+                })
+                .collect(),
         }
     }
 }
@@ -259,3 +565,43 @@ impl From<ank_base::FreeMemory> for FilteredFreeMemory {
         }
     }
 }
+
+impl From<ank_base::DiskUsage> for FilteredDiskUsage {
+    fn from(value: ank_base::DiskUsage) -> Self {
+        FilteredDiskUsage {
+            total_space: Some(value.total_space),
+            available_space: Some(value.available_space),
+        }
+    }
+}
+
+impl From<ank_base::NetworkUsage> for FilteredNetworkUsage {
+    fn from(value: ank_base::NetworkUsage) -> Self {
+        FilteredNetworkUsage {
+            interfaces: value
+                .interfaces
+                .into_iter()
+                .map(|interface| FilteredNetworkInterfaceUsage {
+                    name: interface.name,
+                    received_bytes: interface.received_bytes,
+                    transmitted_bytes: interface.transmitted_bytes,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<ank_base::Temperature> for FilteredTemperature {
+    fn from(value: ank_base::Temperature) -> Self {
+        FilteredTemperature {
+            thermal_zones: value
+                .thermal_zones
+                .into_iter()
+                .map(|thermal_zone| FilteredThermalZone {
+                    zone_type: thermal_zone.zone_type,
+                    temperature: thermal_zone.temperature,
+                })
+                .collect(),
+        }
+    }
+}
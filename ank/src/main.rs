@@ -12,16 +12,22 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::env;
+use std::{env, io, time::Duration};
 
 mod cli;
 mod cli_commands;
-use cli_commands::CliCommands;
+mod cli_completion;
+use cli_commands::{output::ListOutputFormat, CliCommands};
+use common::objects::desired_state_path_of_namespace;
 use common::std_extensions::GracefulExitResult;
 use grpc::security::TLSConfig;
 mod cli_error;
+#[cfg(feature = "dev_mode")]
+mod dev_mode;
 mod filtered_complete_state;
 mod log;
+mod manifest_check;
+mod manifest_schema;
 
 #[cfg(test)]
 pub mod test_helper;
@@ -41,6 +47,30 @@ async fn main() {
         args
     );
 
+    // The completion script is generated locally and does not need a connection to the server
+    // [impl->swdd~cli-provides-completion-command~1]
+    if let cli::Commands::Completion(completion_args) = &args.command {
+        if let Err(err) =
+            cli_completion::write_completion_script(completion_args.shell, &mut io::stdout())
+        {
+            output_and_error!("{}", err);
+        }
+        return;
+    }
+
+    // The manifest schema is generated locally and does not need a connection to the server
+    // [impl->swdd~cli-provides-manifest-schema~1]
+    if let cli::Commands::Schema(schema_args) = &args.command {
+        if let Err(err) = manifest_schema::write_manifest_schema(
+            &schema_args.api_version,
+            schema_args.format,
+            &mut io::stdout(),
+        ) {
+            output_and_error!("{}", err);
+        }
+        return;
+    }
+
     let server_url = match args.insecure {
         true => args.server_url.replace("http[s]", "http"),
         false => args.server_url.replace("http[s]", "https"),
@@ -52,21 +82,60 @@ async fn main() {
         output_warn!("{}", err_message);
     }
 
+    // [impl->swdd~cli-supports-grpc-message-size-and-compression~1]
+    let connection_config = args.connection_config();
+
     // [impl->swdd~cli-provides-file-paths-to-communication-middleware~1]
     // [impl->swdd~cli-establishes-insecure-communication-based-on-provided-insecure-cli-argument~1]
     // [impl->swdd~cli-fails-on-missing-file-paths-and-insecure-cli-arguments~1]
     let tls_config = TLSConfig::new(args.insecure, args.ca_pem, args.crt_pem, args.key_pem);
 
+    // The development mode starts its own server and does not need a connection to an existing one
+    // [impl->swdd~cli-provides-dev-mode~2]
+    #[cfg(feature = "dev_mode")]
+    if let cli::Commands::Dev(dev_args) = &args.command {
+        match &dev_args.command {
+            Some(cli::DevCommands::Up(up_args)) => {
+                if let Err(err) = dev_mode::dev_up(up_args).await {
+                    output_and_error!("{}", err);
+                }
+            }
+            None => unreachable!("Unreachable code."),
+        }
+        return;
+    }
+
+    // The manifest check works offline and does not need a connection to the server
+    // [impl->swdd~cli-provides-offline-manifest-check~1]
+    if let cli::Commands::Check(check_args) = &args.command {
+        if let Err(err) = manifest_check::check_manifests(check_args) {
+            output_and_error!("{}", err);
+        }
+        return;
+    }
+
+    // [impl->swdd~cli-supports-per-command-response-timeout~2]
+    let response_timeout = args
+        .command
+        .timeout()
+        .unwrap_or(Duration::from_millis(args.response_timeout_ms));
+
     let mut cmd = CliCommands::init(
-        args.response_timeout_ms,
+        response_timeout,
         cli_name.to_string(),
         server_url,
         args.no_wait,
+        connection_config,
         // [impl->swdd~cli-fails-on-missing-file-paths-and-insecure-cli-arguments~1]
         tls_config.unwrap_or_exit_func(
             |err| output_and_error!("Missing certificate files: {}", err),
             -1,
         ),
+        // [impl->swdd~cli-supports-proxy-and-ca-bundle-arguments~1]
+        args.proxy_url,
+        args.ca_bundle,
+        // [impl->swdd~cli-traces-requests-with-debug-requests~1]
+        args.debug_requests,
     )
     .unwrap_or_else(|err| {
         output_and_error!("Cannot connect to server: '{}'", err);
@@ -77,16 +146,32 @@ async fn main() {
             // [impl->swdd~cli-provides-get-desired-state~1]
             // [impl->swdd~cli-provides-object-field-mask-arg-to-get-partial-desired-state~1]
             Some(cli::GetCommands::State {
-                object_field_mask,
+                mut object_field_mask,
                 output_format,
+                watch,
+                target_api_version,
+                namespace,
             }) => {
+                // [impl->swdd~cli-filters-by-namespace~1]
+                if let Some(namespace) = namespace {
+                    object_field_mask = vec![desired_state_path_of_namespace(&namespace)];
+                }
+                let writer = io::BufWriter::new(io::stdout());
                 // [impl->swdd~cli-provides-get-desired-state~1]
                 // [impl->swdd~cli-blocks-until-ankaios-server-responds-get-desired-state~1]
-                if let Ok(out_text) = cmd.get_state(object_field_mask, output_format).await {
-                    // [impl -> swdd~cli-returns-desired-state-from-server~1]
-                    output_and_exit!("{}", out_text);
+                // [impl -> swdd~cli-returns-desired-state-from-server~1]
+                // [impl->swdd~cli-watches-state~1]
+                // [impl->swdd~cli-outputs-state-in-target-api-version~1]
+                let target_api_version = target_api_version.as_deref();
+                let result = if watch {
+                    cmd.watch_state(object_field_mask, output_format, target_api_version, writer)
+                        .await
                 } else {
-                    output_and_error!("Could not retrieve state.");
+                    cmd.get_state(object_field_mask, output_format, target_api_version, writer)
+                        .await
+                };
+                if let Err(error) = result {
+                    output_and_error!("Could not retrieve state: '{}'", error);
                 }
             }
 
@@ -95,40 +180,152 @@ async fn main() {
                 workload_name,
                 agent_name,
                 state,
+                namespace,
+                selector,
+                history,
+                output_format,
             }) => {
                 output_debug!(
-                    "Received get workload with workload_name='{:?}', agent_name='{:?}', state='{:?}'",
+                    "Received get workload with workload_name='{:?}', agent_name='{:?}', state='{:?}', namespace='{:?}', selector='{:?}', history='{:?}', output_format='{:?}'",
                     workload_name,
                     agent_name,
                     state,
+                    namespace,
+                    selector,
+                    history,
+                    output_format,
                 );
 
-                match cmd
-                    .get_workloads_table(agent_name, state, workload_name)
+                let result = if history {
+                    // [impl->swdd~cli-provides-workload-state-history~1]
+                    cmd.get_workload_history_table(agent_name, workload_name, &output_format)
+                        .await
+                } else {
+                    cmd.get_workloads_table(
+                        agent_name,
+                        state,
+                        namespace,
+                        workload_name,
+                        selector,
+                        &output_format,
+                    )
                     .await
-                {
+                };
+                match result {
                     Ok(out_text) => output_and_exit!("{}", out_text),
                     Err(error) => output_and_error!("Failed to get workloads: '{}'", error),
                 }
             }
             // [impl->swdd~cli-provides-list-of-agents~1]
-            Some(cli::GetCommands::Agent {}) => {
-                output_debug!("Received get agent.");
+            Some(cli::GetCommands::Agent {
+                agent_name,
+                wide,
+                output_format,
+            }) => {
+                output_debug!(
+                    "Received get agent with agent_name = '{:?}', wide = '{}', output_format = '{:?}'.",
+                    agent_name,
+                    wide,
+                    output_format
+                );
+
+                let output_format = if wide {
+                    ListOutputFormat::Wide
+                } else {
+                    output_format
+                };
+                let result = match agent_name {
+                    // [impl->swdd~cli-provides-agent-details~1]
+                    Some(agent_name) => cmd.get_agent(&agent_name, &output_format).await,
+                    None => cmd.get_agents(&output_format).await,
+                };
 
-                match cmd.get_agents().await {
+                match result {
                     Ok(out_text) => output_and_exit!("{}", out_text),
                     Err(error) => output_and_error!("Failed to get agents: '{}'", error),
                 }
             }
             // [impl->swdd~cli-provides-list-of-configs~1]
-            Some(cli::GetCommands::Config {}) => {
-                output_debug!("Received get config.");
+            Some(cli::GetCommands::Config {
+                config_name,
+                rendered_for,
+                output_format,
+            }) => {
+                output_debug!(
+                    "Received get config with config_name = '{:?}', rendered_for = '{:?}', output_format = '{:?}'.",
+                    config_name,
+                    rendered_for,
+                    output_format
+                );
 
-                match cmd.get_configs().await {
+                let result = match (config_name, rendered_for) {
+                    // [impl->swdd~cli-provides-rendered-config-of-workload~2]
+                    (Some(config_name), Some(workload_name)) => {
+                        cmd.get_rendered_config(&config_name, &workload_name, &output_format)
+                            .await
+                    }
+                    // [impl->swdd~cli-provides-content-of-config~1]
+                    (Some(config_name), None) => cmd.get_config(&config_name, &output_format).await,
+                    _ => cmd.get_configs(&output_format).await,
+                };
+
+                match result {
                     Ok(out_text) => output_and_exit!("{}", out_text),
                     Err(error) => output_and_error!("Failed to get configs: '{}'", error),
                 }
             }
+            // [impl->swdd~cli-provides-list-of-images~1]
+            Some(cli::GetCommands::Image {
+                agent_name,
+                output_format,
+            }) => {
+                output_debug!(
+                    "Received get image with agent_name = '{:?}', output_format = '{:?}'.",
+                    agent_name,
+                    output_format
+                );
+
+                match cmd.get_images(agent_name, &output_format).await {
+                    Ok(out_text) => output_and_exit!("{}", out_text),
+                    Err(error) => output_and_error!("Failed to get images: '{}'", error),
+                }
+            }
+            // [impl->swdd~cli-provides-events~1]
+            Some(cli::GetCommands::Events {
+                follow,
+                output_format,
+            }) => {
+                output_debug!(
+                    "Received get events with follow='{}', output_format='{:?}'",
+                    follow,
+                    output_format
+                );
+
+                if let Err(error) = cmd
+                    .get_events(follow, output_format, io::BufWriter::new(io::stdout()))
+                    .await
+                {
+                    output_and_error!("Failed to get events: '{}'", error);
+                }
+            }
+            // [impl->swdd~cli-provides-audit-log~1]
+            Some(cli::GetCommands::Audit {
+                follow,
+                output_format,
+            }) => {
+                output_debug!(
+                    "Received get audit with follow='{}', output_format='{:?}'",
+                    follow,
+                    output_format
+                );
+
+                if let Err(error) = cmd
+                    .get_audit(follow, output_format, io::BufWriter::new(io::stdout()))
+                    .await
+                {
+                    output_and_error!("Failed to get audit log: '{}'", error);
+                }
+            }
             None => unreachable!("Unreachable code."),
         },
         cli::Commands::Set(set_args) => match set_args.command {
@@ -150,13 +347,64 @@ async fn main() {
             }
             None => unreachable!("Unreachable code."),
         },
+        cli::Commands::Edit(edit_args) => match edit_args.command {
+            // [impl->swdd~cli-provides-edit-state~1]
+            Some(cli::EditCommands::State { object_field_mask }) => {
+                output_debug!(
+                    "Received edit with object_field_mask='{:?}'",
+                    object_field_mask
+                );
+
+                if let Err(err) = cmd.edit_state(object_field_mask).await {
+                    output_and_error!("Failed to edit state: '{}'", err)
+                }
+            }
+            None => unreachable!("Unreachable code."),
+        },
+        // [impl->swdd~cli-provides-exec~1]
+        cli::Commands::Exec(exec_args) => {
+            output_debug!("Received exec with '{:?}'", exec_args);
+            match cmd.exec(exec_args.workload_name, exec_args.command).await {
+                Ok(exit_code) => {
+                    cmd.shut_down().await;
+                    // the exit code of the executed command is the exit code of the CLI
+                    std::process::exit(exit_code);
+                }
+                Err(err) => output_and_error!("Failed to execute the command: '{}'", err),
+            }
+        }
+        cli::Commands::PortForward(port_forward_args) => {
+            output_debug!("Received port-forward with '{:?}'", port_forward_args);
+            if let Err(err) = cmd
+                .port_forward(
+                    port_forward_args.workload_name,
+                    port_forward_args.ports.local_port,
+                    port_forward_args.ports.workload_port,
+                )
+                .await
+            {
+                output_and_error!("Failed to forward the port: '{}'", err);
+            }
+        }
         cli::Commands::Delete(delete_args) => match delete_args.command {
-            Some(cli::DeleteCommands::Workload { workload_name }) => {
+            Some(cli::DeleteCommands::Workload {
+                workload_name,
+                grace_period_secs,
+                selector,
+            }) => {
                 output_debug!(
-                    "Received delete workload with workload_name = '{:?}'",
-                    workload_name
+                    "Received delete workload with workload_name = '{:?}', grace_period_secs = '{:?}', selector = '{:?}'",
+                    workload_name,
+                    grace_period_secs,
+                    selector
                 );
-                if let Err(error) = cmd.delete_workloads(workload_name).await {
+                let result = if selector.is_empty() {
+                    cmd.delete_workloads(workload_name, grace_period_secs).await
+                } else {
+                    cmd.delete_selected_workloads(selector, grace_period_secs)
+                        .await
+                };
+                if let Err(error) = result {
                     output_and_error!("Failed to delete workloads: '{}'", error);
                 }
             }
@@ -203,11 +451,138 @@ async fn main() {
             }
             None => unreachable!("Unreachable code."),
         },
+        cli::Commands::Scale(scale_args) => match scale_args.command {
+            // [impl->swdd~cli-provides-scale-workload~1]
+            Some(cli::ScaleCommands::Workload {
+                workload_name,
+                replicas,
+            }) => {
+                output_debug!(
+                    "Received scale workload with workload_name='{:?}', replicas='{:?}'",
+                    workload_name,
+                    replicas
+                );
+                if let Err(error) = cmd.scale_workload(workload_name, replicas).await {
+                    output_and_error!("Failed to scale workload: '{}'", error);
+                }
+            }
+            None => unreachable!("Unreachable code."),
+        },
+        cli::Commands::Restart(restart_args) => match restart_args.command {
+            // [impl->swdd~cli-provides-restart-workload~1]
+            Some(cli::RestartCommands::Workload { workload_name }) => {
+                output_debug!(
+                    "Received restart workload with workload_name='{:?}'",
+                    workload_name
+                );
+                match cmd.restart_workload(workload_name).await {
+                    Ok(out_text) => output_and_exit!("{}", out_text),
+                    Err(error) => output_and_error!("Failed to restart workload: '{}'", error),
+                }
+            }
+            None => unreachable!("Unreachable code."),
+        },
+        cli::Commands::Pause(pause_args) => match pause_args.command {
+            // [impl->swdd~cli-provides-pause-workload~1]
+            Some(cli::PauseCommands::Workload { workload_name }) => {
+                output_debug!(
+                    "Received pause workload with workload_name='{:?}'",
+                    workload_name
+                );
+                match cmd.pause_workload(workload_name, false).await {
+                    Ok(out_text) => output_and_exit!("{}", out_text),
+                    Err(error) => output_and_error!("Failed to pause workload: '{}'", error),
+                }
+            }
+            None => unreachable!("Unreachable code."),
+        },
+        cli::Commands::Resume(resume_args) => match resume_args.command {
+            // [impl->swdd~cli-provides-pause-workload~1]
+            Some(cli::ResumeCommands::Workload { workload_name }) => {
+                output_debug!(
+                    "Received resume workload with workload_name='{:?}'",
+                    workload_name
+                );
+                match cmd.pause_workload(workload_name, true).await {
+                    Ok(out_text) => output_and_exit!("{}", out_text),
+                    Err(error) => output_and_error!("Failed to resume workload: '{}'", error),
+                }
+            }
+            None => unreachable!("Unreachable code."),
+        },
+        cli::Commands::Describe(describe_args) => match describe_args.command {
+            // [impl->swdd~cli-provides-describe-workload~1]
+            Some(cli::DescribeCommands::Workload { workload_name }) => {
+                output_debug!(
+                    "Received describe workload with workload_name='{:?}'",
+                    workload_name
+                );
+                match cmd.describe_workload(workload_name).await {
+                    Ok(out_text) => output_and_exit!("{}", out_text),
+                    Err(error) => output_and_error!("Failed to describe workload: '{}'", error),
+                }
+            }
+            None => unreachable!("Unreachable code."),
+        },
         cli::Commands::Apply(apply_args) => {
             if let Err(err) = cmd.apply_manifests(apply_args).await {
                 output_and_error!("{}", err);
             }
         }
+        // [impl->swdd~cli-diffs-manifests-against-desired-state~1]
+        cli::Commands::Diff(diff_args) => {
+            output_debug!("Received diff with '{:?}'", diff_args);
+            match cli_commands::get_input_sources(&diff_args.manifest_files) {
+                Ok(manifests) => {
+                    if let Err(err) = cmd
+                        .diff_manifests(
+                            manifests,
+                            &diff_args.agent_name,
+                            diff_args.output_format,
+                            io::BufWriter::new(io::stdout()),
+                        )
+                        .await
+                    {
+                        output_and_error!("Could not compute the diff: '{}'", err);
+                    }
+                }
+                Err(err) => output_and_error!("{}", err),
+            }
+        }
+        // [impl->swdd~cli-provides-dependency-graph~1]
+        cli::Commands::Graph(graph_args) => {
+            output_debug!("Received graph with output format '{:?}'", graph_args);
+            if let Err(err) = cmd
+                .get_dependency_graph(graph_args.output_format, io::BufWriter::new(io::stdout()))
+                .await
+            {
+                output_and_error!("Could not retrieve dependency graph: '{}'", err);
+            }
+        }
+        // [impl->swdd~cli-provides-workload-resources-as-table~1]
+        cli::Commands::Top(top_args) => {
+            output_debug!("Received top with '{:?}'", top_args);
+            if let Err(err) = cmd
+                .top(top_args.agent_name, top_args.interval, top_args.once)
+                .await
+            {
+                output_and_error!("Could not retrieve the workload resources: '{}'", err);
+            }
+        }
+        cli::Commands::Wait(wait_args) => {
+            output_debug!("Received wait with '{:?}'", wait_args);
+            if let Err(err) = cmd
+                .wait_for_workloads(wait_args.workload_name, wait_args.state, wait_args.timeout)
+                .await
+            {
+                output_and_error!("Waiting for the workloads failed: '{}'", err);
+            }
+        }
+        #[cfg(feature = "dev_mode")]
+        cli::Commands::Dev(_) => unreachable!("Handled before connecting to the server."),
+        cli::Commands::Check(_) | cli::Commands::Completion(_) | cli::Commands::Schema(_) => {
+            unreachable!("Handled before connecting to the server.")
+        }
     }
     cmd.shut_down().await;
 }
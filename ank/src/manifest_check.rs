@@ -0,0 +1,621 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Read;
+
+use common::{
+    cycle_check,
+    objects::{AddCondition, State, StoredWorkloadSpec, WorkloadSpec, STR_RE_CONFIG_REFERENCES},
+};
+use regex::Regex;
+use serde::Serialize;
+use serde_yaml::Value;
+
+use crate::{
+    cli::{CheckArgs, CheckOutputFormat},
+    cli_error::CliError,
+    output,
+};
+
+#[cfg(not(test))]
+use crate::cli_commands::get_input_sources;
+#[cfg(test)]
+use tests::get_input_sources_mock as get_input_sources;
+
+const API_VERSION: &str = "apiVersion";
+const AGENT: &str = "agent";
+const WORKLOADS: &str = "workloads";
+const CONFIGS: &str = "configs";
+const DEFAULTS: &str = "defaults";
+const DEPENDENCIES: &str = "dependencies";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+struct ManifestChecker<'a> {
+    file: &'a str,
+    content: &'a str,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> ManifestChecker<'a> {
+    fn report(&mut self, severity: Severity, line: Option<usize>, message: String) {
+        self.diagnostics.push(Diagnostic {
+            file: self.file.to_owned(),
+            line,
+            severity,
+            message,
+        });
+    }
+
+    fn report_at(&mut self, severity: Severity, path: &[&str], message: String) {
+        let line = find_line(self.content, path);
+        self.report(severity, line, message);
+    }
+
+    fn check(mut self, agent_name: Option<&str>) -> Vec<Diagnostic> {
+        // [impl->swdd~cli-check-validates-manifest-format~1]
+        let mut manifest = match serde_yaml::from_str::<Value>(self.content) {
+            Ok(manifest) => manifest,
+            Err(err) => {
+                let line = err.location().map(|location| location.line());
+                self.report(Severity::Error, line, format!("Invalid YAML: {err}"));
+                return self.diagnostics;
+            }
+        };
+        // Only the parser of the manifest text provides the line numbers of invalid fields
+        let parsed_state = match agent_name {
+            Some(agent_name) => {
+                set_agent_name(&mut manifest, agent_name);
+                serde_yaml::from_value::<State>(manifest)
+            }
+            None => serde_yaml::from_str::<State>(self.content),
+        };
+        let mut state = match parsed_state {
+            Ok(state) => state,
+            Err(err) => {
+                let line = err.location().map(|location| location.line());
+                self.report(Severity::Error, line, format!("Invalid manifest: {err}"));
+                return self.diagnostics;
+            }
+        };
+
+        // [impl->swdd~cli-check-validates-manifest-content~1]
+        if let Err(err) = State::verify_api_version(&state) {
+            self.report_at(Severity::Error, &[API_VERSION], err);
+        }
+        if let Err(err) = state.apply_workload_defaults() {
+            self.report_at(Severity::Error, &[DEFAULTS], err);
+        }
+        self.check_configs(&state);
+
+        let mut workload_names: Vec<&String> = state.workloads.keys().collect();
+        workload_names.sort();
+        for workload_name in workload_names {
+            self.check_workload(workload_name, &state.workloads[workload_name], &state);
+        }
+
+        if let Some(workload_name) = cycle_check::dfs(&state, None) {
+            self.report_at(
+                Severity::Error,
+                &[WORKLOADS, &workload_name, DEPENDENCIES],
+                format!(
+                    "The workload '{}' is part of a cycle in the inter-workload dependencies",
+                    workload_name
+                ),
+            );
+        }
+
+        self.diagnostics
+    }
+
+    fn check_configs(&mut self, state: &State) {
+        let re_config_items = Regex::new(STR_RE_CONFIG_REFERENCES).unwrap();
+        let mut config_keys: Vec<&String> = state.configs.keys().collect();
+        config_keys.sort();
+        for config_key in config_keys {
+            if !re_config_items.is_match(config_key) {
+                self.report_at(
+                    Severity::Error,
+                    &[CONFIGS, config_key],
+                    format!(
+                        "Unsupported config item key. Received '{}', expected to have characters in {}",
+                        config_key, STR_RE_CONFIG_REFERENCES
+                    ),
+                );
+            }
+        }
+    }
+
+    fn check_workload(
+        &mut self,
+        workload_name: &str,
+        workload: &StoredWorkloadSpec,
+        state: &State,
+    ) {
+        if let Err(err) = WorkloadSpec::verify_fields_format(&WorkloadSpec::from((
+            workload_name.to_owned(),
+            workload.clone(),
+        ))) {
+            self.report_at(Severity::Error, &[WORKLOADS, workload_name], err);
+        }
+
+        if let Err(err) = StoredWorkloadSpec::verify_config_reference_format(&workload.configs) {
+            self.report_at(Severity::Error, &[WORKLOADS, workload_name, CONFIGS], err);
+        }
+        let mut config_references: Vec<(&String, &String)> = workload.configs.iter().collect();
+        config_references.sort();
+        for (config_alias, config_key) in config_references {
+            if !state.configs.contains_key(config_key) {
+                self.report_at(
+                    Severity::Warning,
+                    &[WORKLOADS, workload_name, CONFIGS, config_alias],
+                    format!(
+                        "The config '{}' referenced by the workload '{}' is not part of the manifest and must already exist in the Ankaios system",
+                        config_key, workload_name
+                    ),
+                );
+            }
+        }
+
+        let mut dependencies: Vec<(&String, &AddCondition)> =
+            workload.dependencies.iter().collect();
+        dependencies.sort_by_key(|(dependency, _)| *dependency);
+        for (dependency, add_condition) in dependencies {
+            if add_condition.refers_to_config() {
+                if !state.configs.contains_key(dependency) {
+                    self.report_at(
+                        Severity::Warning,
+                        &[WORKLOADS, workload_name, DEPENDENCIES, dependency],
+                        format!(
+                            "The config dependency '{}' of the workload '{}' is not part of the manifest and must already exist in the Ankaios system",
+                            dependency, workload_name
+                        ),
+                    );
+                }
+            } else if !state.workloads.contains_key(dependency) {
+                self.report_at(
+                    Severity::Warning,
+                    &[WORKLOADS, workload_name, DEPENDENCIES, dependency],
+                    format!(
+                        "The dependency '{}' of the workload '{}' is not part of the manifest and must already exist in the Ankaios system",
+                        dependency, workload_name
+                    ),
+                );
+            }
+        }
+    }
+}
+
+// The agent name provided via '--agent' overwrites the agent names of all workloads as in 'ank apply'
+fn set_agent_name(manifest: &mut Value, agent_name: &str) {
+    if let Some(workloads) = manifest.get_mut(WORKLOADS).and_then(Value::as_mapping_mut) {
+        for workload in workloads.values_mut().filter_map(Value::as_mapping_mut) {
+            workload.insert(Value::from(AGENT), Value::from(agent_name));
+        }
+    }
+}
+
+// Returns the line number of the key at the given path or of its deepest existing parent.
+// The YAML parser does not provide the positions of the parsed values, thus the keys are searched
+// by their indentation, which is sufficient for the block style used in Ankaios manifests.
+fn find_line(content: &str, path: &[&str]) -> Option<usize> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut start = 0;
+    let mut parent_indent: Option<usize> = None;
+    let mut line_number = None;
+    for key in path {
+        let mut found = None;
+        let mut child_indent = None;
+        for (index, line) in lines.iter().enumerate().skip(start) {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let indent = line.len() - trimmed.len();
+            if parent_indent.is_some_and(|parent_indent| indent <= parent_indent) {
+                break;
+            }
+            // Only the direct children of the parent are considered
+            if *child_indent.get_or_insert(indent) != indent {
+                continue;
+            }
+            let name = trimmed
+                .split_once(':')
+                .map(|(name, _)| name.trim().trim_matches(|c| c == '"' || c == '\''));
+            if name == Some(key) {
+                found = Some((index, indent));
+                break;
+            }
+        }
+        let Some((index, indent)) = found else {
+            break;
+        };
+        start = index + 1;
+        parent_indent = Some(indent);
+        line_number = Some(index + 1);
+    }
+    line_number
+}
+
+// [impl->swdd~cli-check-validates-manifest-format~1]
+// [impl->swdd~cli-check-validates-manifest-content~1]
+pub fn check_manifest(file: &str, content: &str, agent_name: Option<&str>) -> Vec<Diagnostic> {
+    ManifestChecker {
+        file,
+        content,
+        diagnostics: Vec::new(),
+    }
+    .check(agent_name)
+}
+
+// [impl->swdd~cli-check-outputs-diagnostics~1]
+fn format_diagnostics(
+    diagnostics: &[Diagnostic],
+    output_format: CheckOutputFormat,
+) -> Result<String, CliError> {
+    match output_format {
+        CheckOutputFormat::Json => Ok(serde_json::to_string_pretty(diagnostics)?),
+        CheckOutputFormat::Text if diagnostics.is_empty() => Ok("No problems found.".to_owned()),
+        CheckOutputFormat::Text => Ok(diagnostics
+            .iter()
+            .map(|diagnostic| {
+                let location = match diagnostic.line {
+                    Some(line) => format!("{}:{}", diagnostic.file, line),
+                    None => diagnostic.file.clone(),
+                };
+                let severity = match diagnostic.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                };
+                format!("{location}: {severity}: {}", diagnostic.message)
+            })
+            .collect::<Vec<String>>()
+            .join("\n")),
+    }
+}
+
+// [impl->swdd~cli-provides-offline-manifest-check~1]
+pub fn check_manifests(args: &CheckArgs) -> Result<(), CliError> {
+    let input_sources =
+        get_input_sources(&args.manifest_files).map_err(CliError::ExecutionError)?;
+
+    let mut diagnostics = Vec::new();
+    for (file, mut reader) in input_sources {
+        let mut content = String::new();
+        reader.read_to_string(&mut content).map_err(|err| {
+            CliError::ExecutionError(format!("Could not read '{}': '{}'", file, err))
+        })?;
+        diagnostics.extend(check_manifest(&file, &content, args.agent_name.as_deref()));
+    }
+
+    output!("{}", format_diagnostics(&diagnostics, args.output_format)?);
+
+    let error_count = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Error)
+        .count();
+    if error_count > 0 {
+        return Err(CliError::ExecutionError(format!(
+            "Found {} error(s) in the manifest(s)",
+            error_count
+        )));
+    }
+    Ok(())
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::{
+        check_manifest, check_manifests, find_line, format_diagnostics, Diagnostic, Severity,
+    };
+    use crate::{
+        cli::{CheckArgs, CheckOutputFormat},
+        cli_commands::InputSourcePair,
+        cli_error::CliError,
+    };
+
+    const FILE: &str = "manifest.yaml";
+    const MANIFEST: &str = r#"apiVersion: v0.1
+workloads:
+  nginx:
+    runtime: podman
+    agent: agent_A
+    configs:
+      port: web_server_port
+    dependencies:
+      database: ADD_COND_RUNNING
+    runtimeConfig: |
+      image: docker.io/nginx:latest
+  database:
+    runtime: podman
+    agent: agent_A
+    runtimeConfig: |
+      image: docker.io/postgres:latest
+configs:
+  web_server_port: "8081"
+"#;
+
+    pub fn get_input_sources_mock(
+        manifest_files: &[String],
+    ) -> Result<Vec<InputSourcePair>, String> {
+        Ok(manifest_files
+            .iter()
+            .map(|file| {
+                (
+                    file.clone(),
+                    Box::new(io::Cursor::new(file.clone().into_bytes()))
+                        as Box<dyn io::Read + Send + Sync>,
+                )
+            })
+            .collect())
+    }
+
+    fn error(line: usize, message: &str) -> Diagnostic {
+        Diagnostic {
+            file: FILE.to_owned(),
+            line: Some(line),
+            severity: Severity::Error,
+            message: message.to_owned(),
+        }
+    }
+
+    // [utest->swdd~cli-check-validates-manifest-content~1]
+    #[test]
+    fn utest_check_manifest_valid() {
+        assert!(check_manifest(FILE, MANIFEST, None).is_empty());
+    }
+
+    // [utest->swdd~cli-check-validates-manifest-format~1]
+    #[test]
+    fn utest_check_manifest_invalid_yaml() {
+        let diagnostics =
+            check_manifest(FILE, "apiVersion: v0.1\nworkloads:\n  - a\n b: c\n", None);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].line, Some(4));
+        assert!(diagnostics[0].message.starts_with("Invalid YAML"));
+    }
+
+    // [utest->swdd~cli-check-validates-manifest-format~1]
+    #[test]
+    fn utest_check_manifest_invalid_field_type() {
+        let manifest = MANIFEST.replace(
+            "    agent: agent_A\n    configs",
+            "    agent: [agent_A]\n    configs",
+        );
+
+        let diagnostics = check_manifest(FILE, &manifest, None);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, Some(5));
+        assert!(diagnostics[0].message.starts_with("Invalid manifest"));
+    }
+
+    // [utest->swdd~cli-check-validates-manifest-content~1]
+    #[test]
+    fn utest_check_manifest_unsupported_api_version() {
+        let manifest = MANIFEST.replace("v0.1", "v0.0");
+
+        assert_eq!(
+            check_manifest(FILE, &manifest, None),
+            vec![error(
+                1,
+                "Unsupported API version. Received 'v0.0', expected one of 'v0.1', 'v0.2'"
+            )]
+        );
+    }
+
+    // [utest->swdd~cli-check-validates-manifest-content~1]
+    #[test]
+    fn utest_check_manifest_invalid_workload_name() {
+        let manifest = MANIFEST
+            .replace("  database:", "  data.base:")
+            .replace("database: ADD_COND", "data.base: ADD_COND");
+
+        let diagnostics = check_manifest(FILE, &manifest, None);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, Some(12));
+        assert!(diagnostics[0]
+            .message
+            .starts_with("Unsupported workload name. Received 'data.base'"));
+    }
+
+    // [utest->swdd~cli-check-validates-manifest-format~1]
+    #[test]
+    fn utest_check_manifest_missing_agent() {
+        let manifest = MANIFEST.replace("    agent: agent_A\n    configs", "    configs");
+
+        assert_eq!(
+            check_manifest(FILE, &manifest, None),
+            vec![error(
+                4,
+                "Invalid manifest: workloads.nginx: missing field `agent` at line 4 column 5"
+            )]
+        );
+        assert!(check_manifest(FILE, &manifest, Some("agent_B")).is_empty());
+    }
+
+    // [utest->swdd~cli-check-validates-manifest-content~1]
+    #[test]
+    fn utest_check_manifest_dependency_cycle() {
+        let manifest = MANIFEST.replace(
+            "    agent: agent_A\n    runtimeConfig: |\n      image: docker.io/postgres",
+            "    agent: agent_A\n    dependencies:\n      nginx: ADD_COND_RUNNING\n    runtimeConfig: |\n      image: docker.io/postgres",
+        );
+
+        let diagnostics = check_manifest(FILE, &manifest, None);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert!(diagnostics[0].message.contains("cycle"));
+        assert!(diagnostics[0].line == Some(8) || diagnostics[0].line == Some(15));
+    }
+
+    // [utest->swdd~cli-check-validates-manifest-content~1]
+    #[test]
+    fn utest_check_manifest_unresolved_references_are_warnings() {
+        let manifest = MANIFEST
+            .replace("configs:\n  web_server_port: \"8081\"\n", "")
+            .replace("database: ADD_COND_RUNNING", "other: ADD_COND_RUNNING");
+
+        assert_eq!(
+            check_manifest(FILE, &manifest, None),
+            vec![
+                Diagnostic {
+                    file: FILE.to_owned(),
+                    line: Some(7),
+                    severity: Severity::Warning,
+                    message: "The config 'web_server_port' referenced by the workload 'nginx' is not part of the manifest and must already exist in the Ankaios system".to_owned(),
+                },
+                Diagnostic {
+                    file: FILE.to_owned(),
+                    line: Some(9),
+                    severity: Severity::Warning,
+                    message: "The dependency 'other' of the workload 'nginx' is not part of the manifest and must already exist in the Ankaios system".to_owned(),
+                },
+            ]
+        );
+    }
+
+    // [utest->swdd~cli-check-validates-manifest-content~1]
+    #[test]
+    fn utest_check_manifest_config_dependency() {
+        let manifest = MANIFEST.replace(
+            "database: ADD_COND_RUNNING",
+            "web_server_port: ADD_COND_CONFIG_CHANGED",
+        );
+
+        assert!(check_manifest(FILE, &manifest, None).is_empty());
+
+        let manifest = manifest.replace("configs:\n  web_server_port: \"8081\"\n", "");
+
+        let diagnostics = check_manifest(FILE, &manifest, None);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[1].message,
+            "The config dependency 'web_server_port' of the workload 'nginx' is not part of the manifest and must already exist in the Ankaios system"
+        );
+    }
+
+    #[test]
+    fn utest_find_line_uses_indentation() {
+        assert_eq!(find_line(MANIFEST, &["workloads", "database"]), Some(12));
+        assert_eq!(
+            find_line(
+                MANIFEST,
+                &["workloads", "nginx", "dependencies", "database"]
+            ),
+            Some(9)
+        );
+        assert_eq!(
+            find_line(MANIFEST, &["configs", "web_server_port"]),
+            Some(18)
+        );
+        assert_eq!(
+            find_line(MANIFEST, &["workloads", "nginx", "tags"]),
+            Some(3)
+        );
+        assert_eq!(find_line(MANIFEST, &["defaults"]), None);
+    }
+
+    // [utest->swdd~cli-check-outputs-diagnostics~1]
+    #[test]
+    fn utest_format_diagnostics() {
+        let diagnostics = vec![
+            error(3, "first problem"),
+            Diagnostic {
+                file: FILE.to_owned(),
+                line: None,
+                severity: Severity::Warning,
+                message: "second problem".to_owned(),
+            },
+        ];
+
+        assert_eq!(
+            format_diagnostics(&diagnostics, CheckOutputFormat::Text),
+            Ok(
+                "manifest.yaml:3: error: first problem\nmanifest.yaml: warning: second problem"
+                    .to_owned()
+            )
+        );
+        assert_eq!(
+            format_diagnostics(&[], CheckOutputFormat::Text),
+            Ok("No problems found.".to_owned())
+        );
+
+        let json: serde_json::Value = serde_json::from_str(
+            &format_diagnostics(&diagnostics, CheckOutputFormat::Json).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"file": FILE, "line": 3, "severity": "error", "message": "first problem"},
+                {"file": FILE, "severity": "warning", "message": "second problem"}
+            ])
+        );
+    }
+
+    // [utest->swdd~cli-provides-offline-manifest-check~1]
+    #[test]
+    fn utest_check_manifests_fails_on_errors() {
+        // The mocked input sources provide the file name as content
+        let args = CheckArgs {
+            manifest_files: vec!["apiVersion: v0.0".to_owned()],
+            agent_name: None,
+            output_format: CheckOutputFormat::Json,
+        };
+
+        assert_eq!(
+            check_manifests(&args),
+            Err(CliError::ExecutionError(
+                "Found 1 error(s) in the manifest(s)".to_owned()
+            ))
+        );
+
+        let args = CheckArgs {
+            manifest_files: vec!["apiVersion: v0.1".to_owned()],
+            agent_name: None,
+            output_format: CheckOutputFormat::Json,
+        };
+        assert_eq!(check_manifests(&args), Ok(()));
+    }
+}
@@ -0,0 +1,609 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Write;
+
+use common::objects::{
+    API_VERSION_V0_1, API_VERSION_V0_1_STATE_FIELDS, API_VERSION_V0_1_WORKLOAD_FIELDS,
+    STR_RE_ENV_VAR_NAME, STR_RE_NAMESPACE, SUPPORTED_API_VERSIONS,
+};
+use serde_json::{json, Value};
+
+use crate::cli::SchemaFormat;
+
+const JSON_SCHEMA_DIALECT: &str = "http://json-schema.org/draft-07/schema#";
+const NAME_PATTERN: &str = "^[a-zA-Z0-9_-]+$";
+
+// [impl->swdd~cli-provides-manifest-schema~1]
+pub fn write_manifest_schema(
+    api_version: &str,
+    format: SchemaFormat,
+    buf: &mut dyn Write,
+) -> Result<(), String> {
+    let schema = manifest_schema(api_version)?;
+    let serialized_schema = match format {
+        SchemaFormat::Json => serde_json::to_string_pretty(&schema).map_err(|err| err.to_string()),
+        SchemaFormat::Yaml => serde_yaml::to_string(&schema).map_err(|err| err.to_string()),
+    }
+    .map_err(|err| format!("Could not serialize the manifest schema: '{}'", err))?;
+
+    writeln!(buf, "{}", serialized_schema.trim_end())
+        .map_err(|err| format!("Could not write the manifest schema: '{}'", err))
+}
+
+// [impl->swdd~cli-provides-manifest-schema~1]
+fn manifest_schema(api_version: &str) -> Result<Value, String> {
+    if !SUPPORTED_API_VERSIONS.contains(&api_version) {
+        return Err(format!(
+            "Unsupported API version '{}'. Supported API versions: '{}'",
+            api_version,
+            SUPPORTED_API_VERSIONS.join("', '")
+        ));
+    }
+
+    let mut schema = json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": "Ankaios manifest",
+        "type": "object",
+        "required": ["apiVersion"],
+        "additionalProperties": false,
+        "properties": {
+            "apiVersion": { "const": api_version },
+            "workloads": {
+                "type": "object",
+                "propertyNames": { "pattern": NAME_PATTERN },
+                "additionalProperties": { "$ref": "#/definitions/workload" }
+            },
+            "configs": {
+                "type": "object",
+                "propertyNames": { "pattern": NAME_PATTERN },
+                "additionalProperties": { "$ref": "#/definitions/configItem" }
+            },
+            "defaults": { "$ref": "#/definitions/workloadDefaults" },
+            "namespaces": {
+                "type": "object",
+                "propertyNames": { "pattern": STR_RE_NAMESPACE },
+                "additionalProperties": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "workloads": {
+                            "type": "object",
+                            "propertyNames": { "pattern": NAME_PATTERN },
+                            "additionalProperties": { "$ref": "#/definitions/workload" }
+                        }
+                    }
+                }
+            },
+            "images": {
+                "description": "Images pulled ahead of their use and kept by the agents, by agent name",
+                "type": "object",
+                "additionalProperties": { "type": "array", "items": { "type": "string" } }
+            }
+        },
+        "definitions": {
+            "workload": workload_schema(),
+            "workloadDefaults": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "restartPolicy": { "$ref": "#/definitions/restartPolicy" },
+                    "tags": { "type": "array", "items": { "$ref": "#/definitions/tag" } },
+                    "runtime": { "type": "string" }
+                }
+            },
+            "restartPolicy": { "enum": ["NEVER", "ON_FAILURE", "ALWAYS"] },
+            "tag": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "key": { "type": "string" },
+                    "value": { "type": "string" }
+                }
+            },
+            "port": { "type": "integer", "minimum": 0, "maximum": 65535 },
+            "controlInterfaceAccess": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "allowRules": {
+                        "type": "array",
+                        "items": { "$ref": "#/definitions/accessRightsRule" }
+                    },
+                    "denyRules": {
+                        "type": "array",
+                        "items": { "$ref": "#/definitions/accessRightsRule" }
+                    },
+                    "pipesOwner": {
+                        "type": "object",
+                        "required": ["uid", "gid"],
+                        "additionalProperties": false,
+                        "properties": {
+                            "uid": { "type": "integer", "minimum": 0 },
+                            "gid": { "type": "integer", "minimum": 0 }
+                        }
+                    }
+                }
+            },
+            "accessRightsRule": {
+                "type": "object",
+                "required": ["type", "operation", "filterMask"],
+                "additionalProperties": false,
+                "properties": {
+                    "type": { "const": "StateRule" },
+                    "operation": { "enum": ["Nothing", "Read", "Write", "ReadWrite"] },
+                    "filterMask": { "type": "array", "items": { "type": "string" } }
+                }
+            },
+            "healthCheck": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "command": { "type": "array", "items": { "type": "string" } },
+                    "tcp": {
+                        "type": "object",
+                        "required": ["host", "port"],
+                        "additionalProperties": false,
+                        "properties": {
+                            "host": { "type": "string" },
+                            "port": { "$ref": "#/definitions/port" }
+                        }
+                    },
+                    "http": {
+                        "type": "object",
+                        "required": ["url"],
+                        "additionalProperties": false,
+                        "properties": {
+                            "url": { "type": "string" }
+                        }
+                    },
+                    "intervalSecs": { "type": "integer", "minimum": 0 },
+                    "retries": { "type": "integer", "minimum": 0 }
+                }
+            },
+            "lifecycleHook": {
+                "type": "object",
+                "required": ["command"],
+                "additionalProperties": false,
+                "properties": {
+                    "command": { "type": "array", "items": { "type": "string" } },
+                    "timeoutSecs": { "type": "integer", "minimum": 1 }
+                }
+            },
+            "configItem": {
+                "anyOf": [
+                    { "type": "string" },
+                    { "type": "array", "items": { "$ref": "#/definitions/configItem" } },
+                    { "$ref": "#/definitions/secretRef" },
+                    {
+                        "type": "object",
+                        "not": { "required": ["secretRef"] },
+                        "additionalProperties": { "$ref": "#/definitions/configItem" }
+                    }
+                ]
+            },
+            "secretRef": {
+                "type": "object",
+                "description": "Reference to a secret resolved by the agent when creating the workload",
+                "properties": {
+                    "secretRef": {
+                        "type": "object",
+                        "properties": {
+                            "provider": { "enum": ["file", "env", "systemd-creds"] },
+                            "name": { "type": "string", "minLength": 1, "pattern": "^[^}]*$" }
+                        },
+                        "required": ["provider", "name"],
+                        "additionalProperties": false
+                    }
+                },
+                "required": ["secretRef"],
+                "additionalProperties": false
+            }
+        }
+    });
+
+    if api_version == API_VERSION_V0_1 {
+        restrict_to_api_version_v0_1(&mut schema);
+    }
+    Ok(schema)
+}
+
+// the workload is a separate fragment to keep the json! macro below its recursion limit
+fn workload_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["runtimeConfig"],
+        "additionalProperties": false,
+        "properties": {
+            "agent": {
+                "description": "Name of the agent the workload is executed on, can be templated with configs",
+                "type": "string"
+            },
+            "tags": { "type": "array", "items": { "$ref": "#/definitions/tag" } },
+            "dependencies": {
+                "type": "object",
+                "additionalProperties": {
+                    "enum": [
+                        "ADD_COND_RUNNING",
+                        "ADD_COND_SUCCEEDED",
+                        "ADD_COND_FAILED",
+                        "ADD_COND_CONFIG_CHANGED",
+                        "ADD_COND_READY"
+                    ]
+                }
+            },
+            "restartPolicy": { "$ref": "#/definitions/restartPolicy" },
+            "runtime": { "type": "string" },
+            "runtimeConfig": {
+                "description": "Runtime specific configuration of the workload, can be templated with configs",
+                "type": "string"
+            },
+            "controlInterfaceAccess": { "$ref": "#/definitions/controlInterfaceAccess" },
+            "configs": {
+                "type": "object",
+                "propertyNames": { "pattern": NAME_PATTERN },
+                "additionalProperties": { "type": "string", "pattern": NAME_PATTERN }
+            },
+            "hostname": { "type": "string" },
+            "extraHosts": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["hostname", "ip"],
+                    "additionalProperties": false,
+                    "properties": {
+                        "hostname": { "type": "string" },
+                        "ip": { "type": "string" }
+                    }
+                }
+            },
+            "ports": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "hostPort": { "$ref": "#/definitions/port" },
+                        "containerPort": { "$ref": "#/definitions/port" },
+                        "protocol": { "enum": ["TCP", "UDP"] }
+                    }
+                }
+            },
+            "resourceThresholds": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "cpuUsage": { "type": "integer", "minimum": 0 },
+                    "memoryUsage": { "type": "integer", "minimum": 0 }
+                }
+            },
+            "resources": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "cpuQuota": { "type": "integer", "minimum": 0 },
+                    "memoryLimit": { "type": "integer", "minimum": 0 }
+                }
+            },
+            "healthCheck": { "$ref": "#/definitions/healthCheck" },
+            "restartBackoff": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "initialDelay": { "type": "integer", "minimum": 0 },
+                    "maxDelay": { "type": "integer", "minimum": 0 },
+                    "multiplier": { "type": "integer", "minimum": 0 },
+                    "maxRetries": { "type": "integer", "minimum": 0 }
+                }
+            },
+            "priority": { "type": "integer", "minimum": 0 },
+            "volumes": volumes_schema(),
+            "registryAuth": { "type": "string" },
+            "replicas": { "type": "integer", "minimum": 0 },
+            "networks": networks_schema(),
+            "env": {
+                "type": "object",
+                "propertyNames": { "pattern": STR_RE_ENV_VAR_NAME },
+                "additionalProperties": { "type": "string" }
+            },
+            "onAgentDisconnect": {
+                "type": "string",
+                "pattern": "^(keep|stop|stop-after=[0-9]+)$"
+            },
+            "restartOn": {
+                "type": "array",
+                "items": { "type": "string" }
+            },
+            "lifecycleHooks": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "postStart": { "$ref": "#/definitions/lifecycleHook" },
+                    "preStop": { "$ref": "#/definitions/lifecycleHook" }
+                }
+            },
+            "nodeSelector": {
+                "type": "object",
+                "additionalProperties": { "type": "string" }
+            },
+            "stateDebounce": { "type": "integer", "minimum": 0 },
+            "securityContext": security_context_schema()
+        }
+    })
+}
+
+fn volumes_schema() -> Value {
+    json!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "required": ["mountPath"],
+            "additionalProperties": false,
+            "properties": {
+                "type": { "enum": ["hostPath", "named", "tmpfs"] },
+                "source": { "type": "string" },
+                "mountPath": { "type": "string" },
+                "readOnly": { "type": "boolean" }
+            }
+        }
+    })
+}
+
+fn networks_schema() -> Value {
+    json!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "required": ["name"],
+            "additionalProperties": false,
+            "properties": {
+                "name": { "type": "string" },
+                "aliases": { "type": "array", "items": { "type": "string" } }
+            }
+        }
+    })
+}
+
+fn security_context_schema() -> Value {
+    json!({
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "runAsUser": { "type": "integer", "minimum": 0 },
+            "runAsGroup": { "type": "integer", "minimum": 0 },
+            "capabilities": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "add": { "type": "array", "items": { "type": "string" } },
+                    "drop": { "type": "array", "items": { "type": "string" } }
+                }
+            },
+            "readOnlyRootFilesystem": { "type": "boolean" },
+            "noNewPrivileges": { "type": "boolean" }
+        }
+    })
+}
+
+fn restrict_to_api_version_v0_1(schema: &mut Value) {
+    if let Some(properties) = schema["properties"].as_object_mut() {
+        properties.retain(|field, _| API_VERSION_V0_1_STATE_FIELDS.contains(&field.as_str()));
+    }
+
+    let workload = &mut schema["definitions"]["workload"];
+    if let Some(properties) = workload["properties"].as_object_mut() {
+        properties.retain(|field, _| API_VERSION_V0_1_WORKLOAD_FIELDS.contains(&field.as_str()));
+    }
+    if let Some(conditions) =
+        workload["properties"]["dependencies"]["additionalProperties"]["enum"].as_array_mut()
+    {
+        conditions.retain(|condition| {
+            condition != "ADD_COND_CONFIG_CHANGED" && condition != "ADD_COND_READY"
+        });
+    }
+
+    // secret references were introduced with the API version v0.2
+    if let Some(config_item_variants) = schema["definitions"]["configItem"]["anyOf"].as_array_mut()
+    {
+        config_item_variants.retain(|variant| variant["$ref"] != "#/definitions/secretRef");
+    }
+    if let Some(definitions) = schema["definitions"].as_object_mut() {
+        definitions.remove("secretRef");
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use common::objects::{
+        AccessRightsRule, AddCondition, Capabilities, ControlInterfaceAccess, ExtraHost,
+        HealthCheck, LifecycleHook, LifecycleHooks, Network, PipesOwner, Port, ReadWriteEnum,
+        ResourceLimits, ResourceThresholds, RestartBackoff, RestartPolicy, SecurityContext,
+        StateRule, StoredWorkloadSpec, Tag, Volume, API_VERSION_V0_1, CURRENT_API_VERSION,
+    };
+    use serde_json::Value;
+
+    use super::{manifest_schema, write_manifest_schema};
+    use crate::cli::SchemaFormat;
+
+    fn fully_populated_workload() -> StoredWorkloadSpec {
+        StoredWorkloadSpec {
+            agent: "agent_A".to_string(),
+            tags: vec![Tag {
+                key: "owner".to_string(),
+                value: "someone".to_string(),
+            }],
+            dependencies: HashMap::from([("workload_B".to_string(), AddCondition::AddCondRunning)]),
+            restart_policy: Some(RestartPolicy::Always),
+            runtime: "podman".to_string(),
+            runtime_config: "image: alpine:latest".to_string(),
+            control_interface_access: ControlInterfaceAccess {
+                allow_rules: vec![AccessRightsRule::StateRule(StateRule {
+                    operation: ReadWriteEnum::Read,
+                    filter_mask: vec!["desiredState".to_string()],
+                })],
+                deny_rules: vec![],
+                pipes_owner: Some(PipesOwner {
+                    uid: 1000,
+                    gid: 1000,
+                }),
+            },
+            configs: HashMap::from([("alias".to_string(), "config_A".to_string())]),
+            hostname: Some("web".to_string()),
+            extra_hosts: vec![ExtraHost {
+                hostname: "db".to_string(),
+                ip: "10.0.0.2".to_string(),
+            }],
+            ports: vec![Port {
+                host_port: 8080,
+                container_port: 80,
+                ..Default::default()
+            }],
+            resource_thresholds: Some(ResourceThresholds {
+                cpu_usage: Some(80),
+                memory_usage: Some(1024),
+            }),
+            resources: Some(ResourceLimits {
+                cpu_quota: Some(50),
+                memory_limit: Some(1024),
+            }),
+            health_check: Some(HealthCheck {
+                command: Some(vec!["true".to_string()]),
+                interval_secs: Some(5),
+                ..Default::default()
+            }),
+            restart_backoff: Some(RestartBackoff {
+                initial_delay: Some(100),
+                ..Default::default()
+            }),
+            priority: Some(10),
+            volumes: vec![Volume {
+                source: "/opt/web".to_string(),
+                mount_path: "/usr/share/nginx/html".to_string(),
+                read_only: true,
+                ..Default::default()
+            }],
+            registry_auth: None,
+            replicas: None,
+            networks: vec![Network {
+                name: "backend".to_string(),
+                aliases: vec!["web".to_string()],
+            }],
+            env: HashMap::from([("LOG_LEVEL".to_string(), "debug".to_string())]),
+            on_agent_disconnect: None,
+            restart_on: vec!["backend".into()],
+            lifecycle_hooks: Some(LifecycleHooks {
+                post_start: None,
+                pre_stop: Some(LifecycleHook {
+                    command: vec!["/bin/flush".to_string()],
+                    timeout_secs: Some(10),
+                }),
+            }),
+            node_selector: HashMap::from([("zone".to_string(), "cockpit".to_string())]),
+            state_debounce: Some(500),
+            security_context: Some(SecurityContext {
+                run_as_user: Some(1000),
+                run_as_group: Some(1000),
+                capabilities: Capabilities {
+                    add: vec!["NET_BIND_SERVICE".to_string()],
+                    drop: vec!["ALL".to_string()],
+                },
+                read_only_root_filesystem: true,
+                no_new_privileges: true,
+            }),
+        }
+    }
+
+    // [utest->swdd~cli-provides-manifest-schema~1]
+    #[test]
+    fn utest_manifest_schema_covers_all_workload_fields() {
+        let schema = manifest_schema(CURRENT_API_VERSION).unwrap();
+        let workload_properties = &schema["definitions"]["workload"]["properties"];
+
+        let Value::Object(workload) = serde_json::to_value(fully_populated_workload()).unwrap()
+        else {
+            panic!("The workload is not serialized as object");
+        };
+        for field in workload.keys() {
+            assert!(
+                workload_properties.get(field).is_some(),
+                "The field '{}' is missing in the schema",
+                field
+            );
+        }
+    }
+
+    // [utest->swdd~cli-provides-manifest-schema~1]
+    #[test]
+    fn utest_manifest_schema_for_older_api_version() {
+        let schema = manifest_schema(API_VERSION_V0_1).unwrap();
+
+        assert_eq!(
+            schema["properties"]["apiVersion"]["const"],
+            API_VERSION_V0_1
+        );
+        assert!(schema["properties"].get("defaults").is_none());
+        assert!(schema["properties"].get("namespaces").is_none());
+        assert!(schema["properties"].get("images").is_none());
+        let workload_properties = &schema["definitions"]["workload"]["properties"];
+        assert!(workload_properties.get("runtimeConfig").is_some());
+        assert!(workload_properties.get("replicas").is_none());
+        assert!(
+            !workload_properties["dependencies"]["additionalProperties"]["enum"]
+                .as_array()
+                .unwrap()
+                .contains(&Value::from("ADD_COND_CONFIG_CHANGED"))
+        );
+        assert!(
+            !workload_properties["dependencies"]["additionalProperties"]["enum"]
+                .as_array()
+                .unwrap()
+                .contains(&Value::from("ADD_COND_READY"))
+        );
+        assert!(schema["definitions"].get("secretRef").is_none());
+    }
+
+    // [utest->swdd~cli-provides-manifest-schema~1]
+    #[test]
+    fn utest_manifest_schema_fails_on_unsupported_api_version() {
+        assert!(manifest_schema("v1").is_err());
+    }
+
+    // [utest->swdd~cli-provides-manifest-schema~1]
+    #[test]
+    fn utest_write_manifest_schema_in_json_and_yaml() {
+        let mut json_output = Vec::new();
+        write_manifest_schema(CURRENT_API_VERSION, SchemaFormat::Json, &mut json_output).unwrap();
+        let json_schema: Value = serde_json::from_slice(&json_output).unwrap();
+
+        let mut yaml_output = Vec::new();
+        write_manifest_schema(CURRENT_API_VERSION, SchemaFormat::Yaml, &mut yaml_output).unwrap();
+        let yaml_schema: Value = serde_yaml::from_slice(&yaml_output).unwrap();
+
+        assert_eq!(json_schema, yaml_schema);
+        assert_eq!(
+            json_schema["properties"]["apiVersion"]["const"],
+            CURRENT_API_VERSION
+        );
+    }
+}
@@ -17,6 +17,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .build_server(true)
         .boxed("Request.RequestContent.updateStateRequest")
         .boxed("FromAnkaios.FromAnkaiosEnum.response")
+        .boxed("State.defaults")
+        .boxed("Response.ResponseContent.completeState")
         .type_attribute(".", "#[derive(serde::Deserialize, serde::Serialize)]")
         .type_attribute(".", "#[serde(rename_all = \"camelCase\")]")
         .type_attribute(
@@ -39,7 +41,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .field_attribute("ExecutionsStatesForId.idStateMap", "#[serde(flatten)]")
         .field_attribute("WorkloadMap.workloads", "#[serde(flatten)]")
         .field_attribute("AgentMap.agents", "#[serde(flatten)]")
+        .field_attribute(
+            "WorkloadResourcesMap.agentResourcesMap",
+            "#[serde(flatten)]",
+        )
+        .field_attribute(
+            "WorkloadResourcesOfAgent.wlNameResourcesMap",
+            "#[serde(flatten)]",
+        )
         .field_attribute("ConfigMap.configs", "#[serde(flatten)]")
+        .field_attribute("WorkloadStateHistoryMap.workloads", "#[serde(flatten)]")
         .field_attribute(
             "ControlInterfaceAccess.allowRules",
             "#[serde(with = \"serde_yaml::with::singleton_map_recursive\")]",
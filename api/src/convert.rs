@@ -14,7 +14,56 @@
 
 use std::collections::HashMap;
 
-use crate::ank_base::{config_item, ConfigArray, ConfigItem, ConfigObject};
+use crate::ank_base::{
+    config_item, ConfigArray, ConfigItem, ConfigObject, SecretProvider, SecretRef,
+};
+
+const SECRET_REF_KEY: &str = "secretRef";
+
+fn secret_provider_from_str(provider: &str) -> Result<SecretProvider, String> {
+    match provider {
+        "file" => Ok(SecretProvider::File),
+        "env" => Ok(SecretProvider::Env),
+        "systemd-creds" => Ok(SecretProvider::SystemdCreds),
+        _ => Err(format!("Unknown secret provider '{}'", provider)),
+    }
+}
+
+fn secret_provider_to_str(provider: i32) -> &'static str {
+    match SecretProvider::from_i32(provider) {
+        Some(SecretProvider::Env) => "env",
+        Some(SecretProvider::SystemdCreds) => "systemd-creds",
+        _ => "file",
+    }
+}
+
+fn secret_ref_from_yaml(value: serde_yaml::Value) -> Result<SecretRef, String> {
+    let serde_yaml::Value::Mapping(fields) = value else {
+        return Err("Secret reference is not a mapping".into());
+    };
+    let mut provider = None;
+    let mut name = None;
+    for (key, value) in fields {
+        match (key.as_str(), value) {
+            (Some("provider"), serde_yaml::Value::String(value)) => {
+                provider = Some(secret_provider_from_str(&value)?)
+            }
+            (Some("name"), serde_yaml::Value::String(value)) => name = Some(value),
+            _ => {
+                return Err(
+                    "Secret reference supports only the string fields 'provider' and 'name'".into(),
+                )
+            }
+        }
+    }
+    match (provider, name) {
+        (Some(provider), Some(name)) => Ok(SecretRef {
+            provider: provider as i32,
+            name,
+        }),
+        _ => Err("Secret reference requires the fields 'provider' and 'name'".into()),
+    }
+}
 
 impl TryFrom<serde_yaml::Value> for ConfigItem {
     type Error = String;
@@ -35,6 +84,21 @@ impl TryFrom<serde_yaml::Value> for ConfigItem {
                         .collect::<Result<Vec<ConfigItem>, Self::Error>>()?,
                 })),
             }),
+            // [impl->swdd~common-config-secret-ref-format~1]
+            serde_yaml::Value::Mapping(mut object) if object.contains_key(SECRET_REF_KEY) => {
+                let secret_ref = object.remove(SECRET_REF_KEY).unwrap_or_default();
+                if !object.is_empty() {
+                    return Err(format!(
+                        "A secret reference must not contain fields besides '{}'",
+                        SECRET_REF_KEY
+                    ));
+                }
+                Ok(Self {
+                    config_item: Some(config_item::ConfigItem::SecretRef(secret_ref_from_yaml(
+                        secret_ref,
+                    )?)),
+                })
+            }
             serde_yaml::Value::Mapping(object) => Ok(Self {
                 config_item: Some(config_item::ConfigItem::Object(ConfigObject {
                     fields: object
@@ -75,6 +139,16 @@ impl From<ConfigItem> for serde_yaml::Value {
                         .collect(),
                 )
             }
+            Some(config_item::ConfigItem::SecretRef(SecretRef { provider, name })) => {
+                let secret_ref = serde_yaml::Mapping::from_iter([
+                    ("provider".into(), secret_provider_to_str(provider).into()),
+                    ("name".into(), name.into()),
+                ]);
+                serde_yaml::Value::Mapping(serde_yaml::Mapping::from_iter([(
+                    SECRET_REF_KEY.into(),
+                    serde_yaml::Value::Mapping(secret_ref),
+                )]))
+            }
         }
     }
 }
@@ -91,7 +165,9 @@ impl From<ConfigItem> for serde_yaml::Value {
 mod tests {
     use serde_yaml::Value;
 
-    use crate::ank_base::{config_item, ConfigArray, ConfigItem, ConfigObject};
+    use crate::ank_base::{
+        config_item, ConfigArray, ConfigItem, ConfigObject, SecretProvider, SecretRef,
+    };
 
     const YAML_CONFIG_EXAMPLE: &str = r#"
 - string_value
@@ -230,4 +306,35 @@ mod tests {
         let parsed_config = serde_yaml::from_str::<ConfigItem>("- 32");
         assert!(parsed_config.is_err());
     }
+
+    #[test]
+    fn utest_convert_secret_ref_from_and_to_yaml() {
+        const YAML_SECRET_REF: &str = "secretRef:\n  provider: systemd-creds\n  name: token\n";
+        let expected_config = ConfigItem {
+            config_item: Some(config_item::ConfigItem::SecretRef(SecretRef {
+                provider: SecretProvider::SystemdCreds as i32,
+                name: "token".to_string(),
+            })),
+        };
+
+        let parsed_config: ConfigItem = serde_yaml::from_str(YAML_SECRET_REF).unwrap();
+        assert_eq!(parsed_config, expected_config);
+        assert_eq!(
+            serde_yaml::to_string(&expected_config).unwrap(),
+            YAML_SECRET_REF
+        );
+    }
+
+    #[test]
+    fn utest_convert_invalid_secret_ref_fails() {
+        assert!(serde_yaml::from_str::<ConfigItem>(
+            "secretRef:\n  provider: vault\n  name: token\n"
+        )
+        .is_err());
+        assert!(serde_yaml::from_str::<ConfigItem>("secretRef:\n  provider: env\n").is_err());
+        assert!(serde_yaml::from_str::<ConfigItem>(
+            "secretRef:\n  provider: env\n  name: TOKEN\nother: value\n"
+        )
+        .is_err());
+    }
 }
@@ -12,13 +12,20 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::objects::{CompleteState, CpuUsage, DeletedWorkload, FreeMemory, WorkloadSpec};
+use crate::objects::{
+    CompleteState, CpuUsage, DeletedWorkload, DiskUsage, FreeMemory, ImageState, NetworkUsage, Tag,
+    Temperature, WorkloadResources, WorkloadSpec,
+};
 use api::ank_base;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
 pub struct AgentHello {
     pub agent_name: String,
+    pub labels: HashMap<String, String>,
+    pub version: String,
+    pub runtimes: Vec<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -26,6 +33,11 @@ pub struct AgentLoadStatus {
     pub agent_name: String,
     pub cpu_usage: CpuUsage,
     pub free_memory: FreeMemory,
+    pub workload_resources: Vec<WorkloadResources>,
+    pub disk_usage: DiskUsage,
+    pub network_usage: NetworkUsage,
+    pub temperature: Temperature,
+    pub image_states: HashMap<String, ImageState>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -76,6 +88,13 @@ impl TryFrom<ank_base::Request> for Request {
 pub enum RequestContent {
     CompleteStateRequest(CompleteStateRequest),
     UpdateStateRequest(Box<UpdateStateRequest>),
+    DependencyGraphRequest(DependencyGraphRequest),
+    EventsRequest(EventsRequest),
+    ExecRequest(ExecRequest),
+    PortForwardRequest(PortForwardRequest),
+    RestartWorkloadRequest(RestartWorkloadRequest),
+    PauseWorkloadRequest(PauseWorkloadRequest),
+    AuditRequest(AuditRequest),
 }
 
 impl From<RequestContent> for ank_base::request::RequestContent {
@@ -87,6 +106,27 @@ impl From<RequestContent> for ank_base::request::RequestContent {
             RequestContent::UpdateStateRequest(content) => {
                 ank_base::request::RequestContent::UpdateStateRequest(Box::new((*content).into()))
             }
+            RequestContent::DependencyGraphRequest(content) => {
+                ank_base::request::RequestContent::DependencyGraphRequest(content.into())
+            }
+            RequestContent::EventsRequest(content) => {
+                ank_base::request::RequestContent::EventsRequest(content.into())
+            }
+            RequestContent::ExecRequest(content) => {
+                ank_base::request::RequestContent::ExecRequest(content.into())
+            }
+            RequestContent::PortForwardRequest(content) => {
+                ank_base::request::RequestContent::PortForwardRequest(content.into())
+            }
+            RequestContent::RestartWorkloadRequest(content) => {
+                ank_base::request::RequestContent::RestartWorkloadRequest(content.into())
+            }
+            RequestContent::PauseWorkloadRequest(content) => {
+                ank_base::request::RequestContent::PauseWorkloadRequest(content.into())
+            }
+            RequestContent::AuditRequest(content) => {
+                ank_base::request::RequestContent::AuditRequest(content.into())
+            }
         }
     }
 }
@@ -101,6 +141,27 @@ impl TryFrom<ank_base::request::RequestContent> for RequestContent {
             ank_base::request::RequestContent::CompleteStateRequest(value) => {
                 RequestContent::CompleteStateRequest(value.into())
             }
+            ank_base::request::RequestContent::DependencyGraphRequest(value) => {
+                RequestContent::DependencyGraphRequest(value.into())
+            }
+            ank_base::request::RequestContent::EventsRequest(value) => {
+                RequestContent::EventsRequest(value.into())
+            }
+            ank_base::request::RequestContent::ExecRequest(value) => {
+                RequestContent::ExecRequest(value.try_into()?)
+            }
+            ank_base::request::RequestContent::PortForwardRequest(value) => {
+                RequestContent::PortForwardRequest(value.try_into()?)
+            }
+            ank_base::request::RequestContent::RestartWorkloadRequest(value) => {
+                RequestContent::RestartWorkloadRequest(value.into())
+            }
+            ank_base::request::RequestContent::PauseWorkloadRequest(value) => {
+                RequestContent::PauseWorkloadRequest(value.into())
+            }
+            ank_base::request::RequestContent::AuditRequest(value) => {
+                RequestContent::AuditRequest(value.into())
+            }
         })
     }
 }
@@ -108,12 +169,14 @@ impl TryFrom<ank_base::request::RequestContent> for RequestContent {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CompleteStateRequest {
     pub field_mask: Vec<String>,
+    pub selector: Vec<Tag>,
 }
 
 impl From<CompleteStateRequest> for ank_base::CompleteStateRequest {
     fn from(item: CompleteStateRequest) -> Self {
         ank_base::CompleteStateRequest {
             field_mask: item.field_mask,
+            selector: item.selector.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -122,14 +185,305 @@ impl From<ank_base::CompleteStateRequest> for CompleteStateRequest {
     fn from(item: ank_base::CompleteStateRequest) -> Self {
         CompleteStateRequest {
             field_mask: item.field_mask,
+            selector: item.selector.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyGraphRequest {}
+
+impl From<DependencyGraphRequest> for ank_base::DependencyGraphRequest {
+    fn from(_: DependencyGraphRequest) -> Self {
+        ank_base::DependencyGraphRequest {}
+    }
+}
+
+impl From<ank_base::DependencyGraphRequest> for DependencyGraphRequest {
+    fn from(_: ank_base::DependencyGraphRequest) -> Self {
+        DependencyGraphRequest {}
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventsRequest {
+    pub after_sequence_number: u64,
+}
+
+impl From<EventsRequest> for ank_base::EventsRequest {
+    fn from(item: EventsRequest) -> Self {
+        ank_base::EventsRequest {
+            after_sequence_number: item.after_sequence_number,
         }
     }
 }
 
+impl From<ank_base::EventsRequest> for EventsRequest {
+    fn from(item: ank_base::EventsRequest) -> Self {
+        EventsRequest {
+            after_sequence_number: item.after_sequence_number,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditRequest {
+    pub after_sequence_number: u64,
+}
+
+impl From<AuditRequest> for ank_base::AuditRequest {
+    fn from(item: AuditRequest) -> Self {
+        ank_base::AuditRequest {
+            after_sequence_number: item.after_sequence_number,
+        }
+    }
+}
+
+impl From<ank_base::AuditRequest> for AuditRequest {
+    fn from(item: ank_base::AuditRequest) -> Self {
+        AuditRequest {
+            after_sequence_number: item.after_sequence_number,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecRequest {
+    Start(ExecStart),
+    Input(ExecInput),
+}
+
+impl From<ExecRequest> for ank_base::ExecRequest {
+    fn from(item: ExecRequest) -> Self {
+        ank_base::ExecRequest {
+            exec_request_enum: Some(match item {
+                ExecRequest::Start(start) => {
+                    ank_base::exec_request::ExecRequestEnum::Start(ank_base::ExecStart {
+                        workload_name: start.workload_name,
+                        command: start.command,
+                    })
+                }
+                ExecRequest::Input(input) => {
+                    ank_base::exec_request::ExecRequestEnum::Input(ank_base::ExecInput {
+                        stdin: input.stdin,
+                        close_stdin: input.close_stdin,
+                    })
+                }
+            }),
+        }
+    }
+}
+
+impl TryFrom<ank_base::ExecRequest> for ExecRequest {
+    type Error = String;
+
+    fn try_from(item: ank_base::ExecRequest) -> Result<Self, Self::Error> {
+        Ok(
+            match item
+                .exec_request_enum
+                .ok_or_else(|| "ExecRequest has no content".to_string())?
+            {
+                ank_base::exec_request::ExecRequestEnum::Start(start) => {
+                    ExecRequest::Start(ExecStart {
+                        workload_name: start.workload_name,
+                        command: start.command,
+                    })
+                }
+                ank_base::exec_request::ExecRequestEnum::Input(input) => {
+                    ExecRequest::Input(ExecInput {
+                        stdin: input.stdin,
+                        close_stdin: input.close_stdin,
+                    })
+                }
+            },
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecStart {
+    pub workload_name: String,
+    pub command: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecInput {
+    pub stdin: Vec<u8>,
+    pub close_stdin: bool,
+}
+
+// An exec request forwarded by the server to the agent running the workload of the exec session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentExecRequest {
+    pub agent_name: Option<String>,
+    pub request_id: String,
+    pub exec_request: ExecRequest,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PortForwardRequest {
+    Start(PortForwardStart),
+    Data(PortForwardData),
+}
+
+impl From<PortForwardRequest> for ank_base::PortForwardRequest {
+    fn from(item: PortForwardRequest) -> Self {
+        ank_base::PortForwardRequest {
+            port_forward_request_enum: Some(match item {
+                PortForwardRequest::Start(start) => {
+                    ank_base::port_forward_request::PortForwardRequestEnum::Start(
+                        ank_base::PortForwardStart {
+                            workload_name: start.workload_name,
+                            port: start.port,
+                        },
+                    )
+                }
+                PortForwardRequest::Data(data) => {
+                    ank_base::port_forward_request::PortForwardRequestEnum::Data(data.into())
+                }
+            }),
+        }
+    }
+}
+
+impl TryFrom<ank_base::PortForwardRequest> for PortForwardRequest {
+    type Error = String;
+
+    fn try_from(item: ank_base::PortForwardRequest) -> Result<Self, Self::Error> {
+        Ok(
+            match item
+                .port_forward_request_enum
+                .ok_or_else(|| "PortForwardRequest has no content".to_string())?
+            {
+                ank_base::port_forward_request::PortForwardRequestEnum::Start(start) => {
+                    PortForwardRequest::Start(PortForwardStart {
+                        workload_name: start.workload_name,
+                        port: start.port,
+                    })
+                }
+                ank_base::port_forward_request::PortForwardRequestEnum::Data(data) => {
+                    PortForwardRequest::Data(data.into())
+                }
+            },
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortForwardStart {
+    pub workload_name: String,
+    pub port: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortForwardData {
+    pub data: Vec<u8>,
+    pub close: bool,
+}
+
+impl From<PortForwardData> for ank_base::PortForwardData {
+    fn from(item: PortForwardData) -> Self {
+        ank_base::PortForwardData {
+            data: item.data,
+            close: item.close,
+        }
+    }
+}
+
+impl From<ank_base::PortForwardData> for PortForwardData {
+    fn from(item: ank_base::PortForwardData) -> Self {
+        PortForwardData {
+            data: item.data,
+            close: item.close,
+        }
+    }
+}
+
+// A port forward request forwarded by the server to the agent running the workload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentPortForwardRequest {
+    pub agent_name: Option<String>,
+    pub request_id: String,
+    pub port_forward_request: PortForwardRequest,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestartWorkloadRequest {
+    pub workload_name: String,
+}
+
+impl From<RestartWorkloadRequest> for ank_base::RestartWorkloadRequest {
+    fn from(item: RestartWorkloadRequest) -> Self {
+        ank_base::RestartWorkloadRequest {
+            workload_name: item.workload_name,
+        }
+    }
+}
+
+impl From<ank_base::RestartWorkloadRequest> for RestartWorkloadRequest {
+    fn from(item: ank_base::RestartWorkloadRequest) -> Self {
+        RestartWorkloadRequest {
+            workload_name: item.workload_name,
+        }
+    }
+}
+
+// A restart workload request forwarded by the server to the agent running the workload.
+// The workload names are the names of the workload and its replicas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentRestartWorkloadRequest {
+    pub agent_name: Option<String>,
+    pub request_id: String,
+    pub workload_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PauseWorkloadRequest {
+    pub workload_name: String,
+    pub resume: bool,
+}
+
+impl From<PauseWorkloadRequest> for ank_base::PauseWorkloadRequest {
+    fn from(item: PauseWorkloadRequest) -> Self {
+        ank_base::PauseWorkloadRequest {
+            workload_name: item.workload_name,
+            resume: item.resume,
+        }
+    }
+}
+
+impl From<ank_base::PauseWorkloadRequest> for PauseWorkloadRequest {
+    fn from(item: ank_base::PauseWorkloadRequest) -> Self {
+        PauseWorkloadRequest {
+            workload_name: item.workload_name,
+            resume: item.resume,
+        }
+    }
+}
+
+// A pause or resume workload request forwarded by the server to the agent running the workload.
+// The workload names are the names of the workload and its replicas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentPauseWorkloadRequest {
+    pub agent_name: Option<String>,
+    pub request_id: String,
+    pub workload_names: Vec<String>,
+    pub resume: bool,
+}
+
+// The images an agent shall pull ahead of their use and keep until they are not contained anymore.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullImages {
+    pub agent_name: Option<String>,
+    pub images: Vec<String>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct UpdateStateRequest {
     pub state: CompleteState,
     pub update_mask: Vec<String>,
+    pub dry_run: bool,
+    pub grace_period_secs: Option<u32>,
 }
 
 impl From<UpdateStateRequest> for ank_base::UpdateStateRequest {
@@ -137,6 +491,8 @@ impl From<UpdateStateRequest> for ank_base::UpdateStateRequest {
         Self {
             new_state: Some(value.state.into()),
             update_mask: value.update_mask,
+            dry_run: value.dry_run,
+            grace_period_secs: value.grace_period_secs,
         }
     }
 }
@@ -148,6 +504,8 @@ impl TryFrom<ank_base::UpdateStateRequest> for UpdateStateRequest {
         Ok(UpdateStateRequest {
             state: item.new_state.unwrap_or_default().try_into()?,
             update_mask: item.update_mask,
+            dry_run: item.dry_run,
+            grace_period_secs: item.grace_period_secs,
         })
     }
 }
@@ -170,6 +528,9 @@ pub struct Goodbye {}
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Stop {}
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ServerConnectionLost {}
+
 //////////////////////////////////////////////////////////////////////////////
 //                 ########  #######    #########  #########                //
 //                    ##     ##        ##             ##                    //
@@ -184,18 +545,26 @@ mod tests {
 
     mod ank_base {
         pub use api::ank_base::{
-            request::RequestContent, CompleteState, CompleteStateRequest, ConfigMappings,
-            Dependencies, Request, RestartPolicy, State, Tag, Tags, UpdateStateRequest, Workload,
-            WorkloadMap,
+            request::RequestContent, AuditRequest, CompleteState, CompleteStateRequest,
+            ConfigMappings, Dependencies, DependencyGraphRequest, EventsRequest, ExtraHost,
+            ExtraHosts, HealthCheck, PauseWorkloadRequest, Port, PortProtocol, Ports, Request,
+            ResourceLimits, ResourceThresholds, RestartPolicy, RestartWorkloadRequest, State, Tag,
+            Tags, TcpProbe, UpdateStateRequest, Volume, VolumeType, Volumes, Workload, WorkloadMap,
         };
     }
 
     mod ankaios {
         pub use crate::{
-            commands::{CompleteStateRequest, Request, RequestContent, UpdateStateRequest},
+            commands::{
+                AuditRequest, CompleteStateRequest, DependencyGraphRequest, EventsRequest,
+                PauseWorkloadRequest, Request, RequestContent, RestartWorkloadRequest,
+                UpdateStateRequest,
+            },
             objects::{
                 generate_test_agent_map, generate_test_workload_states_map_with_data,
-                CompleteState, ExecutionState, RestartPolicy, State, StoredWorkloadSpec, Tag,
+                CompleteState, ExecutionState, ExtraHost, HealthCheck, Port, PortProtocol,
+                ResourceLimits, ResourceThresholds, RestartPolicy, State, StoredWorkloadSpec, Tag,
+                TcpProbe, Volume, VolumeType, CURRENT_API_VERSION,
             },
         };
     }
@@ -207,7 +576,9 @@ mod tests {
     const WORKLOAD_NAME_1: &str = "workload_name_1";
     const RUNTIME: &str = "my_favorite_runtime";
     const RUNTIME_CONFIG: &str = "generalOptions: [\"--version\"]\ncommandOptions: [\"--network=host\"]\nimage: alpine:latest\ncommandArgs: [\"bash\"]\n";
+    const HOSTNAME: &str = "vehicle-1";
     const HASH: &str = "hash_1";
+    const AFTER_SEQUENCE_NUMBER: u64 = 42;
 
     macro_rules! complete_state_request {
         ($expression:ident) => {{
@@ -216,6 +587,76 @@ mod tests {
                 request_content: $expression::RequestContent::CompleteStateRequest(
                     $expression::CompleteStateRequest {
                         field_mask: vec![FIELD_1.into(), FIELD_2.into()],
+                        selector: vec![],
+                    },
+                )
+                .into(),
+            }
+        }};
+    }
+
+    macro_rules! dependency_graph_request {
+        ($expression:ident) => {{
+            $expression::Request {
+                request_id: REQUEST_ID.into(),
+                request_content: $expression::RequestContent::DependencyGraphRequest(
+                    $expression::DependencyGraphRequest {},
+                )
+                .into(),
+            }
+        }};
+    }
+
+    macro_rules! events_request {
+        ($expression:ident) => {{
+            $expression::Request {
+                request_id: REQUEST_ID.into(),
+                request_content: $expression::RequestContent::EventsRequest(
+                    $expression::EventsRequest {
+                        after_sequence_number: AFTER_SEQUENCE_NUMBER,
+                    },
+                )
+                .into(),
+            }
+        }};
+    }
+
+    macro_rules! audit_request {
+        ($expression:ident) => {{
+            $expression::Request {
+                request_id: REQUEST_ID.into(),
+                request_content: $expression::RequestContent::AuditRequest(
+                    $expression::AuditRequest {
+                        after_sequence_number: AFTER_SEQUENCE_NUMBER,
+                    },
+                )
+                .into(),
+            }
+        }};
+    }
+
+    macro_rules! restart_workload_request {
+        ($expression:ident) => {{
+            $expression::Request {
+                request_id: REQUEST_ID.into(),
+                request_content: $expression::RequestContent::RestartWorkloadRequest(
+                    $expression::RestartWorkloadRequest {
+                        workload_name: "workload_A".into(),
+                    },
+                )
+                .into(),
+            }
+        }};
+    }
+
+    macro_rules! pause_workload_request {
+        ($expression:ident) => {{
+            $expression::Request {
+                request_id: REQUEST_ID.into(),
+                request_content: $expression::RequestContent::PauseWorkloadRequest(
+                    $expression::PauseWorkloadRequest {
+                        workload_name: "workload_A".into(),
+                        resume: true,
                     },
                 )
                 .into(),
@@ -237,12 +678,16 @@ mod tests {
             ank_base::RequestContent::UpdateStateRequest(Box::new(ank_base::UpdateStateRequest {
                 new_state: complete_state!(ank_base).into(),
                 update_mask: vec![FIELD_1.into(), FIELD_2.into()],
+                dry_run: true,
+                grace_period_secs: None,
             }))
         };
         (ankaios) => {
             ankaios::RequestContent::UpdateStateRequest(Box::new(ankaios::UpdateStateRequest {
                 state: complete_state!(ankaios),
                 update_mask: vec![FIELD_1.into(), FIELD_2.into()],
+                dry_run: true,
+                grace_period_secs: None,
             }))
         };
     }
@@ -254,10 +699,15 @@ mod tests {
                     api_version: "v0.1".into(),
                     workloads: HashMap::from([("desired".into(), workload!(ankaios))]),
                     configs: HashMap::new(),
+                    defaults: Default::default(),
+                    namespaces: Default::default(),
+                    images: Default::default(),
                 }
                 .into(),
                 workload_states: workload_states_map!(ankaios),
                 agents: agent_map!(ankaios),
+                workload_resources: Default::default(),
+                image_states: Default::default(),
             }
         };
         (ank_base) => {
@@ -268,9 +718,15 @@ mod tests {
                         workloads: HashMap::from([("desired".to_string(), workload!(ank_base))]),
                     }),
                     configs: Some(Default::default()),
+                    defaults: None,
+                    namespaces: None,
+                    images: None,
                 }),
                 workload_states: workload_states_map!(ank_base),
                 agents: agent_map!(ank_base),
+                workload_resources: None,
+                workload_state_history: None,
+                image_states: None,
             }
         };
     }
@@ -297,6 +753,55 @@ mod tests {
                     ]
                     .into(),
                 }),
+                hostname: Some(HOSTNAME.to_string()),
+                extra_hosts: Some(ank_base::ExtraHosts {
+                    extra_hosts: vec![ank_base::ExtraHost {
+                        hostname: "database".into(),
+                        ip: "10.0.0.5".into(),
+                    }],
+                }),
+                ports: Some(ank_base::Ports {
+                    ports: vec![ank_base::Port {
+                        host_port: 8080,
+                        container_port: 80,
+                        protocol: ank_base::PortProtocol::Tcp as i32,
+                    }],
+                }),
+                resource_thresholds: Some(ank_base::ResourceThresholds {
+                    cpu_usage: Some(80),
+                    memory_usage: None,
+                }),
+                resources: Some(ank_base::ResourceLimits {
+                    cpu_quota: Some(150),
+                    memory_limit: None,
+                }),
+                health_check: Some(ank_base::HealthCheck {
+                    tcp: Some(ank_base::TcpProbe {
+                        host: "localhost".into(),
+                        port: 8080,
+                    }),
+                    ..Default::default()
+                }),
+                restart_backoff: None,
+                priority: None,
+                volumes: Some(ank_base::Volumes {
+                    volumes: vec![ank_base::Volume {
+                        r#type: ank_base::VolumeType::Named as i32,
+                        source: "data".into(),
+                        mount_path: "/var/lib/data".into(),
+                        read_only: false,
+                    }],
+                }),
+                registry_auth: None,
+                replicas: None,
+                networks: Some(Default::default()),
+                env: Some(Default::default()),
+                on_agent_disconnect: None,
+                restart_on: Some(Default::default()),
+                lifecycle_hooks: None,
+                node_selector: Some(Default::default()),
+                state_debounce: None,
+                security_context: None,
             }
         };
         (ankaios) => {
@@ -307,7 +812,7 @@ mod tests {
                     value: "value".into(),
                 }],
                 dependencies: HashMap::new(),
-                restart_policy: ankaios::RestartPolicy::Always,
+                restart_policy: Some(ankaios::RestartPolicy::Always),
                 runtime: RUNTIME.to_string(),
                 runtime_config: RUNTIME_CONFIG.to_string(),
                 control_interface_access: Default::default(),
@@ -316,6 +821,49 @@ mod tests {
                     ("ref2".into(), "config_2".into()),
                 ]
                 .into(),
+                hostname: Some(HOSTNAME.to_string()),
+                extra_hosts: vec![ankaios::ExtraHost {
+                    hostname: "database".into(),
+                    ip: "10.0.0.5".into(),
+                }],
+                ports: vec![ankaios::Port {
+                    host_port: 8080,
+                    container_port: 80,
+                    protocol: ankaios::PortProtocol::Tcp,
+                }],
+                resource_thresholds: Some(ankaios::ResourceThresholds {
+                    cpu_usage: Some(80),
+                    memory_usage: None,
+                }),
+                resources: Some(ankaios::ResourceLimits {
+                    cpu_quota: Some(150),
+                    memory_limit: None,
+                }),
+                health_check: Some(ankaios::HealthCheck {
+                    tcp: Some(ankaios::TcpProbe {
+                        host: "localhost".into(),
+                        port: 8080,
+                    }),
+                    ..Default::default()
+                }),
+                restart_backoff: None,
+                priority: None,
+                volumes: vec![ankaios::Volume {
+                    volume_type: ankaios::VolumeType::Named,
+                    source: "data".into(),
+                    mount_path: "/var/lib/data".into(),
+                    read_only: false,
+                }],
+                registry_auth: None,
+                replicas: None,
+                networks: vec![],
+                env: Default::default(),
+                on_agent_disconnect: None,
+                restart_on: vec![],
+                lifecycle_hooks: None,
+                node_selector: Default::default(),
+                state_debounce: None,
+                security_context: None,
             }
         };
     }
@@ -360,6 +908,211 @@ mod tests {
         );
     }
 
+    #[test]
+    fn utest_converts_from_proto_dependency_graph_request() {
+        let proto_request_dependency_graph = dependency_graph_request!(ank_base);
+        let ankaios_request_dependency_graph = dependency_graph_request!(ankaios);
+
+        assert_eq!(
+            ankaios::Request::try_from(proto_request_dependency_graph).unwrap(),
+            ankaios_request_dependency_graph
+        );
+    }
+
+    #[test]
+    fn utest_converts_to_proto_dependency_graph_request() {
+        let proto_request_dependency_graph = dependency_graph_request!(ank_base);
+        let ankaios_request_dependency_graph = dependency_graph_request!(ankaios);
+
+        assert_eq!(
+            ank_base::Request::from(ankaios_request_dependency_graph),
+            proto_request_dependency_graph
+        );
+    }
+
+    #[test]
+    fn utest_converts_from_proto_events_request() {
+        let proto_request_events = events_request!(ank_base);
+        let ankaios_request_events = events_request!(ankaios);
+
+        assert_eq!(
+            ankaios::Request::try_from(proto_request_events).unwrap(),
+            ankaios_request_events
+        );
+    }
+
+    #[test]
+    fn utest_converts_to_proto_events_request() {
+        let proto_request_events = events_request!(ank_base);
+        let ankaios_request_events = events_request!(ankaios);
+
+        assert_eq!(
+            ank_base::Request::from(ankaios_request_events),
+            proto_request_events
+        );
+    }
+
+    #[test]
+    fn utest_converts_proto_audit_request() {
+        let proto_request_audit = audit_request!(ank_base);
+        let ankaios_request_audit = audit_request!(ankaios);
+
+        assert_eq!(
+            ankaios::Request::try_from(proto_request_audit.clone()).unwrap(),
+            ankaios_request_audit
+        );
+        assert_eq!(
+            ank_base::Request::from(ankaios_request_audit),
+            proto_request_audit
+        );
+    }
+
+    #[test]
+    fn utest_converts_proto_restart_workload_request() {
+        let proto_restart_workload = restart_workload_request!(ank_base);
+        let ankaios_restart_workload = restart_workload_request!(ankaios);
+
+        assert_eq!(
+            ankaios::Request::try_from(proto_restart_workload.clone()).unwrap(),
+            ankaios_restart_workload
+        );
+        assert_eq!(
+            ank_base::Request::from(ankaios_restart_workload),
+            proto_restart_workload
+        );
+    }
+
+    #[test]
+    fn utest_converts_proto_pause_workload_request() {
+        let proto_pause_workload = pause_workload_request!(ank_base);
+        let ankaios_pause_workload = pause_workload_request!(ankaios);
+
+        assert_eq!(
+            ankaios::Request::try_from(proto_pause_workload.clone()).unwrap(),
+            ankaios_pause_workload
+        );
+        assert_eq!(
+            ank_base::Request::from(ankaios_pause_workload),
+            proto_pause_workload
+        );
+    }
+
+    #[test]
+    fn utest_converts_proto_exec_request() {
+        let proto_exec_start = api::ank_base::ExecRequest {
+            exec_request_enum: Some(api::ank_base::exec_request::ExecRequestEnum::Start(
+                api::ank_base::ExecStart {
+                    workload_name: WORKLOAD_NAME_1.into(),
+                    command: vec!["sh".into(), "-c".into(), "ls".into()],
+                },
+            )),
+        };
+        let ankaios_exec_start = crate::commands::ExecRequest::Start(crate::commands::ExecStart {
+            workload_name: WORKLOAD_NAME_1.into(),
+            command: vec!["sh".into(), "-c".into(), "ls".into()],
+        });
+        assert_eq!(
+            crate::commands::ExecRequest::try_from(proto_exec_start.clone()),
+            Ok(ankaios_exec_start.clone())
+        );
+        assert_eq!(
+            api::ank_base::ExecRequest::from(ankaios_exec_start),
+            proto_exec_start
+        );
+
+        let proto_exec_input = api::ank_base::ExecRequest {
+            exec_request_enum: Some(api::ank_base::exec_request::ExecRequestEnum::Input(
+                api::ank_base::ExecInput {
+                    stdin: b"exit\n".to_vec(),
+                    close_stdin: true,
+                },
+            )),
+        };
+        let ankaios_exec_input = crate::commands::ExecRequest::Input(crate::commands::ExecInput {
+            stdin: b"exit\n".to_vec(),
+            close_stdin: true,
+        });
+        assert_eq!(
+            crate::commands::ExecRequest::try_from(proto_exec_input.clone()),
+            Ok(ankaios_exec_input.clone())
+        );
+        assert_eq!(
+            api::ank_base::ExecRequest::from(ankaios_exec_input),
+            proto_exec_input
+        );
+    }
+
+    #[test]
+    fn utest_converts_from_proto_exec_request_fails_empty_content() {
+        assert_eq!(
+            crate::commands::ExecRequest::try_from(api::ank_base::ExecRequest {
+                exec_request_enum: None
+            }),
+            Err("ExecRequest has no content".to_string())
+        );
+    }
+
+    #[test]
+    fn utest_converts_proto_port_forward_request() {
+        let proto_start = api::ank_base::PortForwardRequest {
+            port_forward_request_enum: Some(
+                api::ank_base::port_forward_request::PortForwardRequestEnum::Start(
+                    api::ank_base::PortForwardStart {
+                        workload_name: WORKLOAD_NAME_1.into(),
+                        port: 8080,
+                    },
+                ),
+            ),
+        };
+        let ankaios_start =
+            crate::commands::PortForwardRequest::Start(crate::commands::PortForwardStart {
+                workload_name: WORKLOAD_NAME_1.into(),
+                port: 8080,
+            });
+        assert_eq!(
+            crate::commands::PortForwardRequest::try_from(proto_start.clone()),
+            Ok(ankaios_start.clone())
+        );
+        assert_eq!(
+            api::ank_base::PortForwardRequest::from(ankaios_start),
+            proto_start
+        );
+
+        let proto_data = api::ank_base::PortForwardRequest {
+            port_forward_request_enum: Some(
+                api::ank_base::port_forward_request::PortForwardRequestEnum::Data(
+                    api::ank_base::PortForwardData {
+                        data: b"GET /".to_vec(),
+                        close: true,
+                    },
+                ),
+            ),
+        };
+        let ankaios_data =
+            crate::commands::PortForwardRequest::Data(crate::commands::PortForwardData {
+                data: b"GET /".to_vec(),
+                close: true,
+            });
+        assert_eq!(
+            crate::commands::PortForwardRequest::try_from(proto_data.clone()),
+            Ok(ankaios_data.clone())
+        );
+        assert_eq!(
+            api::ank_base::PortForwardRequest::from(ankaios_data),
+            proto_data
+        );
+    }
+
+    #[test]
+    fn utest_converts_from_proto_port_forward_request_fails_empty_content() {
+        assert_eq!(
+            crate::commands::PortForwardRequest::try_from(api::ank_base::PortForwardRequest {
+                port_forward_request_enum: None
+            }),
+            Err("PortForwardRequest has no content".to_string())
+        );
+    }
+
     #[test]
     fn utest_converts_from_proto_update_state_request() {
         let proto_request_complete_state = update_state_request!(ank_base);
@@ -386,11 +1139,14 @@ mod tests {
         };
         proto_request_content.new_state = Some(ank_base::CompleteState {
             desired_state: Some(ank_base::State {
-                api_version: "v0.1".into(),
+                api_version: ankaios::CURRENT_API_VERSION.into(),
                 workloads: Some(ank_base::WorkloadMap {
                     workloads: HashMap::new(),
                 }),
                 configs: Some(Default::default()),
+                defaults: None,
+                namespaces: None,
+                images: None,
             }),
             ..Default::default()
         });
@@ -428,11 +1184,14 @@ mod tests {
             .as_mut()
             .unwrap()
             .desired_state = Some(ank_base::State {
-            api_version: "v0.1".into(),
+            api_version: ankaios::CURRENT_API_VERSION.into(),
             workloads: Some(ank_base::WorkloadMap {
                 workloads: HashMap::new(),
             }),
             configs: Some(Default::default()),
+            defaults: None,
+            namespaces: None,
+            images: None,
         });
 
         let ankaios::RequestContent::UpdateStateRequest(ankaios_request_content) =
@@ -504,6 +1263,7 @@ mod tests {
             request_content: ankaios::RequestContent::CompleteStateRequest(
                 ankaios::CompleteStateRequest {
                     field_mask: vec!["1".to_string(), "2".to_string()],
+                    selector: vec![],
                 },
             ),
         };
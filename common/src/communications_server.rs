@@ -12,7 +12,7 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::net::SocketAddr;
+use std::{fmt::Display, net::SocketAddr, path::PathBuf, str::FromStr};
 
 use async_trait::async_trait;
 
@@ -20,12 +20,101 @@ use crate::{
     communications_error::CommunicationMiddlewareError, from_server_interface::FromServerReceiver,
 };
 
+pub const UNIX_SOCKET_SCHEME: &str = "unix://";
+
+// [impl->swdd~common-server-address-supports-unix-domain-sockets~1]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerAddress {
+    Tcp(SocketAddr),
+    UnixSocket(PathBuf),
+}
+
+impl FromStr for ServerAddress {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Some(socket_path) = value.strip_prefix(UNIX_SOCKET_SCHEME) {
+            if !socket_path.starts_with('/') {
+                return Err(format!(
+                    "The unix domain socket address '{}' must contain an absolute path, e.g. '{}/run/ankaios/server.sock'.",
+                    value, UNIX_SOCKET_SCHEME
+                ));
+            }
+            return Ok(ServerAddress::UnixSocket(PathBuf::from(socket_path)));
+        }
+
+        value
+            .parse::<SocketAddr>()
+            .map(ServerAddress::Tcp)
+            .map_err(|err| format!("The server address '{}' is invalid: '{}'", value, err))
+    }
+}
+
+impl Display for ServerAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServerAddress::Tcp(addr) => write!(f, "{}", addr),
+            ServerAddress::UnixSocket(socket_path) => {
+                write!(f, "{}{}", UNIX_SOCKET_SCHEME, socket_path.display())
+            }
+        }
+    }
+}
+
 // [impl->swdd~common-interface-definitions~1]
 #[async_trait]
 pub trait CommunicationsServer {
     async fn start(
         &mut self,
         mut receiver: FromServerReceiver,
-        addr: SocketAddr,
+        addr: ServerAddress,
     ) -> Result<(), CommunicationMiddlewareError>;
 }
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::ServerAddress;
+
+    // [utest->swdd~common-server-address-supports-unix-domain-sockets~1]
+    #[test]
+    fn utest_server_address_from_str_tcp() {
+        assert_eq!(
+            "127.0.0.1:25551".parse::<ServerAddress>(),
+            Ok(ServerAddress::Tcp("127.0.0.1:25551".parse().unwrap()))
+        );
+    }
+
+    // [utest->swdd~common-server-address-supports-unix-domain-sockets~1]
+    #[test]
+    fn utest_server_address_from_str_unix_socket() {
+        let server_address = "unix:///run/ankaios/server.sock".parse::<ServerAddress>();
+
+        assert_eq!(
+            server_address,
+            Ok(ServerAddress::UnixSocket(PathBuf::from(
+                "/run/ankaios/server.sock"
+            )))
+        );
+        assert_eq!(
+            server_address.unwrap().to_string(),
+            "unix:///run/ankaios/server.sock"
+        );
+    }
+
+    // [utest->swdd~common-server-address-supports-unix-domain-sockets~1]
+    #[test]
+    fn utest_server_address_from_str_fails_on_invalid_address() {
+        assert!("unix://server.sock".parse::<ServerAddress>().is_err());
+        assert!("localhost".parse::<ServerAddress>().is_err());
+    }
+}
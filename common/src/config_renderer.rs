@@ -0,0 +1,956 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashMap, env, fmt};
+
+use base64::Engine;
+use handlebars::{
+    handlebars_helper, Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext,
+    RenderError, RenderErrorReason, ScopedJson,
+};
+use serde_json::Value;
+
+use crate::objects::{ConfigItem, StoredWorkloadSpec, WorkloadInstanceName, WorkloadSpec};
+
+pub type RenderedWorkloads = HashMap<String, WorkloadSpec>;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ConfigRenderError {
+    Field(String, String),
+    NotExistingConfigKey(String),
+}
+
+impl fmt::Display for ConfigRenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigRenderError::Field(field, reason) => {
+                write!(f, "Failed to render field '{}': '{}'", field, reason)
+            }
+            ConfigRenderError::NotExistingConfigKey(config_key) => {
+                write!(
+                    f,
+                    "Workload references config key '{}' that does not exist",
+                    config_key
+                )
+            }
+        }
+    }
+}
+
+// [impl->swdd~config-renderer-provides-template-helpers~2]
+handlebars_helper!(to_json_helper: |value: Json| serde_json::to_string(value).unwrap_or_default());
+
+// [impl->swdd~config-renderer-provides-template-helpers~2]
+handlebars_helper!(base64_helper: |value: str| base64::engine::general_purpose::STANDARD.encode(value));
+
+// [impl->swdd~config-renderer-provides-template-helpers~2]
+handlebars_helper!(indent_helper: |width: u64, value: str| {
+    let indentation = " ".repeat(width as usize);
+    value
+        .lines()
+        .map(|line| format!("{}{}", indentation, line))
+        .collect::<Vec<String>>()
+        .join("\n")
+});
+
+// The helper fails on a missing environment variable to be consistent with the strict rendering of configs.
+// Only variables with an allowed prefix can be read, as the environment of the server can contain credentials.
+// [impl->swdd~config-renderer-provides-template-helpers~2]
+struct EnvHelper {
+    allowed_prefixes: Vec<String>,
+    lookup: fn(&str) -> Result<String, env::VarError>,
+}
+
+impl EnvHelper {
+    fn new(
+        allowed_prefixes: Vec<String>,
+        lookup: fn(&str) -> Result<String, env::VarError>,
+    ) -> Self {
+        Self {
+            allowed_prefixes,
+            lookup,
+        }
+    }
+
+    fn is_allowed(&self, variable_name: &str) -> bool {
+        self.allowed_prefixes
+            .iter()
+            .any(|prefix| !prefix.is_empty() && variable_name.starts_with(prefix.as_str()))
+    }
+}
+
+fn read_env_variable(variable_name: &str) -> Result<String, env::VarError> {
+    env::var(variable_name)
+}
+
+impl HelperDef for EnvHelper {
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+    ) -> Result<ScopedJson<'rc>, RenderError> {
+        let variable_name = h
+            .param(0)
+            .and_then(|param| param.value().as_str())
+            .ok_or(RenderErrorReason::ParamNotFoundForIndex("env", 0))?;
+
+        if !self.is_allowed(variable_name) {
+            return Err(RenderErrorReason::Other(format!(
+                "Reading environment variable '{}' is not allowed",
+                variable_name
+            ))
+            .into());
+        }
+
+        (self.lookup)(variable_name)
+            .map(|value| ScopedJson::Derived(Value::String(value)))
+            .map_err(|err| {
+                RenderErrorReason::Other(format!(
+                    "Could not read environment variable '{}': '{}'",
+                    variable_name, err
+                ))
+                .into()
+            })
+    }
+}
+
+// Used where the environment of the server is not known, e.g. in the CLI. The variable is shown as
+// a placeholder instead of failing the rendering, as only the server can read its value.
+// [impl->swdd~cli-provides-rendered-config-of-workload~2]
+struct EnvPlaceholderHelper;
+
+impl HelperDef for EnvPlaceholderHelper {
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+    ) -> Result<ScopedJson<'rc>, RenderError> {
+        let variable_name = h
+            .param(0)
+            .and_then(|param| param.value().as_str())
+            .ok_or(RenderErrorReason::ParamNotFoundForIndex("env", 0))?;
+
+        Ok(ScopedJson::Derived(Value::String(env_placeholder(
+            variable_name,
+        ))))
+    }
+}
+
+fn env_placeholder(variable_name: &str) -> String {
+    format!("${{server-env:{}}}", variable_name)
+}
+
+// The output of the helpers is written without HTML escaping, as escaping would corrupt e.g. the
+// JSON or base64 output. All other rendered values keep the default escaping of the template engine.
+// [impl->swdd~config-renderer-provides-template-helpers~2]
+struct UnescapedHelper<H: HelperDef>(H);
+
+impl<H: HelperDef> HelperDef for UnescapedHelper<H> {
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'rc>,
+        r: &'reg Handlebars<'reg>,
+        ctx: &'rc Context,
+        rc: &mut RenderContext<'reg, 'rc>,
+    ) -> Result<ScopedJson<'rc>, RenderError> {
+        self.0.call_inner(h, r, ctx, rc)
+    }
+
+    fn call<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'rc>,
+        r: &'reg Handlebars<'reg>,
+        ctx: &'rc Context,
+        rc: &mut RenderContext<'reg, 'rc>,
+        out: &mut dyn Output,
+    ) -> HelperResult {
+        let result = self.call_inner(h, r, ctx, rc)?;
+        out.write(&result.render())?;
+        Ok(())
+    }
+}
+
+// [impl->swdd~server-delegate-template-render-to-external-library~1]
+pub struct ConfigRenderer {
+    template_engine: Handlebars<'static>,
+}
+
+impl Default for ConfigRenderer {
+    fn default() -> Self {
+        let mut template_engine = Handlebars::new();
+        template_engine.set_strict_mode(true); // enable throwing render errors if context data is valid
+
+        template_engine.register_helper("toJson", Box::new(UnescapedHelper(to_json_helper)));
+        template_engine.register_helper("base64", Box::new(UnescapedHelper(base64_helper)));
+        template_engine.register_helper("indent", Box::new(UnescapedHelper(indent_helper)));
+        template_engine.register_helper(
+            "env",
+            Box::new(UnescapedHelper(EnvHelper::new(vec![], read_env_variable))),
+        );
+        Self { template_engine }
+    }
+}
+
+impl ConfigRenderer {
+    // [impl->swdd~config-renderer-provides-template-helpers~2]
+    pub fn set_allowed_env_prefixes(&mut self, allowed_prefixes: Vec<String>) {
+        self.set_env_helper(EnvHelper::new(allowed_prefixes, read_env_variable));
+    }
+
+    // [impl->swdd~cli-provides-rendered-config-of-workload~2]
+    pub fn set_env_placeholders(&mut self) {
+        self.template_engine
+            .register_helper("env", Box::new(UnescapedHelper(EnvPlaceholderHelper)));
+    }
+
+    fn set_env_helper(&mut self, env_helper: EnvHelper) {
+        self.template_engine
+            .register_helper("env", Box::new(UnescapedHelper(env_helper)));
+    }
+
+    // [impl->swdd~config-renderer-renders-workload-configuration~3]
+    pub fn render_workloads(
+        &self,
+        workloads: &HashMap<String, StoredWorkloadSpec>,
+        configs: &HashMap<String, ConfigItem>,
+    ) -> Result<RenderedWorkloads, ConfigRenderError> {
+        let mut rendered_workloads = HashMap::new();
+        for (workload_name, stored_workload) in workloads {
+            let workload_spec = self.render_workload(workload_name, stored_workload, configs)?;
+
+            // [impl->swdd~config-renderer-creates-workload-replicas~1]
+            for replica_name in stored_workload.replica_names(workload_name) {
+                let mut replica = workload_spec.clone();
+                replica.instance_name = WorkloadInstanceName::new(
+                    workload_spec.instance_name.agent_name(),
+                    replica_name.clone(),
+                    workload_spec.instance_name.id(),
+                );
+                // [impl->swdd~config-renderer-expands-self-placeholder~1]
+                replica
+                    .control_interface_access
+                    .expand_self_placeholder(workload_name, &replica_name);
+                rendered_workloads.insert(replica_name, replica);
+            }
+        }
+        log::debug!("Rendered CompleteState: {:?}", rendered_workloads);
+        Ok(rendered_workloads)
+    }
+
+    // [impl->swdd~config-renderer-renders-workload-configuration~3]
+    pub fn render_workload(
+        &self,
+        workload_name: &str,
+        stored_workload: &StoredWorkloadSpec,
+        configs: &HashMap<String, ConfigItem>,
+    ) -> Result<WorkloadSpec, ConfigRenderError> {
+        if stored_workload.configs.is_empty() {
+            log::debug!(
+                "Skipping to render workload '{}' as no config is assigned to the workload",
+                workload_name
+            );
+            return Ok(WorkloadSpec::from((
+                workload_name.to_owned(),
+                stored_workload.clone(),
+            )));
+        }
+
+        let wl_config_map = self.create_config_map_for_workload(stored_workload, configs)?;
+        log::debug!(
+            "Rendering workload '{}' with config '{:?}'",
+            workload_name,
+            wl_config_map
+        );
+        self.render_workload_fields(workload_name, stored_workload, &wl_config_map)
+    }
+
+    // [impl->swdd~config-renderer-renders-workload-configuration~3]
+    fn create_config_map_for_workload<'a>(
+        &self,
+        workload_spec: &'a StoredWorkloadSpec,
+        configs: &'a HashMap<String, ConfigItem>,
+    ) -> Result<HashMap<&'a String, ConfigItem>, ConfigRenderError> {
+        let mut wl_config_map = HashMap::new();
+        for (config_alias, config_key) in &workload_spec.configs {
+            if let Some(config_value) = configs.get(config_key) {
+                // [impl->swdd~config-renderer-renders-secret-placeholders~1]
+                wl_config_map.insert(config_alias, config_value.with_secret_placeholders());
+            } else {
+                return Err(ConfigRenderError::NotExistingConfigKey(config_key.clone()));
+            }
+        }
+        Ok(wl_config_map)
+    }
+
+    // [impl->swdd~config-renderer-renders-workload-configuration~3]
+    fn render_workload_fields(
+        &self,
+        workload_name: &str,
+        workload: &StoredWorkloadSpec,
+        wl_config_map: &HashMap<&String, ConfigItem>,
+    ) -> Result<WorkloadSpec, ConfigRenderError> {
+        let rendered_runtime_config = self
+            .template_engine
+            .render_template(&workload.runtime_config, &wl_config_map)
+            .map_err(|err| ConfigRenderError::Field("runtimeConfig".to_owned(), err.to_string()))?;
+
+        let rendered_agent_name = self
+            .template_engine
+            .render_template(&workload.agent, &wl_config_map)
+            .map_err(|err| ConfigRenderError::Field("agent".to_owned(), err.to_string()))?;
+
+        let rendered_registry_auth = workload
+            .registry_auth
+            .as_ref()
+            .map(|registry_auth| {
+                self.template_engine
+                    .render_template(registry_auth, &wl_config_map)
+            })
+            .transpose()
+            .map_err(|err| ConfigRenderError::Field("registryAuth".to_owned(), err.to_string()))?;
+
+        let rendered_env = workload
+            .env
+            .iter()
+            .map(|(name, value)| {
+                self.template_engine
+                    .render_template(value, &wl_config_map)
+                    .map(|rendered_value| (name.clone(), rendered_value))
+                    .map_err(|err| {
+                        ConfigRenderError::Field(format!("env.{}", name), err.to_string())
+                    })
+            })
+            .collect::<Result<HashMap<String, String>, ConfigRenderError>>()?;
+
+        // The node selector is ignored for workloads with an agent, so only the workloads scheduled
+        // by the server keep it.
+        let node_selector = if rendered_agent_name.is_empty() {
+            workload.node_selector.clone()
+        } else {
+            HashMap::new()
+        };
+
+        Ok(WorkloadSpec {
+            instance_name: WorkloadInstanceName::builder()
+                .workload_name(workload_name)
+                .agent_name(rendered_agent_name)
+                .config(&rendered_runtime_config)
+                .build(),
+            runtime: workload.runtime.clone(),
+            runtime_config: rendered_runtime_config,
+            tags: workload.tags.clone(),
+            dependencies: workload.dependencies.clone(),
+            restart_policy: workload.restart_policy.clone().unwrap_or_default(),
+            control_interface_access: workload.control_interface_access.clone(),
+            hostname: workload.hostname.clone(),
+            extra_hosts: workload.extra_hosts.clone(),
+            ports: workload.ports.clone(),
+            resource_thresholds: workload.resource_thresholds.clone(),
+            resources: workload.resources.clone(),
+            health_check: workload.health_check.clone(),
+            restart_backoff: workload.restart_backoff.clone(),
+            priority: workload.priority,
+            volumes: workload.volumes.clone(),
+            registry_auth: rendered_registry_auth,
+            networks: workload.networks.clone(),
+            env: rendered_env,
+            on_agent_disconnect: workload.on_agent_disconnect,
+            restart_on: workload.restart_on.clone(),
+            lifecycle_hooks: workload.lifecycle_hooks.clone(),
+            node_selector,
+            state_debounce: workload.state_debounce,
+            security_context: workload.security_context.clone(),
+        })
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{ConfigRenderError, ConfigRenderer, EnvHelper, RenderedWorkloads};
+    use std::{collections::HashMap, env};
+
+    use crate::objects::{
+        generate_test_configs, generate_test_stored_workload_spec_with_config,
+        generate_test_workload_spec_with_runtime_config, AccessRightsRule, ConfigItem,
+        ControlInterfaceAccess, ReadWriteEnum, SecretProvider, SecretRef, StateRule,
+    };
+
+    const WORKLOAD_NAME_1: &str = "workload_1";
+    const AGENT_A: &str = "agent_A";
+    const RUNTIME: &str = "runtime";
+
+    // [utest->swdd~config-renderer-renders-workload-configuration~3]
+    #[test]
+    fn utest_render_workloads_render_required_fields_successfully() {
+        let templated_runtime_config =
+            "some_value_1: {{ref1.values.value_1}}\nsome_value_2: {{ref1.values.value_2.0}}";
+        let templated_agent_name = "{{ref1.agent_name}}";
+        let stored_workload = generate_test_stored_workload_spec_with_config(
+            templated_agent_name,
+            RUNTIME,
+            templated_runtime_config,
+        );
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let configs = generate_test_configs();
+        let renderer = ConfigRenderer::default();
+
+        let expected_workload_spec = generate_test_workload_spec_with_runtime_config(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+            "some_value_1: value123\nsome_value_2: list_value_1".to_owned(),
+        );
+
+        let result = renderer.render_workloads(&workloads, &configs);
+
+        assert_eq!(
+            Ok(RenderedWorkloads::from([(
+                WORKLOAD_NAME_1.to_owned(),
+                expected_workload_spec
+            )])),
+            result
+        );
+    }
+
+    // [utest->swdd~config-renderer-renders-workload-configuration~3]
+    #[test]
+    fn utest_render_workloads_renders_registry_auth() {
+        let mut stored_workload =
+            generate_test_stored_workload_spec_with_config(AGENT_A, RUNTIME, "image: alpine");
+        stored_workload.registry_auth =
+            Some(r#"{"auths": {"registry": {"auth": "{{ref1.values.value_1}}"}}}"#.to_owned());
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let configs = generate_test_configs();
+        let renderer = ConfigRenderer::default();
+
+        let result = renderer.render_workloads(&workloads, &configs).unwrap();
+
+        assert_eq!(
+            result[WORKLOAD_NAME_1].registry_auth,
+            Some(r#"{"auths": {"registry": {"auth": "value123"}}}"#.to_owned())
+        );
+    }
+
+    // [utest->swdd~config-renderer-renders-workload-configuration~3]
+    #[test]
+    fn utest_render_workloads_keeps_node_selector_only_without_agent() {
+        let node_selector = HashMap::from([("zone".to_owned(), "cockpit".to_owned())]);
+        let mut workload_with_agent =
+            generate_test_stored_workload_spec_with_config(AGENT_A, RUNTIME, "image: alpine");
+        workload_with_agent.node_selector = node_selector.clone();
+        let mut workload_without_agent =
+            generate_test_stored_workload_spec_with_config("", RUNTIME, "image: alpine");
+        workload_without_agent.node_selector = node_selector.clone();
+
+        let workloads = HashMap::from([
+            ("with_agent".to_owned(), workload_with_agent),
+            ("without_agent".to_owned(), workload_without_agent),
+        ]);
+        let configs = generate_test_configs();
+        let renderer = ConfigRenderer::default();
+
+        let result = renderer.render_workloads(&workloads, &configs).unwrap();
+
+        assert!(result["with_agent"].node_selector.is_empty());
+        assert_eq!(result["without_agent"].node_selector, node_selector);
+    }
+
+    // [utest->swdd~config-renderer-renders-workload-configuration~3]
+    #[test]
+    fn utest_render_workloads_renders_env() {
+        let mut stored_workload =
+            generate_test_stored_workload_spec_with_config(AGENT_A, RUNTIME, "image: alpine");
+        stored_workload.env = HashMap::from([
+            ("VALUE".to_owned(), "{{ref1.values.value_1}}".to_owned()),
+            ("LOG_LEVEL".to_owned(), "debug".to_owned()),
+        ]);
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let configs = generate_test_configs();
+        let renderer = ConfigRenderer::default();
+
+        let result = renderer.render_workloads(&workloads, &configs).unwrap();
+
+        assert_eq!(
+            result[WORKLOAD_NAME_1].env,
+            HashMap::from([
+                ("VALUE".to_owned(), "value123".to_owned()),
+                ("LOG_LEVEL".to_owned(), "debug".to_owned()),
+            ])
+        );
+    }
+
+    // [utest->swdd~config-renderer-renders-workload-configuration~3]
+    #[test]
+    fn utest_render_workloads_fails_env_contains_non_existing_config() {
+        let mut stored_workload =
+            generate_test_stored_workload_spec_with_config(AGENT_A, RUNTIME, "image: alpine");
+        stored_workload.env =
+            HashMap::from([("URL".to_owned(), "{{ref1.not_existing_key}}".to_owned())]);
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let configs = generate_test_configs();
+        let renderer = ConfigRenderer::default();
+
+        let result = renderer.render_workloads(&workloads, &configs);
+
+        assert!(
+            matches!(result.unwrap_err(), ConfigRenderError::Field(field, _) if field == "env.URL")
+        );
+    }
+
+    // [utest->swdd~config-renderer-renders-secret-placeholders~1]
+    #[test]
+    fn utest_render_workloads_renders_secret_placeholders() {
+        let stored_workload = generate_test_stored_workload_spec_with_config(
+            AGENT_A,
+            RUNTIME,
+            "password: {{ref1.password}}",
+        );
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let mut configs = generate_test_configs();
+        configs.insert(
+            "config_1".to_owned(),
+            ConfigItem::ConfigObject(HashMap::from([(
+                "password".to_owned(),
+                ConfigItem::SecretRef(SecretRef {
+                    provider: SecretProvider::Env,
+                    name: "DB_PASSWORD".to_owned(),
+                }),
+            )])),
+        );
+        let renderer = ConfigRenderer::default();
+
+        let result = renderer.render_workloads(&workloads, &configs).unwrap();
+
+        assert_eq!(
+            result[WORKLOAD_NAME_1].runtime_config,
+            "password: ${ankaios-secret:env:DB_PASSWORD}"
+        );
+    }
+
+    // [utest->swdd~config-renderer-creates-workload-replicas~1]
+    #[test]
+    fn utest_render_workloads_creates_replicas() {
+        let mut stored_workload =
+            generate_test_stored_workload_spec_with_config(AGENT_A, RUNTIME, "image: alpine");
+        stored_workload.replicas = Some(2);
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let configs = generate_test_configs();
+        let renderer = ConfigRenderer::default();
+
+        let result = renderer.render_workloads(&workloads, &configs).unwrap();
+
+        let replica_name = format!("{}-1", WORKLOAD_NAME_1);
+        assert_eq!(result.len(), 2);
+        assert_eq!(
+            result[WORKLOAD_NAME_1].instance_name.workload_name(),
+            WORKLOAD_NAME_1
+        );
+        assert_eq!(
+            result[&replica_name].instance_name.workload_name(),
+            replica_name
+        );
+        assert_eq!(
+            result[&replica_name].instance_name.id(),
+            result[WORKLOAD_NAME_1].instance_name.id()
+        );
+        assert_eq!(
+            result[&replica_name].runtime_config,
+            result[WORKLOAD_NAME_1].runtime_config
+        );
+    }
+
+    // [utest->swdd~config-renderer-expands-self-placeholder~1]
+    #[test]
+    fn utest_render_workloads_expands_self_placeholder_for_each_replica() {
+        let mut stored_workload =
+            generate_test_stored_workload_spec_with_config(AGENT_A, RUNTIME, "image: alpine");
+        stored_workload.replicas = Some(2);
+        stored_workload.control_interface_access = ControlInterfaceAccess {
+            allow_rules: vec![AccessRightsRule::StateRule(StateRule {
+                operation: ReadWriteEnum::Read,
+                filter_mask: vec![
+                    "desiredState.workloads.self".to_owned(),
+                    "workloadStates.*.self".to_owned(),
+                ],
+            })],
+            ..Default::default()
+        };
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let configs = generate_test_configs();
+        let renderer = ConfigRenderer::default();
+
+        let result = renderer.render_workloads(&workloads, &configs).unwrap();
+
+        let replica_name = format!("{}-1", WORKLOAD_NAME_1);
+        let expected_allow_rules = |name: &str| {
+            vec![AccessRightsRule::StateRule(StateRule {
+                operation: ReadWriteEnum::Read,
+                filter_mask: vec![
+                    format!("desiredState.workloads.{}", WORKLOAD_NAME_1),
+                    format!("workloadStates.*.{}", name),
+                ],
+            })]
+        };
+        assert_eq!(
+            result[WORKLOAD_NAME_1].control_interface_access.allow_rules,
+            expected_allow_rules(WORKLOAD_NAME_1)
+        );
+        assert_eq!(
+            result[&replica_name].control_interface_access.allow_rules,
+            expected_allow_rules(&replica_name)
+        );
+    }
+
+    // [utest->swdd~config-renderer-renders-workload-configuration~3]
+    #[test]
+    fn utest_render_workloads_fails_field_uses_config_key_instead_of_alias() {
+        let templated_runtime_config = "config_1: {{config_1.values.value_1}}";
+        let stored_workload = generate_test_stored_workload_spec_with_config(
+            AGENT_A,
+            RUNTIME,
+            templated_runtime_config,
+        );
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let configs = generate_test_configs();
+        let renderer = ConfigRenderer::default();
+
+        assert!(renderer.render_workloads(&workloads, &configs).is_err());
+    }
+
+    // [utest->swdd~config-renderer-renders-workload-configuration~3]
+    #[test]
+    fn utest_render_workloads_not_rendering_workloads_with_no_referenced_configs() {
+        let templated_runtime_config = "config_1: {{config_1.values.value_1}}";
+        let templated_agent_name = "{{config_1.agent_name}}";
+        let mut stored_workload = generate_test_stored_workload_spec_with_config(
+            templated_agent_name,
+            RUNTIME,
+            templated_runtime_config,
+        );
+
+        stored_workload.configs.clear(); // no configs assigned
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let configs = generate_test_configs();
+        let renderer = ConfigRenderer::default();
+
+        let expected_workload_spec = generate_test_workload_spec_with_runtime_config(
+            templated_agent_name.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME.to_owned(),
+            templated_runtime_config.to_owned(),
+        );
+
+        let result = renderer.render_workloads(&workloads, &configs);
+
+        assert_eq!(
+            Ok(RenderedWorkloads::from([(
+                WORKLOAD_NAME_1.to_owned(),
+                expected_workload_spec
+            )])),
+            result
+        );
+    }
+
+    // [utest->swdd~config-renderer-renders-workload-configuration~3]
+    #[test]
+    fn utest_render_workloads_fails_workload_references_not_existing_config_key() {
+        let templated_runtime_config = "config_1: {{ref1.values.value_1}}";
+        let mut stored_workload = generate_test_stored_workload_spec_with_config(
+            AGENT_A,
+            RUNTIME,
+            templated_runtime_config,
+        );
+
+        stored_workload.configs =
+            HashMap::from([("ref1".to_owned(), "not_existing_config_key".to_owned())]);
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let configs = generate_test_configs();
+        let renderer = ConfigRenderer::default();
+        let result = renderer.render_workloads(&workloads, &configs);
+        assert!(result.is_err());
+        assert!(
+            matches!(result.unwrap_err(), ConfigRenderError::NotExistingConfigKey(config_key) if config_key == "not_existing_config_key")
+        );
+    }
+
+    // [utest->swdd~config-renderer-renders-workload-configuration~3]
+    #[test]
+    fn utest_render_workloads_fails_workload_references_unused_not_existing_config_key() {
+        let mut stored_workload =
+            generate_test_stored_workload_spec_with_config(AGENT_A, RUNTIME, "some runtime config");
+
+        stored_workload.configs = HashMap::from([(
+            "ref1".to_owned(),
+            "not_existing_unused_config_key".to_owned(),
+        )]);
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let configs = generate_test_configs();
+        let renderer = ConfigRenderer::default();
+        let result = renderer.render_workloads(&workloads, &configs);
+        assert!(result.is_err());
+        assert!(
+            matches!(result.unwrap_err(), ConfigRenderError::NotExistingConfigKey(config_key) if config_key == "not_existing_unused_config_key")
+        );
+    }
+
+    // [utest->swdd~config-renderer-renders-workload-configuration~3]
+    #[test]
+    fn utest_render_workloads_fails_runtime_config_contains_non_existing_config() {
+        let templated_runtime_config = "config_1: {{config_1.values.not_existing_key}}";
+        let stored_workload = generate_test_stored_workload_spec_with_config(
+            AGENT_A,
+            RUNTIME,
+            templated_runtime_config,
+        );
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let configs = generate_test_configs();
+        let renderer = ConfigRenderer::default();
+
+        let result = renderer.render_workloads(&workloads, &configs);
+
+        assert!(result.is_err());
+        assert!(
+            matches!(result.unwrap_err(), ConfigRenderError::Field(field, _) if field == "runtimeConfig")
+        );
+    }
+
+    // [utest->swdd~config-renderer-renders-workload-configuration~3]
+    #[test]
+    fn utest_render_workloads_fails_agent_contains_non_existing_config() {
+        let stored_workload = generate_test_stored_workload_spec_with_config(
+            "{{config_1.not_existing_key}}",
+            RUNTIME,
+            "some runtime config",
+        );
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let configs = generate_test_configs();
+        let renderer = ConfigRenderer::default();
+
+        let result = renderer.render_workloads(&workloads, &configs);
+
+        assert!(result.is_err());
+        assert!(
+            matches!(result.unwrap_err(), ConfigRenderError::Field(field, _) if field == "agent")
+        );
+    }
+
+    fn lookup_test_env_variable(variable_name: &str) -> Result<String, env::VarError> {
+        match variable_name {
+            "APP_HOST" => Ok("from_env".to_owned()),
+            "APP_MARKUP" => Ok("<a&b>".to_owned()),
+            "SECRET_TOKEN" => Ok("secret".to_owned()),
+            _ => Err(env::VarError::NotPresent),
+        }
+    }
+
+    fn renderer_with_test_env(allowed_prefixes: &[&str]) -> ConfigRenderer {
+        let mut renderer = ConfigRenderer::default();
+        renderer.set_env_helper(EnvHelper::new(
+            allowed_prefixes.iter().map(|x| x.to_string()).collect(),
+            lookup_test_env_variable,
+        ));
+        renderer
+    }
+
+    // [utest->swdd~config-renderer-provides-template-helpers~2]
+    #[test]
+    fn utest_render_workloads_with_template_helpers() {
+        let templated_runtime_config = concat!(
+            "json: {{toJson ref1.values}}\n",
+            "encoded: {{base64 ref1.values.value_1}}\n",
+            "list:\n{{indent 2 ref2}}\n",
+            "env: {{env \"APP_HOST\"}}\n",
+            "{{#each ref1.values.value_2}}item: {{this}}\n{{/each}}"
+        );
+        let stored_workload = generate_test_stored_workload_spec_with_config(
+            AGENT_A,
+            RUNTIME,
+            templated_runtime_config,
+        );
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let mut configs = generate_test_configs();
+        configs.insert(
+            "config_2".to_owned(),
+            ConfigItem::String("- a\n- b".to_owned()),
+        );
+        let renderer = renderer_with_test_env(&["APP_"]);
+
+        let result = renderer.render_workloads(&workloads, &configs).unwrap();
+
+        assert_eq!(
+            result[WORKLOAD_NAME_1].runtime_config,
+            concat!(
+                "json: {\"value_1\":\"value123\",\"value_2\":[\"list_value_1\",\"list_value_2\"]}\n",
+                "encoded: dmFsdWUxMjM=\n",
+                "list:\n  - a\n  - b\n",
+                "env: from_env\n",
+                "item: list_value_1\nitem: list_value_2\n"
+            )
+        );
+    }
+
+    // [utest->swdd~cli-provides-rendered-config-of-workload~2]
+    #[test]
+    fn utest_render_workloads_with_env_placeholders() {
+        let stored_workload = generate_test_stored_workload_spec_with_config(
+            AGENT_A,
+            RUNTIME,
+            "host: {{env \"APP_HOST\"}}, encoded: {{base64 (env \"APP_HOST\")}}",
+        );
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let mut renderer = ConfigRenderer::default();
+        renderer.set_env_placeholders();
+
+        let result = renderer
+            .render_workloads(&workloads, &generate_test_configs())
+            .unwrap();
+
+        assert_eq!(
+            result[WORKLOAD_NAME_1].runtime_config,
+            "host: ${server-env:APP_HOST}, encoded: JHtzZXJ2ZXItZW52OkFQUF9IT1NUfQ=="
+        );
+    }
+
+    // [utest->swdd~config-renderer-provides-template-helpers~2]
+    #[test]
+    fn utest_render_workloads_escapes_values_but_not_helper_output() {
+        let stored_workload = generate_test_stored_workload_spec_with_config(
+            AGENT_A,
+            RUNTIME,
+            "plain: {{ref2}}\njson: {{toJson ref2}}\nenv: {{env \"APP_MARKUP\"}}",
+        );
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let mut configs = generate_test_configs();
+        configs.insert(
+            "config_2".to_owned(),
+            ConfigItem::String("<a&b>".to_owned()),
+        );
+        let renderer = renderer_with_test_env(&["APP_"]);
+
+        let result = renderer.render_workloads(&workloads, &configs).unwrap();
+
+        assert_eq!(
+            result[WORKLOAD_NAME_1].runtime_config,
+            "plain: &lt;a&amp;b&gt;\njson: \"<a&b>\"\nenv: <a&b>"
+        );
+    }
+
+    // [utest->swdd~config-renderer-provides-template-helpers~2]
+    #[test]
+    fn utest_render_workloads_fails_on_missing_environment_variable() {
+        let stored_workload = generate_test_stored_workload_spec_with_config(
+            AGENT_A,
+            RUNTIME,
+            "value: {{env \"APP_NOT_EXISTING\"}}",
+        );
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let configs = generate_test_configs();
+        let renderer = renderer_with_test_env(&["APP_"]);
+
+        let result = renderer.render_workloads(&workloads, &configs);
+
+        assert!(
+            matches!(result.unwrap_err(), ConfigRenderError::Field(field, _) if field == "runtimeConfig")
+        );
+    }
+
+    // [utest->swdd~config-renderer-provides-template-helpers~2]
+    #[test]
+    fn utest_render_workloads_fails_on_environment_variable_without_allowed_prefix() {
+        let stored_workload = generate_test_stored_workload_spec_with_config(
+            AGENT_A,
+            RUNTIME,
+            "value: {{env \"SECRET_TOKEN\"}}",
+        );
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let configs = generate_test_configs();
+        let renderer = renderer_with_test_env(&["APP_"]);
+
+        let result = renderer.render_workloads(&workloads, &configs);
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ConfigRenderError::Field(field, reason)
+                if field == "runtimeConfig" && reason.contains("Reading environment variable 'SECRET_TOKEN' is not allowed")
+        ));
+    }
+
+    // [utest->swdd~config-renderer-provides-template-helpers~2]
+    #[test]
+    fn utest_render_workloads_fails_on_environment_variable_by_default() {
+        let stored_workload = generate_test_stored_workload_spec_with_config(
+            AGENT_A,
+            RUNTIME,
+            "value: {{env \"PATH\"}}",
+        );
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let configs = generate_test_configs();
+        let renderer = ConfigRenderer::default();
+
+        let result = renderer.render_workloads(&workloads, &configs);
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ConfigRenderError::Field(field, reason)
+                if field == "runtimeConfig" && reason.contains("Reading environment variable 'PATH' is not allowed")
+        ));
+    }
+
+    // [utest->swdd~config-renderer-renders-workload-configuration~3]
+    #[test]
+    fn utest_render_workloads_fails_workload_references_empty_configs() {
+        let templated_runtime_config = "config_1: {{config_1.values.value_1}}";
+        let stored_workload = generate_test_stored_workload_spec_with_config(
+            AGENT_A,
+            RUNTIME,
+            templated_runtime_config,
+        );
+
+        let workloads = HashMap::from([(WORKLOAD_NAME_1.to_owned(), stored_workload)]);
+        let configs = HashMap::default();
+        let renderer = ConfigRenderer::default();
+
+        assert!(renderer.render_workloads(&workloads, &configs).is_err());
+    }
+}
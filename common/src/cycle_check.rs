@@ -11,7 +11,7 @@
 // under the License.
 //
 // SPDX-License-Identifier: Apache-2.0
-use common::objects::State;
+use crate::objects::State;
 use std::collections::{HashSet, VecDeque};
 
 /// Returns an Option containing the workload dependency that is part of a cycle
@@ -24,7 +24,7 @@ use std::collections::{HashSet, VecDeque};
 ///
 /// * `state` - The State with workloads representing the directed graph to check for a cycle
 /// * `start_nodes` - Start visiting the graph only for the passed workloads
-///                   if [`None`] the search is started from all workloads of the state
+///   if [`None`] the search is started from all workloads of the state
 ///
 pub fn dfs(state: &State, start_nodes: Option<Vec<&str>>) -> Option<String> {
     log::trace!(
@@ -82,7 +82,13 @@ pub fn dfs(state: &State, start_nodes: Option<Vec<&str>>) -> Option<String> {
                 }
 
                 // sort the map to have an constant equal outcome
-                let mut dependencies: Vec<&String> = workload_spec.dependencies.keys().collect();
+                // [impl->swdd~cycle-detection-ignores-config-dependencies~1]
+                let mut dependencies: Vec<&String> = workload_spec
+                    .dependencies
+                    .iter()
+                    .filter(|(_, add_condition)| !add_condition.refers_to_config())
+                    .map(|(dependency, _)| dependency)
+                    .collect();
                 dependencies.sort();
 
                 for dependency in dependencies {
@@ -117,7 +123,7 @@ pub fn dfs(state: &State, start_nodes: Option<Vec<&str>>) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use common::{
+    use crate::{
         objects::{generate_test_stored_workload_spec, AddCondition},
         test_utils::generate_test_complete_state,
     };
@@ -526,6 +532,22 @@ mod tests {
         assert_no_cycle!(builder, &workloads);
     }
 
+    // [utest->swdd~cycle-detection-ignores-config-dependencies~1]
+    /// A config dependency named like a workload does not close the cycle A -> B -> A.
+    #[test]
+    fn utest_detect_no_cycle_over_config_dependencies() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let workloads = ["A", "B"];
+
+        let builder = StateBuilder::default()
+            .with_workloads(&workloads)
+            .workload_dependency("A", "B", AddCondition::AddCondRunning)
+            .workload_dependency("B", "A", AddCondition::AddCondConfigChanged);
+
+        assert_no_cycle!(builder, &workloads);
+    }
+
     /// Graph visualized: https://dreampuf.github.io/GraphvizOnline/#digraph%20%7B%0A%20%20%20%20A%20-%3E%20D%3B%0A%20%20%20%20B%20-%3E%20D%3B%0A%20%20%20%20B%20-%3E%20E%3B%0A%20%20%20%20C%20-%3E%20E%3B%0A%20%20%20%20C%20-%3E%20H%3B%0A%20%20%20%20D%20-%3E%20F%3B%0A%20%20%20%20D%20-%3E%20G%3B%0A%20%20%20%20D%20-%3E%20H%3B%0A%7D
     #[test]
     fn utest_detect_no_cycle_in_dependencies_1() {
@@ -39,7 +39,14 @@ pub enum FromServer {
     UpdateWorkload(commands::UpdateWorkload),
     UpdateWorkloadState(commands::UpdateWorkloadState),
     Response(ank_base::Response),
+    ExecRequest(commands::AgentExecRequest),
+    PortForwardRequest(commands::AgentPortForwardRequest),
+    RestartWorkloadRequest(commands::AgentRestartWorkloadRequest),
+    PauseWorkloadRequest(commands::AgentPauseWorkloadRequest),
+    PullImages(commands::PullImages),
     Stop(commands::Stop),
+    // Sent by the communication middleware of the agent and not by the server itself
+    ServerConnectionLost(commands::ServerConnectionLost),
 }
 
 // [impl->swdd~from-server-channel~1]
@@ -60,6 +67,36 @@ pub trait FromServerInterface {
         workload_running: Vec<WorkloadState>,
     ) -> Result<(), FromServerInterfaceError>;
     async fn response(&self, response: ank_base::Response) -> Result<(), FromServerInterfaceError>;
+    async fn exec_request(
+        &self,
+        agent_name: Option<String>,
+        request_id: String,
+        exec_request: commands::ExecRequest,
+    ) -> Result<(), FromServerInterfaceError>;
+    async fn port_forward_request(
+        &self,
+        agent_name: Option<String>,
+        request_id: String,
+        port_forward_request: commands::PortForwardRequest,
+    ) -> Result<(), FromServerInterfaceError>;
+    async fn restart_workload_request(
+        &self,
+        agent_name: Option<String>,
+        request_id: String,
+        workload_names: Vec<String>,
+    ) -> Result<(), FromServerInterfaceError>;
+    async fn pause_workload_request(
+        &self,
+        agent_name: Option<String>,
+        request_id: String,
+        workload_names: Vec<String>,
+        resume: bool,
+    ) -> Result<(), FromServerInterfaceError>;
+    async fn pull_images(
+        &self,
+        agent_name: Option<String>,
+        images: Vec<String>,
+    ) -> Result<(), FromServerInterfaceError>;
     async fn complete_state(
         &self,
         request_id: String,
@@ -77,6 +114,7 @@ pub trait FromServerInterface {
         message: String,
     ) -> Result<(), FromServerInterfaceError>;
     async fn stop(&self) -> Result<(), FromServerInterfaceError>;
+    async fn server_connection_lost(&self) -> Result<(), FromServerInterfaceError>;
 }
 
 pub type FromServerSender = tokio::sync::mpsc::Sender<FromServer>;
@@ -126,6 +164,92 @@ impl FromServerInterface for FromServerSender {
         Ok(self.send(FromServer::Response(response)).await?)
     }
 
+    async fn exec_request(
+        &self,
+        // The agent name is only used by the server to route the request
+        agent_name: Option<String>,
+        request_id: String,
+        exec_request: commands::ExecRequest,
+    ) -> Result<(), FromServerInterfaceError> {
+        Ok(self
+            .send(FromServer::ExecRequest(commands::AgentExecRequest {
+                agent_name,
+                request_id,
+                exec_request,
+            }))
+            .await?)
+    }
+
+    async fn port_forward_request(
+        &self,
+        // The agent name is only used by the server to route the request
+        agent_name: Option<String>,
+        request_id: String,
+        port_forward_request: commands::PortForwardRequest,
+    ) -> Result<(), FromServerInterfaceError> {
+        Ok(self
+            .send(FromServer::PortForwardRequest(
+                commands::AgentPortForwardRequest {
+                    agent_name,
+                    request_id,
+                    port_forward_request,
+                },
+            ))
+            .await?)
+    }
+
+    async fn restart_workload_request(
+        &self,
+        // The agent name is only used by the server to route the request
+        agent_name: Option<String>,
+        request_id: String,
+        workload_names: Vec<String>,
+    ) -> Result<(), FromServerInterfaceError> {
+        Ok(self
+            .send(FromServer::RestartWorkloadRequest(
+                commands::AgentRestartWorkloadRequest {
+                    agent_name,
+                    request_id,
+                    workload_names,
+                },
+            ))
+            .await?)
+    }
+
+    async fn pause_workload_request(
+        &self,
+        // The agent name is only used by the server to route the request
+        agent_name: Option<String>,
+        request_id: String,
+        workload_names: Vec<String>,
+        resume: bool,
+    ) -> Result<(), FromServerInterfaceError> {
+        Ok(self
+            .send(FromServer::PauseWorkloadRequest(
+                commands::AgentPauseWorkloadRequest {
+                    agent_name,
+                    request_id,
+                    workload_names,
+                    resume,
+                },
+            ))
+            .await?)
+    }
+
+    async fn pull_images(
+        &self,
+        // The agent name is only used by the server to route the images to the agent
+        agent_name: Option<String>,
+        images: Vec<String>,
+    ) -> Result<(), FromServerInterfaceError> {
+        Ok(self
+            .send(FromServer::PullImages(commands::PullImages {
+                agent_name,
+                images,
+            }))
+            .await?)
+    }
+
     async fn complete_state(
         &self,
         request_id: String,
@@ -134,9 +258,9 @@ impl FromServerInterface for FromServerSender {
         Ok(self
             .send(FromServer::Response(ank_base::Response {
                 request_id,
-                response_content: ank_base::response::ResponseContent::CompleteState(
+                response_content: ank_base::response::ResponseContent::CompleteState(Box::new(
                     complete_state,
-                )
+                ))
                 .into(),
             }))
             .await?)
@@ -181,6 +305,14 @@ impl FromServerInterface for FromServerSender {
     async fn stop(&self) -> Result<(), FromServerInterfaceError> {
         Ok(self.send(FromServer::Stop(commands::Stop {})).await?)
     }
+
+    async fn server_connection_lost(&self) -> Result<(), FromServerInterfaceError> {
+        Ok(self
+            .send(FromServer::ServerConnectionLost(
+                commands::ServerConnectionLost {},
+            ))
+            .await?)
+    }
 }
 
 //////////////////////////////////////////////////////////////////////////////
@@ -271,7 +403,7 @@ mod tests {
             FromServer::Response(ank_base::Response {
                 request_id: REQUEST_ID.to_string(),
                 response_content: Some(ank_base::response::ResponseContent::CompleteState(
-                    complete_state
+                    Box::new(complete_state)
                 )),
             })
         )
@@ -330,4 +462,137 @@ mod tests {
             })
         )
     }
+
+    // [utest->swdd~from-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_exec_request() {
+        let (tx, mut rx): (FromServerSender, FromServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let exec_request = commands::ExecRequest::Input(commands::ExecInput {
+            stdin: b"ls\n".to_vec(),
+            close_stdin: false,
+        });
+        assert!(tx
+            .exec_request(
+                Some(AGENT_NAME.to_string()),
+                REQUEST_ID.to_string(),
+                exec_request.clone()
+            )
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            FromServer::ExecRequest(commands::AgentExecRequest {
+                agent_name: Some(AGENT_NAME.to_string()),
+                request_id: REQUEST_ID.to_string(),
+                exec_request,
+            })
+        )
+    }
+
+    // [utest->swdd~from-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_port_forward_request() {
+        let (tx, mut rx): (FromServerSender, FromServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let port_forward_request = commands::PortForwardRequest::Data(commands::PortForwardData {
+            data: b"GET /".to_vec(),
+            close: false,
+        });
+        assert!(tx
+            .port_forward_request(
+                Some(AGENT_NAME.to_string()),
+                REQUEST_ID.to_string(),
+                port_forward_request.clone()
+            )
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            FromServer::PortForwardRequest(commands::AgentPortForwardRequest {
+                agent_name: Some(AGENT_NAME.to_string()),
+                request_id: REQUEST_ID.to_string(),
+                port_forward_request,
+            })
+        )
+    }
+
+    // [utest->swdd~from-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_restart_workload_request() {
+        let (tx, mut rx): (FromServerSender, FromServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let workload_names = vec!["nginx".to_string(), "nginx-1".to_string()];
+        assert!(tx
+            .restart_workload_request(
+                Some(AGENT_NAME.to_string()),
+                REQUEST_ID.to_string(),
+                workload_names.clone()
+            )
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            FromServer::RestartWorkloadRequest(commands::AgentRestartWorkloadRequest {
+                agent_name: Some(AGENT_NAME.to_string()),
+                request_id: REQUEST_ID.to_string(),
+                workload_names,
+            })
+        )
+    }
+
+    // [utest->swdd~from-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_pause_workload_request() {
+        let (tx, mut rx): (FromServerSender, FromServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let workload_names = vec!["nginx".to_string()];
+        assert!(tx
+            .pause_workload_request(
+                Some(AGENT_NAME.to_string()),
+                REQUEST_ID.to_string(),
+                workload_names.clone(),
+                false
+            )
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            FromServer::PauseWorkloadRequest(commands::AgentPauseWorkloadRequest {
+                agent_name: Some(AGENT_NAME.to_string()),
+                request_id: REQUEST_ID.to_string(),
+                workload_names,
+                resume: false,
+            })
+        )
+    }
+
+    // [utest->swdd~from-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_pull_images() {
+        let (tx, mut rx): (FromServerSender, FromServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let images = vec!["ghcr.io/eclipse-ankaios/tests/nginx:alpine-slim".to_string()];
+        assert!(tx
+            .pull_images(Some(AGENT_NAME.to_string()), images.clone())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            FromServer::PullImages(commands::PullImages {
+                agent_name: Some(AGENT_NAME.to_string()),
+                images,
+            })
+        )
+    }
 }
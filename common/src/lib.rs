@@ -22,9 +22,14 @@ pub mod commands;
 pub mod communications_client;
 pub mod communications_error;
 pub mod communications_server;
+pub mod config_renderer;
+pub mod cycle_check;
 pub mod from_server_interface;
 pub mod helpers;
 pub use helpers::check_version_compatibility;
+#[cfg(feature = "loopback")]
+pub mod loopback_communications;
+pub mod metrics_endpoint;
 pub mod objects;
 pub mod request_id_prepending;
 pub mod state_manipulation;
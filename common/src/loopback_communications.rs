@@ -0,0 +1,508 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    commands,
+    communications_client::CommunicationsClient,
+    communications_error::CommunicationMiddlewareError,
+    communications_server::{CommunicationsServer, ServerAddress},
+    from_server_interface::{FromServer, FromServerReceiver, FromServerSender},
+    objects::get_workloads_per_agent,
+    request_id_prepending::{detach_prefix_from_request_id, prepend_request_id},
+    to_server_interface::{ToServer, ToServerInterface, ToServerReceiver, ToServerSender},
+};
+
+type AgentSenders = Arc<Mutex<HashMap<String, FromServerSender>>>;
+
+fn get_agent_sender(agent_senders: &AgentSenders, agent_name: &str) -> Option<FromServerSender> {
+    agent_senders
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(agent_name)
+        .cloned()
+}
+
+fn get_all_agent_names(agent_senders: &AgentSenders) -> Vec<String> {
+    agent_senders
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .keys()
+        .cloned()
+        .collect()
+}
+
+async fn send_to_agent(agent_senders: &AgentSenders, agent_name: &str, message: FromServer) {
+    if let Some(sender) = get_agent_sender(agent_senders, agent_name) {
+        if sender.send(message).await.is_err() {
+            log::warn!("Could not send message to agent '{}'", agent_name);
+        }
+    } else {
+        log::info!(
+            "Agent '{}' not connected to the loopback server, message not sent.",
+            agent_name
+        );
+    }
+}
+
+/// Communications server that routes the `FromServer` messages over channels to
+/// the [LoopbackCommunicationsClient]s created by it, instead of using a network connection.
+///
+/// The messages are distributed to the agents in the same way as done by the gRPC middleware,
+/// which allows running the server and the agent logic together in tests.
+pub struct LoopbackCommunicationsServer {
+    agent_senders: AgentSenders,
+    to_server: ToServerSender,
+}
+
+impl LoopbackCommunicationsServer {
+    pub fn new(to_server: ToServerSender) -> Self {
+        Self {
+            agent_senders: Arc::default(),
+            to_server,
+        }
+    }
+
+    pub fn new_agent_communication(&self, agent_name: &str) -> LoopbackCommunicationsClient {
+        LoopbackCommunicationsClient {
+            name: agent_name.to_owned(),
+            agent_senders: self.agent_senders.clone(),
+            to_server: self.to_server.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl CommunicationsServer for LoopbackCommunicationsServer {
+    async fn start(
+        &mut self,
+        mut receiver: FromServerReceiver,
+        _addr: ServerAddress,
+    ) -> Result<(), CommunicationMiddlewareError> {
+        while let Some(message) = receiver.recv().await {
+            match message {
+                FromServer::ServerHello(server_hello) => {
+                    let agent_name = server_hello.agent_name.clone().unwrap_or_default();
+                    send_to_agent(
+                        &self.agent_senders,
+                        &agent_name,
+                        FromServer::ServerHello(server_hello),
+                    )
+                    .await;
+                }
+                FromServer::UpdateWorkload(update_workload) => {
+                    for (agent_name, (added_workloads, deleted_workloads)) in
+                        get_workloads_per_agent(
+                            update_workload.added_workloads,
+                            update_workload.deleted_workloads,
+                        )
+                    {
+                        send_to_agent(
+                            &self.agent_senders,
+                            &agent_name,
+                            FromServer::UpdateWorkload(commands::UpdateWorkload {
+                                added_workloads,
+                                deleted_workloads,
+                            }),
+                        )
+                        .await;
+                    }
+                }
+                FromServer::UpdateWorkloadState(update_workload_state) => {
+                    for agent_name in get_all_agent_names(&self.agent_senders) {
+                        // an agent does not get its own workload states back
+                        let workload_states: Vec<_> = update_workload_state
+                            .workload_states
+                            .iter()
+                            .filter(|state| state.instance_name.agent_name() != agent_name)
+                            .cloned()
+                            .collect();
+                        if !workload_states.is_empty() {
+                            send_to_agent(
+                                &self.agent_senders,
+                                &agent_name,
+                                FromServer::UpdateWorkloadState(commands::UpdateWorkloadState {
+                                    workload_states,
+                                }),
+                            )
+                            .await;
+                        }
+                    }
+                }
+                FromServer::Response(mut response) => {
+                    let (agent_name, request_id) =
+                        detach_prefix_from_request_id(&response.request_id);
+                    response.request_id = request_id;
+                    send_to_agent(
+                        &self.agent_senders,
+                        &agent_name,
+                        FromServer::Response(response),
+                    )
+                    .await;
+                }
+                FromServer::ExecRequest(exec_request) => {
+                    let agent_name = exec_request.agent_name.clone().unwrap_or_default();
+                    send_to_agent(
+                        &self.agent_senders,
+                        &agent_name,
+                        FromServer::ExecRequest(exec_request),
+                    )
+                    .await;
+                }
+                FromServer::PortForwardRequest(port_forward_request) => {
+                    let agent_name = port_forward_request.agent_name.clone().unwrap_or_default();
+                    send_to_agent(
+                        &self.agent_senders,
+                        &agent_name,
+                        FromServer::PortForwardRequest(port_forward_request),
+                    )
+                    .await;
+                }
+                FromServer::RestartWorkloadRequest(restart_workload_request) => {
+                    let agent_name = restart_workload_request
+                        .agent_name
+                        .clone()
+                        .unwrap_or_default();
+                    send_to_agent(
+                        &self.agent_senders,
+                        &agent_name,
+                        FromServer::RestartWorkloadRequest(restart_workload_request),
+                    )
+                    .await;
+                }
+                FromServer::PauseWorkloadRequest(pause_workload_request) => {
+                    let agent_name = pause_workload_request
+                        .agent_name
+                        .clone()
+                        .unwrap_or_default();
+                    send_to_agent(
+                        &self.agent_senders,
+                        &agent_name,
+                        FromServer::PauseWorkloadRequest(pause_workload_request),
+                    )
+                    .await;
+                }
+                FromServer::PullImages(pull_images) => {
+                    let agent_name = pull_images.agent_name.clone().unwrap_or_default();
+                    send_to_agent(
+                        &self.agent_senders,
+                        &agent_name,
+                        FromServer::PullImages(pull_images),
+                    )
+                    .await;
+                }
+                FromServer::ServerConnectionLost(_) => {}
+                FromServer::Stop(_) => {
+                    log::debug!("Loopback communications server stopped.");
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Communications client of an agent connected to a [LoopbackCommunicationsServer].
+pub struct LoopbackCommunicationsClient {
+    name: String,
+    agent_senders: AgentSenders,
+    to_server: ToServerSender,
+}
+
+impl LoopbackCommunicationsClient {
+    async fn forward_to_server(
+        &self,
+        message: ToServer,
+    ) -> Result<(), CommunicationMiddlewareError> {
+        let message = match message {
+            ToServer::Request(mut request) => {
+                request.request_id = prepend_request_id(&request.request_id, &self.name);
+                ToServer::Request(request)
+            }
+            message => message,
+        };
+        self.to_server
+            .send(message)
+            .await
+            .map_err(|err| CommunicationMiddlewareError(err.to_string()))
+    }
+}
+
+#[async_trait]
+impl CommunicationsClient for LoopbackCommunicationsClient {
+    async fn run(
+        &mut self,
+        mut receiver: ToServerReceiver,
+        manager_interface: FromServerSender,
+    ) -> Result<(), CommunicationMiddlewareError> {
+        self.agent_senders
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(self.name.clone(), manager_interface);
+
+        let mut result = self
+            .to_server
+            .agent_hello(commands::AgentHello {
+                agent_name: self.name.clone(),
+                ..Default::default()
+            })
+            .await
+            .map_err(|err| CommunicationMiddlewareError(err.to_string()));
+
+        if result.is_ok() {
+            while let Some(message) = receiver.recv().await {
+                // the agent stops its connection in the same way as for the gRPC middleware
+                if let ToServer::Goodbye(_) | ToServer::Stop(_) = message {
+                    break;
+                }
+                result = self.forward_to_server(message).await;
+                if result.is_err() {
+                    break;
+                }
+            }
+        }
+
+        self.agent_senders
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(&self.name);
+        // the server could already be gone, nobody to inform in that case
+        let _ = self.to_server.agent_gone(self.name.clone()).await;
+        result
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use api::ank_base;
+    use tokio::{
+        sync::mpsc::{channel, Receiver},
+        task::JoinHandle,
+    };
+
+    use super::{LoopbackCommunicationsClient, LoopbackCommunicationsServer};
+    use crate::{
+        commands,
+        communications_client::CommunicationsClient,
+        communications_error::CommunicationMiddlewareError,
+        communications_server::CommunicationsServer,
+        from_server_interface::{
+            FromServer, FromServerInterface, FromServerReceiver, FromServerSender,
+        },
+        objects::{
+            generate_test_workload_spec_with_param, generate_test_workload_state_with_agent,
+            ExecutionState,
+        },
+        to_server_interface::{ToServer, ToServerInterface, ToServerSender},
+        CHANNEL_CAPACITY, DEFAULT_SOCKET_ADDRESS,
+    };
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+    const WORKLOAD_NAME: &str = "workload_1";
+    const RUNTIME_NAME: &str = "runtime";
+
+    async fn connect_agent(
+        server: &LoopbackCommunicationsServer,
+        server_receiver: &mut Receiver<ToServer>,
+        agent_name: &str,
+    ) -> (
+        ToServerSender,
+        FromServerReceiver,
+        JoinHandle<Result<(), CommunicationMiddlewareError>>,
+    ) {
+        let mut client: LoopbackCommunicationsClient = server.new_agent_communication(agent_name);
+        let (to_server, to_server_receiver) = channel(CHANNEL_CAPACITY);
+        let (from_server, from_server_receiver) = channel(CHANNEL_CAPACITY);
+        let client_task =
+            tokio::spawn(async move { client.run(to_server_receiver, from_server).await });
+
+        assert_eq!(
+            server_receiver.recv().await,
+            Some(ToServer::AgentHello(commands::AgentHello {
+                agent_name: agent_name.to_owned(),
+                ..Default::default()
+            }))
+        );
+        (to_server, from_server_receiver, client_task)
+    }
+
+    #[tokio::test]
+    async fn utest_loopback_communications_distributes_messages_to_agents() {
+        let (server_sender, mut server_receiver) = channel(CHANNEL_CAPACITY);
+        let mut server = LoopbackCommunicationsServer::new(server_sender);
+        let (_to_server_a, mut agent_a_receiver, _client_a) =
+            connect_agent(&server, &mut server_receiver, AGENT_A).await;
+        let (_to_server_b, mut agent_b_receiver, _client_b) =
+            connect_agent(&server, &mut server_receiver, AGENT_B).await;
+
+        let (from_server, from_server_receiver) = channel(CHANNEL_CAPACITY);
+        let workload_spec = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME.to_owned(),
+            RUNTIME_NAME.to_owned(),
+        );
+        let workload_state = generate_test_workload_state_with_agent(
+            WORKLOAD_NAME,
+            AGENT_A,
+            ExecutionState::running(),
+        );
+        from_server
+            .server_hello(Some(AGENT_B.to_owned()), vec![])
+            .await
+            .unwrap();
+        from_server
+            .update_workload(vec![workload_spec.clone()], vec![])
+            .await
+            .unwrap();
+        from_server
+            .update_workload_state(vec![workload_state.clone()])
+            .await
+            .unwrap();
+        from_server.stop().await.unwrap();
+
+        server
+            .start(
+                from_server_receiver,
+                DEFAULT_SOCKET_ADDRESS.parse().unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            agent_a_receiver.recv().await,
+            Some(FromServer::UpdateWorkload(commands::UpdateWorkload {
+                added_workloads: vec![workload_spec],
+                deleted_workloads: vec![],
+            }))
+        );
+        assert!(agent_a_receiver.try_recv().is_err());
+
+        assert_eq!(
+            agent_b_receiver.recv().await,
+            Some(FromServer::ServerHello(commands::ServerHello {
+                agent_name: Some(AGENT_B.to_owned()),
+                added_workloads: vec![],
+            }))
+        );
+        assert_eq!(
+            agent_b_receiver.recv().await,
+            Some(FromServer::UpdateWorkloadState(
+                commands::UpdateWorkloadState {
+                    workload_states: vec![workload_state],
+                }
+            ))
+        );
+        assert!(agent_b_receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn utest_loopback_communications_routes_responses_to_requesting_agent() {
+        let (server_sender, mut server_receiver) = channel(CHANNEL_CAPACITY);
+        let mut server = LoopbackCommunicationsServer::new(server_sender);
+        let (to_server, mut agent_receiver, _client) =
+            connect_agent(&server, &mut server_receiver, AGENT_A).await;
+
+        to_server
+            .request_complete_state(
+                "request_id".to_owned(),
+                commands::CompleteStateRequest {
+                    field_mask: vec![],
+                    selector: vec![],
+                },
+            )
+            .await
+            .unwrap();
+        let Some(ToServer::Request(request)) = server_receiver.recv().await else {
+            panic!("Expected a request");
+        };
+        assert_eq!(request.request_id, format!("{AGENT_A}@request_id"));
+
+        let (from_server, from_server_receiver): (FromServerSender, _) = channel(CHANNEL_CAPACITY);
+        from_server
+            .response(ank_base::Response {
+                request_id: request.request_id,
+                response_content: None,
+            })
+            .await
+            .unwrap();
+        from_server.stop().await.unwrap();
+        server
+            .start(
+                from_server_receiver,
+                DEFAULT_SOCKET_ADDRESS.parse().unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            agent_receiver.recv().await,
+            Some(FromServer::Response(ank_base::Response {
+                request_id: "request_id".to_owned(),
+                response_content: None,
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn utest_loopback_communications_client_stops_on_stop_request() {
+        let (server_sender, mut server_receiver) = channel(CHANNEL_CAPACITY);
+        let server = LoopbackCommunicationsServer::new(server_sender);
+        let (to_server, _agent_receiver, client) =
+            connect_agent(&server, &mut server_receiver, AGENT_A).await;
+
+        to_server.stop().await.unwrap();
+
+        assert!(client.await.unwrap().is_ok());
+        assert_eq!(
+            server_receiver.recv().await,
+            Some(ToServer::AgentGone(commands::AgentGone {
+                agent_name: AGENT_A.to_owned()
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn utest_loopback_communications_client_reports_agent_gone() {
+        let (server_sender, mut server_receiver) = channel(CHANNEL_CAPACITY);
+        let server = LoopbackCommunicationsServer::new(server_sender);
+        let (to_server, _agent_receiver, client) =
+            connect_agent(&server, &mut server_receiver, AGENT_A).await;
+
+        drop(to_server);
+
+        assert!(client.await.unwrap().is_ok());
+        assert_eq!(
+            server_receiver.recv().await,
+            Some(ToServer::AgentGone(commands::AgentGone {
+                agent_name: AGENT_A.to_owned()
+            }))
+        );
+        assert!(server.agent_senders.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,231 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fmt::Write, io, net::SocketAddr, sync::Arc, time::Duration};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+
+const METRICS_PATH: &str = "/metrics";
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+// a scrape request is only a request line and a few headers
+const MAX_REQUEST_HEADER_SIZE: usize = 8 * 1024;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Writes the `HELP` and `TYPE` lines of a metric in the Prometheus text format
+pub fn write_metric_header(output: &mut String, name: &str, help: &str, metric_type: &str) {
+    let _ = writeln!(output, "# HELP {} {}", name, help);
+    let _ = writeln!(output, "# TYPE {} {}", name, metric_type);
+}
+
+pub async fn bind_metrics_endpoint(bind_address: SocketAddr) -> Result<TcpListener, String> {
+    TcpListener::bind(bind_address).await.map_err(|err| {
+        format!(
+            "Could not bind the metrics endpoint to '{}': '{}'",
+            bind_address, err
+        )
+    })
+}
+
+/// Answers HTTP GET requests to `/metrics` with the output of `render`
+// [impl->swdd~common-serves-metrics-endpoint~1]
+pub async fn serve_metrics<F>(listener: TcpListener, render: F)
+where
+    F: Fn() -> String + Send + Sync + 'static,
+{
+    let render = Arc::new(render);
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                let render = render.clone();
+                tokio::spawn(async move {
+                    if let Err(err) =
+                        handle_connection(stream, render.as_ref(), REQUEST_TIMEOUT).await
+                    {
+                        log::debug!("Could not serve the metrics: '{}'", err);
+                    }
+                });
+            }
+            Err(err) => log::warn!("Could not accept metrics connection: '{}'", err),
+        }
+    }
+}
+
+// [impl->swdd~common-metrics-endpoint-limits-requests~1]
+async fn handle_connection<F>(
+    stream: TcpStream,
+    render: &F,
+    request_timeout: Duration,
+) -> std::io::Result<()>
+where
+    F: Fn() -> String,
+{
+    let mut reader = BufReader::new(stream);
+    let request_line = tokio::time::timeout(request_timeout, read_request_line(&mut reader))
+        .await
+        .map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::TimedOut,
+                "The client did not send the request in time",
+            )
+        })??;
+
+    let mut request_parts = request_line.split_whitespace();
+    let response = match (request_parts.next(), request_parts.next()) {
+        (Some("GET"), Some(METRICS_PATH)) => {
+            let body = render();
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                CONTENT_TYPE,
+                body.len(),
+                body
+            )
+        }
+        _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_owned(),
+    };
+
+    let mut stream = reader.into_inner();
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+// Reads the request line and the headers, which are not needed, but are read such that the
+// client receives the complete response.
+async fn read_request_line(reader: &mut BufReader<TcpStream>) -> io::Result<String> {
+    let mut request = Vec::new();
+    while !request.ends_with(b"\r\n\r\n") && !request.ends_with(b"\n\n") {
+        if request.len() >= MAX_REQUEST_HEADER_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "The request header is too large",
+            ));
+        }
+        match reader.read_u8().await {
+            Ok(byte) => request.push(byte),
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(err) => return Err(err),
+        }
+    }
+    let request = String::from_utf8_lossy(&request);
+    Ok(request.lines().next().unwrap_or_default().to_owned())
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+
+    use std::time::Duration;
+
+    use super::{
+        bind_metrics_endpoint, handle_connection, serve_metrics, write_metric_header,
+        MAX_REQUEST_HEADER_SIZE,
+    };
+
+    async fn send_request(address: std::net::SocketAddr, request: &[u8]) -> String {
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream.write_all(request).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[test]
+    fn utest_write_metric_header() {
+        let mut output = String::new();
+        write_metric_header(&mut output, "some_metric", "Some help.", "gauge");
+
+        assert_eq!(
+            output,
+            "# HELP some_metric Some help.\n# TYPE some_metric gauge\n"
+        );
+    }
+
+    // [utest->swdd~common-serves-metrics-endpoint~1]
+    #[tokio::test]
+    async fn utest_serve_metrics() {
+        let listener = bind_metrics_endpoint("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(serve_metrics(listener, || "some_metric 1\n".to_owned()));
+
+        let response =
+            send_request(address, b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with("\r\n\r\nsome_metric 1\n"));
+
+        let response = send_request(address, b"GET /other HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+
+    // [utest->swdd~common-metrics-endpoint-limits-requests~1]
+    #[tokio::test]
+    async fn utest_serve_metrics_refuses_too_large_header() {
+        let listener = bind_metrics_endpoint("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(serve_metrics(listener, || "some_metric 1\n".to_owned()));
+
+        let mut request = b"GET /metrics HTTP/1.1\r\nX-Padding: ".to_vec();
+        request.resize(MAX_REQUEST_HEADER_SIZE + 1, b'a');
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream.write_all(&request).await.unwrap();
+
+        // The connection is closed or reset without a response.
+        let mut response = String::new();
+        let _ = stream.read_to_string(&mut response).await;
+        assert!(response.is_empty());
+    }
+
+    // [utest->swdd~common-metrics-endpoint-limits-requests~1]
+    #[tokio::test]
+    async fn utest_handle_connection_times_out_incomplete_request() {
+        let listener = bind_metrics_endpoint("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap();
+        let address = listener.local_addr().unwrap();
+        let mut client = TcpStream::connect(address).await.unwrap();
+        client
+            .write_all(b"GET /metrics HTTP/1.1\r\n")
+            .await
+            .unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(1),
+            handle_connection(
+                stream,
+                &|| "some_metric 1\n".to_owned(),
+                Duration::from_millis(10),
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::TimedOut);
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fmt::Display, str::FromStr, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+const KEEP: &str = "keep";
+const STOP: &str = "stop";
+const STOP_AFTER: &str = "stop-after";
+
+// [impl->swdd~common-workload-agent-disconnect-policy-format~1]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub enum AgentDisconnectPolicy {
+    #[default]
+    Keep,
+    Stop,
+    StopAfter(u64),
+}
+
+impl AgentDisconnectPolicy {
+    /// Returns the additional time after the disconnect threshold until the workload is stopped
+    pub fn stop_delay(&self) -> Option<Duration> {
+        match self {
+            AgentDisconnectPolicy::Keep => None,
+            AgentDisconnectPolicy::Stop => Some(Duration::ZERO),
+            AgentDisconnectPolicy::StopAfter(seconds) => Some(Duration::from_secs(*seconds)),
+        }
+    }
+}
+
+impl FromStr for AgentDisconnectPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            KEEP => Ok(AgentDisconnectPolicy::Keep),
+            STOP => Ok(AgentDisconnectPolicy::Stop),
+            _ => value
+                .strip_prefix(STOP_AFTER)
+                .and_then(|seconds| seconds.strip_prefix('='))
+                .and_then(|seconds| seconds.parse::<u64>().ok())
+                .map(AgentDisconnectPolicy::StopAfter)
+                .ok_or_else(|| {
+                    format!(
+                        "Unsupported agent disconnect policy '{}'. Expected '{}', '{}' or '{}=<seconds>'",
+                        value, KEEP, STOP, STOP_AFTER
+                    )
+                }),
+        }
+    }
+}
+
+impl TryFrom<String> for AgentDisconnectPolicy {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<AgentDisconnectPolicy> for String {
+    fn from(value: AgentDisconnectPolicy) -> Self {
+        value.to_string()
+    }
+}
+
+impl Display for AgentDisconnectPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AgentDisconnectPolicy::Keep => write!(f, "{}", KEEP),
+            AgentDisconnectPolicy::Stop => write!(f, "{}", STOP),
+            AgentDisconnectPolicy::StopAfter(seconds) => write!(f, "{}={}", STOP_AFTER, seconds),
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::AgentDisconnectPolicy;
+
+    // [utest->swdd~common-workload-agent-disconnect-policy-format~1]
+    #[test]
+    fn utest_agent_disconnect_policy_from_str_and_display() {
+        for (value, policy) in [
+            ("keep", AgentDisconnectPolicy::Keep),
+            ("stop", AgentDisconnectPolicy::Stop),
+            ("stop-after=30", AgentDisconnectPolicy::StopAfter(30)),
+        ] {
+            assert_eq!(value.parse::<AgentDisconnectPolicy>(), Ok(policy));
+            assert_eq!(policy.to_string(), value);
+        }
+    }
+
+    // [utest->swdd~common-workload-agent-disconnect-policy-format~1]
+    #[test]
+    fn utest_agent_disconnect_policy_from_str_fails_on_invalid_policy() {
+        assert!("stop-after".parse::<AgentDisconnectPolicy>().is_err());
+        assert!("stop-after=".parse::<AgentDisconnectPolicy>().is_err());
+        assert!("stop-after=-1".parse::<AgentDisconnectPolicy>().is_err());
+        assert!("stop-after 30".parse::<AgentDisconnectPolicy>().is_err());
+        assert!("restart".parse::<AgentDisconnectPolicy>().is_err());
+    }
+
+    // [utest->swdd~common-workload-agent-disconnect-policy-format~1]
+    #[test]
+    fn utest_agent_disconnect_policy_serde() {
+        let policy: AgentDisconnectPolicy = serde_yaml::from_str("stop-after=5").unwrap();
+        assert_eq!(policy, AgentDisconnectPolicy::StopAfter(5));
+        assert_eq!(policy.stop_delay(), Some(Duration::from_secs(5)));
+        assert_eq!(serde_yaml::to_string(&policy).unwrap(), "stop-after=5\n");
+
+        assert!(serde_yaml::from_str::<AgentDisconnectPolicy>("never").is_err());
+        assert_eq!(AgentDisconnectPolicy::Keep.stop_delay(), None);
+    }
+}
@@ -17,9 +17,14 @@ use serde::{Deserialize, Serialize};
 use std::collections::{hash_map::Entry, HashMap};
 
 use crate::commands;
+use crate::helpers::serialize_to_ordered_map;
 
 type AgentName = String;
 
+// The load history is sampled at a lower rate than the load status is reported to keep it short.
+const LOAD_HISTORY_SAMPLE_INTERVAL_MS: u64 = 60_000;
+const LOAD_HISTORY_MAX_SAMPLES: usize = 10;
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
 pub struct CpuUsage {
     pub cpu_usage: u32,
@@ -38,10 +43,71 @@ pub struct FreeMemory {
     pub free_memory: u64,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct DiskUsage {
+    pub total_space: u64,
+    pub available_space: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct NetworkInterfaceUsage {
+    pub name: String,
+    pub received_bytes: u64,
+    pub transmitted_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct NetworkUsage {
+    pub interfaces: Vec<NetworkInterfaceUsage>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct ThermalZone {
+    pub zone_type: String,
+    // expressed in millidegree Celsius as provided by the kernel
+    pub temperature: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct Temperature {
+    pub thermal_zones: Vec<ThermalZone>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct AgentLoadSample {
+    pub timestamp: u64,
+    pub cpu_usage: u32,
+    pub free_memory: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
 pub struct AgentAttributes {
     pub cpu_usage: Option<CpuUsage>,
     pub free_memory: Option<FreeMemory>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_usage: Option<DiskUsage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_usage: Option<NetworkUsage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<Temperature>,
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub labels: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub runtimes: Vec<String>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub connected_at: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub load_history: Vec<AgentLoadSample>,
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
@@ -61,14 +127,46 @@ impl AgentMap {
         self.0.contains_key(key)
     }
 
+    pub fn get(&self, key: &str) -> Option<&AgentAttributes> {
+        self.0.get(key)
+    }
+
     pub fn remove(&mut self, key: &str) {
         self.0.remove(key);
     }
 
-    pub fn update_resource_availability(&mut self, agent_load_status: commands::AgentLoadStatus) {
+    pub fn iter(&self) -> impl Iterator<Item = (&AgentName, &AgentAttributes)> {
+        self.0.iter()
+    }
+
+    // [impl->swdd~agent-map-records-load-history~1]
+    pub fn update_resource_availability(
+        &mut self,
+        agent_load_status: commands::AgentLoadStatus,
+        timestamp: u64,
+    ) {
         self.0.entry(agent_load_status.agent_name).and_modify(|e| {
+            let is_sample_due = e.load_history.last().is_none_or(|last_sample| {
+                timestamp >= last_sample.timestamp + LOAD_HISTORY_SAMPLE_INTERVAL_MS
+            });
+            if is_sample_due {
+                e.load_history.push(AgentLoadSample {
+                    timestamp,
+                    cpu_usage: agent_load_status.cpu_usage.cpu_usage,
+                    free_memory: agent_load_status.free_memory.free_memory,
+                });
+                let excess_samples = e
+                    .load_history
+                    .len()
+                    .saturating_sub(LOAD_HISTORY_MAX_SAMPLES);
+                e.load_history.drain(..excess_samples);
+            }
+
             e.cpu_usage = Some(agent_load_status.cpu_usage);
             e.free_memory = Some(agent_load_status.free_memory);
+            e.disk_usage = Some(agent_load_status.disk_usage);
+            e.network_usage = Some(agent_load_status.network_usage);
+            e.temperature = Some(agent_load_status.temperature);
         });
     }
 }
@@ -105,6 +203,114 @@ impl From<ank_base::FreeMemory> for FreeMemory {
     }
 }
 
+impl From<DiskUsage> for ank_base::DiskUsage {
+    fn from(item: DiskUsage) -> ank_base::DiskUsage {
+        ank_base::DiskUsage {
+            total_space: item.total_space,
+            available_space: item.available_space,
+        }
+    }
+}
+
+impl From<ank_base::DiskUsage> for DiskUsage {
+    fn from(item: ank_base::DiskUsage) -> Self {
+        DiskUsage {
+            total_space: item.total_space,
+            available_space: item.available_space,
+        }
+    }
+}
+
+impl From<NetworkInterfaceUsage> for ank_base::NetworkInterfaceUsage {
+    fn from(item: NetworkInterfaceUsage) -> ank_base::NetworkInterfaceUsage {
+        ank_base::NetworkInterfaceUsage {
+            name: item.name,
+            received_bytes: item.received_bytes,
+            transmitted_bytes: item.transmitted_bytes,
+        }
+    }
+}
+
+impl From<ank_base::NetworkInterfaceUsage> for NetworkInterfaceUsage {
+    fn from(item: ank_base::NetworkInterfaceUsage) -> Self {
+        NetworkInterfaceUsage {
+            name: item.name,
+            received_bytes: item.received_bytes,
+            transmitted_bytes: item.transmitted_bytes,
+        }
+    }
+}
+
+impl From<NetworkUsage> for ank_base::NetworkUsage {
+    fn from(item: NetworkUsage) -> ank_base::NetworkUsage {
+        ank_base::NetworkUsage {
+            interfaces: item.interfaces.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ank_base::NetworkUsage> for NetworkUsage {
+    fn from(item: ank_base::NetworkUsage) -> Self {
+        NetworkUsage {
+            interfaces: item.interfaces.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ThermalZone> for ank_base::ThermalZone {
+    fn from(item: ThermalZone) -> ank_base::ThermalZone {
+        ank_base::ThermalZone {
+            zone_type: item.zone_type,
+            temperature: item.temperature,
+        }
+    }
+}
+
+impl From<ank_base::ThermalZone> for ThermalZone {
+    fn from(item: ank_base::ThermalZone) -> Self {
+        ThermalZone {
+            zone_type: item.zone_type,
+            temperature: item.temperature,
+        }
+    }
+}
+
+impl From<Temperature> for ank_base::Temperature {
+    fn from(item: Temperature) -> ank_base::Temperature {
+        ank_base::Temperature {
+            thermal_zones: item.thermal_zones.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ank_base::Temperature> for Temperature {
+    fn from(item: ank_base::Temperature) -> Self {
+        Temperature {
+            thermal_zones: item.thermal_zones.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<AgentLoadSample> for ank_base::AgentLoadSample {
+    fn from(item: AgentLoadSample) -> Self {
+        ank_base::AgentLoadSample {
+            timestamp: item.timestamp,
+            cpu_usage: item.cpu_usage,
+            free_memory: item.free_memory,
+        }
+    }
+}
+
+impl From<ank_base::AgentLoadSample> for AgentLoadSample {
+    fn from(item: ank_base::AgentLoadSample) -> Self {
+        AgentLoadSample {
+            timestamp: item.timestamp,
+            cpu_usage: item.cpu_usage,
+            free_memory: item.free_memory,
+        }
+    }
+}
+
 impl From<AgentAttributes> for ank_base::AgentAttributes {
     fn from(item: AgentAttributes) -> ank_base::AgentAttributes {
         ank_base::AgentAttributes {
@@ -114,6 +320,14 @@ impl From<AgentAttributes> for ank_base::AgentAttributes {
             free_memory: Some(ank_base::FreeMemory {
                 free_memory: item.free_memory.unwrap_or_default().free_memory,
             }),
+            disk_usage: item.disk_usage.map(Into::into),
+            network_usage: item.network_usage.map(Into::into),
+            temperature: item.temperature.map(Into::into),
+            labels: item.labels,
+            version: item.version,
+            runtimes: item.runtimes,
+            connected_at: item.connected_at,
+            load_history: item.load_history.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -127,6 +341,14 @@ impl From<ank_base::AgentAttributes> for AgentAttributes {
             free_memory: Some(FreeMemory {
                 free_memory: item.free_memory.unwrap_or_default().free_memory,
             }),
+            disk_usage: item.disk_usage.map(Into::into),
+            network_usage: item.network_usage.map(Into::into),
+            temperature: item.temperature.map(Into::into),
+            labels: item.labels,
+            version: item.version,
+            runtimes: item.runtimes,
+            connected_at: item.connected_at,
+            load_history: item.load_history.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -174,6 +396,14 @@ pub fn generate_test_agent_map(agent_name: impl Into<String>) -> AgentMap {
         .or_insert(AgentAttributes {
             cpu_usage: Some(CpuUsage { cpu_usage: 42 }),
             free_memory: Some(FreeMemory { free_memory: 42 }),
+            disk_usage: None,
+            network_usage: None,
+            temperature: None,
+            labels: HashMap::new(),
+            version: String::new(),
+            runtimes: Vec::new(),
+            connected_at: 0,
+            load_history: Vec::new(),
         });
     agent_map
 }
@@ -189,7 +419,97 @@ pub fn generate_test_agent_map_from_specs(workloads: &[crate::objects::WorkloadS
                 .or_insert(AgentAttributes {
                     cpu_usage: Some(CpuUsage { cpu_usage: 42 }),
                     free_memory: Some(FreeMemory { free_memory: 42 }),
+                    disk_usage: None,
+                    network_usage: None,
+                    temperature: None,
+                    labels: HashMap::new(),
+                    version: String::new(),
+                    runtimes: Vec::new(),
+                    connected_at: 0,
+                    load_history: Vec::new(),
                 });
             agent_map
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{
+        generate_test_agent_map, AgentLoadSample, CpuUsage, FreeMemory, LOAD_HISTORY_MAX_SAMPLES,
+        LOAD_HISTORY_SAMPLE_INTERVAL_MS,
+    };
+    use crate::commands::AgentLoadStatus;
+
+    const AGENT_A: &str = "agent_A";
+
+    fn agent_load_status(cpu_usage: u32) -> AgentLoadStatus {
+        AgentLoadStatus {
+            agent_name: AGENT_A.to_string(),
+            cpu_usage: CpuUsage { cpu_usage },
+            free_memory: FreeMemory { free_memory: 42 },
+            workload_resources: Vec::new(),
+            disk_usage: Default::default(),
+            network_usage: Default::default(),
+            temperature: Default::default(),
+            image_states: HashMap::new(),
+        }
+    }
+
+    // [utest->swdd~agent-map-records-load-history~1]
+    #[test]
+    fn utest_update_resource_availability_samples_load_history() {
+        let mut agent_map = generate_test_agent_map(AGENT_A);
+
+        agent_map.update_resource_availability(agent_load_status(10), 1_000);
+        agent_map.update_resource_availability(
+            agent_load_status(20),
+            1_000 + LOAD_HISTORY_SAMPLE_INTERVAL_MS - 1,
+        );
+        agent_map.update_resource_availability(
+            agent_load_status(30),
+            1_000 + LOAD_HISTORY_SAMPLE_INTERVAL_MS,
+        );
+
+        let agent_attributes = agent_map.get(AGENT_A).unwrap();
+        assert_eq!(agent_attributes.cpu_usage, Some(CpuUsage { cpu_usage: 30 }));
+        assert_eq!(
+            agent_attributes.load_history,
+            vec![
+                AgentLoadSample {
+                    timestamp: 1_000,
+                    cpu_usage: 10,
+                    free_memory: 42,
+                },
+                AgentLoadSample {
+                    timestamp: 1_000 + LOAD_HISTORY_SAMPLE_INTERVAL_MS,
+                    cpu_usage: 30,
+                    free_memory: 42,
+                },
+            ]
+        );
+    }
+
+    // [utest->swdd~agent-map-records-load-history~1]
+    #[test]
+    fn utest_update_resource_availability_keeps_latest_samples() {
+        let mut agent_map = generate_test_agent_map(AGENT_A);
+
+        let sample_count = LOAD_HISTORY_MAX_SAMPLES as u64 + 2;
+        for sample in 0..sample_count {
+            agent_map.update_resource_availability(
+                agent_load_status(sample as u32),
+                sample * LOAD_HISTORY_SAMPLE_INTERVAL_MS,
+            );
+        }
+
+        let load_history = &agent_map.get(AGENT_A).unwrap().load_history;
+        assert_eq!(load_history.len(), LOAD_HISTORY_MAX_SAMPLES);
+        assert_eq!(load_history.first().unwrap().cpu_usage, 2);
+        assert_eq!(
+            load_history.last().unwrap().cpu_usage,
+            sample_count as u32 - 1
+        );
+    }
+}
@@ -15,7 +15,7 @@
 use api::ank_base;
 use serde::{Deserialize, Serialize};
 
-use super::{AgentMap, State, WorkloadStatesMap};
+use super::{AgentMap, ImageStatesMap, State, WorkloadResourcesMap, WorkloadStatesMap};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -26,6 +26,10 @@ pub struct CompleteState {
     pub workload_states: WorkloadStatesMap,
     #[serde(default)]
     pub agents: AgentMap,
+    #[serde(default)]
+    pub workload_resources: WorkloadResourcesMap,
+    #[serde(default)]
+    pub image_states: ImageStatesMap,
 }
 
 impl From<CompleteState> for ank_base::CompleteState {
@@ -34,6 +38,9 @@ impl From<CompleteState> for ank_base::CompleteState {
             desired_state: Some(ank_base::State::from(item.desired_state)),
             workload_states: item.workload_states.into(),
             agents: item.agents.into(),
+            workload_resources: item.workload_resources.into(),
+            workload_state_history: None,
+            image_states: item.image_states.into(),
         }
     }
 }
@@ -46,6 +53,8 @@ impl TryFrom<ank_base::CompleteState> for CompleteState {
             desired_state: item.desired_state.unwrap_or_default().try_into()?,
             workload_states: item.workload_states.unwrap_or_default().into(),
             agents: item.agents.unwrap_or_default().into(),
+            workload_resources: item.workload_resources.unwrap_or_default().into(),
+            image_states: item.image_states.unwrap_or_default().into(),
         })
     }
 }
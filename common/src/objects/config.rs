@@ -16,16 +16,198 @@ use std::collections::HashMap;
 
 use crate::helpers::serialize_to_ordered_map;
 use api::ank_base::{self, config_item};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub(crate) const SECRET_REF_KEY: &str = "secretRef";
+const SECRET_PLACEHOLDER_PREFIX: &str = "${ankaios-secret:";
+const SECRET_PLACEHOLDER_SUFFIX: &str = "}";
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum ConfigItem {
     String(String),
     ConfigArray(Vec<ConfigItem>),
+    // must be tried before the object as a secret reference is a special object
+    SecretRef(
+        #[serde(
+            serialize_with = "serialize_secret_ref",
+            deserialize_with = "deserialize_secret_ref"
+        )]
+        SecretRef,
+    ),
     ConfigObject(#[serde(serialize_with = "serialize_to_ordered_map")] HashMap<String, ConfigItem>),
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SecretProvider {
+    File = 0,
+    Env = 1,
+    SystemdCreds = 2,
+}
+
+impl std::fmt::Display for SecretProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecretProvider::File => write!(f, "file"),
+            SecretProvider::Env => write!(f, "env"),
+            SecretProvider::SystemdCreds => write!(f, "systemd-creds"),
+        }
+    }
+}
+
+impl std::str::FromStr for SecretProvider {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "file" => Ok(SecretProvider::File),
+            "env" => Ok(SecretProvider::Env),
+            "systemd-creds" => Ok(SecretProvider::SystemdCreds),
+            _ => Err(format!("Unknown secret provider '{}'", value)),
+        }
+    }
+}
+
+impl TryFrom<i32> for SecretProvider {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            x if x == SecretProvider::File as i32 => Ok(SecretProvider::File),
+            x if x == SecretProvider::Env as i32 => Ok(SecretProvider::Env),
+            x if x == SecretProvider::SystemdCreds as i32 => Ok(SecretProvider::SystemdCreds),
+            _ => Err(format!(
+                "Received an unknown value '{value}' as secret provider."
+            )),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SecretRef {
+    pub provider: SecretProvider,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SecretRefEntry {
+    #[serde(rename = "secretRef")]
+    secret_ref: SecretRef,
+}
+
+fn serialize_secret_ref<S: Serializer>(
+    secret_ref: &SecretRef,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    SecretRefEntry {
+        secret_ref: secret_ref.clone(),
+    }
+    .serialize(serializer)
+}
+
+fn deserialize_secret_ref<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<SecretRef, D::Error> {
+    SecretRefEntry::deserialize(deserializer).map(|entry| entry.secret_ref)
+}
+
+impl SecretRef {
+    // [impl->swdd~common-config-secret-ref-format~1]
+    pub fn verify_format(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err(format!(
+                "The name of the '{}' secret reference must not be empty",
+                self.provider
+            ));
+        }
+        if self.name.contains(SECRET_PLACEHOLDER_SUFFIX) {
+            return Err(format!(
+                "The name '{}' of the '{}' secret reference must not contain '{}'",
+                self.name, self.provider, SECRET_PLACEHOLDER_SUFFIX
+            ));
+        }
+        Ok(())
+    }
+
+    // [impl->swdd~common-config-secret-ref-placeholder~1]
+    pub fn placeholder(&self) -> String {
+        format!(
+            "{}{}:{}{}",
+            SECRET_PLACEHOLDER_PREFIX, self.provider, self.name, SECRET_PLACEHOLDER_SUFFIX
+        )
+    }
+}
+
+impl ConfigItem {
+    // A config object holding only a malformed secret reference would silently be taken as object.
+    // [impl->swdd~common-config-secret-ref-format~1]
+    pub fn verify_secret_refs(&self) -> Result<(), String> {
+        match self {
+            ConfigItem::String(_) => Ok(()),
+            ConfigItem::ConfigArray(array) => array.iter().try_for_each(Self::verify_secret_refs),
+            ConfigItem::SecretRef(secret_ref) => secret_ref.verify_format(),
+            ConfigItem::ConfigObject(object) if object.contains_key(SECRET_REF_KEY) => {
+                Err(format!(
+                    "Invalid secret reference, '{}' requires exactly the fields 'provider' and 'name'",
+                    SECRET_REF_KEY
+                ))
+            }
+            ConfigItem::ConfigObject(object) => {
+                object.values().try_for_each(Self::verify_secret_refs)
+            }
+        }
+    }
+
+    // The values of the secrets are never known to the server, the agent replaces the placeholders.
+    // [impl->swdd~common-config-secret-ref-placeholder~1]
+    pub fn with_secret_placeholders(&self) -> ConfigItem {
+        match self {
+            ConfigItem::String(_) => self.clone(),
+            ConfigItem::ConfigArray(array) => {
+                ConfigItem::ConfigArray(array.iter().map(Self::with_secret_placeholders).collect())
+            }
+            ConfigItem::SecretRef(secret_ref) => ConfigItem::String(secret_ref.placeholder()),
+            ConfigItem::ConfigObject(object) => ConfigItem::ConfigObject(
+                object
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.with_secret_placeholders()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+// Replaces all secret placeholders in the text with the values returned by the resolve function.
+// [impl->swdd~common-config-secret-ref-placeholder~1]
+pub fn replace_secret_placeholders(
+    text: &str,
+    mut resolve: impl FnMut(&SecretRef) -> Result<String, String>,
+) -> Result<String, String> {
+    let mut result = String::with_capacity(text.len());
+    let mut remaining = text;
+    while let Some(start) = remaining.find(SECRET_PLACEHOLDER_PREFIX) {
+        result.push_str(&remaining[..start]);
+        let placeholder = &remaining[start + SECRET_PLACEHOLDER_PREFIX.len()..];
+        let end = placeholder
+            .find(SECRET_PLACEHOLDER_SUFFIX)
+            .ok_or_else(|| "Unterminated secret placeholder".to_string())?;
+        let (provider, name) = placeholder[..end]
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid secret placeholder '{}'", &placeholder[..end]))?;
+        let secret_ref = SecretRef {
+            provider: provider.parse()?,
+            name: name.to_owned(),
+        };
+        result.push_str(&resolve(&secret_ref)?);
+        remaining = &placeholder[end + SECRET_PLACEHOLDER_SUFFIX.len()..];
+    }
+    result.push_str(remaining);
+    Ok(result)
+}
+
 impl From<ConfigItem> for ank_base::ConfigItem {
     fn from(value: ConfigItem) -> Self {
         Self {
@@ -36,6 +218,12 @@ impl From<ConfigItem> for ank_base::ConfigItem {
                         values: array.into_iter().map(Into::into).collect(),
                     })
                 }
+                ConfigItem::SecretRef(secret_ref) => {
+                    config_item::ConfigItem::SecretRef(ank_base::SecretRef {
+                        provider: secret_ref.provider as i32,
+                        name: secret_ref.name,
+                    })
+                }
                 ConfigItem::ConfigObject(object) => {
                     config_item::ConfigItem::Object(ank_base::ConfigObject {
                         fields: object
@@ -71,6 +259,12 @@ impl TryFrom<ank_base::ConfigItem> for ConfigItem {
                         .collect::<Result<HashMap<String, ConfigItem>, Self::Error>>()?,
                 )
             }
+            config_item::ConfigItem::SecretRef(ank_base::SecretRef { provider, name }) => {
+                Self::SecretRef(SecretRef {
+                    provider: provider.try_into()?,
+                    name,
+                })
+            }
         })
     }
 }
@@ -122,7 +316,9 @@ pub fn generate_test_configs() -> HashMap<String, ConfigItem> {
 mod tests {
     use api::ank_base;
 
-    use crate::objects::ConfigItem;
+    use std::collections::HashMap;
+
+    use crate::objects::{replace_secret_placeholders, ConfigItem, SecretProvider, SecretRef};
 
     macro_rules! sample_config {
         ($expression:ident) => {{
@@ -252,4 +448,108 @@ mod tests {
         let converted_config = ConfigItem::try_from(proto_config);
         assert_eq!(converted_config, Err("Value of ConfigItem is None".into()));
     }
+
+    fn secret_ref(provider: SecretProvider, name: &str) -> SecretRef {
+        SecretRef {
+            provider,
+            name: name.to_string(),
+        }
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn convert_secret_ref_from_and_to_proto() {
+        let proto_config = ank_base::ConfigItem {
+            config_item: Some(ank_base::config_item::ConfigItem::SecretRef(
+                ank_base::SecretRef {
+                    provider: ank_base::SecretProvider::SystemdCreds as i32,
+                    name: "db_password".to_string(),
+                },
+            )),
+        };
+        let internal_config =
+            ConfigItem::SecretRef(secret_ref(SecretProvider::SystemdCreds, "db_password"));
+
+        assert_eq!(
+            ConfigItem::try_from(proto_config.clone()),
+            Ok(internal_config.clone())
+        );
+        assert_eq!(ank_base::ConfigItem::from(internal_config), proto_config);
+    }
+
+    // [utest->swdd~common-config-secret-ref-format~1]
+    #[test]
+    fn deserialize_secret_ref_from_yaml() {
+        let config: ConfigItem = serde_yaml::from_str(
+            "password:\n  secretRef:\n    provider: env\n    name: DB_PASSWORD\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            ConfigItem::ConfigObject(HashMap::from([(
+                "password".to_string(),
+                ConfigItem::SecretRef(secret_ref(SecretProvider::Env, "DB_PASSWORD"))
+            )]))
+        );
+        assert_eq!(
+            serde_yaml::to_string(&config).unwrap(),
+            "password:\n  secretRef:\n    provider: env\n    name: DB_PASSWORD\n"
+        );
+    }
+
+    // [utest->swdd~common-config-secret-ref-format~1]
+    #[test]
+    fn verify_secret_refs() {
+        let valid_config: ConfigItem = serde_yaml::from_str(
+            "- secretRef:\n    provider: file\n    name: /run/secrets/token\n",
+        )
+        .unwrap();
+        assert!(valid_config.verify_secret_refs().is_ok());
+
+        let unknown_provider: ConfigItem =
+            serde_yaml::from_str("secretRef:\n  provider: vault\n  name: token\n").unwrap();
+        assert!(matches!(unknown_provider, ConfigItem::ConfigObject(_)));
+        assert!(unknown_provider.verify_secret_refs().is_err());
+
+        let empty_name = ConfigItem::SecretRef(secret_ref(SecretProvider::Env, ""));
+        assert!(empty_name.verify_secret_refs().is_err());
+
+        let invalid_name = ConfigItem::SecretRef(secret_ref(SecretProvider::Env, "A}B"));
+        assert!(invalid_name.verify_secret_refs().is_err());
+    }
+
+    // [utest->swdd~common-config-secret-ref-placeholder~1]
+    #[test]
+    fn replace_secret_refs_with_placeholders_and_values() {
+        let config = ConfigItem::ConfigArray(vec![
+            ConfigItem::String("plain".to_string()),
+            ConfigItem::SecretRef(secret_ref(SecretProvider::File, "/run/secrets/token")),
+        ]);
+
+        assert_eq!(
+            config.with_secret_placeholders(),
+            ConfigItem::ConfigArray(vec![
+                ConfigItem::String("plain".to_string()),
+                ConfigItem::String("${ankaios-secret:file:/run/secrets/token}".to_string()),
+            ])
+        );
+
+        let resolved = replace_secret_placeholders(
+            "user: admin, token: ${ankaios-secret:file:/run/secrets/token}, ${ankaios-secret:env:A}",
+            |secret_ref| Ok(format!("<{}>", secret_ref.name)),
+        );
+        assert_eq!(
+            resolved,
+            Ok("user: admin, token: </run/secrets/token>, <A>".to_string())
+        );
+
+        let no_secret = |_: &SecretRef| Ok::<_, String>(String::new());
+        assert!(replace_secret_placeholders("${ankaios-secret:vault:A}", no_secret).is_err());
+        assert!(replace_secret_placeholders("${ankaios-secret:env:A", no_secret).is_err());
+        assert_eq!(
+            replace_secret_placeholders("${ankaios-secret:env:A}", |_| Err("failed".into())),
+            Err("failed".to_string())
+        );
+    }
 }
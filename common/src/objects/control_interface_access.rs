@@ -14,6 +14,11 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::desired_state_path_of_workload;
+
+const SELF_PLACEHOLDER: &str = "self";
+const DESIRED_STATE_SELF_SEGMENTS: [&str; 3] = ["desiredState", "workloads", SELF_PLACEHOLDER];
+
 #[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct ControlInterfaceAccess {
@@ -21,6 +26,8 @@ pub struct ControlInterfaceAccess {
     pub allow_rules: Vec<AccessRightsRule>,
     #[serde(default)]
     pub deny_rules: Vec<AccessRightsRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pipes_owner: Option<PipesOwner>,
 }
 
 impl ControlInterfaceAccess {
@@ -31,6 +38,53 @@ impl ControlInterfaceAccess {
             .chain(self.deny_rules.iter())
             .try_for_each(|rule| rule.verify_format())
     }
+
+    // [impl->swdd~common-access-rules-self-placeholder~1]
+    pub fn expand_self_placeholder(&mut self, workload_name: &str, replica_name: &str) {
+        for AccessRightsRule::StateRule(state_rule) in self
+            .allow_rules
+            .iter_mut()
+            .chain(self.deny_rules.iter_mut())
+        {
+            for filter_mask in &mut state_rule.filter_mask {
+                *filter_mask = expand_self_placeholder_in_filter_mask(
+                    filter_mask,
+                    workload_name,
+                    replica_name,
+                );
+            }
+        }
+    }
+}
+
+// The desired state of a replica is the desired state of its workload, which
+// can be inside a namespace. All other "self" segments refer to the replica.
+fn expand_self_placeholder_in_filter_mask(
+    filter_mask: &str,
+    workload_name: &str,
+    replica_name: &str,
+) -> String {
+    let segments: Vec<&str> = filter_mask.split('.').collect();
+    let (prefix, remaining_segments) = if segments.starts_with(&DESIRED_STATE_SELF_SEGMENTS) {
+        (
+            Some(desired_state_path_of_workload(workload_name)),
+            &segments[DESIRED_STATE_SELF_SEGMENTS.len()..],
+        )
+    } else {
+        (None, &segments[..])
+    };
+
+    prefix
+        .into_iter()
+        .chain(remaining_segments.iter().map(|segment| {
+            if *segment == SELF_PLACEHOLDER {
+                replica_name.to_owned()
+            } else {
+                (*segment).to_owned()
+            }
+        }))
+        .collect::<Vec<_>>()
+        .join(".")
 }
 
 impl TryFrom<api::ank_base::ControlInterfaceAccess> for ControlInterfaceAccess {
@@ -39,23 +93,55 @@ impl TryFrom<api::ank_base::ControlInterfaceAccess> for ControlInterfaceAccess {
         Ok(Self {
             allow_rules: convert_rule_vec(value.allow_rules)?,
             deny_rules: convert_rule_vec(value.deny_rules)?,
+            pipes_owner: value.pipes_owner.map(Into::into),
         })
     }
 }
 
 impl From<ControlInterfaceAccess> for Option<api::ank_base::ControlInterfaceAccess> {
     fn from(value: ControlInterfaceAccess) -> Self {
-        if value.allow_rules.is_empty() && value.deny_rules.is_empty() {
+        if value.allow_rules.is_empty()
+            && value.deny_rules.is_empty()
+            && value.pipes_owner.is_none()
+        {
             None
         } else {
             Some(api::ank_base::ControlInterfaceAccess {
                 allow_rules: value.allow_rules.into_iter().map(|x| x.into()).collect(),
                 deny_rules: value.deny_rules.into_iter().map(|x| x.into()).collect(),
+                pipes_owner: value.pipes_owner.map(Into::into),
             })
         }
     }
 }
 
+// The user and group owning the Control Interface pipes of a workload
+// [impl->swdd~common-control-interface-pipes-owner~1]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PipesOwner {
+    pub uid: u32,
+    pub gid: u32,
+}
+
+impl From<api::ank_base::PipesOwner> for PipesOwner {
+    fn from(value: api::ank_base::PipesOwner) -> Self {
+        Self {
+            uid: value.uid,
+            gid: value.gid,
+        }
+    }
+}
+
+impl From<PipesOwner> for api::ank_base::PipesOwner {
+    fn from(value: PipesOwner) -> Self {
+        Self {
+            uid: value.uid,
+            gid: value.gid,
+        }
+    }
+}
+
 fn convert_rule_vec(
     value: Vec<api::ank_base::AccessRightsRule>,
 ) -> Result<Vec<AccessRightsRule>, String> {
@@ -199,13 +285,15 @@ pub fn generate_test_control_interface_access() -> ControlInterfaceAccess {
             operation: ReadWriteEnum::Write,
             filter_mask: vec!["desiredState.workload.watchDog".to_string()],
         })],
+        pipes_owner: None,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::objects::{
-        generate_test_control_interface_access, AccessRightsRule, ReadWriteEnum, StateRule,
+        generate_test_control_interface_access, AccessRightsRule, ControlInterfaceAccess,
+        PipesOwner, ReadWriteEnum, StateRule,
     };
 
     // [utest->swdd~common-access-rules-filter-mask-convention~1]
@@ -275,4 +363,139 @@ mod tests {
 
         assert!(control_interface_access.verify_format().is_ok());
     }
+
+    // [utest->swdd~common-control-interface-pipes-owner~1]
+    #[test]
+    fn utest_control_interface_access_with_pipes_owner_from_and_to_proto() {
+        let control_interface_access = ControlInterfaceAccess {
+            pipes_owner: Some(PipesOwner {
+                uid: 1000,
+                gid: 1001,
+            }),
+            ..Default::default()
+        };
+
+        let proto_control_interface_access: Option<api::ank_base::ControlInterfaceAccess> =
+            control_interface_access.clone().into();
+        assert_eq!(
+            proto_control_interface_access
+                .as_ref()
+                .and_then(|access| access.pipes_owner.clone()),
+            Some(api::ank_base::PipesOwner {
+                uid: 1000,
+                gid: 1001
+            })
+        );
+        assert_eq!(
+            ControlInterfaceAccess::try_from(proto_control_interface_access.unwrap()),
+            Ok(control_interface_access)
+        );
+    }
+
+    fn generate_test_control_interface_access_with_self_placeholder() -> ControlInterfaceAccess {
+        ControlInterfaceAccess {
+            allow_rules: vec![AccessRightsRule::StateRule(StateRule {
+                operation: ReadWriteEnum::ReadWrite,
+                filter_mask: vec![
+                    "desiredState.workloads.self".to_string(),
+                    "workloadStates.*.self".to_string(),
+                ],
+            })],
+            deny_rules: vec![AccessRightsRule::StateRule(StateRule {
+                operation: ReadWriteEnum::Write,
+                filter_mask: vec!["desiredState.workloads.self.agent".to_string()],
+            })],
+            pipes_owner: None,
+        }
+    }
+
+    fn filter_masks(rules: &[AccessRightsRule]) -> Vec<&str> {
+        rules
+            .iter()
+            .flat_map(|AccessRightsRule::StateRule(state_rule)| &state_rule.filter_mask)
+            .map(String::as_str)
+            .collect()
+    }
+
+    // [utest->swdd~common-access-rules-self-placeholder~1]
+    #[test]
+    fn utest_control_interface_access_expand_self_placeholder() {
+        let mut control_interface_access =
+            generate_test_control_interface_access_with_self_placeholder();
+
+        control_interface_access.expand_self_placeholder("wl", "wl");
+
+        assert_eq!(
+            filter_masks(&control_interface_access.allow_rules),
+            vec!["desiredState.workloads.wl", "workloadStates.*.wl"]
+        );
+        assert_eq!(
+            filter_masks(&control_interface_access.deny_rules),
+            vec!["desiredState.workloads.wl.agent"]
+        );
+    }
+
+    // [utest->swdd~common-access-rules-self-placeholder~1]
+    #[test]
+    fn utest_control_interface_access_expand_self_placeholder_in_namespace() {
+        let mut control_interface_access =
+            generate_test_control_interface_access_with_self_placeholder();
+
+        control_interface_access.expand_self_placeholder("team-a__wl", "team-a__wl");
+
+        assert_eq!(
+            filter_masks(&control_interface_access.allow_rules),
+            vec![
+                "desiredState.namespaces.team-a.workloads.wl",
+                "workloadStates.*.team-a__wl"
+            ]
+        );
+        assert_eq!(
+            filter_masks(&control_interface_access.deny_rules),
+            vec!["desiredState.namespaces.team-a.workloads.wl.agent"]
+        );
+    }
+
+    // [utest->swdd~common-access-rules-self-placeholder~1]
+    #[test]
+    fn utest_control_interface_access_expand_self_placeholder_for_replica() {
+        let mut control_interface_access =
+            generate_test_control_interface_access_with_self_placeholder();
+
+        control_interface_access.expand_self_placeholder("wl", "wl-1");
+
+        assert_eq!(
+            filter_masks(&control_interface_access.allow_rules),
+            vec!["desiredState.workloads.wl", "workloadStates.*.wl-1"]
+        );
+        assert_eq!(
+            filter_masks(&control_interface_access.deny_rules),
+            vec!["desiredState.workloads.wl.agent"]
+        );
+    }
+
+    // [utest->swdd~common-access-rules-self-placeholder~1]
+    #[test]
+    fn utest_control_interface_access_expand_self_placeholder_keeps_other_segments() {
+        let mut control_interface_access = ControlInterfaceAccess {
+            allow_rules: vec![AccessRightsRule::StateRule(StateRule {
+                operation: ReadWriteEnum::Read,
+                filter_mask: vec![
+                    "desiredState.workloads.selfish".to_string(),
+                    "desiredState.configs.self".to_string(),
+                ],
+            })],
+            ..Default::default()
+        };
+
+        control_interface_access.expand_self_placeholder("wl", "wl-1");
+
+        assert_eq!(
+            filter_masks(&control_interface_access.allow_rules),
+            vec![
+                "desiredState.workloads.selfish",
+                "desiredState.configs.wl-1"
+            ]
+        );
+    }
 }
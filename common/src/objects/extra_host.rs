@@ -0,0 +1,146 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use api::ank_base;
+
+const MAX_CHARACTERS_HOSTNAME: usize = 253;
+pub const STR_RE_HOSTNAME: &str = r"^[a-zA-Z0-9]([a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?(\.[a-zA-Z0-9]([a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?)*$";
+
+// [impl->swdd~common-workload-hostname-convention~1]
+pub fn verify_hostname_format(hostname: &str) -> Result<(), String> {
+    let re_hostname = Regex::new(STR_RE_HOSTNAME).unwrap();
+    if !re_hostname.is_match(hostname) {
+        return Err(format!(
+            "Unsupported hostname. Received '{}', expected to match {}",
+            hostname, STR_RE_HOSTNAME
+        ));
+    }
+
+    if hostname.len() > MAX_CHARACTERS_HOSTNAME {
+        Err(format!(
+            "Hostname length {} exceeds the maximum limit of {} characters",
+            hostname.len(),
+            MAX_CHARACTERS_HOSTNAME
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ExtraHost {
+    pub hostname: String,
+    pub ip: String,
+}
+
+impl ExtraHost {
+    // [impl->swdd~common-workload-hostname-convention~1]
+    pub fn verify_format(&self) -> Result<(), String> {
+        verify_hostname_format(&self.hostname)?;
+        self.ip.parse::<IpAddr>().map_err(|_| {
+            format!(
+                "Unsupported IP address '{}' for the extra host '{}'",
+                self.ip, self.hostname
+            )
+        })?;
+        Ok(())
+    }
+}
+
+impl From<ank_base::ExtraHost> for ExtraHost {
+    fn from(item: ank_base::ExtraHost) -> Self {
+        ExtraHost {
+            hostname: item.hostname,
+            ip: item.ip,
+        }
+    }
+}
+
+impl From<ExtraHost> for ank_base::ExtraHost {
+    fn from(item: ExtraHost) -> Self {
+        ank_base::ExtraHost {
+            hostname: item.hostname,
+            ip: item.ip,
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{verify_hostname_format, ExtraHost};
+    use api::ank_base;
+
+    fn extra_host(hostname: &str, ip: &str) -> ExtraHost {
+        ExtraHost {
+            hostname: hostname.to_owned(),
+            ip: ip.to_owned(),
+        }
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_extra_host_from_and_to_proto() {
+        let proto_extra_host = ank_base::ExtraHost {
+            hostname: String::from("database"),
+            ip: String::from("10.0.0.5"),
+        };
+
+        assert_eq!(
+            ExtraHost::from(proto_extra_host.clone()),
+            extra_host("database", "10.0.0.5")
+        );
+        assert_eq!(
+            ank_base::ExtraHost::from(extra_host("database", "10.0.0.5")),
+            proto_extra_host
+        );
+    }
+
+    // [utest->swdd~common-workload-hostname-convention~1]
+    #[test]
+    fn utest_verify_hostname_format() {
+        assert!(verify_hostname_format("vehicle-1").is_ok());
+        assert!(verify_hostname_format("db.local.example").is_ok());
+        assert!(verify_hostname_format("").is_err());
+        assert!(verify_hostname_format("-vehicle").is_err());
+        assert!(verify_hostname_format("vehicle_1").is_err());
+        assert!(verify_hostname_format("vehicle..local").is_err());
+        assert!(verify_hostname_format(&"a".repeat(64)).is_err());
+        assert!(verify_hostname_format(&format!("{0}.{0}.{0}.{0}", "a".repeat(63))).is_err());
+    }
+
+    // [utest->swdd~common-workload-hostname-convention~1]
+    #[test]
+    fn utest_verify_extra_host_format() {
+        assert!(extra_host("database", "10.0.0.5").verify_format().is_ok());
+        assert!(extra_host("database", "fd00::5").verify_format().is_ok());
+        assert!(extra_host("database", "10.0.0.256")
+            .verify_format()
+            .is_err());
+        assert!(extra_host("data base", "10.0.0.5").verify_format().is_err());
+    }
+}
@@ -0,0 +1,317 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use api::ank_base;
+
+pub const DEFAULT_HEALTH_CHECK_INTERVAL_SECS: u32 = 10;
+pub const DEFAULT_HEALTH_CHECK_RETRIES: u32 = 3;
+
+#[derive(Debug, Clone, Serialize, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HealthCheck {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tcp: Option<TcpProbe>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http: Option<HttpProbe>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval_secs: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TcpProbe {
+    pub host: String,
+    pub port: u16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpProbe {
+    pub url: String,
+}
+
+impl HealthCheck {
+    // [impl->swdd~common-workload-health-check-convention~1]
+    pub fn verify_format(&self) -> Result<(), String> {
+        let probe_count = [
+            self.command.is_some(),
+            self.tcp.is_some(),
+            self.http.is_some(),
+        ]
+        .into_iter()
+        .filter(|is_set| *is_set)
+        .count();
+        if probe_count != 1 {
+            return Err(
+                "Unsupported health check. Expected exactly one of the 'command', 'tcp' and 'http' probes"
+                    .to_string(),
+            );
+        }
+        if let Some(command) = &self.command {
+            if command.first().is_none_or(|program| program.is_empty()) {
+                return Err(
+                    "Unsupported health check command. Expected a non-empty command".to_string(),
+                );
+            }
+        }
+        if let Some(tcp) = &self.tcp {
+            if tcp.host.is_empty() || tcp.port == 0 {
+                return Err(format!(
+                    "Unsupported health check TCP probe '{}:{}'. Expected a host and a port greater than 0",
+                    tcp.host, tcp.port
+                ));
+            }
+        }
+        if let Some(http) = &self.http {
+            if http
+                .url
+                .strip_prefix(HttpProbe::URL_SCHEME)
+                .is_none_or(str::is_empty)
+            {
+                return Err(format!(
+                    "Unsupported health check URL '{}'. Expected an URL starting with '{}'",
+                    http.url,
+                    HttpProbe::URL_SCHEME
+                ));
+            }
+        }
+        if self.interval_secs == Some(0) {
+            return Err(
+                "Unsupported health check interval '0'. Expected a value in seconds greater than 0"
+                    .to_string(),
+            );
+        }
+        if self.retries == Some(0) {
+            return Err(
+                "Unsupported health check retries '0'. Expected a value greater than 0".to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(
+            self.interval_secs
+                .unwrap_or(DEFAULT_HEALTH_CHECK_INTERVAL_SECS)
+                .into(),
+        )
+    }
+
+    pub fn retries(&self) -> u32 {
+        self.retries.unwrap_or(DEFAULT_HEALTH_CHECK_RETRIES)
+    }
+}
+
+impl HttpProbe {
+    pub const URL_SCHEME: &'static str = "http://";
+}
+
+impl TryFrom<ank_base::HealthCheck> for HealthCheck {
+    type Error = String;
+
+    fn try_from(item: ank_base::HealthCheck) -> Result<Self, String> {
+        Ok(HealthCheck {
+            command: item.command.map(|probe| probe.command),
+            tcp: item
+                .tcp
+                .map(|probe| {
+                    Ok::<_, String>(TcpProbe {
+                        port: u16::try_from(probe.port).map_err(|_| {
+                            format!("Received an out of range port '{}'.", probe.port)
+                        })?,
+                        host: probe.host,
+                    })
+                })
+                .transpose()?,
+            http: item.http.map(|probe| HttpProbe { url: probe.url }),
+            interval_secs: item.interval_secs,
+            retries: item.retries,
+        })
+    }
+}
+
+impl From<HealthCheck> for ank_base::HealthCheck {
+    fn from(item: HealthCheck) -> Self {
+        ank_base::HealthCheck {
+            command: item
+                .command
+                .map(|command| ank_base::CommandProbe { command }),
+            tcp: item.tcp.map(|probe| ank_base::TcpProbe {
+                host: probe.host,
+                port: probe.port.into(),
+            }),
+            http: item
+                .http
+                .map(|probe| ank_base::HttpProbe { url: probe.url }),
+            interval_secs: item.interval_secs,
+            retries: item.retries,
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{HealthCheck, HttpProbe, TcpProbe};
+    use api::ank_base;
+
+    fn tcp_health_check(host: &str, port: u16) -> HealthCheck {
+        HealthCheck {
+            tcp: Some(TcpProbe {
+                host: host.into(),
+                port,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn http_health_check(url: &str) -> HealthCheck {
+        HealthCheck {
+            http: Some(HttpProbe { url: url.into() }),
+            ..Default::default()
+        }
+    }
+
+    fn command_health_check(command: &[&str]) -> HealthCheck {
+        HealthCheck {
+            command: Some(command.iter().map(ToString::to_string).collect()),
+            ..Default::default()
+        }
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_health_check_from_and_to_proto() {
+        let proto_health_check = ank_base::HealthCheck {
+            command: None,
+            tcp: Some(ank_base::TcpProbe {
+                host: "localhost".into(),
+                port: 8080,
+            }),
+            http: None,
+            interval_secs: Some(5),
+            retries: None,
+        };
+        let health_check = HealthCheck {
+            interval_secs: Some(5),
+            ..tcp_health_check("localhost", 8080)
+        };
+
+        assert_eq!(
+            HealthCheck::try_from(proto_health_check.clone()),
+            Ok(health_check.clone())
+        );
+        assert_eq!(
+            ank_base::HealthCheck::from(health_check),
+            proto_health_check
+        );
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_health_check_from_proto_fails_on_out_of_range_port() {
+        let proto_health_check = ank_base::HealthCheck {
+            tcp: Some(ank_base::TcpProbe {
+                host: "localhost".into(),
+                port: 70000,
+            }),
+            ..Default::default()
+        };
+
+        assert!(HealthCheck::try_from(proto_health_check).is_err());
+    }
+
+    // [utest->swdd~common-workload-health-check-convention~1]
+    #[test]
+    fn utest_verify_health_check_format_probes() {
+        assert!(command_health_check(&["pg_isready"])
+            .verify_format()
+            .is_ok());
+        assert!(tcp_health_check("localhost", 5432).verify_format().is_ok());
+        assert!(http_health_check("http://localhost:8080/health")
+            .verify_format()
+            .is_ok());
+
+        assert!(HealthCheck::default().verify_format().is_err());
+        assert!(HealthCheck {
+            command: Some(vec!["pg_isready".into()]),
+            ..tcp_health_check("localhost", 5432)
+        }
+        .verify_format()
+        .is_err());
+        assert!(command_health_check(&[]).verify_format().is_err());
+        assert!(tcp_health_check("", 5432).verify_format().is_err());
+        assert!(tcp_health_check("localhost", 0).verify_format().is_err());
+        assert!(http_health_check("https://localhost/health")
+            .verify_format()
+            .is_err());
+        assert!(http_health_check("http://").verify_format().is_err());
+    }
+
+    // [utest->swdd~common-workload-health-check-convention~1]
+    #[test]
+    fn utest_verify_health_check_format_interval_and_retries() {
+        let health_check = HealthCheck {
+            interval_secs: Some(1),
+            retries: Some(1),
+            ..tcp_health_check("localhost", 5432)
+        };
+        assert!(health_check.verify_format().is_ok());
+
+        assert!(HealthCheck {
+            interval_secs: Some(0),
+            ..health_check.clone()
+        }
+        .verify_format()
+        .is_err());
+        assert!(HealthCheck {
+            retries: Some(0),
+            ..health_check
+        }
+        .verify_format()
+        .is_err());
+    }
+
+    #[test]
+    fn utest_health_check_defaults() {
+        let health_check = tcp_health_check("localhost", 5432);
+        assert_eq!(health_check.interval(), Duration::from_secs(10));
+        assert_eq!(health_check.retries(), 3);
+
+        let health_check = HealthCheck {
+            interval_secs: Some(2),
+            retries: Some(5),
+            ..health_check
+        };
+        assert_eq!(health_check.interval(), Duration::from_secs(2));
+        assert_eq!(health_check.retries(), 5);
+    }
+}
@@ -0,0 +1,310 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use api::ank_base;
+use serde::{Deserialize, Serialize};
+
+type AgentName = String;
+type ImageName = String;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImagePullState {
+    #[default]
+    Pending,
+    Pulling,
+    Pulled,
+    Failed,
+}
+
+impl Display for ImagePullState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImagePullState::Pending => write!(f, "Pending"),
+            ImagePullState::Pulling => write!(f, "Pulling"),
+            ImagePullState::Pulled => write!(f, "Pulled"),
+            ImagePullState::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
+impl From<i32> for ImagePullState {
+    fn from(value: i32) -> Self {
+        match value {
+            x if x == ank_base::ImagePullState::Pulling as i32 => ImagePullState::Pulling,
+            x if x == ank_base::ImagePullState::Pulled as i32 => ImagePullState::Pulled,
+            x if x == ank_base::ImagePullState::PullFailed as i32 => ImagePullState::Failed,
+            // unknown states of newer agents are shown as pending
+            _ => ImagePullState::Pending,
+        }
+    }
+}
+
+impl From<ImagePullState> for i32 {
+    fn from(value: ImagePullState) -> Self {
+        match value {
+            ImagePullState::Pending => ank_base::ImagePullState::PullPending as i32,
+            ImagePullState::Pulling => ank_base::ImagePullState::Pulling as i32,
+            ImagePullState::Pulled => ank_base::ImagePullState::Pulled as i32,
+            ImagePullState::Failed => ank_base::ImagePullState::PullFailed as i32,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageState {
+    pub state: ImagePullState,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub additional_info: String,
+}
+
+impl ImageState {
+    pub fn pending() -> Self {
+        ImageState::default()
+    }
+
+    pub fn pulling() -> Self {
+        ImageState {
+            state: ImagePullState::Pulling,
+            ..Default::default()
+        }
+    }
+
+    pub fn pulled() -> Self {
+        ImageState {
+            state: ImagePullState::Pulled,
+            ..Default::default()
+        }
+    }
+
+    pub fn failed(additional_info: impl ToString) -> Self {
+        ImageState {
+            state: ImagePullState::Failed,
+            additional_info: additional_info.to_string(),
+        }
+    }
+}
+
+// [impl->swdd~image-states-map-stores-image-states-per-agent~1]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct ImageStatesMap(HashMap<AgentName, HashMap<ImageName, ImageState>>);
+
+impl ImageStatesMap {
+    pub fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    pub fn get_image_states_for_agent(
+        &self,
+        agent_name: &str,
+    ) -> Option<&HashMap<ImageName, ImageState>> {
+        self.0.get(agent_name)
+    }
+
+    // The reports of an agent always contain all its pinned images,
+    // so the previous states of the agent are replaced completely.
+    pub fn update_for_agent(
+        &mut self,
+        agent_name: &str,
+        image_states: HashMap<ImageName, ImageState>,
+    ) {
+        if image_states.is_empty() {
+            self.0.remove(agent_name);
+        } else {
+            self.0.insert(agent_name.to_owned(), image_states);
+        }
+    }
+
+    pub fn remove_agent(&mut self, agent_name: &str) {
+        self.0.remove(agent_name);
+    }
+}
+
+impl IntoIterator for ImageStatesMap {
+    type Item = <HashMap<AgentName, HashMap<ImageName, ImageState>> as IntoIterator>::Item;
+
+    type IntoIter = <HashMap<AgentName, HashMap<ImageName, ImageState>> as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl From<ImageState> for ank_base::ImageState {
+    fn from(item: ImageState) -> Self {
+        ank_base::ImageState {
+            state: item.state.into(),
+            additional_info: item.additional_info,
+        }
+    }
+}
+
+impl From<ank_base::ImageState> for ImageState {
+    fn from(item: ank_base::ImageState) -> Self {
+        ImageState {
+            state: item.state.into(),
+            additional_info: item.additional_info,
+        }
+    }
+}
+
+impl From<ImageStatesMap> for Option<ank_base::ImageStatesMap> {
+    fn from(item: ImageStatesMap) -> Option<ank_base::ImageStatesMap> {
+        if item.0.is_empty() {
+            return None;
+        }
+        Some(ank_base::ImageStatesMap {
+            agent_image_states: item
+                .0
+                .into_iter()
+                .map(|(agent_name, image_states)| {
+                    (
+                        agent_name,
+                        ank_base::ImageStatesOfAgent {
+                            image_states: image_states
+                                .into_iter()
+                                .map(|(image, state)| (image, state.into()))
+                                .collect(),
+                        },
+                    )
+                })
+                .collect(),
+        })
+    }
+}
+
+impl From<ank_base::ImageStatesMap> for ImageStatesMap {
+    fn from(item: ank_base::ImageStatesMap) -> ImageStatesMap {
+        ImageStatesMap(
+            item.agent_image_states
+                .into_iter()
+                .map(|(agent_name, image_states)| {
+                    (
+                        agent_name,
+                        image_states
+                            .image_states
+                            .into_iter()
+                            .map(|(image, state)| (image, state.into()))
+                            .collect(),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use api::ank_base;
+
+    use super::{ImagePullState, ImageState, ImageStatesMap};
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+    const IMAGE_1: &str = "ghcr.io/eclipse-ankaios/image_1:1.0";
+    const IMAGE_2: &str = "ghcr.io/eclipse-ankaios/image_2:1.0";
+
+    // [utest->swdd~image-states-map-stores-image-states-per-agent~1]
+    #[test]
+    fn utest_image_states_map_update_for_agent_replaces_previous_states() {
+        let mut image_states_map = ImageStatesMap::new();
+        image_states_map.update_for_agent(
+            AGENT_A,
+            HashMap::from([
+                (IMAGE_1.to_string(), ImageState::pulling()),
+                (IMAGE_2.to_string(), ImageState::pending()),
+            ]),
+        );
+        image_states_map.update_for_agent(
+            AGENT_B,
+            HashMap::from([(IMAGE_1.to_string(), ImageState::pulled())]),
+        );
+
+        image_states_map.update_for_agent(
+            AGENT_A,
+            HashMap::from([(IMAGE_1.to_string(), ImageState::failed("not found"))]),
+        );
+
+        assert_eq!(
+            image_states_map.get_image_states_for_agent(AGENT_A),
+            Some(&HashMap::from([(
+                IMAGE_1.to_string(),
+                ImageState::failed("not found")
+            )]))
+        );
+        assert_eq!(
+            image_states_map.get_image_states_for_agent(AGENT_B),
+            Some(&HashMap::from([(
+                IMAGE_1.to_string(),
+                ImageState::pulled()
+            )]))
+        );
+
+        image_states_map.update_for_agent(AGENT_A, HashMap::new());
+        image_states_map.remove_agent(AGENT_B);
+        assert_eq!(image_states_map, ImageStatesMap::new());
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_image_states_map_from_and_to_proto() {
+        let mut image_states_map = ImageStatesMap::new();
+        image_states_map.update_for_agent(
+            AGENT_A,
+            HashMap::from([(IMAGE_1.to_string(), ImageState::failed("not found"))]),
+        );
+
+        let proto_image_states_map: Option<ank_base::ImageStatesMap> =
+            image_states_map.clone().into();
+
+        assert_eq!(
+            proto_image_states_map.as_ref().unwrap().agent_image_states[AGENT_A].image_states
+                [IMAGE_1],
+            ank_base::ImageState {
+                state: ank_base::ImagePullState::PullFailed as i32,
+                additional_info: "not found".to_string(),
+            }
+        );
+        assert_eq!(
+            ImageStatesMap::from(proto_image_states_map.unwrap()),
+            image_states_map
+        );
+        assert_eq!(
+            Option::<ank_base::ImageStatesMap>::from(ImageStatesMap::new()),
+            None
+        );
+    }
+
+    #[test]
+    fn utest_image_pull_state_from_unknown_value_is_pending() {
+        assert_eq!(ImagePullState::from(42), ImagePullState::Pending);
+        assert_eq!(
+            ImagePullState::from(i32::from(ImagePullState::Pulled)),
+            ImagePullState::Pulled
+        );
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use api::ank_base;
+
+pub const DEFAULT_LIFECYCLE_HOOK_TIMEOUT_SECS: u32 = 30;
+
+#[derive(Debug, Clone, Serialize, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LifecycleHooks {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_start: Option<LifecycleHook>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_stop: Option<LifecycleHook>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LifecycleHook {
+    pub command: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u32>,
+}
+
+impl LifecycleHooks {
+    // [impl->swdd~common-workload-lifecycle-hooks-convention~1]
+    pub fn verify_format(&self) -> Result<(), String> {
+        [("postStart", &self.post_start), ("preStop", &self.pre_stop)]
+            .into_iter()
+            .filter_map(|(hook_name, hook)| hook.as_ref().map(|hook| (hook_name, hook)))
+            .try_for_each(|(hook_name, hook)| hook.verify_format(hook_name))
+    }
+}
+
+impl LifecycleHook {
+    fn verify_format(&self, hook_name: &str) -> Result<(), String> {
+        if self
+            .command
+            .first()
+            .is_none_or(|program| program.is_empty())
+        {
+            return Err(format!(
+                "Unsupported '{}' lifecycle hook. Expected a non-empty command",
+                hook_name
+            ));
+        }
+        if self.timeout_secs == Some(0) {
+            return Err(format!(
+                "Unsupported '{}' lifecycle hook timeout '0'. Expected a value in seconds greater than 0",
+                hook_name
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.timeout_secs
+                .unwrap_or(DEFAULT_LIFECYCLE_HOOK_TIMEOUT_SECS)
+                .into(),
+        )
+    }
+}
+
+impl From<ank_base::LifecycleHooks> for LifecycleHooks {
+    fn from(item: ank_base::LifecycleHooks) -> Self {
+        LifecycleHooks {
+            post_start: item.post_start.map(Into::into),
+            pre_stop: item.pre_stop.map(Into::into),
+        }
+    }
+}
+
+impl From<LifecycleHooks> for ank_base::LifecycleHooks {
+    fn from(item: LifecycleHooks) -> Self {
+        ank_base::LifecycleHooks {
+            post_start: item.post_start.map(Into::into),
+            pre_stop: item.pre_stop.map(Into::into),
+        }
+    }
+}
+
+impl From<ank_base::LifecycleHook> for LifecycleHook {
+    fn from(item: ank_base::LifecycleHook) -> Self {
+        LifecycleHook {
+            command: item.command,
+            timeout_secs: item.timeout_secs,
+        }
+    }
+}
+
+impl From<LifecycleHook> for ank_base::LifecycleHook {
+    fn from(item: LifecycleHook) -> Self {
+        ank_base::LifecycleHook {
+            command: item.command,
+            timeout_secs: item.timeout_secs,
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{LifecycleHook, LifecycleHooks};
+    use api::ank_base;
+
+    fn lifecycle_hook(command: &[&str], timeout_secs: Option<u32>) -> LifecycleHook {
+        LifecycleHook {
+            command: command.iter().map(|part| part.to_string()).collect(),
+            timeout_secs,
+        }
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_lifecycle_hooks_from_and_to_proto() {
+        let lifecycle_hooks = LifecycleHooks {
+            post_start: Some(lifecycle_hook(&["warmup"], Some(10))),
+            pre_stop: Some(lifecycle_hook(&["flush", "--all"], None)),
+        };
+
+        let proto_lifecycle_hooks = ank_base::LifecycleHooks::from(lifecycle_hooks.clone());
+
+        assert_eq!(
+            proto_lifecycle_hooks.pre_stop,
+            Some(ank_base::LifecycleHook {
+                command: vec!["flush".into(), "--all".into()],
+                timeout_secs: None,
+            })
+        );
+        assert_eq!(LifecycleHooks::from(proto_lifecycle_hooks), lifecycle_hooks);
+    }
+
+    // [utest->swdd~common-workload-lifecycle-hooks-convention~1]
+    #[test]
+    fn utest_verify_lifecycle_hooks_format() {
+        assert!(LifecycleHooks::default().verify_format().is_ok());
+        assert!(LifecycleHooks {
+            post_start: Some(lifecycle_hook(&["warmup"], Some(10))),
+            pre_stop: Some(lifecycle_hook(&["flush"], None)),
+        }
+        .verify_format()
+        .is_ok());
+
+        assert!(LifecycleHooks {
+            post_start: Some(lifecycle_hook(&[], None)),
+            ..Default::default()
+        }
+        .verify_format()
+        .is_err());
+        assert!(LifecycleHooks {
+            pre_stop: Some(lifecycle_hook(&[""], None)),
+            ..Default::default()
+        }
+        .verify_format()
+        .is_err());
+        assert!(LifecycleHooks {
+            pre_stop: Some(lifecycle_hook(&["flush"], Some(0))),
+            ..Default::default()
+        }
+        .verify_format()
+        .is_err());
+    }
+
+    #[test]
+    fn utest_lifecycle_hook_timeout() {
+        assert_eq!(
+            lifecycle_hook(&["warmup"], None).timeout(),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            lifecycle_hook(&["warmup"], Some(5)).timeout(),
+            Duration::from_secs(5)
+        );
+    }
+}
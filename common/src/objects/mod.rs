@@ -18,7 +18,10 @@
 
 pub mod state;
 pub use state::State;
-pub use state::CURRENT_API_VERSION;
+pub use state::{
+    convert_state_to_api_version, API_VERSION_V0_1, API_VERSION_V0_1_STATE_FIELDS,
+    API_VERSION_V0_1_WORKLOAD_FIELDS, CURRENT_API_VERSION, SUPPORTED_API_VERSIONS,
+};
 
 mod complete_state;
 pub use complete_state::CompleteState;
@@ -26,7 +29,18 @@ pub use complete_state::CompleteState;
 mod agent_map;
 #[cfg(any(feature = "test_utils", test))]
 pub use agent_map::{generate_test_agent_map, generate_test_agent_map_from_specs};
-pub use agent_map::{AgentAttributes, AgentMap, CpuUsage, FreeMemory};
+pub use agent_map::{
+    AgentAttributes, AgentLoadSample, AgentMap, CpuUsage, DiskUsage, FreeMemory,
+    NetworkInterfaceUsage, NetworkUsage, Temperature, ThermalZone,
+};
+
+mod workload_resources;
+#[cfg(any(feature = "test_utils", test))]
+pub use workload_resources::generate_test_workload_resources;
+pub use workload_resources::{ResourceUsage, WorkloadResources, WorkloadResourcesMap};
+
+mod image_states;
+pub use image_states::{ImagePullState, ImageState, ImageStatesMap};
 
 mod workload_states_map;
 pub use workload_states_map::WorkloadStatesMap;
@@ -62,7 +76,7 @@ pub use workload_spec::{
     generate_test_workload_spec_with_dependencies, generate_test_workload_spec_with_param,
     generate_test_workload_spec_with_runtime_config,
 };
-pub use workload_spec::{STR_RE_AGENT, STR_RE_WORKLOAD};
+pub use workload_spec::{STR_RE_AGENT, STR_RE_ENV_VAR_NAME, STR_RE_WORKLOAD};
 
 pub use workload_spec::{
     get_workloads_per_agent, AddCondition, DeleteCondition, DeletedWorkload,
@@ -72,6 +86,48 @@ pub use workload_spec::{
 mod tag;
 pub use tag::Tag;
 
+mod extra_host;
+pub use extra_host::{ExtraHost, STR_RE_HOSTNAME};
+
+mod port;
+pub use port::{Port, PortProtocol};
+
+mod volume;
+pub use volume::{Volume, VolumeType};
+
+mod network;
+pub use network::Network;
+
+mod health_check;
+pub use health_check::{HealthCheck, HttpProbe, TcpProbe};
+
+mod lifecycle_hooks;
+pub use lifecycle_hooks::{LifecycleHook, LifecycleHooks};
+
+mod security_context;
+pub use security_context::{Capabilities, SecurityContext};
+
+mod restart_backoff;
+pub use restart_backoff::RestartBackoff;
+
+mod agent_disconnect_policy;
+pub use agent_disconnect_policy::AgentDisconnectPolicy;
+
+mod resource_limits;
+pub use resource_limits::ResourceLimits;
+
+mod resource_thresholds;
+pub use resource_thresholds::ResourceThresholds;
+
+mod workload_defaults;
+pub use workload_defaults::WorkloadDefaults;
+
+mod namespace;
+pub use namespace::{
+    desired_state_path_of_namespace, desired_state_path_of_workload, qualified_workload_name,
+    split_qualified_workload_name, Namespace, NAMESPACE_SEPARATOR, STR_RE_NAMESPACE,
+};
+
 mod workload_instance_name;
 #[cfg(any(feature = "test_utils", test))]
 pub use workload_instance_name::generate_test_workload_instance_name;
@@ -84,10 +140,10 @@ mod control_interface_access;
 #[cfg(any(feature = "test_utils", test))]
 pub use control_interface_access::generate_test_control_interface_access;
 pub use control_interface_access::{
-    AccessRightsRule, ControlInterfaceAccess, ReadWriteEnum, StateRule,
+    AccessRightsRule, ControlInterfaceAccess, PipesOwner, ReadWriteEnum, StateRule,
 };
 
 mod config;
 #[cfg(any(feature = "test_utils", test))]
 pub use config::generate_test_configs;
-pub use config::ConfigItem;
+pub use config::{replace_secret_placeholders, ConfigItem, SecretProvider, SecretRef};
@@ -0,0 +1,158 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use api::ank_base;
+
+use crate::helpers::serialize_to_ordered_map;
+
+use super::StoredWorkloadSpec;
+
+pub const STR_RE_NAMESPACE: &str = r"^[a-zA-Z0-9-]+$";
+pub const NAMESPACE_SEPARATOR: &str = "__";
+
+const DESIRED_STATE_WORKLOADS: &str = "desiredState.workloads";
+const DESIRED_STATE_NAMESPACES: &str = "desiredState.namespaces";
+
+// [impl->swdd~common-namespaces~1]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Namespace {
+    #[serde(default, serialize_with = "serialize_to_ordered_map")]
+    pub workloads: HashMap<String, StoredWorkloadSpec>,
+}
+
+impl TryFrom<ank_base::Namespace> for Namespace {
+    type Error = String;
+
+    fn try_from(item: ank_base::Namespace) -> Result<Self, Self::Error> {
+        Ok(Namespace {
+            workloads: item
+                .workloads
+                .unwrap_or_default()
+                .workloads
+                .into_iter()
+                .map(|(k, v)| Ok((k, v.try_into()?)))
+                .collect::<Result<_, Self::Error>>()?,
+        })
+    }
+}
+
+impl From<Namespace> for ank_base::Namespace {
+    fn from(item: Namespace) -> Self {
+        ank_base::Namespace {
+            workloads: Some(ank_base::WorkloadMap {
+                workloads: item
+                    .workloads
+                    .into_iter()
+                    .map(|(k, v)| (k, v.into()))
+                    .collect(),
+            }),
+        }
+    }
+}
+
+// [impl->swdd~common-namespaced-workload-names~1]
+pub fn qualified_workload_name(namespace: &str, workload_name: &str) -> String {
+    format!("{}{}{}", namespace, NAMESPACE_SEPARATOR, workload_name)
+}
+
+// [impl->swdd~common-namespaced-workload-names~1]
+pub fn split_qualified_workload_name(workload_name: &str) -> Option<(&str, &str)> {
+    workload_name.split_once(NAMESPACE_SEPARATOR)
+}
+
+// Returns the path of a workload inside the desired state, e.g., for building field masks
+// [impl->swdd~common-namespaced-workload-names~1]
+pub fn desired_state_path_of_workload(workload_name: &str) -> String {
+    match split_qualified_workload_name(workload_name) {
+        Some((namespace, workload_name)) => format!(
+            "{}.{}.workloads.{}",
+            DESIRED_STATE_NAMESPACES, namespace, workload_name
+        ),
+        None => format!("{}.{}", DESIRED_STATE_WORKLOADS, workload_name),
+    }
+}
+
+// Returns the path of a namespace inside the desired state
+pub fn desired_state_path_of_namespace(namespace: &str) -> String {
+    format!("{}.{}", DESIRED_STATE_NAMESPACES, namespace)
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        desired_state_path_of_workload, qualified_workload_name, split_qualified_workload_name,
+        Namespace,
+    };
+    use crate::objects::generate_test_stored_workload_spec;
+    use api::ank_base;
+    use std::collections::HashMap;
+
+    const NAMESPACE: &str = "team-a";
+    const WORKLOAD_NAME: &str = "workload_1";
+    const AGENT_A: &str = "agent_A";
+    const RUNTIME: &str = "runtime";
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_namespace_from_and_to_proto() {
+        let namespace = Namespace {
+            workloads: HashMap::from([(
+                WORKLOAD_NAME.to_owned(),
+                generate_test_stored_workload_spec(AGENT_A, RUNTIME),
+            )]),
+        };
+
+        let proto_namespace = ank_base::Namespace::from(namespace.clone());
+
+        assert_eq!(Namespace::try_from(proto_namespace), Ok(namespace));
+    }
+
+    // [utest->swdd~common-namespaced-workload-names~1]
+    #[test]
+    fn utest_qualified_workload_name_is_split_into_namespace_and_workload_name() {
+        let qualified_name = qualified_workload_name(NAMESPACE, WORKLOAD_NAME);
+
+        assert_eq!(qualified_name, "team-a__workload_1");
+        assert_eq!(
+            split_qualified_workload_name(&qualified_name),
+            Some((NAMESPACE, WORKLOAD_NAME))
+        );
+        assert_eq!(split_qualified_workload_name(WORKLOAD_NAME), None);
+    }
+
+    // [utest->swdd~common-namespaced-workload-names~1]
+    #[test]
+    fn utest_desired_state_path_of_workload() {
+        assert_eq!(
+            desired_state_path_of_workload(WORKLOAD_NAME),
+            "desiredState.workloads.workload_1"
+        );
+        assert_eq!(
+            desired_state_path_of_workload("team-a__workload_1"),
+            "desiredState.namespaces.team-a.workloads.workload_1"
+        );
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use api::ank_base;
+
+use super::extra_host::verify_hostname_format;
+
+pub const STR_RE_NETWORK_NAME: &str = r"^[a-zA-Z0-9][a-zA-Z0-9_.-]*$";
+// the network modes of the runtime which replace the networks instead of joining one
+const EXCLUSIVE_NETWORK_NAMES: [&str; 2] = ["host", "none"];
+
+#[derive(Debug, Clone, Serialize, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Network {
+    pub name: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+}
+
+impl Network {
+    // [impl->swdd~common-workload-network-convention~1]
+    pub fn verify_format(&self) -> Result<(), String> {
+        let re_network_name = Regex::new(STR_RE_NETWORK_NAME).unwrap();
+        if !re_network_name.is_match(&self.name) {
+            return Err(format!(
+                "Unsupported network name. Received '{}', expected to match {}",
+                self.name, STR_RE_NETWORK_NAME
+            ));
+        }
+        for alias in &self.aliases {
+            verify_hostname_format(alias)?;
+        }
+        Ok(())
+    }
+}
+
+// [impl->swdd~common-workload-network-combinations~1]
+pub fn verify_network_combination(networks: &[Network]) -> Result<(), String> {
+    for (index, network) in networks.iter().enumerate() {
+        if networks[..index]
+            .iter()
+            .any(|other| other.name == network.name)
+        {
+            return Err(format!(
+                "Unsupported networks. The network '{}' is joined more than once",
+                network.name
+            ));
+        }
+        if EXCLUSIVE_NETWORK_NAMES.contains(&network.name.as_str())
+            && (networks.len() > 1 || !network.aliases.is_empty())
+        {
+            return Err(format!(
+                "Unsupported networks. The network '{}' can neither be combined with other networks nor have aliases",
+                network.name
+            ));
+        }
+    }
+    Ok(())
+}
+
+impl From<ank_base::Network> for Network {
+    fn from(item: ank_base::Network) -> Self {
+        Network {
+            name: item.name,
+            aliases: item.aliases,
+        }
+    }
+}
+
+impl From<Network> for ank_base::Network {
+    fn from(item: Network) -> Self {
+        ank_base::Network {
+            name: item.name,
+            aliases: item.aliases,
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{verify_network_combination, Network};
+    use api::ank_base;
+
+    fn network(name: &str, aliases: &[&str]) -> Network {
+        Network {
+            name: name.to_owned(),
+            aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+        }
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_network_from_and_to_proto() {
+        let proto_network = ank_base::Network {
+            name: String::from("backend"),
+            aliases: vec![String::from("api")],
+        };
+
+        assert_eq!(
+            Network::from(proto_network.clone()),
+            network("backend", &["api"])
+        );
+        assert_eq!(
+            ank_base::Network::from(network("backend", &["api"])),
+            proto_network
+        );
+    }
+
+    // [utest->swdd~common-workload-network-convention~1]
+    #[test]
+    fn utest_verify_network_format() {
+        assert!(network("backend", &[]).verify_format().is_ok());
+        assert!(network("vehicle_net-1.0", &["api", "api.local"])
+            .verify_format()
+            .is_ok());
+
+        assert!(network("", &[]).verify_format().is_err());
+        assert!(network("-backend", &[]).verify_format().is_err());
+        assert!(network("back end", &[]).verify_format().is_err());
+        assert!(network("backend", &["api_1"]).verify_format().is_err());
+    }
+
+    // [utest->swdd~common-workload-network-combinations~1]
+    #[test]
+    fn utest_verify_network_combination() {
+        let backend = network("backend", &["api"]);
+        let frontend = network("frontend", &[]);
+        let host = network("host", &[]);
+
+        assert!(verify_network_combination(&[]).is_ok());
+        assert!(verify_network_combination(std::slice::from_ref(&host)).is_ok());
+        assert!(verify_network_combination(&[backend.clone(), frontend]).is_ok());
+
+        assert!(verify_network_combination(&[backend.clone(), backend.clone()]).is_err());
+        assert!(verify_network_combination(&[host, backend]).is_err());
+        assert!(verify_network_combination(&[network("none", &["api"])]).is_err());
+    }
+}
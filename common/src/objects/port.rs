@@ -0,0 +1,186 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use api::ank_base;
+
+#[derive(Debug, Clone, Copy, Serialize, Default, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum PortProtocol {
+    #[default]
+    Tcp = 0,
+    Udp = 1,
+}
+
+impl std::fmt::Display for PortProtocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PortProtocol::Tcp => write!(f, "tcp"),
+            PortProtocol::Udp => write!(f, "udp"),
+        }
+    }
+}
+
+impl TryFrom<i32> for PortProtocol {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            x if x == PortProtocol::Tcp as i32 => Ok(PortProtocol::Tcp),
+            x if x == PortProtocol::Udp as i32 => Ok(PortProtocol::Udp),
+            _ => Err(format!(
+                "Received an unknown value '{value}' as port protocol."
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Port {
+    pub host_port: u16,
+    pub container_port: u16,
+    pub protocol: PortProtocol,
+}
+
+impl Port {
+    // [impl->swdd~common-workload-port-convention~1]
+    pub fn verify_format(&self) -> Result<(), String> {
+        if self.host_port == 0 || self.container_port == 0 {
+            return Err(format!(
+                "Unsupported port mapping '{}'. Host and container ports must be greater than 0",
+                self
+            ));
+        }
+        Ok(())
+    }
+
+    // [impl->swdd~agent-detects-host-port-conflicts~1]
+    pub fn conflicts_with(&self, other: &Port) -> bool {
+        self.host_port == other.host_port && self.protocol == other.protocol
+    }
+}
+
+impl std::fmt::Display for Port {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}/{}",
+            self.host_port, self.container_port, self.protocol
+        )
+    }
+}
+
+impl TryFrom<ank_base::Port> for Port {
+    type Error = String;
+
+    fn try_from(item: ank_base::Port) -> Result<Self, String> {
+        let to_port = |value: u32| {
+            u16::try_from(value).map_err(|_| format!("Received an out of range port '{value}'."))
+        };
+        Ok(Port {
+            host_port: to_port(item.host_port)?,
+            container_port: to_port(item.container_port)?,
+            protocol: item.protocol.try_into()?,
+        })
+    }
+}
+
+impl From<Port> for ank_base::Port {
+    fn from(item: Port) -> Self {
+        ank_base::Port {
+            host_port: item.host_port.into(),
+            container_port: item.container_port.into(),
+            protocol: item.protocol as i32,
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{Port, PortProtocol};
+    use api::ank_base;
+
+    fn port(host_port: u16, container_port: u16, protocol: PortProtocol) -> Port {
+        Port {
+            host_port,
+            container_port,
+            protocol,
+        }
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_port_from_and_to_proto() {
+        let proto_port = ank_base::Port {
+            host_port: 8080,
+            container_port: 80,
+            protocol: ank_base::PortProtocol::Udp as i32,
+        };
+
+        assert_eq!(
+            Port::try_from(proto_port.clone()),
+            Ok(port(8080, 80, PortProtocol::Udp))
+        );
+        assert_eq!(
+            ank_base::Port::from(port(8080, 80, PortProtocol::Udp)),
+            proto_port
+        );
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_port_from_proto_fails_on_invalid_values() {
+        let proto_port = ank_base::Port {
+            host_port: 70000,
+            container_port: 80,
+            protocol: ank_base::PortProtocol::Tcp as i32,
+        };
+        assert!(Port::try_from(proto_port).is_err());
+
+        let proto_port = ank_base::Port {
+            host_port: 8080,
+            container_port: 80,
+            protocol: 42,
+        };
+        assert!(Port::try_from(proto_port).is_err());
+    }
+
+    // [utest->swdd~common-workload-port-convention~1]
+    #[test]
+    fn utest_verify_port_format() {
+        assert!(port(8080, 80, PortProtocol::Tcp).verify_format().is_ok());
+        assert!(port(0, 80, PortProtocol::Tcp).verify_format().is_err());
+        assert!(port(8080, 0, PortProtocol::Udp).verify_format().is_err());
+    }
+
+    // [utest->swdd~agent-detects-host-port-conflicts~1]
+    #[test]
+    fn utest_port_conflicts_only_on_same_host_port_and_protocol() {
+        let tcp_port = port(8080, 80, PortProtocol::Tcp);
+
+        assert!(tcp_port.conflicts_with(&port(8080, 8080, PortProtocol::Tcp)));
+        assert!(!tcp_port.conflicts_with(&port(8080, 80, PortProtocol::Udp)));
+        assert!(!tcp_port.conflicts_with(&port(8081, 80, PortProtocol::Tcp)));
+    }
+}
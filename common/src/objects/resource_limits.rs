@@ -0,0 +1,138 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use api::ank_base;
+
+const CPU_QUOTA_PER_CORE: u32 = 100;
+
+#[derive(Debug, Clone, Serialize, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ResourceLimits {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_quota: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_limit: Option<u64>,
+}
+
+impl ResourceLimits {
+    // [impl->swdd~common-workload-resource-limits-convention~1]
+    pub fn verify_format(&self) -> Result<(), String> {
+        if self.cpu_quota == Some(0) {
+            return Err(
+                "Unsupported CPU quota '0'. Expected a value in percent of a CPU core greater than 0"
+                    .to_string(),
+            );
+        }
+        if self.memory_limit == Some(0) {
+            return Err(
+                "Unsupported memory limit '0'. Expected a value in bytes greater than 0"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the CPU quota as decimal number of CPU cores, e.g. '1.5' for a quota of 150%
+    // [impl->swdd~common-workload-resource-limits-convention~1]
+    pub fn cpus(&self) -> Option<String> {
+        self.cpu_quota.map(|cpu_quota| {
+            format!(
+                "{}.{:02}",
+                cpu_quota / CPU_QUOTA_PER_CORE,
+                cpu_quota % CPU_QUOTA_PER_CORE
+            )
+        })
+    }
+}
+
+impl From<ank_base::ResourceLimits> for ResourceLimits {
+    fn from(item: ank_base::ResourceLimits) -> Self {
+        ResourceLimits {
+            cpu_quota: item.cpu_quota,
+            memory_limit: item.memory_limit,
+        }
+    }
+}
+
+impl From<ResourceLimits> for ank_base::ResourceLimits {
+    fn from(item: ResourceLimits) -> Self {
+        ank_base::ResourceLimits {
+            cpu_quota: item.cpu_quota,
+            memory_limit: item.memory_limit,
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::ResourceLimits;
+    use api::ank_base;
+
+    const MEMORY_LIMIT: u64 = 512 * 1024 * 1024;
+
+    fn resource_limits(cpu_quota: Option<u32>, memory_limit: Option<u64>) -> ResourceLimits {
+        ResourceLimits {
+            cpu_quota,
+            memory_limit,
+        }
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_resource_limits_from_and_to_proto() {
+        let proto_limits = ank_base::ResourceLimits {
+            cpu_quota: Some(150),
+            memory_limit: None,
+        };
+
+        assert_eq!(
+            ResourceLimits::from(proto_limits.clone()),
+            resource_limits(Some(150), None)
+        );
+        assert_eq!(
+            ank_base::ResourceLimits::from(resource_limits(Some(150), None)),
+            proto_limits
+        );
+    }
+
+    // [utest->swdd~common-workload-resource-limits-convention~1]
+    #[test]
+    fn utest_verify_resource_limits_format() {
+        assert!(resource_limits(None, None).verify_format().is_ok());
+        assert!(resource_limits(Some(250), Some(MEMORY_LIMIT))
+            .verify_format()
+            .is_ok());
+        assert!(resource_limits(Some(0), None).verify_format().is_err());
+        assert!(resource_limits(None, Some(0)).verify_format().is_err());
+    }
+
+    // [utest->swdd~common-workload-resource-limits-convention~1]
+    #[test]
+    fn utest_resource_limits_cpus() {
+        assert_eq!(resource_limits(None, None).cpus(), None);
+        assert_eq!(resource_limits(Some(150), None).cpus(), Some("1.50".into()));
+        assert_eq!(resource_limits(Some(5), None).cpus(), Some("0.05".into()));
+        assert_eq!(resource_limits(Some(200), None).cpus(), Some("2.00".into()));
+    }
+}
@@ -0,0 +1,163 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use api::ank_base;
+
+#[derive(Debug, Clone, Serialize, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ResourceThresholds {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_usage: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_usage: Option<u64>,
+}
+
+impl ResourceThresholds {
+    // [impl->swdd~common-workload-resource-thresholds-convention~1]
+    pub fn verify_format(&self) -> Result<(), String> {
+        if self.cpu_usage == Some(0) {
+            return Err(
+                "Unsupported CPU usage threshold '0'. Expected a value in percent greater than 0"
+                    .to_string(),
+            );
+        }
+        if self.memory_usage == Some(0) {
+            return Err(
+                "Unsupported memory usage threshold '0'. Expected a value in bytes greater than 0"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    // [impl->swdd~common-workload-resource-thresholds-breach~1]
+    pub fn breaches(&self, cpu_usage: f32, memory_usage: u64) -> Option<String> {
+        let mut breaches = Vec::new();
+        if let Some(cpu_threshold) = self.cpu_usage {
+            if cpu_usage > cpu_threshold as f32 {
+                breaches.push(format!("CPU usage above threshold of {cpu_threshold}%"));
+            }
+        }
+        if let Some(memory_threshold) = self.memory_usage {
+            if memory_usage > memory_threshold {
+                breaches.push(format!(
+                    "memory usage above threshold of {memory_threshold}B"
+                ));
+            }
+        }
+
+        if breaches.is_empty() {
+            None
+        } else {
+            Some(breaches.join(", "))
+        }
+    }
+}
+
+impl From<ank_base::ResourceThresholds> for ResourceThresholds {
+    fn from(item: ank_base::ResourceThresholds) -> Self {
+        ResourceThresholds {
+            cpu_usage: item.cpu_usage,
+            memory_usage: item.memory_usage,
+        }
+    }
+}
+
+impl From<ResourceThresholds> for ank_base::ResourceThresholds {
+    fn from(item: ResourceThresholds) -> Self {
+        ank_base::ResourceThresholds {
+            cpu_usage: item.cpu_usage,
+            memory_usage: item.memory_usage,
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::ResourceThresholds;
+    use api::ank_base;
+
+    const MEMORY_THRESHOLD: u64 = 512 * 1024 * 1024;
+
+    fn resource_thresholds(
+        cpu_usage: Option<u32>,
+        memory_usage: Option<u64>,
+    ) -> ResourceThresholds {
+        ResourceThresholds {
+            cpu_usage,
+            memory_usage,
+        }
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_resource_thresholds_from_and_to_proto() {
+        let proto_thresholds = ank_base::ResourceThresholds {
+            cpu_usage: Some(80),
+            memory_usage: None,
+        };
+
+        assert_eq!(
+            ResourceThresholds::from(proto_thresholds.clone()),
+            resource_thresholds(Some(80), None)
+        );
+        assert_eq!(
+            ank_base::ResourceThresholds::from(resource_thresholds(Some(80), None)),
+            proto_thresholds
+        );
+    }
+
+    // [utest->swdd~common-workload-resource-thresholds-convention~1]
+    #[test]
+    fn utest_verify_resource_thresholds_format() {
+        assert!(resource_thresholds(None, None).verify_format().is_ok());
+        assert!(resource_thresholds(Some(150), Some(MEMORY_THRESHOLD))
+            .verify_format()
+            .is_ok());
+        assert!(resource_thresholds(Some(0), None).verify_format().is_err());
+        assert!(resource_thresholds(None, Some(0)).verify_format().is_err());
+    }
+
+    // [utest->swdd~common-workload-resource-thresholds-breach~1]
+    #[test]
+    fn utest_resource_thresholds_breaches() {
+        let thresholds = resource_thresholds(Some(80), Some(MEMORY_THRESHOLD));
+
+        assert_eq!(thresholds.breaches(80.0, MEMORY_THRESHOLD), None);
+        assert_eq!(
+            thresholds.breaches(80.5, MEMORY_THRESHOLD),
+            Some("CPU usage above threshold of 80%".to_string())
+        );
+        assert_eq!(
+            thresholds.breaches(95.0, MEMORY_THRESHOLD + 1),
+            Some(format!(
+                "CPU usage above threshold of 80%, memory usage above threshold of {MEMORY_THRESHOLD}B"
+            ))
+        );
+        assert_eq!(
+            resource_thresholds(None, None).breaches(100.0, u64::MAX),
+            None
+        );
+    }
+}
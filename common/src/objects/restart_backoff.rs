@@ -0,0 +1,223 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use api::ank_base;
+
+pub const DEFAULT_RESTART_BACKOFF_INITIAL_DELAY_MS: u32 = 1000;
+pub const DEFAULT_RESTART_BACKOFF_MAX_DELAY_MS: u32 = 60000;
+pub const DEFAULT_RESTART_BACKOFF_MULTIPLIER: u32 = 2;
+pub const DEFAULT_RESTART_BACKOFF_MAX_RETRIES: u32 = 20;
+
+#[derive(Debug, Clone, Serialize, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RestartBackoff {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_delay: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_delay: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiplier: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
+}
+
+impl RestartBackoff {
+    // [impl->swdd~common-workload-restart-backoff-convention~1]
+    pub fn verify_format(&self) -> Result<(), String> {
+        if self.initial_delay == Some(0) {
+            return Err(
+                "Unsupported restart backoff initial delay '0'. Expected a value in milliseconds greater than 0"
+                    .to_string(),
+            );
+        }
+        if self.multiplier == Some(0) {
+            return Err(
+                "Unsupported restart backoff multiplier '0'. Expected a value greater than 0"
+                    .to_string(),
+            );
+        }
+        if self.max_retries == Some(0) {
+            return Err(
+                "Unsupported restart backoff max retries '0'. Expected a value greater than 0"
+                    .to_string(),
+            );
+        }
+        if self.max_delay() < self.initial_delay() {
+            return Err(format!(
+                "Unsupported restart backoff max delay '{}'. Expected a value not less than the initial delay '{}'",
+                self.max_delay().as_millis(),
+                self.initial_delay().as_millis()
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn initial_delay(&self) -> Duration {
+        Duration::from_millis(
+            self.initial_delay
+                .unwrap_or(DEFAULT_RESTART_BACKOFF_INITIAL_DELAY_MS)
+                .into(),
+        )
+    }
+
+    pub fn max_delay(&self) -> Duration {
+        Duration::from_millis(
+            self.max_delay
+                .unwrap_or(DEFAULT_RESTART_BACKOFF_MAX_DELAY_MS)
+                .into(),
+        )
+    }
+
+    pub fn multiplier(&self) -> u32 {
+        self.multiplier
+            .unwrap_or(DEFAULT_RESTART_BACKOFF_MULTIPLIER)
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+            .unwrap_or(DEFAULT_RESTART_BACKOFF_MAX_RETRIES)
+    }
+
+    /// Returns the delay before the given retry, starting with the initial delay for retry 0
+    /// and growing by the multiplier up to the max delay
+    // [impl->swdd~common-workload-restart-backoff-convention~1]
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = self.multiplier().saturating_pow(retry);
+        self.initial_delay()
+            .checked_mul(factor)
+            .map_or(self.max_delay(), |delay| delay.min(self.max_delay()))
+    }
+}
+
+impl From<ank_base::RestartBackoff> for RestartBackoff {
+    fn from(item: ank_base::RestartBackoff) -> Self {
+        RestartBackoff {
+            initial_delay: item.initial_delay,
+            max_delay: item.max_delay,
+            multiplier: item.multiplier,
+            max_retries: item.max_retries,
+        }
+    }
+}
+
+impl From<RestartBackoff> for ank_base::RestartBackoff {
+    fn from(item: RestartBackoff) -> Self {
+        ank_base::RestartBackoff {
+            initial_delay: item.initial_delay,
+            max_delay: item.max_delay,
+            multiplier: item.multiplier,
+            max_retries: item.max_retries,
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RestartBackoff;
+    use api::ank_base;
+
+    fn restart_backoff(initial_delay: u32, max_delay: u32, multiplier: u32) -> RestartBackoff {
+        RestartBackoff {
+            initial_delay: Some(initial_delay),
+            max_delay: Some(max_delay),
+            multiplier: Some(multiplier),
+            max_retries: None,
+        }
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_restart_backoff_from_and_to_proto() {
+        let proto_restart_backoff = ank_base::RestartBackoff {
+            initial_delay: Some(500),
+            max_delay: Some(10000),
+            multiplier: None,
+            max_retries: Some(5),
+        };
+        let restart_backoff = RestartBackoff {
+            initial_delay: Some(500),
+            max_delay: Some(10000),
+            multiplier: None,
+            max_retries: Some(5),
+        };
+
+        assert_eq!(
+            RestartBackoff::from(proto_restart_backoff.clone()),
+            restart_backoff
+        );
+        assert_eq!(
+            ank_base::RestartBackoff::from(restart_backoff),
+            proto_restart_backoff
+        );
+    }
+
+    // [utest->swdd~common-workload-restart-backoff-convention~1]
+    #[test]
+    fn utest_restart_backoff_verify_format() {
+        assert_eq!(RestartBackoff::default().verify_format(), Ok(()));
+        assert_eq!(restart_backoff(100, 100, 1).verify_format(), Ok(()));
+        assert!(restart_backoff(0, 100, 2).verify_format().is_err());
+        assert!(restart_backoff(100, 99, 2).verify_format().is_err());
+        assert!(restart_backoff(100, 1000, 0).verify_format().is_err());
+        assert!(RestartBackoff {
+            max_retries: Some(0),
+            ..Default::default()
+        }
+        .verify_format()
+        .is_err());
+        assert!(RestartBackoff {
+            initial_delay: Some(120000),
+            ..Default::default()
+        }
+        .verify_format()
+        .is_err());
+    }
+
+    // [utest->swdd~common-workload-restart-backoff-convention~1]
+    #[test]
+    fn utest_restart_backoff_defaults() {
+        let restart_backoff = RestartBackoff::default();
+
+        assert_eq!(restart_backoff.initial_delay(), Duration::from_secs(1));
+        assert_eq!(restart_backoff.max_delay(), Duration::from_secs(60));
+        assert_eq!(restart_backoff.multiplier(), 2);
+        assert_eq!(restart_backoff.max_retries(), 20);
+    }
+
+    // [utest->swdd~common-workload-restart-backoff-convention~1]
+    #[test]
+    fn utest_restart_backoff_delay_grows_up_to_max_delay() {
+        let restart_backoff = restart_backoff(100, 1000, 3);
+
+        assert_eq!(restart_backoff.delay(0), Duration::from_millis(100));
+        assert_eq!(restart_backoff.delay(1), Duration::from_millis(300));
+        assert_eq!(restart_backoff.delay(2), Duration::from_millis(900));
+        assert_eq!(restart_backoff.delay(3), Duration::from_millis(1000));
+        assert_eq!(restart_backoff.delay(u32::MAX), Duration::from_millis(1000));
+    }
+}
@@ -0,0 +1,215 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use api::ank_base;
+
+pub const STR_RE_CAPABILITY: &str = r"^[A-Z][A-Z0-9_]*$";
+
+#[derive(Debug, Clone, Serialize, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SecurityContext {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_as_user: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_as_group: Option<u32>,
+    #[serde(skip_serializing_if = "Capabilities::is_empty")]
+    pub capabilities: Capabilities,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub read_only_root_filesystem: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub no_new_privileges: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Capabilities {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub add: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub drop: Vec<String>,
+}
+
+impl SecurityContext {
+    // [impl->swdd~common-workload-security-context-convention~1]
+    pub fn verify_format(&self) -> Result<(), String> {
+        if self.run_as_group.is_some() && self.run_as_user.is_none() {
+            return Err(
+                "Unsupported security context. The field 'runAsGroup' requires 'runAsUser'"
+                    .to_string(),
+            );
+        }
+        self.capabilities.verify_format()
+    }
+}
+
+impl Capabilities {
+    pub fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.drop.is_empty()
+    }
+
+    fn verify_format(&self) -> Result<(), String> {
+        let re_capability = Regex::new(STR_RE_CAPABILITY).unwrap();
+        if let Some(capability) = self
+            .add
+            .iter()
+            .chain(&self.drop)
+            .find(|capability| !re_capability.is_match(capability))
+        {
+            return Err(format!(
+                "Unsupported capability. Received '{}', expected to match {}",
+                capability, STR_RE_CAPABILITY
+            ));
+        }
+        if let Some(capability) = self
+            .add
+            .iter()
+            .find(|capability| self.drop.contains(capability))
+        {
+            return Err(format!(
+                "Unsupported capabilities. The capability '{}' is added and dropped",
+                capability
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl From<ank_base::SecurityContext> for SecurityContext {
+    fn from(item: ank_base::SecurityContext) -> Self {
+        SecurityContext {
+            run_as_user: item.run_as_user,
+            run_as_group: item.run_as_group,
+            capabilities: item.capabilities.map(Into::into).unwrap_or_default(),
+            read_only_root_filesystem: item.read_only_root_filesystem.unwrap_or_default(),
+            no_new_privileges: item.no_new_privileges.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<SecurityContext> for ank_base::SecurityContext {
+    fn from(item: SecurityContext) -> Self {
+        ank_base::SecurityContext {
+            run_as_user: item.run_as_user,
+            run_as_group: item.run_as_group,
+            capabilities: Some(item.capabilities.into()),
+            read_only_root_filesystem: Some(item.read_only_root_filesystem),
+            no_new_privileges: Some(item.no_new_privileges),
+        }
+    }
+}
+
+impl From<ank_base::Capabilities> for Capabilities {
+    fn from(item: ank_base::Capabilities) -> Self {
+        Capabilities {
+            add: item.add,
+            drop: item.drop,
+        }
+    }
+}
+
+impl From<Capabilities> for ank_base::Capabilities {
+    fn from(item: Capabilities) -> Self {
+        ank_base::Capabilities {
+            add: item.add,
+            drop: item.drop,
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{Capabilities, SecurityContext};
+    use api::ank_base;
+
+    fn capabilities(add: &[&str], drop: &[&str]) -> Capabilities {
+        Capabilities {
+            add: add
+                .iter()
+                .map(|capability| capability.to_string())
+                .collect(),
+            drop: drop
+                .iter()
+                .map(|capability| capability.to_string())
+                .collect(),
+        }
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_security_context_from_and_to_proto() {
+        let security_context = SecurityContext {
+            run_as_user: Some(1000),
+            run_as_group: Some(1000),
+            capabilities: capabilities(&["NET_BIND_SERVICE"], &["ALL"]),
+            read_only_root_filesystem: true,
+            no_new_privileges: false,
+        };
+        let proto_security_context = ank_base::SecurityContext {
+            run_as_user: Some(1000),
+            run_as_group: Some(1000),
+            capabilities: Some(ank_base::Capabilities {
+                add: vec!["NET_BIND_SERVICE".to_string()],
+                drop: vec!["ALL".to_string()],
+            }),
+            read_only_root_filesystem: Some(true),
+            no_new_privileges: Some(false),
+        };
+
+        assert_eq!(
+            SecurityContext::from(proto_security_context.clone()),
+            security_context
+        );
+        assert_eq!(
+            ank_base::SecurityContext::from(security_context),
+            proto_security_context
+        );
+        assert_eq!(
+            SecurityContext::from(ank_base::SecurityContext::default()),
+            SecurityContext::default()
+        );
+    }
+
+    // [utest->swdd~common-workload-security-context-convention~1]
+    #[test]
+    fn utest_verify_security_context_format() {
+        let mut security_context = SecurityContext {
+            run_as_user: Some(1000),
+            run_as_group: Some(100),
+            capabilities: capabilities(&["NET_ADMIN", "CAP_SYS_TIME"], &["ALL"]),
+            ..Default::default()
+        };
+        assert!(security_context.verify_format().is_ok());
+
+        security_context.capabilities = capabilities(&["net_admin"], &[]);
+        assert!(security_context.verify_format().is_err());
+
+        security_context.capabilities = capabilities(&["NET_ADMIN"], &["NET_ADMIN"]);
+        assert!(security_context.verify_format().is_err());
+
+        security_context.capabilities = Capabilities::default();
+        security_context.run_as_user = None;
+        assert!(security_context.verify_format().is_err());
+    }
+}
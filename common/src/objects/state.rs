@@ -17,12 +17,34 @@ use regex::Regex;
 use std::collections::HashMap;
 
 use crate::helpers::serialize_to_ordered_map;
-use crate::objects::ConfigItem;
-use crate::objects::{StoredWorkloadSpec, STR_RE_CONFIG_REFERENCES};
+use crate::objects::{config::SECRET_REF_KEY, ConfigItem};
+use crate::objects::{
+    desired_state_path_of_namespace, qualified_workload_name, split_qualified_workload_name,
+    AccessRightsRule, Namespace, StoredWorkloadSpec, WorkloadDefaults, NAMESPACE_SEPARATOR,
+    STR_RE_CONFIG_REFERENCES, STR_RE_NAMESPACE,
+};
+use crate::PATH_SEPARATOR;
 
 use api::ank_base;
 
-pub const CURRENT_API_VERSION: &str = "v0.1";
+pub const CURRENT_API_VERSION: &str = "v0.2";
+pub const API_VERSION_V0_1: &str = "v0.1";
+// ordered from the oldest to the current API version
+pub const SUPPORTED_API_VERSIONS: [&str; 2] = [API_VERSION_V0_1, CURRENT_API_VERSION];
+
+// All other fields of the state and the workloads have been added with API version v0.2.
+pub const API_VERSION_V0_1_STATE_FIELDS: [&str; 3] = ["apiVersion", "workloads", "configs"];
+pub const API_VERSION_V0_1_WORKLOAD_FIELDS: [&str; 8] = [
+    "agent",
+    "tags",
+    "dependencies",
+    "restartPolicy",
+    "runtime",
+    "runtimeConfig",
+    "controlInterfaceAccess",
+    "configs",
+];
+const API_VERSION_V0_2_ADD_CONDITIONS: [&str; 2] = ["ADD_COND_CONFIG_CHANGED", "ADD_COND_READY"];
 
 // [impl->swdd~common-object-representation~1]
 // [impl->swdd~common-object-serialization~1]
@@ -34,6 +56,21 @@ pub struct State {
     pub workloads: HashMap<String, StoredWorkloadSpec>,
     #[serde(default)]
     pub configs: HashMap<String, ConfigItem>,
+    #[serde(default, skip_serializing_if = "WorkloadDefaults::is_empty")]
+    pub defaults: WorkloadDefaults,
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub namespaces: HashMap<String, Namespace>,
+    // [impl->swdd~common-state-contains-images-of-agents~1]
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub images: HashMap<String, Vec<String>>,
 }
 
 impl Default for State {
@@ -42,6 +79,9 @@ impl Default for State {
             api_version: CURRENT_API_VERSION.into(),
             workloads: Default::default(),
             configs: Default::default(),
+            defaults: Default::default(),
+            namespaces: Default::default(),
+            images: Default::default(),
         }
     }
 }
@@ -64,6 +104,21 @@ impl From<State> for ank_base::State {
                     .map(|(key, config_item)| (key, config_item.into()))
                     .collect(),
             }),
+            defaults: (!item.defaults.is_empty()).then(|| Box::new(item.defaults.into())),
+            namespaces: (!item.namespaces.is_empty()).then(|| ank_base::NamespaceMap {
+                namespaces: item
+                    .namespaces
+                    .into_iter()
+                    .map(|(k, v)| (k, v.into()))
+                    .collect(),
+            }),
+            images: (!item.images.is_empty()).then(|| ank_base::ImagesMap {
+                agent_images: item
+                    .images
+                    .into_iter()
+                    .map(|(agent_name, images)| (agent_name, ank_base::AgentImages { images }))
+                    .collect(),
+            }),
         }
     }
 }
@@ -88,21 +143,156 @@ impl TryFrom<ank_base::State> for State {
                 .into_iter()
                 .map(|(k, v)| Ok((k, v.try_into()?)))
                 .collect::<Result<_, Self::Error>>()?,
+            defaults: item
+                .defaults
+                .map(|x| (*x).try_into())
+                .transpose()?
+                .unwrap_or_default(),
+            namespaces: item
+                .namespaces
+                .unwrap_or_default()
+                .namespaces
+                .into_iter()
+                .map(|(k, v)| Ok((k, v.try_into()?)))
+                .collect::<Result<_, Self::Error>>()?,
+            images: item
+                .images
+                .unwrap_or_default()
+                .agent_images
+                .into_iter()
+                .map(|(agent_name, agent_images)| (agent_name, agent_images.images))
+                .collect(),
         })
     }
 }
 
 impl State {
+    // [impl->swdd~common-workload-defaults~1]
+    pub fn apply_workload_defaults(&mut self) -> Result<(), String> {
+        for (workload_name, workload) in self.workloads.iter_mut() {
+            self.defaults.apply_to(workload_name, workload)?;
+        }
+        for (namespace_name, namespace) in self.namespaces.iter_mut() {
+            for (workload_name, workload) in namespace.workloads.iter_mut() {
+                self.defaults.apply_to(
+                    &qualified_workload_name(namespace_name, workload_name),
+                    workload,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    // Returns the workload with the given name. Workloads of namespaces are found by their qualified name.
+    // [impl->swdd~common-namespaced-workload-names~1]
+    pub fn get_workload(&self, workload_name: &str) -> Option<&StoredWorkloadSpec> {
+        self.workloads.get(workload_name).or_else(|| {
+            let (namespace_name, workload_name) = split_qualified_workload_name(workload_name)?;
+            self.namespaces
+                .get(namespace_name)?
+                .workloads
+                .get(workload_name)
+        })
+    }
+
+    // Returns a copy of the state in which the workloads of the namespaces are moved to the
+    // top level workloads under their qualified names. Dependencies between workloads of the
+    // same namespace are adapted accordingly.
+    // [impl->swdd~common-state-resolves-namespaces~2]
+    pub fn resolve_namespaces(&self) -> Result<State, String> {
+        if let Some(workload_name) = self
+            .workloads
+            .keys()
+            .find(|workload_name| workload_name.contains(NAMESPACE_SEPARATOR))
+        {
+            return Err(format!(
+                "workload name '{}' contains '{}' which is reserved for workloads of namespaces",
+                workload_name, NAMESPACE_SEPARATOR
+            ));
+        }
+
+        let re_namespace = Regex::new(STR_RE_NAMESPACE).unwrap();
+        let mut resolved_state = State {
+            namespaces: HashMap::new(),
+            ..self.clone()
+        };
+        for (namespace_name, namespace) in &self.namespaces {
+            if !re_namespace.is_match(namespace_name) {
+                return Err(format!(
+                    "Unsupported namespace name. Received '{}', expected to have characters in {}",
+                    namespace_name, STR_RE_NAMESPACE
+                ));
+            }
+
+            for (workload_name, workload) in &namespace.workloads {
+                if workload_name.contains(NAMESPACE_SEPARATOR) {
+                    return Err(format!(
+                        "workload name '{}' of namespace '{}' contains the reserved '{}'",
+                        workload_name, namespace_name, NAMESPACE_SEPARATOR
+                    ));
+                }
+                verify_namespace_access_rules(namespace_name, workload_name, workload)?;
+
+                let mut resolved_workload = workload.clone();
+                resolved_workload.dependencies = workload
+                    .dependencies
+                    .iter()
+                    .map(|(dependency, add_condition)| {
+                        if add_condition.refers_to_config() {
+                            (dependency.clone(), *add_condition)
+                        } else {
+                            (
+                                qualified_workload_name(namespace_name, dependency),
+                                *add_condition,
+                            )
+                        }
+                    })
+                    .collect();
+                resolved_workload.restart_on = workload
+                    .restart_on
+                    .iter()
+                    .map(|restart_on| qualified_workload_name(namespace_name, restart_on))
+                    .collect();
+
+                // the reserved separator ensures that the qualified names do not collide
+                resolved_state.workloads.insert(
+                    qualified_workload_name(namespace_name, workload_name),
+                    resolved_workload,
+                );
+            }
+        }
+        Ok(resolved_state)
+    }
+
     pub fn verify_api_version(provided_state: &State) -> Result<(), String> {
-        if provided_state.api_version != CURRENT_API_VERSION {
-            Err(format!(
-                "Unsupported API version. Received '{}', expected '{}'",
-                provided_state.api_version,
-                State::default().api_version
-            ))
-        } else {
-            Ok(())
+        verify_supported_api_version(&provided_state.api_version)
+    }
+
+    // [impl->swdd~common-state-migrates-older-api-versions~2]
+    pub fn migrate_to_current_api_version(&mut self) -> Result<(), String> {
+        State::verify_api_version(self)?;
+        // API version v0.2 adds fields and reserves the namespace separator in workload names,
+        // i.e., a state of API version v0.1 is a valid state of v0.2 if it does not use the separator
+        if self.api_version == API_VERSION_V0_1 {
+            let mut reserved_names = self
+                .workloads
+                .keys()
+                .filter(|workload_name| workload_name.contains(NAMESPACE_SEPARATOR))
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+            if !reserved_names.is_empty() {
+                reserved_names.sort();
+                return Err(format!(
+                    "Cannot migrate the state from API version '{}' to '{}': the workload names '{}' contain '{}' which is reserved for workloads of namespaces. Please rename these workloads.",
+                    API_VERSION_V0_1,
+                    CURRENT_API_VERSION,
+                    reserved_names.join("', '"),
+                    NAMESPACE_SEPARATOR
+                ));
+            }
         }
+        self.api_version = CURRENT_API_VERSION.into();
+        Ok(())
     }
 
     // [impl->swdd~common-config-item-key-naming-convention~1]
@@ -117,7 +307,12 @@ impl State {
             }
         }
 
-        for workload in provided_state.workloads.values() {
+        for workload in provided_state.workloads.values().chain(
+            provided_state
+                .namespaces
+                .values()
+                .flat_map(|namespace| namespace.workloads.values()),
+        ) {
             // [impl->swdd~common-config-aliases-and-config-reference-keys-naming-convention~1]
             StoredWorkloadSpec::verify_config_reference_format(&workload.configs)?;
         }
@@ -125,6 +320,150 @@ impl State {
     }
 }
 
+// Workloads of a namespace must not be allowed to access anything outside of their namespace
+// [impl->swdd~common-state-resolves-namespaces~2]
+fn verify_namespace_access_rules(
+    namespace_name: &str,
+    workload_name: &str,
+    workload: &StoredWorkloadSpec,
+) -> Result<(), String> {
+    let namespace_path = desired_state_path_of_namespace(namespace_name);
+    // the placeholder "self" is checked as the workload inside of the namespace it refers to
+    let qualified_name = qualified_workload_name(namespace_name, workload_name);
+    let mut expanded_access = workload.control_interface_access.clone();
+    expanded_access.expand_self_placeholder(&qualified_name, &qualified_name);
+    for (rule, expanded_rule) in workload
+        .control_interface_access
+        .allow_rules
+        .iter()
+        .zip(&expanded_access.allow_rules)
+    {
+        let (AccessRightsRule::StateRule(state_rule), AccessRightsRule::StateRule(expanded_rule)) =
+            (rule, expanded_rule);
+        if let Some((filter_mask, _)) = state_rule
+            .filter_mask
+            .iter()
+            .zip(&expanded_rule.filter_mask)
+            .find(|(_, expanded_filter_mask)| {
+                !expanded_filter_mask
+                    .strip_prefix(&namespace_path)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(PATH_SEPARATOR))
+            })
+        {
+            return Err(format!(
+                "workload '{}' of namespace '{}' must not be allowed to access '{}' outside of its namespace",
+                workload_name, namespace_name, filter_mask
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn verify_supported_api_version(api_version: &str) -> Result<(), String> {
+    if SUPPORTED_API_VERSIONS.contains(&api_version) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unsupported API version. Received '{}', expected one of '{}'",
+            api_version,
+            SUPPORTED_API_VERSIONS.join("', '")
+        ))
+    }
+}
+
+// Converts a serialized state, e.g., a state dump, to an older API version so that it can be
+// exchanged with Ankaios versions not supporting the current API version.
+// Returns the paths of the omitted fields which are not supported by the target API version.
+// [impl->swdd~common-state-converts-to-older-api-versions~1]
+pub fn convert_state_to_api_version(
+    state: &mut serde_yaml::Value,
+    target_api_version: &str,
+) -> Result<Vec<String>, String> {
+    verify_supported_api_version(target_api_version)?;
+    let serde_yaml::Value::Mapping(state) = state else {
+        return Err("The state to convert is not a mapping".into());
+    };
+    if let Some(api_version) = state.get_mut("apiVersion") {
+        *api_version = target_api_version.into();
+    }
+    if target_api_version == CURRENT_API_VERSION {
+        return Ok(Vec::new());
+    }
+
+    if state.get("configs").is_some_and(contains_secret_ref) {
+        return Err(format!(
+            "Secret references in configs are not supported by API version '{}'",
+            target_api_version
+        ));
+    }
+
+    let mut omitted_fields = Vec::new();
+    state.retain(|field, _| {
+        let supported = field
+            .as_str()
+            .is_some_and(|field| API_VERSION_V0_1_STATE_FIELDS.contains(&field));
+        if !supported {
+            omitted_fields.push(field_path(&[], field));
+        }
+        supported
+    });
+
+    let Some(serde_yaml::Value::Mapping(workloads)) = state.get_mut("workloads") else {
+        return Ok(omitted_fields);
+    };
+    for (workload_name, workload) in workloads.iter_mut() {
+        let serde_yaml::Value::Mapping(workload) = workload else {
+            continue;
+        };
+        let workload_path = ["workloads", workload_name.as_str().unwrap_or_default()];
+        workload.retain(|field, _| {
+            let supported = field
+                .as_str()
+                .is_some_and(|field| API_VERSION_V0_1_WORKLOAD_FIELDS.contains(&field));
+            if !supported {
+                omitted_fields.push(field_path(&workload_path, field));
+            }
+            supported
+        });
+
+        if let Some(serde_yaml::Value::Mapping(dependencies)) = workload.get_mut("dependencies") {
+            dependencies.retain(|dependency, add_condition| {
+                let supported = !add_condition.as_str().is_some_and(|add_condition| {
+                    API_VERSION_V0_2_ADD_CONDITIONS.contains(&add_condition)
+                });
+                if !supported {
+                    omitted_fields.push(field_path(
+                        &[workload_path[0], workload_path[1], "dependencies"],
+                        dependency,
+                    ));
+                }
+                supported
+            });
+        }
+    }
+    Ok(omitted_fields)
+}
+
+fn field_path(parent_path: &[&str], field: &serde_yaml::Value) -> String {
+    let field = field.as_str().unwrap_or_default();
+    parent_path
+        .iter()
+        .copied()
+        .chain([field])
+        .collect::<Vec<&str>>()
+        .join(".")
+}
+
+fn contains_secret_ref(config: &serde_yaml::Value) -> bool {
+    match config {
+        serde_yaml::Value::Mapping(object) => {
+            object.contains_key(SECRET_REF_KEY) || object.values().any(contains_secret_ref)
+        }
+        serde_yaml::Value::Sequence(array) => array.iter().any(contains_secret_ref),
+        _ => false,
+    }
+}
+
 //////////////////////////////////////////////////////////////////////////////
 //                 ########  #######    #########  #########                //
 //                    ##     ##        ##             ##                    //
@@ -142,7 +481,11 @@ mod tests {
     use std::collections::HashMap;
 
     use crate::{
-        objects::{generate_test_configs, generate_test_stored_workload_spec, ConfigItem, State},
+        objects::{
+            convert_state_to_api_version, generate_test_configs,
+            generate_test_stored_workload_spec, AccessRightsRule, AddCondition, ConfigItem,
+            ControlInterfaceAccess, ReadWriteEnum, RestartPolicy, State, StateRule,
+        },
         test_utils::{generate_test_proto_state, generate_test_state},
     };
 
@@ -150,6 +493,39 @@ mod tests {
     const AGENT_A: &str = "agent_A";
     const RUNTIME: &str = "runtime";
     const INVALID_CONFIG_KEY: &str = "invalid%key";
+    const AGENT_B: &str = "agent_B";
+    const NAMESPACED_STATE: &str = r#"
+        apiVersion: v0.2
+        configs:
+          nginx_config: "port: 8080"
+        workloads:
+          nginx:
+            agent: agent_A
+            runtime: podman
+            runtimeConfig: "image: nginx"
+        namespaces:
+          team-a:
+            workloads:
+              nginx:
+                agent: agent_B
+                runtime: podman
+                runtimeConfig: "image: nginx"
+                dependencies:
+                  backend: ADD_COND_RUNNING
+                  nginx_config: ADD_COND_CONFIG_CHANGED
+                restartOn:
+                  - backend
+              backend:
+                agent: agent_B
+                runtime: podman
+                runtimeConfig: "image: backend"
+                controlInterfaceAccess:
+                  allowRules:
+                    - type: StateRule
+                      operation: ReadWrite
+                      filterMask:
+                        - desiredState.namespaces.team-a.workloads.backend
+        "#;
 
     #[test]
     fn utest_converts_to_proto_state() {
@@ -167,6 +543,23 @@ mod tests {
         assert_eq!(State::try_from(proto_state), Ok(ankaios_state));
     }
 
+    // [utest->swdd~common-state-contains-images-of-agents~1]
+    #[test]
+    fn utest_state_converts_images_from_and_to_proto() {
+        let ankaios_state = State {
+            images: HashMap::from([(AGENT_A.to_string(), vec!["nginx:1.27".to_string()])]),
+            ..Default::default()
+        };
+
+        let proto_state = ank_base::State::from(ankaios_state.clone());
+        assert_eq!(
+            proto_state.images.as_ref().unwrap().agent_images[AGENT_A].images,
+            vec!["nginx:1.27".to_string()]
+        );
+        assert_eq!(State::try_from(proto_state), Ok(ankaios_state));
+        assert_eq!(ank_base::State::from(State::default()).images, None);
+    }
+
     #[test]
     fn utest_serialize_state_into_ordered_output() {
         // input: random sorted state
@@ -199,13 +592,140 @@ mod tests {
         assert_eq!(
             State::verify_api_version(&state_incompatible_version),
             Err(format!(
-                "Unsupported API version. Received '{}', expected '{}'",
+                "Unsupported API version. Received '{}', expected one of 'v0.1', '{}'",
                 api_version,
                 super::CURRENT_API_VERSION
             ))
         );
     }
 
+    // [utest->swdd~common-state-migrates-older-api-versions~2]
+    #[test]
+    fn utest_state_migrates_older_api_version() {
+        let mut state = State {
+            api_version: super::API_VERSION_V0_1.into(),
+            workloads: HashMap::from([(
+                WORKLOAD_NAME_1.to_string(),
+                generate_test_stored_workload_spec(AGENT_A, RUNTIME),
+            )]),
+            ..Default::default()
+        };
+        let mut expected_state = state.clone();
+        expected_state.api_version = super::CURRENT_API_VERSION.into();
+
+        assert_eq!(state.migrate_to_current_api_version(), Ok(()));
+        assert_eq!(state, expected_state);
+
+        let mut unsupported_state = State {
+            api_version: "v0.0".into(),
+            ..Default::default()
+        };
+        assert!(unsupported_state.migrate_to_current_api_version().is_err());
+    }
+
+    // [utest->swdd~common-state-migrates-older-api-versions~2]
+    #[test]
+    fn utest_state_migration_fails_on_reserved_workload_name() {
+        let mut state = State {
+            api_version: super::API_VERSION_V0_1.into(),
+            workloads: HashMap::from([
+                (
+                    "a__b".to_string(),
+                    generate_test_stored_workload_spec(AGENT_A, RUNTIME),
+                ),
+                (
+                    WORKLOAD_NAME_1.to_string(),
+                    generate_test_stored_workload_spec(AGENT_A, RUNTIME),
+                ),
+            ]),
+            ..Default::default()
+        };
+
+        let error = state.migrate_to_current_api_version().unwrap_err();
+
+        assert!(error.contains("'a__b'"));
+        assert!(error.contains(super::API_VERSION_V0_1));
+        assert!(!error.contains(WORKLOAD_NAME_1));
+        assert_eq!(state.api_version, super::API_VERSION_V0_1);
+    }
+
+    // [utest->swdd~common-state-converts-to-older-api-versions~1]
+    #[test]
+    fn utest_convert_state_to_older_api_version() {
+        let mut state: serde_yaml::Value = serde_yaml::from_str(
+            r#"
+            apiVersion: v0.2
+            defaults:
+              runtime: podman
+            workloads:
+              nginx:
+                agent: agent_A
+                runtime: podman
+                runtimeConfig: "image: nginx"
+                replicas: 2
+                dependencies:
+                  database: ADD_COND_RUNNING
+                  nginx_config: ADD_COND_CONFIG_CHANGED
+                  cache: ADD_COND_READY
+            configs:
+              nginx_config: value
+            "#,
+        )
+        .unwrap();
+        let expected_state: serde_yaml::Value = serde_yaml::from_str(
+            r#"
+            apiVersion: v0.1
+            workloads:
+              nginx:
+                agent: agent_A
+                runtime: podman
+                runtimeConfig: "image: nginx"
+                dependencies:
+                  database: ADD_COND_RUNNING
+            configs:
+              nginx_config: value
+            "#,
+        )
+        .unwrap();
+
+        let omitted_fields = convert_state_to_api_version(&mut state, super::API_VERSION_V0_1);
+
+        assert_eq!(
+            omitted_fields,
+            Ok(vec![
+                "defaults".to_string(),
+                "workloads.nginx.replicas".to_string(),
+                "workloads.nginx.dependencies.nginx_config".to_string(),
+                "workloads.nginx.dependencies.cache".to_string(),
+            ])
+        );
+        assert_eq!(state, expected_state);
+    }
+
+    // [utest->swdd~common-state-converts-to-older-api-versions~1]
+    #[test]
+    fn utest_convert_state_to_api_version_fails() {
+        let mut state: serde_yaml::Value = serde_yaml::from_str(
+            r#"
+            apiVersion: v0.2
+            configs:
+              database:
+                password:
+                  secretRef:
+                    provider: env
+                    name: DB_PASSWORD
+            "#,
+        )
+        .unwrap();
+
+        assert!(convert_state_to_api_version(&mut state, super::API_VERSION_V0_1).is_err());
+        assert!(convert_state_to_api_version(&mut state, "v0.0").is_err());
+        assert_eq!(
+            convert_state_to_api_version(&mut state, super::CURRENT_API_VERSION),
+            Ok(vec![])
+        );
+    }
+
     #[test]
     fn utest_state_rejects_state_without_api_version() {
         let state_proto_no_version = ank_base::State {
@@ -216,6 +736,9 @@ mod tests {
             configs: Some(ank_base::ConfigMap {
                 configs: HashMap::new(),
             }),
+            defaults: None,
+            namespaces: None,
+            images: None,
         };
         let state_ankaios_no_version = State::try_from(state_proto_no_version).unwrap();
 
@@ -228,6 +751,200 @@ mod tests {
         assert_eq!(deserialization_result, "missing field `apiVersion`");
     }
 
+    // [utest->swdd~common-workload-defaults~1]
+    #[test]
+    fn utest_state_applies_workload_defaults() {
+        let mut state: State = serde_yaml::from_str(
+            r#"
+            apiVersion: v0.1
+            defaults:
+              restartPolicy: ALWAYS
+              runtime: podman
+            workloads:
+              nginx:
+                agent: agent_A
+                runtimeConfig: "image: nginx"
+              sleepy:
+                agent: agent_A
+                restartPolicy: NEVER
+                runtime: podman-kube
+                runtimeConfig: "manifest: ''"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(state.apply_workload_defaults(), Ok(()));
+
+        let nginx = state.workloads.get("nginx").unwrap();
+        assert_eq!(nginx.restart_policy, Some(RestartPolicy::Always));
+        assert_eq!(nginx.runtime, "podman");
+
+        let sleepy = state.workloads.get("sleepy").unwrap();
+        assert_eq!(sleepy.restart_policy, Some(RestartPolicy::Never));
+        assert_eq!(sleepy.runtime, "podman-kube");
+    }
+
+    // [utest->swdd~common-workload-defaults~1]
+    #[test]
+    fn utest_state_applies_workload_defaults_to_workloads_of_namespaces() {
+        let mut state: State = serde_yaml::from_str(
+            r#"
+            apiVersion: v0.2
+            defaults:
+              runtime: podman
+            namespaces:
+              team-a:
+                workloads:
+                  nginx:
+                    agent: agent_A
+                    runtimeConfig: "image: nginx"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(state.apply_workload_defaults(), Ok(()));
+
+        let nginx = state.get_workload("team-a__nginx").unwrap();
+        assert_eq!(nginx.runtime, "podman");
+    }
+
+    // [utest->swdd~common-namespaced-workload-names~1]
+    #[test]
+    fn utest_state_get_workload_of_namespace() {
+        let state: State = serde_yaml::from_str(NAMESPACED_STATE).unwrap();
+
+        assert_eq!(
+            state.get_workload("nginx").map(|workload| &workload.agent),
+            Some(&AGENT_A.to_owned())
+        );
+        assert_eq!(
+            state
+                .get_workload("team-a__nginx")
+                .map(|workload| &workload.agent),
+            Some(&AGENT_B.to_owned())
+        );
+        assert!(state.get_workload("team-b__nginx").is_none());
+        assert!(state.get_workload("team-a__other").is_none());
+    }
+
+    // [utest->swdd~common-state-resolves-namespaces~2]
+    #[test]
+    fn utest_state_resolve_namespaces() {
+        let state: State = serde_yaml::from_str(NAMESPACED_STATE).unwrap();
+
+        let resolved_state = state.resolve_namespaces().unwrap();
+
+        assert!(resolved_state.namespaces.is_empty());
+        let mut workload_names: Vec<&String> = resolved_state.workloads.keys().collect();
+        workload_names.sort();
+        assert_eq!(
+            workload_names,
+            vec!["nginx", "team-a__backend", "team-a__nginx"]
+        );
+
+        let dependencies = &resolved_state.workloads["team-a__nginx"].dependencies;
+        assert_eq!(
+            dependencies.get("team-a__backend"),
+            Some(&AddCondition::AddCondRunning)
+        );
+        assert_eq!(
+            dependencies.get("nginx_config"),
+            Some(&AddCondition::AddCondConfigChanged)
+        );
+        assert_eq!(
+            resolved_state.workloads["team-a__nginx"].restart_on,
+            vec!["team-a__backend".to_owned()]
+        );
+    }
+
+    // [utest->swdd~common-state-resolves-namespaces~2]
+    #[test]
+    fn utest_state_resolve_namespaces_fails_on_reserved_separator() {
+        let mut state: State = serde_yaml::from_str(NAMESPACED_STATE).unwrap();
+        let workload = state.workloads["nginx"].clone();
+        state.workloads.insert("team-a__nginx".into(), workload);
+
+        assert!(state.resolve_namespaces().is_err());
+
+        let mut state: State = serde_yaml::from_str(NAMESPACED_STATE).unwrap();
+        let namespace = state.namespaces.get_mut("team-a").unwrap();
+        let workload = namespace.workloads["nginx"].clone();
+        namespace.workloads.insert("web__nginx".into(), workload);
+
+        assert!(state.resolve_namespaces().is_err());
+    }
+
+    // [utest->swdd~common-state-resolves-namespaces~2]
+    #[test]
+    fn utest_state_resolve_namespaces_fails_on_invalid_namespace_name() {
+        let mut state: State = serde_yaml::from_str(NAMESPACED_STATE).unwrap();
+        let namespace = state.namespaces.remove("team-a").unwrap();
+        state.namespaces.insert("team_a".into(), namespace);
+
+        assert_eq!(
+            state.resolve_namespaces(),
+            Err(format!(
+                "Unsupported namespace name. Received 'team_a', expected to have characters in {}",
+                super::STR_RE_NAMESPACE
+            ))
+        );
+    }
+
+    // [utest->swdd~common-state-resolves-namespaces~2]
+    #[test]
+    fn utest_state_resolve_namespaces_fails_on_access_outside_of_namespace() {
+        let mut state: State = serde_yaml::from_str(NAMESPACED_STATE).unwrap();
+        let backend = state
+            .namespaces
+            .get_mut("team-a")
+            .unwrap()
+            .workloads
+            .get_mut("backend")
+            .unwrap();
+        backend.control_interface_access = ControlInterfaceAccess {
+            allow_rules: vec![AccessRightsRule::StateRule(StateRule {
+                operation: ReadWriteEnum::Read,
+                filter_mask: vec!["desiredState.namespaces.team-ab".into()],
+            })],
+            deny_rules: vec![],
+            pipes_owner: None,
+        };
+
+        assert_eq!(
+            state.resolve_namespaces(),
+            Err("workload 'backend' of namespace 'team-a' must not be allowed to access 'desiredState.namespaces.team-ab' outside of its namespace".into())
+        );
+    }
+
+    // [utest->swdd~common-state-resolves-namespaces~2]
+    #[test]
+    fn utest_state_resolve_namespaces_checks_self_placeholder_inside_of_namespace() {
+        let mut state: State = serde_yaml::from_str(NAMESPACED_STATE).unwrap();
+        let backend = state
+            .namespaces
+            .get_mut("team-a")
+            .unwrap()
+            .workloads
+            .get_mut("backend")
+            .unwrap();
+        backend.control_interface_access = ControlInterfaceAccess {
+            allow_rules: vec![AccessRightsRule::StateRule(StateRule {
+                operation: ReadWriteEnum::Read,
+                filter_mask: vec![
+                    "desiredState.workloads.self".into(),
+                    "workloadStates.*.self".into(),
+                ],
+            })],
+            deny_rules: vec![],
+            pipes_owner: None,
+        };
+
+        assert_eq!(
+            state.resolve_namespaces(),
+            Err("workload 'backend' of namespace 'team-a' must not be allowed to access 'workloadStates.*.self' outside of its namespace".into())
+        );
+    }
+
     // [utest->swdd~common-config-item-key-naming-convention~1]
     #[test]
     fn utest_verify_configs_format_compatible_config_item_keys_and_config_references() {
@@ -236,6 +953,9 @@ mod tests {
             api_version: super::CURRENT_API_VERSION.into(),
             workloads: HashMap::from([(WORKLOAD_NAME_1.to_string(), workload)]),
             configs: generate_test_configs(),
+            defaults: Default::default(),
+            namespaces: Default::default(),
+            images: Default::default(),
         };
 
         assert_eq!(State::verify_configs_format(&state), Ok(()));
@@ -21,8 +21,10 @@ use serde::{Deserialize, Serialize};
 use crate::helpers::serialize_to_ordered_map;
 
 use super::{
-    control_interface_access::ControlInterfaceAccess, AddCondition, RestartPolicy, Tag,
-    WorkloadInstanceName, WorkloadSpec,
+    control_interface_access::ControlInterfaceAccess, AddCondition, AgentDisconnectPolicy,
+    ExtraHost, HealthCheck, LifecycleHooks, Network, Port, ResourceLimits, ResourceThresholds,
+    RestartBackoff, RestartPolicy, SecurityContext, Tag, Volume, WorkloadInstanceName,
+    WorkloadSpec,
 };
 
 pub const STR_RE_CONFIG_REFERENCES: &str = r"^[a-zA-Z0-9_-]*$";
@@ -35,14 +37,61 @@ pub struct StoredWorkloadSpec {
     pub tags: Vec<Tag>,
     #[serde(default, serialize_with = "serialize_to_ordered_map")]
     pub dependencies: HashMap<String, AddCondition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart_policy: Option<RestartPolicy>,
     #[serde(default)]
-    pub restart_policy: RestartPolicy,
     pub runtime: String,
     pub runtime_config: String,
     #[serde(default)]
     pub control_interface_access: ControlInterfaceAccess,
     #[serde(default, serialize_with = "serialize_to_ordered_map")]
     pub configs: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_hosts: Vec<ExtraHost>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ports: Vec<Port>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_thresholds: Option<ResourceThresholds>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resources: Option<ResourceLimits>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheck>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart_backoff: Option<RestartBackoff>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub volumes: Vec<Volume>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry_auth: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replicas: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub networks: Vec<Network>,
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub env: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_agent_disconnect: Option<AgentDisconnectPolicy>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub restart_on: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifecycle_hooks: Option<LifecycleHooks>,
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_to_ordered_map"
+    )]
+    pub node_selector: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_debounce: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security_context: Option<SecurityContext>,
 }
 
 impl StoredWorkloadSpec {
@@ -68,6 +117,17 @@ impl StoredWorkloadSpec {
         }
         Ok(())
     }
+
+    // [impl->swdd~common-workload-replica-names~1]
+    pub fn replica_names(&self, workload_name: &str) -> Vec<String> {
+        let replicas = self.replicas.unwrap_or(1);
+        (0..replicas)
+            .map(|index| match index {
+                0 => workload_name.to_owned(),
+                _ => format!("{}-{}", workload_name, index),
+            })
+            .collect()
+    }
 }
 
 impl TryFrom<ank_base::Workload> for StoredWorkloadSpec {
@@ -90,14 +150,57 @@ impl TryFrom<ank_base::Workload> for StoredWorkloadSpec {
                 .into_iter()
                 .map(|(k, v)| Ok((k, v.try_into()?)))
                 .collect::<Result<HashMap<String, AddCondition>, String>>()?,
-            restart_policy: value.restart_policy.unwrap_or_default().try_into()?,
-            runtime: value.runtime.ok_or("Missing field runtime")?,
+            restart_policy: value.restart_policy.map(|x| x.try_into()).transpose()?,
+            runtime: value.runtime.unwrap_or_default(),
             runtime_config: value.runtime_config.ok_or("Missing field runtimeConfig")?,
             control_interface_access: value
                 .control_interface_access
                 .unwrap_or_default()
                 .try_into()?,
             configs: value.configs.unwrap_or_default().configs,
+            hostname: value.hostname,
+            extra_hosts: value
+                .extra_hosts
+                .unwrap_or_default()
+                .extra_hosts
+                .into_iter()
+                .map(|x| x.into())
+                .collect(),
+            ports: value
+                .ports
+                .unwrap_or_default()
+                .ports
+                .into_iter()
+                .map(|x| x.try_into())
+                .collect::<Result<Vec<Port>, String>>()?,
+            resource_thresholds: value.resource_thresholds.map(|x| x.into()),
+            resources: value.resources.map(|x| x.into()),
+            health_check: value.health_check.map(|x| x.try_into()).transpose()?,
+            restart_backoff: value.restart_backoff.map(|x| x.into()),
+            priority: value.priority,
+            volumes: value
+                .volumes
+                .unwrap_or_default()
+                .volumes
+                .into_iter()
+                .map(|x| x.try_into())
+                .collect::<Result<Vec<Volume>, String>>()?,
+            registry_auth: value.registry_auth,
+            replicas: value.replicas,
+            networks: value
+                .networks
+                .unwrap_or_default()
+                .networks
+                .into_iter()
+                .map(|x| x.into())
+                .collect(),
+            env: value.env.unwrap_or_default().env,
+            on_agent_disconnect: value.on_agent_disconnect.map(|x| x.parse()).transpose()?,
+            restart_on: value.restart_on.unwrap_or_default().workloads,
+            lifecycle_hooks: value.lifecycle_hooks.map(|x| x.into()),
+            node_selector: value.node_selector.unwrap_or_default().labels,
+            state_debounce: value.state_debounce,
+            security_context: value.security_context.map(|x| x.into()),
         })
     }
 }
@@ -113,7 +216,7 @@ impl From<StoredWorkloadSpec> for ank_base::Workload {
                     .map(|(k, v)| (k, v as i32))
                     .collect(),
             }),
-            restart_policy: (workload.restart_policy as i32).into(),
+            restart_policy: workload.restart_policy.map(|x| x as i32),
             runtime: workload.runtime.into(),
             runtime_config: workload.runtime_config.into(),
             tags: Some(ank_base::Tags {
@@ -123,6 +226,37 @@ impl From<StoredWorkloadSpec> for ank_base::Workload {
             configs: Some(ank_base::ConfigMappings {
                 configs: workload.configs,
             }),
+            hostname: workload.hostname,
+            extra_hosts: Some(ank_base::ExtraHosts {
+                extra_hosts: workload.extra_hosts.into_iter().map(|x| x.into()).collect(),
+            }),
+            ports: Some(ank_base::Ports {
+                ports: workload.ports.into_iter().map(|x| x.into()).collect(),
+            }),
+            resource_thresholds: workload.resource_thresholds.map(|x| x.into()),
+            resources: workload.resources.map(|x| x.into()),
+            health_check: workload.health_check.map(|x| x.into()),
+            restart_backoff: workload.restart_backoff.map(|x| x.into()),
+            priority: workload.priority,
+            volumes: Some(ank_base::Volumes {
+                volumes: workload.volumes.into_iter().map(|x| x.into()).collect(),
+            }),
+            registry_auth: workload.registry_auth,
+            replicas: workload.replicas,
+            networks: Some(ank_base::Networks {
+                networks: workload.networks.into_iter().map(|x| x.into()).collect(),
+            }),
+            env: Some(ank_base::EnvironmentVariables { env: workload.env }),
+            on_agent_disconnect: workload.on_agent_disconnect.map(|x| x.to_string()),
+            restart_on: Some(ank_base::RestartOn {
+                workloads: workload.restart_on,
+            }),
+            lifecycle_hooks: workload.lifecycle_hooks.map(|x| x.into()),
+            node_selector: Some(ank_base::NodeSelector {
+                labels: workload.node_selector,
+            }),
+            state_debounce: workload.state_debounce,
+            security_context: workload.security_context.map(|x| x.into()),
         }
     }
 }
@@ -137,10 +271,28 @@ impl From<(String, StoredWorkloadSpec)> for WorkloadSpec {
                 .build(),
             tags: spec.tags,
             dependencies: spec.dependencies,
-            restart_policy: spec.restart_policy,
+            restart_policy: spec.restart_policy.unwrap_or_default(),
             runtime: spec.runtime,
             runtime_config: spec.runtime_config,
             control_interface_access: spec.control_interface_access,
+            hostname: spec.hostname,
+            extra_hosts: spec.extra_hosts,
+            ports: spec.ports,
+            resource_thresholds: spec.resource_thresholds,
+            resources: spec.resources,
+            health_check: spec.health_check,
+            restart_backoff: spec.restart_backoff,
+            priority: spec.priority,
+            volumes: spec.volumes,
+            registry_auth: spec.registry_auth,
+            networks: spec.networks,
+            env: spec.env,
+            on_agent_disconnect: spec.on_agent_disconnect,
+            restart_on: spec.restart_on,
+            lifecycle_hooks: spec.lifecycle_hooks,
+            node_selector: spec.node_selector,
+            state_debounce: spec.state_debounce,
+            security_context: spec.security_context,
         }
     }
 }
@@ -150,12 +302,31 @@ impl From<WorkloadSpec> for StoredWorkloadSpec {
         StoredWorkloadSpec {
             runtime: value.runtime,
             agent: value.instance_name.agent_name().to_owned(),
-            restart_policy: value.restart_policy,
+            restart_policy: Some(value.restart_policy),
             dependencies: value.dependencies,
             tags: value.tags,
             runtime_config: value.runtime_config,
             control_interface_access: value.control_interface_access,
             configs: Default::default(),
+            hostname: value.hostname,
+            extra_hosts: value.extra_hosts,
+            ports: value.ports,
+            resource_thresholds: value.resource_thresholds,
+            resources: value.resources,
+            health_check: value.health_check,
+            restart_backoff: value.restart_backoff,
+            priority: value.priority,
+            volumes: value.volumes,
+            registry_auth: value.registry_auth,
+            replicas: None,
+            networks: value.networks,
+            env: value.env,
+            on_agent_disconnect: value.on_agent_disconnect,
+            restart_on: value.restart_on,
+            lifecycle_hooks: value.lifecycle_hooks,
+            node_selector: value.node_selector,
+            state_debounce: value.state_debounce,
+            security_context: value.security_context,
         }
     }
 }
@@ -180,7 +351,7 @@ pub fn generate_test_stored_workload_spec_with_config(
             (String::from("workload_A"), AddCondition::AddCondRunning),
             (String::from("workload_C"), AddCondition::AddCondSucceeded),
         ]),
-        restart_policy: RestartPolicy::Always,
+        restart_policy: Some(RestartPolicy::Always),
         runtime: runtime_name.into(),
         tags: vec![Tag {
             key: "key".into(),
@@ -193,6 +364,25 @@ pub fn generate_test_stored_workload_spec_with_config(
             ("ref2".into(), "config_2".into()),
         ]
         .into(),
+        hostname: None,
+        extra_hosts: vec![],
+        ports: vec![],
+        resource_thresholds: None,
+        resources: None,
+        health_check: None,
+        restart_backoff: None,
+        priority: None,
+        volumes: vec![],
+        registry_auth: None,
+        replicas: None,
+        networks: vec![],
+        env: HashMap::new(),
+        on_agent_disconnect: None,
+        restart_on: vec![],
+        lifecycle_hooks: None,
+        node_selector: HashMap::new(),
+        state_debounce: None,
+        security_context: None,
     }
 }
 
@@ -234,4 +424,19 @@ mod tests {
             ))
         );
     }
+    // [utest->swdd~common-workload-replica-names~1]
+    #[test]
+    fn utest_replica_names() {
+        let mut workload = StoredWorkloadSpec::default();
+        assert_eq!(workload.replica_names("nginx"), vec!["nginx"]);
+
+        workload.replicas = Some(3);
+        assert_eq!(
+            workload.replica_names("nginx"),
+            vec!["nginx", "nginx-1", "nginx-2"]
+        );
+
+        workload.replicas = Some(0);
+        assert!(workload.replica_names("nginx").is_empty());
+    }
 }
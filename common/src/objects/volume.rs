@@ -0,0 +1,243 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use api::ank_base;
+
+pub const STR_RE_VOLUME_NAME: &str = r"^[a-zA-Z0-9][a-zA-Z0-9_.-]*$";
+
+#[derive(Debug, Clone, Copy, Serialize, Default, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum VolumeType {
+    #[default]
+    HostPath = 0,
+    Named = 1,
+    Tmpfs = 2,
+}
+
+impl std::fmt::Display for VolumeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VolumeType::HostPath => write!(f, "hostPath"),
+            VolumeType::Named => write!(f, "named"),
+            VolumeType::Tmpfs => write!(f, "tmpfs"),
+        }
+    }
+}
+
+impl TryFrom<i32> for VolumeType {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            x if x == VolumeType::HostPath as i32 => Ok(VolumeType::HostPath),
+            x if x == VolumeType::Named as i32 => Ok(VolumeType::Named),
+            x if x == VolumeType::Tmpfs as i32 => Ok(VolumeType::Tmpfs),
+            _ => Err(format!(
+                "Received an unknown value '{value}' as volume type."
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Volume {
+    #[serde(rename = "type")]
+    pub volume_type: VolumeType,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub source: String,
+    pub mount_path: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub read_only: bool,
+}
+
+impl Volume {
+    // [impl->swdd~common-workload-volume-convention~1]
+    pub fn verify_format(&self) -> Result<(), String> {
+        if !Path::new(&self.mount_path).is_absolute() {
+            return Err(format!(
+                "Unsupported volume '{}'. The mount path must be an absolute path",
+                self
+            ));
+        }
+
+        let re_volume_name = Regex::new(STR_RE_VOLUME_NAME).unwrap();
+        match self.volume_type {
+            VolumeType::HostPath if !Path::new(&self.source).is_absolute() => Err(format!(
+                "Unsupported volume '{}'. The source of a host path must be an absolute path",
+                self
+            )),
+            VolumeType::Named if !re_volume_name.is_match(&self.source) => Err(format!(
+                "Unsupported volume '{}'. The name of a named volume must match {}",
+                self, STR_RE_VOLUME_NAME
+            )),
+            VolumeType::Tmpfs if !self.source.is_empty() => Err(format!(
+                "Unsupported volume '{}'. A tmpfs volume has no source",
+                self
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+// [impl->swdd~common-workload-volume-mount-path-conflicts~1]
+pub fn find_mount_path_conflict(volumes: &[Volume]) -> Option<&str> {
+    volumes.iter().enumerate().find_map(|(index, volume)| {
+        volumes[..index]
+            .iter()
+            .any(|other| Path::new(&other.mount_path) == Path::new(&volume.mount_path))
+            .then_some(volume.mount_path.as_str())
+    })
+}
+
+impl std::fmt::Display for Volume {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}",
+            self.volume_type, self.source, self.mount_path
+        )
+    }
+}
+
+impl TryFrom<ank_base::Volume> for Volume {
+    type Error = String;
+
+    fn try_from(item: ank_base::Volume) -> Result<Self, String> {
+        Ok(Volume {
+            volume_type: item.r#type.try_into()?,
+            source: item.source,
+            mount_path: item.mount_path,
+            read_only: item.read_only,
+        })
+    }
+}
+
+impl From<Volume> for ank_base::Volume {
+    fn from(item: Volume) -> Self {
+        ank_base::Volume {
+            r#type: item.volume_type as i32,
+            source: item.source,
+            mount_path: item.mount_path,
+            read_only: item.read_only,
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{find_mount_path_conflict, Volume, VolumeType};
+    use api::ank_base;
+
+    fn volume(volume_type: VolumeType, source: &str, mount_path: &str) -> Volume {
+        Volume {
+            volume_type,
+            source: source.into(),
+            mount_path: mount_path.into(),
+            read_only: false,
+        }
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_volume_from_and_to_proto() {
+        let proto_volume = ank_base::Volume {
+            r#type: ank_base::VolumeType::Named as i32,
+            source: "data".into(),
+            mount_path: "/var/lib/data".into(),
+            read_only: true,
+        };
+        let expected_volume = Volume {
+            read_only: true,
+            ..volume(VolumeType::Named, "data", "/var/lib/data")
+        };
+
+        assert_eq!(
+            Volume::try_from(proto_volume.clone()),
+            Ok(expected_volume.clone())
+        );
+        assert_eq!(ank_base::Volume::from(expected_volume), proto_volume);
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_volume_from_proto_fails_on_unknown_type() {
+        let proto_volume = ank_base::Volume {
+            r#type: 42,
+            source: "data".into(),
+            mount_path: "/var/lib/data".into(),
+            read_only: false,
+        };
+        assert!(Volume::try_from(proto_volume).is_err());
+    }
+
+    // [utest->swdd~common-workload-volume-convention~1]
+    #[test]
+    fn utest_verify_volume_format() {
+        assert!(volume(VolumeType::HostPath, "/opt/data", "/data")
+            .verify_format()
+            .is_ok());
+        assert!(volume(VolumeType::Named, "data-1.0", "/data")
+            .verify_format()
+            .is_ok());
+        assert!(volume(VolumeType::Tmpfs, "", "/tmp")
+            .verify_format()
+            .is_ok());
+
+        assert!(volume(VolumeType::HostPath, "/opt/data", "data")
+            .verify_format()
+            .is_err());
+        assert!(volume(VolumeType::HostPath, "opt/data", "/data")
+            .verify_format()
+            .is_err());
+        assert!(volume(VolumeType::Named, "", "/data")
+            .verify_format()
+            .is_err());
+        assert!(volume(VolumeType::Named, "/opt/data", "/data")
+            .verify_format()
+            .is_err());
+        assert!(volume(VolumeType::Tmpfs, "/opt/data", "/tmp")
+            .verify_format()
+            .is_err());
+    }
+
+    // [utest->swdd~common-workload-volume-mount-path-conflicts~1]
+    #[test]
+    fn utest_find_mount_path_conflict() {
+        let volumes = vec![
+            volume(VolumeType::HostPath, "/opt/data", "/data"),
+            volume(VolumeType::Tmpfs, "", "/tmp"),
+        ];
+        assert_eq!(find_mount_path_conflict(&volumes), None);
+
+        let volumes = vec![
+            volume(VolumeType::HostPath, "/opt/data", "/data"),
+            volume(VolumeType::Named, "data", "/data/"),
+        ];
+        assert_eq!(find_mount_path_conflict(&volumes), Some("/data/"));
+    }
+}
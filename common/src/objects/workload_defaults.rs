@@ -0,0 +1,196 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use api::ank_base;
+
+use super::{RestartPolicy, StoredWorkloadSpec, Tag};
+
+// [impl->swdd~common-workload-defaults~1]
+#[derive(Debug, Clone, Serialize, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WorkloadDefaults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restart_policy: Option<RestartPolicy>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<String>,
+}
+
+impl WorkloadDefaults {
+    pub fn is_empty(&self) -> bool {
+        self == &WorkloadDefaults::default()
+    }
+
+    // [impl->swdd~common-workload-defaults~1]
+    pub fn apply_to(
+        &self,
+        workload_name: &str,
+        workload: &mut StoredWorkloadSpec,
+    ) -> Result<(), String> {
+        if workload.restart_policy.is_none() {
+            workload.restart_policy.clone_from(&self.restart_policy);
+        }
+        if workload.tags.is_empty() {
+            workload.tags.clone_from(&self.tags);
+        }
+        if workload.runtime.is_empty() {
+            workload.runtime = self.runtime.clone().ok_or_else(|| {
+                format!(
+                    "Workload '{}' has no runtime and the state defines no default runtime",
+                    workload_name
+                )
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<ank_base::WorkloadDefaults> for WorkloadDefaults {
+    type Error = String;
+
+    fn try_from(item: ank_base::WorkloadDefaults) -> Result<Self, Self::Error> {
+        Ok(WorkloadDefaults {
+            restart_policy: item.restart_policy.map(|x| x.try_into()).transpose()?,
+            tags: item
+                .tags
+                .unwrap_or_default()
+                .tags
+                .into_iter()
+                .map(|x| x.into())
+                .collect(),
+            runtime: item.runtime,
+        })
+    }
+}
+
+impl From<WorkloadDefaults> for ank_base::WorkloadDefaults {
+    fn from(item: WorkloadDefaults) -> Self {
+        ank_base::WorkloadDefaults {
+            restart_policy: item.restart_policy.map(|x| x as i32),
+            tags: Some(ank_base::Tags {
+                tags: item.tags.into_iter().map(|x| x.into()).collect(),
+            }),
+            runtime: item.runtime,
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::WorkloadDefaults;
+    use crate::objects::{generate_test_stored_workload_spec, RestartPolicy, Tag};
+    use api::ank_base;
+
+    const WORKLOAD_NAME: &str = "workload_1";
+    const AGENT_A: &str = "agent_A";
+    const DEFAULT_RUNTIME: &str = "podman";
+
+    fn generate_test_workload_defaults() -> WorkloadDefaults {
+        WorkloadDefaults {
+            restart_policy: Some(RestartPolicy::OnFailure),
+            tags: vec![Tag {
+                key: "fleet".into(),
+                value: "vehicles".into(),
+            }],
+            runtime: Some(DEFAULT_RUNTIME.into()),
+        }
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_workload_defaults_from_and_to_proto() {
+        let proto_defaults = ank_base::WorkloadDefaults {
+            restart_policy: Some(ank_base::RestartPolicy::OnFailure as i32),
+            tags: Some(ank_base::Tags {
+                tags: vec![ank_base::Tag {
+                    key: "fleet".into(),
+                    value: "vehicles".into(),
+                }],
+            }),
+            runtime: Some(DEFAULT_RUNTIME.into()),
+        };
+
+        assert_eq!(
+            WorkloadDefaults::try_from(proto_defaults.clone()),
+            Ok(generate_test_workload_defaults())
+        );
+        assert_eq!(
+            ank_base::WorkloadDefaults::from(generate_test_workload_defaults()),
+            proto_defaults
+        );
+    }
+
+    // [utest->swdd~common-conversions-between-ankaios-and-proto~1]
+    #[test]
+    fn utest_converts_workload_defaults_from_proto_fails_on_unknown_restart_policy() {
+        let proto_defaults = ank_base::WorkloadDefaults {
+            restart_policy: Some(42),
+            ..Default::default()
+        };
+
+        assert!(WorkloadDefaults::try_from(proto_defaults).is_err());
+    }
+
+    // [utest->swdd~common-workload-defaults~1]
+    #[test]
+    fn utest_workload_defaults_apply_to_unset_fields() {
+        let mut workload = generate_test_stored_workload_spec(AGENT_A, "");
+        workload.restart_policy = None;
+        workload.tags = vec![];
+
+        assert_eq!(
+            generate_test_workload_defaults().apply_to(WORKLOAD_NAME, &mut workload),
+            Ok(())
+        );
+
+        assert_eq!(workload.restart_policy, Some(RestartPolicy::OnFailure));
+        assert_eq!(workload.tags, generate_test_workload_defaults().tags);
+        assert_eq!(workload.runtime, DEFAULT_RUNTIME);
+    }
+
+    // [utest->swdd~common-workload-defaults~1]
+    #[test]
+    fn utest_workload_defaults_keep_overridden_fields() {
+        let mut workload = generate_test_stored_workload_spec(AGENT_A, "runtime_x");
+        let expected_workload = workload.clone();
+
+        assert_eq!(
+            generate_test_workload_defaults().apply_to(WORKLOAD_NAME, &mut workload),
+            Ok(())
+        );
+
+        assert_eq!(workload, expected_workload);
+    }
+
+    // [utest->swdd~common-workload-defaults~1]
+    #[test]
+    fn utest_workload_defaults_apply_to_fails_without_runtime() {
+        let mut workload = generate_test_stored_workload_spec(AGENT_A, "");
+
+        assert!(WorkloadDefaults::default()
+            .apply_to(WORKLOAD_NAME, &mut workload)
+            .is_err());
+    }
+}
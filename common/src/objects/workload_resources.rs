@@ -0,0 +1,303 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use api::ank_base;
+use serde::{Deserialize, Serialize};
+
+use super::WorkloadInstanceName;
+
+type AgentName = String;
+type WorkloadName = String;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceUsage {
+    // CPU usage in percent
+    pub cpu_usage: u32,
+    // memory usage in bytes
+    pub memory_usage: u64,
+}
+
+impl ResourceUsage {
+    pub fn new(cpu_usage: f32, memory_usage: u64) -> Self {
+        Self {
+            cpu_usage: cpu_usage.round() as u32,
+            memory_usage,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkloadResources {
+    pub instance_name: WorkloadInstanceName,
+    pub resource_usage: ResourceUsage,
+}
+
+// [impl->swdd~workload-resources-map-stores-resource-usage-per-agent~1]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct WorkloadResourcesMap(HashMap<AgentName, HashMap<WorkloadName, ResourceUsage>>);
+
+impl WorkloadResourcesMap {
+    pub fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    pub fn get_resource_usage_for_agent(
+        &self,
+        agent_name: &str,
+    ) -> Option<&HashMap<WorkloadName, ResourceUsage>> {
+        self.0.get(agent_name)
+    }
+
+    // The reports of an agent always contain all its running workloads,
+    // so the previous measurements of the agent are replaced completely.
+    pub fn update_for_agent(
+        &mut self,
+        agent_name: &str,
+        workload_resources: Vec<WorkloadResources>,
+    ) {
+        let resources_of_agent: HashMap<WorkloadName, ResourceUsage> = workload_resources
+            .into_iter()
+            .filter(|x| x.instance_name.agent_name() == agent_name)
+            .map(|x| (x.instance_name.workload_name().to_owned(), x.resource_usage))
+            .collect();
+
+        if resources_of_agent.is_empty() {
+            self.0.remove(agent_name);
+        } else {
+            self.0.insert(agent_name.to_owned(), resources_of_agent);
+        }
+    }
+
+    pub fn remove_agent(&mut self, agent_name: &str) {
+        self.0.remove(agent_name);
+    }
+}
+
+impl IntoIterator for WorkloadResourcesMap {
+    type Item = <HashMap<AgentName, HashMap<WorkloadName, ResourceUsage>> as IntoIterator>::Item;
+
+    type IntoIter =
+        <HashMap<AgentName, HashMap<WorkloadName, ResourceUsage>> as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl From<ResourceUsage> for ank_base::ResourceUsage {
+    fn from(item: ResourceUsage) -> Self {
+        ank_base::ResourceUsage {
+            cpu_usage: item.cpu_usage,
+            memory_usage: item.memory_usage,
+        }
+    }
+}
+
+impl From<ank_base::ResourceUsage> for ResourceUsage {
+    fn from(item: ank_base::ResourceUsage) -> Self {
+        ResourceUsage {
+            cpu_usage: item.cpu_usage,
+            memory_usage: item.memory_usage,
+        }
+    }
+}
+
+impl From<WorkloadResourcesMap> for Option<ank_base::WorkloadResourcesMap> {
+    fn from(item: WorkloadResourcesMap) -> Option<ank_base::WorkloadResourcesMap> {
+        if item.0.is_empty() {
+            return None;
+        }
+        Some(ank_base::WorkloadResourcesMap {
+            agent_resources_map: item
+                .0
+                .into_iter()
+                .map(|(agent_name, wl_map)| {
+                    (
+                        agent_name,
+                        ank_base::WorkloadResourcesOfAgent {
+                            wl_name_resources_map: wl_map
+                                .into_iter()
+                                .map(|(wl_name, usage)| (wl_name, usage.into()))
+                                .collect(),
+                        },
+                    )
+                })
+                .collect(),
+        })
+    }
+}
+
+impl From<ank_base::WorkloadResourcesMap> for WorkloadResourcesMap {
+    fn from(item: ank_base::WorkloadResourcesMap) -> WorkloadResourcesMap {
+        WorkloadResourcesMap(
+            item.agent_resources_map
+                .into_iter()
+                .map(|(agent_name, wl_map)| {
+                    (
+                        agent_name,
+                        wl_map
+                            .wl_name_resources_map
+                            .into_iter()
+                            .map(|(wl_name, usage)| (wl_name, usage.into()))
+                            .collect(),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(any(feature = "test_utils", test))]
+pub fn generate_test_workload_resources(
+    agent_name: impl Into<String>,
+    workload_name: impl Into<String>,
+) -> WorkloadResources {
+    WorkloadResources {
+        instance_name: WorkloadInstanceName::new(agent_name, workload_name, "some_id"),
+        resource_usage: ResourceUsage {
+            cpu_usage: 42,
+            memory_usage: 4096,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api::ank_base;
+
+    use super::{generate_test_workload_resources, ResourceUsage, WorkloadResourcesMap};
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+    const WORKLOAD_1: &str = "workload_1";
+    const WORKLOAD_2: &str = "workload_2";
+
+    #[test]
+    fn utest_resource_usage_new_rounds_cpu_usage() {
+        assert_eq!(
+            ResourceUsage::new(12.6, 1024),
+            ResourceUsage {
+                cpu_usage: 13,
+                memory_usage: 1024
+            }
+        );
+    }
+
+    // [utest->swdd~workload-resources-map-stores-resource-usage-per-agent~1]
+    #[test]
+    fn utest_workload_resources_map_update_replaces_previous_report_of_agent() {
+        let mut map = WorkloadResourcesMap::new();
+        map.update_for_agent(
+            AGENT_A,
+            vec![
+                generate_test_workload_resources(AGENT_A, WORKLOAD_1),
+                generate_test_workload_resources(AGENT_A, WORKLOAD_2),
+            ],
+        );
+        map.update_for_agent(
+            AGENT_B,
+            vec![generate_test_workload_resources(AGENT_B, WORKLOAD_1)],
+        );
+
+        map.update_for_agent(
+            AGENT_A,
+            vec![generate_test_workload_resources(AGENT_A, WORKLOAD_2)],
+        );
+
+        let agent_a_resources = map.get_resource_usage_for_agent(AGENT_A).unwrap();
+        assert_eq!(agent_a_resources.len(), 1);
+        assert!(agent_a_resources.contains_key(WORKLOAD_2));
+        assert!(map
+            .get_resource_usage_for_agent(AGENT_B)
+            .unwrap()
+            .contains_key(WORKLOAD_1));
+    }
+
+    // [utest->swdd~workload-resources-map-stores-resource-usage-per-agent~1]
+    #[test]
+    fn utest_workload_resources_map_update_ignores_workloads_of_other_agents() {
+        let mut map = WorkloadResourcesMap::new();
+        map.update_for_agent(
+            AGENT_A,
+            vec![generate_test_workload_resources(AGENT_B, WORKLOAD_1)],
+        );
+
+        assert!(map.get_resource_usage_for_agent(AGENT_A).is_none());
+        assert!(map.get_resource_usage_for_agent(AGENT_B).is_none());
+    }
+
+    // [utest->swdd~workload-resources-map-stores-resource-usage-per-agent~1]
+    #[test]
+    fn utest_workload_resources_map_update_with_empty_report_removes_agent() {
+        let mut map = WorkloadResourcesMap::new();
+        map.update_for_agent(
+            AGENT_A,
+            vec![generate_test_workload_resources(AGENT_A, WORKLOAD_1)],
+        );
+
+        map.update_for_agent(AGENT_A, vec![]);
+
+        assert_eq!(map, WorkloadResourcesMap::new());
+    }
+
+    #[test]
+    fn utest_workload_resources_map_remove_agent() {
+        let mut map = WorkloadResourcesMap::new();
+        map.update_for_agent(
+            AGENT_A,
+            vec![generate_test_workload_resources(AGENT_A, WORKLOAD_1)],
+        );
+
+        map.remove_agent(AGENT_A);
+
+        assert_eq!(map, WorkloadResourcesMap::new());
+    }
+
+    #[test]
+    fn utest_workload_resources_map_proto_conversion_round_trip() {
+        let mut map = WorkloadResourcesMap::new();
+        map.update_for_agent(
+            AGENT_A,
+            vec![generate_test_workload_resources(AGENT_A, WORKLOAD_1)],
+        );
+
+        let proto: Option<ank_base::WorkloadResourcesMap> = map.clone().into();
+        let proto = proto.unwrap();
+        assert_eq!(
+            proto.agent_resources_map[AGENT_A].wl_name_resources_map[WORKLOAD_1],
+            ank_base::ResourceUsage {
+                cpu_usage: 42,
+                memory_usage: 4096
+            }
+        );
+        assert_eq!(WorkloadResourcesMap::from(proto), map);
+    }
+
+    #[test]
+    fn utest_empty_workload_resources_map_converts_to_none() {
+        let proto: Option<ank_base::WorkloadResourcesMap> = WorkloadResourcesMap::new().into();
+        assert!(proto.is_none());
+    }
+}
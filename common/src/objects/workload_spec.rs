@@ -17,9 +17,15 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::helpers::serialize_to_ordered_map;
-use crate::objects::Tag;
+use crate::objects::{
+    AgentDisconnectPolicy, ExtraHost, HealthCheck, LifecycleHooks, Network, Port, ResourceLimits,
+    ResourceThresholds, RestartBackoff, SecurityContext, Tag, Volume,
+};
 
 use super::control_interface_access::ControlInterfaceAccess;
+use super::extra_host::verify_hostname_format;
+use super::network::verify_network_combination;
+use super::volume::find_mount_path_conflict;
 use super::ExecutionState;
 use super::WorkloadInstanceName;
 
@@ -29,6 +35,7 @@ pub type DeletedWorkloadCollection = Vec<DeletedWorkload>;
 const MAX_CHARACTERS_WORKLOAD_NAME: usize = 63;
 pub const STR_RE_WORKLOAD: &str = r"^[a-zA-Z0-9_-]+*$";
 pub const STR_RE_AGENT: &str = r"^[a-zA-Z0-9_-]*$";
+pub const STR_RE_ENV_VAR_NAME: &str = r"^[a-zA-Z_][a-zA-Z0-9_]*$";
 
 // [impl->swdd~common-object-serialization~1]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -36,6 +43,8 @@ pub struct DeletedWorkload {
     pub instance_name: WorkloadInstanceName,
     #[serde(serialize_with = "serialize_to_ordered_map")]
     pub dependencies: HashMap<String, DeleteCondition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grace_period_secs: Option<u32>,
 }
 
 // [impl->swdd~common-object-serialization~1]
@@ -50,6 +59,26 @@ pub struct WorkloadSpec {
     pub runtime: String,
     pub runtime_config: String,
     pub control_interface_access: ControlInterfaceAccess,
+    pub hostname: Option<String>,
+    pub extra_hosts: Vec<ExtraHost>,
+    pub ports: Vec<Port>,
+    pub resource_thresholds: Option<ResourceThresholds>,
+    pub resources: Option<ResourceLimits>,
+    pub health_check: Option<HealthCheck>,
+    pub restart_backoff: Option<RestartBackoff>,
+    pub priority: Option<u32>,
+    pub volumes: Vec<Volume>,
+    pub registry_auth: Option<String>,
+    pub networks: Vec<Network>,
+    #[serde(serialize_with = "serialize_to_ordered_map")]
+    pub env: HashMap<String, String>,
+    pub on_agent_disconnect: Option<AgentDisconnectPolicy>,
+    pub restart_on: Vec<String>,
+    pub lifecycle_hooks: Option<LifecycleHooks>,
+    #[serde(serialize_with = "serialize_to_ordered_map")]
+    pub node_selector: HashMap<String, String>,
+    pub state_debounce: Option<u32>,
+    pub security_context: Option<SecurityContext>,
 }
 
 // [impl->swdd~common-workload-needs-control-interface~1]
@@ -61,13 +90,93 @@ impl WorkloadSpec {
     // [impl->swdd~common-workload-naming-convention~1]
     // [impl->swdd~common-agent-naming-convention~1]
     // [impl->swdd~common-access-rules-filter-mask-convention~1]
+    // [impl->swdd~common-workload-hostname-convention~1]
+    // [impl->swdd~common-workload-port-convention~1]
+    // [impl->swdd~common-workload-resource-thresholds-convention~1]
+    // [impl->swdd~common-workload-resource-limits-convention~1]
+    // [impl->swdd~common-workload-health-check-convention~1]
+    // [impl->swdd~common-workload-restart-backoff-convention~1]
+    // [impl->swdd~common-workload-volume-convention~1]
+    // [impl->swdd~common-workload-volume-mount-path-conflicts~1]
+    // [impl->swdd~common-workload-network-convention~1]
+    // [impl->swdd~common-workload-network-combinations~1]
+    // [impl->swdd~common-workload-env-var-naming-convention~1]
+    // [impl->swdd~common-workload-restart-on-convention~1]
     pub fn verify_fields_format(workload_spec: &WorkloadSpec) -> Result<(), String> {
         Self::verify_workload_name_format(workload_spec.instance_name.workload_name())?;
         Self::verify_agent_name_format(workload_spec.instance_name.agent_name())?;
         workload_spec.control_interface_access.verify_format()?;
+        if let Some(hostname) = &workload_spec.hostname {
+            verify_hostname_format(hostname)?;
+        }
+        for extra_host in &workload_spec.extra_hosts {
+            extra_host.verify_format()?;
+        }
+        for port in &workload_spec.ports {
+            port.verify_format()?;
+        }
+        if let Some(resource_thresholds) = &workload_spec.resource_thresholds {
+            resource_thresholds.verify_format()?;
+        }
+        if let Some(resources) = &workload_spec.resources {
+            resources.verify_format()?;
+        }
+        if let Some(health_check) = &workload_spec.health_check {
+            health_check.verify_format()?;
+        }
+        if let Some(restart_backoff) = &workload_spec.restart_backoff {
+            restart_backoff.verify_format()?;
+        }
+        if let Some(lifecycle_hooks) = &workload_spec.lifecycle_hooks {
+            lifecycle_hooks.verify_format()?;
+        }
+        if let Some(security_context) = &workload_spec.security_context {
+            security_context.verify_format()?;
+        }
+        for volume in &workload_spec.volumes {
+            volume.verify_format()?;
+        }
+        if let Some(mount_path) = find_mount_path_conflict(&workload_spec.volumes) {
+            return Err(format!(
+                "Unsupported volumes. The mount path '{}' is used by more than one volume",
+                mount_path
+            ));
+        }
+        for network in &workload_spec.networks {
+            network.verify_format()?;
+        }
+        verify_network_combination(&workload_spec.networks)?;
+        Self::verify_env_var_names_format(&workload_spec.env)?;
+        Self::verify_restart_on_format(workload_spec)?;
         Ok(())
     }
 
+    // [impl->swdd~common-workload-restart-on-convention~1]
+    fn verify_restart_on_format(workload_spec: &WorkloadSpec) -> Result<(), String> {
+        for workload_name in &workload_spec.restart_on {
+            Self::verify_workload_name_format(workload_name)?;
+            if workload_name == workload_spec.instance_name.workload_name() {
+                return Err(format!(
+                    "Unsupported restartOn entry. The workload '{}' cannot be restarted on itself",
+                    workload_name
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // [impl->swdd~common-workload-env-var-naming-convention~1]
+    fn verify_env_var_names_format(env: &HashMap<String, String>) -> Result<(), String> {
+        let re_env_var_name = Regex::new(STR_RE_ENV_VAR_NAME).unwrap();
+        match env.keys().find(|name| !re_env_var_name.is_match(name)) {
+            Some(name) => Err(format!(
+                "Unsupported environment variable name. Received '{}', expected to match {}",
+                name, STR_RE_ENV_VAR_NAME
+            )),
+            None => Ok(()),
+        }
+    }
+
     // [impl->swdd~common-workload-naming-convention~1]
     fn verify_workload_name_format(workload_name: &str) -> Result<(), String> {
         let re_workloads = Regex::new(STR_RE_WORKLOAD).unwrap();
@@ -186,15 +295,27 @@ pub enum AddCondition {
     AddCondRunning = 0,
     AddCondSucceeded = 1,
     AddCondFailed = 2,
+    AddCondConfigChanged = 3,
+    AddCondReady = 4,
+}
+
+impl AddCondition {
+    // [impl->swdd~workload-config-dependencies~1]
+    pub fn refers_to_config(&self) -> bool {
+        *self == AddCondition::AddCondConfigChanged
+    }
 }
 
 impl FulfilledBy<ExecutionState> for AddCondition {
-    // [impl->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~1]
+    // [impl->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~2]
     fn fulfilled_by(&self, other: &ExecutionState) -> bool {
         match self {
             AddCondition::AddCondRunning => (*other).is_running(),
+            AddCondition::AddCondReady => (*other).is_ready(),
             AddCondition::AddCondSucceeded => (*other).is_succeeded(),
             AddCondition::AddCondFailed => (*other).is_failed(),
+            // [impl->swdd~workload-config-dependencies~1]
+            AddCondition::AddCondConfigChanged => true,
         }
     }
 }
@@ -207,6 +328,10 @@ impl TryFrom<i32> for AddCondition {
             x if x == AddCondition::AddCondRunning as i32 => Ok(AddCondition::AddCondRunning),
             x if x == AddCondition::AddCondSucceeded as i32 => Ok(AddCondition::AddCondSucceeded),
             x if x == AddCondition::AddCondFailed as i32 => Ok(AddCondition::AddCondFailed),
+            x if x == AddCondition::AddCondConfigChanged as i32 => {
+                Ok(AddCondition::AddCondConfigChanged)
+            }
+            x if x == AddCondition::AddCondReady as i32 => Ok(AddCondition::AddCondReady),
             _ => Err(format!(
                 "Received an unknown value '{value}' as AddCondition."
             )),
@@ -223,7 +348,7 @@ pub enum DeleteCondition {
 }
 
 impl FulfilledBy<ExecutionState> for DeleteCondition {
-    // [impl->swdd~execution-states-of-workload-dependencies-fulfill-delete-conditions~1]
+    // [impl->swdd~execution-states-of-workload-dependencies-fulfill-delete-conditions~2]
     fn fulfilled_by(&self, other: &ExecutionState) -> bool {
         if other.is_waiting_to_start() {
             return true;
@@ -316,6 +441,24 @@ pub fn generate_test_workload_spec_with_runtime_config(
         }],
         runtime_config,
         control_interface_access: Default::default(),
+        hostname: None,
+        extra_hosts: vec![],
+        ports: vec![],
+        resource_thresholds: None,
+        resources: None,
+        health_check: None,
+        restart_backoff: None,
+        priority: None,
+        volumes: vec![],
+        registry_auth: None,
+        networks: vec![],
+        env: HashMap::new(),
+        on_agent_disconnect: None,
+        restart_on: vec![],
+        lifecycle_hooks: None,
+        node_selector: HashMap::new(),
+        state_debounce: None,
+        security_context: None,
     }
 }
 
@@ -364,6 +507,8 @@ pub fn generate_test_workload_spec_with_dependencies(
 // [utest->swdd~common-object-serialization~1]
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use crate::objects::*;
     use crate::test_utils::*;
     const RUNTIME: &str = "runtime";
@@ -451,6 +596,14 @@ mod tests {
             AddCondition::try_from(2).unwrap(),
             AddCondition::AddCondFailed
         );
+        assert_eq!(
+            AddCondition::try_from(3).unwrap(),
+            AddCondition::AddCondConfigChanged
+        );
+        assert_eq!(
+            AddCondition::try_from(4).unwrap(),
+            AddCondition::AddCondReady
+        );
         assert_eq!(
             AddCondition::try_from(100),
             Err::<AddCondition, String>(
@@ -499,7 +652,7 @@ mod tests {
         );
     }
 
-    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~1]
+    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~2]
     #[test]
     fn utest_add_condition_fulfilled_by_fulfilled() {
         let add_condition = AddCondition::AddCondRunning;
@@ -510,9 +663,43 @@ mod tests {
 
         let add_condition = AddCondition::AddCondFailed;
         assert!(add_condition.fulfilled_by(&ExecutionState::failed("some failure".to_string())));
+
+        let add_condition = AddCondition::AddCondReady;
+        assert!(add_condition.fulfilled_by(&ExecutionState::running()));
+    }
+
+    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~2]
+    #[test]
+    fn utest_add_condition_ready_not_fulfilled_by_running_not_ready() {
+        let not_ready = ExecutionState::running_not_ready("waiting for health check");
+        assert!(AddCondition::AddCondRunning.fulfilled_by(&not_ready));
+        assert!(!AddCondition::AddCondReady.fulfilled_by(&not_ready));
+        assert!(!AddCondition::AddCondReady
+            .fulfilled_by(&ExecutionState::running_unhealthy("health check failed")));
     }
 
-    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-delete-conditions~1]
+    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-add-conditions~2]
+    #[test]
+    fn utest_add_condition_not_fulfilled_by_paused() {
+        for add_condition in [
+            AddCondition::AddCondRunning,
+            AddCondition::AddCondSucceeded,
+            AddCondition::AddCondFailed,
+            AddCondition::AddCondReady,
+        ] {
+            assert!(!add_condition.fulfilled_by(&ExecutionState::paused()));
+        }
+    }
+
+    // [utest->swdd~workload-config-dependencies~1]
+    #[test]
+    fn utest_add_condition_config_changed_refers_to_config() {
+        assert!(AddCondition::AddCondConfigChanged.refers_to_config());
+        assert!(!AddCondition::AddCondRunning.refers_to_config());
+        assert!(AddCondition::AddCondConfigChanged.fulfilled_by(&ExecutionState::initial()));
+    }
+
+    // [utest->swdd~execution-states-of-workload-dependencies-fulfill-delete-conditions~2]
     #[test]
     fn utest_delete_condition_fulfilled_by() {
         let delete_condition = DeleteCondition::DelCondNotPendingNorRunning;
@@ -523,6 +710,9 @@ mod tests {
 
         let delete_condition = DeleteCondition::DelCondNotPendingNorRunning;
         assert!(delete_condition.fulfilled_by(&ExecutionState::waiting_to_start()));
+
+        let delete_condition = DeleteCondition::DelCondNotPendingNorRunning;
+        assert!(!delete_condition.fulfilled_by(&ExecutionState::paused()));
     }
 
     // [utest->swdd~agent-supports-restart-policies~1]
@@ -610,6 +800,224 @@ mod tests {
         );
     }
 
+    // [utest->swdd~common-workload-hostname-convention~1]
+    #[test]
+    fn utest_workload_verify_fields_incompatible_hostname_and_extra_hosts() {
+        let mut workload_spec = generate_test_workload_spec();
+        workload_spec.hostname = Some("vehicle-1".to_owned());
+        workload_spec.extra_hosts = vec![ExtraHost {
+            hostname: "database".to_owned(),
+            ip: "10.0.0.5".to_owned(),
+        }];
+        assert_eq!(WorkloadSpec::verify_fields_format(&workload_spec), Ok(()));
+
+        let mut spec_with_wrong_hostname = workload_spec.clone();
+        spec_with_wrong_hostname.hostname = Some("vehicle_1".to_owned());
+        assert!(WorkloadSpec::verify_fields_format(&spec_with_wrong_hostname).is_err());
+
+        let mut spec_with_wrong_extra_host = workload_spec;
+        spec_with_wrong_extra_host.extra_hosts[0].ip = "not an ip".to_owned();
+        assert!(WorkloadSpec::verify_fields_format(&spec_with_wrong_extra_host).is_err());
+    }
+
+    // [utest->swdd~common-workload-resource-thresholds-convention~1]
+    #[test]
+    fn utest_workload_verify_fields_incompatible_resource_thresholds() {
+        let mut workload_spec = generate_test_workload_spec();
+        workload_spec.resource_thresholds = Some(ResourceThresholds {
+            cpu_usage: Some(80),
+            memory_usage: Some(512 * 1024 * 1024),
+        });
+        assert_eq!(WorkloadSpec::verify_fields_format(&workload_spec), Ok(()));
+
+        workload_spec.resource_thresholds = Some(ResourceThresholds {
+            cpu_usage: Some(0),
+            memory_usage: None,
+        });
+        assert!(WorkloadSpec::verify_fields_format(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~common-workload-resource-limits-convention~1]
+    #[test]
+    fn utest_workload_verify_fields_incompatible_resource_limits() {
+        let mut workload_spec = generate_test_workload_spec();
+        workload_spec.resources = Some(ResourceLimits {
+            cpu_quota: Some(150),
+            memory_limit: Some(512 * 1024 * 1024),
+        });
+        assert_eq!(WorkloadSpec::verify_fields_format(&workload_spec), Ok(()));
+
+        workload_spec.resources = Some(ResourceLimits {
+            cpu_quota: None,
+            memory_limit: Some(0),
+        });
+        assert!(WorkloadSpec::verify_fields_format(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~common-workload-health-check-convention~1]
+    #[test]
+    fn utest_workload_verify_fields_incompatible_health_check() {
+        let mut workload_spec = generate_test_workload_spec();
+        workload_spec.health_check = Some(HealthCheck {
+            command: Some(vec!["pg_isready".into()]),
+            ..Default::default()
+        });
+        assert_eq!(WorkloadSpec::verify_fields_format(&workload_spec), Ok(()));
+
+        workload_spec.health_check = Some(HealthCheck::default());
+        assert!(WorkloadSpec::verify_fields_format(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~common-workload-restart-backoff-convention~1]
+    #[test]
+    fn utest_workload_verify_fields_incompatible_restart_backoff() {
+        let mut workload_spec = generate_test_workload_spec();
+        workload_spec.restart_backoff = Some(RestartBackoff {
+            max_retries: Some(5),
+            ..Default::default()
+        });
+        assert_eq!(WorkloadSpec::verify_fields_format(&workload_spec), Ok(()));
+
+        workload_spec.restart_backoff = Some(RestartBackoff {
+            max_retries: Some(0),
+            ..Default::default()
+        });
+        assert!(WorkloadSpec::verify_fields_format(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~common-workload-volume-convention~1]
+    // [utest->swdd~common-workload-volume-mount-path-conflicts~1]
+    #[test]
+    fn utest_workload_verify_fields_incompatible_volumes() {
+        let mut workload_spec = generate_test_workload_spec();
+        workload_spec.volumes = vec![
+            Volume {
+                volume_type: VolumeType::HostPath,
+                source: "/opt/data".into(),
+                mount_path: "/data".into(),
+                read_only: true,
+            },
+            Volume {
+                volume_type: VolumeType::Tmpfs,
+                mount_path: "/tmp".into(),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(WorkloadSpec::verify_fields_format(&workload_spec), Ok(()));
+
+        workload_spec.volumes[1].mount_path = "tmp".into();
+        assert!(WorkloadSpec::verify_fields_format(&workload_spec).is_err());
+
+        workload_spec.volumes[1].mount_path = "/data".into();
+        assert_eq!(
+            WorkloadSpec::verify_fields_format(&workload_spec),
+            Err(
+                "Unsupported volumes. The mount path '/data' is used by more than one volume"
+                    .into()
+            )
+        );
+    }
+
+    // [utest->swdd~common-workload-network-convention~1]
+    // [utest->swdd~common-workload-network-combinations~1]
+    #[test]
+    fn utest_workload_verify_fields_incompatible_networks() {
+        let mut workload_spec = generate_test_workload_spec();
+        workload_spec.networks = vec![
+            Network {
+                name: "backend".into(),
+                aliases: vec!["api".into()],
+            },
+            Network {
+                name: "frontend".into(),
+                aliases: vec![],
+            },
+        ];
+        assert_eq!(WorkloadSpec::verify_fields_format(&workload_spec), Ok(()));
+
+        workload_spec.networks[0].aliases = vec!["api_1".into()];
+        assert!(WorkloadSpec::verify_fields_format(&workload_spec).is_err());
+
+        workload_spec.networks[0].aliases = vec![];
+        workload_spec.networks[1].name = "host".into();
+        assert!(WorkloadSpec::verify_fields_format(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~common-workload-env-var-naming-convention~1]
+    #[test]
+    fn utest_workload_verify_fields_env_var_names() {
+        let mut workload_spec = generate_test_workload_spec();
+        workload_spec.env = HashMap::from([
+            ("LOG_LEVEL".into(), "debug".into()),
+            ("_proxy2".into(), "{{proxy.url}}".into()),
+        ]);
+        assert_eq!(WorkloadSpec::verify_fields_format(&workload_spec), Ok(()));
+
+        for invalid_name in ["", "2FAST", "LOG-LEVEL", "A=B"] {
+            workload_spec.env = HashMap::from([(invalid_name.into(), "value".into())]);
+            assert!(WorkloadSpec::verify_fields_format(&workload_spec).is_err());
+        }
+    }
+
+    // [utest->swdd~common-workload-restart-on-convention~1]
+    #[test]
+    fn utest_workload_verify_fields_restart_on() {
+        let mut workload_spec = generate_test_workload_spec();
+        workload_spec.restart_on = vec!["backend".into(), "team-a__backend".into()];
+        assert_eq!(WorkloadSpec::verify_fields_format(&workload_spec), Ok(()));
+
+        workload_spec.restart_on = vec!["back.end".into()];
+        assert!(WorkloadSpec::verify_fields_format(&workload_spec).is_err());
+
+        workload_spec.restart_on = vec![workload_spec.instance_name.workload_name().to_owned()];
+        assert!(WorkloadSpec::verify_fields_format(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~common-workload-lifecycle-hooks-convention~1]
+    #[test]
+    fn utest_workload_verify_fields_lifecycle_hooks() {
+        let mut workload_spec = generate_test_workload_spec();
+        workload_spec.lifecycle_hooks = Some(LifecycleHooks {
+            post_start: Some(LifecycleHook {
+                command: vec!["/bin/warmup".into()],
+                timeout_secs: Some(10),
+            }),
+            pre_stop: None,
+        });
+        assert_eq!(WorkloadSpec::verify_fields_format(&workload_spec), Ok(()));
+
+        workload_spec.lifecycle_hooks = Some(LifecycleHooks {
+            post_start: None,
+            pre_stop: Some(LifecycleHook {
+                command: vec![],
+                timeout_secs: None,
+            }),
+        });
+        assert!(WorkloadSpec::verify_fields_format(&workload_spec).is_err());
+    }
+
+    // [utest->swdd~common-workload-security-context-convention~1]
+    #[test]
+    fn utest_workload_verify_fields_security_context() {
+        let mut workload_spec = generate_test_workload_spec();
+        workload_spec.security_context = Some(SecurityContext {
+            run_as_user: Some(1000),
+            capabilities: Capabilities {
+                add: vec![],
+                drop: vec!["ALL".into()],
+            },
+            read_only_root_filesystem: true,
+            ..Default::default()
+        });
+        assert_eq!(WorkloadSpec::verify_fields_format(&workload_spec), Ok(()));
+
+        workload_spec.security_context = Some(SecurityContext {
+            run_as_group: Some(1000),
+            ..Default::default()
+        });
+        assert!(WorkloadSpec::verify_fields_format(&workload_spec).is_err());
+    }
+
     // [utest->swdd~common-workload-naming-convention~1]
     #[test]
     fn utest_verify_workload_name_format_inordinately_long_workload_name() {
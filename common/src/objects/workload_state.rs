@@ -58,11 +58,19 @@ impl Display for PendingSubstate {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum RunningSubstate {
     Ok = 0,
+    ResourceWarning = 1,
+    Unhealthy = 2,
+    NotReady = 3,
 }
 
 impl From<i32> for RunningSubstate {
-    fn from(_x: i32) -> Self {
-        RunningSubstate::Ok
+    fn from(x: i32) -> Self {
+        match x {
+            x if x == RunningSubstate::ResourceWarning as i32 => RunningSubstate::ResourceWarning,
+            x if x == RunningSubstate::Unhealthy as i32 => RunningSubstate::Unhealthy,
+            x if x == RunningSubstate::NotReady as i32 => RunningSubstate::NotReady,
+            _ => RunningSubstate::Ok,
+        }
     }
 }
 
@@ -70,6 +78,9 @@ impl Display for RunningSubstate {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             RunningSubstate::Ok => write!(f, "Ok"),
+            RunningSubstate::ResourceWarning => write!(f, "ResourceWarning"),
+            RunningSubstate::Unhealthy => write!(f, "Unhealthy"),
+            RunningSubstate::NotReady => write!(f, "NotReady"),
         }
     }
 }
@@ -79,6 +90,7 @@ pub enum StoppingSubstate {
     Stopping = 0,
     WaitingToStop = 1,
     RequestedAtRuntime = 2,
+    GracePeriod = 3,
     DeleteFailed = 8,
 }
 
@@ -89,6 +101,7 @@ impl From<i32> for StoppingSubstate {
             x if x == StoppingSubstate::RequestedAtRuntime as i32 => {
                 StoppingSubstate::RequestedAtRuntime
             }
+            x if x == StoppingSubstate::GracePeriod as i32 => StoppingSubstate::GracePeriod,
             x if x == StoppingSubstate::DeleteFailed as i32 => StoppingSubstate::DeleteFailed,
             _ => StoppingSubstate::Stopping,
         }
@@ -101,6 +114,7 @@ impl Display for StoppingSubstate {
             StoppingSubstate::Stopping => write!(f, "Stopping"),
             StoppingSubstate::WaitingToStop => write!(f, "WaitingToStop"),
             StoppingSubstate::RequestedAtRuntime => write!(f, "RequestedAtRuntime"),
+            StoppingSubstate::GracePeriod => write!(f, "GracePeriod"),
             StoppingSubstate::DeleteFailed => write!(f, "DeleteFailed"),
         }
     }
@@ -130,6 +144,8 @@ pub enum FailedSubstate {
     ExecFailed = 0,
     Unknown = 1,
     Lost = 2,
+    Evicted = 3,
+    StoppedOnDisconnect = 4,
 }
 
 impl From<i32> for FailedSubstate {
@@ -138,6 +154,10 @@ impl From<i32> for FailedSubstate {
             x if x == FailedSubstate::ExecFailed as i32 => FailedSubstate::ExecFailed,
             x if x == FailedSubstate::Unknown as i32 => FailedSubstate::Unknown,
             x if x == FailedSubstate::Lost as i32 => FailedSubstate::Lost,
+            x if x == FailedSubstate::Evicted as i32 => FailedSubstate::Evicted,
+            x if x == FailedSubstate::StoppedOnDisconnect as i32 => {
+                FailedSubstate::StoppedOnDisconnect
+            }
             _ => FailedSubstate::Unknown,
         }
     }
@@ -149,6 +169,8 @@ impl Display for FailedSubstate {
             FailedSubstate::ExecFailed => write!(f, "ExecFailed"),
             FailedSubstate::Unknown => write!(f, "Unknown"),
             FailedSubstate::Lost => write!(f, "Lost"),
+            FailedSubstate::Evicted => write!(f, "Evicted"),
+            FailedSubstate::StoppedOnDisconnect => write!(f, "StoppedOnDisconnect"),
         }
     }
 }
@@ -165,6 +187,9 @@ pub enum ExecutionStateEnum {
     #[default]
     NotScheduled,
     Removed,
+    Paused,
+    AgentLost,
+    Rescheduled,
 }
 
 // [impl->swdd~common-workload-state-transitions~1]
@@ -173,8 +198,12 @@ impl ExecutionState {
         match (&self.state, &incoming.state) {
             (
                 ExecutionStateEnum::Stopping(StoppingSubstate::RequestedAtRuntime)
+                | ExecutionStateEnum::Stopping(StoppingSubstate::GracePeriod)
                 | ExecutionStateEnum::Stopping(StoppingSubstate::WaitingToStop),
                 ExecutionStateEnum::Running(RunningSubstate::Ok)
+                | ExecutionStateEnum::Running(RunningSubstate::ResourceWarning)
+                | ExecutionStateEnum::Running(RunningSubstate::Unhealthy)
+                | ExecutionStateEnum::Running(RunningSubstate::NotReady)
                 | ExecutionStateEnum::Succeeded(SucceededSubstate::Ok)
                 | ExecutionStateEnum::Failed(FailedSubstate::ExecFailed)
                 | ExecutionStateEnum::Failed(FailedSubstate::Lost)
@@ -220,6 +249,19 @@ impl From<ExecutionStateEnum> for ank_base::execution_state::ExecutionStateEnum
             ExecutionStateEnum::Removed => ank_base::execution_state::ExecutionStateEnum::Removed(
                 ank_base::Removed::Removed as i32,
             ),
+            ExecutionStateEnum::Paused => ank_base::execution_state::ExecutionStateEnum::Paused(
+                ank_base::Paused::Paused as i32,
+            ),
+            ExecutionStateEnum::AgentLost => {
+                ank_base::execution_state::ExecutionStateEnum::AgentLost(
+                    ank_base::AgentLost::AgentLost as i32,
+                )
+            }
+            ExecutionStateEnum::Rescheduled => {
+                ank_base::execution_state::ExecutionStateEnum::Rescheduled(
+                    ank_base::Rescheduled::Rescheduled as i32,
+                )
+            }
             ExecutionStateEnum::Stopping(value) => {
                 ank_base::execution_state::ExecutionStateEnum::Stopping(value as i32)
             }
@@ -254,11 +296,18 @@ impl From<ank_base::execution_state::ExecutionStateEnum> for ExecutionStateEnum
             ank_base::execution_state::ExecutionStateEnum::Removed(_) => {
                 ExecutionStateEnum::Removed
             }
+            ank_base::execution_state::ExecutionStateEnum::Paused(_) => ExecutionStateEnum::Paused,
+            ank_base::execution_state::ExecutionStateEnum::AgentLost(_) => {
+                ExecutionStateEnum::AgentLost
+            }
+            ank_base::execution_state::ExecutionStateEnum::Rescheduled(_) => {
+                ExecutionStateEnum::Rescheduled
+            }
         }
     }
 }
 
-// [impl->swdd~common-workload-states-supported-states~1]
+// [impl->swdd~common-workload-states-supported-states~7]
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(default, rename_all = "camelCase")]
 pub struct ExecutionState {
@@ -282,7 +331,20 @@ impl ExecutionState {
     }
 
     pub fn is_running(&self) -> bool {
-        ExecutionStateEnum::Running(RunningSubstate::Ok) == self.state
+        matches!(self.state, ExecutionStateEnum::Running(_))
+    }
+
+    pub fn is_unhealthy(&self) -> bool {
+        ExecutionStateEnum::Running(RunningSubstate::Unhealthy) == self.state
+    }
+
+    // a workload is ready when it is running and its health check, if any, succeeds
+    pub fn is_ready(&self) -> bool {
+        matches!(
+            self.state,
+            ExecutionStateEnum::Running(RunningSubstate::Ok)
+                | ExecutionStateEnum::Running(RunningSubstate::ResourceWarning)
+        )
     }
 
     pub fn is_succeeded(&self) -> bool {
@@ -293,8 +355,21 @@ impl ExecutionState {
         ExecutionStateEnum::Failed(FailedSubstate::ExecFailed) == self.state
     }
 
+    pub fn is_evicted(&self) -> bool {
+        ExecutionStateEnum::Failed(FailedSubstate::Evicted) == self.state
+    }
+
+    pub fn is_stopped_on_disconnect(&self) -> bool {
+        ExecutionStateEnum::Failed(FailedSubstate::StoppedOnDisconnect) == self.state
+    }
+
+    pub fn is_paused(&self) -> bool {
+        ExecutionStateEnum::Paused == self.state
+    }
+
+    // a paused workload still exists and needs its dependencies once it is resumed
     pub fn is_not_pending_nor_running(&self) -> bool {
-        !self.is_pending() && !self.is_running()
+        !self.is_pending() && !self.is_running() && !self.is_paused()
     }
 
     pub fn is_waiting_to_start(&self) -> bool {
@@ -391,6 +466,27 @@ impl ExecutionState {
         }
     }
 
+    pub fn running_resource_warning(additional_info: impl ToString) -> Self {
+        ExecutionState {
+            state: ExecutionStateEnum::Running(RunningSubstate::ResourceWarning),
+            additional_info: additional_info.to_string(),
+        }
+    }
+
+    pub fn running_unhealthy(additional_info: impl ToString) -> Self {
+        ExecutionState {
+            state: ExecutionStateEnum::Running(RunningSubstate::Unhealthy),
+            additional_info: additional_info.to_string(),
+        }
+    }
+
+    pub fn running_not_ready(additional_info: impl ToString) -> Self {
+        ExecutionState {
+            state: ExecutionStateEnum::Running(RunningSubstate::NotReady),
+            additional_info: additional_info.to_string(),
+        }
+    }
+
     pub fn stopping(additional_info: impl ToString) -> Self {
         ExecutionState {
             state: ExecutionStateEnum::Stopping(StoppingSubstate::Stopping),
@@ -405,6 +501,13 @@ impl ExecutionState {
         }
     }
 
+    pub fn stopping_grace_period(grace_period_secs: u32) -> Self {
+        ExecutionState {
+            state: ExecutionStateEnum::Stopping(StoppingSubstate::GracePeriod),
+            additional_info: format!("Grace period of {}s", grace_period_secs),
+        }
+    }
+
     pub fn delete_failed(additional_info: impl ToString) -> Self {
         ExecutionState {
             state: ExecutionStateEnum::Stopping(StoppingSubstate::DeleteFailed),
@@ -419,6 +522,20 @@ impl ExecutionState {
         }
     }
 
+    pub fn evicted(additional_info: impl ToString) -> Self {
+        ExecutionState {
+            state: ExecutionStateEnum::Failed(FailedSubstate::Evicted),
+            additional_info: additional_info.to_string(),
+        }
+    }
+
+    pub fn stopped_on_disconnect(additional_info: impl ToString) -> Self {
+        ExecutionState {
+            state: ExecutionStateEnum::Failed(FailedSubstate::StoppedOnDisconnect),
+            additional_info: additional_info.to_string(),
+        }
+    }
+
     pub fn waiting_to_start() -> Self {
         ExecutionState {
             state: ExecutionStateEnum::Pending(PendingSubstate::WaitingToStart),
@@ -446,6 +563,27 @@ impl ExecutionState {
             ..Default::default()
         }
     }
+
+    pub fn paused() -> Self {
+        ExecutionState {
+            state: ExecutionStateEnum::Paused,
+            ..Default::default()
+        }
+    }
+
+    pub fn agent_lost() -> Self {
+        ExecutionState {
+            state: ExecutionStateEnum::AgentLost,
+            ..Default::default()
+        }
+    }
+
+    pub fn rescheduled(additional_info: impl ToString) -> Self {
+        ExecutionState {
+            state: ExecutionStateEnum::Rescheduled,
+            additional_info: additional_info.to_string(),
+        }
+    }
 }
 
 impl From<ExecutionState> for ank_base::ExecutionState {
@@ -484,6 +622,9 @@ impl Display for ExecutionStateEnum {
             ExecutionStateEnum::Failed(substate) => write!(f, "Failed({substate})"),
             ExecutionStateEnum::NotScheduled => write!(f, "NotScheduled"),
             ExecutionStateEnum::Removed => write!(f, "Removed"),
+            ExecutionStateEnum::Paused => write!(f, "Paused"),
+            ExecutionStateEnum::AgentLost => write!(f, "AgentLost"),
+            ExecutionStateEnum::Rescheduled => write!(f, "Rescheduled"),
         }
     }
 }
@@ -597,6 +738,22 @@ mod tests {
             ExecutionState::stopping_requested().transition(ExecutionState::running()),
             ExecutionState::stopping_requested()
         );
+        assert_eq!(
+            ExecutionState::stopping_requested().transition(
+                ExecutionState::running_resource_warning("memory usage above threshold")
+            ),
+            ExecutionState::stopping_requested()
+        );
+        assert_eq!(
+            ExecutionState::stopping_requested()
+                .transition(ExecutionState::running_unhealthy("health check failed")),
+            ExecutionState::stopping_requested()
+        );
+        assert_eq!(
+            ExecutionState::stopping_requested()
+                .transition(ExecutionState::running_not_ready("health check pending")),
+            ExecutionState::stopping_requested()
+        );
         assert_eq!(
             ExecutionState::stopping_requested().transition(ExecutionState::succeeded()),
             ExecutionState::stopping_requested()
@@ -615,6 +772,10 @@ mod tests {
                 .transition(ExecutionState::unknown("I lost the thing")),
             ExecutionState::stopping_requested()
         );
+        assert_eq!(
+            ExecutionState::stopping_grace_period(30).transition(ExecutionState::running()),
+            ExecutionState::stopping_grace_period(30)
+        );
         assert_eq!(
             ExecutionState::stopping_requested().transition(ExecutionState::delete_failed(
                 "mi mi mi, I could not delete it..."
@@ -693,7 +854,7 @@ mod tests {
     }
 
     // [utest->swdd~common-workload-state-additional-information~1]
-    // [utest->swdd~common-workload-states-supported-states~1]
+    // [utest->swdd~common-workload-states-supported-states~7]
     #[test]
     fn utest_execution_state_to_proto_mapping() {
         let additional_info = "some additional info";
@@ -775,6 +936,33 @@ mod tests {
             },
             ExecutionState::running().into(),
         );
+        assert_eq!(
+            ank_base::ExecutionState {
+                additional_info: additional_info.to_string(),
+                execution_state_enum: Some(ank_base::execution_state::ExecutionStateEnum::Running(
+                    ank_base::Running::ResourceWarning.into(),
+                )),
+            },
+            ExecutionState::running_resource_warning(additional_info).into(),
+        );
+        assert_eq!(
+            ank_base::ExecutionState {
+                additional_info: additional_info.to_string(),
+                execution_state_enum: Some(ank_base::execution_state::ExecutionStateEnum::Running(
+                    ank_base::Running::Unhealthy.into(),
+                )),
+            },
+            ExecutionState::running_unhealthy(additional_info).into(),
+        );
+        assert_eq!(
+            ank_base::ExecutionState {
+                additional_info: additional_info.to_string(),
+                execution_state_enum: Some(ank_base::execution_state::ExecutionStateEnum::Running(
+                    ank_base::Running::NotReady.into(),
+                )),
+            },
+            ExecutionState::running_not_ready(additional_info).into(),
+        );
         assert_eq!(
             ank_base::ExecutionState {
                 additional_info: additional_info.to_string(),
@@ -795,10 +983,59 @@ mod tests {
             },
             ExecutionState::lost().into(),
         );
+        assert_eq!(
+            ank_base::ExecutionState {
+                additional_info: additional_info.to_string(),
+                execution_state_enum: Some(ank_base::execution_state::ExecutionStateEnum::Failed(
+                    ank_base::Failed::Evicted.into(),
+                )),
+            },
+            ExecutionState::evicted(additional_info).into(),
+        );
+        assert_eq!(
+            ank_base::ExecutionState {
+                additional_info: additional_info.to_string(),
+                execution_state_enum: Some(ank_base::execution_state::ExecutionStateEnum::Failed(
+                    ank_base::Failed::StoppedOnDisconnect.into(),
+                )),
+            },
+            ExecutionState::stopped_on_disconnect(additional_info).into(),
+        );
+        assert_eq!(
+            ank_base::ExecutionState {
+                additional_info: "".to_string(),
+                execution_state_enum: Some(ank_base::execution_state::ExecutionStateEnum::Paused(
+                    ank_base::Paused::Paused.into(),
+                )),
+            },
+            ExecutionState::paused().into(),
+        );
+        assert_eq!(
+            ank_base::ExecutionState {
+                additional_info: "".to_string(),
+                execution_state_enum: Some(
+                    ank_base::execution_state::ExecutionStateEnum::AgentLost(
+                        ank_base::AgentLost::AgentLost.into(),
+                    )
+                ),
+            },
+            ExecutionState::agent_lost().into(),
+        );
+        assert_eq!(
+            ank_base::ExecutionState {
+                additional_info: additional_info.to_string(),
+                execution_state_enum: Some(
+                    ank_base::execution_state::ExecutionStateEnum::Rescheduled(
+                        ank_base::Rescheduled::Rescheduled.into(),
+                    )
+                ),
+            },
+            ExecutionState::rescheduled(additional_info).into(),
+        );
     }
 
     // [utest->swdd~common-workload-state-additional-information~1]
-    // [utest->swdd~common-workload-states-supported-states~1]
+    // [utest->swdd~common-workload-states-supported-states~7]
     #[test]
     fn utest_execution_state_from_proto_mapping() {
         let additional_info = "some additional info";
@@ -866,6 +1103,36 @@ mod tests {
             }
             .into(),
         );
+        assert_eq!(
+            ExecutionState::running_resource_warning(additional_info),
+            ank_base::ExecutionState {
+                additional_info: additional_info.to_string(),
+                execution_state_enum: Some(ank_base::execution_state::ExecutionStateEnum::Running(
+                    ank_base::Running::ResourceWarning.into(),
+                )),
+            }
+            .into(),
+        );
+        assert_eq!(
+            ExecutionState::running_unhealthy(additional_info),
+            ank_base::ExecutionState {
+                additional_info: additional_info.to_string(),
+                execution_state_enum: Some(ank_base::execution_state::ExecutionStateEnum::Running(
+                    ank_base::Running::Unhealthy.into(),
+                )),
+            }
+            .into(),
+        );
+        assert_eq!(
+            ExecutionState::running_not_ready(additional_info),
+            ank_base::ExecutionState {
+                additional_info: additional_info.to_string(),
+                execution_state_enum: Some(ank_base::execution_state::ExecutionStateEnum::Running(
+                    ank_base::Running::NotReady.into(),
+                )),
+            }
+            .into(),
+        );
         assert_eq!(
             ExecutionState::succeeded(),
             ank_base::ExecutionState {
@@ -910,10 +1177,64 @@ mod tests {
             }
             .into(),
         );
+        assert_eq!(
+            ExecutionState::evicted(additional_info),
+            ank_base::ExecutionState {
+                additional_info: additional_info.to_string(),
+                execution_state_enum: Some(ank_base::execution_state::ExecutionStateEnum::Failed(
+                    ank_base::Failed::Evicted.into(),
+                )),
+            }
+            .into(),
+        );
+        assert_eq!(
+            ExecutionState::stopped_on_disconnect(additional_info),
+            ank_base::ExecutionState {
+                additional_info: additional_info.to_string(),
+                execution_state_enum: Some(ank_base::execution_state::ExecutionStateEnum::Failed(
+                    ank_base::Failed::StoppedOnDisconnect.into(),
+                )),
+            }
+            .into(),
+        );
+        assert_eq!(
+            ExecutionState::paused(),
+            ank_base::ExecutionState {
+                additional_info: "".to_string(),
+                execution_state_enum: Some(ank_base::execution_state::ExecutionStateEnum::Paused(
+                    ank_base::Paused::Paused.into(),
+                )),
+            }
+            .into(),
+        );
+        assert_eq!(
+            ExecutionState::agent_lost(),
+            ank_base::ExecutionState {
+                additional_info: "".to_string(),
+                execution_state_enum: Some(
+                    ank_base::execution_state::ExecutionStateEnum::AgentLost(
+                        ank_base::AgentLost::AgentLost.into(),
+                    )
+                ),
+            }
+            .into(),
+        );
+        assert_eq!(
+            ExecutionState::rescheduled(additional_info),
+            ank_base::ExecutionState {
+                additional_info: additional_info.to_string(),
+                execution_state_enum: Some(
+                    ank_base::execution_state::ExecutionStateEnum::Rescheduled(
+                        ank_base::Rescheduled::Rescheduled.into(),
+                    )
+                ),
+            }
+            .into(),
+        );
     }
 
     // [utest->swdd~common-workload-state-additional-information~1]
-    // [utest->swdd~common-workload-states-supported-states~1]
+    // [utest->swdd~common-workload-states-supported-states~7]
     #[test]
     fn utest_execution_state_to_string_basic_mapping() {
         let additional_info = "some additional info";
@@ -937,6 +1258,14 @@ mod tests {
             ExecutionState::removed().to_string(),
             String::from("Removed")
         );
+        assert_eq!(
+            ExecutionState::agent_lost().to_string(),
+            String::from("AgentLost")
+        );
+        assert_eq!(
+            ExecutionState::rescheduled(additional_info).to_string(),
+            format!("Rescheduled: '{additional_info}'")
+        );
         assert_eq!(
             ExecutionState::unknown(additional_info).to_string(),
             format!("Failed(Unknown): '{additional_info}'")
@@ -957,13 +1286,38 @@ mod tests {
             ExecutionState::running().to_string(),
             String::from("Running(Ok)")
         );
+        assert_eq!(
+            ExecutionState::running_resource_warning(additional_info).to_string(),
+            format!("Running(ResourceWarning): '{additional_info}'")
+        );
+        assert_eq!(
+            ExecutionState::running_unhealthy(additional_info).to_string(),
+            format!("Running(Unhealthy): '{additional_info}'")
+        );
+        assert_eq!(
+            ExecutionState::running_not_ready(additional_info).to_string(),
+            format!("Running(NotReady): '{additional_info}'")
+        );
         assert_eq!(
             ExecutionState::stopping(additional_info).to_string(),
             format!("Stopping(Stopping): '{additional_info}'")
         );
+        assert_eq!(
+            ExecutionState::stopping_grace_period(30).to_string(),
+            String::from("Stopping(GracePeriod): 'Grace period of 30s'")
+        );
         assert_eq!(
             ExecutionState::lost().to_string(),
             String::from("Failed(Lost)")
         );
+        assert_eq!(
+            ExecutionState::evicted(additional_info).to_string(),
+            format!("Failed(Evicted): '{additional_info}'")
+        );
+        assert_eq!(
+            ExecutionState::stopped_on_disconnect(additional_info).to_string(),
+            format!("Failed(StoppedOnDisconnect): '{additional_info}'")
+        );
+        assert_eq!(ExecutionState::paused().to_string(), String::from("Paused"));
     }
 }
@@ -17,7 +17,9 @@ use std::collections::{hash_map::Entry, HashMap};
 use api::ank_base;
 use serde::{Deserialize, Serialize};
 
-use super::{ExecutionState, WorkloadInstanceName, WorkloadSpec, WorkloadState};
+use super::{
+    ExecutionState, ExecutionStateEnum, WorkloadInstanceName, WorkloadSpec, WorkloadState,
+};
 
 type AgentName = String;
 type WorkloadName = String;
@@ -92,6 +94,21 @@ impl WorkloadStatesMap {
             .and_then(|id_map| id_map.get(instance_name.id()))
     }
 
+    pub fn execution_states(&self) -> impl Iterator<Item = &ExecutionState> {
+        self.0
+            .values()
+            .flat_map(|name_map| name_map.values())
+            .flat_map(|id_map| id_map.values())
+    }
+
+    // Keeps only the states of the workloads with the given names.
+    pub fn retain_workloads(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.0.retain(|_, name_map| {
+            name_map.retain(|workload_name, _| keep(workload_name));
+            !name_map.is_empty()
+        });
+    }
+
     pub fn agent_disconnected(&mut self, agent_name: &str) {
         if let Some(agent_states) = self.0.get_mut(agent_name) {
             agent_states.iter_mut().for_each(|(_, name_map)| {
@@ -102,6 +119,20 @@ impl WorkloadStatesMap {
         }
     }
 
+    // The workloads of an agent which reconnected in the meantime already have new states.
+    pub fn agent_lost(&mut self, agent_name: &str) {
+        if let Some(agent_states) = self.0.get_mut(agent_name) {
+            agent_states.iter_mut().for_each(|(_, name_map)| {
+                name_map
+                    .iter_mut()
+                    .filter(|(_, exec_state)| {
+                        exec_state.state == ExecutionStateEnum::AgentDisconnected
+                    })
+                    .for_each(|(_, exec_state)| *exec_state = ExecutionState::agent_lost())
+            })
+        }
+    }
+
     pub fn initial_state(&mut self, workload_specs: &Vec<WorkloadSpec>) {
         for spec in workload_specs {
             self.entry(spec.instance_name.agent_name().to_owned())
@@ -132,21 +163,34 @@ impl WorkloadStatesMap {
         }
     }
 
-    pub fn process_new_states(&mut self, workload_states: Vec<WorkloadState>) {
-        workload_states.into_iter().for_each(|workload_state| {
-            if workload_state.execution_state.is_removed() {
-                self.remove(&workload_state.instance_name);
-            } else {
-                self.entry(workload_state.instance_name.agent_name().to_owned())
-                    .or_default()
-                    .entry(workload_state.instance_name.workload_name().to_owned())
-                    .or_default()
-                    .insert(
-                        workload_state.instance_name.id().to_owned(),
-                        workload_state.execution_state,
-                    );
-            }
-        });
+    // Stores the new states and returns only the ones which differ from the already stored states.
+    pub fn process_new_states(
+        &mut self,
+        workload_states: Vec<WorkloadState>,
+    ) -> Vec<WorkloadState> {
+        workload_states
+            .into_iter()
+            .filter(|workload_state| {
+                if workload_state.execution_state.is_removed() {
+                    let is_known = self
+                        .get_workload_state_for_workload(&workload_state.instance_name)
+                        .is_some();
+                    self.remove(&workload_state.instance_name);
+                    is_known
+                } else {
+                    let previous_state = self
+                        .entry(workload_state.instance_name.agent_name().to_owned())
+                        .or_default()
+                        .entry(workload_state.instance_name.workload_name().to_owned())
+                        .or_default()
+                        .insert(
+                            workload_state.instance_name.id().to_owned(),
+                            workload_state.execution_state.clone(),
+                        );
+                    previous_state.as_ref() != Some(&workload_state.execution_state)
+                }
+            })
+            .collect()
     }
 }
 
@@ -392,6 +436,22 @@ mod tests {
         )
     }
 
+    #[test]
+    fn utest_workload_states_map_execution_states() {
+        let wls_db = create_test_setup();
+
+        let mut execution_states: Vec<String> = wls_db
+            .execution_states()
+            .map(|exec_state| exec_state.state.to_string())
+            .collect();
+        execution_states.sort();
+
+        assert_eq!(
+            execution_states,
+            vec!["Pending(Starting)", "Running(Ok)", "Succeeded(Ok)"]
+        );
+    }
+
     #[test]
     fn utest_workload_states_store_new() {
         let mut wls_db = create_test_setup();
@@ -513,6 +573,40 @@ mod tests {
         )
     }
 
+    #[test]
+    fn utest_mark_disconnected_workload_states_for_agent_lost() {
+        let mut wls_db = create_test_setup();
+        wls_db.agent_disconnected(AGENT_A);
+        wls_db.process_new_states(vec![generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_2,
+            AGENT_A,
+            ExecutionState::rescheduled("agent_B"),
+        )]);
+
+        wls_db.agent_lost(AGENT_A);
+
+        assert_eq!(
+            wls_db,
+            generate_test_workload_states_map_from_workload_states(vec![
+                generate_test_workload_state_with_agent(
+                    WORKLOAD_NAME_1,
+                    AGENT_A,
+                    ExecutionState::agent_lost()
+                ),
+                generate_test_workload_state_with_agent(
+                    WORKLOAD_NAME_2,
+                    AGENT_A,
+                    ExecutionState::rescheduled("agent_B")
+                ),
+                generate_test_workload_state_with_agent(
+                    WORKLOAD_NAME_3,
+                    AGENT_B,
+                    ExecutionState::running()
+                ),
+            ])
+        )
+    }
+
     #[test]
     fn utest_get_workload_state_for_agent_returns_workload_state_of_existing_agent_name() {
         let wls_db = create_test_setup();
@@ -550,6 +644,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn utest_workload_states_process_new_states_returns_only_changed_states() {
+        let mut wls_db = create_test_setup();
+
+        let wl_state_1_unchanged = generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_1,
+            AGENT_A,
+            ExecutionState::succeeded(),
+        );
+        let wl_state_2_update = generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_2,
+            AGENT_A,
+            ExecutionState::running(),
+        );
+        let wl_state_3_removed = generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_3,
+            AGENT_B,
+            ExecutionState::removed(),
+        );
+        let wl_state_4_unknown_removed = generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_4,
+            AGENT_B,
+            ExecutionState::removed(),
+        );
+
+        let changed_states = wls_db.process_new_states(vec![
+            wl_state_1_unchanged,
+            wl_state_2_update.clone(),
+            wl_state_3_removed.clone(),
+            wl_state_4_unknown_removed,
+        ]);
+
+        assert_eq!(changed_states, vec![wl_state_2_update, wl_state_3_removed]);
+    }
+
     #[test]
     fn utest_workload_states_deletes_removed() {
         let mut wls_db = create_test_setup();
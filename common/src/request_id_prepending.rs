@@ -14,6 +14,10 @@
 use crate::std_extensions::UnreachableOption;
 
 const SEPARATOR: &str = "@";
+// not a valid agent name, such that the requests of the server itself never collide with an agent
+pub const SERVER_INTERNAL_REQUEST_PREFIX: &str = "ank.server";
+// the names of the CLI connections start with this prefix followed by a unique id
+pub const CLI_CONNECTION_NAME_PREFIX: &str = "cli-conn-";
 
 pub fn prepend_request_id(request_id: &str, agent_name: &str) -> String {
     if request_id.is_empty() {
@@ -336,6 +336,8 @@ mod tests {
                 ExecutionState::running(),
             ),
             agents: agent_map,
+            workload_resources: Default::default(),
+            image_states: Default::default(),
         };
 
         let expected = Object {
@@ -365,6 +367,8 @@ mod tests {
                 ExecutionState::running(),
             ),
             agents: agent_map,
+            workload_resources: Default::default(),
+            image_states: Default::default(),
         };
         let actual: CompleteState = object.try_into().unwrap();
 
@@ -743,6 +747,8 @@ mod tests {
                             .entry("free_memory", Mapping::default().entry("free_memory", 42)),
                     ),
                 )
+                .entry("workloadResources", Mapping::default())
+                .entry("imageStates", Mapping::default())
         }
 
         pub fn generate_test_state() -> Mapping {
@@ -12,6 +12,10 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+pub use crate::loopback_communications::{
+    LoopbackCommunicationsClient, LoopbackCommunicationsServer,
+};
+
 use std::collections::HashMap;
 
 use api::ank_base::{self, ConfigMappings, Dependencies, Tags, WorkloadMap};
@@ -51,6 +55,9 @@ pub fn generate_test_state_from_workloads(workloads: Vec<WorkloadSpec>) -> State
             ("config_3".into(), ConfigItem::String("value 3".into())),
         ]
         .into(),
+        defaults: Default::default(),
+        namespaces: Default::default(),
+        images: Default::default(),
     }
 }
 
@@ -67,9 +74,15 @@ pub fn generate_test_proto_complete_state(
                     .collect(),
             }),
             configs: Some(Default::default()),
+            defaults: None,
+            namespaces: None,
+            images: None,
         }),
         workload_states: None,
         agents: None,
+        workload_resources: None,
+        workload_state_history: None,
+        image_states: None,
     }
 }
 
@@ -89,9 +102,14 @@ pub fn generate_test_complete_state(workloads: Vec<WorkloadSpec>) -> crate::obje
                 .map(|v| (v.instance_name.workload_name().to_owned(), v.into()))
                 .collect(),
             configs: HashMap::new(),
+            defaults: Default::default(),
+            namespaces: Default::default(),
+            images: Default::default(),
         },
         workload_states: generate_test_workload_states_map_from_specs(workloads),
         agents,
+        workload_resources: Default::default(),
+        image_states: Default::default(),
     }
 }
 
@@ -137,6 +155,9 @@ pub fn generate_test_state() -> State {
         api_version: API_VERSION.into(),
         workloads: ankaios_workloads,
         configs: HashMap::new(),
+        defaults: Default::default(),
+        namespaces: Default::default(),
+        images: Default::default(),
     }
 }
 
@@ -153,6 +174,9 @@ pub fn generate_test_proto_state() -> ank_base::State {
         api_version: API_VERSION.into(),
         workloads: proto_workloads,
         configs: Some(Default::default()),
+        defaults: None,
+        namespaces: None,
+        images: None,
     }
 }
 
@@ -197,7 +221,26 @@ pub fn generate_test_proto_workload_with_param(
         configs: Some(ConfigMappings{configs: [
             ("ref1".into(), "config_1".into()),
             ("ref2".into(), "config_2".into()),
-        ].into()})
+        ].into()}),
+        hostname: None,
+        extra_hosts: Some(Default::default()),
+        ports: Some(Default::default()),
+        resource_thresholds: None,
+        resources: None,
+        health_check: None,
+        restart_backoff: None,
+        priority: None,
+        volumes: Some(Default::default()),
+        registry_auth: None,
+        replicas: None,
+        networks: Some(Default::default()),
+        env: Some(Default::default()),
+        on_agent_disconnect: None,
+        restart_on: Some(Default::default()),
+        lifecycle_hooks: None,
+        node_selector: Some(Default::default()),
+        state_debounce: None,
+        security_context: None,
     }
 }
 
@@ -217,7 +260,26 @@ pub fn generate_test_proto_workload() -> ank_base::Workload {
         configs: Some(ConfigMappings{configs: [
             ("ref1".into(), "config_1".into()),
             ("ref2".into(), "config_2".into()),
-        ].into()})
+        ].into()}),
+        hostname: None,
+        extra_hosts: Some(Default::default()),
+        ports: Some(Default::default()),
+        resource_thresholds: None,
+        resources: None,
+        health_check: None,
+        restart_backoff: None,
+        priority: None,
+        volumes: Some(Default::default()),
+        registry_auth: None,
+        replicas: None,
+        networks: Some(Default::default()),
+        env: Some(Default::default()),
+        on_agent_disconnect: None,
+        restart_on: Some(Default::default()),
+        lifecycle_hooks: None,
+        node_selector: Some(Default::default()),
+        state_debounce: None,
+        security_context: None,
     }
 }
 
@@ -233,6 +295,7 @@ pub fn generate_test_deleted_workload(
     DeletedWorkload {
         instance_name,
         dependencies: generate_test_delete_dependencies(),
+        grace_period_secs: None,
     }
 }
 
@@ -257,11 +320,11 @@ impl MockAllContextSync {
             mutex_std: std::sync::Mutex::new(()),
         }
     }
-    pub async fn get_lock_async(&self) -> tokio::sync::MutexGuard<()> {
+    pub async fn get_lock_async(&self) -> tokio::sync::MutexGuard<'_, ()> {
         self.mutex_tokio.lock().await
     }
 
-    pub fn get_lock(&self) -> std::sync::MutexGuard<()> {
+    pub fn get_lock(&self) -> std::sync::MutexGuard<'_, ()> {
         match self.mutex_std.lock() {
             Ok(guard) => guard,
             Err(poisoned) => poisoned.into_inner(),
@@ -16,17 +16,19 @@ use crate::{
     commands::{self, RequestContent},
     objects::CompleteState,
 };
+use api::ank_base;
 use async_trait::async_trait;
 use std::fmt;
 use tokio::sync::mpsc::error::SendError;
 
 #[allow(clippy::large_enum_variant)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum ToServer {
     AgentHello(commands::AgentHello),
     AgentLoadStatus(commands::AgentLoadStatus),
     AgentGone(commands::AgentGone),
     Request(commands::Request),
+    Response(ank_base::Response),
     UpdateWorkloadState(commands::UpdateWorkloadState),
     Stop(commands::Stop),
     Goodbye(commands::Goodbye),
@@ -50,7 +52,7 @@ impl fmt::Display for ToServerError {
 // [impl->swdd~to-server-channel~1]
 #[async_trait]
 pub trait ToServerInterface {
-    async fn agent_hello(&self, agent_name: String) -> Result<(), ToServerError>;
+    async fn agent_hello(&self, agent_hello: commands::AgentHello) -> Result<(), ToServerError>;
     async fn agent_load_status(
         &self,
         agent_resource: commands::AgentLoadStatus,
@@ -62,6 +64,19 @@ pub trait ToServerInterface {
         state: CompleteState,
         update_mask: Vec<String>,
     ) -> Result<(), ToServerError>;
+    async fn update_state_dry_run(
+        &self,
+        request_id: String,
+        state: CompleteState,
+        update_mask: Vec<String>,
+    ) -> Result<(), ToServerError>;
+    async fn update_state_with_grace_period(
+        &self,
+        request_id: String,
+        state: CompleteState,
+        update_mask: Vec<String>,
+        grace_period_secs: u32,
+    ) -> Result<(), ToServerError>;
     async fn update_workload_state(
         &self,
         workload_running: Vec<crate::objects::WorkloadState>,
@@ -71,6 +86,38 @@ pub trait ToServerInterface {
         request_id: String,
         request_complete_state: commands::CompleteStateRequest,
     ) -> Result<(), ToServerError>;
+    async fn request_dependency_graph(&self, request_id: String) -> Result<(), ToServerError>;
+    async fn request_events(
+        &self,
+        request_id: String,
+        request_events: commands::EventsRequest,
+    ) -> Result<(), ToServerError>;
+    async fn request_exec(
+        &self,
+        request_id: String,
+        exec_request: commands::ExecRequest,
+    ) -> Result<(), ToServerError>;
+    async fn request_port_forward(
+        &self,
+        request_id: String,
+        port_forward_request: commands::PortForwardRequest,
+    ) -> Result<(), ToServerError>;
+    async fn request_restart_workload(
+        &self,
+        request_id: String,
+        restart_workload_request: commands::RestartWorkloadRequest,
+    ) -> Result<(), ToServerError>;
+    async fn request_pause_workload(
+        &self,
+        request_id: String,
+        pause_workload_request: commands::PauseWorkloadRequest,
+    ) -> Result<(), ToServerError>;
+    async fn request_audit(
+        &self,
+        request_id: String,
+        audit_request: commands::AuditRequest,
+    ) -> Result<(), ToServerError>;
+    async fn response(&self, response: ank_base::Response) -> Result<(), ToServerError>;
     async fn stop(&self) -> Result<(), ToServerError>;
 }
 
@@ -79,10 +126,8 @@ pub type ToServerReceiver = tokio::sync::mpsc::Receiver<ToServer>;
 
 #[async_trait]
 impl ToServerInterface for ToServerSender {
-    async fn agent_hello(&self, agent_name: String) -> Result<(), ToServerError> {
-        Ok(self
-            .send(ToServer::AgentHello(commands::AgentHello { agent_name }))
-            .await?)
+    async fn agent_hello(&self, agent_hello: commands::AgentHello) -> Result<(), ToServerError> {
+        Ok(self.send(ToServer::AgentHello(agent_hello)).await?)
     }
 
     async fn agent_load_status(
@@ -110,7 +155,55 @@ impl ToServerInterface for ToServerSender {
             .send(ToServer::Request(commands::Request {
                 request_id,
                 request_content: commands::RequestContent::UpdateStateRequest(Box::new(
-                    commands::UpdateStateRequest { state, update_mask },
+                    commands::UpdateStateRequest {
+                        state,
+                        update_mask,
+                        dry_run: false,
+                        grace_period_secs: None,
+                    },
+                )),
+            }))
+            .await?)
+    }
+
+    async fn update_state_dry_run(
+        &self,
+        request_id: String,
+        state: CompleteState,
+        update_mask: Vec<String>,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: commands::RequestContent::UpdateStateRequest(Box::new(
+                    commands::UpdateStateRequest {
+                        state,
+                        update_mask,
+                        dry_run: true,
+                        grace_period_secs: None,
+                    },
+                )),
+            }))
+            .await?)
+    }
+
+    async fn update_state_with_grace_period(
+        &self,
+        request_id: String,
+        state: CompleteState,
+        update_mask: Vec<String>,
+        grace_period_secs: u32,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: commands::RequestContent::UpdateStateRequest(Box::new(
+                    commands::UpdateStateRequest {
+                        state,
+                        update_mask,
+                        dry_run: false,
+                        grace_period_secs: Some(grace_period_secs),
+                    },
                 )),
             }))
             .await?)
@@ -137,15 +230,104 @@ impl ToServerInterface for ToServerSender {
         Ok(self
             .send(ToServer::Request(commands::Request {
                 request_id,
-                request_content: RequestContent::CompleteStateRequest(
-                    commands::CompleteStateRequest {
-                        field_mask: request_complete_state.field_mask,
-                    },
+                request_content: RequestContent::CompleteStateRequest(request_complete_state),
+            }))
+            .await?)
+    }
+
+    async fn request_dependency_graph(&self, request_id: String) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: RequestContent::DependencyGraphRequest(
+                    commands::DependencyGraphRequest {},
                 ),
             }))
             .await?)
     }
 
+    async fn request_events(
+        &self,
+        request_id: String,
+        request_events: commands::EventsRequest,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: RequestContent::EventsRequest(request_events),
+            }))
+            .await?)
+    }
+
+    async fn request_exec(
+        &self,
+        request_id: String,
+        exec_request: commands::ExecRequest,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: RequestContent::ExecRequest(exec_request),
+            }))
+            .await?)
+    }
+
+    async fn request_port_forward(
+        &self,
+        request_id: String,
+        port_forward_request: commands::PortForwardRequest,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: RequestContent::PortForwardRequest(port_forward_request),
+            }))
+            .await?)
+    }
+
+    async fn request_restart_workload(
+        &self,
+        request_id: String,
+        restart_workload_request: commands::RestartWorkloadRequest,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: RequestContent::RestartWorkloadRequest(restart_workload_request),
+            }))
+            .await?)
+    }
+
+    async fn request_pause_workload(
+        &self,
+        request_id: String,
+        pause_workload_request: commands::PauseWorkloadRequest,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: RequestContent::PauseWorkloadRequest(pause_workload_request),
+            }))
+            .await?)
+    }
+
+    async fn request_audit(
+        &self,
+        request_id: String,
+        audit_request: commands::AuditRequest,
+    ) -> Result<(), ToServerError> {
+        Ok(self
+            .send(ToServer::Request(commands::Request {
+                request_id,
+                request_content: RequestContent::AuditRequest(audit_request),
+            }))
+            .await?)
+    }
+
+    async fn response(&self, response: ank_base::Response) -> Result<(), ToServerError> {
+        Ok(self.send(ToServer::Response(response)).await?)
+    }
+
     async fn stop(&self) -> Result<(), ToServerError> {
         Ok(self.send(ToServer::Stop(commands::Stop {})).await?)
     }
@@ -172,6 +354,7 @@ mod tests {
     };
 
     use super::{ToServerReceiver, ToServerSender};
+    use std::collections::HashMap;
 
     const TEST_CHANNEL_CAPA: usize = 5;
     const WORKLOAD_NAME: &str = "X";
@@ -187,14 +370,15 @@ mod tests {
         let (tx, mut rx): (ToServerSender, ToServerReceiver) =
             tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
 
-        assert!(tx.agent_hello(AGENT_NAME.to_string()).await.is_ok());
+        let agent_hello = commands::AgentHello {
+            agent_name: AGENT_NAME.to_string(),
+            labels: HashMap::from([("zone".to_string(), "cockpit".to_string())]),
+            version: "0.5.0".to_string(),
+            runtimes: vec!["podman".to_string()],
+        };
+        assert!(tx.agent_hello(agent_hello.clone()).await.is_ok());
 
-        assert_eq!(
-            rx.recv().await.unwrap(),
-            ToServer::AgentHello(commands::AgentHello {
-                agent_name: AGENT_NAME.to_string()
-            })
-        )
+        assert_eq!(rx.recv().await.unwrap(), ToServer::AgentHello(agent_hello))
     }
 
     // [utest->swdd~to-server-channel~1]
@@ -208,6 +392,11 @@ mod tests {
                 agent_name: AGENT_NAME.to_string(),
                 cpu_usage: CPU_USAGE.clone(),
                 free_memory: FREE_MEMORY.clone(),
+                workload_resources: vec![],
+                disk_usage: Default::default(),
+                network_usage: Default::default(),
+                temperature: Default::default(),
+                image_states: Default::default(),
             })
             .await
             .is_ok());
@@ -218,6 +407,11 @@ mod tests {
                 agent_name: AGENT_NAME.to_string(),
                 cpu_usage: CPU_USAGE.clone(),
                 free_memory: FREE_MEMORY.clone(),
+                workload_resources: vec![],
+                disk_usage: Default::default(),
+                network_usage: Default::default(),
+                temperature: Default::default(),
+                image_states: Default::default(),
             }))
         )
     }
@@ -262,7 +456,76 @@ mod tests {
                 request_content: commands::RequestContent::UpdateStateRequest(Box::new(
                     commands::UpdateStateRequest {
                         state: complete_state,
-                        update_mask: vec![FIELD_MASK.to_string()]
+                        update_mask: vec![FIELD_MASK.to_string()],
+                        dry_run: false,
+                        grace_period_secs: None,
+                    },
+                )),
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_update_state_dry_run() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let workload1 = generate_test_workload_spec();
+        let complete_state = generate_test_complete_state(vec![workload1]);
+        assert!(tx
+            .update_state_dry_run(
+                REQUEST_ID.to_string(),
+                complete_state.clone(),
+                vec![FIELD_MASK.to_string()]
+            )
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: commands::RequestContent::UpdateStateRequest(Box::new(
+                    commands::UpdateStateRequest {
+                        state: complete_state,
+                        update_mask: vec![FIELD_MASK.to_string()],
+                        dry_run: true,
+                        grace_period_secs: None,
+                    },
+                )),
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_update_state_with_grace_period() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let workload1 = generate_test_workload_spec();
+        let complete_state = generate_test_complete_state(vec![workload1]);
+        assert!(tx
+            .update_state_with_grace_period(
+                REQUEST_ID.to_string(),
+                complete_state.clone(),
+                vec![FIELD_MASK.to_string()],
+                30
+            )
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: commands::RequestContent::UpdateStateRequest(Box::new(
+                    commands::UpdateStateRequest {
+                        state: complete_state,
+                        update_mask: vec![FIELD_MASK.to_string()],
+                        dry_run: false,
+                        grace_period_secs: Some(30),
                     },
                 )),
             })
@@ -297,6 +560,7 @@ mod tests {
 
         let complete_state_request = commands::CompleteStateRequest {
             field_mask: vec![FIELD_MASK.to_string()],
+            selector: vec![],
         };
         let request_content = RequestContent::CompleteStateRequest(complete_state_request.clone());
         assert!(tx
@@ -312,4 +576,194 @@ mod tests {
             })
         )
     }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_request_dependency_graph() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        assert!(tx
+            .request_dependency_graph(REQUEST_ID.to_string())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: RequestContent::DependencyGraphRequest(
+                    commands::DependencyGraphRequest {}
+                )
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_request_events() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let events_request = commands::EventsRequest {
+            after_sequence_number: 42,
+        };
+
+        assert!(tx
+            .request_events(REQUEST_ID.to_string(), events_request.clone())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: RequestContent::EventsRequest(events_request)
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_request_exec() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let exec_request = commands::ExecRequest::Start(commands::ExecStart {
+            workload_name: "nginx".to_string(),
+            command: vec!["ls".to_string()],
+        });
+
+        assert!(tx
+            .request_exec(REQUEST_ID.to_string(), exec_request.clone())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: RequestContent::ExecRequest(exec_request)
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_request_port_forward() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let port_forward_request =
+            commands::PortForwardRequest::Start(commands::PortForwardStart {
+                workload_name: "nginx".to_string(),
+                port: 80,
+            });
+
+        assert!(tx
+            .request_port_forward(REQUEST_ID.to_string(), port_forward_request.clone())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: RequestContent::PortForwardRequest(port_forward_request)
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_request_restart_workload() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let restart_workload_request = commands::RestartWorkloadRequest {
+            workload_name: "nginx".to_string(),
+        };
+
+        assert!(tx
+            .request_restart_workload(REQUEST_ID.to_string(), restart_workload_request.clone())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: RequestContent::RestartWorkloadRequest(restart_workload_request)
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_request_pause_workload() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let pause_workload_request = commands::PauseWorkloadRequest {
+            workload_name: "nginx".to_string(),
+            resume: false,
+        };
+
+        assert!(tx
+            .request_pause_workload(REQUEST_ID.to_string(), pause_workload_request.clone())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: RequestContent::PauseWorkloadRequest(pause_workload_request)
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_request_audit() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let audit_request = commands::AuditRequest {
+            after_sequence_number: 42,
+        };
+
+        assert!(tx
+            .request_audit(REQUEST_ID.to_string(), audit_request.clone())
+            .await
+            .is_ok());
+
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            ToServer::Request(commands::Request {
+                request_id: REQUEST_ID.to_string(),
+                request_content: RequestContent::AuditRequest(audit_request)
+            })
+        )
+    }
+
+    // [utest->swdd~to-server-channel~1]
+    #[tokio::test]
+    async fn utest_to_server_send_response() {
+        let (tx, mut rx): (ToServerSender, ToServerReceiver) =
+            tokio::sync::mpsc::channel(TEST_CHANNEL_CAPA);
+
+        let response = api::ank_base::Response {
+            request_id: REQUEST_ID.to_string(),
+            response_content: Some(api::ank_base::response::ResponseContent::ExecOutput(
+                api::ank_base::ExecOutput {
+                    exec_output_enum: Some(api::ank_base::exec_output::ExecOutputEnum::ExitCode(0)),
+                },
+            )),
+        };
+
+        assert!(tx.response(response.clone()).await.is_ok());
+
+        assert_eq!(rx.recv().await.unwrap(), ToServer::Response(response))
+    }
 }
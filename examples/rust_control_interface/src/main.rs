@@ -82,6 +82,25 @@ fn create_request_to_add_new_workload() -> ToAnkaios {
                 }),
                 configs: None,
                 control_interface_access: None,
+                hostname: None,
+                extra_hosts: None,
+                ports: None,
+                resource_thresholds: None,
+                resources: None,
+                health_check: None,
+                restart_backoff: None,
+                priority: None,
+                volumes: None,
+                registry_auth: None,
+                replicas: None,
+                networks: None,
+                env: None,
+                on_agent_disconnect: None,
+                restart_on: None,
+                lifecycle_hooks: None,
+                node_selector: None,
+                state_debounce: None,
+                security_context: None,
             },
         )]),
     });
@@ -114,6 +133,7 @@ fn create_request_for_complete_state() -> ToAnkaios {
             request_id: REQUEST_ID.to_string(),
             request_content: Some(RequestContent::CompleteStateRequest(CompleteStateRequest {
                 field_mask: vec![String::from("workloadStates.agent_A.dynamic_nginx")],
+                selector: vec![],
             })),
         })),
     }
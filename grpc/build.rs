@@ -15,6 +15,7 @@
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     tonic_build::configure()
         .build_server(true)
+        .boxed("Replication.ReplicationEnum.desiredState")
         .compile(
             &["proto/grpc_api.proto"],
             &["proto", "../api/proto"],
@@ -0,0 +1,90 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use regex::Regex;
+
+// [impl->swdd~grpc-agent-connection-checks-agent-allow-list~1]
+#[derive(Debug, Clone, Default)]
+pub struct AgentAllowList {
+    patterns: Vec<Regex>,
+}
+
+impl AgentAllowList {
+    /// Creates an allow list from agent names which can contain the wildcard '*'.
+    /// An empty list allows all agents.
+    pub fn new(agent_name_patterns: &[String]) -> Self {
+        Self {
+            patterns: agent_name_patterns
+                .iter()
+                .map(|pattern| {
+                    let pattern = regex::escape(pattern).replace(r"\*", ".*");
+                    Regex::new(&format!("^{pattern}$")).unwrap_or_else(|err| {
+                        unreachable!("Escaped agent name pattern is not a valid regex: '{err}'")
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    pub fn is_allowed(&self, agent_name: &str) -> bool {
+        self.patterns.is_empty()
+            || self
+                .patterns
+                .iter()
+                .any(|pattern| pattern.is_match(agent_name))
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::AgentAllowList;
+
+    // [utest->swdd~grpc-agent-connection-checks-agent-allow-list~1]
+    #[test]
+    fn utest_agent_allow_list_empty_allows_all_agents() {
+        let allow_list = AgentAllowList::new(&[]);
+
+        assert!(allow_list.is_allowed("agent_A"));
+    }
+
+    // [utest->swdd~grpc-agent-connection-checks-agent-allow-list~1]
+    #[test]
+    fn utest_agent_allow_list_matches_names() {
+        let allow_list = AgentAllowList::new(&["agent_A".to_string()]);
+
+        assert!(allow_list.is_allowed("agent_A"));
+        assert!(!allow_list.is_allowed("agent_B"));
+        assert!(!allow_list.is_allowed("agent_A_2"));
+    }
+
+    // [utest->swdd~grpc-agent-connection-checks-agent-allow-list~1]
+    #[test]
+    fn utest_agent_allow_list_matches_patterns() {
+        let allow_list = AgentAllowList::new(&["vehicle_*".to_string(), "infra".to_string()]);
+
+        assert!(allow_list.is_allowed("vehicle_1"));
+        assert!(allow_list.is_allowed("vehicle_"));
+        assert!(allow_list.is_allowed("infra"));
+        assert!(!allow_list.is_allowed("my_vehicle_1"));
+        assert!(!allow_list.is_allowed("infra_2"));
+    }
+}
@@ -12,20 +12,67 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
 use common::std_extensions::IllegalStateResult;
-use tokio::sync::mpsc::Sender;
+use tokio::sync::mpsc::{Sender, WeakSender};
 use tonic::Status;
 
 use crate::grpc_api::FromServer;
 
 type ShareableHashMap<K, V> = Arc<Mutex<HashMap<K, V>>>;
 
+// [impl->swdd~grpc-agent-connection-handles-duplicate-agent-names~1]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateAgentPolicy {
+    /// The new connection replaces the existing one.
+    #[default]
+    Replace,
+    /// The new connection is rejected, the existing one is kept.
+    Reject,
+    /// Both connections are closed and the agent name is refused until the server restarts.
+    Quarantine,
+}
+
+impl FromStr for DuplicateAgentPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "replace" => Ok(Self::Replace),
+            "reject" => Ok(Self::Reject),
+            "quarantine" => Ok(Self::Quarantine),
+            _ => Err(format!(
+                "Unknown duplicate agent policy '{value}', expected one of 'replace', 'reject' or 'quarantine'"
+            )),
+        }
+    }
+}
+
+impl Display for DuplicateAgentPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Replace => write!(f, "replace"),
+            Self::Reject => write!(f, "reject"),
+            Self::Quarantine => write!(f, "quarantine"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AgentRegistrationError {
+    AlreadyConnected,
+    // The flag tells if the existing connection of the agent has been closed
+    Quarantined(bool),
+}
+
 #[derive(Debug, Clone)]
 pub struct AgentSendersMap {
     agent_senders: ShareableHashMap<String, Sender<Result<FromServer, Status>>>,
+    quarantined_agents: Arc<Mutex<HashSet<String>>>,
 }
 
 // Beside improving readability by hiding the lock steps, this trait helps improve the
@@ -38,6 +85,7 @@ impl AgentSendersMap {
     pub fn new() -> Self {
         AgentSendersMap {
             agent_senders: Arc::new(Mutex::new(HashMap::new())),
+            quarantined_agents: Arc::new(Mutex::new(HashSet::new())),
         }
     }
 
@@ -66,6 +114,46 @@ impl AgentSendersMap {
             );
     }
 
+    // [impl->swdd~grpc-agent-connection-handles-duplicate-agent-names~1]
+    pub fn register(
+        &self,
+        name: &str,
+        sender: Sender<Result<FromServer, Status>>,
+        policy: DuplicateAgentPolicy,
+    ) -> Result<(), AgentRegistrationError> {
+        let mut agent_senders = self.agent_senders.lock().unwrap_or_illegal_state();
+        let mut quarantined_agents = self.quarantined_agents.lock().unwrap_or_illegal_state();
+
+        if quarantined_agents.contains(name) {
+            return Err(AgentRegistrationError::Quarantined(false));
+        }
+
+        let is_connected = agent_senders
+            .get(name)
+            .is_some_and(|existing_sender| !existing_sender.is_closed());
+        if is_connected {
+            match policy {
+                DuplicateAgentPolicy::Replace => {
+                    log::warn!(
+                        "Received a NEW hello from agent {name}. Replacing sender for this agent."
+                    );
+                }
+                DuplicateAgentPolicy::Reject => {
+                    return Err(AgentRegistrationError::AlreadyConnected);
+                }
+                DuplicateAgentPolicy::Quarantine => {
+                    // Dropping the existing sender closes the connection to the connected agent
+                    agent_senders.remove(name);
+                    quarantined_agents.insert(name.to_owned());
+                    return Err(AgentRegistrationError::Quarantined(true));
+                }
+            }
+        }
+
+        agent_senders.insert(name.to_owned(), sender);
+        Ok(())
+    }
+
     pub fn get_all_agent_names(&self) -> Vec<String> {
         self.agent_senders
             .lock()
@@ -81,6 +169,24 @@ impl AgentSendersMap {
             .unwrap_or_illegal_state()
             .remove(name);
     }
+
+    // Removes the sender of the agent only if it was not replaced in the meantime
+    pub fn remove_sender(
+        &self,
+        name: &str,
+        sender: &WeakSender<Result<FromServer, Status>>,
+    ) -> bool {
+        let mut agent_senders = self.agent_senders.lock().unwrap_or_illegal_state();
+        let is_same_sender = agent_senders.get(name).is_some_and(|existing_sender| {
+            sender
+                .upgrade()
+                .is_some_and(|sender| sender.same_channel(existing_sender))
+        });
+        if is_same_sender {
+            agent_senders.remove(name);
+        }
+        is_same_sender
+    }
 }
 
 impl Default for AgentSendersMap {
@@ -88,3 +194,135 @@ impl Default for AgentSendersMap {
         AgentSendersMap::new()
     }
 }
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{AgentRegistrationError, AgentSendersMap, DuplicateAgentPolicy};
+
+    const AGENT_NAME: &str = "agent_A";
+
+    // [utest->swdd~grpc-agent-connection-handles-duplicate-agent-names~1]
+    #[test]
+    fn utest_register_replaces_connected_agent() {
+        let agent_senders = AgentSendersMap::new();
+        let (old_sender, _old_receiver) = tokio::sync::mpsc::channel(1);
+        let (new_sender, _new_receiver) = tokio::sync::mpsc::channel(1);
+
+        assert!(agent_senders
+            .register(AGENT_NAME, old_sender, DuplicateAgentPolicy::Replace)
+            .is_ok());
+        assert!(agent_senders
+            .register(
+                AGENT_NAME,
+                new_sender.clone(),
+                DuplicateAgentPolicy::Replace
+            )
+            .is_ok());
+
+        assert!(agent_senders
+            .get(AGENT_NAME)
+            .is_some_and(|sender| sender.same_channel(&new_sender)));
+    }
+
+    // [utest->swdd~grpc-agent-connection-handles-duplicate-agent-names~1]
+    #[test]
+    fn utest_register_rejects_connected_agent() {
+        let agent_senders = AgentSendersMap::new();
+        let (old_sender, _old_receiver) = tokio::sync::mpsc::channel(1);
+        let (new_sender, _new_receiver) = tokio::sync::mpsc::channel(1);
+
+        assert!(agent_senders
+            .register(AGENT_NAME, old_sender.clone(), DuplicateAgentPolicy::Reject)
+            .is_ok());
+        assert_eq!(
+            agent_senders.register(AGENT_NAME, new_sender, DuplicateAgentPolicy::Reject),
+            Err(AgentRegistrationError::AlreadyConnected)
+        );
+
+        assert!(agent_senders
+            .get(AGENT_NAME)
+            .is_some_and(|sender| sender.same_channel(&old_sender)));
+    }
+
+    // [utest->swdd~grpc-agent-connection-handles-duplicate-agent-names~1]
+    #[test]
+    fn utest_register_accepts_reconnecting_agent_with_closed_connection() {
+        let agent_senders = AgentSendersMap::new();
+        let (old_sender, old_receiver) = tokio::sync::mpsc::channel(1);
+        let (new_sender, _new_receiver) = tokio::sync::mpsc::channel(1);
+
+        assert!(agent_senders
+            .register(AGENT_NAME, old_sender, DuplicateAgentPolicy::Reject)
+            .is_ok());
+        drop(old_receiver);
+
+        assert!(agent_senders
+            .register(AGENT_NAME, new_sender, DuplicateAgentPolicy::Reject)
+            .is_ok());
+    }
+
+    // [utest->swdd~grpc-agent-connection-handles-duplicate-agent-names~1]
+    #[test]
+    fn utest_register_quarantines_connected_agent() {
+        let agent_senders = AgentSendersMap::new();
+        let (old_sender, _old_receiver) = tokio::sync::mpsc::channel(1);
+        let (new_sender, _new_receiver) = tokio::sync::mpsc::channel(1);
+
+        assert!(agent_senders
+            .register(
+                AGENT_NAME,
+                old_sender.clone(),
+                DuplicateAgentPolicy::Quarantine
+            )
+            .is_ok());
+        assert_eq!(
+            agent_senders.register(
+                AGENT_NAME,
+                new_sender.clone(),
+                DuplicateAgentPolicy::Quarantine
+            ),
+            Err(AgentRegistrationError::Quarantined(true))
+        );
+        assert!(agent_senders.get(AGENT_NAME).is_none());
+
+        assert_eq!(
+            agent_senders.register(AGENT_NAME, new_sender, DuplicateAgentPolicy::Quarantine),
+            Err(AgentRegistrationError::Quarantined(false))
+        );
+    }
+
+    #[test]
+    fn utest_remove_sender_keeps_replaced_sender() {
+        let agent_senders = AgentSendersMap::new();
+        let (old_sender, _old_receiver) = tokio::sync::mpsc::channel(1);
+        let (new_sender, _new_receiver) = tokio::sync::mpsc::channel(1);
+        let old_weak_sender = old_sender.downgrade();
+
+        agent_senders.insert(AGENT_NAME, old_sender);
+        agent_senders.insert(AGENT_NAME, new_sender.clone());
+
+        assert!(!agent_senders.remove_sender(AGENT_NAME, &old_weak_sender));
+        assert!(agent_senders.remove_sender(AGENT_NAME, &new_sender.downgrade()));
+        assert!(agent_senders.get(AGENT_NAME).is_none());
+    }
+
+    #[test]
+    fn utest_duplicate_agent_policy_from_str() {
+        for policy in [
+            DuplicateAgentPolicy::Replace,
+            DuplicateAgentPolicy::Reject,
+            DuplicateAgentPolicy::Quarantine,
+        ] {
+            assert_eq!(policy.to_string().parse(), Ok(policy));
+        }
+        assert!("unknown".parse::<DuplicateAgentPolicy>().is_err());
+    }
+}
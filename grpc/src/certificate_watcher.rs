@@ -0,0 +1,172 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::security::TLSConfig;
+
+// [impl->swdd~grpc-watches-certificate-files~1]
+const CERTIFICATE_CHECK_INTERVAL_SECS: u64 = 5;
+
+type FileContents = Vec<Option<Vec<u8>>>;
+
+/// Polls the PEM files of a [`TLSConfig`] and reports when their content changed.
+#[derive(Debug)]
+pub struct CertificateWatcher {
+    paths: Vec<PathBuf>,
+    contents: FileContents,
+    pending_contents: Option<FileContents>,
+    check_interval: Duration,
+}
+
+impl CertificateWatcher {
+    pub fn new(tls_config: &TLSConfig) -> Self {
+        let paths: Vec<PathBuf> = [
+            &tls_config.path_to_ca_pem,
+            &tls_config.path_to_crt_pem,
+            &tls_config.path_to_key_pem,
+        ]
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+        let contents = read_files(&paths);
+
+        CertificateWatcher {
+            paths,
+            contents,
+            pending_contents: None,
+            check_interval: Duration::from_secs(CERTIFICATE_CHECK_INTERVAL_SECS),
+        }
+    }
+
+    /// Returns as soon as the content of a watched file changed and stayed the same for one check interval.
+    /// Waiting for the content to settle avoids reacting to a certificate that was replaced before its key.
+    // [impl->swdd~grpc-watches-certificate-files~1]
+    pub async fn changed(&mut self) {
+        loop {
+            tokio::time::sleep(self.check_interval).await;
+            let current_contents = read_files(&self.paths);
+
+            if current_contents == self.contents {
+                self.pending_contents = None;
+            } else if self.pending_contents.as_ref() == Some(&current_contents) {
+                self.contents = current_contents;
+                self.pending_contents = None;
+                return;
+            } else {
+                self.pending_contents = Some(current_contents);
+            }
+        }
+    }
+}
+
+/// Waits for a change of the certificate files if a watcher is given, otherwise forever.
+pub async fn certificates_changed(certificate_watcher: Option<&mut CertificateWatcher>) {
+    match certificate_watcher {
+        Some(certificate_watcher) => certificate_watcher.changed().await,
+        None => std::future::pending().await,
+    }
+}
+
+fn read_files(paths: &[PathBuf]) -> FileContents {
+    paths.iter().map(|path| std::fs::read(path).ok()).collect()
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tempfile::TempDir;
+
+    use super::CertificateWatcher;
+    use crate::security::TLSConfig;
+
+    const CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+    fn create_certificate_watcher(dir: &TempDir) -> CertificateWatcher {
+        for file in ["ca.pem", "crt.pem", "key.pem"] {
+            std::fs::write(dir.path().join(file), "initial").unwrap();
+        }
+        let path = |file: &str| dir.path().join(file).to_string_lossy().to_string();
+        let mut certificate_watcher = CertificateWatcher::new(&TLSConfig {
+            path_to_ca_pem: path("ca.pem"),
+            path_to_crt_pem: path("crt.pem"),
+            path_to_key_pem: path("key.pem"),
+        });
+        certificate_watcher.check_interval = CHECK_INTERVAL;
+        certificate_watcher
+    }
+
+    // [utest->swdd~grpc-watches-certificate-files~1]
+    #[tokio::test]
+    async fn utest_certificate_watcher_reports_changed_files() {
+        let dir = TempDir::new().unwrap();
+        let mut certificate_watcher = create_certificate_watcher(&dir);
+
+        std::fs::write(dir.path().join("key.pem"), "rotated").unwrap();
+
+        assert!(
+            tokio::time::timeout(Duration::from_secs(1), certificate_watcher.changed())
+                .await
+                .is_ok()
+        );
+    }
+
+    // [utest->swdd~grpc-watches-certificate-files~1]
+    #[tokio::test]
+    async fn utest_certificate_watcher_ignores_unchanged_files() {
+        let dir = TempDir::new().unwrap();
+        let mut certificate_watcher = create_certificate_watcher(&dir);
+
+        std::fs::write(dir.path().join("crt.pem"), "initial").unwrap();
+
+        assert!(
+            tokio::time::timeout(CHECK_INTERVAL * 10, certificate_watcher.changed())
+                .await
+                .is_err()
+        );
+    }
+
+    // [utest->swdd~grpc-watches-certificate-files~1]
+    #[tokio::test]
+    async fn utest_certificate_watcher_waits_for_settled_contents() {
+        let dir = TempDir::new().unwrap();
+        let mut certificate_watcher = create_certificate_watcher(&dir);
+        certificate_watcher.pending_contents = Some(vec![None, None, None]);
+
+        std::fs::write(dir.path().join("crt.pem"), "rotated").unwrap();
+        tokio::time::timeout(Duration::from_secs(1), certificate_watcher.changed())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            certificate_watcher.contents,
+            vec![
+                Some(b"initial".to_vec()),
+                Some(b"rotated".to_vec()),
+                Some(b"initial".to_vec())
+            ]
+        );
+        assert_eq!(certificate_watcher.pending_contents, None);
+    }
+}
@@ -12,8 +12,11 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
+use crate::certificate_watcher::{certificates_changed, CertificateWatcher};
+use crate::connection_config::{with_message_config, ConnectionConfig, ReconnectBackoff};
 use crate::from_server_proxy::GRPCFromServerStreaming;
 use crate::grpc_api::{
     self, agent_connection_client::AgentConnectionClient,
@@ -21,26 +24,30 @@ use crate::grpc_api::{
 };
 use crate::grpc_middleware_error::GrpcMiddlewareError;
 use crate::security::{read_pem_file, TLSConfig};
-use crate::to_server_proxy;
 use crate::{from_server_proxy, CommanderHello};
+use crate::{proxy, to_server_proxy};
 
 use common::communications_client::CommunicationsClient;
 use common::communications_error::CommunicationMiddlewareError;
-use common::from_server_interface::FromServerSender;
+use common::communications_server::UNIX_SOCKET_SCHEME;
+use common::from_server_interface::{FromServerInterface, FromServerSender};
 
 use common::std_extensions::IllegalStateResult;
 use common::to_server_interface::ToServerReceiver;
 
 use regex::Regex;
+use tokio::net::UnixStream;
 use tokio::select;
 use tokio::sync::mpsc::Receiver;
 use tokio_stream::wrappers::ReceiverStream;
 
 use async_trait::async_trait;
 
-use tonic::transport::{Certificate, Channel, ClientTlsConfig, Identity};
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity, Uri};
+use tower::service_fn;
 
-const RECONNECT_TIMEOUT_SECONDS: u64 = 1;
+// The host is required by tonic, but ignored when connecting to a unix domain socket.
+const UNIX_SOCKET_PLACEHOLDER_HOST: &str = "ank-server";
 
 enum ConnectionType {
     Agent,
@@ -52,6 +59,11 @@ pub struct GRPCCommunicationsClient {
     server_address: String,
     connection_type: ConnectionType,
     tls_config: Option<TLSConfig>,
+    connection_config: ConnectionConfig,
+    agent_labels: HashMap<String, String>,
+    agent_runtimes: Vec<String>,
+    proxy_url: Option<String>,
+    ca_bundle: Option<String>,
 }
 
 fn get_server_url(server_address: &str, tls_config: &Option<TLSConfig>) -> String {
@@ -63,7 +75,8 @@ fn get_server_url(server_address: &str, tls_config: &Option<TLSConfig>) -> Strin
 }
 
 fn verify_address_format(server_address: &String) -> Result<(), CommunicationMiddlewareError> {
-    let re = Regex::new(r"^https?:\/\/.+").unwrap_or_illegal_state();
+    // [impl->swdd~grpc-client-connects-to-unix-domain-socket~1]
+    let re = Regex::new(r"^(https?:\/\/.+|unix:\/\/\/.+)").unwrap_or_illegal_state();
     if !re.is_match(server_address) {
         return Err(CommunicationMiddlewareError(format!(
             "Wrong server address format: '{}'.",
@@ -86,6 +99,11 @@ impl GRPCCommunicationsClient {
             server_address: get_server_url(&server_address, &tls_config),
             connection_type: ConnectionType::Agent,
             tls_config,
+            connection_config: ConnectionConfig::default(),
+            agent_labels: HashMap::new(),
+            agent_runtimes: Vec::new(),
+            proxy_url: None,
+            ca_bundle: None,
         })
     }
 
@@ -101,8 +119,48 @@ impl GRPCCommunicationsClient {
             server_address: get_server_url(&server_address, &tls_config),
             connection_type: ConnectionType::Cli,
             tls_config,
+            connection_config: ConnectionConfig::default(),
+            agent_labels: HashMap::new(),
+            agent_runtimes: Vec::new(),
+            proxy_url: None,
+            ca_bundle: None,
         })
     }
+
+    // [impl->swdd~grpc-supports-connection-tuning~1]
+    pub fn with_connection_config(mut self, connection_config: ConnectionConfig) -> Self {
+        self.connection_config = connection_config;
+        self
+    }
+
+    // [impl->swdd~agent-reports-labels~1]
+    pub fn with_agent_labels(mut self, agent_labels: HashMap<String, String>) -> Self {
+        self.agent_labels = agent_labels;
+        self
+    }
+
+    // [impl->swdd~agent-reports-version-and-runtimes~1]
+    pub fn with_agent_runtimes(mut self, agent_runtimes: Vec<String>) -> Self {
+        self.agent_runtimes = agent_runtimes;
+        self
+    }
+
+    /// Connects through the given HTTP proxy or, if not given, through the proxy of the
+    /// `HTTPS_PROXY` or `HTTP_PROXY` environment variables
+    // [impl->swdd~grpc-client-connects-through-http-proxy~2]
+    pub fn with_proxy(mut self, proxy_url: Option<String>) -> Self {
+        self.proxy_url = proxy::select_proxy(&self.server_address, proxy_url.as_deref(), |key| {
+            std::env::var(key).ok()
+        });
+        self
+    }
+
+    /// Trusts the certificates of the given PEM file in addition to the CA certificate
+    // [impl->swdd~grpc-client-trusts-additional-ca-bundle~1]
+    pub fn with_ca_bundle(mut self, path_to_ca_bundle: Option<String>) -> Self {
+        self.ca_bundle = path_to_ca_bundle;
+        self
+    }
 }
 
 #[async_trait]
@@ -114,7 +172,9 @@ impl CommunicationsClient for GRPCCommunicationsClient {
     ) -> Result<(), CommunicationMiddlewareError> {
         log::debug!("gRPC Communication Client starts.");
 
-        // [impl->swdd~grpc-client-retries-connection~2]
+        let mut reconnect_backoff = ReconnectBackoff::new(&self.connection_config);
+
+        // [impl->swdd~grpc-client-retries-connection~3]
         loop {
             let result = self.run_internal(&mut server_rx, &agent_tx).await;
 
@@ -126,12 +186,30 @@ impl CommunicationsClient for GRPCCommunicationsClient {
                 )));
             }
 
+            // [impl->swdd~grpc-client-stops-connection-on-stop-request~1]
+            if server_rx.is_closed() {
+                log::debug!("The connection to the server was stopped on request.");
+                break;
+            }
+
             match self.connection_type {
                 ConnectionType::Agent => {
                     log::warn!("Connection to server interrupted: '{:?}'", result);
 
-                    use tokio::time::{sleep, Duration};
-                    sleep(Duration::from_secs(RECONNECT_TIMEOUT_SECONDS)).await;
+                    // [impl->swdd~grpc-client-retries-connection~3]
+                    // only failed connection attempts increase the delay, a lost connection is retried with the initial delay
+                    if !matches!(result, Err(GrpcMiddlewareError::ServerNotAvailable(_))) {
+                        reconnect_backoff.reset();
+
+                        // [impl->swdd~grpc-client-notifies-agent-about-lost-connection~1]
+                        agent_tx
+                            .server_connection_lost()
+                            .await
+                            .unwrap_or_else(|err| {
+                                log::warn!("Could not notify about the lost connection: '{}'", err)
+                            });
+                    }
+                    tokio::time::sleep(reconnect_backoff.next_delay()).await;
                 }
                 ConnectionType::Cli => {
                     match result {
@@ -187,7 +265,11 @@ impl GRPCCommunicationsClient {
             ConnectionType::Agent => {
                 grpc_tx
                     .send(grpc_api::ToServer {
-                        to_server_enum: Some(ToServerEnum::AgentHello(AgentHello::new(&self.name))),
+                        to_server_enum: Some(ToServerEnum::AgentHello(AgentHello {
+                            labels: self.agent_labels.clone(),
+                            runtimes: self.agent_runtimes.clone(),
+                            ..AgentHello::new(&self.name)
+                        })),
                     })
                     .await?;
             }
@@ -200,6 +282,12 @@ impl GRPCCommunicationsClient {
             }
         }
 
+        // [impl->swdd~grpc-client-reconnects-on-changed-certificates~1]
+        let mut certificate_watcher = match (&self.connection_type, &self.tls_config) {
+            (ConnectionType::Agent, Some(tls_config)) => Some(CertificateWatcher::new(tls_config)),
+            _ => None,
+        };
+
         // [impl->swdd~grpc-client-connects-with-agent-hello~1]
         let mut grpc_to_server_streaming =
             GRPCFromServerStreaming::new(self.connect_to_server(grpc_rx).await?);
@@ -217,11 +305,72 @@ impl GRPCCommunicationsClient {
         select! {
             _ = forward_exec_from_proto_task => {log::debug!("Forward from server message from proto to Ankaios task completed");}
             _ = forward_to_server_from_ank_task => {log::debug!("Forward from server message from Ankaios to proto task completed");}
+            // [impl->swdd~grpc-client-reconnects-on-changed-certificates~1]
+            _ = certificates_changed(certificate_watcher.as_mut()) => {log::info!("The certificates changed, reconnecting to the server with the new certificates.");}
         };
 
         Ok(())
     }
 
+    fn endpoint(&self) -> Result<Endpoint, GrpcMiddlewareError> {
+        let uri = match (
+            self.server_address.starts_with(UNIX_SOCKET_SCHEME),
+            &self.tls_config,
+        ) {
+            (true, Some(_)) => format!("https://{}", UNIX_SOCKET_PLACEHOLDER_HOST),
+            (true, None) => format!("http://{}", UNIX_SOCKET_PLACEHOLDER_HOST),
+            (false, _) => self.server_address.clone(),
+        };
+        let endpoint = Channel::from_shared(uri)
+            .map_err(|err| GrpcMiddlewareError::ServerNotAvailable(err.to_string()))?;
+
+        // [impl->swdd~grpc-supports-connection-tuning~1]
+        Ok(match self.connection_config.keepalive_interval() {
+            Some(keepalive_interval) => endpoint
+                .http2_keep_alive_interval(keepalive_interval)
+                .keep_alive_timeout(self.connection_config.keepalive_timeout()),
+            None => endpoint,
+        })
+    }
+
+    async fn connect_channel(&self, endpoint: Endpoint) -> Result<Channel, GrpcMiddlewareError> {
+        match self.server_address.strip_prefix(UNIX_SOCKET_SCHEME) {
+            // [impl->swdd~grpc-client-connects-to-unix-domain-socket~1]
+            Some(socket_path) => {
+                let socket_path = PathBuf::from(socket_path);
+                Ok(endpoint
+                    .connect_with_connector(service_fn(move |_: Uri| {
+                        UnixStream::connect(socket_path.clone())
+                    }))
+                    .await?)
+            }
+            None => match &self.proxy_url {
+                // [impl->swdd~grpc-client-connects-through-http-proxy~2]
+                Some(proxy_url) => {
+                    let proxy_url = proxy_url.clone();
+                    Ok(endpoint
+                        .connect_with_connector(service_fn(move |uri: Uri| {
+                            proxy::connect_through_proxy(proxy_url.clone(), uri)
+                        }))
+                        .await?)
+                }
+                None => Ok(endpoint.connect().await?),
+            },
+        }
+    }
+
+    // [impl->swdd~grpc-client-trusts-additional-ca-bundle~1]
+    fn read_ca_pem(&self, tls_config: &TLSConfig) -> Result<String, GrpcMiddlewareError> {
+        // [impl->swdd~grpc-supports-pem-file-format-for-X509-certificates~1]
+        let mut ca_pem = read_pem_file(Path::new(&tls_config.path_to_ca_pem), false)?;
+        if let Some(path_to_ca_bundle) = &self.ca_bundle {
+            ca_pem.push('\n');
+            ca_pem.push_str(&read_pem_file(Path::new(path_to_ca_bundle), false)?);
+        }
+        Ok(ca_pem)
+    }
+
+    // [impl->swdd~grpc-supports-message-size-and-compression~1]
     async fn connect_to_server(
         &self,
         grpc_rx: Receiver<grpc_api::ToServer>,
@@ -230,9 +379,7 @@ impl GRPCCommunicationsClient {
             ConnectionType::Agent => match &self.tls_config {
                 // [impl->swdd~grpc-agent-activate-mtls-when-certificates-and-key-provided-upon-start~1]
                 Some(tls_config) => {
-                    // [impl->swdd~grpc-supports-pem-file-format-for-X509-certificates~1]
-                    let ca_pem = read_pem_file(Path::new(&tls_config.path_to_ca_pem), false)?;
-                    let ca = Certificate::from_pem(ca_pem);
+                    let ca = Certificate::from_pem(self.read_ca_pem(tls_config)?);
                     // [impl->swdd~grpc-supports-pem-file-format-for-X509-certificates~1]
                     let client_cert_pem =
                         read_pem_file(Path::new(&tls_config.path_to_crt_pem), false)?;
@@ -249,12 +396,13 @@ impl GRPCCommunicationsClient {
                         .ca_certificate(ca)
                         .identity(client_identity);
 
-                    let channel = Channel::from_shared(self.server_address.to_string())
-                        .map_err(|err| GrpcMiddlewareError::TLSError(err.to_string()))?
-                        .tls_config(tls)?
-                        .connect()
+                    let channel = self
+                        .connect_channel(self.endpoint()?.tls_config(tls)?)
                         .await?;
-                    let mut client = AgentConnectionClient::new(channel);
+                    let mut client = with_message_config!(
+                        AgentConnectionClient::new(channel),
+                        &self.connection_config
+                    );
 
                     let res = client
                         .connect_agent(ReceiverStream::new(grpc_rx))
@@ -264,8 +412,11 @@ impl GRPCCommunicationsClient {
                 }
                 // [impl->swdd~grpc-agent-deactivate-mtls-when-no-certificates-and-no-key-provided-upon-start~1]
                 None => {
-                    let mut client =
-                        AgentConnectionClient::connect(self.server_address.to_string()).await?;
+                    let channel = self.connect_channel(self.endpoint()?).await?;
+                    let mut client = with_message_config!(
+                        AgentConnectionClient::new(channel),
+                        &self.connection_config
+                    );
 
                     let res = client
                         .connect_agent(ReceiverStream::new(grpc_rx))
@@ -277,9 +428,7 @@ impl GRPCCommunicationsClient {
             ConnectionType::Cli => match &self.tls_config {
                 // [impl->swdd~grpc-cli-activate-mtls-when-certificates-and-key-provided-upon-start~1]
                 Some(tls_config) => {
-                    // [impl->swdd~grpc-supports-pem-file-format-for-X509-certificates~1]
-                    let ca_pem = read_pem_file(Path::new(&tls_config.path_to_ca_pem), false)?;
-                    let ca = Certificate::from_pem(ca_pem);
+                    let ca = Certificate::from_pem(self.read_ca_pem(tls_config)?);
                     // [impl->swdd~grpc-supports-pem-file-format-for-X509-certificates~1]
                     let client_cert_pem =
                         read_pem_file(Path::new(&tls_config.path_to_crt_pem), false)?;
@@ -296,12 +445,13 @@ impl GRPCCommunicationsClient {
                         .ca_certificate(ca)
                         .identity(client_identity);
 
-                    let channel = Channel::from_shared(self.server_address.to_string())
-                        .map_err(|err| GrpcMiddlewareError::TLSError(err.to_string()))?
-                        .tls_config(tls)?
-                        .connect()
+                    let channel = self
+                        .connect_channel(self.endpoint()?.tls_config(tls)?)
                         .await?;
-                    let mut client = CliConnectionClient::new(channel);
+                    let mut client = with_message_config!(
+                        CliConnectionClient::new(channel),
+                        &self.connection_config
+                    );
 
                     let res = client
                         .connect_cli(ReceiverStream::new(grpc_rx))
@@ -311,8 +461,11 @@ impl GRPCCommunicationsClient {
                 }
                 // [impl->swdd~grpc-cli-deactivate-mtls-when-no-certificates-and-no-key-provided-upon-start~1]
                 None => {
-                    let mut client =
-                        CliConnectionClient::connect(self.server_address.to_string()).await?;
+                    let channel = self.connect_channel(self.endpoint()?).await?;
+                    let mut client = with_message_config!(
+                        CliConnectionClient::new(channel),
+                        &self.connection_config
+                    );
 
                     let res = client
                         .connect_cli(ReceiverStream::new(grpc_rx))
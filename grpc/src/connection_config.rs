@@ -0,0 +1,207 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use serde::Deserialize;
+use tonic::codec::CompressionEncoding;
+
+const DEFAULT_KEEPALIVE_TIMEOUT_SECS: u64 = 20;
+const DEFAULT_RECONNECT_INITIAL_DELAY_MS: u64 = 1000;
+const DEFAULT_RECONNECT_MAX_DELAY_MS: u64 = 1000;
+const DEFAULT_RECONNECT_MULTIPLIER: u32 = 2;
+// large enough for complete states with hundreds of workloads, tonic limits to 4 MiB by default
+const DEFAULT_MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+/// Compression of the messages sent over a gRPC connection
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    #[default]
+    None,
+    Gzip,
+}
+
+/// Tuning of the gRPC connections between the Ankaios server, the agents and the CLI,
+/// provided in the `[grpc]` section of the config files
+// [impl->swdd~grpc-supports-connection-tuning~1]
+// [impl->swdd~grpc-supports-message-size-and-compression~1]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ConnectionConfig {
+    /// Seconds between two HTTP/2 keepalive pings, keepalive is disabled if not set
+    pub keepalive_interval_secs: Option<u64>,
+    /// Seconds to wait for the acknowledgement of a keepalive ping before the connection is closed
+    pub keepalive_timeout_secs: u64,
+    /// Milliseconds to wait before the first reconnect to the server
+    pub reconnect_initial_delay_ms: u64,
+    /// Upper limit in milliseconds of the delay between two reconnects
+    pub reconnect_max_delay_ms: u64,
+    /// Factor the delay grows with after every failed reconnect
+    pub reconnect_multiplier: u32,
+    /// Maximum size in bytes of a single message sent or received
+    pub max_message_size: usize,
+    /// Compression of the sent messages, compressed messages are always accepted
+    pub compression: Compression,
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        ConnectionConfig {
+            keepalive_interval_secs: None,
+            keepalive_timeout_secs: DEFAULT_KEEPALIVE_TIMEOUT_SECS,
+            reconnect_initial_delay_ms: DEFAULT_RECONNECT_INITIAL_DELAY_MS,
+            reconnect_max_delay_ms: DEFAULT_RECONNECT_MAX_DELAY_MS,
+            reconnect_multiplier: DEFAULT_RECONNECT_MULTIPLIER,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            compression: Compression::None,
+        }
+    }
+}
+
+impl ConnectionConfig {
+    pub fn keepalive_interval(&self) -> Option<Duration> {
+        self.keepalive_interval_secs.map(Duration::from_secs)
+    }
+
+    pub fn keepalive_timeout(&self) -> Duration {
+        Duration::from_secs(self.keepalive_timeout_secs)
+    }
+
+    pub fn send_compression(&self) -> Option<CompressionEncoding> {
+        match self.compression {
+            Compression::None => None,
+            Compression::Gzip => Some(CompressionEncoding::Gzip),
+        }
+    }
+}
+
+/// Applies the message size and the compression of the connection config to a generated
+/// gRPC client or server
+///
+/// Compressed messages are sent only if the peer announced to accept them, such that the
+/// compression is negotiated per connection and older peers keep working uncompressed.
+// [impl->swdd~grpc-supports-message-size-and-compression~1]
+macro_rules! with_message_config {
+    ($service:expr, $connection_config:expr) => {{
+        let connection_config: &$crate::connection_config::ConnectionConfig = $connection_config;
+        let service = $service
+            .accept_compressed(tonic::codec::CompressionEncoding::Gzip)
+            .max_decoding_message_size(connection_config.max_message_size)
+            .max_encoding_message_size(connection_config.max_message_size);
+        match connection_config.send_compression() {
+            Some(encoding) => service.send_compressed(encoding),
+            None => service,
+        }
+    }};
+}
+pub(crate) use with_message_config;
+
+/// Delays between the reconnects of a client growing with every failed reconnect
+// [impl->swdd~grpc-client-retries-connection~3]
+#[derive(Debug)]
+pub struct ReconnectBackoff {
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: u32,
+    next_delay: Duration,
+}
+
+impl ReconnectBackoff {
+    pub fn new(connection_config: &ConnectionConfig) -> Self {
+        let initial_delay = Duration::from_millis(connection_config.reconnect_initial_delay_ms);
+        ReconnectBackoff {
+            initial_delay,
+            max_delay: Duration::from_millis(connection_config.reconnect_max_delay_ms)
+                .max(initial_delay),
+            multiplier: connection_config.reconnect_multiplier.max(1),
+            next_delay: initial_delay,
+        }
+    }
+
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.next_delay;
+        self.next_delay = delay.saturating_mul(self.multiplier).min(self.max_delay);
+        delay
+    }
+
+    pub fn reset(&mut self) {
+        self.next_delay = self.initial_delay;
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tonic::codec::CompressionEncoding;
+
+    use super::{Compression, ConnectionConfig, ReconnectBackoff};
+
+    // [utest->swdd~grpc-supports-connection-tuning~1]
+    #[test]
+    fn utest_connection_config_default_keeps_constant_reconnect_delay() {
+        let connection_config = ConnectionConfig::default();
+        assert_eq!(connection_config.keepalive_interval(), None);
+
+        let mut reconnect_backoff = ReconnectBackoff::new(&connection_config);
+        assert_eq!(reconnect_backoff.next_delay(), Duration::from_secs(1));
+        assert_eq!(reconnect_backoff.next_delay(), Duration::from_secs(1));
+    }
+
+    // [utest->swdd~grpc-supports-message-size-and-compression~1]
+    #[test]
+    fn utest_connection_config_message_size_and_compression() {
+        let connection_config = ConnectionConfig::default();
+        assert_eq!(connection_config.max_message_size, 16 * 1024 * 1024);
+        assert_eq!(connection_config.send_compression(), None);
+
+        let connection_config = ConnectionConfig {
+            compression: Compression::Gzip,
+            ..Default::default()
+        };
+        assert_eq!(
+            connection_config.send_compression(),
+            Some(CompressionEncoding::Gzip)
+        );
+    }
+
+    // [utest->swdd~grpc-client-retries-connection~3]
+    #[test]
+    fn utest_reconnect_backoff_grows_up_to_max_delay_and_resets() {
+        let mut reconnect_backoff = ReconnectBackoff::new(&ConnectionConfig {
+            reconnect_initial_delay_ms: 100,
+            reconnect_max_delay_ms: 500,
+            reconnect_multiplier: 2,
+            ..Default::default()
+        });
+
+        assert_eq!(reconnect_backoff.next_delay(), Duration::from_millis(100));
+        assert_eq!(reconnect_backoff.next_delay(), Duration::from_millis(200));
+        assert_eq!(reconnect_backoff.next_delay(), Duration::from_millis(400));
+        assert_eq!(reconnect_backoff.next_delay(), Duration::from_millis(500));
+        assert_eq!(reconnect_backoff.next_delay(), Duration::from_millis(500));
+
+        reconnect_backoff.reset();
+        assert_eq!(reconnect_backoff.next_delay(), Duration::from_millis(100));
+    }
+}
@@ -27,7 +27,9 @@ use common::objects::{
     get_workloads_per_agent, DeletedWorkload, DeletedWorkloadCollection, WorkloadCollection,
     WorkloadSpec, WorkloadState,
 };
-use common::request_id_prepending::detach_prefix_from_request_id;
+use common::request_id_prepending::{
+    detach_prefix_from_request_id, SERVER_INTERNAL_REQUEST_PREFIX,
+};
 
 use tonic::Streaming;
 
@@ -101,6 +103,71 @@ pub async fn forward_from_proto_to_ankaios(
                     // [impl->swdd~agent-adds-workload-prefix-id-control-interface-request~1]
                     agent_tx.response(response).await?;
                 }
+                // [impl->swdd~grpc-client-forwards-exec-request-to-agent~1]
+                FromServerEnum::Request(ank_base::Request {
+                    request_id,
+                    request_content:
+                        Some(ank_base::request::RequestContent::ExecRequest(exec_request)),
+                }) => {
+                    agent_tx
+                        .exec_request(
+                            None,
+                            request_id,
+                            exec_request
+                                .try_into()
+                                .map_err(GrpcMiddlewareError::ConversionError)?,
+                        )
+                        .await?;
+                }
+                // [impl->swdd~grpc-client-forwards-port-forward-request-to-agent~1]
+                FromServerEnum::Request(ank_base::Request {
+                    request_id,
+                    request_content:
+                        Some(ank_base::request::RequestContent::PortForwardRequest(
+                            port_forward_request,
+                        )),
+                }) => {
+                    agent_tx
+                        .port_forward_request(
+                            None,
+                            request_id,
+                            port_forward_request
+                                .try_into()
+                                .map_err(GrpcMiddlewareError::ConversionError)?,
+                        )
+                        .await?;
+                }
+                FromServerEnum::Request(request) => {
+                    return Err(GrpcMiddlewareError::ReceiveError(format!(
+                        "Unsupported request from server: '{:?}'",
+                        request
+                    )));
+                }
+                // [impl->swdd~grpc-client-forwards-restart-workload-request-to-agent~1]
+                FromServerEnum::RestartWorkloads(restart_workloads) => {
+                    agent_tx
+                        .restart_workload_request(
+                            None,
+                            restart_workloads.request_id,
+                            restart_workloads.workload_names,
+                        )
+                        .await?;
+                }
+                // [impl->swdd~grpc-client-forwards-pause-workload-request-to-agent~1]
+                FromServerEnum::PauseWorkloads(pause_workloads) => {
+                    agent_tx
+                        .pause_workload_request(
+                            None,
+                            pause_workloads.request_id,
+                            pause_workloads.workload_names,
+                            pause_workloads.resume,
+                        )
+                        .await?;
+                }
+                // [impl->swdd~grpc-client-forwards-pull-images-to-agent~1]
+                FromServerEnum::PullImages(pull_images) => {
+                    agent_tx.pull_images(None, pull_images.images).await?;
+                }
             }
             Ok(()) as Result<(), GrpcMiddlewareError>
         }
@@ -192,10 +259,102 @@ pub async fn forward_from_ankaios_to_proto(
                     if result.is_err() {
                         log::warn!("Could not send response to agent '{}'", agent_name,);
                     }
+                } else if agent_name == SERVER_INTERNAL_REQUEST_PREFIX {
+                    log::debug!(
+                        "Dropping the response to the server internal request '{}'",
+                        request_id
+                    );
                 } else {
                     log::warn!("Unknown agent with name: '{}'", agent_name);
                 }
             }
+            // [impl->swdd~grpc-server-forwards-exec-request-to-agent~1]
+            FromServer::ExecRequest(method_obj) => {
+                forward_request_to_agent(
+                    agent_senders,
+                    method_obj.agent_name.unwrap_or_default(),
+                    ank_base::Request {
+                        request_id: method_obj.request_id,
+                        request_content: Some(ank_base::request::RequestContent::ExecRequest(
+                            method_obj.exec_request.into(),
+                        )),
+                    },
+                )
+                .await;
+            }
+            // [impl->swdd~grpc-server-forwards-port-forward-request-to-agent~1]
+            FromServer::PortForwardRequest(method_obj) => {
+                forward_request_to_agent(
+                    agent_senders,
+                    method_obj.agent_name.unwrap_or_default(),
+                    ank_base::Request {
+                        request_id: method_obj.request_id,
+                        request_content: Some(
+                            ank_base::request::RequestContent::PortForwardRequest(
+                                method_obj.port_forward_request.into(),
+                            ),
+                        ),
+                    },
+                )
+                .await;
+            }
+            // [impl->swdd~grpc-server-forwards-restart-workload-request-to-agent~1]
+            FromServer::RestartWorkloadRequest(method_obj) => {
+                let request_id = method_obj.request_id.clone();
+                send_to_agent(
+                    agent_senders,
+                    method_obj.agent_name.unwrap_or_default(),
+                    &request_id,
+                    FromServerEnum::RestartWorkloads(grpc_api::RestartWorkloads {
+                        request_id: method_obj.request_id,
+                        workload_names: method_obj.workload_names,
+                    }),
+                )
+                .await;
+            }
+            // [impl->swdd~grpc-server-forwards-pause-workload-request-to-agent~1]
+            FromServer::PauseWorkloadRequest(method_obj) => {
+                let request_id = method_obj.request_id.clone();
+                send_to_agent(
+                    agent_senders,
+                    method_obj.agent_name.unwrap_or_default(),
+                    &request_id,
+                    FromServerEnum::PauseWorkloads(grpc_api::PauseWorkloads {
+                        request_id: method_obj.request_id,
+                        workload_names: method_obj.workload_names,
+                        resume: method_obj.resume,
+                    }),
+                )
+                .await;
+            }
+            // [impl->swdd~grpc-server-forwards-pull-images-to-agent~1]
+            FromServer::PullImages(method_obj) => {
+                let agent_name = method_obj.agent_name.unwrap_or_default();
+                if let Some(sender) = agent_senders.get(&agent_name) {
+                    log::trace!(
+                        "Sending {} images to pull to agent '{}'.",
+                        method_obj.images.len(),
+                        agent_name
+                    );
+                    let result = sender
+                        .send(Ok(grpc_api::FromServer {
+                            from_server_enum: Some(FromServerEnum::PullImages(
+                                grpc_api::PullImages {
+                                    images: method_obj.images,
+                                },
+                            )),
+                        }))
+                        .await;
+                    if result.is_err() {
+                        log::warn!("Could not send images to pull to agent '{}'", agent_name);
+                    }
+                } else {
+                    log::warn!("Unknown agent with name: '{}'", agent_name);
+                }
+            }
+            FromServer::ServerConnectionLost(_) => {
+                log::warn!("Unexpected ServerConnectionLost on the server side, ignoring it.");
+            }
             FromServer::Stop(_method_obj) => {
                 log::debug!("Received Stop from server.");
                 // TODO: handle the call
@@ -205,6 +364,46 @@ pub async fn forward_from_ankaios_to_proto(
     }
 }
 
+async fn forward_request_to_agent(
+    agent_senders: &AgentSendersMap,
+    agent_name: String,
+    request: ank_base::Request,
+) {
+    let request_id = request.request_id.clone();
+    send_to_agent(
+        agent_senders,
+        agent_name,
+        &request_id,
+        FromServerEnum::Request(request),
+    )
+    .await;
+}
+
+async fn send_to_agent(
+    agent_senders: &AgentSendersMap,
+    agent_name: String,
+    request_id: &str,
+    from_server_enum: FromServerEnum,
+) {
+    if let Some(sender) = agent_senders.get(&agent_name) {
+        log::trace!(
+            "Sending request '{}' to agent '{}'.",
+            request_id,
+            agent_name
+        );
+        let result = sender
+            .send(Ok(grpc_api::FromServer {
+                from_server_enum: Some(from_server_enum),
+            }))
+            .await;
+        if result.is_err() {
+            log::warn!("Could not send request to agent '{}'", agent_name);
+        }
+    } else {
+        log::warn!("Unknown agent with name: '{}'", agent_name);
+    }
+}
+
 // [impl->swdd~grpc-server-forwards-from-server-messages-to-grpc-client~1]
 async fn distribute_workload_states_to_agents(
     agent_senders: &AgentSendersMap,
@@ -215,10 +414,9 @@ async fn distribute_workload_states_to_agents(
     for agent_name in agent_senders.get_all_agent_names() {
         // Filter the workload states as we don't want to send an agent its own updates
         let filtered_workload_states: Vec<ank_base::WorkloadState> = workload_state_collection
-            .clone()
-            .into_iter()
+            .iter()
             .filter(|workload_state| workload_state.instance_name.agent_name() != agent_name)
-            .map(|x| x.into())
+            .map(|x| x.clone().into())
             .collect();
         if filtered_workload_states.is_empty() {
             log::trace!(
@@ -335,6 +533,7 @@ mod tests {
     );
 
     const WORKLOAD_NAME: &str = "workload_1";
+    const IMAGE_NAME: &str = "ghcr.io/eclipse-ankaios/tests/nginx:alpine-slim";
 
     fn create_test_setup(agent_name: &str) -> TestSetup {
         let (to_manager, manager_receiver) =
@@ -531,6 +730,7 @@ mod tests {
                 ..Default::default()
             }),
             dependencies: [("name".into(), -1)].into(),
+            grace_period_secs: None,
         };
 
         // simulate the reception of an update workload grpc from server message
@@ -626,6 +826,355 @@ mod tests {
         ));
     }
 
+    // [utest->swdd~grpc-server-forwards-exec-request-to-agent~1]
+    #[tokio::test]
+    async fn utest_from_server_proxy_forward_from_ankaios_to_proto_exec_request() {
+        let agent = "agent_X";
+        let (to_manager, mut manager_receiver, _, mut agent_rx, agent_senders_map) =
+            create_test_setup(agent);
+
+        let exec_request = common::commands::ExecRequest::Start(common::commands::ExecStart {
+            workload_name: WORKLOAD_NAME.to_string(),
+            command: vec!["ls".to_string()],
+        });
+        assert!(to_manager
+            .exec_request(
+                Some(agent.to_string()),
+                "cli@request_id".to_string(),
+                exec_request.clone()
+            )
+            .await
+            .is_ok());
+
+        let handle = forward_from_ankaios_to_proto(&agent_senders_map, &mut manager_receiver);
+        drop(to_manager);
+        join!(handle).0;
+
+        let result = agent_rx.recv().await.unwrap().unwrap();
+        assert_eq!(
+            result.from_server_enum,
+            Some(FromServerEnum::Request(ank_base::Request {
+                request_id: "cli@request_id".to_string(),
+                request_content: Some(ank_base::request::RequestContent::ExecRequest(
+                    exec_request.into()
+                )),
+            }))
+        );
+    }
+
+    // [utest->swdd~grpc-client-forwards-exec-request-to-agent~1]
+    #[tokio::test]
+    async fn utest_from_server_proxy_forward_from_proto_to_ankaios_exec_request() {
+        let (to_agent, mut agent_receiver) =
+            mpsc::channel::<common::from_server_interface::FromServer>(common::CHANNEL_CAPACITY);
+
+        let exec_request = common::commands::ExecRequest::Input(common::commands::ExecInput {
+            stdin: b"ls\n".to_vec(),
+            close_stdin: false,
+        });
+        let mut mock_grpc_ex_request_streaming =
+            MockGRPCFromServerStreaming::new(LinkedList::from([
+                Some(FromServer {
+                    from_server_enum: Some(FromServerEnum::Request(ank_base::Request {
+                        request_id: "cli@request_id".to_string(),
+                        request_content: Some(ank_base::request::RequestContent::ExecRequest(
+                            exec_request.clone().into(),
+                        )),
+                    })),
+                }),
+                None,
+            ]));
+
+        let forward_result = tokio::spawn(async move {
+            forward_from_proto_to_ankaios(&mut mock_grpc_ex_request_streaming, &to_agent).await
+        })
+        .await;
+        assert!(forward_result.is_ok());
+
+        assert_eq!(
+            agent_receiver.recv().await.unwrap(),
+            common::from_server_interface::FromServer::ExecRequest(
+                common::commands::AgentExecRequest {
+                    agent_name: None,
+                    request_id: "cli@request_id".to_string(),
+                    exec_request,
+                }
+            )
+        );
+    }
+
+    // [utest->swdd~grpc-server-forwards-port-forward-request-to-agent~1]
+    #[tokio::test]
+    async fn utest_from_server_proxy_forward_from_ankaios_to_proto_port_forward_request() {
+        let agent = "agent_X";
+        let (to_manager, mut manager_receiver, _, mut agent_rx, agent_senders_map) =
+            create_test_setup(agent);
+
+        let port_forward_request =
+            common::commands::PortForwardRequest::Start(common::commands::PortForwardStart {
+                workload_name: WORKLOAD_NAME.to_string(),
+                port: 8080,
+            });
+        assert!(to_manager
+            .port_forward_request(
+                Some(agent.to_string()),
+                "cli@request_id".to_string(),
+                port_forward_request.clone()
+            )
+            .await
+            .is_ok());
+
+        let handle = forward_from_ankaios_to_proto(&agent_senders_map, &mut manager_receiver);
+        drop(to_manager);
+        join!(handle).0;
+
+        let result = agent_rx.recv().await.unwrap().unwrap();
+        assert_eq!(
+            result.from_server_enum,
+            Some(FromServerEnum::Request(ank_base::Request {
+                request_id: "cli@request_id".to_string(),
+                request_content: Some(ank_base::request::RequestContent::PortForwardRequest(
+                    port_forward_request.into()
+                )),
+            }))
+        );
+    }
+
+    // [utest->swdd~grpc-client-forwards-port-forward-request-to-agent~1]
+    #[tokio::test]
+    async fn utest_from_server_proxy_forward_from_proto_to_ankaios_port_forward_request() {
+        let (to_agent, mut agent_receiver) =
+            mpsc::channel::<common::from_server_interface::FromServer>(common::CHANNEL_CAPACITY);
+
+        let port_forward_request =
+            common::commands::PortForwardRequest::Data(common::commands::PortForwardData {
+                data: b"GET /".to_vec(),
+                close: false,
+            });
+        let mut mock_grpc_ex_request_streaming =
+            MockGRPCFromServerStreaming::new(LinkedList::from([
+                Some(FromServer {
+                    from_server_enum: Some(FromServerEnum::Request(ank_base::Request {
+                        request_id: "cli@request_id".to_string(),
+                        request_content: Some(
+                            ank_base::request::RequestContent::PortForwardRequest(
+                                port_forward_request.clone().into(),
+                            ),
+                        ),
+                    })),
+                }),
+                None,
+            ]));
+
+        let forward_result = tokio::spawn(async move {
+            forward_from_proto_to_ankaios(&mut mock_grpc_ex_request_streaming, &to_agent).await
+        })
+        .await;
+        assert!(forward_result.is_ok());
+
+        assert_eq!(
+            agent_receiver.recv().await.unwrap(),
+            common::from_server_interface::FromServer::PortForwardRequest(
+                common::commands::AgentPortForwardRequest {
+                    agent_name: None,
+                    request_id: "cli@request_id".to_string(),
+                    port_forward_request,
+                }
+            )
+        );
+    }
+
+    // [utest->swdd~grpc-server-forwards-restart-workload-request-to-agent~1]
+    #[tokio::test]
+    async fn utest_from_server_proxy_forward_from_ankaios_to_proto_restart_workload_request() {
+        let agent = "agent_X";
+        let (to_manager, mut manager_receiver, _, mut agent_rx, agent_senders_map) =
+            create_test_setup(agent);
+
+        assert!(to_manager
+            .restart_workload_request(
+                Some(agent.to_string()),
+                "cli@request_id".to_string(),
+                vec![WORKLOAD_NAME.to_string()]
+            )
+            .await
+            .is_ok());
+
+        let handle = forward_from_ankaios_to_proto(&agent_senders_map, &mut manager_receiver);
+        drop(to_manager);
+        join!(handle).0;
+
+        let result = agent_rx.recv().await.unwrap().unwrap();
+        assert_eq!(
+            result.from_server_enum,
+            Some(FromServerEnum::RestartWorkloads(
+                grpc_api::RestartWorkloads {
+                    request_id: "cli@request_id".to_string(),
+                    workload_names: vec![WORKLOAD_NAME.to_string()],
+                }
+            ))
+        );
+    }
+
+    // [utest->swdd~grpc-client-forwards-restart-workload-request-to-agent~1]
+    #[tokio::test]
+    async fn utest_from_server_proxy_forward_from_proto_to_ankaios_restart_workload_request() {
+        let (to_agent, mut agent_receiver) =
+            mpsc::channel::<common::from_server_interface::FromServer>(common::CHANNEL_CAPACITY);
+
+        let mut mock_grpc_restart_streaming = MockGRPCFromServerStreaming::new(LinkedList::from([
+            Some(FromServer {
+                from_server_enum: Some(FromServerEnum::RestartWorkloads(
+                    grpc_api::RestartWorkloads {
+                        request_id: "cli@request_id".to_string(),
+                        workload_names: vec![WORKLOAD_NAME.to_string()],
+                    },
+                )),
+            }),
+            None,
+        ]));
+
+        let forward_result = tokio::spawn(async move {
+            forward_from_proto_to_ankaios(&mut mock_grpc_restart_streaming, &to_agent).await
+        })
+        .await;
+        assert!(forward_result.is_ok());
+
+        assert_eq!(
+            agent_receiver.recv().await.unwrap(),
+            common::from_server_interface::FromServer::RestartWorkloadRequest(
+                common::commands::AgentRestartWorkloadRequest {
+                    agent_name: None,
+                    request_id: "cli@request_id".to_string(),
+                    workload_names: vec![WORKLOAD_NAME.to_string()],
+                }
+            )
+        );
+    }
+
+    // [utest->swdd~grpc-server-forwards-pause-workload-request-to-agent~1]
+    #[tokio::test]
+    async fn utest_from_server_proxy_forward_from_ankaios_to_proto_pause_workload_request() {
+        let agent = "agent_X";
+        let (to_manager, mut manager_receiver, _, mut agent_rx, agent_senders_map) =
+            create_test_setup(agent);
+
+        assert!(to_manager
+            .pause_workload_request(
+                Some(agent.to_string()),
+                "cli@request_id".to_string(),
+                vec![WORKLOAD_NAME.to_string()],
+                false
+            )
+            .await
+            .is_ok());
+
+        let handle = forward_from_ankaios_to_proto(&agent_senders_map, &mut manager_receiver);
+        drop(to_manager);
+        join!(handle).0;
+
+        let result = agent_rx.recv().await.unwrap().unwrap();
+        assert_eq!(
+            result.from_server_enum,
+            Some(FromServerEnum::PauseWorkloads(grpc_api::PauseWorkloads {
+                request_id: "cli@request_id".to_string(),
+                workload_names: vec![WORKLOAD_NAME.to_string()],
+                resume: false,
+            }))
+        );
+    }
+
+    // [utest->swdd~grpc-client-forwards-pause-workload-request-to-agent~1]
+    #[tokio::test]
+    async fn utest_from_server_proxy_forward_from_proto_to_ankaios_pause_workload_request() {
+        let (to_agent, mut agent_receiver) =
+            mpsc::channel::<common::from_server_interface::FromServer>(common::CHANNEL_CAPACITY);
+
+        let mut mock_grpc_pause_streaming = MockGRPCFromServerStreaming::new(LinkedList::from([
+            Some(FromServer {
+                from_server_enum: Some(FromServerEnum::PauseWorkloads(grpc_api::PauseWorkloads {
+                    request_id: "cli@request_id".to_string(),
+                    workload_names: vec![WORKLOAD_NAME.to_string()],
+                    resume: true,
+                })),
+            }),
+            None,
+        ]));
+
+        let forward_result = tokio::spawn(async move {
+            forward_from_proto_to_ankaios(&mut mock_grpc_pause_streaming, &to_agent).await
+        })
+        .await;
+        assert!(forward_result.is_ok());
+
+        assert_eq!(
+            agent_receiver.recv().await.unwrap(),
+            common::from_server_interface::FromServer::PauseWorkloadRequest(
+                common::commands::AgentPauseWorkloadRequest {
+                    agent_name: None,
+                    request_id: "cli@request_id".to_string(),
+                    workload_names: vec![WORKLOAD_NAME.to_string()],
+                    resume: true,
+                }
+            )
+        );
+    }
+
+    // [utest->swdd~grpc-server-forwards-pull-images-to-agent~1]
+    #[tokio::test]
+    async fn utest_from_server_proxy_forward_from_ankaios_to_proto_pull_images() {
+        let agent = "agent_X";
+        let (to_manager, mut manager_receiver, _, mut agent_rx, agent_senders_map) =
+            create_test_setup(agent);
+
+        assert!(to_manager
+            .pull_images(Some(agent.to_string()), vec![IMAGE_NAME.to_string()])
+            .await
+            .is_ok());
+
+        let handle = forward_from_ankaios_to_proto(&agent_senders_map, &mut manager_receiver);
+        drop(to_manager);
+        join!(handle).0;
+
+        let result = agent_rx.recv().await.unwrap().unwrap();
+        assert_eq!(
+            result.from_server_enum,
+            Some(FromServerEnum::PullImages(grpc_api::PullImages {
+                images: vec![IMAGE_NAME.to_string()],
+            }))
+        );
+    }
+
+    // [utest->swdd~grpc-client-forwards-pull-images-to-agent~1]
+    #[tokio::test]
+    async fn utest_from_server_proxy_forward_from_proto_to_ankaios_pull_images() {
+        let (to_agent, mut agent_receiver) =
+            mpsc::channel::<common::from_server_interface::FromServer>(common::CHANNEL_CAPACITY);
+
+        let mut mock_grpc_pull_streaming = MockGRPCFromServerStreaming::new(LinkedList::from([
+            Some(FromServer {
+                from_server_enum: Some(FromServerEnum::PullImages(grpc_api::PullImages {
+                    images: vec![IMAGE_NAME.to_string()],
+                })),
+            }),
+            None,
+        ]));
+
+        let forward_result = tokio::spawn(async move {
+            forward_from_proto_to_ankaios(&mut mock_grpc_pull_streaming, &to_agent).await
+        })
+        .await;
+        assert!(forward_result.is_ok());
+
+        assert_eq!(
+            agent_receiver.recv().await.unwrap(),
+            common::from_server_interface::FromServer::PullImages(common::commands::PullImages {
+                agent_name: None,
+                images: vec![IMAGE_NAME.to_string()],
+            })
+        );
+    }
+
     #[tokio::test]
     async fn utest_distribute_workloads_to_agents_shall_distribute_workloads_to_existing_agents() {
         let agent_name = "agent_X";
@@ -741,11 +1290,10 @@ mod tests {
             result.from_server_enum,
             Some(FromServerEnum::Response(ank_base::Response {
                 request_id,
-                response_content: Some(ank_base::response::ResponseContent::CompleteState(ank_base::CompleteState{
-                    desired_state: Some(desired_state), ..}))
+                response_content: Some(ank_base::response::ResponseContent::CompleteState(complete_state))
 
             })) if request_id == my_request_id
-            && desired_state == test_complete_state.desired_state.unwrap()
+            && complete_state.desired_state == test_complete_state.desired_state
         ));
     }
 
@@ -781,9 +1329,9 @@ mod tests {
 
         let proto_response = ank_base::Response {
             request_id: my_request_id.clone(),
-            response_content: Some(response::ResponseContent::CompleteState(
+            response_content: Some(response::ResponseContent::CompleteState(Box::new(
                 proto_complete_state,
-            )),
+            ))),
         };
 
         // simulate the reception of an update workload state grpc from server message
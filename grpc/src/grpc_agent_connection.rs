@@ -15,6 +15,7 @@
 use std::pin::Pin;
 
 use common::check_version_compatibility;
+use common::commands;
 use common::std_extensions::GracefulExitResult;
 use tokio::sync::mpsc::Sender;
 use tokio_stream::wrappers::ReceiverStream;
@@ -26,25 +27,36 @@ use x509_parser::certificate::X509Certificate;
 use x509_parser::der_parser::asn1_rs::FromDer;
 use x509_parser::extensions::GeneralName;
 
-use crate::agent_senders_map::AgentSendersMap;
+use crate::agent_allow_list::AgentAllowList;
+use crate::agent_senders_map::{AgentRegistrationError, AgentSendersMap, DuplicateAgentPolicy};
 use crate::grpc_api::{self, agent_connection_server::AgentConnection, to_server::ToServerEnum};
+use crate::stream_error_counter::StreamErrorCounter;
 use crate::to_server_proxy::{forward_from_proto_to_ankaios, GRPCToServerStreaming};
 use common::to_server_interface::{self, ToServerInterface};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct GRPCAgentConnection {
     agent_senders: AgentSendersMap,
     to_ankaios_server: Sender<to_server_interface::ToServer>,
+    duplicate_agent_policy: DuplicateAgentPolicy,
+    agent_allow_list: AgentAllowList,
+    stream_errors: StreamErrorCounter,
 }
 
 impl GRPCAgentConnection {
     pub fn new(
         agent_senders: AgentSendersMap,
         to_ankaios_server: Sender<to_server_interface::ToServer>,
+        duplicate_agent_policy: DuplicateAgentPolicy,
+        agent_allow_list: AgentAllowList,
+        stream_errors: StreamErrorCounter,
     ) -> Self {
         Self {
             agent_senders,
             to_ankaios_server,
+            duplicate_agent_policy,
+            agent_allow_list,
+            stream_errors,
         }
     }
 }
@@ -107,6 +119,7 @@ impl AgentConnection for GRPCAgentConnection {
 
         let ankaios_tx = self.to_ankaios_server.clone();
         let agent_senders = self.agent_senders.clone();
+        let stream_errors = self.stream_errors.clone();
 
         // The first_message must be an agent hello
         match stream
@@ -119,6 +132,8 @@ impl AgentConnection for GRPCAgentConnection {
             ToServerEnum::AgentHello(grpc_api::AgentHello {
                 agent_name,
                 protocol_version,
+                labels,
+                runtimes,
             }) => {
                 log::trace!("Received a hello from '{}'", agent_name);
 
@@ -127,15 +142,65 @@ impl AgentConnection for GRPCAgentConnection {
                     log::warn!("Refused connection from agent '{agent_name}' due to unsupported version: '{protocol_version}'");
                     Status::failed_precondition(err)})?;
 
+                // [impl->swdd~grpc-agent-connection-checks-agent-allow-list~1]
+                if !self.agent_allow_list.is_allowed(&agent_name) {
+                    log::warn!("Refused connection from agent '{agent_name}' as it is not in the list of allowed agents.");
+                    return Err(Status::permission_denied(format!(
+                        "Agent name '{agent_name}' is not in the list of allowed agents!"
+                    )));
+                }
+
                 if sans.is_empty()
                     || sans.contains(&agent_name)
                     || sans.contains(&String::from("*"))
                 {
                     // [impl->swdd~grpc-agent-connection-stores-from-server-channel-tx~1]
-                    self.agent_senders
-                        .insert(&agent_name, new_agent_sender.to_owned());
+                    // [impl->swdd~grpc-agent-connection-handles-duplicate-agent-names~1]
+                    match self.agent_senders.register(
+                        &agent_name,
+                        new_agent_sender.to_owned(),
+                        self.duplicate_agent_policy,
+                    ) {
+                        Ok(()) => {}
+                        Err(AgentRegistrationError::AlreadyConnected) => {
+                            log::warn!("Refused connection from agent '{agent_name}' as an agent with the same name is already connected.");
+                            return Err(Status::already_exists(format!(
+                                "An agent with the name '{agent_name}' is already connected!"
+                            )));
+                        }
+                        Err(AgentRegistrationError::Quarantined(disconnected)) => {
+                            if disconnected {
+                                log::error!("Two agents connected with the name '{agent_name}'. Both are quarantined until the server restarts.");
+                                // [impl->swdd~grpc-agent-connection-sends-agent-gone~1]
+                                if let Err(error) =
+                                    self.to_ankaios_server.agent_gone(agent_name.clone()).await
+                                {
+                                    log::error!(
+                                        "Could not inform server about gone agent: '{}'",
+                                        error
+                                    );
+                                }
+                            } else {
+                                log::warn!(
+                                    "Refused connection from quarantined agent '{agent_name}'."
+                                );
+                            }
+                            return Err(Status::permission_denied(format!(
+                                "The agent name '{agent_name}' is quarantined as it has been used by multiple agents at the same time!"
+                            )));
+                        }
+                    }
+                    let weak_agent_sender = new_agent_sender.downgrade();
                     // [impl->swdd~grpc-agent-connection-forwards-hello-to-ankaios-server~1]
-                    if let Err(error) = self.to_ankaios_server.agent_hello(agent_name.clone()).await
+                    if let Err(error) = self
+                        .to_ankaios_server
+                        .agent_hello(commands::AgentHello {
+                            agent_name: agent_name.clone(),
+                            labels,
+                            version: protocol_version,
+                            runtimes,
+                        })
+                        .await
                     {
                         log::error!("Could not send agent hello: '{error}'");
                     }
@@ -155,8 +220,13 @@ impl AgentConnection for GRPCAgentConnection {
                                 agent_name,
                                 error
                             );
+                            // [impl->swdd~grpc-server-counts-stream-errors~1]
+                            stream_errors.increment();
 
-                            agent_senders.remove(&agent_name);
+                            // A replaced or quarantined connection must not remove the current sender
+                            if !agent_senders.remove_sender(&agent_name, &weak_agent_sender) {
+                                return;
+                            }
                             log::trace!(
                             "The connection is interrupted or has been closed. Deleting the agent sender '{}'",
                             agent_name
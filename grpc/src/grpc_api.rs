@@ -26,6 +26,8 @@ impl AgentHello {
         AgentHello {
             agent_name: agent_name.into(),
             protocol_version: common::ANKAIOS_VERSION.into(),
+            labels: Default::default(),
+            runtimes: Default::default(),
         }
     }
 }
@@ -34,6 +36,9 @@ impl From<AgentHello> for commands::AgentHello {
     fn from(item: AgentHello) -> Self {
         commands::AgentHello {
             agent_name: item.agent_name,
+            labels: item.labels,
+            version: item.protocol_version,
+            runtimes: item.runtimes,
         }
     }
 }
@@ -46,12 +51,34 @@ impl CommanderHello {
     }
 }
 
+impl StandbyHello {
+    pub fn new(standby_name: impl Into<String>) -> Self {
+        StandbyHello {
+            standby_name: standby_name.into(),
+            protocol_version: common::ANKAIOS_VERSION.into(),
+        }
+    }
+}
+
 impl From<AgentLoadStatus> for commands::AgentLoadStatus {
     fn from(item: AgentLoadStatus) -> Self {
         commands::AgentLoadStatus {
             agent_name: item.agent_name,
             cpu_usage: item.cpu_usage.unwrap_or_default().into(),
             free_memory: item.free_memory.unwrap_or_default().into(),
+            workload_resources: item
+                .workload_resources
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            disk_usage: item.disk_usage.unwrap_or_default().into(),
+            network_usage: item.network_usage.unwrap_or_default().into(),
+            temperature: item.temperature.unwrap_or_default().into(),
+            image_states: item
+                .image_states
+                .into_iter()
+                .map(|(image, state)| (image, state.into()))
+                .collect(),
         }
     }
 }
@@ -62,6 +89,37 @@ impl From<commands::AgentLoadStatus> for AgentLoadStatus {
             agent_name: item.agent_name,
             cpu_usage: Some(item.cpu_usage.into()),
             free_memory: Some(item.free_memory.into()),
+            workload_resources: item
+                .workload_resources
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            disk_usage: Some(item.disk_usage.into()),
+            network_usage: Some(item.network_usage.into()),
+            temperature: Some(item.temperature.into()),
+            image_states: item
+                .image_states
+                .into_iter()
+                .map(|(image, state)| (image, state.into()))
+                .collect(),
+        }
+    }
+}
+
+impl From<WorkloadResources> for objects::WorkloadResources {
+    fn from(item: WorkloadResources) -> Self {
+        objects::WorkloadResources {
+            instance_name: item.instance_name.unwrap_or_default().into(),
+            resource_usage: item.resource_usage.unwrap_or_default().into(),
+        }
+    }
+}
+
+impl From<objects::WorkloadResources> for WorkloadResources {
+    fn from(item: objects::WorkloadResources) -> Self {
+        WorkloadResources {
+            instance_name: Some(item.instance_name.into()),
+            resource_usage: Some(item.resource_usage.into()),
         }
     }
 }
@@ -126,9 +184,64 @@ impl TryFrom<from_server_interface::FromServer> for FromServer {
             from_server_interface::FromServer::Response(response) => Ok(FromServer {
                 from_server_enum: Some(from_server::FromServerEnum::Response(response)),
             }),
+            from_server_interface::FromServer::ExecRequest(exec_request) => Ok(FromServer {
+                from_server_enum: Some(from_server::FromServerEnum::Request(
+                    super::ank_base::Request {
+                        request_id: exec_request.request_id,
+                        request_content: Some(
+                            super::ank_base::request::RequestContent::ExecRequest(
+                                exec_request.exec_request.into(),
+                            ),
+                        ),
+                    },
+                )),
+            }),
+            from_server_interface::FromServer::PortForwardRequest(port_forward_request) => {
+                Ok(FromServer {
+                    from_server_enum: Some(from_server::FromServerEnum::Request(
+                        super::ank_base::Request {
+                            request_id: port_forward_request.request_id,
+                            request_content: Some(
+                                super::ank_base::request::RequestContent::PortForwardRequest(
+                                    port_forward_request.port_forward_request.into(),
+                                ),
+                            ),
+                        },
+                    )),
+                })
+            }
+            from_server_interface::FromServer::RestartWorkloadRequest(restart_workload_request) => {
+                Ok(FromServer {
+                    from_server_enum: Some(from_server::FromServerEnum::RestartWorkloads(
+                        RestartWorkloads {
+                            request_id: restart_workload_request.request_id,
+                            workload_names: restart_workload_request.workload_names,
+                        },
+                    )),
+                })
+            }
+            from_server_interface::FromServer::PauseWorkloadRequest(pause_workload_request) => {
+                Ok(FromServer {
+                    from_server_enum: Some(from_server::FromServerEnum::PauseWorkloads(
+                        PauseWorkloads {
+                            request_id: pause_workload_request.request_id,
+                            workload_names: pause_workload_request.workload_names,
+                            resume: pause_workload_request.resume,
+                        },
+                    )),
+                })
+            }
+            from_server_interface::FromServer::PullImages(pull_images) => Ok(FromServer {
+                from_server_enum: Some(from_server::FromServerEnum::PullImages(PullImages {
+                    images: pull_images.images,
+                })),
+            }),
             from_server_interface::FromServer::Stop(_) => {
                 Err("Stop command not implemented in proto")
             }
+            from_server_interface::FromServer::ServerConnectionLost(_) => {
+                Err("ServerConnectionLost command not implemented in proto")
+            }
         }
     }
 }
@@ -147,6 +260,7 @@ impl TryFrom<DeletedWorkload> for objects::DeletedWorkload {
                 .into_iter()
                 .map(|(k, v)| Ok((k, v.try_into()?)))
                 .collect::<Result<HashMap<String, objects::DeleteCondition>, String>>()?,
+            grace_period_secs: deleted_workload.grace_period_secs,
         })
     }
 }
@@ -160,6 +274,7 @@ impl From<objects::DeletedWorkload> for DeletedWorkload {
                 .into_iter()
                 .map(|(k, v)| (k, v as i32))
                 .collect(),
+            grace_period_secs: value.grace_period_secs,
         }
     }
 }
@@ -183,6 +298,35 @@ impl TryFrom<AddedWorkload> for objects::WorkloadSpec {
                 .control_interface_access
                 .unwrap_or_default()
                 .try_into()?,
+            hostname: workload.hostname,
+            extra_hosts: workload.extra_hosts.into_iter().map(|x| x.into()).collect(),
+            ports: workload
+                .ports
+                .into_iter()
+                .map(|x| x.try_into())
+                .collect::<Result<Vec<objects::Port>, String>>()?,
+            resource_thresholds: workload.resource_thresholds.map(|x| x.into()),
+            resources: workload.resources.map(|x| x.into()),
+            health_check: workload.health_check.map(|x| x.try_into()).transpose()?,
+            restart_backoff: workload.restart_backoff.map(|x| x.into()),
+            priority: workload.priority,
+            volumes: workload
+                .volumes
+                .into_iter()
+                .map(|x| x.try_into())
+                .collect::<Result<Vec<objects::Volume>, String>>()?,
+            registry_auth: workload.registry_auth,
+            networks: workload.networks.into_iter().map(|x| x.into()).collect(),
+            env: workload.env,
+            on_agent_disconnect: workload
+                .on_agent_disconnect
+                .map(|x| x.parse())
+                .transpose()?,
+            restart_on: workload.restart_on,
+            lifecycle_hooks: workload.lifecycle_hooks.map(|x| x.into()),
+            node_selector: workload.node_selector,
+            state_debounce: workload.state_debounce,
+            security_context: workload.security_context.map(|x| x.into()),
         })
     }
 }
@@ -202,6 +346,24 @@ impl From<objects::WorkloadSpec> for AddedWorkload {
             runtime_config: workload.runtime_config,
             tags: workload.tags.into_iter().map(|x| x.into()).collect(),
             control_interface_access: workload.control_interface_access.into(),
+            hostname: workload.hostname,
+            extra_hosts: workload.extra_hosts.into_iter().map(|x| x.into()).collect(),
+            ports: workload.ports.into_iter().map(|x| x.into()).collect(),
+            resource_thresholds: workload.resource_thresholds.map(|x| x.into()),
+            resources: workload.resources.map(|x| x.into()),
+            health_check: workload.health_check.map(|x| x.into()),
+            restart_backoff: workload.restart_backoff.map(|x| x.into()),
+            priority: workload.priority,
+            volumes: workload.volumes.into_iter().map(|x| x.into()).collect(),
+            registry_auth: workload.registry_auth,
+            networks: workload.networks.into_iter().map(|x| x.into()).collect(),
+            env: workload.env,
+            on_agent_disconnect: workload.on_agent_disconnect.map(|x| x.to_string()),
+            restart_on: workload.restart_on,
+            lifecycle_hooks: workload.lifecycle_hooks.map(|x| x.into()),
+            node_selector: workload.node_selector,
+            state_debounce: workload.state_debounce,
+            security_context: workload.security_context.map(|x| x.into()),
         }
     }
 }
@@ -232,6 +394,7 @@ impl TryFrom<ToServer> for to_server_interface::ToServer {
             ToServerEnum::Goodbye(_) => {
                 to_server_interface::ToServer::Goodbye(commands::Goodbye {})
             }
+            ToServerEnum::Response(protobuf) => to_server_interface::ToServer::Response(protobuf),
         })
     }
 }
@@ -263,6 +426,7 @@ pub fn generate_test_proto_deleted_workload() -> DeletedWorkload {
     DeletedWorkload {
         instance_name: Some(instance_name.into()),
         dependencies: generate_test_proto_delete_dependencies(),
+        grace_period_secs: None,
     }
 }
 
@@ -289,12 +453,15 @@ mod tests {
     use crate::{
         from_server::FromServerEnum, generate_test_proto_deleted_workload, to_server::ToServerEnum,
         AddedWorkload, AgentHello, AgentLoadStatus, DeletedWorkload, FromServer, ToServer,
-        UpdateWorkload, UpdateWorkloadState,
+        UpdateWorkload, UpdateWorkloadState, WorkloadResources,
     };
 
     use api::ank_base::{self, Dependencies};
     use common::{
-        objects::{generate_test_workload_spec, ConfigHash, CpuUsage, FreeMemory},
+        objects::{
+            generate_test_workload_resources, generate_test_workload_spec, ConfigHash, CpuUsage,
+            FreeMemory,
+        },
         test_utils::{self, generate_test_deleted_workload},
     };
 
@@ -308,17 +475,24 @@ mod tests {
     ///////////////////////////////////////////////////////////////////////////
     // ToServer tests
     ///////////////////////////////////////////////////////////////////////////
+    // [utest->swdd~agent-reports-version-and-runtimes~1]
     #[test]
     fn utest_convert_proto_to_server_agent_hello() {
         let agent_name = "agent_A".to_string();
 
         let proto_request = ToServer {
-            to_server_enum: Some(ToServerEnum::AgentHello(AgentHello::new(
-                &agent_name,
-            ))),
+            to_server_enum: Some(ToServerEnum::AgentHello(AgentHello {
+                runtimes: vec!["podman".to_string()],
+                ..AgentHello::new(&agent_name)
+            })),
         };
 
-        let ankaios_command = ankaios::ToServer::AgentHello(ankaios::AgentHello { agent_name });
+        let ankaios_command = ankaios::ToServer::AgentHello(ankaios::AgentHello {
+            agent_name,
+            labels: Default::default(),
+            version: common::ANKAIOS_VERSION.into(),
+            runtimes: vec!["podman".to_string()],
+        });
 
         assert_eq!(
             ankaios::ToServer::try_from(proto_request),
@@ -332,13 +506,34 @@ mod tests {
             agent_name: "agent_A".to_string(),
             cpu_usage: CpuUsage { cpu_usage: 42 },
             free_memory: FreeMemory { free_memory: 42 },
+            workload_resources: vec![generate_test_workload_resources("agent_A", "workload_1")],
+            disk_usage: Default::default(),
+            network_usage: Default::default(),
+            temperature: Default::default(),
+            image_states: Default::default(),
         };
 
+        // an agent not reporting the disk, network and temperature data results in empty values
         let proto_request = ToServer {
             to_server_enum: Some(ToServerEnum::AgentLoadStatus(AgentLoadStatus {
                 agent_name: agent_load_status.agent_name.clone(),
                 cpu_usage: Some(agent_load_status.cpu_usage.clone().into()),
                 free_memory: Some(agent_load_status.free_memory.clone().into()),
+                workload_resources: vec![WorkloadResources {
+                    instance_name: Some(ank_base::WorkloadInstanceName {
+                        agent_name: "agent_A".into(),
+                        workload_name: "workload_1".into(),
+                        id: "some_id".into(),
+                    }),
+                    resource_usage: Some(ank_base::ResourceUsage {
+                        cpu_usage: 42,
+                        memory_usage: 4096,
+                    }),
+                }],
+                disk_usage: None,
+                network_usage: None,
+                temperature: None,
+                image_states: Default::default(),
             })),
         };
 
@@ -346,6 +541,11 @@ mod tests {
             agent_name: agent_load_status.agent_name,
             cpu_usage: agent_load_status.cpu_usage,
             free_memory: agent_load_status.free_memory,
+            workload_resources: agent_load_status.workload_resources,
+            disk_usage: agent_load_status.disk_usage,
+            network_usage: agent_load_status.network_usage,
+            temperature: agent_load_status.temperature,
+            image_states: agent_load_status.image_states,
         });
 
         assert_eq!(
@@ -393,6 +593,8 @@ mod tests {
                         },
                         ..Default::default()
                     },
+                    dry_run: false,
+                    grace_period_secs: None,
                 },
             )),
         };
@@ -428,6 +630,8 @@ mod tests {
                             "test_workload",
                             workloads,
                         )])),
+                        dry_run: false,
+                        grace_period_secs: None,
                     }),
                 )),
             })),
@@ -447,6 +651,7 @@ mod tests {
                 request_content: Some(ank_base::request::RequestContent::CompleteStateRequest(
                     ank_base::CompleteStateRequest {
                         field_mask: field_mask.clone(),
+                        selector: vec![],
                     },
                 )),
             })),
@@ -455,7 +660,10 @@ mod tests {
         let ankaios_command = ankaios::ToServer::Request(ankaios::Request {
             request_id,
             request_content: ankaios::RequestContent::CompleteStateRequest(
-                ankaios::CompleteStateRequest { field_mask },
+                ankaios::CompleteStateRequest {
+                    field_mask,
+                    selector: vec![],
+                },
             ),
         });
 
@@ -526,13 +734,13 @@ mod tests {
         let proto_response = ank_base::Response {
             request_id: "req_id".to_owned(),
             response_content: Some(ank_base::response::ResponseContent::CompleteState(
-                ank_base::CompleteState {
+                Box::new(ank_base::CompleteState {
                     desired_state: Some(api::ank_base::State {
                         api_version: "v0.1".into(),
                         ..Default::default()
                     }),
                     ..Default::default()
-                },
+                }),
             )),
         };
 
@@ -604,6 +812,24 @@ mod tests {
                 value: "value".into(),
             }],
             control_interface_access: Default::default(),
+            hostname: None,
+            extra_hosts: vec![],
+            ports: vec![],
+            resource_thresholds: None,
+            resources: None,
+            health_check: None,
+            restart_backoff: None,
+            priority: None,
+            volumes: vec![],
+            registry_auth: None,
+            networks: vec![],
+            env: Default::default(),
+            on_agent_disconnect: None,
+            restart_on: vec![],
+            lifecycle_hooks: None,
+            node_selector: Default::default(),
+            state_debounce: None,
+            security_context: None,
         };
 
         assert_eq!(AddedWorkload::from(workload_spec), proto_workload);
@@ -631,6 +857,55 @@ mod tests {
             tags: vec![],
             runtime_config: String::from("some config"),
             control_interface_access: Default::default(),
+            hostname: Some(String::from("vehicle-1")),
+            extra_hosts: vec![ankaios::ExtraHost {
+                hostname: String::from("database"),
+                ip: String::from("10.0.0.5"),
+            }],
+            ports: vec![ankaios::Port {
+                host_port: 8080,
+                container_port: 80,
+                protocol: ankaios::PortProtocol::Udp,
+            }],
+            resource_thresholds: Some(ankaios::ResourceThresholds {
+                cpu_usage: Some(80),
+                memory_usage: Some(1024),
+            }),
+            resources: Some(ankaios::ResourceLimits {
+                cpu_quota: Some(150),
+                memory_limit: Some(2048),
+            }),
+            health_check: Some(ankaios::HealthCheck {
+                http: Some(ankaios::HttpProbe {
+                    url: "http://localhost:8080/health".into(),
+                }),
+                retries: Some(5),
+                ..Default::default()
+            }),
+            restart_backoff: None,
+            priority: None,
+            volumes: vec![ankaios::Volume {
+                volume_type: ankaios::VolumeType::Named,
+                source: "data".into(),
+                mount_path: "/var/lib/data".into(),
+                read_only: false,
+            }],
+            registry_auth: None,
+            networks: vec![ankaios::Network {
+                name: "backend".into(),
+                aliases: vec!["api".into()],
+            }],
+            env: HashMap::from([("LOG_LEVEL".into(), "debug".into())]),
+            on_agent_disconnect: None,
+            restart_on: vec![],
+            lifecycle_hooks: None,
+            node_selector: Default::default(),
+            state_debounce: None,
+            security_context: Some(ankaios::SecurityContext {
+                run_as_user: Some(1000),
+                no_new_privileges: true,
+                ..Default::default()
+            }),
         };
 
         let proto_workload = AddedWorkload {
@@ -654,6 +929,55 @@ mod tests {
             runtime_config: String::from("some config"),
             tags: vec![],
             control_interface_access: Default::default(),
+            hostname: Some(String::from("vehicle-1")),
+            extra_hosts: vec![ank_base::ExtraHost {
+                hostname: String::from("database"),
+                ip: String::from("10.0.0.5"),
+            }],
+            ports: vec![ank_base::Port {
+                host_port: 8080,
+                container_port: 80,
+                protocol: ank_base::PortProtocol::Udp as i32,
+            }],
+            resource_thresholds: Some(ank_base::ResourceThresholds {
+                cpu_usage: Some(80),
+                memory_usage: Some(1024),
+            }),
+            resources: Some(ank_base::ResourceLimits {
+                cpu_quota: Some(150),
+                memory_limit: Some(2048),
+            }),
+            health_check: Some(ank_base::HealthCheck {
+                http: Some(ank_base::HttpProbe {
+                    url: "http://localhost:8080/health".into(),
+                }),
+                retries: Some(5),
+                ..Default::default()
+            }),
+            restart_backoff: None,
+            priority: None,
+            volumes: vec![ank_base::Volume {
+                r#type: ank_base::VolumeType::Named as i32,
+                source: "data".into(),
+                mount_path: "/var/lib/data".into(),
+                read_only: false,
+            }],
+            registry_auth: None,
+            networks: vec![ank_base::Network {
+                name: "backend".into(),
+                aliases: vec!["api".into()],
+            }],
+            env: HashMap::from([("LOG_LEVEL".into(), "debug".into())]),
+            on_agent_disconnect: None,
+            restart_on: vec![],
+            lifecycle_hooks: None,
+            node_selector: Default::default(),
+            state_debounce: None,
+            security_context: Some(ank_base::SecurityContext {
+                run_as_user: Some(1000),
+                no_new_privileges: Some(true),
+                ..Default::default()
+            }),
         };
 
         assert_eq!(
@@ -685,6 +1009,24 @@ mod tests {
             runtime_config: String::from("some config"),
             tags: vec![],
             control_interface_access: Default::default(),
+            hostname: None,
+            extra_hosts: vec![],
+            ports: vec![],
+            resource_thresholds: None,
+            resources: None,
+            health_check: None,
+            restart_backoff: None,
+            priority: None,
+            volumes: vec![],
+            registry_auth: None,
+            networks: vec![],
+            env: Default::default(),
+            on_agent_disconnect: None,
+            restart_on: vec![],
+            lifecycle_hooks: None,
+            node_selector: Default::default(),
+            state_debounce: None,
+            security_context: None,
         };
 
         assert!(ankaios::WorkloadSpec::try_from(proto_workload).is_err());
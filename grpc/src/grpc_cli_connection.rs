@@ -14,6 +14,7 @@
 
 use std::pin::Pin;
 
+use common::request_id_prepending::CLI_CONNECTION_NAME_PREFIX;
 use common::std_extensions::GracefulExitResult;
 use common::{check_version_compatibility, to_server_interface};
 use tokio::sync::mpsc::Sender;
@@ -29,7 +30,7 @@ use grpc_api::cli_connection_server::CliConnection;
 
 use crate::grpc_api;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct GRPCCliConnection {
     cli_senders: AgentSendersMap,
     to_ankaios_server: Sender<to_server_interface::ToServer>,
@@ -64,7 +65,7 @@ impl CliConnection for GRPCCliConnection {
             Result<grpc_api::FromServer, tonic::Status>,
         >(common::CHANNEL_CAPACITY);
 
-        let cli_connection_name = format!("cli-conn-{}", uuid::Uuid::new_v4());
+        let cli_connection_name = format!("{}{}", CLI_CONNECTION_NAME_PREFIX, uuid::Uuid::new_v4());
         log::debug!("Connection to CLI (name={}) open.", cli_connection_name);
 
         let ankaios_tx = self.to_ankaios_server.clone();
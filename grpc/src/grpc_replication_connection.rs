@@ -0,0 +1,302 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::pin::Pin;
+use std::time::Duration;
+
+use api::ank_base;
+use common::check_version_compatibility;
+use tokio::sync::{mpsc, watch};
+use tokio_stream::wrappers::ReceiverStream;
+
+use tonic::codegen::futures_core::Stream;
+use tonic::transport::Certificate;
+use tonic::{Request, Response, Status};
+use x509_parser::certificate::X509Certificate;
+use x509_parser::der_parser::asn1_rs::FromDer;
+use x509_parser::extensions::GeneralName;
+
+use crate::grpc_api::{
+    self, replication::ReplicationEnum, replication_connection_server::ReplicationConnection,
+    LeaseRenewal, StandbyHello,
+};
+use crate::state_replicator::StateReplicator;
+
+// The lease timeout of a standby server must be longer than the renewal interval.
+pub const LEASE_RENEWAL_INTERVAL: Duration = Duration::from_secs(1);
+
+type ReplicationSender = mpsc::Sender<Result<grpc_api::Replication, Status>>;
+
+#[derive(Debug, Clone)]
+pub struct GRPCReplicationConnection {
+    state_replicator: StateReplicator,
+    allowed_standbys: Vec<String>,
+}
+
+impl GRPCReplicationConnection {
+    pub fn new(state_replicator: StateReplicator, allowed_standbys: Vec<String>) -> Self {
+        Self {
+            state_replicator,
+            allowed_standbys,
+        }
+    }
+
+    // With mTLS, the standby name must also be one of the DNS names the client certificate is issued for.
+    // [impl->swdd~grpc-replication-connection-checks-standby-identity~1]
+    fn check_standby_identity(
+        &self,
+        standby_name: &str,
+        peer_certs: Option<&[Certificate]>,
+    ) -> Result<(), String> {
+        if !self
+            .allowed_standbys
+            .iter()
+            .any(|allowed_standby| allowed_standby == standby_name)
+        {
+            return Err(format!(
+                "Standby server '{standby_name}' is not in the list of allowed standby servers!"
+            ));
+        }
+
+        if let Some(peer_certs) = peer_certs {
+            let [client_cert] = peer_certs else {
+                return Err("Ambiguous standby server certificates provided!".to_string());
+            };
+            if !subject_alt_dns_names(client_cert)?
+                .iter()
+                .any(|name| name == standby_name)
+            {
+                return Err(format!(
+                    "The certificate of standby server '{standby_name}' is not issued for its name!"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn subject_alt_dns_names(certificate: &Certificate) -> Result<Vec<String>, String> {
+    let (_, certificate) = X509Certificate::from_der(certificate.as_ref())
+        .map_err(|err| format!("Could not parse the certificate: '{err}'"))?;
+    let subject_alt_names = certificate
+        .subject_alternative_name()
+        .map_err(|err| format!("Could not get the subject alt names: '{err}'"))?
+        .ok_or_else(|| "No subject alt names found in the certificate!".to_string())?;
+
+    Ok(subject_alt_names
+        .value
+        .general_names
+        .iter()
+        .filter_map(|name| match name {
+            GeneralName::DNSName(name) => Some(name.to_string()),
+            _ => None,
+        })
+        .collect())
+}
+
+#[tonic::async_trait]
+impl ReplicationConnection for GRPCReplicationConnection {
+    type ConnectStandbyStream =
+        Pin<Box<dyn Stream<Item = Result<grpc_api::Replication, Status>> + Send + 'static>>;
+
+    // [impl->swdd~grpc-server-replicates-desired-state-to-standby~2]
+    async fn connect_standby(
+        &self,
+        request: Request<StandbyHello>,
+    ) -> Result<Response<Self::ConnectStandbyStream>, Status> {
+        let peer_certs = request.peer_certs();
+        let StandbyHello {
+            standby_name,
+            protocol_version,
+        } = request.into_inner();
+
+        // [impl->swdd~grpc-replication-connection-checks-version-compatibility~1]
+        check_version_compatibility(&protocol_version).map_err(|err| {
+            log::warn!(
+                "Refused standby server '{standby_name}' due to unsupported version: '{protocol_version}'"
+            );
+            Status::failed_precondition(err)
+        })?;
+
+        // [impl->swdd~grpc-replication-connection-checks-standby-identity~1]
+        self.check_standby_identity(&standby_name, peer_certs.as_deref().map(Vec::as_slice))
+            .map_err(|err| {
+                log::warn!("Refused standby server '{}': '{}'", standby_name, err);
+                Status::permission_denied(err)
+            })?;
+        log::info!("Standby server '{}' connected.", standby_name);
+
+        let (replication_tx, replication_rx) = mpsc::channel(common::CHANNEL_CAPACITY);
+        tokio::spawn(replicate_to_standby(
+            self.state_replicator.subscribe(),
+            replication_tx,
+            LEASE_RENEWAL_INTERVAL,
+            standby_name,
+        ));
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(replication_rx))))
+    }
+}
+
+async fn replicate_to_standby(
+    mut state_rx: watch::Receiver<Option<ank_base::State>>,
+    replication_tx: ReplicationSender,
+    lease_renewal_interval: Duration,
+    standby_name: String,
+) {
+    let mut lease_renewal_timer = tokio::time::interval(lease_renewal_interval);
+    // the currently published state is sent directly after the connection is established
+    state_rx.mark_changed();
+
+    loop {
+        let replication_enum = tokio::select! {
+            changed = state_rx.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+                match state_rx.borrow_and_update().clone() {
+                    Some(desired_state) => ReplicationEnum::DesiredState(Box::new(desired_state)),
+                    None => continue,
+                }
+            }
+            // [impl->swdd~grpc-server-renews-lease-of-primary~1]
+            _ = lease_renewal_timer.tick() => ReplicationEnum::LeaseRenewal(LeaseRenewal {}),
+        };
+
+        let replication = grpc_api::Replication {
+            replication_enum: Some(replication_enum),
+        };
+        if replication_tx.send(Ok(replication)).await.is_err() {
+            break;
+        }
+    }
+    log::info!("Standby server '{}' disconnected.", standby_name);
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use api::ank_base;
+    use tokio::sync::mpsc;
+
+    use tonic::{Code, Request};
+
+    use super::{replicate_to_standby, GRPCReplicationConnection};
+    use crate::grpc_api::{
+        replication::ReplicationEnum, replication_connection_server::ReplicationConnection,
+        LeaseRenewal, StandbyHello,
+    };
+    use crate::state_replicator::StateReplicator;
+
+    const STANDBY_NAME: &str = "standby";
+
+    async fn next_replication(
+        replication_rx: &mut mpsc::Receiver<Result<crate::grpc_api::Replication, tonic::Status>>,
+    ) -> ReplicationEnum {
+        replication_rx
+            .recv()
+            .await
+            .unwrap()
+            .unwrap()
+            .replication_enum
+            .unwrap()
+    }
+
+    // [utest->swdd~grpc-replication-connection-checks-standby-identity~1]
+    #[tokio::test]
+    async fn utest_connect_standby_accepts_allowed_standby() {
+        let replication_connection =
+            GRPCReplicationConnection::new(StateReplicator::default(), vec![STANDBY_NAME.into()]);
+
+        let result = replication_connection
+            .connect_standby(Request::new(StandbyHello::new(STANDBY_NAME)))
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    // [utest->swdd~grpc-replication-connection-checks-standby-identity~1]
+    #[tokio::test]
+    async fn utest_connect_standby_refuses_not_allowed_standby() {
+        let replication_connection =
+            GRPCReplicationConnection::new(StateReplicator::default(), vec![STANDBY_NAME.into()]);
+
+        let result = replication_connection
+            .connect_standby(Request::new(StandbyHello::new("other_standby")))
+            .await;
+
+        assert!(matches!(result, Err(status) if status.code() == Code::PermissionDenied));
+    }
+
+    // [utest->swdd~grpc-server-replicates-desired-state-to-standby~2]
+    // [utest->swdd~grpc-server-renews-lease-of-primary~1]
+    #[tokio::test]
+    async fn utest_replicate_to_standby_sends_current_and_changed_states() {
+        let state_replicator = StateReplicator::default();
+        let initial_state = ank_base::State {
+            api_version: "v0.1".into(),
+            ..Default::default()
+        };
+        state_replicator.publish(initial_state.clone());
+
+        let (replication_tx, mut replication_rx) = mpsc::channel(common::CHANNEL_CAPACITY);
+        let replication_task = tokio::spawn(replicate_to_standby(
+            state_replicator.subscribe(),
+            replication_tx,
+            Duration::from_secs(3600),
+            STANDBY_NAME.into(),
+        ));
+
+        // the first tick of the lease renewal timer completes immediately
+        let mut first_messages = vec![
+            next_replication(&mut replication_rx).await,
+            next_replication(&mut replication_rx).await,
+        ];
+        first_messages.sort_by_key(|message| matches!(message, ReplicationEnum::LeaseRenewal(_)));
+        assert_eq!(
+            first_messages,
+            vec![
+                ReplicationEnum::DesiredState(Box::new(initial_state)),
+                ReplicationEnum::LeaseRenewal(LeaseRenewal {})
+            ]
+        );
+
+        let changed_state = ank_base::State {
+            api_version: "v0.2".into(),
+            ..Default::default()
+        };
+        state_replicator.publish(changed_state.clone());
+        assert_eq!(
+            next_replication(&mut replication_rx).await,
+            ReplicationEnum::DesiredState(Box::new(changed_state))
+        );
+
+        drop(replication_rx);
+        state_replicator.publish(ank_base::State::default());
+        assert!(
+            tokio::time::timeout(Duration::from_secs(1), replication_task)
+                .await
+                .is_ok()
+        );
+    }
+}
@@ -129,12 +129,20 @@ pub mod security {
     }
 }
 
+mod agent_allow_list;
 mod agent_senders_map;
+mod certificate_watcher;
 pub mod client;
+pub mod connection_config;
 mod from_server_proxy;
 mod grpc_agent_connection;
 mod grpc_cli_connection;
+mod grpc_replication_connection;
+mod proxy;
+pub mod replication_client;
 pub mod server;
+mod state_replicator;
+mod stream_error_counter;
 mod to_server_proxy;
 
 use api::ank_base;
@@ -0,0 +1,230 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+
+use api::ank_base;
+use common::communications_error::CommunicationMiddlewareError;
+use common::std_extensions::IllegalStateResult;
+use regex::Regex;
+use tokio::sync::mpsc;
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Identity};
+
+use crate::connection_config::{with_message_config, ConnectionConfig, ReconnectBackoff};
+use crate::grpc_api::{
+    replication::ReplicationEnum, replication_connection_client::ReplicationConnectionClient,
+    StandbyHello,
+};
+use crate::grpc_middleware_error::GrpcMiddlewareError;
+use crate::security::{read_pem_file, TLSConfig};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplicationEvent {
+    DesiredState(Box<ank_base::State>),
+    LeaseRenewal,
+}
+
+impl From<ReplicationEnum> for ReplicationEvent {
+    fn from(item: ReplicationEnum) -> Self {
+        match item {
+            ReplicationEnum::DesiredState(desired_state) => {
+                ReplicationEvent::DesiredState(desired_state)
+            }
+            ReplicationEnum::LeaseRenewal(_) => ReplicationEvent::LeaseRenewal,
+        }
+    }
+}
+
+/// Connects a standby server to the primary server and receives the replicated desired state
+pub struct GRPCReplicationClient {
+    name: String,
+    server_address: String,
+    tls_config: Option<TLSConfig>,
+    connection_config: ConnectionConfig,
+}
+
+impl GRPCReplicationClient {
+    pub fn new(
+        name: String,
+        server_address: String,
+        tls_config: Option<TLSConfig>,
+    ) -> Result<Self, CommunicationMiddlewareError> {
+        // the primary server is expected on another host, unix domain sockets are not supported
+        let re = Regex::new(r"^https?:\/\/.+").unwrap_or_illegal_state();
+        if !re.is_match(&server_address) {
+            return Err(CommunicationMiddlewareError(format!(
+                "Wrong primary server address format: '{}'.",
+                server_address
+            )));
+        }
+
+        Ok(Self {
+            name,
+            server_address: match tls_config {
+                Some(_) => server_address,
+                None => server_address.replace("https:", "http:"),
+            },
+            tls_config,
+            connection_config: ConnectionConfig::default(),
+        })
+    }
+
+    // [impl->swdd~grpc-supports-connection-tuning~1]
+    pub fn with_connection_config(mut self, connection_config: ConnectionConfig) -> Self {
+        self.connection_config = connection_config;
+        self
+    }
+
+    /// Forwards the replication received from the primary server until the receiver of
+    /// the events is dropped. An interrupted connection is established again.
+    // [impl->swdd~grpc-replication-client-retries-connection~1]
+    pub async fn run(
+        &self,
+        events_tx: mpsc::Sender<ReplicationEvent>,
+    ) -> Result<(), CommunicationMiddlewareError> {
+        let mut reconnect_backoff = ReconnectBackoff::new(&self.connection_config);
+
+        loop {
+            let result = self.run_internal(&events_tx).await;
+
+            if let Err(GrpcMiddlewareError::VersionMismatch(err)) = result {
+                return Err(CommunicationMiddlewareError(format!(
+                    "Ankaios version mismatch: '{}'.",
+                    err
+                )));
+            }
+            if events_tx.is_closed() {
+                return Ok(());
+            }
+
+            log::warn!(
+                "Connection to the primary server interrupted: '{:?}'",
+                result
+            );
+            if !matches!(result, Err(GrpcMiddlewareError::ServerNotAvailable(_))) {
+                reconnect_backoff.reset();
+            }
+            tokio::time::sleep(reconnect_backoff.next_delay()).await;
+        }
+    }
+
+    async fn run_internal(
+        &self,
+        events_tx: &mpsc::Sender<ReplicationEvent>,
+    ) -> Result<(), GrpcMiddlewareError> {
+        // [impl->swdd~grpc-supports-message-size-and-compression~1]
+        let mut client = with_message_config!(
+            ReplicationConnectionClient::new(self.connect_channel().await?),
+            &self.connection_config
+        );
+        let mut replication_stream = client
+            .connect_standby(StandbyHello::new(&self.name))
+            .await?
+            .into_inner();
+        log::info!("Connected to the primary server '{}'.", self.server_address);
+
+        while let Some(replication) = replication_stream.message().await? {
+            let Some(replication_enum) = replication.replication_enum else {
+                continue;
+            };
+            events_tx
+                .send(replication_enum.into())
+                .await
+                .map_err(|err| GrpcMiddlewareError::SendError(err.to_string()))?;
+        }
+        Err(GrpcMiddlewareError::ConnectionInterrupted(
+            "The primary server closed the replication.".into(),
+        ))
+    }
+
+    async fn connect_channel(&self) -> Result<Channel, GrpcMiddlewareError> {
+        let mut endpoint = Channel::from_shared(self.server_address.clone())
+            .map_err(|err| GrpcMiddlewareError::ServerNotAvailable(err.to_string()))?;
+
+        // [impl->swdd~grpc-supports-connection-tuning~1]
+        if let Some(keepalive_interval) = self.connection_config.keepalive_interval() {
+            endpoint = endpoint
+                .http2_keep_alive_interval(keepalive_interval)
+                .keep_alive_timeout(self.connection_config.keepalive_timeout());
+        }
+
+        if let Some(tls_config) = &self.tls_config {
+            // [impl->swdd~grpc-supports-pem-file-format-for-X509-certificates~1]
+            let ca_pem = read_pem_file(Path::new(&tls_config.path_to_ca_pem), false)?;
+            let client_cert_pem = read_pem_file(Path::new(&tls_config.path_to_crt_pem), false)?;
+            // [impl->swdd~grpc-supports-pem-file-format-for-keys~1]
+            let client_key_pem = read_pem_file(Path::new(&tls_config.path_to_key_pem), true)?;
+
+            let tls = ClientTlsConfig::new()
+                .domain_name("ank-server")
+                .ca_certificate(Certificate::from_pem(ca_pem))
+                .identity(Identity::from_pem(client_cert_pem, client_key_pem));
+            endpoint = endpoint.tls_config(tls)?;
+        }
+
+        Ok(endpoint.connect().await?)
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use api::ank_base;
+
+    use super::{GRPCReplicationClient, ReplicationEvent};
+    use crate::grpc_api::{replication::ReplicationEnum, LeaseRenewal};
+
+    const STANDBY_NAME: &str = "standby";
+
+    #[test]
+    fn utest_replication_client_rejects_unix_socket_address() {
+        assert!(GRPCReplicationClient::new(
+            STANDBY_NAME.into(),
+            "unix:///run/ankaios/server.sock".into(),
+            None
+        )
+        .is_err());
+        assert!(GRPCReplicationClient::new(
+            STANDBY_NAME.into(),
+            "https://primary:25551".into(),
+            None
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn utest_replication_event_from_proto() {
+        let desired_state = ank_base::State {
+            api_version: "v0.1".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            ReplicationEvent::from(ReplicationEnum::DesiredState(Box::new(
+                desired_state.clone()
+            ))),
+            ReplicationEvent::DesiredState(Box::new(desired_state))
+        );
+        assert_eq!(
+            ReplicationEvent::from(ReplicationEnum::LeaseRenewal(LeaseRenewal {})),
+            ReplicationEvent::LeaseRenewal
+        );
+    }
+}
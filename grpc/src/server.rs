@@ -14,17 +14,30 @@
 
 use crate::grpc_api::cli_connection_server::CliConnectionServer;
 use common::communications_error::CommunicationMiddlewareError;
-use common::communications_server::CommunicationsServer;
+use common::communications_server::{CommunicationsServer, ServerAddress};
 
-use tonic::transport::{Certificate, Identity, Server};
+use tokio::net::UnixListener;
+use tokio_stream::wrappers::UnixListenerStream;
+use tonic::transport::server::Router;
+use tonic::transport::{Certificate, Identity, Server, ServerTlsConfig};
 
-use std::net::SocketAddr;
+use std::fs::{DirBuilder, Permissions};
+use std::io::ErrorKind;
+use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
 use std::path::Path;
 
+pub use crate::agent_allow_list::AgentAllowList;
 use crate::agent_senders_map::AgentSendersMap;
+pub use crate::agent_senders_map::DuplicateAgentPolicy;
+use crate::certificate_watcher::CertificateWatcher;
+use crate::connection_config::{with_message_config, ConnectionConfig};
 use crate::grpc_api::agent_connection_server::AgentConnectionServer;
+use crate::grpc_api::replication_connection_server::ReplicationConnectionServer;
 use crate::grpc_cli_connection::GRPCCliConnection;
 use crate::grpc_middleware_error::GrpcMiddlewareError;
+use crate::grpc_replication_connection::GRPCReplicationConnection;
+pub use crate::state_replicator::StateReplicator;
+pub use crate::stream_error_counter::StreamErrorCounter;
 
 use crate::security::{read_pem_file, TLSConfig};
 
@@ -36,11 +49,22 @@ use common::to_server_interface::ToServerSender;
 
 use async_trait::async_trait;
 
+// Read and write access for the owner and the group of the server process.
+const UNIX_SOCKET_PERMISSIONS: u32 = 0o660;
+const BIND_DIR_PERMISSIONS: u32 = 0o700;
+const BIND_DIR_SUFFIX_LENGTH: usize = 8;
+
 #[derive(Debug)]
 pub struct GRPCCommunicationsServer {
     sender: ToServerSender,
     agent_senders: AgentSendersMap,
     tls_config: Option<TLSConfig>,
+    duplicate_agent_policy: DuplicateAgentPolicy,
+    agent_allow_list: AgentAllowList,
+    stream_errors: StreamErrorCounter,
+    connection_config: ConnectionConfig,
+    state_replicator: Option<StateReplicator>,
+    allowed_standbys: Vec<String>,
 }
 
 #[async_trait]
@@ -48,61 +72,89 @@ impl CommunicationsServer for GRPCCommunicationsServer {
     async fn start(
         &mut self,
         mut receiver: FromServerReceiver,
-        addr: SocketAddr,
+        addr: ServerAddress,
     ) -> Result<(), CommunicationMiddlewareError> {
         // [impl->swdd~grpc-server-creates-agent-connection~1]
-        let my_connection =
-            GRPCAgentConnection::new(self.agent_senders.clone(), self.sender.clone());
+        let my_connection = GRPCAgentConnection::new(
+            self.agent_senders.clone(),
+            self.sender.clone(),
+            self.duplicate_agent_policy,
+            self.agent_allow_list.clone(),
+            self.stream_errors.clone(),
+        );
 
         // [impl->swdd~grpc-server-creates-cli-connection~1]
         let my_cli_connection =
             GRPCCliConnection::new(self.agent_senders.clone(), self.sender.clone());
 
+        // [impl->swdd~grpc-server-replicates-desired-state-to-standby~2]
+        let my_replication_connection = self.state_replicator.clone().map(|state_replicator| {
+            with_message_config!(
+                ReplicationConnectionServer::new(GRPCReplicationConnection::new(
+                    state_replicator,
+                    self.allowed_standbys.clone()
+                )),
+                &self.connection_config
+            )
+        });
+
+        // [impl->swdd~grpc-supports-message-size-and-compression~1]
+        let agent_connection_server = with_message_config!(
+            AgentConnectionServer::new(my_connection),
+            &self.connection_config
+        );
+        let cli_connection_server = with_message_config!(
+            CliConnectionServer::new(my_cli_connection),
+            &self.connection_config
+        );
+
         let agent_senders_clone = self.agent_senders.clone();
 
         match &self.tls_config {
             // [impl->swdd~grpc-server-activate-mtls-when-certificates-and-key-provided-upon-start~1]
             Some(tls_config) => {
-                let ca_pem = &tls_config.path_to_ca_pem;
-                let crt_pem = &tls_config.path_to_crt_pem;
-                let key_pem = &tls_config.path_to_key_pem;
-
-                // [impl->swdd~grpc-supports-pem-file-format-for-X509-certificates~1]
-                let ca = read_pem_file(Path::new(ca_pem), false)
-                    .map_err(|err| CommunicationMiddlewareError(err.to_string()))?;
-                // [impl->swdd~grpc-supports-pem-file-format-for-X509-certificates~1]
-                let cert = read_pem_file(Path::new(crt_pem), false)
-                    .map_err(|err| CommunicationMiddlewareError(err.to_string()))?;
-                let key = read_pem_file(Path::new(key_pem), true)
+                let mut certificate_watcher = CertificateWatcher::new(tls_config);
+                let mut tls = load_server_tls_config(tls_config)
                     .map_err(|err| CommunicationMiddlewareError(err.to_string()))?;
 
-                let server_identity = Identity::from_pem(cert, key);
-                let tls = tonic::transport::ServerTlsConfig::new()
-                    .client_ca_root(Certificate::from_pem(ca))
-                    .identity(server_identity);
-                tokio::select! {
-                    // [impl->swdd~grpc-server-spawns-tonic-service~1]
-                    // [impl->swdd~grpc-delegate-workflow-to-external-library~1]
-                    result = Server::builder()
-                        .tls_config(tls).map_err(|err| CommunicationMiddlewareError(err.to_string()))?
-                        .add_service(AgentConnectionServer::new(my_connection))
-                        // [impl->swdd~grpc-server-provides-endpoint-for-cli-connection-handling~1]
-                        .add_service(CliConnectionServer::new(my_cli_connection))
-                        .serve(addr) => {
-                            result.map_err(|err| {
-                                GrpcMiddlewareError::StartError(format!("{err:?}"))
-                            })?
+                // [impl->swdd~grpc-server-forwards-from-server-messages-to-grpc-client~1]
+                let forward_from_server_task = from_server_proxy::forward_from_ankaios_to_proto(
+                    &agent_senders_clone,
+                    &mut receiver,
+                );
+                tokio::pin!(forward_from_server_task);
+
+                loop {
+                    tokio::select! {
+                        // [impl->swdd~grpc-server-spawns-tonic-service~1]
+                        // [impl->swdd~grpc-delegate-workflow-to-external-library~1]
+                        result = serve(server_builder(&self.connection_config)
+                            .tls_config(tls.clone()).map_err(|err| CommunicationMiddlewareError(err.to_string()))?
+                            .add_service(agent_connection_server.clone())
+                            // [impl->swdd~grpc-server-provides-endpoint-for-cli-connection-handling~1]
+                            .add_service(cli_connection_server.clone())
+                            .add_optional_service(my_replication_connection.clone()), &addr) => {
+                                result?;
+                                break;
+                            }
+                        _ = &mut forward_from_server_task => {
+                            Err(GrpcMiddlewareError::ConnectionInterrupted(
+                                "Connection between Ankaios server and the communication middleware dropped.".into())
+                            )?
+                        }
+                        // [impl->swdd~grpc-server-reloads-changed-certificates~1]
+                        _ = certificate_watcher.changed() => {
+                            match load_server_tls_config(tls_config) {
+                                Ok(new_tls) => {
+                                    log::info!("The certificates changed, restarting the gRPC server with the new certificates.");
+                                    tls = new_tls;
+                                }
+                                Err(err) => {
+                                    log::warn!("Could not reload the changed certificates, keeping the current ones: '{err}'");
+                                }
+                            }
                         }
-                    // [impl->swdd~grpc-server-forwards-from-server-messages-to-grpc-client~1]
-                    _ = from_server_proxy::forward_from_ankaios_to_proto(
-                        &agent_senders_clone,
-                        &mut receiver,
-                    ) => {
-                        Err(GrpcMiddlewareError::ConnectionInterrupted(
-                            "Connection between Ankaios server and the communication middleware dropped.".into())
-                        )?
                     }
-
                 }
             }
             // [impl->swdd~grpc-server-deactivate-mtls-when-no-certificates-and-no-key-provided-upon-start~1]
@@ -113,14 +165,12 @@ impl CommunicationsServer for GRPCCommunicationsServer {
                 tokio::select! {
                     // [impl->swdd~grpc-server-spawns-tonic-service~1]
                     // [impl->swdd~grpc-delegate-workflow-to-external-library~1]
-                    result = Server::builder()
-                        .add_service(AgentConnectionServer::new(my_connection))
+                    result = serve(server_builder(&self.connection_config)
+                        .add_service(agent_connection_server)
                         // [impl->swdd~grpc-server-provides-endpoint-for-cli-connection-handling~1]
-                        .add_service(CliConnectionServer::new(my_cli_connection))
-                        .serve(addr) => {
-                            result.map_err(|err| {
-                                GrpcMiddlewareError::StartError(format!("{err:?}"))
-                            })?
+                        .add_service(cli_connection_server)
+                        .add_optional_service(my_replication_connection), &addr) => {
+                            result?
                         }
                     // [impl->swdd~grpc-server-forwards-from-server-messages-to-grpc-client~1]
                     _ = from_server_proxy::forward_from_ankaios_to_proto(
@@ -139,12 +189,293 @@ impl CommunicationsServer for GRPCCommunicationsServer {
     }
 }
 
+// [impl->swdd~grpc-supports-connection-tuning~1]
+fn server_builder(connection_config: &ConnectionConfig) -> Server {
+    Server::builder()
+        .http2_keepalive_interval(connection_config.keepalive_interval())
+        .http2_keepalive_timeout(Some(connection_config.keepalive_timeout()))
+}
+
+async fn serve(router: Router, addr: &ServerAddress) -> Result<(), GrpcMiddlewareError> {
+    match addr {
+        ServerAddress::Tcp(socket_addr) => router.serve(*socket_addr).await,
+        // [impl->swdd~grpc-server-listens-on-unix-domain-socket~4]
+        ServerAddress::UnixSocket(socket_path) => {
+            let listener = bind_unix_socket(socket_path)?;
+            router
+                .serve_with_incoming(UnixListenerStream::new(listener))
+                .await
+        }
+    }
+    .map_err(|err| GrpcMiddlewareError::StartError(format!("{err:?}")))
+}
+
+// The socket is bound inside a private directory and moved to the given path only after its
+// permissions are restricted, as the permissions are the only access control of an insecure
+// server on a socket. Changing the umask instead would affect all threads of the process.
+fn bind_unix_socket(socket_path: &Path) -> Result<UnixListener, GrpcMiddlewareError> {
+    remove_stale_socket(socket_path)?;
+
+    let parent_dir = match socket_path.parent() {
+        Some(parent_dir) if !parent_dir.as_os_str().is_empty() => parent_dir,
+        _ => Path::new("."),
+    };
+    let bind_dir = parent_dir.join(format!(
+        ".ank-bind-{}",
+        &uuid::Uuid::new_v4().simple().to_string()[..BIND_DIR_SUFFIX_LENGTH]
+    ));
+    DirBuilder::new()
+        .mode(BIND_DIR_PERMISSIONS)
+        .create(&bind_dir)
+        .map_err(|err| {
+            GrpcMiddlewareError::StartError(format!(
+                "Could not create the directory '{}' for binding the socket: '{}'",
+                bind_dir.display(),
+                err
+            ))
+        })?;
+
+    let result = bind_unix_socket_in_dir(socket_path, &bind_dir);
+    if let Err(err) = std::fs::remove_dir_all(&bind_dir) {
+        log::warn!(
+            "Could not remove the directory '{}' used for binding the socket: '{}'",
+            bind_dir.display(),
+            err
+        );
+    }
+    result
+}
+
+fn bind_unix_socket_in_dir(
+    socket_path: &Path,
+    bind_dir: &Path,
+) -> Result<UnixListener, GrpcMiddlewareError> {
+    let bind_path = bind_dir.join("socket");
+    let listener = UnixListener::bind(&bind_path).map_err(|err| {
+        GrpcMiddlewareError::StartError(format!(
+            "Could not bind the socket '{}': '{}'",
+            socket_path.display(),
+            err
+        ))
+    })?;
+
+    std::fs::set_permissions(&bind_path, Permissions::from_mode(UNIX_SOCKET_PERMISSIONS)).map_err(
+        |err| {
+            GrpcMiddlewareError::StartError(format!(
+                "Could not set the permissions of the socket '{}': '{}'",
+                socket_path.display(),
+                err
+            ))
+        },
+    )?;
+
+    // Linking fails if the path was created in the meantime, e.g., by another server, which a
+    // rename would silently replace.
+    std::fs::hard_link(&bind_path, socket_path).map_err(|err| {
+        GrpcMiddlewareError::StartError(match err.kind() {
+            ErrorKind::AlreadyExists => format!(
+                "Could not bind the socket '{}': the path was created by another process",
+                socket_path.display()
+            ),
+            _ => format!(
+                "Could not move the socket to '{}': '{}'",
+                socket_path.display(),
+                err
+            ),
+        })
+    })?;
+    if let Err(err) = std::fs::remove_file(&bind_path) {
+        log::warn!(
+            "Could not remove the socket '{}' used for binding: '{}'",
+            bind_path.display(),
+            err
+        );
+    }
+
+    Ok(listener)
+}
+
+// A socket file left over from a previous run prevents binding the socket again.
+// Other files and sockets of running servers are never removed.
+fn remove_stale_socket(socket_path: &Path) -> Result<(), GrpcMiddlewareError> {
+    let Ok(metadata) = std::fs::symlink_metadata(socket_path) else {
+        return Ok(());
+    };
+
+    if !metadata.file_type().is_socket() {
+        return Err(GrpcMiddlewareError::StartError(format!(
+            "Could not bind the socket '{}': the path exists and is not a socket",
+            socket_path.display()
+        )));
+    }
+
+    if std::os::unix::net::UnixStream::connect(socket_path).is_ok() {
+        return Err(GrpcMiddlewareError::StartError(format!(
+            "Could not bind the socket '{}': the socket is in use by another process",
+            socket_path.display()
+        )));
+    }
+
+    std::fs::remove_file(socket_path).map_err(|err| {
+        GrpcMiddlewareError::StartError(format!(
+            "Could not remove the existing socket '{}': '{}'",
+            socket_path.display(),
+            err
+        ))
+    })
+}
+
+fn load_server_tls_config(tls_config: &TLSConfig) -> Result<ServerTlsConfig, GrpcMiddlewareError> {
+    // [impl->swdd~grpc-supports-pem-file-format-for-X509-certificates~1]
+    let ca = read_pem_file(Path::new(&tls_config.path_to_ca_pem), false)?;
+    // [impl->swdd~grpc-supports-pem-file-format-for-X509-certificates~1]
+    let cert = read_pem_file(Path::new(&tls_config.path_to_crt_pem), false)?;
+    let key = read_pem_file(Path::new(&tls_config.path_to_key_pem), true)?;
+
+    let server_identity = Identity::from_pem(cert, key);
+    Ok(ServerTlsConfig::new()
+        .client_ca_root(Certificate::from_pem(ca))
+        .identity(server_identity))
+}
+
 impl GRPCCommunicationsServer {
     pub fn new(sender: ToServerSender, tls_config: Option<TLSConfig>) -> Self {
         GRPCCommunicationsServer {
             agent_senders: AgentSendersMap::new(),
             sender,
             tls_config,
+            duplicate_agent_policy: DuplicateAgentPolicy::default(),
+            agent_allow_list: AgentAllowList::default(),
+            stream_errors: StreamErrorCounter::default(),
+            connection_config: ConnectionConfig::default(),
+            state_replicator: None,
+            allowed_standbys: Vec::new(),
         }
     }
+
+    // [impl->swdd~grpc-supports-connection-tuning~1]
+    pub fn with_connection_config(mut self, connection_config: ConnectionConfig) -> Self {
+        self.connection_config = connection_config;
+        self
+    }
+
+    pub fn with_duplicate_agent_policy(mut self, policy: DuplicateAgentPolicy) -> Self {
+        self.duplicate_agent_policy = policy;
+        self
+    }
+
+    pub fn with_agent_allow_list(mut self, agent_allow_list: AgentAllowList) -> Self {
+        self.agent_allow_list = agent_allow_list;
+        self
+    }
+
+    // The replication connection is only served if a state replicator is provided
+    // [impl->swdd~grpc-server-replicates-desired-state-to-standby~2]
+    pub fn with_state_replicator(
+        mut self,
+        state_replicator: StateReplicator,
+        allowed_standbys: Vec<String>,
+    ) -> Self {
+        self.state_replicator = Some(state_replicator);
+        self.allowed_standbys = allowed_standbys;
+        self
+    }
+
+    pub fn stream_error_counter(&self) -> StreamErrorCounter {
+        self.stream_errors.clone()
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::fs::PermissionsExt;
+
+    use tempfile::TempDir;
+
+    use super::{bind_unix_socket, bind_unix_socket_in_dir, UNIX_SOCKET_PERMISSIONS};
+
+    // [utest->swdd~grpc-server-listens-on-unix-domain-socket~4]
+    #[tokio::test]
+    async fn utest_bind_unix_socket_sets_permissions() {
+        let dir = TempDir::new().unwrap();
+        let socket_path = dir.path().join("server.sock");
+
+        let _listener = bind_unix_socket(&socket_path).unwrap();
+
+        let mode = std::fs::metadata(&socket_path)
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, UNIX_SOCKET_PERMISSIONS);
+    }
+
+    // [utest->swdd~grpc-server-listens-on-unix-domain-socket~4]
+    #[tokio::test]
+    async fn utest_bind_unix_socket_removes_bind_directory() {
+        let dir = TempDir::new().unwrap();
+        let socket_path = dir.path().join("server.sock");
+
+        let _listener = bind_unix_socket(&socket_path).unwrap();
+
+        let entries = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect::<Vec<_>>();
+        assert_eq!(entries, vec!["server.sock"]);
+        assert!(std::os::unix::net::UnixStream::connect(&socket_path).is_ok());
+    }
+
+    // [utest->swdd~grpc-server-listens-on-unix-domain-socket~4]
+    #[tokio::test]
+    async fn utest_bind_unix_socket_replaces_stale_socket() {
+        let dir = TempDir::new().unwrap();
+        let socket_path = dir.path().join("server.sock");
+        drop(std::os::unix::net::UnixListener::bind(&socket_path).unwrap());
+
+        assert!(bind_unix_socket(&socket_path).is_ok());
+    }
+
+    // [utest->swdd~grpc-server-listens-on-unix-domain-socket~4]
+    #[tokio::test]
+    async fn utest_bind_unix_socket_keeps_socket_in_use() {
+        let dir = TempDir::new().unwrap();
+        let socket_path = dir.path().join("server.sock");
+        let _listener = std::os::unix::net::UnixListener::bind(&socket_path).unwrap();
+
+        assert!(bind_unix_socket(&socket_path).is_err());
+        assert!(socket_path.exists());
+    }
+
+    // [utest->swdd~grpc-server-listens-on-unix-domain-socket~4]
+    #[tokio::test]
+    async fn utest_bind_unix_socket_keeps_other_files() {
+        let dir = TempDir::new().unwrap();
+        let socket_path = dir.path().join("server.sock");
+        std::fs::write(&socket_path, "no socket").unwrap();
+
+        assert!(bind_unix_socket(&socket_path).is_err());
+        assert_eq!(std::fs::read_to_string(&socket_path).unwrap(), "no socket");
+    }
+
+    // [utest->swdd~grpc-server-listens-on-unix-domain-socket~4]
+    #[tokio::test]
+    async fn utest_bind_unix_socket_keeps_path_created_after_removing_stale_socket() {
+        let dir = TempDir::new().unwrap();
+        let socket_path = dir.path().join("server.sock");
+        let bind_dir = dir.path().join("bind");
+        std::fs::create_dir(&bind_dir).unwrap();
+        // created by another process between the check for a stale socket and the binding
+        let _other_listener = std::os::unix::net::UnixListener::bind(&socket_path).unwrap();
+
+        assert!(bind_unix_socket_in_dir(&socket_path, &bind_dir).is_err());
+        assert!(std::os::unix::net::UnixStream::connect(&socket_path).is_ok());
+    }
 }
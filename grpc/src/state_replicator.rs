@@ -0,0 +1,81 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use api::ank_base;
+use tokio::sync::watch;
+
+/// Publishes the desired state of the primary server to the connected standby servers
+///
+/// The replicator is shared between the Ankaios server, which publishes every change
+/// of the desired state, and the gRPC Server, which forwards the latest published
+/// state to the standby servers. Only the latest state is kept, such that a slow
+/// standby server skips intermediate states instead of slowing down the primary.
+// [impl->swdd~grpc-server-replicates-desired-state-to-standby~2]
+#[derive(Debug, Clone)]
+pub struct StateReplicator(Arc<watch::Sender<Option<ank_base::State>>>);
+
+impl Default for StateReplicator {
+    fn default() -> Self {
+        StateReplicator(Arc::new(watch::channel(None).0))
+    }
+}
+
+impl StateReplicator {
+    pub fn publish(&self, desired_state: ank_base::State) {
+        self.0.send_replace(Some(desired_state));
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<Option<ank_base::State>> {
+        self.0.subscribe()
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::StateReplicator;
+    use api::ank_base;
+
+    // [utest->swdd~grpc-server-replicates-desired-state-to-standby~2]
+    #[test]
+    fn utest_state_replicator_keeps_latest_published_state() {
+        let replicator = StateReplicator::default();
+        let mut subscriber = replicator.clone().subscribe();
+        assert_eq!(*subscriber.borrow_and_update(), None);
+
+        replicator.publish(ank_base::State {
+            api_version: "v0.1".into(),
+            ..Default::default()
+        });
+        replicator.publish(ank_base::State {
+            api_version: "v0.2".into(),
+            ..Default::default()
+        });
+
+        assert!(subscriber.has_changed().unwrap());
+        assert_eq!(
+            subscriber.borrow_and_update().as_ref().unwrap().api_version,
+            "v0.2"
+        );
+    }
+}
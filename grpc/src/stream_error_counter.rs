@@ -0,0 +1,61 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+/// Counts the agent connections interrupted with an error
+///
+/// The counter is shared between the gRPC Server and its clones, such that
+/// the count can be read outside of the communication middleware.
+// [impl->swdd~grpc-server-counts-stream-errors~1]
+#[derive(Debug, Clone, Default)]
+pub struct StreamErrorCounter(Arc<AtomicU64>);
+
+impl StreamErrorCounter {
+    pub fn increment(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::StreamErrorCounter;
+
+    // [utest->swdd~grpc-server-counts-stream-errors~1]
+    #[test]
+    fn utest_stream_error_counter_shared_between_clones() {
+        let counter = StreamErrorCounter::default();
+        let cloned_counter = counter.clone();
+
+        cloned_counter.increment();
+        cloned_counter.increment();
+
+        assert_eq!(counter.get(), 2);
+    }
+}
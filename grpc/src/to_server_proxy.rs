@@ -16,9 +16,7 @@ use crate::ankaios_streaming::GRPCStreaming;
 use crate::grpc_middleware_error::GrpcMiddlewareError;
 
 use crate::grpc_api::{self, to_server::ToServerEnum};
-use api::ank_base::{
-    self, request::RequestContent, CompleteStateRequest, Request, UpdateStateRequest,
-};
+use api::ank_base::{request::RequestContent, Request, UpdateStateRequest};
 
 use common::request_id_prepending::prepend_request_id;
 use common::to_server_interface::{ToServer, ToServerInterface, ToServerReceiver, ToServerSender};
@@ -75,12 +73,28 @@ pub async fn forward_from_proto_to_ankaios(
                         let UpdateStateRequest {
                             new_state,
                             update_mask,
+                            dry_run,
+                            grace_period_secs,
                         } = *update_state_request;
                         log::debug!("Received UpdateStateRequest from '{}'", agent_name);
                         match new_state.unwrap_or_default().try_into() {
+                            Ok(new_state) if dry_run => {
+                                sink.update_state_dry_run(request_id, new_state, update_mask)
+                                    .await?;
+                            }
                             Ok(new_state) => {
-                                sink.update_state(request_id, new_state, update_mask)
+                                if let Some(grace_period_secs) = grace_period_secs {
+                                    sink.update_state_with_grace_period(
+                                        request_id,
+                                        new_state,
+                                        update_mask,
+                                        grace_period_secs,
+                                    )
                                     .await?;
+                                } else {
+                                    sink.update_state(request_id, new_state, update_mask)
+                                        .await?;
+                                }
                             }
                             Err(error) => {
                                 return Err(GrpcMiddlewareError::ConversionError(format!(
@@ -90,17 +104,64 @@ pub async fn forward_from_proto_to_ankaios(
                             }
                         };
                     }
-                    RequestContent::CompleteStateRequest(CompleteStateRequest { field_mask }) => {
+                    RequestContent::CompleteStateRequest(complete_state_request) => {
                         log::trace!("Received RequestCompleteState from '{}'", agent_name);
-                        sink.request_complete_state(
+                        sink.request_complete_state(request_id, complete_state_request.into())
+                            .await?;
+                    }
+                    RequestContent::DependencyGraphRequest(_) => {
+                        log::trace!("Received DependencyGraphRequest from '{}'", agent_name);
+                        sink.request_dependency_graph(request_id).await?;
+                    }
+                    RequestContent::EventsRequest(events_request) => {
+                        log::trace!("Received EventsRequest from '{}'", agent_name);
+                        sink.request_events(request_id, events_request.into())
+                            .await?;
+                    }
+                    RequestContent::ExecRequest(exec_request) => {
+                        log::trace!("Received ExecRequest from '{}'", agent_name);
+                        sink.request_exec(
+                            request_id,
+                            exec_request
+                                .try_into()
+                                .map_err(GrpcMiddlewareError::ConversionError)?,
+                        )
+                        .await?;
+                    }
+                    RequestContent::PortForwardRequest(port_forward_request) => {
+                        log::trace!("Received PortForwardRequest from '{}'", agent_name);
+                        sink.request_port_forward(
                             request_id,
-                            ank_base::CompleteStateRequest { field_mask }.into(),
+                            port_forward_request
+                                .try_into()
+                                .map_err(GrpcMiddlewareError::ConversionError)?,
                         )
                         .await?;
                     }
+                    RequestContent::RestartWorkloadRequest(restart_workload_request) => {
+                        log::trace!("Received RestartWorkloadRequest from '{}'", agent_name);
+                        sink.request_restart_workload(request_id, restart_workload_request.into())
+                            .await?;
+                    }
+                    RequestContent::PauseWorkloadRequest(pause_workload_request) => {
+                        log::trace!("Received PauseWorkloadRequest from '{}'", agent_name);
+                        sink.request_pause_workload(request_id, pause_workload_request.into())
+                            .await?;
+                    }
+                    RequestContent::AuditRequest(audit_request) => {
+                        log::trace!("Received AuditRequest from '{}'", agent_name);
+                        sink.request_audit(request_id, audit_request.into()).await?;
+                    }
                 }
             }
 
+            // [impl->swdd~grpc-agent-connection-forwards-exec-output-to-server~1]
+            ToServerEnum::Response(response) => {
+                log::trace!("Received Response from '{}'", agent_name);
+                // the request id already contains the prefix of the requester the response is for
+                sink.response(response).await?;
+            }
+
             ToServerEnum::UpdateWorkloadState(update_workload_state) => {
                 log::trace!("Received UpdateWorkloadState from '{}'", agent_name);
 
@@ -153,6 +214,14 @@ pub async fn forward_from_ankaios_to_proto(
                     })
                     .await?;
             }
+            ToServer::Response(response) => {
+                log::trace!("Received Response from agent");
+                grpc_tx
+                    .send(grpc_api::ToServer {
+                        to_server_enum: Some(ToServerEnum::Response(response)),
+                    })
+                    .await?;
+            }
             ToServer::UpdateWorkloadState(method_obj) => {
                 log::trace!("Received UpdateWorkloadState from agent");
 
@@ -171,7 +240,8 @@ pub async fn forward_from_ankaios_to_proto(
             }
             ToServer::Stop(_method_obj) => {
                 log::debug!("Received Stop from agent");
-                // TODO: handle the call
+                // [impl->swdd~grpc-client-stops-connection-on-stop-request~1]
+                server_rx.close();
                 break;
             }
             ToServer::AgentHello(_) => {
@@ -187,6 +257,11 @@ pub async fn forward_from_ankaios_to_proto(
                                 agent_name: status.agent_name,
                                 cpu_usage: status.cpu_usage,
                                 free_memory: status.free_memory,
+                                workload_resources: status.workload_resources,
+                                disk_usage: status.disk_usage,
+                                network_usage: status.network_usage,
+                                temperature: status.temperature,
+                                image_states: status.image_states,
                             }
                             .into(),
                         )),
@@ -230,7 +305,10 @@ mod tests {
 
     use super::{forward_from_ankaios_to_proto, forward_from_proto_to_ankaios, GRPCStreaming};
     use async_trait::async_trait;
-    use common::objects::{CpuUsage, FreeMemory};
+    use common::objects::{
+        generate_test_workload_resources, CpuUsage, DiskUsage, FreeMemory, NetworkInterfaceUsage,
+        NetworkUsage, Temperature, ThermalZone,
+    };
     use common::test_utils::generate_test_complete_state;
     use common::{
         objects::generate_test_workload_spec_with_param,
@@ -272,6 +350,25 @@ mod tests {
             agent_name: agent_name.clone(),
             cpu_usage: CpuUsage { cpu_usage: 42 },
             free_memory: FreeMemory { free_memory: 42 },
+            workload_resources: vec![generate_test_workload_resources(&agent_name, "workload_1")],
+            disk_usage: DiskUsage {
+                total_space: 100,
+                available_space: 42,
+            },
+            network_usage: NetworkUsage {
+                interfaces: vec![NetworkInterfaceUsage {
+                    name: "eth0".to_string(),
+                    received_bytes: 42,
+                    transmitted_bytes: 42,
+                }],
+            },
+            temperature: Temperature {
+                thermal_zones: vec![ThermalZone {
+                    zone_type: "cpu-thermal".to_string(),
+                    temperature: 42000,
+                }],
+            },
+            image_states: Default::default(),
         };
 
         let agent_resource_result = server_tx.agent_load_status(agent_load_status.clone()).await;
@@ -290,6 +387,13 @@ mod tests {
             agent_name: agent_name.clone(),
             cpu_usage: Some(ank_base::CpuUsage { cpu_usage: 42 }),
             free_memory: Some(ank_base::FreeMemory { free_memory: 42 }),
+            workload_resources: vec![
+                generate_test_workload_resources(&agent_name, "workload_1").into()
+            ],
+            disk_usage: Some(agent_load_status.disk_usage.into()),
+            network_usage: Some(agent_load_status.network_usage.into()),
+            temperature: Some(agent_load_status.temperature.into()),
+            image_states: Default::default(),
         });
 
         assert_eq!(result.to_server_enum, Some(expected));
@@ -303,6 +407,11 @@ mod tests {
             agent_name: agent_name.clone(),
             cpu_usage: CpuUsage { cpu_usage: 42 },
             free_memory: FreeMemory { free_memory: 42 },
+            workload_resources: vec![generate_test_workload_resources(&agent_name, "workload_1")],
+            disk_usage: Default::default(),
+            network_usage: Default::default(),
+            temperature: Default::default(),
+            image_states: Default::default(),
         };
 
         let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
@@ -512,6 +621,8 @@ mod tests {
                                 ank_base::UpdateStateRequest {
                                     new_state: Some(ankaios_state),
                                     update_mask: ankaios_update_mask.clone(),
+                                    dry_run: false,
+                                    grace_period_secs: None,
                                 },
                             )),
                         ),
@@ -556,6 +667,8 @@ mod tests {
                                 ank_base::UpdateStateRequest {
                                     new_state: Some(ankaios_state.clone().into()),
                                     update_mask: ankaios_update_mask.clone(),
+                                    dry_run: false,
+                                    grace_period_secs: None,
                                 },
                             )),
                         ),
@@ -587,6 +700,113 @@ mod tests {
             if request_id == expected_prefixed_my_request_id && update_request.state == ankaios_state && update_request.update_mask == ankaios_update_mask));
     }
 
+    // [utest->swdd~grpc-agent-connection-forwards-commands-to-server~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_proto_to_ankaios_update_workload_dry_run() {
+        let agent_name = "fake_agent";
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+
+        let ankaios_state =
+            generate_test_complete_state(vec![generate_test_workload_spec_with_param(
+                agent_name.into(),
+                "name".to_string(),
+                "my_runtime".into(),
+            )]);
+
+        let mut mock_grpc_ex_request_streaming =
+            MockGRPCToServerStreaming::new(LinkedList::from([
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::Request(ank_base::Request {
+                        request_id: "my_request_id".to_owned(),
+                        request_content: Some(
+                            ank_base::request::RequestContent::UpdateStateRequest(Box::new(
+                                ank_base::UpdateStateRequest {
+                                    new_state: Some(ankaios_state.clone().into()),
+                                    update_mask: vec![],
+                                    dry_run: true,
+                                    grace_period_secs: None,
+                                },
+                            )),
+                        ),
+                    })),
+                }),
+                None,
+            ]));
+
+        let forward_result = forward_from_proto_to_ankaios(
+            agent_name.into(),
+            &mut mock_grpc_ex_request_streaming,
+            server_tx,
+        )
+        .await;
+
+        assert!(forward_result.is_ok());
+
+        let result = server_rx.recv().await.unwrap();
+
+        assert!(matches!(
+            result,
+            ToServer::Request(common::commands::Request {
+                request_content: common::commands::RequestContent::UpdateStateRequest(update_request),
+                ..
+            })
+            if update_request.state == ankaios_state && update_request.dry_run));
+    }
+
+    // [utest->swdd~grpc-agent-connection-forwards-commands-to-server~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_proto_to_ankaios_update_workload_with_grace_period(
+    ) {
+        let agent_name = "fake_agent";
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+
+        let ankaios_state =
+            generate_test_complete_state(vec![generate_test_workload_spec_with_param(
+                agent_name.into(),
+                "name".to_string(),
+                "my_runtime".into(),
+            )]);
+
+        let mut mock_grpc_ex_request_streaming =
+            MockGRPCToServerStreaming::new(LinkedList::from([
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::Request(ank_base::Request {
+                        request_id: "my_request_id".to_owned(),
+                        request_content: Some(
+                            ank_base::request::RequestContent::UpdateStateRequest(Box::new(
+                                ank_base::UpdateStateRequest {
+                                    new_state: Some(ankaios_state.clone().into()),
+                                    update_mask: vec![],
+                                    dry_run: false,
+                                    grace_period_secs: Some(30),
+                                },
+                            )),
+                        ),
+                    })),
+                }),
+                None,
+            ]));
+
+        let forward_result = forward_from_proto_to_ankaios(
+            agent_name.into(),
+            &mut mock_grpc_ex_request_streaming,
+            server_tx,
+        )
+        .await;
+
+        assert!(forward_result.is_ok());
+
+        let result = server_rx.recv().await.unwrap();
+
+        assert!(matches!(
+            result,
+            ToServer::Request(common::commands::Request {
+                request_content: common::commands::RequestContent::UpdateStateRequest(update_request),
+                ..
+            })
+            if update_request.state == ankaios_state && update_request.grace_period_secs == Some(30)));
+    }
+
     // [utest->swdd~grpc-agent-connection-forwards-commands-to-server~1]
     #[tokio::test]
     async fn utest_to_server_command_forward_from_proto_to_ankaios_update_workload_state() {
@@ -648,7 +868,10 @@ mod tests {
                         request_id: "my_request_id".to_owned(),
                         request_content: Some(
                             ank_base::request::RequestContent::CompleteStateRequest(
-                                ank_base::CompleteStateRequest { field_mask: vec![] },
+                                ank_base::CompleteStateRequest {
+                                    field_mask: vec![],
+                                    selector: vec![],
+                                },
                             ),
                         ),
                     })),
@@ -675,18 +898,145 @@ mod tests {
                 request_id,
                 request_content:
                     common::commands::RequestContent::CompleteStateRequest(
-                        common::commands::CompleteStateRequest { field_mask },
+                        common::commands::CompleteStateRequest { field_mask, .. },
                     ),
             }) if request_id == expected_prefixed_my_request_id && field_mask == exepected_empty_field_mask)
         );
     }
 
+    // [utest->swdd~grpc-agent-connection-forwards-commands-to-server~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_proto_to_ankaios_request_dependency_graph() {
+        let agent_name = "fake_agent";
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+
+        let mut mock_grpc_ex_request_streaming =
+            MockGRPCToServerStreaming::new(LinkedList::from([
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::Request(ank_base::Request {
+                        request_id: "my_request_id".to_owned(),
+                        request_content: Some(
+                            ank_base::request::RequestContent::DependencyGraphRequest(
+                                ank_base::DependencyGraphRequest {},
+                            ),
+                        ),
+                    })),
+                }),
+                None,
+            ]));
+
+        let forward_result = forward_from_proto_to_ankaios(
+            agent_name.into(),
+            &mut mock_grpc_ex_request_streaming,
+            server_tx,
+        )
+        .await;
+        assert!(forward_result.is_ok());
+
+        assert_eq!(
+            server_rx.recv().await.unwrap(),
+            ToServer::Request(common::commands::Request {
+                request_id: String::from("fake_agent@my_request_id"),
+                request_content: common::commands::RequestContent::DependencyGraphRequest(
+                    common::commands::DependencyGraphRequest {}
+                ),
+            })
+        );
+    }
+
+    // [utest->swdd~grpc-agent-connection-forwards-commands-to-server~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_proto_to_ankaios_request_events() {
+        let agent_name = "fake_agent";
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+
+        let mut mock_grpc_ex_request_streaming =
+            MockGRPCToServerStreaming::new(LinkedList::from([
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::Request(ank_base::Request {
+                        request_id: "my_request_id".to_owned(),
+                        request_content: Some(ank_base::request::RequestContent::EventsRequest(
+                            ank_base::EventsRequest {
+                                after_sequence_number: 42,
+                            },
+                        )),
+                    })),
+                }),
+                None,
+            ]));
+
+        let forward_result = forward_from_proto_to_ankaios(
+            agent_name.into(),
+            &mut mock_grpc_ex_request_streaming,
+            server_tx,
+        )
+        .await;
+        assert!(forward_result.is_ok());
+
+        assert_eq!(
+            server_rx.recv().await.unwrap(),
+            ToServer::Request(common::commands::Request {
+                request_id: String::from("fake_agent@my_request_id"),
+                request_content: common::commands::RequestContent::EventsRequest(
+                    common::commands::EventsRequest {
+                        after_sequence_number: 42,
+                    }
+                ),
+            })
+        );
+    }
+
+    // [utest->swdd~grpc-agent-connection-forwards-commands-to-server~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_proto_to_ankaios_request_audit() {
+        let agent_name = "fake_agent";
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+
+        let mut mock_grpc_ex_request_streaming =
+            MockGRPCToServerStreaming::new(LinkedList::from([
+                Some(grpc_api::ToServer {
+                    to_server_enum: Some(ToServerEnum::Request(ank_base::Request {
+                        request_id: "my_request_id".to_owned(),
+                        request_content: Some(ank_base::request::RequestContent::AuditRequest(
+                            ank_base::AuditRequest {
+                                after_sequence_number: 42,
+                            },
+                        )),
+                    })),
+                }),
+                None,
+            ]));
+
+        let forward_result = forward_from_proto_to_ankaios(
+            agent_name.into(),
+            &mut mock_grpc_ex_request_streaming,
+            server_tx,
+        )
+        .await;
+        assert!(forward_result.is_ok());
+
+        assert_eq!(
+            server_rx.recv().await.unwrap(),
+            ToServer::Request(common::commands::Request {
+                request_id: String::from("fake_agent@my_request_id"),
+                request_content: common::commands::RequestContent::AuditRequest(
+                    common::commands::AuditRequest {
+                        after_sequence_number: 42,
+                    }
+                ),
+            })
+        );
+    }
+
     #[tokio::test]
     async fn utest_to_server_command_forward_from_ankaios_to_proto_request_complete_state() {
         let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
         let (grpc_tx, mut grpc_rx) = mpsc::channel::<grpc_api::ToServer>(common::CHANNEL_CAPACITY);
 
-        let request_complete_state = common::commands::CompleteStateRequest { field_mask: vec![] };
+        let request_complete_state = common::commands::CompleteStateRequest {
+            field_mask: vec![],
+            selector: vec![],
+        };
 
         let request_complete_state_result = server_tx
             .request_complete_state("my_request_id".to_owned(), request_complete_state.clone())
@@ -708,9 +1058,76 @@ mod tests {
             request_id,
             request_content:
                 Some(ank_base::request::RequestContent::CompleteStateRequest(
-                    ank_base::CompleteStateRequest { field_mask },
+                    ank_base::CompleteStateRequest { field_mask, .. },
                 )),
         }))
         if request_id == "my_request_id" && field_mask == vec![] as Vec<String>));
     }
+
+    // [utest->swdd~grpc-agent-connection-forwards-exec-output-to-server~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_response_of_agent() {
+        let response = ank_base::Response {
+            request_id: "cli@my_request_id".to_owned(),
+            response_content: Some(ank_base::response::ResponseContent::ExecOutput(
+                ank_base::ExecOutput {
+                    exec_output_enum: Some(ank_base::exec_output::ExecOutputEnum::Stdout(
+                        b"hello".to_vec(),
+                    )),
+                },
+            )),
+        };
+
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+        let (grpc_tx, mut grpc_rx) = mpsc::channel::<grpc_api::ToServer>(common::CHANNEL_CAPACITY);
+        assert!(server_tx.response(response.clone()).await.is_ok());
+        tokio::spawn(async move {
+            let _ = forward_from_ankaios_to_proto(grpc_tx, &mut server_rx).await;
+        });
+        drop(server_tx);
+
+        let proto_message = grpc_rx.recv().await.unwrap();
+        assert_eq!(
+            proto_message.to_server_enum,
+            Some(ToServerEnum::Response(response.clone()))
+        );
+
+        // the request id of the response is not prefixed with the name of the agent
+        let (sink_tx, mut sink_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+        let mut mock_grpc_ex_request_streaming =
+            MockGRPCToServerStreaming::new(LinkedList::from([Some(proto_message), None]));
+        let forward_result = forward_from_proto_to_ankaios(
+            "fake_agent".into(),
+            &mut mock_grpc_ex_request_streaming,
+            sink_tx,
+        )
+        .await;
+        assert!(forward_result.is_ok());
+        assert_eq!(sink_rx.recv().await.unwrap(), ToServer::Response(response));
+    }
+
+    // [utest->swdd~grpc-client-stops-connection-on-stop-request~1]
+    #[tokio::test]
+    async fn utest_to_server_command_forward_from_ankaios_to_proto_stop() {
+        let (server_tx, mut server_rx) = mpsc::channel::<ToServer>(common::CHANNEL_CAPACITY);
+        let (grpc_tx, mut grpc_rx) = mpsc::channel::<grpc_api::ToServer>(common::CHANNEL_CAPACITY);
+
+        assert!(server_tx.stop().await.is_ok());
+
+        let forward_task =
+            tokio::spawn(
+                async move { forward_from_ankaios_to_proto(grpc_tx, &mut server_rx).await },
+            );
+
+        let result = grpc_rx.recv().await.unwrap();
+        assert_eq!(
+            result.to_server_enum,
+            Some(ToServerEnum::Goodbye(grpc_api::Goodbye {}))
+        );
+
+        // the forwarding stops although the senders of the agent are still alive
+        drop(grpc_rx);
+        assert!(forward_task.await.unwrap().is_ok());
+        assert!(server_tx.is_closed());
+    }
 }
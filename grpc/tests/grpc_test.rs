@@ -27,7 +27,7 @@ mod grpc_tests {
         commands::{self, CompleteStateRequest, Request, RequestContent},
         communications_client::CommunicationsClient,
         communications_error::CommunicationMiddlewareError,
-        communications_server::CommunicationsServer,
+        communications_server::{CommunicationsServer, ServerAddress},
         from_server_interface::{FromServer, FromServerSender},
         objects::CompleteState,
         to_server_interface::{ToServer, ToServerInterface, ToServerReceiver, ToServerSender},
@@ -317,7 +317,7 @@ MC4CAQAwBQYDK2VwBCIEILwDB7W+KEw+UkzfOQA9ghy70Em4ubdS42DLkDmdmYyb
         // create communication server
         let mut communications_server = GRPCCommunicationsServer::new(to_server, server_tls_config);
 
-        let socket_addr: std::net::SocketAddr = server_addr.parse().unwrap();
+        let socket_addr = ServerAddress::Tcp(server_addr.parse().unwrap());
 
         let grpc_server_task = tokio::spawn(async move {
             communications_server
@@ -368,7 +368,10 @@ MC4CAQAwBQYDK2VwBCIEILwDB7W+KEw+UkzfOQA9ghy70Em4ubdS42DLkDmdmYyb
         let request_complete_state_result = to_grpc_client
             .request_complete_state(
                 test_request_id.to_owned(),
-                CompleteStateRequest { field_mask: vec![] },
+                CompleteStateRequest {
+                    field_mask: vec![],
+                    selector: vec![],
+                },
             )
             .await;
 
@@ -385,13 +388,68 @@ MC4CAQAwBQYDK2VwBCIEILwDB7W+KEw+UkzfOQA9ghy70Em4ubdS42DLkDmdmYyb
                 Request{
                     request_id,
                     request_content: RequestContent::CompleteStateRequest(CompleteStateRequest {
-                        field_mask
+                        field_mask,
+                        ..
                     })
                 }
             ))) if request_id.contains(test_request_id) && field_mask.is_empty()
         ));
     }
 
+    // [itest->swdd~grpc-server-listens-on-unix-domain-socket~4]
+    // [itest->swdd~grpc-client-connects-to-unix-domain-socket~1]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)] // set worker_threads = 1 to solve the failing of the test on woodpecker
+    async fn itest_grpc_communication_client_cli_connection_over_unix_domain_socket() {
+        let test_request_id = "test_request_id";
+        let socket_dir = TempDir::new().unwrap();
+        let socket_path = socket_dir.path().join("server.sock");
+
+        let (_to_grpc_server, grpc_server_receiver) = tokio::sync::mpsc::channel::<FromServer>(20);
+        let (to_server, mut server_receiver) = tokio::sync::mpsc::channel::<ToServer>(20);
+        let mut communications_server = GRPCCommunicationsServer::new(to_server, None);
+        let server_address = ServerAddress::UnixSocket(socket_path.clone());
+        let _grpc_server_task = tokio::spawn(async move {
+            communications_server
+                .start(grpc_server_receiver, server_address)
+                .await
+        });
+
+        let (to_grpc_client, grpc_client_receiver) = tokio::sync::mpsc::channel::<ToServer>(20);
+        let (to_cli, _cli_receiver) = tokio::sync::mpsc::channel::<FromServer>(20);
+        let mut grpc_communications_client = GRPCCommunicationsClient::new_cli_communication(
+            test_request_id.to_owned(),
+            format!("unix://{}", socket_path.display()),
+            None,
+        )
+        .unwrap();
+        let _grpc_client_task = tokio::spawn(async move {
+            grpc_communications_client
+                .run(grpc_client_receiver, to_cli)
+                .await
+        });
+
+        to_grpc_client
+            .request_complete_state(
+                test_request_id.to_owned(),
+                CompleteStateRequest {
+                    field_mask: vec![],
+                    selector: vec![],
+                },
+            )
+            .await
+            .unwrap();
+
+        let result = timeout(Duration::from_secs(10), server_receiver.recv()).await;
+
+        assert!(matches!(
+            result,
+            Ok(Some(ToServer::Request(Request {
+                request_id,
+                request_content: RequestContent::CompleteStateRequest(_)
+            }))) if request_id.contains(test_request_id)
+        ));
+    }
+
     // [itest->swdd~grpc-server-provides-endpoint-for-cli-connection-handling~1]
     // [itest->swdd~grpc-server-creates-cli-connection~1]
     // [itest->swdd~grpc-server-deactivate-mtls-when-no-certificates-and-no-key-provided-upon-start~1]
@@ -412,7 +470,10 @@ MC4CAQAwBQYDK2VwBCIEILwDB7W+KEw+UkzfOQA9ghy70Em4ubdS42DLkDmdmYyb
         let request_complete_state_result = to_grpc_client
             .request_complete_state(
                 test_request_id.to_owned(),
-                CompleteStateRequest { field_mask: vec![] },
+                CompleteStateRequest {
+                    field_mask: vec![],
+                    selector: vec![],
+                },
             )
             .await;
 
@@ -427,7 +488,8 @@ MC4CAQAwBQYDK2VwBCIEILwDB7W+KEw+UkzfOQA9ghy70Em4ubdS42DLkDmdmYyb
                 Request{
                     request_id,
                     request_content: RequestContent::CompleteStateRequest(CompleteStateRequest {
-                        field_mask
+                        field_mask,
+                        ..
                     })
                 }
             ))) if request_id.contains(test_request_id) && field_mask.is_empty()
@@ -487,6 +549,9 @@ MC4CAQAwBQYDK2VwBCIEILwDB7W+KEw+UkzfOQA9ghy70Em4ubdS42DLkDmdmYyb
             result,
             Ok(Some(ToServer::AgentHello(commands::AgentHello {
                 agent_name: test_agent_name.to_owned(),
+                labels: Default::default(),
+                version: common::ANKAIOS_VERSION.to_owned(),
+                runtimes: Default::default(),
             })))
         );
     }
@@ -512,7 +577,10 @@ MC4CAQAwBQYDK2VwBCIEILwDB7W+KEw+UkzfOQA9ghy70Em4ubdS42DLkDmdmYyb
         assert_eq!(
             result,
             Ok(Some(ToServer::AgentHello(commands::AgentHello {
-                agent_name: test_agent_name.to_owned()
+                agent_name: test_agent_name.to_owned(),
+                labels: Default::default(),
+                version: common::ANKAIOS_VERSION.to_owned(),
+                runtimes: Default::default(),
             })))
         );
     }
@@ -12,30 +12,60 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+mod admission;
+mod audit_log;
 mod config_renderer;
-mod cycle_check;
 mod delete_graph;
+mod dependency_graph;
+mod event_log;
+mod failover_controller;
+mod metrics;
+mod pending_agent_requests;
+mod replication;
+mod scheduler;
 mod server_state;
+mod workload_diff;
+mod workload_state_history;
 
 use api::ank_base;
-use common::commands::{Request, UpdateWorkload};
+use common::commands::{
+    ExecRequest, PauseWorkloadRequest, PortForwardRequest, Request, RestartWorkloadRequest,
+    UpdateWorkload,
+};
 use common::from_server_interface::{FromServerReceiver, FromServerSender};
 use common::objects::{
-    CompleteState, DeletedWorkload, ExecutionState, State, WorkloadState, WorkloadStatesMap,
+    CompleteState, DeletedWorkload, ExecutionState, State, WorkloadSpec, WorkloadState,
+    WorkloadStatesMap,
 };
 
 use common::std_extensions::IllegalStateResult;
 use common::to_server_interface::{ToServerReceiver, ToServerSender};
-
+use grpc::server::StateReplicator;
+
+use crate::notifications::Notifier;
+pub use admission::{AdmissionConfig, AdmissionValidators};
+pub use audit_log::AuditLogConfig;
+use audit_log::{AuditLog, AuditRequester};
+pub use config_renderer::ConfigRenderingConfig;
+use event_log::EventLog;
+use failover_controller::FailoverController;
+pub use metrics::Metrics;
+use pending_agent_requests::PendingAgentRequests;
+pub use replication::{promotion_signal, StandbyServer};
 #[cfg_attr(test, mockall_double::double)]
 use server_state::ServerState;
+use workload_state_history::WorkloadStateHistory;
+pub use workload_state_history::WorkloadStateHistoryConfig;
 
 use common::{
     from_server_interface::{FromServer, FromServerInterface},
     to_server_interface::ToServer,
 };
 
+use std::collections::HashMap;
+use std::time::Duration;
 use tokio::sync::mpsc::channel;
+use tokio::time::{interval_at, Instant, Interval};
 
 pub type ToServerChannel = (ToServerSender, ToServerReceiver);
 pub type FromServerChannel = (FromServerSender, FromServerReceiver);
@@ -54,6 +84,21 @@ pub struct AnkaiosServer {
     to_agents: FromServerSender,
     server_state: ServerState,
     workload_states_map: WorkloadStatesMap,
+    workload_states_resync_interval: Option<Duration>,
+    failover_controller: FailoverController,
+    event_log: EventLog,
+    audit_log: AuditLog,
+    workload_state_history: WorkloadStateHistory,
+    notifier: Notifier,
+    metrics: Metrics,
+    state_replicator: Option<StateReplicator>,
+    // the agents running the exec sessions and port forwardings,
+    // stored by the request id that started them
+    agent_sessions: HashMap<String, String>,
+    // the restart and pause requests forwarded to agents and not answered yet
+    pending_agent_requests: PendingAgentRequests,
+    // the images last sent to the connected agents to be pulled and pinned
+    images_to_pull: HashMap<String, Vec<String>>,
 }
 
 impl AnkaiosServer {
@@ -63,17 +108,85 @@ impl AnkaiosServer {
             to_agents,
             server_state: ServerState::default(),
             workload_states_map: WorkloadStatesMap::default(),
+            workload_states_resync_interval: None,
+            failover_controller: FailoverController::default(),
+            event_log: EventLog::default(),
+            audit_log: AuditLog::default(),
+            workload_state_history: WorkloadStateHistory::default(),
+            notifier: Notifier::default(),
+            metrics: Metrics::default(),
+            state_replicator: None,
+            agent_sessions: HashMap::new(),
+            pending_agent_requests: PendingAgentRequests::default(),
+            images_to_pull: HashMap::new(),
         }
     }
 
+    pub fn with_workload_states_resync_interval(mut self, interval: Duration) -> Self {
+        self.workload_states_resync_interval = Some(interval);
+        self
+    }
+
+    pub fn with_agent_lost_grace_period(mut self, grace_period: Duration) -> Self {
+        self.failover_controller = FailoverController::new(grace_period);
+        self
+    }
+
+    pub fn with_agent_request_timeout(mut self, timeout: Duration) -> Self {
+        self.pending_agent_requests = PendingAgentRequests::new(timeout);
+        self
+    }
+
+    pub fn with_workload_state_history(mut self, config: &WorkloadStateHistoryConfig) -> Self {
+        self.workload_state_history = WorkloadStateHistory::new(config);
+        self
+    }
+
+    pub fn with_audit_log(mut self, config: &AuditLogConfig) -> Self {
+        self.audit_log = AuditLog::new(config);
+        self
+    }
+
+    pub fn with_notifier(mut self, notifier: Notifier) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    pub fn with_state_replicator(mut self, state_replicator: StateReplicator) -> Self {
+        self.state_replicator = Some(state_replicator);
+        self
+    }
+
+    pub fn with_config_rendering(mut self, config: &ConfigRenderingConfig) -> Self {
+        self.server_state
+            .set_allowed_env_prefixes(config.allowed_env_prefixes.clone());
+        self
+    }
+
+    pub fn with_admission_validators(mut self, admission_validators: AdmissionValidators) -> Self {
+        self.server_state
+            .set_admission_validators(admission_validators);
+        self
+    }
+
     pub async fn start(&mut self, startup_state: Option<CompleteState>) -> Result<(), String> {
-        if let Some(state) = startup_state {
-            State::verify_api_version(&state.desired_state)?;
+        if let Some(mut state) = startup_state {
+            state.desired_state.migrate_to_current_api_version()?;
 
             match self.server_state.update(state, vec![]) {
                 Ok(Some((added_workloads, deleted_workloads))) => {
+                    self.replicate_desired_state();
                     // [impl->swdd~server-sets-state-of-new-workloads-to-pending~1]
                     self.workload_states_map.initial_state(&added_workloads);
+                    self.record_workload_events(&added_workloads, &deleted_workloads);
+                    // [impl->swdd~server-collects-metrics~1]
+                    self.metrics
+                        .update_workload_states(&self.workload_states_map);
 
                     let from_server_command = FromServer::UpdateWorkload(UpdateWorkload {
                         added_workloads,
@@ -85,7 +198,10 @@ impl AnkaiosServer {
                         .await
                         .unwrap_or_illegal_state();
                 }
-                Ok(None) => log::info!("No initial workloads to send to agents."),
+                Ok(None) => {
+                    self.replicate_desired_state();
+                    log::info!("No initial workloads to send to agents.");
+                }
                 Err(err) => {
                     // [impl->swdd~server-fails-on-invalid-startup-state~1]
                     return Err(err.to_string());
@@ -103,12 +219,23 @@ impl AnkaiosServer {
 
     async fn listen_to_agents(&mut self) {
         log::debug!("Start listening to agents...");
-        while let Some(to_server_command) = self.receiver.recv().await {
+        let mut resync_timer = self
+            .workload_states_resync_interval
+            .map(|period| interval_at(Instant::now() + period, period));
+        while let Some(to_server_command) = self.receive_next_command(&mut resync_timer).await {
             match to_server_command {
                 ToServer::AgentHello(method_obj) => {
                     log::info!("Received AgentHello from '{}'", method_obj.agent_name);
 
-                    let agent_name = method_obj.agent_name;
+                    let agent_name = method_obj.agent_name.clone();
+
+                    // [impl->swdd~server-stores-newly-connected-agent~1]
+                    self.server_state.add_agent(method_obj);
+                    // [impl->swdd~server-detects-lost-agents~1]
+                    self.failover_controller.agent_connected(&agent_name);
+
+                    // [impl->swdd~server-schedules-workloads-on-newly-connected-agent~1]
+                    self.schedule_unscheduled_workloads().await;
 
                     // [impl->swdd~server-informs-a-newly-connected-agent-workload-states~1]
                     let workload_states = self
@@ -146,8 +273,15 @@ impl AnkaiosServer {
                         .await
                         .unwrap_or_illegal_state();
 
-                    // [impl->swdd~server-stores-newly-connected-agent~1]
-                    self.server_state.add_agent(agent_name);
+                    // a newly connected agent has not pinned any images yet
+                    self.images_to_pull.remove(&agent_name);
+                    // [impl->swdd~server-sends-images-to-pull-to-agents~1]
+                    self.update_images_to_pull(&agent_name).await;
+
+                    // [impl->swdd~server-records-events~1]
+                    self.event_log.agent_connected(&agent_name);
+                    // [impl->swdd~server-collects-metrics~1]
+                    self.metrics.agent_connected(&agent_name);
                 }
                 // [impl->swdd~server-receives-resource-availability~1]
                 ToServer::AgentLoadStatus(method_obj) => {
@@ -171,15 +305,45 @@ impl AnkaiosServer {
                     // [impl->swdd~server-set-workload-state-on-disconnect~1]
                     self.workload_states_map.agent_disconnected(&agent_name);
 
+                    // [impl->swdd~server-detects-lost-agents~1]
+                    self.failover_controller.agent_disconnected(&agent_name);
+                    self.images_to_pull.remove(&agent_name);
+
+                    let disconnected_workload_states = self
+                        .workload_states_map
+                        .get_workload_state_for_agent(&agent_name);
+
+                    // [impl->swdd~server-records-events~1]
+                    self.event_log.agent_disconnected(&agent_name);
+                    disconnected_workload_states
+                        .iter()
+                        .for_each(|state| self.event_log.execution_state_changed(state));
+                    // [impl->swdd~server-records-workload-state-history~1]
+                    self.workload_state_history
+                        .record(&disconnected_workload_states);
+
+                    // [impl->swdd~server-collects-metrics~1]
+                    self.metrics.agent_disconnected(&agent_name);
+                    self.metrics
+                        .update_workload_states(&self.workload_states_map);
+
+                    // [impl->swdd~server-notifies-workload-state-changes~1]
+                    self.notifier
+                        .notify_execution_states(&disconnected_workload_states);
+
                     // communicate the workload execution states to other agents
                     // [impl->swdd~server-distribute-workload-state-on-disconnect~1]
                     self.to_agents
-                        .update_workload_state(
-                            self.workload_states_map
-                                .get_workload_state_for_agent(&agent_name),
-                        )
+                        .update_workload_state(disconnected_workload_states)
                         .await
                         .unwrap_or_illegal_state();
+
+                    // [impl->swdd~server-ends-exec-sessions-of-disconnected-agent~1]
+                    // [impl->swdd~server-ends-port-forwardings-of-disconnected-agent~1]
+                    self.end_sessions_of_agent(&agent_name).await;
+
+                    // [impl->swdd~server-cancels-forwarded-requests-of-disconnected-agent~1]
+                    self.cancel_requests_of_agent(&agent_name).await;
                 }
                 // [impl->swdd~server-provides-update-desired-state-interface~1]
                 ToServer::Request(Request {
@@ -196,15 +360,20 @@ impl AnkaiosServer {
                             request_id,
                             complete_state_request.field_mask
                         );
+                        let field_mask = complete_state_request.field_mask.clone();
                         match self.server_state.get_complete_state_by_field_mask(
                             complete_state_request,
                             &self.workload_states_map,
                         ) {
-                            Ok(complete_state) => self
-                                .to_agents
-                                .complete_state(request_id, complete_state)
-                                .await
-                                .unwrap_or_illegal_state(),
+                            Ok(mut complete_state) => {
+                                // [impl->swdd~server-provides-workload-state-history~1]
+                                complete_state.workload_state_history =
+                                    self.workload_state_history.get_by_field_mask(&field_mask);
+                                self.to_agents
+                                    .complete_state(request_id, complete_state)
+                                    .await
+                                    .unwrap_or_illegal_state()
+                            }
                             Err(error) => {
                                 log::error!("Failed to get complete state: '{}'", error);
                                 self.to_agents
@@ -220,22 +389,132 @@ impl AnkaiosServer {
                         }
                     }
 
+                    // [impl->swdd~server-provides-dependency-graph~1]
+                    common::commands::RequestContent::DependencyGraphRequest(_) => {
+                        log::debug!("Received DependencyGraphRequest with id '{}'", request_id);
+                        let dependency_graph = self
+                            .server_state
+                            .get_dependency_graph(&self.workload_states_map);
+                        self.to_agents
+                            .response(ank_base::Response {
+                                request_id,
+                                response_content: Some(
+                                    ank_base::response::ResponseContent::DependencyGraph(
+                                        dependency_graph,
+                                    ),
+                                ),
+                            })
+                            .await
+                            .unwrap_or_illegal_state();
+                    }
+
+                    // [impl->swdd~server-provides-events~1]
+                    common::commands::RequestContent::EventsRequest(events_request) => {
+                        log::debug!(
+                            "Received EventsRequest with id '{}' for events after sequence number '{}'",
+                            request_id,
+                            events_request.after_sequence_number
+                        );
+                        let events = self
+                            .event_log
+                            .events_after(events_request.after_sequence_number);
+                        self.to_agents
+                            .response(ank_base::Response {
+                                request_id,
+                                response_content: Some(
+                                    ank_base::response::ResponseContent::Events(ank_base::Events {
+                                        events,
+                                    }),
+                                ),
+                            })
+                            .await
+                            .unwrap_or_illegal_state();
+                    }
+
+                    // [impl->swdd~server-provides-audit-log~1]
+                    common::commands::RequestContent::AuditRequest(audit_request) => {
+                        log::debug!(
+                            "Received AuditRequest with id '{}' for entries after sequence number '{}'",
+                            request_id,
+                            audit_request.after_sequence_number
+                        );
+                        let entries = self
+                            .audit_log
+                            .entries_after(audit_request.after_sequence_number);
+                        self.to_agents
+                            .response(ank_base::Response {
+                                request_id,
+                                response_content: Some(
+                                    ank_base::response::ResponseContent::AuditEntries(
+                                        ank_base::AuditEntries { entries },
+                                    ),
+                                ),
+                            })
+                            .await
+                            .unwrap_or_illegal_state();
+                    }
+
+                    // [impl->swdd~server-forwards-exec-request-to-agent~1]
+                    common::commands::RequestContent::ExecRequest(exec_request) => {
+                        log::debug!("Received ExecRequest with id '{}'", request_id);
+                        self.forward_exec_request(request_id, exec_request).await;
+                    }
+
+                    // [impl->swdd~server-forwards-port-forward-request-to-agent~1]
+                    common::commands::RequestContent::PortForwardRequest(port_forward_request) => {
+                        log::debug!("Received PortForwardRequest with id '{}'", request_id);
+                        self.forward_port_forward_request(request_id, port_forward_request)
+                            .await;
+                    }
+
+                    // [impl->swdd~server-forwards-restart-workload-request-to-agent~1]
+                    common::commands::RequestContent::RestartWorkloadRequest(
+                        restart_workload_request,
+                    ) => {
+                        log::debug!("Received RestartWorkloadRequest with id '{}'", request_id);
+                        self.forward_restart_workload_request(request_id, restart_workload_request)
+                            .await;
+                    }
+
+                    // [impl->swdd~server-forwards-pause-workload-request-to-agent~1]
+                    common::commands::RequestContent::PauseWorkloadRequest(
+                        pause_workload_request,
+                    ) => {
+                        log::debug!("Received PauseWorkloadRequest with id '{}'", request_id);
+                        self.forward_pause_workload_request(request_id, pause_workload_request)
+                            .await;
+                    }
+
                     // [impl->swdd~server-provides-update-desired-state-interface~1]
-                    common::commands::RequestContent::UpdateStateRequest(update_state_request) => {
+                    common::commands::RequestContent::UpdateStateRequest(
+                        mut update_state_request,
+                    ) => {
+                        let request_start = Instant::now();
                         log::debug!(
                             "Received UpdateState. State '{:?}', update mask '{:?}'",
                             update_state_request.state,
                             update_state_request.update_mask
                         );
+                        let update_mask = update_state_request.update_mask.clone();
 
-                        // [impl->swdd~update-desired-state-with-invalid-version~1]
+                        // [impl->swdd~update-desired-state-with-invalid-version~2]
                         // [impl->swdd~update-desired-state-with-missing-version~1]
                         // [impl->swdd~server-desired-state-field-conventions~1]
-                        let updated_desired_state = &update_state_request.state.desired_state;
-                        if let Err(error_message) = State::verify_api_version(updated_desired_state)
+                        let updated_desired_state = &mut update_state_request.state.desired_state;
+                        if let Err(error_message) = updated_desired_state
+                            .migrate_to_current_api_version()
                             .and_then(|_| State::verify_configs_format(updated_desired_state))
                         {
                             log::warn!("The CompleteState in the request has wrong format. {} -> ignoring the request", error_message);
+                            // [impl->swdd~server-collects-metrics~1]
+                            self.metrics
+                                .update_state_request_handled(request_start.elapsed(), false);
+                            // [impl->swdd~server-records-audit-log~1]
+                            self.record_audit_entry(
+                                &request_id,
+                                update_mask,
+                                Err(error_message.as_str()),
+                            );
 
                             self.to_agents
                                 .error(request_id, error_message)
@@ -244,21 +523,60 @@ impl AnkaiosServer {
                             continue;
                         }
 
+                        // [impl->swdd~server-handles-dry-run-update-state-request~1]
+                        if update_state_request.dry_run {
+                            let response_content = match self.server_state.dry_run_update(
+                                update_state_request.state,
+                                update_state_request.update_mask,
+                            ) {
+                                Ok(dry_run_result) => {
+                                    ank_base::response::ResponseContent::UpdateStateDryRunResult(
+                                        dry_run_result,
+                                    )
+                                }
+                                Err(error_msg) => {
+                                    log::debug!("Dry run update rejected: '{error_msg}'");
+                                    ank_base::response::ResponseContent::Error(ank_base::Error {
+                                        message: format!("Update rejected: '{error_msg}'"),
+                                    })
+                                }
+                            };
+                            self.to_agents
+                                .response(ank_base::Response {
+                                    request_id,
+                                    response_content: Some(response_content),
+                                })
+                                .await
+                                .unwrap_or_illegal_state();
+                            continue;
+                        }
+
+                        let grace_period_secs = update_state_request.grace_period_secs;
+
                         // [impl->swdd~update-desired-state-with-update-mask~1]
                         // [impl->swdd~update-desired-state-empty-update-mask~1]
                         match self
                             .server_state
                             .update(update_state_request.state, update_state_request.update_mask)
                         {
-                            Ok(Some((added_workloads, deleted_workloads))) => {
+                            Ok(Some((added_workloads, mut deleted_workloads))) => {
+                                self.replicate_desired_state();
+                                // [impl->swdd~server-sends-images-to-pull-to-agents~1]
+                                self.update_images_to_pull_of_connected_agents().await;
                                 log::info!(
                                         "The update has {} new or updated workloads, {} workloads to delete",
                                         added_workloads.len(),
                                         deleted_workloads.len()
                                     );
 
+                                // [impl->swdd~server-forwards-delete-grace-period~1]
+                                deleted_workloads.iter_mut().for_each(|deleted_workload| {
+                                    deleted_workload.grace_period_secs = grace_period_secs
+                                });
+
                                 // [impl->swdd~server-sets-state-of-new-workloads-to-pending~1]
                                 self.workload_states_map.initial_state(&added_workloads);
+                                self.record_workload_events(&added_workloads, &deleted_workloads);
 
                                 let added_workloads_names = added_workloads
                                     .iter()
@@ -274,6 +592,14 @@ impl AnkaiosServer {
                                     .handle_not_started_deleted_workloads(deleted_workloads)
                                     .await;
 
+                                // [impl->swdd~server-collects-metrics~1]
+                                self.metrics
+                                    .update_workload_states(&self.workload_states_map);
+                                self.metrics
+                                    .update_state_request_handled(request_start.elapsed(), true);
+                                // [impl->swdd~server-records-audit-log~1]
+                                self.record_audit_entry(&request_id, update_mask, Ok(()));
+
                                 let from_server_command =
                                     FromServer::UpdateWorkload(UpdateWorkload {
                                         added_workloads,
@@ -295,9 +621,17 @@ impl AnkaiosServer {
                                     .unwrap_or_illegal_state();
                             }
                             Ok(None) => {
+                                self.replicate_desired_state();
+                                // [impl->swdd~server-sends-images-to-pull-to-agents~1]
+                                self.update_images_to_pull_of_connected_agents().await;
                                 log::debug!(
                                 "The current state and new state are identical -> nothing to do"
                             );
+                                // [impl->swdd~server-collects-metrics~1]
+                                self.metrics
+                                    .update_state_request_handled(request_start.elapsed(), true);
+                                // [impl->swdd~server-records-audit-log~1]
+                                self.record_audit_entry(&request_id, update_mask, Ok(()));
                                 self.to_agents
                                     .update_state_success(request_id, vec![], vec![])
                                     .await
@@ -306,6 +640,15 @@ impl AnkaiosServer {
                             Err(error_msg) => {
                                 // [impl->swdd~server-continues-on-invalid-updated-state~1]
                                 log::error!("Update rejected: '{error_msg}'",);
+                                // [impl->swdd~server-collects-metrics~1]
+                                self.metrics
+                                    .update_state_request_handled(request_start.elapsed(), false);
+                                // [impl->swdd~server-records-audit-log~1]
+                                self.record_audit_entry(
+                                    &request_id,
+                                    update_mask,
+                                    Err(error_msg.to_string().as_str()),
+                                );
                                 self.to_agents
                                     .error(request_id, format!("Update rejected: '{error_msg}'"))
                                     .await
@@ -321,15 +664,58 @@ impl AnkaiosServer {
                     );
 
                     // [impl->swdd~server-stores-workload-state~1]
-                    self.workload_states_map
+                    let changed_workload_states = self
+                        .workload_states_map
                         .process_new_states(method_obj.workload_states.clone());
 
                     // [impl->swdd~server-cleans-up-state~1]
                     self.server_state.cleanup_state(&method_obj.workload_states);
 
-                    // [impl->swdd~server-forwards-workload-state~1]
+                    // [impl->swdd~server-records-events~1]
+                    changed_workload_states
+                        .iter()
+                        .for_each(|state| self.event_log.execution_state_changed(state));
+                    // [impl->swdd~server-records-workload-state-history~1]
+                    self.workload_state_history.record(&changed_workload_states);
+
+                    // [impl->swdd~server-notifies-workload-state-changes~1]
+                    self.notifier
+                        .notify_execution_states(&changed_workload_states);
+
+                    // [impl->swdd~server-collects-metrics~1]
+                    self.metrics
+                        .update_workload_states(&self.workload_states_map);
+
+                    // [impl->swdd~server-forwards-workload-state~2]
+                    if changed_workload_states.is_empty() {
+                        log::trace!("No workload state changed -> nothing to forward.");
+                    } else {
+                        self.to_agents
+                            .update_workload_state(changed_workload_states)
+                            .await
+                            .unwrap_or_illegal_state();
+                    }
+                }
+                // [impl->swdd~server-forwards-exec-output~1]
+                // [impl->swdd~server-forwards-port-forward-data~1]
+                ToServer::Response(response) => {
+                    log::trace!("Received Response with id '{}'", response.request_id);
+                    if is_end_of_agent_session(&response) {
+                        self.agent_sessions.remove(&response.request_id);
+                    }
+                    // [impl->swdd~server-times-out-forwarded-agent-requests~2]
+                    if !self
+                        .pending_agent_requests
+                        .response_received(&response.request_id)
+                    {
+                        log::warn!(
+                            "Dropping the late response to the timed out request with id '{}'",
+                            response.request_id
+                        );
+                        continue;
+                    }
                     self.to_agents
-                        .update_workload_state(method_obj.workload_states)
+                        .response(response)
                         .await
                         .unwrap_or_illegal_state();
                 }
@@ -348,6 +734,493 @@ impl AnkaiosServer {
         }
     }
 
+    // [impl->swdd~server-forwards-exec-request-to-agent~1]
+    async fn forward_exec_request(&mut self, request_id: String, exec_request: ExecRequest) {
+        let agent_name = match &exec_request {
+            ExecRequest::Start(exec_start) => {
+                self.start_agent_session(&request_id, &exec_start.workload_name)
+                    .await
+            }
+            ExecRequest::Input(_) => {
+                self.get_agent_of_session(&request_id, "No running exec session")
+                    .await
+            }
+        };
+        let Some(agent_name) = agent_name else {
+            return;
+        };
+
+        log::debug!(
+            "Forwarding ExecRequest with id '{}' to agent '{}'",
+            request_id,
+            agent_name
+        );
+        self.to_agents
+            .exec_request(Some(agent_name), request_id, exec_request)
+            .await
+            .unwrap_or_illegal_state();
+    }
+
+    // [impl->swdd~server-forwards-port-forward-request-to-agent~1]
+    async fn forward_port_forward_request(
+        &mut self,
+        request_id: String,
+        port_forward_request: PortForwardRequest,
+    ) {
+        let agent_name = match &port_forward_request {
+            PortForwardRequest::Start(port_forward_start) => {
+                self.start_agent_session(&request_id, &port_forward_start.workload_name)
+                    .await
+            }
+            PortForwardRequest::Data(port_forward_data) => {
+                let agent_name = self
+                    .get_agent_of_session(&request_id, "No open port forwarding")
+                    .await;
+                if port_forward_data.close {
+                    self.agent_sessions.remove(&request_id);
+                }
+                agent_name
+            }
+        };
+        let Some(agent_name) = agent_name else {
+            return;
+        };
+
+        log::debug!(
+            "Forwarding PortForwardRequest with id '{}' to agent '{}'",
+            request_id,
+            agent_name
+        );
+        self.to_agents
+            .port_forward_request(Some(agent_name), request_id, port_forward_request)
+            .await
+            .unwrap_or_illegal_state();
+    }
+
+    // [impl->swdd~server-forwards-restart-workload-request-to-agent~1]
+    async fn forward_restart_workload_request(
+        &mut self,
+        request_id: String,
+        restart_workload_request: RestartWorkloadRequest,
+    ) {
+        let Some((agent_name, workload_names)) = self
+            .get_instances_of_workload(
+                &request_id,
+                &restart_workload_request.workload_name,
+                "restart",
+            )
+            .await
+        else {
+            return;
+        };
+
+        log::debug!(
+            "Forwarding RestartWorkloadRequest with id '{}' for '{:?}' to agent '{}'",
+            request_id,
+            workload_names,
+            agent_name
+        );
+        // [impl->swdd~server-times-out-forwarded-agent-requests~2]
+        self.pending_agent_requests
+            .request_forwarded(&request_id, &agent_name);
+        self.to_agents
+            .restart_workload_request(Some(agent_name), request_id, workload_names)
+            .await
+            .unwrap_or_illegal_state();
+    }
+
+    // [impl->swdd~server-forwards-pause-workload-request-to-agent~1]
+    async fn forward_pause_workload_request(
+        &mut self,
+        request_id: String,
+        pause_workload_request: PauseWorkloadRequest,
+    ) {
+        let action = if pause_workload_request.resume {
+            "resume"
+        } else {
+            "pause"
+        };
+        let Some((agent_name, workload_names)) = self
+            .get_instances_of_workload(&request_id, &pause_workload_request.workload_name, action)
+            .await
+        else {
+            return;
+        };
+
+        log::debug!(
+            "Forwarding PauseWorkloadRequest with id '{}' to {} '{:?}' to agent '{}'",
+            request_id,
+            action,
+            workload_names,
+            agent_name
+        );
+        // [impl->swdd~server-times-out-forwarded-agent-requests~2]
+        self.pending_agent_requests
+            .request_forwarded(&request_id, &agent_name);
+        self.to_agents
+            .pause_workload_request(
+                Some(agent_name),
+                request_id,
+                workload_names,
+                pause_workload_request.resume,
+            )
+            .await
+            .unwrap_or_illegal_state();
+    }
+
+    // Returns the connected agent of the workload and the names of the workload instances.
+    // An error is sent back if the workload does not exist, has no instances or its agent is not connected.
+    async fn get_instances_of_workload(
+        &mut self,
+        request_id: &str,
+        workload_name: &str,
+        action: &str,
+    ) -> Option<(String, Vec<String>)> {
+        let workload_names = self
+            .server_state
+            .get_replica_names_of_workload(workload_name);
+        // a workload scaled to zero replicas has no instances
+        if workload_names.as_ref().is_some_and(Vec::is_empty) {
+            self.to_agents
+                .error(
+                    request_id.to_owned(),
+                    format!(
+                        "Workload '{}' has no instances to {}",
+                        workload_name, action
+                    ),
+                )
+                .await
+                .unwrap_or_illegal_state();
+            return None;
+        }
+        let agent_name = self
+            .get_connected_agent_of_workload(request_id, workload_name)
+            .await?;
+        Some((agent_name, workload_names.unwrap_or_default()))
+    }
+
+    // Stores the agent of the workload as the agent of the session started by the request.
+    // An error is sent back if the workload does not exist or its agent is not connected.
+    async fn start_agent_session(
+        &mut self,
+        request_id: &str,
+        workload_name: &str,
+    ) -> Option<String> {
+        let agent_name = self
+            .get_connected_agent_of_workload(request_id, workload_name)
+            .await?;
+        self.agent_sessions
+            .insert(request_id.to_owned(), agent_name.clone());
+        Some(agent_name)
+    }
+
+    // An error is sent back if the workload does not exist or its agent is not connected.
+    async fn get_connected_agent_of_workload(
+        &mut self,
+        request_id: &str,
+        workload_name: &str,
+    ) -> Option<String> {
+        let Some(agent_name) = self.server_state.get_agent_of_workload(workload_name) else {
+            self.to_agents
+                .error(
+                    request_id.to_owned(),
+                    format!("Workload '{}' does not exist", workload_name),
+                )
+                .await
+                .unwrap_or_illegal_state();
+            return None;
+        };
+        if !self.server_state.contains_connected_agent(&agent_name) {
+            self.to_agents
+                .error(
+                    request_id.to_owned(),
+                    format!(
+                        "Agent '{}' of workload '{}' is not connected",
+                        agent_name, workload_name
+                    ),
+                )
+                .await
+                .unwrap_or_illegal_state();
+            return None;
+        }
+        Some(agent_name)
+    }
+
+    async fn get_agent_of_session(
+        &mut self,
+        request_id: &str,
+        missing_session_message: &str,
+    ) -> Option<String> {
+        let agent_name = self.agent_sessions.get(request_id).cloned();
+        if agent_name.is_none() {
+            self.to_agents
+                .error(request_id.to_owned(), missing_session_message.to_owned())
+                .await
+                .unwrap_or_illegal_state();
+        }
+        agent_name
+    }
+
+    // [impl->swdd~server-ends-exec-sessions-of-disconnected-agent~1]
+    // [impl->swdd~server-ends-port-forwardings-of-disconnected-agent~1]
+    async fn end_sessions_of_agent(&mut self, agent_name: &str) {
+        let ended_sessions: Vec<String> = self
+            .agent_sessions
+            .iter()
+            .filter(|(_, session_agent)| *session_agent == agent_name)
+            .map(|(request_id, _)| request_id.clone())
+            .collect();
+
+        for request_id in ended_sessions {
+            self.agent_sessions.remove(&request_id);
+            self.to_agents
+                .error(
+                    request_id,
+                    format!("Session ended, agent '{}' disconnected", agent_name),
+                )
+                .await
+                .unwrap_or_illegal_state();
+        }
+    }
+
+    // [impl->swdd~server-cancels-forwarded-requests-of-disconnected-agent~1]
+    async fn cancel_requests_of_agent(&mut self, agent_name: &str) {
+        for request_id in self
+            .pending_agent_requests
+            .take_requests_of_agent(agent_name)
+        {
+            self.to_agents
+                .error(
+                    request_id,
+                    format!("Request canceled, agent '{}' disconnected", agent_name),
+                )
+                .await
+                .unwrap_or_illegal_state();
+        }
+    }
+
+    // [impl->swdd~server-times-out-forwarded-agent-requests~2]
+    async fn time_out_agent_requests(&mut self) {
+        let timeout = self.pending_agent_requests.timeout().unwrap_or_default();
+        for (request_id, agent_name) in self
+            .pending_agent_requests
+            .take_timed_out_requests(Instant::now())
+        {
+            log::warn!(
+                "Agent '{}' did not respond to the request with id '{}' in time",
+                agent_name,
+                request_id
+            );
+            self.to_agents
+                .error(
+                    request_id,
+                    format!(
+                        "Request timed out, agent '{}' did not respond within {:?}",
+                        agent_name, timeout
+                    ),
+                )
+                .await
+                .unwrap_or_illegal_state();
+        }
+    }
+
+    async fn receive_next_command(
+        &mut self,
+        resync_timer: &mut Option<Interval>,
+    ) -> Option<ToServer> {
+        loop {
+            let lost_agent_deadline = self.failover_controller.next_deadline();
+            let agent_request_deadline = self.pending_agent_requests.next_deadline();
+            let elapsed_timer = tokio::select! {
+                to_server_command = self.receiver.recv() => return to_server_command,
+                _ = tick(resync_timer) => ServerTimer::WorkloadStatesResync,
+                _ = sleep_until(lost_agent_deadline) => ServerTimer::AgentLost,
+                _ = sleep_until(agent_request_deadline) => ServerTimer::AgentRequestTimeout,
+            };
+
+            match elapsed_timer {
+                ServerTimer::WorkloadStatesResync => self.resync_workload_states().await,
+                ServerTimer::AgentLost => self.reschedule_workloads_of_lost_agents().await,
+                ServerTimer::AgentRequestTimeout => self.time_out_agent_requests().await,
+            }
+        }
+    }
+
+    // [impl->swdd~server-reschedules-workloads-of-lost-agents~1]
+    async fn reschedule_workloads_of_lost_agents(&mut self) {
+        for agent_name in self.failover_controller.take_lost_agents(Instant::now()) {
+            log::info!(
+                "Agent '{}' did not reconnect within the grace period and is regarded as lost",
+                agent_name
+            );
+            self.workload_states_map.agent_lost(&agent_name);
+
+            if let Some((added_workloads, deleted_workloads)) = self
+                .server_state
+                .reschedule_workloads_of_lost_agent(&agent_name)
+            {
+                log::info!(
+                    "Rescheduled {} workloads of lost agent '{}'",
+                    added_workloads.len(),
+                    agent_name
+                );
+                self.workload_states_map.initial_state(&added_workloads);
+                self.record_workload_events(&added_workloads, &deleted_workloads);
+
+                let rescheduled_states = deleted_workloads
+                    .iter()
+                    .filter_map(|deleted| {
+                        added_workloads
+                            .iter()
+                            .find(|added| {
+                                added.instance_name.workload_name()
+                                    == deleted.instance_name.workload_name()
+                            })
+                            .map(|added| WorkloadState {
+                                instance_name: deleted.instance_name.clone(),
+                                execution_state: ExecutionState::rescheduled(format!(
+                                    "Rescheduled to agent '{}'",
+                                    added.instance_name.agent_name()
+                                )),
+                            })
+                    })
+                    .collect();
+                self.workload_states_map
+                    .process_new_states(rescheduled_states);
+
+                // the lost agent removes its instances when it reconnects
+                self.to_agents
+                    .send(FromServer::UpdateWorkload(UpdateWorkload {
+                        added_workloads,
+                        deleted_workloads,
+                    }))
+                    .await
+                    .unwrap_or_illegal_state();
+            }
+
+            let lost_workload_states = self
+                .workload_states_map
+                .get_workload_state_for_agent(&agent_name);
+
+            // [impl->swdd~server-records-events~1]
+            lost_workload_states
+                .iter()
+                .for_each(|state| self.event_log.execution_state_changed(state));
+            // [impl->swdd~server-records-workload-state-history~1]
+            self.workload_state_history.record(&lost_workload_states);
+            // [impl->swdd~server-notifies-workload-state-changes~1]
+            self.notifier.notify_execution_states(&lost_workload_states);
+            // [impl->swdd~server-collects-metrics~1]
+            self.metrics
+                .update_workload_states(&self.workload_states_map);
+
+            if !lost_workload_states.is_empty() {
+                self.to_agents
+                    .update_workload_state(lost_workload_states)
+                    .await
+                    .unwrap_or_illegal_state();
+            }
+        }
+    }
+
+    // [impl->swdd~server-resyncs-workload-states~1]
+    // Sends the images of the desired state to the agent if they differ from the images sent before
+    // [impl->swdd~server-sends-images-to-pull-to-agents~1]
+    async fn update_images_to_pull(&mut self, agent_name: &str) {
+        let images = self.server_state.get_images_for_agent(agent_name);
+        let previous_images = self
+            .images_to_pull
+            .insert(agent_name.to_owned(), images.clone())
+            .unwrap_or_default();
+        if images != previous_images {
+            log::debug!(
+                "Sending {} images to pull to agent '{}'",
+                images.len(),
+                agent_name
+            );
+            self.to_agents
+                .pull_images(Some(agent_name.to_owned()), images)
+                .await
+                .unwrap_or_illegal_state();
+        }
+    }
+
+    async fn update_images_to_pull_of_connected_agents(&mut self) {
+        let connected_agents: Vec<String> = self.images_to_pull.keys().cloned().collect();
+        for agent_name in connected_agents {
+            self.update_images_to_pull(&agent_name).await;
+        }
+    }
+
+    async fn resync_workload_states(&self) {
+        let workload_states: Vec<WorkloadState> = self.workload_states_map.clone().into();
+        if workload_states.is_empty() {
+            return;
+        }
+
+        log::debug!(
+            "Sending full resync of {} workload states.",
+            workload_states.len()
+        );
+        self.to_agents
+            .update_workload_state(workload_states)
+            .await
+            .unwrap_or_illegal_state();
+    }
+
+    // [impl->swdd~server-replicates-desired-state~2]
+    fn replicate_desired_state(&self) {
+        if let Some(state_replicator) = &self.state_replicator {
+            state_replicator.publish(self.server_state.get_desired_state());
+        }
+    }
+
+    // [impl->swdd~server-records-audit-log~1]
+    fn record_audit_entry(
+        &mut self,
+        request_id: &str,
+        update_mask: Vec<String>,
+        result: Result<(), &str>,
+    ) {
+        let requester = AuditRequester::from_request_id(request_id, &self.workload_states_map);
+        self.audit_log.record(requester, update_mask, result);
+    }
+
+    // [impl->swdd~server-records-events~1]
+    fn record_workload_events(
+        &mut self,
+        added_workloads: &[WorkloadSpec],
+        deleted_workloads: &[DeletedWorkload],
+    ) {
+        deleted_workloads
+            .iter()
+            .for_each(|deleted| self.event_log.workload_deleted(&deleted.instance_name));
+        added_workloads
+            .iter()
+            .for_each(|added| self.event_log.workload_added(&added.instance_name));
+    }
+
+    // [impl->swdd~server-schedules-workloads-on-newly-connected-agent~1]
+    async fn schedule_unscheduled_workloads(&mut self) {
+        if let Some((added_workloads, deleted_workloads)) =
+            self.server_state.schedule_unscheduled_workloads()
+        {
+            log::info!(
+                "Scheduled {} workloads by their node selector",
+                added_workloads.len()
+            );
+            self.workload_states_map.initial_state(&added_workloads);
+            self.record_workload_events(&added_workloads, &deleted_workloads);
+
+            // the unscheduled workloads were never started on an agent
+            self.handle_not_started_deleted_workloads(deleted_workloads)
+                .await;
+            // [impl->swdd~server-collects-metrics~1]
+            self.metrics
+                .update_workload_states(&self.workload_states_map);
+        }
+    }
+
     // [impl->swdd~server-handles-not-started-deleted-workloads~1]
     async fn handle_not_started_deleted_workloads(
         &mut self,
@@ -389,12 +1262,49 @@ impl AnkaiosServer {
             && self
                 .workload_states_map
                 .get_workload_state_for_workload(&deleted_workload.instance_name)
-                .map_or(false, |current_execution_state| {
-                    current_execution_state.is_pending_initial()
-                })
+                .is_some_and(|current_execution_state| current_execution_state.is_pending_initial())
+    }
+}
+
+enum ServerTimer {
+    WorkloadStatesResync,
+    AgentLost,
+    AgentRequestTimeout,
+}
+
+async fn tick(timer: &mut Option<Interval>) {
+    match timer {
+        Some(timer) => {
+            timer.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
     }
 }
 
+// The exit code or an error are the last responses of an exec session,
+// closing data or an error are the last responses of a port forwarding
+fn is_end_of_agent_session(response: &ank_base::Response) -> bool {
+    matches!(
+        response.response_content,
+        Some(ank_base::response::ResponseContent::Error(_))
+            | Some(ank_base::response::ResponseContent::ExecOutput(
+                ank_base::ExecOutput {
+                    exec_output_enum: Some(ank_base::exec_output::ExecOutputEnum::ExitCode(_)),
+                }
+            ))
+            | Some(ank_base::response::ResponseContent::PortForwardData(
+                ank_base::PortForwardData { close: true, .. }
+            ))
+    )
+}
+
 //////////////////////////////////////////////////////////////////////////////
 //                 ########  #######    #########  #########                //
 //                    ##     ##        ##             ##                    //
@@ -414,17 +1324,22 @@ mod tests {
     use super::ank_base;
     use api::ank_base::WorkloadMap;
     use common::commands::{
-        AgentLoadStatus, CompleteStateRequest, ServerHello, UpdateWorkload, UpdateWorkloadState,
+        AgentExecRequest, AgentHello, AgentLoadStatus, AgentPauseWorkloadRequest,
+        AgentPortForwardRequest, AgentRestartWorkloadRequest, CompleteStateRequest, ExecInput,
+        ExecRequest, ExecStart, PauseWorkloadRequest, PortForwardData, PortForwardRequest,
+        PortForwardStart, PullImages, RestartWorkloadRequest, ServerHello, UpdateWorkload,
+        UpdateWorkloadState,
     };
     use common::from_server_interface::FromServer;
     use common::objects::{
         generate_test_stored_workload_spec, generate_test_workload_spec_with_param,
         generate_test_workload_states_map_with_data, CompleteState, CpuUsage, DeletedWorkload,
         ExecutionState, ExecutionStateEnum, FreeMemory, PendingSubstate, State,
-        WorkloadInstanceName, WorkloadState,
+        WorkloadInstanceName, WorkloadState, SUPPORTED_API_VERSIONS,
     };
     use common::test_utils::generate_test_proto_workload_with_param;
     use common::to_server_interface::ToServerInterface;
+    use grpc::server::StateReplicator;
     use mockall::predicate;
 
     const AGENT_A: &str = "agent_A";
@@ -435,7 +1350,14 @@ mod tests {
     const RUNTIME_NAME: &str = "runtime";
     const REQUEST_ID_A: &str = "agent_A@id1";
 
-    // [utest->swdd~server-uses-async-channels~1]
+    fn generate_test_agent_hello(agent_name: &str) -> AgentHello {
+        AgentHello {
+            agent_name: agent_name.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    // [utest->swdd~server-uses-async-channels~1]
     // [utest->swdd~server-fails-on-invalid-startup-state~1]
     #[tokio::test]
     async fn utest_server_start_fail_on_invalid_startup_config() {
@@ -497,7 +1419,10 @@ mod tests {
         let result = server.start(Some(startup_state)).await;
         assert_eq!(
             result,
-            Err("Unsupported API version. Received 'invalidVersion', expected 'v0.1'".into())
+            Err(
+                "Unsupported API version. Received 'invalidVersion', expected one of 'v0.1', 'v0.2'"
+                    .into()
+            )
         );
     }
 
@@ -730,6 +1655,19 @@ mod tests {
         mock_server_state.expect_cleanup_state().return_const(());
 
         let mut seq = mockall::Sequence::new();
+        mock_server_state
+            .expect_add_agent()
+            .with(predicate::eq(generate_test_agent_hello(AGENT_A)))
+            .once()
+            .in_sequence(&mut seq)
+            .return_const(());
+
+        mock_server_state
+            .expect_schedule_unscheduled_workloads()
+            .once()
+            .in_sequence(&mut seq)
+            .return_const(None);
+
         mock_server_state
             .expect_get_workloads_for_agent()
             .with(mockall::predicate::eq(AGENT_A.to_string()))
@@ -739,11 +1677,17 @@ mod tests {
 
         mock_server_state
             .expect_add_agent()
-            .with(predicate::eq(AGENT_A.to_owned()))
+            .with(predicate::eq(generate_test_agent_hello(AGENT_B)))
             .once()
             .in_sequence(&mut seq)
             .return_const(());
 
+        mock_server_state
+            .expect_schedule_unscheduled_workloads()
+            .once()
+            .in_sequence(&mut seq)
+            .return_const(None);
+
         mock_server_state
             .expect_get_workloads_for_agent()
             .with(mockall::predicate::eq(AGENT_B.to_string()))
@@ -752,18 +1696,17 @@ mod tests {
             .return_const(vec![w2.clone()]);
 
         mock_server_state
-            .expect_add_agent()
-            .with(predicate::eq(AGENT_B.to_owned()))
-            .once()
-            .in_sequence(&mut seq)
-            .return_const(());
+            .expect_get_images_for_agent()
+            .return_const(Vec::new());
 
         server.server_state = mock_server_state;
 
         let server_task = tokio::spawn(async move { server.start(None).await });
 
         // first agent connects to the server
-        let agent_hello_result = to_server.agent_hello(AGENT_A.to_string()).await;
+        let agent_hello_result = to_server
+            .agent_hello(generate_test_agent_hello(AGENT_A))
+            .await;
         assert!(agent_hello_result.is_ok());
 
         let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
@@ -797,7 +1740,9 @@ mod tests {
             from_server_command
         );
 
-        let agent_hello_result = to_server.agent_hello(AGENT_B.to_owned()).await;
+        let agent_hello_result = to_server
+            .agent_hello(generate_test_agent_hello(AGENT_B))
+            .await;
         assert!(agent_hello_result.is_ok());
 
         let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
@@ -819,7 +1764,7 @@ mod tests {
             from_server_command
         );
 
-        // [utest->swdd~server-forwards-workload-state~1]
+        // [utest->swdd~server-forwards-workload-state~2]
         // send update_workload_state for second agent which is then stored in the workload_state_db in ankaios server
         let test_wl_2_state_succeeded = common::objects::generate_test_workload_state(
             WORKLOAD_NAME_2,
@@ -841,7 +1786,7 @@ mod tests {
 
         // send update_workload_state for first agent again which is then updated in the workload_state_db in ankaios server
         let test_wl_1_state_succeeded = common::objects::generate_test_workload_state(
-            WORKLOAD_NAME_2,
+            WORKLOAD_NAME_1,
             ExecutionState::succeeded(),
         );
         let update_workload_state_result = to_server
@@ -862,6 +1807,165 @@ mod tests {
         assert!(comm_middle_ware_receiver.try_recv().is_err());
     }
 
+    // [utest->swdd~server-forwards-workload-state~2]
+    #[tokio::test]
+    async fn utest_server_does_not_forward_unchanged_workload_states() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state.expect_cleanup_state().return_const(());
+        server.server_state = mock_server_state;
+
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        let test_wl_1_state_running = common::objects::generate_test_workload_state(
+            WORKLOAD_NAME_1,
+            ExecutionState::running(),
+        );
+        let test_wl_2_state_running = common::objects::generate_test_workload_state(
+            WORKLOAD_NAME_2,
+            ExecutionState::running(),
+        );
+
+        let update_workload_state_result = to_server
+            .update_workload_state(vec![test_wl_1_state_running.clone()])
+            .await;
+        assert!(update_workload_state_result.is_ok());
+
+        let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
+        assert_eq!(
+            FromServer::UpdateWorkloadState(UpdateWorkloadState {
+                workload_states: vec![test_wl_1_state_running.clone()]
+            }),
+            from_server_command
+        );
+
+        let update_workload_state_result = to_server
+            .update_workload_state(vec![
+                test_wl_1_state_running,
+                test_wl_2_state_running.clone(),
+            ])
+            .await;
+        assert!(update_workload_state_result.is_ok());
+
+        let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
+        assert_eq!(
+            FromServer::UpdateWorkloadState(UpdateWorkloadState {
+                workload_states: vec![test_wl_2_state_running]
+            }),
+            from_server_command
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-records-workload-state-history~1]
+    // [utest->swdd~server-provides-workload-state-history~1]
+    #[tokio::test]
+    async fn utest_server_returns_workload_state_history_when_requested() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state.expect_cleanup_state().return_const(());
+        mock_server_state
+            .expect_get_complete_state_by_field_mask()
+            .once()
+            .return_const(Ok(ank_base::CompleteState::default()));
+        server.server_state = mock_server_state;
+
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        let test_wl_1_state_running = common::objects::generate_test_workload_state(
+            WORKLOAD_NAME_1,
+            ExecutionState::running(),
+        );
+        let update_workload_state_result = to_server
+            .update_workload_state(vec![test_wl_1_state_running])
+            .await;
+        assert!(update_workload_state_result.is_ok());
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkloadState(_)
+        ));
+
+        let request_complete_state_result = to_server
+            .request_complete_state(
+                REQUEST_ID_A.to_string(),
+                CompleteStateRequest {
+                    field_mask: vec!["workloadStateHistory".to_string()],
+                    selector: vec![],
+                },
+            )
+            .await;
+        assert!(request_complete_state_result.is_ok());
+
+        let FromServer::Response(response) = comm_middle_ware_receiver.recv().await.unwrap() else {
+            panic!("Expected a response");
+        };
+        let Some(ank_base::response::ResponseContent::CompleteState(complete_state)) =
+            response.response_content
+        else {
+            panic!("Expected a complete state response");
+        };
+        let workload_state_history = complete_state.workload_state_history.unwrap();
+        let transitions = &workload_state_history.workloads[WORKLOAD_NAME_1].transitions;
+        assert_eq!(transitions.len(), 1);
+        assert_eq!(
+            transitions[0].execution_state,
+            Some(ExecutionState::running().into())
+        );
+
+        server_task.abort();
+    }
+
+    // [utest->swdd~server-resyncs-workload-states~1]
+    #[tokio::test]
+    async fn utest_server_resyncs_all_workload_states_periodically() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents)
+            .with_workload_states_resync_interval(std::time::Duration::from_millis(10));
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state.expect_cleanup_state().return_const(());
+        server.server_state = mock_server_state;
+
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        let test_wl_1_state_running = common::objects::generate_test_workload_state(
+            WORKLOAD_NAME_1,
+            ExecutionState::running(),
+        );
+        let update_workload_state_result = to_server
+            .update_workload_state(vec![test_wl_1_state_running.clone()])
+            .await;
+        assert!(update_workload_state_result.is_ok());
+
+        // the first message forwards the change, the following ones are the resyncs
+        for _ in 0..2 {
+            let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
+            assert_eq!(
+                FromServer::UpdateWorkloadState(UpdateWorkloadState {
+                    workload_states: vec![test_wl_1_state_running.clone()]
+                }),
+                from_server_command
+            );
+        }
+
+        server_task.abort();
+    }
+
     // [utest->swdd~server-uses-async-channels~1]
     // [utest->swdd~server-provides-update-desired-state-interface~1]
     // [utest->swdd~server-starts-without-startup-config~1]
@@ -950,6 +2054,62 @@ mod tests {
         assert!(comm_middle_ware_receiver.try_recv().is_err());
     }
 
+    // [utest->swdd~server-forwards-delete-grace-period~1]
+    #[tokio::test]
+    async fn utest_server_forwards_grace_period_to_deleted_workloads() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let w1 = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME_NAME.to_string(),
+        );
+        let deleted_workload = DeletedWorkload {
+            instance_name: w1.instance_name.clone(),
+            ..Default::default()
+        };
+
+        let update_mask = vec![format!("desiredState.workloads.{}", WORKLOAD_NAME_1)];
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_update()
+            .once()
+            .return_const(Ok(Some((vec![], vec![deleted_workload.clone()]))));
+        mock_server_state
+            .expect_contains_connected_agent()
+            .return_const(true);
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        let update_state_result = to_server
+            .update_state_with_grace_period(
+                REQUEST_ID_A.to_string(),
+                CompleteState::default(),
+                update_mask,
+                30,
+            )
+            .await;
+        assert!(update_state_result.is_ok());
+
+        let update_workload_message = comm_middle_ware_receiver.recv().await.unwrap();
+        assert_eq!(
+            FromServer::UpdateWorkload(UpdateWorkload {
+                added_workloads: vec![],
+                deleted_workloads: vec![DeletedWorkload {
+                    grace_period_secs: Some(30),
+                    ..deleted_workload
+                }],
+            }),
+            update_workload_message
+        );
+
+        server_task.abort();
+    }
+
     // [utest->swdd~server-uses-async-channels~1]
     // [utest->swdd~server-provides-update-desired-state-interface~1]
     // [utest->swdd~server-starts-without-startup-config~1]
@@ -1016,135 +2176,1144 @@ mod tests {
         assert!(comm_middle_ware_receiver.try_recv().is_err());
     }
 
-    // [utest->swdd~server-uses-async-channels~1]
-    // [utest->swdd~server-provides-update-desired-state-interface~1]
-    // [utest->swdd~server-starts-without-startup-config~1]
+    // [utest->swdd~server-replicates-desired-state~2]
     #[tokio::test]
-    async fn utest_server_sends_workloads_and_workload_states_when_requested_update_state_error() {
+    async fn utest_server_replicates_desired_state_after_update_state() {
         let _ = env_logger::builder().is_test(true).try_init();
         let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
         let (to_agents, mut comm_middle_ware_receiver) =
             create_from_server_channel(common::CHANNEL_CAPACITY);
 
-        let w1 = generate_test_stored_workload_spec(AGENT_A.to_owned(), RUNTIME_NAME.to_string());
-
-        let update_state = CompleteState {
-            desired_state: State {
-                workloads: vec![(WORKLOAD_NAME_1.to_owned(), w1.clone())]
-                    .into_iter()
-                    .collect(),
-                ..Default::default()
-            },
+        let update_state = CompleteState::default();
+        let replicated_state = ank_base::State {
+            api_version: "v0.1".into(),
             ..Default::default()
         };
-        let update_mask = vec![format!("desiredState.workloads.{}", WORKLOAD_NAME_1)];
-        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+
+        let state_replicator = StateReplicator::default();
+        let mut server = AnkaiosServer::new(server_receiver, to_agents)
+            .with_state_replicator(state_replicator.clone());
         let mut mock_server_state = MockServerState::new();
         mock_server_state
             .expect_update()
-            .with(
-                mockall::predicate::eq(update_state.clone()),
-                mockall::predicate::eq(update_mask.clone()),
-            )
             .once()
-            .return_const(Err(UpdateStateError::ResultInvalid(
-                "some update error.".to_string(),
-            )));
+            .return_const(Ok(None));
+        mock_server_state
+            .expect_get_desired_state()
+            .once()
+            .return_const(replicated_state.clone());
         server.server_state = mock_server_state;
+
+        let replicated_state_rx = state_replicator.subscribe();
         let server_task = tokio::spawn(async move { server.start(None).await });
 
-        // send new state to server
         let update_state_result = to_server
-            .update_state(REQUEST_ID_A.to_string(), update_state, update_mask)
+            .update_state(REQUEST_ID_A.to_string(), update_state, vec![])
             .await;
         assert!(update_state_result.is_ok());
-
         assert!(matches!(
             comm_middle_ware_receiver.recv().await.unwrap(),
-            FromServer::Response(ank_base::Response {
-                request_id,
-                response_content: Some(ank_base::response::ResponseContent::Error(_))
-            }) if request_id == REQUEST_ID_A
+            FromServer::Response(ank_base::Response { request_id, .. })
+                if request_id == REQUEST_ID_A
         ));
 
-        assert!(tokio::time::timeout(
-            tokio::time::Duration::from_millis(200),
-            comm_middle_ware_receiver.recv()
-        )
-        .await
-        .is_err());
+        assert!(replicated_state_rx.has_changed().unwrap());
+        assert_eq!(*replicated_state_rx.borrow(), Some(replicated_state));
 
         server_task.abort();
-        assert!(comm_middle_ware_receiver.try_recv().is_err());
     }
 
-    // [utest->swdd~server-uses-async-channels~1]
-    // [utest->swdd~server-provides-interface-get-complete-state~2]
-    // [utest->swdd~server-includes-id-in-control-interface-response~1]
-    // [utest->swdd~server-starts-without-startup-config~1]
+    // [utest->swdd~server-sends-images-to-pull-to-agents~1]
     #[tokio::test]
-    async fn utest_server_returns_complete_state_when_received_request_complete_state() {
+    async fn utest_server_sends_images_to_pull_to_connected_agents() {
         let _ = env_logger::builder().is_test(true).try_init();
         let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
         let (to_agents, mut comm_middle_ware_receiver) =
             create_from_server_channel(common::CHANNEL_CAPACITY);
 
-        let w1 = generate_test_proto_workload_with_param(AGENT_A, RUNTIME_NAME);
+        let images = vec!["image_1:1.0".to_string()];
+        let updated_images = vec!["image_1:1.0".to_string(), "image_2:1.0".to_string()];
 
-        let w2 = generate_test_proto_workload_with_param(AGENT_A, RUNTIME_NAME);
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state.expect_add_agent().once().return_const(());
+        mock_server_state
+            .expect_schedule_unscheduled_workloads()
+            .once()
+            .return_const(None);
+        mock_server_state
+            .expect_get_workloads_for_agent()
+            .once()
+            .return_const(vec![]);
+        mock_server_state
+            .expect_update()
+            .times(2)
+            .return_const(Ok(None));
+        mock_server_state
+            .expect_get_desired_state()
+            .return_const(ank_base::State::default());
+
+        let mut seq = mockall::Sequence::new();
+        mock_server_state
+            .expect_get_images_for_agent()
+            .with(predicate::eq(AGENT_A.to_owned()))
+            .once()
+            .in_sequence(&mut seq)
+            .return_const(images.clone());
+        mock_server_state
+            .expect_get_images_for_agent()
+            .with(predicate::eq(AGENT_A.to_owned()))
+            .times(2)
+            .in_sequence(&mut seq)
+            .return_const(updated_images.clone());
+        server.server_state = mock_server_state;
+
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .agent_hello(generate_test_agent_hello(AGENT_A))
+            .await
+            .is_ok());
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::ServerHello(_)
+        ));
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::PullImages(PullImages {
+                agent_name: Some(AGENT_A.to_owned()),
+                images,
+            })
+        );
+
+        assert!(to_server
+            .update_state(REQUEST_ID_A.to_string(), CompleteState::default(), vec![])
+            .await
+            .is_ok());
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::PullImages(PullImages {
+                agent_name: Some(AGENT_A.to_owned()),
+                images: updated_images,
+            })
+        );
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(_)
+        ));
+
+        // unchanged images are not sent again
+        assert!(to_server
+            .update_state(REQUEST_ID_A.to_string(), CompleteState::default(), vec![])
+            .await
+            .is_ok());
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(_)
+        ));
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-uses-async-channels~1]
+    // [utest->swdd~server-provides-update-desired-state-interface~1]
+    // [utest->swdd~server-starts-without-startup-config~1]
+    #[tokio::test]
+    async fn utest_server_sends_workloads_and_workload_states_when_requested_update_state_error() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let w1 = generate_test_stored_workload_spec(AGENT_A.to_owned(), RUNTIME_NAME.to_string());
+
+        let update_state = CompleteState {
+            desired_state: State {
+                workloads: vec![(WORKLOAD_NAME_1.to_owned(), w1.clone())]
+                    .into_iter()
+                    .collect(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let update_mask = vec![format!("desiredState.workloads.{}", WORKLOAD_NAME_1)];
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_update()
+            .with(
+                mockall::predicate::eq(update_state.clone()),
+                mockall::predicate::eq(update_mask.clone()),
+            )
+            .once()
+            .return_const(Err(UpdateStateError::ResultInvalid(
+                "some update error.".to_string(),
+            )));
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        // send new state to server
+        let update_state_result = to_server
+            .update_state(REQUEST_ID_A.to_string(), update_state, update_mask)
+            .await;
+        assert!(update_state_result.is_ok());
+
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(ank_base::Response {
+                request_id,
+                response_content: Some(ank_base::response::ResponseContent::Error(_))
+            }) if request_id == REQUEST_ID_A
+        ));
+
+        assert!(tokio::time::timeout(
+            tokio::time::Duration::from_millis(200),
+            comm_middle_ware_receiver.recv()
+        )
+        .await
+        .is_err());
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-schedules-workloads-on-newly-connected-agent~1]
+    #[tokio::test]
+    async fn utest_server_schedules_unscheduled_workloads_on_agent_hello() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let unscheduled_workload = generate_test_workload_spec_with_param(
+            "".to_string(),
+            WORKLOAD_NAME_1.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+        let scheduled_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_string(),
+            WORKLOAD_NAME_1.to_string(),
+            RUNTIME_NAME.to_string(),
+        );
+        let labels = HashMap::from([("zone".to_string(), "cockpit".to_string())]);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        server
+            .workload_states_map
+            .initial_state(&vec![unscheduled_workload.clone()]);
+
+        let mut mock_server_state = MockServerState::new();
+        let mut seq = mockall::Sequence::new();
+        mock_server_state
+            .expect_add_agent()
+            .with(predicate::eq(AgentHello {
+                labels: labels.clone(),
+                ..generate_test_agent_hello(AGENT_A)
+            }))
+            .once()
+            .in_sequence(&mut seq)
+            .return_const(());
+        mock_server_state
+            .expect_schedule_unscheduled_workloads()
+            .once()
+            .in_sequence(&mut seq)
+            .return_const(Some((
+                vec![scheduled_workload.clone()],
+                vec![DeletedWorkload {
+                    instance_name: unscheduled_workload.instance_name.clone(),
+                    ..Default::default()
+                }],
+            )));
+        mock_server_state
+            .expect_get_workloads_for_agent()
+            .with(predicate::eq(AGENT_A.to_string()))
+            .once()
+            .in_sequence(&mut seq)
+            .return_const(vec![scheduled_workload.clone()]);
+        mock_server_state
+            .expect_get_images_for_agent()
+            .return_const(Vec::new());
+
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .agent_hello(AgentHello {
+                labels,
+                ..generate_test_agent_hello(AGENT_A)
+            })
+            .await
+            .is_ok());
+
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkloadState(UpdateWorkloadState {
+                workload_states: vec![WorkloadState {
+                    instance_name: unscheduled_workload.instance_name,
+                    execution_state: ExecutionState::removed(),
+                }]
+            })
+        );
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::ServerHello(ServerHello {
+                agent_name: Some(AGENT_A.to_string()),
+                added_workloads: vec![scheduled_workload],
+            })
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-records-events~1]
+    // [utest->swdd~server-provides-events~1]
+    #[tokio::test]
+    async fn utest_server_returns_events_when_received_request_events() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let request_id = format!("{AGENT_A}@my_request_id");
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_workloads_for_agent()
+            .once()
+            .return_const(vec![]);
+        mock_server_state.expect_add_agent().once().return_const(());
+        mock_server_state
+            .expect_schedule_unscheduled_workloads()
+            .once()
+            .return_const(None);
+        mock_server_state
+            .expect_get_images_for_agent()
+            .return_const(Vec::new());
+
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .agent_hello(generate_test_agent_hello(AGENT_A))
+            .await
+            .is_ok());
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::ServerHello(_)
+        ));
+
+        assert!(to_server
+            .request_events(
+                request_id.clone(),
+                common::commands::EventsRequest {
+                    after_sequence_number: 0
+                }
+            )
+            .await
+            .is_ok());
+
+        let Some(FromServer::Response(ank_base::Response {
+            request_id: received_request_id,
+            response_content: Some(ank_base::response::ResponseContent::Events(events)),
+        })) = comm_middle_ware_receiver.recv().await
+        else {
+            panic!("Expected an Events response");
+        };
+        assert_eq!(received_request_id, request_id);
+        assert_eq!(events.events.len(), 1);
+        assert_eq!(events.events[0].sequence_number, 1);
+        assert_eq!(
+            events.events[0].event_type(),
+            ank_base::EventType::AgentConnected
+        );
+        assert_eq!(events.events[0].agent, AGENT_A);
+
+        assert!(to_server
+            .request_events(
+                request_id.clone(),
+                common::commands::EventsRequest {
+                    after_sequence_number: 1
+                }
+            )
+            .await
+            .is_ok());
+
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(ank_base::Response {
+                request_id,
+                response_content: Some(ank_base::response::ResponseContent::Events(
+                    ank_base::Events { events: vec![] }
+                ))
+            })
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-records-audit-log~1]
+    // [utest->swdd~server-provides-audit-log~1]
+    #[tokio::test]
+    async fn utest_server_returns_audit_entries_of_rejected_update_when_received_audit_request() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let request_id = "cli-conn-1234@my_request_id".to_string();
+        let update_state = CompleteState {
+            desired_state: State {
+                api_version: "incompatible_version".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let update_mask = vec![format!("desiredState.workloads.{}", WORKLOAD_NAME_1)];
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .update_state(request_id.clone(), update_state, update_mask.clone())
+            .await
+            .is_ok());
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(ank_base::Response {
+                response_content: Some(ank_base::response::ResponseContent::Error(_)),
+                ..
+            })
+        ));
+
+        assert!(to_server
+            .request_audit(
+                request_id.clone(),
+                common::commands::AuditRequest {
+                    after_sequence_number: 0
+                }
+            )
+            .await
+            .is_ok());
+
+        let Some(FromServer::Response(ank_base::Response {
+            request_id: received_request_id,
+            response_content: Some(ank_base::response::ResponseContent::AuditEntries(audit_entries)),
+        })) = comm_middle_ware_receiver.recv().await
+        else {
+            panic!("Expected an AuditEntries response");
+        };
+        assert_eq!(received_request_id, request_id);
+        assert_eq!(audit_entries.entries.len(), 1);
+        let entry = &audit_entries.entries[0];
+        assert_eq!(entry.sequence_number, 1);
+        assert_eq!(entry.requester_type(), ank_base::RequesterType::Cli);
+        assert_eq!(entry.requester, "cli-conn-1234");
+        assert_eq!(entry.update_mask, update_mask);
+        assert!(!entry.accepted);
+        assert!(entry.message.contains("incompatible_version"));
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-uses-async-channels~1]
+    // [utest->swdd~server-provides-interface-get-complete-state~2]
+    // [utest->swdd~server-includes-id-in-control-interface-response~1]
+    // [utest->swdd~server-starts-without-startup-config~1]
+    #[tokio::test]
+    async fn utest_server_returns_complete_state_when_received_request_complete_state() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let w1 = generate_test_proto_workload_with_param(AGENT_A, RUNTIME_NAME);
+
+        let w2 = generate_test_proto_workload_with_param(AGENT_A, RUNTIME_NAME);
 
         let w3 = generate_test_proto_workload_with_param(AGENT_B, RUNTIME_NAME);
 
-        let workloads = HashMap::from([
-            (WORKLOAD_NAME_1.to_owned(), w1),
-            (WORKLOAD_NAME_2.to_owned(), w2),
-            (WORKLOAD_NAME_3.to_owned(), w3),
-        ]);
+        let workloads = HashMap::from([
+            (WORKLOAD_NAME_1.to_owned(), w1),
+            (WORKLOAD_NAME_2.to_owned(), w2),
+            (WORKLOAD_NAME_3.to_owned(), w3),
+        ]);
+
+        let workload_map = WorkloadMap { workloads };
+
+        let current_complete_state = ank_base::CompleteState {
+            desired_state: Some(ank_base::State {
+                workloads: Some(workload_map),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let request_id = format!("{AGENT_A}@my_request_id");
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_complete_state_by_field_mask()
+            .with(
+                mockall::predicate::function(|request_compl_state| {
+                    request_compl_state
+                        == &CompleteStateRequest {
+                            field_mask: vec![],
+                            selector: vec![],
+                        }
+                }),
+                mockall::predicate::always(),
+            )
+            .once()
+            .return_const(Ok(current_complete_state.clone()));
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        // send command 'CompleteStateRequest'
+        // CompleteState shall contain the complete state
+        let request_complete_state_result = to_server
+            .request_complete_state(
+                request_id.clone(),
+                CompleteStateRequest {
+                    field_mask: vec![],
+                    selector: vec![],
+                },
+            )
+            .await;
+        assert!(request_complete_state_result.is_ok());
+
+        let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
+
+        assert_eq!(
+            from_server_command,
+            common::from_server_interface::FromServer::Response(ank_base::Response {
+                request_id,
+                response_content: Some(ank_base::response::ResponseContent::CompleteState(
+                    Box::new(current_complete_state)
+                ))
+            })
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-provides-dependency-graph~1]
+    #[tokio::test]
+    async fn utest_server_returns_dependency_graph_when_received_request_dependency_graph() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let dependency_graph = ank_base::DependencyGraph {
+            nodes: vec![ank_base::DependencyGraphNode {
+                workload_name: WORKLOAD_NAME_1.to_owned(),
+                agent: AGENT_A.to_owned(),
+                execution_state: Some(ExecutionState::running().into()),
+            }],
+            edges: vec![],
+        };
+
+        let request_id = format!("{AGENT_A}@my_request_id");
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_dependency_graph()
+            .once()
+            .return_const(dependency_graph.clone());
+        server.server_state = mock_server_state;
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .request_dependency_graph(request_id.clone())
+            .await
+            .is_ok());
+
+        let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
+
+        assert_eq!(
+            from_server_command,
+            common::from_server_interface::FromServer::Response(ank_base::Response {
+                request_id,
+                response_content: Some(ank_base::response::ResponseContent::DependencyGraph(
+                    dependency_graph
+                ))
+            })
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    fn exec_start() -> ExecRequest {
+        ExecRequest::Start(ExecStart {
+            workload_name: WORKLOAD_NAME_1.to_owned(),
+            command: vec!["sh".to_owned()],
+        })
+    }
+
+    fn exec_input() -> ExecRequest {
+        ExecRequest::Input(ExecInput {
+            stdin: b"exit 3\n".to_vec(),
+            close_stdin: true,
+        })
+    }
+
+    // [utest->swdd~server-forwards-exec-request-to-agent~1]
+    // [utest->swdd~server-forwards-exec-output~1]
+    #[tokio::test]
+    async fn utest_server_forwards_exec_session_to_agent_of_workload() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_agent_of_workload()
+            .with(predicate::eq(WORKLOAD_NAME_1))
+            .once()
+            .return_const(Some(AGENT_B.to_owned()));
+        mock_server_state
+            .expect_contains_connected_agent()
+            .with(predicate::eq(AGENT_B))
+            .once()
+            .return_const(true);
+        server.server_state = mock_server_state;
+
+        let exit_code_response = ank_base::Response {
+            request_id: REQUEST_ID_A.to_owned(),
+            response_content: Some(ank_base::response::ResponseContent::ExecOutput(
+                ank_base::ExecOutput {
+                    exec_output_enum: Some(ank_base::exec_output::ExecOutputEnum::ExitCode(3)),
+                },
+            )),
+        };
+
+        assert!(to_server
+            .request_exec(REQUEST_ID_A.to_owned(), exec_start())
+            .await
+            .is_ok());
+        assert!(to_server
+            .request_exec(REQUEST_ID_A.to_owned(), exec_input())
+            .await
+            .is_ok());
+        assert!(to_server.response(exit_code_response.clone()).await.is_ok());
+        // the session has ended with the exit code
+        assert!(to_server
+            .request_exec(REQUEST_ID_A.to_owned(), exec_input())
+            .await
+            .is_ok());
+
+        drop(to_server);
+        assert!(server.start(None).await.is_ok());
+
+        for expected_exec_request in [exec_start(), exec_input()] {
+            assert_eq!(
+                comm_middle_ware_receiver.recv().await.unwrap(),
+                FromServer::ExecRequest(AgentExecRequest {
+                    agent_name: Some(AGENT_B.to_owned()),
+                    request_id: REQUEST_ID_A.to_owned(),
+                    exec_request: expected_exec_request,
+                })
+            );
+        }
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(exit_code_response)
+        );
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(ank_base::Response {
+                request_id,
+                response_content: Some(ank_base::response::ResponseContent::Error(_)),
+            }) if request_id == REQUEST_ID_A
+        ));
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-forwards-exec-request-to-agent~1]
+    #[tokio::test]
+    async fn utest_server_rejects_exec_request_for_unknown_workload() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_agent_of_workload()
+            .once()
+            .return_const(None);
+        server.server_state = mock_server_state;
+
+        assert!(to_server
+            .request_exec(REQUEST_ID_A.to_owned(), exec_start())
+            .await
+            .is_ok());
+        drop(to_server);
+        assert!(server.start(None).await.is_ok());
+
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(ank_base::Response {
+                request_id: REQUEST_ID_A.to_owned(),
+                response_content: Some(ank_base::response::ResponseContent::Error(
+                    ank_base::Error {
+                        message: format!("Workload '{WORKLOAD_NAME_1}' does not exist"),
+                    }
+                )),
+            })
+        );
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-forwards-restart-workload-request-to-agent~1]
+    #[tokio::test]
+    async fn utest_server_forwards_restart_workload_request_to_agent_of_workload() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        let replica_names = vec![WORKLOAD_NAME_1.to_owned(), format!("{WORKLOAD_NAME_1}-1")];
+        mock_server_state
+            .expect_get_replica_names_of_workload()
+            .with(predicate::eq(WORKLOAD_NAME_1))
+            .once()
+            .return_const(Some(replica_names.clone()));
+        mock_server_state
+            .expect_get_agent_of_workload()
+            .with(predicate::eq(WORKLOAD_NAME_1))
+            .once()
+            .return_const(Some(AGENT_B.to_owned()));
+        mock_server_state
+            .expect_contains_connected_agent()
+            .with(predicate::eq(AGENT_B))
+            .once()
+            .return_const(true);
+        server.server_state = mock_server_state;
+
+        assert!(to_server
+            .request_restart_workload(
+                REQUEST_ID_A.to_owned(),
+                RestartWorkloadRequest {
+                    workload_name: WORKLOAD_NAME_1.to_owned(),
+                }
+            )
+            .await
+            .is_ok());
+        drop(to_server);
+        assert!(server.start(None).await.is_ok());
+
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::RestartWorkloadRequest(AgentRestartWorkloadRequest {
+                agent_name: Some(AGENT_B.to_owned()),
+                request_id: REQUEST_ID_A.to_owned(),
+                workload_names: replica_names,
+            })
+        );
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+        // the restart is no session
+        assert!(server.agent_sessions.is_empty());
+    }
+
+    // [utest->swdd~server-forwards-restart-workload-request-to-agent~1]
+    #[tokio::test]
+    async fn utest_server_rejects_restart_workload_request_for_workload_without_instances() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_replica_names_of_workload()
+            .once()
+            .return_const(Some(vec![]));
+        mock_server_state.expect_get_agent_of_workload().never();
+        server.server_state = mock_server_state;
+
+        assert!(to_server
+            .request_restart_workload(
+                REQUEST_ID_A.to_owned(),
+                RestartWorkloadRequest {
+                    workload_name: WORKLOAD_NAME_1.to_owned(),
+                }
+            )
+            .await
+            .is_ok());
+        drop(to_server);
+        assert!(server.start(None).await.is_ok());
+
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(ank_base::Response {
+                request_id: REQUEST_ID_A.to_owned(),
+                response_content: Some(ank_base::response::ResponseContent::Error(
+                    ank_base::Error {
+                        message: format!(
+                            "Workload '{WORKLOAD_NAME_1}' has no instances to restart"
+                        ),
+                    }
+                )),
+            })
+        );
+    }
+
+    // [utest->swdd~server-forwards-pause-workload-request-to-agent~1]
+    #[tokio::test]
+    async fn utest_server_forwards_pause_workload_request_to_agent_of_workload() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_replica_names_of_workload()
+            .with(predicate::eq(WORKLOAD_NAME_1))
+            .once()
+            .return_const(Some(vec![WORKLOAD_NAME_1.to_owned()]));
+        mock_server_state
+            .expect_get_agent_of_workload()
+            .with(predicate::eq(WORKLOAD_NAME_1))
+            .once()
+            .return_const(Some(AGENT_B.to_owned()));
+        mock_server_state
+            .expect_contains_connected_agent()
+            .with(predicate::eq(AGENT_B))
+            .once()
+            .return_const(true);
+        server.server_state = mock_server_state;
+
+        assert!(to_server
+            .request_pause_workload(
+                REQUEST_ID_A.to_owned(),
+                PauseWorkloadRequest {
+                    workload_name: WORKLOAD_NAME_1.to_owned(),
+                    resume: true,
+                }
+            )
+            .await
+            .is_ok());
+        drop(to_server);
+        assert!(server.start(None).await.is_ok());
+
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::PauseWorkloadRequest(AgentPauseWorkloadRequest {
+                agent_name: Some(AGENT_B.to_owned()),
+                request_id: REQUEST_ID_A.to_owned(),
+                workload_names: vec![WORKLOAD_NAME_1.to_owned()],
+                resume: true,
+            })
+        );
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-forwards-pause-workload-request-to-agent~1]
+    #[tokio::test]
+    async fn utest_server_rejects_pause_workload_request_for_workload_without_instances() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_replica_names_of_workload()
+            .once()
+            .return_const(Some(vec![]));
+        mock_server_state.expect_get_agent_of_workload().never();
+        server.server_state = mock_server_state;
+
+        assert!(to_server
+            .request_pause_workload(
+                REQUEST_ID_A.to_owned(),
+                PauseWorkloadRequest {
+                    workload_name: WORKLOAD_NAME_1.to_owned(),
+                    resume: false,
+                }
+            )
+            .await
+            .is_ok());
+        drop(to_server);
+        assert!(server.start(None).await.is_ok());
+
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(ank_base::Response {
+                request_id: REQUEST_ID_A.to_owned(),
+                response_content: Some(ank_base::response::ResponseContent::Error(
+                    ank_base::Error {
+                        message: format!("Workload '{WORKLOAD_NAME_1}' has no instances to pause"),
+                    }
+                )),
+            })
+        );
+    }
+
+    // [utest->swdd~server-ends-exec-sessions-of-disconnected-agent~1]
+    #[tokio::test]
+    async fn utest_server_ends_exec_sessions_of_disconnected_agent() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_agent_of_workload()
+            .return_const(Some(AGENT_B.to_owned()));
+        mock_server_state
+            .expect_contains_connected_agent()
+            .return_const(true);
+        mock_server_state
+            .expect_remove_agent()
+            .with(predicate::eq(AGENT_B))
+            .once()
+            .return_const(());
+        server.server_state = mock_server_state;
+
+        assert!(to_server
+            .request_exec(REQUEST_ID_A.to_owned(), exec_start())
+            .await
+            .is_ok());
+        assert!(to_server.agent_gone(AGENT_B.to_owned()).await.is_ok());
+        drop(to_server);
+        assert!(server.start(None).await.is_ok());
+
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::ExecRequest(_)
+        ));
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkloadState(_)
+        ));
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(ank_base::Response {
+                request_id: REQUEST_ID_A.to_owned(),
+                response_content: Some(ank_base::response::ResponseContent::Error(
+                    ank_base::Error {
+                        message: format!("Session ended, agent '{AGENT_B}' disconnected"),
+                    }
+                )),
+            })
+        );
+        assert!(server.agent_sessions.is_empty());
+    }
+
+    // [utest->swdd~server-ends-port-forwardings-of-disconnected-agent~1]
+    #[tokio::test]
+    async fn utest_server_ends_port_forwardings_of_disconnected_agent() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_agent_of_workload()
+            .return_const(Some(AGENT_B.to_owned()));
+        mock_server_state
+            .expect_contains_connected_agent()
+            .return_const(true);
+        mock_server_state
+            .expect_remove_agent()
+            .with(predicate::eq(AGENT_B))
+            .once()
+            .return_const(());
+        server.server_state = mock_server_state;
+
+        assert!(to_server
+            .request_port_forward(
+                REQUEST_ID_A.to_owned(),
+                PortForwardRequest::Start(PortForwardStart {
+                    workload_name: WORKLOAD_NAME_1.to_owned(),
+                    port: 8080,
+                })
+            )
+            .await
+            .is_ok());
+        assert!(to_server.agent_gone(AGENT_B.to_owned()).await.is_ok());
+        drop(to_server);
+        assert!(server.start(None).await.is_ok());
+
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::PortForwardRequest(_)
+        ));
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkloadState(_)
+        ));
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(ank_base::Response {
+                request_id: REQUEST_ID_A.to_owned(),
+                response_content: Some(ank_base::response::ResponseContent::Error(
+                    ank_base::Error {
+                        message: format!("Session ended, agent '{AGENT_B}' disconnected"),
+                    }
+                )),
+            })
+        );
+        assert!(server.agent_sessions.is_empty());
+    }
+
+    // [utest->swdd~server-forwards-port-forward-request-to-agent~1]
+    // [utest->swdd~server-forwards-port-forward-data~1]
+    #[tokio::test]
+    async fn utest_server_forwards_port_forwarding_to_agent_of_workload() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_agent_of_workload()
+            .with(predicate::eq(WORKLOAD_NAME_1))
+            .once()
+            .return_const(Some(AGENT_B.to_owned()));
+        mock_server_state
+            .expect_contains_connected_agent()
+            .with(predicate::eq(AGENT_B))
+            .once()
+            .return_const(true);
+        server.server_state = mock_server_state;
+
+        let port_forward_start = PortForwardRequest::Start(PortForwardStart {
+            workload_name: WORKLOAD_NAME_1.to_owned(),
+            port: 8080,
+        });
+        let port_forward_data = PortForwardRequest::Data(PortForwardData {
+            data: b"GET /".to_vec(),
+            close: false,
+        });
+        let close_response = ank_base::Response {
+            request_id: REQUEST_ID_A.to_owned(),
+            response_content: Some(ank_base::response::ResponseContent::PortForwardData(
+                ank_base::PortForwardData {
+                    data: vec![],
+                    close: true,
+                },
+            )),
+        };
+
+        assert!(to_server
+            .request_port_forward(REQUEST_ID_A.to_owned(), port_forward_start.clone())
+            .await
+            .is_ok());
+        assert!(to_server
+            .request_port_forward(REQUEST_ID_A.to_owned(), port_forward_data.clone())
+            .await
+            .is_ok());
+        assert!(to_server.response(close_response.clone()).await.is_ok());
+        // the connection has been closed by the workload
+        assert!(to_server
+            .request_port_forward(REQUEST_ID_A.to_owned(), port_forward_data.clone())
+            .await
+            .is_ok());
+
+        drop(to_server);
+        assert!(server.start(None).await.is_ok());
+
+        for expected_request in [port_forward_start, port_forward_data] {
+            assert_eq!(
+                comm_middle_ware_receiver.recv().await.unwrap(),
+                FromServer::PortForwardRequest(AgentPortForwardRequest {
+                    agent_name: Some(AGENT_B.to_owned()),
+                    request_id: REQUEST_ID_A.to_owned(),
+                    port_forward_request: expected_request,
+                })
+            );
+        }
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(close_response)
+        );
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(ank_base::Response {
+                request_id: REQUEST_ID_A.to_owned(),
+                response_content: Some(ank_base::response::ResponseContent::Error(
+                    ank_base::Error {
+                        message: "No open port forwarding".to_owned(),
+                    }
+                )),
+            })
+        );
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+        assert!(server.agent_sessions.is_empty());
+    }
 
-        let workload_map = WorkloadMap { workloads };
+    // [utest->swdd~server-handles-dry-run-update-state-request~1]
+    #[tokio::test]
+    async fn utest_server_returns_dry_run_result_when_received_dry_run_update_state() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
 
-        let current_complete_state = ank_base::CompleteState {
-            desired_state: Some(ank_base::State {
-                workloads: Some(workload_map),
+        let w1 = generate_test_stored_workload_spec(AGENT_A.to_owned(), RUNTIME_NAME.to_string());
+        let update_state = CompleteState {
+            desired_state: State {
+                workloads: vec![(WORKLOAD_NAME_1.to_owned(), w1)].into_iter().collect(),
                 ..Default::default()
-            }),
+            },
             ..Default::default()
         };
-        let request_id = format!("{AGENT_A}@my_request_id");
+        let update_mask = vec![format!("desiredState.workloads.{}", WORKLOAD_NAME_1)];
+
+        let dry_run_result = ank_base::UpdateStateDryRunResult {
+            added_workloads: vec![WORKLOAD_NAME_1.to_owned()],
+            ..Default::default()
+        };
+
         let mut server = AnkaiosServer::new(server_receiver, to_agents);
         let mut mock_server_state = MockServerState::new();
         mock_server_state
-            .expect_get_complete_state_by_field_mask()
+            .expect_dry_run_update()
             .with(
-                mockall::predicate::function(|request_compl_state| {
-                    request_compl_state == &CompleteStateRequest { field_mask: vec![] }
-                }),
-                mockall::predicate::always(),
+                mockall::predicate::eq(update_state.clone()),
+                mockall::predicate::eq(update_mask.clone()),
             )
             .once()
-            .return_const(Ok(current_complete_state.clone()));
+            .return_const(Ok(dry_run_result.clone()));
+        mock_server_state.expect_update().never();
         server.server_state = mock_server_state;
         let server_task = tokio::spawn(async move { server.start(None).await });
 
-        // send command 'CompleteStateRequest'
-        // CompleteState shall contain the complete state
-        let request_complete_state_result = to_server
-            .request_complete_state(
-                request_id.clone(),
-                CompleteStateRequest { field_mask: vec![] },
-            )
-            .await;
-        assert!(request_complete_state_result.is_ok());
-
-        let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
+        assert!(to_server
+            .update_state_dry_run(REQUEST_ID_A.to_string(), update_state, update_mask)
+            .await
+            .is_ok());
 
         assert_eq!(
-            from_server_command,
-            common::from_server_interface::FromServer::Response(ank_base::Response {
-                request_id,
-                response_content: Some(ank_base::response::ResponseContent::CompleteState(
-                    current_complete_state
-                ))
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(ank_base::Response {
+                request_id: REQUEST_ID_A.to_string(),
+                response_content: Some(
+                    ank_base::response::ResponseContent::UpdateStateDryRunResult(dry_run_result)
+                )
             })
         );
 
@@ -1169,7 +3338,11 @@ mod tests {
             .expect_get_complete_state_by_field_mask()
             .with(
                 mockall::predicate::function(|request_compl_state| {
-                    request_compl_state == &CompleteStateRequest { field_mask: vec![] }
+                    request_compl_state
+                        == &CompleteStateRequest {
+                            field_mask: vec![],
+                            selector: vec![],
+                        }
                 }),
                 mockall::predicate::always(),
             )
@@ -1184,7 +3357,10 @@ mod tests {
         let request_complete_state_result = to_server
             .request_complete_state(
                 request_id.clone(),
-                CompleteStateRequest { field_mask: vec![] },
+                CompleteStateRequest {
+                    field_mask: vec![],
+                    selector: vec![],
+                },
             )
             .await;
         assert!(request_complete_state_result.is_ok());
@@ -1200,7 +3376,7 @@ mod tests {
             common::from_server_interface::FromServer::Response(ank_base::Response {
                 request_id,
                 response_content: Some(ank_base::response::ResponseContent::CompleteState(
-                    expected_complete_state
+                    Box::new(expected_complete_state)
                 ))
             })
         );
@@ -1287,6 +3463,304 @@ mod tests {
         assert!(comm_middle_ware_receiver.try_recv().is_err());
     }
 
+    fn mock_server_state_with_restartable_workload() -> MockServerState {
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state
+            .expect_get_replica_names_of_workload()
+            .return_const(Some(vec![WORKLOAD_NAME_1.to_owned()]));
+        mock_server_state
+            .expect_get_agent_of_workload()
+            .return_const(Some(AGENT_B.to_owned()));
+        mock_server_state
+            .expect_contains_connected_agent()
+            .return_const(true);
+        mock_server_state
+    }
+
+    // [utest->swdd~server-times-out-forwarded-agent-requests~2]
+    #[tokio::test]
+    async fn utest_server_times_out_restart_workload_request_of_hanging_agent() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let request_timeout = std::time::Duration::from_millis(10);
+        let mut server = AnkaiosServer::new(server_receiver, to_agents)
+            .with_agent_request_timeout(request_timeout);
+        server.server_state = mock_server_state_with_restartable_workload();
+
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .request_restart_workload(
+                REQUEST_ID_A.to_owned(),
+                RestartWorkloadRequest {
+                    workload_name: WORKLOAD_NAME_1.to_owned(),
+                }
+            )
+            .await
+            .is_ok());
+
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::RestartWorkloadRequest(_)
+        ));
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(ank_base::Response {
+                request_id: REQUEST_ID_A.to_owned(),
+                response_content: Some(ank_base::response::ResponseContent::Error(
+                    ank_base::Error {
+                        message: format!(
+                            "Request timed out, agent '{AGENT_B}' did not respond within {request_timeout:?}"
+                        ),
+                    }
+                )),
+            })
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-times-out-forwarded-agent-requests~2]
+    #[tokio::test]
+    async fn utest_server_drops_late_response_to_timed_out_request() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents)
+            .with_agent_request_timeout(std::time::Duration::from_millis(10));
+        server.server_state = mock_server_state_with_restartable_workload();
+
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        assert!(to_server
+            .request_restart_workload(
+                REQUEST_ID_A.to_owned(),
+                RestartWorkloadRequest {
+                    workload_name: WORKLOAD_NAME_1.to_owned(),
+                }
+            )
+            .await
+            .is_ok());
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::RestartWorkloadRequest(_)
+        ));
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(ank_base::Response {
+                response_content: Some(ank_base::response::ResponseContent::Error(_)),
+                ..
+            })
+        ));
+
+        assert!(to_server
+            .response(ank_base::Response {
+                request_id: REQUEST_ID_A.to_owned(),
+                response_content: Some(
+                    ank_base::response::ResponseContent::RestartWorkloadSuccess(
+                        ank_base::RestartWorkloadSuccess::default(),
+                    )
+                ),
+            })
+            .await
+            .is_ok());
+        drop(to_server);
+        assert!(server_task.await.unwrap().is_ok());
+
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-times-out-forwarded-agent-requests~2]
+    #[tokio::test]
+    async fn utest_server_does_not_time_out_answered_restart_workload_request() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents)
+            .with_agent_request_timeout(std::time::Duration::from_secs(30));
+        server.server_state = mock_server_state_with_restartable_workload();
+
+        let response = ank_base::Response {
+            request_id: REQUEST_ID_A.to_owned(),
+            response_content: Some(ank_base::response::ResponseContent::RestartWorkloadSuccess(
+                ank_base::RestartWorkloadSuccess::default(),
+            )),
+        };
+        assert!(to_server
+            .request_restart_workload(
+                REQUEST_ID_A.to_owned(),
+                RestartWorkloadRequest {
+                    workload_name: WORKLOAD_NAME_1.to_owned(),
+                }
+            )
+            .await
+            .is_ok());
+        assert!(to_server.response(response.clone()).await.is_ok());
+        drop(to_server);
+        assert!(server.start(None).await.is_ok());
+
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::RestartWorkloadRequest(_)
+        ));
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(response)
+        );
+        assert_eq!(server.pending_agent_requests.next_deadline(), None);
+    }
+
+    // [utest->swdd~server-cancels-forwarded-requests-of-disconnected-agent~1]
+    #[tokio::test]
+    async fn utest_server_cancels_restart_workload_request_of_disconnected_agent() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents);
+        let mut mock_server_state = mock_server_state_with_restartable_workload();
+        mock_server_state
+            .expect_remove_agent()
+            .with(predicate::eq(AGENT_B))
+            .once()
+            .return_const(());
+        server.server_state = mock_server_state;
+
+        assert!(to_server
+            .request_restart_workload(
+                REQUEST_ID_A.to_owned(),
+                RestartWorkloadRequest {
+                    workload_name: WORKLOAD_NAME_1.to_owned(),
+                }
+            )
+            .await
+            .is_ok());
+        assert!(to_server.agent_gone(AGENT_B.to_owned()).await.is_ok());
+        drop(to_server);
+        assert!(server.start(None).await.is_ok());
+
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::RestartWorkloadRequest(_)
+        ));
+        assert!(matches!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::UpdateWorkloadState(_)
+        ));
+        assert_eq!(
+            comm_middle_ware_receiver.recv().await.unwrap(),
+            FromServer::Response(ank_base::Response {
+                request_id: REQUEST_ID_A.to_owned(),
+                response_content: Some(ank_base::response::ResponseContent::Error(
+                    ank_base::Error {
+                        message: format!("Request canceled, agent '{AGENT_B}' disconnected"),
+                    }
+                )),
+            })
+        );
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-detects-lost-agents~1]
+    // [utest->swdd~server-reschedules-workloads-of-lost-agents~1]
+    #[tokio::test]
+    async fn utest_server_reschedules_workloads_of_lost_agent_after_grace_period() {
+        let _ = env_logger::builder().is_test(true).try_init();
+        let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
+        let (to_agents, mut comm_middle_ware_receiver) =
+            create_from_server_channel(common::CHANNEL_CAPACITY);
+
+        let lost_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME_NAME.to_string(),
+        );
+        let rescheduled_workload = generate_test_workload_spec_with_param(
+            AGENT_B.to_owned(),
+            WORKLOAD_NAME_1.to_owned(),
+            RUNTIME_NAME.to_string(),
+        );
+        let deleted_workload = DeletedWorkload {
+            instance_name: lost_workload.instance_name.clone(),
+            ..Default::default()
+        };
+
+        let mut server = AnkaiosServer::new(server_receiver, to_agents)
+            .with_agent_lost_grace_period(std::time::Duration::from_millis(10));
+        let mut mock_server_state = MockServerState::new();
+        mock_server_state.expect_cleanup_state().return_const(());
+        mock_server_state
+            .expect_remove_agent()
+            .once()
+            .with(predicate::eq(AGENT_A))
+            .return_const(());
+        mock_server_state
+            .expect_reschedule_workloads_of_lost_agent()
+            .once()
+            .with(predicate::eq(AGENT_A))
+            .return_const(Some((
+                vec![rescheduled_workload.clone()],
+                vec![deleted_workload.clone()],
+            )));
+        server.server_state = mock_server_state;
+
+        let server_task = tokio::spawn(async move { server.start(None).await });
+
+        let running_state = WorkloadState {
+            instance_name: lost_workload.instance_name.clone(),
+            execution_state: ExecutionState::running(),
+        };
+        assert!(to_server
+            .update_workload_state(vec![running_state.clone()])
+            .await
+            .is_ok());
+        assert!(to_server.agent_gone(AGENT_A.to_owned()).await.is_ok());
+
+        assert_eq!(
+            FromServer::UpdateWorkloadState(UpdateWorkloadState {
+                workload_states: vec![running_state]
+            }),
+            comm_middle_ware_receiver.recv().await.unwrap()
+        );
+        assert_eq!(
+            FromServer::UpdateWorkloadState(UpdateWorkloadState {
+                workload_states: vec![WorkloadState {
+                    instance_name: lost_workload.instance_name.clone(),
+                    execution_state: ExecutionState::agent_disconnected(),
+                }]
+            }),
+            comm_middle_ware_receiver.recv().await.unwrap()
+        );
+        assert_eq!(
+            FromServer::UpdateWorkload(UpdateWorkload {
+                added_workloads: vec![rescheduled_workload],
+                deleted_workloads: vec![deleted_workload],
+            }),
+            comm_middle_ware_receiver.recv().await.unwrap()
+        );
+        assert_eq!(
+            FromServer::UpdateWorkloadState(UpdateWorkloadState {
+                workload_states: vec![WorkloadState {
+                    instance_name: lost_workload.instance_name,
+                    execution_state: ExecutionState::rescheduled("Rescheduled to agent 'agent_B'"),
+                }]
+            }),
+            comm_middle_ware_receiver.recv().await.unwrap()
+        );
+
+        server_task.abort();
+        assert!(comm_middle_ware_receiver.try_recv().is_err());
+    }
+
     // [utest->swdd~server-sets-state-of-new-workloads-to-pending~1]
     // [utest->swdd~server-uses-async-channels~1]
     // [utest->swdd~server-starts-without-startup-config~1]
@@ -1330,6 +3804,7 @@ mod tests {
         let deleted_workloads = vec![DeletedWorkload {
             instance_name: w1.instance_name.clone(),
             dependencies: HashMap::new(),
+            grace_period_secs: None,
         }];
 
         let mut server = AnkaiosServer::new(server_receiver, to_agents);
@@ -1350,6 +3825,11 @@ mod tests {
             .times(2)
             .return_const(());
 
+        mock_server_state
+            .expect_schedule_unscheduled_workloads()
+            .times(2)
+            .return_const(None);
+
         mock_server_state
             .expect_get_workloads_for_agent()
             .with(mockall::predicate::eq(AGENT_B.to_string()))
@@ -1366,12 +3846,20 @@ mod tests {
             .once()
             .in_sequence(&mut seq)
             .return_const(Ok(Some((added_workloads, deleted_workloads))));
+        mock_server_state
+            .expect_get_images_for_agent()
+            .return_const(Vec::new());
+
         server.server_state = mock_server_state;
 
-        let agent_hello1_result = to_server.agent_hello(AGENT_A.to_owned()).await;
+        let agent_hello1_result = to_server
+            .agent_hello(generate_test_agent_hello(AGENT_A))
+            .await;
         assert!(agent_hello1_result.is_ok());
 
-        let agent_hello2_result = to_server.agent_hello(AGENT_B.to_owned()).await;
+        let agent_hello2_result = to_server
+            .agent_hello(generate_test_agent_hello(AGENT_B))
+            .await;
         assert!(agent_hello2_result.is_ok());
 
         let update_state_result = to_server
@@ -1410,6 +3898,7 @@ mod tests {
                 deleted_workloads: vec![DeletedWorkload {
                     instance_name: w1.instance_name.clone(),
                     dependencies: HashMap::new(),
+                    grace_period_secs: None,
                 }]
             }),
             from_server_command
@@ -1467,7 +3956,7 @@ mod tests {
         }
     }
 
-    // [utest->swdd~update-desired-state-with-invalid-version~1]
+    // [utest->swdd~update-desired-state-with-invalid-version~2]
     #[tokio::test]
     async fn utest_server_rejects_update_state_with_incompatible_version() {
         let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
@@ -1493,8 +3982,8 @@ mod tests {
         assert!(update_state_result.is_ok());
 
         let error_message = format!(
-            "Unsupported API version. Received 'incompatible_version', expected '{}'",
-            State::default().api_version
+            "Unsupported API version. Received 'incompatible_version', expected one of '{}'",
+            SUPPORTED_API_VERSIONS.join("', '")
         );
         let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
         assert_eq!(
@@ -1541,8 +4030,8 @@ mod tests {
         assert!(update_state_result.is_ok());
 
         let error_message = format!(
-            "Unsupported API version. Received '', expected '{}'",
-            State::default().api_version
+            "Unsupported API version. Received '', expected one of '{}'",
+            SUPPORTED_API_VERSIONS.join("', '")
         );
         let from_server_command = comm_middle_ware_receiver.recv().await.unwrap();
         assert_eq!(
@@ -1690,6 +4179,11 @@ mod tests {
             agent_name: AGENT_A.to_string(),
             cpu_usage: CpuUsage { cpu_usage: 42 },
             free_memory: FreeMemory { free_memory: 42 },
+            workload_resources: vec![],
+            disk_usage: Default::default(),
+            network_usage: Default::default(),
+            temperature: Default::default(),
+            image_states: Default::default(),
         };
 
         let _ = env_logger::builder().is_test(true).try_init();
@@ -0,0 +1,418 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use common::objects::WorkloadSpec;
+use serde::Deserialize;
+
+use super::config_renderer::RenderedWorkloads;
+
+/// Configuration of the built-in admission validators as provided in the server config file
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+pub struct AdmissionConfig {
+    pub max_workloads_per_agent: Option<usize>,
+    #[serde(default)]
+    pub forbidden_runtimes: Vec<String>,
+    #[serde(default)]
+    pub required_tags: Vec<String>,
+}
+
+/// Reason why an admission validator rejected an update of the desired state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdmissionRejection {
+    pub validator: String,
+    pub reason: String,
+}
+
+impl Display for AdmissionRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.validator, self.reason)
+    }
+}
+
+/// A policy checked before an update of the desired state is accepted
+///
+/// The validators get the rendered workloads before and after the update, such
+/// that they can restrict the checks to the changed workloads. This allows to
+/// remove workloads from a state which does not fulfill a newly added policy.
+pub trait AdmissionValidator: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Returns the reasons for rejecting the update, which is accepted if no reason is returned
+    fn validate(
+        &self,
+        current_workloads: &RenderedWorkloads,
+        new_workloads: &RenderedWorkloads,
+    ) -> Vec<String>;
+}
+
+// [impl->swdd~server-state-runs-admission-validators~1]
+#[derive(Default)]
+pub struct AdmissionValidators {
+    validators: Vec<Box<dyn AdmissionValidator>>,
+}
+
+impl AdmissionValidators {
+    // [impl->swdd~server-provides-built-in-admission-validators~1]
+    pub fn new(config: &AdmissionConfig) -> Self {
+        let mut admission_validators = AdmissionValidators::default();
+        if let Some(max_workloads) = config.max_workloads_per_agent {
+            admission_validators.add(Box::new(MaxWorkloadsPerAgent { max_workloads }));
+        }
+        if !config.forbidden_runtimes.is_empty() {
+            admission_validators.add(Box::new(ForbiddenRuntimes {
+                runtimes: config.forbidden_runtimes.clone(),
+            }));
+        }
+        if !config.required_tags.is_empty() {
+            admission_validators.add(Box::new(RequiredTags {
+                tag_keys: config.required_tags.clone(),
+            }));
+        }
+        admission_validators
+    }
+
+    pub fn add(&mut self, validator: Box<dyn AdmissionValidator>) {
+        self.validators.push(validator);
+    }
+
+    // [impl->swdd~server-state-runs-admission-validators~1]
+    pub fn validate(
+        &self,
+        current_workloads: &RenderedWorkloads,
+        new_workloads: &RenderedWorkloads,
+    ) -> Result<(), Vec<AdmissionRejection>> {
+        let rejections: Vec<AdmissionRejection> = self
+            .validators
+            .iter()
+            .flat_map(|validator| {
+                validator
+                    .validate(current_workloads, new_workloads)
+                    .into_iter()
+                    .map(|reason| AdmissionRejection {
+                        validator: validator.name().to_owned(),
+                        reason,
+                    })
+            })
+            .collect();
+
+        if rejections.is_empty() {
+            Ok(())
+        } else {
+            Err(rejections)
+        }
+    }
+}
+
+// The changed workloads sorted by name to get the rejections in a stable order.
+fn changed_workloads<'a>(
+    current_workloads: &RenderedWorkloads,
+    new_workloads: &'a RenderedWorkloads,
+) -> BTreeMap<&'a String, &'a WorkloadSpec> {
+    new_workloads
+        .iter()
+        .filter(|(workload_name, workload)| {
+            current_workloads.get(*workload_name) != Some(*workload)
+        })
+        .collect()
+}
+
+fn count_workloads_per_agent(workloads: &RenderedWorkloads) -> BTreeMap<&str, usize> {
+    let mut workloads_per_agent = BTreeMap::new();
+    for workload in workloads.values() {
+        let agent_name = workload.instance_name.agent_name();
+        if !agent_name.is_empty() {
+            *workloads_per_agent.entry(agent_name).or_default() += 1;
+        }
+    }
+    workloads_per_agent
+}
+
+// [impl->swdd~server-provides-built-in-admission-validators~1]
+struct MaxWorkloadsPerAgent {
+    max_workloads: usize,
+}
+
+impl AdmissionValidator for MaxWorkloadsPerAgent {
+    fn name(&self) -> &str {
+        "max_workloads_per_agent"
+    }
+
+    fn validate(
+        &self,
+        current_workloads: &RenderedWorkloads,
+        new_workloads: &RenderedWorkloads,
+    ) -> Vec<String> {
+        let current_workloads_per_agent = count_workloads_per_agent(current_workloads);
+
+        count_workloads_per_agent(new_workloads)
+            .into_iter()
+            .filter(|(agent_name, workload_count)| {
+                *workload_count > self.max_workloads
+                    && *workload_count
+                        > current_workloads_per_agent
+                            .get(agent_name)
+                            .copied()
+                            .unwrap_or_default()
+            })
+            .map(|(agent_name, workload_count)| {
+                format!(
+                    "agent '{}' would run '{}' workloads, but at most '{}' are allowed",
+                    agent_name, workload_count, self.max_workloads
+                )
+            })
+            .collect()
+    }
+}
+
+// [impl->swdd~server-provides-built-in-admission-validators~1]
+struct ForbiddenRuntimes {
+    runtimes: Vec<String>,
+}
+
+impl AdmissionValidator for ForbiddenRuntimes {
+    fn name(&self) -> &str {
+        "forbidden_runtimes"
+    }
+
+    fn validate(
+        &self,
+        current_workloads: &RenderedWorkloads,
+        new_workloads: &RenderedWorkloads,
+    ) -> Vec<String> {
+        changed_workloads(current_workloads, new_workloads)
+            .into_iter()
+            .filter(|(_, workload)| self.runtimes.contains(&workload.runtime))
+            .map(|(workload_name, workload)| {
+                format!(
+                    "workload '{}' uses the forbidden runtime '{}'",
+                    workload_name, workload.runtime
+                )
+            })
+            .collect()
+    }
+}
+
+// [impl->swdd~server-provides-built-in-admission-validators~1]
+struct RequiredTags {
+    tag_keys: Vec<String>,
+}
+
+impl AdmissionValidator for RequiredTags {
+    fn name(&self) -> &str {
+        "required_tags"
+    }
+
+    fn validate(
+        &self,
+        current_workloads: &RenderedWorkloads,
+        new_workloads: &RenderedWorkloads,
+    ) -> Vec<String> {
+        let mut reasons = Vec::new();
+        for (workload_name, workload) in changed_workloads(current_workloads, new_workloads) {
+            for tag_key in &self.tag_keys {
+                if !workload.tags.iter().any(|tag| &tag.key == tag_key) {
+                    reasons.push(format!(
+                        "workload '{}' misses the required tag '{}'",
+                        workload_name, tag_key
+                    ));
+                }
+            }
+        }
+        reasons
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::objects::{generate_test_workload_spec_with_param, Tag};
+
+    use super::{
+        AdmissionConfig, AdmissionRejection, AdmissionValidator, AdmissionValidators,
+        RenderedWorkloads,
+    };
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+    const RUNTIME_NAME: &str = "runtime";
+
+    fn generate_rendered_workloads(workloads: &[(&str, &str)]) -> RenderedWorkloads {
+        workloads
+            .iter()
+            .map(|(agent_name, workload_name)| {
+                (
+                    workload_name.to_string(),
+                    generate_test_workload_spec_with_param(
+                        agent_name.to_string(),
+                        workload_name.to_string(),
+                        RUNTIME_NAME.to_string(),
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    // [utest->swdd~server-state-runs-admission-validators~1]
+    #[test]
+    fn utest_admission_validators_without_config_accept_all() {
+        let admission_validators = AdmissionValidators::new(&AdmissionConfig::default());
+
+        assert_eq!(
+            admission_validators.validate(
+                &RenderedWorkloads::new(),
+                &generate_rendered_workloads(&[(AGENT_A, "workload_1")])
+            ),
+            Ok(())
+        );
+    }
+
+    // [utest->swdd~server-provides-built-in-admission-validators~1]
+    #[test]
+    fn utest_admission_validators_max_workloads_per_agent() {
+        let admission_validators = AdmissionValidators::new(&AdmissionConfig {
+            max_workloads_per_agent: Some(1),
+            ..Default::default()
+        });
+        let current_workloads = generate_rendered_workloads(&[(AGENT_A, "workload_1")]);
+
+        assert_eq!(
+            admission_validators.validate(
+                &current_workloads,
+                &generate_rendered_workloads(&[
+                    (AGENT_A, "workload_1"),
+                    (AGENT_A, "workload_2"),
+                    (AGENT_B, "workload_3"),
+                ])
+            ),
+            Err(vec![AdmissionRejection {
+                validator: "max_workloads_per_agent".to_string(),
+                reason: "agent 'agent_A' would run '2' workloads, but at most '1' are allowed"
+                    .to_string(),
+            }])
+        );
+    }
+
+    // [utest->swdd~server-provides-built-in-admission-validators~1]
+    #[test]
+    fn utest_admission_validators_max_workloads_per_agent_allows_reducing_workloads() {
+        let admission_validators = AdmissionValidators::new(&AdmissionConfig {
+            max_workloads_per_agent: Some(1),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            admission_validators.validate(
+                &generate_rendered_workloads(&[
+                    (AGENT_A, "workload_1"),
+                    (AGENT_A, "workload_2"),
+                    (AGENT_A, "workload_3"),
+                ]),
+                &generate_rendered_workloads(&[(AGENT_A, "workload_1"), (AGENT_A, "workload_2")])
+            ),
+            Ok(())
+        );
+    }
+
+    // [utest->swdd~server-provides-built-in-admission-validators~1]
+    #[test]
+    fn utest_admission_validators_forbidden_runtimes_checks_changed_workloads() {
+        let admission_validators = AdmissionValidators::new(&AdmissionConfig {
+            forbidden_runtimes: vec![RUNTIME_NAME.to_string()],
+            ..Default::default()
+        });
+        let current_workloads = generate_rendered_workloads(&[(AGENT_A, "workload_1")]);
+
+        assert_eq!(
+            admission_validators.validate(
+                &current_workloads,
+                &generate_rendered_workloads(&[(AGENT_A, "workload_1"), (AGENT_B, "workload_2")])
+            ),
+            Err(vec![AdmissionRejection {
+                validator: "forbidden_runtimes".to_string(),
+                reason: "workload 'workload_2' uses the forbidden runtime 'runtime'".to_string(),
+            }])
+        );
+    }
+
+    // [utest->swdd~server-provides-built-in-admission-validators~1]
+    #[test]
+    fn utest_admission_validators_required_tags() {
+        let admission_validators = AdmissionValidators::new(&AdmissionConfig {
+            required_tags: vec!["owner".to_string(), "team".to_string()],
+            ..Default::default()
+        });
+        let mut new_workloads = generate_rendered_workloads(&[(AGENT_A, "workload_1")]);
+        new_workloads.get_mut("workload_1").unwrap().tags = vec![Tag {
+            key: "owner".to_string(),
+            value: "someone".to_string(),
+        }];
+
+        assert_eq!(
+            admission_validators.validate(&RenderedWorkloads::new(), &new_workloads),
+            Err(vec![AdmissionRejection {
+                validator: "required_tags".to_string(),
+                reason: "workload 'workload_1' misses the required tag 'team'".to_string(),
+            }])
+        );
+    }
+
+    struct RejectAll;
+
+    impl AdmissionValidator for RejectAll {
+        fn name(&self) -> &str {
+            "reject_all"
+        }
+
+        fn validate(&self, _: &RenderedWorkloads, _: &RenderedWorkloads) -> Vec<String> {
+            vec!["no updates allowed".to_string()]
+        }
+    }
+
+    // [utest->swdd~server-state-runs-admission-validators~1]
+    #[test]
+    fn utest_admission_validators_collects_rejections_of_all_validators() {
+        let mut admission_validators = AdmissionValidators::new(&AdmissionConfig {
+            forbidden_runtimes: vec![RUNTIME_NAME.to_string()],
+            ..Default::default()
+        });
+        admission_validators.add(Box::new(RejectAll));
+
+        assert_eq!(
+            admission_validators.validate(
+                &RenderedWorkloads::new(),
+                &generate_rendered_workloads(&[(AGENT_A, "workload_1")])
+            ),
+            Err(vec![
+                AdmissionRejection {
+                    validator: "forbidden_runtimes".to_string(),
+                    reason: "workload 'workload_1' uses the forbidden runtime 'runtime'"
+                        .to_string(),
+                },
+                AdmissionRejection {
+                    validator: "reject_all".to_string(),
+                    reason: "no updates allowed".to_string(),
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,582 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use api::ank_base;
+use common::objects::WorkloadStatesMap;
+use common::request_id_prepending::{
+    detach_prefix_from_request_id, CLI_CONNECTION_NAME_PREFIX, SERVER_INTERNAL_REQUEST_PREFIX,
+};
+use serde::Deserialize;
+
+const DEFAULT_AUDIT_LOG_SIZE: usize = 1000;
+const DEFAULT_AUDIT_LOG_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+const DEFAULT_AUDIT_LOG_MAX_FILES: usize = 5;
+const AUDIT_LOG_FILE_QUEUE_CAPACITY: usize = 1000;
+
+/// Configuration of the audit log as provided in the server config file
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct AuditLogConfig {
+    /// The number of entries kept in memory, 0 disables the audit log
+    pub size: usize,
+    /// The file the entries are appended to as one JSON object per line
+    pub file: Option<PathBuf>,
+    /// The size in bytes after which the file is rotated, 0 disables the rotation
+    pub max_file_size: u64,
+    /// The number of rotated files kept next to the file
+    pub max_files: usize,
+}
+
+impl Default for AuditLogConfig {
+    fn default() -> Self {
+        AuditLogConfig {
+            size: DEFAULT_AUDIT_LOG_SIZE,
+            file: None,
+            max_file_size: DEFAULT_AUDIT_LOG_MAX_FILE_SIZE,
+            max_files: DEFAULT_AUDIT_LOG_MAX_FILES,
+        }
+    }
+}
+
+/// The origin of a request as derived from the prefixes of its request id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditRequester {
+    requester_type: ank_base::RequesterType,
+    requester: String,
+    workload_instance_name: String,
+}
+
+impl AuditRequester {
+    /// Derives the requester from the request id
+    ///
+    /// The request ids of the CLI start with the name of the CLI connection and the request ids
+    /// of the Control Interface with the name of the agent followed by the name of the workload.
+    /// The instance name of the workload is looked up in the workload states.
+    // [impl->swdd~server-audit-log-identifies-requester~1]
+    pub fn from_request_id(request_id: &str, workload_states_map: &WorkloadStatesMap) -> Self {
+        let (prefix, raw_request_id) = detach_prefix_from_request_id(request_id);
+        if prefix.starts_with(CLI_CONNECTION_NAME_PREFIX) {
+            return AuditRequester {
+                requester_type: ank_base::RequesterType::Cli,
+                requester: prefix,
+                workload_instance_name: String::new(),
+            };
+        }
+        if prefix == SERVER_INTERNAL_REQUEST_PREFIX {
+            return AuditRequester {
+                requester_type: ank_base::RequesterType::Server,
+                requester: prefix,
+                workload_instance_name: String::new(),
+            };
+        }
+
+        let (workload_name, _) = detach_prefix_from_request_id(&raw_request_id);
+        if workload_name.is_empty() {
+            return AuditRequester {
+                requester_type: ank_base::RequesterType::Agent,
+                requester: prefix,
+                workload_instance_name: String::new(),
+            };
+        }
+        let workload_instance_name = workload_states_map
+            .get_workload_state_for_agent(&prefix)
+            .into_iter()
+            .map(|workload_state| workload_state.instance_name)
+            .find(|instance_name| instance_name.workload_name() == workload_name)
+            .map(|instance_name| instance_name.to_string())
+            // the workload might already be deleted by a previous update
+            .unwrap_or(workload_name);
+        AuditRequester {
+            requester_type: ank_base::RequesterType::Workload,
+            requester: prefix,
+            workload_instance_name,
+        }
+    }
+}
+
+/// Append-only log of the accepted and rejected updates of the desired state
+///
+/// Each entry gets a sequence number increasing monotonically starting from 1.
+/// When the configured size is reached the oldest entries are dropped from memory,
+/// while the optional file keeps all entries until it is rotated.
+// [impl->swdd~server-records-audit-log~1]
+pub struct AuditLog {
+    entries: VecDeque<ank_base::AuditEntry>,
+    last_sequence_number: u64,
+    size: usize,
+    file_writer: Option<AuditLogFileWriter>,
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        Self::new(&AuditLogConfig::default())
+    }
+}
+
+impl AuditLog {
+    pub fn new(config: &AuditLogConfig) -> Self {
+        AuditLog {
+            entries: VecDeque::with_capacity(config.size),
+            last_sequence_number: 0,
+            size: config.size,
+            file_writer: config.file.clone().and_then(|path| {
+                AuditLogFileWriter::spawn(AuditLogFile {
+                    path,
+                    max_file_size: config.max_file_size,
+                    max_files: config.max_files,
+                })
+            }),
+        }
+    }
+
+    pub fn record(
+        &mut self,
+        requester: AuditRequester,
+        update_mask: Vec<String>,
+        result: Result<(), &str>,
+    ) {
+        if self.size == 0 {
+            return;
+        }
+        if self.entries.len() == self.size {
+            self.entries.pop_front();
+        }
+
+        self.last_sequence_number += 1;
+        let entry = ank_base::AuditEntry {
+            sequence_number: self.last_sequence_number,
+            timestamp: current_timestamp_millis(),
+            requester_type: requester.requester_type as i32,
+            requester: requester.requester,
+            workload_instance_name: requester.workload_instance_name,
+            update_mask,
+            accepted: result.is_ok(),
+            message: result.err().unwrap_or_default().to_owned(),
+        };
+
+        // [impl->swdd~server-writes-audit-log-file~2]
+        if let Some(file_writer) = &self.file_writer {
+            file_writer.append(&entry);
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Returns the recorded entries with a sequence number greater than the passed one
+    ///
+    /// # Arguments
+    ///
+    /// * `after_sequence_number` - The sequence number of the last entry already known to the caller
+    ///
+    // [impl->swdd~server-provides-audit-log~1]
+    pub fn entries_after(&self, after_sequence_number: u64) -> Vec<ank_base::AuditEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.sequence_number > after_sequence_number)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Writes the entries to the audit log file in an own thread
+///
+/// The file operations are blocking and a slow disk must not stall the handling of
+/// the requests by the server. If the queue of the thread is full, the entry is only
+/// kept in memory.
+struct AuditLogFileWriter {
+    path: PathBuf,
+    // both are only taken when the writer is closed
+    sender: Option<SyncSender<Vec<u8>>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl AuditLogFileWriter {
+    fn spawn(file: AuditLogFile) -> Option<Self> {
+        let path = file.path.clone();
+        let (sender, receiver) = mpsc::sync_channel(AUDIT_LOG_FILE_QUEUE_CAPACITY);
+        match thread::Builder::new()
+            .name("audit_log_writer".into())
+            .spawn(move || write_lines(file, receiver))
+        {
+            Ok(thread) => Some(AuditLogFileWriter {
+                path,
+                sender: Some(sender),
+                thread: Some(thread),
+            }),
+            Err(err) => {
+                log::warn!(
+                    "Could not start writing the audit log to '{}': '{}'",
+                    path.display(),
+                    err
+                );
+                None
+            }
+        }
+    }
+
+    fn append(&self, entry: &ank_base::AuditEntry) {
+        let line = match to_line(entry) {
+            Ok(line) => line,
+            Err(err) => {
+                log::warn!("Could not serialize the audit log entry: '{}'", err);
+                return;
+            }
+        };
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        match sender.try_send(line) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => log::warn!(
+                "The queue of the audit log file '{}' is full, the entry is not written to the file.",
+                self.path.display()
+            ),
+            Err(TrySendError::Disconnected(_)) => log::warn!(
+                "The writer of the audit log file '{}' stopped, the entry is not written to the file.",
+                self.path.display()
+            ),
+        }
+    }
+
+    // Waits until all queued entries are written
+    fn close(&mut self) {
+        // the thread stops after writing the queued entries when the sender is dropped
+        drop(self.sender.take());
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                log::warn!(
+                    "The writer of the audit log file '{}' stopped unexpectedly.",
+                    self.path.display()
+                );
+            }
+        }
+    }
+}
+
+impl Drop for AuditLogFileWriter {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+fn to_line(entry: &ank_base::AuditEntry) -> Result<Vec<u8>, String> {
+    let mut line = serde_json::to_value(entry).map_err(|err| err.to_string())?;
+    // the name of the requester type is more meaningful to the readers of the file
+    line["requesterType"] = entry.requester_type().as_str_name().into();
+    let mut line = serde_json::to_vec(&line).map_err(|err| err.to_string())?;
+    line.push(b'\n');
+    Ok(line)
+}
+
+fn write_lines(file: AuditLogFile, receiver: Receiver<Vec<u8>>) {
+    for line in receiver {
+        if let Err(err) = file.append(&line) {
+            log::warn!(
+                "Could not write the audit log entry to '{}': '{}'",
+                file.path.display(),
+                err
+            );
+        }
+    }
+}
+
+struct AuditLogFile {
+    path: PathBuf,
+    max_file_size: u64,
+    max_files: usize,
+}
+
+impl AuditLogFile {
+    fn append(&self, line: &[u8]) -> Result<(), String> {
+        let current_size = fs::metadata(&self.path)
+            .map(|metadata| metadata.len())
+            .unwrap_or_default();
+        if self.max_file_size > 0
+            && current_size > 0
+            && current_size + line.len() as u64 > self.max_file_size
+        {
+            self.rotate().map_err(|err| err.to_string())?;
+        }
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line))
+            .map_err(|err| err.to_string())
+    }
+
+    // The oldest rotated file is dropped, the others are renamed from '<file>.<n>' to '<file>.<n + 1>'
+    fn rotate(&self) -> std::io::Result<()> {
+        if self.max_files == 0 {
+            return fs::remove_file(&self.path);
+        }
+        for index in (1..self.max_files).rev() {
+            let rotated_file = self.rotated_path(index);
+            if rotated_file.exists() {
+                fs::rename(rotated_file, self.rotated_path(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated_path(1))
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut rotated_file = self.path.clone().into_os_string();
+        rotated_file.push(format!(".{index}"));
+        rotated_file.into()
+    }
+}
+
+fn current_timestamp_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use api::ank_base;
+    use common::objects::{
+        generate_test_workload_states_map_with_data, ExecutionState, WorkloadStatesMap,
+    };
+
+    use super::{AuditLog, AuditLogConfig, AuditRequester};
+
+    const AGENT_A: &str = "agent_A";
+    const WORKLOAD_NAME_1: &str = "workload_1";
+    const WORKLOAD_ID: &str = "1234";
+    const CLI_REQUEST_ID: &str = "cli-conn-5678@request_1";
+    const UPDATE_MASK: &str = "desiredState.workloads.nginx";
+
+    fn cli_requester() -> AuditRequester {
+        AuditRequester::from_request_id(CLI_REQUEST_ID, &WorkloadStatesMap::default())
+    }
+
+    fn read_lines(file: &std::path::Path) -> Vec<serde_json::Value> {
+        std::fs::read_to_string(file)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    // [utest->swdd~server-audit-log-identifies-requester~1]
+    #[test]
+    fn utest_audit_requester_from_request_id() {
+        let workload_states_map = generate_test_workload_states_map_with_data(
+            AGENT_A,
+            WORKLOAD_NAME_1,
+            WORKLOAD_ID,
+            ExecutionState::running(),
+        );
+
+        assert_eq!(
+            AuditRequester::from_request_id(CLI_REQUEST_ID, &workload_states_map),
+            AuditRequester {
+                requester_type: ank_base::RequesterType::Cli,
+                requester: "cli-conn-5678".to_owned(),
+                workload_instance_name: String::new(),
+            }
+        );
+        assert_eq!(
+            AuditRequester::from_request_id("ank.server@reload_1", &workload_states_map),
+            AuditRequester {
+                requester_type: ank_base::RequesterType::Server,
+                requester: "ank.server".to_owned(),
+                workload_instance_name: String::new(),
+            }
+        );
+        assert_eq!(
+            AuditRequester::from_request_id("agent_A@request_1", &workload_states_map),
+            AuditRequester {
+                requester_type: ank_base::RequesterType::Agent,
+                requester: AGENT_A.to_owned(),
+                workload_instance_name: String::new(),
+            }
+        );
+        assert_eq!(
+            AuditRequester::from_request_id("agent_A@workload_1@request_1", &workload_states_map),
+            AuditRequester {
+                requester_type: ank_base::RequesterType::Workload,
+                requester: AGENT_A.to_owned(),
+                workload_instance_name: "workload_1.1234.agent_A".to_owned(),
+            }
+        );
+        assert_eq!(
+            AuditRequester::from_request_id(
+                "agent_A@deleted_workload@request_1",
+                &workload_states_map
+            ),
+            AuditRequester {
+                requester_type: ank_base::RequesterType::Workload,
+                requester: AGENT_A.to_owned(),
+                workload_instance_name: "deleted_workload".to_owned(),
+            }
+        );
+    }
+
+    // [utest->swdd~server-records-audit-log~1]
+    // [utest->swdd~server-provides-audit-log~1]
+    #[test]
+    fn utest_audit_log_records_accepted_and_rejected_updates() {
+        let mut audit_log = AuditLog::default();
+
+        audit_log.record(cli_requester(), vec![UPDATE_MASK.to_owned()], Ok(()));
+        audit_log.record(cli_requester(), vec![], Err("invalid state"));
+
+        let entries = audit_log.entries_after(0);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].sequence_number, 1);
+        assert_eq!(entries[0].requester_type(), ank_base::RequesterType::Cli);
+        assert_eq!(entries[0].requester, "cli-conn-5678");
+        assert_eq!(entries[0].update_mask, vec![UPDATE_MASK.to_owned()]);
+        assert!(entries[0].accepted);
+        assert!(entries[0].message.is_empty());
+        assert!(entries[0].timestamp > 0);
+        assert_eq!(entries[1].sequence_number, 2);
+        assert!(!entries[1].accepted);
+        assert_eq!(entries[1].message, "invalid state");
+
+        assert_eq!(audit_log.entries_after(1), vec![entries[1].clone()]);
+        assert!(audit_log.entries_after(2).is_empty());
+    }
+
+    // [utest->swdd~server-records-audit-log~1]
+    #[test]
+    fn utest_audit_log_drops_oldest_entries_when_size_reached() {
+        let mut audit_log = AuditLog::new(&AuditLogConfig {
+            size: 2,
+            ..Default::default()
+        });
+
+        for _ in 0..3 {
+            audit_log.record(cli_requester(), vec![], Ok(()));
+        }
+
+        let sequence_numbers: Vec<u64> = audit_log
+            .entries_after(0)
+            .iter()
+            .map(|entry| entry.sequence_number)
+            .collect();
+        assert_eq!(sequence_numbers, vec![2, 3]);
+    }
+
+    // [utest->swdd~server-writes-audit-log-file~2]
+    #[test]
+    fn utest_audit_log_appends_entries_to_file() {
+        let audit_dir = tempfile::tempdir().unwrap();
+        let audit_file = audit_dir.path().join("audit.log");
+        let mut audit_log = AuditLog::new(&AuditLogConfig {
+            file: Some(audit_file.clone()),
+            ..Default::default()
+        });
+
+        audit_log.record(cli_requester(), vec![UPDATE_MASK.to_owned()], Ok(()));
+        audit_log.record(cli_requester(), vec![], Err("invalid state"));
+        audit_log.file_writer.take().unwrap().close();
+
+        let lines = read_lines(&audit_file);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["sequenceNumber"], 1);
+        assert_eq!(lines[0]["requesterType"], "REQUESTER_TYPE_CLI");
+        assert_eq!(lines[0]["requester"], "cli-conn-5678");
+        assert_eq!(lines[0]["updateMask"], serde_json::json!([UPDATE_MASK]));
+        assert_eq!(lines[0]["accepted"], true);
+        assert_eq!(lines[1]["accepted"], false);
+        assert_eq!(lines[1]["message"], "invalid state");
+    }
+
+    // [utest->swdd~server-writes-audit-log-file~2]
+    #[test]
+    fn utest_audit_log_writes_queued_entries_when_dropped() {
+        let audit_dir = tempfile::tempdir().unwrap();
+        let audit_file = audit_dir.path().join("audit.log");
+        let mut audit_log = AuditLog::new(&AuditLogConfig {
+            file: Some(audit_file.clone()),
+            ..Default::default()
+        });
+
+        for _ in 0..3 {
+            audit_log.record(cli_requester(), vec![], Ok(()));
+        }
+        drop(audit_log);
+
+        let sequence_numbers: Vec<serde_json::Value> = read_lines(&audit_file)
+            .into_iter()
+            .map(|line| line["sequenceNumber"].clone())
+            .collect();
+        assert_eq!(sequence_numbers, vec![1, 2, 3]);
+    }
+
+    // [utest->swdd~server-writes-audit-log-file~2]
+    #[test]
+    fn utest_audit_log_rotates_file() {
+        let audit_dir = tempfile::tempdir().unwrap();
+        let audit_file = audit_dir.path().join("audit.log");
+        let mut audit_log = AuditLog::new(&AuditLogConfig {
+            file: Some(audit_file.clone()),
+            // every entry is larger than the maximum file size and is written to a new file
+            max_file_size: 1,
+            max_files: 2,
+            ..Default::default()
+        });
+
+        for _ in 0..4 {
+            audit_log.record(cli_requester(), vec![], Ok(()));
+        }
+        audit_log.file_writer.take().unwrap().close();
+
+        assert_eq!(read_lines(&audit_file)[0]["sequenceNumber"], 4);
+        assert_eq!(
+            read_lines(&audit_dir.path().join("audit.log.1"))[0]["sequenceNumber"],
+            3
+        );
+        assert_eq!(
+            read_lines(&audit_dir.path().join("audit.log.2"))[0]["sequenceNumber"],
+            2
+        );
+        assert!(!audit_dir.path().join("audit.log.3").exists());
+        // the in-memory entries are independent of the rotation
+        assert_eq!(audit_log.entries_after(0).len(), 4);
+    }
+
+    // [utest->swdd~server-records-audit-log~1]
+    #[test]
+    fn utest_audit_log_disabled() {
+        let mut audit_log = AuditLog::new(&AuditLogConfig {
+            size: 0,
+            ..Default::default()
+        });
+
+        audit_log.record(cli_requester(), vec![], Ok(()));
+
+        assert!(audit_log.entries_after(0).is_empty());
+    }
+}
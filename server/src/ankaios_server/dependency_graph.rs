@@ -0,0 +1,167 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use api::ank_base;
+use common::objects::WorkloadStatesMap;
+
+use super::config_renderer::RenderedWorkloads;
+
+/// Returns the dependency graph of the passed workloads
+///
+/// Each workload is a node annotated with its current execution state and each
+/// dependency is an edge from the dependent workload to the workload it depends on.
+/// Nodes and edges are sorted by the workload names to provide a stable output.
+///
+/// # Arguments
+///
+/// * `workloads` - The rendered workloads of the desired state
+/// * `workload_states_map` - The current execution states of the workloads
+///
+// [impl->swdd~server-provides-dependency-graph~1]
+pub fn build_dependency_graph(
+    workloads: &RenderedWorkloads,
+    workload_states_map: &WorkloadStatesMap,
+) -> ank_base::DependencyGraph {
+    let mut nodes: Vec<ank_base::DependencyGraphNode> = workloads
+        .iter()
+        .map(
+            |(workload_name, workload_spec)| ank_base::DependencyGraphNode {
+                workload_name: workload_name.clone(),
+                agent: workload_spec.instance_name.agent_name().to_owned(),
+                execution_state: workload_states_map
+                    .get_workload_state_for_workload(&workload_spec.instance_name)
+                    .cloned()
+                    .map(Into::into),
+            },
+        )
+        .collect();
+    nodes.sort_by(|left, right| left.workload_name.cmp(&right.workload_name));
+
+    let mut edges: Vec<ank_base::DependencyGraphEdge> = workloads
+        .iter()
+        .flat_map(|(workload_name, workload_spec)| {
+            workload_spec
+                .dependencies
+                .iter()
+                // config dependencies are not part of the graph as configs are no nodes
+                .filter(|(_, condition)| !condition.refers_to_config())
+                .map(|(dependency, condition)| ank_base::DependencyGraphEdge {
+                    workload_name: workload_name.clone(),
+                    dependency: dependency.clone(),
+                    condition: *condition as i32,
+                })
+        })
+        .collect();
+    edges.sort_by(|left, right| {
+        (&left.workload_name, &left.dependency).cmp(&(&right.workload_name, &right.dependency))
+    });
+
+    ank_base::DependencyGraph { nodes, edges }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use api::ank_base;
+    use common::objects::{
+        generate_test_workload_spec_with_dependencies, generate_test_workload_states_map_with_data,
+        AddCondition, ExecutionState,
+    };
+
+    use super::build_dependency_graph;
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+    const RUNTIME: &str = "runtime";
+
+    // [utest->swdd~server-provides-dependency-graph~1]
+    #[test]
+    fn utest_build_dependency_graph_annotates_nodes_with_execution_states() {
+        let backend = generate_test_workload_spec_with_dependencies(
+            AGENT_A,
+            "backend",
+            RUNTIME,
+            HashMap::new(),
+        );
+        let frontend = generate_test_workload_spec_with_dependencies(
+            AGENT_B,
+            "frontend",
+            RUNTIME,
+            HashMap::from([
+                ("backend".to_owned(), AddCondition::AddCondRunning),
+                ("init".to_owned(), AddCondition::AddCondSucceeded),
+            ]),
+        );
+        let workload_states_map = generate_test_workload_states_map_with_data(
+            AGENT_A,
+            "backend",
+            backend.instance_name.id(),
+            ExecutionState::running(),
+        );
+        let workloads = HashMap::from([
+            ("frontend".to_owned(), frontend),
+            ("backend".to_owned(), backend),
+        ]);
+
+        let dependency_graph = build_dependency_graph(&workloads, &workload_states_map);
+
+        assert_eq!(
+            dependency_graph,
+            ank_base::DependencyGraph {
+                nodes: vec![
+                    ank_base::DependencyGraphNode {
+                        workload_name: "backend".to_owned(),
+                        agent: AGENT_A.to_owned(),
+                        execution_state: Some(ExecutionState::running().into()),
+                    },
+                    ank_base::DependencyGraphNode {
+                        workload_name: "frontend".to_owned(),
+                        agent: AGENT_B.to_owned(),
+                        execution_state: None,
+                    },
+                ],
+                edges: vec![
+                    ank_base::DependencyGraphEdge {
+                        workload_name: "frontend".to_owned(),
+                        dependency: "backend".to_owned(),
+                        condition: ank_base::AddCondition::AddCondRunning as i32,
+                    },
+                    ank_base::DependencyGraphEdge {
+                        workload_name: "frontend".to_owned(),
+                        dependency: "init".to_owned(),
+                        condition: ank_base::AddCondition::AddCondSucceeded as i32,
+                    },
+                ],
+            }
+        );
+    }
+
+    // [utest->swdd~server-provides-dependency-graph~1]
+    #[test]
+    fn utest_build_dependency_graph_empty() {
+        assert_eq!(
+            build_dependency_graph(&HashMap::new(), &Default::default()),
+            ank_base::DependencyGraph::default()
+        );
+    }
+}
@@ -0,0 +1,248 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use api::ank_base;
+use common::objects::{WorkloadInstanceName, WorkloadState};
+
+const DEFAULT_EVENT_LOG_CAPACITY: usize = 1000;
+
+/// Bounded log of the events of the Ankaios system
+///
+/// Each recorded event gets a sequence number increasing monotonically starting from 1.
+/// When the capacity is reached the oldest events are dropped, such that clients
+/// polling for new events only miss events if they fall behind by more than the capacity.
+// [impl->swdd~server-records-events~1]
+pub struct EventLog {
+    events: VecDeque<ank_base::Event>,
+    last_sequence_number: u64,
+    capacity: usize,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_EVENT_LOG_CAPACITY)
+    }
+}
+
+impl EventLog {
+    pub fn with_capacity(capacity: usize) -> Self {
+        EventLog {
+            events: VecDeque::with_capacity(capacity),
+            last_sequence_number: 0,
+            capacity,
+        }
+    }
+
+    pub fn workload_added(&mut self, instance_name: &WorkloadInstanceName) {
+        self.record(
+            ank_base::EventType::WorkloadAdded,
+            instance_name.agent_name(),
+            instance_name.workload_name(),
+            None,
+        );
+    }
+
+    pub fn workload_deleted(&mut self, instance_name: &WorkloadInstanceName) {
+        self.record(
+            ank_base::EventType::WorkloadDeleted,
+            instance_name.agent_name(),
+            instance_name.workload_name(),
+            None,
+        );
+    }
+
+    pub fn execution_state_changed(&mut self, workload_state: &WorkloadState) {
+        self.record(
+            ank_base::EventType::ExecutionStateChanged,
+            workload_state.instance_name.agent_name(),
+            workload_state.instance_name.workload_name(),
+            Some(workload_state.execution_state.clone().into()),
+        );
+    }
+
+    pub fn agent_connected(&mut self, agent_name: &str) {
+        self.record(ank_base::EventType::AgentConnected, agent_name, "", None);
+    }
+
+    pub fn agent_disconnected(&mut self, agent_name: &str) {
+        self.record(ank_base::EventType::AgentDisconnected, agent_name, "", None);
+    }
+
+    /// Returns the recorded events with a sequence number greater than the passed one
+    ///
+    /// # Arguments
+    ///
+    /// * `after_sequence_number` - The sequence number of the last event already known to the caller
+    ///
+    // [impl->swdd~server-provides-events~1]
+    pub fn events_after(&self, after_sequence_number: u64) -> Vec<ank_base::Event> {
+        self.events
+            .iter()
+            .filter(|event| event.sequence_number > after_sequence_number)
+            .cloned()
+            .collect()
+    }
+
+    fn record(
+        &mut self,
+        event_type: ank_base::EventType,
+        agent_name: &str,
+        workload_name: &str,
+        execution_state: Option<ank_base::ExecutionState>,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+        }
+
+        self.last_sequence_number += 1;
+        self.events.push_back(ank_base::Event {
+            sequence_number: self.last_sequence_number,
+            timestamp: current_timestamp_millis(),
+            event_type: event_type as i32,
+            agent: agent_name.to_owned(),
+            workload_name: workload_name.to_owned(),
+            execution_state,
+        });
+    }
+}
+
+fn current_timestamp_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use api::ank_base;
+    use common::objects::{ExecutionState, WorkloadInstanceName, WorkloadState};
+
+    use super::EventLog;
+
+    const AGENT_A: &str = "agent_A";
+    const WORKLOAD_NAME_1: &str = "workload_1";
+
+    fn instance_name() -> WorkloadInstanceName {
+        WorkloadInstanceName::builder()
+            .agent_name(AGENT_A)
+            .workload_name(WORKLOAD_NAME_1)
+            .config(&String::from("config"))
+            .build()
+    }
+
+    // [utest->swdd~server-records-events~1]
+    #[test]
+    fn utest_event_log_records_events_with_increasing_sequence_numbers() {
+        let mut event_log = EventLog::default();
+
+        event_log.agent_connected(AGENT_A);
+        event_log.workload_added(&instance_name());
+        event_log.execution_state_changed(&WorkloadState {
+            instance_name: instance_name(),
+            execution_state: ExecutionState::running(),
+        });
+        event_log.workload_deleted(&instance_name());
+        event_log.agent_disconnected(AGENT_A);
+
+        let events = event_log.events_after(0);
+        assert_eq!(
+            events
+                .iter()
+                .map(|event| (
+                    event.sequence_number,
+                    event.event_type(),
+                    event.agent.as_str(),
+                    event.workload_name.as_str()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, ank_base::EventType::AgentConnected, AGENT_A, ""),
+                (
+                    2,
+                    ank_base::EventType::WorkloadAdded,
+                    AGENT_A,
+                    WORKLOAD_NAME_1
+                ),
+                (
+                    3,
+                    ank_base::EventType::ExecutionStateChanged,
+                    AGENT_A,
+                    WORKLOAD_NAME_1
+                ),
+                (
+                    4,
+                    ank_base::EventType::WorkloadDeleted,
+                    AGENT_A,
+                    WORKLOAD_NAME_1
+                ),
+                (5, ank_base::EventType::AgentDisconnected, AGENT_A, ""),
+            ]
+        );
+        assert_eq!(
+            events[2].execution_state,
+            Some(ExecutionState::running().into())
+        );
+        assert!(events.iter().all(|event| event.timestamp > 0));
+    }
+
+    // [utest->swdd~server-provides-events~1]
+    #[test]
+    fn utest_event_log_returns_only_events_after_sequence_number() {
+        let mut event_log = EventLog::default();
+
+        event_log.agent_connected(AGENT_A);
+        event_log.workload_added(&instance_name());
+        event_log.agent_disconnected(AGENT_A);
+
+        let sequence_numbers: Vec<u64> = event_log
+            .events_after(1)
+            .iter()
+            .map(|event| event.sequence_number)
+            .collect();
+        assert_eq!(sequence_numbers, vec![2, 3]);
+        assert!(event_log.events_after(3).is_empty());
+    }
+
+    // [utest->swdd~server-records-events~1]
+    #[test]
+    fn utest_event_log_drops_oldest_events_when_capacity_reached() {
+        let mut event_log = EventLog::with_capacity(2);
+
+        event_log.agent_connected(AGENT_A);
+        event_log.workload_added(&instance_name());
+        event_log.agent_disconnected(AGENT_A);
+
+        let sequence_numbers: Vec<u64> = event_log
+            .events_after(0)
+            .iter()
+            .map(|event| event.sequence_number)
+            .collect();
+        assert_eq!(sequence_numbers, vec![2, 3]);
+    }
+}
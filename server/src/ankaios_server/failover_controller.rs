@@ -0,0 +1,133 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+// Tracks the disconnected agents until they are regarded as lost at the end of the grace period.
+// Without a grace period, agents are never regarded as lost.
+// [impl->swdd~server-detects-lost-agents~1]
+#[derive(Debug, Default)]
+pub struct FailoverController {
+    grace_period: Option<Duration>,
+    lost_agent_deadlines: HashMap<String, Instant>,
+}
+
+impl FailoverController {
+    pub fn new(grace_period: Duration) -> Self {
+        FailoverController {
+            grace_period: Some(grace_period),
+            lost_agent_deadlines: HashMap::new(),
+        }
+    }
+
+    pub fn agent_disconnected(&mut self, agent_name: &str) {
+        if let Some(grace_period) = self.grace_period {
+            self.lost_agent_deadlines
+                .insert(agent_name.to_owned(), Instant::now() + grace_period);
+        }
+    }
+
+    // An agent reconnecting within the grace period is not regarded as lost
+    pub fn agent_connected(&mut self, agent_name: &str) {
+        self.lost_agent_deadlines.remove(agent_name);
+    }
+
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.lost_agent_deadlines.values().min().copied()
+    }
+
+    // Returns the agents whose grace period has elapsed at the given time, sorted by name
+    pub fn take_lost_agents(&mut self, now: Instant) -> Vec<String> {
+        let mut lost_agents: Vec<String> = self
+            .lost_agent_deadlines
+            .iter()
+            .filter(|(_, deadline)| **deadline <= now)
+            .map(|(agent_name, _)| agent_name.clone())
+            .collect();
+        lost_agents.sort();
+
+        lost_agents.iter().for_each(|agent_name| {
+            self.lost_agent_deadlines.remove(agent_name);
+        });
+        lost_agents
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use super::FailoverController;
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+    const GRACE_PERIOD: Duration = Duration::from_secs(60);
+
+    // [utest->swdd~server-detects-lost-agents~1]
+    #[test]
+    fn utest_failover_controller_returns_agents_after_grace_period() {
+        let mut failover_controller = FailoverController::new(GRACE_PERIOD);
+        let start = Instant::now();
+
+        failover_controller.agent_disconnected(AGENT_B);
+        failover_controller.agent_disconnected(AGENT_A);
+
+        assert!(failover_controller
+            .next_deadline()
+            .is_some_and(|deadline| deadline >= start + GRACE_PERIOD));
+        assert!(failover_controller.take_lost_agents(start).is_empty());
+        assert_eq!(
+            failover_controller.take_lost_agents(Instant::now() + GRACE_PERIOD),
+            vec![AGENT_A.to_string(), AGENT_B.to_string()]
+        );
+        assert_eq!(failover_controller.next_deadline(), None);
+    }
+
+    // [utest->swdd~server-detects-lost-agents~1]
+    #[test]
+    fn utest_failover_controller_ignores_reconnected_agents() {
+        let mut failover_controller = FailoverController::new(GRACE_PERIOD);
+
+        failover_controller.agent_disconnected(AGENT_A);
+        failover_controller.agent_connected(AGENT_A);
+
+        assert_eq!(failover_controller.next_deadline(), None);
+        assert!(failover_controller
+            .take_lost_agents(Instant::now() + GRACE_PERIOD)
+            .is_empty());
+    }
+
+    // [utest->swdd~server-detects-lost-agents~1]
+    #[test]
+    fn utest_failover_controller_without_grace_period_never_loses_agents() {
+        let mut failover_controller = FailoverController::default();
+
+        failover_controller.agent_disconnected(AGENT_A);
+
+        assert_eq!(failover_controller.next_deadline(), None);
+    }
+}
@@ -0,0 +1,284 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use common::{metrics_endpoint::write_metric_header, objects::WorkloadStatesMap};
+use grpc::server::StreamErrorCounter;
+
+const RESULT_SUCCESS: &str = "success";
+const RESULT_ERROR: &str = "error";
+
+#[derive(Debug, Default)]
+struct MetricsData {
+    connected_agents: HashSet<String>,
+    workloads_per_state: BTreeMap<String, u64>,
+    update_state_requests: BTreeMap<&'static str, u64>,
+    update_state_duration_sum: Duration,
+}
+
+/// Collects the metrics of the Ankaios server
+///
+/// The metrics are updated by the AnkaiosServer and rendered in the Prometheus
+/// text format when the metrics endpoint is scraped.
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    data: Arc<Mutex<MetricsData>>,
+    stream_errors: StreamErrorCounter,
+}
+
+impl Metrics {
+    pub fn new(stream_errors: StreamErrorCounter) -> Self {
+        Metrics {
+            data: Arc::default(),
+            stream_errors,
+        }
+    }
+
+    // [impl->swdd~server-collects-metrics~1]
+    pub fn agent_connected(&self, agent_name: &str) {
+        self.lock_data()
+            .connected_agents
+            .insert(agent_name.to_owned());
+    }
+
+    // [impl->swdd~server-collects-metrics~1]
+    pub fn agent_disconnected(&self, agent_name: &str) {
+        self.lock_data().connected_agents.remove(agent_name);
+    }
+
+    // [impl->swdd~server-collects-metrics~1]
+    pub fn update_workload_states(&self, workload_states_map: &WorkloadStatesMap) {
+        let mut workloads_per_state = BTreeMap::new();
+        for execution_state in workload_states_map.execution_states() {
+            *workloads_per_state
+                .entry(execution_state.state.to_string())
+                .or_default() += 1;
+        }
+        self.lock_data().workloads_per_state = workloads_per_state;
+    }
+
+    // [impl->swdd~server-collects-metrics~1]
+    pub fn update_state_request_handled(&self, duration: Duration, success: bool) {
+        let mut data = self.lock_data();
+        let result = if success {
+            RESULT_SUCCESS
+        } else {
+            RESULT_ERROR
+        };
+        *data.update_state_requests.entry(result).or_default() += 1;
+        data.update_state_duration_sum += duration;
+    }
+
+    // [impl->swdd~server-provides-metrics-endpoint~1]
+    pub fn render(&self) -> String {
+        let data = self.lock_data();
+        let mut output = String::new();
+
+        write_metric_header(
+            &mut output,
+            "ankaios_connected_agents",
+            "Number of agents connected to the server.",
+            "gauge",
+        );
+        let _ = writeln!(
+            output,
+            "ankaios_connected_agents {}",
+            data.connected_agents.len()
+        );
+
+        write_metric_header(
+            &mut output,
+            "ankaios_workloads",
+            "Number of workloads per execution state.",
+            "gauge",
+        );
+        for (state, count) in &data.workloads_per_state {
+            let _ = writeln!(output, "ankaios_workloads{{state=\"{}\"}} {}", state, count);
+        }
+
+        write_metric_header(
+            &mut output,
+            "ankaios_update_state_requests_total",
+            "Number of handled update state requests.",
+            "counter",
+        );
+        for (result, count) in &data.update_state_requests {
+            let _ = writeln!(
+                output,
+                "ankaios_update_state_requests_total{{result=\"{}\"}} {}",
+                result, count
+            );
+        }
+
+        write_metric_header(
+            &mut output,
+            "ankaios_update_state_request_duration_seconds",
+            "Time needed to handle the update state requests.",
+            "summary",
+        );
+        let _ = writeln!(
+            output,
+            "ankaios_update_state_request_duration_seconds_sum {}",
+            data.update_state_duration_sum.as_secs_f64()
+        );
+        let _ = writeln!(
+            output,
+            "ankaios_update_state_request_duration_seconds_count {}",
+            data.update_state_requests.values().sum::<u64>()
+        );
+
+        write_metric_header(
+            &mut output,
+            "ankaios_grpc_stream_errors_total",
+            "Number of agent connections interrupted with an error.",
+            "counter",
+        );
+        let _ = writeln!(
+            output,
+            "ankaios_grpc_stream_errors_total {}",
+            self.stream_errors.get()
+        );
+
+        output
+    }
+
+    fn lock_data(&self) -> std::sync::MutexGuard<'_, MetricsData> {
+        // The data stays consistent even if a thread panicked while holding the lock.
+        self.data
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use common::metrics_endpoint::{bind_metrics_endpoint, serve_metrics};
+    use common::objects::{
+        generate_test_workload_state_with_agent, ExecutionState, WorkloadState, WorkloadStatesMap,
+    };
+    use grpc::server::StreamErrorCounter;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+
+    use super::Metrics;
+
+    fn generate_test_workload_states_map(workload_states: Vec<WorkloadState>) -> WorkloadStatesMap {
+        let mut workload_states_map = WorkloadStatesMap::default();
+        workload_states_map.process_new_states(workload_states);
+        workload_states_map
+    }
+
+    // [utest->swdd~server-collects-metrics~1]
+    // [utest->swdd~server-provides-metrics-endpoint~1]
+    #[test]
+    fn utest_metrics_render() {
+        let stream_errors = StreamErrorCounter::default();
+        stream_errors.increment();
+        let metrics = Metrics::new(stream_errors);
+
+        metrics.agent_connected("agent_A");
+        metrics.agent_connected("agent_B");
+        metrics.agent_connected("agent_B");
+        metrics.agent_disconnected("agent_A");
+        metrics.update_workload_states(&generate_test_workload_states_map(vec![
+            generate_test_workload_state_with_agent(
+                "workload_1",
+                "agent_B",
+                ExecutionState::running(),
+            ),
+            generate_test_workload_state_with_agent(
+                "workload_2",
+                "agent_B",
+                ExecutionState::running(),
+            ),
+            generate_test_workload_state_with_agent(
+                "workload_3",
+                "agent_B",
+                ExecutionState::succeeded(),
+            ),
+        ]));
+        metrics.update_state_request_handled(Duration::from_millis(250), true);
+        metrics.update_state_request_handled(Duration::from_millis(250), false);
+
+        let output = metrics.render();
+
+        assert!(output.contains("# TYPE ankaios_connected_agents gauge\n"));
+        assert!(output.contains("\nankaios_connected_agents 1\n"));
+        assert!(output.contains("\nankaios_workloads{state=\"Running(Ok)\"} 2\n"));
+        assert!(output.contains("\nankaios_workloads{state=\"Succeeded(Ok)\"} 1\n"));
+        assert!(output.contains("\nankaios_update_state_requests_total{result=\"success\"} 1\n"));
+        assert!(output.contains("\nankaios_update_state_requests_total{result=\"error\"} 1\n"));
+        assert!(output.contains("\nankaios_update_state_request_duration_seconds_sum 0.5\n"));
+        assert!(output.contains("\nankaios_update_state_request_duration_seconds_count 2\n"));
+        assert!(output.contains("\nankaios_grpc_stream_errors_total 1\n"));
+    }
+
+    // [utest->swdd~server-collects-metrics~1]
+    #[test]
+    fn utest_metrics_workload_states_replaced_on_update() {
+        let metrics = Metrics::default();
+        metrics.update_workload_states(&generate_test_workload_states_map(vec![
+            generate_test_workload_state_with_agent(
+                "workload_1",
+                "agent_A",
+                ExecutionState::running(),
+            ),
+        ]));
+
+        metrics.update_workload_states(&Default::default());
+
+        assert!(!metrics.render().contains("ankaios_workloads{"));
+    }
+
+    // [utest->swdd~server-provides-metrics-endpoint~1]
+    #[tokio::test]
+    async fn utest_serve_metrics() {
+        let listener = bind_metrics_endpoint("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap();
+        let address = listener.local_addr().unwrap();
+        let metrics = Metrics::default();
+        metrics.agent_connected("agent_A");
+        tokio::spawn(serve_metrics(listener, move || metrics.render()));
+
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("\nankaios_connected_agents 1\n"));
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+#[derive(Debug)]
+struct PendingRequest {
+    agent_name: String,
+    deadline: Option<Instant>,
+}
+
+// Tracks the requests forwarded to agents until the agent responds. Without a timeout,
+// the requests are only ended by a response or by the disconnect of the agent.
+// The timed out requests are kept for another timeout to drop the late responses of the agents.
+// [impl->swdd~server-times-out-forwarded-agent-requests~2]
+#[derive(Debug, Default)]
+pub struct PendingAgentRequests {
+    timeout: Option<Duration>,
+    requests: HashMap<String, PendingRequest>,
+    timed_out_requests: HashMap<String, Instant>,
+}
+
+impl PendingAgentRequests {
+    pub fn new(timeout: Duration) -> Self {
+        PendingAgentRequests {
+            timeout: Some(timeout),
+            requests: HashMap::new(),
+            timed_out_requests: HashMap::new(),
+        }
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub fn request_forwarded(&mut self, request_id: &str, agent_name: &str) {
+        self.requests.insert(
+            request_id.to_owned(),
+            PendingRequest {
+                agent_name: agent_name.to_owned(),
+                deadline: self.timeout.map(|timeout| Instant::now() + timeout),
+            },
+        );
+    }
+
+    // Returns false for the late response of a timed out request, which must not be forwarded
+    // [impl->swdd~server-times-out-forwarded-agent-requests~2]
+    pub fn response_received(&mut self, request_id: &str) -> bool {
+        self.requests.remove(request_id);
+        self.timed_out_requests.remove(request_id).is_none()
+    }
+
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.requests
+            .values()
+            .filter_map(|request| request.deadline)
+            .min()
+    }
+
+    // Returns the ids and agents of the requests whose timeout has elapsed at the given time,
+    // sorted by request id
+    pub fn take_timed_out_requests(&mut self, now: Instant) -> Vec<(String, String)> {
+        self.timed_out_requests
+            .retain(|_, forget_deadline| *forget_deadline > now);
+
+        let timed_out_requests =
+            self.take_requests(|request| request.deadline.is_some_and(|deadline| deadline <= now));
+        let forget_deadline = now + self.timeout.unwrap_or_default();
+        for (request_id, _) in &timed_out_requests {
+            self.timed_out_requests
+                .insert(request_id.clone(), forget_deadline);
+        }
+        timed_out_requests
+    }
+
+    // Returns the ids of the requests forwarded to the agent, sorted by request id
+    // [impl->swdd~server-cancels-forwarded-requests-of-disconnected-agent~1]
+    pub fn take_requests_of_agent(&mut self, agent_name: &str) -> Vec<String> {
+        self.take_requests(|request| request.agent_name == agent_name)
+            .into_iter()
+            .map(|(request_id, _)| request_id)
+            .collect()
+    }
+
+    fn take_requests(&mut self, filter: impl Fn(&PendingRequest) -> bool) -> Vec<(String, String)> {
+        let mut request_ids: Vec<String> = self
+            .requests
+            .iter()
+            .filter(|(_, request)| filter(request))
+            .map(|(request_id, _)| request_id.clone())
+            .collect();
+        request_ids.sort();
+
+        request_ids
+            .into_iter()
+            .filter_map(|request_id| {
+                self.requests
+                    .remove(&request_id)
+                    .map(|request| (request_id, request.agent_name))
+            })
+            .collect()
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use super::PendingAgentRequests;
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+    const REQUEST_ID_1: &str = "request_1";
+    const REQUEST_ID_2: &str = "request_2";
+    const REQUEST_ID_3: &str = "request_3";
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    // [utest->swdd~server-times-out-forwarded-agent-requests~2]
+    #[test]
+    fn utest_pending_agent_requests_returns_requests_after_timeout() {
+        let mut pending_requests = PendingAgentRequests::new(TIMEOUT);
+        let start = Instant::now();
+
+        pending_requests.request_forwarded(REQUEST_ID_2, AGENT_B);
+        pending_requests.request_forwarded(REQUEST_ID_1, AGENT_A);
+
+        assert!(pending_requests
+            .next_deadline()
+            .is_some_and(|deadline| deadline >= start + TIMEOUT));
+        assert!(pending_requests.take_timed_out_requests(start).is_empty());
+        assert_eq!(
+            pending_requests.take_timed_out_requests(Instant::now() + TIMEOUT),
+            vec![
+                (REQUEST_ID_1.to_string(), AGENT_A.to_string()),
+                (REQUEST_ID_2.to_string(), AGENT_B.to_string())
+            ]
+        );
+        assert_eq!(pending_requests.next_deadline(), None);
+    }
+
+    // [utest->swdd~server-times-out-forwarded-agent-requests~2]
+    #[test]
+    fn utest_pending_agent_requests_ignores_answered_requests() {
+        let mut pending_requests = PendingAgentRequests::new(TIMEOUT);
+
+        pending_requests.request_forwarded(REQUEST_ID_1, AGENT_A);
+        assert!(pending_requests.response_received(REQUEST_ID_1));
+
+        assert_eq!(pending_requests.next_deadline(), None);
+        assert!(pending_requests
+            .take_timed_out_requests(Instant::now() + TIMEOUT)
+            .is_empty());
+    }
+
+    // [utest->swdd~server-times-out-forwarded-agent-requests~2]
+    #[test]
+    fn utest_pending_agent_requests_drops_late_response_of_timed_out_request() {
+        let mut pending_requests = PendingAgentRequests::new(TIMEOUT);
+
+        pending_requests.request_forwarded(REQUEST_ID_1, AGENT_A);
+        let timed_out_at = Instant::now() + TIMEOUT;
+        assert_eq!(
+            pending_requests.take_timed_out_requests(timed_out_at).len(),
+            1
+        );
+
+        assert!(!pending_requests.response_received(REQUEST_ID_1));
+        // only the first late response is expected, further ones are unknown ids
+        assert!(pending_requests.response_received(REQUEST_ID_1));
+    }
+
+    // [utest->swdd~server-times-out-forwarded-agent-requests~2]
+    #[test]
+    fn utest_pending_agent_requests_forgets_timed_out_requests_after_another_timeout() {
+        let mut pending_requests = PendingAgentRequests::new(TIMEOUT);
+
+        pending_requests.request_forwarded(REQUEST_ID_1, AGENT_A);
+        let timed_out_at = Instant::now() + TIMEOUT;
+        pending_requests.take_timed_out_requests(timed_out_at);
+        pending_requests.take_timed_out_requests(timed_out_at + TIMEOUT);
+
+        assert!(pending_requests.response_received(REQUEST_ID_1));
+    }
+
+    // [utest->swdd~server-cancels-forwarded-requests-of-disconnected-agent~1]
+    #[test]
+    fn utest_pending_agent_requests_returns_requests_of_agent_without_timeout() {
+        let mut pending_requests = PendingAgentRequests::default();
+
+        pending_requests.request_forwarded(REQUEST_ID_3, AGENT_A);
+        pending_requests.request_forwarded(REQUEST_ID_2, AGENT_B);
+        pending_requests.request_forwarded(REQUEST_ID_1, AGENT_A);
+
+        assert_eq!(pending_requests.next_deadline(), None);
+        assert_eq!(
+            pending_requests.take_requests_of_agent(AGENT_A),
+            vec![REQUEST_ID_1.to_string(), REQUEST_ID_3.to_string()]
+        );
+        assert!(pending_requests.take_requests_of_agent(AGENT_A).is_empty());
+        assert_eq!(
+            pending_requests.take_requests_of_agent(AGENT_B),
+            vec![REQUEST_ID_2.to_string()]
+        );
+    }
+}
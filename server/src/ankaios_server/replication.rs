@@ -0,0 +1,240 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::future::Future;
+use std::time::Duration;
+
+use api::ank_base;
+use common::objects::{CompleteState, State};
+use grpc::replication_client::ReplicationEvent;
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::mpsc;
+
+/// A server waiting as hot standby until it is promoted to the primary server
+///
+/// The standby stores the desired state replicated by the primary server and is
+/// promoted if the lease of the primary server is not renewed in time or if the
+/// promotion is requested manually.
+pub struct StandbyServer {
+    lease_timeout: Duration,
+    replicated_state: Option<ank_base::State>,
+}
+
+impl StandbyServer {
+    pub fn new(lease_timeout: Duration) -> Self {
+        StandbyServer {
+            lease_timeout,
+            replicated_state: None,
+        }
+    }
+
+    /// Returns the last replicated desired state as startup state of the promoted server
+    ///
+    /// Fails without promotion if the replication events end, i.e., if the replication client terminated.
+    // [impl->swdd~server-standby-stores-replicated-state~1]
+    // [impl->swdd~server-standby-promotes-on-lease-expiry~2]
+    // [impl->swdd~server-standby-promotes-on-request~1]
+    pub async fn wait_for_promotion(
+        mut self,
+        mut events_rx: mpsc::Receiver<ReplicationEvent>,
+        promotion_requested: impl Future<Output = ()>,
+    ) -> Result<Option<CompleteState>, String> {
+        tokio::pin!(promotion_requested);
+
+        loop {
+            tokio::select! {
+                event = tokio::time::timeout(self.lease_timeout, events_rx.recv()) => match event {
+                    Ok(Some(ReplicationEvent::DesiredState(desired_state))) => {
+                        log::debug!("Received the replicated desired state of the primary server.");
+                        self.replicated_state = Some(*desired_state);
+                    }
+                    Ok(Some(ReplicationEvent::LeaseRenewal)) => {
+                        log::trace!("The primary server renewed its lease.");
+                    }
+                    // A terminated replication client, e.g., because of a version mismatch, must not
+                    // promote the standby, as the primary server could still be running.
+                    Ok(None) => {
+                        return Err("The replication from the primary server terminated, the standby server is not promoted".into());
+                    }
+                    Err(_) => {
+                        log::warn!("The lease of the primary server expired -> promoting to primary.");
+                        break;
+                    }
+                },
+                _ = &mut promotion_requested => {
+                    log::info!("Promotion requested -> promoting to primary.");
+                    break;
+                }
+            }
+        }
+
+        self.replicated_state
+            .map(|desired_state| {
+                Ok(CompleteState {
+                    desired_state: State::try_from(desired_state)?,
+                    ..Default::default()
+                })
+            })
+            .transpose()
+    }
+}
+
+// [impl->swdd~server-standby-promotes-on-request~1]
+pub async fn promotion_signal() {
+    match signal(SignalKind::user_defined1()) {
+        Ok(mut sigusr1) => {
+            sigusr1.recv().await;
+        }
+        Err(err) => {
+            log::warn!(
+                "Could not listen to SIGUSR1, the manual promotion is not available: '{err}'"
+            );
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use api::ank_base;
+    use common::objects::{CompleteState, State};
+    use grpc::replication_client::ReplicationEvent;
+    use tokio::sync::{mpsc, oneshot};
+
+    use super::StandbyServer;
+
+    const LEASE_TIMEOUT: Duration = Duration::from_millis(100);
+
+    fn replicated_state(api_version: &str) -> ank_base::State {
+        ank_base::State {
+            api_version: api_version.into(),
+            ..Default::default()
+        }
+    }
+
+    fn expected_complete_state(api_version: &str) -> CompleteState {
+        CompleteState {
+            desired_state: State {
+                api_version: api_version.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    // [utest->swdd~server-standby-stores-replicated-state~1]
+    // [utest->swdd~server-standby-promotes-on-lease-expiry~2]
+    #[tokio::test]
+    async fn utest_standby_promotes_with_last_replicated_state_on_lease_expiry() {
+        let (events_tx, events_rx) = mpsc::channel(common::CHANNEL_CAPACITY);
+        events_tx
+            .send(ReplicationEvent::DesiredState(Box::new(replicated_state(
+                "v0.1",
+            ))))
+            .await
+            .unwrap();
+        events_tx
+            .send(ReplicationEvent::DesiredState(Box::new(replicated_state(
+                "v0.2",
+            ))))
+            .await
+            .unwrap();
+        events_tx
+            .send(ReplicationEvent::LeaseRenewal)
+            .await
+            .unwrap();
+
+        let promoted_state = StandbyServer::new(LEASE_TIMEOUT)
+            .wait_for_promotion(events_rx, std::future::pending())
+            .await;
+
+        assert_eq!(promoted_state, Ok(Some(expected_complete_state("v0.2"))));
+        drop(events_tx);
+    }
+
+    // [utest->swdd~server-standby-promotes-on-request~1]
+    #[tokio::test]
+    async fn utest_standby_promotes_on_request_while_lease_is_renewed() {
+        let (events_tx, events_rx) = mpsc::channel(common::CHANNEL_CAPACITY);
+        let (promotion_tx, promotion_rx) = oneshot::channel::<()>();
+
+        let renewal_task = tokio::spawn(async move {
+            events_tx
+                .send(ReplicationEvent::DesiredState(Box::new(replicated_state(
+                    "v0.1",
+                ))))
+                .await
+                .unwrap();
+            for _ in 0..5 {
+                tokio::time::sleep(LEASE_TIMEOUT / 4).await;
+                events_tx
+                    .send(ReplicationEvent::LeaseRenewal)
+                    .await
+                    .unwrap();
+            }
+            promotion_tx.send(()).unwrap();
+            events_tx
+        });
+
+        let promoted_state = StandbyServer::new(LEASE_TIMEOUT)
+            .wait_for_promotion(events_rx, async {
+                promotion_rx.await.unwrap();
+            })
+            .await;
+
+        assert_eq!(promoted_state, Ok(Some(expected_complete_state("v0.1"))));
+        drop(renewal_task.await.unwrap());
+    }
+
+    // [utest->swdd~server-standby-promotes-on-lease-expiry~2]
+    #[tokio::test]
+    async fn utest_standby_promotes_without_state_if_nothing_replicated() {
+        let (_events_tx, events_rx) = mpsc::channel(common::CHANNEL_CAPACITY);
+
+        let promoted_state = StandbyServer::new(LEASE_TIMEOUT)
+            .wait_for_promotion(events_rx, std::future::pending())
+            .await;
+
+        assert_eq!(promoted_state, Ok(None));
+    }
+
+    // [utest->swdd~server-standby-promotes-on-lease-expiry~2]
+    #[tokio::test]
+    async fn utest_standby_does_not_promote_on_terminated_replication() {
+        let (events_tx, events_rx) = mpsc::channel(common::CHANNEL_CAPACITY);
+        events_tx
+            .send(ReplicationEvent::DesiredState(Box::new(replicated_state(
+                "v0.1",
+            ))))
+            .await
+            .unwrap();
+        drop(events_tx);
+
+        let promoted_state = StandbyServer::new(LEASE_TIMEOUT)
+            .wait_for_promotion(events_rx, std::future::pending())
+            .await;
+
+        assert!(promoted_state.is_err());
+    }
+}
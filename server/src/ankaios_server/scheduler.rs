@@ -0,0 +1,260 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+
+use common::objects::{AgentMap, WorkloadInstanceName};
+
+use super::config_renderer::RenderedWorkloads;
+
+// Assigns the workloads without an agent but with a node selector to a connected agent having all
+// labels of the node selector. A workload keeps the agent it was scheduled to before as long as the
+// agent still matches or is disconnected, as the labels of a disconnected agent are unknown.
+// Otherwise, the matching agent with the fewest workloads is selected. Workloads without a matching
+// agent stay unscheduled until a matching agent connects.
+// [impl->swdd~server-schedules-workloads-by-node-selector~1]
+pub fn schedule_workloads(
+    new_workloads: &mut RenderedWorkloads,
+    current_workloads: &RenderedWorkloads,
+    connected_agents: &AgentMap,
+) {
+    let mut workload_counts: BTreeMap<&str, usize> = connected_agents
+        .iter()
+        .map(|(agent_name, _)| (agent_name.as_str(), 0))
+        .collect();
+    new_workloads.values().for_each(|workload| {
+        if let Some(count) = workload_counts.get_mut(workload.instance_name.agent_name()) {
+            *count += 1;
+        }
+    });
+
+    // the workloads are scheduled in a fixed order to get reproducible results
+    let mut workloads_to_schedule: Vec<&String> = new_workloads
+        .iter()
+        .filter(|(_, workload)| {
+            workload.instance_name.agent_name().is_empty() && !workload.node_selector.is_empty()
+        })
+        .map(|(workload_name, _)| workload_name)
+        .collect();
+    workloads_to_schedule.sort();
+
+    let mut scheduled_agents: Vec<(String, String)> = Vec::new();
+    for workload_name in workloads_to_schedule {
+        let node_selector = &new_workloads[workload_name].node_selector;
+
+        let previous_agent = current_workloads
+            .get(workload_name)
+            .map(|workload| workload.instance_name.agent_name())
+            .filter(|agent_name| !agent_name.is_empty())
+            .filter(|agent_name| {
+                connected_agents
+                    .get(agent_name)
+                    .is_none_or(|agent| matches_node_selector(node_selector, &agent.labels))
+            });
+
+        let selected_agent = previous_agent.or_else(|| {
+            workload_counts
+                .iter()
+                .filter(|(agent_name, _)| {
+                    connected_agents
+                        .get(agent_name)
+                        .is_some_and(|agent| matches_node_selector(node_selector, &agent.labels))
+                })
+                .min_by_key(|(_, count)| **count)
+                .map(|(agent_name, _)| *agent_name)
+        });
+
+        if let Some(agent_name) = selected_agent {
+            if let Some(count) = workload_counts.get_mut(agent_name) {
+                *count += 1;
+            }
+            scheduled_agents.push((workload_name.clone(), agent_name.to_owned()));
+        } else {
+            log::debug!(
+                "No connected agent matches the node selector of workload '{workload_name}'"
+            );
+        }
+    }
+
+    for (workload_name, agent_name) in scheduled_agents {
+        if let Some(workload) = new_workloads.get_mut(&workload_name) {
+            log::debug!("Scheduled workload '{workload_name}' to agent '{agent_name}'");
+            workload.instance_name = WorkloadInstanceName::new(
+                agent_name,
+                workload.instance_name.workload_name(),
+                workload.instance_name.id(),
+            );
+        }
+    }
+}
+
+fn matches_node_selector(
+    node_selector: &HashMap<String, String>,
+    labels: &HashMap<String, String>,
+) -> bool {
+    node_selector
+        .iter()
+        .all(|(key, value)| labels.get(key) == Some(value))
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use common::objects::{
+        generate_test_workload_spec_with_param, AgentAttributes, AgentMap, WorkloadSpec,
+    };
+
+    use super::{schedule_workloads, RenderedWorkloads};
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+    const WORKLOAD_NAME_1: &str = "workload_1";
+    const WORKLOAD_NAME_2: &str = "workload_2";
+    const RUNTIME: &str = "runtime";
+
+    fn labels(labels: &[(&str, &str)]) -> HashMap<String, String> {
+        labels
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn workload(
+        agent_name: &str,
+        workload_name: &str,
+        node_selector: &[(&str, &str)],
+    ) -> WorkloadSpec {
+        let mut workload = generate_test_workload_spec_with_param(
+            agent_name.to_string(),
+            workload_name.to_string(),
+            RUNTIME.to_string(),
+        );
+        workload.node_selector = labels(node_selector);
+        workload
+    }
+
+    fn rendered_workloads(workloads: Vec<WorkloadSpec>) -> RenderedWorkloads {
+        workloads
+            .into_iter()
+            .map(|workload| (workload.instance_name.workload_name().to_owned(), workload))
+            .collect()
+    }
+
+    fn connected_agents(agents: &[(&str, &[(&str, &str)])]) -> AgentMap {
+        let mut agent_map = AgentMap::new();
+        for (agent_name, agent_labels) in agents {
+            agent_map
+                .entry(agent_name.to_string())
+                .or_insert(AgentAttributes {
+                    labels: labels(agent_labels),
+                    ..Default::default()
+                });
+        }
+        agent_map
+    }
+
+    fn scheduled_agent(workloads: &RenderedWorkloads, workload_name: &str) -> String {
+        workloads[workload_name]
+            .instance_name
+            .agent_name()
+            .to_owned()
+    }
+
+    // [utest->swdd~server-schedules-workloads-by-node-selector~1]
+    #[test]
+    fn utest_schedule_workloads_to_matching_agent() {
+        let mut new_workloads =
+            rendered_workloads(vec![workload("", WORKLOAD_NAME_1, &[("zone", "cockpit")])]);
+        let agents = connected_agents(&[
+            (AGENT_A, &[("zone", "body")]),
+            (AGENT_B, &[("zone", "cockpit"), ("gpu", "true")]),
+        ]);
+
+        schedule_workloads(&mut new_workloads, &RenderedWorkloads::new(), &agents);
+
+        assert_eq!(scheduled_agent(&new_workloads, WORKLOAD_NAME_1), AGENT_B);
+    }
+
+    // [utest->swdd~server-schedules-workloads-by-node-selector~1]
+    #[test]
+    fn utest_schedule_workloads_keeps_workloads_without_matching_agent_unscheduled() {
+        let mut new_workloads = rendered_workloads(vec![
+            workload("", WORKLOAD_NAME_1, &[("zone", "cockpit")]),
+            workload("", WORKLOAD_NAME_2, &[]),
+        ]);
+        let agents = connected_agents(&[(AGENT_A, &[("zone", "body")]), (AGENT_B, &[])]);
+
+        schedule_workloads(&mut new_workloads, &RenderedWorkloads::new(), &agents);
+
+        assert_eq!(scheduled_agent(&new_workloads, WORKLOAD_NAME_1), "");
+        assert_eq!(scheduled_agent(&new_workloads, WORKLOAD_NAME_2), "");
+    }
+
+    // [utest->swdd~server-schedules-workloads-by-node-selector~1]
+    #[test]
+    fn utest_schedule_workloads_selects_agent_with_fewest_workloads() {
+        let mut new_workloads = rendered_workloads(vec![
+            workload(AGENT_A, "pinned", &[]),
+            workload("", WORKLOAD_NAME_1, &[("zone", "cockpit")]),
+            workload("", WORKLOAD_NAME_2, &[("zone", "cockpit")]),
+        ]);
+        let agents = connected_agents(&[
+            (AGENT_A, &[("zone", "cockpit")]),
+            (AGENT_B, &[("zone", "cockpit")]),
+        ]);
+
+        schedule_workloads(&mut new_workloads, &RenderedWorkloads::new(), &agents);
+
+        assert_eq!(scheduled_agent(&new_workloads, WORKLOAD_NAME_1), AGENT_B);
+        assert_eq!(scheduled_agent(&new_workloads, WORKLOAD_NAME_2), AGENT_A);
+    }
+
+    // [utest->swdd~server-schedules-workloads-by-node-selector~1]
+    #[test]
+    fn utest_schedule_workloads_keeps_previous_agent() {
+        let mut new_workloads = rendered_workloads(vec![
+            workload("", WORKLOAD_NAME_1, &[("zone", "cockpit")]),
+            workload("", WORKLOAD_NAME_2, &[("zone", "cockpit")]),
+        ]);
+        let current_workloads = rendered_workloads(vec![
+            workload(AGENT_B, WORKLOAD_NAME_1, &[("zone", "cockpit")]),
+            workload(
+                "disconnected_agent",
+                WORKLOAD_NAME_2,
+                &[("zone", "cockpit")],
+            ),
+        ]);
+        let agents = connected_agents(&[
+            (AGENT_A, &[("zone", "cockpit")]),
+            (AGENT_B, &[("zone", "cockpit")]),
+        ]);
+
+        schedule_workloads(&mut new_workloads, &current_workloads, &agents);
+
+        assert_eq!(scheduled_agent(&new_workloads, WORKLOAD_NAME_1), AGENT_B);
+        assert_eq!(
+            scheduled_agent(&new_workloads, WORKLOAD_NAME_2),
+            "disconnected_agent"
+        );
+    }
+}
@@ -12,18 +12,23 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+use super::admission::{AdmissionRejection, AdmissionValidators};
 use super::config_renderer::RenderedWorkloads;
 use api::ank_base;
 use common::commands;
+use common::cycle_check;
 
 #[cfg_attr(test, mockall_double::double)]
 use super::config_renderer::ConfigRenderer;
 
-use super::cycle_check;
 #[cfg_attr(test, mockall_double::double)]
 use super::delete_graph::DeleteGraph;
+use super::dependency_graph::build_dependency_graph;
+use super::scheduler::schedule_workloads;
+use super::workload_diff::diff_workloads;
 use common::objects::{
-    AgentAttributes, CpuUsage, FreeMemory, State, WorkloadState, WorkloadStatesMap,
+    AgentAttributes, CpuUsage, DiskUsage, FreeMemory, NetworkUsage, State, StoredWorkloadSpec, Tag,
+    Temperature, WorkloadInstanceName, WorkloadState, WorkloadStatesMap,
 };
 use common::std_extensions::IllegalStateResult;
 use common::{
@@ -31,7 +36,9 @@ use common::{
     objects::{CompleteState, DeletedWorkload, WorkloadSpec},
     state_manipulation::{Object, Path},
 };
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[cfg(test)]
 use mockall::automock;
@@ -79,11 +86,154 @@ fn extract_added_and_deleted_workloads(
     Some((added_workloads, deleted_workloads))
 }
 
+// [impl->swdd~server-state-restarts-workloads-on-changed-config-dependencies~1]
+fn add_workloads_with_changed_config_dependencies(
+    added_deleted_workloads: Option<(Vec<WorkloadSpec>, Vec<DeletedWorkload>)>,
+    changed_config_keys: &HashSet<&String>,
+    current_workloads: &RenderedWorkloads,
+    new_workloads: &RenderedWorkloads,
+) -> Option<(Vec<WorkloadSpec>, Vec<DeletedWorkload>)> {
+    let (mut added_workloads, mut deleted_workloads) = added_deleted_workloads.unwrap_or_default();
+
+    for (wl_name, new_wls) in new_workloads {
+        // new and already updated workloads are started anyway
+        let Some(current_wls) = current_workloads.get(wl_name) else {
+            continue;
+        };
+        if current_wls != new_wls {
+            continue;
+        }
+
+        if new_wls
+            .dependencies
+            .iter()
+            .any(|(dependency, add_condition)| {
+                add_condition.refers_to_config() && changed_config_keys.contains(dependency)
+            })
+        {
+            added_workloads.push(new_wls.clone());
+            deleted_workloads.push(DeletedWorkload {
+                instance_name: current_wls.instance_name.clone(),
+                ..Default::default()
+            });
+        }
+    }
+
+    if added_workloads.is_empty() && deleted_workloads.is_empty() {
+        return None;
+    }
+
+    Some((added_workloads, deleted_workloads))
+}
+
+// [impl->swdd~server-state-restarts-workloads-on-updated-restart-on-workloads~1]
+fn add_workloads_restarting_on_updated_workloads(
+    added_deleted_workloads: Option<(Vec<WorkloadSpec>, Vec<DeletedWorkload>)>,
+    current_workloads: &RenderedWorkloads,
+    new_workloads: &RenderedWorkloads,
+) -> Option<(Vec<WorkloadSpec>, Vec<DeletedWorkload>)> {
+    let (mut added_workloads, mut deleted_workloads) = added_deleted_workloads?;
+
+    // workloads being added and deleted at the same time are updated or restarted
+    let mut restarted_workloads: HashSet<String> = added_workloads
+        .iter()
+        .map(|added_workload| added_workload.instance_name.workload_name())
+        .filter(|workload_name| current_workloads.contains_key(*workload_name))
+        .map(ToOwned::to_owned)
+        .collect();
+
+    // the restarts are propagated until no further unchanged workload is restarted on them
+    loop {
+        let workloads_to_restart: Vec<(&String, &WorkloadSpec)> = new_workloads
+            .iter()
+            .filter(|(wl_name, new_wls)| {
+                !restarted_workloads.contains(*wl_name)
+                    && current_workloads.get(*wl_name) == Some(*new_wls)
+                    && new_wls
+                        .restart_on
+                        .iter()
+                        .any(|restart_on| restarted_workloads.contains(restart_on))
+            })
+            .collect();
+        if workloads_to_restart.is_empty() {
+            break;
+        }
+
+        for (wl_name, new_wls) in workloads_to_restart {
+            restarted_workloads.insert(wl_name.clone());
+            added_workloads.push(new_wls.clone());
+            deleted_workloads.push(DeletedWorkload {
+                instance_name: new_wls.instance_name.clone(),
+                ..Default::default()
+            });
+        }
+    }
+
+    Some((added_workloads, deleted_workloads))
+}
+
+fn get_changed_config_keys<'a>(
+    current_desired_state: &'a State,
+    new_desired_state: &'a State,
+) -> HashSet<&'a String> {
+    current_desired_state
+        .configs
+        .keys()
+        .chain(new_desired_state.configs.keys())
+        .filter(|config_key| {
+            current_desired_state.configs.get(*config_key)
+                != new_desired_state.configs.get(*config_key)
+        })
+        .collect()
+}
+
+// [impl->swdd~server-state-rejects-config-dependencies-to-not-existing-configs~1]
+fn verify_config_dependencies(desired_state: &State) -> Result<(), UpdateStateError> {
+    for (workload_name, workload) in &desired_state.workloads {
+        for (dependency, add_condition) in &workload.dependencies {
+            if add_condition.refers_to_config() && !desired_state.configs.contains_key(dependency) {
+                return Err(UpdateStateError::ResultInvalid(format!(
+                    "workload '{}' depends on the not existing config '{}'",
+                    workload_name, dependency
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+// [impl->swdd~server-state-rejects-invalid-secret-refs~1]
+fn verify_secret_refs(desired_state: &State) -> Result<(), UpdateStateError> {
+    for (config_name, config) in &desired_state.configs {
+        config.verify_secret_refs().map_err(|err| {
+            UpdateStateError::ResultInvalid(format!("config '{}': {}", config_name, err))
+        })?;
+    }
+    Ok(())
+}
+
+// [impl->swdd~server-state-rejects-conflicting-replica-names~1]
+fn verify_replica_names(desired_state: &State) -> Result<(), UpdateStateError> {
+    let mut instance_names: HashMap<String, &str> = HashMap::new();
+    for (workload_name, workload) in &desired_state.workloads {
+        for replica_name in workload.replica_names(workload_name) {
+            if let Some(other_workload_name) = instance_names.insert(replica_name, workload_name) {
+                return Err(UpdateStateError::ResultInvalid(format!(
+                    "the replicas of the workloads '{}' and '{}' have the same name",
+                    other_workload_name, workload_name
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum UpdateStateError {
     FieldNotFound(String),
     ResultInvalid(String),
     CycleInDependencies(String),
+    AdmissionRejected(Vec<AdmissionRejection>),
 }
 
 impl Display for UpdateStateError {
@@ -102,6 +252,17 @@ impl Display for UpdateStateError {
                     workload_part_of_cycle
                 )
             }
+            UpdateStateError::AdmissionRejected(rejections) => {
+                write!(
+                    f,
+                    "Update rejected by the admission validators: '{}'",
+                    rejections
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join("', '")
+                )
+            }
         }
     }
 }
@@ -112,6 +273,7 @@ pub struct ServerState {
     rendered_workloads: RenderedWorkloads,
     delete_graph: DeleteGraph,
     config_renderer: ConfigRenderer,
+    admission_validators: AdmissionValidators,
 }
 
 pub type AddedDeletedWorkloads = Option<(Vec<WorkloadSpec>, Vec<DeletedWorkload>)>;
@@ -120,6 +282,10 @@ pub type AddedDeletedWorkloads = Option<(Vec<WorkloadSpec>, Vec<DeletedWorkload>
 impl ServerState {
     const API_VERSION_FILTER_MASK: &'static str = "desiredState.apiVersion";
     const DESIRED_STATE_FIELD_MASK_PART: &'static str = "desiredState";
+    const WORKLOAD_STATES_FIELD_MASK_PART: &'static str = "workloadStates";
+    const AGENTS_FIELD_MASK_PART: &'static str = "agents";
+    const WORKLOAD_RESOURCES_FIELD_MASK_PART: &'static str = "workloadResources";
+    const IMAGE_STATES_FIELD_MASK_PART: &'static str = "imageStates";
 
     // [impl->swdd~server-provides-interface-get-complete-state~2]
     // [impl->swdd~server-filters-get-complete-state-result~2]
@@ -128,12 +294,36 @@ impl ServerState {
         request_complete_state: CompleteStateRequest,
         workload_states_map: &WorkloadStatesMap,
     ) -> Result<ank_base::CompleteState, String> {
-        let current_complete_state: ank_base::CompleteState = CompleteState {
-            desired_state: self.state.desired_state.clone(),
-            workload_states: workload_states_map.clone(),
-            agents: self.state.agents.clone(),
-        }
-        .into();
+        // Only the parts of the state which are selected by the field mask are copied.
+        let field_mask = &request_complete_state.field_mask;
+        let selector = &request_complete_state.selector;
+        let current_complete_state = ank_base::CompleteState {
+            desired_state: Self::is_part_requested(field_mask, Self::DESIRED_STATE_FIELD_MASK_PART)
+                .then(|| self.select_desired_state(selector).into()),
+            workload_states: Self::is_part_requested(
+                field_mask,
+                Self::WORKLOAD_STATES_FIELD_MASK_PART,
+            )
+            .then(|| {
+                self.select_workload_states(workload_states_map, selector)
+                    .into()
+            })
+            .flatten(),
+            agents: Self::is_part_requested(field_mask, Self::AGENTS_FIELD_MASK_PART)
+                .then(|| self.state.agents.clone().into())
+                .flatten(),
+            workload_resources: Self::is_part_requested(
+                field_mask,
+                Self::WORKLOAD_RESOURCES_FIELD_MASK_PART,
+            )
+            .then(|| self.state.workload_resources.clone().into())
+            .flatten(),
+            workload_state_history: None,
+            // [impl->swdd~server-provides-image-states~1]
+            image_states: Self::is_part_requested(field_mask, Self::IMAGE_STATES_FIELD_MASK_PART)
+                .then(|| self.state.image_states.clone().into())
+                .flatten(),
+        };
 
         if !request_complete_state.field_mask.is_empty() {
             let mut filters = request_complete_state.field_mask;
@@ -171,6 +361,56 @@ impl ServerState {
         }
     }
 
+    // [impl->swdd~server-selects-workloads-by-tags~1]
+    fn select_desired_state(&self, selector: &[Tag]) -> State {
+        let mut desired_state = self.state.desired_state.clone();
+        desired_state
+            .workloads
+            .retain(|_, workload| Self::is_selected(workload, selector));
+        for namespace in desired_state.namespaces.values_mut() {
+            namespace
+                .workloads
+                .retain(|_, workload| Self::is_selected(workload, selector));
+        }
+        desired_state
+    }
+
+    // [impl->swdd~server-selects-workloads-by-tags~1]
+    fn select_workload_states(
+        &self,
+        workload_states_map: &WorkloadStatesMap,
+        selector: &[Tag],
+    ) -> WorkloadStatesMap {
+        let mut workload_states_map = workload_states_map.clone();
+        if !selector.is_empty() {
+            // the rendered workloads contain the replicas of the selected workloads as well
+            workload_states_map.retain_workloads(|workload_name| {
+                self.rendered_workloads
+                    .get(workload_name)
+                    .is_some_and(|workload| selector.iter().all(|tag| workload.tags.contains(tag)))
+            });
+        }
+        workload_states_map
+    }
+
+    fn is_selected(workload: &StoredWorkloadSpec, selector: &[Tag]) -> bool {
+        selector.iter().all(|tag| workload.tags.contains(tag))
+    }
+
+    fn is_part_requested(field_mask: &[String], part: &str) -> bool {
+        field_mask.is_empty()
+            || field_mask.iter().any(|field| {
+                field
+                    .strip_prefix(part)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(common::PATH_SEPARATOR))
+            })
+    }
+
+    // [impl->swdd~server-replicates-desired-state~2]
+    pub fn get_desired_state(&self) -> ank_base::State {
+        self.state.desired_state.clone().into()
+    }
+
     // [impl->swdd~agent-from-agent-field~1]
     pub fn get_workloads_for_agent(&self, agent_name: &str) -> Vec<WorkloadSpec> {
         self.rendered_workloads
@@ -180,89 +420,211 @@ impl ServerState {
             .collect()
     }
 
+    // [impl->swdd~server-sends-images-to-pull-to-agents~1]
+    pub fn get_images_for_agent(&self, agent_name: &str) -> Vec<String> {
+        self.state
+            .desired_state
+            .images
+            .get(agent_name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    // [impl->swdd~server-forwards-exec-request-to-agent~1]
+    pub fn get_agent_of_workload(&self, workload_name: &str) -> Option<String> {
+        self.rendered_workloads
+            .get(workload_name)
+            .map(|workload| workload.instance_name.agent_name().to_string())
+    }
+
+    // [impl->swdd~server-forwards-restart-workload-request-to-agent~1]
+    // [impl->swdd~server-forwards-pause-workload-request-to-agent~1]
+    pub fn get_replica_names_of_workload(&self, workload_name: &str) -> Option<Vec<String>> {
+        self.state
+            .desired_state
+            .get_workload(workload_name)
+            .map(|workload| workload.replica_names(workload_name))
+    }
+
+    // [impl->swdd~server-provides-dependency-graph~1]
+    pub fn get_dependency_graph(
+        &self,
+        workload_states_map: &WorkloadStatesMap,
+    ) -> ank_base::DependencyGraph {
+        build_dependency_graph(&self.rendered_workloads, workload_states_map)
+    }
+
     pub fn update(
         &mut self,
         new_state: CompleteState,
         update_mask: Vec<String>,
     ) -> Result<AddedDeletedWorkloads, UpdateStateError> {
-        // [impl->swdd~update-desired-state-with-update-mask~1]
-        // [impl->swdd~update-desired-state-empty-update-mask~1]
-        match self.generate_new_state(new_state, update_mask) {
-            Ok(new_templated_state) => {
-                // [impl->swdd~server-state-triggers-configuration-rendering-of-workloads~1]
-                let new_rendered_workloads = self
-                    .config_renderer
-                    .render_workloads(
-                        &new_templated_state.desired_state.workloads,
-                        &new_templated_state.desired_state.configs,
-                    )
-                    .map_err(|err| UpdateStateError::ResultInvalid(err.to_string()))?;
-
-                // [impl->swdd~server-state-triggers-validation-of-workload-fields~1]
-                self.verify_workload_fields_format(&new_rendered_workloads)?;
-
-                // [impl->swdd~server-state-compares-rendered-workloads~1]
-                let cmd = extract_added_and_deleted_workloads(
-                    &self.rendered_workloads,
-                    &new_rendered_workloads,
-                );
+        let (new_desired_state, new_rendered_workloads, added_deleted_workloads) =
+            self.prepare_update(new_state, update_mask)?;
 
-                if let Some((added_workloads, mut deleted_workloads)) = cmd {
-                    let start_nodes: Vec<&str> = added_workloads
-                        .iter()
-                        .filter_map(|w| {
-                            if !w.dependencies.is_empty() {
-                                Some(w.instance_name.workload_name())
-                            } else {
-                                None
-                            }
-                        })
-                        .collect();
-
-                    // [impl->swdd~server-state-rejects-state-with-cyclic-dependencies~1]
-                    if let Some(workload_part_of_cycle) =
-                        cycle_check::dfs(&new_templated_state.desired_state, Some(start_nodes))
-                    {
-                        return Err(UpdateStateError::CycleInDependencies(
-                            workload_part_of_cycle,
-                        ));
-                    }
+        if let Some((added_workloads, mut deleted_workloads)) = added_deleted_workloads {
+            // [impl->swdd~server-state-stores-delete-condition~2]
+            self.delete_graph.insert(&added_workloads);
 
-                    // [impl->swdd~server-state-stores-delete-condition~1]
-                    self.delete_graph.insert(&added_workloads);
+            // [impl->swdd~server-state-adds-delete-conditions-to-deleted-workload~1]
+            self.delete_graph
+                .apply_delete_conditions_to(&mut deleted_workloads);
 
-                    // [impl->swdd~server-state-adds-delete-conditions-to-deleted-workload~1]
-                    self.delete_graph
-                        .apply_delete_conditions_to(&mut deleted_workloads);
+            self.set_desired_state(new_desired_state);
+            self.rendered_workloads = new_rendered_workloads;
+            Ok(Some((added_workloads, deleted_workloads)))
+        } else {
+            // update state with changed fields not affecting workloads, e.g. config items
+            // [impl->swdd~server-state-updates-state-on-unmodified-workloads~1]
+            self.set_desired_state(new_desired_state);
+            Ok(None)
+        }
+    }
 
-                    self.set_desired_state(new_templated_state.desired_state);
-                    self.rendered_workloads = new_rendered_workloads;
-                    Ok(Some((added_workloads, deleted_workloads)))
-                } else {
-                    // update state with changed fields not affecting workloads, e.g. config items
-                    // [impl->swdd~server-state-updates-state-on-unmodified-workloads~1]
-                    self.set_desired_state(new_templated_state.desired_state);
-                    Ok(None)
+    // [impl->swdd~server-state-computes-dry-run-update~1]
+    pub fn dry_run_update(
+        &self,
+        new_state: CompleteState,
+        update_mask: Vec<String>,
+    ) -> Result<ank_base::UpdateStateDryRunResult, UpdateStateError> {
+        let (_, new_rendered_workloads, added_deleted_workloads) =
+            self.prepare_update(new_state, update_mask)?;
+
+        let mut dry_run_result = ank_base::UpdateStateDryRunResult::default();
+        let Some((added_workloads, deleted_workloads)) = added_deleted_workloads else {
+            return Ok(dry_run_result);
+        };
+
+        for added_workload in added_workloads {
+            let workload_name = added_workload.instance_name.workload_name();
+            match self.rendered_workloads.get(workload_name) {
+                Some(current_workload) => {
+                    dry_run_result
+                        .updated_workloads
+                        .push(ank_base::WorkloadDiff {
+                            workload_name: workload_name.to_string(),
+                            field_diffs: diff_workloads(current_workload, &added_workload),
+                        })
                 }
+                None => dry_run_result
+                    .added_workloads
+                    .push(added_workload.instance_name.to_string()),
             }
-            Err(error) => Err(error),
         }
+
+        dry_run_result.deleted_workloads = deleted_workloads
+            .into_iter()
+            .filter(|deleted_workload| {
+                !new_rendered_workloads.contains_key(deleted_workload.instance_name.workload_name())
+            })
+            .map(|deleted_workload| deleted_workload.instance_name.to_string())
+            .collect();
+
+        Ok(dry_run_result)
+    }
+
+    // [impl->swdd~server-state-restricts-env-template-helper~1]
+    pub fn set_allowed_env_prefixes(&mut self, allowed_prefixes: Vec<String>) {
+        self.config_renderer
+            .set_allowed_env_prefixes(allowed_prefixes);
+    }
+
+    // [impl->swdd~server-state-runs-admission-validators~1]
+    pub fn set_admission_validators(&mut self, admission_validators: AdmissionValidators) {
+        self.admission_validators = admission_validators;
     }
 
     // [impl->swdd~server-state-stores-agent-in-complete-state~1]
-    pub fn add_agent(&mut self, agent_name: String) {
+    pub fn add_agent(&mut self, agent_hello: commands::AgentHello) {
         self.state
             .agents
-            .entry(agent_name)
+            .entry(agent_hello.agent_name)
             .or_insert(AgentAttributes {
                 cpu_usage: Some(CpuUsage::default()),
                 free_memory: Some(FreeMemory::default()),
+                disk_usage: Some(DiskUsage::default()),
+                network_usage: Some(NetworkUsage::default()),
+                temperature: Some(Temperature::default()),
+                labels: agent_hello.labels,
+                // [impl->swdd~server-state-stores-agent-version-and-runtimes~1]
+                version: agent_hello.version,
+                runtimes: agent_hello.runtimes,
+                connected_at: current_timestamp_millis(),
+                load_history: Vec::new(),
             });
     }
 
+    // Schedules the unscheduled workloads, e.g., after an agent with new labels has connected.
+    // [impl->swdd~server-state-schedules-workloads-by-node-selector~1]
+    pub fn schedule_unscheduled_workloads(&mut self) -> AddedDeletedWorkloads {
+        let mut new_rendered_workloads = self.rendered_workloads.clone();
+        schedule_workloads(
+            &mut new_rendered_workloads,
+            &self.rendered_workloads,
+            &self.state.agents,
+        );
+
+        let added_deleted_workloads =
+            extract_added_and_deleted_workloads(&self.rendered_workloads, &new_rendered_workloads);
+        if let Some((added_workloads, _)) = &added_deleted_workloads {
+            // [impl->swdd~server-state-stores-delete-condition~2]
+            self.delete_graph.insert(added_workloads);
+        }
+        self.rendered_workloads = new_rendered_workloads;
+        added_deleted_workloads
+    }
+
+    // Moves the workloads scheduled by node selector away from a lost agent. Workloads without another
+    // matching agent stay on the lost agent.
+    // [impl->swdd~server-state-reschedules-workloads-of-lost-agent~1]
+    pub fn reschedule_workloads_of_lost_agent(
+        &mut self,
+        agent_name: &str,
+    ) -> AddedDeletedWorkloads {
+        let mut new_rendered_workloads = self.rendered_workloads.clone();
+        new_rendered_workloads
+            .values_mut()
+            .filter(|workload| {
+                workload.instance_name.agent_name() == agent_name
+                    && !workload.node_selector.is_empty()
+            })
+            .for_each(|workload| {
+                workload.instance_name = WorkloadInstanceName::new(
+                    "",
+                    workload.instance_name.workload_name(),
+                    workload.instance_name.id(),
+                );
+            });
+        schedule_workloads(
+            &mut new_rendered_workloads,
+            &RenderedWorkloads::new(),
+            &self.state.agents,
+        );
+        new_rendered_workloads
+            .iter_mut()
+            .filter(|(_, workload)| workload.instance_name.agent_name().is_empty())
+            .for_each(|(workload_name, workload)| {
+                if let Some(current_workload) = self.rendered_workloads.get(workload_name) {
+                    *workload = current_workload.clone();
+                }
+            });
+
+        let added_deleted_workloads =
+            extract_added_and_deleted_workloads(&self.rendered_workloads, &new_rendered_workloads);
+        if let Some((added_workloads, _)) = &added_deleted_workloads {
+            // [impl->swdd~server-state-stores-delete-condition~2]
+            self.delete_graph.insert(added_workloads);
+        }
+        self.rendered_workloads = new_rendered_workloads;
+        added_deleted_workloads
+    }
+
     // [impl->swdd~server-state-removes-agent-from-complete-state~1]
     pub fn remove_agent(&mut self, agent_name: &str) {
-        self.state.agents.remove(agent_name);
+        let state = &mut self.state;
+        state.agents.remove(agent_name);
+        state.workload_resources.remove_agent(agent_name);
+        state.image_states.remove_agent(agent_name);
     }
 
     // [impl->swdd~server-state-provides-connected-agent-exists-check~1]
@@ -275,9 +637,20 @@ impl ServerState {
         &mut self,
         agent_load_status: commands::AgentLoadStatus,
     ) {
-        self.state
+        let state = &mut self.state;
+        // [impl->swdd~server-stores-workload-resources-in-complete-state~1]
+        state.workload_resources.update_for_agent(
+            &agent_load_status.agent_name,
+            agent_load_status.workload_resources.clone(),
+        );
+        // [impl->swdd~server-stores-image-states-in-complete-state~1]
+        state.image_states.update_for_agent(
+            &agent_load_status.agent_name,
+            agent_load_status.image_states.clone(),
+        );
+        state
             .agents
-            .update_resource_availability(agent_load_status);
+            .update_resource_availability(agent_load_status, current_timestamp_millis());
     }
 
     // [impl->swdd~server-cleans-up-state~1]
@@ -288,7 +661,7 @@ impl ServerState {
     }
 
     fn generate_new_state(
-        &mut self,
+        &self,
         updated_state: CompleteState,
         update_mask: Vec<String>,
     ) -> Result<CompleteState, UpdateStateError> {
@@ -324,6 +697,145 @@ impl ServerState {
         })
     }
 
+    // Computes the new desired state, its rendered workloads and the resulting workload changes without modifying the current state.
+    fn prepare_update(
+        &self,
+        new_state: CompleteState,
+        update_mask: Vec<String>,
+    ) -> Result<(State, RenderedWorkloads, AddedDeletedWorkloads), UpdateStateError> {
+        // [impl->swdd~update-desired-state-with-update-mask~1]
+        // [impl->swdd~update-desired-state-empty-update-mask~1]
+        let mut new_templated_state = self.generate_new_state(new_state, update_mask)?;
+
+        // [impl->swdd~server-state-applies-workload-defaults~1]
+        new_templated_state
+            .desired_state
+            .apply_workload_defaults()
+            .map_err(UpdateStateError::ResultInvalid)?;
+
+        // The namespaces are kept in the desired state, everything else works on the resolved one
+        // [impl->swdd~server-state-resolves-namespaces~1]
+        let resolved_desired_state = new_templated_state
+            .desired_state
+            .resolve_namespaces()
+            .map_err(UpdateStateError::ResultInvalid)?;
+
+        verify_config_dependencies(&resolved_desired_state)?;
+        verify_secret_refs(&resolved_desired_state)?;
+        verify_replica_names(&resolved_desired_state)?;
+
+        // The rendered workloads carry the qualified names of the workloads of namespaces, thus
+        // the new state is compared with the resolved current one. The current state was resolved
+        // when it was accepted, otherwise all workloads are rendered again.
+        let current_resolved_desired_state = self
+            .state
+            .desired_state
+            .resolve_namespaces()
+            .unwrap_or_default();
+
+        // [impl->swdd~server-state-triggers-configuration-rendering-of-workloads~1]
+        // [impl->swdd~server-state-renders-only-affected-workloads~1]
+        let workloads_to_render =
+            self.get_workloads_to_render(&current_resolved_desired_state, &resolved_desired_state);
+        let mut new_rendered_workloads = self
+            .config_renderer
+            .render_workloads(&workloads_to_render, &resolved_desired_state.configs)
+            .map_err(|err| UpdateStateError::ResultInvalid(err.to_string()))?;
+
+        for (workload_name, workload) in &resolved_desired_state.workloads {
+            if new_rendered_workloads.contains_key(workload_name) {
+                continue;
+            }
+            // [impl->swdd~config-renderer-creates-workload-replicas~1]
+            for replica_name in workload.replica_names(workload_name) {
+                if let Some(rendered_workload) = self.rendered_workloads.get(&replica_name) {
+                    new_rendered_workloads.insert(replica_name, rendered_workload.clone());
+                }
+            }
+        }
+
+        // [impl->swdd~server-state-schedules-workloads-by-node-selector~1]
+        schedule_workloads(
+            &mut new_rendered_workloads,
+            &self.rendered_workloads,
+            &self.state.agents,
+        );
+
+        // [impl->swdd~server-state-triggers-validation-of-workload-fields~1]
+        self.verify_workload_fields_format(&new_rendered_workloads)?;
+
+        // [impl->swdd~server-state-runs-admission-validators~1]
+        self.admission_validators
+            .validate(&self.rendered_workloads, &new_rendered_workloads)
+            .map_err(UpdateStateError::AdmissionRejected)?;
+
+        // [impl->swdd~server-state-compares-rendered-workloads~1]
+        let added_deleted_workloads =
+            extract_added_and_deleted_workloads(&self.rendered_workloads, &new_rendered_workloads);
+        let added_deleted_workloads = add_workloads_with_changed_config_dependencies(
+            added_deleted_workloads,
+            &get_changed_config_keys(&current_resolved_desired_state, &resolved_desired_state),
+            &self.rendered_workloads,
+            &new_rendered_workloads,
+        );
+        let added_deleted_workloads = add_workloads_restarting_on_updated_workloads(
+            added_deleted_workloads,
+            &self.rendered_workloads,
+            &new_rendered_workloads,
+        );
+
+        if let Some((added_workloads, _)) = &added_deleted_workloads {
+            let start_nodes: Vec<&str> = added_workloads
+                .iter()
+                .filter_map(|w| {
+                    if !w.dependencies.is_empty() {
+                        Some(w.instance_name.workload_name())
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+
+            // [impl->swdd~server-state-rejects-state-with-cyclic-dependencies~1]
+            if let Some(workload_part_of_cycle) =
+                cycle_check::dfs(&resolved_desired_state, Some(start_nodes))
+            {
+                return Err(UpdateStateError::CycleInDependencies(
+                    workload_part_of_cycle,
+                ));
+            }
+        }
+
+        Ok((
+            new_templated_state.desired_state,
+            new_rendered_workloads,
+            added_deleted_workloads,
+        ))
+    }
+
+    // [impl->swdd~server-state-renders-only-affected-workloads~1]
+    fn get_workloads_to_render(
+        &self,
+        current_desired_state: &State,
+        new_desired_state: &State,
+    ) -> HashMap<String, StoredWorkloadSpec> {
+        let changed_config_keys = get_changed_config_keys(current_desired_state, new_desired_state);
+
+        new_desired_state
+            .workloads
+            .iter()
+            .filter(|(workload_name, workload)| {
+                !self.rendered_workloads.contains_key(*workload_name)
+                    || current_desired_state.get_workload(workload_name) != Some(*workload)
+                    || workload
+                        .configs
+                        .values()
+                        .any(|config_key| changed_config_keys.contains(config_key))
+            })
+            .map(|(workload_name, workload)| (workload_name.clone(), workload.clone()))
+            .collect()
+    }
+
     fn set_desired_state(&mut self, new_desired_state: State) {
         self.state.desired_state = new_desired_state;
     }
@@ -341,6 +853,13 @@ impl ServerState {
     }
 }
 
+fn current_timestamp_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
 //////////////////////////////////////////////////////////////////////////////
 //                 ########  #######    #########  #########                //
 //                    ##     ##        ##             ##                    //
@@ -354,18 +873,24 @@ mod tests {
 
     use api::ank_base::{self, Dependencies, Tags};
     use common::{
-        commands::{AgentLoadStatus, CompleteStateRequest},
+        commands::{AgentHello, AgentLoadStatus, CompleteStateRequest},
         objects::{
             generate_test_agent_map, generate_test_configs, generate_test_stored_workload_spec,
+            generate_test_workload_resources,
             generate_test_workload_spec_with_control_interface_access,
-            generate_test_workload_spec_with_param, AgentMap, CompleteState, ConfigItem, CpuUsage,
-            DeletedWorkload, FreeMemory, State, WorkloadSpec, WorkloadStatesMap,
+            generate_test_workload_spec_with_param,
+            generate_test_workload_spec_with_runtime_config, AddCondition, AgentMap, CompleteState,
+            ConfigItem, CpuUsage, DeletedWorkload, DiskUsage, FreeMemory, ImageState,
+            ImageStatesMap, Namespace, NetworkUsage, RestartPolicy, State, StoredWorkloadSpec, Tag,
+            Temperature, ThermalZone, WorkloadDefaults, WorkloadResourcesMap, WorkloadSpec,
+            WorkloadStatesMap,
         },
         test_utils::{self, generate_test_complete_state},
     };
     use mockall::predicate;
 
     use crate::ankaios_server::{
+        admission::{AdmissionConfig, AdmissionRejection, AdmissionValidators},
         config_renderer::{ConfigRenderError, MockConfigRenderer, RenderedWorkloads},
         delete_graph::MockDeleteGraph,
         server_state::UpdateStateError,
@@ -393,6 +918,14 @@ mod tests {
             .collect()
     }
 
+    fn agent_hello(agent_name: &str, labels: HashMap<String, String>) -> AgentHello {
+        AgentHello {
+            agent_name: agent_name.to_string(),
+            labels,
+            ..Default::default()
+        }
+    }
+
     // [utest->swdd~server-provides-interface-get-complete-state~2]
     // [utest->swdd~server-filters-get-complete-state-result~2]
     #[test]
@@ -420,7 +953,10 @@ mod tests {
             ..Default::default()
         };
 
-        let request_complete_state = CompleteStateRequest { field_mask: vec![] };
+        let request_complete_state = CompleteStateRequest {
+            field_mask: vec![],
+            selector: vec![],
+        };
 
         let mut workload_state_db = WorkloadStatesMap::default();
         workload_state_db.process_new_states(server_state.state.workload_states.clone().into());
@@ -429,7 +965,7 @@ mod tests {
             .get_complete_state_by_field_mask(request_complete_state, &workload_state_db)
             .unwrap();
 
-        let expected_complete_state = ank_base::CompleteState::from(server_state.state);
+        let expected_complete_state = ank_base::CompleteState::from(server_state.state.clone());
         assert_eq!(received_complete_state, expected_complete_state);
     }
 
@@ -453,6 +989,7 @@ mod tests {
                 "workloads.invalidMask".to_string(), // invalid not existing workload
                 format!("desiredState.workloads.{}", WORKLOAD_NAME_1),
             ],
+            selector: vec![],
         };
 
         let mut workload_state_map = WorkloadStatesMap::default();
@@ -466,12 +1003,142 @@ mod tests {
             desired_state: Some(server_state.state.desired_state.clone().into()),
             workload_states: None,
             agents: None,
+            workload_resources: None,
+            workload_state_history: None,
+            image_states: None,
         };
         if let Some(expected_desired_state) = &mut expected_complete_state.desired_state {
             expected_desired_state.configs = None;
         }
 
-        assert_eq!(received_complete_state, expected_complete_state);
+        assert_eq!(received_complete_state, expected_complete_state);
+    }
+
+    // [utest->swdd~server-provides-interface-get-complete-state~2]
+    // [utest->swdd~server-filters-get-complete-state-result~2]
+    #[test]
+    fn utest_server_state_get_complete_state_by_field_mask_only_requested_parts() {
+        let w1 = generate_test_workload_spec_with_param(
+            AGENT_A.to_string(),
+            WORKLOAD_NAME_1.to_string(),
+            RUNTIME.to_string(),
+        );
+
+        let server_state = ServerState {
+            state: generate_test_complete_state(vec![w1.clone()]),
+            ..Default::default()
+        };
+
+        let request_complete_state = CompleteStateRequest {
+            field_mask: vec!["agents".to_string(), "workloadStatesX".to_string()],
+            selector: vec![],
+        };
+
+        let mut workload_state_map = WorkloadStatesMap::default();
+        workload_state_map.process_new_states(server_state.state.workload_states.clone().into());
+
+        let received_complete_state = server_state
+            .get_complete_state_by_field_mask(request_complete_state, &workload_state_map)
+            .unwrap();
+
+        let expected_complete_state = ank_base::CompleteState {
+            desired_state: None,
+            workload_states: None,
+            agents: server_state.state.agents.clone().into(),
+            workload_resources: None,
+            workload_state_history: None,
+            image_states: None,
+        };
+
+        assert_eq!(received_complete_state, expected_complete_state);
+    }
+
+    // [utest->swdd~server-provides-image-states~1]
+    #[test]
+    fn utest_server_state_get_complete_state_by_field_mask_image_states() {
+        let mut complete_state = generate_test_complete_state(vec![]);
+        complete_state.image_states.update_for_agent(
+            AGENT_A,
+            HashMap::from([("image_1:1.0".to_string(), ImageState::pulled())]),
+        );
+
+        let server_state = ServerState {
+            state: complete_state,
+            ..Default::default()
+        };
+
+        let request_complete_state = CompleteStateRequest {
+            field_mask: vec!["imageStates".to_string()],
+            selector: vec![],
+        };
+
+        let received_complete_state = server_state
+            .get_complete_state_by_field_mask(request_complete_state, &WorkloadStatesMap::default())
+            .unwrap();
+
+        let expected_complete_state = ank_base::CompleteState {
+            image_states: server_state.state.image_states.clone().into(),
+            ..Default::default()
+        };
+
+        assert_eq!(received_complete_state, expected_complete_state);
+    }
+
+    // [utest->swdd~server-selects-workloads-by-tags~1]
+    #[test]
+    fn utest_server_state_get_complete_state_by_selector() {
+        let team_adas = Tag {
+            key: "team".into(),
+            value: "adas".into(),
+        };
+        let mut w1 = generate_test_workload_spec_with_param(
+            AGENT_A.to_string(),
+            WORKLOAD_NAME_1.to_string(),
+            RUNTIME.to_string(),
+        );
+        w1.tags.push(team_adas.clone());
+        let w2 = generate_test_workload_spec_with_param(
+            AGENT_B.to_string(),
+            WORKLOAD_NAME_2.to_string(),
+            RUNTIME.to_string(),
+        );
+
+        let complete_state = generate_test_complete_state(vec![w1, w2]);
+        let server_state = ServerState {
+            rendered_workloads: generate_rendered_workloads_from_state(
+                &complete_state.desired_state,
+            ),
+            state: complete_state,
+            ..Default::default()
+        };
+
+        let request_complete_state = CompleteStateRequest {
+            field_mask: vec![],
+            selector: vec![team_adas],
+        };
+
+        let mut workload_state_map = WorkloadStatesMap::default();
+        workload_state_map.process_new_states(server_state.state.workload_states.clone().into());
+
+        let received_complete_state = server_state
+            .get_complete_state_by_field_mask(request_complete_state, &workload_state_map)
+            .unwrap();
+
+        let selected_workloads: Vec<String> = received_complete_state
+            .desired_state
+            .and_then(|desired_state| desired_state.workloads)
+            .unwrap()
+            .workloads
+            .into_keys()
+            .collect();
+        assert_eq!(selected_workloads, vec![WORKLOAD_NAME_1.to_string()]);
+
+        let workload_states = received_complete_state.workload_states.unwrap();
+        assert_eq!(workload_states.agent_state_map.len(), 1);
+        assert!(workload_states.agent_state_map[AGENT_A]
+            .wl_name_state_map
+            .contains_key(WORKLOAD_NAME_1));
+        assert!(received_complete_state.agents.is_some());
     }
 
     // [utest->swdd~server-provides-interface-get-complete-state~2]
@@ -506,6 +1173,7 @@ mod tests {
                 format!("desiredState.workloads.{}", WORKLOAD_NAME_1),
                 format!("desiredState.workloads.{}.agent", WORKLOAD_NAME_3),
             ],
+            selector: vec![],
         };
 
         let mut workload_state_map = WorkloadStatesMap::default();
@@ -527,6 +1195,25 @@ mod tests {
                     runtime_config: None,
                     control_interface_access: None,
                     configs: None,
+                    hostname: None,
+                    extra_hosts: None,
+                    ports: None,
+                    resource_thresholds: None,
+                    resources: None,
+                    health_check: None,
+                    restart_backoff: None,
+                    priority: None,
+                    volumes: None,
+                    registry_auth: None,
+                    replicas: None,
+                    networks: None,
+                    env: None,
+                    on_agent_disconnect: None,
+                    restart_on: None,
+                    lifecycle_hooks: None,
+                    node_selector: None,
+                    state_debounce: None,
+                    security_context: None,
                 },
             ),
             (
@@ -548,6 +1235,25 @@ mod tests {
                     runtime_config: Some(w1.runtime_config.clone()),
                     control_interface_access: w1.control_interface_access.into(),
                     configs: Some(Default::default()),
+                    hostname: None,
+                    extra_hosts: Some(Default::default()),
+                    ports: Some(Default::default()),
+                    resource_thresholds: None,
+                    resources: None,
+                    health_check: None,
+                    restart_backoff: None,
+                    priority: None,
+                    volumes: Some(Default::default()),
+                    registry_auth: None,
+                    replicas: None,
+                    networks: Some(Default::default()),
+                    env: Some(Default::default()),
+                    on_agent_disconnect: None,
+                    restart_on: Some(Default::default()),
+                    lifecycle_hooks: None,
+                    node_selector: Some(Default::default()),
+                    state_debounce: None,
+                    security_context: None,
                 },
             ),
         ];
@@ -607,6 +1313,137 @@ mod tests {
         assert_eq!(workloads.len(), 0);
     }
 
+    // [utest->swdd~server-forwards-exec-request-to-agent~1]
+    #[test]
+    fn utest_server_state_get_agent_of_workload() {
+        let w1 = generate_test_workload_spec_with_param(
+            AGENT_B.to_string(),
+            WORKLOAD_NAME_1.to_string(),
+            RUNTIME.to_string(),
+        );
+        let complete_state = generate_test_complete_state(vec![w1]);
+
+        let server_state = ServerState {
+            rendered_workloads: generate_rendered_workloads_from_state(
+                &complete_state.desired_state,
+            ),
+            state: complete_state,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            server_state.get_agent_of_workload(WORKLOAD_NAME_1),
+            Some(AGENT_B.to_string())
+        );
+        assert_eq!(server_state.get_agent_of_workload("unknown_workload"), None);
+    }
+
+    // [utest->swdd~server-sends-images-to-pull-to-agents~1]
+    #[test]
+    fn utest_server_state_get_images_for_agent() {
+        let mut complete_state = generate_test_complete_state(vec![]);
+        complete_state.desired_state.images =
+            HashMap::from([(AGENT_A.to_string(), vec!["image_1:1.0".to_string()])]);
+
+        let server_state = ServerState {
+            state: complete_state,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            server_state.get_images_for_agent(AGENT_A),
+            vec!["image_1:1.0".to_string()]
+        );
+        assert!(server_state.get_images_for_agent(AGENT_B).is_empty());
+    }
+
+    // [utest->swdd~server-forwards-restart-workload-request-to-agent~1]
+    #[test]
+    fn utest_server_state_get_replica_names_of_workload() {
+        let w1 = generate_test_workload_spec_with_param(
+            AGENT_B.to_string(),
+            WORKLOAD_NAME_1.to_string(),
+            RUNTIME.to_string(),
+        );
+        let mut complete_state = generate_test_complete_state(vec![w1]);
+        complete_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .replicas = Some(2);
+
+        let server_state = ServerState {
+            state: complete_state,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            server_state.get_replica_names_of_workload(WORKLOAD_NAME_1),
+            Some(vec![
+                WORKLOAD_NAME_1.to_string(),
+                format!("{}-1", WORKLOAD_NAME_1)
+            ])
+        );
+        assert_eq!(
+            server_state.get_replica_names_of_workload("unknown_workload"),
+            None
+        );
+    }
+
+    // [utest->swdd~server-replicates-desired-state~2]
+    #[test]
+    fn utest_server_state_get_desired_state() {
+        let w1 = generate_test_workload_spec_with_param(
+            AGENT_A.to_string(),
+            WORKLOAD_NAME_1.to_string(),
+            RUNTIME.to_string(),
+        );
+        let complete_state = generate_test_complete_state(vec![w1]);
+
+        let server_state = ServerState {
+            state: complete_state.clone(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            server_state.get_desired_state(),
+            ank_base::State::from(complete_state.desired_state)
+        );
+    }
+
+    // [utest->swdd~server-provides-dependency-graph~1]
+    #[test]
+    fn utest_server_state_get_dependency_graph_of_rendered_workloads() {
+        let w1 = generate_test_workload_spec_with_param(
+            AGENT_A.to_string(),
+            WORKLOAD_NAME_1.to_string(),
+            RUNTIME.to_string(),
+        );
+
+        let old_complete_state = generate_test_complete_state(vec![w1.clone()]);
+
+        let server_state = ServerState {
+            rendered_workloads: generate_rendered_workloads_from_state(
+                &old_complete_state.desired_state,
+            ),
+            state: old_complete_state,
+            ..Default::default()
+        };
+
+        let dependency_graph = server_state.get_dependency_graph(&WorkloadStatesMap::default());
+
+        assert_eq!(
+            dependency_graph.nodes,
+            vec![ank_base::DependencyGraphNode {
+                workload_name: WORKLOAD_NAME_1.to_string(),
+                agent: AGENT_A.to_string(),
+                execution_state: None,
+            }]
+        );
+        assert_eq!(dependency_graph.edges.len(), w1.dependencies.len());
+    }
+
     // [utest->swdd~server-state-rejects-state-with-cyclic-dependencies~1]
     #[test]
     fn utest_server_state_update_state_reject_state_with_cyclic_dependencies() {
@@ -663,6 +1500,7 @@ mod tests {
             rendered_workloads: generate_rendered_workloads_from_state(&old_state.desired_state),
             delete_graph: delete_graph_mock,
             config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
         };
 
         let result = server_state.update(rejected_new_state, vec![]);
@@ -709,6 +1547,7 @@ mod tests {
             rendered_workloads: generate_rendered_workloads_from_state(&old_state.desired_state),
             delete_graph: delete_graph_mock,
             config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
         };
 
         server_state
@@ -764,6 +1603,7 @@ mod tests {
             rendered_workloads: generate_rendered_workloads_from_state(&old_state.desired_state),
             delete_graph: delete_graph_mock,
             config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
         };
         server_state.update(update_state, update_mask).unwrap();
 
@@ -820,6 +1660,7 @@ mod tests {
             rendered_workloads: generate_rendered_workloads_from_state(&old_state.desired_state),
             delete_graph: delete_graph_mock,
             config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
         };
         server_state.update(update_state, update_mask).unwrap();
 
@@ -829,6 +1670,7 @@ mod tests {
     // [utest->swdd~update-desired-state-with-update-mask~1]
     // [utest->swdd~server-state-triggers-configuration-rendering-of-workloads~1]
     // [utest->swdd~server-state-updates-state-on-unmodified-workloads~1]
+    // [utest->swdd~server-state-renders-only-affected-workloads~1]
     #[test]
     fn utest_server_state_update_state_update_configs_not_affecting_workloads() {
         let old_state = generate_test_old_state();
@@ -844,40 +1686,304 @@ mod tests {
             .expect_apply_delete_conditions_to()
             .never();
 
+        // no workload references the changed configs, so nothing needs to be rendered
         let mut mock_config_renderer = MockConfigRenderer::new();
-        let cloned_state_with_updated_config = state_with_updated_config.desired_state.clone();
         mock_config_renderer
             .expect_render_workloads()
             .once()
             .with(
-                predicate::eq(state_with_updated_config.desired_state.workloads.clone()),
+                predicate::eq(HashMap::new()),
                 predicate::eq(state_with_updated_config.desired_state.configs.clone()),
             )
-            .returning(move |_, _| {
-                Ok(generate_rendered_workloads_from_state(
-                    &cloned_state_with_updated_config,
-                ))
-            });
+            .returning(|_, _| Ok(RenderedWorkloads::new()));
 
         let mut server_state = ServerState {
             state: old_state.clone(),
             rendered_workloads: generate_rendered_workloads_from_state(&old_state.desired_state),
             delete_graph: delete_graph_mock,
             config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
         };
 
         let expected = state_with_updated_config.clone();
 
-        let added_deleted_workloads = server_state
-            .update(state_with_updated_config, update_mask)
-            .unwrap();
+        let added_deleted_workloads = server_state
+            .update(state_with_updated_config, update_mask)
+            .unwrap();
+
+        assert!(added_deleted_workloads.is_none());
+        assert_eq!(expected, server_state.state);
+    }
+
+    // [utest->swdd~server-state-restarts-workloads-on-changed-config-dependencies~1]
+    #[test]
+    fn utest_server_state_update_state_restarts_workload_with_changed_config_dependency() {
+        let mut old_state = generate_test_old_state();
+        old_state.desired_state.configs = generate_test_configs();
+        old_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .dependencies
+            .insert("config_1".to_string(), AddCondition::AddCondConfigChanged);
+
+        let mut state_with_updated_config = old_state.clone();
+        state_with_updated_config.desired_state.configs = HashMap::from([(
+            "config_1".to_string(),
+            ConfigItem::String("changed value".to_string()),
+        )]);
+
+        let update_mask = vec!["desiredState".to_string()];
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().once().return_const(());
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .once()
+            .return_const(());
+
+        let mut mock_config_renderer = MockConfigRenderer::new();
+        mock_config_renderer
+            .expect_render_workloads()
+            .once()
+            .returning(|_, _| Ok(RenderedWorkloads::new()));
+
+        let old_rendered_workloads =
+            generate_rendered_workloads_from_state(&old_state.desired_state);
+        let restarted_workload = old_rendered_workloads.get(WORKLOAD_NAME_1).unwrap().clone();
+
+        let mut server_state = ServerState {
+            state: old_state.clone(),
+            rendered_workloads: old_rendered_workloads,
+            delete_graph: delete_graph_mock,
+            config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
+        };
+
+        let added_deleted_workloads = server_state
+            .update(state_with_updated_config, update_mask)
+            .unwrap();
+
+        let (added_workloads, deleted_workloads) = added_deleted_workloads.unwrap();
+        assert_eq!(added_workloads, vec![restarted_workload.clone()]);
+        assert_eq!(
+            deleted_workloads,
+            vec![DeletedWorkload {
+                instance_name: restarted_workload.instance_name,
+                ..Default::default()
+            }]
+        );
+    }
+
+    // [utest->swdd~server-state-restarts-workloads-on-updated-restart-on-workloads~1]
+    #[test]
+    fn utest_server_state_restarts_workloads_restarting_on_updated_workload() {
+        let generate_workload = |workload_name: &str, runtime_config: &str, restart_on: &[&str]| {
+            let mut workload = generate_test_workload_spec_with_runtime_config(
+                AGENT_A.to_string(),
+                workload_name.to_string(),
+                RUNTIME.to_string(),
+                runtime_config.to_string(),
+            );
+            workload.restart_on = restart_on.iter().map(|name| name.to_string()).collect();
+            (workload_name.to_string(), workload)
+        };
+        let current_workloads = RenderedWorkloads::from([
+            generate_workload(WORKLOAD_NAME_1, "image: backend:1", &[]),
+            generate_workload(WORKLOAD_NAME_2, "image: proxy", &[WORKLOAD_NAME_1]),
+            generate_workload(WORKLOAD_NAME_3, "image: cache", &[WORKLOAD_NAME_2]),
+            generate_workload(WORKLOAD_NAME_4, "image: other", &[]),
+        ]);
+        let mut new_workloads = current_workloads.clone();
+        new_workloads.extend([generate_workload(WORKLOAD_NAME_1, "image: backend:2", &[])]);
+
+        let added_deleted_workloads = super::add_workloads_restarting_on_updated_workloads(
+            super::extract_added_and_deleted_workloads(&current_workloads, &new_workloads),
+            &current_workloads,
+            &new_workloads,
+        );
+
+        let (added_workloads, deleted_workloads) = added_deleted_workloads.unwrap();
+        let mut added_workload_names: Vec<&str> = added_workloads
+            .iter()
+            .map(|workload| workload.instance_name.workload_name())
+            .collect();
+        added_workload_names.sort();
+        assert_eq!(
+            added_workload_names,
+            vec![WORKLOAD_NAME_1, WORKLOAD_NAME_2, WORKLOAD_NAME_3]
+        );
+        assert!(deleted_workloads.contains(&DeletedWorkload {
+            instance_name: current_workloads[WORKLOAD_NAME_3].instance_name.clone(),
+            ..Default::default()
+        }));
+        assert_eq!(deleted_workloads.len(), 3);
+    }
+
+    // [utest->swdd~server-state-rejects-config-dependencies-to-not-existing-configs~1]
+    #[test]
+    fn utest_server_state_update_state_rejects_dependency_to_not_existing_config() {
+        let old_state = generate_test_old_state();
+        let mut updated_state = old_state.clone();
+        updated_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .dependencies
+            .insert(
+                "not_existing_config".to_string(),
+                AddCondition::AddCondConfigChanged,
+            );
+
+        let mut server_state = ServerState {
+            state: old_state.clone(),
+            rendered_workloads: generate_rendered_workloads_from_state(&old_state.desired_state),
+            delete_graph: MockDeleteGraph::new(),
+            config_renderer: MockConfigRenderer::new(),
+            admission_validators: Default::default(),
+        };
+
+        let result = server_state.update(updated_state, vec![]);
+
+        assert!(matches!(result, Err(UpdateStateError::ResultInvalid(_))));
+        assert_eq!(old_state, server_state.state);
+    }
+
+    // [utest->swdd~server-state-rejects-invalid-secret-refs~1]
+    #[test]
+    fn utest_server_state_update_state_rejects_invalid_secret_refs() {
+        let old_state = generate_test_old_state();
+        let mut updated_state = old_state.clone();
+        updated_state.desired_state.configs.insert(
+            "secret".to_string(),
+            ConfigItem::ConfigObject(HashMap::from([(
+                "secretRef".to_string(),
+                ConfigItem::String("DB_PASSWORD".to_string()),
+            )])),
+        );
+
+        let mut mock_config_renderer = MockConfigRenderer::new();
+        mock_config_renderer.expect_render_workloads().never();
+
+        let mut server_state = ServerState {
+            state: old_state.clone(),
+            rendered_workloads: generate_rendered_workloads_from_state(&old_state.desired_state),
+            delete_graph: MockDeleteGraph::new(),
+            config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
+        };
+
+        let result = server_state.update(updated_state, vec![]);
+
+        assert!(matches!(result, Err(UpdateStateError::ResultInvalid(_))));
+        assert_eq!(old_state, server_state.state);
+    }
+
+    // [utest->swdd~server-state-rejects-conflicting-replica-names~1]
+    #[test]
+    fn utest_server_state_update_state_rejects_conflicting_replica_names() {
+        let old_state = generate_test_old_state();
+        let mut updated_state = old_state.clone();
+        let workload = updated_state.desired_state.workloads[WORKLOAD_NAME_1].clone();
+        updated_state.desired_state.workloads.insert(
+            "nginx".to_string(),
+            StoredWorkloadSpec {
+                replicas: Some(2),
+                ..workload.clone()
+            },
+        );
+        updated_state
+            .desired_state
+            .workloads
+            .insert("nginx-1".to_string(), workload);
+
+        let mut mock_config_renderer = MockConfigRenderer::new();
+        mock_config_renderer.expect_render_workloads().never();
+
+        let mut server_state = ServerState {
+            state: old_state.clone(),
+            rendered_workloads: generate_rendered_workloads_from_state(&old_state.desired_state),
+            delete_graph: MockDeleteGraph::new(),
+            config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
+        };
+
+        let result = server_state.update(updated_state, vec![]);
+
+        assert!(matches!(result, Err(UpdateStateError::ResultInvalid(_))));
+        assert_eq!(old_state, server_state.state);
+    }
+
+    // [utest->swdd~server-state-restricts-env-template-helper~1]
+    #[test]
+    fn utest_server_state_set_allowed_env_prefixes() {
+        let mut mock_config_renderer = MockConfigRenderer::new();
+        mock_config_renderer
+            .expect_set_allowed_env_prefixes()
+            .with(mockall::predicate::eq(vec!["APP_".to_string()]))
+            .once()
+            .return_const(());
+
+        let mut server_state = ServerState {
+            config_renderer: mock_config_renderer,
+            ..Default::default()
+        };
+
+        server_state.set_allowed_env_prefixes(vec!["APP_".to_string()]);
+    }
+
+    // [utest->swdd~server-state-runs-admission-validators~1]
+    #[test]
+    fn utest_server_state_update_state_rejected_by_admission_validators() {
+        let old_state = generate_test_old_state();
+        let mut updated_state = old_state.clone();
+        updated_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .runtime = "forbidden_runtime".to_string();
+
+        let new_rendered_workloads =
+            generate_rendered_workloads_from_state(&updated_state.desired_state);
+        let mut mock_config_renderer = MockConfigRenderer::new();
+        mock_config_renderer
+            .expect_render_workloads()
+            .once()
+            .return_once(|_, _| Ok(new_rendered_workloads));
+
+        let mut server_state = ServerState {
+            state: old_state.clone(),
+            rendered_workloads: generate_rendered_workloads_from_state(&old_state.desired_state),
+            delete_graph: MockDeleteGraph::new(),
+            config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
+        };
+        server_state.set_admission_validators(AdmissionValidators::new(&AdmissionConfig {
+            forbidden_runtimes: vec!["forbidden_runtime".to_string()],
+            ..Default::default()
+        }));
+
+        let result = server_state.update(updated_state, vec![]);
 
-        assert!(added_deleted_workloads.is_none());
-        assert_eq!(expected, server_state.state);
+        assert_eq!(
+            result,
+            Err(UpdateStateError::AdmissionRejected(vec![
+                AdmissionRejection {
+                    validator: "forbidden_runtimes".to_string(),
+                    reason: "workload 'workload_1' uses the forbidden runtime 'forbidden_runtime'"
+                        .to_string(),
+                }
+            ]))
+        );
+        assert_eq!(old_state, server_state.state);
     }
 
     // [utest->swdd~update-desired-state-with-update-mask~1]
     // [utest->swdd~server-state-triggers-configuration-rendering-of-workloads~1]
+    // [utest->swdd~server-state-renders-only-affected-workloads~1]
     #[test]
     fn utest_server_state_update_state_update_workload_with_existing_configs() {
         let mut old_state = generate_test_old_state();
@@ -913,11 +2019,15 @@ mod tests {
 
         let mut mock_config_renderer = MockConfigRenderer::new();
         let state_to_render = updated_state.desired_state.clone();
+        let workloads_to_render = HashMap::from([(
+            WORKLOAD_NAME_1.to_owned(),
+            updated_state.desired_state.workloads[WORKLOAD_NAME_1].clone(),
+        )]); // only the changed workload
         mock_config_renderer
             .expect_render_workloads()
             .once()
             .with(
-                predicate::eq(updated_state.desired_state.workloads.clone()),
+                predicate::eq(workloads_to_render),
                 predicate::eq(old_state.desired_state.configs.clone()), // existing configs due to update mask
             )
             .returning(move |_, _| Ok(generate_rendered_workloads_from_state(&state_to_render)));
@@ -927,6 +2037,7 @@ mod tests {
             rendered_workloads: generate_rendered_workloads_from_state(&old_state.desired_state),
             delete_graph: delete_graph_mock,
             config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
         };
 
         let mut expected = updated_state.clone();
@@ -950,10 +2061,17 @@ mod tests {
     // [utest->swdd~update-desired-state-with-update-mask~1]
     // [utest->swdd~server-state-triggers-configuration-rendering-of-workloads~1]
     // [utest->swdd~server-state-compares-rendered-workloads~1]
+    // [utest->swdd~server-state-renders-only-affected-workloads~1]
     #[test]
     fn utest_server_state_update_state_update_workload_on_changed_configs() {
         let mut old_state = generate_test_old_state();
         old_state.desired_state.configs = generate_test_configs();
+        old_state
+            .desired_state
+            .workloads
+            .get_mut(WORKLOAD_NAME_1)
+            .unwrap()
+            .configs = HashMap::from([("ref1".to_owned(), "config_1".to_owned())]);
 
         let mut updated_state = old_state.clone();
         updated_state.desired_state.configs = HashMap::from([(
@@ -983,7 +2101,10 @@ mod tests {
             .expect_render_workloads()
             .once()
             .with(
-                predicate::eq(updated_state.desired_state.workloads.clone()),
+                predicate::eq(HashMap::from([(
+                    WORKLOAD_NAME_1.to_owned(),
+                    updated_state.desired_state.workloads[WORKLOAD_NAME_1].clone(),
+                )])), // only the workload referencing the changed config
                 predicate::eq(updated_state.desired_state.configs.clone()),
             )
             .returning(move |_, _| Ok(generate_rendered_workloads_from_state(&state_to_render)));
@@ -993,6 +2114,7 @@ mod tests {
             rendered_workloads: generate_rendered_workloads_from_state(&old_state.desired_state),
             delete_graph: delete_graph_mock,
             config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
         };
 
         let expected = updated_state.clone();
@@ -1057,6 +2179,7 @@ mod tests {
             rendered_workloads: generate_rendered_workloads_from_state(&old_state.desired_state),
             delete_graph: delete_graph_mock,
             config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
         };
 
         let result = server_state.update(updated_state, update_mask);
@@ -1104,6 +2227,7 @@ mod tests {
             rendered_workloads: generate_rendered_workloads_from_state(&old_state.desired_state),
             delete_graph: delete_graph_mock,
             config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
         };
         server_state.update(update_state, update_mask).unwrap();
 
@@ -1142,6 +2266,7 @@ mod tests {
             rendered_workloads: generate_rendered_workloads_from_state(&old_state.desired_state),
             delete_graph: delete_graph_mock,
             config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
         };
         server_state.update(update_state, update_mask).unwrap();
 
@@ -1169,6 +2294,7 @@ mod tests {
             rendered_workloads: generate_rendered_workloads_from_state(&old_state.desired_state),
             delete_graph: delete_graph_mock,
             config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
         };
         let result = server_state.update(update_state, update_mask);
 
@@ -1324,6 +2450,7 @@ mod tests {
                 &current_complete_state.desired_state,
             ),
             config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
         };
 
         let added_deleted_workloads = server_state.update(update_state, update_mask).unwrap();
@@ -1345,6 +2472,7 @@ mod tests {
             .map(|(name, workload_spec)| DeletedWorkload {
                 instance_name: (name.to_owned(), workload_spec).into(),
                 dependencies: HashMap::new(),
+                grace_period_secs: None,
             })
             .collect();
         expected_deleted_workloads.sort_by(|left, right| {
@@ -1405,6 +2533,7 @@ mod tests {
             ),
             delete_graph: delete_graph_mock,
             config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
         };
 
         let added_deleted_workloads = server_state
@@ -1421,13 +2550,14 @@ mod tests {
             vec![DeletedWorkload {
                 instance_name: (WORKLOAD_NAME_1.to_string(), workload_to_update).into(),
                 dependencies: HashMap::new(),
+                grace_period_secs: None,
             }]
         );
 
         assert_eq!(server_state.state, new_complete_state);
     }
 
-    // [utest->swdd~server-state-stores-delete-condition~1]
+    // [utest->swdd~server-state-stores-delete-condition~2]
     // [utest->swdd~server-state-adds-delete-conditions-to-deleted-workload~1]
     // [utest->swdd~server-state-triggers-configuration-rendering-of-workloads~1]
     #[test]
@@ -1479,6 +2609,7 @@ mod tests {
             .with(mockall::predicate::eq(vec![DeletedWorkload {
                 instance_name: workload.instance_name,
                 dependencies: HashMap::new(),
+                grace_period_secs: None,
             }]))
             .once()
             .return_const(());
@@ -1501,6 +2632,7 @@ mod tests {
             state: current_complete_state,
             delete_graph: delete_graph_mock,
             config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
         };
 
         let added_deleted_workloads = server_state
@@ -1524,10 +2656,27 @@ mod tests {
         };
         let cpu_usage = CpuUsage { cpu_usage: 42 };
         let free_memory = FreeMemory { free_memory: 42 };
+        let disk_usage = DiskUsage {
+            total_space: 100,
+            available_space: 42,
+        };
+        let temperature = Temperature {
+            thermal_zones: vec![ThermalZone {
+                zone_type: "cpu-thermal".to_string(),
+                temperature: 42000,
+            }],
+        };
+        let workload_resources = generate_test_workload_resources(AGENT_A, WORKLOAD_NAME_1);
+        let image_states = HashMap::from([("image_1:1.0".to_string(), ImageState::pulled())]);
         server_state.update_agent_resource_availability(AgentLoadStatus {
             agent_name: AGENT_A.to_string(),
             cpu_usage: cpu_usage.clone(),
             free_memory: free_memory.clone(),
+            workload_resources: vec![workload_resources.clone()],
+            disk_usage: disk_usage.clone(),
+            network_usage: Default::default(),
+            temperature: temperature.clone(),
+            image_states: image_states.clone(),
         });
 
         let stored_state = server_state
@@ -1539,6 +2688,30 @@ mod tests {
 
         assert_eq!(stored_state.cpu_usage, Some(cpu_usage));
         assert_eq!(stored_state.free_memory, Some(free_memory));
+        assert_eq!(stored_state.disk_usage, Some(disk_usage));
+        assert_eq!(stored_state.temperature, Some(temperature));
+        assert_eq!(stored_state.load_history.len(), 1);
+        assert_eq!(stored_state.load_history[0].cpu_usage, 42);
+        assert_eq!(stored_state.load_history[0].free_memory, 42);
+
+        // [utest->swdd~server-stores-workload-resources-in-complete-state~1]
+        assert_eq!(
+            server_state
+                .state
+                .workload_resources
+                .get_resource_usage_for_agent(AGENT_A)
+                .and_then(|resources| resources.get(WORKLOAD_NAME_1)),
+            Some(&workload_resources.resource_usage)
+        );
+
+        // [utest->swdd~server-stores-image-states-in-complete-state~1]
+        assert_eq!(
+            server_state
+                .state
+                .image_states
+                .get_image_states_for_agent(AGENT_A),
+            Some(&image_states)
+        );
     }
 
     // [utest->swdd~server-removes-obsolete-delete-graph-entires~1]
@@ -1560,28 +2733,404 @@ mod tests {
         server_state.cleanup_state(&workload_states);
     }
 
+    // [utest->swdd~server-state-applies-workload-defaults~1]
+    #[test]
+    fn utest_server_state_update_state_applies_workload_defaults() {
+        let mut workload = generate_test_stored_workload_spec(AGENT_A, "");
+        workload.restart_policy = None;
+
+        let new_state = CompleteState {
+            desired_state: State {
+                workloads: HashMap::from([(WORKLOAD_NAME_1.to_owned(), workload)]),
+                defaults: WorkloadDefaults {
+                    restart_policy: Some(RestartPolicy::OnFailure),
+                    runtime: Some(RUNTIME.to_owned()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().once().return_const(());
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .once()
+            .return_const(());
+
+        let mut mock_config_renderer = MockConfigRenderer::new();
+        mock_config_renderer
+            .expect_render_workloads()
+            .once()
+            .returning(|workloads, _| {
+                Ok(workloads
+                    .iter()
+                    .map(|(name, spec)| {
+                        (
+                            name.to_owned(),
+                            WorkloadSpec::from((name.to_owned(), spec.to_owned())),
+                        )
+                    })
+                    .collect())
+            });
+
+        let mut server_state = ServerState {
+            delete_graph: delete_graph_mock,
+            config_renderer: mock_config_renderer,
+            ..Default::default()
+        };
+
+        let (added_workloads, _) = server_state.update(new_state, vec![]).unwrap().unwrap();
+
+        let stored_workload = server_state
+            .state
+            .desired_state
+            .workloads
+            .get(WORKLOAD_NAME_1)
+            .unwrap();
+        assert_eq!(
+            stored_workload.restart_policy,
+            Some(RestartPolicy::OnFailure)
+        );
+        assert_eq!(stored_workload.runtime, RUNTIME);
+        assert_eq!(added_workloads.len(), 1);
+        assert_eq!(added_workloads[0].restart_policy, RestartPolicy::OnFailure);
+        assert_eq!(added_workloads[0].runtime, RUNTIME);
+    }
+
+    // [utest->swdd~server-state-applies-workload-defaults~1]
+    #[test]
+    fn utest_server_state_update_state_rejects_workload_without_runtime() {
+        let new_state = CompleteState {
+            desired_state: State {
+                workloads: HashMap::from([(
+                    WORKLOAD_NAME_1.to_owned(),
+                    generate_test_stored_workload_spec(AGENT_A, ""),
+                )]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut server_state = ServerState::default();
+
+        assert!(matches!(
+            server_state.update(new_state, vec![]),
+            Err(UpdateStateError::ResultInvalid(_))
+        ));
+        assert_eq!(server_state.state, CompleteState::default());
+    }
+
+    // [utest->swdd~server-state-resolves-namespaces~1]
+    #[test]
+    fn utest_server_state_update_state_resolves_namespaces() {
+        const NAMESPACED_WORKLOAD_NAME: &str = "team-a__workload_1";
+        let new_state = CompleteState {
+            desired_state: State {
+                namespaces: HashMap::from([(
+                    "team-a".to_owned(),
+                    Namespace {
+                        workloads: HashMap::from([(
+                            WORKLOAD_NAME_1.to_owned(),
+                            generate_test_stored_workload_spec(AGENT_A, RUNTIME),
+                        )]),
+                    },
+                )]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().once().return_const(());
+        delete_graph_mock
+            .expect_apply_delete_conditions_to()
+            .once()
+            .return_const(());
+
+        let mut mock_config_renderer = MockConfigRenderer::new();
+        mock_config_renderer
+            .expect_render_workloads()
+            .once()
+            .returning(|workloads, _| {
+                Ok(workloads
+                    .iter()
+                    .map(|(name, spec)| {
+                        (
+                            name.to_owned(),
+                            WorkloadSpec::from((name.to_owned(), spec.to_owned())),
+                        )
+                    })
+                    .collect())
+            });
+
+        let mut server_state = ServerState {
+            delete_graph: delete_graph_mock,
+            config_renderer: mock_config_renderer,
+            ..Default::default()
+        };
+
+        let (added_workloads, _) = server_state
+            .update(new_state.clone(), vec![])
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(server_state.state.desired_state, new_state.desired_state);
+        assert_eq!(added_workloads.len(), 1);
+        assert_eq!(
+            added_workloads[0].instance_name.workload_name(),
+            NAMESPACED_WORKLOAD_NAME
+        );
+        assert!(added_workloads[0]
+            .dependencies
+            .contains_key("team-a__workload_A"));
+        assert_eq!(
+            server_state.get_agent_of_workload(NAMESPACED_WORKLOAD_NAME),
+            Some(AGENT_A.to_owned())
+        );
+        assert_eq!(
+            server_state.get_replica_names_of_workload(NAMESPACED_WORKLOAD_NAME),
+            Some(vec![NAMESPACED_WORKLOAD_NAME.to_owned()])
+        );
+    }
+
+    // [utest->swdd~server-state-renders-only-affected-workloads~1]
+    #[test]
+    fn utest_server_state_update_state_does_not_render_unchanged_workloads_of_namespaces() {
+        let old_state = CompleteState {
+            desired_state: State {
+                namespaces: HashMap::from([(
+                    "team-a".to_owned(),
+                    Namespace {
+                        workloads: HashMap::from([(
+                            WORKLOAD_NAME_1.to_owned(),
+                            generate_test_stored_workload_spec(AGENT_A, RUNTIME),
+                        )]),
+                    },
+                )]),
+                configs: generate_test_configs(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut state_with_updated_config = old_state.clone();
+        state_with_updated_config.desired_state.configs.insert(
+            "config_3".to_owned(),
+            ConfigItem::String("value_3".to_owned()),
+        );
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().never();
+
+        // the workload of the namespace is unchanged and does not reference the added config
+        let mut mock_config_renderer = MockConfigRenderer::new();
+        mock_config_renderer
+            .expect_render_workloads()
+            .once()
+            .with(predicate::eq(HashMap::new()), predicate::always())
+            .returning(|_, _| Ok(RenderedWorkloads::new()));
+
+        let mut server_state = ServerState {
+            state: old_state.clone(),
+            rendered_workloads: generate_rendered_workloads_from_state(
+                &old_state.desired_state.resolve_namespaces().unwrap(),
+            ),
+            delete_graph: delete_graph_mock,
+            config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
+        };
+
+        let added_deleted_workloads = server_state
+            .update(
+                state_with_updated_config.clone(),
+                vec!["desiredState.configs".to_owned()],
+            )
+            .unwrap();
+
+        assert!(added_deleted_workloads.is_none());
+        assert_eq!(server_state.state, state_with_updated_config);
+    }
+
+    // [utest->swdd~server-state-resolves-namespaces~1]
+    #[test]
+    fn utest_server_state_update_state_rejects_invalid_namespace() {
+        let new_state = CompleteState {
+            desired_state: State {
+                namespaces: HashMap::from([(
+                    "team_a".to_owned(),
+                    Namespace {
+                        workloads: HashMap::from([(
+                            WORKLOAD_NAME_1.to_owned(),
+                            generate_test_stored_workload_spec(AGENT_A, RUNTIME),
+                        )]),
+                    },
+                )]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut server_state = ServerState::default();
+
+        assert!(matches!(
+            server_state.update(new_state, vec![]),
+            Err(UpdateStateError::ResultInvalid(_))
+        ));
+        assert_eq!(server_state.state, CompleteState::default());
+    }
+
     // [utest->swdd~server-state-stores-agent-in-complete-state~1]
     #[test]
     fn utest_add_agent() {
         let mut server_state = ServerState::default();
-        server_state.add_agent(AGENT_A.to_string());
-        server_state.update_agent_resource_availability(AgentLoadStatus {
+        server_state.add_agent(AgentHello {
             agent_name: AGENT_A.to_string(),
-            cpu_usage: CpuUsage { cpu_usage: 42 },
-            free_memory: FreeMemory { free_memory: 42 },
+            labels: HashMap::from([("zone".to_string(), "cockpit".to_string())]),
+            version: "0.5.0".to_string(),
+            runtimes: vec![RUNTIME.to_string()],
         });
 
-        let expected_agent_map = generate_test_agent_map(AGENT_A);
+        let agent_attributes = server_state.state.agents.get(AGENT_A).unwrap();
+        assert_eq!(agent_attributes.cpu_usage, Some(CpuUsage::default()));
+        assert_eq!(agent_attributes.free_memory, Some(FreeMemory::default()));
+        assert_eq!(agent_attributes.disk_usage, Some(DiskUsage::default()));
+        assert_eq!(
+            agent_attributes.network_usage,
+            Some(NetworkUsage::default())
+        );
+        assert_eq!(agent_attributes.temperature, Some(Temperature::default()));
+        assert_eq!(
+            agent_attributes.labels,
+            HashMap::from([("zone".to_string(), "cockpit".to_string())])
+        );
+        // [utest->swdd~server-state-stores-agent-version-and-runtimes~1]
+        assert_eq!(agent_attributes.version, "0.5.0");
+        assert_eq!(agent_attributes.runtimes, vec![RUNTIME.to_string()]);
+        assert!(agent_attributes.connected_at > 0);
+        assert!(agent_attributes.load_history.is_empty());
+    }
 
-        assert_eq!(server_state.state.agents, expected_agent_map);
+    // [utest->swdd~server-state-schedules-workloads-by-node-selector~1]
+    #[test]
+    fn utest_schedule_unscheduled_workloads_on_newly_connected_agent() {
+        let mut unscheduled_workload = generate_test_workload_spec_with_param(
+            "".to_string(),
+            WORKLOAD_NAME_1.to_string(),
+            RUNTIME.to_string(),
+        );
+        unscheduled_workload.node_selector =
+            HashMap::from([("zone".to_string(), "cockpit".to_string())]);
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().once().return_const(());
+
+        let mut server_state = ServerState {
+            rendered_workloads: HashMap::from([(
+                WORKLOAD_NAME_1.to_string(),
+                unscheduled_workload.clone(),
+            )]),
+            delete_graph: delete_graph_mock,
+            ..Default::default()
+        };
+
+        server_state.add_agent(agent_hello(AGENT_A, HashMap::new()));
+        assert_eq!(server_state.schedule_unscheduled_workloads(), None);
+
+        server_state.add_agent(agent_hello(
+            AGENT_B,
+            HashMap::from([("zone".to_string(), "cockpit".to_string())]),
+        ));
+        let (added_workloads, deleted_workloads) =
+            server_state.schedule_unscheduled_workloads().unwrap();
+
+        assert_eq!(added_workloads.len(), 1);
+        assert_eq!(added_workloads[0].instance_name.agent_name(), AGENT_B);
+        assert_eq!(
+            deleted_workloads,
+            vec![DeletedWorkload {
+                instance_name: unscheduled_workload.instance_name,
+                ..Default::default()
+            }]
+        );
+        assert_eq!(
+            server_state.get_workloads_for_agent(AGENT_B),
+            added_workloads
+        );
+    }
+
+    // [utest->swdd~server-state-reschedules-workloads-of-lost-agent~1]
+    #[test]
+    fn utest_reschedule_workloads_of_lost_agent() {
+        let node_selector = HashMap::from([("zone".to_string(), "cockpit".to_string())]);
+        let mut selected_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_string(),
+            WORKLOAD_NAME_1.to_string(),
+            RUNTIME.to_string(),
+        );
+        selected_workload.node_selector = node_selector.clone();
+        let pinned_workload = generate_test_workload_spec_with_param(
+            AGENT_A.to_string(),
+            WORKLOAD_NAME_2.to_string(),
+            RUNTIME.to_string(),
+        );
+
+        let mut delete_graph_mock = MockDeleteGraph::new();
+        delete_graph_mock.expect_insert().once().return_const(());
+
+        let mut server_state = ServerState {
+            rendered_workloads: HashMap::from([
+                (WORKLOAD_NAME_1.to_string(), selected_workload.clone()),
+                (WORKLOAD_NAME_2.to_string(), pinned_workload.clone()),
+            ]),
+            delete_graph: delete_graph_mock,
+            ..Default::default()
+        };
+
+        server_state.add_agent(agent_hello("agent_C", HashMap::new()));
+        assert_eq!(
+            server_state.reschedule_workloads_of_lost_agent(AGENT_A),
+            None
+        );
+
+        server_state.add_agent(agent_hello(AGENT_B, node_selector));
+        let (added_workloads, deleted_workloads) = server_state
+            .reschedule_workloads_of_lost_agent(AGENT_A)
+            .unwrap();
+
+        assert_eq!(added_workloads.len(), 1);
+        assert_eq!(added_workloads[0].instance_name.agent_name(), AGENT_B);
+        assert_eq!(
+            deleted_workloads,
+            vec![DeletedWorkload {
+                instance_name: selected_workload.instance_name,
+                ..Default::default()
+            }]
+        );
+        assert_eq!(
+            server_state.get_workloads_for_agent(AGENT_A),
+            vec![pinned_workload]
+        );
     }
 
     // [utest->swdd~server-state-removes-agent-from-complete-state~1]
     #[test]
     fn utest_remove_agent() {
+        let mut workload_resources = WorkloadResourcesMap::default();
+        workload_resources.update_for_agent(
+            AGENT_A,
+            vec![generate_test_workload_resources(AGENT_A, WORKLOAD_NAME_1)],
+        );
+        let mut image_states = ImageStatesMap::default();
+        image_states.update_for_agent(
+            AGENT_A,
+            HashMap::from([("image_1:1.0".to_string(), ImageState::pulled())]),
+        );
         let mut server_state = ServerState {
             state: CompleteState {
                 agents: generate_test_agent_map(AGENT_A),
+                workload_resources,
+                image_states,
                 ..Default::default()
             },
             ..Default::default()
@@ -1591,6 +3140,11 @@ mod tests {
 
         let expected_agent_map = AgentMap::default();
         assert_eq!(server_state.state.agents, expected_agent_map);
+        assert_eq!(
+            server_state.state.workload_resources,
+            WorkloadResourcesMap::default()
+        );
+        assert_eq!(server_state.state.image_states, ImageStatesMap::default());
     }
 
     // [utest->swdd~server-state-provides-connected-agent-exists-check~1]
@@ -1608,6 +3162,73 @@ mod tests {
         assert!(!server_state.contains_connected_agent(AGENT_B));
     }
 
+    // [utest->swdd~server-state-computes-dry-run-update~1]
+    #[test]
+    fn utest_server_state_dry_run_update_does_not_modify_state() {
+        let old_state = generate_test_old_state();
+        let update_state = generate_test_update_state();
+        let old_rendered_workloads =
+            generate_rendered_workloads_from_state(&old_state.desired_state);
+        let new_rendered_workloads =
+            generate_rendered_workloads_from_state(&update_state.desired_state);
+
+        let mut mock_config_renderer = MockConfigRenderer::new();
+        let cloned_new_rendered_workloads = new_rendered_workloads.clone();
+        mock_config_renderer
+            .expect_render_workloads()
+            .once()
+            .return_once(|_, _| Ok(cloned_new_rendered_workloads));
+
+        let server_state = ServerState {
+            state: old_state.clone(),
+            rendered_workloads: old_rendered_workloads.clone(),
+            delete_graph: MockDeleteGraph::new(),
+            config_renderer: mock_config_renderer,
+            admission_validators: Default::default(),
+        };
+
+        let mut dry_run_result = server_state.dry_run_update(update_state, vec![]).unwrap();
+        dry_run_result
+            .updated_workloads
+            .sort_by(|a, b| a.workload_name.cmp(&b.workload_name));
+
+        assert_eq!(
+            dry_run_result.added_workloads,
+            vec![new_rendered_workloads
+                .get(WORKLOAD_NAME_4)
+                .unwrap()
+                .instance_name
+                .to_string()]
+        );
+        assert_eq!(
+            dry_run_result.deleted_workloads,
+            vec![old_rendered_workloads
+                .get(WORKLOAD_NAME_2)
+                .unwrap()
+                .instance_name
+                .to_string()]
+        );
+        assert_eq!(
+            dry_run_result
+                .updated_workloads
+                .iter()
+                .map(|workload_diff| workload_diff.workload_name.as_str())
+                .collect::<Vec<_>>(),
+            vec![WORKLOAD_NAME_1, WORKLOAD_NAME_3]
+        );
+        assert!(dry_run_result.updated_workloads[0]
+            .field_diffs
+            .iter()
+            .any(|field_diff| field_diff.field == "instanceName.agentName"));
+        assert!(dry_run_result.updated_workloads[1]
+            .field_diffs
+            .iter()
+            .any(|field_diff| field_diff.field == "runtime"));
+
+        assert_eq!(old_state, server_state.state);
+        assert_eq!(old_rendered_workloads, server_state.rendered_workloads);
+    }
+
     fn generate_test_old_state() -> CompleteState {
         generate_test_complete_state(vec![
             generate_test_workload_spec_with_param(
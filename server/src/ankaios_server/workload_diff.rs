@@ -0,0 +1,143 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeSet;
+
+use api::ank_base;
+use common::{objects::WorkloadSpec, std_extensions::IllegalStateResult};
+use serde_json::Value;
+
+// The id is derived from the runtime config and changes with it.
+const IGNORED_FIELDS: [&str; 1] = ["instanceName.id"];
+
+// [impl->swdd~server-state-computes-dry-run-update~1]
+pub fn diff_workloads(current: &WorkloadSpec, new: &WorkloadSpec) -> Vec<ank_base::FieldDiff> {
+    let current = serde_json::to_value(current).unwrap_or_illegal_state();
+    let new = serde_json::to_value(new).unwrap_or_illegal_state();
+
+    let mut field_diffs = Vec::new();
+    diff_values("", &current, &new, &mut field_diffs);
+    field_diffs
+}
+
+fn diff_values(
+    field: &str,
+    current: &Value,
+    new: &Value,
+    field_diffs: &mut Vec<ank_base::FieldDiff>,
+) {
+    if current == new || IGNORED_FIELDS.contains(&field) {
+        return;
+    }
+
+    match (current, new) {
+        (Value::Object(current_fields), Value::Object(new_fields)) => {
+            let keys: BTreeSet<&String> = current_fields.keys().chain(new_fields.keys()).collect();
+            for key in keys {
+                let sub_field = if field.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", field, key)
+                };
+                diff_values(
+                    &sub_field,
+                    current_fields.get(key).unwrap_or(&Value::Null),
+                    new_fields.get(key).unwrap_or(&Value::Null),
+                    field_diffs,
+                );
+            }
+        }
+        _ => field_diffs.push(ank_base::FieldDiff {
+            field: field.to_string(),
+            current_value: value_to_string(current),
+            new_value: value_to_string(new),
+        }),
+    }
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::diff_workloads;
+    use api::ank_base;
+    use common::objects::{generate_test_workload_spec_with_param, RestartPolicy};
+
+    // [utest->swdd~server-state-computes-dry-run-update~1]
+    #[test]
+    fn utest_diff_workloads_identical() {
+        let workload = generate_test_workload_spec_with_param(
+            "agent_A".to_string(),
+            "workload_1".to_string(),
+            "runtime".to_string(),
+        );
+
+        assert!(diff_workloads(&workload, &workload.clone()).is_empty());
+    }
+
+    // [utest->swdd~server-state-computes-dry-run-update~1]
+    #[test]
+    fn utest_diff_workloads_changed_fields() {
+        let current = generate_test_workload_spec_with_param(
+            "agent_A".to_string(),
+            "workload_1".to_string(),
+            "runtime".to_string(),
+        );
+        let mut new = generate_test_workload_spec_with_param(
+            "agent_B".to_string(),
+            "workload_1".to_string(),
+            "runtime".to_string(),
+        );
+        new.restart_policy = RestartPolicy::OnFailure;
+        new.hostname = Some("web-server".to_string());
+
+        let mut field_diffs = diff_workloads(&current, &new);
+        field_diffs.sort_by(|a, b| a.field.cmp(&b.field));
+
+        assert_eq!(
+            field_diffs,
+            vec![
+                ank_base::FieldDiff {
+                    field: "hostname".to_string(),
+                    current_value: String::new(),
+                    new_value: "web-server".to_string(),
+                },
+                ank_base::FieldDiff {
+                    field: "instanceName.agentName".to_string(),
+                    current_value: "agent_A".to_string(),
+                    new_value: "agent_B".to_string(),
+                },
+                ank_base::FieldDiff {
+                    field: "restartPolicy".to_string(),
+                    current_value: "ALWAYS".to_string(),
+                    new_value: "ON_FAILURE".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,349 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use api::ank_base;
+use common::objects::WorkloadState;
+use serde::Deserialize;
+
+const DEFAULT_WORKLOAD_STATE_HISTORY_SIZE: usize = 20;
+const WORKLOAD_STATE_HISTORY_FIELD_MASK_PART: &str = "workloadStateHistory";
+
+type WorkloadName = String;
+
+/// Configuration of the workload state history as provided in the server config file
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct WorkloadStateHistoryConfig {
+    /// The number of transitions kept per workload, 0 disables the history
+    pub size: usize,
+    /// The file the history is persisted in to survive restarts of the server
+    pub file: Option<PathBuf>,
+}
+
+impl Default for WorkloadStateHistoryConfig {
+    fn default() -> Self {
+        WorkloadStateHistoryConfig {
+            size: DEFAULT_WORKLOAD_STATE_HISTORY_SIZE,
+            file: None,
+        }
+    }
+}
+
+/// Bounded history of the execution state transitions per workload
+///
+/// When the configured size is reached the oldest transitions of the workload are dropped.
+/// The history is kept after the workload is deleted, such that the transitions
+/// leading to a problem can be inspected afterwards.
+// [impl->swdd~server-records-workload-state-history~1]
+pub struct WorkloadStateHistory {
+    transitions: HashMap<WorkloadName, VecDeque<ank_base::WorkloadStateTransition>>,
+    size: usize,
+    file: Option<PathBuf>,
+}
+
+impl Default for WorkloadStateHistory {
+    fn default() -> Self {
+        Self::new(&WorkloadStateHistoryConfig::default())
+    }
+}
+
+impl WorkloadStateHistory {
+    pub fn new(config: &WorkloadStateHistoryConfig) -> Self {
+        let mut workload_state_history = WorkloadStateHistory {
+            transitions: HashMap::new(),
+            size: config.size,
+            file: config.file.clone(),
+        };
+        // [impl->swdd~server-persists-workload-state-history~1]
+        if let Some(file) = &config.file {
+            match load_transitions(file) {
+                Ok(transitions) => workload_state_history.transitions = transitions,
+                Err(err) => log::warn!("Starting with an empty workload state history: '{}'", err),
+            }
+            workload_state_history.truncate();
+        }
+        workload_state_history
+    }
+
+    pub fn record(&mut self, workload_states: &[WorkloadState]) {
+        if self.size == 0 || workload_states.is_empty() {
+            return;
+        }
+
+        let timestamp = current_timestamp_millis();
+        for workload_state in workload_states {
+            let instance_name = &workload_state.instance_name;
+            let transitions = self
+                .transitions
+                .entry(instance_name.workload_name().to_owned())
+                .or_default();
+            if transitions.len() == self.size {
+                transitions.pop_front();
+            }
+            transitions.push_back(ank_base::WorkloadStateTransition {
+                timestamp,
+                agent: instance_name.agent_name().to_owned(),
+                id: instance_name.id().to_owned(),
+                execution_state: Some(workload_state.execution_state.clone().into()),
+            });
+        }
+        self.persist();
+    }
+
+    /// Returns the history of the workloads selected by the field mask
+    ///
+    /// The history is only returned if it is requested explicitly with the field mask
+    /// `workloadStateHistory` or `workloadStateHistory.<workload name>`.
+    // [impl->swdd~server-provides-workload-state-history~1]
+    pub fn get_by_field_mask(
+        &self,
+        field_mask: &[String],
+    ) -> Option<ank_base::WorkloadStateHistoryMap> {
+        let mut requested_workloads = Vec::new();
+        for field in field_mask {
+            let mut parts = field.split('.');
+            if parts.next() != Some(WORKLOAD_STATE_HISTORY_FIELD_MASK_PART) {
+                continue;
+            }
+            match parts.next() {
+                Some(workload_name) => requested_workloads.push(workload_name),
+                None => return Some(self.select(|_| true)),
+            }
+        }
+
+        (!requested_workloads.is_empty())
+            .then(|| self.select(|workload_name| requested_workloads.contains(&workload_name)))
+    }
+
+    fn select(&self, is_selected: impl Fn(&str) -> bool) -> ank_base::WorkloadStateHistoryMap {
+        ank_base::WorkloadStateHistoryMap {
+            workloads: self
+                .transitions
+                .iter()
+                .filter(|(workload_name, _)| is_selected(workload_name))
+                .map(|(workload_name, transitions)| {
+                    (
+                        workload_name.clone(),
+                        ank_base::WorkloadStateHistory {
+                            transitions: transitions.iter().cloned().collect(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn truncate(&mut self) {
+        for transitions in self.transitions.values_mut() {
+            let surplus = transitions.len().saturating_sub(self.size);
+            transitions.drain(..surplus);
+        }
+        self.transitions
+            .retain(|_, transitions| !transitions.is_empty());
+    }
+
+    // [impl->swdd~server-persists-workload-state-history~1]
+    fn persist(&self) {
+        let Some(file) = &self.file else {
+            return;
+        };
+        // the file is replaced atomically to not lose the history if the server stops while writing
+        let temp_file = file.with_extension("tmp");
+        let result = serde_json::to_vec(&self.transitions)
+            .map_err(|err| err.to_string())
+            .and_then(|content| fs::write(&temp_file, content).map_err(|err| err.to_string()))
+            .and_then(|_| fs::rename(&temp_file, file).map_err(|err| err.to_string()));
+        if let Err(err) = result {
+            log::warn!(
+                "Could not persist the workload state history to '{}': '{}'",
+                file.display(),
+                err
+            );
+        }
+    }
+}
+
+fn load_transitions(
+    file: &Path,
+) -> Result<HashMap<WorkloadName, VecDeque<ank_base::WorkloadStateTransition>>, String> {
+    if !file.exists() {
+        return Ok(HashMap::new());
+    }
+    let content =
+        fs::read(file).map_err(|err| format!("Could not read '{}': '{}'", file.display(), err))?;
+    serde_json::from_slice(&content)
+        .map_err(|err| format!("Could not parse '{}': '{}'", file.display(), err))
+}
+
+fn current_timestamp_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::objects::{ExecutionState, WorkloadInstanceName, WorkloadState};
+
+    use super::{WorkloadStateHistory, WorkloadStateHistoryConfig};
+
+    const AGENT_A: &str = "agent_A";
+    const WORKLOAD_NAME_1: &str = "workload_1";
+    const WORKLOAD_NAME_2: &str = "workload_2";
+
+    fn workload_state(workload_name: &str, execution_state: ExecutionState) -> WorkloadState {
+        WorkloadState {
+            instance_name: WorkloadInstanceName::builder()
+                .agent_name(AGENT_A)
+                .workload_name(workload_name)
+                .config(&String::from("config"))
+                .build(),
+            execution_state,
+        }
+    }
+
+    fn recorded_states(
+        workload_state_history: &WorkloadStateHistory,
+        workload_name: &str,
+    ) -> Vec<ExecutionState> {
+        workload_state_history
+            .get_by_field_mask(&[format!("workloadStateHistory.{}", workload_name)])
+            .and_then(|mut history| history.workloads.remove(workload_name))
+            .map(|history| {
+                history
+                    .transitions
+                    .into_iter()
+                    .map(|transition| transition.execution_state.unwrap().into())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // [utest->swdd~server-records-workload-state-history~1]
+    #[test]
+    fn utest_workload_state_history_drops_oldest_transitions() {
+        let mut workload_state_history = WorkloadStateHistory::new(&WorkloadStateHistoryConfig {
+            size: 2,
+            file: None,
+        });
+
+        workload_state_history.record(&[
+            workload_state(WORKLOAD_NAME_1, ExecutionState::starting_triggered()),
+            workload_state(WORKLOAD_NAME_2, ExecutionState::running()),
+        ]);
+        workload_state_history
+            .record(&[workload_state(WORKLOAD_NAME_1, ExecutionState::running())]);
+        workload_state_history.record(&[workload_state(
+            WORKLOAD_NAME_1,
+            ExecutionState::failed("exit code 1"),
+        )]);
+
+        assert_eq!(
+            recorded_states(&workload_state_history, WORKLOAD_NAME_1),
+            vec![
+                ExecutionState::running(),
+                ExecutionState::failed("exit code 1")
+            ]
+        );
+        assert_eq!(
+            recorded_states(&workload_state_history, WORKLOAD_NAME_2),
+            vec![ExecutionState::running()]
+        );
+    }
+
+    // [utest->swdd~server-records-workload-state-history~1]
+    #[test]
+    fn utest_workload_state_history_disabled() {
+        let mut workload_state_history = WorkloadStateHistory::new(&WorkloadStateHistoryConfig {
+            size: 0,
+            file: None,
+        });
+
+        workload_state_history
+            .record(&[workload_state(WORKLOAD_NAME_1, ExecutionState::running())]);
+
+        assert!(recorded_states(&workload_state_history, WORKLOAD_NAME_1).is_empty());
+    }
+
+    // [utest->swdd~server-provides-workload-state-history~1]
+    #[test]
+    fn utest_workload_state_history_get_by_field_mask() {
+        let mut workload_state_history = WorkloadStateHistory::default();
+        workload_state_history.record(&[
+            workload_state(WORKLOAD_NAME_1, ExecutionState::running()),
+            workload_state(WORKLOAD_NAME_2, ExecutionState::running()),
+        ]);
+
+        assert_eq!(workload_state_history.get_by_field_mask(&[]), None);
+        assert_eq!(
+            workload_state_history.get_by_field_mask(&["workloadStates".to_string()]),
+            None
+        );
+
+        let complete_history = workload_state_history
+            .get_by_field_mask(&["workloadStateHistory".to_string()])
+            .unwrap();
+        assert_eq!(complete_history.workloads.len(), 2);
+
+        let selected_history = workload_state_history
+            .get_by_field_mask(&[format!("workloadStateHistory.{}", WORKLOAD_NAME_2)])
+            .unwrap();
+        assert_eq!(
+            selected_history.workloads.keys().collect::<Vec<_>>(),
+            vec![WORKLOAD_NAME_2]
+        );
+        let transition = &selected_history.workloads[WORKLOAD_NAME_2].transitions[0];
+        assert_eq!(transition.agent, AGENT_A);
+        assert!(transition.timestamp > 0);
+    }
+
+    // [utest->swdd~server-persists-workload-state-history~1]
+    #[test]
+    fn utest_workload_state_history_is_persisted() {
+        let history_dir = tempfile::tempdir().unwrap();
+        let config = WorkloadStateHistoryConfig {
+            size: 2,
+            file: Some(history_dir.path().join("history.json")),
+        };
+
+        let mut workload_state_history = WorkloadStateHistory::new(&config);
+        workload_state_history.record(&[workload_state(
+            WORKLOAD_NAME_1,
+            ExecutionState::starting_triggered(),
+        )]);
+        workload_state_history
+            .record(&[workload_state(WORKLOAD_NAME_1, ExecutionState::running())]);
+
+        let restored_history =
+            WorkloadStateHistory::new(&WorkloadStateHistoryConfig { size: 1, ..config });
+        assert_eq!(
+            recorded_states(&restored_history, WORKLOAD_NAME_1),
+            vec![ExecutionState::running()]
+        );
+    }
+}
@@ -13,8 +13,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use clap::Parser;
-use common::DEFAULT_SOCKET_ADDRESS;
-use std::{env, net::SocketAddr};
+use common::{communications_server::ServerAddress, DEFAULT_SOCKET_ADDRESS};
+use grpc::server::DuplicateAgentPolicy;
+use std::env;
+
+const DEFAULT_WORKLOAD_STATES_RESYNC_INTERVAL_SECS: u64 = 60;
+const DEFAULT_LEASE_TIMEOUT_SECS: u64 = 5;
+const DEFAULT_AGENT_REQUEST_TIMEOUT_SECS: u64 = 30;
 
 pub fn parse() -> Arguments {
     Arguments::parse()
@@ -30,9 +35,12 @@ pub struct Arguments {
     #[clap(short = 'c', long = "startup-config")]
     /// The path to the startup config yaml.
     pub path: Option<String>,
+    #[clap(long = "startup-manifest-dir", env = "ANKSERVER_STARTUP_MANIFEST_DIR")]
+    /// The path to a directory with additional startup manifests. All '*.yaml' files are loaded in alphabetical order and merged into the startup config.
+    pub startup_manifest_dir: Option<String>,
     #[clap(short = 'a', long = "address", default_value_t = DEFAULT_SOCKET_ADDRESS.parse().unwrap())]
-    /// The address, including the port, the server shall listen at.
-    pub addr: SocketAddr,
+    /// The address, including the port, the server shall listen at. Use 'unix:///path/to/server.sock' to listen at a unix domain socket instead.
+    pub addr: ServerAddress,
     #[clap(
         short = 'k',
         long = "insecure",
@@ -50,6 +58,61 @@ pub struct Arguments {
     #[clap(long = "key_pem", env = "ANKSERVER_KEY_PEM")]
     /// Path to server key pem file.
     pub key_pem: Option<String>,
+    #[clap(
+        long = "workload-states-resync-interval",
+        env = "ANKSERVER_WORKLOAD_STATES_RESYNC_INTERVAL",
+        default_value_t = DEFAULT_WORKLOAD_STATES_RESYNC_INTERVAL_SECS
+    )]
+    /// Interval in seconds for sending all workload states to the connected agents in addition to the changed ones. The value 0 disables the resync.
+    pub workload_states_resync_interval: u64,
+    #[clap(
+        long = "agent-lost-grace-period",
+        env = "ANKSERVER_AGENT_LOST_GRACE_PERIOD",
+        default_value_t = 0
+    )]
+    /// Seconds after which a disconnected agent is regarded as lost and its workloads scheduled by node selector are rescheduled to other matching agents. The value 0 disables the rescheduling.
+    pub agent_lost_grace_period: u64,
+    #[clap(
+        long = "agent-request-timeout",
+        env = "ANKSERVER_AGENT_REQUEST_TIMEOUT",
+        default_value_t = DEFAULT_AGENT_REQUEST_TIMEOUT_SECS
+    )]
+    /// Seconds to wait for the response of an agent to a forwarded restart or pause request before answering the request with a timeout error. The value 0 disables the timeout.
+    pub agent_request_timeout: u64,
+    #[clap(
+        long = "duplicate-agent-policy",
+        env = "ANKSERVER_DUPLICATE_AGENT_POLICY",
+        default_value_t = DuplicateAgentPolicy::default()
+    )]
+    /// Behavior when an agent connects with the name of an already connected agent: 'replace' the existing connection, 'reject' the new one or 'quarantine' both.
+    pub duplicate_agent_policy: DuplicateAgentPolicy,
+    #[clap(
+        long = "allowed-agents",
+        env = "ANKSERVER_ALLOWED_AGENTS",
+        value_delimiter = ','
+    )]
+    /// Comma separated list of agent names allowed to connect. A name can contain the wildcard '*'. If not provided, all agents are allowed.
+    pub allowed_agents: Vec<String>,
+    #[clap(long = "server-config", env = "ANKSERVER_CONFIG")]
+    /// The path to the server config file in TOML format. If not provided, '/etc/ankaios/ank-server.conf' is used if it exists.
+    pub server_config: Option<String>,
+    #[clap(
+        long = "standby-of",
+        env = "ANKSERVER_STANDBY_OF",
+        requires = "standby_name"
+    )]
+    /// Start as hot standby of the primary server at the given address, e.g. 'https://primary:25551'. The standby replicates the desired state of the primary and serves at its own address only after its promotion.
+    pub standby_of: Option<String>,
+    #[clap(long = "standby-name", env = "ANKSERVER_STANDBY_NAME")]
+    /// The name the standby server connects to the primary server with. It must be allowed in the replication config of the primary server and, with mTLS, be a DNS name of the certificate of the standby server.
+    pub standby_name: Option<String>,
+    #[clap(
+        long = "lease-timeout",
+        env = "ANKSERVER_LEASE_TIMEOUT",
+        default_value_t = DEFAULT_LEASE_TIMEOUT_SECS
+    )]
+    /// Seconds without a lease renewal of the primary server after which a standby server promotes itself to primary. A standby is promoted manually with the signal SIGUSR1.
+    pub lease_timeout: u64,
 }
 // Note: this code is intentionally without unit tests.
 // There is no business logic which can be tested, here we have only a config and a call of "clap" crate.
@@ -0,0 +1,212 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use serde_yaml::Value;
+
+const VARIABLE_START: &str = "${";
+const VARIABLE_END: char = '}';
+const DEFAULT_SEPARATOR: &str = ":-";
+const ESCAPED_VARIABLE_START: &str = "$${";
+
+// The variables are substituted in the string values of the parsed manifest, thus the values of
+// the variables cannot change the structure of the manifest and comments are not substituted.
+// [impl->swdd~server-substitutes-env-variables-in-startup-config~3]
+pub fn substitute_env_variables(manifest: &mut Value) -> Result<(), String> {
+    substitute_variables_in_values(manifest, &|name| std::env::var(name).ok())
+}
+
+fn substitute_variables_in_values(
+    value: &mut Value,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<(), String> {
+    match value {
+        Value::String(content) => *content = substitute_variables(content, lookup)?,
+        Value::Sequence(sequence) => {
+            for value in sequence {
+                substitute_variables_in_values(value, lookup)?;
+            }
+        }
+        Value::Mapping(mapping) => {
+            for (_, value) in mapping.iter_mut() {
+                substitute_variables_in_values(value, lookup)?;
+            }
+        }
+        Value::Tagged(tagged) => substitute_variables_in_values(&mut tagged.value, lookup)?,
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+    Ok(())
+}
+
+fn substitute_variables(
+    content: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut result = String::with_capacity(content.len());
+    let mut remaining = content;
+
+    while let Some(start) = remaining.find('$') {
+        result.push_str(&remaining[..start]);
+        remaining = &remaining[start..];
+
+        if let Some(rest) = remaining.strip_prefix(ESCAPED_VARIABLE_START) {
+            result.push_str(VARIABLE_START);
+            remaining = rest;
+        } else if let Some(rest) = remaining.strip_prefix(VARIABLE_START) {
+            let end = rest.find(VARIABLE_END).ok_or_else(|| {
+                format!("Missing closing '{VARIABLE_END}' for variable starting at '{remaining}'")
+            })?;
+            let expression = &rest[..end];
+            let (name, default) = match expression.split_once(DEFAULT_SEPARATOR) {
+                Some((name, default)) => (name, Some(default)),
+                None => (expression, None),
+            };
+            if name.is_empty() {
+                return Err(format!("Empty variable name in '${{{expression}}}'"));
+            }
+
+            match (lookup(name), default) {
+                (Some(value), _) => result.push_str(&value),
+                (None, Some(default)) => result.push_str(default),
+                (None, None) => {
+                    return Err(format!(
+                        "Environment variable '{name}' is not set and has no default value"
+                    ))
+                }
+            }
+            remaining = &rest[end + 1..];
+        } else {
+            result.push('$');
+            remaining = &remaining[1..];
+        }
+    }
+    result.push_str(remaining);
+    Ok(result)
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use serde_yaml::Value;
+
+    use super::{substitute_variables, substitute_variables_in_values};
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "SERIAL_NUMBER" => Some("SN-1234".to_owned()),
+            "INJECTION" => Some("value\nagent: injected_agent".to_owned()),
+            "DEVICE_IP" => Some("10.0.0.42".to_owned()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    // [utest->swdd~server-substitutes-env-variables-in-startup-config~3]
+    #[test]
+    fn utest_substitute_variables_replaces_set_variables() {
+        assert_eq!(
+            substitute_variables(
+                "serial: ${SERIAL_NUMBER}\nurl: http://${DEVICE_IP}:8080/${EMPTY}",
+                lookup
+            ),
+            Ok("serial: SN-1234\nurl: http://10.0.0.42:8080/".to_owned())
+        );
+    }
+
+    // [utest->swdd~server-substitutes-env-variables-in-startup-config~3]
+    #[test]
+    fn utest_substitute_variables_uses_default_for_unset_variables() {
+        assert_eq!(
+            substitute_variables(
+                "ip: ${NOT_SET:-127.0.0.1}, serial: ${SERIAL_NUMBER:-none}",
+                lookup
+            ),
+            Ok("ip: 127.0.0.1, serial: SN-1234".to_owned())
+        );
+        assert_eq!(
+            substitute_variables("value: '${NOT_SET:-}'", lookup),
+            Ok("value: ''".to_owned())
+        );
+    }
+
+    // [utest->swdd~server-substitutes-env-variables-in-startup-config~3]
+    #[test]
+    fn utest_substitute_variables_keeps_other_content() {
+        let content = "price: 5$\ncmd: echo $HOME {{config}} $${NOT_SUBSTITUTED}";
+        assert_eq!(
+            substitute_variables(content, lookup),
+            Ok("price: 5$\ncmd: echo $HOME {{config}} ${NOT_SUBSTITUTED}".to_owned())
+        );
+    }
+
+    // [utest->swdd~server-substitutes-env-variables-in-startup-config~3]
+    #[test]
+    fn utest_substitute_variables_fails_on_unset_variable_without_default() {
+        assert!(substitute_variables("serial: ${NOT_SET}", lookup)
+            .unwrap_err()
+            .contains("'NOT_SET'"));
+    }
+
+    // [utest->swdd~server-substitutes-env-variables-in-startup-config~3]
+    #[test]
+    fn utest_substitute_variables_fails_on_invalid_expression() {
+        assert!(substitute_variables("serial: ${SERIAL_NUMBER", lookup).is_err());
+        assert!(substitute_variables("serial: ${}", lookup).is_err());
+        assert!(substitute_variables("serial: ${:-default}", lookup).is_err());
+    }
+
+    // [utest->swdd~server-substitutes-env-variables-in-startup-config~3]
+    #[test]
+    fn utest_substitute_variables_in_values_keeps_structure() {
+        let mut manifest: Value = serde_yaml::from_str(concat!(
+            "workloads:\n",
+            "  nginx:\n",
+            "    agent: agent_A\n",
+            "    tags:\n",
+            "      - key: serial\n",
+            "        value: ${INJECTION}\n",
+        ))
+        .unwrap();
+
+        substitute_variables_in_values(&mut manifest, &lookup).unwrap();
+
+        assert_eq!(manifest["workloads"]["nginx"]["agent"], "agent_A");
+        assert_eq!(
+            manifest["workloads"]["nginx"]["tags"][0]["value"],
+            "value\nagent: injected_agent"
+        );
+    }
+
+    // [utest->swdd~server-substitutes-env-variables-in-startup-config~3]
+    #[test]
+    fn utest_substitute_variables_in_values_skips_comments() {
+        let mut manifest: Value = serde_yaml::from_str(concat!(
+            "# the serial is ${NOT_SET}\n",
+            "serial: ${SERIAL_NUMBER} # set by ${NOT_SET} on provisioning\n",
+            "port: 8080\n",
+        ))
+        .unwrap();
+
+        substitute_variables_in_values(&mut manifest, &lookup).unwrap();
+
+        assert_eq!(manifest["serial"], "SN-1234");
+        assert_eq!(manifest["port"], 8080);
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright (c) 2023 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+// The library target is used by the binary and allows running the server within another process,
+// e.g., in the development mode of the CLI. The modules are private and only the entry point is
+// exported.
+
+mod ankaios_server;
+mod cli;
+mod env_substitution;
+mod notifications;
+mod server_config;
+mod server_runner;
+mod startup_state_loader;
+mod startup_state_reloader;
+
+pub use ankaios_server::create_to_server_channel;
+pub use cli::{parse as parse_arguments, Arguments as ServerArguments};
+pub use server_config::ServerConfig;
+pub use server_runner::{run_server, ServerRunConfig, StandbyConfig};
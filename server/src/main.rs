@@ -12,25 +12,21 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-mod ankaios_server;
-mod cli;
+use std::time::Duration;
 
-use common::objects::CompleteState;
-use std::fs;
-
-use common::communications_server::CommunicationsServer;
-use common::objects::State;
+use ank_server::{ServerConfig, ServerRunConfig, StandbyConfig};
 use common::std_extensions::GracefulExitResult;
 
-use ankaios_server::{create_from_server_channel, create_to_server_channel, AnkaiosServer};
-
-use grpc::{security::TLSConfig, server::GRPCCommunicationsServer};
+use grpc::{
+    security::TLSConfig,
+    server::{AgentAllowList, GRPCCommunicationsServer, StateReplicator},
+};
 
 #[tokio::main]
 async fn main() {
     env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
 
-    let args = cli::parse();
+    let args = ank_server::parse_arguments();
 
     log::debug!(
         "Starting the Ankaios server with \n\tserver address: '{}', \n\tstartup config path: '{}'",
@@ -40,29 +36,11 @@ async fn main() {
             .unwrap_or("[no config file provided]".to_string()),
     );
 
-    let startup_state = match args.path {
-        Some(config_path) => {
-            let data =
-                fs::read_to_string(config_path).unwrap_or_exit("Could not read the startup config");
-            // [impl->swdd~server-state-in-memory~1]
-            // [impl->swdd~server-loads-startup-state-file~3]
-            let state: State = serde_yaml::from_str(&data)
-                .unwrap_or_exit("Parsing start config failed with error");
-            log::trace!(
-                "The state is initialized with the following workloads: {:?}",
-                state.workloads
-            );
-            Some(CompleteState {
-                desired_state: state,
-                ..Default::default()
-            })
-        }
-        // [impl->swdd~server-starts-without-startup-config~1]
-        _ => None,
-    };
+    // [impl->swdd~server-loads-server-config-file~1]
+    let mut server_config = ServerConfig::load(args.server_config.as_deref())
+        .unwrap_or_exit("Could not load the server config");
 
-    let (to_server, server_receiver) = create_to_server_channel(common::CHANNEL_CAPACITY);
-    let (to_agents, agents_receiver) = create_from_server_channel(common::CHANNEL_CAPACITY);
+    let (to_server, server_receiver) = ank_server::create_to_server_channel(common::CHANNEL_CAPACITY);
 
     if let Err(err_message) = TLSConfig::is_config_conflicting(args.insecure, &args.ca_pem, &args.crt_pem, &args.key_pem) {
         log::warn!("{}", err_message);
@@ -71,22 +49,52 @@ async fn main() {
     // [impl->swdd~server-establishes-insecure-communication-based-on-provided-insecure-cli-argument~1]
     // [impl->swdd~server-provides-file-paths-to-communication-middleware~1]
     // [impl->swdd~server-fails-on-missing-file-paths-and-insecure-cli-arguments~1]
-    let tls_config = TLSConfig::new(args.insecure, args.ca_pem, args.crt_pem, args.key_pem);
-
-    let mut communications_server = GRPCCommunicationsServer::new(
-        to_server.clone(),
+    let tls_config = TLSConfig::new(args.insecure, args.ca_pem, args.crt_pem, args.key_pem)
         // [impl->swdd~server-fails-on-missing-file-paths-and-insecure-cli-arguments~1]
-        tls_config.unwrap_or_exit("Missing certificates files"),
-    );
-    let mut server = AnkaiosServer::new(server_receiver, to_agents.clone());
+        .unwrap_or_exit("Missing certificates files");
 
-    tokio::select! {
-        // [impl->swdd~server-default-communication-grpc~1]
-        communication_result = communications_server.start(agents_receiver, args.addr) => {
-            communication_result.unwrap_or_exit("server error")
-        }
-        server_result = server.start(startup_state) => {
-            server_result.unwrap_or_exit("server error")
-        }
+    let standby = args
+        .standby_of
+        .zip(args.standby_name)
+        .map(|(primary_address, standby_name)| StandbyConfig {
+            primary_address,
+            standby_name,
+            lease_timeout: Duration::from_secs(args.lease_timeout),
+            tls_config: tls_config.clone(),
+        });
+
+    let mut communications_server = GRPCCommunicationsServer::new(to_server.clone(), tls_config)
+        // [impl->swdd~grpc-agent-connection-handles-duplicate-agent-names~1]
+        .with_duplicate_agent_policy(args.duplicate_agent_policy)
+        // [impl->swdd~grpc-agent-connection-checks-agent-allow-list~1]
+        .with_agent_allow_list(AgentAllowList::new(&args.allowed_agents))
+        // [impl->swdd~grpc-supports-connection-tuning~1]
+        .with_connection_config(server_config.grpc);
+    let mut state_replicator = None;
+    if let Some(replication_config) = server_config.replication.take() {
+        // [impl->swdd~server-replicates-desired-state~2]
+        let replicator = StateReplicator::default();
+        // [impl->swdd~grpc-server-replicates-desired-state-to-standby~2]
+        communications_server = communications_server
+            .with_state_replicator(replicator.clone(), replication_config.standbys);
+        state_replicator = Some(replicator);
     }
+
+    let enabled_interval = |seconds: u64| (seconds > 0).then(|| Duration::from_secs(seconds));
+    let config = ServerRunConfig {
+        startup_config: args.path,
+        startup_manifest_dir: args.startup_manifest_dir,
+        address: args.addr,
+        stream_errors: communications_server.stream_error_counter(),
+        server_config,
+        standby,
+        state_replicator,
+        workload_states_resync_interval: enabled_interval(args.workload_states_resync_interval),
+        agent_lost_grace_period: enabled_interval(args.agent_lost_grace_period),
+        agent_request_timeout: enabled_interval(args.agent_request_timeout),
+    };
+
+    ank_server::run_server(config, communications_server, (to_server, server_receiver))
+        .await
+        .unwrap_or_exit("server error");
 }
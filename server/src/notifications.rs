@@ -0,0 +1,39 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+mod notification_filter;
+mod notifier;
+mod unix_socket_sink;
+mod webhook_sink;
+
+pub use notification_filter::NotificationFilter;
+pub use notifier::Notifier;
+
+use serde::Deserialize;
+
+/// Configuration of a single notification sink as provided in the server config file
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct NotificationSinkConfig {
+    #[serde(flatten)]
+    pub target: NotificationTarget,
+    #[serde(flatten)]
+    pub filter: NotificationFilter,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NotificationTarget {
+    Webhook { url: String },
+    UnixSocket { path: String },
+}
@@ -0,0 +1,137 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use common::objects::WorkloadState;
+use serde::Deserialize;
+
+/// Filter selecting the workload state transitions forwarded to a notification sink
+///
+/// An empty list matches everything. The states are given by their name without substate,
+/// e.g. "Failed", or including the substate as output by the CLI, e.g. "Failed(ExecFailed)".
+// [impl->swdd~server-filters-notifications~1]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct NotificationFilter {
+    #[serde(default)]
+    pub agents: Vec<String>,
+    #[serde(default)]
+    pub workloads: Vec<String>,
+    #[serde(default)]
+    pub states: Vec<String>,
+}
+
+impl NotificationFilter {
+    pub fn matches(&self, workload_state: &WorkloadState) -> bool {
+        let instance_name = &workload_state.instance_name;
+        let state = workload_state.execution_state.state.to_string();
+        let state_name = state.split('(').next().unwrap_or_default();
+
+        matches_any(&self.agents, instance_name.agent_name())
+            && matches_any(&self.workloads, instance_name.workload_name())
+            && (self.states.is_empty()
+                || self
+                    .states
+                    .iter()
+                    .any(|filter_state| filter_state == state_name || *filter_state == state))
+    }
+}
+
+fn matches_any(filter_values: &[String], value: &str) -> bool {
+    filter_values.is_empty()
+        || filter_values
+            .iter()
+            .any(|filter_value| filter_value == value)
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::objects::{generate_test_workload_state_with_agent, ExecutionState};
+
+    use super::NotificationFilter;
+
+    const AGENT_A: &str = "agent_A";
+    const AGENT_B: &str = "agent_B";
+    const WORKLOAD_NAME_1: &str = "workload_1";
+    const WORKLOAD_NAME_2: &str = "workload_2";
+
+    // [utest->swdd~server-filters-notifications~1]
+    #[test]
+    fn utest_notification_filter_empty_matches_all() {
+        let filter = NotificationFilter::default();
+
+        assert!(filter.matches(&generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_1,
+            AGENT_A,
+            ExecutionState::running()
+        )));
+    }
+
+    // [utest->swdd~server-filters-notifications~1]
+    #[test]
+    fn utest_notification_filter_agents_and_workloads() {
+        let filter = NotificationFilter {
+            agents: vec![AGENT_A.to_string()],
+            workloads: vec![WORKLOAD_NAME_1.to_string()],
+            ..Default::default()
+        };
+
+        assert!(filter.matches(&generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_1,
+            AGENT_A,
+            ExecutionState::running()
+        )));
+        assert!(!filter.matches(&generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_1,
+            AGENT_B,
+            ExecutionState::running()
+        )));
+        assert!(!filter.matches(&generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_2,
+            AGENT_A,
+            ExecutionState::running()
+        )));
+    }
+
+    // [utest->swdd~server-filters-notifications~1]
+    #[test]
+    fn utest_notification_filter_states_with_and_without_substate() {
+        let filter = NotificationFilter {
+            states: vec!["Failed".to_string(), "Running(Unhealthy)".to_string()],
+            ..Default::default()
+        };
+
+        assert!(filter.matches(&generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_1,
+            AGENT_A,
+            ExecutionState::failed("some error")
+        )));
+        assert!(filter.matches(&generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_1,
+            AGENT_A,
+            ExecutionState::running_unhealthy("probe failed")
+        )));
+        assert!(!filter.matches(&generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_1,
+            AGENT_A,
+            ExecutionState::running()
+        )));
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use common::objects::WorkloadState;
+use tokio::sync::mpsc::{self, error::TrySendError, Receiver, Sender};
+
+use super::{
+    unix_socket_sink::UnixSocketSink, webhook_sink::WebhookSink, NotificationFilter,
+    NotificationSinkConfig, NotificationTarget,
+};
+
+const NOTIFICATION_QUEUE_CAPACITY: usize = 100;
+const NOTIFICATION_DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+enum NotificationSink {
+    Webhook(WebhookSink),
+    UnixSocket(UnixSocketSink),
+}
+
+impl NotificationSink {
+    fn new(target: &NotificationTarget) -> Result<Self, String> {
+        match target {
+            NotificationTarget::Webhook { url } => Ok(Self::Webhook(WebhookSink::new(url)?)),
+            NotificationTarget::UnixSocket { path } => {
+                Ok(Self::UnixSocket(UnixSocketSink::new(path)))
+            }
+        }
+    }
+
+    async fn send(&self, payload: &str) -> Result<(), String> {
+        match self {
+            NotificationSink::Webhook(sink) => sink.send(payload).await,
+            NotificationSink::UnixSocket(sink) => sink.send(payload).await,
+        }
+    }
+}
+
+struct SinkQueue {
+    name: String,
+    filter: NotificationFilter,
+    sender: Sender<String>,
+}
+
+/// Forwards the workload execution state transitions to the configured notification sinks
+///
+/// Each sink is served by an own task, such that a slow or unreachable sink
+/// neither blocks the server nor the other sinks.
+#[derive(Default)]
+pub struct Notifier {
+    sink_queues: Vec<SinkQueue>,
+}
+
+impl Notifier {
+    pub fn new(sink_configs: Vec<NotificationSinkConfig>) -> Result<Self, String> {
+        let mut sink_queues = Vec::new();
+        for sink_config in sink_configs {
+            let sink = NotificationSink::new(&sink_config.target)?;
+            let name = match &sink_config.target {
+                NotificationTarget::Webhook { url } => url.clone(),
+                NotificationTarget::UnixSocket { path } => path.clone(),
+            };
+
+            let (sender, receiver) = mpsc::channel(NOTIFICATION_QUEUE_CAPACITY);
+            tokio::spawn(deliver_notifications(name.clone(), sink, receiver));
+
+            sink_queues.push(SinkQueue {
+                name,
+                filter: sink_config.filter,
+                sender,
+            });
+        }
+        Ok(Notifier { sink_queues })
+    }
+
+    // [impl->swdd~server-notifies-workload-state-changes~1]
+    pub fn notify_execution_states(&self, workload_states: &[WorkloadState]) {
+        for workload_state in workload_states {
+            let sink_queues = self
+                .sink_queues
+                .iter()
+                .filter(|sink_queue| sink_queue.filter.matches(workload_state))
+                .collect::<Vec<_>>();
+            if sink_queues.is_empty() {
+                continue;
+            }
+
+            let payload = match serde_json::to_string(workload_state) {
+                Ok(payload) => payload,
+                Err(err) => {
+                    log::warn!("Could not serialize the notification: '{}'", err);
+                    continue;
+                }
+            };
+
+            for sink_queue in sink_queues {
+                if let Err(TrySendError::Full(_)) = sink_queue.sender.try_send(payload.clone()) {
+                    log::warn!(
+                        "Notification queue of sink '{}' is full, dropping notification.",
+                        sink_queue.name
+                    );
+                }
+            }
+        }
+    }
+}
+
+async fn deliver_notifications(
+    name: String,
+    sink: NotificationSink,
+    mut receiver: Receiver<String>,
+) {
+    while let Some(payload) = receiver.recv().await {
+        match tokio::time::timeout(NOTIFICATION_DELIVERY_TIMEOUT, sink.send(&payload)).await {
+            Ok(Ok(())) => log::trace!("Sent notification to sink '{}'.", name),
+            Ok(Err(err)) => log::warn!("Could not send notification to sink '{}': {}", name, err),
+            Err(_) => log::warn!("Sending notification to sink '{}' timed out.", name),
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use common::objects::{generate_test_workload_state_with_agent, ExecutionState};
+    use tokio::{
+        io::{AsyncBufReadExt, BufReader},
+        net::UnixListener,
+    };
+
+    use super::Notifier;
+    use crate::notifications::{NotificationFilter, NotificationSinkConfig, NotificationTarget};
+
+    const AGENT_A: &str = "agent_A";
+    const WORKLOAD_NAME_1: &str = "workload_1";
+    const WORKLOAD_NAME_2: &str = "workload_2";
+
+    // [utest->swdd~server-notifies-workload-state-changes~1]
+    #[tokio::test]
+    async fn utest_notifier_forwards_matching_workload_states() {
+        let socket_path =
+            std::env::temp_dir().join(format!("ank-server-notifier-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&socket_path);
+        let listener = UnixListener::bind(&socket_path).unwrap();
+
+        let notifier = Notifier::new(vec![NotificationSinkConfig {
+            target: NotificationTarget::UnixSocket {
+                path: socket_path.to_str().unwrap().to_string(),
+            },
+            filter: NotificationFilter {
+                states: vec!["Failed".to_string()],
+                ..Default::default()
+            },
+        }])
+        .unwrap();
+
+        let failed_state = generate_test_workload_state_with_agent(
+            WORKLOAD_NAME_2,
+            AGENT_A,
+            ExecutionState::failed("some error"),
+        );
+        notifier.notify_execution_states(&[
+            generate_test_workload_state_with_agent(
+                WORKLOAD_NAME_1,
+                AGENT_A,
+                ExecutionState::running(),
+            ),
+            failed_state.clone(),
+        ]);
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).await.unwrap();
+        assert_eq!(
+            line,
+            format!("{}\n", serde_json::to_string(&failed_state).unwrap())
+        );
+
+        std::fs::remove_file(&socket_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn utest_notifier_fails_on_invalid_sink_config() {
+        let notifier = Notifier::new(vec![NotificationSinkConfig {
+            target: NotificationTarget::Webhook {
+                url: "https://alerts.example.com".to_string(),
+            },
+            filter: NotificationFilter::default(),
+        }]);
+
+        assert!(notifier.is_err());
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use tokio::{io::AsyncWriteExt, net::UnixStream};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnixSocketSink {
+    path: PathBuf,
+}
+
+impl UnixSocketSink {
+    pub fn new(path: &str) -> Self {
+        UnixSocketSink { path: path.into() }
+    }
+
+    // The socket is connected for each notification such that a restarted receiver
+    // does not require a restart of the server.
+    // [impl->swdd~server-writes-notifications-to-unix-sockets~1]
+    pub async fn send(&self, payload: &str) -> Result<(), String> {
+        let mut stream = UnixStream::connect(&self.path).await.map_err(|err| {
+            format!(
+                "Could not connect to socket '{}': '{}'",
+                self.path.display(),
+                err
+            )
+        })?;
+
+        stream
+            .write_all(format!("{}\n", payload).as_bytes())
+            .await
+            .map_err(|err| {
+                format!(
+                    "Could not write to socket '{}': '{}'",
+                    self.path.display(),
+                    err
+                )
+            })
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncBufReadExt, BufReader},
+        net::UnixListener,
+    };
+
+    use super::UnixSocketSink;
+
+    // [utest->swdd~server-writes-notifications-to-unix-sockets~1]
+    #[tokio::test]
+    async fn utest_unix_socket_sink_writes_payload_as_line() {
+        let socket_path = std::env::temp_dir().join(format!(
+            "ank-server-notifications-{}.sock",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&socket_path);
+        let listener = UnixListener::bind(&socket_path).unwrap();
+
+        let sink = UnixSocketSink::new(socket_path.to_str().unwrap());
+        assert_eq!(sink.send(r#"{"key":"value"}"#).await, Ok(()));
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).await.unwrap();
+        assert_eq!(line, "{\"key\":\"value\"}\n");
+
+        std::fs::remove_file(&socket_path).unwrap();
+    }
+
+    // [utest->swdd~server-writes-notifications-to-unix-sockets~1]
+    #[tokio::test]
+    async fn utest_unix_socket_sink_fails_on_missing_socket() {
+        let sink = UnixSocketSink::new("/non/existing/notifications.sock");
+        assert!(sink.send("{}").await.is_err());
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpStream,
+};
+
+const HTTP_SCHEME: &str = "http://";
+const DEFAULT_HTTP_PORT: u16 = 80;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookSink {
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl WebhookSink {
+    // Only plain http is supported, https webhooks can be reached via a local relay.
+    pub fn new(url: &str) -> Result<Self, String> {
+        let address_and_path = url.strip_prefix(HTTP_SCHEME).ok_or_else(|| {
+            format!(
+                "Unsupported webhook url '{}': the url must start with '{}'.",
+                url, HTTP_SCHEME
+            )
+        })?;
+
+        let (address, path) = match address_and_path.find('/') {
+            Some(path_start) => address_and_path.split_at(path_start),
+            None => (address_and_path, "/"),
+        };
+
+        let (host, port) = match address.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse::<u16>()
+                    .map_err(|_| format!("Invalid port '{}' in webhook url '{}'.", port, url))?,
+            ),
+            None => (address, DEFAULT_HTTP_PORT),
+        };
+
+        if host.is_empty() {
+            return Err(format!("Missing host in webhook url '{}'.", url));
+        }
+
+        Ok(WebhookSink {
+            host: host.to_owned(),
+            port,
+            path: path.to_owned(),
+        })
+    }
+
+    // [impl->swdd~server-sends-notifications-to-webhooks~1]
+    pub async fn send(&self, payload: &str) -> Result<(), String> {
+        let mut stream = TcpStream::connect((self.host.as_str(), self.port))
+            .await
+            .map_err(|err| {
+                format!(
+                    "Could not connect to '{}:{}': '{}'",
+                    self.host, self.port, err
+                )
+            })?;
+
+        let request = format!(
+            "POST {} HTTP/1.1\r\nHost: {}:{}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.path,
+            self.host,
+            self.port,
+            payload.len(),
+            payload
+        );
+        stream
+            .write_all(request.as_bytes())
+            .await
+            .map_err(|err| format!("Could not send the request: '{}'", err))?;
+
+        let mut status_line = String::new();
+        BufReader::new(stream)
+            .read_line(&mut status_line)
+            .await
+            .map_err(|err| format!("Could not read the response: '{}'", err))?;
+
+        match status_line.split_whitespace().nth(1) {
+            Some(status) if status.starts_with('2') => Ok(()),
+            _ => Err(format!("Unexpected response '{}'", status_line.trim_end())),
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::WebhookSink;
+
+    #[test]
+    fn utest_webhook_sink_parses_url() {
+        assert_eq!(
+            WebhookSink::new("http://alerts.example.com:8080/ankaios/hook"),
+            Ok(WebhookSink {
+                host: "alerts.example.com".to_string(),
+                port: 8080,
+                path: "/ankaios/hook".to_string(),
+            })
+        );
+        assert_eq!(
+            WebhookSink::new("http://alerts.example.com"),
+            Ok(WebhookSink {
+                host: "alerts.example.com".to_string(),
+                port: 80,
+                path: "/".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn utest_webhook_sink_rejects_invalid_url() {
+        assert!(WebhookSink::new("https://alerts.example.com").is_err());
+        assert!(WebhookSink::new("alerts.example.com").is_err());
+        assert!(WebhookSink::new("http://alerts.example.com:port/").is_err());
+        assert!(WebhookSink::new("http://:8080/").is_err());
+    }
+
+    // [utest->swdd~server-sends-notifications-to-webhooks~1]
+    #[tokio::test]
+    async fn utest_webhook_sink_posts_payload() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = vec![0; 1024];
+            let length = stream.read(&mut buffer).await.unwrap();
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&buffer[..length]).to_string()
+        });
+
+        let sink = WebhookSink::new(&format!("http://127.0.0.1:{}/hook", port)).unwrap();
+        assert_eq!(sink.send(r#"{"key":"value"}"#).await, Ok(()));
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /hook HTTP/1.1\r\n"));
+        assert!(request.contains("Content-Length: 15\r\n"));
+        assert!(request.ends_with("\r\n\r\n{\"key\":\"value\"}"));
+    }
+
+    // [utest->swdd~server-sends-notifications-to-webhooks~1]
+    #[tokio::test]
+    async fn utest_webhook_sink_fails_on_error_response() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = vec![0; 1024];
+            let _ = stream.read(&mut buffer).await.unwrap();
+            stream
+                .write_all(b"HTTP/1.1 500 Internal Server Error\r\n\r\n")
+                .await
+                .unwrap();
+        });
+
+        let sink = WebhookSink::new(&format!("http://127.0.0.1:{}/", port)).unwrap();
+        assert!(sink.send("{}").await.is_err());
+    }
+}
@@ -0,0 +1,348 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fs, net::SocketAddr, path::Path};
+
+use grpc::connection_config::ConnectionConfig;
+use serde::Deserialize;
+
+use crate::ankaios_server::{
+    AdmissionConfig, AuditLogConfig, ConfigRenderingConfig, WorkloadStateHistoryConfig,
+};
+use crate::notifications::NotificationSinkConfig;
+
+pub const DEFAULT_SERVER_CONFIG_PATH: &str = "/etc/ankaios/ank-server.conf";
+
+/// Content of the server config file 'ank-server.conf' in TOML format
+// [impl->swdd~server-loads-server-config-file~1]
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct ServerConfig {
+    pub startup_manifest_dir: Option<String>,
+    /// Substitutes the environment variables in the startup manifests
+    #[serde(default)]
+    pub substitute_env_variables: bool,
+    #[serde(default)]
+    pub notifications: Vec<NotificationSinkConfig>,
+    pub metrics: Option<MetricsConfig>,
+    #[serde(default)]
+    pub admission: AdmissionConfig,
+    #[serde(default)]
+    pub grpc: ConnectionConfig,
+    #[serde(default)]
+    pub workload_state_history: WorkloadStateHistoryConfig,
+    #[serde(default)]
+    pub audit_log: AuditLogConfig,
+    #[serde(default)]
+    pub config_rendering: ConfigRenderingConfig,
+    pub replication: Option<ReplicationConfig>,
+}
+
+/// Configuration of the metrics endpoint, which is disabled if not configured
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+pub struct MetricsConfig {
+    pub bind_address: SocketAddr,
+}
+
+/// Configuration of the replication to standby servers, which is disabled if not configured
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+pub struct ReplicationConfig {
+    /// The names of the standby servers allowed to replicate the desired state
+    pub standbys: Vec<String>,
+}
+
+impl ServerConfig {
+    /// Loads the server config from the given path
+    ///
+    /// If no path is given, the default config file is used if it exists.
+    pub fn load(path: Option<&str>) -> Result<Self, String> {
+        match path {
+            Some(path) => Self::from_file(path),
+            None if Path::new(DEFAULT_SERVER_CONFIG_PATH).exists() => {
+                Self::from_file(DEFAULT_SERVER_CONFIG_PATH)
+            }
+            None => Ok(ServerConfig::default()),
+        }
+    }
+
+    fn from_file(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("Could not read the server config '{}': '{}'", path, err))?;
+        Self::from_toml(&content)
+            .map_err(|err| format!("Could not parse the server config '{}': '{}'", path, err))
+    }
+
+    fn from_toml(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|err| err.to_string())
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{MetricsConfig, ReplicationConfig, ServerConfig};
+    use crate::ankaios_server::{
+        AdmissionConfig, AuditLogConfig, ConfigRenderingConfig, WorkloadStateHistoryConfig,
+    };
+    use crate::notifications::{NotificationFilter, NotificationSinkConfig, NotificationTarget};
+    use grpc::connection_config::{Compression, ConnectionConfig};
+
+    // [utest->swdd~server-loads-server-config-file~1]
+    #[test]
+    fn utest_server_config_with_notification_sinks() {
+        let content = r#"
+            startup_manifest_dir = "/etc/ankaios/manifests.d"
+
+            [[notifications]]
+            type = "webhook"
+            url = "http://alerts.example.com:8080/ankaios"
+            agents = ["agent_A"]
+            states = ["Failed"]
+
+            [[notifications]]
+            type = "unix_socket"
+            path = "/run/ankaios/notifications.sock"
+            workloads = ["nginx"]
+        "#;
+
+        assert_eq!(
+            ServerConfig::from_toml(content),
+            Ok(ServerConfig {
+                startup_manifest_dir: Some("/etc/ankaios/manifests.d".to_string()),
+                substitute_env_variables: false,
+                notifications: vec![
+                    NotificationSinkConfig {
+                        target: NotificationTarget::Webhook {
+                            url: "http://alerts.example.com:8080/ankaios".to_string()
+                        },
+                        filter: NotificationFilter {
+                            agents: vec!["agent_A".to_string()],
+                            workloads: vec![],
+                            states: vec!["Failed".to_string()],
+                        },
+                    },
+                    NotificationSinkConfig {
+                        target: NotificationTarget::UnixSocket {
+                            path: "/run/ankaios/notifications.sock".to_string()
+                        },
+                        filter: NotificationFilter {
+                            agents: vec![],
+                            workloads: vec!["nginx".to_string()],
+                            states: vec![],
+                        },
+                    },
+                ],
+                metrics: None,
+                admission: AdmissionConfig::default(),
+                grpc: ConnectionConfig::default(),
+                workload_state_history: WorkloadStateHistoryConfig::default(),
+                audit_log: AuditLogConfig::default(),
+                config_rendering: ConfigRenderingConfig::default(),
+                replication: None,
+            })
+        );
+    }
+
+    // [utest->swdd~server-loads-server-config-file~1]
+    // [utest->swdd~server-substitutes-env-variables-in-startup-config~3]
+    #[test]
+    fn utest_server_config_with_env_substitution() {
+        let content = r#"
+            substitute_env_variables = true
+        "#;
+
+        assert_eq!(
+            ServerConfig::from_toml(content),
+            Ok(ServerConfig {
+                substitute_env_variables: true,
+                ..Default::default()
+            })
+        );
+    }
+
+    // [utest->swdd~server-loads-server-config-file~1]
+    #[test]
+    fn utest_server_config_with_metrics() {
+        let content = r#"
+            [metrics]
+            bind_address = "127.0.0.1:9100"
+        "#;
+
+        assert_eq!(
+            ServerConfig::from_toml(content),
+            Ok(ServerConfig {
+                metrics: Some(MetricsConfig {
+                    bind_address: "127.0.0.1:9100".parse().unwrap(),
+                }),
+                ..Default::default()
+            })
+        );
+    }
+
+    // [utest->swdd~server-loads-server-config-file~1]
+    #[test]
+    fn utest_server_config_with_workload_state_history() {
+        let content = r#"
+            [workload_state_history]
+            size = 50
+            file = "/var/lib/ankaios/workload-state-history.json"
+        "#;
+
+        assert_eq!(
+            ServerConfig::from_toml(content),
+            Ok(ServerConfig {
+                workload_state_history: WorkloadStateHistoryConfig {
+                    size: 50,
+                    file: Some("/var/lib/ankaios/workload-state-history.json".into()),
+                },
+                ..Default::default()
+            })
+        );
+    }
+
+    // [utest->swdd~server-loads-server-config-file~1]
+    #[test]
+    fn utest_server_config_with_audit_log() {
+        let content = r#"
+            [audit_log]
+            size = 100
+            file = "/var/log/ankaios/audit.log"
+            max_files = 3
+        "#;
+
+        assert_eq!(
+            ServerConfig::from_toml(content),
+            Ok(ServerConfig {
+                audit_log: AuditLogConfig {
+                    size: 100,
+                    file: Some("/var/log/ankaios/audit.log".into()),
+                    max_files: 3,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+        );
+    }
+
+    // [utest->swdd~server-loads-server-config-file~1]
+    #[test]
+    fn utest_server_config_with_config_rendering() {
+        let content = r#"
+            [config_rendering]
+            allowed_env_prefixes = ["APP_", "REGISTRY"]
+        "#;
+
+        assert_eq!(
+            ServerConfig::from_toml(content),
+            Ok(ServerConfig {
+                config_rendering: ConfigRenderingConfig {
+                    allowed_env_prefixes: vec!["APP_".to_string(), "REGISTRY".to_string()],
+                },
+                ..Default::default()
+            })
+        );
+    }
+
+    // [utest->swdd~server-loads-server-config-file~1]
+    #[test]
+    fn utest_server_config_with_replication() {
+        let content = r#"
+            [replication]
+            standbys = ["standby-1"]
+        "#;
+
+        assert_eq!(
+            ServerConfig::from_toml(content),
+            Ok(ServerConfig {
+                replication: Some(ReplicationConfig {
+                    standbys: vec!["standby-1".to_string()],
+                }),
+                ..Default::default()
+            })
+        );
+    }
+
+    // [utest->swdd~server-loads-server-config-file~1]
+    #[test]
+    fn utest_server_config_with_admission() {
+        let content = r#"
+            [admission]
+            max_workloads_per_agent = 10
+            forbidden_runtimes = ["podman-kube"]
+            required_tags = ["owner"]
+        "#;
+
+        assert_eq!(
+            ServerConfig::from_toml(content),
+            Ok(ServerConfig {
+                admission: AdmissionConfig {
+                    max_workloads_per_agent: Some(10),
+                    forbidden_runtimes: vec!["podman-kube".to_string()],
+                    required_tags: vec!["owner".to_string()],
+                },
+                ..Default::default()
+            })
+        );
+    }
+
+    // [utest->swdd~server-loads-server-config-file~1]
+    // [utest->swdd~grpc-supports-connection-tuning~1]
+    // [utest->swdd~grpc-supports-message-size-and-compression~1]
+    #[test]
+    fn utest_server_config_with_grpc_connection_tuning() {
+        let content = r#"
+            [grpc]
+            keepalive_interval_secs = 10
+            keepalive_timeout_secs = 5
+            compression = "gzip"
+        "#;
+
+        assert_eq!(
+            ServerConfig::from_toml(content),
+            Ok(ServerConfig {
+                grpc: ConnectionConfig {
+                    keepalive_interval_secs: Some(10),
+                    keepalive_timeout_secs: 5,
+                    compression: Compression::Gzip,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+        );
+    }
+
+    // [utest->swdd~server-loads-server-config-file~1]
+    #[test]
+    fn utest_server_config_empty() {
+        assert_eq!(ServerConfig::from_toml(""), Ok(ServerConfig::default()));
+    }
+
+    // [utest->swdd~server-loads-server-config-file~1]
+    #[test]
+    fn utest_server_config_unknown_sink_type() {
+        let content = r#"
+            [[notifications]]
+            type = "email"
+            address = "admin@example.com"
+        "#;
+
+        assert!(ServerConfig::from_toml(content).is_err());
+    }
+}
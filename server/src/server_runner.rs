@@ -0,0 +1,178 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use common::communications_server::{CommunicationsServer, ServerAddress};
+use common::metrics_endpoint::{bind_metrics_endpoint, serve_metrics};
+use common::std_extensions::GracefulExitResult;
+use grpc::{
+    replication_client::GRPCReplicationClient,
+    security::TLSConfig,
+    server::{StateReplicator, StreamErrorCounter},
+};
+use tokio::sync::mpsc;
+
+use crate::ankaios_server::{
+    create_from_server_channel, promotion_signal, AdmissionValidators, AnkaiosServer, Metrics,
+    StandbyServer, ToServerChannel,
+};
+use crate::notifications::Notifier;
+use crate::server_config::ServerConfig;
+use crate::startup_state_loader::load_startup_state;
+use crate::startup_state_reloader::StartupStateReloader;
+
+// The settings of a server started with run_server. The binary fills them from its command line
+// arguments and other processes, e.g., the development mode of the CLI, with their own values.
+pub struct ServerRunConfig {
+    pub startup_config: Option<String>,
+    pub startup_manifest_dir: Option<String>,
+    pub address: ServerAddress,
+    pub server_config: ServerConfig,
+    pub standby: Option<StandbyConfig>,
+    pub state_replicator: Option<StateReplicator>,
+    pub stream_errors: StreamErrorCounter,
+    pub workload_states_resync_interval: Option<Duration>,
+    pub agent_lost_grace_period: Option<Duration>,
+    pub agent_request_timeout: Option<Duration>,
+}
+
+pub struct StandbyConfig {
+    pub primary_address: String,
+    pub standby_name: String,
+    pub lease_timeout: Duration,
+    pub tls_config: Option<TLSConfig>,
+}
+
+// Runs the server until it is stopped or the communication middleware fails. The middleware is
+// created by the caller, as it differs between the binary and the development mode of the CLI.
+// [impl->swdd~server-runs-within-other-process~1]
+pub async fn run_server(
+    config: ServerRunConfig,
+    mut communications_server: impl CommunicationsServer + Send,
+    (to_server, server_receiver): ToServerChannel,
+) -> Result<(), String> {
+    let server_config = config.server_config;
+    let startup_manifest_dir = config
+        .startup_manifest_dir
+        .or(server_config.startup_manifest_dir);
+    let startup_state = load_startup_state(
+        config.startup_config.as_deref(),
+        startup_manifest_dir.as_deref(),
+        server_config.substitute_env_variables,
+    )
+    .map_err(|err| format!("Could not load the startup config: '{}'", err))?;
+    let startup_state_reloader = StartupStateReloader::new(
+        config.startup_config,
+        startup_manifest_dir,
+        startup_state.as_ref(),
+    )
+    .with_env_substitution(server_config.substitute_env_variables);
+
+    let (to_agents, agents_receiver) = create_from_server_channel(common::CHANNEL_CAPACITY);
+
+    let startup_state = match config.standby {
+        // [impl->swdd~server-starts-as-standby~2]
+        Some(standby) => {
+            log::info!(
+                "Starting as standby '{}' of the primary server '{}'",
+                standby.standby_name,
+                standby.primary_address
+            );
+            let replication_client = GRPCReplicationClient::new(
+                standby.standby_name,
+                standby.primary_address,
+                standby.tls_config,
+            )
+            .map_err(|err| format!("Invalid primary server address: '{}'", err))?
+            .with_connection_config(server_config.grpc);
+
+            let (events_tx, events_rx) = mpsc::channel(common::CHANNEL_CAPACITY);
+            let replication_task = tokio::spawn(async move {
+                replication_client
+                    .run(events_tx)
+                    .await
+                    .unwrap_or_exit("Replication from the primary server failed");
+            });
+            let replicated_state = StandbyServer::new(standby.lease_timeout)
+                .wait_for_promotion(events_rx, promotion_signal())
+                .await
+                .map_err(|err| format!("Standby server failed: '{}'", err))?;
+            replication_task.abort();
+
+            // the replicated state is more recent than the startup config
+            replicated_state.or(startup_state)
+        }
+        None => startup_state,
+    };
+
+    // [impl->swdd~server-provides-built-in-admission-validators~1]
+    let mut server = AnkaiosServer::new(server_receiver, to_agents)
+        .with_admission_validators(AdmissionValidators::new(&server_config.admission))
+        // [impl->swdd~server-records-workload-state-history~1]
+        .with_workload_state_history(&server_config.workload_state_history)
+        // [impl->swdd~server-records-audit-log~1]
+        .with_audit_log(&server_config.audit_log)
+        .with_config_rendering(&server_config.config_rendering);
+    if let Some(state_replicator) = config.state_replicator {
+        // [impl->swdd~server-replicates-desired-state~2]
+        server = server.with_state_replicator(state_replicator);
+    }
+    if !server_config.notifications.is_empty() {
+        // [impl->swdd~server-notifies-workload-state-changes~1]
+        server = server.with_notifier(
+            Notifier::new(server_config.notifications)
+                .map_err(|err| format!("Invalid notification config: '{}'", err))?,
+        );
+    }
+    if let Some(metrics_config) = server_config.metrics {
+        // [impl->swdd~server-provides-metrics-endpoint~1]
+        let listener = bind_metrics_endpoint(metrics_config.bind_address)
+            .await
+            .map_err(|err| format!("Could not start the metrics endpoint: '{}'", err))?;
+        log::info!(
+            "Providing metrics at 'http://{}/metrics'",
+            metrics_config.bind_address
+        );
+        let metrics = Metrics::new(config.stream_errors);
+        let rendered_metrics = metrics.clone();
+        tokio::spawn(serve_metrics(listener, move || rendered_metrics.render()));
+        server = server.with_metrics(metrics);
+    }
+    if let Some(interval) = config.workload_states_resync_interval {
+        // [impl->swdd~server-resyncs-workload-states~1]
+        server = server.with_workload_states_resync_interval(interval);
+    }
+    if let Some(grace_period) = config.agent_lost_grace_period {
+        // [impl->swdd~server-reschedules-workloads-of-lost-agents~1]
+        server = server.with_agent_lost_grace_period(grace_period);
+    }
+    if let Some(timeout) = config.agent_request_timeout {
+        // [impl->swdd~server-times-out-forwarded-agent-requests~2]
+        server = server.with_agent_request_timeout(timeout);
+    }
+
+    // [impl->swdd~server-reloads-startup-manifests-on-sighup~3]
+    tokio::spawn(startup_state_reloader.run(to_server));
+
+    tokio::select! {
+        // [impl->swdd~server-default-communication-grpc~1]
+        communication_result = communications_server.start(agents_receiver, config.address) => {
+            communication_result.map_err(|err| err.to_string())
+        }
+        server_result = server.start(startup_state) => {
+            server_result
+        }
+    }
+}
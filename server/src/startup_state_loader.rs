@@ -0,0 +1,492 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use common::objects::{CompleteState, State};
+
+use crate::env_substitution;
+
+const MANIFEST_FILE_EXTENSION: &str = "yaml";
+
+/// Loads the startup state from the startup manifest and all manifests of the startup manifest directory
+///
+/// Returns `None` if no manifest is provided. The environment variables are only substituted in
+/// the manifests if `substitute_env_variables` is set.
+pub fn load_startup_state(
+    manifest_path: Option<&str>,
+    manifest_dir: Option<&str>,
+    substitute_env_variables: bool,
+) -> Result<Option<CompleteState>, String> {
+    let mut manifest_paths: Vec<PathBuf> = manifest_path.map(PathBuf::from).into_iter().collect();
+    if let Some(manifest_dir) = manifest_dir {
+        manifest_paths.extend(manifests_in_dir(Path::new(manifest_dir))?);
+    }
+
+    // [impl->swdd~server-starts-without-startup-config~1]
+    if manifest_paths.is_empty() {
+        return Ok(None);
+    }
+
+    let mut merger = ManifestMerger::default();
+    for path in manifest_paths {
+        merger.merge(read_manifest(&path, substitute_env_variables)?, &path)?;
+    }
+
+    let state = merger.state.unwrap_or_default();
+    log::trace!(
+        "The state is initialized with the following workloads: {:?}",
+        state.workloads
+    );
+    Ok(Some(CompleteState {
+        desired_state: state,
+        ..Default::default()
+    }))
+}
+
+// [impl->swdd~server-loads-startup-manifest-dir~1]
+fn manifests_in_dir(manifest_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(manifest_dir).map_err(|err| {
+        format!(
+            "Could not read the startup manifest directory '{}': '{}'",
+            manifest_dir.display(),
+            err
+        )
+    })?;
+
+    let mut manifest_paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path.extension().and_then(|extension| extension.to_str())
+                    == Some(MANIFEST_FILE_EXTENSION)
+        })
+        .collect::<Vec<_>>();
+    manifest_paths.sort();
+    Ok(manifest_paths)
+}
+
+fn read_manifest(path: &Path, substitute_env_variables: bool) -> Result<State, String> {
+    let data = fs::read_to_string(path)
+        .map_err(|err| format!("Could not read '{}': '{}'", path.display(), err))?;
+    // [impl->swdd~server-state-in-memory~1]
+    // [impl->swdd~server-loads-startup-state-file~3]
+    let parse_error = |err: serde_yaml::Error| {
+        format!("Parsing '{}' failed with error: '{}'", path.display(), err)
+    };
+    let mut manifest: State = if substitute_env_variables {
+        let mut manifest: serde_yaml::Value = serde_yaml::from_str(&data).map_err(parse_error)?;
+        // [impl->swdd~server-substitutes-env-variables-in-startup-config~3]
+        env_substitution::substitute_env_variables(&mut manifest).map_err(|err| {
+            format!(
+                "Could not substitute the environment variables in '{}': '{}'",
+                path.display(),
+                err
+            )
+        })?;
+        serde_yaml::from_value(manifest).map_err(parse_error)?
+    } else {
+        serde_yaml::from_str(&data).map_err(parse_error)?
+    };
+    // [impl->swdd~server-merges-startup-manifests~3]
+    manifest
+        .migrate_to_current_api_version()
+        .map_err(|err| format!("Loading '{}' failed: '{}'", path.display(), err))?;
+    Ok(manifest)
+}
+
+#[derive(Default)]
+struct ManifestMerger {
+    state: Option<State>,
+    workload_origins: HashMap<String, PathBuf>,
+    config_origins: HashMap<String, PathBuf>,
+    namespace_origins: HashMap<String, PathBuf>,
+    image_origins: HashMap<(String, String), PathBuf>,
+}
+
+impl ManifestMerger {
+    // [impl->swdd~server-merges-startup-manifests~3]
+    fn merge(&mut self, manifest: State, path: &Path) -> Result<(), String> {
+        let Some(state) = &mut self.state else {
+            self.workload_origins = manifest
+                .workloads
+                .keys()
+                .map(|name| (name.clone(), path.to_path_buf()))
+                .collect();
+            self.config_origins = manifest
+                .configs
+                .keys()
+                .map(|name| (name.clone(), path.to_path_buf()))
+                .collect();
+            self.namespace_origins = manifest
+                .namespaces
+                .keys()
+                .map(|name| (name.clone(), path.to_path_buf()))
+                .collect();
+            self.image_origins = manifest
+                .images
+                .iter()
+                .flat_map(|(agent_name, images)| {
+                    images
+                        .iter()
+                        .map(move |image| ((agent_name.clone(), image.clone()), path.to_path_buf()))
+                })
+                .collect();
+            self.state = Some(manifest);
+            return Ok(());
+        };
+
+        if !manifest.defaults.is_empty() {
+            if !state.defaults.is_empty() && state.defaults != manifest.defaults {
+                return Err(format!(
+                    "The workload defaults of '{}' conflict with the workload defaults of the other manifests",
+                    path.display()
+                ));
+            }
+            state.defaults = manifest.defaults;
+        }
+
+        for (name, workload) in manifest.workloads {
+            if let Some(origin) = self.workload_origins.get(&name) {
+                return Err(format!(
+                    "The workload '{}' of '{}' is already defined in '{}'",
+                    name,
+                    path.display(),
+                    origin.display()
+                ));
+            }
+            self.workload_origins
+                .insert(name.clone(), path.to_path_buf());
+            state.workloads.insert(name, workload);
+        }
+
+        for (name, config) in manifest.configs {
+            if let Some(origin) = self.config_origins.get(&name) {
+                return Err(format!(
+                    "The config '{}' of '{}' is already defined in '{}'",
+                    name,
+                    path.display(),
+                    origin.display()
+                ));
+            }
+            self.config_origins.insert(name.clone(), path.to_path_buf());
+            state.configs.insert(name, config);
+        }
+
+        // a namespace belongs to a single manifest, thus also its workloads are never merged
+        for (name, namespace) in manifest.namespaces {
+            if let Some(origin) = self.namespace_origins.get(&name) {
+                return Err(format!(
+                    "The namespace '{}' of '{}' is already defined in '{}'",
+                    name,
+                    path.display(),
+                    origin.display()
+                ));
+            }
+            self.namespace_origins
+                .insert(name.clone(), path.to_path_buf());
+            state.namespaces.insert(name, namespace);
+        }
+
+        // the images of an agent can be spread over multiple manifests
+        for (agent_name, images) in manifest.images {
+            for image in images {
+                let image_key = (agent_name.clone(), image);
+                if let Some(origin) = self.image_origins.get(&image_key) {
+                    return Err(format!(
+                        "The image '{}' of agent '{}' of '{}' is already defined in '{}'",
+                        image_key.1,
+                        agent_name,
+                        path.display(),
+                        origin.display()
+                    ));
+                }
+                state
+                    .images
+                    .entry(agent_name.clone())
+                    .or_default()
+                    .push(image_key.1.clone());
+                self.image_origins.insert(image_key, path.to_path_buf());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use common::objects::CURRENT_API_VERSION;
+
+    use super::load_startup_state;
+
+    const MANIFEST_A: &str = r#"
+apiVersion: v0.1
+workloads:
+  workload_A:
+    runtime: podman
+    agent: agent_A
+    runtimeConfig: |
+      image: alpine:latest
+configs:
+  config_A: value_A
+"#;
+
+    const MANIFEST_B: &str = r#"
+apiVersion: v0.1
+workloads:
+  workload_B:
+    runtime: podman
+    agent: agent_B
+    runtimeConfig: |
+      image: alpine:latest
+"#;
+
+    const MANIFEST_NAMESPACE_A: &str = r#"
+apiVersion: v0.2
+namespaces:
+  team-a:
+    workloads:
+      nginx:
+        runtime: podman
+        agent: agent_A
+        runtimeConfig: |
+          image: nginx:latest
+"#;
+
+    const MANIFEST_IMAGES: &str = r#"
+apiVersion: v0.2
+images:
+  agent_A:
+    - alpine:latest
+"#;
+
+    fn write_file(dir: &Path, name: &str, content: &str) {
+        fs::write(dir.join(name), content).unwrap();
+    }
+
+    // [utest->swdd~server-starts-without-startup-config~1]
+    #[test]
+    fn utest_load_startup_state_without_manifests() {
+        assert_eq!(load_startup_state(None, None, false), Ok(None));
+    }
+
+    // [utest->swdd~server-loads-startup-manifest-dir~1]
+    // [utest->swdd~server-merges-startup-manifests~3]
+    #[test]
+    fn utest_load_startup_state_merges_manifest_and_manifest_dir() {
+        let manifest_dir = tempfile::tempdir().unwrap();
+        let manifest_file = tempfile::tempdir().unwrap();
+        write_file(manifest_file.path(), "startup.yaml", MANIFEST_A);
+        write_file(manifest_dir.path(), "10-workload-b.yaml", MANIFEST_B);
+        write_file(manifest_dir.path(), "README.md", "not a manifest");
+
+        let startup_state = load_startup_state(
+            manifest_file.path().join("startup.yaml").to_str(),
+            manifest_dir.path().to_str(),
+            false,
+        )
+        .unwrap()
+        .unwrap();
+
+        let mut workload_names = startup_state
+            .desired_state
+            .workloads
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        workload_names.sort();
+        assert_eq!(workload_names, vec!["workload_A", "workload_B"]);
+        assert!(startup_state.desired_state.configs.contains_key("config_A"));
+    }
+
+    // [utest->swdd~server-loads-startup-manifest-dir~1]
+    #[test]
+    fn utest_load_startup_state_empty_manifest_dir() {
+        let manifest_dir = tempfile::tempdir().unwrap();
+
+        assert_eq!(
+            load_startup_state(None, manifest_dir.path().to_str(), false),
+            Ok(None)
+        );
+    }
+
+    // [utest->swdd~server-loads-startup-manifest-dir~1]
+    #[test]
+    fn utest_load_startup_state_fails_on_missing_manifest_dir() {
+        assert!(load_startup_state(None, Some("/not/existing/manifest/dir"), false).is_err());
+    }
+
+    // [utest->swdd~server-merges-startup-manifests~3]
+    #[test]
+    fn utest_load_startup_state_fails_on_duplicate_workload_name() {
+        let manifest_dir = tempfile::tempdir().unwrap();
+        write_file(manifest_dir.path(), "01-first.yaml", MANIFEST_A);
+        write_file(manifest_dir.path(), "02-second.yaml", MANIFEST_A);
+
+        let error = load_startup_state(None, manifest_dir.path().to_str(), false).unwrap_err();
+
+        assert!(error.contains("workload_A"));
+        assert!(error.contains("02-second.yaml"));
+        assert!(error.contains("01-first.yaml"));
+    }
+
+    // [utest->swdd~server-merges-startup-manifests~3]
+    #[test]
+    fn utest_load_startup_state_migrates_api_versions() {
+        let manifest_dir = tempfile::tempdir().unwrap();
+        write_file(manifest_dir.path(), "01-first.yaml", MANIFEST_A);
+        write_file(
+            manifest_dir.path(),
+            "02-second.yaml",
+            &MANIFEST_B.replace("v0.1", CURRENT_API_VERSION),
+        );
+
+        let startup_state = load_startup_state(None, manifest_dir.path().to_str(), false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(startup_state.desired_state.api_version, CURRENT_API_VERSION);
+        assert_eq!(startup_state.desired_state.workloads.len(), 2);
+    }
+
+    // [utest->swdd~server-merges-startup-manifests~3]
+    #[test]
+    fn utest_load_startup_state_fails_on_unsupported_api_version() {
+        let manifest_dir = tempfile::tempdir().unwrap();
+        write_file(manifest_dir.path(), "01-first.yaml", MANIFEST_A);
+        write_file(
+            manifest_dir.path(),
+            "02-second.yaml",
+            &MANIFEST_B.replace("v0.1", "v0.0"),
+        );
+
+        assert!(load_startup_state(None, manifest_dir.path().to_str(), false).is_err());
+    }
+
+    // [utest->swdd~server-substitutes-env-variables-in-startup-config~3]
+    #[test]
+    fn utest_load_startup_state_substitutes_env_variables_only_if_enabled() {
+        let manifest_dir = tempfile::tempdir().unwrap();
+        write_file(
+            manifest_dir.path(),
+            "startup.yaml",
+            &MANIFEST_B.replace("agent_B", "${NOT_SET_AGENT_NAME:-agent_C}"),
+        );
+
+        let startup_state = load_startup_state(None, manifest_dir.path().to_str(), true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            startup_state.desired_state.workloads["workload_B"].agent,
+            "agent_C"
+        );
+
+        let startup_state = load_startup_state(None, manifest_dir.path().to_str(), false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            startup_state.desired_state.workloads["workload_B"].agent,
+            "${NOT_SET_AGENT_NAME:-agent_C}"
+        );
+    }
+
+    // [utest->swdd~server-merges-startup-manifests~3]
+    #[test]
+    fn utest_load_startup_state_merges_namespaces() {
+        let manifest_dir = tempfile::tempdir().unwrap();
+        write_file(manifest_dir.path(), "01-team-a.yaml", MANIFEST_NAMESPACE_A);
+        write_file(
+            manifest_dir.path(),
+            "02-team-b.yaml",
+            &MANIFEST_NAMESPACE_A.replace("team-a", "team-b"),
+        );
+
+        let startup_state = load_startup_state(None, manifest_dir.path().to_str(), false)
+            .unwrap()
+            .unwrap();
+
+        let namespaces = &startup_state.desired_state.namespaces;
+        assert_eq!(namespaces.len(), 2);
+        assert!(namespaces["team-a"].workloads.contains_key("nginx"));
+        assert!(namespaces["team-b"].workloads.contains_key("nginx"));
+    }
+
+    // [utest->swdd~server-merges-startup-manifests~3]
+    #[test]
+    fn utest_load_startup_state_fails_on_duplicate_namespace() {
+        let manifest_dir = tempfile::tempdir().unwrap();
+        write_file(manifest_dir.path(), "01-first.yaml", MANIFEST_NAMESPACE_A);
+        write_file(
+            manifest_dir.path(),
+            "02-second.yaml",
+            &MANIFEST_NAMESPACE_A.replace("nginx:", "other:"),
+        );
+
+        let error = load_startup_state(None, manifest_dir.path().to_str(), false).unwrap_err();
+
+        assert!(error.contains("team-a"));
+        assert!(error.contains("02-second.yaml"));
+        assert!(error.contains("01-first.yaml"));
+    }
+
+    // [utest->swdd~server-merges-startup-manifests~3]
+    #[test]
+    fn utest_load_startup_state_merges_images() {
+        let manifest_dir = tempfile::tempdir().unwrap();
+        write_file(manifest_dir.path(), "01-first.yaml", MANIFEST_IMAGES);
+        write_file(
+            manifest_dir.path(),
+            "02-second.yaml",
+            &format!("{}  agent_B:\n    - nginx:latest\n", MANIFEST_IMAGES)
+                .replace("alpine:latest", "busybox:latest"),
+        );
+
+        let startup_state = load_startup_state(None, manifest_dir.path().to_str(), false)
+            .unwrap()
+            .unwrap();
+
+        let images = &startup_state.desired_state.images;
+        assert_eq!(images["agent_A"], vec!["alpine:latest", "busybox:latest"]);
+        assert_eq!(images["agent_B"], vec!["nginx:latest"]);
+    }
+
+    // [utest->swdd~server-merges-startup-manifests~3]
+    #[test]
+    fn utest_load_startup_state_fails_on_duplicate_image_of_agent() {
+        let manifest_dir = tempfile::tempdir().unwrap();
+        write_file(manifest_dir.path(), "01-first.yaml", MANIFEST_IMAGES);
+        write_file(manifest_dir.path(), "02-second.yaml", MANIFEST_IMAGES);
+
+        let error = load_startup_state(None, manifest_dir.path().to_str(), false).unwrap_err();
+
+        assert!(error.contains("alpine:latest"));
+        assert!(error.contains("agent_A"));
+        assert!(error.contains("02-second.yaml"));
+        assert!(error.contains("01-first.yaml"));
+    }
+}
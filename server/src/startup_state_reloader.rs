@@ -0,0 +1,451 @@
+// Copyright (c) 2024 Elektrobit Automotive GmbH
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License, Version 2.0 which is available at
+// https://www.apache.org/licenses/LICENSE-2.0.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeSet;
+
+use common::objects::{CompleteState, State};
+use common::request_id_prepending::{prepend_request_id, SERVER_INTERNAL_REQUEST_PREFIX};
+use common::std_extensions::IllegalStateResult;
+use common::to_server_interface::{ToServerInterface, ToServerSender};
+use tokio::signal::unix::{signal, SignalKind};
+
+use crate::startup_state_loader::load_startup_state;
+
+const DESIRED_STATE_WORKLOADS: &str = "desiredState.workloads";
+const DESIRED_STATE_CONFIGS: &str = "desiredState.configs";
+const DESIRED_STATE_DEFAULTS: &str = "desiredState.defaults";
+const DESIRED_STATE_NAMESPACES: &str = "desiredState.namespaces";
+const DESIRED_STATE_IMAGES: &str = "desiredState.images";
+
+/// Reloads the startup manifests on SIGHUP and applies the changes as update of the desired state
+///
+/// Only the workloads, configs, namespaces and images of the agents defined by the previously loaded or the reloaded
+/// startup manifests are updated, such that workloads added with the CLI in the meantime are kept.
+pub struct StartupStateReloader {
+    manifest_path: Option<String>,
+    manifest_dir: Option<String>,
+    substitute_env_variables: bool,
+    loaded_state: State,
+    reload_count: usize,
+}
+
+impl StartupStateReloader {
+    pub fn new(
+        manifest_path: Option<String>,
+        manifest_dir: Option<String>,
+        startup_state: Option<&CompleteState>,
+    ) -> Self {
+        StartupStateReloader {
+            manifest_path,
+            manifest_dir,
+            substitute_env_variables: false,
+            loaded_state: startup_state
+                .map(|startup_state| startup_state.desired_state.clone())
+                .unwrap_or_default(),
+            reload_count: 0,
+        }
+    }
+
+    /// Substitutes the environment variables in the reloaded manifests like in the loaded ones
+    pub fn with_env_substitution(mut self, substitute_env_variables: bool) -> Self {
+        self.substitute_env_variables = substitute_env_variables;
+        self
+    }
+
+    // [impl->swdd~server-reloads-startup-manifests-on-sighup~3]
+    pub async fn run(mut self, to_server: ToServerSender) {
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(err) => {
+                log::warn!(
+                    "Could not register the handler for SIGHUP, the startup manifests are not reloaded: '{}'",
+                    err
+                );
+                return;
+            }
+        };
+        while hangup.recv().await.is_some() {
+            self.reload(&to_server).await;
+        }
+    }
+
+    async fn reload(&mut self, to_server: &ToServerSender) {
+        log::info!("Received SIGHUP, reloading the startup manifests");
+        let reloaded_state = match load_startup_state(
+            self.manifest_path.as_deref(),
+            self.manifest_dir.as_deref(),
+            self.substitute_env_variables,
+        ) {
+            Ok(startup_state) => startup_state
+                .map(|startup_state| startup_state.desired_state)
+                .unwrap_or_default(),
+            Err(err) => {
+                // [impl->swdd~server-keeps-desired-state-on-invalid-reloaded-manifests~1]
+                log::error!(
+                    "Keeping the desired state, the startup manifests could not be reloaded: '{}'",
+                    err
+                );
+                return;
+            }
+        };
+
+        let update_mask = reload_update_mask(&self.loaded_state, &reloaded_state);
+        // an empty update mask would replace the complete desired state
+        if reloaded_state == self.loaded_state || update_mask.is_empty() {
+            log::info!("The startup manifests are unchanged, nothing to update");
+            return;
+        }
+
+        self.reload_count += 1;
+        let request_id = prepend_request_id(
+            &format!("startup-manifest-reload-{}", self.reload_count),
+            SERVER_INTERNAL_REQUEST_PREFIX,
+        );
+        log::debug!(
+            "Requesting the update of the desired state with '{}' and update mask '{:?}'",
+            request_id,
+            update_mask
+        );
+        to_server
+            .update_state(
+                request_id,
+                CompleteState {
+                    desired_state: reloaded_state.clone(),
+                    ..Default::default()
+                },
+                update_mask,
+            )
+            .await
+            .unwrap_or_illegal_state();
+        self.loaded_state = reloaded_state;
+    }
+}
+
+fn entry_masks<'a>(
+    field: &'a str,
+    loaded_entries: impl Iterator<Item = &'a String> + 'a,
+    reloaded_entries: impl Iterator<Item = &'a String> + 'a,
+) -> impl Iterator<Item = String> + 'a {
+    loaded_entries
+        .chain(reloaded_entries)
+        .map(move |name| format!("{}.{}", field, name))
+}
+
+// The workloads, configs, namespaces and images of agents removed from the startup manifests are deleted
+// as they are part of the update mask, but not of the reloaded state.
+// [impl->swdd~server-reloads-startup-manifests-on-sighup~3]
+fn reload_update_mask(loaded_state: &State, reloaded_state: &State) -> Vec<String> {
+    let workload_masks = entry_masks(
+        DESIRED_STATE_WORKLOADS,
+        loaded_state.workloads.keys(),
+        reloaded_state.workloads.keys(),
+    );
+    let config_masks = entry_masks(
+        DESIRED_STATE_CONFIGS,
+        loaded_state.configs.keys(),
+        reloaded_state.configs.keys(),
+    );
+    let namespace_masks = entry_masks(
+        DESIRED_STATE_NAMESPACES,
+        loaded_state.namespaces.keys(),
+        reloaded_state.namespaces.keys(),
+    );
+    let image_masks = entry_masks(
+        DESIRED_STATE_IMAGES,
+        loaded_state.images.keys(),
+        reloaded_state.images.keys(),
+    );
+    let mut update_mask: Vec<String> = workload_masks
+        .chain(config_masks)
+        .chain(namespace_masks)
+        .chain(image_masks)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    if loaded_state.defaults != reloaded_state.defaults {
+        update_mask.push(DESIRED_STATE_DEFAULTS.to_owned());
+    }
+    update_mask
+}
+
+//////////////////////////////////////////////////////////////////////////////
+//                 ########  #######    #########  #########                //
+//                    ##     ##        ##             ##                    //
+//                    ##     #####     #########      ##                    //
+//                    ##     ##                ##     ##                    //
+//                    ##     #######   #########      ##                    //
+//////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+
+    use common::commands::{Request, RequestContent};
+    use common::objects::{
+        generate_test_stored_workload_spec, CompleteState, ConfigItem, Namespace, State,
+    };
+    use common::to_server_interface::ToServer;
+    use tokio::sync::mpsc;
+
+    use super::{reload_update_mask, StartupStateReloader};
+
+    const AGENT_A: &str = "agent_A";
+    const RUNTIME: &str = "podman";
+
+    fn state(workload_names: &[&str], config_names: &[&str]) -> State {
+        State {
+            workloads: workload_names
+                .iter()
+                .map(|name| {
+                    (
+                        name.to_string(),
+                        generate_test_stored_workload_spec(AGENT_A, RUNTIME),
+                    )
+                })
+                .collect(),
+            configs: config_names
+                .iter()
+                .map(|name| (name.to_string(), ConfigItem::String("value".to_string())))
+                .collect::<HashMap<_, _>>(),
+            ..Default::default()
+        }
+    }
+
+    // [utest->swdd~server-reloads-startup-manifests-on-sighup~3]
+    #[test]
+    fn utest_reload_update_mask_contains_previous_and_reloaded_entries() {
+        let update_mask = reload_update_mask(
+            &state(&["workload_A", "workload_B"], &["config_A"]),
+            &state(&["workload_B", "workload_C"], &[]),
+        );
+
+        assert_eq!(
+            update_mask,
+            vec![
+                "desiredState.configs.config_A".to_string(),
+                "desiredState.workloads.workload_A".to_string(),
+                "desiredState.workloads.workload_B".to_string(),
+                "desiredState.workloads.workload_C".to_string(),
+            ]
+        );
+        assert!(reload_update_mask(&State::default(), &State::default()).is_empty());
+    }
+
+    // [utest->swdd~server-reloads-startup-manifests-on-sighup~3]
+    #[test]
+    fn utest_reload_update_mask_contains_namespaces() {
+        let mut loaded_state = state(&[], &[]);
+        loaded_state
+            .namespaces
+            .insert("team_a".to_string(), Namespace::default());
+        let mut reloaded_state = state(&[], &[]);
+        reloaded_state
+            .namespaces
+            .insert("team_b".to_string(), Namespace::default());
+
+        assert_eq!(
+            reload_update_mask(&loaded_state, &reloaded_state),
+            vec![
+                "desiredState.namespaces.team_a".to_string(),
+                "desiredState.namespaces.team_b".to_string(),
+            ]
+        );
+    }
+
+    // [utest->swdd~server-reloads-startup-manifests-on-sighup~3]
+    #[test]
+    fn utest_reload_update_mask_contains_images_of_agents() {
+        let mut loaded_state = state(&[], &[]);
+        loaded_state
+            .images
+            .insert("agent_A".to_string(), vec!["nginx:1.25".to_string()]);
+        let mut reloaded_state = state(&[], &[]);
+        reloaded_state
+            .images
+            .insert("agent_B".to_string(), vec!["nginx:1.26".to_string()]);
+
+        assert_eq!(
+            reload_update_mask(&loaded_state, &reloaded_state),
+            vec![
+                "desiredState.images.agent_A".to_string(),
+                "desiredState.images.agent_B".to_string(),
+            ]
+        );
+    }
+
+    // [utest->swdd~server-reloads-startup-manifests-on-sighup~3]
+    #[tokio::test]
+    async fn utest_reload_sends_update_state_request_for_changed_images() {
+        let manifest_dir = tempfile::tempdir().unwrap();
+        let manifest_path = manifest_dir.path().join("startup.yaml");
+        fs::write(
+            &manifest_path,
+            "apiVersion: v0.2\nimages:\n  agent_A:\n    - nginx:1.26\n",
+        )
+        .unwrap();
+        let (to_server, mut server_receiver) = mpsc::channel(1);
+
+        let mut loaded_state = State::default();
+        loaded_state
+            .images
+            .insert("agent_A".to_string(), vec!["nginx:1.25".to_string()]);
+        let mut reloader = StartupStateReloader::new(
+            Some(manifest_path.display().to_string()),
+            None,
+            Some(&CompleteState {
+                desired_state: loaded_state,
+                ..Default::default()
+            }),
+        );
+        reloader.reload(&to_server).await;
+
+        let Some(ToServer::Request(Request {
+            request_content: RequestContent::UpdateStateRequest(update_state_request),
+            ..
+        })) = server_receiver.recv().await
+        else {
+            panic!("Expected an update state request");
+        };
+        assert_eq!(
+            update_state_request.update_mask,
+            vec!["desiredState.images.agent_A".to_string()]
+        );
+        assert_eq!(
+            update_state_request.state.desired_state.images["agent_A"],
+            vec!["nginx:1.26".to_string()]
+        );
+    }
+
+    // [utest->swdd~server-reloads-startup-manifests-on-sighup~3]
+    #[tokio::test]
+    async fn utest_reload_sends_update_state_request_for_changed_namespace() {
+        let manifest_dir = tempfile::tempdir().unwrap();
+        let manifest_path = manifest_dir.path().join("startup.yaml");
+        fs::write(
+            &manifest_path,
+            concat!(
+                "apiVersion: v0.2\n",
+                "namespaces:\n",
+                "  team_a:\n",
+                "    workloads:\n",
+                "      nginx:\n",
+                "        runtime: podman\n",
+                "        agent: agent_B\n",
+                "        runtimeConfig: \"\"\n"
+            ),
+        )
+        .unwrap();
+        let (to_server, mut server_receiver) = mpsc::channel(1);
+
+        let mut loaded_state = State::default();
+        loaded_state.namespaces.insert(
+            "team_a".to_string(),
+            Namespace {
+                workloads: HashMap::from([(
+                    "nginx".to_string(),
+                    generate_test_stored_workload_spec(AGENT_A, RUNTIME),
+                )]),
+            },
+        );
+        let mut reloader = StartupStateReloader::new(
+            Some(manifest_path.display().to_string()),
+            None,
+            Some(&CompleteState {
+                desired_state: loaded_state,
+                ..Default::default()
+            }),
+        );
+        reloader.reload(&to_server).await;
+
+        let Some(ToServer::Request(Request {
+            request_content: RequestContent::UpdateStateRequest(update_state_request),
+            ..
+        })) = server_receiver.recv().await
+        else {
+            panic!("Expected an update state request");
+        };
+        assert_eq!(
+            update_state_request.update_mask,
+            vec!["desiredState.namespaces.team_a".to_string()]
+        );
+        assert_eq!(
+            update_state_request.state.desired_state.namespaces["team_a"].workloads["nginx"].agent,
+            "agent_B"
+        );
+    }
+
+    // [utest->swdd~server-reloads-startup-manifests-on-sighup~3]
+    #[tokio::test]
+    async fn utest_reload_sends_update_state_request() {
+        let manifest_dir = tempfile::tempdir().unwrap();
+        let manifest_path = manifest_dir.path().join("startup.yaml");
+        fs::write(
+            &manifest_path,
+            "apiVersion: v0.1\nworkloads:\n  workload_B:\n    runtime: podman\n    agent: agent_A\n    runtimeConfig: \"\"\n",
+        )
+        .unwrap();
+        let (to_server, mut server_receiver) = mpsc::channel(1);
+
+        let mut reloader = StartupStateReloader::new(
+            Some(manifest_path.display().to_string()),
+            None,
+            Some(&CompleteState {
+                desired_state: state(&["workload_A"], &[]),
+                ..Default::default()
+            }),
+        );
+        reloader.reload(&to_server).await;
+
+        let Some(ToServer::Request(Request {
+            request_id,
+            request_content: RequestContent::UpdateStateRequest(update_state_request),
+        })) = server_receiver.recv().await
+        else {
+            panic!("Expected an update state request");
+        };
+        assert_eq!(request_id, "ank.server@startup-manifest-reload-1");
+        assert_eq!(
+            update_state_request.update_mask,
+            vec![
+                "desiredState.workloads.workload_A".to_string(),
+                "desiredState.workloads.workload_B".to_string(),
+            ]
+        );
+        let desired_workloads = &update_state_request.state.desired_state.workloads;
+        assert_eq!(
+            desired_workloads.keys().collect::<Vec<_>>(),
+            vec!["workload_B"]
+        );
+
+        // the second reload of the same manifests does not update the desired state
+        reloader.reload(&to_server).await;
+        assert!(server_receiver.try_recv().is_err());
+    }
+
+    // [utest->swdd~server-keeps-desired-state-on-invalid-reloaded-manifests~1]
+    #[tokio::test]
+    async fn utest_reload_keeps_desired_state_on_invalid_manifest() {
+        let manifest_dir = tempfile::tempdir().unwrap();
+        let manifest_path = manifest_dir.path().join("startup.yaml");
+        fs::write(&manifest_path, "apiVersion: v0.1\nworkloads: [invalid]\n").unwrap();
+        let (to_server, mut server_receiver) = mpsc::channel(1);
+
+        let mut reloader =
+            StartupStateReloader::new(Some(manifest_path.display().to_string()), None, None);
+        reloader.reload(&to_server).await;
+
+        assert!(server_receiver.try_recv().is_err());
+    }
+}
@@ -27,10 +27,14 @@ use std::{
     io::{Read, Write},
     path::Path,
     process::exit,
+    thread::sleep,
+    time::Duration,
 };
 
 const ANKAIOS_CONTROL_INTERFACE_BASE_PATH: &str = "/run/ankaios/control_interface";
 const MAX_VARINT_SIZE: usize = 19;
+// a length prefix followed by bytes that are not a valid protobuf message
+const MALFORMED_MESSAGE: [u8; 5] = [4, 0xff, 0xff, 0xff, 0xff];
 
 mod logging {
     pub fn log(msg: &str) {
@@ -58,6 +62,9 @@ enum CommandEnum {
     UpdateState(UpdateState),
     GetState(GetState),
     SendHello(Version),
+    SendMalformedMessage,
+    DelayRead(DelayRead),
+    CloseConnectionDuringRequest,
 }
 
 #[derive(Deserialize)]
@@ -76,6 +83,11 @@ struct GetState {
     field_mask: Vec<String>,
 }
 
+#[derive(Deserialize)]
+struct DelayRead {
+    delay_ms: u64,
+}
+
 #[derive(Serialize)]
 struct TestResult {
     result: TestResultEnum,
@@ -88,6 +100,9 @@ enum TestResultEnum {
     GetStateResult(TagSerializedResult<Option<State>>),
     NoApi,
     SendHelloResult(TagSerializedResult<()>),
+    SendMalformedMessageResult(TagSerializedResult<()>),
+    DelayReadResult(TagSerializedResult<()>),
+    CloseConnectionDuringRequestResult(TagSerializedResult<()>),
     ConnectionClosed,
 }
 
@@ -180,14 +195,27 @@ fn write_result(output_path: String, result: Vec<TestResult>) {
     });
 }
 
-struct Connection {
-    id_counter: i32,
+struct Pipes {
     output: File,
     input: File,
 }
 
+struct Connection {
+    id_counter: i32,
+    read_delay: Duration,
+    pipes: Option<Pipes>,
+}
+
 impl Connection {
     pub fn new() -> Result<Self, String> {
+        Ok(Connection {
+            id_counter: 0,
+            read_delay: Duration::ZERO,
+            pipes: Some(Self::open_pipes()?),
+        })
+    }
+
+    fn open_pipes() -> Result<Pipes, String> {
         let pipes_location = Path::new(ANKAIOS_CONTROL_INTERFACE_BASE_PATH);
         let output_fifo = pipes_location.join("output");
 
@@ -209,11 +237,19 @@ impl Connection {
             )
         })?;
 
-        Ok(Connection {
-            id_counter: 0,
-            output,
-            input,
-        })
+        Ok(Pipes { output, input })
+    }
+
+    fn pipes(&mut self) -> Result<&mut Pipes, io::Error> {
+        self.pipes
+            .as_mut()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "The pipes are closed"))
+    }
+
+    fn write_message(&mut self, data: &[u8]) -> Result<(), CommandError> {
+        self.pipes()
+            .and_then(|pipes| pipes.output.write_all(data))
+            .map_err(|err| CommandError::GenericError(err.to_string()))
     }
 
     fn handle_command(&mut self, command: Command) -> Result<TestResult, CommandError> {
@@ -226,6 +262,14 @@ impl Connection {
                     self.handle_get_state_command(get_state_command)?
                 }
                 CommandEnum::SendHello(Version { version }) => self.send_hello(version)?,
+                CommandEnum::SendMalformedMessage => self.send_malformed_message()?,
+                CommandEnum::DelayRead(DelayRead { delay_ms }) => {
+                    self.read_delay = Duration::from_millis(delay_ms);
+                    TestResultEnum::DelayReadResult(TagSerializedResult::Ok(()))
+                }
+                CommandEnum::CloseConnectionDuringRequest => {
+                    self.close_connection_during_request()?
+                }
             },
         })
     }
@@ -238,12 +282,30 @@ impl Connection {
         };
 
         Ok(TestResultEnum::SendHelloResult(TagSerializedResult::Ok(
-            self.output
-                .write_all(&proto.encode_length_delimited_to_vec())
-                .map_err(|err| CommandError::GenericError(err.to_string()))?,
+            self.write_message(&proto.encode_length_delimited_to_vec())?,
         )))
     }
 
+    fn send_malformed_message(&mut self) -> Result<TestResultEnum, CommandError> {
+        Ok(TestResultEnum::SendMalformedMessageResult(
+            TagSerializedResult::Ok(self.write_message(&MALFORMED_MESSAGE)?),
+        ))
+    }
+
+    // Sends only the first half of a request and reopens the pipes afterwards.
+    fn close_connection_during_request(&mut self) -> Result<TestResultEnum, CommandError> {
+        let (_, proto) = self.create_get_state_request(vec![]);
+        let request = proto.encode_length_delimited_to_vec();
+        self.write_message(&request[..request.len() / 2])?;
+
+        self.pipes = None;
+        self.pipes = Some(Self::open_pipes().map_err(CommandError::GenericError)?);
+
+        Ok(TestResultEnum::CloseConnectionDuringRequestResult(
+            TagSerializedResult::Ok(()),
+        ))
+    }
+
     pub fn handle_update_state_command(
         &mut self,
         update_state_command: UpdateState,
@@ -260,6 +322,8 @@ impl Connection {
                 UpdateStateRequest {
                     new_state: Some(state.into()),
                     update_mask: update_state_command.update_mask,
+                    dry_run: false,
+                    grace_period_secs: None,
                 }
                 .try_into()
                 .map_err(CommandError::GenericError)?,
@@ -272,9 +336,7 @@ impl Connection {
             )),
         };
 
-        self.output
-            .write_all(&proto.encode_length_delimited_to_vec())
-            .unwrap();
+        self.write_message(&proto.encode_length_delimited_to_vec())?;
 
         let response = self.wait_for_response(request_id)?;
 
@@ -292,17 +354,17 @@ impl Connection {
         }))
     }
 
-    pub fn handle_get_state_command(
+    fn create_get_state_request(
         &mut self,
-        get_state_command: GetState,
-    ) -> Result<TestResultEnum, CommandError> {
+        field_mask: Vec<String>,
+    ) -> (String, api::control_api::ToAnkaios) {
         let request_id = self.get_next_id();
-
         let request = common::commands::Request {
             request_id: request_id.clone(),
             request_content: common::commands::RequestContent::CompleteStateRequest(
                 common::commands::CompleteStateRequest {
-                    field_mask: get_state_command.field_mask,
+                    field_mask,
+                    selector: vec![],
                 },
             ),
         };
@@ -312,22 +374,35 @@ impl Connection {
                 request.into(),
             )),
         };
+        (request_id, proto)
+    }
 
-        self.output
-            .write_all(&proto.encode_length_delimited_to_vec())
-            .unwrap();
+    fn get_state(
+        &mut self,
+        field_mask: Vec<String>,
+    ) -> Result<Result<Option<State>, String>, CommandError> {
+        let (request_id, proto) = self.create_get_state_request(field_mask);
+
+        self.write_message(&proto.encode_length_delimited_to_vec())?;
 
         let response = self.wait_for_response(request_id)?;
 
-        Ok(TestResultEnum::GetStateResult(match response {
-            ResponseContent::CompleteState(complete_state) => {
-                TagSerializedResult::Ok(complete_state.desired_state)
-            }
-            response_content => TagSerializedResult::Err(format!(
+        Ok(match response {
+            ResponseContent::CompleteState(complete_state) => Ok(complete_state.desired_state),
+            response_content => Err(format!(
                 "Received wrong response type. Expected CompleteState, received: '{:?}'",
                 response_content
             )),
-        }))
+        })
+    }
+
+    pub fn handle_get_state_command(
+        &mut self,
+        get_state_command: GetState,
+    ) -> Result<TestResultEnum, CommandError> {
+        Ok(TestResultEnum::GetStateResult(
+            self.get_state(get_state_command.field_mask)?.into(),
+        ))
     }
 
     fn wait_for_response(
@@ -375,6 +450,7 @@ impl Connection {
     }
 
     fn read_protobuf_data(&mut self) -> Result<Box<[u8]>, io::Error> {
+        sleep(self.read_delay);
         let varint_data = self.read_varint_data()?;
         let mut varint_data = Box::new(&varint_data[..]);
 
@@ -382,15 +458,16 @@ impl Connection {
         let size = prost::encoding::decode_varint(&mut varint_data)? as usize;
 
         let mut buf = vec![0; size];
-        self.input.read_exact(&mut buf[..])?; // read exact bytes from file
+        self.pipes()?.input.read_exact(&mut buf[..])?; // read exact bytes from file
         Ok(buf.into_boxed_slice())
     }
 
     fn read_varint_data(&mut self) -> Result<[u8; MAX_VARINT_SIZE], io::Error> {
         let mut res = [0u8; MAX_VARINT_SIZE];
         let mut one_byte_buffer = [0u8; 1];
+        let input = &mut self.pipes()?.input;
         for item in res.iter_mut() {
-            self.input.read_exact(&mut one_byte_buffer)?;
+            input.read_exact(&mut one_byte_buffer)?;
             *item = one_byte_buffer[0];
             // check if most significant bit is set to 0 if so it is the last byte to be readxxxxxxfff
             if *item & 0b10000000 == 0 {
@@ -177,6 +177,7 @@ Description=Ankaios server
 [Service]
 Environment="RUST_LOG=${INSTALL_ANK_SERVER_RUST_LOG}"
 ExecStart=${BIN_DESTINATION}/ank-server $SERVER_OPT
+ExecReload=/bin/kill -HUP \$MAINPID
 
 [Install]
 WantedBy=default.target